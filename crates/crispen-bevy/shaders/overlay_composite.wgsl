@@ -0,0 +1,57 @@
+// Composites a UI overlay layer over the rendered view.
+//
+// Drawn as a fullscreen triangle into a viewport covering the visible part
+// of the layer; `uv_offset` and `uv_scale` map that viewport back onto the
+// layer texture. Blending is fixed-function: `SrcAlpha, OneMinusSrcAlpha`
+// for straight alpha and `One, OneMinusSrcAlpha` with PREMULTIPLIED.
+// Layers are sRGB; `to_display` takes their linear color to the display's
+// primaries (and UI white level) before blending. It is linear, so it
+// applies to premultiplied color unchanged.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct LayerUniform {
+    uv_offset: vec2<f32>,
+    uv_scale: vec2<f32>,
+    to_display: mat3x3<f32>,
+}
+
+@group(0) @binding(0) var overlay_texture: texture_2d<f32>;
+@group(0) @binding(1) var overlay_sampler: sampler;
+@group(0) @binding(2) var<uniform> layer: LayerUniform;
+
+fn srgb_encode(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3(0.0031308));
+}
+
+fn srgb_decode(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, c <= vec3(0.04045));
+}
+
+@fragment
+fn composite(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(overlay_texture, overlay_sampler, layer.uv_offset + in.uv * layer.uv_scale);
+#ifdef PREMULTIPLIED
+    // The texture holds sRGB-encoded premultiplied values, which sampling
+    // linearises as if they were straight. Divide alpha out in sRGB space
+    // and multiply it back in linear space so edges blend without fringes.
+    if color.a <= 0.0 {
+        return vec4(0.0);
+    }
+    let straight = srgb_decode(min(srgb_encode(color.rgb) / color.a, vec3(1.0)));
+    return vec4(layer.to_display * straight * color.a, color.a);
+#else
+    return vec4(layer.to_display * color.rgb, color.a);
+#endif
+}
+
+// Output is ignored: the dim pipeline scales the target by the blend
+// constant.
+@fragment
+fn dim(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return vec4(0.0);
+}
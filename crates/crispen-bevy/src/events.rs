@@ -1,28 +1,158 @@
 //! Bevy messages for cross-system communication in the grading pipeline.
 
+use std::path::PathBuf;
+
 use bevy::prelude::*;
+use crispen_core::framing::Blanking;
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::GradingImage;
+use crispen_core::scopes::{ScopeAccumulation, ScopeDisplayOptions, ScopeQuality};
+use crispen_core::transform::compare::DeltaEReport;
+use crispen_core::transform::look_preview::LookEntry;
+use crispen_core::transform::lut_analysis::LutAnalysis;
+use crispen_core::transform::lut_domain::LutDomain;
 use crispen_core::transform::params::GradingParams;
+use crispen_core::transform::parity::ParityReport;
+use crispen_core::transform::shader_export::ShaderLanguage;
+use crispen_core::transform::shaper::ShaperTransfer;
 
 // === Inbound Commands (UI -> ECS) ===
 
 /// Commands received from the UI that the grading systems process.
 /// Each variant maps 1:1 to a `UiToBevy` IPC message.
+// `SetParams` carries `GradingParams` by value; only a handful of commands
+// are sent per frame, so boxing it isn't worth the churn.
 #[derive(Message)]
+#[allow(clippy::large_enum_variant)]
 pub enum ColorGradingCommand {
     /// Apply new grading parameters.
     SetParams { params: GradingParams },
     /// Run automatic white balance on the current image.
     AutoBalance,
+    /// Stretch master lift/gain so the histogram's clipping percentiles
+    /// reach black and white, blended by `strength`.
+    AutoContrast {
+        low_percentile: f32,
+        high_percentile: f32,
+        strength: f32,
+    },
     /// Reset all grading to identity defaults.
     ResetGrade,
     /// Load a source image from disk.
     LoadImage { path: String },
-    /// Load a 3D LUT file into a named slot.
-    LoadLut { path: String, slot: String },
-    /// Export the current grading as a .cube LUT file.
-    ExportLut { path: String, size: u32 },
+    /// Load a 3D LUT file into a named slot. `domain` declares the LUT's
+    /// input/output encodings; `None` guesses from the file name.
+    LoadLut {
+        path: String,
+        slot: String,
+        domain: Option<LutDomain>,
+    },
+    /// Change the declared encodings of the LUT in `slot`.
+    SetLutDomain { slot: String, domain: LutDomain },
+    /// Remove the LUT from `slot`.
+    ClearLut { slot: String },
+    /// Enable or disable reloading LUT files when they change on disk.
+    SetLutWatch { enabled: bool },
+    /// Export the current grading as a .cube LUT file, optionally behind a
+    /// 1D shaper for scene-linear input.
+    ExportLut {
+        path: String,
+        size: u32,
+        shaper: Option<ShaperTransfer>,
+    },
+    /// Export an ICC profile describing the output color space.
+    ExportIcc { path: String },
+    /// Export the current grading as a Nuke script or gizmo (by extension).
+    ExportNuke { path: String, size: u32 },
+    /// Export the current grading as a GLSL/HLSL/WGSL function.
+    ExportShader {
+        path: String,
+        size: u32,
+        language: ShaderLanguage,
+    },
     /// Toggle visibility of a scope type.
     ToggleScope { scope_type: String, visible: bool },
+    /// Set how successive scope frames are combined (instant, average, peak hold).
+    SetScopeAccumulation { mode: ScopeAccumulation },
+    /// Set scope drawing options (CIE gamut overlays and projection).
+    SetScopeDisplayOptions { options: ScopeDisplayOptions },
+    /// Set per-scope resolutions and automatic quality reduction.
+    SetScopeQuality { quality: ScopeQuality },
+    /// Restrict scope analysis to the inside of a polygon in normalized
+    /// image coordinates (0..1, top-left origin). The polygon closes
+    /// automatically; fewer than three points clears the mask.
+    SetScopeMaskPolygon { points: Vec<[f32; 2]> },
+    /// Include the whole image in scope analysis again.
+    ClearScopeMask,
+    /// Fit an input correction to a ColorChecker chart whose outer corners
+    /// (normalized, TL/TR/BR/BL) have been marked in the source image.
+    MatchChart { corners: [[f32; 2]; 4] },
+    /// Grade the current image on the GPU and the CPU and report the
+    /// difference, to catch drift between the bake shader and the
+    /// reference math.
+    RunParityCheck,
+    /// Compute per-pixel ΔE2000 between the graded output and `reference`
+    /// on the GPU and report it with a heatmap that reaches red at `scale`.
+    CompareDeltaE {
+        reference: DeltaEReference,
+        scale: f32,
+    },
+    /// Hide the ΔE heatmap. Handled by the frontend that shows it.
+    ClearDeltaE,
+    /// Render the current frame through every LUT and grade preset in
+    /// `dirs` as a grid of thumbnails on the GPU.
+    PreviewLooks { dirs: Vec<String> },
+    /// Render the current frame under the current grade and under each of
+    /// `grades` as thumbnails on the GPU, e.g. for gallery stills. Each
+    /// grade keeps the shot's color management.
+    PreviewGrades { grades: Vec<GradingParams> },
+    /// Apply a look from the browser: a `.cube` is loaded into the input
+    /// LUT slot, a `.json` grade preset replaces the grade.
+    ApplyLook { path: String },
+    /// Render the current frame once per variant of `bracket` for an
+    /// option review. Each still is named after `path` and its variant's
+    /// label; the frontend writes the files.
+    ExportBracket { path: String, bracket: Bracket },
+    /// Save the current grade as a `.json` version, recorded in the audit
+    /// log and readable as a look or bracket version.
+    SaveVersion { path: String },
+    /// Write the audit log as one JSON array.
+    ExportAuditLog { path: String },
+    /// Render the current frame with the current grade as a gallery still
+    /// labelled `label`; the frontend stores it.
+    GrabStill { label: String },
+    /// Set the output matte baked into renders and previewed in the viewer.
+    SetBlanking { blanking: Blanking },
+    /// Replace the dead-pixel and dust map repaired on the source before
+    /// grading, e.g. with a clip's `Timeline::defect_map_for`. An empty map
+    /// turns repair off.
+    SetDefectMap { map: DefectMap },
+    /// Mark a defect at `at` in normalized image coordinates, `radius`
+    /// source pixels wide (0 for a single pixel).
+    MarkDefect { at: [f32; 2], radius: u32 },
+    /// Treat the loaded source as a black frame and add the hot pixels
+    /// `detect_hot_pixels` finds at `threshold` to the defect map.
+    DetectDefects { threshold: f32 },
+    /// Run a Rhai script against the current grade and source image; the
+    /// grade it leaves behind is applied. Needs the `script` feature.
+    RunScript { path: String },
+}
+
+/// What a `CompareDeltaE` command compares the graded output against.
+pub enum DeltaEReference {
+    /// A render in the output space's code values, the size of the source.
+    Image(GradingImage),
+    /// The source graded with another version of the grade, in the current
+    /// grade's output space.
+    Grade(Box<GradingParams>),
+}
+
+/// Grade variations of a [`ColorGradingCommand::ExportBracket`].
+pub enum Bracket {
+    /// The current grade with each offset, in stops, added to its exposure.
+    Exposure { stops: Vec<f32> },
+    /// Saved `.json` grade versions, keeping the current color management.
+    Versions { paths: Vec<String> },
 }
 
 // === Outbound Notifications (ECS -> UI) ===
@@ -47,6 +177,140 @@ pub struct ImageLoadedEvent {
     pub bit_depth: String,
 }
 
+/// Fired when an export command has written its file.
+#[derive(Message)]
+pub struct ExportCompletedEvent {
+    /// File path written.
+    pub path: String,
+    /// Export type: `lut`, `icc`, `nuke`, `shader`, `bracket`, `version`,
+    /// `audit`, or `still`.
+    pub kind: &'static str,
+}
+
+/// Fired when a grade has been saved, as a version, a tool preset, or a
+/// gallery still.
+#[derive(Message)]
+pub struct GradeSavedEvent {
+    /// File path written.
+    pub path: String,
+    /// What was saved: `version`, `preset`, or `still`.
+    pub kind: &'static str,
+}
+
 /// Fired when scope data has been computed and is ready for display.
 #[derive(Message)]
 pub struct ScopeDataReadyEvent;
+
+/// Fired when a GPU vs CPU parity check completes.
+#[derive(Message)]
+pub struct ParityReportEvent {
+    pub report: ParityReport,
+}
+
+/// Fired when a ΔE comparison completes.
+#[derive(Message)]
+pub struct DeltaEReportEvent {
+    pub report: DeltaEReport,
+    /// `report.width * report.height` RGBA8 heatmap pixels (sRGB-encoded).
+    pub heatmap: Vec<u8>,
+}
+
+/// Fired when the look browser's thumbnails have been rendered.
+#[derive(Message)]
+pub struct LookPreviewsReadyEvent {
+    /// Thumbnail width in pixels.
+    pub width: u32,
+    /// Thumbnail height in pixels.
+    pub height: u32,
+    /// Every look that could be read, in browser order.
+    pub looks: Vec<LookPreview>,
+}
+
+/// One look browser thumbnail.
+pub struct LookPreview {
+    pub look: LookEntry,
+    /// `width * height` RGBA8 pixels in the output space's code values.
+    pub thumbnail: Vec<u8>,
+}
+
+/// Fired when the thumbnails for `PreviewGrades` have been rendered.
+#[derive(Message)]
+pub struct GradePreviewsReadyEvent {
+    /// Thumbnail width in pixels.
+    pub width: u32,
+    /// Thumbnail height in pixels.
+    pub height: u32,
+    /// The frame under the current grade, `width * height` RGBA8 pixels in
+    /// the output space's code values.
+    pub current: Vec<u8>,
+    /// The frame under each requested grade, in request order.
+    pub thumbnails: Vec<Vec<u8>>,
+}
+
+/// Fired when a bracketed still export has rendered every variant.
+#[derive(Message)]
+pub struct BracketRenderedEvent {
+    /// One still per variant that could be rendered, in bracket order.
+    pub stills: Vec<BracketStill>,
+}
+
+/// One rendered variant of a bracket.
+pub struct BracketStill {
+    /// The variant's label, e.g. `EV +1` or a version's file stem.
+    pub label: String,
+    /// File the still should be written to.
+    pub path: PathBuf,
+    /// The graded frame in the output space's code values, blanking
+    /// applied.
+    pub image: GradingImage,
+}
+
+/// Fired when a gallery still has been rendered for `GrabStill`.
+#[derive(Message)]
+pub struct StillGrabbedEvent {
+    /// Label requested for the still.
+    pub label: String,
+    /// The graded frame in the output space's code values, blanking
+    /// applied.
+    pub image: GradingImage,
+    /// The grade the still was rendered with.
+    pub params: GradingParams,
+    /// File path of the source image, if it came from a file.
+    pub source: Option<String>,
+}
+
+/// Fired when an imported LUT has been loaded and analyzed.
+#[derive(Message)]
+pub struct LutAnalyzedEvent {
+    /// File path of the LUT.
+    pub path: String,
+    /// Slot the LUT was loaded into.
+    pub slot: String,
+    /// Declared or guessed input/output encodings.
+    pub domain: LutDomain,
+    /// Monotonicity, smoothness, and invertibility checks.
+    pub analysis: LutAnalysis,
+}
+
+/// Fired when a script started with `RunScript` finishes.
+#[derive(Message)]
+pub struct ScriptFinishedEvent {
+    /// Script file path.
+    pub path: String,
+    /// Lines the script printed.
+    pub output: Vec<String>,
+    /// Error message if the script failed; its grade changes are discarded.
+    pub error: Option<String>,
+}
+
+/// Fired when a watched LUT file changed on disk and was reloaded.
+#[derive(Message)]
+pub struct LutReloadedEvent {
+    /// File path of the LUT.
+    pub path: String,
+    /// Slot the LUT is loaded into.
+    pub slot: String,
+    /// Read error if the new file could not be loaded; the previous LUT
+    /// stays applied.
+    pub error: Option<String>,
+}
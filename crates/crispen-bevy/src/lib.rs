@@ -16,16 +16,28 @@ use crispen_gpu::vulkan_interop::VulkanInterop;
 // Re-export for downstream crates.
 pub use crispen_gpu::ViewerFormat;
 
-use events::{ColorGradingCommand, ImageLoadedEvent, ParamsUpdatedEvent, ScopeDataReadyEvent};
+use events::{
+    BracketRenderedEvent, ColorGradingCommand, DeltaEReportEvent, ExportCompletedEvent,
+    GradePreviewsReadyEvent, GradeSavedEvent, ImageLoadedEvent, LookPreviewsReadyEvent,
+    LutAnalyzedEvent, LutReloadedEvent, ParamsUpdatedEvent, ParityReportEvent, ScopeDataReadyEvent,
+    ScriptFinishedEvent, StillGrabbedEvent,
+};
 use resources::{
-    GpuPipelineState, GradingState, ImageState, PipelinePerfStats, ScopeConfig, ScopeMaskData,
-    ScopeState, ViewerData, VulkanInteropState,
+    AuditState, GpuAdapterPreference, GpuPipelineState, GradingState, HookState, ImageState,
+    InputLutState, LutWatchState, OutputBlanking, PipelineActivity, PipelinePerfStats,
+    PipelineSettings, ScopeConfig, ScopeMaskData, ScopeState, SourceDefects, ViewerData,
+    VulkanInteropState,
 };
 #[cfg(feature = "ocio")]
 use systems::bake_ocio_luts;
+#[cfg(feature = "script")]
+use systems::run_scripts;
 use systems::{
-    consume_gpu_results, detect_param_changes, handle_grading_commands, submit_gpu_work,
-    upload_scope_mask,
+    adapt_scope_quality, apply_pipeline_settings, consume_gpu_results, detect_param_changes,
+    export_audit_log, export_bracket, fire_hooks, grab_still, handle_grading_commands,
+    handle_lut_commands, record_audit, render_grade_previews, render_look_previews,
+    run_delta_e_compare, run_parity_check, submit_gpu_work, sync_defect_map, sync_input_lut,
+    track_pipeline_activity, upload_scope_mask, watch_input_lut,
 };
 
 /// Main Bevy plugin for the Crispen color grading pipeline.
@@ -43,13 +55,32 @@ impl Plugin for CrispenPlugin {
             .add_message::<ParamsUpdatedEvent>()
             .add_message::<ImageLoadedEvent>()
             .add_message::<ScopeDataReadyEvent>()
+            .add_message::<ParityReportEvent>()
+            .add_message::<DeltaEReportEvent>()
+            .add_message::<LookPreviewsReadyEvent>()
+            .add_message::<GradePreviewsReadyEvent>()
+            .add_message::<BracketRenderedEvent>()
+            .add_message::<StillGrabbedEvent>()
+            .add_message::<LutAnalyzedEvent>()
+            .add_message::<LutReloadedEvent>()
+            .add_message::<ScriptFinishedEvent>()
+            .add_message::<ExportCompletedEvent>()
+            .add_message::<GradeSavedEvent>()
             .init_resource::<GradingState>()
             .init_resource::<ImageState>()
+            .init_resource::<InputLutState>()
+            .init_resource::<LutWatchState>()
+            .insert_resource(HookState::from_env())
+            .insert_resource(AuditState::from_env())
             .init_resource::<ViewerData>()
             .init_resource::<ScopeState>()
             .init_resource::<ScopeConfig>()
             .init_resource::<ScopeMaskData>()
+            .init_resource::<OutputBlanking>()
+            .init_resource::<SourceDefects>()
             .init_resource::<PipelinePerfStats>()
+            .init_resource::<PipelineSettings>()
+            .init_resource::<PipelineActivity>()
             .add_systems(Startup, init_gpu_pipeline)
             .add_systems(
                 Update,
@@ -57,11 +88,39 @@ impl Plugin for CrispenPlugin {
                     handle_grading_commands,
                     consume_gpu_results.after(handle_grading_commands),
                     upload_scope_mask.after(consume_gpu_results),
-                    submit_gpu_work.after(upload_scope_mask),
+                    adapt_scope_quality.after(upload_scope_mask),
+                    apply_pipeline_settings.after(adapt_scope_quality),
+                    submit_gpu_work.after(apply_pipeline_settings),
+                    run_parity_check.after(submit_gpu_work),
+                    run_delta_e_compare.after(run_parity_check),
+                    render_look_previews.after(run_delta_e_compare),
+                    render_grade_previews.after(render_look_previews),
+                    export_bracket.after(render_grade_previews),
+                    grab_still.after(export_bracket),
+                    track_pipeline_activity.after(submit_gpu_work),
+                    handle_lut_commands,
+                    watch_input_lut.after(handle_lut_commands),
+                    sync_input_lut
+                        .after(watch_input_lut)
+                        .after(handle_grading_commands)
+                        .before(submit_gpu_work),
                     detect_param_changes,
+                    record_audit.after(handle_grading_commands),
+                    export_audit_log,
+                    fire_hooks
+                        .after(handle_grading_commands)
+                        .after(handle_lut_commands)
+                        .after(watch_input_lut),
                 ),
             );
 
+        app.add_systems(
+            Update,
+            sync_defect_map
+                .after(handle_grading_commands)
+                .before(submit_gpu_work),
+        );
+
         #[cfg(feature = "ocio")]
         app.add_systems(
             Update,
@@ -69,12 +128,21 @@ impl Plugin for CrispenPlugin {
                 .after(handle_grading_commands)
                 .before(submit_gpu_work),
         );
+
+        #[cfg(feature = "script")]
+        app.add_systems(
+            Update,
+            run_scripts
+                .after(handle_grading_commands)
+                .before(submit_gpu_work),
+        );
     }
 }
 
 /// Startup system: create the GPU grading pipeline and insert as a resource.
-fn init_gpu_pipeline(mut commands: Commands) {
-    match GpuGradingPipeline::create_blocking() {
+fn init_gpu_pipeline(mut commands: Commands, preference: Option<Res<GpuAdapterPreference>>) {
+    let preferred = preference.as_ref().and_then(|p| p.name.as_deref());
+    match GpuGradingPipeline::create_blocking_with_adapter(preferred) {
         Ok(pipeline) => {
             let interop_caps = VulkanInterop::probe(
                 pipeline.adapter_backend(),
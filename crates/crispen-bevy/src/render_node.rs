@@ -1,7 +1,410 @@
-//! Bevy render graph node for the grading pipeline.
+//! Bevy render graph nodes.
 //!
-//! Phase 2: will dispatch GPU pipeline via crispen-gpu, replacing the
-//! CPU path in `systems::rebake_lut_if_dirty`.
+//! `OverlayCompositeNode` blends UI overlay textures (such as an offscreen
+//! browser's frames) over a camera's view after Bevy's own UI pass, with
+//! explicit alpha handling and optional dimming of what is underneath.
+//! Overlays are sRGB and are converted to the display's primaries as they
+//! are drawn, so UI chrome keeps its colors on wide-gamut and HDR views.
+//!
+//! Phase 2: `GradingRenderNode` will dispatch GPU pipeline via crispen-gpu,
+//! replacing the CPU path in `systems::rebake_lut_if_dirty`.
+
+use bevy::core_pipeline::FullscreenShader;
+use bevy::core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy::core_pipeline::core_3d::graph::{Core3d, Node3d};
+use bevy::ecs::query::QueryItem;
+use bevy::image::BevyDefault;
+use bevy::prelude::*;
+use bevy::render::extract_component::{ExtractComponent, ExtractComponentPlugin};
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_graph::{
+    NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries, BlendComponent,
+    BlendFactor, BlendOperation, BlendState, CachedRenderPipelineId, ColorTargetState, ColorWrites,
+    FragmentState, PipelineCache, RenderPassDescriptor, RenderPipelineDescriptor, Sampler,
+    SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType, TextureFormat,
+    TextureSampleType, UniformBuffer,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice, RenderQueue};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::ViewTarget;
+use bevy::render::{RenderApp, RenderStartup};
+use bevy::shader::ShaderDefVal;
+use bevy::ui_render::graph::NodeUi;
+use crispen_core::color_management::color_space::get_conversion_matrix;
+use crispen_core::transform::params::ColorSpaceId;
+
+// ── Overlay compositing ──────────────────────────────────────────
+
+/// How the color channels of an overlay texture relate to its alpha.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverlayAlpha {
+    /// Color is independent of alpha, as Bevy's UI pipeline assumes.
+    #[default]
+    Straight,
+    /// Color is already multiplied by alpha, as offscreen browsers deliver it.
+    /// Treating such a texture as straight darkens every soft edge.
+    Premultiplied,
+}
+
+/// One texture in the overlay stack.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OverlayLayer {
+    /// sRGB-format image to composite.
+    pub image: AssetId<Image>,
+    /// Where to draw it, in physical pixels; `None` stretches it over the
+    /// whole view. Parts outside the view are clipped.
+    pub rect: Option<IRect>,
+}
+
+/// Overlay layers composited over every camera with [`OverlayCompositeCamera`].
+///
+/// Layers are drawn in order after the UI pass, so they cover all native UI.
+/// While `dimmed` is set, what is already in the view is darkened by `dim`
+/// first, e.g. to push native panels back behind a modal dialog.
+///
+/// Layers are tagged sRGB. They are converted to `display_space` primaries
+/// in linear light and scaled by `ui_white` before blending, so a view shown
+/// on a P3 or Rec. 2020 display, or an HDR view where 1.0 is not the
+/// display's white, does not oversaturate or dim the UI.
+#[derive(Resource, ExtractResource, Clone, Debug)]
+pub struct OverlayComposite {
+    /// Layers from bottom to top.
+    pub layers: Vec<OverlayLayer>,
+    /// Alpha convention shared by all layers.
+    pub alpha: OverlayAlpha,
+    /// Dimming strength in `0.0..=1.0`; 0 leaves the view untouched.
+    pub dim: f32,
+    /// Whether to dim the view now.
+    pub dimmed: bool,
+    /// Color space whose primaries the view is presented in. Only the gamut
+    /// is used; the target format applies the transfer function.
+    pub display_space: ColorSpaceId,
+    /// Linear level UI white is drawn at: 1.0 for SDR views, the ratio of
+    /// UI white to the view's 1.0 for HDR ones.
+    pub ui_white: f32,
+}
+
+impl Default for OverlayComposite {
+    fn default() -> Self {
+        Self {
+            layers: Vec::new(),
+            alpha: OverlayAlpha::default(),
+            dim: 0.5,
+            dimmed: false,
+            display_space: ColorSpaceId::Srgb,
+            ui_white: 1.0,
+        }
+    }
+}
+
+impl OverlayComposite {
+    /// Dimming to apply this frame.
+    pub fn dim_amount(&self) -> f32 {
+        if self.dimmed {
+            self.dim.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Linear sRGB to linear display RGB, with `ui_white` folded in.
+    /// Identity for Rec. 709 displays at SDR white.
+    pub fn display_matrix(&self) -> Mat3 {
+        let m = get_conversion_matrix(ColorSpaceId::Srgb, self.display_space).0;
+        let col = |j: usize| Vec3::new(m[0][j] as f32, m[1][j] as f32, m[2][j] as f32);
+        Mat3::from_cols(col(0), col(1), col(2)) * self.ui_white.max(0.0)
+    }
+}
+
+/// Marks the cameras `OverlayComposite` draws into.
+#[derive(Component, ExtractComponent, Clone, Copy, Debug, Default)]
+pub struct OverlayCompositeCamera;
+
+/// Render graph label of [`OverlayCompositeNode`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct OverlayCompositeLabel;
+
+/// Registers [`OverlayComposite`] and its render graph node between the UI
+/// pass and upscaling of the 2D and 3D graphs.
+///
+/// Add it after `DefaultPlugins`; the node is ordered against the UI pass.
+pub struct OverlayCompositePlugin;
+
+impl Plugin for OverlayCompositePlugin {
+    fn build(&self, app: &mut App) {
+        let shader = app
+            .world_mut()
+            .resource_mut::<Assets<Shader>>()
+            .add(Shader::from_wgsl(
+                include_str!("../shaders/overlay_composite.wgsl"),
+                "crispen_bevy/shaders/overlay_composite.wgsl",
+            ));
+
+        app.init_resource::<OverlayComposite>().add_plugins((
+            ExtractResourcePlugin::<OverlayComposite>::default(),
+            ExtractComponentPlugin::<OverlayCompositeCamera>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(OverlayCompositeShader(shader))
+            .add_systems(RenderStartup, init_overlay_composite_pipelines)
+            .add_render_graph_node::<ViewNodeRunner<OverlayCompositeNode>>(
+                Core2d,
+                OverlayCompositeLabel,
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (NodeUi::UiPass, OverlayCompositeLabel, Node2d::Upscaling),
+            )
+            .add_render_graph_node::<ViewNodeRunner<OverlayCompositeNode>>(
+                Core3d,
+                OverlayCompositeLabel,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (NodeUi::UiPass, OverlayCompositeLabel, Node3d::Upscaling),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct OverlayCompositeShader(Handle<Shader>);
+
+/// Maps the visible part of a layer back onto its texture, and its colors
+/// into the display's.
+#[derive(ShaderType, Clone, Copy)]
+struct LayerUniform {
+    uv_offset: Vec2,
+    uv_scale: Vec2,
+    to_display: Mat3,
+}
+
+/// Pipelines for one target format.
+struct FormatPipelines {
+    dim: CachedRenderPipelineId,
+    straight: CachedRenderPipelineId,
+    premultiplied: CachedRenderPipelineId,
+}
+
+#[derive(Resource)]
+struct OverlayCompositePipelines {
+    layout: BindGroupLayoutDescriptor,
+    sampler: Sampler,
+    sdr: FormatPipelines,
+    hdr: FormatPipelines,
+}
+
+fn init_overlay_composite_pipelines(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    shader: Res<OverlayCompositeShader>,
+    fullscreen_shader: Res<FullscreenShader>,
+    pipeline_cache: Res<PipelineCache>,
+) {
+    let layout = BindGroupLayoutDescriptor::new(
+        "overlay_composite_bind_group_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                sampler(SamplerBindingType::Filtering),
+                uniform_buffer::<LayerUniform>(false),
+            ),
+        ),
+    );
+    let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+    let queue = |format: TextureFormat, entry: &'static str, blend: BlendState, defs| {
+        let layout = if entry == "dim" {
+            Vec::new()
+        } else {
+            vec![layout.clone()]
+        };
+        pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+            label: Some("overlay_composite_pipeline".into()),
+            layout,
+            vertex: fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: shader.0.clone(),
+                shader_defs: defs,
+                entry_point: Some(entry.into()),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(blend),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            ..default()
+        })
+    };
+    let pipelines = |format| FormatPipelines {
+        dim: queue(format, "dim", DIM_BLEND, Vec::new()),
+        straight: queue(format, "composite", BlendState::ALPHA_BLENDING, Vec::new()),
+        premultiplied: queue(
+            format,
+            "composite",
+            BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            vec![ShaderDefVal::from("PREMULTIPLIED")],
+        ),
+    };
+
+    commands.insert_resource(OverlayCompositePipelines {
+        sdr: pipelines(TextureFormat::bevy_default()),
+        hdr: pipelines(ViewTarget::TEXTURE_FORMAT_HDR),
+        layout,
+        sampler,
+    });
+}
+
+/// Scales the target by one minus the blend constant, ignoring the shader.
+const DIM_BLEND: BlendState = BlendState {
+    color: BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::OneMinusConstant,
+        operation: BlendOperation::Add,
+    },
+    alpha: BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    },
+};
+
+/// Visible part of `rect` inside a `size` target, and the matching UV
+/// offset and scale into the layer texture.
+fn clip_layer(rect: IRect, size: UVec2, to_display: Mat3) -> Option<(IRect, LayerUniform)> {
+    let visible = rect.intersect(IRect::from_corners(IVec2::ZERO, size.as_ivec2()));
+    if visible.is_empty() || rect.is_empty() {
+        return None;
+    }
+    let full = rect.size().as_vec2();
+    Some((
+        visible,
+        LayerUniform {
+            uv_offset: (visible.min - rect.min).as_vec2() / full,
+            uv_scale: visible.size().as_vec2() / full,
+            to_display,
+        },
+    ))
+}
+
+/// Dims the view and blends [`OverlayComposite`]'s layers over it.
+#[derive(Default)]
+pub struct OverlayCompositeNode;
+
+impl ViewNode for OverlayCompositeNode {
+    type ViewQuery = (&'static ViewTarget, &'static OverlayCompositeCamera);
+
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (view_target, _): QueryItem<'w, '_, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Some(composite) = world.get_resource::<OverlayComposite>() else {
+            return Ok(());
+        };
+        let dim = composite.dim_amount();
+        if dim <= 0.0 && composite.layers.is_empty() {
+            return Ok(());
+        }
+
+        let pipelines = world.resource::<OverlayCompositePipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+        let render_queue = world.resource::<RenderQueue>();
+
+        let format = if view_target.is_hdr() {
+            &pipelines.hdr
+        } else {
+            &pipelines.sdr
+        };
+        let layer_pipeline = match composite.alpha {
+            OverlayAlpha::Straight => format.straight,
+            OverlayAlpha::Premultiplied => format.premultiplied,
+        };
+        let (Some(dim_pipeline), Some(layer_pipeline)) = (
+            pipeline_cache.get_render_pipeline(format.dim),
+            pipeline_cache.get_render_pipeline(layer_pipeline),
+        ) else {
+            return Ok(());
+        };
+
+        let target_size = {
+            let size = view_target.main_texture().size();
+            UVec2::new(size.width, size.height)
+        };
+        let layout = pipeline_cache.get_bind_group_layout(&pipelines.layout);
+        let to_display = composite.display_matrix();
+        let mut draws = Vec::with_capacity(composite.layers.len());
+        for layer in &composite.layers {
+            let Some(image) = gpu_images.get(layer.image) else {
+                continue;
+            };
+            let rect = layer
+                .rect
+                .unwrap_or(IRect::from_corners(IVec2::ZERO, target_size.as_ivec2()));
+            let Some((visible, uv)) = clip_layer(rect, target_size, to_display) else {
+                continue;
+            };
+
+            let mut uniform = UniformBuffer::from(uv);
+            uniform.write_buffer(render_context.render_device(), render_queue);
+            let bind_group = render_context.render_device().create_bind_group(
+                "overlay_composite_bind_group",
+                &layout,
+                &BindGroupEntries::sequential((
+                    &image.texture_view,
+                    &pipelines.sampler,
+                    uniform.binding().expect("uniform buffer was just written"),
+                )),
+            );
+            draws.push((visible, bind_group));
+        }
+
+        let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("overlay_composite_pass"),
+            color_attachments: &[Some(view_target.get_unsampled_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        if dim > 0.0 {
+            pass.set_render_pipeline(dim_pipeline);
+            pass.set_blend_constant(LinearRgba::gray(dim));
+            pass.draw(0..3, 0..1);
+        }
+
+        pass.set_render_pipeline(layer_pipeline);
+        for (visible, bind_group) in &draws {
+            let size = visible.size().as_vec2();
+            pass.set_viewport(
+                visible.min.x as f32,
+                visible.min.y as f32,
+                size.x,
+                size.y,
+                0.0,
+                1.0,
+            );
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+// ── Grading ──────────────────────────────────────────────────────
 
 /// Render graph node that executes the GPU grading pipeline.
 pub struct GradingRenderNode {
@@ -1,17 +1,27 @@
 //! Bevy resources for the color grading pipeline.
 
 use bevy::prelude::*;
-use crispen_core::image::GradingImage;
-use crispen_core::scopes::{CieData, HistogramData, VectorscopeData, WaveformData};
+use crispen_core::framing::Blanking;
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::hooks::HookConfig;
+use crispen_core::image::{GradingImage, SourceImage, SourcePrecision};
+use crispen_core::project::audit::{AuditLog, current_operator};
+use crispen_core::scopes::{
+    CieData, HistogramData, ScopeDisplayOptions, ScopeHistory, ScopeQuality, ScopeQualityGovernor,
+    ScopeScheduler, VectorscopeData, WaveformData,
+};
 use crispen_core::transform::lut::Lut3D;
-use crispen_core::transform::params::GradingParams;
-use crispen_gpu::GpuImageHandle;
+use crispen_core::transform::lut_domain::LutDomain;
+use crispen_core::transform::params::{ColorManagementConfig, GradingParams};
 use crispen_gpu::ViewerFormat;
 use crispen_gpu::pipeline::GpuGradingPipeline;
 use crispen_gpu::vulkan_interop::VulkanInteropCapabilities;
+use crispen_gpu::{DEFAULT_LUT_CACHE_CAPACITY, DEFAULT_READBACK_SLOTS, GpuImageHandle};
 #[cfg(feature = "ocio")]
 use crispen_ocio::OcioConfig;
-use std::time::{Duration, Instant};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Bevy resource holding the current grading parameters.
 ///
@@ -58,9 +68,130 @@ pub struct OcioColorManagement {
 #[derive(Resource, Default)]
 pub struct ImageState {
     /// The original source image (None until loaded).
-    pub source: Option<GradingImage>,
+    pub source: Option<SourceImage>,
     /// File path of the currently loaded image.
     pub source_path: Option<String>,
+    /// Precision newly loaded sources are held in.
+    pub precision: SourcePrecision,
+}
+
+impl ImageState {
+    /// Replace the source with `image`, held in [`Self::precision`].
+    pub fn set_source(&mut self, image: GradingImage, path: String) -> &SourceImage {
+        self.source_path = Some(path);
+        self.source.insert(SourceImage::new(image, self.precision))
+    }
+}
+
+/// Slot name whose LUT replaces the input color transform.
+pub const INPUT_LUT_SLOT: &str = "input";
+
+/// LUT imported into the [`INPUT_LUT_SLOT`], applied in place of the input
+/// transform with its declared domain conversions.
+#[derive(Resource, Default)]
+pub struct InputLutState {
+    /// File path of the imported LUT.
+    pub path: Option<String>,
+    /// The LUT as loaded from disk.
+    pub lut: Option<Lut3D>,
+    /// Declared input/output encodings of `lut`.
+    pub domain: Option<LutDomain>,
+    /// Source → working-linear LUT uploaded to the GPU.
+    pub baked: Option<Lut3D>,
+    /// Color management `baked` was made for; a change triggers a re-bake.
+    pub baked_for: Option<ColorManagementConfig>,
+    /// Whether the LUT or its domain changed since the last upload.
+    pub dirty: bool,
+    /// Modification time of `path` when it was last read.
+    pub modified: Option<SystemTime>,
+}
+
+/// Hooks fired on pipeline events.
+///
+/// Loaded at startup from the JSON file named by `CRISPEN_HOOKS`; empty if
+/// the variable is unset or the file is unreadable.
+#[derive(Resource, Default)]
+pub struct HookState {
+    /// Configured hooks.
+    pub config: HookConfig,
+}
+
+impl HookState {
+    /// Load the config named by `CRISPEN_HOOKS`, if any.
+    pub fn from_env() -> Self {
+        let Ok(path) = std::env::var("CRISPEN_HOOKS") else {
+            return Self::default();
+        };
+        match HookConfig::load(std::path::Path::new(&path)) {
+            Ok(config) => {
+                tracing::info!("Loaded {} hook(s) from {path}", config.hooks.len());
+                Self { config }
+            }
+            Err(e) => {
+                tracing::error!("Ignoring hook config {path}: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Audit log of grade changes.
+///
+/// Enabled at startup when `CRISPEN_AUDIT_LOG` names the log file, usually
+/// `<project>.audit.jsonl` beside the project; entries are recorded as
+/// `CRISPEN_OPERATOR`, else the login name. Hand edits are recorded once
+/// the grade has been still for [`AUDIT_SETTLE`], so a slider drag is one
+/// entry rather than one per frame.
+#[derive(Resource, Default)]
+pub struct AuditState {
+    /// The log, or `None` when auditing is off.
+    pub log: Option<AuditLog>,
+    /// Grade as of the last entry.
+    pub committed: GradingParams,
+    /// Grade at the end of the previous frame.
+    pub seen: GradingParams,
+    /// When the grade last changed, while the change is unrecorded.
+    pub changed_at: Option<Instant>,
+}
+
+/// How long the grade must be unchanged before hand edits are recorded.
+pub const AUDIT_SETTLE: Duration = Duration::from_secs(2);
+
+impl AuditState {
+    /// Open the log named by `CRISPEN_AUDIT_LOG`, if any.
+    pub fn from_env() -> Self {
+        let Ok(path) = std::env::var("CRISPEN_AUDIT_LOG") else {
+            return Self::default();
+        };
+        let log = AuditLog::new(path, current_operator());
+        tracing::info!(
+            "Auditing grade changes by {} to {}",
+            log.operator(),
+            log.path().display()
+        );
+        Self {
+            log: Some(log),
+            ..Self::default()
+        }
+    }
+}
+
+/// Polling of LUT files for changes made by other tools.
+#[derive(Resource)]
+pub struct LutWatchState {
+    /// Whether changed files are reloaded automatically.
+    pub enabled: bool,
+    /// Interval between modification-time checks.
+    pub poll: Timer,
+}
+
+impl Default for LutWatchState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
 }
 
 /// Raw pixel bytes for the viewer, produced by the GPU pipeline.
@@ -97,8 +228,21 @@ pub struct ScopeState {
     pub waveform: Option<WaveformData>,
     pub vectorscope: Option<VectorscopeData>,
     pub cie: Option<CieData>,
+    /// Temporal accumulation applied to each readback before it is stored.
+    pub history: ScopeHistory,
 }
 
+/// Output matte for the session, previewed by the frontend's viewer and
+/// baked into script renders.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct OutputBlanking(pub Blanking);
+
+/// Dead-pixel and dust map inpainted on the source before grading, set
+/// from a clip's defect map, marked in the viewer, or detected on a black
+/// frame.
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct SourceDefects(pub DefectMap);
+
 /// Configuration for which scopes are active.
 #[derive(Resource)]
 pub struct ScopeConfig {
@@ -106,6 +250,14 @@ pub struct ScopeConfig {
     pub waveform_visible: bool,
     pub vectorscope_visible: bool,
     pub cie_visible: bool,
+    /// Whether the scopes are on screen at all. A frontend clears this while
+    /// their panel is hidden or collapsed, pausing every scope without
+    /// touching the per-scope flags above.
+    pub on_screen: bool,
+    /// Overlay and projection choices used when drawing the scopes.
+    pub display: ScopeDisplayOptions,
+    /// Requested scope resolutions and frame budget.
+    pub quality: ScopeQuality,
 }
 
 impl Default for ScopeConfig {
@@ -115,6 +267,9 @@ impl Default for ScopeConfig {
             waveform_visible: true,
             vectorscope_visible: true,
             cie_visible: true,
+            on_screen: true,
+            display: ScopeDisplayOptions::default(),
+            quality: ScopeQuality::default(),
         }
     }
 }
@@ -122,10 +277,67 @@ impl Default for ScopeConfig {
 impl ScopeConfig {
     /// Whether any scope computation should run.
     pub fn any_visible(&self) -> bool {
-        self.histogram_visible
-            || self.waveform_visible
-            || self.vectorscope_visible
-            || self.cie_visible
+        self.on_screen
+            && (self.histogram_visible
+                || self.waveform_visible
+                || self.vectorscope_visible
+                || self.cie_visible)
+    }
+}
+
+/// GPU adapter the grading pipeline should use, by adapter name.
+///
+/// Read once at startup. Insert before [`CrispenPlugin`](crate::CrispenPlugin)
+/// runs its startup systems; `None` or an unknown name uses the default
+/// high-performance adapter.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GpuAdapterPreference {
+    pub name: Option<String>,
+}
+
+/// Quality and bandwidth choices for the GPU pipeline.
+///
+/// Changing this resource re-renders the current image with the new values.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PipelineSettings {
+    /// Edge length of the baked grading LUT.
+    pub lut_size: u32,
+    /// Pixel format of the viewer readback.
+    pub viewer_format: ViewerFormat,
+    /// Number of frames that may be in flight in the async readback ring.
+    pub readback_slots: usize,
+    /// Submissions allowed in flight at once, capped by `readback_slots`.
+    ///
+    /// While the cap is reached the grade stays dirty, so changes made
+    /// during a drag coalesce and only the latest params are submitted once
+    /// a readback completes.
+    pub max_in_flight: usize,
+    /// Baked LUTs kept besides the current one, so switching back to a
+    /// recent grade version or bypass state skips the bake. `0` disables
+    /// the cache.
+    pub lut_cache: usize,
+}
+
+impl PipelineSettings {
+    /// Supported grading LUT sizes.
+    pub const LUT_SIZE_RANGE: RangeInclusive<u32> = 17..=129;
+    /// Supported readback ring sizes.
+    pub const READBACK_SLOTS_RANGE: RangeInclusive<usize> = 2..=4;
+    /// Supported in-flight submission caps.
+    pub const MAX_IN_FLIGHT_RANGE: RangeInclusive<usize> = 1..=4;
+    /// Supported LUT cache sizes.
+    pub const LUT_CACHE_RANGE: RangeInclusive<usize> = 0..=32;
+}
+
+impl Default for PipelineSettings {
+    fn default() -> Self {
+        Self {
+            lut_size: 65,
+            viewer_format: ViewerFormat::Srgb8,
+            readback_slots: DEFAULT_READBACK_SLOTS,
+            max_in_flight: 2,
+            lut_cache: DEFAULT_LUT_CACHE_CAPACITY,
+        }
     }
 }
 
@@ -147,17 +359,82 @@ pub struct VulkanInteropState {
     pub capabilities: VulkanInteropCapabilities,
 }
 
-/// Per-pixel scope mask uploaded to the GPU.
+/// Polygon restricting scope analysis, rasterized into the GPU scope mask.
 ///
-/// When `active` is true, only pixels with `mask[i] == 1` are included in
-/// scope analysis. The mask length must match the source image pixel count.
+/// When `active` is true, only pixels inside `polygon` (normalized 0..1
+/// image UV, even-odd rule) are included in scope analysis. The mask is
+/// rasterized on the GPU at `width x height`, the source image size.
 #[derive(Resource, Default)]
 pub struct ScopeMaskData {
-    pub mask: Vec<u32>,
+    pub polygon: Vec<Vec2>,
+    pub width: u32,
+    pub height: u32,
     pub active: bool,
     pub dirty: bool,
 }
 
+impl ScopeMaskData {
+    /// Restrict scopes to the inside of a normalized (0..1 image UV) polygon.
+    /// Fewer than three points clears the mask.
+    pub fn set_polygon(&mut self, polygon: &[Vec2], width: u32, height: u32) {
+        if polygon.len() < 3 {
+            self.clear();
+            return;
+        }
+        self.polygon.clear();
+        self.polygon.extend_from_slice(polygon);
+        self.width = width;
+        self.height = height;
+        self.active = true;
+        self.dirty = true;
+    }
+
+    /// Include every pixel in scope analysis again.
+    pub fn clear(&mut self) {
+        self.polygon.clear();
+        self.active = false;
+        self.dirty = true;
+    }
+
+    /// The polygon as plain coordinate pairs for the GPU pipeline.
+    pub fn vertices(&self) -> Vec<[f32; 2]> {
+        self.polygon.iter().map(|p| p.to_array()).collect()
+    }
+}
+
+/// When the grading pipeline last had work, so a frontend can lower its
+/// frame rate while nothing changes.
+///
+/// `track_pipeline_activity` marks every frame with a dirty grade or a
+/// readback in flight; frontends also mark user input.
+#[derive(Resource, Debug)]
+pub struct PipelineActivity {
+    last_active: Instant,
+    /// Time without work after which the pipeline counts as idle.
+    pub idle_after: Duration,
+}
+
+impl Default for PipelineActivity {
+    fn default() -> Self {
+        Self {
+            last_active: Instant::now(),
+            idle_after: Duration::from_secs(2),
+        }
+    }
+}
+
+impl PipelineActivity {
+    /// Record work, or input that may lead to work, now.
+    pub fn mark_active(&mut self) {
+        self.last_active = Instant::now();
+    }
+
+    /// Whether nothing happened for `idle_after`.
+    pub fn is_idle(&self) -> bool {
+        self.last_active.elapsed() >= self.idle_after
+    }
+}
+
 /// Runtime timings for the grading pipeline.
 #[derive(Resource)]
 pub struct PipelinePerfStats {
@@ -168,6 +445,14 @@ pub struct PipelinePerfStats {
     pub total_time: Duration,
     pub slow_update_threshold: Duration,
     pub last_log_at: Instant,
+    /// Frame-time tracker that lowers scope resolution when over budget.
+    pub scope_governor: ScopeQualityGovernor,
+    /// Which viewer updates also compute scopes, per `ScopeQuality::cadence`.
+    pub scope_schedule: ScopeScheduler,
+    /// Frames a dirty grade was held back by `PipelineSettings::max_in_flight`.
+    pub coalesced: u64,
+    /// Change-to-display latency and result rate.
+    pub latency: ChangeLatency,
 }
 
 impl Default for PipelinePerfStats {
@@ -180,6 +465,94 @@ impl Default for PipelinePerfStats {
             total_time: Duration::ZERO,
             slow_update_threshold: Duration::from_millis(10),
             last_log_at: Instant::now(),
+            scope_governor: ScopeQualityGovernor::default(),
+            scope_schedule: ScopeScheduler::default(),
+            coalesced: 0,
+            latency: ChangeLatency::default(),
+        }
+    }
+}
+
+/// Measures how long a grade change takes to reach the viewer.
+///
+/// A change is stamped on the first frame its grade is seen dirty and
+/// carried with the submission that includes it. When a readback is
+/// consumed, the oldest change it retires gives the latency, so changes
+/// coalesced into a later submission count their full wait.
+#[derive(Debug)]
+pub struct ChangeLatency {
+    pending_since: Option<Instant>,
+    submitted: VecDeque<Option<Instant>>,
+    retired: u64,
+    /// Latency of the most recently displayed change.
+    pub last: Duration,
+    /// Smoothed latency over recent changes.
+    pub mean: Duration,
+    /// Results displayed per second, measured over windows of at least a
+    /// second.
+    pub results_per_second: u32,
+    window_start: Instant,
+    window_results: u32,
+}
+
+impl Default for ChangeLatency {
+    fn default() -> Self {
+        Self {
+            pending_since: None,
+            submitted: VecDeque::new(),
+            retired: 0,
+            last: Duration::ZERO,
+            mean: Duration::ZERO,
+            results_per_second: 0,
+            window_start: Instant::now(),
+            window_results: 0,
+        }
+    }
+}
+
+impl ChangeLatency {
+    /// Stamp the grade as changed, unless an earlier change is still waiting.
+    pub fn note_dirty(&mut self) {
+        self.pending_since.get_or_insert_with(Instant::now);
+    }
+
+    /// Attach the waiting change to a submission.
+    pub fn note_submit(&mut self) {
+        self.submitted.push_back(self.pending_since.take());
+    }
+
+    /// Record a consumed result. `retired` is the ring's running count of
+    /// consumed plus dropped readbacks.
+    pub fn note_result(&mut self, retired: u64) {
+        if retired < self.retired {
+            // The ring was rebuilt and its in-flight work discarded.
+            self.submitted.clear();
+            self.retired = retired;
+            return;
+        }
+        let count = (retired - self.retired) as usize;
+        self.retired = retired;
+        let oldest = self
+            .submitted
+            .drain(..count.min(self.submitted.len()))
+            .flatten()
+            .min();
+        if let Some(changed_at) = oldest {
+            self.last = changed_at.elapsed();
+            self.mean = if self.mean.is_zero() {
+                self.last
+            } else {
+                (self.mean * 7 + self.last) / 8
+            };
+        }
+
+        self.window_results += 1;
+        let window = self.window_start.elapsed();
+        if window >= Duration::from_secs(1) {
+            self.results_per_second =
+                (f64::from(self.window_results) / window.as_secs_f64()).round() as u32;
+            self.window_start = Instant::now();
+            self.window_results = 0;
         }
     }
 }
@@ -5,34 +5,59 @@
 //! pushes new state back via outbound messages.
 
 use bevy::prelude::*;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use crispen_core::grading::auto_balance;
+use crispen_core::color_management::icc;
+use crispen_core::grading::auto_contrast::{self, AutoContrastSettings};
+use crispen_core::grading::{auto_balance, chart_match, pixel_repair};
+use crispen_core::hooks::{HookAction, HookEvent, HookInvocation, spawn_command};
+use crispen_core::project::audit::AuditAction;
+use crispen_core::project::trace::diff_grades;
+use crispen_core::transform::bracket::{BracketVariant, bracket_path, exposure_bracket};
+use crispen_core::transform::compare::DEFAULT_TOLERANCE;
+use crispen_core::transform::look_preview::{self, LookEntry, LookKind};
+use crispen_core::transform::lut::Lut3D;
+use crispen_core::transform::lut_domain::{LutDomain, bake_input_lut};
 use crispen_core::transform::params::GradingParams;
-use crispen_gpu::ScopeResults;
+use crispen_core::transform::{nuke_export, parity, shader_export, shaper};
+use crispen_gpu::{ScopeConfig as GpuScopeConfig, ScopeResults};
 
 use crate::events::{
-    ColorGradingCommand, ImageLoadedEvent, ParamsUpdatedEvent, ScopeDataReadyEvent,
+    Bracket, BracketRenderedEvent, BracketStill, ColorGradingCommand, DeltaEReference,
+    DeltaEReportEvent, ExportCompletedEvent, GradePreviewsReadyEvent, GradeSavedEvent,
+    ImageLoadedEvent, LookPreview, LookPreviewsReadyEvent, LutAnalyzedEvent, LutReloadedEvent,
+    ParamsUpdatedEvent, ParityReportEvent, ScopeDataReadyEvent, ScriptFinishedEvent,
+    StillGrabbedEvent,
 };
 #[cfg(feature = "ocio")]
 use crate::resources::OcioColorManagement;
 use crate::resources::{
-    GpuPipelineState, GradingState, ImageState, PipelinePerfStats, ScopeConfig, ScopeMaskData,
-    ScopeState, ViewerData,
+    AUDIT_SETTLE, AuditState, GpuPipelineState, GradingState, HookState, INPUT_LUT_SLOT,
+    ImageState, InputLutState, LutWatchState, OutputBlanking, PipelineActivity, PipelinePerfStats,
+    PipelineSettings, ScopeConfig, ScopeMaskData, ScopeState, SourceDefects, ViewerData,
 };
 
 /// Process inbound grading commands from the UI.
 ///
 /// Reads `ColorGradingCommand` messages and mutates `GradingState`,
-/// `ImageState`, and `ScopeConfig` accordingly. Fires outbound
-/// notification messages when state changes.
+/// `ImageState`, `ScopeConfig`, `ScopeState`, `ScopeMaskData`,
+/// `OutputBlanking`, and `SourceDefects` accordingly. Fires outbound notification messages when
+/// state changes.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_grading_commands(
     mut commands: MessageReader<ColorGradingCommand>,
     mut state: ResMut<GradingState>,
     images: Res<ImageState>,
+    mut scope_state: ResMut<ScopeState>,
     mut scope_config: ResMut<ScopeConfig>,
+    mut scope_mask: ResMut<ScopeMaskData>,
+    mut blanking: ResMut<OutputBlanking>,
+    mut defects: ResMut<SourceDefects>,
     mut params_updated: MessageWriter<ParamsUpdatedEvent>,
     mut _image_loaded: MessageWriter<ImageLoadedEvent>,
+    mut exported: MessageWriter<ExportCompletedEvent>,
+    mut saved: MessageWriter<GradeSavedEvent>,
 ) {
     let mut pending_params_update: Option<GradingParams> = None;
 
@@ -47,7 +72,7 @@ pub fn handle_grading_commands(
             }
             ColorGradingCommand::AutoBalance => {
                 if let Some(ref source) = images.source {
-                    let (temp, tint) = auto_balance::auto_white_balance(source);
+                    let (temp, tint) = auto_balance::auto_white_balance(&source.promoted());
                     if state.params.temperature != temp || state.params.tint != tint {
                         state.params.temperature = temp;
                         state.params.tint = tint;
@@ -58,6 +83,120 @@ pub fn handle_grading_commands(
                     tracing::warn!("AutoBalance: no source image loaded");
                 }
             }
+            ColorGradingCommand::AutoContrast {
+                low_percentile,
+                high_percentile,
+                strength,
+            } => {
+                if let Some(ref histogram) = scope_state.histogram {
+                    let settings = AutoContrastSettings {
+                        low_percentile: *low_percentile,
+                        high_percentile: *high_percentile,
+                        strength: *strength,
+                    };
+                    let params = auto_contrast::auto_contrast(&state.params, histogram, &settings);
+                    if state.params != params {
+                        state.params = params;
+                        state.dirty = true;
+                        pending_params_update = Some(state.params.clone());
+                    }
+                } else {
+                    tracing::warn!("AutoContrast: no histogram computed yet");
+                }
+            }
+            ColorGradingCommand::MatchChart { corners } => {
+                if let Some(ref source) = images.source {
+                    let correction = chart_match::match_chart(
+                        &source.promoted(),
+                        *corners,
+                        &state.params.color_management,
+                    );
+                    if state.params.input_correction != correction {
+                        state.params.input_correction = correction;
+                        state.dirty = true;
+                        pending_params_update = Some(state.params.clone());
+                    }
+                } else {
+                    tracing::warn!("MatchChart: no source image loaded");
+                }
+            }
+            ColorGradingCommand::RunParityCheck => {
+                // Handled by run_parity_check, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::CompareDeltaE { .. } => {
+                // Handled by run_delta_e_compare, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::ClearDeltaE => {
+                // The frontend owns the heatmap overlay.
+            }
+            ColorGradingCommand::PreviewLooks { .. } => {
+                // Handled by render_look_previews, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::PreviewGrades { .. } => {
+                // Handled by render_grade_previews, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::ExportBracket { .. } => {
+                // Handled by export_bracket, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::ApplyLook { path } => {
+                // LUT looks are loaded by handle_lut_commands.
+                let Some(look) = LookEntry::from_path(path.into()) else {
+                    tracing::warn!("ApplyLook: {path} is not a .cube or .json look");
+                    continue;
+                };
+                if look.kind == LookKind::Grade {
+                    match look.load_grade(&state.params) {
+                        Ok(params) if params != state.params => {
+                            state.params = params;
+                            state.dirty = true;
+                            pending_params_update = Some(state.params.clone());
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::error!("ApplyLook: failed to read {path}: {e}"),
+                    }
+                }
+            }
+            ColorGradingCommand::SetBlanking { blanking: new } => {
+                blanking.set_if_neq(OutputBlanking(*new));
+            }
+            ColorGradingCommand::SetDefectMap { map } => {
+                defects.set_if_neq(SourceDefects(map.clone()));
+            }
+            ColorGradingCommand::MarkDefect { at, radius } => {
+                let Some(ref source) = images.source else {
+                    tracing::warn!("MarkDefect: no source image loaded");
+                    continue;
+                };
+                let (width, height) = (source.width(), source.height());
+                let x = ((at[0] * width as f32) as u32).min(width.saturating_sub(1));
+                let y = ((at[1] * height as f32) as u32).min(height.saturating_sub(1));
+                defects.0.mark(x, y, *radius);
+            }
+            ColorGradingCommand::DetectDefects { threshold } => {
+                let Some(ref source) = images.source else {
+                    tracing::warn!("DetectDefects: no source image loaded");
+                    continue;
+                };
+                let found = pixel_repair::detect_hot_pixels(&source.promoted(), *threshold);
+                let before = defects.0.defects.len();
+                for defect in found.defects {
+                    if !defects.0.defects.contains(&defect) {
+                        defects.0.defects.push(defect);
+                    }
+                }
+                tracing::info!(
+                    "DetectDefects: {} new hot pixels",
+                    defects.0.defects.len() - before
+                );
+            }
+            #[cfg(feature = "script")]
+            ColorGradingCommand::RunScript { .. } => {
+                // Handled by run_scripts.
+            }
+            #[cfg(not(feature = "script"))]
+            ColorGradingCommand::RunScript { path } => {
+                tracing::warn!("RunScript {path}: built without the `script` feature");
+            }
             ColorGradingCommand::ResetGrade => {
                 let defaults = GradingParams::default();
                 if state.params != defaults {
@@ -72,14 +211,103 @@ pub fn handle_grading_commands(
                 // loads the file, and injects into ImageState directly.
                 tracing::info!("LoadImage command received: {}", path);
             }
-            ColorGradingCommand::LoadLut { path, slot } => {
-                tracing::info!("LoadLut: {} -> slot {}", path, slot);
+            ColorGradingCommand::LoadLut { .. }
+            | ColorGradingCommand::SetLutDomain { .. }
+            | ColorGradingCommand::ClearLut { .. }
+            | ColorGradingCommand::SetLutWatch { .. } => {
+                // Handled by handle_lut_commands, which owns InputLutState.
             }
-            ColorGradingCommand::ExportLut { path, size } => {
-                if state.lut.is_some() {
-                    tracing::info!("ExportLut: {} (size {})", path, size);
-                } else {
-                    tracing::warn!("ExportLut: no LUT baked yet");
+            ColorGradingCommand::ExportLut { path, size, shaper } => {
+                let path_ref = std::path::Path::new(path);
+                let result = match shaper {
+                    Some(shaper) => {
+                        shaper::save_shaped_cube(&state.params, path_ref, *shaper, *size)
+                    }
+                    None => {
+                        let mut lut = Lut3D::new(*size);
+                        lut.bake(&state.params);
+                        lut.save_cube(path_ref)
+                    }
+                };
+                match result {
+                    Ok(()) => {
+                        tracing::info!("ExportLut: {} (size {}, shaper {:?})", path, size, shaper);
+                        exported.write(ExportCompletedEvent {
+                            path: path.clone(),
+                            kind: "lut",
+                        });
+                    }
+                    Err(e) => tracing::error!("ExportLut: failed to write {}: {}", path, e),
+                }
+            }
+            ColorGradingCommand::ExportIcc { path } => {
+                match icc::save_output_profile(
+                    &state.params.color_management,
+                    std::path::Path::new(path),
+                ) {
+                    Ok(()) => {
+                        tracing::info!("ExportIcc: {}", path);
+                        exported.write(ExportCompletedEvent {
+                            path: path.clone(),
+                            kind: "icc",
+                        });
+                    }
+                    Err(e) => tracing::error!("ExportIcc: failed to write {}: {}", path, e),
+                }
+            }
+            ColorGradingCommand::ExportNuke { path, size } => {
+                match nuke_export::save_nuke(&state.params, std::path::Path::new(path), *size) {
+                    Ok(()) => {
+                        tracing::info!("ExportNuke: {}", path);
+                        exported.write(ExportCompletedEvent {
+                            path: path.clone(),
+                            kind: "nuke",
+                        });
+                    }
+                    Err(e) => tracing::error!("ExportNuke: failed to write {}: {}", path, e),
+                }
+            }
+            ColorGradingCommand::SaveVersion { path } => {
+                match look_preview::save_grade(&state.params, Path::new(path)) {
+                    Ok(()) => {
+                        tracing::info!("SaveVersion: {}", path);
+                        exported.write(ExportCompletedEvent {
+                            path: path.clone(),
+                            kind: "version",
+                        });
+                        saved.write(GradeSavedEvent {
+                            path: path.clone(),
+                            kind: "version",
+                        });
+                    }
+                    Err(e) => tracing::error!("SaveVersion: failed to write {}: {}", path, e),
+                }
+            }
+            ColorGradingCommand::ExportAuditLog { .. } => {
+                // Handled by export_audit_log, which owns the audit log.
+            }
+            ColorGradingCommand::GrabStill { .. } => {
+                // Handled by grab_still, which needs the GPU pipeline.
+            }
+            ColorGradingCommand::ExportShader {
+                path,
+                size,
+                language,
+            } => {
+                match shader_export::save_shader(
+                    &state.params,
+                    *size,
+                    *language,
+                    std::path::Path::new(path),
+                ) {
+                    Ok(()) => {
+                        tracing::info!("ExportShader: {} ({:?})", path, language);
+                        exported.write(ExportCompletedEvent {
+                            path: path.clone(),
+                            kind: "shader",
+                        });
+                    }
+                    Err(e) => tracing::error!("ExportShader: failed to write {}: {}", path, e),
                 }
             }
             ColorGradingCommand::ToggleScope {
@@ -108,6 +336,36 @@ pub fn handle_grading_commands(
                 }
                 other => tracing::warn!("Unknown scope type: {}", other),
             },
+            ColorGradingCommand::SetScopeDisplayOptions { options } => {
+                if scope_config.display != *options {
+                    scope_config.display = options.clone();
+                }
+            }
+            ColorGradingCommand::SetScopeQuality { quality } => {
+                if scope_config.quality != *quality {
+                    scope_config.quality = *quality;
+                }
+            }
+            ColorGradingCommand::SetScopeMaskPolygon { points } => {
+                if let Some(ref source) = images.source {
+                    let polygon: Vec<Vec2> = points.iter().copied().map(Vec2::from).collect();
+                    scope_mask.set_polygon(&polygon, source.width(), source.height());
+                    state.dirty = true;
+                } else {
+                    tracing::warn!("SetScopeMaskPolygon: no source image loaded");
+                }
+            }
+            ColorGradingCommand::ClearScopeMask => {
+                if scope_mask.active {
+                    scope_mask.clear();
+                    state.dirty = true;
+                }
+            }
+            ColorGradingCommand::SetScopeAccumulation { mode } => {
+                if scope_state.history.mode() != *mode {
+                    scope_state.history.set_mode(*mode);
+                }
+            }
         }
     }
 
@@ -180,15 +438,25 @@ pub fn bake_ocio_luts(
 
 /// Submit GPU work (bake + apply + scopes) when params are dirty. Non-blocking.
 ///
+/// Scopes follow the configured [`ScopeCadence`](crispen_core::scopes::ScopeCadence):
+/// updates that skip them read back only the viewer image, and once the
+/// grade settles a clean frame resubmits the same params to bring stale
+/// scopes up to date.
+///
 /// The actual results are consumed by [`consume_gpu_results`] on a subsequent frame.
 pub fn submit_gpu_work(
     mut state: ResMut<GradingState>,
     mut perf: ResMut<PipelinePerfStats>,
     gpu: Option<ResMut<GpuPipelineState>>,
     scope_config: Res<ScopeConfig>,
+    settings: Res<PipelineSettings>,
     #[cfg(feature = "ocio")] ocio: Option<Res<OcioColorManagement>>,
 ) {
-    if !state.dirty {
+    let cadence = scope_config.quality.cadence;
+    let refresh = !state.dirty
+        && scope_config.any_visible()
+        && perf.scope_schedule.refresh_due(cadence, Instant::now());
+    if !state.dirty && !refresh {
         return;
     }
 
@@ -219,41 +487,151 @@ pub fn submit_gpu_work(
         }
     }
 
-    // Don't submit if the previous readback hasn't been consumed yet.
-    // Keep dirty=true so we retry next frame after consume frees the slot.
-    if gpu.pipeline.has_pending_readback() {
+    if state.dirty {
+        perf.latency.note_dirty();
+    }
+
+    // Latest wins: while the in-flight cap is reached, hold the dirty grade
+    // instead of queueing params that a newer change will supersede.
+    let max_in_flight = settings.max_in_flight.min(settings.readback_slots).max(1);
+    let in_flight = gpu
+        .pipeline
+        .readback_stats()
+        .map_or(0, |ring| ring.in_flight);
+    if in_flight >= max_in_flight {
+        if state.dirty {
+            perf.coalesced += 1;
+        }
         return;
     }
 
-    // Sync scope visibility so hidden scopes skip GPU compute.
+    // Sync scope visibility so hidden scopes, and updates the cadence
+    // skips, leave out scope compute and readback.
+    let with_scopes = refresh || perf.scope_schedule.wants_scopes(cadence);
+    let on_screen = scope_config.on_screen && with_scopes;
     gpu.pipeline.set_scope_visibility(
-        scope_config.histogram_visible,
-        scope_config.waveform_visible,
-        scope_config.vectorscope_visible,
-        scope_config.cie_visible,
+        on_screen && scope_config.histogram_visible,
+        on_screen && scope_config.waveform_visible,
+        on_screen && scope_config.vectorscope_visible,
+        on_screen && scope_config.cie_visible,
     );
 
     let submit_start = Instant::now();
 
     // Non-blocking GPU submission: bake → apply → format convert → scopes → async readback.
-    gpu.pipeline
-        .submit_gpu_work(source_handle, &state.params, 65);
+    // With every readback slot in flight nothing is submitted; keep dirty=true
+    // so we retry next frame after consume frees a slot.
+    if !gpu
+        .pipeline
+        .submit_gpu_work(source_handle, &state.params, settings.lut_size)
+    {
+        return;
+    }
 
     let submit_time = submit_start.elapsed();
     perf.updates += 1;
     perf.total_time = submit_time;
+    perf.latency.note_submit();
+    perf.scope_schedule
+        .record_update(with_scopes && scope_config.any_visible(), submit_start);
 
     if submit_time >= perf.slow_update_threshold || perf.last_log_at.elapsed().as_secs_f32() >= 1.0
     {
         tracing::info!(
-            "gpu submit: {:.2}ms (update #{})",
+            "gpu submit: {:.2}ms (update #{}, {} coalesced)",
             submit_time.as_secs_f64() * 1000.0,
-            perf.updates
+            perf.updates,
+            perf.coalesced
         );
         perf.last_log_at = Instant::now();
     }
 
-    state.dirty = false;
+    // A refresh leaves the grade untouched.
+    if state.dirty {
+        state.dirty = false;
+    }
+}
+
+/// Mark the pipeline active while a grade waits to be submitted or a
+/// readback is in flight.
+pub fn track_pipeline_activity(
+    state: Res<GradingState>,
+    gpu: Option<Res<GpuPipelineState>>,
+    mut activity: ResMut<PipelineActivity>,
+) {
+    let pending = gpu.is_some_and(|gpu| gpu.pipeline.has_pending_readback());
+    if state.dirty || pending {
+        activity.mark_active();
+    }
+}
+
+/// Track frame times and push the effective scope resolution to the GPU.
+///
+/// Applies the requested [`ScopeQuality`](crispen_core::scopes::ScopeQuality),
+/// halved by the frame-budget governor while frames run slow. A change
+/// marks the grade dirty so the scopes are recomputed at the new size.
+/// Frames are not recorded while the pipeline is idle, since a frontend
+/// may be pacing them slowly on purpose; that includes the first frame of
+/// new work, which `track_pipeline_activity` only marks afterwards.
+pub fn adapt_scope_quality(
+    time: Res<Time>,
+    scope_config: Res<ScopeConfig>,
+    activity: Res<PipelineActivity>,
+    mut perf: ResMut<PipelinePerfStats>,
+    mut state: ResMut<GradingState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+) {
+    let quality = &scope_config.quality;
+    let frame_ms = time.delta_secs() * 1000.0;
+    if frame_ms > 0.0 && !activity.is_idle() && perf.scope_governor.record_frame(frame_ms, quality)
+    {
+        tracing::info!(
+            "scope quality level {} (avg frame {:.1}ms, budget {:.1}ms)",
+            perf.scope_governor.level(),
+            perf.scope_governor.average_ms().unwrap_or(frame_ms),
+            quality.frame_budget_ms
+        );
+    }
+
+    let Some(mut gpu) = gpu else {
+        return;
+    };
+    let effective = perf.scope_governor.effective(quality);
+    let wanted = GpuScopeConfig {
+        waveform_height: effective.waveform_height,
+        vectorscope_resolution: effective.vectorscope,
+        cie_resolution: effective.cie,
+        sample_stride: quality.sample_stride,
+    };
+    if gpu.pipeline.scope_config() != wanted {
+        gpu.pipeline.set_scope_config(wanted);
+        if gpu.source_handle.is_some() {
+            state.dirty = true;
+        }
+    }
+}
+
+/// Push [`PipelineSettings`] to the GPU pipeline.
+///
+/// A change marks the grade dirty so the image is re-rendered with the new
+/// LUT size and viewer format.
+pub fn apply_pipeline_settings(
+    settings: Res<PipelineSettings>,
+    mut state: ResMut<GradingState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+) {
+    let Some(mut gpu) = gpu else {
+        return;
+    };
+    if !settings.is_changed() && !gpu.is_added() {
+        return;
+    }
+    gpu.pipeline.set_viewer_format(settings.viewer_format);
+    gpu.pipeline.set_readback_slots(settings.readback_slots);
+    gpu.pipeline.set_lut_cache_capacity(settings.lut_cache);
+    if gpu.source_handle.is_some() {
+        state.dirty = true;
+    }
 }
 
 /// Non-blocking: poll for async GPU readback results and update viewer + scopes.
@@ -263,10 +641,17 @@ pub fn consume_gpu_results(
     mut viewer_data: ResMut<ViewerData>,
     mut scope_state: ResMut<ScopeState>,
     mut scope_ready: MessageWriter<ScopeDataReadyEvent>,
+    images: Res<ImageState>,
+    mut perf: ResMut<PipelinePerfStats>,
     gpu: Option<ResMut<GpuPipelineState>>,
 ) {
     let t0 = Instant::now();
 
+    // A new source invalidates averaged / held scope history.
+    if images.is_changed() {
+        scope_state.history.reset();
+    }
+
     let Some(mut gpu) = gpu else {
         return;
     };
@@ -278,7 +663,9 @@ pub fn consume_gpu_results(
     let t_readback = t0.elapsed();
 
     let viewer_bytes_len = result.viewer_bytes.len();
-    viewer_data.pixel_bytes = result.viewer_bytes;
+    // Return the previous frame's buffer so the next readback reuses it.
+    let previous = std::mem::replace(&mut viewer_data.pixel_bytes, result.viewer_bytes);
+    gpu.pipeline.recycle_viewer_bytes(previous);
     viewer_data.width = result.width;
     viewer_data.height = result.height;
     viewer_data.format = result.format;
@@ -291,16 +678,928 @@ pub fn consume_gpu_results(
     }
 
     let t_total = t0.elapsed();
+    let ring = gpu.pipeline.readback_stats().unwrap_or_default();
+    perf.latency.note_result(ring.consumed + ring.dropped);
     tracing::info!(
-        "[PERF] consume_gpu_results: readback={:.2}ms viewer_copy={:.2}ms total={:.2}ms ({}bytes)",
+        "[PERF] consume_gpu_results: readback={:.2}ms viewer_copy={:.2}ms total={:.2}ms ({}bytes) \
+         latency={:.2}ms change_latency={:.2}ms results/s={} in_flight={}/{} dropped={} stalls={} \
+         viewer_allocs={}",
         t_readback.as_secs_f64() * 1000.0,
         (t_viewer_copy - t_readback).as_secs_f64() * 1000.0,
         t_total.as_secs_f64() * 1000.0,
         viewer_bytes_len,
+        ring.mean_latency.as_secs_f64() * 1000.0,
+        perf.latency.mean.as_secs_f64() * 1000.0,
+        perf.latency.results_per_second,
+        ring.in_flight,
+        ring.slots,
+        ring.dropped,
+        ring.stalls,
+        ring.viewer_allocations,
+    );
+}
+
+/// Blocking: on `RunParityCheck`, grade the source through the GPU LUT path
+/// with the current params, read it back, and compare against
+/// `evaluate_transform()` on the CPU.
+///
+/// Debug-only, so the stall of a synchronous bake + readback is acceptable.
+pub fn run_parity_check(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    input_lut: Res<InputLutState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+    mut reports: MessageWriter<ParityReportEvent>,
+) {
+    if !commands
+        .read()
+        .any(|cmd| matches!(cmd, ColorGradingCommand::RunParityCheck))
+    {
+        return;
+    }
+
+    let Some(mut gpu) = gpu else {
+        tracing::warn!("RunParityCheck: no GPU pipeline");
+        return;
+    };
+    let gpu = &mut *gpu;
+    let (Some(source), Some(source_handle)) = (images.source.as_ref(), gpu.source_handle.as_ref())
+    else {
+        tracing::warn!("RunParityCheck: no source image loaded");
+        return;
+    };
+    // Compare against exactly the pixels the GPU was given.
+    let source = source.promoted();
+
+    let start = Instant::now();
+    gpu.pipeline.bake_lut(&state.params, 65);
+    gpu.pipeline.apply_lut(source_handle);
+    let Some(output) = gpu.pipeline.download_current_output() else {
+        tracing::warn!("RunParityCheck: GPU produced no output");
+        return;
+    };
+
+    match parity::compare_with_cpu(
+        &state.params,
+        &source,
+        &output,
+        input_lut.baked.as_ref(),
+        parity::DEFAULT_MAX_SAMPLES,
+    ) {
+        Some(report) => {
+            tracing::info!(
+                "parity: {} px (stride {}), max err {:.2e}, mean err {:.2e}, \
+                 ΔE mean {:.3} p95 {:.3} max {:.3} at ({}, {}) in {:.1}ms",
+                report.sampled,
+                report.stride,
+                report.max_abs_error,
+                report.mean_abs_error,
+                report.delta_e_mean,
+                report.delta_e_p95,
+                report.delta_e_max,
+                report.worst.x,
+                report.worst.y,
+                start.elapsed().as_secs_f64() * 1000.0,
+            );
+            reports.write(ParityReportEvent { report });
+        }
+        None => tracing::warn!(
+            "RunParityCheck: GPU output {}x{} does not match source {}x{}",
+            output.width,
+            output.height,
+            source.width,
+            source.height
+        ),
+    }
+}
+
+/// Blocking: on `CompareDeltaE`, grade the source with the current params,
+/// grade or upload the reference, and compare the two per pixel on the GPU.
+///
+/// Only the last comparison requested in a frame runs. A grade reference is
+/// baked with the current output space so both sides are decoded alike;
+/// the current grade is baked again afterwards.
+pub fn run_delta_e_compare(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+    mut reports: MessageWriter<DeltaEReportEvent>,
+) {
+    let Some((reference, scale)) = commands.read().fold(None, |last, cmd| match cmd {
+        ColorGradingCommand::CompareDeltaE { reference, scale } => Some((reference, *scale)),
+        _ => last,
+    }) else {
+        return;
+    };
+
+    let Some(mut gpu) = gpu else {
+        tracing::warn!("CompareDeltaE: no GPU pipeline");
+        return;
+    };
+    let gpu = &mut *gpu;
+    let (Some(source), Some(source_handle)) = (images.source.as_ref(), gpu.source_handle.as_ref())
+    else {
+        tracing::warn!("CompareDeltaE: no source image loaded");
+        return;
+    };
+
+    let start = Instant::now();
+    let output_space = state.params.color_management.output_space;
+    let reference_handle = match reference {
+        DeltaEReference::Image(image) => {
+            if (image.width, image.height) != (source.width(), source.height()) {
+                tracing::warn!(
+                    "CompareDeltaE: reference {}x{} does not match source {}x{}",
+                    image.width,
+                    image.height,
+                    source.width(),
+                    source.height()
+                );
+                return;
+            }
+            gpu.pipeline.upload_image(image)
+        }
+        DeltaEReference::Grade(params) => {
+            let mut params = (**params).clone();
+            params.color_management.output_space = output_space;
+            gpu.pipeline.bake_lut(&params, 65);
+            gpu.pipeline.apply_lut(source_handle);
+            let Some(snapshot) = gpu.pipeline.snapshot_current_output() else {
+                tracing::warn!("CompareDeltaE: GPU produced no reference output");
+                return;
+            };
+            snapshot
+        }
+    };
+
+    gpu.pipeline.bake_lut(&state.params, 65);
+    gpu.pipeline.apply_lut(source_handle);
+    let Some(result) = gpu
+        .pipeline
+        .compare_delta_e(&reference_handle, output_space, scale)
+    else {
+        tracing::warn!("CompareDeltaE: GPU produced no output");
+        return;
+    };
+
+    let report = result.map.report(DEFAULT_TOLERANCE);
+    tracing::info!(
+        "ΔE compare: {}x{}, mean {:.3} p95 {:.3} max {:.3} at ({}, {}), {:.1}% above {} in {:.1}ms",
+        report.width,
+        report.height,
+        report.delta_e_mean,
+        report.delta_e_p95,
+        report.delta_e_max,
+        report.worst_x,
+        report.worst_y,
+        report.above_tolerance * 100.0,
+        report.tolerance,
+        start.elapsed().as_secs_f64() * 1000.0,
+    );
+    reports.write(DeltaEReportEvent {
+        report,
+        heatmap: result.heatmap,
+    });
+}
+
+/// Blocking: on `PreviewLooks`, render the source through every look in the
+/// given folders as thumbnails, in one GPU dispatch.
+///
+/// Each look is baked on the CPU into a small preview LUT of the chain it
+/// would produce with the current grade; looks that fail to read are
+/// skipped. Only the last request in a frame runs.
+pub fn render_look_previews(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    gpu: Option<Res<GpuPipelineState>>,
+    mut ready: MessageWriter<LookPreviewsReadyEvent>,
+) {
+    let Some(dirs) = commands.read().fold(None, |last, cmd| match cmd {
+        ColorGradingCommand::PreviewLooks { dirs } => Some(dirs),
+        _ => last,
+    }) else {
+        return;
+    };
+
+    let Some(gpu) = gpu else {
+        tracing::warn!("PreviewLooks: no GPU pipeline");
+        return;
+    };
+    let Some(source) = images.source.as_ref() else {
+        tracing::warn!("PreviewLooks: no source image loaded");
+        return;
+    };
+    let dirs: Vec<PathBuf> = dirs.iter().map(PathBuf::from).collect();
+    let entries = match look_preview::scan_looks(&dirs) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("PreviewLooks: {e}");
+            return;
+        }
+    };
+
+    let start = Instant::now();
+    let mut looks = Vec::with_capacity(entries.len());
+    let mut luts = Vec::with_capacity(entries.len());
+    for look in entries {
+        match look.preview_lut(&state.params, look_preview::PREVIEW_LUT_SIZE) {
+            Ok(lut) => {
+                luts.push(lut);
+                looks.push(look);
+            }
+            Err(e) => tracing::warn!("PreviewLooks: skipping {}: {e}", look.path.display()),
+        }
+    }
+    let proxy = look_preview::thumbnail_source(&source.promoted(), look_preview::THUMBNAIL_WIDTH);
+    let thumbnails = if luts.is_empty() {
+        Vec::new()
+    } else {
+        let Some(thumbnails) = gpu.pipeline.render_look_previews(&proxy, &luts) else {
+            tracing::warn!("PreviewLooks: GPU produced no thumbnails");
+            return;
+        };
+        thumbnails
+    };
+
+    tracing::info!(
+        "Look previews: {} looks at {}x{} in {:.1}ms",
+        looks.len(),
+        proxy.width,
+        proxy.height,
+        start.elapsed().as_secs_f64() * 1000.0,
+    );
+    ready.write(LookPreviewsReadyEvent {
+        width: proxy.width,
+        height: proxy.height,
+        looks: looks
+            .into_iter()
+            .zip(thumbnails)
+            .map(|(look, thumbnail)| LookPreview { look, thumbnail })
+            .collect(),
+    });
+}
+
+/// Blocking: on `PreviewGrades`, render the source under the current grade
+/// and each requested grade as thumbnails, in one GPU dispatch.
+///
+/// Grades are baked into small preview LUTs like grade presets in the look
+/// browser, with the shot's color management. Only the last request in a
+/// frame runs.
+pub fn render_grade_previews(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    gpu: Option<Res<GpuPipelineState>>,
+    mut ready: MessageWriter<GradePreviewsReadyEvent>,
+) {
+    let Some(grades) = commands.read().fold(None, |last, cmd| match cmd {
+        ColorGradingCommand::PreviewGrades { grades } => Some(grades),
+        _ => last,
+    }) else {
+        return;
+    };
+
+    let Some(gpu) = gpu else {
+        tracing::warn!("PreviewGrades: no GPU pipeline");
+        return;
+    };
+    let Some(source) = images.source.as_ref() else {
+        tracing::warn!("PreviewGrades: no source image loaded");
+        return;
+    };
+
+    let start = Instant::now();
+    let luts: Vec<Lut3D> = std::iter::once(&state.params)
+        .chain(grades)
+        .map(|grade| {
+            let mut grade = grade.clone();
+            grade.color_management = state.params.color_management.clone();
+            let mut lut = Lut3D::new(look_preview::PREVIEW_LUT_SIZE);
+            lut.bake(&grade);
+            lut
+        })
+        .collect();
+    let proxy = look_preview::thumbnail_source(&source.promoted(), look_preview::THUMBNAIL_WIDTH);
+    let Some(mut thumbnails) = gpu.pipeline.render_look_previews(&proxy, &luts) else {
+        tracing::warn!("PreviewGrades: GPU produced no thumbnails");
+        return;
+    };
+
+    tracing::info!(
+        "Grade previews: {} grades at {}x{} in {:.1}ms",
+        grades.len(),
+        proxy.width,
+        proxy.height,
+        start.elapsed().as_secs_f64() * 1000.0,
+    );
+    let current = thumbnails.remove(0);
+    ready.write(GradePreviewsReadyEvent {
+        width: proxy.width,
+        height: proxy.height,
+        current,
+        thumbnails,
+    });
+}
+
+/// Blocking: on `ExportBracket`, render the source once per variant with
+/// the full GPU chain and the output blanking, for the frontend to write.
+///
+/// Versions that fail to read are skipped. Only the last request in a
+/// frame runs.
+pub fn export_bracket(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    settings: Res<PipelineSettings>,
+    blanking: Res<OutputBlanking>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+    mut rendered: MessageWriter<BracketRenderedEvent>,
+) {
+    let Some((path, bracket)) = commands.read().fold(None, |last, cmd| match cmd {
+        ColorGradingCommand::ExportBracket { path, bracket } => Some((path, bracket)),
+        _ => last,
+    }) else {
+        return;
+    };
+
+    let Some(mut gpu) = gpu else {
+        tracing::warn!("ExportBracket: no GPU pipeline");
+        return;
+    };
+    let gpu = &mut *gpu;
+    let Some(source_handle) = gpu.source_handle.as_ref() else {
+        tracing::warn!("ExportBracket: no source image loaded");
+        return;
+    };
+
+    let variants = match bracket {
+        Bracket::Exposure { stops } => exposure_bracket(&state.params, stops),
+        Bracket::Versions { paths } => paths
+            .iter()
+            .filter_map(|version| {
+                let Some(look) = LookEntry::from_path(version.into())
+                    .filter(|look| look.kind == LookKind::Grade)
+                else {
+                    tracing::warn!("ExportBracket: {version} is not a .json grade");
+                    return None;
+                };
+                match look.load_grade(&state.params) {
+                    Ok(params) => Some(BracketVariant {
+                        label: look.name,
+                        params,
+                    }),
+                    Err(e) => {
+                        tracing::warn!("ExportBracket: skipping {version}: {e}");
+                        None
+                    }
+                }
+            })
+            .collect(),
+    };
+
+    let start = Instant::now();
+    let path = Path::new(path);
+    let lut_size = settings.lut_size;
+    let stills: Vec<BracketStill> = variants
+        .into_iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let mut image = gpu
+                .pipeline
+                .render_still(source_handle, &variant.params, lut_size);
+            let (width, height) = (image.width, image.height);
+            blanking.0.apply(&mut image.pixels, width, height);
+            BracketStill {
+                path: bracket_path(path, index, &variant.label),
+                label: variant.label,
+                image,
+            }
+        })
+        .collect();
+
+    tracing::info!(
+        "Bracket: {} stills in {:.1}ms",
+        stills.len(),
+        start.elapsed().as_secs_f64() * 1000.0,
     );
+    rendered.write(BracketRenderedEvent { stills });
 }
 
-/// Upload the scope mask to the GPU pipeline when it changes.
+/// Blocking: on `GrabStill`, render the source with the current grade, the
+/// full GPU chain, and the output blanking, like a bracket still, for the
+/// frontend to store in its gallery.
+pub fn grab_still(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    settings: Res<PipelineSettings>,
+    blanking: Res<OutputBlanking>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+    mut grabbed: MessageWriter<StillGrabbedEvent>,
+) {
+    let labels: Vec<String> = commands
+        .read()
+        .filter_map(|cmd| match cmd {
+            ColorGradingCommand::GrabStill { label } => Some(label.clone()),
+            _ => None,
+        })
+        .collect();
+    if labels.is_empty() {
+        return;
+    }
+
+    let Some(mut gpu) = gpu else {
+        tracing::warn!("GrabStill: no GPU pipeline");
+        return;
+    };
+    let gpu = &mut *gpu;
+    let Some(source_handle) = gpu.source_handle.as_ref() else {
+        tracing::warn!("GrabStill: no source image loaded");
+        return;
+    };
+
+    // Every grab in a frame sees the same grade, so render once.
+    let mut image = gpu
+        .pipeline
+        .render_still(source_handle, &state.params, settings.lut_size);
+    let (width, height) = (image.width, image.height);
+    blanking.0.apply(&mut image.pixels, width, height);
+    for label in labels {
+        grabbed.write(StillGrabbedEvent {
+            label,
+            image: image.clone(),
+            params: state.params.clone(),
+            source: images.source_path.clone(),
+        });
+    }
+}
+
+/// Kind of the look file at `path`, if it is one.
+fn look_kind(path: &str) -> Option<LookKind> {
+    LookEntry::from_path(path.into()).map(|look| look.kind)
+}
+
+/// Load, re-declare, and clear imported LUTs.
+///
+/// Every loaded `.cube` runs the LUT health checks so the inspector can flag
+/// broken LUTs before they are used. A LUT in the [`INPUT_LUT_SLOT`] is kept
+/// in `InputLutState` for `sync_input_lut` to apply; other slots are
+/// analyzed only. `ApplyLook` on a `.cube` loads it into the input slot.
+pub fn handle_lut_commands(
+    mut commands: MessageReader<ColorGradingCommand>,
+    state: Res<GradingState>,
+    mut input_lut: ResMut<InputLutState>,
+    mut watch: ResMut<LutWatchState>,
+    mut analyzed: MessageWriter<LutAnalyzedEvent>,
+) {
+    for cmd in commands.read() {
+        // A LUT applied from the look browser loads like an import into
+        // the input slot, with its domain guessed from the file name.
+        let load = match cmd {
+            ColorGradingCommand::LoadLut { path, slot, domain } => {
+                Some((path, slot.as_str(), *domain))
+            }
+            ColorGradingCommand::ApplyLook { path } if look_kind(path) == Some(LookKind::Lut) => {
+                Some((path, INPUT_LUT_SLOT, None))
+            }
+            _ => None,
+        };
+        if let Some((path, slot, domain)) = load {
+            let lut = match Lut3D::load_cube(std::path::Path::new(path)) {
+                Ok(lut) => lut,
+                Err(e) => {
+                    tracing::error!("LoadLut: failed to read {path}: {e}");
+                    continue;
+                }
+            };
+            let domain = domain.unwrap_or_else(|| {
+                LutDomain::guess(path, state.params.color_management.input_space)
+            });
+            let analysis = lut.analyze();
+            if !analysis.is_clean() {
+                tracing::warn!("LoadLut: {path} failed checks: {analysis:?}");
+            }
+            tracing::info!(
+                "LoadLut: {path} -> slot {slot} ({} -> {})",
+                domain.input.label(),
+                domain.output.label()
+            );
+            if slot == INPUT_LUT_SLOT {
+                input_lut.modified = file_modified(path);
+                input_lut.path = Some(path.clone());
+                input_lut.lut = Some(lut);
+                input_lut.domain = Some(domain);
+                input_lut.dirty = true;
+            }
+            analyzed.write(LutAnalyzedEvent {
+                path: path.clone(),
+                slot: slot.to_string(),
+                domain,
+                analysis,
+            });
+            continue;
+        }
+
+        match cmd {
+            ColorGradingCommand::SetLutDomain { slot, domain } => {
+                if slot == INPUT_LUT_SLOT && input_lut.lut.is_some() {
+                    input_lut.domain = Some(*domain);
+                    input_lut.dirty = true;
+                } else {
+                    tracing::warn!("SetLutDomain: no LUT loaded in slot {slot}");
+                }
+            }
+            ColorGradingCommand::ClearLut { slot } if slot == INPUT_LUT_SLOT => {
+                *input_lut = InputLutState {
+                    dirty: true,
+                    ..default()
+                };
+            }
+            ColorGradingCommand::SetLutWatch { enabled } => {
+                watch.enabled = *enabled;
+                tracing::info!("LUT file watching {}", if *enabled { "on" } else { "off" });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Blocking: on `RunScript`, run the script against a copy of the current
+/// grade and source image, then apply the grade it leaves behind.
+///
+/// Images the script loads or renders stay inside the script; only the grade
+/// comes back. A failed script changes nothing.
+#[cfg(feature = "script")]
+pub fn run_scripts(
+    mut commands: MessageReader<ColorGradingCommand>,
+    mut state: ResMut<GradingState>,
+    images: Res<ImageState>,
+    blanking: Res<OutputBlanking>,
+    defects: Res<SourceDefects>,
+    mut params_updated: MessageWriter<ParamsUpdatedEvent>,
+    mut finished: MessageWriter<ScriptFinishedEvent>,
+) {
+    use crispen_script::{ScriptContext, ScriptHost};
+
+    for cmd in commands.read() {
+        let ColorGradingCommand::RunScript { path } = cmd else {
+            continue;
+        };
+        let host = ScriptHost::new(ScriptContext {
+            blanking: blanking.0,
+            defects: defects.0.clone(),
+            ..ScriptContext::new(
+                state.params.clone(),
+                images.source.as_ref().map(|s| s.promoted().into_owned()),
+            )
+        });
+        let result = host.run_file(std::path::Path::new(path), &[]);
+        let context = host.into_context();
+
+        let error = match result {
+            Ok(()) => {
+                tracing::info!("RunScript: {path} finished");
+                if context.params != state.params {
+                    state.params = context.params;
+                    state.dirty = true;
+                    params_updated.write(ParamsUpdatedEvent {
+                        params: state.params.clone(),
+                    });
+                }
+                None
+            }
+            Err(e) => {
+                tracing::error!("RunScript: {path} failed: {e}");
+                Some(e.to_string())
+            }
+        };
+        finished.write(ScriptFinishedEvent {
+            path: path.clone(),
+            output: context.output,
+            error,
+        });
+    }
+}
+
+/// Run configured hooks for this frame's pipeline events.
+///
+/// Commands are spawned and reaped on a helper thread so a slow publish
+/// script never stalls the frame; a non-zero exit is logged. Script hooks
+/// run on their own thread against a copy of the current grade and cannot
+/// change the session.
+pub fn fire_hooks(
+    hooks: Res<HookState>,
+    #[cfg(feature = "script")] state: Res<GradingState>,
+    mut images: MessageReader<ImageLoadedEvent>,
+    mut luts: MessageReader<LutAnalyzedEvent>,
+    mut exports: MessageReader<ExportCompletedEvent>,
+    mut scripts: MessageReader<ScriptFinishedEvent>,
+    mut saved: MessageReader<GradeSavedEvent>,
+) {
+    let mut invocations = Vec::new();
+    invocations.extend(images.read().map(|e| {
+        HookInvocation::new(
+            HookEvent::ImageLoaded,
+            [
+                ("path", e.path.clone()),
+                ("width", e.width.to_string()),
+                ("height", e.height.to_string()),
+                ("bit_depth", e.bit_depth.clone()),
+            ],
+        )
+    }));
+    invocations.extend(luts.read().map(|e| {
+        HookInvocation::new(
+            HookEvent::LutLoaded,
+            [("path", e.path.clone()), ("slot", e.slot.clone())],
+        )
+    }));
+    invocations.extend(exports.read().map(|e| {
+        HookInvocation::new(
+            HookEvent::ExportComplete,
+            [("path", e.path.clone()), ("kind", e.kind.to_string())],
+        )
+    }));
+    invocations.extend(scripts.read().map(|e| {
+        let status = if e.error.is_some() { "error" } else { "ok" };
+        HookInvocation::new(
+            HookEvent::ScriptFinished,
+            [("path", e.path.clone()), ("status", status.to_string())],
+        )
+    }));
+    invocations.extend(saved.read().map(|e| {
+        HookInvocation::new(
+            HookEvent::GradeSaved,
+            [("path", e.path.clone()), ("kind", e.kind.to_string())],
+        )
+    }));
+
+    for invocation in &invocations {
+        for hook in hooks.config.for_event(invocation.event) {
+            match &hook.action {
+                HookAction::Command(_) => match spawn_command(&hook.action, invocation) {
+                    Some(Ok(mut child)) => {
+                        let event = invocation.event.name();
+                        std::thread::spawn(move || match child.wait() {
+                            Ok(status) if !status.success() => {
+                                tracing::warn!("{event} hook exited with {status}")
+                            }
+                            Err(e) => tracing::warn!("{event} hook: {e}"),
+                            Ok(_) => {}
+                        });
+                    }
+                    Some(Err(e)) => {
+                        tracing::error!("{} hook failed to start: {e}", invocation.event.name())
+                    }
+                    None => tracing::warn!("{} hook has an empty command", invocation.event.name()),
+                },
+                #[cfg(feature = "script")]
+                HookAction::Script { path, args } => {
+                    use crispen_script::{ScriptContext, ScriptHost};
+
+                    let path = invocation.expand(path);
+                    let args = invocation.expand_all(args);
+                    let params = state.params.clone();
+                    std::thread::spawn(move || {
+                        let host = ScriptHost::new(ScriptContext::new(params, None));
+                        if let Err(e) = host.run_file(std::path::Path::new(&path), &args) {
+                            tracing::warn!("Hook script {path} failed: {e}");
+                        }
+                    });
+                }
+                #[cfg(not(feature = "script"))]
+                HookAction::Script { path, .. } => {
+                    tracing::warn!("Hook script {path}: built without the `script` feature");
+                }
+            }
+        }
+    }
+}
+
+/// Append this frame's grade changes to the audit log.
+///
+/// Looks and resets are recorded with exactly the fields they changed, and
+/// saved versions by path. Anything else that changes the grade — sliders,
+/// auto balance, scripts — is recorded as one entry once the grade has
+/// settled for [`AUDIT_SETTLE`]; edits still pending when a look, reset, or
+/// version arrives are recorded first, so each entry keeps its own cause.
+pub fn record_audit(
+    mut commands: MessageReader<ColorGradingCommand>,
+    mut updates: MessageReader<ParamsUpdatedEvent>,
+    mut exports: MessageReader<ExportCompletedEvent>,
+    state: Res<GradingState>,
+    mut audit: ResMut<AuditState>,
+) {
+    let events: Vec<AuditAction> = commands
+        .read()
+        .filter_map(|cmd| match cmd {
+            ColorGradingCommand::ApplyLook { path } if look_kind(path).is_some() => {
+                Some(AuditAction::LookApplied {
+                    path: path.clone(),
+                    changes: Vec::new(),
+                })
+            }
+            ColorGradingCommand::ResetGrade => Some(AuditAction::GradeReset {
+                changes: Vec::new(),
+            }),
+            _ => None,
+        })
+        .collect();
+    let versions: Vec<String> = exports
+        .read()
+        .filter(|e| e.kind == "version")
+        .map(|e| e.path.clone())
+        .collect();
+    let updated = updates.read().count() > 0;
+    let audit = &mut *audit;
+    let Some(log) = &audit.log else {
+        return;
+    };
+    let write = |action| {
+        if let Err(e) = log.record(action) {
+            tracing::error!("Audit log {}: {e}", log.path().display());
+        }
+    };
+    let flush = |committed: &mut GradingParams, to: &GradingParams| {
+        let changes = diff_grades(committed, to);
+        if !changes.is_empty() {
+            write(AuditAction::ParamsChanged { changes });
+            *committed = to.clone();
+        }
+    };
+
+    if !events.is_empty() {
+        // Commands were applied this frame; the previous frame's grade is
+        // where hand edits stopped.
+        flush(&mut audit.committed, &audit.seen);
+        let changes = diff_grades(&audit.committed, &state.params);
+        for mut event in events {
+            if let AuditAction::LookApplied { changes: c, .. }
+            | AuditAction::GradeReset { changes: c } = &mut event
+            {
+                // Several in one frame share the combined change.
+                c.clone_from(&changes);
+            }
+            write(event);
+        }
+        audit.committed = state.params.clone();
+        audit.changed_at = None;
+    }
+    if !versions.is_empty() {
+        flush(&mut audit.committed, &state.params);
+        audit.changed_at = None;
+        for path in versions {
+            write(AuditAction::VersionCreated { path });
+        }
+    }
+
+    let now = Instant::now();
+    if updated {
+        audit.changed_at = Some(now);
+        audit.seen = state.params.clone();
+    }
+    if audit
+        .changed_at
+        .is_some_and(|at| now.duration_since(at) >= AUDIT_SETTLE)
+    {
+        flush(&mut audit.committed, &state.params);
+        audit.changed_at = None;
+    }
+}
+
+/// Write the audit log as a JSON array on `ExportAuditLog`.
+pub fn export_audit_log(
+    mut commands: MessageReader<ColorGradingCommand>,
+    audit: Res<AuditState>,
+    mut exported: MessageWriter<ExportCompletedEvent>,
+) {
+    for cmd in commands.read() {
+        let ColorGradingCommand::ExportAuditLog { path } = cmd else {
+            continue;
+        };
+        let Some(log) = &audit.log else {
+            tracing::warn!("ExportAuditLog: auditing is off; set CRISPEN_AUDIT_LOG");
+            continue;
+        };
+        match log.export_json(Path::new(path)) {
+            Ok(count) => {
+                tracing::info!("ExportAuditLog: {count} entries to {path}");
+                exported.write(ExportCompletedEvent {
+                    path: path.clone(),
+                    kind: "audit",
+                });
+            }
+            Err(e) => tracing::error!("ExportAuditLog: failed to write {path}: {e}"),
+        }
+    }
+}
+
+/// Reload the input LUT when its file changes on disk.
+///
+/// Polls the file's modification time on [`LutWatchState::poll`] rather than
+/// subscribing to OS notifications; one `stat` per interval is negligible and
+/// behaves the same on network shares. The declared domain is kept, and a
+/// file that fails to read (e.g. caught mid-write) leaves the previous LUT
+/// applied until the next change.
+pub fn watch_input_lut(
+    time: Res<Time>,
+    mut watch: ResMut<LutWatchState>,
+    mut input_lut: ResMut<InputLutState>,
+    mut analyzed: MessageWriter<LutAnalyzedEvent>,
+    mut reloaded: MessageWriter<LutReloadedEvent>,
+) {
+    if !watch.poll.tick(time.delta()).just_finished() || !watch.enabled {
+        return;
+    }
+    let (Some(path), Some(domain)) = (input_lut.path.clone(), input_lut.domain) else {
+        return;
+    };
+    let modified = file_modified(&path);
+    if modified.is_none() || modified == input_lut.modified {
+        return;
+    }
+    input_lut.modified = modified;
+
+    let error = match Lut3D::load_cube(std::path::Path::new(&path)) {
+        Ok(lut) => {
+            tracing::info!("LUT changed on disk, reloaded {path}");
+            analyzed.write(LutAnalyzedEvent {
+                path: path.clone(),
+                slot: INPUT_LUT_SLOT.to_string(),
+                domain,
+                analysis: lut.analyze(),
+            });
+            input_lut.lut = Some(lut);
+            input_lut.dirty = true;
+            None
+        }
+        Err(e) => {
+            tracing::warn!("LUT changed on disk but failed to reload {path}: {e}");
+            Some(e.to_string())
+        }
+    };
+    reloaded.write(LutReloadedEvent {
+        path,
+        slot: INPUT_LUT_SLOT.to_string(),
+        error,
+    });
+}
+
+fn file_modified(path: &str) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Bake the input LUT with its domain conversions and upload it to the GPU.
+///
+/// Re-bakes when the LUT or its domain changes, or when the input/working
+/// color management changes underneath it, then marks the grade dirty so
+/// the next LUT bake picks it up.
+pub fn sync_input_lut(
+    mut input_lut: ResMut<InputLutState>,
+    mut state: ResMut<GradingState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+) {
+    let Some(mut gpu) = gpu else { return };
+    let config = &state.params.color_management;
+    let stale = input_lut.lut.is_some() && input_lut.baked_for.as_ref() != Some(config);
+    if !input_lut.dirty && !stale {
+        return;
+    }
+
+    let input_lut = &mut *input_lut;
+    input_lut.baked = match (&input_lut.lut, input_lut.domain) {
+        (Some(lut), Some(domain)) => Some(bake_input_lut(lut, domain, config, 65)),
+        _ => None,
+    };
+    input_lut.baked_for = input_lut.lut.as_ref().map(|_| config.clone());
+    input_lut.dirty = false;
+
+    match &input_lut.baked {
+        Some(baked) => gpu.pipeline.set_input_lut(Some(&baked.data), baked.size),
+        None => gpu.pipeline.set_input_lut(None, 0),
+    }
+    state.dirty = true;
+}
+
+/// Upload the defect map to the GPU repair pass when it changes, or when
+/// the pipeline has just been created, and mark the frame dirty so the
+/// repaired source is graded.
+pub fn sync_defect_map(
+    defects: Res<SourceDefects>,
+    mut state: ResMut<GradingState>,
+    gpu: Option<ResMut<GpuPipelineState>>,
+) {
+    let Some(mut gpu) = gpu else { return };
+    if !defects.is_changed() && !gpu.is_added() {
+        return;
+    }
+    gpu.pipeline.set_defect_map(&defects.0);
+    state.dirty = true;
+}
+
+/// Rasterize the scope mask polygon on the GPU when it changes.
 pub fn upload_scope_mask(
     mut mask_data: ResMut<ScopeMaskData>,
     gpu: Option<ResMut<GpuPipelineState>>,
@@ -313,8 +1612,12 @@ pub fn upload_scope_mask(
         return;
     };
 
-    if mask_data.active && !mask_data.mask.is_empty() {
-        gpu.pipeline.set_scope_mask(&mask_data.mask);
+    if mask_data.active && mask_data.polygon.len() >= 3 {
+        gpu.pipeline.set_scope_mask_polygon(
+            &mask_data.vertices(),
+            mask_data.width,
+            mask_data.height,
+        );
     } else {
         gpu.pipeline.clear_scope_mask();
     }
@@ -329,6 +1632,12 @@ fn apply_scope_results(scope_state: &mut ScopeState, results: ScopeResults) {
         cie,
     } = results;
 
+    let history = &mut scope_state.history;
+    let histogram = history.histogram.push(histogram);
+    let waveform = history.waveform.push(waveform);
+    let vectorscope = history.vectorscope.push(vectorscope);
+    let cie = history.cie.push(cie);
+
     scope_state.histogram = Some(histogram);
     scope_state.waveform = Some(waveform);
     scope_state.vectorscope = Some(vectorscope);
@@ -0,0 +1,137 @@
+/* Crispen C API. Generated by cbindgen from src/lib.rs — do not edit. */
+
+#ifndef CRISPEN_H
+#define CRISPEN_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a fallible call.
+typedef enum CrispenStatus {
+  // The call succeeded.
+  CRISPEN_STATUS_OK = 0,
+  // A required pointer was null.
+  CRISPEN_STATUS_NULL_POINTER = 1,
+  // An argument was out of range, such as a buffer that is too small.
+  CRISPEN_STATUS_INVALID_ARGUMENT = 2,
+  // Grade JSON could not be parsed.
+  CRISPEN_STATUS_PARSE_ERROR = 3,
+} CrispenStatus;
+
+// A grade. Opaque to C; free with [`crispen_grade_free`].
+typedef struct CrispenGrade CrispenGrade;
+
+// An ASC CDL, as converted by [`crispen_grade_to_cdl`].
+typedef struct CrispenCdl {
+  float slope[3];
+  float offset[3];
+  float power[3];
+  float saturation;
+} CrispenCdl;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The message of the last failure on this thread, or an empty string.
+//
+// The pointer stays valid until the next failing call on the same thread.
+const char *crispen_last_error(void);
+
+// The library version, as a static NUL-terminated string.
+const char *crispen_version(void);
+
+// A new identity grade.
+struct CrispenGrade *crispen_grade_new(void);
+
+// Parse a grade from `GradingParams` JSON. Returns null on failure.
+//
+// # Safety
+//
+// `json` must be null or a valid NUL-terminated string.
+struct CrispenGrade *crispen_grade_from_json(const char *json);
+
+// The grade as `GradingParams` JSON. Free with [`crispen_string_free`].
+//
+// # Safety
+//
+// `grade` must be null or a live grade from this library.
+char *crispen_grade_to_json(const struct CrispenGrade *grade);
+
+// Free a string returned by this library. Null is ignored.
+//
+// # Safety
+//
+// `text` must be null or a string from [`crispen_grade_to_json`] that has
+// not been freed.
+void crispen_string_free(char *text);
+
+// Free a grade. Null is ignored.
+//
+// # Safety
+//
+// `grade` must be null or a grade from this library that has not been
+// freed.
+void crispen_grade_free(struct CrispenGrade *grade);
+
+// Grade `pixel_count` interleaved RGB pixels through to the grade's output
+// space, exactly as `evaluate_transform` does.
+//
+// # Safety
+//
+// `grade` must be null or live. `rgb_in` must be readable and `rgb_out`
+// writable for `pixel_count * 3` floats; they may be the same buffer.
+enum CrispenStatus crispen_evaluate(const struct CrispenGrade *grade,
+                                    const float *rgb_in,
+                                    float *rgb_out,
+                                    size_t pixel_count);
+
+// [`crispen_evaluate`], stopping in working-space linear before the output
+// transform.
+//
+// # Safety
+//
+// As for [`crispen_evaluate`].
+enum CrispenStatus crispen_evaluate_working(const struct CrispenGrade *grade,
+                                            const float *rgb_in,
+                                            float *rgb_out,
+                                            size_t pixel_count);
+
+// Bake the grade into a `size³` 3D LUT.
+//
+// Writes `size³` RGBA entries, red varying fastest, into `rgba_out`,
+// which must hold at least `value_count` floats, `size³ * 4` of them used.
+//
+// # Safety
+//
+// `grade` must be null or live, and `rgba_out` null or writable for
+// `value_count` floats.
+enum CrispenStatus crispen_bake_lut(const struct CrispenGrade *grade,
+                                    uint32_t size,
+                                    float *rgba_out,
+                                    size_t value_count);
+
+// The ASC CDL equivalent of the grade's wheels, bars, and saturation.
+//
+// # Safety
+//
+// `grade` must be null or live, and `cdl_out` null or writable.
+enum CrispenStatus crispen_grade_to_cdl(const struct CrispenGrade *grade,
+                                        struct CrispenCdl *cdl_out);
+
+// Replace the grade's wheels, bars, and saturation with a CDL. Other
+// settings are kept.
+//
+// # Safety
+//
+// `grade` must be null or live, and `cdl` null or readable.
+enum CrispenStatus crispen_grade_set_cdl(struct CrispenGrade *grade, const struct CrispenCdl *cdl);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CRISPEN_H */
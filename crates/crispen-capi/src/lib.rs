@@ -0,0 +1,424 @@
+//! Crispen C API — the core transform behind a stable C ABI.
+//!
+//! Exposes [`evaluate_transform`](crispen_core::evaluate_transform), LUT
+//! baking, and ASC CDL conversion to C, C++, and anything with a C FFI
+//! (Python `ctypes` / `cffi`, Nuke and Houdini plugins). The declarations
+//! are in `include/crispen.h`, generated with `cbindgen` from this file.
+//!
+//! A grade is an opaque [`CrispenGrade`] created from `GradingParams` JSON
+//! as saved by the app. Functions that can fail return a [`CrispenStatus`]
+//! (or null) and leave a message for [`crispen_last_error`].
+
+#![allow(unsafe_code)]
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+
+use crispen_core::grading::cdl::AscCdl;
+use crispen_core::{GradingParams, Lut3D, OutputEncoding, evaluate_transform_batch};
+
+/// Largest LUT [`crispen_bake_lut`] bakes, matching the app's maximum.
+const MAX_LUT_SIZE: u32 = 129;
+
+/// Result of a fallible call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrispenStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// An argument was out of range, such as a buffer that is too small.
+    InvalidArgument = 2,
+    /// Grade JSON could not be parsed.
+    ParseError = 3,
+}
+
+/// An ASC CDL, as converted by [`crispen_grade_to_cdl`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrispenCdl {
+    pub slope: [f32; 3],
+    pub offset: [f32; 3],
+    pub power: [f32; 3],
+    pub saturation: f32,
+}
+
+impl From<AscCdl> for CrispenCdl {
+    fn from(cdl: AscCdl) -> Self {
+        Self {
+            slope: cdl.slope,
+            offset: cdl.offset,
+            power: cdl.power,
+            saturation: cdl.saturation,
+        }
+    }
+}
+
+impl From<CrispenCdl> for AscCdl {
+    fn from(cdl: CrispenCdl) -> Self {
+        Self {
+            slope: cdl.slope,
+            offset: cdl.offset,
+            power: cdl.power,
+            saturation: cdl.saturation,
+        }
+    }
+}
+
+/// A grade. Opaque to C; free with [`crispen_grade_free`].
+pub struct CrispenGrade {
+    params: GradingParams,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn fail(status: CrispenStatus, message: impl Into<String>) -> CrispenStatus {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    status
+}
+
+/// The message of the last failure on this thread, or an empty string.
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn crispen_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+/// The library version, as a static NUL-terminated string.
+#[unsafe(no_mangle)]
+pub extern "C" fn crispen_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// A new identity grade.
+#[unsafe(no_mangle)]
+pub extern "C" fn crispen_grade_new() -> *mut CrispenGrade {
+    Box::into_raw(Box::new(CrispenGrade {
+        params: GradingParams::default(),
+    }))
+}
+
+/// Parse a grade from `GradingParams` JSON. Returns null on failure.
+///
+/// # Safety
+///
+/// `json` must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_grade_from_json(json: *const c_char) -> *mut CrispenGrade {
+    if json.is_null() {
+        fail(CrispenStatus::NullPointer, "json is null");
+        return std::ptr::null_mut();
+    }
+    // SAFETY: checked non-null; the caller guarantees NUL termination.
+    let json = unsafe { CStr::from_ptr(json) };
+    let parsed = json
+        .to_str()
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(json).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(params) => Box::into_raw(Box::new(CrispenGrade { params })),
+        Err(e) => {
+            fail(
+                CrispenStatus::ParseError,
+                format!("invalid grade JSON: {e}"),
+            );
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// The grade as `GradingParams` JSON. Free with [`crispen_string_free`].
+///
+/// # Safety
+///
+/// `grade` must be null or a live grade from this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_grade_to_json(grade: *const CrispenGrade) -> *mut c_char {
+    // SAFETY: the caller guarantees `grade` is null or live.
+    let Some(grade) = (unsafe { grade.as_ref() }) else {
+        fail(CrispenStatus::NullPointer, "grade is null");
+        return std::ptr::null_mut();
+    };
+    let json = serde_json::to_string(&grade.params).unwrap_or_default();
+    CString::new(json).unwrap_or_default().into_raw()
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `text` must be null or a string from [`crispen_grade_to_json`] that has
+/// not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the pointer came from `CString::into_raw` in this library.
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Free a grade. Null is ignored.
+///
+/// # Safety
+///
+/// `grade` must be null or a grade from this library that has not been
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_grade_free(grade: *mut CrispenGrade) {
+    if !grade.is_null() {
+        // SAFETY: the pointer came from `Box::into_raw` in this library.
+        drop(unsafe { Box::from_raw(grade) });
+    }
+}
+
+/// Shared body of the evaluate calls.
+///
+/// # Safety
+///
+/// As for [`crispen_evaluate`].
+unsafe fn evaluate(
+    grade: *const CrispenGrade,
+    rgb_in: *const f32,
+    rgb_out: *mut f32,
+    pixel_count: usize,
+    encoding: OutputEncoding,
+) -> CrispenStatus {
+    // SAFETY: the caller guarantees `grade` is null or live.
+    let Some(grade) = (unsafe { grade.as_ref() }) else {
+        return fail(CrispenStatus::NullPointer, "grade is null");
+    };
+    if rgb_in.is_null() || rgb_out.is_null() {
+        return fail(CrispenStatus::NullPointer, "pixel buffer is null");
+    }
+    let Some(len) = pixel_count.checked_mul(3) else {
+        return fail(CrispenStatus::InvalidArgument, "pixel_count overflows");
+    };
+    // SAFETY: the caller guarantees `len` readable floats. The input slice
+    // is dropped before the output is written, so the two may alias.
+    let graded = evaluate_transform_batch(
+        unsafe { std::slice::from_raw_parts(rgb_in, len) },
+        &grade.params,
+        encoding,
+    );
+    // SAFETY: the caller guarantees `len` writable floats.
+    unsafe { std::slice::from_raw_parts_mut(rgb_out, len) }.copy_from_slice(&graded);
+    CrispenStatus::Ok
+}
+
+/// Grade `pixel_count` interleaved RGB pixels through to the grade's output
+/// space, exactly as `evaluate_transform` does.
+///
+/// # Safety
+///
+/// `grade` must be null or live. `rgb_in` must be readable and `rgb_out`
+/// writable for `pixel_count * 3` floats; they may be the same buffer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_evaluate(
+    grade: *const CrispenGrade,
+    rgb_in: *const f32,
+    rgb_out: *mut f32,
+    pixel_count: usize,
+) -> CrispenStatus {
+    // SAFETY: forwarded caller guarantees.
+    unsafe { evaluate(grade, rgb_in, rgb_out, pixel_count, OutputEncoding::Display) }
+}
+
+/// [`crispen_evaluate`], stopping in working-space linear before the output
+/// transform.
+///
+/// # Safety
+///
+/// As for [`crispen_evaluate`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_evaluate_working(
+    grade: *const CrispenGrade,
+    rgb_in: *const f32,
+    rgb_out: *mut f32,
+    pixel_count: usize,
+) -> CrispenStatus {
+    // SAFETY: forwarded caller guarantees.
+    unsafe { evaluate(grade, rgb_in, rgb_out, pixel_count, OutputEncoding::Working) }
+}
+
+/// Bake the grade into a `size³` 3D LUT.
+///
+/// Writes `size³` RGBA entries, red varying fastest, into `rgba_out`,
+/// which must hold at least `value_count` floats, `size³ * 4` of them used.
+///
+/// # Safety
+///
+/// `grade` must be null or live, and `rgba_out` null or writable for
+/// `value_count` floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_bake_lut(
+    grade: *const CrispenGrade,
+    size: u32,
+    rgba_out: *mut f32,
+    value_count: usize,
+) -> CrispenStatus {
+    // SAFETY: the caller guarantees `grade` is null or live.
+    let Some(grade) = (unsafe { grade.as_ref() }) else {
+        return fail(CrispenStatus::NullPointer, "grade is null");
+    };
+    if rgba_out.is_null() {
+        return fail(CrispenStatus::NullPointer, "LUT buffer is null");
+    }
+    if !(2..=MAX_LUT_SIZE).contains(&size) {
+        return fail(
+            CrispenStatus::InvalidArgument,
+            format!("LUT size {size} is outside 2..={MAX_LUT_SIZE}"),
+        );
+    }
+    let needed = (size as usize).pow(3) * 4;
+    if value_count < needed {
+        return fail(
+            CrispenStatus::InvalidArgument,
+            format!("a {size}³ LUT needs {needed} floats, got {value_count}"),
+        );
+    }
+    let mut lut = Lut3D::new(size);
+    lut.bake(&grade.params);
+    // SAFETY: the caller guarantees `value_count >= needed` writable floats.
+    let out = unsafe { std::slice::from_raw_parts_mut(rgba_out, needed) };
+    out.copy_from_slice(lut.data.as_flattened());
+    CrispenStatus::Ok
+}
+
+/// The ASC CDL equivalent of the grade's wheels, bars, and saturation.
+///
+/// # Safety
+///
+/// `grade` must be null or live, and `cdl_out` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_grade_to_cdl(
+    grade: *const CrispenGrade,
+    cdl_out: *mut CrispenCdl,
+) -> CrispenStatus {
+    // SAFETY: the caller guarantees both pointers are null or valid.
+    let (Some(grade), Some(cdl_out)) = (unsafe { grade.as_ref() }, unsafe { cdl_out.as_mut() })
+    else {
+        return fail(CrispenStatus::NullPointer, "grade or cdl_out is null");
+    };
+    *cdl_out = AscCdl::from_params(&grade.params).into();
+    CrispenStatus::Ok
+}
+
+/// Replace the grade's wheels, bars, and saturation with a CDL. Other
+/// settings are kept.
+///
+/// # Safety
+///
+/// `grade` must be null or live, and `cdl` null or readable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crispen_grade_set_cdl(
+    grade: *mut CrispenGrade,
+    cdl: *const CrispenCdl,
+) -> CrispenStatus {
+    // SAFETY: the caller guarantees both pointers are null or valid.
+    let (Some(grade), Some(cdl)) = (unsafe { grade.as_mut() }, unsafe { cdl.as_ref() }) else {
+        return fail(CrispenStatus::NullPointer, "grade or cdl is null");
+    };
+    AscCdl::from(*cdl).apply_to_params(&mut grade.params);
+    CrispenStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crispen_core::evaluate_transform;
+
+    #[test]
+    fn test_evaluate_matches_core() {
+        let grade = crispen_grade_new();
+        // SAFETY: `grade` is live and the buffers hold two pixels.
+        unsafe {
+            (*grade).params.saturation = 1.3;
+            let mut rgb = [0.2, 0.4, 0.6, 0.9, 0.5, 0.1];
+            let status = crispen_evaluate(grade, rgb.as_ptr(), rgb.as_mut_ptr(), 2);
+            assert_eq!(status, CrispenStatus::Ok);
+            let expected = evaluate_transform([0.9, 0.5, 0.1], &(*grade).params);
+            assert_eq!(&rgb[3..], &expected);
+            crispen_grade_free(grade);
+        }
+    }
+
+    #[test]
+    fn test_bake_lut_rejects_short_buffer() {
+        let grade = crispen_grade_new();
+        let mut lut = vec![0.0; 17 * 17 * 17 * 4 - 1];
+        // SAFETY: `grade` is live and `lut` holds `lut.len()` floats.
+        unsafe {
+            let status = crispen_bake_lut(grade, 17, lut.as_mut_ptr(), lut.len());
+            assert_eq!(status, CrispenStatus::InvalidArgument);
+            let message = CStr::from_ptr(crispen_last_error()).to_string_lossy();
+            assert!(message.contains("needs 19652 floats"), "{message}");
+            crispen_grade_free(grade);
+        }
+    }
+
+    #[test]
+    fn test_json_and_cdl_round_trip() {
+        let source = CrispenCdl {
+            slope: [1.2, 1.0, 0.8],
+            offset: [0.01, 0.0, -0.02],
+            power: [1.0, 1.1, 0.9],
+            saturation: 0.9,
+        };
+        // SAFETY: all pointers come from this library or live locals.
+        unsafe {
+            let grade = crispen_grade_new();
+            assert_eq!(crispen_grade_set_cdl(grade, &source), CrispenStatus::Ok);
+
+            let text = crispen_grade_to_json(grade);
+            let copy = crispen_grade_from_json(text);
+            assert!(!copy.is_null());
+            assert_eq!((*copy).params, (*grade).params);
+            crispen_string_free(text);
+
+            let mut cdl = CrispenCdl::from(AscCdl::default());
+            assert_eq!(crispen_grade_to_cdl(copy, &mut cdl), CrispenStatus::Ok);
+            assert_eq!(cdl.slope, source.slope);
+            assert_eq!(cdl.offset, source.offset);
+            assert_eq!(cdl.saturation, source.saturation);
+            for c in 0..3 {
+                assert!((cdl.power[c] - source.power[c]).abs() < 1e-6);
+            }
+            crispen_grade_free(copy);
+            crispen_grade_free(grade);
+
+            let bad = CString::new("{").unwrap();
+            assert!(crispen_grade_from_json(bad.as_ptr()).is_null());
+            let message = CStr::from_ptr(crispen_last_error()).to_string_lossy();
+            assert!(message.starts_with("invalid grade JSON"), "{message}");
+        }
+    }
+
+    /// The checked-in header must be what cbindgen generates from this
+    /// file. Run with `CRISPEN_WRITE_HEADER=1` to regenerate it.
+    #[test]
+    fn test_header_matches_cbindgen_output() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+        let bindings = cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(dir.join("src/lib.rs"))
+            .generate()
+            .unwrap();
+        let mut generated = Vec::new();
+        bindings.write(&mut generated);
+        let header_path = dir.join("include/crispen.h");
+        if std::env::var_os("CRISPEN_WRITE_HEADER").is_some() {
+            std::fs::write(&header_path, &generated).unwrap();
+        }
+        let header = std::fs::read(&header_path).unwrap();
+        assert!(
+            header == generated,
+            "include/crispen.h is out of date; rerun this test with CRISPEN_WRITE_HEADER=1"
+        );
+    }
+}
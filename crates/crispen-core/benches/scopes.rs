@@ -0,0 +1,32 @@
+//! CPU scope renderers on a 1080p frame.
+//!
+//! `cargo bench -p crispen-core --bench scopes`. The GPU scopes are
+//! covered by `crispen-gpu`'s `pipeline` bench.
+
+use crispen_core::ColorSpaceId;
+use crispen_core::image::BitDepth;
+use crispen_core::patterns::{TestPattern, generate};
+use crispen_core::scopes::{cie, histogram, parade, vectorscope, waveform};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+
+fn scopes(c: &mut Criterion) {
+    let image = generate(
+        TestPattern::Macbeth,
+        1920,
+        1080,
+        BitDepth::F32,
+        ColorSpaceId::Srgb,
+    );
+    let mut group = c.benchmark_group("scopes_1080p");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(image.pixels.len() as u64));
+    group.bench_function("histogram", |b| b.iter(|| histogram::compute(&image)));
+    group.bench_function("waveform", |b| b.iter(|| waveform::compute(&image)));
+    group.bench_function("parade", |b| b.iter(|| parade::compute(&image)));
+    group.bench_function("vectorscope", |b| b.iter(|| vectorscope::compute(&image)));
+    group.bench_function("cie", |b| b.iter(|| cie::compute(&image)));
+    group.finish();
+}
+
+criterion_group!(benches, scopes);
+criterion_main!(benches);
@@ -0,0 +1,114 @@
+//! CPU transform hot paths: curve baking, per-pixel evaluation, LUT
+//! baking, and LUT sampling.
+//!
+//! `cargo bench -p crispen-core --bench transform`. See the crate README
+//! for saving and comparing baselines.
+
+use std::hint::black_box;
+
+use crispen_core::grading::curves::{CurveInterpolation, bake_curve_to_1d_lut, evaluate_curve};
+use crispen_core::{
+    GradingParams, Lut3D, OutputEncoding, evaluate_transform, evaluate_transform_batch,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+/// A grade that touches every stage of the chain.
+fn busy_params() -> GradingParams {
+    GradingParams {
+        temperature: 12.0,
+        tint: -4.0,
+        lift: [0.02, 0.0, -0.01, 0.0],
+        gamma_wheel: [1.05, 1.0, 0.95, 1.0],
+        gain: [1.1, 1.0, 0.9, 1.0],
+        contrast: 1.2,
+        midtone_detail: 0.2,
+        shadows: 0.1,
+        highlights: -0.2,
+        saturation: 1.15,
+        hue: 8.0,
+        hue_vs_sat: vec![[0.0, 1.0], [0.3, 1.4], [0.6, 0.8], [1.0, 1.0]],
+        lum_vs_sat: vec![[0.0, 0.6], [0.5, 1.0], [1.0, 0.7]],
+        ..GradingParams::default()
+    }
+}
+
+/// `count` interleaved RGB pixels spread over the unit cube.
+fn pixels(count: usize) -> Vec<f32> {
+    (0..count * 3)
+        .map(|i| (i as f32 * 0.618_034).fract())
+        .collect()
+}
+
+fn curves(c: &mut Criterion) {
+    let points = [
+        [0.0, 0.0],
+        [0.25, 0.2],
+        [0.5, 0.55],
+        [0.75, 0.8],
+        [1.0, 1.0],
+    ];
+    c.bench_function("bake_curve_to_1d_lut/1024", |b| {
+        b.iter(|| bake_curve_to_1d_lut(black_box(&points), 1024))
+    });
+    let mut group = c.benchmark_group("evaluate_curve");
+    for interpolation in [CurveInterpolation::Linear, CurveInterpolation::Smooth] {
+        group.bench_function(format!("{interpolation:?}"), |b| {
+            b.iter(|| {
+                (0..256)
+                    .map(|i| evaluate_curve(&points, i as f32 / 255.0, interpolation))
+                    .sum::<f32>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn evaluate(c: &mut Criterion) {
+    let params = busy_params();
+    c.bench_function("evaluate_transform/pixel", |b| {
+        b.iter(|| evaluate_transform(black_box([0.4, 0.3, 0.2]), &params))
+    });
+
+    let mut group = c.benchmark_group("evaluate_transform_batch");
+    for count in [4096, 1 << 18] {
+        let rgb = pixels(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &rgb, |b, rgb| {
+            b.iter(|| evaluate_transform_batch(rgb, &params, OutputEncoding::Display))
+        });
+    }
+    group.finish();
+}
+
+fn lut(c: &mut Criterion) {
+    let params = busy_params();
+    let mut group = c.benchmark_group("lut_bake");
+    group.sample_size(10);
+    for size in [17, 33] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter(|| {
+                let mut lut = Lut3D::new(size);
+                lut.bake(&params);
+                lut
+            })
+        });
+    }
+    group.finish();
+
+    let mut baked = Lut3D::new(33);
+    baked.bake(&params);
+    let rgb = pixels(4096);
+    let mut group = c.benchmark_group("lut_apply");
+    group.throughput(Throughput::Elements(4096));
+    group.bench_function("33", |b| {
+        b.iter(|| {
+            rgb.chunks_exact(3)
+                .map(|p| baked.apply([p[0], p[1], p[2]])[1])
+                .sum::<f32>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, curves, evaluate, lut);
+criterion_main!(benches);
@@ -8,10 +8,13 @@
 //! ```text
 //! Input (encoded) → linearize → matrix to working → [grading] → matrix to output → encode
 //! ```
+//!
+//! [`NegativePolicy`] decides what the decode, matrices, and encode do with
+//! negative values; `bake_lut.wgsl` applies the same policy on the GPU.
 
 use crate::color_management::color_space::get_conversion_matrix;
-use crate::color_management::transfer::get_transfer;
-use crate::transform::params::ColorManagementConfig;
+use crate::color_management::transfer::{TransferFunction, get_transfer};
+use crate::transform::params::{ColorManagementConfig, NegativePolicy};
 
 /// Apply the input device transform based on the color management configuration.
 ///
@@ -25,20 +28,18 @@ pub fn apply_input_transform(rgb: [f32; 3], config: &ColorManagementConfig) -> [
         return rgb;
     }
 
+    let policy = config.negative_policy;
+
     // Step 1: Linearize the input if it has a non-linear transfer function
     let linear = if let Some(tf) = get_transfer(config.input_space) {
-        [
-            tf.to_linear(rgb[0]),
-            tf.to_linear(rgb[1]),
-            tf.to_linear(rgb[2]),
-        ]
+        rgb.map(|v| to_linear(tf.as_ref(), v, policy))
     } else {
-        rgb
+        clamp_negatives(rgb, policy)
     };
 
     // Step 2: Matrix convert from source gamut to working gamut
     let matrix = get_conversion_matrix(config.input_space, config.working_space);
-    matrix.apply(linear)
+    clamp_negatives(matrix.apply(linear), policy)
 }
 
 /// Apply the output device transform based on the color management configuration.
@@ -53,22 +54,55 @@ pub fn apply_output_transform(rgb: [f32; 3], config: &ColorManagementConfig) ->
         return rgb;
     }
 
+    let policy = config.negative_policy;
+
     // Step 1: Matrix convert from working gamut to output gamut
     let matrix = get_conversion_matrix(config.working_space, config.output_space);
-    let converted = matrix.apply(rgb);
+    let converted = clamp_negatives(matrix.apply(rgb), policy);
 
     // Step 2: Apply output transfer function if non-linear
     if let Some(tf) = get_transfer(config.output_space) {
-        [
-            tf.to_encoded(converted[0]),
-            tf.to_encoded(converted[1]),
-            tf.to_encoded(converted[2]),
-        ]
+        converted.map(|v| to_encoded(tf.as_ref(), v, policy))
     } else {
         converted
     }
 }
 
+/// Zero negative linear values under [`NegativePolicy::Clamp`].
+fn clamp_negatives(rgb: [f32; 3], policy: NegativePolicy) -> [f32; 3] {
+    match policy {
+        NegativePolicy::Clamp => rgb.map(|v| v.max(0.0)),
+        NegativePolicy::Allow | NegativePolicy::Mirror => rgb,
+    }
+}
+
+/// Decode one channel under `policy`. Mirroring reflects code values
+/// below the code of linear black, so the curve is point-symmetric there.
+fn to_linear(tf: &dyn TransferFunction, encoded: f32, policy: NegativePolicy) -> f32 {
+    match policy {
+        NegativePolicy::Allow => tf.to_linear(encoded),
+        NegativePolicy::Clamp => tf.to_linear(encoded).max(0.0),
+        NegativePolicy::Mirror => {
+            let black = tf.to_encoded(0.0);
+            if encoded < black {
+                -tf.to_linear(2.0 * black - encoded)
+            } else {
+                tf.to_linear(encoded)
+            }
+        }
+    }
+}
+
+/// Encode one channel under `policy`, the inverse of [`to_linear`].
+fn to_encoded(tf: &dyn TransferFunction, linear: f32, policy: NegativePolicy) -> f32 {
+    match policy {
+        NegativePolicy::Allow => tf.to_encoded(linear),
+        NegativePolicy::Clamp => tf.to_encoded(linear.max(0.0)),
+        NegativePolicy::Mirror if linear < 0.0 => 2.0 * tf.to_encoded(0.0) - tf.to_encoded(-linear),
+        NegativePolicy::Mirror => tf.to_encoded(linear),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +117,7 @@ mod tests {
             working_space: ColorSpaceId::AcesCg,
             output_space: ColorSpaceId::Srgb,
             display_oetf: DisplayOetf::Srgb,
+            ..Default::default()
         };
         let rgb = [0.5, 0.3, 0.7];
         assert_eq!(apply_input_transform(rgb, &config), rgb);
@@ -95,6 +130,7 @@ mod tests {
             working_space: ColorSpaceId::AcesCg,
             output_space: ColorSpaceId::AcesCg,
             display_oetf: DisplayOetf::Srgb,
+            ..Default::default()
         };
         let rgb = [0.5, 0.3, 0.7];
         assert_eq!(apply_output_transform(rgb, &config), rgb);
@@ -107,6 +143,7 @@ mod tests {
             working_space: ColorSpaceId::AcesCg,
             output_space: ColorSpaceId::LinearSrgb,
             display_oetf: DisplayOetf::Srgb,
+            ..Default::default()
         };
         let rgb = [0.5, 0.3, 0.7];
         let working = apply_input_transform(rgb, &config);
@@ -120,4 +157,75 @@ mod tests {
             );
         }
     }
+
+    fn with_policy(
+        input_space: ColorSpaceId,
+        output_space: ColorSpaceId,
+        policy: NegativePolicy,
+    ) -> ColorManagementConfig {
+        ColorManagementConfig {
+            input_space,
+            working_space: ColorSpaceId::AcesCg,
+            output_space,
+            negative_policy: policy,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clamp_zeroes_out_of_gamut_negatives() {
+        // Saturated Rec. 2020 green falls outside sRGB.
+        let green = [0.0, 0.8, 0.0];
+        let allow = with_policy(
+            ColorSpaceId::Rec2020,
+            ColorSpaceId::LinearSrgb,
+            NegativePolicy::Allow,
+        );
+        let clamp = with_policy(
+            ColorSpaceId::Rec2020,
+            ColorSpaceId::LinearSrgb,
+            NegativePolicy::Clamp,
+        );
+        let loose = apply_output_transform(apply_input_transform(green, &allow), &allow);
+        assert!(loose[0] < 0.0 && loose[2] < 0.0, "{loose:?}");
+        let clamped = apply_output_transform(apply_input_transform(green, &clamp), &clamp);
+        assert!(clamped.iter().all(|&v| v >= 0.0), "{clamped:?}");
+        assert!((clamped[1] - loose[1]).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_mirror_is_point_symmetric_around_black() {
+        for space in [
+            ColorSpaceId::Srgb,
+            ColorSpaceId::ArriLogC3,
+            ColorSpaceId::SLog3,
+        ] {
+            let config = with_policy(space, space, NegativePolicy::Mirror);
+            let tf = get_transfer(space).unwrap();
+            let black = tf.to_encoded(0.0);
+            for v in [0.01, 0.18, 0.9] {
+                let up = to_encoded(tf.as_ref(), v, config.negative_policy);
+                let down = to_encoded(tf.as_ref(), -v, config.negative_policy);
+                assert!((up - black + down - black).abs() < EPSILON, "{space:?} {v}");
+                let back = to_linear(tf.as_ref(), down, config.negative_policy);
+                assert!((back + v).abs() < 1e-3, "{space:?} {v} → {back}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_policies_agree_on_positive_values() {
+        let rgb = [0.4, 0.3, 0.2];
+        let allow = with_policy(
+            ColorSpaceId::ArriLogC3,
+            ColorSpaceId::Srgb,
+            NegativePolicy::Allow,
+        );
+        let expected = apply_output_transform(apply_input_transform(rgb, &allow), &allow);
+        for policy in [NegativePolicy::Clamp, NegativePolicy::Mirror] {
+            let config = with_policy(ColorSpaceId::ArriLogC3, ColorSpaceId::Srgb, policy);
+            let got = apply_output_transform(apply_input_transform(rgb, &config), &config);
+            assert_eq!(got, expected, "{policy:?}");
+        }
+    }
 }
@@ -50,6 +50,16 @@ impl ColorMatrix {
             (self.0[2][0] * r + self.0[2][1] * g + self.0[2][2] * b) as f32,
         ]
     }
+
+    /// The inverse matrix.
+    pub fn inverse(&self) -> Self {
+        Self(mat3_inv(&self.0))
+    }
+
+    /// One matrix applying `first`, then `self`.
+    pub fn after(&self, first: &Self) -> Self {
+        Self(mat3_mul(&self.0, &first.0))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -173,9 +183,9 @@ const S_GAMUT3_CINE: CieChromaticity = CieChromaticity {
 };
 
 const RED_WIDE_GAMUT: CieChromaticity = CieChromaticity {
-    r: [0.78010, 0.30490],
-    g: [0.12120, 1.49310],
-    b: [0.09530, -0.08490],
+    r: [0.780_308, 0.304_253],
+    g: [0.121_595, 1.493_994],
+    b: [0.095_612, -0.084_589],
     w: D65_WHITE,
 };
 
@@ -367,6 +377,47 @@ pub fn get_conversion_matrix(from: ColorSpaceId, to: ColorSpaceId) -> ColorMatri
     ColorMatrix(mat3_mul(&m_from_xyz, &m_to_xyz))
 }
 
+/// Get the matrix converting a color space's linear RGB to CIE XYZ D65.
+///
+/// The Y row is normalized so that RGB white maps to Y = 1.
+pub fn rgb_to_xyz_matrix(space: ColorSpaceId) -> ColorMatrix {
+    ColorMatrix(to_xyz_d65(gamut_of(space)))
+}
+
+/// Get the matrix converting CIE XYZ D65 to a color space's linear RGB.
+pub fn xyz_to_rgb_matrix(space: ColorSpaceId) -> ColorMatrix {
+    ColorMatrix(from_xyz_d65(gamut_of(space)))
+}
+
+/// Bradford adaptation from the D65 hub white to the ICC D50 PCS white.
+pub fn d65_to_d50_matrix() -> ColorMatrix {
+    ColorMatrix(bradford_adaptation([0.3127, 0.3290], [0.3457, 0.3585]))
+}
+
+/// Normalized Primary Matrix for any primaries: linear RGB to CIE XYZ at
+/// the primaries' own white point, with white at Y = 1.
+pub fn npm_from_primaries(primaries: &CieChromaticity) -> ColorMatrix {
+    ColorMatrix(compute_npm(primaries))
+}
+
+/// Bradford chromatic adaptation from white point `src` to `dst`, both
+/// CIE 1931 xy.
+pub fn bradford_matrix(src: [f64; 2], dst: [f64; 2]) -> ColorMatrix {
+    ColorMatrix(bradford_adaptation(src, dst))
+}
+
+/// Matrix converting linear RGB with primaries `from` to linear RGB with
+/// primaries `to`, with Bradford adaptation when the white points differ.
+/// The same derivation as [`get_conversion_matrix`], for primaries that
+/// have no [`ColorSpaceId`].
+pub fn primaries_conversion_matrix(from: &CieChromaticity, to: &CieChromaticity) -> ColorMatrix {
+    let mut m = compute_npm(from);
+    if from.w != to.w {
+        m = mat3_mul(&bradford_adaptation(from.w, to.w), &m);
+    }
+    ColorMatrix(mat3_mul(&mat3_inv(&compute_npm(to)), &m))
+}
+
 /// Get the CIE 1931 xy chromaticity coordinates for a color space.
 ///
 /// Returns the R, G, B primary coordinates and white point used by the
@@ -478,4 +529,26 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_primaries_matrix_matches_named_spaces() {
+        for (from, to) in [
+            (ColorSpaceId::LinearSrgb, ColorSpaceId::AcesCg),
+            (ColorSpaceId::Aces2065_1, ColorSpaceId::Rec2020),
+            (ColorSpaceId::DciP3, ColorSpaceId::LinearSrgb),
+        ] {
+            let named = get_conversion_matrix(from, to);
+            let derived = primaries_conversion_matrix(chromaticity(from), chromaticity(to));
+            let rgb = [0.2, 0.5, 0.8];
+            assert_rgb_close(named.apply(rgb), derived.apply(rgb), EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_npm_maps_white_to_white_point() {
+        let npm = npm_from_primaries(chromaticity(ColorSpaceId::Rec2020));
+        assert_rgb_close(npm.apply([1.0, 1.0, 1.0]), [0.95046, 1.0, 1.08906], 1e-4);
+        let back = npm.inverse().after(&npm).apply([0.3, 0.6, 0.9]);
+        assert_rgb_close(back, [0.3, 0.6, 0.9], EPSILON);
+    }
 }
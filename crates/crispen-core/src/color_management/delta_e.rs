@@ -0,0 +1,296 @@
+//! CIELAB conversion and color difference (ΔE) metrics.
+//!
+//! Used wherever two colors need to be compared perceptually: display
+//! probe verification, chart matching, and difference overlays.
+//!
+//! # Reference
+//! - CIE 15:2004, Colorimetry (CIELAB)
+//! - Sharma, Wu, Dalal. "The CIEDE2000 Color-Difference Formula:
+//!   Implementation Notes, Supplementary Test Data, and Mathematical
+//!   Observations" (2005)
+
+use super::color_space::{ColorSpaceId, rgb_to_xyz_matrix, xyz_to_rgb_matrix};
+
+/// CIE XYZ of the D65 reference white, normalized to Y = 1.
+pub const D65_WHITE_XYZ: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Convert CIE XYZ to CIELAB relative to the given reference white.
+///
+/// `xyz` and `white` must share the same scale (e.g. both normalized to
+/// Y = 1, or both in cd/m²).
+pub fn xyz_to_lab(xyz: [f32; 3], white: [f32; 3]) -> [f32; 3] {
+    const EPS: f64 = 216.0 / 24389.0;
+    const KAPPA: f64 = 24389.0 / 27.0;
+
+    let f = |t: f64| {
+        if t > EPS {
+            t.cbrt()
+        } else {
+            (KAPPA * t + 16.0) / 116.0
+        }
+    };
+
+    let fx = f(xyz[0] as f64 / white[0] as f64);
+    let fy = f(xyz[1] as f64 / white[1] as f64);
+    let fz = f(xyz[2] as f64 / white[2] as f64);
+
+    [
+        (116.0 * fy - 16.0) as f32,
+        (500.0 * (fx - fy)) as f32,
+        (200.0 * (fy - fz)) as f32,
+    ]
+}
+
+/// Convert CIELAB back to CIE XYZ relative to the given reference white.
+pub fn lab_to_xyz(lab: [f32; 3], white: [f32; 3]) -> [f32; 3] {
+    const EPS: f64 = 6.0 / 29.0;
+    const KAPPA: f64 = 24389.0 / 27.0;
+
+    let fy = (lab[0] as f64 + 16.0) / 116.0;
+    let fx = fy + lab[1] as f64 / 500.0;
+    let fz = fy - lab[2] as f64 / 200.0;
+    let f_inv = |f: f64| {
+        if f > EPS {
+            f * f * f
+        } else {
+            (116.0 * f - 16.0) / KAPPA
+        }
+    };
+
+    [
+        (f_inv(fx) * white[0] as f64) as f32,
+        (f_inv(fy) * white[1] as f64) as f32,
+        (f_inv(fz) * white[2] as f64) as f32,
+    ]
+}
+
+/// Convert linear RGB in the given color space to CIELAB (D65 white).
+pub fn linear_rgb_to_lab(rgb: [f32; 3], space: ColorSpaceId) -> [f32; 3] {
+    let xyz = rgb_to_xyz_matrix(space).apply(rgb);
+    xyz_to_lab(xyz, D65_WHITE_XYZ)
+}
+
+/// Convert CIELAB (D65 white) to linear RGB in the given color space.
+pub fn lab_to_linear_rgb(lab: [f32; 3], space: ColorSpaceId) -> [f32; 3] {
+    xyz_to_rgb_matrix(space).apply(lab_to_xyz(lab, D65_WHITE_XYZ))
+}
+
+/// CIE76 color difference — Euclidean distance in CIELAB.
+pub fn delta_e_76(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    let dl = lab1[0] - lab2[0];
+    let da = lab1[1] - lab2[1];
+    let db = lab1[2] - lab2[2];
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+/// CIEDE2000 color difference with unit weighting factors (kL = kC = kH = 1).
+pub fn delta_e_2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    use std::f64::consts::PI;
+
+    let (l1, a1, b1) = (lab1[0] as f64, lab1[1] as f64, lab1[2] as f64);
+    let (l2, a2, b2) = (lab2[0] as f64, lab2[1] as f64, lab2[2] as f64);
+
+    let c1 = (a1 * a1 + b1 * b1).sqrt();
+    let c2 = (a2 * a2 + b2 * b2).sqrt();
+    let c_bar = (c1 + c2) * 0.5;
+    let c_bar7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + 25.0_f64.powi(7))).sqrt());
+
+    let a1p = (1.0 + g) * a1;
+    let a2p = (1.0 + g) * a2;
+    let c1p = (a1p * a1p + b1 * b1).sqrt();
+    let c2p = (a2p * a2p + b2 * b2).sqrt();
+
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).rem_euclid(2.0 * PI)
+        }
+    };
+    let h1p = hue(b1, a1p);
+    let h2p = hue(b2, a2p);
+
+    let dlp = l2 - l1;
+    let dcp = c2p - c1p;
+
+    let dhp = if c1p * c2p == 0.0 {
+        0.0
+    } else {
+        let d = h2p - h1p;
+        if d > PI {
+            d - 2.0 * PI
+        } else if d < -PI {
+            d + 2.0 * PI
+        } else {
+            d
+        }
+    };
+    let dhp_big = 2.0 * (c1p * c2p).sqrt() * (dhp * 0.5).sin();
+
+    let l_bar_p = (l1 + l2) * 0.5;
+    let c_bar_p = (c1p + c2p) * 0.5;
+    let h_bar_p = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= PI {
+        (h1p + h2p) * 0.5
+    } else if h1p + h2p < 2.0 * PI {
+        (h1p + h2p + 2.0 * PI) * 0.5
+    } else {
+        (h1p + h2p - 2.0 * PI) * 0.5
+    };
+
+    let t = 1.0 - 0.17 * (h_bar_p - 30.0_f64.to_radians()).cos()
+        + 0.24 * (2.0 * h_bar_p).cos()
+        + 0.32 * (3.0 * h_bar_p + 6.0_f64.to_radians()).cos()
+        - 0.20 * (4.0 * h_bar_p - 63.0_f64.to_radians()).cos();
+
+    let d_theta = 30.0_f64.to_radians() * (-((h_bar_p.to_degrees() - 275.0) / 25.0).powi(2)).exp();
+    let c_bar_p7 = c_bar_p.powi(7);
+    let r_c = 2.0 * (c_bar_p7 / (c_bar_p7 + 25.0_f64.powi(7))).sqrt();
+    let l50 = (l_bar_p - 50.0).powi(2);
+    let s_l = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar_p;
+    let s_h = 1.0 + 0.015 * c_bar_p * t;
+    let r_t = -(2.0 * d_theta).sin() * r_c;
+
+    let tl = dlp / s_l;
+    let tc = dcp / s_c;
+    let th = dhp_big / s_h;
+
+    (tl * tl + tc * tc + th * th + r_t * tc * th).sqrt() as f32
+}
+
+/// Summary statistics over a set of ΔE measurements.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeltaEStats {
+    /// Number of samples summarized.
+    pub count: usize,
+    /// Arithmetic mean ΔE.
+    pub mean: f32,
+    /// Largest ΔE.
+    pub max: f32,
+    /// 95th percentile ΔE (nearest-rank).
+    pub p95: f32,
+}
+
+impl DeltaEStats {
+    /// Summarize a list of ΔE values. Returns all-zero stats for an empty list.
+    pub fn from_values(values: &[f32]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f32::total_cmp);
+
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<f32>() / count as f32;
+        let rank = ((0.95 * count as f32).ceil() as usize).clamp(1, count);
+
+        Self {
+            count,
+            mean,
+            max: sorted[count - 1],
+            p95: sorted[rank - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-3;
+
+    #[test]
+    fn test_white_maps_to_l100() {
+        let lab = xyz_to_lab(D65_WHITE_XYZ, D65_WHITE_XYZ);
+        assert!((lab[0] - 100.0).abs() < EPSILON, "L* = {}", lab[0]);
+        assert!(lab[1].abs() < EPSILON, "a* = {}", lab[1]);
+        assert!(lab[2].abs() < EPSILON, "b* = {}", lab[2]);
+    }
+
+    #[test]
+    fn test_lab_round_trips() {
+        // Dark values exercise the linear segment below the cube-root knee.
+        for xyz in [[0.2, 0.3, 0.1], [0.9, 0.5, 0.05], [0.001, 0.002, 0.004]] {
+            let back = lab_to_xyz(xyz_to_lab(xyz, D65_WHITE_XYZ), D65_WHITE_XYZ);
+            for c in 0..3 {
+                assert!((back[c] - xyz[c]).abs() < 1e-5, "{xyz:?} → {back:?}");
+            }
+        }
+        let rgb = [0.7, 0.2, 0.4];
+        let back = lab_to_linear_rgb(
+            linear_rgb_to_lab(rgb, ColorSpaceId::AcesCg),
+            ColorSpaceId::AcesCg,
+        );
+        for c in 0..3 {
+            assert!((back[c] - rgb[c]).abs() < 1e-4, "{back:?}");
+        }
+    }
+
+    #[test]
+    fn test_linear_srgb_white_is_neutral() {
+        let lab = linear_rgb_to_lab([1.0, 1.0, 1.0], ColorSpaceId::LinearSrgb);
+        assert!((lab[0] - 100.0).abs() < 0.01, "L* = {}", lab[0]);
+        assert!(lab[1].abs() < 0.05 && lab[2].abs() < 0.05, "lab = {lab:?}");
+    }
+
+    #[test]
+    fn test_delta_e_identical_is_zero() {
+        let lab = [50.0, 10.0, -20.0];
+        assert_eq!(delta_e_76(lab, lab), 0.0);
+        assert!(delta_e_2000(lab, lab).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_delta_e_2000_matches_sharma_reference_pairs() {
+        // Selected pairs from Sharma et al. (2005), Table 1.
+        let cases = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+            ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+            (
+                [22.7233, 20.0904, -46.6940],
+                [23.0331, 14.9730, -42.5619],
+                2.0373,
+            ),
+        ];
+        for (lab1, lab2, expected) in cases {
+            let de = delta_e_2000(lab1, lab2);
+            assert!(
+                (de - expected).abs() < 1e-3,
+                "ΔE00({lab1:?}, {lab2:?}) = {de}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_delta_e_2000_is_symmetric() {
+        let a = [40.0, 30.0, -10.0];
+        let b = [45.0, 25.0, 5.0];
+        assert!((delta_e_2000(a, b) - delta_e_2000(b, a)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_stats_summarize_values() {
+        let values: Vec<f32> = (1..=20).map(|v| v as f32).collect();
+        let stats = DeltaEStats::from_values(&values);
+        assert_eq!(stats.count, 20);
+        assert!((stats.mean - 10.5).abs() < EPSILON);
+        assert_eq!(stats.max, 20.0);
+        assert_eq!(stats.p95, 19.0);
+    }
+
+    #[test]
+    fn test_stats_empty_is_zero() {
+        assert_eq!(DeltaEStats::from_values(&[]), DeltaEStats::default());
+    }
+}
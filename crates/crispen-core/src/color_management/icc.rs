@@ -0,0 +1,290 @@
+//! ICC v4 display profile generation for the output transform.
+//!
+//! Builds a matrix/TRC (`mntr`, RGB → XYZ) profile describing the output
+//! color space: the tone curves decode the output transfer function and the
+//! colorants are the output primaries adapted to the D50 PCS with Bradford.
+//! Still-image review tools and OS soft-proofing can then interpret exported
+//! frames the way Crispen encoded them.
+//!
+//! Log output spaces decode to scene-linear values above 1.0; ICC curves
+//! are limited to `[0, 1]`, so those curves clip at diffuse white.
+//!
+//! # Reference
+//! - ICC.1:2022 (Profile version 4.4), §7 (header / tag table), §10 (tag types)
+
+#[cfg(feature = "lut-io")]
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::color_space::{ColorSpaceId, d65_to_d50_matrix, rgb_to_xyz_matrix};
+use super::transfer::get_transfer;
+use crate::transform::params::ColorManagementConfig;
+
+/// PCS illuminant (D50) as stored in the header and `wtpt` tag.
+const D50_XYZ: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+/// Entries in each sampled `curv` tone curve.
+const CURVE_ENTRIES: usize = 1024;
+
+/// Build an ICC profile for the configured output color space.
+pub fn build_output_profile(config: &ColorManagementConfig) -> Vec<u8> {
+    build_profile(config.output_space, creation_date())
+}
+
+/// Build the output profile and write it to `path`.
+#[cfg(feature = "lut-io")]
+pub fn save_output_profile(config: &ColorManagementConfig, path: &Path) -> std::io::Result<()> {
+    std::fs::write(path, build_output_profile(config))
+}
+
+/// Build a matrix/TRC display profile for `space`.
+///
+/// `date` is `[year, month, day, hour, minute, second]` (UTC).
+pub fn build_profile(space: ColorSpaceId, date: [u16; 6]) -> Vec<u8> {
+    let to_d50 = d65_to_d50_matrix();
+    let to_xyz = rgb_to_xyz_matrix(space);
+    let colorant = |c: usize| {
+        let mut unit = [0.0_f32; 3];
+        unit[c] = 1.0;
+        to_d50.apply(to_xyz.apply(unit)).map(f64::from)
+    };
+    let curve = curve_tag(space);
+
+    let tags: Vec<([u8; 4], Vec<u8>)> = vec![
+        (*b"desc", mluc_tag(&format!("Crispen {}", space.label()))),
+        (*b"cprt", mluc_tag("No copyright, use freely")),
+        (*b"wtpt", xyz_tag(D50_XYZ)),
+        (*b"chad", sf32_tag(&to_d50.0)),
+        (*b"rXYZ", xyz_tag(colorant(0))),
+        (*b"gXYZ", xyz_tag(colorant(1))),
+        (*b"bXYZ", xyz_tag(colorant(2))),
+        (*b"rTRC", curve.clone()),
+        (*b"gTRC", curve.clone()),
+        (*b"bTRC", curve),
+    ];
+
+    // Tag data starts after the header and the tag table.
+    let mut offset = 128 + 4 + 12 * tags.len();
+    let mut table = Vec::new();
+    let mut data = Vec::new();
+    for (sig, bytes) in &tags {
+        table.extend_from_slice(sig);
+        table.extend_from_slice(&(offset as u32).to_be_bytes());
+        table.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        data.extend_from_slice(bytes);
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+        offset = 128 + 4 + 12 * tags.len() + data.len();
+    }
+
+    let total = 128 + 4 + table.len() + data.len();
+    let mut out = header(total as u32, date);
+    out.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+    out.extend_from_slice(&table);
+    out.extend_from_slice(&data);
+    out
+}
+
+fn header(size: u32, date: [u16; 6]) -> Vec<u8> {
+    let mut h = Vec::with_capacity(128);
+    h.extend_from_slice(&size.to_be_bytes());
+    h.extend_from_slice(&[0; 4]); // preferred CMM
+    h.extend_from_slice(&0x0440_0000_u32.to_be_bytes()); // v4.4
+    h.extend_from_slice(b"mntr");
+    h.extend_from_slice(b"RGB ");
+    h.extend_from_slice(b"XYZ ");
+    for v in date {
+        h.extend_from_slice(&v.to_be_bytes());
+    }
+    h.extend_from_slice(b"acsp");
+    h.extend_from_slice(&[0; 4]); // platform
+    h.extend_from_slice(&[0; 4]); // flags
+    h.extend_from_slice(&[0; 4]); // manufacturer
+    h.extend_from_slice(&[0; 4]); // model
+    h.extend_from_slice(&[0; 8]); // attributes
+    h.extend_from_slice(&0_u32.to_be_bytes()); // perceptual intent
+    for v in D50_XYZ {
+        h.extend_from_slice(&s15_fixed16(v).to_be_bytes());
+    }
+    h.extend_from_slice(&[0; 4]); // creator
+    h.extend_from_slice(&[0; 16]); // profile ID (not computed)
+    h.resize(128, 0);
+    h
+}
+
+/// `curv` tag decoding the space's transfer function (identity if linear).
+fn curve_tag(space: ColorSpaceId) -> Vec<u8> {
+    let mut tag = b"curv".to_vec();
+    tag.extend_from_slice(&[0; 4]);
+    let Some(tf) = get_transfer(space) else {
+        tag.extend_from_slice(&0_u32.to_be_bytes());
+        return tag;
+    };
+    tag.extend_from_slice(&(CURVE_ENTRIES as u32).to_be_bytes());
+    for i in 0..CURVE_ENTRIES {
+        let encoded = i as f32 / (CURVE_ENTRIES - 1) as f32;
+        let linear = tf.to_linear(encoded).clamp(0.0, 1.0);
+        let v = (linear * 65535.0).round() as u16;
+        tag.extend_from_slice(&v.to_be_bytes());
+    }
+    tag
+}
+
+fn xyz_tag(xyz: [f64; 3]) -> Vec<u8> {
+    let mut tag = b"XYZ ".to_vec();
+    tag.extend_from_slice(&[0; 4]);
+    for v in xyz {
+        tag.extend_from_slice(&s15_fixed16(v).to_be_bytes());
+    }
+    tag
+}
+
+fn sf32_tag(m: &[[f64; 3]; 3]) -> Vec<u8> {
+    let mut tag = b"sf32".to_vec();
+    tag.extend_from_slice(&[0; 4]);
+    for v in m.iter().flatten() {
+        tag.extend_from_slice(&s15_fixed16(*v).to_be_bytes());
+    }
+    tag
+}
+
+/// Single-record (en-US) `multiLocalizedUnicodeType`.
+fn mluc_tag(text: &str) -> Vec<u8> {
+    let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let mut tag = b"mluc".to_vec();
+    tag.extend_from_slice(&[0; 4]);
+    tag.extend_from_slice(&1_u32.to_be_bytes()); // record count
+    tag.extend_from_slice(&12_u32.to_be_bytes()); // record size
+    tag.extend_from_slice(b"enUS");
+    tag.extend_from_slice(&(utf16.len() as u32).to_be_bytes());
+    tag.extend_from_slice(&28_u32.to_be_bytes()); // string offset
+    tag.extend_from_slice(&utf16);
+    tag
+}
+
+fn s15_fixed16(v: f64) -> i32 {
+    (v * 65536.0).round() as i32
+}
+
+/// Current UTC date/time for the profile header.
+fn creation_date() -> [u16; 6] {
+    // The browser target has no system clock and `SystemTime::now` panics
+    // there; the epoch is as valid a creation date as any.
+    let secs = if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        0
+    } else {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    };
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (Howard Hinnant).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    [
+        year as u16,
+        month as u16,
+        day as u16,
+        (rem / 3600) as u16,
+        (rem % 3600 / 60) as u16,
+        (rem % 60) as u16,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATE: [u16; 6] = [2025, 1, 2, 3, 4, 5];
+
+    fn read_u32(b: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes(b[at..at + 4].try_into().unwrap())
+    }
+
+    fn find_tag<'a>(profile: &'a [u8], sig: &[u8; 4]) -> &'a [u8] {
+        let count = read_u32(profile, 128) as usize;
+        for i in 0..count {
+            let entry = 132 + 12 * i;
+            if &profile[entry..entry + 4] == sig {
+                let offset = read_u32(profile, entry + 4) as usize;
+                let size = read_u32(profile, entry + 8) as usize;
+                return &profile[offset..offset + size];
+            }
+        }
+        panic!("tag {:?} missing", std::str::from_utf8(sig));
+    }
+
+    #[test]
+    fn test_header_is_well_formed() {
+        let p = build_profile(ColorSpaceId::Srgb, DATE);
+        assert_eq!(read_u32(&p, 0) as usize, p.len());
+        assert_eq!(&p[12..16], b"mntr");
+        assert_eq!(&p[16..20], b"RGB ");
+        assert_eq!(&p[36..40], b"acsp");
+        assert_eq!(u16::from_be_bytes([p[24], p[25]]), 2025);
+        assert_eq!(read_u32(&p, 128), 10);
+    }
+
+    #[test]
+    fn test_colorants_sum_to_d50_white() {
+        for space in [
+            ColorSpaceId::Srgb,
+            ColorSpaceId::Rec2020,
+            ColorSpaceId::AcesCg,
+        ] {
+            let p = build_profile(space, DATE);
+            let mut sum = [0.0_f64; 3];
+            for sig in [b"rXYZ", b"gXYZ", b"bXYZ"] {
+                let tag = find_tag(&p, sig);
+                for (c, s) in sum.iter_mut().enumerate() {
+                    *s += read_u32(tag, 8 + 4 * c) as i32 as f64 / 65536.0;
+                }
+            }
+            for (c, s) in sum.iter().enumerate() {
+                assert!(
+                    (s - D50_XYZ[c]).abs() < 2e-3,
+                    "{space:?} ch{c}: {s} vs {}",
+                    D50_XYZ[c]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_srgb_curve_decodes_midpoint() {
+        let p = build_profile(ColorSpaceId::Srgb, DATE);
+        let curv = find_tag(&p, b"rTRC");
+        assert_eq!(read_u32(curv, 8) as usize, CURVE_ENTRIES);
+        // Entry closest to 0.5 encoded ≈ 0.214 linear.
+        let i = (CURVE_ENTRIES - 1) / 2;
+        let v = u16::from_be_bytes([curv[12 + 2 * i], curv[13 + 2 * i]]) as f64 / 65535.0;
+        assert!((v - 0.214).abs() < 0.002, "{v}");
+    }
+
+    #[test]
+    fn test_linear_space_uses_identity_curve() {
+        let p = build_profile(ColorSpaceId::LinearSrgb, DATE);
+        assert_eq!(read_u32(find_tag(&p, b"gTRC"), 8), 0);
+    }
+
+    #[test]
+    fn test_tag_offsets_are_aligned() {
+        let p = build_profile(ColorSpaceId::Srgb, DATE);
+        let count = read_u32(&p, 128) as usize;
+        for i in 0..count {
+            assert_eq!(read_u32(&p, 132 + 12 * i + 4) % 4, 0);
+        }
+    }
+}
@@ -2,7 +2,12 @@
 
 pub mod aces;
 pub mod color_space;
+pub mod delta_e;
+pub mod icc;
+pub mod perceptual;
+pub mod planckian;
 pub mod transfer;
 pub mod white_balance;
 
-pub use color_space::{CieChromaticity, chromaticity};
+pub use color_space::{CieChromaticity, chromaticity, primaries_conversion_matrix};
+pub use planckian::{kelvin_duv_to_xy, xy_to_kelvin_duv};
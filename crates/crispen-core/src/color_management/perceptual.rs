@@ -0,0 +1,185 @@
+//! Perceptual opponent spaces — OKLab and JzAzBz — for chroma adjustments
+//! that keep hue and lightness.
+//!
+//! Both take CIE XYZ D65 with white at Y = 1. JzAzBz is an absolute model;
+//! Y = 1 is taken as [`JZ_WHITE_NITS`]. Only the `Iz` form is exposed: `Jz`
+//! is a monotonic function of `Iz` alone, so a chroma change that keeps
+//! `Iz` keeps `Jz` too. The WGSL bake uses the same constants.
+
+use super::color_space::{ColorMatrix, ColorSpaceId, rgb_to_xyz_matrix, xyz_to_rgb_matrix};
+
+/// Luminance in cd/m² of XYZ Y = 1 when converting to JzAzBz.
+pub const JZ_WHITE_NITS: f32 = 100.0;
+
+const OKLAB_XYZ_TO_LMS: ColorMatrix = ColorMatrix([
+    [0.8189330101, 0.3618667424, -0.1288597137],
+    [0.0329845436, 0.9293118715, 0.0361456387],
+    [0.0482003018, 0.2643662691, 0.6338517070],
+]);
+const OKLAB_LMS_TO_XYZ: ColorMatrix = ColorMatrix([
+    [1.2270138511, -0.5577999807, 0.2812561490],
+    [-0.0405801784, 1.1122568696, -0.0716766787],
+    [-0.0763812845, -0.4214819784, 1.5861632204],
+]);
+const OKLAB_LMS_TO_LAB: ColorMatrix = ColorMatrix([
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+]);
+const OKLAB_LAB_TO_LMS: ColorMatrix = ColorMatrix([
+    [0.9999999985, 0.3963377922, 0.2158037581],
+    [1.0000000089, -0.1055613423, -0.0638541748],
+    [1.0000000547, -0.0894841821, -1.2914855379],
+]);
+
+const JZ_XYZ_TO_LMS: ColorMatrix = ColorMatrix([
+    [0.41478972, 0.579999, 0.0146480],
+    [-0.2015100, 1.120649, 0.0531008],
+    [-0.0166008, 0.264800, 0.6684799],
+]);
+const JZ_LMS_TO_XYZ: ColorMatrix = ColorMatrix([
+    [1.9242264358, -1.0047923126, 0.0376514040],
+    [0.3503167621, 0.7264811939, -0.0653844229],
+    [-0.0909828110, -0.3127282905, 1.5227665613],
+]);
+const JZ_LMS_TO_IAB: ColorMatrix = ColorMatrix([
+    [0.5, 0.5, 0.0],
+    [3.524000, -4.066708, 0.542708],
+    [0.199076, 1.096799, -1.295875],
+]);
+const JZ_IAB_TO_LMS: ColorMatrix = ColorMatrix([
+    [1.0, 0.1386050433, 0.0580473162],
+    [1.0, -0.1386050433, -0.0580473162],
+    [1.0, -0.0960192420, -0.8118918961],
+]);
+const JZ_B: f32 = 1.15;
+const JZ_G: f32 = 0.66;
+
+// PQ constants with JzAzBz's modified exponent.
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 128.0;
+const PQ_C3: f32 = 2392.0 / 128.0;
+const PQ_N: f32 = 2610.0 / 16384.0;
+const PQ_P: f32 = 1.7 * 2523.0 / 32.0;
+
+/// CIE XYZ D65 to OKLab `[L, a, b]`.
+pub fn xyz_to_oklab(xyz: [f32; 3]) -> [f32; 3] {
+    let lms = OKLAB_XYZ_TO_LMS.apply(xyz).map(f32::cbrt);
+    OKLAB_LMS_TO_LAB.apply(lms)
+}
+
+/// OKLab `[L, a, b]` to CIE XYZ D65.
+pub fn oklab_to_xyz(lab: [f32; 3]) -> [f32; 3] {
+    let lms = OKLAB_LAB_TO_LMS.apply(lab).map(|v| v * v * v);
+    OKLAB_LMS_TO_XYZ.apply(lms)
+}
+
+/// Linear RGB in `space` to OKLab `[L, a, b]`.
+pub fn linear_rgb_to_oklab(rgb: [f32; 3], space: ColorSpaceId) -> [f32; 3] {
+    xyz_to_oklab(rgb_to_xyz_matrix(space).apply(rgb))
+}
+
+/// OKLab `[L, a, b]` to linear RGB in `space`.
+pub fn oklab_to_linear_rgb(lab: [f32; 3], space: ColorSpaceId) -> [f32; 3] {
+    xyz_to_rgb_matrix(space).apply(oklab_to_xyz(lab))
+}
+
+/// Opponent `[L, a, b]` to cylindrical `[L, C, h]`, hue in degrees in
+/// [0, 360). Works for CIELAB and OKLab alike.
+pub fn lab_to_lch(lab: [f32; 3]) -> [f32; 3] {
+    let [l, a, b] = lab;
+    [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+/// Cylindrical `[L, C, h]`, hue in degrees, back to opponent `[L, a, b]`.
+pub fn lch_to_lab(lch: [f32; 3]) -> [f32; 3] {
+    let [l, c, h] = lch;
+    let (sin, cos) = h.to_radians().sin_cos();
+    [l, c * cos, c * sin]
+}
+
+/// CIE XYZ D65 to JzAzBz `[Iz, az, bz]`.
+pub fn xyz_to_izazbz(xyz: [f32; 3]) -> [f32; 3] {
+    let [x, y, z] = xyz.map(|v| v * JZ_WHITE_NITS);
+    let pre = [JZ_B * x - (JZ_B - 1.0) * z, JZ_G * y - (JZ_G - 1.0) * x, z];
+    let lms = JZ_XYZ_TO_LMS.apply(pre).map(pq_encode);
+    JZ_LMS_TO_IAB.apply(lms)
+}
+
+/// JzAzBz `[Iz, az, bz]` to CIE XYZ D65.
+pub fn izazbz_to_xyz(iab: [f32; 3]) -> [f32; 3] {
+    let lms = JZ_IAB_TO_LMS.apply(iab).map(pq_decode);
+    let [xp, yp, z] = JZ_LMS_TO_XYZ.apply(lms);
+    let x = (xp + (JZ_B - 1.0) * z) / JZ_B;
+    let y = (yp + (JZ_G - 1.0) * x) / JZ_G;
+    [x, y, z].map(|v| v / JZ_WHITE_NITS)
+}
+
+/// PQ with JzAzBz's exponent, odd-extended so negative LMS survive.
+fn pq_encode(v: f32) -> f32 {
+    let yn = (v.abs() / 10000.0).powf(PQ_N);
+    ((PQ_C1 + PQ_C2 * yn) / (1.0 + PQ_C3 * yn)).powf(PQ_P) * v.signum()
+}
+
+fn pq_decode(v: f32) -> f32 {
+    let vp = v.abs().powf(1.0 / PQ_P);
+    let y = ((PQ_C1 - vp) / (PQ_C3 * vp - PQ_C2))
+        .max(0.0)
+        .powf(1.0 / PQ_N);
+    y * 10000.0 * v.signum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const D65_WHITE: [f32; 3] = [0.95047, 1.0, 1.08883];
+
+    fn assert_close(a: [f32; 3], b: [f32; 3], tol: f32) {
+        for c in 0..3 {
+            assert!((a[c] - b[c]).abs() < tol, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_oklab_white_is_neutral() {
+        let lab = xyz_to_oklab(D65_WHITE);
+        assert!((lab[0] - 1.0).abs() < 1e-3, "{lab:?}");
+        assert!(lab[1].abs() < 1e-3 && lab[2].abs() < 1e-3, "{lab:?}");
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for xyz in [
+            [0.2, 0.3, 0.1],
+            [0.9, 0.5, 0.05],
+            [0.01, 0.02, 0.4],
+            [2.0, 1.8, 1.5],
+        ] {
+            assert_close(oklab_to_xyz(xyz_to_oklab(xyz)), xyz, 1e-4);
+            assert_close(izazbz_to_xyz(xyz_to_izazbz(xyz)), xyz, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_rgb_and_lch_round_trips() {
+        let rgb = [0.6, 0.3, 0.1];
+        let lab = linear_rgb_to_oklab(rgb, ColorSpaceId::LinearSrgb);
+        assert_close(
+            oklab_to_linear_rgb(lab, ColorSpaceId::LinearSrgb),
+            rgb,
+            1e-4,
+        );
+        let lch = lab_to_lch(lab);
+        assert!(lch[2] > 0.0 && lch[2] < 90.0, "orange hue {lch:?}");
+        assert_close(lch_to_lab(lch), lab, 1e-6);
+        assert_eq!(lab_to_lch([0.5, 0.0, -0.1])[2], 270.0);
+    }
+
+    #[test]
+    fn test_jzazbz_white_is_neutral() {
+        let iab = xyz_to_izazbz(D65_WHITE);
+        assert!(iab[0] > 0.0);
+        assert!(iab[1].abs() < 2e-3 && iab[2].abs() < 2e-3, "{iab:?}");
+    }
+}
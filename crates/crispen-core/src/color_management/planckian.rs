@@ -0,0 +1,205 @@
+//! Chromaticity coordinates and the Planckian locus.
+//!
+//! Conversions between CIE 1931 xy, CIE 1960 uv (the space correlated
+//! color temperature and Duv are defined in), and CIE 1976 u′v′, plus
+//! color temperature ↔ chromaticity along the Planckian locus with a Duv
+//! offset. Duv is the signed distance from the locus in 1960 uv, positive
+//! above it (toward green) and negative below (toward magenta).
+//!
+//! # Reference
+//! - Krystek, M. (1985). "An algorithm to calculate correlated colour
+//!   temperature." Color Research & Application 10(1) — rational
+//!   approximation of the locus in uv, within 8·10⁻⁵ from 1000 K to 15000 K
+//! - ANSI C78.377-2017 — Duv sign convention
+
+/// Lowest color temperature in kelvin the locus approximation covers.
+pub const MIN_KELVIN: f64 = 1000.0;
+/// Highest color temperature in kelvin the locus approximation covers.
+pub const MAX_KELVIN: f64 = 15000.0;
+
+/// CIE 1931 xy to CIE 1960 uv.
+pub fn xy_to_uv(xy: [f64; 2]) -> [f64; 2] {
+    let d = -2.0 * xy[0] + 12.0 * xy[1] + 3.0;
+    [4.0 * xy[0] / d, 6.0 * xy[1] / d]
+}
+
+/// CIE 1960 uv to CIE 1931 xy.
+pub fn uv_to_xy(uv: [f64; 2]) -> [f64; 2] {
+    let d = 2.0 * uv[0] - 8.0 * uv[1] + 4.0;
+    [3.0 * uv[0] / d, 2.0 * uv[1] / d]
+}
+
+/// CIE 1931 xy to CIE 1976 u′v′. `v′` is 1.5 × the 1960 `v`.
+pub fn xy_to_upvp(xy: [f64; 2]) -> [f64; 2] {
+    let [u, v] = xy_to_uv(xy);
+    [u, 1.5 * v]
+}
+
+/// CIE 1976 u′v′ to CIE 1931 xy.
+pub fn upvp_to_xy(upvp: [f64; 2]) -> [f64; 2] {
+    uv_to_xy([upvp[0], upvp[1] / 1.5])
+}
+
+/// CIE 1931 xy at luminance `y_lum` to CIE XYZ.
+pub fn xy_to_xyz(xy: [f64; 2], y_lum: f64) -> [f64; 3] {
+    [
+        xy[0] / xy[1] * y_lum,
+        y_lum,
+        (1.0 - xy[0] - xy[1]) / xy[1] * y_lum,
+    ]
+}
+
+/// CIE XYZ to CIE 1931 xy. Black maps to the equal-energy point.
+pub fn xyz_to_xy(xyz: [f64; 3]) -> [f64; 2] {
+    let sum = xyz[0] + xyz[1] + xyz[2];
+    if sum == 0.0 {
+        return [1.0 / 3.0, 1.0 / 3.0];
+    }
+    [xyz[0] / sum, xyz[1] / sum]
+}
+
+/// 1960 uv of a Planckian radiator at `kelvin`, clamped to
+/// [`MIN_KELVIN`]–[`MAX_KELVIN`].
+pub fn planckian_uv(kelvin: f64) -> [f64; 2] {
+    let t = kelvin.clamp(MIN_KELVIN, MAX_KELVIN);
+    let t2 = t * t;
+    let u = (0.860_117_757 + 1.541_182_54e-4 * t + 1.286_412_12e-7 * t2)
+        / (1.0 + 8.424_202_35e-4 * t + 7.081_451_63e-7 * t2);
+    let v = (0.317_398_726 + 4.228_062_45e-5 * t + 4.204_816_91e-8 * t2)
+        / (1.0 - 2.897_418_16e-5 * t + 1.614_560_53e-7 * t2);
+    [u, v]
+}
+
+/// Unit normal to the locus at `kelvin` in 1960 uv, pointing toward
+/// positive Duv.
+fn locus_normal(kelvin: f64) -> [f64; 2] {
+    let t = kelvin.clamp(MIN_KELVIN + 1.0, MAX_KELVIN - 1.0);
+    let a = planckian_uv(t - 1.0);
+    let b = planckian_uv(t + 1.0);
+    let (du, dv) = (b[0] - a[0], b[1] - a[1]);
+    let len = du.hypot(dv);
+    [dv / len, -du / len]
+}
+
+/// Chromaticity at color temperature `kelvin` offset `duv` from the
+/// Planckian locus, as 1960 uv.
+pub fn kelvin_duv_to_uv(kelvin: f64, duv: f64) -> [f64; 2] {
+    let [u, v] = planckian_uv(kelvin);
+    let n = locus_normal(kelvin);
+    [u + n[0] * duv, v + n[1] * duv]
+}
+
+/// Chromaticity at color temperature `kelvin` offset `duv` from the
+/// Planckian locus, as CIE 1931 xy.
+pub fn kelvin_duv_to_xy(kelvin: f64, duv: f64) -> [f64; 2] {
+    uv_to_xy(kelvin_duv_to_uv(kelvin, duv))
+}
+
+/// CIE 1931 xy of a Planckian radiator at `kelvin`.
+pub fn kelvin_to_xy(kelvin: f64) -> [f64; 2] {
+    uv_to_xy(planckian_uv(kelvin))
+}
+
+/// Correlated color temperature in kelvin and Duv of a chromaticity.
+///
+/// Finds the nearest point on the locus in 1960 uv by a coarse scan in
+/// mired followed by golden-section refinement. Chromaticities whose
+/// nearest point lies outside [`MIN_KELVIN`]–[`MAX_KELVIN`] report the
+/// range limit.
+pub fn xy_to_kelvin_duv(xy: [f64; 2]) -> (f64, f64) {
+    let uv = xy_to_uv(xy);
+    let dist2 = |mired: f64| {
+        let p = planckian_uv(1e6 / mired);
+        (p[0] - uv[0]).powi(2) + (p[1] - uv[1]).powi(2)
+    };
+
+    const STEPS: usize = 64;
+    let (lo, hi) = (1e6 / MAX_KELVIN, 1e6 / MIN_KELVIN);
+    let step = (hi - lo) / STEPS as f64;
+    let best = (0..=STEPS)
+        .map(|i| lo + step * i as f64)
+        .min_by(|a, b| dist2(*a).total_cmp(&dist2(*b)))
+        .unwrap_or(lo);
+
+    let (mut a, mut b) = ((best - step).max(lo), (best + step).min(hi));
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    for _ in 0..60 {
+        let c = b - ratio * (b - a);
+        let d = a + ratio * (b - a);
+        if dist2(c) < dist2(d) {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    let kelvin = 1e6 / ((a + b) / 2.0);
+
+    let p = planckian_uv(kelvin);
+    let n = locus_normal(kelvin);
+    let (du, dv) = (uv[0] - p[0], uv[1] - p[1]);
+    let sign = if du * n[0] + dv * n[1] < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    (kelvin, sign * du.hypot(dv))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const D65: [f64; 2] = [0.3127, 0.3290];
+
+    #[test]
+    fn test_uv_round_trips() {
+        for xy in [D65, [0.64, 0.33], [0.15, 0.06], [0.4476, 0.4074]] {
+            let back = uv_to_xy(xy_to_uv(xy));
+            assert!((back[0] - xy[0]).abs() < 1e-12 && (back[1] - xy[1]).abs() < 1e-12);
+            let back = upvp_to_xy(xy_to_upvp(xy));
+            assert!((back[0] - xy[0]).abs() < 1e-12 && (back[1] - xy[1]).abs() < 1e-12);
+        }
+        let upvp = xy_to_upvp(D65);
+        assert!((upvp[0] - 0.1978).abs() < 1e-4 && (upvp[1] - 0.4683).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_xyz_round_trip() {
+        let xyz = xy_to_xyz(D65, 1.0);
+        assert!((xyz[0] - 0.950_456).abs() < 1e-5 && (xyz[2] - 1.089_058).abs() < 1e-5);
+        let xy = xyz_to_xy(xyz);
+        assert!((xy[0] - D65[0]).abs() < 1e-12 && (xy[1] - D65[1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_locus_matches_published_points() {
+        // Illuminant A is a 2856 K Planckian radiator, defined with an
+        // older c₂, so it sits a few kelvin off today's locus.
+        let a = kelvin_to_xy(2856.0);
+        assert!(
+            (a[0] - 0.44757).abs() < 5e-4 && (a[1] - 0.40745).abs() < 5e-4,
+            "{a:?}"
+        );
+        let (k, duv) = xy_to_kelvin_duv([0.44757, 0.40745]);
+        assert!((k - 2856.0).abs() < 15.0, "{k}");
+        assert!(duv.abs() < 3e-4, "{duv}");
+    }
+
+    #[test]
+    fn test_d65_sits_above_locus() {
+        let (k, duv) = xy_to_kelvin_duv(D65);
+        assert!((k - 6504.0).abs() < 10.0, "{k}");
+        assert!((duv - 0.0032).abs() < 2e-4, "{duv}");
+    }
+
+    #[test]
+    fn test_kelvin_duv_round_trips() {
+        for kelvin in [1800.0, 3200.0, 5600.0, 9000.0, 14000.0] {
+            for duv in [-0.02, 0.0, 0.01] {
+                let (k, d) = xy_to_kelvin_duv(kelvin_duv_to_xy(kelvin, duv));
+                assert!((k - kelvin).abs() < kelvin * 1e-3, "{kelvin} → {k}");
+                assert!((d - duv).abs() < 1e-5, "{duv} → {d}");
+            }
+        }
+    }
+}
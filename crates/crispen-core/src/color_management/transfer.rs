@@ -126,38 +126,40 @@ impl TransferFunction for ArriLogC3Transfer {
 /// ARRI LogC4 Specification (2022)
 ///
 /// ```text
-/// to_linear: t <= E_CUT → (t - D) / C
-///            t >  E_CUT → (2^((t - D) / C) - B) / A
+/// a = (2^18 − 16) / 117.45,  b = (1023 − 95) / 1023,  c = 95 / 1023
 ///
-/// from_linear: x <= CUT → C × x + D
-///              x >  CUT → C × log2(A × x + B) + D
+/// to_linear: E <  0 → E × S + T
+///            E >= 0 → (2^(14 × (E − c) / b + 6) − 64) / a
+///
+/// from_linear: x <  T → (x − T) / S
+///              x >= T → (log2(a × x + 64) − 6) / 14 × b + c
 /// ```
 #[derive(Debug, Clone, Copy)]
 pub struct ArriLogC4Transfer;
 
 impl ArriLogC4Transfer {
     const A: f32 = 2_231.826_3;
-    const B: f32 = 64.0;
-    const C: f32 = 0.074_107_56;
-    const D: f32 = 0.092_864_12;
-    const CUT: f32 = -0.023_440_45;
-    const E_CUT: f32 = 0.090_600_96;
+    const B: f32 = 0.907_135_9;
+    const C: f32 = 0.092_864_126;
+    // Slope and offset of the linear toe, derived from a, b, c.
+    const S: f32 = 0.113_597_21;
+    const T: f32 = -0.018_056_996;
 }
 
 impl TransferFunction for ArriLogC4Transfer {
     fn to_linear(&self, encoded: f32) -> f32 {
-        if encoded <= Self::E_CUT {
-            (encoded - Self::D) / Self::C
+        if encoded < 0.0 {
+            encoded * Self::S + Self::T
         } else {
-            (2.0_f32.powf((encoded - Self::D) / Self::C) - Self::B) / Self::A
+            (2.0_f32.powf(14.0 * (encoded - Self::C) / Self::B + 6.0) - 64.0) / Self::A
         }
     }
 
     fn to_encoded(&self, linear: f32) -> f32 {
-        if linear <= Self::CUT {
-            Self::C * linear + Self::D
+        if linear < Self::T {
+            (linear - Self::T) / Self::S
         } else {
-            Self::C * (Self::A * linear + Self::B).log2() + Self::D
+            ((Self::A * linear + 64.0).log2() - 6.0) / 14.0 * Self::B + Self::C
         }
     }
 }
@@ -215,36 +217,37 @@ impl TransferFunction for SLog3Transfer {
 /// RED White Paper: REDWideGamutRGB and Log3G10 (2017)
 ///
 /// ```text
-/// to_linear: t <= 0 → (t - 0.01) / 155.975327
-///            t >  0 → (10^(t / 0.224282) − 1) / 155.975327
+/// to_linear: t <  0 → t / 15.1927 − 0.01
+///            t >= 0 → (10^(t / 0.224282) − 1) / 155.975327 − 0.01
 ///
-/// from_linear: x <= CUT → x × 155.975327 + 0.01
-///              x >  CUT → 0.224282 × log10(x × 155.975327 + 1) + 0.0
+/// from_linear: x + 0.01 <  0 → (x + 0.01) × 15.1927
+///              x + 0.01 >= 0 → 0.224282 × log10((x + 0.01) × 155.975327 + 1)
 /// ```
 #[derive(Debug, Clone, Copy)]
 pub struct RedLog3G10Transfer;
 
 impl RedLog3G10Transfer {
-    const A: f32 = 155.975_33;
-    const B: f32 = 0.01;
-    const C: f32 = 0.224_282;
+    const A: f32 = 0.224_282;
+    const B: f32 = 155.975_33;
+    const C: f32 = 0.01;
+    const G: f32 = 15.1927;
 }
 
 impl TransferFunction for RedLog3G10Transfer {
     fn to_linear(&self, encoded: f32) -> f32 {
         if encoded < 0.0 {
-            (encoded - Self::B) / Self::A
+            encoded / Self::G - Self::C
         } else {
-            (10.0_f32.powf(encoded / Self::C) - 1.0) / Self::A
+            (10.0_f32.powf(encoded / Self::A) - 1.0) / Self::B - Self::C
         }
     }
 
     fn to_encoded(&self, linear: f32) -> f32 {
-        let x = linear * Self::A;
+        let x = linear + Self::C;
         if x < 0.0 {
-            x + Self::B
+            x * Self::G
         } else {
-            Self::C * (x + 1.0).log10()
+            Self::A * (x * Self::B + 1.0).log10()
         }
     }
 }
@@ -305,20 +308,27 @@ impl TransferFunction for VLogTransfer {
 /// S-2014-003: ACEScc — A Logarithmic Encoding of ACES Data
 ///
 /// ```text
-/// to_linear: t <= −0.3014 → (2^(t × 17.52 − 9.72) − 1e-15) × 2
+/// to_linear: t <= −0.3014 → (2^(t × 17.52 − 9.72) − 2^-16) × 2
 ///            otherwise   → 2^(t × 17.52 − 9.72)
 ///
-/// from_linear: x <= 0       → (log2(1e-15) + 9.72) / 17.52
-///              x <  2^-15   → (log2(1e-15 + x × 0.5) + 9.72) / 17.52
+/// from_linear: x <= 0       → (log2(2^-16) + 9.72) / 17.52
+///              x <  2^-15   → (log2(2^-16 + x × 0.5) + 9.72) / 17.52
 ///              otherwise    → (log2(x) + 9.72) / 17.52
 /// ```
 #[derive(Debug, Clone, Copy)]
 pub struct AcesCcTransfer;
 
+impl AcesCcTransfer {
+    /// 2^-16, the floor the toe segment approaches.
+    const FLOOR: f32 = 1.0 / 65_536.0;
+    /// Encoded value of linear 2^-15, where the toe meets the log segment.
+    const TOE_ENCODED: f32 = (9.72 - 15.0) / 17.52;
+}
+
 impl TransferFunction for AcesCcTransfer {
     fn to_linear(&self, encoded: f32) -> f32 {
-        if encoded <= -0.3014 {
-            (2.0_f32.powf(encoded * 17.52 - 9.72) - 1e-15) * 2.0
+        if encoded < Self::TOE_ENCODED {
+            (2.0_f32.powf(encoded * 17.52 - 9.72) - Self::FLOOR) * 2.0
         } else {
             2.0_f32.powf(encoded * 17.52 - 9.72)
         }
@@ -327,9 +337,9 @@ impl TransferFunction for AcesCcTransfer {
     fn to_encoded(&self, linear: f32) -> f32 {
         let min_val: f32 = 2.0_f32.powi(-15);
         if linear <= 0.0 {
-            (1e-15_f32.log2() + 9.72) / 17.52
+            (Self::FLOOR.log2() + 9.72) / 17.52
         } else if linear < min_val {
-            ((1e-15 + linear * 0.5).log2() + 9.72) / 17.52
+            ((Self::FLOOR + linear * 0.5).log2() + 9.72) / 17.52
         } else {
             (linear.log2() + 9.72) / 17.52
         }
@@ -428,6 +438,29 @@ mod tests {
         assert_roundtrip(&tf, &[0.0, 0.001, 0.01, 0.1, 0.5, 1.0]);
     }
 
+    #[test]
+    fn test_log_curves_encode_mid_gray_per_spec() {
+        // 18% grey code values from the vendor specifications.
+        let cases: [(&dyn TransferFunction, f32); 4] = [
+            (&ArriLogC4Transfer, 0.278_396),
+            (&RedLog3G10Transfer, 1.0 / 3.0),
+            (&ArriLogC3Transfer, 0.391_007),
+            (&SLog3Transfer, 420.0 / 1023.0),
+        ];
+        for (tf, expected) in cases {
+            let encoded = tf.to_encoded(0.18);
+            assert!((encoded - expected).abs() < 1e-4, "{encoded} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn test_acescc_floor_matches_spec() {
+        let tf = AcesCcTransfer;
+        // S-2014-003: linear 0 encodes to (log2(2^-16) + 9.72) / 17.52.
+        assert!((tf.to_encoded(0.0) - (-0.358_447_5)).abs() < EPSILON);
+        assert!(tf.to_linear(tf.to_encoded(0.0)).abs() < EPSILON);
+    }
+
     #[test]
     fn test_slog3_linearize_roundtrip_preserves_values() {
         let tf = SLog3Transfer;
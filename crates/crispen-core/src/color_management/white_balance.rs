@@ -1,121 +1,164 @@
 //! White balance adjustment via chromaticity shift.
 //!
-//! Temperature shifts along the Planckian locus (blue-yellow axis) in CIE xy,
-//! then applies a Bradford chromatic adaptation. Tint shifts perpendicular to
-//! the Planckian locus (green-magenta axis).
+//! Temperature and tint pick a destination white point, and the image is
+//! adapted from the reference white to it with Bradford. Two models pick
+//! that white point:
+//!
+//! - [`WhiteBalanceModel::Planckian`] moves along the Planckian locus in
+//!   mired for temperature and across it in Duv for tint, starting from
+//!   D65's own color temperature and Duv. The adaptation runs on CIE XYZ,
+//!   converted from and back to the scene-linear working space.
+//! - [`WhiteBalanceModel::Legacy`] shifts D65 along a fixed tangent in xy
+//!   and adapts the working RGB directly. Grades saved before the
+//!   Planckian model load with it so they keep their look.
+//!
+//! Both models reduce to one 3×3 matrix per grade, which the GPU bake
+//! receives precomputed.
 //!
 //! # Reference
-//! - Hernández-Andrés et al. (1999) — Planckian locus approximation
+//! - Krystek (1985) — Planckian locus in CIE 1960 uv
 //! - Lindbloom, Bruce J. — Bradford chromatic adaptation
 
-/// Apply white balance adjustment using temperature and tint.
+use std::cell::Cell;
+use std::sync::LazyLock;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::color_space::{ColorMatrix, bradford_matrix, rgb_to_xyz_matrix, xyz_to_rgb_matrix};
+use super::planckian::{kelvin_duv_to_xy, xy_to_kelvin_duv};
+use crate::transform::params::ColorSpaceId;
+
+/// How temperature and tint choose the white point to adapt to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WhiteBalanceModel {
+    /// Fixed tangent shift at D65 in xy, adapted on working RGB. The
+    /// model of grades saved without a white balance model.
+    Legacy,
+    /// Mired along the Planckian locus and Duv across it, adapted in CIE
+    /// XYZ from scene-linear working RGB.
+    #[default]
+    Planckian,
+}
+
+impl WhiteBalanceModel {
+    /// Model of grades saved before the choice existed.
+    #[cfg(feature = "serde")]
+    pub(crate) fn legacy() -> Self {
+        Self::Legacy
+    }
+}
+
+/// Mired shift per temperature step in the Planckian model. Full scale
+/// (±100) is ±80 mired, about a full CTO or CTB gel.
+pub const TEMPERATURE_MIRED: f64 = 0.8;
+
+/// Duv shift per tint step in the Planckian model. Full scale (±100) is
+/// ±0.02, about a full plus- or minus-green gel.
+pub const TINT_DUV: f64 = 0.0002;
+
+const D65_XY: [f64; 2] = [0.3127, 0.3290];
+
+/// D65's correlated color temperature and Duv. The Planckian model starts
+/// here, so temperature and tint 0 land on the reference white itself.
+static D65_KELVIN_DUV: LazyLock<(f64, f64)> = LazyLock::new(|| xy_to_kelvin_duv(D65_XY));
+
+/// White point `temperature` and `tint` pick in the Planckian model, as
+/// CIE 1931 xy. Positive temperature is warmer (lower kelvin), positive
+/// tint is more magenta (lower Duv).
+pub fn planckian_white(temperature: f32, tint: f32) -> [f64; 2] {
+    let (kelvin, duv) = *D65_KELVIN_DUV;
+    let mired = 1e6 / kelvin + temperature as f64 * TEMPERATURE_MIRED;
+    kelvin_duv_to_xy(1e6 / mired, duv - tint as f64 * TINT_DUV)
+}
+
+/// The matrix white balance applies to working-space RGB.
 ///
-/// - `temperature`: shift along the Planckian locus. 0.0 = neutral.
-///   Positive values warm (toward yellow), negative values cool (toward blue).
-///   Range is approximately −1.0 to +1.0, mapped to ~2000K–12000K shift.
-/// - `tint`: shift perpendicular to Planckian locus. 0.0 = neutral.
-///   Positive values shift toward magenta, negative toward green.
+/// Temperature and tint 0 give the identity in both models. The
+/// Planckian model adapts from the locus point for 0/0 rather than from
+/// the D65 constant, so the CCT search's rounding cancels out and small
+/// slider moves never step away from identity.
+pub fn white_balance_matrix(
+    temperature: f32,
+    tint: f32,
+    model: WhiteBalanceModel,
+    working_space: ColorSpaceId,
+) -> ColorMatrix {
+    if temperature.abs() < 1e-7 && tint.abs() < 1e-7 {
+        return ColorMatrix::IDENTITY;
+    }
+    match model {
+        WhiteBalanceModel::Legacy => legacy_matrix(temperature, tint),
+        WhiteBalanceModel::Planckian => {
+            let adapt = bradford_matrix(
+                planckian_white(0.0, 0.0),
+                planckian_white(temperature, tint),
+            );
+            xyz_to_rgb_matrix(working_space)
+                .after(&adapt)
+                .after(&rgb_to_xyz_matrix(working_space))
+        }
+    }
+}
+
+/// Apply white balance adjustment using temperature and tint.
 ///
-/// Both values at 0.0 produce no change (identity).
+/// - `temperature`: 0.0 = neutral. Positive values warm (toward yellow),
+///   negative values cool (toward blue). Range is −100 to +100.
+/// - `tint`: 0.0 = neutral. Positive values shift toward magenta,
+///   negative toward green.
 ///
-/// # Algorithm
-/// 1. Map temperature to a correlated color temperature (CCT) offset
-/// 2. Compute source and destination white points on the Planckian locus
-/// 3. Apply tint as perpendicular offset in CIE xy
-/// 4. Compute Bradford adaptation matrix from source to destination
-/// 5. Apply matrix to input RGB
-pub fn apply_white_balance(rgb: [f32; 3], temperature: f32, tint: f32) -> [f32; 3] {
+/// Both values at 0.0 produce no change (identity). The matrix for the
+/// last settings is cached per thread, so baking a LUT builds it once.
+pub fn apply_white_balance(
+    rgb: [f32; 3],
+    temperature: f32,
+    tint: f32,
+    model: WhiteBalanceModel,
+    working_space: ColorSpaceId,
+) -> [f32; 3] {
     if temperature.abs() < 1e-7 && tint.abs() < 1e-7 {
         return rgb;
     }
 
-    // Reference white point: D65 (neutral starting point)
-    let ref_x: f64 = 0.3127;
-    let ref_y: f64 = 0.3290;
+    type Key = (u32, u32, WhiteBalanceModel, ColorSpaceId);
+    thread_local! {
+        static LAST: Cell<Option<(Key, ColorMatrix)>> = const { Cell::new(None) };
+    }
+    let key = (temperature.to_bits(), tint.to_bits(), model, working_space);
+    let matrix = LAST.with(|last| match last.get() {
+        Some((k, m)) if k == key => m,
+        _ => {
+            let m = white_balance_matrix(temperature, tint, model, working_space);
+            last.set(Some((key, m)));
+            m
+        }
+    });
+    matrix.apply(rgb)
+}
 
-    // Approximate Planckian locus tangent direction at D65
-    // This simplified model shifts xy along the blue-yellow axis
+/// The original model: D65 shifted along an approximate locus tangent in
+/// xy, with the Bradford matrix applied to working RGB as if it were XYZ.
+fn legacy_matrix(temperature: f32, tint: f32) -> ColorMatrix {
     let temp_scale: f64 = 0.05;
     let tint_scale: f64 = 0.05;
 
-    // Temperature shifts along the Planckian locus tangent (approximately)
     // At D65, the tangent direction is roughly (-0.35, -0.15) normalized
     let tangent_x: f64 = 0.3585;
     let tangent_y: f64 = 0.1501;
 
-    // Tint shifts perpendicular to the Planckian locus
-    let perp_x: f64 = -tangent_y; // Rotate 90 degrees
+    // Tint shifts perpendicular to the tangent
+    let perp_x: f64 = -tangent_y;
     let perp_y: f64 = tangent_x;
 
-    // Compute destination white point
     let t = temperature as f64 * temp_scale;
     let p = tint as f64 * tint_scale;
-    let dst_x = ref_x + tangent_x * t + perp_x * p;
-    let dst_y = ref_y + tangent_y * t + perp_y * p;
-
-    // Bradford cone response matrix
-    const M: [[f64; 3]; 3] = [
-        [0.8951, 0.2664, -0.1614],
-        [-0.7502, 1.7135, 0.0367],
-        [0.0389, -0.0685, 1.0296],
-    ];
-    const M_INV: [[f64; 3]; 3] = [
-        [0.9869929055, -0.1470542564, 0.1599626517],
-        [0.4323052697, 0.5183602715, 0.0492912282],
-        [-0.0085286646, 0.0400428217, 0.9684866958],
+    let dst = [
+        D65_XY[0] + tangent_x * t + perp_x * p,
+        D65_XY[1] + tangent_y * t + perp_y * p,
     ];
-
-    // Convert xy to XYZ (Y=1)
-    let src_xyz = [ref_x / ref_y, 1.0, (1.0 - ref_x - ref_y) / ref_y];
-    let dst_xyz = [dst_x / dst_y, 1.0, (1.0 - dst_x - dst_y) / dst_y];
-
-    // Compute cone responses
-    let src_cone = mat3_vec3(M, src_xyz);
-    let dst_cone = mat3_vec3(M, dst_xyz);
-
-    // Build adaptation matrix: M_INV * diag(dst/src) * M
-    let scale = [
-        dst_cone[0] / src_cone[0],
-        dst_cone[1] / src_cone[1],
-        dst_cone[2] / src_cone[2],
-    ];
-
-    // Compute M_INV * diag(scale) * M directly
-    let adapt = compose_bradford(M_INV, scale, M);
-
-    // Apply to RGB
-    let r = rgb[0] as f64;
-    let g = rgb[1] as f64;
-    let b = rgb[2] as f64;
-    [
-        (adapt[0][0] * r + adapt[0][1] * g + adapt[0][2] * b) as f32,
-        (adapt[1][0] * r + adapt[1][1] * g + adapt[1][2] * b) as f32,
-        (adapt[2][0] * r + adapt[2][1] * g + adapt[2][2] * b) as f32,
-    ]
-}
-
-fn mat3_vec3(m: [[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
-    [
-        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
-        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
-        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
-    ]
-}
-
-/// Compute M_INV * diag(s) * M in a single pass.
-fn compose_bradford(m_inv: [[f64; 3]; 3], s: [f64; 3], m: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
-    let sm = [
-        [s[0] * m[0][0], s[0] * m[0][1], s[0] * m[0][2]],
-        [s[1] * m[1][0], s[1] * m[1][1], s[1] * m[1][2]],
-        [s[2] * m[2][0], s[2] * m[2][1], s[2] * m[2][2]],
-    ];
-    let mut out = [[0.0; 3]; 3];
-    for i in 0..3 {
-        for j in 0..3 {
-            out[i][j] = m_inv[i][0] * sm[0][j] + m_inv[i][1] * sm[1][j] + m_inv[i][2] * sm[2][j];
-        }
-    }
-    out
+    bradford_matrix(D65_XY, dst)
 }
 
 #[cfg(test)]
@@ -123,33 +166,103 @@ mod tests {
     use super::*;
 
     const EPSILON: f32 = 1e-5;
+    const MODELS: [WhiteBalanceModel; 2] =
+        [WhiteBalanceModel::Legacy, WhiteBalanceModel::Planckian];
+
+    fn balance(rgb: [f32; 3], temperature: f32, tint: f32, model: WhiteBalanceModel) -> [f32; 3] {
+        apply_white_balance(rgb, temperature, tint, model, ColorSpaceId::AcesCg)
+    }
 
     #[test]
     fn test_white_balance_zero_is_identity() {
         let rgb = [0.5, 0.4, 0.3];
-        let result = apply_white_balance(rgb, 0.0, 0.0);
-        assert_eq!(result, rgb);
+        for model in MODELS {
+            assert_eq!(balance(rgb, 0.0, 0.0, model), rgb);
+        }
+    }
+
+    #[test]
+    fn test_planckian_reference_is_d65() {
+        let xy = planckian_white(0.0, 0.0);
+        assert!(
+            (xy[0] - D65_XY[0]).abs() < 1e-6 && (xy[1] - D65_XY[1]).abs() < 1e-6,
+            "{xy:?}"
+        );
+        // A tiny move stays continuous with the identity.
+        let rgb = [0.5, 0.4, 0.3];
+        let nudged = balance(rgb, 1e-4, 0.0, WhiteBalanceModel::Planckian);
+        for c in 0..3 {
+            assert!((nudged[c] - rgb[c]).abs() < 1e-4, "{nudged:?}");
+        }
     }
 
     #[test]
     fn test_white_balance_warm_shifts_toward_yellow() {
         let rgb = [0.5, 0.5, 0.5];
-        let result = apply_white_balance(rgb, 1.0, 0.0);
-        assert!(result[2] < rgb[2], "blue should decrease when warming");
+        for model in MODELS {
+            let result = balance(rgb, 1.0, 0.0, model);
+            assert!(
+                result[2] < rgb[2],
+                "{model:?}: blue should decrease when warming"
+            );
+        }
     }
 
     #[test]
     fn test_white_balance_cool_shifts_toward_blue() {
         let rgb = [0.5, 0.5, 0.5];
-        let result = apply_white_balance(rgb, -1.0, 0.0);
-        assert!(result[2] > rgb[2], "blue should increase when cooling");
+        for model in MODELS {
+            let result = balance(rgb, -1.0, 0.0, model);
+            assert!(
+                result[2] > rgb[2],
+                "{model:?}: blue should increase when cooling"
+            );
+        }
+    }
+
+    #[test]
+    fn test_planckian_tint_moves_across_locus() {
+        let rgb = [0.5, 0.5, 0.5];
+        let magenta = balance(rgb, 0.0, 50.0, WhiteBalanceModel::Planckian);
+        let green = balance(rgb, 0.0, -50.0, WhiteBalanceModel::Planckian);
+        assert!(
+            magenta[1] < magenta[0] && magenta[1] < magenta[2],
+            "{magenta:?}"
+        );
+        assert!(green[1] > green[0] && green[1] > green[2], "{green:?}");
+    }
+
+    #[test]
+    fn test_planckian_follows_mired_scale() {
+        let (d65_kelvin, duv) = xy_to_kelvin_duv(planckian_white(0.0, 0.0));
+        let (kelvin, warm_duv) = xy_to_kelvin_duv(planckian_white(100.0, 0.0));
+        let mired = 1e6 / kelvin - 1e6 / d65_kelvin;
+        assert!((mired - 100.0 * TEMPERATURE_MIRED).abs() < 0.5, "{mired}");
+        assert!((warm_duv - duv).abs() < 1e-5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_grades_saved_without_model_load_as_legacy() {
+        use crate::transform::params::GradingParams;
+
+        let mut json = serde_json::to_value(GradingParams::default()).unwrap();
+        json.as_object_mut().unwrap().remove("white_balance_model");
+        let params: GradingParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.white_balance_model, WhiteBalanceModel::Legacy);
+        assert_eq!(
+            GradingParams::default().white_balance_model,
+            WhiteBalanceModel::Planckian
+        );
     }
 
     #[test]
     fn test_white_balance_preserves_black() {
-        let result = apply_white_balance([0.0, 0.0, 0.0], 0.5, 0.5);
-        for channel in result.iter().take(3) {
-            assert!(channel.abs() < EPSILON);
+        for model in MODELS {
+            let result = balance([0.0, 0.0, 0.0], 0.5, 0.5, model);
+            for channel in result.iter().take(3) {
+                assert!(channel.abs() < EPSILON);
+            }
         }
     }
 }
@@ -0,0 +1,108 @@
+//! Active picture area for a target aspect ratio.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rectangle in pixels; `x` and `y` are the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FrameRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl FrameRect {
+    /// The whole `width` × `height` image.
+    pub fn full(width: u32, height: u32) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: width as f32,
+            height: height as f32,
+        }
+    }
+
+    /// A rectangle of `fraction` of this one's size, centered in it.
+    pub fn inset(&self, fraction: f32) -> Self {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let (w, h) = (self.width * fraction, self.height * fraction);
+        Self {
+            x: self.x + (self.width - w) * 0.5,
+            y: self.y + (self.height - h) * 0.5,
+            width: w,
+            height: h,
+        }
+    }
+
+    /// Whether the pixel center of `(px, py)` lies inside.
+    pub fn contains_pixel(&self, px: u32, py: u32) -> bool {
+        let (cx, cy) = (px as f32 + 0.5, py as f32 + 0.5);
+        cx >= self.x && cx < self.x + self.width && cy >= self.y && cy < self.y + self.height
+    }
+}
+
+/// Largest centered rectangle of `aspect` (width / height) inside a
+/// `width` × `height` image: letterboxed when the aspect is wider than the
+/// image, pillarboxed when narrower. The whole image for `None` or an
+/// invalid aspect.
+pub fn active_area(width: u32, height: u32, aspect: Option<f32>) -> FrameRect {
+    let full = FrameRect::full(width, height);
+    let Some(aspect) = aspect.filter(|a| a.is_finite() && *a > 0.0) else {
+        return full;
+    };
+    if height == 0 {
+        return full;
+    }
+    let image_aspect = width as f32 / height as f32;
+    if aspect > image_aspect {
+        let h = width as f32 / aspect;
+        FrameRect {
+            y: (height as f32 - h) * 0.5,
+            height: h,
+            ..full
+        }
+    } else {
+        let w = height as f32 * aspect;
+        FrameRect {
+            x: (width as f32 - w) * 0.5,
+            width: w,
+            ..full
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letterbox_and_pillarbox() {
+        let scope = active_area(1920, 1080, Some(2.39));
+        assert_eq!(scope.width, 1920.0);
+        assert!((scope.height - 803.35).abs() < 0.01);
+        assert!((scope.y - 138.33).abs() < 0.01);
+
+        let square = active_area(1920, 1080, Some(1.0));
+        assert_eq!(
+            (square.x, square.width, square.height),
+            (420.0, 1080.0, 1080.0)
+        );
+
+        assert_eq!(active_area(1920, 1080, None), FrameRect::full(1920, 1080));
+        assert_eq!(
+            active_area(1920, 1080, Some(0.0)),
+            FrameRect::full(1920, 1080)
+        );
+    }
+
+    #[test]
+    fn test_inset_is_centered() {
+        let safe = FrameRect::full(1000, 500).inset(0.9);
+        assert_eq!(
+            (safe.x, safe.y, safe.width, safe.height),
+            (50.0, 25.0, 900.0, 450.0)
+        );
+    }
+}
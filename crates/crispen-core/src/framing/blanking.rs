@@ -0,0 +1,97 @@
+//! Output blanking — hard black mattes outside a delivery aspect ratio.
+//!
+//! Unlike the viewer's aspect mask in [`FramingGuides`](super::FramingGuides),
+//! blanking is part of the deliverable: exports write black outside the
+//! active area. Pixels are matted by center, so the matte edge is the same
+//! at every resolution the active area lands on whole pixels.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::aspect::{FrameRect, active_area};
+
+/// Output matte for a project or clip.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Blanking {
+    /// Deliver the full frame.
+    #[default]
+    None,
+    /// Black outside the largest centered area of this width / height.
+    Aspect(f32),
+}
+
+impl Blanking {
+    /// 2.39:1 scope.
+    pub const SCOPE: Self = Self::Aspect(2.39);
+    /// 1.85:1 flat.
+    pub const FLAT: Self = Self::Aspect(1.85);
+
+    /// Blanking to `aspect`, or none for a non-positive or non-finite one.
+    pub fn from_aspect(aspect: f32) -> Self {
+        if aspect.is_finite() && aspect > 0.0 {
+            Self::Aspect(aspect)
+        } else {
+            Self::None
+        }
+    }
+
+    /// The matte's aspect ratio, if any.
+    pub fn aspect(&self) -> Option<f32> {
+        match *self {
+            Self::None => None,
+            Self::Aspect(aspect) => Some(aspect).filter(|a| a.is_finite() && *a > 0.0),
+        }
+    }
+
+    /// Picture left unmatted in a `width` × `height` frame.
+    pub fn active_area(&self, width: u32, height: u32) -> FrameRect {
+        active_area(width, height, self.aspect())
+    }
+
+    /// Set the color of every pixel outside the active area to black.
+    /// Alpha is left untouched.
+    pub fn apply(&self, pixels: &mut [[f32; 4]], width: u32, height: u32) {
+        if self.aspect().is_none() || width == 0 {
+            return;
+        }
+        let active = self.active_area(width, height);
+        for (i, p) in pixels.iter_mut().enumerate() {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            if !active.contains_pixel(x, y) {
+                p[0] = 0.0;
+                p[1] = 0.0;
+                p[2] = 0.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_matte_rows() {
+        let mut pixels = vec![[0.5, 0.5, 0.5, 1.0]; 1920 * 1080];
+        Blanking::SCOPE.apply(&mut pixels, 1920, 1080);
+        // 1920 / 2.39 = 803.3 active lines, y 138.3–941.7 by pixel center.
+        assert_eq!(pixels[137 * 1920], [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(pixels[138 * 1920], [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(pixels[941 * 1920], [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(pixels[942 * 1920], [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_none_and_invalid_leave_frame() {
+        let mut pixels = vec![[0.5, 0.5, 0.5, 1.0]; 16];
+        Blanking::None.apply(&mut pixels, 4, 4);
+        Blanking::from_aspect(0.0).apply(&mut pixels, 4, 4);
+        Blanking::Aspect(f32::NAN).apply(&mut pixels, 4, 4);
+        assert!(pixels.iter().all(|p| *p == [0.5, 0.5, 0.5, 1.0]));
+    }
+}
@@ -0,0 +1,262 @@
+//! Safe area and framing guides.
+//!
+//! [`GuideLayer::render`] draws the enabled guides into a straight-alpha
+//! RGBA layer at image resolution. The viewer shows that layer over the
+//! graded image, and review renders composite it with
+//! [`GuideLayer::burn_into`], so both show the same pixels.
+//!
+//! Safe areas, the center cross, and the thirds are laid out inside the
+//! aspect mask's active area, since that is the picture the audience sees.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::aspect::{FrameRect, active_area};
+
+/// Action safe area as a fraction of the picture (SMPTE ST 2046-1).
+pub const ACTION_SAFE: f32 = 0.93;
+
+/// Title safe area as a fraction of the picture (SMPTE ST 2046-1).
+pub const TITLE_SAFE: f32 = 0.90;
+
+/// Guide line thickness per 1080 lines of image height, in pixels.
+const LINE_WIDTH_PER_1080: f32 = 2.0;
+
+/// Center cross arm length as a fraction of the active height.
+const CROSS_ARM: f32 = 0.04;
+
+/// Which guides to draw and how.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct FramingGuides {
+    /// Master switch; the individual toggles keep their state while off.
+    pub enabled: bool,
+    pub action_safe: bool,
+    /// Action safe size as a fraction of the active area.
+    pub action_safe_size: f32,
+    pub title_safe: bool,
+    /// Title safe size as a fraction of the active area.
+    pub title_safe_size: f32,
+    pub center_cross: bool,
+    /// Rule-of-thirds grid.
+    pub thirds: bool,
+    /// Width / height of the framing mask, e.g. 2.39; `None` for no mask.
+    pub aspect: Option<f32>,
+    /// Opacity of the mask outside the aspect, 0–1.
+    pub mask_opacity: f32,
+    /// Line color in display code values.
+    pub line_color: [f32; 3],
+    /// Line opacity, 0–1.
+    pub line_opacity: f32,
+}
+
+impl Default for FramingGuides {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action_safe: true,
+            action_safe_size: ACTION_SAFE,
+            title_safe: true,
+            title_safe_size: TITLE_SAFE,
+            center_cross: false,
+            thirds: false,
+            aspect: None,
+            mask_opacity: 0.75,
+            line_color: [1.0, 1.0, 1.0],
+            line_opacity: 0.6,
+        }
+    }
+}
+
+impl FramingGuides {
+    /// Whether anything would be drawn.
+    pub fn is_visible(&self) -> bool {
+        self.enabled
+            && (self.action_safe
+                || self.title_safe
+                || self.center_cross
+                || self.thirds
+                || (self.aspect.is_some() && self.mask_opacity > 0.0))
+    }
+}
+
+/// Rendered guides for one image size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuideLayer {
+    pub width: u32,
+    pub height: u32,
+    /// Straight-alpha RGBA in display code values, row-major.
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl GuideLayer {
+    /// Draw `guides` for a `width` × `height` image.
+    pub fn render(guides: &FramingGuides, width: u32, height: u32) -> Self {
+        let mut layer = Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width as usize * height as usize],
+        };
+        if !guides.is_visible() {
+            return layer;
+        }
+
+        let active = active_area(width, height, guides.aspect);
+        if guides.aspect.is_some() && guides.mask_opacity > 0.0 {
+            let mask = [0.0, 0.0, 0.0, guides.mask_opacity.clamp(0.0, 1.0)];
+            for y in 0..height {
+                for x in 0..width {
+                    if !active.contains_pixel(x, y) {
+                        layer.pixels[(y * width + x) as usize] = mask;
+                    }
+                }
+            }
+        }
+
+        let [r, g, b] = guides.line_color;
+        let color = [r, g, b, guides.line_opacity.clamp(0.0, 1.0)];
+        let t = (height as f32 / 1080.0 * LINE_WIDTH_PER_1080)
+            .round()
+            .max(1.0);
+        if guides.action_safe {
+            layer.outline(active.inset(guides.action_safe_size), t, color);
+        }
+        if guides.title_safe {
+            layer.outline(active.inset(guides.title_safe_size), t, color);
+        }
+        if guides.thirds {
+            for i in 1..3 {
+                let x = active.x + active.width * i as f32 / 3.0;
+                let y = active.y + active.height * i as f32 / 3.0;
+                layer.fill(x - t * 0.5, active.y, t, active.height, color);
+                layer.fill(active.x, y - t * 0.5, active.width, t, color);
+            }
+        }
+        if guides.center_cross {
+            let (cx, cy) = (
+                active.x + active.width * 0.5,
+                active.y + active.height * 0.5,
+            );
+            let arm = active.height * CROSS_ARM;
+            layer.fill(cx - arm, cy - t * 0.5, arm * 2.0, t, color);
+            layer.fill(cx - t * 0.5, cy - arm, t, arm * 2.0, color);
+        }
+        layer
+    }
+
+    /// Composite the layer over RGBA pixels of the same size. Alpha is left
+    /// untouched.
+    pub fn burn_into(&self, pixels: &mut [[f32; 4]]) {
+        for (p, g) in pixels.iter_mut().zip(&self.pixels) {
+            let a = g[3];
+            if a > 0.0 {
+                for c in 0..3 {
+                    p[c] = p[c] * (1.0 - a) + g[c] * a;
+                }
+            }
+        }
+    }
+
+    /// The layer as straight-alpha RGBA8.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|p| p.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8))
+            .collect()
+    }
+
+    /// Draw the border of `rect`, `t` pixels wide, on its inside.
+    fn outline(&mut self, rect: FrameRect, t: f32, color: [f32; 4]) {
+        let FrameRect {
+            x,
+            y,
+            width: w,
+            height: h,
+        } = rect;
+        self.fill(x, y, w, t, color);
+        self.fill(x, y + h - t, w, t, color);
+        self.fill(x, y + t, t, h - 2.0 * t, color);
+        self.fill(x + w - t, y + t, t, h - 2.0 * t, color);
+    }
+
+    /// Fill the pixels whose centers fall inside a rectangle.
+    fn fill(&mut self, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) {
+        let x0 = (x - 0.5).ceil().max(0.0) as u32;
+        let y0 = (y - 0.5).ceil().max(0.0) as u32;
+        let x1 = ((x + w - 0.5).ceil().max(0.0) as u32).min(self.width);
+        let y1 = ((y + h - 0.5).ceil().max(0.0) as u32).min(self.height);
+        for py in y0..y1 {
+            let row = (py * self.width) as usize;
+            self.pixels[row + x0 as usize..row + x1 as usize].fill(color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(layer: &GuideLayer, x: u32, y: u32) -> [f32; 4] {
+        layer.pixels[(y * layer.width + x) as usize]
+    }
+
+    #[test]
+    fn test_disabled_guides_are_transparent() {
+        let layer = GuideLayer::render(&FramingGuides::default(), 64, 36);
+        assert!(layer.pixels.iter().all(|p| p[3] == 0.0));
+    }
+
+    #[test]
+    fn test_title_safe_outline_position() {
+        let guides = FramingGuides {
+            enabled: true,
+            action_safe: false,
+            ..FramingGuides::default()
+        };
+        let layer = GuideLayer::render(&guides, 200, 100);
+        // 90% of 200×100, centered: x 10..190, y 5..95, one pixel wide.
+        assert_eq!(at(&layer, 10, 50)[3], 0.6);
+        assert_eq!(at(&layer, 189, 50)[3], 0.6);
+        assert_eq!(at(&layer, 100, 5)[3], 0.6);
+        assert_eq!(at(&layer, 100, 94)[3], 0.6);
+        assert_eq!(at(&layer, 11, 50)[3], 0.0);
+        assert_eq!(at(&layer, 100, 50)[3], 0.0);
+    }
+
+    #[test]
+    fn test_aspect_mask_covers_bars_only() {
+        let guides = FramingGuides {
+            enabled: true,
+            action_safe: false,
+            title_safe: false,
+            aspect: Some(2.0),
+            ..FramingGuides::default()
+        };
+        let layer = GuideLayer::render(&guides, 160, 100);
+        // 2:1 inside 160×100 leaves 80 active lines from y = 10.
+        assert_eq!(at(&layer, 80, 9), [0.0, 0.0, 0.0, 0.75]);
+        assert_eq!(at(&layer, 80, 10)[3], 0.0);
+        assert_eq!(at(&layer, 80, 89)[3], 0.0);
+        assert_eq!(at(&layer, 80, 90)[3], 0.75);
+    }
+
+    #[test]
+    fn test_burn_in_blends_and_keeps_alpha() {
+        let guides = FramingGuides {
+            enabled: true,
+            action_safe: false,
+            title_safe: false,
+            center_cross: true,
+            line_opacity: 0.5,
+            ..FramingGuides::default()
+        };
+        let layer = GuideLayer::render(&guides, 64, 64);
+        let mut pixels = vec![[0.2, 0.2, 0.2, 0.5]; 64 * 64];
+        layer.burn_into(&mut pixels);
+        // One pixel wide at 64 lines, crossing just above-left of center.
+        let crossing = pixels[31 * 64 + 31];
+        assert!((crossing[0] - 0.6).abs() < 1e-6, "{crossing:?}");
+        assert_eq!(crossing[3], 0.5);
+        assert_eq!(pixels[0], [0.2, 0.2, 0.2, 0.5]);
+    }
+}
@@ -0,0 +1,10 @@
+//! Framing — safe area guides, aspect ratio masks for review, and output
+//! blanking.
+
+pub mod aspect;
+pub mod blanking;
+pub mod guides;
+
+pub use aspect::{FrameRect, active_area};
+pub use blanking::Blanking;
+pub use guides::{FramingGuides, GuideLayer};
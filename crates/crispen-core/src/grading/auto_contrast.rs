@@ -0,0 +1,185 @@
+//! Histogram-based automatic contrast (levels).
+//!
+//! Finds black and white points at clipping percentiles of the graded
+//! output's histogram and folds the resulting levels stretch into the master
+//! lift / gain / offset, so the shadows and highlights just reach 0 and 1.
+//!
+//! The histogram is measured after the whole chain while the correction is
+//! applied at the CDL stage, so the fit is exact for an otherwise linear
+//! grade and approximate otherwise; running it again converges.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::scopes::HistogramData;
+use crate::transform::params::GradingParams;
+
+/// Percentiles and blend amount for [`auto_contrast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AutoContrastSettings {
+    /// Share of samples (percent) allowed to clip to black.
+    pub low_percentile: f32,
+    /// Percentile (percent) that maps to white.
+    pub high_percentile: f32,
+    /// Blend between the current grade (0) and the full correction (1).
+    pub strength: f32,
+}
+
+impl Default for AutoContrastSettings {
+    fn default() -> Self {
+        Self {
+            low_percentile: 0.1,
+            high_percentile: 99.9,
+            strength: 1.0,
+        }
+    }
+}
+
+/// Value in `[0, 1]` below which `percent` of the histogram's samples lie.
+///
+/// Bins follow the scope histograms: bin `i` starts at `i / (len − 1)`, and
+/// the last bin holds 1.0 and everything clipped above it. Interpolates
+/// linearly within the bin. Returns `None` for an empty histogram.
+pub fn histogram_percentile(bins: &[u32], percent: f32) -> Option<f32> {
+    let total: u64 = bins.iter().map(|&b| u64::from(b)).sum();
+    if total == 0 {
+        return None;
+    }
+    let target = f64::from(percent.clamp(0.0, 100.0)) / 100.0 * total as f64;
+    let mut seen = 0.0_f64;
+    for (i, &count) in bins.iter().enumerate() {
+        let next = seen + f64::from(count);
+        if next >= target && count > 0 {
+            let frac = ((target - seen) / f64::from(count)) as f32;
+            return Some(((i as f32 + frac) / (bins.len() - 1) as f32).min(1.0));
+        }
+        seen = next;
+    }
+    Some(1.0)
+}
+
+/// Measured `(black, white)` points across R, G, and B.
+///
+/// Takes the darkest low percentile and the brightest high percentile of
+/// the three channels so that no channel clips beyond the requested share.
+pub fn measure_levels(
+    histogram: &HistogramData,
+    settings: &AutoContrastSettings,
+) -> Option<(f32, f32)> {
+    let mut black = f32::MAX;
+    let mut white = f32::MIN;
+    for bins in &histogram.bins[..3] {
+        black = black.min(histogram_percentile(bins, settings.low_percentile)?);
+        white = white.max(histogram_percentile(bins, settings.high_percentile)?);
+    }
+    Some((black, white))
+}
+
+/// Stretch `params` so the histogram's clipping points map to 0 and 1.
+///
+/// Returns `params` unchanged if the histogram is empty or its range is
+/// degenerate.
+pub fn auto_contrast(
+    params: &GradingParams,
+    histogram: &HistogramData,
+    settings: &AutoContrastSettings,
+) -> GradingParams {
+    let mut out = params.clone();
+    let Some((black, white)) = measure_levels(histogram, settings) else {
+        return out;
+    };
+    // Narrower than a couple of bins is a flat frame, not a range to stretch.
+    if white - black < 2.0 / histogram.bins[0].len() as f32 {
+        return out;
+    }
+
+    // Compose levels (x − black) / (white − black) after the master CDL
+    // term x·G + L·(1 − G) + O: gain scales, and the constant becomes
+    // scale · (L·(1 − G) + O − black).
+    let scale = 1.0 / (white - black);
+    let (lift, gain, offset) = (params.lift[3], params.gain[3], params.offset[3]);
+    let new_gain = gain * scale;
+    let constant = scale * (lift * (1.0 - gain) + offset - black);
+    let (new_lift, new_offset) = if (1.0 - new_gain).abs() > 1e-3 {
+        ((constant - offset) / (1.0 - new_gain), offset)
+    } else {
+        // Lift has no effect at unity gain; carry the shift in offset.
+        (lift, constant - lift * (1.0 - new_gain))
+    };
+
+    let t = settings.strength.clamp(0.0, 1.0);
+    out.gain[3] = gain + (new_gain - gain) * t;
+    out.lift[3] = lift + (new_lift - lift) * t;
+    out.offset[3] = offset + (new_offset - offset) * t;
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::wheels::apply_cdl;
+
+    const EPSILON: f32 = 1e-4;
+
+    /// Histogram of a uniform ramp over `[lo, hi]` on all channels.
+    fn ramp_histogram(lo: f32, hi: f32) -> HistogramData {
+        let mut bins = vec![0u32; 256];
+        for i in 0..10_000 {
+            let v = lo + (hi - lo) * (i as f32 + 0.5) / 10_000.0;
+            bins[((v * 255.0) as usize).min(255)] += 1;
+        }
+        HistogramData {
+            bins: [bins.clone(), bins.clone(), bins.clone(), bins],
+            peak: 0,
+        }
+    }
+
+    #[test]
+    fn test_percentile_of_ramp() {
+        let hist = ramp_histogram(0.2, 0.8);
+        let p50 = histogram_percentile(&hist.bins[0], 50.0).unwrap();
+        assert!((p50 - 0.5).abs() < 0.01, "{p50}");
+        assert_eq!(histogram_percentile(&[0; 256], 50.0), None);
+    }
+
+    #[test]
+    fn test_full_strength_maps_levels_to_unit_range() {
+        let hist = ramp_histogram(0.2, 0.8);
+        let settings = AutoContrastSettings {
+            low_percentile: 0.0,
+            high_percentile: 100.0,
+            strength: 1.0,
+        };
+        let (black, white) = measure_levels(&hist, &settings).unwrap();
+        let p = auto_contrast(&GradingParams::default(), &hist, &settings);
+        for (input, expected) in [(black, 0.0), (white, 1.0)] {
+            let out = apply_cdl([input; 3], &p.lift, &p.gamma, &p.gain, &p.offset);
+            assert!((out[0] - expected).abs() < EPSILON, "{input} -> {out:?}");
+        }
+    }
+
+    #[test]
+    fn test_zero_strength_is_noop() {
+        let params = GradingParams {
+            gain: [1.0, 1.0, 1.0, 1.3],
+            ..GradingParams::default()
+        };
+        let settings = AutoContrastSettings {
+            strength: 0.0,
+            ..AutoContrastSettings::default()
+        };
+        let out = auto_contrast(&params, &ramp_histogram(0.1, 0.6), &settings);
+        assert_eq!(out, params);
+    }
+
+    #[test]
+    fn test_degenerate_histogram_is_noop() {
+        let params = GradingParams::default();
+        let flat = ramp_histogram(0.5, 0.5);
+        assert_eq!(
+            auto_contrast(&params, &flat, &AutoContrastSettings::default()),
+            params
+        );
+    }
+}
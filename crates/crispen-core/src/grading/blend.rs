@@ -0,0 +1,186 @@
+//! Layer blend modes and mix for combining parallel grades.
+//!
+//! A parallel grade runs several corrections on the same input and combines
+//! their outputs. Each layer is composited over the running result with a
+//! blend mode, then mixed back by its opacity ("key output gain"):
+//!
+//! ```text
+//! out = lerp(base, blend(base, layer, mode), mix)
+//! ```
+//!
+//! All modes operate per pixel in working-space linear, so a combination of
+//! layers stays a pure color transform that can be baked into one 3D LUT.
+//!
+//! Every grade carries a [`LayerBlend`] in
+//! [`GradingParams::layer_blend`](crate::transform::params::GradingParams::layer_blend):
+//! the grade's result is blended over the image it received. In a
+//! [`GradeStack`](crate::project::GradeStack) the group pre-clip, clip, and
+//! group post-clip grades are layers folded in that order.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::LUMA_REC709;
+
+/// How a layer's output combines with the result beneath it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BlendMode {
+    /// Layer replaces the base.
+    #[default]
+    Normal,
+    /// Per-channel product; darkens.
+    Multiply,
+    /// Multiply below mid-grey, screen above; boosts contrast. Expects
+    /// values around [0, 1] and extends the screen branch above 1.
+    Overlay,
+    /// Hue and saturation of the layer with the luminance of the base.
+    Color,
+}
+
+/// How a grade's result combines with the image it was applied to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LayerBlend {
+    /// Blend of the graded result over the grade's input.
+    pub mode: BlendMode,
+    /// Key output gain in [0, 1]; 0 leaves the input untouched.
+    pub mix: f32,
+}
+
+impl Default for LayerBlend {
+    /// The grade replaces its input outright.
+    fn default() -> Self {
+        Self {
+            mode: BlendMode::Normal,
+            mix: 1.0,
+        }
+    }
+}
+
+impl LayerBlend {
+    /// Whether the graded result passes through unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.mode == BlendMode::Normal && self.mix >= 1.0
+    }
+
+    /// Blend `graded` over `input`, the grade's output and input.
+    pub fn apply(&self, input: [f32; 3], graded: [f32; 3]) -> [f32; 3] {
+        if self.is_identity() {
+            return graded;
+        }
+        blend(input, graded, self.mode, self.mix)
+    }
+}
+
+/// Combine `layer` over `base` with `mode`, then mix by `mix` in [0, 1].
+///
+/// `mix = 0` returns `base`; `mix = 1` returns the full blend.
+pub fn blend(base: [f32; 3], layer: [f32; 3], mode: BlendMode, mix: f32) -> [f32; 3] {
+    let blended = match mode {
+        BlendMode::Normal => layer,
+        BlendMode::Multiply => [0, 1, 2].map(|c| base[c] * layer[c]),
+        BlendMode::Overlay => [0, 1, 2].map(|c| overlay(base[c], layer[c])),
+        BlendMode::Color => {
+            let shift = luma(base) - luma(layer);
+            layer.map(|v| v + shift)
+        }
+    };
+    let mix = mix.clamp(0.0, 1.0);
+    [0, 1, 2].map(|c| base[c] + (blended[c] - base[c]) * mix)
+}
+
+/// Fold parallel layer outputs over `base` in order.
+pub fn blend_layers(
+    base: [f32; 3],
+    layers: impl IntoIterator<Item = ([f32; 3], BlendMode, f32)>,
+) -> [f32; 3] {
+    layers
+        .into_iter()
+        .fold(base, |acc, (layer, mode, mix)| blend(acc, layer, mode, mix))
+}
+
+fn overlay(base: f32, layer: f32) -> f32 {
+    if base < 0.5 {
+        2.0 * base * layer
+    } else {
+        1.0 - 2.0 * (1.0 - base) * (1.0 - layer)
+    }
+}
+
+fn luma(rgb: [f32; 3]) -> f32 {
+    rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-6;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for c in 0..3 {
+            assert!((a[c] - b[c]).abs() < EPSILON, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_zero_mix_returns_base() {
+        let base = [0.2, 0.4, 0.6];
+        for mode in [
+            BlendMode::Normal,
+            BlendMode::Multiply,
+            BlendMode::Overlay,
+            BlendMode::Color,
+        ] {
+            assert_close(blend(base, [0.9, 0.1, 0.5], mode, 0.0), base);
+        }
+    }
+
+    #[test]
+    fn test_normal_mix_is_linear_interpolation() {
+        let out = blend([0.0, 0.2, 1.0], [1.0, 0.6, 0.0], BlendMode::Normal, 0.25);
+        assert_close(out, [0.25, 0.3, 0.75]);
+    }
+
+    #[test]
+    fn test_overlay_is_continuous_at_mid_grey() {
+        let below = overlay(0.5 - 1e-4, 0.3);
+        let above = overlay(0.5, 0.3);
+        assert!((below - above).abs() < 1e-3);
+        assert_close(blend([0.5; 3], [0.5; 3], BlendMode::Overlay, 1.0), [0.5; 3]);
+    }
+
+    #[test]
+    fn test_color_keeps_base_luminance() {
+        let base = [0.3, 0.3, 0.3];
+        let out = blend(base, [0.8, 0.2, 0.1], BlendMode::Color, 1.0);
+        assert!((luma(out) - luma(base)).abs() < EPSILON);
+        assert!(out[0] > out[1] && out[1] > out[2]);
+    }
+
+    #[test]
+    fn test_default_layer_blend_passes_grade_through() {
+        let layer = LayerBlend::default();
+        assert!(layer.is_identity());
+        assert_eq!(layer.apply([0.1; 3], [0.7, 0.2, 0.4]), [0.7, 0.2, 0.4]);
+        let half = LayerBlend {
+            mix: 0.5,
+            ..LayerBlend::default()
+        };
+        assert_close(half.apply([0.2; 3], [0.6; 3]), [0.4; 3]);
+    }
+
+    #[test]
+    fn test_layers_fold_in_order() {
+        let out = blend_layers(
+            [0.5; 3],
+            [
+                ([0.5, 0.25, 1.0], BlendMode::Multiply, 1.0),
+                ([1.0; 3], BlendMode::Normal, 0.5),
+            ],
+        );
+        assert_close(out, [0.625, 0.5625, 0.75]);
+    }
+}
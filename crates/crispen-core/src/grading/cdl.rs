@@ -0,0 +1,182 @@
+//! ASC CDL slope/offset/power/saturation conversion.
+//!
+//! Crispen's wheels are a superset of the ASC CDL: lift separates out of
+//! the offset term, and bars and wheels combine per channel and master.
+//! Collapsing them gives an exact slope/offset/power, so a grade's primaries
+//! can be handed to any CDL-aware tool. Saturation is carried across as-is;
+//! the CDL applies it with Rec. 709 weights right after power, while Crispen
+//! applies it later in the chain, so it matches only when contrast, curves,
+//! and the other sliders are neutral.
+
+use crate::grading::sliders::LUMA_REC709;
+use crate::transform::params::GradingParams;
+
+/// An ASC CDL correction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AscCdl {
+    /// Per-channel multiplier.
+    pub slope: [f32; 3],
+    /// Per-channel addition after slope.
+    pub offset: [f32; 3],
+    /// Per-channel exponent after clamping to zero.
+    pub power: [f32; 3],
+    /// Saturation around Rec. 709 luma.
+    pub saturation: f32,
+}
+
+impl Default for AscCdl {
+    fn default() -> Self {
+        Self {
+            slope: [1.0; 3],
+            offset: [0.0; 3],
+            power: [1.0; 3],
+            saturation: 1.0,
+        }
+    }
+}
+
+impl AscCdl {
+    /// The CDL equivalent of a grade's combined wheels and bars.
+    pub fn from_params(params: &GradingParams) -> Self {
+        let lift = params.combined_lift();
+        let gamma = params.combined_gamma();
+        let gain = params.combined_gain();
+        let offset = params.combined_offset();
+        let slope = [0, 1, 2].map(|c| gain[c] * gain[3]);
+        Self {
+            slope,
+            offset: [0, 1, 2]
+                .map(|c| (lift[c] + lift[3]) * (1.0 - slope[c]) + offset[c] + offset[3]),
+            power: [0, 1, 2].map(|c| {
+                let g = gamma[c] * gamma[3];
+                if g > 0.0 { 1.0 / g } else { 1.0 }
+            }),
+            saturation: params.saturation,
+        }
+    }
+
+    /// Write this CDL into a grade's bars and saturation, resetting lift
+    /// and the wheels so the grade's primaries equal the CDL.
+    pub fn apply_to_params(&self, params: &mut GradingParams) {
+        params.lift = [0.0; 4];
+        let gamma = self.power.map(|p| if p > 0.0 { 1.0 / p } else { 1.0 });
+        params.gamma = [gamma[0], gamma[1], gamma[2], 1.0];
+        params.gain = [self.slope[0], self.slope[1], self.slope[2], 1.0];
+        params.offset = [self.offset[0], self.offset[1], self.offset[2], 0.0];
+        params.lift_wheel = [0.0; 4];
+        params.gamma_wheel = [1.0; 4];
+        params.gain_wheel = [1.0; 4];
+        params.offset_wheel = [0.0; 4];
+        params.saturation = self.saturation;
+    }
+
+    /// Apply the CDL to a pixel as the ASC v1.2 specification defines it.
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let sop = [0, 1, 2].map(|c| {
+            (rgb[c] * self.slope[c] + self.offset[c])
+                .max(0.0)
+                .powf(self.power[c])
+        });
+        let luma = sop[0] * LUMA_REC709[0] + sop[1] * LUMA_REC709[1] + sop[2] * LUMA_REC709[2];
+        sop.map(|v| luma + self.saturation * (v - luma))
+    }
+
+    /// The CDL as an ASC `.cc` ColorCorrection document with id `id`.
+    pub fn to_cc(&self, id: &str) -> String {
+        let triple = |v: [f32; 3]| format!("{:.6} {:.6} {:.6}", v[0], v[1], v[2]);
+        let id = id
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <ColorCorrection id=\"{id}\">\n\
+             \x20   <SOPNode>\n\
+             \x20       <Slope>{}</Slope>\n\
+             \x20       <Offset>{}</Offset>\n\
+             \x20       <Power>{}</Power>\n\
+             \x20   </SOPNode>\n\
+             \x20   <SatNode>\n\
+             \x20       <Saturation>{:.6}</Saturation>\n\
+             \x20   </SatNode>\n\
+             </ColorCorrection>\n",
+            triple(self.slope),
+            triple(self.offset),
+            triple(self.power),
+            self.saturation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::wheels::apply_cdl;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn graded_params() -> GradingParams {
+        GradingParams {
+            lift: [0.02, -0.01, 0.0, 0.01],
+            gamma: [1.1, 1.0, 0.9, 1.0],
+            gain_wheel: [1.2, 1.0, 0.85, 1.1],
+            offset: [0.0, 0.01, -0.02, 0.0],
+            ..GradingParams::default()
+        }
+    }
+
+    #[test]
+    fn test_default_params_give_identity_cdl() {
+        assert_eq!(
+            AscCdl::from_params(&GradingParams::default()),
+            AscCdl::default()
+        );
+    }
+
+    #[test]
+    fn test_cdl_matches_crispen_wheels() {
+        let params = graded_params();
+        let cdl = AscCdl::from_params(&params);
+        for rgb in [[0.0, 0.0, 0.0], [0.18, 0.18, 0.18], [0.9, 0.4, 0.1]] {
+            let expected = apply_cdl(
+                rgb,
+                &params.combined_lift(),
+                &params.combined_gamma(),
+                &params.combined_gain(),
+                &params.combined_offset(),
+            );
+            let got = cdl.apply(rgb);
+            for c in 0..3 {
+                assert!((got[c] - expected[c]).abs() < EPSILON, "{rgb:?}: {got:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_to_params_round_trips() {
+        let cdl = AscCdl::from_params(&graded_params());
+        let mut params = GradingParams::default();
+        cdl.apply_to_params(&mut params);
+        let back = AscCdl::from_params(&params);
+        for c in 0..3 {
+            assert!((back.slope[c] - cdl.slope[c]).abs() < EPSILON);
+            assert!((back.offset[c] - cdl.offset[c]).abs() < EPSILON);
+            assert!((back.power[c] - cdl.power[c]).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_to_cc_writes_sop_and_saturation() {
+        let cdl = AscCdl {
+            slope: [1.2, 1.0, 0.8],
+            saturation: 0.9,
+            ..AscCdl::default()
+        };
+        let cc = cdl.to_cc("sh010 <v2>");
+        assert!(cc.contains("<ColorCorrection id=\"sh010 &lt;v2&gt;\">"));
+        assert!(cc.contains("        <Slope>1.200000 1.000000 0.800000</Slope>\n"));
+        assert!(cc.contains("<Power>1.000000 1.000000 1.000000</Power>"));
+        assert!(cc.contains("<Saturation>0.900000</Saturation>"));
+    }
+}
@@ -0,0 +1,135 @@
+//! Channel mixer — each output channel as a weighted mix of the input
+//! channels plus an offset.
+//!
+//! ```text
+//! R' = rr·R + rg·G + rb·B + r_offset
+//! G' = gr·R + gg·G + gb·B + g_offset
+//! B' = br·R + bg·G + bb·B + b_offset
+//! ```
+//!
+//! Used for technical repairs (a swapped or contaminated channel) and for
+//! black and white looks, where every row holds the same weights.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::LUMA_REC709;
+
+/// Rows of output weights over input R, G, B, plus an offset per output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChannelMixer {
+    /// `matrix[out][in]`. Default: identity.
+    pub matrix: [[f32; 3]; 3],
+    /// Added to each output channel. Default: `[0, 0, 0]`.
+    pub offset: [f32; 3],
+}
+
+impl Default for ChannelMixer {
+    fn default() -> Self {
+        Self {
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            offset: [0.0; 3],
+        }
+    }
+}
+
+/// Starting points for the mixer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MixerPreset {
+    /// Pass-through.
+    Identity,
+    /// Black and white from Rec. 709 luma.
+    Monochrome,
+    /// Black and white through a red filter: dark skies, light skin.
+    RedFilter,
+    /// Black and white through a green filter: lighter foliage.
+    GreenFilter,
+    /// Black and white through a blue filter: light skies, heavier skin.
+    BlueFilter,
+}
+
+impl MixerPreset {
+    /// Every preset, in menu order.
+    pub const ALL: [Self; 5] = [
+        Self::Identity,
+        Self::Monochrome,
+        Self::RedFilter,
+        Self::GreenFilter,
+        Self::BlueFilter,
+    ];
+}
+
+impl ChannelMixer {
+    /// The mixer for `preset`.
+    pub fn preset(preset: MixerPreset) -> Self {
+        let mono = |weights: [f32; 3]| Self {
+            matrix: [weights; 3],
+            offset: [0.0; 3],
+        };
+        match preset {
+            MixerPreset::Identity => Self::default(),
+            MixerPreset::Monochrome => mono(LUMA_REC709),
+            MixerPreset::RedFilter => mono([0.8, 0.2, 0.0]),
+            MixerPreset::GreenFilter => mono([0.2, 0.7, 0.1]),
+            MixerPreset::BlueFilter => mono([0.1, 0.2, 0.7]),
+        }
+    }
+
+    /// Whether the mixer passes pixels through unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Mix one pixel.
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let m = &self.matrix;
+        let mut out = [0.0_f32; 3];
+        for c in 0..3 {
+            out[c] = m[c][0] * rgb[0] + m[c][1] * rgb[1] + m[c][2] * rgb[2] + self.offset[c];
+        }
+        out
+    }
+}
+
+/// Apply the channel mixer in working-space linear; identity is skipped.
+pub fn apply_channel_mixer(rgb: [f32; 3], mixer: &ChannelMixer) -> [f32; 3] {
+    if mixer.is_identity() {
+        return rgb;
+    }
+    mixer.apply(rgb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_and_swap() {
+        let rgb = [0.1, 0.5, 0.9];
+        assert_eq!(apply_channel_mixer(rgb, &ChannelMixer::default()), rgb);
+        let swap = ChannelMixer {
+            matrix: [[0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]],
+            offset: [0.0, 0.0, 0.05],
+        };
+        assert_eq!(apply_channel_mixer(rgb, &swap), [0.9, 0.5, 0.15]);
+    }
+
+    #[test]
+    fn test_filter_presets_are_monochrome_and_keep_white() {
+        for preset in MixerPreset::ALL {
+            let mixer = ChannelMixer::preset(preset);
+            let white = mixer.apply([1.0; 3]);
+            assert!(white.iter().all(|v| (v - 1.0).abs() < 1e-4), "{preset:?}");
+        }
+        let red = ChannelMixer::preset(MixerPreset::RedFilter);
+        let blue = ChannelMixer::preset(MixerPreset::BlueFilter);
+        let sky = [0.2, 0.4, 0.8];
+        let out = red.apply(sky);
+        assert_eq!(out[0], out[1]);
+        assert_eq!(out[1], out[2]);
+        assert!(red.apply(sky)[0] < blue.apply(sky)[0]);
+    }
+}
@@ -0,0 +1,353 @@
+//! ColorChecker chart sampling and grade-to-chart matching.
+//!
+//! The user marks the four outer corners of a 24-patch chart in the image;
+//! patch centres are interpolated across the quad and averaged over a small
+//! window. A solver then fits an [`InputCorrection`] — a per-channel 1D
+//! gain/power on the neutral row followed by a least-squares 3×3 matrix over
+//! all patches — that maps the shot's chart onto the reference values in
+//! the working space.
+
+use crate::color_management::aces::apply_input_transform;
+use crate::color_management::color_space::{ColorSpaceId, get_conversion_matrix};
+use crate::image::GradingImage;
+use crate::patterns::macbeth;
+use crate::transform::params::{ColorManagementConfig, InputCorrection};
+
+/// Number of patches on the chart.
+pub const PATCH_COUNT: usize = macbeth::COLUMNS * macbeth::ROWS;
+
+/// Indices of the neutral (bottom) row used for the 1D fit.
+const NEUTRAL_PATCHES: std::ops::Range<usize> = 18..24;
+
+/// Fraction of a patch's size averaged around its centre.
+const SAMPLE_FRACTION: f32 = 0.4;
+
+/// Allowed range for the fitted per-channel power.
+const POWER_RANGE: (f32, f32) = (0.2, 5.0);
+
+/// Apply an input correction to a working-space pixel.
+///
+/// The 1D power is sign-preserving so negative (out-of-gamut) values pass
+/// through the identity correction unchanged.
+pub fn apply_input_correction(rgb: [f32; 3], correction: &InputCorrection) -> [f32; 3] {
+    if correction.is_identity() {
+        return rgb;
+    }
+    let mut shaped = [0.0; 3];
+    for c in 0..3 {
+        let v = rgb[c];
+        shaped[c] = correction.gain[c] * v.signum() * v.abs().powf(correction.power[c]);
+    }
+    let m = &correction.matrix;
+    [
+        m[0][0] * shaped[0] + m[0][1] * shaped[1] + m[0][2] * shaped[2],
+        m[1][0] * shaped[0] + m[1][1] * shaped[1] + m[1][2] * shaped[2],
+        m[2][0] * shaped[0] + m[2][1] * shaped[1] + m[2][2] * shaped[2],
+    ]
+}
+
+/// Sample the 24 patches of a chart.
+///
+/// `corners` are normalized image coordinates (0..1, origin top-left) of
+/// the chart's outer corners in the order top-left, top-right,
+/// bottom-right, bottom-left, with the dark-skin patch at top-left.
+/// Returned values are in the image's own encoding.
+pub fn sample_chart(image: &GradingImage, corners: [[f32; 2]; 4]) -> [[f32; 3]; PATCH_COUNT] {
+    let mut samples = [[0.0; 3]; PATCH_COUNT];
+    if image.width == 0 || image.height == 0 {
+        return samples;
+    }
+
+    let w = image.width as f32;
+    let h = image.height as f32;
+    let [tl, tr, br, bl] = corners.map(|c| [c[0] * w, c[1] * h]);
+
+    // Approximate patch size in pixels from the quad's mean edge lengths.
+    let dist = |a: [f32; 2], b: [f32; 2]| ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt();
+    let chart_w = (dist(tl, tr) + dist(bl, br)) * 0.5;
+    let chart_h = (dist(tl, bl) + dist(tr, br)) * 0.5;
+    let patch = (chart_w / macbeth::COLUMNS as f32).min(chart_h / macbeth::ROWS as f32);
+    let radius = ((patch * SAMPLE_FRACTION * 0.5) as i64).max(0);
+
+    for row in 0..macbeth::ROWS {
+        for col in 0..macbeth::COLUMNS {
+            let u = (col as f32 + 0.5) / macbeth::COLUMNS as f32;
+            let v = (row as f32 + 0.5) / macbeth::ROWS as f32;
+            let top = lerp2(tl, tr, u);
+            let bottom = lerp2(bl, br, u);
+            let centre = lerp2(top, bottom, v);
+            samples[row * macbeth::COLUMNS + col] = average_window(image, centre, radius);
+        }
+    }
+    samples
+}
+
+fn lerp2(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+fn average_window(image: &GradingImage, centre: [f32; 2], radius: i64) -> [f32; 3] {
+    let cx = centre[0].floor() as i64;
+    let cy = centre[1].floor() as i64;
+    let max_x = image.width as i64 - 1;
+    let max_y = image.height as i64 - 1;
+
+    let mut sum = [0.0_f64; 3];
+    let mut count = 0u32;
+    for y in (cy - radius).clamp(0, max_y)..=(cy + radius).clamp(0, max_y) {
+        for x in (cx - radius).clamp(0, max_x)..=(cx + radius).clamp(0, max_x) {
+            let px = image.pixels[(y * image.width as i64 + x) as usize];
+            for c in 0..3 {
+                sum[c] += px[c] as f64;
+            }
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return [0.0; 3];
+    }
+    sum.map(|s| (s / count as f64) as f32)
+}
+
+/// Reference chart values in the given linear working space.
+pub fn reference_values(working_space: ColorSpaceId) -> [[f32; 3]; PATCH_COUNT] {
+    let to_working = get_conversion_matrix(ColorSpaceId::LinearSrgb, working_space);
+    std::array::from_fn(|i| to_working.apply(macbeth::linear_srgb(i)))
+}
+
+/// Fit an input correction that maps `samples` onto `reference`.
+///
+/// Both sets must be linear values in the same (working) space.
+pub fn solve_chart_match(
+    samples: &[[f32; 3]; PATCH_COUNT],
+    reference: &[[f32; 3]; PATCH_COUNT],
+) -> InputCorrection {
+    let mut correction = InputCorrection::default();
+
+    // 1. Per-channel gain/power from the neutral row: fit
+    //    ln(ref) = ln(gain) + power · ln(sample) by least squares.
+    for c in 0..3 {
+        let points: Vec<(f64, f64)> = NEUTRAL_PATCHES
+            .filter(|&i| samples[i][c] > 1e-4 && reference[i][c] > 1e-4)
+            .map(|i| ((samples[i][c] as f64).ln(), (reference[i][c] as f64).ln()))
+            .collect();
+
+        if let Some((power, ln_gain)) = fit_line(&points) {
+            correction.power[c] = (power as f32).clamp(POWER_RANGE.0, POWER_RANGE.1);
+            correction.gain[c] = (ln_gain as f32).exp();
+        } else if let Some(&(x, y)) = points.first() {
+            correction.gain[c] = (y - x).exp() as f32;
+        }
+    }
+
+    // 2. 3×3 matrix over all patches after the 1D stage.
+    let shaped_only = InputCorrection {
+        matrix: InputCorrection::default().matrix,
+        ..correction.clone()
+    };
+    let shaped: Vec<[f32; 3]> = samples
+        .iter()
+        .map(|s| apply_input_correction(*s, &shaped_only))
+        .collect();
+    if let Some(matrix) = fit_matrix(&shaped, reference) {
+        correction.matrix = matrix;
+    }
+
+    correction
+}
+
+/// Sample a chart from the source image and solve for the input correction.
+///
+/// Samples are brought into the working space with the configured input
+/// transform before fitting, matching where the correction is applied in
+/// the transform chain.
+pub fn match_chart(
+    image: &GradingImage,
+    corners: [[f32; 2]; 4],
+    color_management: &ColorManagementConfig,
+) -> InputCorrection {
+    let samples = sample_chart(image, corners).map(|s| apply_input_transform(s, color_management));
+    let reference = reference_values(color_management.working_space);
+    solve_chart_match(&samples, &reference)
+}
+
+/// Ordinary least-squares line fit. Returns `(slope, intercept)`.
+fn fit_line(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if sxx < 1e-12 {
+        return None;
+    }
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let slope = sxy / sxx;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+/// Least-squares 3×3 matrix `M` minimizing `Σ |M·x − y|²`.
+fn fit_matrix(x: &[[f32; 3]], y: &[[f32; 3]]) -> Option<[[f32; 3]; 3]> {
+    // Normal equations: M = (Yᵀ X)(Xᵀ X)⁻¹.
+    let mut xtx = [[0.0_f64; 3]; 3];
+    let mut ytx = [[0.0_f64; 3]; 3];
+    for (xi, yi) in x.iter().zip(y) {
+        for r in 0..3 {
+            for c in 0..3 {
+                xtx[r][c] += xi[r] as f64 * xi[c] as f64;
+                ytx[r][c] += yi[r] as f64 * xi[c] as f64;
+            }
+        }
+    }
+
+    let inv = invert3(&xtx)?;
+    let mut m = [[0.0_f32; 3]; 3];
+    for r in 0..3 {
+        for c in 0..3 {
+            m[r][c] = (0..3).map(|k| ytx[r][k] * inv[k][c]).sum::<f64>() as f32;
+        }
+    }
+    Some(m)
+}
+
+fn invert3(m: &[[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let c00 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+    let c01 = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+    let c02 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+    let det = m[0][0] * c00 + m[0][1] * c01 + m[0][2] * c02;
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    Some([
+        [
+            c00 * inv_det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+        ],
+        [
+            c01 * inv_det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+        ],
+        [
+            c02 * inv_det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+        ],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+    use crate::patterns::{TestPattern, generate};
+
+    const EPSILON: f32 = 1e-3;
+
+    const FULL_FRAME: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+    #[test]
+    fn test_identity_correction_is_passthrough() {
+        let rgb = [0.3, -0.1, 1.7];
+        assert_eq!(
+            apply_input_correction(rgb, &InputCorrection::default()),
+            rgb
+        );
+    }
+
+    #[test]
+    fn test_sample_chart_reads_generated_patches() {
+        let img = generate(
+            TestPattern::Macbeth,
+            600,
+            400,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        let samples = sample_chart(&img, FULL_FRAME);
+        for (i, sample) in samples.iter().enumerate() {
+            let expected = macbeth::srgb_encoded(i);
+            for c in 0..3 {
+                assert!(
+                    (sample[c] - expected[c]).abs() < 1e-4,
+                    "patch {i} channel {c}: {} vs {}",
+                    sample[c],
+                    expected[c]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_solver_recovers_gain_and_matrix_distortion() {
+        let reference = reference_values(ColorSpaceId::AcesCg);
+        // Simulate a camera with a channel cross-talk matrix and exposure error.
+        let distortion = [[0.9, 0.08, 0.02], [0.05, 0.85, 0.1], [0.0, 0.12, 0.8]];
+        let samples: [[f32; 3]; PATCH_COUNT] = std::array::from_fn(|i| {
+            let r = reference[i];
+            std::array::from_fn(|row| {
+                0.7 * (distortion[row][0] * r[0]
+                    + distortion[row][1] * r[1]
+                    + distortion[row][2] * r[2])
+            })
+        });
+
+        let correction = solve_chart_match(&samples, &reference);
+        for i in 0..PATCH_COUNT {
+            let corrected = apply_input_correction(samples[i], &correction);
+            for c in 0..3 {
+                assert!(
+                    (corrected[c] - reference[i][c]).abs() < 0.01,
+                    "patch {i} channel {c}: {} vs {}",
+                    corrected[c],
+                    reference[i][c]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_chart_on_reference_chart_is_near_identity() {
+        let img = generate(
+            TestPattern::Macbeth,
+            600,
+            400,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        let cm = ColorManagementConfig::default();
+        let correction = match_chart(&img, FULL_FRAME, &cm);
+        for c in 0..3 {
+            assert!(
+                (correction.gain[c] - 1.0).abs() < 0.02,
+                "gain {:?}",
+                correction.gain
+            );
+            assert!(
+                (correction.power[c] - 1.0).abs() < 0.02,
+                "power {:?}",
+                correction.power
+            );
+            for r in 0..3 {
+                let expected = if r == c { 1.0 } else { 0.0 };
+                assert!(
+                    (correction.matrix[r][c] - expected).abs() < 0.02,
+                    "matrix {:?}",
+                    correction.matrix
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_line_recovers_slope() {
+        let points: Vec<(f64, f64)> = (0..5).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+        let (slope, intercept) = fit_line(&points).expect("fit");
+        assert!((slope - 2.0).abs() < EPSILON as f64);
+        assert!((intercept - 1.0).abs() < EPSILON as f64);
+    }
+}
@@ -0,0 +1,166 @@
+//! Chromatic aberration — radial realignment of the red and blue channels
+//! against green, and creative lens fringing.
+//!
+//! Lateral CA shows as the red and blue images being slightly larger or
+//! smaller than green around the optical center. Each output pixel samples
+//! red and blue at a radially rescaled position:
+//!
+//! ```text
+//! r²      = |p − c|² / |c|²                    (1 at the corners)
+//! mag_red = 1 + red_scale  + fringe · FRINGE_SHIFT · r²
+//! mag_blu = 1 + blue_scale − fringe · FRINGE_SHIFT · r²
+//! red(p)  = red_in(c + (p − c) / mag_red)     (bilinear)
+//! ```
+//!
+//! A negative `red_scale` shrinks a red image the lens magnified, which is
+//! the corrective use. `fringe` pulls red and blue apart toward the edges
+//! of the frame, as an uncorrected vintage lens does. Green and alpha are
+//! kept.
+//!
+//! Realignment moves pixels, so it cannot be baked into the LUT. The GPU
+//! pipeline runs it as the first pass on the source
+//! (`chromatic_aberration.wgsl`), and this module is its CPU reference.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::image::GradingImage;
+
+/// Radial shift of red and blue at the frame corners at full fringe.
+pub const FRINGE_SHIFT: f32 = 0.01;
+
+/// Chromatic aberration settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChromaticAberration {
+    /// Magnification of the red channel relative to green, −0.02 to 0.02.
+    /// Default: 0.
+    pub red_scale: f32,
+    /// Magnification of the blue channel relative to green, −0.02 to 0.02.
+    /// Default: 0.
+    pub blue_scale: f32,
+    /// Creative fringing, growing toward the corners, 0–1. Default: 0.
+    pub fringe: f32,
+}
+
+impl ChromaticAberration {
+    /// Whether the pass can be skipped.
+    pub fn is_identity(&self) -> bool {
+        self.red_scale == 0.0 && self.blue_scale == 0.0 && self.fringe == 0.0
+    }
+
+    /// Red and blue magnification at normalized squared radius `r2`.
+    pub fn magnification(&self, r2: f32) -> (f32, f32) {
+        let fringe = self.fringe * FRINGE_SHIFT * r2;
+        (
+            1.0 + self.red_scale + fringe,
+            1.0 + self.blue_scale - fringe,
+        )
+    }
+}
+
+/// Realign `image` in place.
+pub fn apply_chromatic_aberration(image: &mut GradingImage, ca: &ChromaticAberration) {
+    let (width, height) = (image.width, image.height);
+    if ca.is_identity() || width == 0 || height == 0 {
+        return;
+    }
+    let src = image.pixels.clone();
+    let (cx, cy) = (width as f32 * 0.5, height as f32 * 0.5);
+    let norm = 1.0 / (cx * cx + cy * cy);
+
+    // Bilinear sample of channel `ch` at continuous position `(sx, sy)`,
+    // with edges clamped.
+    let sample = |ch: usize, sx: f32, sy: f32| {
+        let fx = (sx - 0.5).clamp(0.0, (width - 1) as f32);
+        let fy = (sy - 0.5).clamp(0.0, (height - 1) as f32);
+        let (x0, y0) = (fx.floor() as u32, fy.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+        let at = |x: u32, y: u32| src[(y * width + x) as usize][ch];
+        let top = at(x0, y0) + (at(x1, y0) - at(x0, y0)) * tx;
+        let bottom = at(x0, y1) + (at(x1, y1) - at(x0, y1)) * tx;
+        top + (bottom - top) * ty
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let (mag_red, mag_blue) = ca.magnification((dx * dx + dy * dy) * norm);
+            let p = &mut image.pixels[(y * width + x) as usize];
+            p[0] = sample(0, cx + dx / mag_red, cy + dy / mag_red);
+            p[2] = sample(2, cx + dx / mag_blue, cy + dy / mag_blue);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    /// A soft white ring on black, the same in every channel.
+    fn ring() -> GradingImage {
+        let (width, height) = (64, 48);
+        let pixels = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as f32 - 31.5, (i / width) as f32 - 23.5);
+                let r = (x * x + y * y).sqrt();
+                let v = (-((r - 19.0) / 3.0).powi(2)).exp();
+                [v, v, v, 1.0]
+            })
+            .collect();
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_default_is_identity() {
+        let mut img = ring();
+        let before = img.pixels.clone();
+        apply_chromatic_aberration(&mut img, &ChromaticAberration::default());
+        assert_eq!(img.pixels, before);
+    }
+
+    #[test]
+    fn test_correction_undoes_magnification() {
+        // A red channel 2% larger than green, as a lens would render it.
+        let mut img = ring();
+        let magnified = ChromaticAberration {
+            red_scale: 0.02,
+            ..Default::default()
+        };
+        apply_chromatic_aberration(&mut img, &magnified);
+        let misaligned: f32 = img.pixels.iter().map(|p| (p[0] - p[1]).abs()).sum();
+        assert!(img.pixels.iter().all(|p| p[3] == 1.0));
+
+        let correction = ChromaticAberration {
+            red_scale: 1.0 / 1.02 - 1.0,
+            ..Default::default()
+        };
+        apply_chromatic_aberration(&mut img, &correction);
+        let residual: f32 = img.pixels.iter().map(|p| (p[0] - p[1]).abs()).sum();
+        assert!(residual < misaligned * 0.5, "{residual} vs {misaligned}");
+    }
+
+    #[test]
+    fn test_fringe_splits_red_and_blue_toward_edges() {
+        let ca = ChromaticAberration {
+            fringe: 1.0,
+            ..Default::default()
+        };
+        let (center_red, center_blue) = ca.magnification(0.0);
+        assert_eq!((center_red, center_blue), (1.0, 1.0));
+        let (red, blue) = ca.magnification(1.0);
+        assert!(red > 1.0 && blue < 1.0);
+
+        let mut img = ring();
+        apply_chromatic_aberration(&mut img, &ca);
+        assert!(img.pixels.iter().any(|p| (p[0] - p[2]).abs() > 0.01));
+    }
+}
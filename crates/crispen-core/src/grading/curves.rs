@@ -11,12 +11,31 @@
 //! q(t) = 0.5 × ((2×P1) + (-P0 + P2)×t + (2×P0 - 5×P1 + 4×P2 - P3)×t² + (-P0 + 3×P1 - 3×P2 + P3)×t³)
 //! ```
 //!
+//! The GPU bake joins points with straight segments unless
+//! [`CurveInterpolation::Smooth`] is selected; [`evaluate_curve`] is the
+//! shared entry point so editors draw exactly what is baked.
+//!
 //! # Complexity
 //! - Evaluate: O(log N) binary search + O(1) interpolation
 //! - Bake to 1D LUT: O(N × size)
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::{SaturationModel, scale_chroma};
 use crate::transform::params::GradingParams;
 
+/// How curve control points are joined when a curve is baked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CurveInterpolation {
+    /// Straight segments between control points.
+    #[default]
+    Linear,
+    /// Catmull-Rom spline through the control points (see [`CurveEvaluator`]).
+    Smooth,
+}
+
 /// Evaluates cubic Catmull-Rom spline curves from control points.
 ///
 /// Control points are `[x, y]` pairs sorted by x-coordinate.
@@ -91,6 +110,44 @@ impl CurveEvaluator<'_> {
     }
 }
 
+/// Evaluate control points sorted by x at `t` with the given interpolation.
+///
+/// Values outside the control point range are clamped to the first/last
+/// control point's y-value. Returns `t` (identity) without control points;
+/// a single point is a constant under either interpolation.
+pub fn evaluate_curve(points: &[[f32; 2]], t: f32, interpolation: CurveInterpolation) -> f32 {
+    match interpolation {
+        CurveInterpolation::Smooth if points.len() >= 2 => CurveEvaluator {
+            control_points: points,
+        }
+        .evaluate(t),
+        _ => evaluate_linear(points, t),
+    }
+}
+
+fn evaluate_linear(points: &[[f32; 2]], t: f32) -> f32 {
+    let Some(first) = points.first() else {
+        return t;
+    };
+    if t <= first[0] {
+        return first[1];
+    }
+    let last = points[points.len() - 1];
+    if t >= last[0] {
+        return last[1];
+    }
+    for pair in points.windows(2) {
+        let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+        if t >= x0 && t <= x1 {
+            if (x1 - x0).abs() < 1e-10 {
+                return y1;
+            }
+            return y0 + (t - x0) / (x1 - x0) * (y1 - y0);
+        }
+    }
+    last[1]
+}
+
 /// Catmull-Rom cubic interpolation between P1 and P2.
 ///
 /// ```text
@@ -119,9 +176,7 @@ pub fn bake_curve_to_1d_lut(control_points: &[[f32; 2]], size: usize) -> Vec<f32
             .collect();
     }
 
-    let evaluator = CurveEvaluator {
-        control_points,
-    };
+    let evaluator = CurveEvaluator { control_points };
 
     (0..size)
         .map(|i| {
@@ -139,14 +194,10 @@ pub fn bake_curve_to_1d_lut(control_points: &[[f32; 2]], size: usize) -> Vec<f32
 /// - **lum_vs_sat**: adjusts saturation based on input luminance
 /// - **sat_vs_sat**: adjusts saturation based on input saturation
 ///
-/// Empty control point vectors produce no adjustment (identity).
+/// Empty control point vectors, or bypassed curves, produce no adjustment
+/// (identity).
 pub fn apply_curves(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
-    let no_curves = params.hue_vs_hue.is_empty()
-        && params.hue_vs_sat.is_empty()
-        && params.lum_vs_sat.is_empty()
-        && params.sat_vs_sat.is_empty();
-
-    if no_curves {
+    if !params.has_active_curves() {
         return rgb;
     }
 
@@ -194,12 +245,27 @@ pub fn apply_curves(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
         sat_mult *= eval.evaluate(sat) / sat.max(1e-10);
     }
 
-    let out_sat = (sat * sat_mult).clamp(0.0, 1.0);
-    hsl_to_rgb(out_hue, out_sat, lum)
+    if params.saturation_model == SaturationModel::Luma {
+        let out_sat = (sat * sat_mult).clamp(0.0, 1.0);
+        return hsl_to_rgb(out_hue, out_sat, lum);
+    }
+    // Perceptual models key the curves on HSL as above but scale chroma in
+    // their own space, so the hue the curves picked is kept.
+    let rotated = if out_hue == hue {
+        rgb
+    } else {
+        hsl_to_rgb(out_hue, sat, lum)
+    };
+    scale_chroma(
+        rotated,
+        sat_mult,
+        params.saturation_model,
+        params.color_management.working_space,
+    )
 }
 
 /// Convert RGB to HSL (hue in degrees, saturation and lightness in 0..1).
-fn rgb_to_hsl(rgb: [f32; 3]) -> (f32, f32, f32) {
+pub(crate) fn rgb_to_hsl(rgb: [f32; 3]) -> (f32, f32, f32) {
     let r = rgb[0];
     let g = rgb[1];
     let b = rgb[2];
@@ -320,6 +386,28 @@ mod tests {
         assert!((lut[255] - 1.0).abs() < EPSILON);
     }
 
+    #[test]
+    fn test_evaluate_curve_linear_and_smooth() {
+        let points = [[0.0, 0.0], [0.5, 1.0], [1.0, 0.0]];
+        let linear = evaluate_curve(&points, 0.25, CurveInterpolation::Linear);
+        assert!((linear - 0.5).abs() < EPSILON);
+
+        // The spline overshoots the straight segment toward the peak.
+        let smooth = evaluate_curve(&points, 0.25, CurveInterpolation::Smooth);
+        let eval = CurveEvaluator {
+            control_points: &points,
+        };
+        assert!((smooth - eval.evaluate(0.25)).abs() < EPSILON);
+        assert!(smooth > linear);
+
+        for interpolation in [CurveInterpolation::Linear, CurveInterpolation::Smooth] {
+            assert!((evaluate_curve(&points, 0.5, interpolation) - 1.0).abs() < EPSILON);
+            assert!((evaluate_curve(&points, -1.0, interpolation) - 0.0).abs() < EPSILON);
+            assert!((evaluate_curve(&[], 0.3, interpolation) - 0.3).abs() < EPSILON);
+            assert!((evaluate_curve(&[[0.5, 0.2]], 0.9, interpolation) - 0.2).abs() < EPSILON);
+        }
+    }
+
     #[test]
     fn test_apply_curves_empty_is_identity() {
         let params = GradingParams::default();
@@ -328,6 +416,18 @@ mod tests {
         assert_eq!(result, rgb);
     }
 
+    #[test]
+    fn test_apply_curves_bypassed_is_identity() {
+        let mut params = GradingParams {
+            hue_vs_sat: vec![[0.0, 0.2], [1.0, 0.2]],
+            ..GradingParams::default()
+        };
+        let rgb = [0.5, 0.3, 0.7];
+        assert_ne!(apply_curves(rgb, &params), rgb);
+        params.bypass.curves = true;
+        assert_eq!(apply_curves(rgb, &params), rgb);
+    }
+
     #[test]
     fn test_hsl_roundtrip_preserves_values() {
         let original = [0.8, 0.4, 0.2];
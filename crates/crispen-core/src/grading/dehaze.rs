@@ -0,0 +1,228 @@
+//! Dehaze — contrast recovery under atmospheric haze, after the dark
+//! channel prior.
+//!
+//! In a haze-free region at least one channel is close to zero, so a
+//! bright minimum channel over a neighborhood is read as haze:
+//!
+//! ```text
+//! dark(x) = box_blur(min_filter(min(R, G, B)))
+//! A       = max over the image of min_filter(min(R, G, B))
+//! t(x)    = max(1 − strength · (dark / A)^(2^depth_bias), MIN_TRANSMISSION)
+//! out     = (in − A) / t + A
+//! ```
+//!
+//! Haze is taken as neutral, so `A` is a single gray level. Negative
+//! strength adds haze instead. The operator needs a neighborhood, so it
+//! cannot be baked into the LUT: the GPU pipeline runs it as a separate
+//! pass on the source before the LUT is applied (`dehaze.wgsl`), and this
+//! module is its CPU reference.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::image::GradingImage;
+
+/// Radius in pixels of the minimum filter and of the smoothing box blur.
+pub const PATCH_RADIUS: u32 = 7;
+/// Floor on the transmission, so dense haze is not amplified into noise.
+pub const MIN_TRANSMISSION: f32 = 0.1;
+/// Floor on the estimated haze level, for images with no bright regions.
+pub const MIN_AIRLIGHT: f32 = 0.05;
+
+/// Dehaze settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Dehaze {
+    /// Haze removed, −1 (adds haze) to 1. Default: 0 (off).
+    pub strength: f32,
+    /// Where the effect concentrates, −1 to 1: positive limits it to the
+    /// densest (farthest) haze, negative spreads it into light haze.
+    /// Default: 0.
+    pub depth_bias: f32,
+}
+
+impl Dehaze {
+    /// Whether the pass can be skipped.
+    pub fn is_identity(&self) -> bool {
+        self.strength == 0.0
+    }
+
+    /// Exponent applied to the normalized haze estimate.
+    pub fn haze_exponent(&self) -> f32 {
+        self.depth_bias.exp2()
+    }
+
+    /// Transmission for a smoothed dark-channel value `dark` under haze
+    /// level `airlight`.
+    pub fn transmission(&self, dark: f32, airlight: f32) -> f32 {
+        let haze = (dark / airlight).clamp(0.0, 1.0).powf(self.haze_exponent());
+        (1.0 - self.strength * haze).max(MIN_TRANSMISSION)
+    }
+}
+
+/// Dehaze `image` in place. Alpha is kept.
+pub fn apply_dehaze(image: &mut GradingImage, dehaze: &Dehaze) {
+    let (width, height) = (image.width as usize, image.height as usize);
+    if dehaze.is_identity() || width == 0 || height == 0 {
+        return;
+    }
+    let radius = PATCH_RADIUS as usize;
+    let min_rgb: Vec<f32> = image
+        .pixels
+        .iter()
+        .map(|p| p[0].min(p[1]).min(p[2]).max(0.0))
+        .collect();
+
+    let rows = filter(&min_rgb, width, height, radius, true, Window::Min);
+    let eroded = filter(&rows, width, height, radius, false, Window::Min);
+    let airlight = eroded
+        .iter()
+        .copied()
+        .fold(0.0_f32, f32::max)
+        .max(MIN_AIRLIGHT);
+    let rows = filter(&eroded, width, height, radius, true, Window::Mean);
+    let dark = filter(&rows, width, height, radius, false, Window::Mean);
+
+    for (px, d) in image.pixels.iter_mut().zip(dark) {
+        let t = dehaze.transmission(d, airlight);
+        for c in &mut px[..3] {
+            *c = (*c - airlight) / t + airlight;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Window {
+    Min,
+    Mean,
+}
+
+/// One separable pass over rows (`horizontal`) or columns, with edge
+/// samples clamped.
+fn filter(
+    src: &[f32],
+    width: usize,
+    height: usize,
+    radius: usize,
+    horizontal: bool,
+    window: Window,
+) -> Vec<f32> {
+    let mut out = vec![0.0; src.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (pos, len) = if horizontal { (x, width) } else { (y, height) };
+            let lo = pos.saturating_sub(radius);
+            let hi = (pos + radius).min(len - 1);
+            let at = |i: usize| {
+                if horizontal {
+                    src[y * width + i]
+                } else {
+                    src[i * width + x]
+                }
+            };
+            out[y * width + x] = match window {
+                Window::Min => (lo..=hi).map(at).fold(f32::INFINITY, f32::min),
+                Window::Mean => {
+                    // Clamped edges repeat the border sample.
+                    let sum: f32 = (0..=2 * radius)
+                        .map(|k| at((pos + k).saturating_sub(radius).min(len - 1)))
+                        .sum();
+                    sum / (2 * radius + 1) as f32
+                }
+            };
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    fn image(pixels: Vec<[f32; 4]>, width: u32) -> GradingImage {
+        let height = pixels.len() as u32 / width;
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    fn spread(img: &GradingImage) -> f32 {
+        let g: Vec<f32> = img.pixels.iter().map(|p| p[1]).collect();
+        g.iter().copied().fold(f32::MIN, f32::max) - g.iter().copied().fold(f32::MAX, f32::min)
+    }
+
+    /// A green-to-red gradient under a uniform gray veil.
+    fn hazy() -> GradingImage {
+        let pixels = (0..32 * 8)
+            .map(|i| {
+                let x = (i % 32) as f32 / 31.0;
+                let veil = 0.4;
+                [x * 0.5 + veil, (1.0 - x) * 0.5 + veil, veil, 1.0]
+            })
+            .collect();
+        image(pixels, 32)
+    }
+
+    #[test]
+    fn test_zero_strength_is_identity() {
+        let mut img = hazy();
+        let before = img.pixels.clone();
+        apply_dehaze(&mut img, &Dehaze::default());
+        assert_eq!(img.pixels, before);
+    }
+
+    #[test]
+    fn test_dehaze_restores_contrast() {
+        let mut img = hazy();
+        let before = spread(&img);
+        apply_dehaze(
+            &mut img,
+            &Dehaze {
+                strength: 0.8,
+                depth_bias: 0.0,
+            },
+        );
+        assert!(spread(&img) > before * 1.5, "{} vs {before}", spread(&img));
+        assert!(img.pixels.iter().all(|p| p[3] == 1.0));
+
+        let mut added = hazy();
+        apply_dehaze(
+            &mut added,
+            &Dehaze {
+                strength: -0.8,
+                depth_bias: 0.0,
+            },
+        );
+        assert!(spread(&added) < before);
+    }
+
+    #[test]
+    fn test_haze_free_pixels_are_kept() {
+        // Saturated primaries have a zero dark channel everywhere.
+        let pixels = (0..16 * 16)
+            .map(|i| match i % 3 {
+                0 => [0.8, 0.0, 0.0, 1.0],
+                1 => [0.0, 0.6, 0.0, 1.0],
+                _ => [0.0, 0.0, 0.7, 1.0],
+            })
+            .collect::<Vec<_>>();
+        let mut img = image(pixels.clone(), 16);
+        apply_dehaze(
+            &mut img,
+            &Dehaze {
+                strength: 1.0,
+                depth_bias: 0.0,
+            },
+        );
+        for (a, b) in img.pixels.iter().zip(&pixels) {
+            for c in 0..3 {
+                assert!((a[c] - b[c]).abs() < 1e-5, "{a:?} vs {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Exposure — a trim in stops applied before the grade.
+//!
+//! Two controls sit at the head of the chain:
+//!
+//! ```text
+//! code'   = code + log_offset              before the input transform
+//! linear' = linear · 2^stops               right after it, in the working space
+//! ```
+//!
+//! `stops` is a scene-linear exposure change: +1 doubles the light, like
+//! opening the lens a stop or doubling ISO, and every later tool sees the
+//! brighter image. `log_offset` shifts the camera's log code values
+//! instead, the way a printer-light or log offset trim works on film scans
+//! and log footage. On a pure log curve the two agree; near a curve's toe
+//! the offset keeps the camera's own shoulder and toe shape. Gain on the
+//! wheels works on the graded image, so neither replaces it.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Input exposure trims.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Exposure {
+    /// Scene-linear exposure in stops. Default: 0.
+    pub stops: f32,
+    /// Offset added to source code values before the input transform.
+    /// Default: 0.
+    pub log_offset: f32,
+}
+
+impl Exposure {
+    /// Whether both trims are off.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Linear multiplier of `stops`.
+    pub fn scale(&self) -> f32 {
+        self.stops.exp2()
+    }
+}
+
+/// Add the log offset to source code values.
+pub fn apply_log_offset(rgb: [f32; 3], exposure: &Exposure) -> [f32; 3] {
+    rgb.map(|v| v + exposure.log_offset)
+}
+
+/// Scale a working-space linear pixel by the exposure in stops.
+pub fn apply_exposure(rgb: [f32; 3], exposure: &Exposure) -> [f32; 3] {
+    let scale = exposure.scale();
+    rgb.map(|v| v * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stops_are_powers_of_two() {
+        let exposure = Exposure {
+            stops: 1.0,
+            ..Exposure::default()
+        };
+        assert_eq!(
+            apply_exposure([0.18, 0.09, 0.0], &exposure),
+            [0.36, 0.18, 0.0]
+        );
+        let under = Exposure {
+            stops: -2.0,
+            ..Exposure::default()
+        };
+        assert_eq!(apply_exposure([1.0; 3], &under), [0.25; 3]);
+    }
+
+    #[test]
+    fn test_identity_leaves_pixels_unchanged() {
+        let rgb = [0.2, 0.5, 0.8];
+        let exposure = Exposure::default();
+        assert!(exposure.is_identity());
+        assert_eq!(apply_exposure(rgb, &exposure), rgb);
+        assert_eq!(apply_log_offset(rgb, &exposure), rgb);
+    }
+}
@@ -0,0 +1,268 @@
+//! Glow — a post-grade bloom around bright areas.
+//!
+//! Works on the graded output, after the LUT:
+//!
+//! ```text
+//! bright = rgb · max(luma − threshold, 0) / luma
+//! level₀ = down(bright),  levelᵢ₊₁ = down(levelᵢ)        (half size each)
+//! levelᵢ += spread · up(levelᵢ₊₁)                       (coarsest first)
+//! out    = rgb + intensity · tint · up(level₀) / Σ spreadᵏ
+//! ```
+//!
+//! `down` is a [1 3 3 1] binomial filter at half resolution and `up` is
+//! bilinear, so the pyramid approximates a wide Gaussian at a fraction of
+//! the cost. Spread weights the coarse levels: 0 keeps a tight halo, 1
+//! spreads it across the frame. The sum in the last step keeps the glow's
+//! energy independent of spread.
+//!
+//! Glow needs a neighborhood of pixels, so it cannot be baked into the LUT.
+//! The GPU pipeline runs it as a separate pass on the graded output
+//! (`glow.wgsl`), and this module is its CPU reference.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::LUMA_REC709;
+use crate::image::GradingImage;
+
+/// Most pyramid levels; fewer are used when the image gets to 1×1 sooner.
+pub const GLOW_LEVELS: usize = 6;
+/// Per-axis weights of the downsampling filter, over source pixels
+/// `2x − 1 ..= 2x + 2`.
+pub const DOWN_WEIGHTS: [f32; 4] = [0.125, 0.375, 0.375, 0.125];
+
+/// Glow settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Glow {
+    /// Output luma above which pixels glow, 0–1. Default: 0.8.
+    pub threshold: f32,
+    /// Weight of the coarser pyramid levels, 0–1. Default: 0.5.
+    pub spread: f32,
+    /// Amount of glow added, 0–2. Default: 0 (off).
+    pub intensity: f32,
+    /// Color multiplied into the glow. Default: white.
+    pub tint: [f32; 3],
+}
+
+impl Default for Glow {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            spread: 0.5,
+            intensity: 0.0,
+            tint: [1.0; 3],
+        }
+    }
+}
+
+impl Glow {
+    /// Whether the pass can be skipped.
+    pub fn is_identity(&self) -> bool {
+        self.intensity <= 0.0
+    }
+
+    /// The part of `rgb` above the threshold, keeping its color.
+    pub fn bright(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let luma = rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2];
+        let k = (luma - self.threshold).max(0.0) / luma.max(1e-4);
+        rgb.map(|c| c * k)
+    }
+
+    /// Multiplier on the finest level when it is added back:
+    /// `intensity / Σ spreadᵏ` over `levels` levels.
+    pub fn composite_scale(&self, levels: usize) -> f32 {
+        let norm: f32 = (0..levels).map(|k| self.spread.powi(k as i32)).sum();
+        self.intensity / norm.max(1e-6)
+    }
+}
+
+/// Sizes of the pyramid levels for a `width × height` image, finest first.
+pub fn level_sizes(width: u32, height: u32) -> Vec<(u32, u32)> {
+    let mut sizes = Vec::with_capacity(GLOW_LEVELS);
+    let (mut w, mut h) = (width, height);
+    while sizes.len() < GLOW_LEVELS && (w > 1 || h > 1) {
+        w = w.div_ceil(2);
+        h = h.div_ceil(2);
+        sizes.push((w, h));
+    }
+    sizes
+}
+
+/// Add glow to `image` in place. Alpha is kept.
+pub fn apply_glow(image: &mut GradingImage, glow: &Glow) {
+    let sizes = level_sizes(image.width, image.height);
+    if glow.is_identity() || sizes.is_empty() {
+        return;
+    }
+    let full = Level {
+        width: image.width,
+        height: image.height,
+        pixels: image
+            .pixels
+            .iter()
+            .map(|p| glow.bright([p[0], p[1], p[2]]))
+            .collect(),
+    };
+    let mut levels: Vec<Level> = Vec::with_capacity(sizes.len());
+    for &(w, h) in &sizes {
+        let src = levels.last().unwrap_or(&full);
+        levels.push(src.down(w, h));
+    }
+    for i in (0..levels.len() - 1).rev() {
+        let (fine, coarse) = levels.split_at_mut(i + 1);
+        let fine = &mut fine[i];
+        for y in 0..fine.height {
+            for x in 0..fine.width {
+                let up = coarse[0].up(x, y, fine.width, fine.height);
+                let p = &mut fine.pixels[(y * fine.width + x) as usize];
+                for c in 0..3 {
+                    p[c] += glow.spread * up[c];
+                }
+            }
+        }
+    }
+    let scale = glow.composite_scale(levels.len());
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let up = levels[0].up(x, y, image.width, image.height);
+            let p = &mut image.pixels[(y * image.width + x) as usize];
+            for c in 0..3 {
+                p[c] += scale * glow.tint[c] * up[c];
+            }
+        }
+    }
+}
+
+/// One pyramid level.
+struct Level {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 3]>,
+}
+
+impl Level {
+    fn at(&self, x: i64, y: i64) -> [f32; 3] {
+        let x = x.clamp(0, i64::from(self.width) - 1);
+        let y = y.clamp(0, i64::from(self.height) - 1);
+        self.pixels[(y * i64::from(self.width) + x) as usize]
+    }
+
+    /// Binomial downsample to `width × height`.
+    fn down(&self, width: u32, height: u32) -> Level {
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in 0..i64::from(height) {
+            for x in 0..i64::from(width) {
+                let mut sum = [0.0_f32; 3];
+                for (j, wy) in DOWN_WEIGHTS.iter().enumerate() {
+                    for (i, wx) in DOWN_WEIGHTS.iter().enumerate() {
+                        let s = self.at(2 * x - 1 + i as i64, 2 * y - 1 + j as i64);
+                        for c in 0..3 {
+                            sum[c] += wx * wy * s[c];
+                        }
+                    }
+                }
+                pixels.push(sum);
+            }
+        }
+        Level {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Bilinear sample for pixel `(x, y)` of a `width × height` target.
+    fn up(&self, x: u32, y: u32, width: u32, height: u32) -> [f32; 3] {
+        let fx = ((x as f32 + 0.5) * self.width as f32 / width as f32 - 0.5).max(0.0);
+        let fy = ((y as f32 + 0.5) * self.height as f32 / height as f32 - 0.5).max(0.0);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let [a, b, c, d] = [
+            self.at(x0, y0),
+            self.at(x0 + 1, y0),
+            self.at(x0, y0 + 1),
+            self.at(x0 + 1, y0 + 1),
+        ];
+        std::array::from_fn(|k| {
+            let top = a[k] + (b[k] - a[k]) * tx;
+            let bottom = c[k] + (d[k] - c[k]) * tx;
+            top + (bottom - top) * ty
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    /// A dark frame with one bright square in the middle.
+    fn spot() -> GradingImage {
+        let (width, height) = (40, 30);
+        let pixels = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let lit = (18..22).contains(&x) && (13..17).contains(&y);
+                let v = if lit { 1.0 } else { 0.1 };
+                [v, v, v, 1.0]
+            })
+            .collect();
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_level_sizes() {
+        assert_eq!(level_sizes(40, 30)[..3], [(20, 15), (10, 8), (5, 4)]);
+        assert_eq!(level_sizes(4000, 3000).len(), GLOW_LEVELS);
+        assert!(level_sizes(1, 1).is_empty());
+    }
+
+    #[test]
+    fn test_off_or_below_threshold_is_identity() {
+        let mut img = spot();
+        let before = img.pixels.clone();
+        apply_glow(&mut img, &Glow::default());
+        assert_eq!(img.pixels, before);
+        let mut img = spot();
+        let glow = Glow {
+            threshold: 1.0,
+            intensity: 1.0,
+            ..Glow::default()
+        };
+        apply_glow(&mut img, &glow);
+        assert_eq!(img.pixels, before);
+    }
+
+    #[test]
+    fn test_glow_spreads_around_bright_area() {
+        let glow = Glow {
+            threshold: 0.5,
+            intensity: 1.0,
+            tint: [1.0, 0.5, 0.25],
+            ..Glow::default()
+        };
+        let mut img = spot();
+        apply_glow(&mut img, &glow);
+        let near = img.pixels[15 * 40 + 24];
+        let far = img.pixels[2 * 40 + 2];
+        assert!(near[0] > 0.1 && near[0] > far[0], "{near:?} {far:?}");
+        assert!(near[0] > near[1] && near[1] > near[2], "{near:?}");
+        assert!(img.pixels.iter().all(|p| p[3] == 1.0));
+
+        let wide = Glow {
+            spread: 1.0,
+            ..glow
+        };
+        let mut wide_img = spot();
+        apply_glow(&mut wide_img, &wide);
+        assert!(wide_img.pixels[2 * 40 + 2][0] > far[0]);
+    }
+}
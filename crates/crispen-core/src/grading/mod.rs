@@ -1,6 +1,19 @@
 //! Grading tools — wheels, sliders, curves, and automatic adjustments.
 
 pub mod auto_balance;
+#[cfg(feature = "scopes")]
+pub mod auto_contrast;
+pub mod blend;
+pub mod cdl;
+pub mod channel_mixer;
+pub mod chart_match;
+pub mod chromatic_aberration;
 pub mod curves;
+pub mod dehaze;
+pub mod exposure;
+pub mod glow;
+pub mod monochrome;
+pub mod pixel_repair;
 pub mod sliders;
+pub mod soft_clip;
 pub mod wheels;
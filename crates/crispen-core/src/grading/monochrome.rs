@@ -0,0 +1,182 @@
+//! Black and white mode — channel-weighted luminance, a film response
+//! curve, and an optional paper tint.
+//!
+//! ```text
+//! y   = (wr·R + wg·G + wb·B) / (wr + wg + wb)
+//! y'  = 0.18 · (y / 0.18)^contrast          (y > 0)
+//! out = y' · mix(1, tint / luma(tint), tint_strength)
+//! ```
+//!
+//! The weights stand in for a film's spectral sensitivity and are
+//! normalized so white stays white. The tint is normalized to unit luma,
+//! so toning changes color but not brightness. Unlike saturation 0 this is
+//! a separate switch, so a color grade underneath is kept for when the
+//! mode is turned off.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::LUMA_REC709;
+
+/// Linear mid gray the response curve pivots on.
+const RESPONSE_PIVOT: f32 = 0.18;
+
+/// Black and white conversion settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Monochrome {
+    /// Whether the image is converted. Default: false.
+    pub enabled: bool,
+    /// Channel weights over R, G, B; normalized to sum 1 when applied.
+    /// Default: Rec. 709 luma.
+    pub weights: [f32; 3],
+    /// Response curve exponent around 18% gray, 0.5–2. Default: 1.
+    pub contrast: f32,
+    /// Paper or toning color, linear RGB. Default: white.
+    pub tint: [f32; 3],
+    /// How much of the tint is applied, 0–1. Default: 0.
+    pub tint_strength: f32,
+}
+
+impl Default for Monochrome {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weights: LUMA_REC709,
+            contrast: 1.0,
+            tint: [1.0; 3],
+            tint_strength: 0.0,
+        }
+    }
+}
+
+/// Classic black and white film responses: channel weights plus contrast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FilmResponse {
+    /// Rec. 709 luma, straight response.
+    Neutral,
+    /// General-purpose panchromatic stock: near-visual weights, a little
+    /// extra contrast.
+    Panchromatic,
+    /// Orthochromatic stock: blind to red, so skin and lips go dark.
+    Orthochromatic,
+    /// Infrared stock: red-dominated, foliage glows and skies go black.
+    Infrared,
+    /// High-contrast document / lith look.
+    HighContrast,
+}
+
+impl FilmResponse {
+    /// Every response, in menu order.
+    pub const ALL: [Self; 5] = [
+        Self::Neutral,
+        Self::Panchromatic,
+        Self::Orthochromatic,
+        Self::Infrared,
+        Self::HighContrast,
+    ];
+
+    /// Channel weights and response contrast for this stock.
+    pub fn weights_and_contrast(self) -> ([f32; 3], f32) {
+        match self {
+            Self::Neutral => (LUMA_REC709, 1.0),
+            Self::Panchromatic => ([0.30, 0.59, 0.11], 1.1),
+            Self::Orthochromatic => ([0.0, 0.45, 0.55], 1.2),
+            Self::Infrared => ([0.8, 0.3, -0.1], 1.3),
+            Self::HighContrast => ([0.25, 0.65, 0.10], 1.6),
+        }
+    }
+}
+
+impl Monochrome {
+    /// These settings with `response`'s weights and contrast; enables the
+    /// conversion and keeps the tint.
+    pub fn with_response(self, response: FilmResponse) -> Self {
+        let (weights, contrast) = response.weights_and_contrast();
+        Self {
+            enabled: true,
+            weights,
+            contrast,
+            ..self
+        }
+    }
+
+    /// Weights scaled to sum 1; left as they are when the sum is near 0.
+    pub fn normalized_weights(&self) -> [f32; 3] {
+        let sum = self.weights.iter().sum::<f32>();
+        if sum.abs() < 1e-6 {
+            self.weights
+        } else {
+            self.weights.map(|w| w / sum)
+        }
+    }
+
+    /// Per-channel gain applied to the gray value for the tint.
+    pub fn tint_gain(&self) -> [f32; 3] {
+        let t = self.tint;
+        let luma = t[0] * LUMA_REC709[0] + t[1] * LUMA_REC709[1] + t[2] * LUMA_REC709[2];
+        if luma <= 1e-6 {
+            return [1.0; 3];
+        }
+        let strength = self.tint_strength.clamp(0.0, 1.0);
+        t.map(|c| 1.0 + (c / luma - 1.0) * strength)
+    }
+
+    /// The response curve on one gray value.
+    pub fn response(&self, y: f32) -> f32 {
+        if y <= 0.0 || self.contrast == 1.0 {
+            y
+        } else {
+            RESPONSE_PIVOT * (y / RESPONSE_PIVOT).powf(self.contrast)
+        }
+    }
+}
+
+/// Convert to black and white in working-space linear; off is skipped.
+pub fn apply_monochrome(rgb: [f32; 3], mono: &Monochrome) -> [f32; 3] {
+    if !mono.enabled {
+        return rgb;
+    }
+    let w = mono.normalized_weights();
+    let y = mono.response(w[0] * rgb[0] + w[1] * rgb[1] + w[2] * rgb[2]);
+    mono.tint_gain().map(|g| y * g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_is_identity_and_white_stays_white() {
+        let rgb = [0.1, 0.5, 0.9];
+        assert_eq!(apply_monochrome(rgb, &Monochrome::default()), rgb);
+        for response in FilmResponse::ALL {
+            let mono = Monochrome::default().with_response(response);
+            let white = apply_monochrome([1.0; 3], &mono);
+            let out = apply_monochrome(rgb, &mono);
+            assert!(out[0] == out[1] && out[1] == out[2], "{response:?}");
+            let gray = apply_monochrome([RESPONSE_PIVOT; 3], &mono);
+            assert!((gray[0] - RESPONSE_PIVOT).abs() < 1e-5, "{response:?}");
+            assert!(white[0] >= 1.0 - 1e-5, "{response:?}");
+        }
+        let ortho = Monochrome::default().with_response(FilmResponse::Orthochromatic);
+        let red = apply_monochrome([0.5, 0.0, 0.0], &ortho);
+        assert_eq!(red[0], 0.0);
+    }
+
+    #[test]
+    fn test_tint_keeps_luma() {
+        let mono = Monochrome {
+            enabled: true,
+            tint: [1.0, 0.85, 0.65],
+            tint_strength: 1.0,
+            ..Monochrome::default()
+        };
+        let out = apply_monochrome([0.4; 3], &mono);
+        let luma = out[0] * LUMA_REC709[0] + out[1] * LUMA_REC709[1] + out[2] * LUMA_REC709[2];
+        assert!((luma - 0.4).abs() < 1e-5, "{out:?}");
+        assert!(out[0] > out[1] && out[1] > out[2]);
+    }
+}
@@ -0,0 +1,249 @@
+//! Dead-pixel and dust repair — inpainting marked pixels before the grade.
+//!
+//! A [`DefectMap`] lists defects as disks in source pixel coordinates:
+//! hot or dead photosites (radius 0) and dust or scratches on a scan. The
+//! map is rasterized to a mask, and every masked pixel is interpolated
+//! from the nearest unmasked pixels along four axes — horizontal,
+//! vertical, and both diagonals:
+//!
+//! ```text
+//! axis  = (a · d_b + b · d_a) / (d_a + d_b)     a, b: nearest clean pixel each way
+//! out   = Σ axis · w / Σ w,   w = 1 / (d_a + d_b)²
+//! ```
+//!
+//! Interpolating across the defect keeps gradients running through it,
+//! and an axis with a clean pixel on one side only counts as if the gap
+//! were twice as wide. Searching from each pixel rather than from the
+//! defect's center lets touching defects be repaired from their shared
+//! surroundings. The GPU pipeline runs the same search as the first pass on the source
+//! (`pixel_repair.wgsl`), and this module is its CPU reference.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::image::GradingImage;
+
+/// Farthest step searched for a clean pixel along each axis. Pixels with
+/// none in reach are kept.
+pub const MAX_SEARCH_RADIUS: u32 = 32;
+
+/// One marked defect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Defect {
+    /// Center column in source pixels.
+    pub x: u32,
+    /// Center row in source pixels.
+    pub y: u32,
+    /// Disk radius in pixels; 0 marks a single pixel.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub radius: u32,
+}
+
+/// Defects of one camera, sensor, or scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DefectMap {
+    /// Marked defects, in the order they were found or added.
+    pub defects: Vec<Defect>,
+}
+
+impl DefectMap {
+    /// Whether there is nothing to repair.
+    pub fn is_empty(&self) -> bool {
+        self.defects.is_empty()
+    }
+
+    /// Mark a point by hand.
+    pub fn mark(&mut self, x: u32, y: u32, radius: u32) {
+        self.defects.push(Defect { x, y, radius });
+    }
+
+    /// Rasterize to a row-major `width × height` mask, `true` where a
+    /// pixel is to be repaired.
+    pub fn mask(&self, width: u32, height: u32) -> Vec<bool> {
+        let mut mask = vec![false; (width as usize) * (height as usize)];
+        for d in &self.defects {
+            let r = i64::from(d.radius);
+            let (cx, cy) = (i64::from(d.x), i64::from(d.y));
+            for y in (cy - r).max(0)..=(cy + r).min(i64::from(height) - 1) {
+                for x in (cx - r).max(0)..=(cx + r).min(i64::from(width) - 1) {
+                    if (x - cx).pow(2) + (y - cy).pow(2) <= r * r {
+                        mask[(y * i64::from(width) + x) as usize] = true;
+                    }
+                }
+            }
+        }
+        mask
+    }
+}
+
+/// Find hot pixels in a frame shot with the lens capped: pixels whose
+/// brightest channel is more than `threshold` above the median of their
+/// eight neighbors.
+pub fn detect_hot_pixels(black_frame: &GradingImage, threshold: f32) -> DefectMap {
+    let (width, height) = (black_frame.width, black_frame.height);
+    let level = |x: u32, y: u32| {
+        let p = black_frame.pixels[(y * width + x) as usize];
+        p[0].max(p[1]).max(p[2])
+    };
+    let mut map = DefectMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            let mut neighbors = Vec::with_capacity(8);
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    if (nx, ny) != (x, y) {
+                        neighbors.push(level(nx, ny));
+                    }
+                }
+            }
+            if neighbors.is_empty() {
+                continue;
+            }
+            neighbors.sort_by(f32::total_cmp);
+            if level(x, y) - neighbors[neighbors.len() / 2] > threshold {
+                map.mark(x, y, 0);
+            }
+        }
+    }
+    map
+}
+
+/// Axes searched from a masked pixel; each is walked both ways.
+const AXES: [(i64, i64); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+/// Inpaint the pixels `map` marks in `image`, in place. Alpha is kept.
+pub fn repair_pixels(image: &mut GradingImage, map: &DefectMap) {
+    let (width, height) = (image.width, image.height);
+    if map.is_empty() || width == 0 || height == 0 {
+        return;
+    }
+    let mask = map.mask(width, height);
+    let src = &image.pixels.clone();
+    let (w, h) = (i64::from(width), i64::from(height));
+    // First clean pixel and its distance in steps from (x, y) along (dx, dy).
+    let nearest = |x: i64, y: i64, dx: i64, dy: i64| {
+        (1..=i64::from(MAX_SEARCH_RADIUS)).find_map(|step| {
+            let (sx, sy) = (x + dx * step, y + dy * step);
+            if sx < 0 || sy < 0 || sx >= w || sy >= h {
+                return Some(None);
+            }
+            let j = (sy * w + sx) as usize;
+            (!mask[j]).then_some(Some((src[j], step as f32)))
+        })?
+    };
+    for (i, _) in mask.iter().enumerate().filter(|(_, masked)| **masked) {
+        let (x, y) = (i as i64 % w, i as i64 / w);
+        let mut sum = [0.0_f32; 3];
+        let mut weight = 0.0_f32;
+        for (dx, dy) in AXES {
+            let (value, span) = match (nearest(x, y, dx, dy), nearest(x, y, -dx, -dy)) {
+                (Some((a, da)), Some((b, db))) => (
+                    std::array::from_fn(|c| (a[c] * db + b[c] * da) / (da + db)),
+                    da + db,
+                ),
+                (Some((a, d)), None) | (None, Some((a, d))) => ([a[0], a[1], a[2]], 2.0 * d),
+                (None, None) => continue,
+            };
+            let wgt = 1.0 / (span * span);
+            for (s, v) in sum.iter_mut().zip(value) {
+                *s += wgt * v;
+            }
+            weight += wgt;
+        }
+        if weight > 0.0 {
+            for (out, s) in image.pixels[i].iter_mut().zip(sum) {
+                *out = s / weight;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    fn image(width: u32, height: u32, f: impl Fn(u32, u32) -> f32) -> GradingImage {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let v = f(i % width, i / width);
+                [v, v, v, 1.0]
+            })
+            .collect();
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_detect_hot_pixels_on_black_frame() {
+        let frame = image(16, 12, |x, y| match (x, y) {
+            (3, 4) => 0.8,
+            (15, 0) => 0.3,
+            _ => 0.01,
+        });
+        let map = detect_hot_pixels(&frame, 0.1);
+        assert_eq!(
+            map.defects,
+            [
+                Defect {
+                    x: 15,
+                    y: 0,
+                    radius: 0
+                },
+                Defect {
+                    x: 3,
+                    y: 4,
+                    radius: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mask_rasterizes_disks() {
+        let mut map = DefectMap::default();
+        map.mark(2, 2, 1);
+        map.mark(0, 0, 0);
+        let mask = map.mask(5, 5);
+        assert_eq!(mask.iter().filter(|m| **m).count(), 6);
+        assert!(mask[0] && mask[2 * 5 + 2] && mask[5 + 2] && !mask[5 + 1]);
+    }
+
+    #[test]
+    fn test_repair_fills_from_surroundings() {
+        // A horizontal ramp with a dust spot and two touching dead pixels.
+        let ramp = |x: u32, _| x as f32 / 20.0;
+        let mut img = image(21, 15, |x, y| {
+            if (x as i32 - 10).pow(2) + (y as i32 - 7).pow(2) <= 4
+                || (y == 1 && (4..6).contains(&x))
+            {
+                0.0
+            } else {
+                ramp(x, y)
+            }
+        });
+        let mut map = DefectMap::default();
+        map.mark(10, 7, 2);
+        map.mark(4, 1, 0);
+        map.mark(5, 1, 0);
+
+        let untouched = img.pixels[0];
+        repair_pixels(&mut img, &map);
+        for (x, y) in [(10, 7), (9, 6), (12, 7), (4, 1), (5, 1)] {
+            let p = img.pixels[(y * 21 + x) as usize];
+            assert!((p[0] - ramp(x, y)).abs() < 0.03, "({x}, {y}): {p:?}");
+            assert_eq!(p[3], 1.0);
+        }
+        assert_eq!(img.pixels[0], untouched);
+
+        let before = img.pixels.clone();
+        repair_pixels(&mut img, &DefectMap::default());
+        assert_eq!(img.pixels, before);
+    }
+}
@@ -1,10 +1,109 @@
-//! Slider-based grading adjustments (contrast, shadows/highlights, saturation, hue).
+//! Slider-based grading adjustments (input levels, contrast,
+//! shadows/highlights, vibrance, saturation, hue).
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::color_management::color_space::{rgb_to_xyz_matrix, xyz_to_rgb_matrix};
+use crate::color_management::perceptual::{
+    izazbz_to_xyz, oklab_to_xyz, xyz_to_izazbz, xyz_to_oklab,
+};
+use crate::grading::curves::rgb_to_hsl;
+use crate::transform::params::ColorSpaceId;
 
 /// Rec. 709 luminance weights.
-const LUMA_REC709: [f32; 3] = [0.2126, 0.7152, 0.0722];
+pub(crate) const LUMA_REC709: [f32; 3] = [0.2126, 0.7152, 0.0722];
 
 /// Equal-weight luminance.
-const LUMA_EQUAL: [f32; 3] = [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0];
+pub(crate) const LUMA_EQUAL: [f32; 3] = [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0];
+
+/// Which source code values are black and white, for sources whose range
+/// flag is missing or wrong.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputRange {
+    /// Code values 0–1 are black to white.
+    #[default]
+    Full,
+    /// Legal range: 10-bit 64–940 (8-bit 16–235) are black to white.
+    Video,
+    /// Cineon printer density: 10-bit 95–685 are black to white.
+    PrinterDensity,
+}
+
+impl InputRange {
+    /// Normalized code values of black and white.
+    pub fn black_white(self) -> (f32, f32) {
+        match self {
+            Self::Full => (0.0, 1.0),
+            Self::Video => (64.0 / 1023.0, 940.0 / 1023.0),
+            Self::PrinterDensity => (95.0 / 1023.0, 685.0 / 1023.0),
+        }
+    }
+}
+
+/// Source range interpretation and per-channel black/white points, applied
+/// to code values before the input transform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InputLevels {
+    /// How the source's code values are interpreted. Default: full range.
+    pub range: InputRange,
+    /// Per-channel black point within the range, 0–1. Default: `[0, 0, 0]`.
+    pub black: [f32; 3],
+    /// Per-channel white point within the range, 0–1. Default: `[1, 1, 1]`.
+    pub white: [f32; 3],
+}
+
+impl Default for InputLevels {
+    fn default() -> Self {
+        Self {
+            range: InputRange::Full,
+            black: [0.0; 3],
+            white: [1.0; 3],
+        }
+    }
+}
+
+impl InputLevels {
+    /// Whether these levels leave code values unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The black and white code values per channel once the range and the
+    /// black/white points are combined. The GPU bake uses these directly.
+    pub fn code_values(&self) -> ([f32; 3], [f32; 3]) {
+        let (lo, hi) = self.range.black_white();
+        let span = hi - lo;
+        (
+            self.black.map(|b| lo + b * span),
+            self.white.map(|w| lo + w * span),
+        )
+    }
+}
+
+/// Map code values so each channel's black point lands on 0 and its white
+/// point on 1.
+///
+/// ```text
+/// out = (in − black) / max(white − black, 1e-6)
+/// ```
+///
+/// Values outside the points are not clipped, so super-whites and
+/// sub-blacks stay recoverable. Default levels produce no change.
+pub fn apply_input_levels(rgb: [f32; 3], levels: &InputLevels) -> [f32; 3] {
+    if levels.is_identity() {
+        return rgb;
+    }
+    let (black, white) = levels.code_values();
+    let mut out = [0.0_f32; 3];
+    for c in 0..3 {
+        out[c] = (rgb[c] - black[c]) / (white[c] - black[c]).max(1e-6);
+    }
+    out
+}
 
 /// Apply contrast with pivot point.
 ///
@@ -109,6 +208,192 @@ pub fn apply_saturation_hue(rgb: [f32; 3], saturation: f32, hue: f32, luma_mix:
     ]
 }
 
+/// Space in which saturation and the saturation curves scale chroma.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SaturationModel {
+    /// RGB distance from luma, YCbCr-style. Cheap, but large changes shift
+    /// hue and brightness.
+    #[default]
+    Luma,
+    /// OKLab `a`/`b` at constant `L`.
+    OkLab,
+    /// JzAzBz `az`/`bz` at constant `Iz`; holds up better on HDR values.
+    JzAzBz,
+}
+
+/// Scale chroma by `factor` in `model`, keeping its lightness and hue.
+///
+/// `working_space` gives the primaries of `rgb`, which the perceptual
+/// models convert through CIE XYZ D65. [`SaturationModel::Luma`] ignores it
+/// and mixes toward Rec. 709 luma.
+pub fn scale_chroma(
+    rgb: [f32; 3],
+    factor: f32,
+    model: SaturationModel,
+    working_space: ColorSpaceId,
+) -> [f32; 3] {
+    if (factor - 1.0).abs() < 1e-7 {
+        return rgb;
+    }
+    type Convert = fn([f32; 3]) -> [f32; 3];
+    let (to_lab, from_lab): (Convert, Convert) = match model {
+        SaturationModel::Luma => {
+            let luma = rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2];
+            return rgb.map(|v| luma + (v - luma) * factor);
+        }
+        SaturationModel::OkLab => (xyz_to_oklab, oklab_to_xyz),
+        SaturationModel::JzAzBz => (xyz_to_izazbz, izazbz_to_xyz),
+    };
+    // Scale away from the gray of the same luminance rather than from
+    // a = b = 0, which is slightly off D65 white in JzAzBz.
+    let to_xyz = rgb_to_xyz_matrix(working_space);
+    let xyz = to_xyz.apply(rgb);
+    let [_, gray_a, gray_b] = to_lab(to_xyz.apply([xyz[1]; 3]));
+    let [l, a, b] = to_lab(xyz);
+    let a = gray_a + (a - gray_a) * factor;
+    let b = gray_b + (b - gray_b) * factor;
+    xyz_to_rgb_matrix(working_space).apply(from_lab([l, a, b]))
+}
+
+/// Center of the protected skin-tone hue wedge, in degrees.
+pub const SKIN_HUE: f32 = 25.0;
+/// Half width of the skin-tone wedge; protection fades out over it.
+pub const SKIN_HALF_WIDTH: f32 = 25.0;
+
+/// Vibrance: saturation that favors muted colors and spares skin.
+///
+/// ```text
+/// sat  = (max − min) / max                      (HSV saturation, 0–1)
+/// skin = 1 − smoothstep(0, SKIN_HALF_WIDTH, |hue − SKIN_HUE|)
+/// gain = 1 + vibrance × (1 − sat) × (1 − skin)
+/// ```
+///
+/// Chroma is then scaled by `gain` with [`scale_chroma`] in `model`, so
+/// vibrance keeps hue the same way the saturation slider does.
+/// `vibrance = 0.0` produces no change.
+pub fn apply_vibrance(
+    rgb: [f32; 3],
+    vibrance: f32,
+    model: SaturationModel,
+    working_space: ColorSpaceId,
+) -> [f32; 3] {
+    if vibrance.abs() < 1e-7 {
+        return rgb;
+    }
+    let max = rgb[0].max(rgb[1]).max(rgb[2]);
+    let min = rgb[0].min(rgb[1]).min(rgb[2]);
+    if max <= 1e-6 {
+        return rgb;
+    }
+    let sat = ((max - min) / max).clamp(0.0, 1.0);
+    let (hue, _, _) = rgb_to_hsl(rgb);
+    let distance = ((hue - SKIN_HUE + 180.0).rem_euclid(360.0) - 180.0).abs();
+    let t = (distance / SKIN_HALF_WIDTH).clamp(0.0, 1.0);
+    let skin = 1.0 - t * t * (3.0 - 2.0 * t);
+    let gain = 1.0 + vibrance * (1.0 - sat) * (1.0 - skin);
+    scale_chroma(rgb, gain, model, working_space)
+}
+
+/// Plane the hue rotation turns in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HueModel {
+    /// Around the RGB neutral axis. Hues bunch up and lightness shifts as
+    /// they turn.
+    #[default]
+    Rgb,
+    /// OKLCh hue angle at constant lightness and chroma.
+    OkLch,
+}
+
+/// Hue rotation settings beyond the master `hue` angle.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HueRotation {
+    /// Plane the rotation turns in. Default: RGB.
+    pub model: HueModel,
+    /// Extra rotation in degrees for shadows, midtones, and highlights,
+    /// blended by luma. Default: `[0, 0, 0]`.
+    pub ranges: [f32; 3],
+}
+
+impl HueRotation {
+    /// Total rotation in degrees for a pixel: `hue` plus the range offsets
+    /// weighted by [`tonal_weights`].
+    pub fn degrees(&self, rgb: [f32; 3], hue: f32) -> f32 {
+        if self.ranges == [0.0; 3] {
+            return hue;
+        }
+        let luma = rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2];
+        let w = tonal_weights(luma);
+        hue + w[0] * self.ranges[0] + w[1] * self.ranges[1] + w[2] * self.ranges[2]
+    }
+}
+
+/// Shadow, midtone, and highlight weights for `luma`, summing to 1.
+///
+/// ```text
+/// shadows    = 1 − smoothstep(0, 0.5, luma)
+/// highlights = smoothstep(0.5, 1, luma)
+/// midtones   = 1 − shadows − highlights
+/// ```
+pub fn tonal_weights(luma: f32) -> [f32; 3] {
+    let smoothstep = |lo: f32, hi: f32, x: f32| {
+        let t = ((x - lo) / (hi - lo)).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+    let shadows = 1.0 - smoothstep(0.0, 0.5, luma);
+    let highlights = smoothstep(0.5, 1.0, luma);
+    [shadows, 1.0 - shadows - highlights, highlights]
+}
+
+/// Rotate the OKLCh hue of `rgb` by `degrees`, keeping OKLab lightness and
+/// chroma. Like [`scale_chroma`], turns around the gray of the same
+/// luminance.
+pub fn rotate_hue_oklch(rgb: [f32; 3], degrees: f32, working_space: ColorSpaceId) -> [f32; 3] {
+    if degrees.abs() < 1e-7 {
+        return rgb;
+    }
+    let to_xyz = rgb_to_xyz_matrix(working_space);
+    let xyz = to_xyz.apply(rgb);
+    let [_, gray_a, gray_b] = xyz_to_oklab(to_xyz.apply([xyz[1]; 3]));
+    let [l, a, b] = xyz_to_oklab(xyz);
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (a, b) = (a - gray_a, b - gray_b);
+    let lab = [l, gray_a + a * cos - b * sin, gray_b + a * sin + b * cos];
+    xyz_to_rgb_matrix(working_space).apply(oklab_to_xyz(lab))
+}
+
+/// Saturation and hue rotation with the models and tonal ranges chosen.
+///
+/// With [`SaturationModel::Luma`], [`HueModel::Rgb`], and no range
+/// offsets this is exactly [`apply_saturation_hue`]. Otherwise hue is
+/// rotated first, then chroma is scaled.
+pub fn apply_saturation(
+    rgb: [f32; 3],
+    saturation: f32,
+    hue: f32,
+    rotation: &HueRotation,
+    luma_mix: f32,
+    model: SaturationModel,
+    working_space: ColorSpaceId,
+) -> [f32; 3] {
+    let degrees = rotation.degrees(rgb, hue);
+    let rotated = match rotation.model {
+        HueModel::Rgb if model == SaturationModel::Luma => {
+            return apply_saturation_hue(rgb, saturation, degrees, luma_mix);
+        }
+        HueModel::Rgb => apply_saturation_hue(rgb, 1.0, degrees, luma_mix),
+        HueModel::OkLch => rotate_hue_oklch(rgb, degrees, working_space),
+    };
+    match model {
+        SaturationModel::Luma => apply_saturation_hue(rotated, saturation, 0.0, luma_mix),
+        _ => scale_chroma(rotated, saturation, model, working_space),
+    }
+}
+
 /// Rotate the chrominance vector by `degrees` around the luminance axis.
 ///
 /// Uses the Rodrigues rotation formula in the plane perpendicular to (1,1,1).
@@ -142,6 +427,124 @@ mod tests {
 
     const EPSILON: f32 = 1e-5;
 
+    #[test]
+    fn test_oklab_saturation_keeps_lightness_and_hue() {
+        let space = ColorSpaceId::AcesCg;
+        let rgb = [0.6, 0.3, 0.1];
+        let lab = |rgb| xyz_to_oklab(rgb_to_xyz_matrix(space).apply(rgb));
+        let rotation = HueRotation::default();
+        let out = apply_saturation(rgb, 2.0, 0.0, &rotation, 0.0, SaturationModel::OkLab, space);
+        let (before, after) = (lab(rgb), lab(out));
+        assert!((before[0] - after[0]).abs() < 1e-4);
+        assert!((after[1] / before[1] - 2.0).abs() < 1e-2);
+        assert!((after[2] / before[2] - 2.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_vibrance_favors_muted_colors_and_spares_skin() {
+        let chroma =
+            |rgb: [f32; 3]| rgb[0].max(rgb[1]).max(rgb[2]) - rgb[0].min(rgb[1]).min(rgb[2]);
+        let gain = |rgb: [f32; 3]| {
+            let out = apply_vibrance(rgb, 1.0, SaturationModel::Luma, ColorSpaceId::AcesCg);
+            chroma(out) / chroma(rgb)
+        };
+        let muted_blue = [0.35, 0.4, 0.5];
+        let vivid_blue = [0.05, 0.1, 0.5];
+        let skin = [0.6, 0.42, 0.32];
+        assert!(gain(muted_blue) > gain(vivid_blue));
+        assert!(gain(vivid_blue) > 1.0);
+        assert!((gain(skin) - 1.0).abs() < 0.05, "{}", gain(skin));
+        assert_eq!(
+            apply_vibrance(muted_blue, 0.0, SaturationModel::Luma, ColorSpaceId::AcesCg),
+            muted_blue
+        );
+    }
+
+    #[test]
+    fn test_oklch_rotation_keeps_lightness_and_chroma() {
+        let space = ColorSpaceId::LinearSrgb;
+        let rgb = [0.5, 0.2, 0.1];
+        let lab = |rgb| xyz_to_oklab(rgb_to_xyz_matrix(space).apply(rgb));
+        let (before, after) = (lab(rgb), lab(rotate_hue_oklch(rgb, 90.0, space)));
+        assert!((before[0] - after[0]).abs() < 1e-4);
+        let chroma = |lab: [f32; 3]| lab[1].hypot(lab[2]);
+        assert!((chroma(before) - chroma(after)).abs() < 2e-3);
+        // A quarter turn puts the new opponent vector at right angles.
+        assert!((before[1] * after[1] + before[2] * after[2]).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_hue_ranges_follow_tonal_weights() {
+        let rotation = HueRotation {
+            ranges: [30.0, 0.0, -30.0],
+            ..HueRotation::default()
+        };
+        assert_eq!(rotation.degrees([0.0; 3], 5.0), 35.0);
+        assert_eq!(rotation.degrees([1.0; 3], 5.0), -25.0);
+        assert!((rotation.degrees([0.5; 3], 5.0) - 5.0).abs() < 1e-5);
+        for luma in [0.0, 0.2, 0.5, 0.8, 1.2] {
+            let w = tonal_weights(luma);
+            assert!((w[0] + w[1] + w[2] - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_perceptual_zero_saturation_is_neutral() {
+        for model in [SaturationModel::OkLab, SaturationModel::JzAzBz] {
+            let out = scale_chroma([0.6, 0.3, 0.1], 0.0, model, ColorSpaceId::LinearSrgb);
+            assert!(
+                (out[0] - out[1]).abs() < 1e-3 && (out[1] - out[2]).abs() < 1e-3,
+                "{model:?} {out:?}"
+            );
+        }
+        let rgb = [0.6, 0.3, 0.1];
+        assert_eq!(
+            apply_saturation(
+                rgb,
+                1.5,
+                10.0,
+                &HueRotation::default(),
+                0.2,
+                SaturationModel::Luma,
+                ColorSpaceId::AcesCg
+            ),
+            apply_saturation_hue(rgb, 1.5, 10.0, 0.2)
+        );
+    }
+
+    #[test]
+    fn test_video_range_maps_legal_to_full() {
+        let levels = InputLevels {
+            range: InputRange::Video,
+            ..InputLevels::default()
+        };
+        let black = apply_input_levels([64.0 / 1023.0; 3], &levels);
+        let white = apply_input_levels([940.0 / 1023.0; 3], &levels);
+        for c in 0..3 {
+            assert!(black[c].abs() < EPSILON, "{black:?}");
+            assert!((white[c] - 1.0).abs() < EPSILON, "{white:?}");
+        }
+        // Super-white is kept above 1, not clipped.
+        assert!(apply_input_levels([1.0; 3], &levels)[0] > 1.0);
+    }
+
+    #[test]
+    fn test_per_channel_points_within_range() {
+        let levels = InputLevels {
+            range: InputRange::Full,
+            black: [0.1, 0.0, 0.0],
+            white: [1.0, 0.5, 1.0],
+        };
+        let out = apply_input_levels([0.55, 0.25, 0.3], &levels);
+        assert!((out[0] - 0.5).abs() < EPSILON);
+        assert!((out[1] - 0.5).abs() < EPSILON);
+        assert!((out[2] - 0.3).abs() < EPSILON);
+        assert_eq!(
+            apply_input_levels([0.3, 0.6, 0.9], &InputLevels::default()),
+            [0.3, 0.6, 0.9]
+        );
+    }
+
     #[test]
     fn test_contrast_at_pivot_is_identity() {
         let pivot = 0.435;
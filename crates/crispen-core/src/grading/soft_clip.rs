@@ -0,0 +1,149 @@
+//! Highlight soft clip and shadow toe — roll values off smoothly instead of
+//! letting the output encode clip them hard.
+//!
+//! Above the knee, highlights are compressed by
+//!
+//! ```text
+//! out = knee + d / (1 + strength · d / (1 − knee)),   d = in − knee
+//! ```
+//!
+//! which meets the straight line at the knee with the same slope and, at
+//! full strength, approaches 1.0 without ever reaching it. The toe mirrors
+//! this below the toe point, approaching 0.0 instead of going negative.
+//! Strength 0 leaves the curve straight.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::LUMA_REC709;
+
+/// What the rolloff is measured on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SoftClipMode {
+    /// Each channel rolls off on its own; bright saturated colors drift
+    /// toward white, like film.
+    #[default]
+    PerChannel,
+    /// Rec.709 luma rolls off and all three channels are scaled by the same
+    /// ratio, keeping hue and saturation.
+    Luma,
+}
+
+/// Highlight rolloff and shadow toe settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SoftClip {
+    /// Level where highlight compression starts, 0–1. Default: 0.8.
+    pub knee: f32,
+    /// Highlight compression, 0 (off) to 1 (asymptotic to 1.0). Default: 0.
+    pub strength: f32,
+    /// Level where shadow compression starts, 0–0.5. Default: 0.05.
+    pub toe: f32,
+    /// Shadow compression, 0 (off) to 1 (asymptotic to 0.0). Default: 0.
+    pub toe_strength: f32,
+    /// Per-channel or luma rolloff. Default: per channel.
+    pub mode: SoftClipMode,
+}
+
+impl Default for SoftClip {
+    fn default() -> Self {
+        Self {
+            knee: 0.8,
+            strength: 0.0,
+            toe: 0.05,
+            toe_strength: 0.0,
+            mode: SoftClipMode::PerChannel,
+        }
+    }
+}
+
+impl SoftClip {
+    /// Whether both the rolloff and the toe are off.
+    pub fn is_identity(&self) -> bool {
+        self.strength <= 0.0 && self.toe_strength <= 0.0
+    }
+
+    /// Apply the rolloff and toe to one value.
+    pub fn curve(&self, v: f32) -> f32 {
+        let knee = self.knee.clamp(0.0, 1.0 - 1e-4);
+        let toe = self.toe.clamp(1e-4, knee);
+        if v > knee && self.strength > 0.0 {
+            let d = v - knee;
+            knee + d / (1.0 + self.strength * d / (1.0 - knee))
+        } else if v < toe && self.toe_strength > 0.0 {
+            let d = toe - v;
+            toe - d / (1.0 + self.toe_strength * d / toe)
+        } else {
+            v
+        }
+    }
+}
+
+/// Apply highlight soft clip and shadow toe in working-space linear.
+pub fn apply_soft_clip(rgb: [f32; 3], soft_clip: &SoftClip) -> [f32; 3] {
+    if soft_clip.is_identity() {
+        return rgb;
+    }
+    match soft_clip.mode {
+        SoftClipMode::PerChannel => rgb.map(|v| soft_clip.curve(v)),
+        SoftClipMode::Luma => {
+            let luma = rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2];
+            if luma <= 1e-6 {
+                return rgb;
+            }
+            let ratio = soft_clip.curve(luma) / luma;
+            rgb.map(|v| v * ratio)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_identity() {
+        let rgb = [-0.2, 0.5, 3.0];
+        assert_eq!(apply_soft_clip(rgb, &SoftClip::default()), rgb);
+    }
+
+    #[test]
+    fn test_full_strength_stays_below_one() {
+        let soft_clip = SoftClip {
+            strength: 1.0,
+            ..SoftClip::default()
+        };
+        let out = apply_soft_clip([0.5, 2.0, 100.0], &soft_clip);
+        assert_eq!(out[0], 0.5);
+        assert!(out[1] > 0.8 && out[1] < out[2] && out[2] < 1.0, "{out:?}");
+        // Same slope on both sides of the knee.
+        let below = soft_clip.curve(0.8) - soft_clip.curve(0.799);
+        let above = soft_clip.curve(0.801) - soft_clip.curve(0.8);
+        assert!((below - above).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_toe_keeps_shadows_positive() {
+        let soft_clip = SoftClip {
+            toe_strength: 1.0,
+            ..SoftClip::default()
+        };
+        let out = soft_clip.curve(-10.0);
+        assert!(out > 0.0 && out < 0.05, "{out}");
+        assert_eq!(soft_clip.curve(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_luma_mode_keeps_ratios() {
+        let soft_clip = SoftClip {
+            strength: 1.0,
+            mode: SoftClipMode::Luma,
+            ..SoftClip::default()
+        };
+        let out = apply_soft_clip([4.0, 2.0, 1.0], &soft_clip);
+        assert!((out[0] / out[1] - 2.0).abs() < 1e-5);
+        assert!((out[1] / out[2] - 2.0).abs() < 1e-5);
+    }
+}
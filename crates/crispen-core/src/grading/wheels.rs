@@ -22,6 +22,38 @@
 //! ```text
 //!   Input ──→ ×Gain ──→ +Lift×(1−Gain) ──→ +Offset ──→ max(0) ──→ ^(1/Gamma) ──→ Output
 //! ```
+//!
+//! # Printer points
+//! Wheel values can also be read in film lab printer points. A point is
+//! 0.025 log10 exposure, about a twelfth of a stop, so multiplicative
+//! channels convert through `log10`. Additive channels use the Cineon
+//! convention of 7.5 code values (of 1023) per point.
+
+/// Log10 exposure change of one printer point.
+pub const PRINTER_POINT_LOG_E: f32 = 0.025;
+
+/// Additive offset of one printer point on a Cineon-style log scale.
+pub const PRINTER_POINT_OFFSET: f32 = 7.5 / 1023.0;
+
+/// Printer points of a multiplicative factor (gain, gamma); 1.0 is zero.
+pub fn factor_to_printer_points(factor: f32) -> f32 {
+    factor.max(1e-6).log10() / PRINTER_POINT_LOG_E
+}
+
+/// Multiplicative factor of a printer point count.
+pub fn printer_points_to_factor(points: f32) -> f32 {
+    10f32.powf(points * PRINTER_POINT_LOG_E)
+}
+
+/// Printer points of an additive value (lift, offset); 0.0 is zero.
+pub fn offset_to_printer_points(offset: f32) -> f32 {
+    offset / PRINTER_POINT_OFFSET
+}
+
+/// Additive value of a printer point count.
+pub fn printer_points_to_offset(points: f32) -> f32 {
+    points * PRINTER_POINT_OFFSET
+}
 
 /// Apply ASC CDL transform with lift separation.
 ///
@@ -182,4 +214,17 @@ mod tests {
             assert!(*channel >= 0.0, "output should never be negative");
         }
     }
+
+    #[test]
+    fn test_printer_points_round_trip() {
+        // Twelve points is close to one stop.
+        assert!((printer_points_to_factor(12.0) - 2.0).abs() < 0.01);
+        assert!(factor_to_printer_points(1.0).abs() < EPSILON);
+        for points in [-24.0, -1.5, 0.0, 3.0, 12.0] {
+            let factor = printer_points_to_factor(points);
+            assert!((factor_to_printer_points(factor) - points).abs() < 1e-3);
+            let offset = printer_points_to_offset(points);
+            assert!((offset_to_printer_points(offset) - points).abs() < 1e-4);
+        }
+    }
 }
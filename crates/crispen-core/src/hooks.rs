@@ -0,0 +1,259 @@
+//! Event hooks — run facility tooling when pipeline events happen.
+//!
+//! A [`HookConfig`] lists commands or scripts to run on events such as an
+//! image being loaded or an export finishing. Arguments are templates:
+//! `{name}` is replaced with the event's variable of that name (`{path}`,
+//! `{kind}`, ...), plus `{event}` and `{timestamp}` on every event.
+//!
+//! ```json
+//! {
+//!   "hooks": [
+//!     { "event": "export_complete", "command": ["publish-lut", "{path}", "--kind", "{kind}"] },
+//!     { "event": "image_loaded", "script": { "path": "/show/hooks/ingest.rhai", "args": ["{path}"] } }
+//!   ]
+//! }
+//! ```
+//!
+//! Commands run directly, not through a shell, so paths with spaces stay one
+//! argument.
+
+use std::path::Path;
+use std::process::{Child, Command};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Pipeline events a hook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    /// A source image was loaded. Variables: `path`, `width`, `height`,
+    /// `bit_depth`.
+    ImageLoaded,
+    /// A LUT was loaded into a slot. Variables: `path`, `slot`.
+    LutLoaded,
+    /// An export was written. Variables: `path`, `kind` (`lut`, `icc`,
+    /// `nuke`, `shader`, `bracket`, `version`, `audit`).
+    ExportComplete,
+    /// A script finished. Variables: `path`, `status` (`ok` or `error`).
+    ScriptFinished,
+    /// A grade was saved. Variables: `path`, `kind` (`version`, `preset`,
+    /// or `still`).
+    GradeSaved,
+}
+
+impl HookEvent {
+    /// Name used in configs and the `{event}` variable.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ImageLoaded => "image_loaded",
+            Self::LutLoaded => "lut_loaded",
+            Self::ExportComplete => "export_complete",
+            Self::ScriptFinished => "script_finished",
+            Self::GradeSaved => "grade_saved",
+        }
+    }
+}
+
+/// What a hook runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookAction {
+    /// Program followed by its argument templates.
+    Command(Vec<String>),
+    /// Script file run by the app's script host, with argument templates
+    /// bound to `ARGS`.
+    Script {
+        path: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// One configured hook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hook {
+    /// Event that fires the hook.
+    pub event: HookEvent,
+    /// Command or script to run.
+    #[serde(flatten)]
+    pub action: HookAction,
+}
+
+/// Errors from loading a hook config.
+#[derive(Debug, thiserror::Error)]
+pub enum HookError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid hook config: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The hooks configured for a session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    /// Hooks in the order they fire.
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+}
+
+impl HookConfig {
+    /// Load a config from a JSON file.
+    pub fn load(path: &Path) -> Result<Self, HookError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Hooks subscribed to `event`.
+    pub fn for_event(&self, event: HookEvent) -> impl Iterator<Item = &Hook> {
+        self.hooks.iter().filter(move |h| h.event == event)
+    }
+}
+
+/// An event occurrence with its template variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookInvocation {
+    /// The event that happened.
+    pub event: HookEvent,
+    /// Variables available to argument templates, including `event` and
+    /// `timestamp` (Unix seconds).
+    pub vars: Vec<(String, String)>,
+}
+
+impl HookInvocation {
+    /// Invocation of `event` with the given variables.
+    pub fn new<'a>(event: HookEvent, vars: impl IntoIterator<Item = (&'a str, String)>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut all = vec![
+            ("event".to_string(), event.name().to_string()),
+            ("timestamp".to_string(), timestamp.to_string()),
+        ];
+        all.extend(vars.into_iter().map(|(k, v)| (k.to_string(), v)));
+        Self { event, vars: all }
+    }
+
+    /// Replace `{name}` placeholders in `template`. Unknown names are left
+    /// as written so a typo is visible in the command that ran.
+    pub fn expand(&self, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                self.vars
+                    .iter()
+                    .find(|(k, _)| k == name)
+                    .map(|(_, v)| (v, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    out.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Expand every template in `templates`.
+    pub fn expand_all(&self, templates: &[String]) -> Vec<String> {
+        templates.iter().map(|t| self.expand(t)).collect()
+    }
+}
+
+/// Start a [`HookAction::Command`] for `invocation` without waiting for it.
+///
+/// Returns `None` for script actions and for an empty command.
+pub fn spawn_command(
+    action: &HookAction,
+    invocation: &HookInvocation,
+) -> Option<std::io::Result<Child>> {
+    let HookAction::Command(argv) = action else {
+        return None;
+    };
+    let (program, args) = argv.split_first()?;
+    Some(
+        Command::new(invocation.expand(program))
+            .args(invocation.expand_all(args))
+            .spawn(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(path: &str) -> HookInvocation {
+        HookInvocation::new(
+            HookEvent::ExportComplete,
+            [("path", path.to_string()), ("kind", "lut".to_string())],
+        )
+    }
+
+    #[test]
+    fn test_expand_replaces_known_variables() {
+        let inv = export("/out/show look.cube");
+        assert_eq!(inv.expand("{kind}:{path}"), "lut:/out/show look.cube");
+        assert_eq!(inv.expand("--event={event}"), "--event=export_complete");
+    }
+
+    #[test]
+    fn test_expand_keeps_unknown_and_unclosed_braces() {
+        let inv = export("a");
+        assert_eq!(inv.expand("{nope} {path"), "{nope} {path");
+        assert_eq!(inv.expand("{}{path}"), "{}a");
+    }
+
+    #[test]
+    fn test_config_parses_commands_and_scripts() {
+        let config: HookConfig = serde_json::from_str(
+            r#"{ "hooks": [
+                { "event": "export_complete", "command": ["publish", "{path}"] },
+                { "event": "image_loaded", "script": { "path": "ingest.rhai" } },
+                { "event": "grade_saved", "command": ["snapshot", "{kind}", "{path}"] }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(config.hooks.len(), 3);
+        assert_eq!(
+            config.hooks[0].action,
+            HookAction::Command(vec!["publish".into(), "{path}".into()])
+        );
+        assert_eq!(
+            config.hooks[1].action,
+            HookAction::Script {
+                path: "ingest.rhai".into(),
+                args: Vec::new()
+            }
+        );
+        assert_eq!(config.for_event(HookEvent::ImageLoaded).count(), 1);
+        assert_eq!(config.for_event(HookEvent::LutLoaded).count(), 0);
+        assert_eq!(config.for_event(HookEvent::GradeSaved).count(), 1);
+        assert_eq!(HookEvent::GradeSaved.name(), "grade_saved");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_command_passes_expanded_arguments() {
+        let dir = std::env::temp_dir().join(format!("crispen_hooks_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let action =
+            HookAction::Command(vec!["touch".into(), format!("{}/{{kind}}", dir.display())]);
+        let status = spawn_command(&action, &export("x"))
+            .expect("command action")
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert!(status.success());
+        assert!(dir.join("lut").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,807 @@
+//! Localization — Fluent (`.ftl`) message catalogs.
+//!
+//! Catalogs use the subset of [Project Fluent](https://projectfluent.org)
+//! syntax the app needs, so they can be edited with standard Fluent tooling:
+//!
+//! ```ftl
+//! # Comment
+//! -brand = Crispen
+//! welcome = Welcome to { -brand }
+//! points = { $count ->
+//!     [one] One point
+//!    *[other] { $count } points
+//! }
+//! toolbar-input = Input
+//!     .tooltip = Source color space
+//! ```
+//!
+//! Supported: messages, terms, attributes (looked up as `id.attr`),
+//! multi-line values, variables, message and term references, string and
+//! number literals, and select expressions keyed on exact values or the
+//! plural category. Not supported: functions such as `NUMBER()`,
+//! parameterized terms, and variants spanning several lines.
+//!
+//! A [`Localizer`] chains catalogs so a message missing from the user's
+//! locale falls back to the next catalog, usually `en-US`.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// References nested deeper than this are not expanded, which also stops
+/// cycles.
+const MAX_DEPTH: usize = 8;
+
+/// A value substituted for a `{ $variable }`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg<'a> {
+    Str(Cow<'a, str>),
+    Num(f64),
+}
+
+impl<'a> From<&'a str> for Arg<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::Str(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for Arg<'_> {
+    fn from(value: String) -> Self {
+        Self::Str(Cow::Owned(value))
+    }
+}
+
+impl From<f64> for Arg<'_> {
+    fn from(value: f64) -> Self {
+        Self::Num(value)
+    }
+}
+
+impl From<f32> for Arg<'_> {
+    fn from(value: f32) -> Self {
+        Self::Num(value.into())
+    }
+}
+
+impl From<u32> for Arg<'_> {
+    fn from(value: u32) -> Self {
+        Self::Num(value.into())
+    }
+}
+
+impl From<usize> for Arg<'_> {
+    fn from(value: usize) -> Self {
+        Self::Num(value as f64)
+    }
+}
+
+/// Errors from parsing an `.ftl` catalog.
+#[derive(Debug, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct FtlError {
+    /// 1-based line of the entry with the error.
+    pub line: usize,
+    pub message: String,
+}
+
+type Pattern = Vec<Element>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Element {
+    Text(String),
+    Placeable(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Str(String),
+    Num(f64),
+    Var(String),
+    Term(String),
+    Message(String),
+    Select {
+        selector: Box<Expr>,
+        variants: Vec<Variant>,
+        default: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Variant {
+    key: VariantKey,
+    value: Pattern,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum VariantKey {
+    Ident(String),
+    Num(f64),
+}
+
+/// Messages for one locale.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    locale: String,
+    /// Messages by id, terms by `-id`, attributes by `id.attr`.
+    entries: HashMap<String, Pattern>,
+}
+
+impl Catalog {
+    /// Parse an `.ftl` source for `locale` (a BCP 47 tag such as `de-DE`).
+    pub fn parse(locale: &str, source: &str) -> Result<Self, FtlError> {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut entries = HashMap::new();
+        let mut i = 0;
+        while i < lines.len() {
+            let line_no = i + 1;
+            let line = lines[i];
+            i += 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| FtlError {
+                line: line_no,
+                message: message.to_string(),
+            };
+            if is_indented(line) {
+                return Err(error("indented line outside an entry"));
+            }
+            let (id, first) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `id = value`"))?;
+            let id = id.trim();
+            if !is_identifier(id.strip_prefix('-').unwrap_or(id)) {
+                return Err(error(&format!("invalid identifier '{id}'")));
+            }
+
+            // The entry continues over indented lines, including blank lines
+            // between them, and a select expression's closing brace.
+            let mut body = Vec::new();
+            while i < lines.len() {
+                if is_continuation(lines[i]) {
+                    body.push(lines[i]);
+                    i += 1;
+                } else if lines[i].trim().is_empty() {
+                    let next = (i..lines.len()).find(|&j| !lines[j].trim().is_empty());
+                    match next {
+                        Some(j) if is_continuation(lines[j]) => {
+                            body.extend(std::iter::repeat_n("", j - i));
+                            i = j;
+                        }
+                        _ => break,
+                    }
+                } else {
+                    break;
+                }
+            }
+
+            // Split off `.attr = value` lines.
+            let mut values = vec![(id.to_string(), first, Vec::new())];
+            for line in body {
+                let trimmed = line.trim_start();
+                if let Some((name, value)) = trimmed
+                    .strip_prefix('.')
+                    .and_then(|rest| rest.split_once('='))
+                    .filter(|(name, _)| is_identifier(name.trim()))
+                {
+                    values.push((format!("{id}.{}", name.trim()), value, Vec::new()));
+                } else if let Some((_, _, lines)) = values.last_mut() {
+                    lines.push(line);
+                }
+            }
+
+            for (key, first, lines) in values {
+                let text = join_value(first, &lines);
+                let pattern = PatternParser::new(&text)
+                    .parse()
+                    .map_err(|message| FtlError {
+                        line: line_no,
+                        message: format!("{key}: {message}"),
+                    })?;
+                entries.insert(key, pattern);
+            }
+        }
+        Ok(Self {
+            locale: locale.to_string(),
+            entries,
+        })
+    }
+
+    /// BCP 47 tag of this catalog.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Whether the catalog has a message (or attribute, as `id.attr`).
+    pub fn contains(&self, id: &str) -> bool {
+        !id.starts_with('-') && self.entries.contains_key(id)
+    }
+
+    /// Ids of every message and attribute, for checking catalogs against
+    /// each other.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !id.starts_with('-'))
+    }
+
+    /// Format a message, or `None` if this catalog does not have it.
+    pub fn format(&self, id: &str, args: &[(&str, Arg<'_>)]) -> Option<String> {
+        if id.starts_with('-') {
+            return None;
+        }
+        let pattern = self.entries.get(id)?;
+        let mut out = String::new();
+        self.write_pattern(pattern, args, &mut out, 0);
+        Some(out)
+    }
+
+    fn write_pattern(
+        &self,
+        pattern: &Pattern,
+        args: &[(&str, Arg<'_>)],
+        out: &mut String,
+        depth: usize,
+    ) {
+        for element in pattern {
+            match element {
+                Element::Text(text) => out.push_str(text),
+                Element::Placeable(expr) => self.write_expr(expr, args, out, depth),
+            }
+        }
+    }
+
+    fn write_expr(&self, expr: &Expr, args: &[(&str, Arg<'_>)], out: &mut String, depth: usize) {
+        match expr {
+            Expr::Str(text) => out.push_str(text),
+            Expr::Num(n) => out.push_str(&format_number(*n)),
+            Expr::Var(name) => match lookup(args, name) {
+                Some(Arg::Str(text)) => out.push_str(text),
+                Some(Arg::Num(n)) => out.push_str(&format_number(*n)),
+                None => {
+                    out.push_str("{$");
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            Expr::Term(name) | Expr::Message(name) => {
+                // Terms only see their own (unsupported) parameters; messages
+                // share the caller's.
+                let (key, args) = match expr {
+                    Expr::Term(_) => (format!("-{name}"), &[][..]),
+                    _ => (name.clone(), args),
+                };
+                match self.entries.get(&key) {
+                    Some(pattern) if depth < MAX_DEPTH => {
+                        self.write_pattern(pattern, args, out, depth + 1);
+                    }
+                    _ => {
+                        out.push('{');
+                        out.push_str(&key);
+                        out.push('}');
+                    }
+                }
+            }
+            Expr::Select {
+                selector,
+                variants,
+                default,
+            } => {
+                let variant = self
+                    .select(selector, variants, args, depth)
+                    .unwrap_or(&variants[*default]);
+                self.write_pattern(&variant.value, args, out, depth);
+            }
+        }
+    }
+
+    fn select<'v>(
+        &self,
+        selector: &Expr,
+        variants: &'v [Variant],
+        args: &[(&str, Arg<'_>)],
+        depth: usize,
+    ) -> Option<&'v Variant> {
+        let value = match selector {
+            Expr::Num(n) => Arg::Num(*n),
+            Expr::Var(name) => lookup(args, name)?.clone(),
+            other => {
+                let mut text = String::new();
+                self.write_expr(other, args, &mut text, depth + 1);
+                Arg::Str(Cow::Owned(text))
+            }
+        };
+        match value {
+            Arg::Num(n) => variants
+                .iter()
+                .find(|v| v.key == VariantKey::Num(n))
+                .or_else(|| {
+                    let category = plural_category(&self.locale, n);
+                    variants
+                        .iter()
+                        .find(|v| matches!(&v.key, VariantKey::Ident(key) if key == category))
+                }),
+            Arg::Str(text) => variants
+                .iter()
+                .find(|v| matches!(&v.key, VariantKey::Ident(key) if *key == text)),
+        }
+    }
+}
+
+/// Catalogs in fallback order: the user's locale first, then the source
+/// locale.
+#[derive(Debug, Clone, Default)]
+pub struct Localizer {
+    catalogs: Vec<Catalog>,
+}
+
+impl Localizer {
+    pub fn new(catalogs: Vec<Catalog>) -> Self {
+        Self { catalogs }
+    }
+
+    /// Locale of the first catalog, `en-US` if there is none.
+    pub fn locale(&self) -> &str {
+        self.catalogs.first().map_or("en-US", Catalog::locale)
+    }
+
+    /// Format `id` from the first catalog that has it. A message no catalog
+    /// has is returned as its id, so a gap is visible but not fatal.
+    pub fn format(&self, id: &str, args: &[(&str, Arg<'_>)]) -> String {
+        self.catalogs
+            .iter()
+            .find_map(|catalog| catalog.format(id, args))
+            .unwrap_or_else(|| {
+                tracing::debug!("missing translation '{id}'");
+                id.to_string()
+            })
+    }
+}
+
+/// Pick the best of `available` for a requested locale such as
+/// `de_DE.UTF-8`, `de-AT` or `pt`: an exact match, else the first available
+/// locale with the same language.
+pub fn negotiate<'a>(requested: &str, available: &[&'a str]) -> Option<&'a str> {
+    let requested = requested
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    let language = requested.split('-').next().unwrap_or_default();
+    let same_language = |tag: &&str| {
+        tag.split('-')
+            .next()
+            .is_some_and(|l| l.eq_ignore_ascii_case(language))
+    };
+    available
+        .iter()
+        .find(|tag| tag.eq_ignore_ascii_case(&requested))
+        .or_else(|| available.iter().find(|tag| same_language(tag)))
+        .copied()
+}
+
+/// CLDR plural category of `n`, for the languages the app ships and their
+/// close relatives.
+fn plural_category(locale: &str, n: f64) -> &'static str {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    match language {
+        "ja" | "ko" | "zh" | "th" | "vi" | "id" => "other",
+        "fr" | "pt" if (0.0..2.0).contains(&n) && n.fract() == 0.0 => "one",
+        _ if n == 1.0 => "one",
+        _ => "other",
+    }
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{n}")
+    }
+}
+
+fn lookup<'a, 'b>(args: &'b [(&str, Arg<'a>)], name: &str) -> Option<&'b Arg<'a>> {
+    args.iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn is_indented(line: &str) -> bool {
+    line.starts_with([' ', '\t'])
+}
+
+fn is_continuation(line: &str) -> bool {
+    (is_indented(line) && !line.trim().is_empty()) || line.starts_with('}')
+}
+
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Join an entry's inline text and continuation lines into one value,
+/// removing the continuation lines' common indent. Closing braces don't count
+/// towards the indent since they usually sit at the start of the line.
+fn join_value(first: &str, lines: &[&str]) -> String {
+    let leading = |line: &str| line.len() - line.trim_start().len();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('}'))
+        .map(|line| leading(line))
+        .min()
+        .unwrap_or(0);
+    let mut parts: Vec<&str> = Vec::new();
+    let first = first.trim();
+    if !first.is_empty() {
+        parts.push(first);
+    }
+    parts.extend(lines.iter().map(|line| &line[leading(line).min(indent)..]));
+    parts.join("\n").trim_end().to_string()
+}
+
+/// Recursive-descent parser for one value.
+struct PatternParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> PatternParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse(mut self) -> Result<Pattern, String> {
+        self.pattern(false)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.src[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.bump() {
+            Some(found) if found == c => Ok(()),
+            Some(found) => Err(format!("expected '{c}', found '{found}'")),
+            None => Err(format!("expected '{c}', found end of value")),
+        }
+    }
+
+    fn skip_blank(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn skip_inline_blank(&mut self) {
+        while self.peek().is_some_and(|c| c == ' ' || c == '\t') {
+            self.bump();
+        }
+    }
+
+    /// Text and placeables, up to the end of the value or, for a variant,
+    /// the end of the line.
+    fn pattern(&mut self, single_line: bool) -> Result<Pattern, String> {
+        let mut pattern = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' if single_line => break,
+                '{' => {
+                    self.bump();
+                    if !text.is_empty() {
+                        pattern.push(Element::Text(std::mem::take(&mut text)));
+                    }
+                    pattern.push(Element::Placeable(self.placeable()?));
+                }
+                '}' => return Err("unbalanced '}'".to_string()),
+                _ => {
+                    text.push(c);
+                    self.bump();
+                }
+            }
+        }
+        if single_line {
+            text.truncate(text.trim_end().len());
+        }
+        if !text.is_empty() {
+            pattern.push(Element::Text(text));
+        }
+        Ok(pattern)
+    }
+
+    /// The inside of `{ ... }`, after the opening brace.
+    fn placeable(&mut self) -> Result<Expr, String> {
+        self.skip_blank();
+        let expr = self.inline_expr()?;
+        self.skip_blank();
+        if self.eat("->") {
+            return self.select(expr);
+        }
+        self.expect('}')?;
+        Ok(expr)
+    }
+
+    fn inline_expr(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('"') => self.string_literal().map(Expr::Str),
+            Some('$') => {
+                self.bump();
+                self.identifier().map(Expr::Var)
+            }
+            Some('-') if self.src[self.pos + 1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                self.number().map(Expr::Num)
+            }
+            Some('-') => {
+                self.bump();
+                self.identifier().map(Expr::Term)
+            }
+            Some(c) if c.is_ascii_digit() => self.number().map(Expr::Num),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut id = self.identifier()?;
+                if self.eat(".") {
+                    id.push('.');
+                    id.push_str(&self.identifier()?);
+                }
+                Ok(Expr::Message(id))
+            }
+            Some(c) => Err(format!("unexpected '{c}' in placeable")),
+            None => Err("unterminated placeable".to_string()),
+        }
+    }
+
+    fn select(&mut self, selector: Expr) -> Result<Expr, String> {
+        let mut variants = Vec::new();
+        let mut default = None;
+        loop {
+            self.skip_blank();
+            match self.bump() {
+                Some('}') => break,
+                Some('*') => {
+                    if default.is_some() {
+                        return Err("more than one default variant".to_string());
+                    }
+                    default = Some(variants.len());
+                    self.expect('[')?;
+                }
+                Some('[') => {}
+                Some(c) => return Err(format!("expected a variant, found '{c}'")),
+                None => return Err("unterminated select expression".to_string()),
+            }
+            self.skip_inline_blank();
+            let key = match self.peek() {
+                Some(c) if c.is_ascii_digit() || c == '-' => VariantKey::Num(self.number()?),
+                _ => VariantKey::Ident(self.identifier()?),
+            };
+            self.skip_inline_blank();
+            self.expect(']')?;
+            self.skip_inline_blank();
+            let value = self.pattern(true)?;
+            variants.push(Variant { key, value });
+        }
+        let default = default.ok_or("select expression has no default variant")?;
+        Ok(Expr::Select {
+            selector: Box::new(selector),
+            variants,
+            default,
+        })
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            self.bump();
+        }
+        let id = &self.src[start..self.pos];
+        if is_identifier(id) {
+            Ok(id.to_string())
+        } else {
+            Err(format!("invalid identifier '{id}'"))
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        self.eat("-");
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.bump();
+        }
+        let literal = &self.src[start..self.pos];
+        literal
+            .parse()
+            .map_err(|_| format!("invalid number '{literal}'"))
+    }
+
+    fn string_literal(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.bump() {
+                    Some(c @ ('"' | '\\')) => out.push(c),
+                    Some('u') => {
+                        let hex = self.src.get(self.pos..self.pos + 4).unwrap_or_default();
+                        let c = u32::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape '\\u{hex}'"))?;
+                        self.pos += 4;
+                        out.push(c);
+                    }
+                    _ => return Err("invalid escape in string literal".to_string()),
+                },
+                Some('\n') | None => return Err("unterminated string literal".to_string()),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = r#"
+# Comment
+-brand = Crispen
+welcome = Welcome to { -brand }
+greeting = Hello, { $name }!
+points = { $count ->
+    [0] No points
+    [one] One point
+   *[other] { $count } points
+}
+multiline =
+    First line
+    Second line
+
+    After a blank line
+toolbar-input = Input
+    .tooltip = Source color space
+braces = Literal { "{" } brace
+nested = { welcome } again
+mode = { $mode ->
+    [fast] Quick
+   *[other] Careful
+}
+"#;
+
+    fn en() -> Catalog {
+        Catalog::parse("en-US", EN).unwrap()
+    }
+
+    #[test]
+    fn formats_text_variables_and_terms() {
+        let catalog = en();
+        assert_eq!(
+            catalog.format("welcome", &[]).unwrap(),
+            "Welcome to Crispen"
+        );
+        assert_eq!(
+            catalog
+                .format("greeting", &[("name", "Ada".into())])
+                .unwrap(),
+            "Hello, Ada!"
+        );
+        assert_eq!(catalog.format("braces", &[]).unwrap(), "Literal { brace");
+        assert_eq!(
+            catalog.format("nested", &[]).unwrap(),
+            "Welcome to Crispen again"
+        );
+    }
+
+    #[test]
+    fn missing_variable_is_shown_in_braces() {
+        assert_eq!(en().format("greeting", &[]).unwrap(), "Hello, {$name}!");
+    }
+
+    #[test]
+    fn select_matches_exact_then_plural_then_default() {
+        let catalog = en();
+        let points = |n: u32| catalog.format("points", &[("count", n.into())]).unwrap();
+        assert_eq!(points(0), "No points");
+        assert_eq!(points(1), "One point");
+        assert_eq!(points(5), "5 points");
+        assert_eq!(catalog.format("points", &[]).unwrap(), "{$count} points");
+
+        let mode = |m: &str| catalog.format("mode", &[("mode", m.into())]).unwrap();
+        assert_eq!(mode("fast"), "Quick");
+        assert_eq!(mode("slow"), "Careful");
+    }
+
+    #[test]
+    fn plural_categories_follow_locale() {
+        let fr =
+            Catalog::parse("fr-FR", "n = { $n ->\n    [one] un\n   *[other] autre\n}").unwrap();
+        assert_eq!(fr.format("n", &[("n", 0u32.into())]).unwrap(), "un");
+        let de = Catalog::parse(
+            "de-DE",
+            "n = { $n ->\n    [one] eins\n   *[other] andere\n}",
+        )
+        .unwrap();
+        assert_eq!(de.format("n", &[("n", 0u32.into())]).unwrap(), "andere");
+        assert_eq!(de.format("n", &[("n", 1u32.into())]).unwrap(), "eins");
+    }
+
+    #[test]
+    fn multiline_values_and_attributes() {
+        let catalog = en();
+        assert_eq!(
+            catalog.format("multiline", &[]).unwrap(),
+            "First line\nSecond line\n\nAfter a blank line"
+        );
+        assert_eq!(catalog.format("toolbar-input", &[]).unwrap(), "Input");
+        assert_eq!(
+            catalog.format("toolbar-input.tooltip", &[]).unwrap(),
+            "Source color space"
+        );
+    }
+
+    #[test]
+    fn terms_are_not_messages() {
+        let catalog = en();
+        assert!(catalog.format("-brand", &[]).is_none());
+        assert!(!catalog.contains("-brand"));
+        assert!(catalog.ids().all(|id| !id.starts_with('-')));
+    }
+
+    #[test]
+    fn reference_cycles_stop() {
+        let catalog = Catalog::parse("en", "a = { b }\nb = { a }").unwrap();
+        let text = catalog.format("a", &[]).unwrap();
+        assert!(text.ends_with("{a}") || text.ends_with("{b}"));
+    }
+
+    #[test]
+    fn errors_report_the_entry_line() {
+        let err = Catalog::parse("en", "ok = fine\n\nbad = { $x\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        let err = Catalog::parse("en", "no-equals-sign").unwrap_err();
+        assert_eq!(err.line, 1);
+        let err = Catalog::parse("en", "s = { $n ->\n    [one] x\n}").unwrap_err();
+        assert!(err.message.contains("default"));
+        assert!(Catalog::parse("en", "  stray = indent").is_err());
+    }
+
+    #[test]
+    fn localizer_falls_back_through_catalogs() {
+        let de = Catalog::parse("de-DE", "greeting = Hallo, { $name }!").unwrap();
+        let localizer = Localizer::new(vec![de, en()]);
+        assert_eq!(localizer.locale(), "de-DE");
+        assert_eq!(
+            localizer.format("greeting", &[("name", "Ada".into())]),
+            "Hallo, Ada!"
+        );
+        assert_eq!(localizer.format("welcome", &[]), "Welcome to Crispen");
+        assert_eq!(localizer.format("nope", &[]), "nope");
+    }
+
+    #[test]
+    fn negotiates_posix_and_bcp47_tags() {
+        let available = ["en-US", "de-DE"];
+        assert_eq!(negotiate("de_DE.UTF-8", &available), Some("de-DE"));
+        assert_eq!(negotiate("de-AT", &available), Some("de-DE"));
+        assert_eq!(negotiate("EN-us", &available), Some("en-US"));
+        assert_eq!(negotiate("fr-FR", &available), None);
+        assert_eq!(negotiate("C", &available), None);
+    }
+}
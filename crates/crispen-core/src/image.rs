@@ -1,10 +1,18 @@
 //! Image representation for the color grading pipeline.
+//!
+//! [`GradingImage`] holds RGBA f32. Large sources can be held as
+//! [`HalfImage`] instead through [`SourceImage`], which promotes back to
+//! f32 for operations that need it.
 
+pub use half::f16;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 
 /// Supported bit depths for source images.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BitDepth {
     /// 8-bit unsigned integer.
     U8,
@@ -57,3 +65,235 @@ pub struct GradingImage {
     /// Original bit depth of the source image.
     pub source_bit_depth: BitDepth,
 }
+
+impl GradingImage {
+    /// Pixel storage in bytes.
+    pub fn byte_size(&self) -> usize {
+        std::mem::size_of_val(self.pixels.as_slice())
+    }
+
+    /// Round every channel to half precision.
+    pub fn to_half(&self) -> HalfImage {
+        HalfImage {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().map(|p| p.map(f16::from_f32)).collect(),
+            source_bit_depth: self.source_bit_depth,
+        }
+    }
+}
+
+/// RGBA half-float image — half the memory of a [`GradingImage`].
+#[derive(Debug, Clone)]
+pub struct HalfImage {
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// Pixel data in RGBA f16 format.
+    pub pixels: Vec<[f16; 4]>,
+    /// Original bit depth of the source image.
+    pub source_bit_depth: BitDepth,
+}
+
+impl HalfImage {
+    /// Pixel storage in bytes.
+    pub fn byte_size(&self) -> usize {
+        std::mem::size_of_val(self.pixels.as_slice())
+    }
+
+    /// Widen every channel back to f32.
+    pub fn to_full(&self) -> GradingImage {
+        GradingImage {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().map(|p| p.map(f16::to_f32)).collect(),
+            source_bit_depth: self.source_bit_depth,
+        }
+    }
+}
+
+/// Pixel count from which [`SourcePrecision::Auto`] stores half floats
+/// (UHD and up).
+pub const AUTO_HALF_PIXELS: u64 = 3840 * 2160;
+
+/// Precision a source image is held in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SourcePrecision {
+    /// Always f32.
+    Full,
+    /// Always f16. Integer sources deeper than 10 bits lose precision.
+    Half,
+    /// f16 for sources of at least [`AUTO_HALF_PIXELS`] whose bit depth
+    /// f16 holds without loss (8- and 10-bit integer, half float), f32
+    /// otherwise.
+    #[default]
+    Auto,
+}
+
+impl SourcePrecision {
+    /// Whether `image` should be stored as half floats.
+    pub fn wants_half(self, image: &GradingImage) -> bool {
+        match self {
+            Self::Full => false,
+            Self::Half => true,
+            Self::Auto => {
+                let lossless = matches!(
+                    image.source_bit_depth,
+                    BitDepth::U8 | BitDepth::U10 | BitDepth::F16
+                );
+                lossless && image.width as u64 * image.height as u64 >= AUTO_HALF_PIXELS
+            }
+        }
+    }
+}
+
+/// A loaded source image in the precision it is held in.
+///
+/// Half storage is for holding and uploading large frames. Operations
+/// that need full precision — analysis, CPU renders, exports — call
+/// [`Self::promoted`], which widens a half source on demand and borrows
+/// a full one.
+#[derive(Debug, Clone)]
+pub enum SourceImage {
+    /// Held as f32.
+    Full(GradingImage),
+    /// Held as f16.
+    Half(HalfImage),
+}
+
+impl SourceImage {
+    /// Store `image` in the precision `precision` picks for it.
+    pub fn new(image: GradingImage, precision: SourcePrecision) -> Self {
+        if precision.wants_half(&image) {
+            Self::Half(image.to_half())
+        } else {
+            Self::Full(image)
+        }
+    }
+
+    /// Image width in pixels.
+    pub fn width(&self) -> u32 {
+        match self {
+            Self::Full(image) => image.width,
+            Self::Half(image) => image.width,
+        }
+    }
+
+    /// Image height in pixels.
+    pub fn height(&self) -> u32 {
+        match self {
+            Self::Full(image) => image.height,
+            Self::Half(image) => image.height,
+        }
+    }
+
+    /// Original bit depth of the source image.
+    pub fn source_bit_depth(&self) -> BitDepth {
+        match self {
+            Self::Full(image) => image.source_bit_depth,
+            Self::Half(image) => image.source_bit_depth,
+        }
+    }
+
+    /// Whether the pixels are held as half floats.
+    pub fn is_half(&self) -> bool {
+        matches!(self, Self::Half(_))
+    }
+
+    /// Pixel storage in bytes.
+    pub fn byte_size(&self) -> usize {
+        match self {
+            Self::Full(image) => image.byte_size(),
+            Self::Half(image) => image.byte_size(),
+        }
+    }
+
+    /// The image at f32 precision, widened from half storage if needed.
+    pub fn promoted(&self) -> Cow<'_, GradingImage> {
+        match self {
+            Self::Full(image) => Cow::Borrowed(image),
+            Self::Half(image) => Cow::Owned(image.to_full()),
+        }
+    }
+
+    /// The image at f32 precision, consuming the source.
+    pub fn into_full(self) -> GradingImage {
+        match self {
+            Self::Full(image) => image,
+            Self::Half(image) => image.to_full(),
+        }
+    }
+}
+
+impl From<GradingImage> for SourceImage {
+    fn from(image: GradingImage) -> Self {
+        Self::Full(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(width: u32, height: u32, depth: BitDepth, levels: u32) -> GradingImage {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let v = (i % (levels + 1)) as f32 / levels as f32;
+                [v, 1.0 - v, v * 0.5, 1.0]
+            })
+            .collect();
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: depth,
+        }
+    }
+
+    #[test]
+    fn test_half_storage_halves_memory() {
+        let image = ramp(64, 32, BitDepth::U8, 255);
+        let source = SourceImage::new(image.clone(), SourcePrecision::Half);
+        assert!(source.is_half());
+        assert_eq!(source.byte_size() * 2, image.byte_size());
+        assert_eq!((source.width(), source.height()), (64, 32));
+    }
+
+    #[test]
+    fn test_ten_bit_survives_half_round_trip() {
+        let image = ramp(1024, 2, BitDepth::U10, 1023);
+        let promoted = image.to_half().to_full();
+        // Red and green hold exact 10-bit levels.
+        for (a, b) in image.pixels.iter().zip(&promoted.pixels) {
+            for c in 0..2 {
+                assert_eq!((a[c] * 1023.0).round(), (b[c] * 1023.0).round());
+            }
+        }
+    }
+
+    #[test]
+    fn test_auto_precision_picks_half_only_when_lossless_and_large() {
+        let small = ramp(16, 16, BitDepth::U10, 1023);
+        assert!(!SourcePrecision::Auto.wants_half(&small));
+        let mut uhd = GradingImage {
+            width: 3840,
+            height: 2160,
+            pixels: Vec::new(),
+            source_bit_depth: BitDepth::U10,
+        };
+        assert!(SourcePrecision::Auto.wants_half(&uhd));
+        uhd.source_bit_depth = BitDepth::U16;
+        assert!(!SourcePrecision::Auto.wants_half(&uhd));
+        assert!(SourcePrecision::Half.wants_half(&uhd));
+    }
+
+    #[test]
+    fn test_promoted_borrows_full_storage() {
+        let source = SourceImage::new(ramp(4, 4, BitDepth::F32, 15), SourcePrecision::Full);
+        assert!(matches!(source.promoted(), Cow::Borrowed(_)));
+        let half = SourceImage::new(ramp(4, 4, BitDepth::F32, 15), SourcePrecision::Half);
+        assert!(matches!(half.promoted(), Cow::Owned(_)));
+    }
+}
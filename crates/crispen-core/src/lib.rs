@@ -2,15 +2,39 @@
 //!
 //! This crate contains all color science, grading math, LUT operations,
 //! and scope computation. No GPU or framework dependencies.
+//!
+//! # Features
+//!
+//! All on by default. With `default-features = false` only the transform
+//! math, grading tools, color management, framing guides, and test
+//! patterns remain.
+//!
+//! - `serde`: `Serialize`/`Deserialize` on grades and data types.
+//! - `scopes`: the `scopes` module and `grading::auto_contrast`.
+//! - `lut-io`: `.cube`, shader, Nuke, and ICC file writers and readers.
+//! - `project`: the `project` and `hooks` modules and
+//!   `transform::look_preview`; implies `serde` and `lut-io`.
 
 pub mod color_management;
+pub mod framing;
 pub mod grading;
+#[cfg(feature = "project")]
+pub mod hooks;
+pub mod i18n;
 pub mod image;
+pub mod patterns;
+#[cfg(feature = "project")]
+pub mod project;
+#[cfg(feature = "scopes")]
 pub mod scopes;
 pub mod transform;
 
 // Re-exports for convenience.
 pub use image::{BitDepth, GradingImage};
-pub use transform::evaluate::evaluate_transform;
+pub use transform::builder::GradingParamsBuilder;
+pub use transform::determinism::Determinism;
+pub use transform::evaluate::{
+    OutputEncoding, evaluate_transform, evaluate_transform_batch, evaluate_transform_batch_with,
+};
 pub use transform::lut::Lut3D;
 pub use transform::params::{ColorManagementConfig, ColorSpaceId, GradingParams};
@@ -0,0 +1,350 @@
+//! Synthetic test pattern generation.
+//!
+//! Produces `GradingImage`s for scope verification and display checks.
+//! Bars, ramps, steps, and PLUGE are defined as signal (code) values and
+//! are written unchanged into whichever color space the image is tagged
+//! with — they exercise the signal path, not a particular colorimetry.
+//! The Macbeth chart is colorimetric: its linear sRGB reference values are
+//! converted into the target gamut and encoded with the target transfer
+//! function.
+//!
+//! All values are finally quantized to the requested bit depth so that
+//! scopes show the same banding a real file of that depth would.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::macbeth;
+use crate::color_management::color_space::get_conversion_matrix;
+use crate::color_management::transfer::get_transfer;
+use crate::image::{BitDepth, GradingImage, f16};
+use crate::transform::params::ColorSpaceId;
+
+/// Built-in test patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TestPattern {
+    /// 75% color bars over a 100% white / black / PLUGE strip.
+    ColorBars,
+    /// Horizontal 0–100% ramps: gray, red, green, blue bands.
+    GradientRamp,
+    /// Eleven vertical gray steps from 0% to 100% in 10% increments.
+    GraySteps,
+    /// Picture line-up generator: -2%, 0%, +2%, +4% bars on black.
+    Pluge,
+    /// 24-patch ColorChecker Classic layout.
+    Macbeth,
+}
+
+impl TestPattern {
+    /// Stable UI label.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::ColorBars => "Color Bars",
+            Self::GradientRamp => "Gradient Ramp",
+            Self::GraySteps => "Gray Steps",
+            Self::Pluge => "PLUGE",
+            Self::Macbeth => "Macbeth Chart",
+        }
+    }
+
+    /// All built-in patterns.
+    pub fn all() -> &'static [Self] {
+        const ALL: [TestPattern; 5] = [
+            TestPattern::ColorBars,
+            TestPattern::GradientRamp,
+            TestPattern::GraySteps,
+            TestPattern::Pluge,
+            TestPattern::Macbeth,
+        ];
+        &ALL
+    }
+}
+
+/// 75% bars in SMPTE order: gray, yellow, cyan, green, magenta, red, blue.
+const BARS_75: [[f32; 3]; 7] = [
+    [0.75, 0.75, 0.75],
+    [0.75, 0.75, 0.0],
+    [0.0, 0.75, 0.75],
+    [0.0, 0.75, 0.0],
+    [0.75, 0.0, 0.75],
+    [0.75, 0.0, 0.0],
+    [0.0, 0.0, 0.75],
+];
+
+/// PLUGE bar levels relative to black.
+const PLUGE_LEVELS: [f32; 4] = [-0.02, 0.0, 0.02, 0.04];
+
+/// Generate a test pattern image.
+///
+/// The resulting pixels are in `color_space`'s encoding (the same
+/// convention as loaded files), quantized to `bit_depth`.
+pub fn generate(
+    pattern: TestPattern,
+    width: u32,
+    height: u32,
+    bit_depth: BitDepth,
+    color_space: ColorSpaceId,
+) -> GradingImage {
+    let width = width.max(1);
+    let height = height.max(1);
+
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        let v = (y as f32 + 0.5) / height as f32;
+        for x in 0..width {
+            let u = (x as f32 + 0.5) / width as f32;
+            let rgb = match pattern {
+                TestPattern::ColorBars => color_bars(u, v),
+                TestPattern::GradientRamp => gradient_ramp(x, width, v),
+                TestPattern::GraySteps => gray_steps(u),
+                TestPattern::Pluge => pluge(u, v),
+                TestPattern::Macbeth => macbeth_chart(u, v, color_space),
+            };
+            let [r, g, b] = rgb.map(|c| quantize(c, bit_depth));
+            pixels.push([r, g, b, 1.0]);
+        }
+    }
+
+    GradingImage {
+        width,
+        height,
+        pixels,
+        source_bit_depth: bit_depth,
+    }
+}
+
+fn color_bars(u: f32, v: f32) -> [f32; 3] {
+    if v < 2.0 / 3.0 {
+        let idx = ((u * 7.0) as usize).min(6);
+        return BARS_75[idx];
+    }
+    // Bottom strip: 100% white, black, then PLUGE in the right third.
+    if u < 1.0 / 3.0 {
+        [1.0; 3]
+    } else if u < 2.0 / 3.0 {
+        [0.0; 3]
+    } else {
+        let local = (u - 2.0 / 3.0) * 3.0;
+        pluge_bar(local)
+    }
+}
+
+fn gradient_ramp(x: u32, width: u32, v: f32) -> [f32; 3] {
+    let t = if width > 1 {
+        x as f32 / (width - 1) as f32
+    } else {
+        0.0
+    };
+    match ((v * 4.0) as usize).min(3) {
+        0 => [t, t, t],
+        1 => [t, 0.0, 0.0],
+        2 => [0.0, t, 0.0],
+        _ => [0.0, 0.0, t],
+    }
+}
+
+fn gray_steps(u: f32) -> [f32; 3] {
+    let step = ((u * 11.0) as usize).min(10);
+    [step as f32 / 10.0; 3]
+}
+
+fn pluge(u: f32, v: f32) -> [f32; 3] {
+    if !(0.25..0.75).contains(&v) || !(0.2..0.8).contains(&u) {
+        return [0.0; 3];
+    }
+    pluge_bar((u - 0.2) / 0.6)
+}
+
+/// Map `t` in `[0, 1)` across the four PLUGE bars.
+fn pluge_bar(t: f32) -> [f32; 3] {
+    let idx = ((t * PLUGE_LEVELS.len() as f32) as usize).min(PLUGE_LEVELS.len() - 1);
+    [PLUGE_LEVELS[idx]; 3]
+}
+
+fn macbeth_chart(u: f32, v: f32, color_space: ColorSpaceId) -> [f32; 3] {
+    let cols = macbeth::COLUMNS as f32;
+    let rows = macbeth::ROWS as f32;
+    let cu = u * cols;
+    let cv = v * rows;
+    let (fu, fv) = (cu.fract(), cv.fract());
+
+    // Black gutters between patches.
+    const GUTTER: f32 = 0.08;
+    let inside = GUTTER..=1.0 - GUTTER;
+    if !inside.contains(&fu) || !inside.contains(&fv) {
+        return [0.0; 3];
+    }
+
+    let col = (cu as usize).min(macbeth::COLUMNS - 1);
+    let row = (cv as usize).min(macbeth::ROWS - 1);
+    let linear = macbeth::linear_srgb(row * macbeth::COLUMNS + col);
+
+    let converted = get_conversion_matrix(ColorSpaceId::LinearSrgb, color_space).apply(linear);
+    match get_transfer(color_space) {
+        Some(tf) => converted.map(|c| tf.to_encoded(c)),
+        None => converted,
+    }
+}
+
+/// Quantize a value to the precision of `depth`.
+///
+/// Integer depths are full-range and clamp to `[0, 1]`; float depths keep
+/// out-of-range values.
+pub fn quantize(value: f32, depth: BitDepth) -> f32 {
+    let max_code = match depth {
+        BitDepth::U8 => 255.0,
+        BitDepth::U10 => 1023.0,
+        BitDepth::U12 => 4095.0,
+        BitDepth::U16 => 65535.0,
+        BitDepth::F16 => return round_to_f16(value),
+        BitDepth::F32 => return value,
+    };
+    (value.clamp(0.0, 1.0) * max_code).round() / max_code
+}
+
+/// Round an `f32` to the nearest value representable as IEEE half,
+/// saturating at ±65504 so out-of-range patches stay finite.
+///
+/// Subnormal halves are kept, as a half-float file stores them, so an F16
+/// pattern holds the same values as the same pattern converted with
+/// [`GradingImage::to_half`].
+fn round_to_f16(value: f32) -> f32 {
+    let clamped = if value.is_finite() {
+        value.clamp(-f16::MAX.to_f32(), f16::MAX.to_f32())
+    } else {
+        value
+    };
+    f16::from_f32(clamped).to_f32()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-6;
+
+    fn pixel(img: &GradingImage, x: u32, y: u32) -> [f32; 4] {
+        img.pixels[(y * img.width + x) as usize]
+    }
+
+    #[test]
+    fn test_generate_has_requested_dimensions() {
+        for &pattern in TestPattern::all() {
+            let img = generate(pattern, 64, 32, BitDepth::U10, ColorSpaceId::Srgb);
+            assert_eq!(img.width, 64);
+            assert_eq!(img.height, 32);
+            assert_eq!(img.pixels.len(), 64 * 32, "{}", pattern.label());
+            assert_eq!(img.source_bit_depth, BitDepth::U10);
+        }
+    }
+
+    #[test]
+    fn test_color_bars_first_bar_is_75_percent_gray() {
+        let img = generate(
+            TestPattern::ColorBars,
+            70,
+            30,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        let px = pixel(&img, 2, 2);
+        assert!((px[0] - 0.75).abs() < EPSILON && (px[2] - 0.75).abs() < EPSILON);
+        let blue = pixel(&img, 68, 2);
+        assert_eq!(blue[0], 0.0);
+        assert!((blue[2] - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_gray_steps_span_full_range() {
+        let img = generate(
+            TestPattern::GraySteps,
+            110,
+            1,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        assert_eq!(pixel(&img, 0, 0)[0], 0.0);
+        assert!((pixel(&img, 109, 0)[0] - 1.0).abs() < EPSILON);
+        assert!((pixel(&img, 55, 0)[0] - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ramp_is_monotonic() {
+        let img = generate(
+            TestPattern::GradientRamp,
+            256,
+            4,
+            BitDepth::U8,
+            ColorSpaceId::Srgb,
+        );
+        for x in 1..256 {
+            assert!(pixel(&img, x, 0)[0] >= pixel(&img, x - 1, 0)[0]);
+        }
+        assert_eq!(pixel(&img, 255, 0)[0], 1.0);
+    }
+
+    #[test]
+    fn test_pluge_sub_black_clips_at_integer_depth() {
+        let float = generate(
+            TestPattern::Pluge,
+            100,
+            100,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        let int = generate(
+            TestPattern::Pluge,
+            100,
+            100,
+            BitDepth::U10,
+            ColorSpaceId::Srgb,
+        );
+        assert!(pixel(&float, 22, 50)[0] < 0.0, "float keeps sub-black");
+        assert_eq!(pixel(&int, 22, 50)[0], 0.0, "integer clips sub-black");
+    }
+
+    #[test]
+    fn test_macbeth_in_srgb_matches_reference() {
+        let img = generate(
+            TestPattern::Macbeth,
+            600,
+            400,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        // Centre of the first patch (dark skin).
+        let px = pixel(&img, 50, 50);
+        let expected = macbeth::srgb_encoded(0);
+        for c in 0..3 {
+            assert!(
+                (px[c] - expected[c]).abs() < 1e-4,
+                "channel {c}: {} vs {}",
+                px[c],
+                expected[c]
+            );
+        }
+    }
+
+    #[test]
+    fn test_quantize_u8_produces_code_values() {
+        let q = quantize(0.5, BitDepth::U8);
+        assert!((q * 255.0 - (q * 255.0).round()).abs() < 1e-4);
+        assert_eq!(quantize(1.5, BitDepth::U8), 1.0);
+        assert_eq!(quantize(0.3, BitDepth::F32), 0.3);
+    }
+
+    #[test]
+    fn test_f16_rounding_matches_half_precision() {
+        // 1 + 2^-11 is exactly halfway between two halves; ties to even → 1.0.
+        assert_eq!(round_to_f16(1.0 + 2f32.powi(-11)), 1.0);
+        // 1 + 2^-10 is representable.
+        assert_eq!(round_to_f16(1.0 + 2f32.powi(-10)), 1.0 + 2f32.powi(-10));
+        assert_eq!(round_to_f16(1.0e6), 65504.0);
+        // Subnormal halves are kept rather than flushed, down to 2^-24;
+        // half of that ties to even, which is zero.
+        assert_eq!(round_to_f16(2f32.powi(-20)), 2f32.powi(-20));
+        assert_eq!(round_to_f16(2f32.powi(-24)), 2f32.powi(-24));
+        assert_eq!(round_to_f16(2f32.powi(-25)), 0.0);
+    }
+}
@@ -0,0 +1,102 @@
+//! ColorChecker Classic (Macbeth) reference values.
+//!
+//! # Reference
+//! X-Rite ColorChecker Classic sRGB values (post-November 2014 formulation),
+//! D65-adapted, 8-bit sRGB-encoded.
+
+/// Number of patch columns on the chart.
+pub const COLUMNS: usize = 6;
+
+/// Number of patch rows on the chart.
+pub const ROWS: usize = 4;
+
+/// Patch names in reading order (left-to-right, top-to-bottom).
+pub const PATCH_NAMES: [&str; 24] = [
+    "dark skin",
+    "light skin",
+    "blue sky",
+    "foliage",
+    "blue flower",
+    "bluish green",
+    "orange",
+    "purplish blue",
+    "moderate red",
+    "purple",
+    "yellow green",
+    "orange yellow",
+    "blue",
+    "green",
+    "red",
+    "yellow",
+    "magenta",
+    "cyan",
+    "white 9.5",
+    "neutral 8",
+    "neutral 6.5",
+    "neutral 5",
+    "neutral 3.5",
+    "black 2",
+];
+
+/// 8-bit sRGB-encoded patch values in reading order.
+const SRGB8: [[u8; 3]; 24] = [
+    [115, 82, 68],
+    [194, 150, 130],
+    [98, 122, 157],
+    [87, 108, 67],
+    [133, 128, 177],
+    [103, 189, 170],
+    [214, 126, 44],
+    [80, 91, 166],
+    [193, 90, 99],
+    [94, 60, 108],
+    [157, 188, 64],
+    [224, 163, 46],
+    [56, 61, 150],
+    [70, 148, 73],
+    [175, 54, 60],
+    [231, 199, 31],
+    [187, 86, 149],
+    [8, 133, 161],
+    [243, 243, 242],
+    [200, 200, 200],
+    [160, 160, 160],
+    [122, 122, 121],
+    [85, 85, 85],
+    [52, 52, 52],
+];
+
+/// sRGB-encoded reference value of patch `index` in `[0, 1]`.
+pub fn srgb_encoded(index: usize) -> [f32; 3] {
+    SRGB8[index].map(|v| v as f32 / 255.0)
+}
+
+/// Linear Rec.709 / sRGB reference value of patch `index`.
+pub fn linear_srgb(index: usize) -> [f32; 3] {
+    use crate::color_management::transfer::{SrgbTransfer, TransferFunction};
+    srgb_encoded(index).map(|v| SrgbTransfer.to_linear(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neutral_row_is_gray_and_descending() {
+        let mut prev = f32::MAX;
+        for i in 18..24 {
+            let [r, g, b] = linear_srgb(i);
+            assert!(
+                (r - g).abs() < 0.01 && (g - b).abs() < 0.01,
+                "patch {i} not neutral"
+            );
+            assert!(g < prev, "neutral row should darken left to right");
+            prev = g;
+        }
+    }
+
+    #[test]
+    fn test_names_match_patch_count() {
+        assert_eq!(PATCH_NAMES.len(), COLUMNS * ROWS);
+    }
+}
@@ -0,0 +1,6 @@
+//! Test patterns — synthetic images for scope verification and display checks.
+
+pub mod generator;
+pub mod macbeth;
+
+pub use generator::{TestPattern, generate};
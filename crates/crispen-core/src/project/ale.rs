@@ -0,0 +1,152 @@
+//! Avid Log Exchange (`.ale`) import.
+//!
+//! Fields are tab-delimited (shown here with spaces):
+//!
+//! ```text
+//! Heading
+//! FIELD_DELIM  TABS
+//! FPS  23.976
+//!
+//! Column
+//! Name  Tape  Start  End  Source File  LUT
+//!
+//! Data
+//! A001C003  A001  01:00:00:00  01:00:02:00  A001C003.mov  show_v2.cube
+//! ```
+//!
+//! Columns are matched by name, case-insensitively. The input LUT is taken
+//! from the first column whose name contains `LUT` and whose value names a
+//! LUT file; cameras and DITs disagree on the exact column name.
+//! Notes come from a `Comments` or `Notes` column.
+
+use std::path::Path;
+
+use super::conform::find_lut_reference;
+use super::edl::parse_timecode;
+use super::timeline::{Clip, SourceRange, Timeline};
+
+/// Frame rate assumed when the heading has no `FPS` entry.
+const DEFAULT_FPS: f64 = 24.0;
+
+#[derive(PartialEq)]
+enum Section {
+    Heading,
+    Column,
+    Data,
+}
+
+/// Load an ALE file.
+pub fn load_ale(path: &Path) -> std::io::Result<Timeline> {
+    read_ale(&std::fs::read_to_string(path)?).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "ALE has no Column section")
+    })
+}
+
+/// Parse ALE text. Returns `None` if data appears before a column header.
+pub fn read_ale(text: &str) -> Option<Timeline> {
+    let mut section = Section::Heading;
+    let mut rate = DEFAULT_FPS;
+    let mut columns: Vec<String> = Vec::new();
+    let mut timeline = Timeline::default();
+
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        match line.trim() {
+            "Heading" => section = Section::Heading,
+            "Column" => section = Section::Column,
+            "Data" => section = Section::Data,
+            "" => {}
+            _ => match section {
+                Section::Heading => {
+                    if let Some((key, value)) = line.split_once('\t')
+                        && key.eq_ignore_ascii_case("FPS")
+                    {
+                        rate = value.trim().parse().unwrap_or(DEFAULT_FPS);
+                    }
+                }
+                Section::Column => {
+                    columns = line.split('\t').map(|c| c.trim().to_lowercase()).collect();
+                }
+                Section::Data => {
+                    if columns.is_empty() {
+                        return None;
+                    }
+                    let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+                    timeline.clips.push(read_row(&columns, &fields, rate));
+                }
+            },
+        }
+    }
+    Some(timeline)
+}
+
+fn read_row(columns: &[String], fields: &[&str], rate: f64) -> Clip {
+    let get = |name: &str| {
+        columns
+            .iter()
+            .position(|c| c == name)
+            .and_then(|i| fields.get(i).copied())
+            .filter(|v| !v.is_empty())
+    };
+
+    let start = get("start").and_then(|tc| parse_timecode(tc, rate));
+    let end = get("end").and_then(|tc| parse_timecode(tc, rate));
+    let source_range = start.zip(end).map(|(start, end)| SourceRange {
+        start,
+        duration: end - start,
+        rate,
+    });
+
+    let input_lut = columns
+        .iter()
+        .zip(fields)
+        .filter(|(c, _)| c.contains("lut"))
+        .find_map(|(_, v)| find_lut_reference(v));
+
+    Clip {
+        name: get("name").unwrap_or_default().to_string(),
+        media_path: get("source file").map(str::to_string),
+        reel: get("tape").map(str::to_string),
+        camera: get("camera").map(str::to_string),
+        source_range,
+        input_lut,
+        notes: get("comments").or_else(|| get("notes")).map(str::to_string),
+        ..Clip::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALE: &str = "Heading\nFIELD_DELIM\tTABS\nFPS\t25\n\n\
+Column\nName\tTape\tStart\tEnd\tSource File\tCamera\tASC_LUT\n\n\
+Data\n\
+A001C003\tA001\t01:00:00:00\t01:00:02:00\t/media/A001C003.mov\tA\tshow_v2.cube\n\
+A001C004\tA001\t01:00:10:00\t01:00:10:10\t\t\t\n";
+
+    #[test]
+    fn test_reads_rows_and_lut_column() {
+        let timeline = read_ale(ALE).unwrap();
+        assert_eq!(timeline.clips.len(), 2);
+
+        let a = &timeline.clips[0];
+        assert_eq!(a.name, "A001C003");
+        assert_eq!(a.media_path.as_deref(), Some("/media/A001C003.mov"));
+        assert_eq!(a.input_lut.as_deref(), Some("show_v2.cube"));
+        assert_eq!(a.reel.as_deref(), Some("A001"));
+        assert_eq!(a.camera.as_deref(), Some("A"));
+        let range = a.source_range.unwrap();
+        assert_eq!(range.rate, 25.0);
+        assert_eq!(range.duration, 50.0);
+
+        let b = &timeline.clips[1];
+        assert_eq!(b.media_path, None);
+        assert_eq!(b.input_lut, None);
+    }
+
+    #[test]
+    fn test_data_without_columns_is_rejected() {
+        assert!(read_ale("Data\nA001\t01:00:00:00\n").is_none());
+    }
+}
@@ -0,0 +1,249 @@
+//! Audit log — who changed the grade, when, and how.
+//!
+//! Facilities with several colorists on one project need a record of every
+//! grade change. An [`AuditLog`] appends one JSON object per line to a file
+//! next to the project and never rewrites it, so concurrent sessions and a
+//! crash mid-write lose at most the line being written:
+//!
+//! ```json
+//! {"timestamp":1760000000,"operator":"ana","action":"params_changed","changes":[{"field":"saturation","before":1.0,"after":1.2}]}
+//! {"timestamp":1760000042,"operator":"ana","action":"version_created","path":"/show/grades/sh010_v2.json"}
+//! ```
+//!
+//! [`AuditLog::export_json`] writes the entries as one JSON array for
+//! review or archiving.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::trace::{FieldChange, diff_grades};
+use crate::transform::params::GradingParams;
+
+/// Extension of the audit log written next to a project file.
+pub const AUDIT_EXTENSION: &str = "audit.jsonl";
+
+/// Errors from reading or writing an audit log.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid audit entry on line {line}: {source}")]
+    Entry {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// What changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Grade fields edited, by hand or by a tool such as auto balance.
+    ParamsChanged { changes: Vec<FieldChange> },
+    /// A look or grade preset applied over the grade.
+    LookApplied {
+        path: String,
+        changes: Vec<FieldChange>,
+    },
+    /// The grade was reset to identity.
+    GradeReset { changes: Vec<FieldChange> },
+    /// The grade was saved as a version.
+    VersionCreated { path: String },
+}
+
+/// One line of the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix time in seconds.
+    pub timestamp: u64,
+    /// Who made the change.
+    pub operator: String,
+    #[serde(flatten)]
+    pub action: AuditAction,
+}
+
+/// An append-only audit log file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLog {
+    path: PathBuf,
+    operator: String,
+}
+
+impl AuditLog {
+    /// Log at `path`, recording changes as `operator`. The file is created
+    /// on the first entry.
+    pub fn new(path: impl Into<PathBuf>, operator: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            operator: operator.into(),
+        }
+    }
+
+    /// Log kept next to `project`: `reel1.otio` logs to
+    /// `reel1.audit.jsonl`.
+    pub fn for_project(project: &Path, operator: impl Into<String>) -> Self {
+        Self::new(project.with_extension(AUDIT_EXTENSION), operator)
+    }
+
+    /// Path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Operator new entries are recorded as.
+    pub fn operator(&self) -> &str {
+        &self.operator
+    }
+
+    /// Append `action` as the current operator, now.
+    pub fn record(&self, action: AuditAction) -> Result<AuditEntry, AuditError> {
+        let entry = AuditEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            operator: self.operator.clone(),
+            action,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        // One write per entry, so appends from other sessions interleave
+        // by whole lines.
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())?;
+        Ok(entry)
+    }
+
+    /// Append the fields that differ from `before` to `after`; nothing is
+    /// written when the grades are equal.
+    pub fn record_params(
+        &self,
+        before: &GradingParams,
+        after: &GradingParams,
+    ) -> Result<Option<AuditEntry>, AuditError> {
+        let changes = diff_grades(before, after);
+        if changes.is_empty() {
+            return Ok(None);
+        }
+        self.record(AuditAction::ParamsChanged { changes })
+            .map(Some)
+    }
+
+    /// Every entry, oldest first. A log that does not exist yet is empty.
+    pub fn entries(&self) -> Result<Vec<AuditEntry>, AuditError> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|source| AuditError::Entry {
+                    line: i + 1,
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    /// Write every entry to `out` as a JSON array. Returns the entry count.
+    pub fn export_json(&self, out: &Path) -> Result<usize, AuditError> {
+        let entries = self.entries()?;
+        std::fs::write(out, serde_json::to_string_pretty(&entries)?)?;
+        Ok(entries.len())
+    }
+}
+
+/// Operator name from `CRISPEN_OPERATOR`, else the login name, else
+/// `unknown`.
+pub fn current_operator() -> String {
+    ["CRISPEN_OPERATOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "unknown".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> AuditLog {
+        let dir = std::env::temp_dir().join(format!("crispen_audit_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let log = AuditLog::for_project(&dir.join("reel1.otio"), "ana");
+        std::fs::remove_file(log.path()).ok();
+        log
+    }
+
+    #[test]
+    fn test_audit_log_appends_and_reads_back() {
+        let log = temp_log("append");
+        assert_eq!(log.path().file_name().unwrap(), "reel1.audit.jsonl");
+        assert!(log.entries().unwrap().is_empty());
+
+        let before = GradingParams::default();
+        let after = GradingParams {
+            saturation: 1.2,
+            ..GradingParams::default()
+        };
+        assert!(log.record_params(&before, &before).unwrap().is_none());
+        log.record_params(&before, &after).unwrap();
+        AuditLog::new(log.path(), "ben")
+            .record(AuditAction::VersionCreated {
+                path: "sh010_v2.json".into(),
+            })
+            .unwrap();
+
+        let entries = log.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operator, "ana");
+        let AuditAction::ParamsChanged { changes } = &entries[0].action else {
+            panic!("expected a params entry, got {:?}", entries[0].action);
+        };
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "saturation");
+        assert_eq!(entries[1].operator, "ben");
+
+        let line = std::fs::read_to_string(log.path()).unwrap();
+        assert!(
+            line.lines()
+                .nth(1)
+                .unwrap()
+                .contains("\"action\":\"version_created\"")
+        );
+        std::fs::remove_dir_all(log.path().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_audit_export_is_a_json_array() {
+        let log = temp_log("export");
+        log.record(AuditAction::LookApplied {
+            path: "day_ext.json".into(),
+            changes: Vec::new(),
+        })
+        .unwrap();
+        let out = log.path().with_file_name("export.json");
+        assert_eq!(log.export_json(&out).unwrap(), 1);
+
+        let exported: Vec<AuditEntry> =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(exported, log.entries().unwrap());
+
+        std::fs::write(log.path(), "{not json}\n").unwrap();
+        assert!(matches!(
+            log.entries(),
+            Err(AuditError::Entry { line: 1, .. })
+        ));
+        std::fs::remove_dir_all(log.path().parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,659 @@
+//! File checksums and checksum manifests for deliveries.
+//!
+//! Manifests use the `md5sum` text format, one `<hex digest>  <path>` line
+//! per file with paths relative to the manifest's folder, so a receiving
+//! facility can check a delivery with `md5sum -c` or `xxhsum -c` and no
+//! Crispen install. The algorithm follows the manifest's extension:
+//! `.md5` for MD5 (RFC 1321), which most delivery specs ask for, or
+//! `.xxh64` for XXH64, which is many times faster on large plates. Both
+//! are implemented here rather than pulled in as dependencies; they guard
+//! against transfer damage, not tampering.
+
+use std::fmt::{self, Write as _};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Errors from building, reading, or verifying a manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum ChecksumError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{} is not a .md5 or .xxh64 manifest", .0.display())]
+    UnknownManifest(PathBuf),
+    #[error("{}: line {line} is not a checksum entry", .path.display())]
+    Parse { path: PathBuf, line: usize },
+}
+
+/// Checksum algorithms a manifest can use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Md5,
+    Xxh64,
+}
+
+impl ChecksumAlgorithm {
+    /// Extension of manifests in this algorithm, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Md5 => "md5",
+            Self::Xxh64 => "xxh64",
+        }
+    }
+
+    /// Algorithm of the manifest at `path`, from its extension.
+    pub fn from_manifest(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "md5" => Some(Self::Md5),
+            "xxh64" => Some(Self::Xxh64),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ChecksumAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "md5" => Ok(Self::Md5),
+            "xxh64" | "xxhash" => Ok(Self::Xxh64),
+            other => Err(format!("unknown checksum algorithm \"{other}\"")),
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// Per-round left-rotation amounts.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// Per-round additive constants, `floor(|sin(i + 1)| * 2^32)`.
+const K: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee,
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501,
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be,
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821,
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa,
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8,
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed,
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a,
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c,
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70,
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05,
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665,
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039,
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1,
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1,
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+/// Streaming MD5 digest.
+#[derive(Debug, Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: [u8; 64],
+    buffered: usize,
+    length: u64,
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self {
+            state: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+            buffer: [0; 64],
+            buffered: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Md5 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `data` into the digest.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let take = data.len().min(64 - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block.try_into().expect("64-byte chunk"));
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// The 16-byte digest of everything fed in.
+    pub fn finish(mut self) -> [u8; 16] {
+        let bits = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80];
+        padding.resize((119 - self.buffered) % 64 + 1, 0);
+        self.update(&padding);
+        self.update(&bits.to_le_bytes());
+        let mut digest = [0; 16];
+        for (out, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            out.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let words: [u32; 16] = std::array::from_fn(|i| {
+            u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap())
+        });
+        let [mut a, mut b, mut c, mut d] = self.state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(K[i])
+                .wrapping_add(words[g])
+                .rotate_left(SHIFTS[i]);
+            (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+        }
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+const XXH_PRIME_1: u64 = 0x9e37_79b1_85eb_ca87;
+const XXH_PRIME_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const XXH_PRIME_3: u64 = 0x1656_67b1_9e37_79f9;
+const XXH_PRIME_4: u64 = 0x85eb_ca77_c2b2_ae63;
+const XXH_PRIME_5: u64 = 0x27d4_eb2f_1656_67c5;
+
+/// Streaming XXH64 digest with seed 0.
+#[derive(Debug, Clone)]
+pub struct Xxh64 {
+    lanes: [u64; 4],
+    buffer: [u8; 32],
+    buffered: usize,
+    length: u64,
+}
+
+impl Default for Xxh64 {
+    fn default() -> Self {
+        Self {
+            lanes: [
+                XXH_PRIME_1.wrapping_add(XXH_PRIME_2),
+                XXH_PRIME_2,
+                0,
+                XXH_PRIME_1.wrapping_neg(),
+            ],
+            buffer: [0; 32],
+            buffered: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Xxh64 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed `data` into the digest.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let take = data.len().min(32 - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 32 {
+                return;
+            }
+            let stripe = self.buffer;
+            self.stripe(&stripe);
+            self.buffered = 0;
+        }
+        let mut stripes = data.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// The digest of everything fed in, big-endian as `xxhsum` prints it.
+    pub fn finish(self) -> [u8; 8] {
+        let mut hash = if self.length >= 32 {
+            let [a, b, c, d] = self.lanes;
+            let mut hash = a
+                .rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18));
+            for lane in self.lanes {
+                hash ^= xxh_round(0, lane);
+                hash = hash.wrapping_mul(XXH_PRIME_1).wrapping_add(XXH_PRIME_4);
+            }
+            hash
+        } else {
+            XXH_PRIME_5
+        };
+        hash = hash.wrapping_add(self.length);
+
+        let mut rest = &self.buffer[..self.buffered];
+        while rest.len() >= 8 {
+            let (word, tail) = rest.split_at(8);
+            hash ^= xxh_round(0, u64::from_le_bytes(word.try_into().unwrap()));
+            hash = hash
+                .rotate_left(27)
+                .wrapping_mul(XXH_PRIME_1)
+                .wrapping_add(XXH_PRIME_4);
+            rest = tail;
+        }
+        if rest.len() >= 4 {
+            let (word, tail) = rest.split_at(4);
+            hash ^=
+                u64::from(u32::from_le_bytes(word.try_into().unwrap())).wrapping_mul(XXH_PRIME_1);
+            hash = hash
+                .rotate_left(23)
+                .wrapping_mul(XXH_PRIME_2)
+                .wrapping_add(XXH_PRIME_3);
+            rest = tail;
+        }
+        for &byte in rest {
+            hash ^= u64::from(byte).wrapping_mul(XXH_PRIME_5);
+            hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(XXH_PRIME_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(XXH_PRIME_3);
+        hash ^= hash >> 32;
+        hash.to_be_bytes()
+    }
+
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (lane, word) in self.lanes.iter_mut().zip(stripe.chunks_exact(8)) {
+            *lane = xxh_round(*lane, u64::from_le_bytes(word.try_into().unwrap()));
+        }
+    }
+}
+
+fn xxh_round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(XXH_PRIME_2))
+        .rotate_left(31)
+        .wrapping_mul(XXH_PRIME_1)
+}
+
+/// Lowercase hex of `bytes`.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// Digest of the file at `path` in `algorithm`, as lowercase hex.
+pub fn file_digest(path: &Path, algorithm: ChecksumAlgorithm) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut buffer = vec![0; 1 << 16];
+    let mut feed = |update: &mut dyn FnMut(&[u8])| -> std::io::Result<()> {
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                return Ok(());
+            }
+            update(&buffer[..read]);
+        }
+    };
+    Ok(match algorithm {
+        ChecksumAlgorithm::Md5 => {
+            let mut md5 = Md5::new();
+            feed(&mut |data| md5.update(data))?;
+            to_hex(&md5.finish())
+        }
+        ChecksumAlgorithm::Xxh64 => {
+            let mut xxh = Xxh64::new();
+            feed(&mut |data| xxh.update(data))?;
+            to_hex(&xxh.finish())
+        }
+    })
+}
+
+/// One file of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Hex digest.
+    pub digest: String,
+    /// Path relative to the manifest's folder, or absolute.
+    pub path: PathBuf,
+}
+
+/// Checksums of a set of files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub algorithm: ChecksumAlgorithm,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Outcome of [`Manifest::verify`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Files whose digest matched.
+    pub matched: usize,
+    /// Files whose digest differs from the manifest.
+    pub mismatched: Vec<PathBuf>,
+    /// Files listed but not found.
+    pub missing: Vec<PathBuf>,
+}
+
+impl VerifyReport {
+    /// Whether every listed file is present and matches.
+    pub fn passed(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty()
+    }
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for path in &self.mismatched {
+            writeln!(f, "{}: FAILED", path.display())?;
+        }
+        for path in &self.missing {
+            writeln!(f, "{}: MISSING", path.display())?;
+        }
+        writeln!(
+            f,
+            "{} matched, {} failed, {} missing",
+            self.matched,
+            self.mismatched.len(),
+            self.missing.len()
+        )
+    }
+}
+
+impl Manifest {
+    /// Checksums of `files`, listed relative to `dir` where they are
+    /// inside it.
+    pub fn from_files(
+        dir: &Path,
+        files: &[PathBuf],
+        algorithm: ChecksumAlgorithm,
+    ) -> std::io::Result<Self> {
+        let entries = files
+            .iter()
+            .map(|file| {
+                Ok(ManifestEntry {
+                    digest: file_digest(file, algorithm)?,
+                    path: file.strip_prefix(dir).unwrap_or(file).to_path_buf(),
+                })
+            })
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self { algorithm, entries })
+    }
+
+    /// Checksums of every file under `dir`, in path order. Hidden files and
+    /// existing `.md5` / `.xxh64` manifests are left out.
+    pub fn from_folder(dir: &Path, algorithm: ChecksumAlgorithm) -> std::io::Result<Self> {
+        let mut files = Vec::new();
+        collect_files(dir, &mut files)?;
+        files.sort();
+        Self::from_files(dir, &files, algorithm)
+    }
+
+    /// Read the manifest at `path`; the algorithm comes from its extension.
+    pub fn load(path: &Path) -> Result<Self, ChecksumError> {
+        let algorithm = ChecksumAlgorithm::from_manifest(path)
+            .ok_or_else(|| ChecksumError::UnknownManifest(path.to_path_buf()))?;
+        let text = std::fs::read_to_string(path)?;
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            // `md5sum` marks binary-mode entries with `*` in place of the
+            // second space.
+            let entry = line
+                .split_once("  ")
+                .or_else(|| line.split_once(" *"))
+                .filter(|(digest, _)| digest.chars().all(|c| c.is_ascii_hexdigit()));
+            let Some((digest, name)) = entry else {
+                return Err(ChecksumError::Parse {
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                });
+            };
+            entries.push(ManifestEntry {
+                digest: digest.to_ascii_lowercase(),
+                path: PathBuf::from(name),
+            });
+        }
+        Ok(Self { algorithm, entries })
+    }
+
+    /// Write the manifest to `path` in `md5sum` format.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let name = entry.path.to_string_lossy().replace('\\', "/");
+            let _ = writeln!(text, "{}  {name}", entry.digest);
+        }
+        std::fs::write(path, text)
+    }
+
+    /// Check every listed file, resolving relative paths against `dir`.
+    pub fn verify(&self, dir: &Path) -> VerifyReport {
+        let mut report = VerifyReport::default();
+        for entry in &self.entries {
+            match file_digest(&dir.join(&entry.path), self.algorithm) {
+                Ok(digest) if digest == entry.digest => report.matched += 1,
+                Ok(_) => report.mismatched.push(entry.path.clone()),
+                Err(_) => report.missing.push(entry.path.clone()),
+            }
+        }
+        report
+    }
+}
+
+/// Default manifest path for a delivery folder: inside it, named after it,
+/// e.g. `reel1/reel1.md5`.
+pub fn folder_manifest_path(dir: &Path, algorithm: ChecksumAlgorithm) -> PathBuf {
+    let name = dir
+        .file_name()
+        .map_or_else(|| "manifest".into(), |n| n.to_string_lossy());
+    dir.join(format!("{name}.{}", algorithm.extension()))
+}
+
+/// Check the manifest at `path` against the files beside it.
+pub fn verify_manifest(path: &Path) -> Result<VerifyReport, ChecksumError> {
+    let manifest = Manifest::load(path)?;
+    Ok(manifest.verify(path.parent().unwrap_or(Path::new(""))))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if ChecksumAlgorithm::from_manifest(&path).is_none() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md5(bytes: &[u8]) -> String {
+        let mut md5 = Md5::new();
+        md5.update(bytes);
+        to_hex(&md5.finish())
+    }
+
+    #[test]
+    fn test_md5_matches_rfc_1321_vectors() {
+        assert_eq!(md5(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            md5(
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            ),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+
+        // Split updates across block boundaries give the same digest.
+        let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        let mut split = Md5::new();
+        for chunk in data.chunks(37) {
+            split.update(chunk);
+        }
+        assert_eq!(to_hex(&split.finish()), md5(&data));
+    }
+
+    fn xxh64(bytes: &[u8]) -> String {
+        let mut xxh = Xxh64::new();
+        xxh.update(bytes);
+        to_hex(&xxh.finish())
+    }
+
+    #[test]
+    fn test_xxh64_matches_reference_vectors() {
+        assert_eq!(xxh64(b""), "ef46db3751d8e999");
+        assert_eq!(xxh64(b"abc"), "44bc2cf5ad770999");
+        assert_eq!(
+            xxh64(b"Nobody inspects the spammish repetition"),
+            "fbcea83c8a378bf1"
+        );
+
+        let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        let mut split = Xxh64::new();
+        for chunk in data.chunks(13) {
+            split.update(chunk);
+        }
+        assert_eq!(to_hex(&split.finish()), xxh64(&data));
+    }
+
+    #[test]
+    fn test_folder_manifest_round_trips_and_verifies() {
+        let dir = std::env::temp_dir().join(format!("crispen_manifest_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("plates")).unwrap();
+        std::fs::write(dir.join("a.txt"), b"abc").unwrap();
+        std::fs::write(dir.join("plates/b.txt"), b"").unwrap();
+        std::fs::write(dir.join(".DS_Store"), b"x").unwrap();
+        std::fs::write(dir.join("old.xxh64"), b"").unwrap();
+
+        let manifest = Manifest::from_folder(&dir, ChecksumAlgorithm::Md5).unwrap();
+        let path = dir.join("delivery.md5");
+        manifest.save(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "900150983cd24fb0d6963f7d28e17f72  a.txt\n\
+             d41d8cd98f00b204e9800998ecf8427e  plates/b.txt\n"
+        );
+        assert_eq!(Manifest::load(&path).unwrap(), manifest);
+        assert!(verify_manifest(&path).unwrap().passed());
+
+        std::fs::write(dir.join("a.txt"), b"abd").unwrap();
+        std::fs::remove_file(dir.join("plates/b.txt")).unwrap();
+        let report = verify_manifest(&path).unwrap();
+        assert_eq!(report.mismatched, [PathBuf::from("a.txt")]);
+        assert_eq!(report.missing, [PathBuf::from("plates/b.txt")]);
+        assert!(!report.passed());
+
+        assert_eq!(
+            folder_manifest_path(Path::new("/out/reel1"), ChecksumAlgorithm::Xxh64),
+            Path::new("/out/reel1/reel1.xxh64")
+        );
+        let xxh = Manifest::from_folder(&dir, ChecksumAlgorithm::Xxh64).unwrap();
+        assert_eq!(xxh.entries[0].digest.len(), 16);
+        assert!(matches!(
+            Manifest::load(&dir.join("a.txt")),
+            Err(ChecksumError::UnknownManifest(_))
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
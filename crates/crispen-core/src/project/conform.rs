@@ -0,0 +1,283 @@
+//! Input LUT conform — resolve editorial LUT references and load them.
+//!
+//! Editorial lists name LUTs by bare filename (an EDL comment, an ALE
+//! column, OTIO clip metadata). Conforming searches the given directories
+//! for each referenced file, rewrites [`Clip::input_lut`] to the resolved
+//! path, loads every distinct LUT once, and records what could not be found
+//! or read in a [`ConformReport`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::timeline::Timeline;
+use crate::transform::lut::Lut3D;
+
+/// File extensions recognized as LUT references in editorial metadata.
+pub const LUT_EXTENSIONS: &[&str] = &["cube", "3dl", "csp", "clf", "cdl", "cc", "ccc"];
+
+/// Outcome of resolving one clip's LUT reference.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LutStatus {
+    /// Found and loaded from this path.
+    Attached(PathBuf),
+    /// Not found in any search directory.
+    Missing,
+    /// Found at this path but could not be loaded.
+    Unreadable(PathBuf, String),
+}
+
+/// One line of the conform report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformEntry {
+    /// Index of the clip in the timeline.
+    pub clip_index: usize,
+    /// Clip name.
+    pub clip_name: String,
+    /// LUT reference as given by editorial.
+    pub lut: String,
+    /// Resolution result.
+    pub status: LutStatus,
+}
+
+/// Result of [`conform_luts`]: per-clip status plus the loaded LUTs.
+#[derive(Debug, Clone, Default)]
+pub struct ConformReport {
+    /// One entry per clip that references a LUT, in timeline order.
+    pub entries: Vec<ConformEntry>,
+    /// Loaded LUTs keyed by resolved path (as stored in `Clip::input_lut`).
+    pub luts: HashMap<String, Lut3D>,
+}
+
+impl ConformReport {
+    /// Entries whose LUT file was not found.
+    pub fn missing(&self) -> impl Iterator<Item = &ConformEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == LutStatus::Missing)
+    }
+
+    /// Whether every referenced LUT was attached.
+    pub fn is_complete(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|e| matches!(e.status, LutStatus::Attached(_)))
+    }
+}
+
+impl fmt::Display for ConformReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attached = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e.status, LutStatus::Attached(_)))
+            .count();
+        writeln!(
+            f,
+            "LUT conform: {attached} of {} attached",
+            self.entries.len()
+        )?;
+        for entry in &self.entries {
+            let (tag, detail) = match &entry.status {
+                LutStatus::Attached(path) => ("OK", path.display().to_string()),
+                LutStatus::Missing => ("MISSING", String::new()),
+                LutStatus::Unreadable(path, err) => {
+                    ("UNREADABLE", format!("{}: {err}", path.display()))
+                }
+            };
+            writeln!(
+                f,
+                "{:>4}  {:<10} {}  {}  {}",
+                entry.clip_index + 1,
+                tag,
+                entry.clip_name,
+                entry.lut,
+                detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` ends in a recognized LUT extension.
+pub fn is_lut_filename(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| LUT_EXTENSIONS.iter().any(|l| ext.eq_ignore_ascii_case(l)))
+}
+
+/// Find a LUT filename in a free-form metadata string.
+///
+/// Handles `LUT: show.cube`, `LUT_FILE=show.cube`, quoted paths, and bare
+/// filenames. Paths containing spaces are only recognized when quoted.
+pub fn find_lut_reference(text: &str) -> Option<String> {
+    if let Some(quoted) = text
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .find(|s| is_lut_filename(s))
+    {
+        return Some(quoted.to_string());
+    }
+    text.split(|c: char| c.is_whitespace() || c == '=' || c == ',')
+        .map(|token| token.trim_matches(|c| c == '\'' || c == ':'))
+        .find(|token| is_lut_filename(token))
+        .map(str::to_string)
+}
+
+/// Resolve and load every clip's input LUT.
+///
+/// References that already point at an existing file are used as-is;
+/// otherwise the file name is looked up in each of `search_dirs` in order.
+/// Attached clips have `input_lut` rewritten to the resolved path.
+pub fn conform_luts(timeline: &mut Timeline, search_dirs: &[&Path]) -> ConformReport {
+    let mut report = ConformReport::default();
+
+    for (clip_index, clip) in timeline.clips.iter_mut().enumerate() {
+        let Some(lut) = clip.input_lut.clone() else {
+            continue;
+        };
+
+        let status = match resolve(&lut, search_dirs) {
+            None => LutStatus::Missing,
+            Some(path) => {
+                let key = path.to_string_lossy().into_owned();
+                let loaded = if report.luts.contains_key(&key) {
+                    Ok(())
+                } else {
+                    load(&path).map(|l| {
+                        report.luts.insert(key.clone(), l);
+                    })
+                };
+                match loaded {
+                    Ok(()) => {
+                        clip.input_lut = Some(key);
+                        LutStatus::Attached(path)
+                    }
+                    Err(e) => LutStatus::Unreadable(path, e),
+                }
+            }
+        };
+
+        if status == LutStatus::Missing {
+            tracing::warn!("clip {}: input LUT {lut} not found", clip.name);
+        }
+        report.entries.push(ConformEntry {
+            clip_index,
+            clip_name: clip.name.clone(),
+            lut,
+            status,
+        });
+    }
+    report
+}
+
+fn resolve(lut: &str, search_dirs: &[&Path]) -> Option<PathBuf> {
+    let direct = Path::new(lut);
+    if direct.is_absolute() && direct.is_file() {
+        return Some(direct.to_path_buf());
+    }
+    // Editorial paths come from other machines; only the file name is portable.
+    let name = lut.rsplit(['/', '\\']).next().unwrap_or(lut);
+    search_dirs
+        .iter()
+        .flat_map(|dir| [dir.join(lut), dir.join(name)])
+        .find(|p| p.is_file())
+}
+
+fn load(path: &Path) -> Result<Lut3D, String> {
+    let is_cube = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("cube"));
+    if !is_cube {
+        return Err("only .cube LUTs can be loaded".into());
+    }
+    Lut3D::load_cube(path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::timeline::Clip;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crispen_conform_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn clip(name: &str, lut: Option<&str>) -> Clip {
+        Clip {
+            name: name.into(),
+            input_lut: lut.map(str::to_string),
+            ..Clip::default()
+        }
+    }
+
+    #[test]
+    fn test_find_lut_reference_forms() {
+        assert_eq!(
+            find_lut_reference("LUT: show_v2.cube").as_deref(),
+            Some("show_v2.cube")
+        );
+        assert_eq!(
+            find_lut_reference("LUT_FILE=/luts/a.3dl").as_deref(),
+            Some("/luts/a.3dl")
+        );
+        assert_eq!(
+            find_lut_reference("look \"My Show/day look.CUBE\" v3").as_deref(),
+            Some("My Show/day look.CUBE")
+        );
+        assert_eq!(find_lut_reference("FROM CLIP NAME: A001.mov"), None);
+    }
+
+    #[test]
+    fn test_conform_attaches_found_and_reports_missing() {
+        let dir = temp_dir("attach");
+        let mut lut = Lut3D::new(2);
+        lut.bake(&Default::default());
+        lut.save_cube(&dir.join("show.cube")).unwrap();
+
+        let mut timeline = Timeline {
+            name: "t".into(),
+            clips: vec![
+                clip("a", Some("C:\\editorial\\show.cube")),
+                clip("b", None),
+                clip("c", Some("gone.cube")),
+                clip("d", Some("show.cube")),
+            ],
+            ..Timeline::default()
+        };
+        let report = conform_luts(&mut timeline, &[&dir]);
+
+        assert_eq!(report.entries.len(), 3);
+        assert!(!report.is_complete());
+        assert_eq!(report.luts.len(), 1);
+        let resolved = dir.join("show.cube").to_string_lossy().into_owned();
+        assert_eq!(timeline.clips[0].input_lut.as_deref(), Some(&*resolved));
+        assert_eq!(timeline.clips[3].input_lut.as_deref(), Some(&*resolved));
+        let missing: Vec<_> = report.missing().map(|e| e.clip_name.as_str()).collect();
+        assert_eq!(missing, ["c"]);
+        assert_eq!(timeline.clips[2].input_lut.as_deref(), Some("gone.cube"));
+        assert!(report.to_string().contains("MISSING"));
+    }
+
+    #[test]
+    fn test_unsupported_format_is_unreadable() {
+        let dir = temp_dir("unreadable");
+        std::fs::write(dir.join("look.3dl"), "0 512 1023\n").unwrap();
+        let mut timeline = Timeline {
+            name: "t".into(),
+            clips: vec![clip("a", Some("look.3dl"))],
+            ..Timeline::default()
+        };
+        let report = conform_luts(&mut timeline, &[&dir]);
+        assert!(matches!(
+            report.entries[0].status,
+            LutStatus::Unreadable(..)
+        ));
+    }
+}
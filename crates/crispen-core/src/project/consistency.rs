@@ -0,0 +1,489 @@
+//! Exposure and color consistency across a clip list.
+//!
+//! Measures one representative frame per clip — luma level and spread,
+//! gray-world white balance, and saturation — and flags clips that sit far
+//! from the rest of the timeline, so the shots needing attention can be
+//! graded first.
+//!
+//! Outliers use a robust z-score (median and median absolute deviation), so
+//! a handful of off shots do not drag the reference they are measured
+//! against. Each metric has a minimum spread: on a well-matched timeline
+//! the MAD is close to zero, and tiny differences would otherwise score as
+//! outliers.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::timeline::{Clip, Timeline};
+use crate::grading::auto_balance::auto_white_balance;
+use crate::image::GradingImage;
+
+/// LUT size used to grade frames when [`ConsistencySettings::graded`] is set.
+const GRADE_LUT_SIZE: u32 = 33;
+
+/// Luma below which exposure is clamped before taking log2, so black frames
+/// stay finite.
+const MIN_LUMA: f32 = 1e-4;
+
+/// Scales a MAD to the standard deviation of a normal distribution.
+const MAD_TO_SIGMA: f32 = 1.4826;
+
+/// Statistics of one frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClipStats {
+    /// Mean Rec.709 luma.
+    pub luma_mean: f32,
+    /// 5th percentile luma.
+    pub luma_p5: f32,
+    /// Median luma.
+    pub luma_p50: f32,
+    /// 95th percentile luma.
+    pub luma_p95: f32,
+    /// Gray-world warm / cool cast, the negated [`auto_white_balance`]
+    /// temperature correction; positive means the frame is warm.
+    pub temperature: f32,
+    /// Gray-world tint cast; positive means the frame is green.
+    pub tint: f32,
+    /// Mean HSV saturation.
+    pub saturation: f32,
+}
+
+impl ClipStats {
+    /// Measure `image`.
+    pub fn measure(image: &GradingImage) -> Self {
+        if image.pixels.is_empty() {
+            return Self::default();
+        }
+        let mut luma: Vec<f32> = image
+            .pixels
+            .iter()
+            .map(|p| 0.2126 * p[0] + 0.7152 * p[1] + 0.0722 * p[2])
+            .collect();
+        let luma_mean =
+            (luma.iter().map(|&l| f64::from(l)).sum::<f64>() / luma.len() as f64) as f32;
+        luma.sort_unstable_by(f32::total_cmp);
+        let percentile = |p: f32| luma[((luma.len() - 1) as f32 * p).round() as usize];
+
+        let saturation = image
+            .pixels
+            .iter()
+            .map(|p| {
+                let max = p[0].max(p[1]).max(p[2]);
+                let min = p[0].min(p[1]).min(p[2]);
+                if max > MIN_LUMA {
+                    f64::from(((max - min) / max).clamp(0.0, 1.0))
+                } else {
+                    0.0
+                }
+            })
+            .sum::<f64>()
+            / image.pixels.len() as f64;
+
+        // auto_white_balance returns the correction; negate it to describe
+        // the cast.
+        let (temperature, tint) = auto_white_balance(image);
+        Self {
+            luma_mean,
+            luma_p5: percentile(0.05),
+            luma_p50: percentile(0.5),
+            luma_p95: percentile(0.95),
+            temperature: -temperature,
+            tint: -tint,
+            saturation: saturation as f32,
+        }
+    }
+
+    /// Value of `metric` for these statistics.
+    pub fn metric(&self, metric: Metric) -> f32 {
+        match metric {
+            Metric::Exposure => self.luma_mean.max(MIN_LUMA).log2(),
+            Metric::Contrast => {
+                self.luma_p95.max(MIN_LUMA).log2() - self.luma_p5.max(MIN_LUMA).log2()
+            }
+            Metric::Temperature => self.temperature,
+            Metric::Tint => self.tint,
+            Metric::Saturation => self.saturation,
+        }
+    }
+}
+
+/// A quantity compared across clips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Metric {
+    /// Mean luma, in stops.
+    Exposure,
+    /// 5th to 95th percentile luma range, in stops.
+    Contrast,
+    /// Warm / cool cast.
+    Temperature,
+    /// Green / magenta cast.
+    Tint,
+    /// Mean saturation.
+    Saturation,
+}
+
+impl Metric {
+    /// Every metric, in report order.
+    pub const ALL: [Metric; 5] = [
+        Metric::Exposure,
+        Metric::Contrast,
+        Metric::Temperature,
+        Metric::Tint,
+        Metric::Saturation,
+    ];
+
+    /// Smallest spread the z-score is measured against, so a timeline that
+    /// already matches does not flag differences nobody would see.
+    pub fn min_spread(self) -> f32 {
+        match self {
+            Metric::Exposure => 0.1,
+            Metric::Contrast => 0.15,
+            Metric::Temperature | Metric::Tint => 0.05,
+            Metric::Saturation => 0.03,
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Metric::Exposure => "exposure",
+            Metric::Contrast => "contrast",
+            Metric::Temperature => "temperature",
+            Metric::Tint => "tint",
+            Metric::Saturation => "saturation",
+        })
+    }
+}
+
+/// Settings for [`analyze_timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencySettings {
+    /// Robust z-score above which a metric is an outlier.
+    pub threshold: f32,
+    /// Measure each frame through the clip's grade stack instead of as
+    /// loaded, to check the grade rather than the footage.
+    pub graded: bool,
+}
+
+impl Default for ConsistencySettings {
+    fn default() -> Self {
+        Self {
+            threshold: 3.5,
+            graded: false,
+        }
+    }
+}
+
+/// A metric on which a clip stands out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Outlier {
+    pub metric: Metric,
+    /// The clip's value.
+    pub value: f32,
+    /// Timeline median.
+    pub median: f32,
+    /// Robust z-score; the sign says which side of the median.
+    pub score: f32,
+}
+
+/// Statistics and outliers of one clip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipConsistency {
+    /// Index of the clip in the timeline.
+    pub clip_index: usize,
+    /// Clip name.
+    pub clip_name: String,
+    pub stats: ClipStats,
+    /// Metrics past the threshold, largest score first.
+    pub outliers: Vec<Outlier>,
+}
+
+impl ClipConsistency {
+    /// Largest absolute score among the outliers, 0 when there are none.
+    pub fn priority(&self) -> f32 {
+        self.outliers.first().map_or(0.0, |o| o.score.abs())
+    }
+}
+
+/// A clip whose frame could not be measured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedClip {
+    pub clip_index: usize,
+    pub clip_name: String,
+    pub reason: String,
+}
+
+/// Result of [`analyze_timeline`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Measured clips, in timeline order.
+    pub clips: Vec<ClipConsistency>,
+    /// Clips with no readable frame.
+    pub skipped: Vec<SkippedClip>,
+    /// Threshold the outliers were found with.
+    pub threshold: f32,
+}
+
+impl ConsistencyReport {
+    /// Flag outliers among already measured clips.
+    pub fn from_stats(clips: Vec<(usize, String, ClipStats)>, threshold: f32) -> Self {
+        let medians = Metric::ALL.map(|metric| {
+            let values: Vec<f32> = clips.iter().map(|c| c.2.metric(metric)).collect();
+            let median = median(&values);
+            let deviations: Vec<f32> = values.iter().map(|v| (v - median).abs()).collect();
+            let spread = (median_of(deviations) * MAD_TO_SIGMA).max(metric.min_spread());
+            (median, spread)
+        });
+
+        let clips = clips
+            .into_iter()
+            .map(|(clip_index, clip_name, stats)| {
+                let mut outliers: Vec<Outlier> = Metric::ALL
+                    .iter()
+                    .zip(&medians)
+                    .filter_map(|(&metric, &(median, spread))| {
+                        let value = stats.metric(metric);
+                        let score = (value - median) / spread;
+                        (score.abs() > threshold).then_some(Outlier {
+                            metric,
+                            value,
+                            median,
+                            score,
+                        })
+                    })
+                    .collect();
+                outliers.sort_by(|a, b| b.score.abs().total_cmp(&a.score.abs()));
+                ClipConsistency {
+                    clip_index,
+                    clip_name,
+                    stats,
+                    outliers,
+                }
+            })
+            .collect();
+
+        Self {
+            clips,
+            skipped: Vec::new(),
+            threshold,
+        }
+    }
+
+    /// Clips with at least one outlier, most inconsistent first.
+    pub fn prioritized(&self) -> Vec<&ClipConsistency> {
+        let mut flagged: Vec<&ClipConsistency> = self
+            .clips
+            .iter()
+            .filter(|c| !c.outliers.is_empty())
+            .collect();
+        flagged.sort_by(|a, b| b.priority().total_cmp(&a.priority()));
+        flagged
+    }
+}
+
+impl fmt::Display for ConsistencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flagged = self.prioritized();
+        writeln!(
+            f,
+            "Consistency: {} of {} clip(s) flagged",
+            flagged.len(),
+            self.clips.len()
+        )?;
+        for clip in flagged {
+            let details: Vec<String> = clip
+                .outliers
+                .iter()
+                .map(|o| {
+                    let side = if o.score > 0.0 { "high" } else { "low" };
+                    format!("{} {side} ({:+.1}σ)", o.metric, o.score)
+                })
+                .collect();
+            writeln!(
+                f,
+                "{:>4}  {}: {}",
+                clip.clip_index + 1,
+                clip.clip_name,
+                details.join(", ")
+            )?;
+        }
+        for skipped in &self.skipped {
+            writeln!(
+                f,
+                "{:>4}  {}: skipped, {}",
+                skipped.clip_index + 1,
+                skipped.clip_name,
+                skipped.reason
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Measure a representative frame of every clip and flag the outliers.
+///
+/// `load` returns the frame to measure for a clip; its error is recorded in
+/// [`ConsistencyReport::skipped`] and the rest of the timeline is still
+/// analyzed.
+pub fn analyze_timeline(
+    timeline: &Timeline,
+    settings: &ConsistencySettings,
+    mut load: impl FnMut(&Clip) -> Result<GradingImage, String>,
+) -> ConsistencyReport {
+    let mut measured = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, clip) in timeline.clips.iter().enumerate() {
+        let mut image = match load(clip) {
+            Ok(image) if !image.pixels.is_empty() => image,
+            Ok(_) => {
+                skipped.push(SkippedClip {
+                    clip_index: index,
+                    clip_name: clip.name.clone(),
+                    reason: "empty frame".into(),
+                });
+                continue;
+            }
+            Err(reason) => {
+                skipped.push(SkippedClip {
+                    clip_index: index,
+                    clip_name: clip.name.clone(),
+                    reason,
+                });
+                continue;
+            }
+        };
+        if settings.graded
+            && let Some(stack) = timeline.grade_stack(index)
+        {
+            let lut = stack.bake(GRADE_LUT_SIZE);
+            for p in &mut image.pixels {
+                let [r, g, b] = lut.apply([p[0], p[1], p[2]]);
+                *p = [r, g, b, p[3]];
+            }
+        }
+        measured.push((index, clip.name.clone(), ClipStats::measure(&image)));
+    }
+
+    let mut report = ConsistencyReport::from_stats(measured, settings.threshold);
+    report.skipped = skipped;
+    report
+}
+
+fn median(values: &[f32]) -> f32 {
+    median_of(values.to_vec())
+}
+
+fn median_of(mut values: Vec<f32>) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_unstable_by(f32::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) * 0.5
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+    use crate::transform::params::GradingParams;
+
+    fn flat(rgb: [f32; 3]) -> GradingImage {
+        GradingImage {
+            width: 4,
+            height: 4,
+            pixels: vec![[rgb[0], rgb[1], rgb[2], 1.0]; 16],
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    fn timeline(n: usize) -> Timeline {
+        Timeline {
+            name: "reel1".into(),
+            clips: (0..n)
+                .map(|i| Clip {
+                    name: format!("shot{i}"),
+                    ..Clip::default()
+                })
+                .collect(),
+            ..Timeline::default()
+        }
+    }
+
+    #[test]
+    fn test_measure_flat_gray() {
+        let stats = ClipStats::measure(&flat([0.18, 0.18, 0.18]));
+        assert!((stats.luma_mean - 0.18).abs() < 1e-5);
+        assert_eq!(stats.luma_p5, stats.luma_p95);
+        assert!(stats.saturation.abs() < 1e-6);
+        assert!(stats.temperature.abs() < 1e-6 && stats.tint.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_warm_frame_has_positive_temperature() {
+        let stats = ClipStats::measure(&flat([0.25, 0.18, 0.12]));
+        assert!(stats.temperature > 0.0, "{}", stats.temperature);
+        assert!(stats.saturation > 0.4);
+    }
+
+    #[test]
+    fn test_underexposed_clip_is_flagged_first() {
+        let timeline = timeline(6);
+        let report = analyze_timeline(&timeline, &ConsistencySettings::default(), |clip| {
+            Ok(match clip.name.as_str() {
+                "shot2" => flat([0.03, 0.03, 0.03]),
+                "shot4" => flat([0.185, 0.18, 0.175]),
+                _ => flat([0.18, 0.18, 0.18]),
+            })
+        });
+
+        let flagged = report.prioritized();
+        assert_eq!(flagged.len(), 1, "{report}");
+        assert_eq!(flagged[0].clip_index, 2);
+        let outlier = flagged[0].outliers[0];
+        assert_eq!(outlier.metric, Metric::Exposure);
+        assert!(outlier.score < 0.0);
+    }
+
+    #[test]
+    fn test_unreadable_clip_is_skipped() {
+        let timeline = timeline(3);
+        let report = analyze_timeline(&timeline, &ConsistencySettings::default(), |clip| {
+            if clip.name == "shot1" {
+                Err("no such file".into())
+            } else {
+                Ok(flat([0.18, 0.18, 0.18]))
+            }
+        });
+        assert_eq!(report.clips.len(), 2);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].clip_index, 1);
+        assert!(report.prioritized().is_empty());
+    }
+
+    #[test]
+    fn test_graded_analysis_uses_clip_grade() {
+        let mut timeline = timeline(5);
+        timeline.clips[3].grade = Some(GradingParams {
+            gain: [0.2, 0.2, 0.2, 0.2],
+            ..GradingParams::default()
+        });
+
+        let load = |_: &Clip| Ok(flat([0.18, 0.18, 0.18]));
+        let ungraded = analyze_timeline(&timeline, &ConsistencySettings::default(), load);
+        assert!(ungraded.prioritized().is_empty());
+
+        let settings = ConsistencySettings {
+            graded: true,
+            ..ConsistencySettings::default()
+        };
+        let graded = analyze_timeline(&timeline, &settings, load);
+        assert_eq!(graded.prioritized()[0].clip_index, 3);
+    }
+}
@@ -0,0 +1,263 @@
+//! Scene cut detection for image sequences.
+//!
+//! Each frame is reduced to a [`FrameProxy`] — a coarse grid of mean colors
+//! and a per-channel histogram — and consecutive proxies are compared. The
+//! grid catches a change of framing, the histogram a change of lighting
+//! that a pan would not cause. A cut needs the difference to pass an
+//! absolute threshold and to stand well above the frames around it, so
+//! handheld motion and slow fades, which raise every difference in the
+//! window, are not split.
+//!
+//! [`split_sequence`] turns the cuts into one [`Clip`] per shot, all
+//! referencing the sequence pattern with their own source range, so each
+//! shot can carry its own grade.
+
+use super::sequence::ImageSequence;
+use super::timeline::{Clip, SourceRange, Timeline};
+use crate::image::GradingImage;
+
+/// Proxy grid size in cells.
+const GRID_WIDTH: u32 = 32;
+const GRID_HEIGHT: u32 = 18;
+
+/// Histogram bins per channel.
+const HISTOGRAM_BINS: usize = 16;
+
+/// Settings for [`detect_cuts`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CutSettings {
+    /// Smallest frame difference, 0–1, that can be a cut.
+    pub threshold: f32,
+    /// How many times the median difference of the surrounding frames a
+    /// cut must reach.
+    pub ratio: f32,
+    /// Frames on each side that make up the surroundings.
+    pub window: usize,
+    /// Shortest shot in frames; a cut closer than this to the previous one
+    /// (e.g. the second spike of a flash frame) is ignored.
+    pub min_shot_frames: usize,
+}
+
+impl Default for CutSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.25,
+            ratio: 3.0,
+            window: 8,
+            min_shot_frames: 6,
+        }
+    }
+}
+
+/// Reduced frame compared by [`FrameProxy::difference`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameProxy {
+    /// Mean RGB per grid cell, row-major, clamped to 0–1.
+    pub grid: Vec<[f32; 3]>,
+    /// Normalized R, G, and B histograms, `HISTOGRAM_BINS` each.
+    pub histogram: Vec<f32>,
+}
+
+impl FrameProxy {
+    /// Build the proxy of a frame.
+    pub fn new(image: &GradingImage) -> Self {
+        let cells = (GRID_WIDTH * GRID_HEIGHT) as usize;
+        let mut sums = vec![[0.0_f64; 3]; cells];
+        let mut counts = vec![0_u32; cells];
+        let mut histogram = vec![0.0_f32; HISTOGRAM_BINS * 3];
+
+        let width = image.width.max(1);
+        let height = image.height.max(1);
+        for (i, p) in image.pixels.iter().enumerate() {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            let cell = ((y * GRID_HEIGHT / height) * GRID_WIDTH + x * GRID_WIDTH / width) as usize;
+            for c in 0..3 {
+                let v = p[c].clamp(0.0, 1.0);
+                sums[cell][c] += f64::from(v);
+                let bin = ((v * HISTOGRAM_BINS as f32) as usize).min(HISTOGRAM_BINS - 1);
+                histogram[c * HISTOGRAM_BINS + bin] += 1.0;
+            }
+            counts[cell] += 1;
+        }
+
+        let total = image.pixels.len().max(1) as f32;
+        histogram.iter_mut().for_each(|h| *h /= total);
+        let grid = sums
+            .iter()
+            .zip(&counts)
+            .map(|(sum, &n)| sum.map(|s| (s / f64::from(n.max(1))) as f32))
+            .collect();
+        Self { grid, histogram }
+    }
+
+    /// Difference to another proxy, 0 (identical) to 1: the mean of the
+    /// grid's mean absolute difference and the histograms' half L1 distance.
+    pub fn difference(&self, other: &FrameProxy) -> f32 {
+        let grid = self
+            .grid
+            .iter()
+            .zip(&other.grid)
+            .map(|(a, b)| (0..3).map(|c| (a[c] - b[c]).abs()).sum::<f32>() / 3.0)
+            .sum::<f32>()
+            / self.grid.len().max(1) as f32;
+        let histogram = self
+            .histogram
+            .iter()
+            .zip(&other.histogram)
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f32>()
+            / 6.0;
+        (0.5 * (grid + histogram)).clamp(0.0, 1.0)
+    }
+}
+
+/// Difference of every frame to the one before it; the first frame is 0.
+pub fn frame_differences(proxies: &[FrameProxy]) -> Vec<f32> {
+    std::iter::once(0.0)
+        .chain(proxies.windows(2).map(|w| w[0].difference(&w[1])))
+        .take(proxies.len())
+        .collect()
+}
+
+/// Indices of the frames that start a new shot, given [`frame_differences`].
+/// Frame 0 is not included.
+pub fn detect_cuts(differences: &[f32], settings: &CutSettings) -> Vec<usize> {
+    let mut cuts = Vec::new();
+    let mut shot_start = 0;
+    for (i, &d) in differences.iter().enumerate().skip(1) {
+        if d < settings.threshold || i - shot_start < settings.min_shot_frames {
+            continue;
+        }
+        let lo = i.saturating_sub(settings.window).max(1);
+        let hi = (i + settings.window + 1).min(differences.len());
+        let mut around: Vec<f32> = (lo..hi)
+            .filter(|&j| j != i)
+            .map(|j| differences[j])
+            .collect();
+        around.sort_unstable_by(f32::total_cmp);
+        let median = around.get(around.len() / 2).copied().unwrap_or(0.0);
+        if d >= settings.ratio * median {
+            cuts.push(i);
+            shot_start = i;
+        }
+    }
+    cuts
+}
+
+/// One clip per shot of `sequence`, split before each index in `cuts`.
+///
+/// Every clip references the sequence pattern; its source range covers the
+/// shot's frame numbers at `rate`.
+pub fn split_sequence(sequence: &ImageSequence, cuts: &[usize], rate: f64) -> Timeline {
+    let frames = &sequence.frames;
+    let mut bounds: Vec<usize> = std::iter::once(0)
+        .chain(cuts.iter().copied().filter(|&c| c > 0 && c < frames.len()))
+        .collect();
+    bounds.dedup();
+
+    let pattern = sequence.pattern();
+    let clips = bounds
+        .iter()
+        .enumerate()
+        .map(|(shot, &start)| {
+            let first = frames[start];
+            let end = match bounds.get(shot + 1) {
+                Some(&next) => frames[next],
+                None => frames[frames.len() - 1] + 1,
+            };
+            Clip {
+                name: format!("{}_{:03}", sequence.base_name(), shot + 1),
+                media_path: Some(pattern.clone()),
+                source_range: Some(SourceRange {
+                    start: first as f64,
+                    duration: (end - first) as f64,
+                    rate,
+                }),
+                ..Clip::default()
+            }
+        })
+        .collect();
+
+    Timeline {
+        name: sequence.base_name().to_string(),
+        clips: if frames.is_empty() { Vec::new() } else { clips },
+        ..Timeline::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+    use std::path::PathBuf;
+
+    /// 64×36 frame: a gradient whose direction and tint identify the shot,
+    /// shifted by `pan` pixels to simulate camera motion.
+    fn frame(shot: u32, pan: u32) -> GradingImage {
+        let (w, h) = (64, 36);
+        let tint = [[1.0, 0.6, 0.3], [0.3, 0.7, 1.0], [0.8, 0.8, 0.8]][shot as usize];
+        let pixels = (0..w * h)
+            .map(|i| {
+                let (x, y) = ((i % w + pan) % w, i / w);
+                let t = if shot == 1 {
+                    y as f32 / h as f32
+                } else {
+                    x as f32 / w as f32
+                };
+                [t * tint[0], t * tint[1], t * tint[2], 1.0]
+            })
+            .collect();
+        GradingImage {
+            width: w,
+            height: h,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_identical_frames_have_no_difference() {
+        let a = FrameProxy::new(&frame(0, 0));
+        assert_eq!(a.difference(&a), 0.0);
+        assert!(a.difference(&FrameProxy::new(&frame(1, 0))) > 0.25);
+    }
+
+    #[test]
+    fn test_detects_cuts_between_panning_shots() {
+        let proxies: Vec<FrameProxy> = (0..30)
+            .map(|i| FrameProxy::new(&frame(i / 10, i % 10)))
+            .collect();
+        let differences = frame_differences(&proxies);
+        assert_eq!(detect_cuts(&differences, &CutSettings::default()), [10, 20]);
+    }
+
+    #[test]
+    fn test_flash_frame_is_one_cut_at_most() {
+        let mut differences = vec![0.01; 30];
+        differences[12] = 0.9;
+        differences[13] = 0.9;
+        assert_eq!(detect_cuts(&differences, &CutSettings::default()), [12]);
+    }
+
+    #[test]
+    fn test_split_sequence_source_ranges() {
+        let sequence = ImageSequence {
+            directory: PathBuf::from("/scans"),
+            prefix: "reel1.".into(),
+            suffix: ".exr".into(),
+            padding: 4,
+            frames: (1001..1031).collect(),
+        };
+        let timeline = split_sequence(&sequence, &[10, 20], 24.0);
+        assert_eq!(timeline.clips.len(), 3);
+        assert_eq!(timeline.clips[1].name, "reel1_002");
+        let range = timeline.clips[1].source_range.unwrap();
+        assert_eq!((range.start, range.duration), (1011.0, 10.0));
+        let last = timeline.clips[2].source_range.unwrap();
+        assert_eq!((last.start, last.duration), (1021.0, 10.0));
+        assert_eq!(
+            timeline.clips[0].media_path.as_deref(),
+            Some("/scans/reel1.%04d.exr")
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Deflicker for image sequences.
+//!
+//! Timelapse and archival scans flicker when exposure or lamp brightness
+//! jumps from frame to frame. Each frame's exposure is measured in stops
+//! and compared with the median of the frames around it; the difference
+//! becomes a per-frame gain key on the clip's master gain track:
+//!
+//! ```text
+//! eᵢ     = log2(mean working-linear luma of frame i)
+//! targetᵢ = median(eᵢ₋w ..= eᵢ₊w)
+//! gainᵢ  = 2^clamp(targetᵢ − eᵢ, ±max_stops)
+//! ```
+//!
+//! A median follows slow changes — a sunset, a fade — but not single-frame
+//! jumps, so only the flicker is taken out. Exposure is measured after the
+//! clip's input transform, where the gain applies, so a stop of correction
+//! is a stop in the grade.
+
+use super::keyframes::{Keyframe, KeyframeTrack};
+use crate::color_management::aces::apply_input_transform;
+use crate::grading::sliders::LUMA_REC709;
+use crate::image::GradingImage;
+use crate::transform::evaluate::apply_source_levels;
+use crate::transform::params::GradingParams;
+
+/// Luma below which exposure is clamped before taking log2, so black frames
+/// stay finite.
+const MIN_LUMA: f32 = 1e-4;
+
+/// Source pixels with a channel at or above this are left out of the
+/// measurement: clipped highlights do not follow the flicker.
+const CLIP_LEVEL: f32 = 0.99;
+
+/// Settings for [`deflicker_gains`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeflickerSettings {
+    /// Frames on each side that make up the reference median.
+    pub window: usize,
+    /// Largest correction in stops, either way.
+    pub max_stops: f32,
+}
+
+impl Default for DeflickerSettings {
+    fn default() -> Self {
+        Self {
+            window: 5,
+            max_stops: 1.0,
+        }
+    }
+}
+
+/// Exposure of a frame in stops, measured in the working space of `grade`.
+pub fn frame_exposure(image: &GradingImage, grade: &GradingParams) -> f32 {
+    let (sum, count) = image
+        .pixels
+        .iter()
+        .filter(|p| p[0].max(p[1]).max(p[2]) < CLIP_LEVEL)
+        .map(|p| {
+            let rgb = apply_source_levels([p[0], p[1], p[2]], grade);
+            let rgb = apply_input_transform(rgb, &grade.color_management);
+            rgb[0] * LUMA_REC709[0] + rgb[1] * LUMA_REC709[1] + rgb[2] * LUMA_REC709[2]
+        })
+        .fold((0.0_f64, 0_usize), |(sum, n), luma| {
+            (sum + f64::from(luma), n + 1)
+        });
+    let mean = if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64) as f32
+    };
+    mean.max(MIN_LUMA).log2()
+}
+
+/// Per-frame linear gains that bring each exposure to the median of its
+/// neighborhood.
+pub fn deflicker_gains(exposures: &[f32], settings: &DeflickerSettings) -> Vec<f32> {
+    (0..exposures.len())
+        .map(|i| {
+            let lo = i.saturating_sub(settings.window);
+            let hi = (i + settings.window + 1).min(exposures.len());
+            let mut window = exposures[lo..hi].to_vec();
+            window.sort_by(f32::total_cmp);
+            let target = window[window.len() / 2];
+            let stops = (target - exposures[i]).clamp(-settings.max_stops, settings.max_stops);
+            stops.exp2()
+        })
+        .collect()
+}
+
+/// A master gain track with one key per measured frame. `frames` are the
+/// source frames `exposures` were measured at.
+pub fn deflicker_track(
+    frames: &[f64],
+    exposures: &[f32],
+    settings: &DeflickerSettings,
+) -> KeyframeTrack {
+    let gains = deflicker_gains(exposures, settings);
+    KeyframeTrack::new(
+        frames
+            .iter()
+            .zip(gains)
+            .map(|(&frame, value)| Keyframe { frame, value })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+    use crate::transform::params::ColorSpaceId;
+
+    fn linear_grade() -> GradingParams {
+        let mut grade = GradingParams::default();
+        grade.color_management.input_space = ColorSpaceId::LinearSrgb;
+        grade.color_management.working_space = ColorSpaceId::LinearSrgb;
+        grade.color_management.output_space = ColorSpaceId::LinearSrgb;
+        grade
+    }
+
+    fn flat(level: f32) -> GradingImage {
+        GradingImage {
+            width: 4,
+            height: 4,
+            pixels: vec![[level, level, level, 1.0]; 16],
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_frame_exposure_in_stops_ignores_clipped() {
+        let grade = linear_grade();
+        let quarter = frame_exposure(&flat(0.25), &grade);
+        assert!((quarter + 2.0).abs() < 1e-3, "{quarter}");
+
+        let mut img = flat(0.25);
+        img.pixels[0] = [1.0, 1.0, 1.0, 1.0];
+        assert!((frame_exposure(&img, &grade) - quarter).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_gains_remove_flicker_but_keep_ramp() {
+        let settings = DeflickerSettings::default();
+        // A slow ramp of a third of a stop per 10 frames, with frame 12
+        // half a stop hot.
+        let mut exposures: Vec<f32> = (0..30).map(|i| -2.0 + i as f32 / 30.0).collect();
+        exposures[12] += 0.5;
+        let gains = deflicker_gains(&exposures, &settings);
+
+        // The hot frame lifts its own median by at most a frame of ramp.
+        let corrected = exposures[12] + gains[12].log2();
+        assert!(
+            (corrected - (-2.0 + 12.0 / 30.0)).abs() < 0.05,
+            "{corrected}"
+        );
+        for i in [3, 20, 25] {
+            assert!((gains[i] - 1.0).abs() < 0.05, "frame {i}: {}", gains[i]);
+        }
+    }
+
+    #[test]
+    fn test_gains_are_limited_and_keyed_per_frame() {
+        let settings = DeflickerSettings {
+            window: 2,
+            max_stops: 0.5,
+        };
+        let exposures = [0.0, 0.0, -3.0, 0.0, 0.0];
+        let track = deflicker_track(&[100.0, 101.0, 102.0, 103.0, 104.0], &exposures, &settings);
+        assert_eq!(track.keys.len(), 5);
+        assert_eq!(track.value_at(102.0), Some(0.5_f32.exp2()));
+        assert_eq!(track.value_at(100.0), Some(1.0));
+    }
+}
@@ -0,0 +1,282 @@
+//! DPX still files — the archival format gallery stills travel in.
+//!
+//! Stills are written as SMPTE 268M version 2.0 files: big-endian, one RGB
+//! image element, 10-bit code values packed three to a 32-bit word with
+//! the two low bits unused ("filled, method A"). The still's label goes in
+//! the element description, which most DPX readers show. The reader takes
+//! what other tools commonly write for stills: 8-, 10-, or 16-bit RGB or
+//! RGBA in either byte order, with 10-bit data in method A.
+//!
+//! Code values map to 0–1 over the full range of the bit depth; no transfer
+//! function is applied either way.
+
+use std::path::Path;
+
+use crate::image::{BitDepth, GradingImage};
+
+/// `SDPX` read as a big-endian `u32`.
+const MAGIC: u32 = 0x5344_5058;
+
+/// Offset of the image data: the full 2048-byte set of headers.
+const DATA_OFFSET: usize = 2048;
+
+/// Offset of the first image element in the image information header.
+const ELEMENT: usize = 780;
+
+/// Image element descriptors.
+const DESCRIPTOR_RGB: u8 = 50;
+const DESCRIPTOR_RGBA: u8 = 51;
+
+/// Errors from reading or writing a DPX file.
+#[derive(Debug, thiserror::Error)]
+pub enum DpxError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a DPX file")]
+    NotDpx,
+    #[error("unsupported DPX data: {0}")]
+    Unsupported(String),
+    #[error("DPX file is truncated")]
+    Truncated,
+}
+
+/// Write `image` to `path` as a 10-bit RGB DPX labelled `label`.
+pub fn write_dpx(path: &Path, image: &GradingImage, label: &str) -> Result<(), DpxError> {
+    std::fs::write(path, encode(image, label))?;
+    Ok(())
+}
+
+/// Read the DPX still at `path`.
+pub fn read_dpx(path: &Path) -> Result<GradingImage, DpxError> {
+    decode(&std::fs::read(path)?)
+}
+
+/// Encode `image` as a 10-bit RGB DPX labelled `label`. Alpha is dropped.
+pub fn encode(image: &GradingImage, label: &str) -> Vec<u8> {
+    let data_len = image.pixels.len() * 4;
+    let mut out = vec![0u8; DATA_OFFSET + data_len];
+    let put_u32 =
+        |out: &mut [u8], at: usize, v: u32| out[at..at + 4].copy_from_slice(&v.to_be_bytes());
+    let put_u16 =
+        |out: &mut [u8], at: usize, v: u16| out[at..at + 2].copy_from_slice(&v.to_be_bytes());
+    let put_str = |out: &mut [u8], at: usize, len: usize, s: &str| {
+        // Fixed-width, NUL-terminated ASCII fields; longer text is cut.
+        let bytes = s.as_bytes();
+        let n = bytes.len().min(len - 1);
+        out[at..at + n].copy_from_slice(&bytes[..n]);
+    };
+
+    // File information header.
+    put_u32(&mut out, 0, MAGIC);
+    put_u32(&mut out, 4, DATA_OFFSET as u32);
+    put_str(&mut out, 8, 8, "V2.0");
+    put_u32(&mut out, 16, (DATA_OFFSET + data_len) as u32);
+    put_u32(&mut out, 20, 1);
+    put_u32(&mut out, 24, 1664);
+    put_u32(&mut out, 28, 384);
+    put_u32(&mut out, 32, 0);
+    put_str(&mut out, 160, 100, "Crispen");
+    put_str(&mut out, 260, 200, label);
+    put_u32(&mut out, 660, u32::MAX);
+
+    // Image information header with one element.
+    put_u16(&mut out, 768, 0);
+    put_u16(&mut out, 770, 1);
+    put_u32(&mut out, 772, image.width);
+    put_u32(&mut out, 776, image.height);
+    put_u32(&mut out, ELEMENT + 4, 0);
+    out[ELEMENT + 8..ELEMENT + 12].copy_from_slice(&0f32.to_be_bytes());
+    put_u32(&mut out, ELEMENT + 12, 1023);
+    out[ELEMENT + 16..ELEMENT + 20].copy_from_slice(&1f32.to_be_bytes());
+    out[ELEMENT + 20] = DESCRIPTOR_RGB;
+    out[ELEMENT + 23] = 10;
+    put_u16(&mut out, ELEMENT + 24, 1);
+    put_u32(&mut out, ELEMENT + 28, DATA_OFFSET as u32);
+    put_str(&mut out, ELEMENT + 40, 32, label);
+    // The orientation, film, and television headers are left undefined,
+    // which the standard spells as all ones.
+    out[1408..DATA_OFFSET].fill(0xFF);
+
+    let quantize = |v: f32| (v.clamp(0.0, 1.0) * 1023.0 + 0.5) as u32;
+    for (i, p) in image.pixels.iter().enumerate() {
+        let word = quantize(p[0]) << 22 | quantize(p[1]) << 12 | quantize(p[2]) << 2;
+        put_u32(&mut out, DATA_OFFSET + i * 4, word);
+    }
+    out
+}
+
+/// Decode a DPX file's first image element.
+pub fn decode(bytes: &[u8]) -> Result<GradingImage, DpxError> {
+    if bytes.len() < ELEMENT + 72 {
+        return Err(DpxError::NotDpx);
+    }
+    let big_endian = match bytes[0..4] {
+        [b'S', b'D', b'P', b'X'] => true,
+        [b'X', b'P', b'D', b'S'] => false,
+        _ => return Err(DpxError::NotDpx),
+    };
+    let u32_at = |at: usize| -> Result<u32, DpxError> {
+        let b: [u8; 4] = bytes
+            .get(at..at + 4)
+            .ok_or(DpxError::Truncated)?
+            .try_into()
+            .expect("slice of four");
+        Ok(if big_endian {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        })
+    };
+    let u16_at = |at: usize| -> Result<u16, DpxError> {
+        let b: [u8; 2] = bytes
+            .get(at..at + 2)
+            .ok_or(DpxError::Truncated)?
+            .try_into()
+            .expect("slice of two");
+        Ok(if big_endian {
+            u16::from_be_bytes(b)
+        } else {
+            u16::from_le_bytes(b)
+        })
+    };
+
+    let width = u32_at(772)?;
+    let height = u32_at(776)?;
+    let channels = match bytes[ELEMENT + 20] {
+        DESCRIPTOR_RGB => 3,
+        DESCRIPTOR_RGBA => 4,
+        other => return Err(DpxError::Unsupported(format!("descriptor {other}"))),
+    };
+    let bits = bytes[ELEMENT + 23];
+    let packing = u16_at(ELEMENT + 24)?;
+    let offset = match u32_at(ELEMENT + 28)? {
+        // Some writers leave the element offset undefined.
+        0 | u32::MAX => u32_at(4)?,
+        offset => offset,
+    } as usize;
+
+    let samples_per_line = width as usize * channels;
+    let (line_bytes, source_bit_depth) = match (bits, packing) {
+        (8, _) => (samples_per_line.div_ceil(4) * 4, BitDepth::U8),
+        (10, 1) => (samples_per_line.div_ceil(3) * 4, BitDepth::U10),
+        (16, _) => (samples_per_line.div_ceil(2) * 4, BitDepth::U16),
+        (bits, packing) => {
+            return Err(DpxError::Unsupported(format!(
+                "{bits}-bit data with packing {packing}"
+            )));
+        }
+    };
+    let data = line_bytes
+        .checked_mul(height as usize)
+        .and_then(|len| bytes.get(offset..offset.checked_add(len)?))
+        .ok_or(DpxError::Truncated)?;
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize);
+    let mut line = vec![0f32; samples_per_line];
+    for row in data.chunks_exact(line_bytes) {
+        for (s, sample) in line.iter_mut().enumerate() {
+            *sample = match bits {
+                8 => row[s] as f32 / 255.0,
+                10 => {
+                    let at = s / 3 * 4;
+                    let b: [u8; 4] = row[at..at + 4].try_into().expect("slice of four");
+                    let word = if big_endian {
+                        u32::from_be_bytes(b)
+                    } else {
+                        u32::from_le_bytes(b)
+                    };
+                    let shift = 22 - 10 * (s % 3) as u32;
+                    ((word >> shift) & 0x3FF) as f32 / 1023.0
+                }
+                _ => {
+                    let b = [row[s * 2], row[s * 2 + 1]];
+                    let v = if big_endian {
+                        u16::from_be_bytes(b)
+                    } else {
+                        u16::from_le_bytes(b)
+                    };
+                    v as f32 / 65535.0
+                }
+            };
+        }
+        pixels.extend(
+            line.chunks_exact(channels)
+                .map(|p| [p[0], p[1], p[2], if channels == 4 { p[3] } else { 1.0 }]),
+        );
+    }
+
+    Ok(GradingImage {
+        width,
+        height,
+        pixels,
+        source_bit_depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> GradingImage {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let t = i as f32 / (width * height - 1) as f32;
+                [t, 1.0 - t, 0.5, 1.0]
+            })
+            .collect();
+        GradingImage {
+            width,
+            height,
+            pixels,
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    #[test]
+    fn test_dpx_round_trips_at_10_bit() {
+        let image = gradient(7, 3);
+        let bytes = encode(&image, "sh010 hero");
+        assert_eq!(&bytes[0..4], b"SDPX");
+        assert_eq!(bytes.len(), DATA_OFFSET + 7 * 3 * 4);
+        assert_eq!(&bytes[ELEMENT + 40..ELEMENT + 50], b"sh010 hero");
+
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!((decoded.width, decoded.height), (7, 3));
+        assert_eq!(decoded.source_bit_depth, BitDepth::U10);
+        for (a, b) in image.pixels.iter().zip(&decoded.pixels) {
+            for c in 0..3 {
+                assert!((a[c] - b[c]).abs() <= 0.5 / 1023.0, "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_dpx_reads_little_endian_16_bit_rgba() {
+        let mut bytes = vec![0u8; DATA_OFFSET + 2 * 4 * 2];
+        bytes[0..4].copy_from_slice(b"XPDS");
+        bytes[4..8].copy_from_slice(&(DATA_OFFSET as u32).to_le_bytes());
+        bytes[772..776].copy_from_slice(&2u32.to_le_bytes());
+        bytes[776..780].copy_from_slice(&1u32.to_le_bytes());
+        bytes[ELEMENT + 20] = DESCRIPTOR_RGBA;
+        bytes[ELEMENT + 23] = 16;
+        for (i, v) in [65535u16, 0, 32768, 65535, 0, 65535, 0, 0]
+            .iter()
+            .enumerate()
+        {
+            let at = DATA_OFFSET + i * 2;
+            bytes[at..at + 2].copy_from_slice(&v.to_le_bytes());
+        }
+
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded.pixels.len(), 2);
+        assert_eq!(decoded.pixels[0][0], 1.0);
+        assert!((decoded.pixels[0][2] - 0.5).abs() < 1e-4);
+        assert_eq!(decoded.pixels[1], [0.0, 1.0, 0.0, 0.0]);
+
+        assert!(matches!(decode(&bytes[..100]), Err(DpxError::NotDpx)));
+        assert!(matches!(
+            decode(&bytes[..DATA_OFFSET + 4]),
+            Err(DpxError::Truncated)
+        ));
+    }
+}
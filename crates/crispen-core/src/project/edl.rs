@@ -0,0 +1,163 @@
+//! CMX3600 EDL import.
+//!
+//! ```text
+//! TITLE: REEL 1
+//! FCM: NON-DROP FRAME
+//!
+//! 001  A001C003 V     C        01:00:00:00 01:00:02:00 00:00:00:00 00:00:02:00
+//! * FROM CLIP NAME: A001C003_220101.mov
+//! * LUT: show_v2.cube
+//! ```
+//!
+//! Only video events are read. The source range comes from the source
+//! in/out timecodes. Comment lines following an event annotate it:
+//! `FROM CLIP NAME` names the clip, `SOURCE FILE` gives the media path, and
+//! any comment naming a LUT file (see
+//! [`find_lut_reference`](super::conform::find_lut_reference)) sets the
+//! clip's input LUT. `COMMENT` lines become the clip's notes, one per line.
+
+use std::path::Path;
+
+use super::conform::find_lut_reference;
+use super::timeline::{Clip, SourceRange, Timeline};
+
+/// Load an EDL file. EDLs carry no frame rate, so `rate` must be supplied.
+pub fn load_edl(path: &Path, rate: f64) -> std::io::Result<Timeline> {
+    Ok(read_edl(&std::fs::read_to_string(path)?, rate))
+}
+
+/// Parse EDL text. Malformed event lines are skipped with a warning.
+pub fn read_edl(text: &str, rate: f64) -> Timeline {
+    let mut timeline = Timeline::default();
+    let mut current: Option<Clip> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(title) = line.strip_prefix("TITLE:") {
+            timeline.name = title.trim().to_string();
+        } else if let Some(comment) = line.strip_prefix('*') {
+            if let Some(clip) = current.as_mut() {
+                annotate(clip, comment.trim());
+            }
+        } else if line.starts_with(|c: char| c.is_ascii_digit()) {
+            timeline.clips.extend(current.take());
+            current = parse_event(line, rate);
+            if current.is_none() && !is_audio_event(line) {
+                tracing::warn!("skipping malformed EDL event: {line}");
+            }
+        }
+    }
+    timeline.clips.extend(current);
+    timeline
+}
+
+fn parse_event(line: &str, rate: f64) -> Option<Clip> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let reel = *fields.get(1)?;
+    if !fields.get(2)?.starts_with('V') {
+        return None;
+    }
+    // Dissolves and wipes carry a duration field before the timecodes.
+    let timecodes: Vec<f64> = fields
+        .iter()
+        .rev()
+        .take(4)
+        .rev()
+        .map(|tc| parse_timecode(tc, rate))
+        .collect::<Option<_>>()?;
+    if timecodes.len() != 4 {
+        return None;
+    }
+
+    Some(Clip {
+        name: reel.to_string(),
+        reel: Some(reel.to_string()),
+        source_range: Some(SourceRange {
+            start: timecodes[0],
+            duration: timecodes[1] - timecodes[0],
+            rate,
+        }),
+        ..Clip::default()
+    })
+}
+
+fn is_audio_event(line: &str) -> bool {
+    line.split_whitespace()
+        .nth(2)
+        .is_some_and(|t| t.starts_with('A'))
+}
+
+fn annotate(clip: &mut Clip, comment: &str) {
+    if let Some(name) = comment.strip_prefix("FROM CLIP NAME:") {
+        clip.name = name.trim().to_string();
+    } else if let Some(file) = comment.strip_prefix("SOURCE FILE:") {
+        clip.media_path = Some(file.trim().to_string());
+    } else if let Some(note) = comment.strip_prefix("COMMENT:") {
+        let note = note.trim();
+        clip.notes = Some(match clip.notes.take() {
+            Some(notes) => format!("{notes}\n{note}"),
+            None => note.to_string(),
+        });
+    } else if let Some(lut) = find_lut_reference(comment) {
+        clip.input_lut = Some(lut);
+    }
+}
+
+/// Parse `HH:MM:SS:FF` (or `;` for drop-frame) into a frame count.
+///
+/// Drop-frame timecodes are counted as non-drop; the error is a few frames
+/// per hour, which only shifts where a range starts, not its length.
+pub(crate) fn parse_timecode(tc: &str, rate: f64) -> Option<f64> {
+    let parts: Vec<u32> = tc
+        .split([':', ';'])
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let [h, m, s, f] = parts[..] else {
+        return None;
+    };
+    let fps = rate.round();
+    Some(((f64::from(h) * 60.0 + f64::from(m)) * 60.0 + f64::from(s)) * fps + f64::from(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDL: &str = "TITLE: REEL 1
+FCM: NON-DROP FRAME
+
+001  A001C003 V     C        01:00:00:00 01:00:02:00 00:00:00:00 00:00:02:00
+* FROM CLIP NAME: A001C003_220101.mov
+* LUT: show_v2.cube
+002  A001C003 AA    C        01:00:00:00 01:00:02:00 00:00:00:00 00:00:02:00
+003  B002C010 V     D    012 02:00:10:00 02:00:11:12 00:00:02:00 00:00:03:12
+*ASC_SAT 1.0
+";
+
+    #[test]
+    fn test_reads_video_events_with_comments() {
+        let timeline = read_edl(EDL, 24.0);
+        assert_eq!(timeline.name, "REEL 1");
+        assert_eq!(timeline.clips.len(), 2);
+
+        let a = &timeline.clips[0];
+        assert_eq!(a.name, "A001C003_220101.mov");
+        assert_eq!(a.reel.as_deref(), Some("A001C003"));
+        assert_eq!(a.input_lut.as_deref(), Some("show_v2.cube"));
+        let range = a.source_range.unwrap();
+        assert_eq!(range.start, 86_400.0);
+        assert_eq!(range.duration, 48.0);
+
+        let b = &timeline.clips[1];
+        assert_eq!(b.name, "B002C010");
+        assert_eq!(b.input_lut, None);
+        assert_eq!(b.source_range.unwrap().duration, 36.0);
+    }
+
+    #[test]
+    fn test_parse_timecode() {
+        assert_eq!(parse_timecode("00:00:01:05", 25.0), Some(30.0));
+        assert_eq!(parse_timecode("00:01:00;02", 29.97), Some(1802.0));
+        assert_eq!(parse_timecode("00:01", 24.0), None);
+    }
+}
@@ -0,0 +1,393 @@
+//! Gallery — reference stills kept with the grade that made them.
+//!
+//! A gallery is a folder of stills, each an EXR or DPX file with a
+//! `<stem>.grade.json` sidecar beside it holding the still's label, the
+//! full grade, and where it came from:
+//!
+//! ```text
+//! gallery/
+//!   sh010_hero.exr
+//!   sh010_hero.grade.json   {"label":"sh010 hero","image":"sh010_hero.exr","grade":{…},…}
+//! ```
+//!
+//! The same pair is the exchange format: exporting a still writes the image
+//! and its sidecar anywhere, and importing one copies both back into a
+//! gallery, so the reference image and its grade arrive together. Decoding
+//! and encoding the images is left to the caller, except for DPX, which
+//! [`dpx`](super::dpx) handles.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::audit::current_operator;
+use super::trace::{FieldChange, diff_grades};
+use crate::transform::params::GradingParams;
+
+/// Extension of the grade sidecar written next to a still.
+pub const SIDECAR_EXTENSION: &str = "grade.json";
+
+/// Errors from reading or writing gallery stills.
+#[derive(Debug, thiserror::Error)]
+pub enum GalleryError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid grade sidecar: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{} has no grade sidecar", .0.display())]
+    NoSidecar(PathBuf),
+    #[error("{} is not an EXR or DPX still", .0.display())]
+    Format(PathBuf),
+    #[error("no still labelled \"{0}\"")]
+    NotFound(String),
+}
+
+/// Archival formats a still is kept in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StillFormat {
+    /// OpenEXR, float code values.
+    Exr,
+    /// 10-bit DPX.
+    Dpx,
+}
+
+impl StillFormat {
+    /// Format of the still at `path`, from its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "exr" => Some(Self::Exr),
+            "dpx" => Some(Self::Dpx),
+            _ => None,
+        }
+    }
+
+    /// File extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Exr => "exr",
+            Self::Dpx => "dpx",
+        }
+    }
+}
+
+/// What a still's `.grade.json` sidecar records.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StillSidecar {
+    /// Display label, unique within a gallery.
+    pub label: String,
+    /// File name of the image, next to the sidecar.
+    pub image: String,
+    /// The grade the still was rendered with.
+    pub grade: GradingParams,
+    /// Source image or clip the still was taken from, if known.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Unix time in seconds when the still was taken.
+    pub captured_at: u64,
+    /// Who took the still.
+    #[serde(default)]
+    pub operator: String,
+}
+
+impl StillSidecar {
+    /// Sidecar for a still of `grade` taken now by the current operator.
+    /// [`image`](Self::image) is filled in when it is written.
+    pub fn new(label: impl Into<String>, grade: GradingParams, source: Option<String>) -> Self {
+        Self {
+            label: label.into(),
+            image: String::new(),
+            grade,
+            source,
+            captured_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            operator: current_operator(),
+        }
+    }
+
+    /// Fields of `current` that applying this still's grade would change.
+    /// Color management is left out, since applying a still keeps the
+    /// shot's; an empty list means the still matches the current grade.
+    pub fn changes_from(&self, current: &GradingParams) -> Vec<FieldChange> {
+        let mut grade = self.grade.clone();
+        grade.color_management = current.color_management.clone();
+        diff_grades(current, &grade)
+    }
+
+    /// Read the sidecar of the still at `image`.
+    pub fn read_beside(image: &Path) -> Result<Self, GalleryError> {
+        let path = sidecar_path(image);
+        match std::fs::read_to_string(&path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(GalleryError::NoSidecar(image.to_path_buf()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write this sidecar next to the still at `image`, pointing at it.
+    /// Returns the sidecar's path.
+    pub fn write_beside(&mut self, image: &Path) -> Result<PathBuf, GalleryError> {
+        self.image = image
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let path = sidecar_path(image);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Sidecar of the still at `image`: `sh010.exr` keeps its grade in
+/// `sh010.grade.json`.
+pub fn sidecar_path(image: &Path) -> PathBuf {
+    image.with_extension(SIDECAR_EXTENSION)
+}
+
+/// One still in a gallery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalleryEntry {
+    pub sidecar: StillSidecar,
+    /// Path of the image file.
+    pub image_path: PathBuf,
+}
+
+/// A gallery folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gallery {
+    dir: PathBuf,
+}
+
+impl Gallery {
+    /// Gallery kept in `dir`. The folder is created on the first still.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The gallery folder.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Every still, oldest first. Sidecars that cannot be read are skipped
+    /// with a warning; a gallery folder that does not exist yet is empty.
+    pub fn entries(&self) -> Result<Vec<GalleryEntry>, GalleryError> {
+        let read = match std::fs::read_dir(&self.dir) {
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let suffix = format!(".{SIDECAR_EXTENSION}");
+        let mut entries = Vec::new();
+        for path in read.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            if !path.to_string_lossy().ends_with(&suffix) {
+                continue;
+            }
+            let sidecar: StillSidecar = match std::fs::read_to_string(&path)
+                .map_err(GalleryError::from)
+                .and_then(|json| Ok(serde_json::from_str(&json)?))
+            {
+                Ok(sidecar) => sidecar,
+                Err(e) => {
+                    tracing::warn!("Skipping gallery sidecar {}: {e}", path.display());
+                    continue;
+                }
+            };
+            entries.push(GalleryEntry {
+                image_path: self.dir.join(&sidecar.image),
+                sidecar,
+            });
+        }
+        entries.sort_by(|a, b| {
+            (a.sidecar.captured_at, &a.sidecar.label)
+                .cmp(&(b.sidecar.captured_at, &b.sidecar.label))
+        });
+        Ok(entries)
+    }
+
+    /// The still labelled `label`.
+    pub fn get(&self, label: &str) -> Result<GalleryEntry, GalleryError> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| entry.sidecar.label == label)
+            .ok_or_else(|| GalleryError::NotFound(label.to_string()))
+    }
+
+    /// Label and image path for a new still: `label`, numbered if the
+    /// gallery already has one by that name, and a file name made from it.
+    /// Creates the gallery folder.
+    pub fn reserve(
+        &self,
+        label: &str,
+        format: StillFormat,
+    ) -> Result<(String, PathBuf), GalleryError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let taken: Vec<String> = self
+            .entries()?
+            .into_iter()
+            .map(|entry| entry.sidecar.label)
+            .collect();
+        let base = if label.trim().is_empty() {
+            "still"
+        } else {
+            label.trim()
+        };
+        for n in 1.. {
+            let label = match n {
+                1 => base.to_string(),
+                n => format!("{base} ({n})"),
+            };
+            let path = self
+                .dir
+                .join(format!("{}.{}", file_stem(&label), format.extension()));
+            if !taken.contains(&label) && !path.exists() && !sidecar_path(&path).exists() {
+                return Ok((label, path));
+            }
+        }
+        unreachable!("some numbered label is free")
+    }
+
+    /// Copy the still at `image` and its sidecar into the gallery, keeping
+    /// its label unless the gallery already has one by that name.
+    pub fn import(&self, image: &Path) -> Result<GalleryEntry, GalleryError> {
+        let format =
+            StillFormat::from_path(image).ok_or_else(|| GalleryError::Format(image.into()))?;
+        let mut sidecar = StillSidecar::read_beside(image)?;
+        let (label, path) = self.reserve(&sidecar.label, format)?;
+        std::fs::copy(image, &path)?;
+        sidecar.label = label;
+        sidecar.write_beside(&path)?;
+        Ok(GalleryEntry {
+            sidecar,
+            image_path: path,
+        })
+    }
+
+    /// Delete the still labelled `label` and its sidecar.
+    pub fn remove(&self, label: &str) -> Result<(), GalleryError> {
+        let entry = self.get(label)?;
+        std::fs::remove_file(sidecar_path(&entry.image_path))?;
+        match std::fs::remove_file(&entry.image_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// File-name-safe form of `label`.
+fn file_stem(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::params::ColorSpaceId;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("crispen_gallery_{name}_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_gallery_reserves_unique_labels() {
+        let gallery = Gallery::new(temp_dir("reserve").join("gallery"));
+        assert!(gallery.entries().unwrap().is_empty());
+
+        let grade = GradingParams {
+            saturation: 1.3,
+            ..GradingParams::default()
+        };
+        let (label, path) = gallery.reserve("sh010 hero", StillFormat::Exr).unwrap();
+        assert_eq!(label, "sh010 hero");
+        assert_eq!(path.file_name().unwrap(), "sh010_hero.exr");
+        std::fs::write(&path, b"exr").unwrap();
+        StillSidecar::new(&label, grade.clone(), Some("a.exr".into()))
+            .write_beside(&path)
+            .unwrap();
+
+        let (second, second_path) = gallery.reserve("sh010 hero", StillFormat::Dpx).unwrap();
+        assert_eq!(second, "sh010 hero (2)");
+        assert_eq!(second_path.file_name().unwrap(), "sh010_hero__2_.dpx");
+
+        let entry = gallery.get("sh010 hero").unwrap();
+        assert_eq!(entry.image_path, path);
+        assert_eq!(entry.sidecar.grade, grade);
+        assert_eq!(entry.sidecar.image, "sh010_hero.exr");
+
+        gallery.remove("sh010 hero").unwrap();
+        assert!(!path.exists());
+        assert!(matches!(
+            gallery.get("sh010 hero"),
+            Err(GalleryError::NotFound(_))
+        ));
+        std::fs::remove_dir_all(gallery.dir().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_changes_ignore_color_management() {
+        let mut current = GradingParams::default();
+        current.color_management.input_space = ColorSpaceId::ArriLogC3;
+        let sidecar = StillSidecar::new("a", GradingParams::default(), None);
+        assert!(sidecar.changes_from(&current).is_empty());
+
+        current.saturation = 0.8;
+        current.lift[0] = 0.01;
+        let fields: Vec<String> = sidecar
+            .changes_from(&current)
+            .into_iter()
+            .map(|change| change.field)
+            .collect();
+        assert_eq!(fields, ["lift", "saturation"]);
+    }
+
+    #[test]
+    fn test_import_brings_image_and_grade() {
+        let dir = temp_dir("import");
+        let exported = dir.join("hero.dpx");
+        std::fs::write(&exported, b"dpx").unwrap();
+        let grade = GradingParams {
+            temperature: 0.2,
+            ..GradingParams::default()
+        };
+        StillSidecar::new("hero", grade.clone(), None)
+            .write_beside(&exported)
+            .unwrap();
+
+        let gallery = Gallery::new(dir.join("gallery"));
+        let entry = gallery.import(&exported).unwrap();
+        assert_eq!(entry.sidecar.label, "hero");
+        assert_eq!(entry.sidecar.grade, grade);
+        assert_eq!(std::fs::read(&entry.image_path).unwrap(), b"dpx");
+        assert_eq!(gallery.import(&exported).unwrap().sidecar.label, "hero (2)");
+        assert_eq!(gallery.entries().unwrap().len(), 2);
+
+        let bare = dir.join("bare.exr");
+        std::fs::write(&bare, b"exr").unwrap();
+        assert!(matches!(
+            gallery.import(&bare),
+            Err(GalleryError::NoSidecar(_))
+        ));
+        assert!(matches!(
+            gallery.import(&dir.join("hero.png")),
+            Err(GalleryError::Format(_))
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,280 @@
+//! Grade groups — evaluate a clip's grade wrapped in its group's grades.
+//!
+//! A clip in an enabled [`GradeGroup`] is graded as
+//!
+//! ```text
+//! source ─ input levels ─ input transform ─▶ profile ─▶ pre-clip ─▶ clip ─▶ post-clip ─ output transform ─▶ display
+//! ```
+//!
+//! A [`CameraProfile`] at the head replaces the input space and adds its
+//! exposure and matrix trim before any grade. All three grades run in working-space linear between a single pair of
+//! color transforms taken from the clip's grade, so a group look never
+//! re-encodes the image and the whole stack still bakes into one 3D LUT.
+
+use std::sync::LazyLock;
+
+use super::profiles::CameraProfile;
+use super::timeline::Timeline;
+use crate::color_management::aces::apply_input_transform;
+use crate::transform::evaluate::{apply_output_stage, apply_source_levels, apply_working_grade};
+use crate::transform::lut::Lut3D;
+use crate::transform::params::GradingParams;
+
+/// The grades applied to one clip, in order.
+#[derive(Debug, Clone, Copy)]
+pub struct GradeStack<'a> {
+    /// The clip's camera profile, if it has one.
+    pub profile: Option<&'a CameraProfile>,
+    /// Group pre-clip grade, if the group is enabled and has one.
+    pub pre_clip: Option<&'a GradingParams>,
+    /// The clip's own grade; its color management governs the stack.
+    pub clip: &'a GradingParams,
+    /// Group post-clip grade, if the group is enabled and has one.
+    pub post_clip: Option<&'a GradingParams>,
+}
+
+impl GradeStack<'_> {
+    /// Evaluate the stack for one source pixel. The clip's input levels,
+    /// log offset, and color management apply to the source, with the profile's input
+    /// space in place of the clip's.
+    pub fn evaluate(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let mut cm = self.clip.color_management.clone();
+        if let Some(profile) = self.profile {
+            cm.input_space = profile.input_space;
+        }
+        let rgb = apply_source_levels(rgb, self.clip);
+        let mut c = if self.clip.bypass.input_transform {
+            rgb
+        } else {
+            apply_input_transform(rgb, &cm)
+        };
+        if let Some(profile) = self.profile {
+            c = profile.apply(c);
+        }
+        if let Some(pre) = self.pre_clip {
+            c = apply_working_grade(c, pre);
+        }
+        c = apply_working_grade(c, self.clip);
+        if let Some(post) = self.post_clip {
+            c = apply_working_grade(c, post);
+        }
+        apply_output_stage(c, self.clip, &cm)
+    }
+
+    /// Bake the stack into a `size`³ LUT over the [0, 1] domain.
+    pub fn bake(&self, size: u32) -> Lut3D {
+        let size = size.max(2);
+        let n = (size - 1) as f32;
+        let mut lut = Lut3D::new(size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let out = self.evaluate([r, g, b].map(|i| i as f32 / n));
+                    let idx = ((b * size + g) * size + r) as usize;
+                    lut.data[idx] = [out[0], out[1], out[2], 1.0];
+                }
+            }
+        }
+        lut
+    }
+}
+
+impl Timeline {
+    /// Grade stack for the clip at `index`, or `None` if out of range.
+    ///
+    /// Ungraded clips use the default grade. Clips whose group is missing
+    /// or disabled get their own grade only. The clip's camera profile is
+    /// resolved with [`Timeline::profile_for`].
+    pub fn grade_stack(&self, index: usize) -> Option<GradeStack<'_>> {
+        static UNGRADED: LazyLock<GradingParams> = LazyLock::new(GradingParams::default);
+
+        let clip = self.clips.get(index)?;
+        let group = clip
+            .group
+            .as_deref()
+            .and_then(|name| self.group(name))
+            .filter(|group| group.enabled);
+        Some(GradeStack {
+            profile: self.profile_for(clip),
+            pre_clip: group.and_then(|g| g.pre_clip.as_ref()),
+            clip: clip.grade.as_ref().unwrap_or(&UNGRADED),
+            post_clip: group.and_then(|g| g.post_clip.as_ref()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color_management::aces::apply_output_transform;
+    use crate::project::timeline::{Clip, GradeGroup};
+    use crate::transform::evaluate::evaluate_transform;
+    use crate::transform::params::ColorSpaceId;
+
+    fn timeline() -> Timeline {
+        let mut look = GradeGroup::new("show");
+        look.pre_clip = Some(GradingParams {
+            offset: [0.05, 0.05, 0.05, 0.0],
+            ..GradingParams::default()
+        });
+        look.post_clip = Some(GradingParams {
+            saturation: 0.5,
+            ..GradingParams::default()
+        });
+        Timeline {
+            name: "reel 1".into(),
+            clips: vec![
+                Clip {
+                    name: "A001".into(),
+                    grade: Some(GradingParams {
+                        contrast: 1.2,
+                        ..GradingParams::default()
+                    }),
+                    group: Some("show".into()),
+                    ..Clip::default()
+                },
+                Clip {
+                    name: "A002".into(),
+                    ..Clip::default()
+                },
+            ],
+            groups: vec![look],
+            ..Timeline::default()
+        }
+    }
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for c in 0..3 {
+            assert!((a[c] - b[c]).abs() < 1e-5, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_ungrouped_clip_matches_plain_evaluation() {
+        let timeline = timeline();
+        let stack = timeline.grade_stack(1).expect("clip");
+        assert!(stack.pre_clip.is_none() && stack.post_clip.is_none());
+        let rgb = [0.2, 0.4, 0.6];
+        assert_close(
+            stack.evaluate(rgb),
+            evaluate_transform(rgb, &GradingParams::default()),
+        );
+        assert!(timeline.grade_stack(2).is_none());
+    }
+
+    #[test]
+    fn test_group_grades_wrap_clip_grade_in_order() {
+        let timeline = timeline();
+        let stack = timeline.grade_stack(0).expect("clip");
+        let group = timeline.group("show").expect("group");
+        let clip = timeline.clips[0].grade.as_ref().expect("grade");
+
+        let rgb = [0.2, 0.4, 0.6];
+        let cm = &clip.color_management;
+        let mut want = apply_input_transform(rgb, cm);
+        want = apply_working_grade(want, group.pre_clip.as_ref().expect("pre"));
+        want = apply_working_grade(want, clip);
+        want = apply_working_grade(want, group.post_clip.as_ref().expect("post"));
+        want = apply_output_transform(want, cm);
+        assert_close(stack.evaluate(rgb), want);
+
+        // The group changes the result versus the clip grade alone.
+        let plain = evaluate_transform(rgb, clip);
+        assert!((stack.evaluate(rgb)[0] - plain[0]).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_disabled_group_is_bypassed() {
+        let mut timeline = timeline();
+        timeline.group_mut("show").expect("group").enabled = false;
+        let stack = timeline.grade_stack(0).expect("clip");
+        let rgb = [0.3, 0.3, 0.5];
+        assert_close(
+            stack.evaluate(rgb),
+            evaluate_transform(rgb, timeline.clips[0].grade.as_ref().expect("grade")),
+        );
+    }
+
+    #[test]
+    fn test_profile_heads_the_stack() {
+        let mut timeline = timeline();
+        let mut profile = CameraProfile::new("alexa", ColorSpaceId::ArriLogC3);
+        profile.matches.camera = Some("A".into());
+        profile.exposure = -1.0;
+        timeline.profiles.insert(profile.clone());
+        timeline.clips[1].camera = Some("A".into());
+
+        let stack = timeline.grade_stack(1).expect("clip");
+        assert_eq!(stack.profile, Some(&profile));
+        let rgb = [0.4, 0.45, 0.5];
+        let mut cm = GradingParams::default().color_management;
+        cm.input_space = ColorSpaceId::ArriLogC3;
+        let mut want = apply_input_transform(rgb, &cm);
+        want = apply_working_grade(want.map(|v| v * 0.5), &GradingParams::default());
+        assert_close(stack.evaluate(rgb), apply_output_transform(want, &cm));
+    }
+
+    #[test]
+    fn test_layers_blend_over_the_stack_beneath() {
+        use crate::grading::blend::{BlendMode, LayerBlend, blend};
+
+        let mut timeline = timeline();
+        let rgb = [0.2, 0.4, 0.6];
+        let clip = timeline.clips[0].grade.as_mut().expect("grade");
+        clip.layer_blend.mix = 0.0;
+        let group = timeline.group("show").expect("group");
+        let pre = group.pre_clip.as_ref().expect("pre");
+        let post = group.post_clip.as_ref().expect("post");
+        let cm = &timeline.clips[0]
+            .grade
+            .as_ref()
+            .expect("grade")
+            .color_management;
+        let mut want = apply_working_grade(apply_input_transform(rgb, cm), pre);
+        want = apply_working_grade(want, post);
+        let stack = timeline.grade_stack(0).expect("clip");
+        assert_close(stack.evaluate(rgb), apply_output_transform(want, cm));
+
+        // A multiplied post-clip layer at half mix.
+        let mut timeline = self::timeline();
+        timeline.group_mut("show").expect("group").post_clip = Some(GradingParams {
+            saturation: 0.5,
+            layer_blend: LayerBlend {
+                mode: BlendMode::Multiply,
+                mix: 0.5,
+            },
+            ..GradingParams::default()
+        });
+        let clip = timeline.clips[0].grade.as_ref().expect("grade");
+        let group = timeline.group("show").expect("group");
+        let cm = &clip.color_management;
+        let mut base = apply_working_grade(
+            apply_input_transform(rgb, cm),
+            group.pre_clip.as_ref().expect("pre"),
+        );
+        base = apply_working_grade(base, clip);
+        let post = GradingParams {
+            saturation: 0.5,
+            ..GradingParams::default()
+        };
+        let want = blend(
+            base,
+            apply_working_grade(base, &post),
+            BlendMode::Multiply,
+            0.5,
+        );
+        let stack = timeline.grade_stack(0).expect("clip");
+        assert_close(stack.evaluate(rgb), apply_output_transform(want, cm));
+    }
+
+    #[test]
+    fn test_bake_matches_evaluate_on_grid() {
+        let timeline = timeline();
+        let stack = timeline.grade_stack(0).expect("clip");
+        let lut = stack.bake(5);
+        assert_close(
+            lut.apply([0.5, 0.25, 0.75]),
+            stack.evaluate([0.5, 0.25, 0.75]),
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Keyframed clip parameters.
+//!
+//! A [`KeyframeTrack`] holds values at source frames and interpolates
+//! linearly between them, holding the first and last values outside the
+//! keyed range. Frames are in the clip's source media frames, the same
+//! units as [`SourceRange::start`](super::timeline::SourceRange::start),
+//! so keys stay attached to the picture when editorial trims the clip.
+//!
+//! A clip's master gain track multiplies into its grade's gain wheel;
+//! [`Clip::grade_at`] resolves the grade for one frame.
+
+use serde::{Deserialize, Serialize};
+
+use super::timeline::Clip;
+use crate::transform::params::GradingParams;
+
+/// One value at one source frame.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// Source media frame.
+    pub frame: f64,
+    /// Value at `frame`.
+    pub value: f32,
+}
+
+/// Values keyed over source frames, sorted by frame.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct KeyframeTrack {
+    /// Keys in ascending frame order.
+    pub keys: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    /// A track from keys in any order.
+    pub fn new(mut keys: Vec<Keyframe>) -> Self {
+        keys.sort_by(|a, b| a.frame.total_cmp(&b.frame));
+        Self { keys }
+    }
+
+    /// Whether the track has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The value at `frame`, or `None` for an empty track.
+    pub fn value_at(&self, frame: f64) -> Option<f32> {
+        let first = self.keys.first()?;
+        let next = self.keys.partition_point(|k| k.frame <= frame);
+        if next == 0 {
+            return Some(first.value);
+        }
+        let a = self.keys[next - 1];
+        let Some(b) = self.keys.get(next) else {
+            return Some(a.value);
+        };
+        let t = ((frame - a.frame) / (b.frame - a.frame)) as f32;
+        Some(a.value + (b.value - a.value) * t)
+    }
+}
+
+impl Clip {
+    /// The clip's grade at source frame `frame`, with its keyframed
+    /// parameters applied. An ungraded clip with keys is graded from the
+    /// identity; `None` when the clip has neither.
+    pub fn grade_at(&self, frame: f64) -> Option<GradingParams> {
+        let gain = self.gain_keys.as_ref().and_then(|t| t.value_at(frame));
+        if self.grade.is_none() && gain.is_none() {
+            return None;
+        }
+        let mut grade = self.grade.clone().unwrap_or_default();
+        if let Some(gain) = gain {
+            grade.gain_wheel[3] *= gain;
+        }
+        Some(grade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track() -> KeyframeTrack {
+        KeyframeTrack::new(vec![
+            Keyframe {
+                frame: 20.0,
+                value: 2.0,
+            },
+            Keyframe {
+                frame: 10.0,
+                value: 1.0,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_value_at_interpolates_and_holds() {
+        let t = track();
+        assert_eq!(t.keys[0].frame, 10.0);
+        assert_eq!(t.value_at(0.0), Some(1.0));
+        assert_eq!(t.value_at(10.0), Some(1.0));
+        assert_eq!(t.value_at(15.0), Some(1.5));
+        assert_eq!(t.value_at(20.0), Some(2.0));
+        assert_eq!(t.value_at(99.0), Some(2.0));
+        assert_eq!(KeyframeTrack::default().value_at(5.0), None);
+    }
+
+    #[test]
+    fn test_grade_at_multiplies_master_gain() {
+        let mut clip = Clip::default();
+        assert!(clip.grade_at(10.0).is_none());
+
+        clip.gain_keys = Some(track());
+        let grade = clip.grade_at(15.0).expect("keyed clip");
+        assert_eq!(grade.gain_wheel, [1.0, 1.0, 1.0, 1.5]);
+
+        clip.grade = Some(GradingParams {
+            gain_wheel: [1.0, 1.0, 1.0, 2.0],
+            ..GradingParams::default()
+        });
+        let grade = clip.grade_at(20.0).expect("graded clip");
+        assert_eq!(grade.gain_wheel[3], 4.0);
+    }
+}
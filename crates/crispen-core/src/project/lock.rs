@@ -0,0 +1,244 @@
+//! Project locking — one writer at a time for a shared project file.
+//!
+//! Colorists working on the same project each grade their own copy and
+//! only touch the shared file to save. A [`ProjectLock`] is a
+//! `<project>.lock` file created exclusively beside it, naming who holds
+//! it, so a save can read the file, merge into it (see
+//! [`merge`](super::merge)), and write it back without another save
+//! landing in between. The lock is released when dropped.
+//!
+//! Locks are advisory: tools that ignore them can still write the project.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Extension of the lock file written next to a project file.
+pub const LOCK_EXTENSION: &str = "lock";
+
+/// Age after which a lock is assumed to be left over from a crashed
+/// session. Saves hold the lock for seconds, so this is generous.
+pub const STALE_LOCK_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Errors from taking or inspecting a project lock.
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid lock file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("project is locked by {0}")]
+    Held(LockInfo),
+}
+
+/// Who holds a lock, as written in the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInfo {
+    /// Operator who took the lock.
+    pub operator: String,
+    /// Machine the lock was taken on.
+    pub host: String,
+    /// Process that holds the lock.
+    pub pid: u32,
+    /// Unix time in seconds when the lock was taken.
+    pub acquired_at: u64,
+}
+
+impl LockInfo {
+    /// Seconds since the lock was taken; zero for a clock in the past.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(unix_now().saturating_sub(self.acquired_at))
+    }
+}
+
+impl fmt::Display for LockInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {} (pid {}, {}s ago)",
+            self.operator,
+            self.host,
+            self.pid,
+            self.age().as_secs()
+        )
+    }
+}
+
+/// A held lock on a project file; released on drop.
+#[derive(Debug)]
+pub struct ProjectLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+impl ProjectLock {
+    /// Lock `project` for `operator`. Fails with [`LockError::Held`] while
+    /// someone else holds a lock younger than [`STALE_LOCK_AGE`]; an older
+    /// one is taken over.
+    pub fn acquire(project: &Path, operator: impl Into<String>) -> Result<Self, LockError> {
+        let path = lock_path(project);
+        let info = LockInfo {
+            operator: operator.into(),
+            host: host_name(),
+            pid: std::process::id(),
+            acquired_at: unix_now(),
+        };
+        let json = serde_json::to_string_pretty(&info)?;
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(json.as_bytes())?;
+                    return Ok(Self { path, info });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    match Self::holder(project)? {
+                        Some(holder) if holder.age() < STALE_LOCK_AGE => {
+                            return Err(LockError::Held(holder));
+                        }
+                        Some(holder) => {
+                            tracing::warn!("Taking over stale project lock held by {holder}");
+                            remove_lock(&path)?;
+                        }
+                        // Released between the two calls; try again.
+                        None => {}
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        // Someone else took it again in between.
+        match Self::holder(project)? {
+            Some(holder) => Err(LockError::Held(holder)),
+            None => Err(std::io::Error::other("project lock changed hands during acquire").into()),
+        }
+    }
+
+    /// Who holds the lock on `project`, if anyone.
+    pub fn holder(project: &Path) -> Result<Option<LockInfo>, LockError> {
+        match std::fs::read_to_string(lock_path(project)) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Remove the lock on `project` whoever holds it, for a lock left by a
+    /// crash that is not yet stale. Returns whether there was one.
+    pub fn break_lock(project: &Path) -> Result<bool, LockError> {
+        let path = lock_path(project);
+        let existed = path.exists();
+        remove_lock(&path)?;
+        Ok(existed)
+    }
+
+    /// Who this lock was taken by.
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+
+    /// Release the lock.
+    pub fn release(self) {}
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        // Only remove the file if it is still ours; it may have been broken
+        // and taken by someone else meanwhile.
+        let ours = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<LockInfo>(&json).ok())
+            .is_some_and(|info| info == self.info);
+        if ours && let Err(e) = remove_lock(&self.path) {
+            tracing::warn!("Failed to release {}: {e}", self.path.display());
+        }
+    }
+}
+
+/// Lock file for `project`: `reel1.otio` locks with `reel1.lock`.
+pub fn lock_path(project: &Path) -> PathBuf {
+    project.with_extension(LOCK_EXTENSION)
+}
+
+fn remove_lock(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn host_name() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+                .filter(|h| !h.is_empty())
+        })
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_until_released() {
+        let dir = std::env::temp_dir().join(format!("crispen_lock_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let project = dir.join("reel1.otio");
+        ProjectLock::break_lock(&project).unwrap();
+
+        let lock = ProjectLock::acquire(&project, "ana").unwrap();
+        assert_eq!(lock_path(&project), dir.join("reel1.lock"));
+        match ProjectLock::acquire(&project, "ben") {
+            Err(LockError::Held(holder)) => assert_eq!(holder.operator, "ana"),
+            other => panic!("expected the lock to be held, got {other:?}"),
+        }
+
+        lock.release();
+        assert_eq!(ProjectLock::holder(&project).unwrap(), None);
+        let lock = ProjectLock::acquire(&project, "ben").unwrap();
+        assert_eq!(lock.info().operator, "ben");
+        drop(lock);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let dir = std::env::temp_dir().join(format!("crispen_stale_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let project = dir.join("reel1.otio");
+        let crashed = LockInfo {
+            operator: "ana".into(),
+            host: "suite-2".into(),
+            pid: 1,
+            acquired_at: unix_now() - STALE_LOCK_AGE.as_secs() - 1,
+        };
+        std::fs::write(
+            lock_path(&project),
+            serde_json::to_string(&crashed).unwrap(),
+        )
+        .unwrap();
+
+        let lock = ProjectLock::acquire(&project, "ben").unwrap();
+        assert_eq!(
+            ProjectLock::holder(&project).unwrap().unwrap().operator,
+            "ben"
+        );
+        drop(lock);
+        assert!(!lock_path(&project).exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
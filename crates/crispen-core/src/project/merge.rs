@@ -0,0 +1,428 @@
+//! Three-way merge of grade state, so colorists can share a project.
+//!
+//! Each colorist grades a copy of the project loaded at some point (the
+//! base). To save, [`save_merged`] locks the shared file, reads what is
+//! there now (theirs), and merges the local copy (ours) into it clip by
+//! clip:
+//!
+//! | base → ours | base → theirs | Result |
+//! |-------------|---------------|--------|
+//! | unchanged   | unchanged     | base |
+//! | changed     | unchanged     | ours |
+//! | unchanged   | changed       | theirs |
+//! | changed     | changed the same way | either |
+//! | changed     | changed differently  | ours, reported as a conflict |
+//!
+//! Grade groups merge the same way by name, and the timeline's blanking,
+//! defect maps, and camera profiles each as a whole. The clip list itself
+//! is editorial's: clips are matched by name, media, and source range, and
+//! a clip only theirs has is reported rather than spliced in.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::lock::{LockError, ProjectLock};
+use super::otio::{OtioError, load_otio, save_otio};
+use super::timeline::{Clip, Timeline};
+
+/// Errors from [`save_merged`].
+#[derive(Debug, thiserror::Error)]
+pub enum MergeError {
+    #[error("{0}")]
+    Lock(#[from] LockError),
+    #[error("{0}")]
+    Otio(#[from] OtioError),
+}
+
+/// What a conflict is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeItem {
+    /// A clip, by its index in the merged timeline.
+    Clip { index: usize, name: String },
+    /// A grade group, by name.
+    Group(String),
+    /// A timeline-wide setting: `blanking`, `defect_maps`, or `profiles`.
+    Timeline(&'static str),
+}
+
+impl fmt::Display for MergeItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Clip { index, name } => write!(f, "clip {} \"{name}\"", index + 1),
+            Self::Group(name) => write!(f, "group \"{name}\""),
+            Self::Timeline(field) => write!(f, "timeline {field}"),
+        }
+    }
+}
+
+/// An item both sides changed differently. Ours was kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    /// What was changed on both sides.
+    pub item: MergeItem,
+    /// Fields ours changed, as dotted paths (e.g. `grade.saturation`).
+    pub ours: Vec<String>,
+    /// Fields theirs changed.
+    pub theirs: Vec<String>,
+}
+
+/// What a merge did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Items taken from theirs.
+    pub from_theirs: Vec<MergeItem>,
+    /// Items changed on both sides; ours was kept.
+    pub conflicts: Vec<MergeConflict>,
+    /// Names of clips only theirs has, left out of the merge.
+    pub unmatched: Vec<String>,
+}
+
+impl MergeReport {
+    /// Whether the merge kept every change from both sides.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty() && self.unmatched.is_empty()
+    }
+}
+
+impl fmt::Display for MergeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Merge: {} from theirs, {} conflict(s), {} unmatched clip(s)",
+            self.from_theirs.len(),
+            self.conflicts.len(),
+            self.unmatched.len()
+        )?;
+        for conflict in &self.conflicts {
+            writeln!(
+                f,
+                "  CONFLICT {}: ours changed {}; theirs changed {}; kept ours",
+                conflict.item,
+                conflict.ours.join(", "),
+                conflict.theirs.join(", ")
+            )?;
+        }
+        for name in &self.unmatched {
+            writeln!(f, "  UNMATCHED \"{name}\": only in theirs, not merged")?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge `ours` and `theirs`, both edited from `base`. The result has
+/// ours' clip order and name.
+pub fn merge_timelines(
+    base: &Timeline,
+    ours: &Timeline,
+    theirs: &Timeline,
+) -> (Timeline, MergeReport) {
+    let mut merged = ours.clone();
+    let mut report = MergeReport::default();
+
+    let base_clips = index_clips(&base.clips);
+    let mut their_clips = index_clips(&theirs.clips);
+    for (index, (key, ours)) in clip_keys(&ours.clips)
+        .into_iter()
+        .zip(&ours.clips)
+        .enumerate()
+    {
+        let Some((_, theirs)) = their_clips.remove(&key) else {
+            continue;
+        };
+        let item = MergeItem::Clip {
+            index,
+            name: ours.name.clone(),
+        };
+        let base = base_clips.get(&key).map(|(_, clip)| *clip);
+        merged.clips[index] = merge_item(base, ours, theirs, item, &mut report);
+    }
+    let mut unmatched: Vec<_> = their_clips.into_values().collect();
+    unmatched.sort_by_key(|(i, _)| *i);
+    report.unmatched = unmatched.into_iter().map(|(_, c)| c.name.clone()).collect();
+
+    merge_groups(base, theirs, &mut merged, &mut report);
+    merged.blanking = merge_field(
+        &base.blanking,
+        &ours.blanking,
+        &theirs.blanking,
+        "blanking",
+        &mut report,
+    );
+    merged.defect_maps = merge_field(
+        &base.defect_maps,
+        &ours.defect_maps,
+        &theirs.defect_maps,
+        "defect_maps",
+        &mut report,
+    );
+    merged.profiles = merge_field(
+        &base.profiles,
+        &ours.profiles,
+        &theirs.profiles,
+        "profiles",
+        &mut report,
+    );
+    (merged, report)
+}
+
+/// Merge `ours` into the OTIO project at `project` under its lock and
+/// write the result. `base` is the project as it was when `ours` was
+/// loaded from it; a project that does not exist yet is treated as
+/// unchanged since then.
+pub fn save_merged(
+    project: &Path,
+    base: &Timeline,
+    ours: &Timeline,
+    operator: &str,
+) -> Result<MergeReport, MergeError> {
+    let lock = ProjectLock::acquire(project, operator)?;
+    let theirs = if project.exists() {
+        load_otio(project)?
+    } else {
+        base.clone()
+    };
+    let (merged, report) = merge_timelines(base, ours, &theirs);
+    save_otio(&merged, project)?;
+    lock.release();
+    Ok(report)
+}
+
+/// Identity of a clip across versions of a timeline: name, media, and
+/// source start, plus how many earlier clips share them.
+type ClipKey = (String, Option<String>, Option<u64>, usize);
+
+fn clip_keys(clips: &[Clip]) -> Vec<ClipKey> {
+    let mut seen: HashMap<(String, Option<String>, Option<u64>), usize> = HashMap::new();
+    clips
+        .iter()
+        .map(|clip| {
+            let id = (
+                clip.name.clone(),
+                clip.media_path.clone(),
+                clip.source_range.map(|r| r.start.to_bits()),
+            );
+            let n = seen.entry(id.clone()).or_default();
+            *n += 1;
+            (id.0, id.1, id.2, *n - 1)
+        })
+        .collect()
+}
+
+fn index_clips(clips: &[Clip]) -> HashMap<ClipKey, (usize, &Clip)> {
+    clip_keys(clips)
+        .into_iter()
+        .zip(clips.iter().enumerate())
+        .collect()
+}
+
+/// Merge theirs' groups into `merged`, which starts as ours'.
+fn merge_groups(
+    base: &Timeline,
+    theirs: &Timeline,
+    merged: &mut Timeline,
+    report: &mut MergeReport,
+) {
+    for their_group in &theirs.groups {
+        let base_group = base.group(&their_group.name);
+        match merged
+            .groups
+            .iter_mut()
+            .find(|g| g.name == their_group.name)
+        {
+            Some(group) => {
+                let item = MergeItem::Group(group.name.clone());
+                *group = merge_item(base_group, group, their_group, item, report);
+            }
+            // A group only theirs has is new, unless ours deleted it.
+            None if base_group.is_none() => {
+                report
+                    .from_theirs
+                    .push(MergeItem::Group(their_group.name.clone()));
+                merged.groups.push(their_group.clone());
+            }
+            None => {}
+        }
+    }
+}
+
+fn merge_field<T: Clone + PartialEq + Serialize>(
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    field: &'static str,
+    report: &mut MergeReport,
+) -> T {
+    merge_item(Some(base), ours, theirs, MergeItem::Timeline(field), report)
+}
+
+/// Three-way merge of one item. Without a base, both sides added it: equal
+/// additions merge, different ones conflict.
+fn merge_item<T: Clone + PartialEq + Serialize>(
+    base: Option<&T>,
+    ours: &T,
+    theirs: &T,
+    item: MergeItem,
+    report: &mut MergeReport,
+) -> T {
+    if ours == theirs || base == Some(theirs) {
+        return ours.clone();
+    }
+    if base == Some(ours) {
+        report.from_theirs.push(item);
+        return theirs.clone();
+    }
+    let base = base.map(to_value).unwrap_or(Value::Null);
+    report.conflicts.push(MergeConflict {
+        item,
+        ours: changed_fields(&base, &to_value(ours)),
+        theirs: changed_fields(&base, &to_value(theirs)),
+    });
+    ours.clone()
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Dotted paths of the leaves that differ between `base` and `side`.
+fn changed_fields(base: &Value, side: &Value) -> Vec<String> {
+    let mut fields = Vec::new();
+    collect_changes(base, side, String::new(), &mut fields);
+    fields
+}
+
+fn collect_changes(base: &Value, side: &Value, path: String, out: &mut Vec<String>) {
+    match (base, side) {
+        (Value::Object(b), Value::Object(s)) => {
+            let mut keys: Vec<&String> = b.keys().chain(s.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let (bv, sv) = (
+                    b.get(key).unwrap_or(&Value::Null),
+                    s.get(key).unwrap_or(&Value::Null),
+                );
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                collect_changes(bv, sv, path, out);
+            }
+        }
+        _ if base != side => out.push(if path.is_empty() {
+            "value".into()
+        } else {
+            path
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::GradeGroup;
+    use crate::transform::params::GradingParams;
+
+    fn graded(saturation: f32) -> Option<GradingParams> {
+        Some(GradingParams {
+            saturation,
+            ..GradingParams::default()
+        })
+    }
+
+    fn base() -> Timeline {
+        let clip = |name: &str| Clip {
+            name: name.into(),
+            media_path: Some(format!("{name}.exr")),
+            ..Clip::default()
+        };
+        Timeline {
+            name: "reel1".into(),
+            clips: vec![clip("sh010"), clip("sh020"), clip("sh030")],
+            groups: vec![GradeGroup::new("day")],
+            ..Timeline::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_takes_each_sides_clips() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.clips[0].grade = graded(1.2);
+        let mut theirs = base.clone();
+        theirs.clips[1].grade = graded(0.8);
+        theirs.clips[2].notes = Some("sky replaced".into());
+        theirs.groups.push(GradeGroup::new("night"));
+
+        let (merged, report) = merge_timelines(&base, &ours, &theirs);
+        assert!(report.is_clean(), "{report}");
+        assert_eq!(merged.clips[0].grade, graded(1.2));
+        assert_eq!(merged.clips[1].grade, graded(0.8));
+        assert_eq!(merged.clips[2].notes.as_deref(), Some("sky replaced"));
+        assert!(merged.group("night").is_some());
+        assert_eq!(report.from_theirs.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts_and_keeps_ours() {
+        let mut base = base();
+        base.clips[1].grade = graded(1.0);
+        let mut ours = base.clone();
+        ours.clips[1].grade = graded(1.2);
+        let mut theirs = base.clone();
+        theirs.clips[1].grade = graded(0.8);
+        theirs.clips.push(Clip {
+            name: "sh040".into(),
+            ..Clip::default()
+        });
+        theirs.groups[0].enabled = false;
+
+        let (merged, report) = merge_timelines(&base, &ours, &theirs);
+        assert_eq!(merged.clips[1].grade, graded(1.2));
+        assert!(
+            !merged.groups[0].enabled,
+            "an unconflicted change still merges"
+        );
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(
+            conflict.item,
+            MergeItem::Clip {
+                index: 1,
+                name: "sh020".into()
+            }
+        );
+        assert_eq!(conflict.ours, ["grade.saturation"]);
+        assert_eq!(conflict.theirs, ["grade.saturation"]);
+        assert_eq!(report.unmatched, ["sh040"]);
+        assert!(report.to_string().contains("CONFLICT clip 2 \"sh020\""));
+    }
+
+    #[test]
+    fn test_save_merged_merges_into_the_file_on_disk() {
+        let dir = std::env::temp_dir().join(format!("crispen_merge_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let project = dir.join("reel1.otio");
+        let base = base();
+        let mut theirs = base.clone();
+        theirs.clips[2].grade = graded(0.5);
+        save_otio(&theirs, &project).unwrap();
+
+        let mut ours = base.clone();
+        ours.clips[0].grade = graded(1.5);
+        let report = save_merged(&project, &base, &ours, "ana").unwrap();
+        assert!(report.is_clean());
+
+        let saved = load_otio(&project).unwrap();
+        assert_eq!(saved.clips[0].grade, graded(1.5));
+        assert_eq!(saved.clips[2].grade, graded(0.5));
+        assert_eq!(ProjectLock::holder(&project).unwrap(), None);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,47 @@
+//! Editorial interchange — clip lists and their per-clip grades.
+
+pub mod ale;
+pub mod audit;
+pub mod checksum;
+pub mod conform;
+pub mod consistency;
+pub mod cuts;
+pub mod deflicker;
+pub mod dpx;
+pub mod edl;
+pub mod gallery;
+pub mod groups;
+pub mod keyframes;
+pub mod lock;
+pub mod merge;
+pub mod otio;
+pub mod profiles;
+pub mod report;
+pub mod sequence;
+pub mod sequence_export;
+pub mod sidecars;
+pub mod timeline;
+pub mod trace;
+
+pub use audit::{AuditAction, AuditEntry, AuditLog};
+pub use checksum::{
+    ChecksumAlgorithm, Manifest, VerifyReport, folder_manifest_path, verify_manifest,
+};
+pub use conform::{ConformReport, conform_luts};
+pub use consistency::{ConsistencyReport, ConsistencySettings, analyze_timeline};
+pub use cuts::{CutSettings, FrameProxy, detect_cuts, frame_differences, split_sequence};
+pub use deflicker::{DeflickerSettings, deflicker_gains, deflicker_track, frame_exposure};
+pub use gallery::{Gallery, GalleryEntry, StillFormat, StillSidecar};
+pub use groups::GradeStack;
+pub use keyframes::{Keyframe, KeyframeTrack};
+pub use lock::ProjectLock;
+pub use merge::{MergeReport, merge_timelines, save_merged};
+pub use profiles::{CameraProfile, ProfileLibrary, ProfileMatch};
+pub use report::{ReportClip, SessionReport};
+pub use sequence::{ImageSequence, expand_pattern};
+pub use sequence_export::{
+    ExportFrame, ExportPlan, FrameRange, OverwritePolicy, SequenceExport, SequenceFormat,
+};
+pub use sidecars::{Sidecars, sidecar_base, write_sidecars};
+pub use timeline::{Clip, GradeGroup, SourceRange, Timeline};
+pub use trace::{MatchKey, RipplePreview, apply_ripple, preview_ripple};
@@ -0,0 +1,685 @@
+//! OpenTimelineIO (`.otio`) read/write.
+//!
+//! OTIO files are JSON documents tagged with `OTIO_SCHEMA` names. Only the
+//! parts Crispen needs are mapped: the first video track's clips, their
+//! source ranges, and their external media references. Gaps, transitions,
+//! and nested stacks are skipped on read.
+//!
+//! Per-clip grades travel as an OTIO `Effect` with `effect_name`
+//! `"CrispenGrade"` and the serialized [`GradingParams`] under
+//! `metadata.crispen.grade`, so editorial tools that don't know Crispen
+//! carry it through untouched.
+//!
+//! Input LUTs are read from any clip or media-reference metadata key
+//! containing `lut` whose value names a LUT file, which covers the keys
+//! Resolve, Baselight, and DIT tools write. Crispen writes its own under
+//! `metadata.crispen.input_lut`. Reel and camera names are found the same
+//! way under the usual `Reel Name` / `Tape` / `Camera` keys.
+//!
+//! Grade groups are stored on the timeline under `metadata.crispen.groups`
+//! and each clip's membership under `metadata.crispen.group`. Output
+//! blanking is `metadata.crispen.blanking` on the timeline and, for clips
+//! that override it, on the clip. A clip's keyframed master gain is
+//! `metadata.crispen.gain_keys`. Dead-pixel maps are
+//! `metadata.crispen.defect_maps` on the timeline, keyed by camera, and
+//! `metadata.crispen.defect_map` on clips with their own. Camera profiles
+//! are `metadata.crispen.profiles` on the timeline and a clip's assigned
+//! profile name `metadata.crispen.profile`. Clip notes are
+//! `metadata.crispen.notes`, falling back to the usual `Notes` / `Comments`
+//! keys.
+//!
+//! Media URLs are percent-decoded on read and encoded on write, so paths
+//! with spaces or `#` survive the trip through Resolve and Avid exports.
+
+use std::path::Path;
+
+use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, utf8_percent_encode};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use super::conform::find_lut_reference;
+use super::keyframes::{Keyframe, KeyframeTrack};
+use super::profiles::ProfileLibrary;
+use super::timeline::{Clip, GradeGroup, SourceRange, Timeline};
+use crate::framing::Blanking;
+use crate::transform::params::GradingParams;
+
+/// Effect name that marks a Crispen grade on a clip.
+pub const GRADE_EFFECT_NAME: &str = "CrispenGrade";
+
+/// Errors from reading or writing OTIO files.
+#[derive(Debug, thiserror::Error)]
+pub enum OtioError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported OTIO document: {0}")]
+    Schema(String),
+}
+
+/// Load a timeline from an `.otio` file.
+pub fn load_otio(path: &Path) -> Result<Timeline, OtioError> {
+    read_otio(&std::fs::read_to_string(path)?)
+}
+
+/// Save a timeline to an `.otio` file.
+pub fn save_otio(timeline: &Timeline, path: &Path) -> Result<(), OtioError> {
+    std::fs::write(path, write_otio(timeline)?)?;
+    Ok(())
+}
+
+/// Parse an OTIO JSON document.
+pub fn read_otio(json: &str) -> Result<Timeline, OtioError> {
+    let root: Value = serde_json::from_str(json)?;
+    if !schema_is(&root, "Timeline") {
+        return Err(OtioError::Schema(format!(
+            "root is {}, expected Timeline",
+            schema_of(&root).unwrap_or("untagged")
+        )));
+    }
+
+    let tracks = root
+        .get("tracks")
+        .and_then(|t| t.get("children"))
+        .and_then(Value::as_array)
+        .ok_or_else(|| OtioError::Schema("timeline has no tracks".into()))?;
+
+    let video = tracks.iter().find(|t| {
+        schema_is(t, "Track") && t.get("kind").and_then(Value::as_str).unwrap_or("Video") == "Video"
+    });
+
+    let clips = video
+        .and_then(|t| t.get("children"))
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter(|item| schema_is(item, "Clip"))
+                .map(read_clip)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Timeline {
+        name: str_field(&root, "name").unwrap_or_default(),
+        clips,
+        groups: read_groups(&root),
+        blanking: read_blanking(&root).unwrap_or_default(),
+        defect_maps: read_crispen(&root, "defect_maps").unwrap_or_default(),
+        profiles: ProfileLibrary {
+            profiles: read_crispen(&root, "profiles").unwrap_or_default(),
+        },
+    })
+}
+
+/// Serialize a timeline as an OTIO JSON document.
+pub fn write_otio(timeline: &Timeline) -> Result<String, OtioError> {
+    let clips = timeline
+        .clips
+        .iter()
+        .map(write_clip)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut crispen = Map::new();
+    if !timeline.groups.is_empty() {
+        crispen.insert("groups".into(), serde_json::to_value(&timeline.groups)?);
+    }
+    if timeline.blanking != Blanking::None {
+        crispen.insert("blanking".into(), serde_json::to_value(timeline.blanking)?);
+    }
+    if !timeline.defect_maps.is_empty() {
+        crispen.insert(
+            "defect_maps".into(),
+            serde_json::to_value(&timeline.defect_maps)?,
+        );
+    }
+    if !timeline.profiles.is_empty() {
+        crispen.insert(
+            "profiles".into(),
+            serde_json::to_value(&timeline.profiles.profiles)?,
+        );
+    }
+    let metadata = if crispen.is_empty() {
+        json!({})
+    } else {
+        json!({ "crispen": crispen })
+    };
+
+    let doc = json!({
+        "OTIO_SCHEMA": "Timeline.1",
+        "metadata": metadata,
+        "name": timeline.name,
+        "global_start_time": null,
+        "tracks": {
+            "OTIO_SCHEMA": "Stack.1",
+            "metadata": {},
+            "name": "tracks",
+            "source_range": null,
+            "effects": [],
+            "markers": [],
+            "enabled": true,
+            "children": [{
+                "OTIO_SCHEMA": "Track.1",
+                "metadata": {},
+                "name": "V1",
+                "source_range": null,
+                "effects": [],
+                "markers": [],
+                "enabled": true,
+                "kind": "Video",
+                "children": clips,
+            }],
+        },
+    });
+    Ok(serde_json::to_string_pretty(&doc)?)
+}
+
+fn read_clip(value: &Value) -> Clip {
+    let media = value
+        .get("media_references")
+        .and_then(|refs| {
+            let key = str_field(value, "active_media_reference_key")
+                .unwrap_or_else(|| "DEFAULT_MEDIA".to_string());
+            refs.get(&key)
+        })
+        .or_else(|| value.get("media_reference"));
+
+    let media_path = media
+        .filter(|m| schema_is(m, "ExternalReference"))
+        .and_then(|m| str_field(m, "target_url"))
+        .map(|url| url_to_path(&url));
+
+    Clip {
+        name: str_field(value, "name").unwrap_or_default(),
+        media_path,
+        source_range: value.get("source_range").and_then(read_time_range),
+        grade: read_grade(value),
+        input_lut: value
+            .pointer("/metadata/crispen/input_lut")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| value.get("metadata").and_then(find_lut_in_metadata))
+            .or_else(|| media?.get("metadata").and_then(find_lut_in_metadata)),
+        reel: read_tag(value, "reel", REEL_KEYS),
+        camera: read_tag(value, "camera", CAMERA_KEYS),
+        group: value
+            .pointer("/metadata/crispen/group")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        blanking: read_blanking(value),
+        gain_keys: read_gain_keys(value),
+        defect_map: read_crispen(value, "defect_map"),
+        profile: value
+            .pointer("/metadata/crispen/profile")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        notes: read_tag(value, "notes", NOTE_KEYS),
+    }
+}
+
+/// Metadata keys (lowercased) that name a clip's source reel.
+const REEL_KEYS: &[&str] = &["reel", "reel name", "reel_name", "tape", "tape name"];
+
+/// Metadata keys (lowercased) that name a clip's camera.
+const CAMERA_KEYS: &[&str] = &["camera", "camera id", "camera_id", "camera name"];
+
+/// Metadata keys (lowercased) that hold notes on a clip.
+const NOTE_KEYS: &[&str] = &["notes", "note", "comments", "comment"];
+
+/// Read `metadata.crispen.<field>`, falling back to any nested key in `keys`.
+fn read_tag(clip: &Value, field: &str, keys: &[&str]) -> Option<String> {
+    let metadata = clip.get("metadata")?;
+    metadata
+        .get("crispen")
+        .and_then(|c| str_field(c, field))
+        .or_else(|| find_key_in_metadata(metadata, keys))
+}
+
+fn find_key_in_metadata(metadata: &Value, keys: &[&str]) -> Option<String> {
+    let map = metadata.as_object()?;
+    map.iter()
+        .filter(|(key, _)| keys.contains(&key.to_ascii_lowercase().as_str()))
+        .find_map(|(_, v)| v.as_str().filter(|s| !s.is_empty()).map(str::to_string))
+        .or_else(|| map.values().find_map(|v| find_key_in_metadata(v, keys)))
+}
+
+/// Depth-first search for a `*lut*` key naming a LUT file.
+fn find_lut_in_metadata(metadata: &Value) -> Option<String> {
+    let map = metadata.as_object()?;
+    map.iter()
+        .filter(|(key, _)| key.to_ascii_lowercase().contains("lut"))
+        .find_map(|(_, v)| v.as_str().and_then(find_lut_reference))
+        .or_else(|| map.values().find_map(find_lut_in_metadata))
+}
+
+fn read_groups(root: &Value) -> Vec<GradeGroup> {
+    let Some(groups) = root.pointer("/metadata/crispen/groups") else {
+        return Vec::new();
+    };
+    match serde_json::from_value(groups.clone()) {
+        Ok(groups) => groups,
+        Err(e) => {
+            tracing::warn!("ignoring unreadable Crispen grade groups: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// `metadata.crispen.blanking` of a timeline or clip.
+fn read_blanking(value: &Value) -> Option<Blanking> {
+    let blanking = value.pointer("/metadata/crispen/blanking")?;
+    match serde_json::from_value(blanking.clone()) {
+        Ok(blanking) => Some(blanking),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable Crispen blanking: {e}");
+            None
+        }
+    }
+}
+
+/// `metadata.crispen.gain_keys` of a clip.
+fn read_gain_keys(clip: &Value) -> Option<KeyframeTrack> {
+    let keys = clip.pointer("/metadata/crispen/gain_keys")?;
+    match serde_json::from_value::<Vec<Keyframe>>(keys.clone()) {
+        Ok(keys) => Some(KeyframeTrack::new(keys)),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable Crispen gain keys: {e}");
+            None
+        }
+    }
+}
+
+/// `metadata.crispen.<field>` of a timeline or clip.
+fn read_crispen<T: DeserializeOwned>(value: &Value, field: &str) -> Option<T> {
+    let field_value = value.get("metadata")?.get("crispen")?.get(field)?;
+    match serde_json::from_value(field_value.clone()) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable Crispen {field}: {e}");
+            None
+        }
+    }
+}
+
+fn read_grade(clip: &Value) -> Option<GradingParams> {
+    let effects = clip.get("effects")?.as_array()?;
+    let effect = effects
+        .iter()
+        .find(|e| e.get("effect_name").and_then(Value::as_str) == Some(GRADE_EFFECT_NAME))?;
+    let grade = effect.pointer("/metadata/crispen/grade")?;
+    match serde_json::from_value(grade.clone()) {
+        Ok(params) => Some(params),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable Crispen grade on clip: {e}");
+            None
+        }
+    }
+}
+
+fn write_clip(clip: &Clip) -> Result<Value, OtioError> {
+    let media = match &clip.media_path {
+        Some(path) => json!({
+            "OTIO_SCHEMA": "ExternalReference.1",
+            "metadata": {},
+            "name": "",
+            "available_range": null,
+            "available_image_bounds": null,
+            "target_url": path_to_url(path),
+        }),
+        None => json!({
+            "OTIO_SCHEMA": "MissingReference.1",
+            "metadata": {},
+            "name": "",
+            "available_range": null,
+            "available_image_bounds": null,
+        }),
+    };
+
+    let effects = match &clip.grade {
+        Some(grade) => vec![json!({
+            "OTIO_SCHEMA": "Effect.1",
+            "metadata": { "crispen": { "grade": serde_json::to_value(grade)? } },
+            "name": "Crispen Grade",
+            "effect_name": GRADE_EFFECT_NAME,
+        })],
+        None => Vec::new(),
+    };
+
+    let mut crispen = Map::new();
+    for (key, value) in [
+        ("input_lut", &clip.input_lut),
+        ("reel", &clip.reel),
+        ("camera", &clip.camera),
+        ("group", &clip.group),
+        ("profile", &clip.profile),
+        ("notes", &clip.notes),
+    ] {
+        if let Some(value) = value {
+            crispen.insert(key.into(), json!(value));
+        }
+    }
+    if let Some(blanking) = clip.blanking {
+        crispen.insert("blanking".into(), serde_json::to_value(blanking)?);
+    }
+    if let Some(track) = &clip.gain_keys {
+        crispen.insert("gain_keys".into(), serde_json::to_value(&track.keys)?);
+    }
+    if let Some(map) = &clip.defect_map {
+        crispen.insert("defect_map".into(), serde_json::to_value(map)?);
+    }
+    let metadata = if crispen.is_empty() {
+        json!({})
+    } else {
+        json!({ "crispen": crispen })
+    };
+
+    let mut refs = Map::new();
+    refs.insert("DEFAULT_MEDIA".into(), media);
+
+    Ok(json!({
+        "OTIO_SCHEMA": "Clip.2",
+        "metadata": metadata,
+        "name": clip.name,
+        "source_range": clip.source_range.map(write_time_range),
+        "effects": effects,
+        "markers": [],
+        "enabled": true,
+        "media_references": refs,
+        "active_media_reference_key": "DEFAULT_MEDIA",
+    }))
+}
+
+fn read_time_range(value: &Value) -> Option<SourceRange> {
+    let start = value.get("start_time")?;
+    let duration = value.get("duration")?;
+    let rate = start.get("rate")?.as_f64()?;
+    Some(SourceRange {
+        start: start.get("value")?.as_f64()?,
+        duration: duration.get("value")?.as_f64()?,
+        rate,
+    })
+}
+
+fn write_time_range(range: SourceRange) -> Value {
+    json!({
+        "OTIO_SCHEMA": "TimeRange.1",
+        "start_time": {
+            "OTIO_SCHEMA": "RationalTime.1",
+            "rate": range.rate,
+            "value": range.start,
+        },
+        "duration": {
+            "OTIO_SCHEMA": "RationalTime.1",
+            "rate": range.rate,
+            "value": range.duration,
+        },
+    })
+}
+
+fn schema_of(value: &Value) -> Option<&str> {
+    value.get("OTIO_SCHEMA").and_then(Value::as_str)
+}
+
+/// Match a schema family regardless of version (`Clip.1`, `Clip.2`, …).
+fn schema_is(value: &Value, family: &str) -> bool {
+    schema_of(value)
+        .and_then(|s| s.split('.').next())
+        .is_some_and(|s| s == family)
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Characters escaped in the path of a `file://` URL: controls, space,
+/// and those URL syntax gives a meaning (`#` would start a fragment).
+const URL_PATH: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+fn url_to_path(url: &str) -> String {
+    let Some(rest) = url.strip_prefix("file://") else {
+        return url.to_string();
+    };
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let path = percent_decode_str(rest).decode_utf8_lossy();
+    // `file:///C:/…` names a Windows drive, not a root directory `C:`.
+    match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path.into_owned(),
+    }
+}
+
+fn path_to_url(path: &str) -> String {
+    if path.contains("://") {
+        path.to_string()
+    } else if path.starts_with('/') {
+        format!("file://{}", utf8_percent_encode(path, URL_PATH))
+    } else if let [drive, b':', b'/' | b'\\', ..] = path.as_bytes()
+        && drive.is_ascii_alphabetic()
+    {
+        let path = path.replace('\\', "/");
+        format!("file:///{}", utf8_percent_encode(&path, URL_PATH))
+    } else {
+        path.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::pixel_repair::{Defect, DefectMap};
+    use crate::project::profiles::CameraProfile;
+    use crate::transform::params::ColorSpaceId;
+
+    fn sample_timeline() -> Timeline {
+        Timeline {
+            name: "Reel 1".into(),
+            clips: vec![
+                Clip {
+                    name: "A001C003".into(),
+                    media_path: Some("/media/A001C003.mov".into()),
+                    source_range: Some(SourceRange {
+                        start: 86_400.0,
+                        duration: 48.0,
+                        rate: 24.0,
+                    }),
+                    grade: Some(GradingParams {
+                        saturation: 1.25,
+                        ..GradingParams::default()
+                    }),
+                    input_lut: Some("/luts/show_v2.cube".into()),
+                    reel: Some("A001".into()),
+                    camera: Some("A".into()),
+                    group: Some("show".into()),
+                    blanking: Some(Blanking::None),
+                    gain_keys: Some(KeyframeTrack::new(vec![
+                        Keyframe {
+                            frame: 86_400.0,
+                            value: 1.0,
+                        },
+                        Keyframe {
+                            frame: 86_401.0,
+                            value: 0.9,
+                        },
+                    ])),
+                    defect_map: None,
+                    profile: Some("alexa".into()),
+                    notes: Some("flicker at the head".into()),
+                },
+                Clip {
+                    name: "A002C001".into(),
+                    camera: Some("A".into()),
+                    defect_map: Some(DefectMap::default()),
+                    ..Clip::default()
+                },
+            ],
+            groups: vec![GradeGroup {
+                post_clip: Some(GradingParams {
+                    contrast: 1.1,
+                    ..GradingParams::default()
+                }),
+                enabled: false,
+                ..GradeGroup::new("show")
+            }],
+            blanking: Blanking::SCOPE,
+            defect_maps: [(
+                "A".to_string(),
+                DefectMap {
+                    defects: vec![Defect {
+                        x: 1021,
+                        y: 344,
+                        radius: 0,
+                    }],
+                },
+            )]
+            .into(),
+            profiles: ProfileLibrary {
+                profiles: vec![CameraProfile {
+                    exposure: 0.5,
+                    input_lut: Some("alexa_show.cube".into()),
+                    ..CameraProfile::new("alexa", ColorSpaceId::ArriLogC3)
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_preserves_clips_and_grades() {
+        let timeline = sample_timeline();
+        let json = write_otio(&timeline).unwrap();
+        let read = read_otio(&json).unwrap();
+        assert_eq!(read, timeline);
+    }
+
+    #[test]
+    fn test_clip_blanking_overrides_timeline() {
+        let read = read_otio(&write_otio(&sample_timeline()).unwrap()).unwrap();
+        assert_eq!(read.blanking, Blanking::SCOPE);
+        assert_eq!(read.blanking_for(&read.clips[0]), Blanking::None);
+        assert_eq!(read.blanking_for(&read.clips[1]), Blanking::SCOPE);
+    }
+
+    #[test]
+    fn test_clip_defect_map_overrides_camera() {
+        let read = read_otio(&write_otio(&sample_timeline()).unwrap()).unwrap();
+        let camera_map = read.defect_map_for(&read.clips[0]).expect("camera A");
+        assert_eq!(camera_map.defects.len(), 1);
+        let own = read.defect_map_for(&read.clips[1]).expect("own map");
+        assert!(own.is_empty());
+    }
+
+    #[test]
+    fn test_writes_file_url_and_grade_effect() {
+        let json = write_otio(&sample_timeline()).unwrap();
+        assert!(json.contains("\"target_url\": \"file:///media/A001C003.mov\""));
+        assert!(json.contains("\"effect_name\": \"CrispenGrade\""));
+    }
+
+    #[test]
+    fn test_media_url_percent_encoding_roundtrips() {
+        let mut timeline = sample_timeline();
+        timeline.clips[0].media_path = Some("/Volumes/My Drive/take #3.mov".into());
+        let json = write_otio(&timeline).unwrap();
+        assert!(json.contains("file:///Volumes/My%20Drive/take%20%233.mov"));
+        let read = read_otio(&json).unwrap();
+        assert_eq!(
+            read.clips[0].media_path.as_deref(),
+            Some("/Volumes/My Drive/take #3.mov")
+        );
+    }
+
+    #[test]
+    fn test_decodes_foreign_file_urls() {
+        assert_eq!(
+            url_to_path("file://localhost/Volumes/My%20Drive/A001.mov"),
+            "/Volumes/My Drive/A001.mov"
+        );
+        assert_eq!(
+            url_to_path("file:///C:/Media/A%20001.mov"),
+            "C:/Media/A 001.mov"
+        );
+        assert_eq!(
+            path_to_url("C:\\Media\\A 001.mov"),
+            "file:///C:/Media/A%20001.mov"
+        );
+    }
+
+    #[test]
+    fn test_reads_clip_1_media_reference_and_skips_gaps() {
+        let json = r#"{
+            "OTIO_SCHEMA": "Timeline.1",
+            "name": "Edit",
+            "tracks": {
+                "OTIO_SCHEMA": "Stack.1",
+                "children": [
+                    { "OTIO_SCHEMA": "Track.1", "kind": "Audio", "children": [] },
+                    { "OTIO_SCHEMA": "Track.1", "kind": "Video", "children": [
+                        { "OTIO_SCHEMA": "Gap.1", "source_range": null },
+                        { "OTIO_SCHEMA": "Clip.1", "name": "shot",
+                          "media_reference": {
+                              "OTIO_SCHEMA": "ExternalReference.1",
+                              "target_url": "shot.exr"
+                          },
+                          "source_range": {
+                              "start_time": { "rate": 25.0, "value": 10.0 },
+                              "duration": { "rate": 25.0, "value": 5.0 }
+                          }
+                        }
+                    ]}
+                ]
+            }
+        }"#;
+        let timeline = read_otio(json).unwrap();
+        assert_eq!(timeline.name, "Edit");
+        assert_eq!(timeline.clips.len(), 1);
+        let clip = &timeline.clips[0];
+        assert_eq!(clip.media_path.as_deref(), Some("shot.exr"));
+        assert_eq!(clip.source_range.unwrap().rate, 25.0);
+        assert!(clip.grade.is_none());
+    }
+
+    #[test]
+    fn test_detects_lut_in_foreign_metadata() {
+        let json = r#"{
+            "OTIO_SCHEMA": "Timeline.1",
+            "tracks": { "OTIO_SCHEMA": "Stack.1", "children": [
+                { "OTIO_SCHEMA": "Track.1", "kind": "Video", "children": [
+                    { "OTIO_SCHEMA": "Clip.1", "name": "a",
+                      "metadata": { "Resolve_OTIO": {
+                          "Input LUT": "Rec709_v3.cube", "Reel Name": "A003" } } },
+                    { "OTIO_SCHEMA": "Clip.1", "name": "b",
+                      "metadata": { "lut_name": "not a file" },
+                      "media_reference": { "OTIO_SCHEMA": "ExternalReference.1",
+                          "target_url": "b.mov",
+                          "metadata": { "dit": { "camera_lut": "/on-set/b.3dl" } } } }
+                ]}
+            ]}
+        }"#;
+        let timeline = read_otio(json).unwrap();
+        assert_eq!(
+            timeline.clips[0].input_lut.as_deref(),
+            Some("Rec709_v3.cube")
+        );
+        assert_eq!(timeline.clips[0].reel.as_deref(), Some("A003"));
+        assert_eq!(
+            timeline.clips[1].input_lut.as_deref(),
+            Some("/on-set/b.3dl")
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_timeline_root() {
+        let err = read_otio(r#"{ "OTIO_SCHEMA": "Clip.2" }"#).unwrap_err();
+        assert!(matches!(err, OtioError::Schema(_)));
+    }
+}
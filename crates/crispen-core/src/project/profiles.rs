@@ -0,0 +1,269 @@
+//! Camera profiles — reusable input corrections assigned by clip metadata.
+//!
+//! A [`CameraProfile`] bundles what every clip from one camera setup needs
+//! before grading starts: the input color space, an optional input LUT, a
+//! matrix trim, and an exposure offset. Profiles live in a
+//! [`ProfileLibrary`], saved as JSON so one library serves many projects,
+//! and are copied onto the timeline that uses them.
+//!
+//! A clip gets a profile by name through [`Clip::profile`], or by the first
+//! profile whose [`ProfileMatch`] fits its camera and reel. The profile
+//! runs at the head of the [`GradeStack`](super::groups::GradeStack):
+//!
+//! ```text
+//! source ─ input levels ─ profile input transform ─▶ exposure ─▶ matrix trim ─▶ pre-clip ─▶ clip ─▶ …
+//! ```
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::timeline::{Clip, Timeline};
+use crate::transform::params::ColorSpaceId;
+
+/// Errors from loading or saving a profile library.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid profile library: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Clip metadata a profile is assigned by. Every criterion that is set must
+/// match; a profile with none set is only assigned by name.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileMatch {
+    /// [`Clip::camera`], compared without regard to case.
+    pub camera: Option<String>,
+    /// Leading characters of [`Clip::reel`] (e.g. `A` for `A001`, `A002`, …).
+    pub reel_prefix: Option<String>,
+}
+
+impl ProfileMatch {
+    /// Whether `clip`'s metadata fits.
+    pub fn matches(&self, clip: &Clip) -> bool {
+        if self.camera.is_none() && self.reel_prefix.is_none() {
+            return false;
+        }
+        let camera = self.camera.as_deref().is_none_or(|want| {
+            clip.camera
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(want))
+        });
+        let reel = self
+            .reel_prefix
+            .as_deref()
+            .is_none_or(|prefix| clip.reel.as_deref().is_some_and(|r| r.starts_with(prefix)));
+        camera && reel
+    }
+}
+
+/// Input corrections shared by every clip from one camera setup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraProfile {
+    /// Profile name, referenced by [`Clip::profile`].
+    pub name: String,
+    /// Metadata that assigns the profile to a clip.
+    #[serde(default)]
+    pub matches: ProfileMatch,
+    /// Encoding of the camera's footage; replaces the input space of the
+    /// clip's grade.
+    pub input_space: ColorSpaceId,
+    /// Input LUT given to assigned clips that reference none of their own.
+    #[serde(default)]
+    pub input_lut: Option<String>,
+    /// Row-major 3×3 trim in the working space, after the exposure offset.
+    #[serde(default = "identity_matrix")]
+    pub matrix: [[f32; 3]; 3],
+    /// Exposure offset in stops, applied in the working space.
+    #[serde(default)]
+    pub exposure: f32,
+}
+
+fn identity_matrix() -> [[f32; 3]; 3] {
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+}
+
+impl CameraProfile {
+    /// A profile for footage in `input_space` with no trims.
+    pub fn new(name: impl Into<String>, input_space: ColorSpaceId) -> Self {
+        Self {
+            name: name.into(),
+            matches: ProfileMatch::default(),
+            input_space,
+            input_lut: None,
+            matrix: identity_matrix(),
+            exposure: 0.0,
+        }
+    }
+
+    /// Apply the exposure offset and matrix trim to a working-space pixel.
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let scale = self.exposure.exp2();
+        let m = &self.matrix;
+        std::array::from_fn(|i| scale * (m[i][0] * rgb[0] + m[i][1] * rgb[1] + m[i][2] * rgb[2]))
+    }
+}
+
+/// A reusable set of camera profiles.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ProfileLibrary {
+    /// Profiles in match priority order.
+    #[serde(default)]
+    pub profiles: Vec<CameraProfile>,
+}
+
+impl ProfileLibrary {
+    /// Load a library from a JSON file.
+    pub fn load(path: &Path) -> Result<Self, ProfileError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Save the library as a JSON file.
+    pub fn save(&self, path: &Path) -> Result<(), ProfileError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Whether the library has no profiles.
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    /// Look up a profile by name.
+    pub fn get(&self, name: &str) -> Option<&CameraProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// The first profile whose metadata match fits `clip`.
+    pub fn find_match(&self, clip: &Clip) -> Option<&CameraProfile> {
+        self.profiles.iter().find(|p| p.matches.matches(clip))
+    }
+
+    /// Add `profile`, replacing any profile of the same name in place.
+    pub fn insert(&mut self, profile: CameraProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+}
+
+impl Timeline {
+    /// The profile in effect for `clip`: the one it names, or else the
+    /// first whose metadata match fits. A name missing from the library
+    /// leaves the clip without a profile.
+    pub fn profile_for(&self, clip: &Clip) -> Option<&CameraProfile> {
+        match clip.profile.as_deref() {
+            Some(name) => self.profiles.get(name),
+            None => self.profiles.find_match(clip),
+        }
+    }
+
+    /// Name the matching profile on every clip that has none, and give
+    /// those clips the profile's input LUT when they reference none of
+    /// their own. Returns the number of clips assigned.
+    ///
+    /// Run before [`conform_luts`](super::conform::conform_luts) so profile
+    /// LUTs are resolved with the rest.
+    pub fn assign_profiles(&mut self) -> usize {
+        let mut assigned = 0;
+        for clip in &mut self.clips {
+            if clip.profile.is_some() {
+                continue;
+            }
+            let Some(profile) = self.profiles.find_match(clip) else {
+                continue;
+            };
+            clip.profile = Some(profile.name.clone());
+            if clip.input_lut.is_none() {
+                clip.input_lut.clone_from(&profile.input_lut);
+            }
+            assigned += 1;
+        }
+        assigned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> ProfileLibrary {
+        let mut alexa = CameraProfile::new("alexa", ColorSpaceId::ArriLogC3);
+        alexa.matches.camera = Some("A".into());
+        alexa.input_lut = Some("alexa_show.cube".into());
+        alexa.exposure = 1.0;
+        let mut b_roll = CameraProfile::new("b roll", ColorSpaceId::Srgb);
+        b_roll.matches.reel_prefix = Some("B0".into());
+        ProfileLibrary {
+            profiles: vec![
+                alexa,
+                b_roll,
+                CameraProfile::new("manual", ColorSpaceId::Rec2020),
+            ],
+        }
+    }
+
+    fn clip(camera: Option<&str>, reel: Option<&str>) -> Clip {
+        Clip {
+            camera: camera.map(str::to_string),
+            reel: reel.map(str::to_string),
+            ..Clip::default()
+        }
+    }
+
+    #[test]
+    fn test_match_by_camera_and_reel_prefix() {
+        let library = library();
+        let name = |c: &Clip| library.find_match(c).map(|p| p.name.as_str());
+        assert_eq!(name(&clip(Some("a"), Some("A001"))), Some("alexa"));
+        assert_eq!(name(&clip(None, Some("B003"))), Some("b roll"));
+        assert_eq!(name(&clip(Some("C"), Some("C001"))), None);
+        // A profile with no criteria never matches on its own.
+        assert_eq!(name(&clip(None, None)), None);
+    }
+
+    #[test]
+    fn test_assign_profiles_keeps_explicit_names_and_own_luts() {
+        let mut timeline = Timeline {
+            clips: vec![
+                clip(Some("A"), Some("A001")),
+                Clip {
+                    input_lut: Some("own.cube".into()),
+                    ..clip(Some("A"), Some("A002"))
+                },
+                Clip {
+                    profile: Some("manual".into()),
+                    ..clip(Some("A"), Some("A003"))
+                },
+                clip(Some("C"), None),
+            ],
+            profiles: library(),
+            ..Timeline::default()
+        };
+        assert_eq!(timeline.assign_profiles(), 2);
+
+        let clips = &timeline.clips;
+        assert_eq!(clips[0].profile.as_deref(), Some("alexa"));
+        assert_eq!(clips[0].input_lut.as_deref(), Some("alexa_show.cube"));
+        assert_eq!(clips[1].input_lut.as_deref(), Some("own.cube"));
+        assert_eq!(
+            timeline.profile_for(&clips[2]).map(|p| p.input_space),
+            Some(ColorSpaceId::Rec2020)
+        );
+        assert!(timeline.profile_for(&clips[3]).is_none());
+    }
+
+    #[test]
+    fn test_apply_exposure_then_matrix() {
+        let mut profile = CameraProfile::new("p", ColorSpaceId::AcesCg);
+        assert_eq!(profile.apply([0.1, 0.2, 0.3]), [0.1, 0.2, 0.3]);
+        profile.exposure = 1.0;
+        profile.matrix[0] = [0.5, 0.5, 0.0];
+        let out = profile.apply([0.1, 0.3, 0.3]);
+        assert!((out[0] - 0.4).abs() < 1e-6 && (out[2] - 0.6).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,291 @@
+//! Session report — a printable summary of a graded timeline.
+//!
+//! For deliverable documentation each clip is listed with its before and
+//! after thumbnails, the ASC CDL of its grade, the input LUT it uses, its
+//! group and camera profile, and its notes, followed by every LUT the
+//! session references. [`SessionReport::to_html`] writes one self-contained
+//! HTML page with a print stylesheet, so "Print to PDF" in any browser
+//! gives the PDF version with one clip per row and no clip split across
+//! pages.
+//!
+//! The report only lays out what it is given: thumbnails are image URLs
+//! (usually `data:` URLs) rendered by the caller, since this crate has no
+//! image encoder.
+
+use super::timeline::{SourceRange, Timeline};
+use crate::grading::cdl::AscCdl;
+
+/// One clip's row in a [`SessionReport`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportClip {
+    /// Clip name.
+    pub name: String,
+    /// Source reel, if known.
+    pub reel: Option<String>,
+    /// Camera, if known.
+    pub camera: Option<String>,
+    /// Used range of the source media.
+    pub source_range: Option<SourceRange>,
+    /// CDL of the clip's own grade; `None` for an ungraded clip.
+    pub cdl: Option<AscCdl>,
+    /// Input LUT reference.
+    pub input_lut: Option<String>,
+    /// Grade group.
+    pub group: Option<String>,
+    /// Camera profile in effect.
+    pub profile: Option<String>,
+    /// Clip notes.
+    pub notes: Option<String>,
+    /// Image URL of the ungraded frame.
+    pub before: Option<String>,
+    /// Image URL of the graded frame.
+    pub after: Option<String>,
+    /// Why the thumbnails are missing, e.g. unreadable media.
+    pub skipped: Option<String>,
+}
+
+/// A printable summary of a grading session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionReport {
+    /// Report heading, usually the timeline name.
+    pub title: String,
+    /// Session-wide notes shown under the heading.
+    pub notes: Option<String>,
+    /// One row per clip, in timeline order.
+    pub clips: Vec<ReportClip>,
+}
+
+impl SessionReport {
+    /// Rows for every clip of `timeline`, without thumbnails.
+    pub fn from_timeline(timeline: &Timeline) -> Self {
+        let clips = timeline
+            .clips
+            .iter()
+            .map(|clip| ReportClip {
+                name: clip.name.clone(),
+                reel: clip.reel.clone(),
+                camera: clip.camera.clone(),
+                source_range: clip.source_range,
+                cdl: clip.grade.as_ref().map(AscCdl::from_params),
+                input_lut: clip.input_lut.clone(),
+                group: clip.group.clone(),
+                profile: timeline.profile_for(clip).map(|p| p.name.clone()),
+                notes: clip.notes.clone(),
+                ..ReportClip::default()
+            })
+            .collect();
+        Self {
+            title: timeline.name.clone(),
+            notes: None,
+            clips,
+        }
+    }
+
+    /// Distinct input LUTs referenced by the clips, in first-use order.
+    pub fn luts_used(&self) -> Vec<&str> {
+        let mut luts: Vec<&str> = Vec::new();
+        for lut in self.clips.iter().filter_map(|c| c.input_lut.as_deref()) {
+            if !luts.contains(&lut) {
+                luts.push(lut);
+            }
+        }
+        luts
+    }
+
+    /// The report as a self-contained HTML page.
+    pub fn to_html(&self) -> String {
+        let title = if self.title.is_empty() {
+            "Grading session"
+        } else {
+            &self.title
+        };
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+            title = escape(title),
+        );
+        if let Some(notes) = &self.notes {
+            html += &format!("<p class=\"notes\">{}</p>\n", escape(notes));
+        }
+        html += &format!("<p class=\"count\">{} clips</p>\n", self.clips.len());
+
+        html += "<table>\n<thead><tr><th>Clip</th><th>Before</th><th>After</th>\
+                 <th>CDL</th><th>Details</th></tr></thead>\n<tbody>\n";
+        for clip in &self.clips {
+            html += &clip_row(clip);
+        }
+        html += "</tbody>\n</table>\n";
+
+        let luts = self.luts_used();
+        html += "<h2>LUTs used</h2>\n";
+        if luts.is_empty() {
+            html += "<p>None</p>\n";
+        } else {
+            html += "<ul>\n";
+            for lut in luts {
+                html += &format!("<li>{}</li>\n", escape(lut));
+            }
+            html += "</ul>\n";
+        }
+        html += "</body>\n</html>\n";
+        html
+    }
+}
+
+/// Print-friendly styling: clips never break across pages.
+const STYLE: &str = "\
+body{font:11px/1.4 sans-serif;margin:16px;color:#111}\
+h1{font-size:18px;margin:0 0 4px}h2{font-size:14px;margin-top:16px}\
+.notes{white-space:pre-wrap}.count{color:#666}\
+table{border-collapse:collapse;width:100%}\
+th,td{border-bottom:1px solid #ccc;padding:4px;text-align:left;vertical-align:top}\
+tr{break-inside:avoid}img{width:200px;display:block}\
+.cdl{font-family:monospace;white-space:pre}.missing{color:#a00}\
+@page{size:A4 landscape;margin:12mm}";
+
+fn clip_row(clip: &ReportClip) -> String {
+    let thumbnail = |url: &Option<String>| match (url, &clip.skipped) {
+        (Some(url), _) => format!("<img src=\"{}\" alt=\"\">", escape(url)),
+        (None, Some(reason)) => format!("<span class=\"missing\">{}</span>", escape(reason)),
+        (None, None) => String::new(),
+    };
+    let cdl = clip.cdl.map_or_else(
+        || "ungraded".to_string(),
+        |cdl| {
+            let triple = |v: [f32; 3]| format!("{:.4} {:.4} {:.4}", v[0], v[1], v[2]);
+            format!(
+                "slope  {}\noffset {}\npower  {}\nsat    {:.4}",
+                triple(cdl.slope),
+                triple(cdl.offset),
+                triple(cdl.power),
+                cdl.saturation
+            )
+        },
+    );
+
+    let mut details = Vec::new();
+    let fields = [
+        ("Reel", &clip.reel),
+        ("Camera", &clip.camera),
+        ("Input LUT", &clip.input_lut),
+        ("Group", &clip.group),
+        ("Profile", &clip.profile),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            details.push(format!("{label}: {}", escape(value)));
+        }
+    }
+    if let Some(range) = clip.source_range {
+        details.push(format!(
+            "Source: {} – {}",
+            timecode(range.start, range.rate),
+            timecode(range.start + range.duration, range.rate)
+        ));
+    }
+    if let Some(notes) = &clip.notes {
+        details.push(format!("<span class=\"notes\">{}</span>", escape(notes)));
+    }
+
+    format!(
+        "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"cdl\">{}</td><td>{}</td></tr>\n",
+        escape(&clip.name),
+        thumbnail(&clip.before),
+        thumbnail(&clip.after),
+        cdl,
+        details.join("<br>")
+    )
+}
+
+/// `HH:MM:SS:FF` for a frame count at `rate` frames per second.
+fn timecode(frames: f64, rate: f64) -> String {
+    let fps = rate.round().max(1.0) as u64;
+    let frames = frames.max(0.0).round() as u64;
+    let seconds = frames / fps;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        frames % fps
+    )
+}
+
+/// Escape text for HTML element content and quoted attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::Clip;
+    use crate::transform::params::GradingParams;
+
+    fn timeline() -> Timeline {
+        let grade = GradingParams {
+            gain_wheel: [1.0, 1.0, 1.0, 2.0],
+            ..GradingParams::default()
+        };
+        Timeline {
+            name: "Reel <1>".into(),
+            clips: vec![
+                Clip {
+                    name: "A001".into(),
+                    grade: Some(grade),
+                    input_lut: Some("show.cube".into()),
+                    notes: Some("client: warmer".into()),
+                    source_range: Some(SourceRange {
+                        start: 86_400.0,
+                        duration: 48.0,
+                        rate: 24.0,
+                    }),
+                    ..Clip::default()
+                },
+                Clip {
+                    name: "A002".into(),
+                    input_lut: Some("show.cube".into()),
+                    ..Clip::default()
+                },
+            ],
+            ..Timeline::default()
+        }
+    }
+
+    #[test]
+    fn test_report_lists_cdl_luts_and_notes() {
+        let report = SessionReport::from_timeline(&timeline());
+        assert_eq!(report.clips[0].cdl.unwrap().slope, [2.0; 3]);
+        assert_eq!(report.clips[1].cdl, None);
+        assert_eq!(report.luts_used(), ["show.cube"]);
+
+        let html = report.to_html();
+        assert!(html.contains("<h1>Reel &lt;1&gt;</h1>"));
+        assert!(html.contains("slope  2.0000 2.0000 2.0000"));
+        assert!(html.contains("client: warmer"));
+        assert!(html.contains("Source: 01:00:00:00 – 01:00:02:00"));
+        assert_eq!(html.matches("<li>show.cube</li>").count(), 1);
+    }
+
+    #[test]
+    fn test_report_shows_thumbnails_or_why_they_are_missing() {
+        let mut report = SessionReport::from_timeline(&timeline());
+        report.clips[0].before = Some("data:image/png;base64,AAA".into());
+        report.clips[0].after = Some("data:image/png;base64,BBB".into());
+        report.clips[1].skipped = Some("no media path".into());
+
+        let html = report.to_html();
+        assert!(html.contains("<img src=\"data:image/png;base64,BBB\""));
+        assert_eq!(html.matches("no media path").count(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+//! Numbered image sequences — `shot.1001.exr`, `shot.1002.exr`, ….
+//!
+//! A sequence is found from any one of its frames: the last run of digits
+//! in the file name is the frame number, and every file in the folder with
+//! the same prefix, suffix, and padding belongs to it. In the project model
+//! a sequence is referenced by its printf-style pattern
+//! (`shot.%04d.exr`), which [`expand_pattern`] turns back into a frame path.
+
+use std::path::{Path, PathBuf};
+
+/// Frames of one numbered image sequence on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageSequence {
+    /// Folder holding the frames.
+    pub directory: PathBuf,
+    /// File name text before the frame number.
+    pub prefix: String,
+    /// File name text after the frame number, including the extension.
+    pub suffix: String,
+    /// Digits in the frame number, zero-padded.
+    pub padding: usize,
+    /// Frame numbers present, ascending. Gaps are allowed.
+    pub frames: Vec<u64>,
+}
+
+impl ImageSequence {
+    /// Find the sequence `frame` belongs to by listing its folder.
+    ///
+    /// Returns `None` if the file name has no frame number or the folder
+    /// can't be read.
+    pub fn from_frame(frame: &Path) -> Option<Self> {
+        let name = frame.file_name()?.to_str()?;
+        let (prefix, digits, suffix) = split_frame_name(name)?;
+        let directory = frame.parent().unwrap_or(Path::new("")).to_path_buf();
+        let listing = if directory.as_os_str().is_empty() {
+            std::fs::read_dir(".")
+        } else {
+            std::fs::read_dir(&directory)
+        };
+
+        let mut frames: Vec<u64> = listing
+            .ok()?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let (p, d, s) = split_frame_name(&name)?;
+                (p == prefix && s == suffix && d.len() == digits.len())
+                    .then(|| d.parse().ok())
+                    .flatten()
+            })
+            .collect();
+        frames.sort_unstable();
+        frames.dedup();
+
+        Some(Self {
+            directory,
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+            padding: digits.len(),
+            frames,
+        })
+    }
+
+    /// Path of frame `number`, whether or not it exists.
+    pub fn frame_path(&self, number: u64) -> PathBuf {
+        self.directory.join(format!(
+            "{}{number:0width$}{}",
+            self.prefix,
+            self.suffix,
+            width = self.padding
+        ))
+    }
+
+    /// Printf-style pattern for the whole sequence, e.g. `dir/shot.%04d.exr`.
+    pub fn pattern(&self) -> String {
+        self.directory
+            .join(format!("{}%0{}d{}", self.prefix, self.padding, self.suffix))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Sequence name without frame number or separators, e.g. `shot`.
+    pub fn base_name(&self) -> &str {
+        self.prefix.trim_end_matches(['.', '_', '-', ' '])
+    }
+}
+
+/// Substitute `frame` for the `%0Nd` (or `%d`) in a sequence pattern.
+///
+/// Returns `None` if `pattern` has no frame placeholder.
+pub fn expand_pattern(pattern: &str, frame: u64) -> Option<String> {
+    let start = pattern.find('%')?;
+    let rest = &pattern[start + 1..];
+    let end = rest.find('d')?;
+    let spec = &rest[..end];
+    if !spec.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let width: usize = spec.trim_start_matches('0').parse().unwrap_or(0);
+    Some(format!(
+        "{}{frame:0width$}{}",
+        &pattern[..start],
+        &rest[end + 1..]
+    ))
+}
+
+/// Split a file name into prefix, frame digits, and suffix at its last run
+/// of digits before the extension.
+fn split_frame_name(name: &str) -> Option<(&str, &str, &str)> {
+    let stem_end = name.rfind('.').unwrap_or(name.len());
+    let stem = &name[..stem_end];
+    let digits_end = stem.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let digits_start = stem[..digits_end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    Some((
+        &name[..digits_start],
+        &name[digits_start..digits_end],
+        &name[digits_end..],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frame_name() {
+        assert_eq!(
+            split_frame_name("shot.1001.exr"),
+            Some(("shot.", "1001", ".exr"))
+        );
+        assert_eq!(
+            split_frame_name("A001C003_0042_v2.dpx"),
+            Some(("A001C003_0042_v", "2", ".dpx"))
+        );
+        assert_eq!(split_frame_name("plate.exr"), None);
+    }
+
+    #[test]
+    fn test_pattern_round_trip() {
+        let seq = ImageSequence {
+            directory: PathBuf::from("/scans"),
+            prefix: "reel1.".into(),
+            suffix: ".exr".into(),
+            padding: 4,
+            frames: vec![1001, 1002],
+        };
+        assert_eq!(seq.pattern(), "/scans/reel1.%04d.exr");
+        assert_eq!(seq.frame_path(7), PathBuf::from("/scans/reel1.0007.exr"));
+        assert_eq!(
+            expand_pattern(&seq.pattern(), 1002).as_deref(),
+            Some("/scans/reel1.1002.exr")
+        );
+        assert_eq!(expand_pattern("a.%d.png", 5).as_deref(), Some("a.5.png"));
+        assert_eq!(expand_pattern("a.png", 5), None);
+        assert_eq!(seq.base_name(), "reel1");
+    }
+
+    #[test]
+    fn test_from_frame_lists_folder() {
+        let dir = std::env::temp_dir().join(format!("crispen_sequence_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "s.0003.png",
+            "s.0001.png",
+            "s.0002.png",
+            "s.01.png",
+            "t.0001.png",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        let seq = ImageSequence::from_frame(&dir.join("s.0002.png")).unwrap();
+        assert_eq!(seq.frames, [1, 2, 3]);
+        assert_eq!(seq.padding, 4);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,336 @@
+//! Sequence export plans — which source frame renders to which file.
+//!
+//! An export names its frames with a printf-style pattern, like sequences
+//! in the project model (`sh010_graded.%04d.exr`), and covers an inclusive
+//! frame range of the source. Output frames keep their source numbers
+//! unless the export renumbers them from a given first frame. The format
+//! comes from the pattern's extension: float EXR, 10-bit DPX, or 16-bit
+//! TIFF.
+//!
+//! [`SequenceExport::plan`] resolves all of that into one job per frame
+//! before anything is rendered, so a clash with existing files is found up
+//! front. With [`OverwritePolicy::Skip`] frames already on disk are left
+//! out of the plan, which makes re-running an interrupted export resume
+//! it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::sequence::expand_pattern;
+use super::sidecars::{Sidecars, sidecar_base};
+use super::timeline::SourceRange;
+
+/// Errors from planning a sequence export.
+#[derive(Debug, thiserror::Error)]
+pub enum SequenceExportError {
+    #[error("output pattern {0} has no %d frame placeholder")]
+    NoPlaceholder(String),
+    #[error("output pattern {0} is not an EXR, DPX, or TIFF sequence")]
+    Format(String),
+    #[error("invalid frame range \"{0}\"")]
+    Range(String),
+    #[error("{} already exists", .0.display())]
+    Exists(PathBuf),
+    #[error("frame {0} is outside the export range {1}")]
+    OutsideRange(u64, FrameRange),
+}
+
+/// Inclusive range of source frame numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameRange {
+    pub first: u64,
+    pub last: u64,
+}
+
+impl FrameRange {
+    /// Range from `first` to `last`, inclusive; `None` if `last < first`.
+    pub fn new(first: u64, last: u64) -> Option<Self> {
+        (first <= last).then_some(Self { first, last })
+    }
+
+    /// Frames a clip uses of its source, `None` for an empty range.
+    pub fn from_source(range: &SourceRange) -> Option<Self> {
+        let first = range.start.max(0.0) as u64;
+        let duration = range.duration.max(0.0) as u64;
+        Self::new(first, (first + duration).checked_sub(1)?)
+    }
+
+    /// Number of frames.
+    pub fn frame_count(&self) -> u64 {
+        self.last - self.first + 1
+    }
+
+    /// Whether `frame` is in the range.
+    pub fn contains(&self, frame: u64) -> bool {
+        (self.first..=self.last).contains(&frame)
+    }
+
+    /// Frame numbers in order.
+    pub fn frames(&self) -> impl Iterator<Item = u64> {
+        self.first..=self.last
+    }
+
+    /// The part of this range inside `other`, if they overlap.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        Self::new(self.first.max(other.first), self.last.min(other.last))
+    }
+}
+
+impl FromStr for FrameRange {
+    type Err = SequenceExportError;
+
+    /// `1001-1100`, or `1001` for a single frame.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SequenceExportError::Range(s.to_string());
+        let (first, last) = s.split_once('-').unwrap_or((s, s));
+        let first = first.trim().parse().map_err(|_| invalid())?;
+        let last = last.trim().parse().map_err(|_| invalid())?;
+        Self::new(first, last).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for FrameRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.first, self.last)
+    }
+}
+
+/// What to do when an output frame already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    /// Leave existing frames alone and render the rest.
+    #[default]
+    Skip,
+    /// Render every frame, replacing existing files.
+    Overwrite,
+    /// Refuse the export if any frame exists.
+    Fail,
+}
+
+impl FromStr for OverwritePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "fail" => Ok(Self::Fail),
+            other => Err(format!("unknown overwrite policy \"{other}\"")),
+        }
+    }
+}
+
+/// File formats a sequence is exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SequenceFormat {
+    /// OpenEXR, float code values.
+    Exr,
+    /// 10-bit DPX.
+    Dpx,
+    /// 16-bit TIFF.
+    Tiff,
+}
+
+impl SequenceFormat {
+    /// Format of the files `pattern` names, from its extension.
+    pub fn from_pattern(pattern: &str) -> Option<Self> {
+        let ext = Path::new(pattern)
+            .extension()?
+            .to_str()?
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "exr" => Some(Self::Exr),
+            "dpx" => Some(Self::Dpx),
+            "tif" | "tiff" => Some(Self::Tiff),
+            _ => None,
+        }
+    }
+}
+
+/// A graded sequence to write.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceExport {
+    /// Output pattern with a `%0Nd` frame placeholder.
+    pub pattern: String,
+    /// Source frames to render.
+    pub range: FrameRange,
+    /// Number of the first output frame; `None` keeps source numbers.
+    #[serde(default)]
+    pub first_output: Option<u64>,
+    #[serde(default)]
+    pub overwrite: OverwritePolicy,
+    /// Color recipe written beside the frames.
+    #[serde(default)]
+    pub sidecars: Sidecars,
+}
+
+/// One frame to render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportFrame {
+    /// Source frame number, for reading the frame and evaluating keys.
+    pub source: u64,
+    /// File to write.
+    pub path: PathBuf,
+}
+
+/// Every frame of an export, resolved against the files on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportPlan {
+    pub format: SequenceFormat,
+    /// Frames to render, in order.
+    pub frames: Vec<ExportFrame>,
+    /// Existing files left alone under [`OverwritePolicy::Skip`].
+    pub skipped: Vec<PathBuf>,
+}
+
+impl SequenceExport {
+    /// Export of `range` to `pattern`, keeping source frame numbers and
+    /// skipping frames that exist.
+    pub fn new(pattern: impl Into<String>, range: FrameRange) -> Self {
+        Self {
+            pattern: pattern.into(),
+            range,
+            first_output: None,
+            overwrite: OverwritePolicy::Skip,
+            sidecars: Sidecars::default(),
+        }
+    }
+
+    /// Output path for source frame `source`, which must be in the
+    /// export's range.
+    pub fn output_path(&self, source: u64) -> Result<PathBuf, SequenceExportError> {
+        if !self.range.contains(source) {
+            return Err(SequenceExportError::OutsideRange(source, self.range));
+        }
+        let number = match self.first_output {
+            Some(first) => first + (source - self.range.first),
+            None => source,
+        };
+        expand_pattern(&self.pattern, number)
+            .map(PathBuf::from)
+            .ok_or_else(|| SequenceExportError::NoPlaceholder(self.pattern.clone()))
+    }
+
+    /// Path the export's sidecars are named from.
+    pub fn sidecar_base(&self) -> PathBuf {
+        sidecar_base(Path::new(&self.pattern))
+    }
+
+    /// Resolve the export into one job per frame, applying the overwrite
+    /// policy to files already on disk.
+    pub fn plan(&self) -> Result<ExportPlan, SequenceExportError> {
+        let format = SequenceFormat::from_pattern(&self.pattern)
+            .ok_or_else(|| SequenceExportError::Format(self.pattern.clone()))?;
+        let mut frames = Vec::with_capacity(self.range.frame_count() as usize);
+        let mut skipped = Vec::new();
+        for source in self.range.frames() {
+            let path = self.output_path(source)?;
+            if path.exists() {
+                match self.overwrite {
+                    OverwritePolicy::Skip => {
+                        skipped.push(path);
+                        continue;
+                    }
+                    OverwritePolicy::Fail => return Err(SequenceExportError::Exists(path)),
+                    OverwritePolicy::Overwrite => {}
+                }
+            }
+            frames.push(ExportFrame { source, path });
+        }
+        Ok(ExportPlan {
+            format,
+            frames,
+            skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_range_parse_and_source() {
+        let range: FrameRange = "1001-1003".parse().unwrap();
+        assert_eq!(range, FrameRange::new(1001, 1003).unwrap());
+        assert_eq!(range.frame_count(), 3);
+        assert_eq!(range.to_string(), "1001-1003");
+        assert_eq!("7".parse::<FrameRange>().unwrap().frame_count(), 1);
+        assert!("9-3".parse::<FrameRange>().is_err());
+        assert!("a-b".parse::<FrameRange>().is_err());
+
+        let source = SourceRange {
+            start: 1001.0,
+            duration: 48.0,
+            rate: 24.0,
+        };
+        let clip = FrameRange::from_source(&source).unwrap();
+        assert_eq!((clip.first, clip.last), (1001, 1048));
+        assert_eq!(
+            clip.intersect(&"1040-2000".parse().unwrap()),
+            FrameRange::new(1040, 1048)
+        );
+        assert_eq!(clip.intersect(&"1-10".parse().unwrap()), None);
+        assert_eq!(
+            FrameRange::from_source(&SourceRange {
+                duration: 0.0,
+                ..source
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plan_renumbers_and_applies_overwrite_policy() {
+        let dir = std::env::temp_dir().join(format!("crispen_seq_export_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pattern = dir.join("out.%04d.dpx").to_string_lossy().into_owned();
+        let mut export = SequenceExport::new(&pattern, "1001-1003".parse().unwrap());
+        export.first_output = Some(1);
+        std::fs::write(dir.join("out.0002.dpx"), b"").unwrap();
+
+        let plan = export.plan().unwrap();
+        assert_eq!(plan.format, SequenceFormat::Dpx);
+        let frames: Vec<(u64, PathBuf)> = plan
+            .frames
+            .iter()
+            .map(|f| (f.source, f.path.clone()))
+            .collect();
+        assert_eq!(
+            frames,
+            [
+                (1001, dir.join("out.0001.dpx")),
+                (1003, dir.join("out.0003.dpx"))
+            ]
+        );
+        assert_eq!(plan.skipped, [dir.join("out.0002.dpx")]);
+
+        export.overwrite = OverwritePolicy::Overwrite;
+        assert_eq!(export.plan().unwrap().frames.len(), 3);
+        export.overwrite = OverwritePolicy::Fail;
+        assert!(matches!(export.plan(), Err(SequenceExportError::Exists(_))));
+
+        assert!(matches!(
+            export.output_path(1000),
+            Err(SequenceExportError::OutsideRange(1000, _))
+        ));
+        assert!(matches!(
+            export.output_path(1004),
+            Err(SequenceExportError::OutsideRange(1004, _))
+        ));
+
+        export.pattern = dir.join("out.dpx").to_string_lossy().into_owned();
+        assert!(matches!(
+            export.plan(),
+            Err(SequenceExportError::NoPlaceholder(_))
+        ));
+        export.pattern = dir.join("out.%04d.png").to_string_lossy().into_owned();
+        assert!(matches!(export.plan(), Err(SequenceExportError::Format(_))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,185 @@
+//! Color recipe sidecars written next to exported media.
+//!
+//! An export can carry the grade that made it, so downstream departments
+//! can rebuild the look without the project: the applied LUT as `.cube`,
+//! the grade's primaries as an ASC `.cc`, the full grade as JSON, and an
+//! `md5sum`-format manifest (MD5 or XXH64) of the media and the other
+//! sidecars. Sidecars share
+//! the export's name with the frame number dropped, so
+//! `sh010_graded.%04d.exr` gets `sh010_graded.cube`, `sh010_graded.cc`,
+//! `sh010_graded.grade.json`, and `sh010_graded.md5` or `.xxh64`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::checksum::{ChecksumAlgorithm, Manifest};
+use crate::grading::cdl::AscCdl;
+use crate::transform::lut::Lut3D;
+use crate::transform::params::GradingParams;
+
+/// Errors from writing sidecars.
+#[derive(Debug, thiserror::Error)]
+pub enum SidecarError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Which sidecars to write with an export. All off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sidecars {
+    /// The applied LUT as `.cube`.
+    pub lut: bool,
+    /// The grade's primaries as an ASC `.cc`.
+    pub cdl: bool,
+    /// The full grade as `.grade.json`.
+    pub grade: bool,
+    /// A checksum manifest of the media and the sidecars above.
+    pub manifest: bool,
+    /// Algorithm of the manifest, which also sets its extension.
+    pub checksum: ChecksumAlgorithm,
+}
+
+impl Sidecars {
+    /// Every sidecar.
+    pub const ALL: Self = Self {
+        lut: true,
+        cdl: true,
+        grade: true,
+        manifest: true,
+        checksum: ChecksumAlgorithm::Md5,
+    };
+
+    /// Whether any sidecar is on.
+    pub fn any(&self) -> bool {
+        self.lut || self.cdl || self.grade || self.manifest
+    }
+}
+
+/// Path sidecars are named from for an export to `pattern`: the pattern
+/// without its extension and frame placeholder.
+pub fn sidecar_base(pattern: &Path) -> PathBuf {
+    let stem = pattern
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let name = match stem.find('%') {
+        Some(start) => stem[..start].trim_end_matches(['.', '_', '-']),
+        None => stem,
+    };
+    pattern.with_file_name(if name.is_empty() { "export" } else { name })
+}
+
+/// Write the sidecars `which` selects beside `base`, for media graded by
+/// `grade` through `lut`. The manifest covers `media` and the sidecars
+/// written before it. Returns the files written.
+pub fn write_sidecars(
+    base: &Path,
+    which: &Sidecars,
+    grade: &GradingParams,
+    lut: &Lut3D,
+    media: &[PathBuf],
+) -> Result<Vec<PathBuf>, SidecarError> {
+    let with_ext = |ext: &str| {
+        let mut name = base.as_os_str().to_owned();
+        name.push(ext);
+        PathBuf::from(name)
+    };
+    let id = base
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut written = Vec::new();
+    if which.lut {
+        let path = with_ext(".cube");
+        lut.save_cube(&path)?;
+        written.push(path);
+    }
+    if which.cdl {
+        let path = with_ext(".cc");
+        std::fs::write(&path, AscCdl::from_params(grade).to_cc(&id))?;
+        written.push(path);
+    }
+    if which.grade {
+        let path = with_ext(".grade.json");
+        std::fs::write(&path, serde_json::to_string_pretty(grade)?)?;
+        written.push(path);
+    }
+    if which.manifest {
+        let path = with_ext(&format!(".{}", which.checksum.extension()));
+        let files: Vec<PathBuf> = media.iter().chain(&written).cloned().collect();
+        let dir = base.parent().unwrap_or(Path::new(""));
+        Manifest::from_files(dir, &files, which.checksum)?.save(&path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_base_drops_frame_placeholder() {
+        let base = |p: &str| sidecar_base(Path::new(p));
+        assert_eq!(
+            base("/out/sh010_graded.%04d.exr"),
+            Path::new("/out/sh010_graded")
+        );
+        assert_eq!(base("/out/sh010_%06d.dpx"), Path::new("/out/sh010"));
+        assert_eq!(base("/out/still.tif"), Path::new("/out/still"));
+        assert_eq!(base("/out/%04d.exr"), Path::new("/out/export"));
+    }
+
+    #[test]
+    fn test_write_sidecars_covers_media_and_recipe() {
+        let dir = std::env::temp_dir().join(format!("crispen_sidecars_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let frame = dir.join("sh010.1001.exr");
+        std::fs::write(&frame, b"pixels").unwrap();
+        let grade = GradingParams {
+            saturation: 0.8,
+            ..GradingParams::default()
+        };
+        let mut lut = Lut3D::new(2);
+        lut.bake(&grade);
+
+        let base = sidecar_base(&dir.join("sh010.%04d.exr"));
+        let written = write_sidecars(&base, &Sidecars::ALL, &grade, &lut, &[frame]).unwrap();
+        let names: Vec<_> = written
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            ["sh010.cube", "sh010.cc", "sh010.grade.json", "sh010.md5"]
+        );
+
+        assert_eq!(Lut3D::load_cube(&written[0]).unwrap().size, 2);
+        let cc = std::fs::read_to_string(&written[1]).unwrap();
+        assert!(cc.contains("<ColorCorrection id=\"sh010\">"));
+        assert!(cc.contains("<Saturation>0.800000</Saturation>"));
+        let json: GradingParams =
+            serde_json::from_str(&std::fs::read_to_string(&written[2]).unwrap()).unwrap();
+        assert_eq!(json, grade);
+        let manifest = std::fs::read_to_string(&written[3]).unwrap();
+        let listed: Vec<&str> = manifest
+            .lines()
+            .map(|l| l.split_once("  ").unwrap().1)
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                "sh010.1001.exr",
+                "sh010.cube",
+                "sh010.cc",
+                "sh010.grade.json"
+            ]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,153 @@
+//! Clip list model shared by the editorial importers and exporters.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::keyframes::KeyframeTrack;
+use super::profiles::ProfileLibrary;
+use crate::framing::Blanking;
+use crate::grading::pixel_repair::DefectMap;
+use crate::transform::params::GradingParams;
+
+/// A frame-accurate range in a clip's source media.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SourceRange {
+    /// First frame (inclusive), in `rate` units.
+    pub start: f64,
+    /// Number of frames.
+    pub duration: f64,
+    /// Frames per second.
+    pub rate: f64,
+}
+
+/// One event in a conformed timeline.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Clip {
+    /// Event / clip name as given by editorial.
+    pub name: String,
+    /// Path or URL of the source media, if known.
+    pub media_path: Option<String>,
+    /// Source reel / tape name.
+    #[serde(default)]
+    pub reel: Option<String>,
+    /// Camera identifier (e.g. `A`, or a camera serial).
+    #[serde(default)]
+    pub camera: Option<String>,
+    /// Used range of the source media.
+    pub source_range: Option<SourceRange>,
+    /// Per-clip grade, if one has been assigned.
+    pub grade: Option<GradingParams>,
+    /// Input LUT referenced by editorial metadata (a filename until
+    /// [`conform_luts`](super::conform::conform_luts) resolves it to a path).
+    #[serde(default)]
+    pub input_lut: Option<String>,
+    /// Name of the [`GradeGroup`] whose pre/post-clip grades wrap this clip.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Output matte for this clip, overriding [`Timeline::blanking`];
+    /// `None` uses the timeline's.
+    #[serde(default)]
+    pub blanking: Option<Blanking>,
+    /// Master gain keyed over source frames, multiplied into the grade's
+    /// gain wheel (e.g. from [`deflicker`](super::deflicker)). See
+    /// [`Clip::grade_at`].
+    #[serde(default)]
+    pub gain_keys: Option<KeyframeTrack>,
+    /// Dead pixels and dust repaired before the grade, overriding the
+    /// camera's map in [`Timeline::defect_maps`].
+    #[serde(default)]
+    pub defect_map: Option<DefectMap>,
+    /// Name of the [`CameraProfile`](super::profiles::CameraProfile) in
+    /// [`Timeline::profiles`] applied at the head of this clip's grade;
+    /// `None` uses the first profile matching its metadata.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Free-text notes from editorial or the colorist, shown in session
+    /// reports.
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Grades shared by every clip in a group, applied around each clip's own.
+///
+/// The pre-clip grade runs before the clip grade (e.g. a camera or show
+/// normalization), the post-clip grade after it (e.g. the show look or an
+/// ODT trim). Disabling the group bypasses both for all its clips.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradeGroup {
+    /// Group name, referenced by [`Clip::group`].
+    pub name: String,
+    /// Grade applied before each clip's grade.
+    #[serde(default)]
+    pub pre_clip: Option<GradingParams>,
+    /// Grade applied after each clip's grade.
+    #[serde(default)]
+    pub post_clip: Option<GradingParams>,
+    /// Whether the group grades are applied.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl GradeGroup {
+    /// An enabled group with no grades yet.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            pre_clip: None,
+            post_clip: None,
+            enabled: true,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// An ordered list of clips on a single video track.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Timeline {
+    /// Sequence name.
+    pub name: String,
+    /// Clips in record order.
+    pub clips: Vec<Clip>,
+    /// Grade groups, referenced by name from [`Clip::group`].
+    #[serde(default)]
+    pub groups: Vec<GradeGroup>,
+    /// Output matte baked into every export, unless a clip overrides it.
+    #[serde(default)]
+    pub blanking: Blanking,
+    /// Dead-pixel maps by [`Clip::camera`], used by every clip from that
+    /// camera that has no map of its own.
+    #[serde(default)]
+    pub defect_maps: BTreeMap<String, DefectMap>,
+    /// Camera profiles available to this timeline's clips.
+    #[serde(default)]
+    pub profiles: ProfileLibrary,
+}
+
+impl Timeline {
+    /// Look up a group by name.
+    pub fn group(&self, name: &str) -> Option<&GradeGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// The output matte in effect for `clip`: its own override, or the
+    /// timeline's.
+    pub fn blanking_for(&self, clip: &Clip) -> Blanking {
+        clip.blanking.unwrap_or(self.blanking)
+    }
+
+    /// The defect map in effect for `clip`: its own, or its camera's.
+    pub fn defect_map_for<'a>(&'a self, clip: &'a Clip) -> Option<&'a DefectMap> {
+        clip.defect_map
+            .as_ref()
+            .or_else(|| self.defect_maps.get(clip.camera.as_deref()?))
+    }
+
+    /// Mutable group lookup, e.g. to edit or toggle a show-wide look.
+    pub fn group_mut(&mut self, name: &str) -> Option<&mut GradeGroup> {
+        self.groups.iter_mut().find(|g| g.name == name)
+    }
+}
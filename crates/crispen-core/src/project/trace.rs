@@ -0,0 +1,272 @@
+//! Color trace — ripple a clip's grade to clips that share its source.
+//!
+//! Clips cut from the same reel or shot on the same camera usually want the
+//! same base grade. [`preview_ripple`] finds the matching clips and diffs
+//! their current grade against the source grade field by field, so the UI
+//! can show what would change before [`apply_ripple`] copies it.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::timeline::{Clip, Timeline};
+use crate::transform::params::GradingParams;
+
+/// Which clip metadata must match for a grade to ripple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKey {
+    /// Same source reel / tape.
+    #[default]
+    Reel,
+    /// Same camera.
+    Camera,
+    /// Same reel and same camera.
+    ReelAndCamera,
+}
+
+impl MatchKey {
+    /// Whether `a` and `b` match. Clips missing the metadata never match.
+    pub fn matches(self, a: &Clip, b: &Clip) -> bool {
+        let same = |x: &Option<String>, y: &Option<String>| x.is_some() && x == y;
+        match self {
+            Self::Reel => same(&a.reel, &b.reel),
+            Self::Camera => same(&a.camera, &b.camera),
+            Self::ReelAndCamera => same(&a.reel, &b.reel) && same(&a.camera, &b.camera),
+        }
+    }
+}
+
+/// One `GradingParams` field that differs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Field name as serialized (e.g. `gain`, `color_management`).
+    pub field: String,
+    /// Current value on the target clip.
+    pub before: Value,
+    /// Value that would be copied from the source.
+    pub after: Value,
+}
+
+/// Pending grade change for one matching clip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RippleChange {
+    /// Index of the clip in the timeline.
+    pub clip_index: usize,
+    /// Clip name.
+    pub clip_name: String,
+    /// Whether the clip had no grade before.
+    pub was_ungraded: bool,
+    /// Fields that would change.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Preview of a ripple from one source clip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RipplePreview {
+    /// Index of the source clip.
+    pub source: usize,
+    /// Grade that would be copied.
+    pub grade: GradingParams,
+    /// Matching clips whose grade would change, in timeline order.
+    pub targets: Vec<RippleChange>,
+}
+
+impl fmt::Display for RipplePreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Ripple from clip {}: {} clip(s)",
+            self.source + 1,
+            self.targets.len()
+        )?;
+        for target in &self.targets {
+            let fields: Vec<&str> = target.changes.iter().map(|c| c.field.as_str()).collect();
+            let note = if target.was_ungraded {
+                " (ungraded)"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "{:>4}  {}{note}: {}",
+                target.clip_index + 1,
+                target.clip_name,
+                fields.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Indices of the clips that match `source` under `key`, excluding it.
+pub fn matching_clips(timeline: &Timeline, source: usize, key: MatchKey) -> Vec<usize> {
+    let Some(src) = timeline.clips.get(source) else {
+        return Vec::new();
+    };
+    timeline
+        .clips
+        .iter()
+        .enumerate()
+        .filter(|&(i, clip)| i != source && key.matches(src, clip))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Preview copying clip `source`'s grade to its matching clips.
+///
+/// Clips that already carry an identical grade are left out. With
+/// `overwrite` off, clips that already have any grade are left out too.
+/// Returns `None` if `source` is out of range or ungraded.
+pub fn preview_ripple(
+    timeline: &Timeline,
+    source: usize,
+    key: MatchKey,
+    overwrite: bool,
+) -> Option<RipplePreview> {
+    let grade = timeline.clips.get(source)?.grade.clone()?;
+
+    let targets = matching_clips(timeline, source, key)
+        .into_iter()
+        .filter_map(|i| {
+            let clip = &timeline.clips[i];
+            if clip.grade.is_some() && !overwrite {
+                return None;
+            }
+            let before = clip.grade.clone().unwrap_or_default();
+            let changes = diff_grades(&before, &grade);
+            if changes.is_empty() && clip.grade.is_some() {
+                return None;
+            }
+            Some(RippleChange {
+                clip_index: i,
+                clip_name: clip.name.clone(),
+                was_ungraded: clip.grade.is_none(),
+                changes,
+            })
+        })
+        .collect();
+
+    Some(RipplePreview {
+        source,
+        grade,
+        targets,
+    })
+}
+
+/// Copy the previewed grade to every target clip. Returns the clip count.
+pub fn apply_ripple(timeline: &mut Timeline, preview: &RipplePreview) -> usize {
+    let mut applied = 0;
+    for target in &preview.targets {
+        if let Some(clip) = timeline.clips.get_mut(target.clip_index) {
+            clip.grade = Some(preview.grade.clone());
+            applied += 1;
+        }
+    }
+    applied
+}
+
+/// Top-level `GradingParams` fields that differ between two grades.
+pub fn diff_grades(before: &GradingParams, after: &GradingParams) -> Vec<FieldChange> {
+    let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    after
+        .into_iter()
+        .filter_map(|(field, after)| {
+            let before = before.get(&field).cloned().unwrap_or(Value::Null);
+            (before != after).then_some(FieldChange {
+                field,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(name: &str, reel: &str, camera: &str) -> Clip {
+        Clip {
+            name: name.into(),
+            reel: Some(reel.into()),
+            camera: Some(camera.into()),
+            ..Clip::default()
+        }
+    }
+
+    fn timeline() -> Timeline {
+        let mut source = clip("a1", "A001", "A");
+        source.grade = Some(GradingParams {
+            saturation: 1.3,
+            gain: [1.1, 1.0, 0.9, 1.0],
+            ..GradingParams::default()
+        });
+        let mut graded = clip("a3", "A001", "A");
+        graded.grade = Some(GradingParams {
+            saturation: 0.5,
+            ..GradingParams::default()
+        });
+        Timeline {
+            name: "t".into(),
+            clips: vec![
+                source,
+                clip("a2", "A001", "A"),
+                clip("b1", "B001", "A"),
+                graded,
+                Clip::default(),
+            ],
+            ..Timeline::default()
+        }
+    }
+
+    #[test]
+    fn test_matching_by_key() {
+        let t = timeline();
+        assert_eq!(matching_clips(&t, 0, MatchKey::Reel), [1, 3]);
+        assert_eq!(matching_clips(&t, 0, MatchKey::Camera), [1, 2, 3]);
+        assert_eq!(matching_clips(&t, 4, MatchKey::Reel), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_preview_lists_changed_fields_and_respects_overwrite() {
+        let t = timeline();
+        let preview = preview_ripple(&t, 0, MatchKey::Reel, false).unwrap();
+        assert_eq!(preview.targets.len(), 1);
+        let target = &preview.targets[0];
+        assert_eq!(target.clip_index, 1);
+        assert!(target.was_ungraded);
+        let fields: Vec<_> = target.changes.iter().map(|c| c.field.as_str()).collect();
+        assert!(fields.contains(&"gain") && fields.contains(&"saturation"));
+        assert_eq!(fields.len(), 2);
+
+        let preview = preview_ripple(&t, 0, MatchKey::Reel, true).unwrap();
+        assert_eq!(preview.targets.len(), 2);
+        assert!(preview.to_string().contains("a3: "));
+    }
+
+    #[test]
+    fn test_apply_copies_grade() {
+        let mut t = timeline();
+        let preview = preview_ripple(&t, 0, MatchKey::Reel, true).unwrap();
+        assert_eq!(apply_ripple(&mut t, &preview), 2);
+        assert_eq!(t.clips[1].grade, t.clips[0].grade);
+        assert_eq!(t.clips[3].grade, t.clips[0].grade);
+        assert!(t.clips[2].grade.is_none());
+        assert!(
+            preview_ripple(&t, 0, MatchKey::Reel, true)
+                .unwrap()
+                .targets
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_ungraded_source_has_no_preview() {
+        assert!(preview_ripple(&timeline(), 1, MatchKey::Reel, true).is_none());
+    }
+}
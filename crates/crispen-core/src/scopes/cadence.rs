@@ -0,0 +1,146 @@
+//! Scope update rate relative to the viewer.
+//!
+//! Scopes cost compute and readback bandwidth on every viewer update, yet
+//! a scope that trails a drag by a frame or two is hard to notice. A
+//! [`ScopeCadence`] lets scopes skip viewer updates while the grade keeps
+//! changing; a [`ScopeScheduler`] tracks the skipped updates and asks for
+//! one refresh once the grade settles, so the scopes always end up showing
+//! the final grade.
+
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How often scopes are recomputed while the grade changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScopeCadence {
+    /// With every viewer update.
+    #[default]
+    EveryUpdate,
+    /// With every Nth viewer update.
+    EveryNth(u32),
+    /// Only once the grade has been unchanged for this many milliseconds.
+    OnSettle(u32),
+}
+
+impl ScopeCadence {
+    /// Longest accepted `EveryNth` interval.
+    pub const MAX_INTERVAL: u32 = 8;
+    /// Longest accepted `OnSettle` delay in milliseconds.
+    pub const MAX_SETTLE_MS: u32 = 2000;
+    /// Quiet time before `EveryNth` catches up on skipped updates.
+    const NTH_SETTLE: Duration = Duration::from_millis(100);
+
+    /// Whether the interval or delay is within the accepted range.
+    pub fn is_valid(self) -> bool {
+        match self {
+            Self::EveryUpdate => true,
+            Self::EveryNth(n) => (1..=Self::MAX_INTERVAL).contains(&n),
+            Self::OnSettle(ms) => ms <= Self::MAX_SETTLE_MS,
+        }
+    }
+
+    /// Time without updates after which stale scopes are refreshed.
+    fn settle_delay(self) -> Duration {
+        match self {
+            Self::EveryUpdate => Duration::ZERO,
+            Self::EveryNth(_) => Self::NTH_SETTLE,
+            Self::OnSettle(ms) => Duration::from_millis(u64::from(ms)),
+        }
+    }
+}
+
+/// Decides which viewer updates also compute scopes.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeScheduler {
+    skipped: u32,
+    last_update: Option<Instant>,
+}
+
+impl ScopeScheduler {
+    /// Whether the next viewer update should compute scopes.
+    pub fn wants_scopes(&self, cadence: ScopeCadence) -> bool {
+        match cadence {
+            ScopeCadence::EveryUpdate => true,
+            ScopeCadence::EveryNth(n) => self.skipped + 1 >= n.max(1),
+            ScopeCadence::OnSettle(_) => false,
+        }
+    }
+
+    /// Record a submitted viewer update.
+    pub fn record_update(&mut self, with_scopes: bool, now: Instant) {
+        self.skipped = if with_scopes { 0 } else { self.skipped + 1 };
+        self.last_update = Some(now);
+    }
+
+    /// Whether the scopes lag the viewer and the grade has been unchanged
+    /// long enough to refresh them.
+    pub fn refresh_due(&self, cadence: ScopeCadence, now: Instant) -> bool {
+        self.skipped > 0
+            && self
+                .last_update
+                .is_some_and(|last| now.duration_since(last) >= cadence.settle_delay())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drag(scheduler: &mut ScopeScheduler, cadence: ScopeCadence, updates: u32) -> Vec<bool> {
+        let now = Instant::now();
+        (0..updates)
+            .map(|_| {
+                let scopes = scheduler.wants_scopes(cadence);
+                scheduler.record_update(scopes, now);
+                scopes
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_update_never_goes_stale() {
+        let mut scheduler = ScopeScheduler::default();
+        let cadence = ScopeCadence::EveryUpdate;
+        assert!(drag(&mut scheduler, cadence, 5).iter().all(|&s| s));
+        assert!(!scheduler.refresh_due(cadence, Instant::now()));
+    }
+
+    #[test]
+    fn test_every_nth_skips_and_refreshes_after_settle() {
+        let mut scheduler = ScopeScheduler::default();
+        let cadence = ScopeCadence::EveryNth(3);
+        assert_eq!(
+            drag(&mut scheduler, cadence, 7),
+            [false, false, true, false, false, true, false]
+        );
+
+        let last = scheduler.last_update.unwrap();
+        assert!(!scheduler.refresh_due(cadence, last));
+        assert!(scheduler.refresh_due(cadence, last + Duration::from_millis(150)));
+
+        scheduler.record_update(true, last + Duration::from_millis(150));
+        assert!(!scheduler.refresh_due(cadence, last + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_on_settle_waits_for_delay() {
+        let mut scheduler = ScopeScheduler::default();
+        let cadence = ScopeCadence::OnSettle(250);
+        assert!(drag(&mut scheduler, cadence, 4).iter().all(|&s| !s));
+
+        let last = scheduler.last_update.unwrap();
+        assert!(!scheduler.refresh_due(cadence, last + Duration::from_millis(200)));
+        assert!(scheduler.refresh_due(cadence, last + Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(ScopeCadence::EveryNth(2).is_valid());
+        assert!(!ScopeCadence::EveryNth(0).is_valid());
+        assert!(!ScopeCadence::EveryNth(ScopeCadence::MAX_INTERVAL + 1).is_valid());
+        assert!(!ScopeCadence::OnSettle(ScopeCadence::MAX_SETTLE_MS + 1).is_valid());
+    }
+}
@@ -2,10 +2,15 @@
 //!
 //! Projects each pixel's color onto the CIE 1931 xy chromaticity diagram
 //! by converting linear RGB to XYZ and then normalizing to xy coordinates.
+//!
+//! Display options (which gamut triangles to overlay, xy vs. u'v'
+//! projection) live here too so every renderer draws the same diagram.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::image::GradingImage;
+use crate::transform::params::{ColorManagementConfig, ColorSpaceId};
 
 /// Default CIE diagram grid resolution.
 const DEFAULT_RESOLUTION: u32 = 256;
@@ -18,7 +23,8 @@ const SRGB_TO_XYZ: [[f32; 3]; 3] = [
 ];
 
 /// CIE chromaticity diagram data — plots pixel colors on a CIE xy diagram.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CieData {
     /// Resolution of the square CIE grid.
     pub resolution: u32,
@@ -75,6 +81,106 @@ pub fn compute(image: &GradingImage) -> CieData {
     }
 }
 
+/// Chromaticity diagram projection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CieProjection {
+    /// CIE 1931 xy.
+    #[default]
+    Xy,
+    /// CIE 1976 u'v' (more perceptually uniform).
+    UvPrime,
+}
+
+impl CieProjection {
+    /// Project an xy chromaticity into this diagram's coordinates.
+    pub fn project(self, x: f32, y: f32) -> [f32; 2] {
+        match self {
+            Self::Xy => [x, y],
+            Self::UvPrime => xy_to_uv_prime(x, y),
+        }
+    }
+
+    /// Upper bounds of the plotted `[horizontal, vertical]` range (both
+    /// axes start at 0).
+    pub fn extent(self) -> [f32; 2] {
+        match self {
+            Self::Xy => [0.8, 0.9],
+            Self::UvPrime => [0.65, 0.65],
+        }
+    }
+}
+
+/// Convert CIE 1931 xy to CIE 1976 u'v'.
+pub fn xy_to_uv_prime(x: f32, y: f32) -> [f32; 2] {
+    let d = -2.0 * x + 12.0 * y + 3.0;
+    if d.abs() < 1e-10 {
+        return [0.0, 0.0];
+    }
+    [4.0 * x / d, 9.0 * y / d]
+}
+
+/// A gamut triangle drawn over the diagram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamutOverlay {
+    /// The grade's output color space.
+    Output,
+    /// The grade's input color space (the camera's native gamut for log
+    /// footage).
+    Input,
+    /// A fixed color space.
+    Space(ColorSpaceId),
+}
+
+impl GamutOverlay {
+    /// Common delivery and working gamuts offered as presets.
+    pub const PRESETS: [Self; 5] = [
+        Self::Space(ColorSpaceId::Srgb),
+        Self::Space(ColorSpaceId::DciP3),
+        Self::Space(ColorSpaceId::Rec2020),
+        Self::Space(ColorSpaceId::AcesCg),
+        Self::Space(ColorSpaceId::Aces2065_1),
+    ];
+
+    /// The color space this overlay shows for the current configuration.
+    pub fn space(self, config: &ColorManagementConfig) -> ColorSpaceId {
+        match self {
+            Self::Output => config.output_space,
+            Self::Input => config.input_space,
+            Self::Space(space) => space,
+        }
+    }
+
+    /// Legend text.
+    pub fn label(self, config: &ColorManagementConfig) -> String {
+        match self {
+            Self::Output => format!("Output: {}", config.output_space.label()),
+            Self::Input => format!("Camera: {}", config.input_space.label()),
+            Self::Space(space) => space.label().to_string(),
+        }
+    }
+}
+
+/// How the scopes are drawn (independent of what they measure).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScopeDisplayOptions {
+    /// Gamut triangles overlaid on the CIE diagram, in legend order.
+    pub cie_gamuts: Vec<GamutOverlay>,
+    /// CIE diagram projection.
+    pub cie_projection: CieProjection,
+}
+
+impl Default for ScopeDisplayOptions {
+    fn default() -> Self {
+        Self {
+            cie_gamuts: vec![GamutOverlay::Output],
+            cie_projection: CieProjection::Xy,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +226,22 @@ mod tests {
         let total: u32 = cie.density.iter().sum();
         assert_eq!(total, 0, "black pixels have no chromaticity");
     }
+
+    #[test]
+    fn test_uv_prime_of_d65() {
+        let [u, v] = xy_to_uv_prime(0.3127, 0.3290);
+        assert!((u - 0.1978).abs() < 1e-3, "{u}");
+        assert!((v - 0.4683).abs() < 1e-3, "{v}");
+    }
+
+    #[test]
+    fn test_gamut_overlay_resolves_against_config() {
+        let config = ColorManagementConfig {
+            input_space: ColorSpaceId::ArriLogC4,
+            ..ColorManagementConfig::default()
+        };
+        assert_eq!(GamutOverlay::Input.space(&config), ColorSpaceId::ArriLogC4);
+        assert_eq!(GamutOverlay::Output.space(&config), config.output_space);
+        assert!(GamutOverlay::Input.label(&config).starts_with("Camera: "));
+    }
 }
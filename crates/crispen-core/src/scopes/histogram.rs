@@ -1,5 +1,6 @@
 //! RGB + luminance histogram computation.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::image::GradingImage;
@@ -13,7 +14,8 @@ const LUMA_G: f32 = 0.7152;
 const LUMA_B: f32 = 0.0722;
 
 /// Histogram data for R, G, B, and luminance channels (256 bins each).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HistogramData {
     /// Bin counts for `[R, G, B, Luma]` channels. Each `Vec` has 256 entries.
     pub bins: [Vec<u32>; 4],
@@ -1,13 +1,19 @@
 //! Scope computation — histogram, waveform, vectorscope, parade, and CIE diagram.
 
+pub mod cadence;
 pub mod cie;
 pub mod histogram;
 pub mod parade;
+pub mod quality;
+pub mod temporal;
 pub mod vectorscope;
 pub mod waveform;
 
-pub use cie::CieData;
+pub use cadence::{ScopeCadence, ScopeScheduler};
+pub use cie::{CieData, CieProjection, GamutOverlay, ScopeDisplayOptions};
 pub use histogram::HistogramData;
 pub use parade::ParadeData;
+pub use quality::{ScopeQuality, ScopeQualityGovernor, ScopeResolution};
+pub use temporal::{ScopeAccumulation, ScopeHistory};
 pub use vectorscope::VectorscopeData;
 pub use waveform::WaveformData;
@@ -3,6 +3,7 @@
 //! Generates separate waveforms for R, G, B channels displayed side by side.
 //! Each channel's waveform shows intensity distribution vs. horizontal position.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::image::GradingImage;
@@ -11,7 +12,8 @@ use crate::image::GradingImage;
 const DEFAULT_HEIGHT: u32 = 256;
 
 /// Parade scope data — separate waveforms for R, G, B channels side by side.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParadeData {
     /// Width of each channel's waveform display.
     pub width: u32,
@@ -0,0 +1,237 @@
+//! Scope resolution settings and automatic quality reduction.
+//!
+//! Scope cost scales with the vectorscope / CIE grid size and the waveform
+//! height. [`ScopeQuality`] holds the user's chosen resolutions; when
+//! `auto_reduce` is on, a [`ScopeQualityGovernor`] watches frame times and
+//! halves the resolutions while frames run over budget, restoring them once
+//! there is headroom again.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::cadence::ScopeCadence;
+
+/// Per-scope buffer dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScopeResolution {
+    /// Waveform / parade rows (value bins).
+    pub waveform_height: u32,
+    /// Vectorscope grid side length.
+    pub vectorscope: u32,
+    /// CIE diagram grid side length.
+    pub cie: u32,
+}
+
+impl ScopeResolution {
+    /// Smallest allowed value for any dimension.
+    pub const MIN: u32 = 64;
+    /// Largest allowed value for any dimension.
+    pub const MAX: u32 = 1024;
+
+    /// Clamp every dimension to `[MIN, MAX]`.
+    pub fn clamped(self) -> Self {
+        let clamp = |v: u32| v.clamp(Self::MIN, Self::MAX);
+        Self {
+            waveform_height: clamp(self.waveform_height),
+            vectorscope: clamp(self.vectorscope),
+            cie: clamp(self.cie),
+        }
+    }
+
+    /// Halve every dimension `level` times, never going below `MIN`.
+    pub fn reduced(self, level: u32) -> Self {
+        let reduce = |v: u32| (v >> level.min(31)).max(Self::MIN.min(v));
+        Self {
+            waveform_height: reduce(self.waveform_height),
+            vectorscope: reduce(self.vectorscope),
+            cie: reduce(self.cie),
+        }
+    }
+}
+
+impl Default for ScopeResolution {
+    fn default() -> Self {
+        Self {
+            waveform_height: 256,
+            vectorscope: 512,
+            cie: 512,
+        }
+    }
+}
+
+/// User-facing scope quality settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScopeQuality {
+    /// Requested resolutions.
+    pub resolution: ScopeResolution,
+    /// Lower resolutions automatically when frames exceed the budget.
+    pub auto_reduce: bool,
+    /// Frame-time budget in milliseconds.
+    pub frame_budget_ms: f32,
+    /// Accumulate only every Nth pixel; `None` picks a stride from the
+    /// image size.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sample_stride: Option<u32>,
+    /// How often scopes follow viewer updates while the grade changes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cadence: ScopeCadence,
+}
+
+impl Default for ScopeQuality {
+    fn default() -> Self {
+        Self {
+            resolution: ScopeResolution::default(),
+            auto_reduce: true,
+            frame_budget_ms: 33.3,
+            sample_stride: None,
+            cadence: ScopeCadence::default(),
+        }
+    }
+}
+
+/// Tracks frame times and picks a reduction level for [`ScopeQuality`].
+///
+/// Decisions use an exponential moving average so a single slow frame
+/// (e.g. a source load) does not drop quality, and require several frames
+/// between steps so the level does not oscillate.
+#[derive(Debug, Clone)]
+pub struct ScopeQualityGovernor {
+    level: u32,
+    average_ms: Option<f32>,
+    frames_since_change: u32,
+}
+
+impl ScopeQualityGovernor {
+    /// Deepest reduction (1/4 of the requested resolution).
+    pub const MAX_LEVEL: u32 = 2;
+    /// Frames to wait after a change before stepping again.
+    const SETTLE_FRAMES: u32 = 30;
+    /// Step back up only when the average is below this share of the budget.
+    const RECOVER_RATIO: f32 = 0.6;
+    /// Weight of the newest frame in the moving average.
+    const SMOOTHING: f32 = 0.1;
+
+    /// Current reduction level (0 = full resolution).
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Smoothed frame time in milliseconds, once any frame was recorded.
+    pub fn average_ms(&self) -> Option<f32> {
+        self.average_ms
+    }
+
+    /// Resolutions to allocate for `quality` at the current level.
+    pub fn effective(&self, quality: &ScopeQuality) -> ScopeResolution {
+        let resolution = quality.resolution.clamped();
+        if quality.auto_reduce {
+            resolution.reduced(self.level)
+        } else {
+            resolution
+        }
+    }
+
+    /// Record one frame time. Returns `true` if the level changed.
+    pub fn record_frame(&mut self, frame_ms: f32, quality: &ScopeQuality) -> bool {
+        let average = match self.average_ms {
+            Some(avg) => avg + (frame_ms - avg) * Self::SMOOTHING,
+            None => frame_ms,
+        };
+        self.average_ms = Some(average);
+
+        if !quality.auto_reduce {
+            return self.set_level(0);
+        }
+        self.frames_since_change = self.frames_since_change.saturating_add(1);
+        if self.frames_since_change < Self::SETTLE_FRAMES {
+            return false;
+        }
+
+        let budget = quality.frame_budget_ms.max(1.0);
+        if average > budget && self.level < Self::MAX_LEVEL {
+            self.set_level(self.level + 1)
+        } else if average < budget * Self::RECOVER_RATIO && self.level > 0 {
+            self.set_level(self.level - 1)
+        } else {
+            false
+        }
+    }
+
+    fn set_level(&mut self, level: u32) -> bool {
+        if self.level == level {
+            return false;
+        }
+        self.level = level;
+        self.frames_since_change = 0;
+        true
+    }
+}
+
+impl Default for ScopeQualityGovernor {
+    fn default() -> Self {
+        Self {
+            level: 0,
+            average_ms: None,
+            frames_since_change: Self::SETTLE_FRAMES,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(governor: &mut ScopeQualityGovernor, quality: &ScopeQuality, ms: f32, frames: u32) {
+        for _ in 0..frames {
+            governor.record_frame(ms, quality);
+        }
+    }
+
+    #[test]
+    fn test_reduced_halves_down_to_min() {
+        let res = ScopeResolution::default();
+        assert_eq!(res.reduced(0), res);
+        let quarter = res.reduced(2);
+        assert_eq!(quarter.waveform_height, 64);
+        assert_eq!(quarter.vectorscope, 128);
+        assert_eq!(res.reduced(10).cie, ScopeResolution::MIN);
+    }
+
+    #[test]
+    fn test_governor_steps_down_over_budget_and_recovers() {
+        let quality = ScopeQuality::default();
+        let mut governor = ScopeQualityGovernor::default();
+        run(&mut governor, &quality, 10.0, 100);
+        assert_eq!(governor.level(), 0);
+
+        run(&mut governor, &quality, 60.0, 200);
+        assert_eq!(governor.level(), ScopeQualityGovernor::MAX_LEVEL);
+        assert_eq!(governor.effective(&quality).vectorscope, 128);
+
+        run(&mut governor, &quality, 5.0, 200);
+        assert_eq!(governor.level(), 0);
+    }
+
+    #[test]
+    fn test_single_spike_does_not_reduce() {
+        let quality = ScopeQuality::default();
+        let mut governor = ScopeQualityGovernor::default();
+        run(&mut governor, &quality, 10.0, 50);
+        assert!(!governor.record_frame(200.0, &quality));
+        assert_eq!(governor.level(), 0);
+    }
+
+    #[test]
+    fn test_auto_reduce_off_uses_requested_resolution() {
+        let quality = ScopeQuality {
+            auto_reduce: false,
+            ..ScopeQuality::default()
+        };
+        let mut governor = ScopeQualityGovernor::default();
+        run(&mut governor, &quality, 100.0, 200);
+        assert_eq!(governor.level(), 0);
+        assert_eq!(governor.effective(&quality), quality.resolution);
+    }
+}
@@ -0,0 +1,291 @@
+//! Temporal scope accumulation — frame averaging and peak hold.
+//!
+//! Scope readbacks arrive once per processed frame. During playback a
+//! single frame hides flicker and transient spikes, so the displayed scope
+//! can instead be the average of the last N frames, or a peak hold that
+//! keeps each bin's maximum and lets it decay.
+//!
+//! Accumulation runs on the read-back `u32` buffers, so the same code
+//! serves CPU and GPU scopes.
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CieData, HistogramData, VectorscopeData, WaveformData};
+
+/// How successive scope frames are combined for display.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum ScopeAccumulation {
+    /// Show only the latest frame.
+    #[default]
+    Instant,
+    /// Mean of the last `frames` frames.
+    Average { frames: u32 },
+    /// Per-bin maximum; held values lose `decay` (0–1) of their height per
+    /// frame. `decay = 0` holds forever.
+    PeakHold { decay: f32 },
+}
+
+/// Scope data whose bins can be accumulated.
+pub trait ScopeBins: Clone {
+    /// Every bin buffer, in a fixed order.
+    fn bins(&self) -> Vec<&[u32]>;
+    /// Mutable access to the same buffers.
+    fn bins_mut(&mut self) -> Vec<&mut [u32]>;
+    /// Refresh derived fields (e.g. a normalization peak) after bins change.
+    fn refresh(&mut self) {}
+}
+
+impl ScopeBins for HistogramData {
+    fn bins(&self) -> Vec<&[u32]> {
+        self.bins.iter().map(Vec::as_slice).collect()
+    }
+    fn bins_mut(&mut self) -> Vec<&mut [u32]> {
+        self.bins.iter_mut().map(Vec::as_mut_slice).collect()
+    }
+    fn refresh(&mut self) {
+        self.peak = self.bins.iter().flatten().copied().max().unwrap_or(0);
+    }
+}
+
+impl ScopeBins for WaveformData {
+    fn bins(&self) -> Vec<&[u32]> {
+        self.data.iter().map(Vec::as_slice).collect()
+    }
+    fn bins_mut(&mut self) -> Vec<&mut [u32]> {
+        self.data.iter_mut().map(Vec::as_mut_slice).collect()
+    }
+}
+
+impl ScopeBins for VectorscopeData {
+    fn bins(&self) -> Vec<&[u32]> {
+        vec![&self.density]
+    }
+    fn bins_mut(&mut self) -> Vec<&mut [u32]> {
+        vec![&mut self.density]
+    }
+}
+
+impl ScopeBins for CieData {
+    fn bins(&self) -> Vec<&[u32]> {
+        vec![&self.density]
+    }
+    fn bins_mut(&mut self) -> Vec<&mut [u32]> {
+        vec![&mut self.density]
+    }
+}
+
+/// Accumulates successive frames of one scope type.
+#[derive(Debug, Clone)]
+pub struct ScopeAccumulator<T> {
+    mode: ScopeAccumulation,
+    /// Frames in the averaging window (oldest first).
+    window: VecDeque<T>,
+    /// Running per-bin sums over `window`.
+    sums: Vec<Vec<u64>>,
+    /// Held per-bin values for peak hold.
+    held: Vec<Vec<f32>>,
+}
+
+impl<T> Default for ScopeAccumulator<T> {
+    fn default() -> Self {
+        Self {
+            mode: ScopeAccumulation::Instant,
+            window: VecDeque::new(),
+            sums: Vec::new(),
+            held: Vec::new(),
+        }
+    }
+}
+
+impl<T: ScopeBins> ScopeAccumulator<T> {
+    /// Create an empty accumulator.
+    pub fn new(mode: ScopeAccumulation) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// Current mode.
+    pub fn mode(&self) -> ScopeAccumulation {
+        self.mode
+    }
+
+    /// Switch mode, discarding accumulated history if it changed.
+    pub fn set_mode(&mut self, mode: ScopeAccumulation) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.reset();
+        }
+    }
+
+    /// Discard accumulated history (e.g. on a new source or cut).
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sums.clear();
+        self.held.clear();
+    }
+
+    /// Add a frame and return what should be displayed.
+    pub fn push(&mut self, frame: T) -> T {
+        if !self.matches_shape(&frame) {
+            self.reset();
+        }
+        match self.mode {
+            ScopeAccumulation::Instant => frame,
+            ScopeAccumulation::Average { frames } => self.push_average(frame, frames.max(1)),
+            ScopeAccumulation::PeakHold { decay } => self.push_peak(frame, decay.clamp(0.0, 1.0)),
+        }
+    }
+
+    fn matches_shape(&self, frame: &T) -> bool {
+        let lens = frame.bins().iter().map(|b| b.len()).collect::<Vec<_>>();
+        let known = if !self.sums.is_empty() {
+            self.sums.iter().map(Vec::len).collect::<Vec<_>>()
+        } else if !self.held.is_empty() {
+            self.held.iter().map(Vec::len).collect::<Vec<_>>()
+        } else {
+            return true;
+        };
+        lens == known
+    }
+
+    fn push_average(&mut self, frame: T, frames: u32) -> T {
+        if self.sums.is_empty() {
+            self.sums = frame.bins().iter().map(|b| vec![0; b.len()]).collect();
+        }
+        for (sum, bins) in self.sums.iter_mut().zip(frame.bins()) {
+            for (s, &v) in sum.iter_mut().zip(bins) {
+                *s += u64::from(v);
+            }
+        }
+        self.window.push_back(frame.clone());
+        while self.window.len() > frames as usize {
+            let Some(old) = self.window.pop_front() else {
+                break;
+            };
+            for (sum, bins) in self.sums.iter_mut().zip(old.bins()) {
+                for (s, &v) in sum.iter_mut().zip(bins) {
+                    *s -= u64::from(v);
+                }
+            }
+        }
+
+        let n = self.window.len() as u64;
+        let mut out = frame;
+        for (dst, sum) in out.bins_mut().into_iter().zip(&self.sums) {
+            for (d, &s) in dst.iter_mut().zip(sum) {
+                *d = ((s + n / 2) / n) as u32;
+            }
+        }
+        out.refresh();
+        out
+    }
+
+    fn push_peak(&mut self, frame: T, decay: f32) -> T {
+        if self.held.is_empty() {
+            self.held = frame.bins().iter().map(|b| vec![0.0; b.len()]).collect();
+        }
+        let keep = 1.0 - decay;
+        let mut out = frame;
+        for (dst, held) in out.bins_mut().into_iter().zip(&mut self.held) {
+            for (d, h) in dst.iter_mut().zip(held.iter_mut()) {
+                *h = (*h * keep).max(*d as f32);
+                *d = h.round() as u32;
+            }
+        }
+        out.refresh();
+        out
+    }
+}
+
+/// One accumulator per scope type, sharing a mode.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeHistory {
+    pub histogram: ScopeAccumulator<HistogramData>,
+    pub waveform: ScopeAccumulator<WaveformData>,
+    pub vectorscope: ScopeAccumulator<VectorscopeData>,
+    pub cie: ScopeAccumulator<CieData>,
+}
+
+impl ScopeHistory {
+    /// Current mode (shared by all scopes).
+    pub fn mode(&self) -> ScopeAccumulation {
+        self.histogram.mode()
+    }
+
+    /// Switch every scope to `mode`.
+    pub fn set_mode(&mut self, mode: ScopeAccumulation) {
+        self.histogram.set_mode(mode);
+        self.waveform.set_mode(mode);
+        self.vectorscope.set_mode(mode);
+        self.cie.set_mode(mode);
+    }
+
+    /// Discard accumulated history for every scope.
+    pub fn reset(&mut self) {
+        self.histogram.reset();
+        self.waveform.reset();
+        self.vectorscope.reset();
+        self.cie.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vs(density: Vec<u32>) -> VectorscopeData {
+        VectorscopeData {
+            resolution: 1,
+            density,
+        }
+    }
+
+    #[test]
+    fn test_instant_passes_frames_through() {
+        let mut acc = ScopeAccumulator::new(ScopeAccumulation::Instant);
+        acc.push(vs(vec![10, 0]));
+        assert_eq!(acc.push(vs(vec![0, 4])).density, [0, 4]);
+    }
+
+    #[test]
+    fn test_average_over_window() {
+        let mut acc = ScopeAccumulator::new(ScopeAccumulation::Average { frames: 2 });
+        assert_eq!(acc.push(vs(vec![10, 0])).density, [10, 0]);
+        assert_eq!(acc.push(vs(vec![20, 4])).density, [15, 2]);
+        // Oldest frame drops out of the window.
+        assert_eq!(acc.push(vs(vec![30, 8])).density, [25, 6]);
+    }
+
+    #[test]
+    fn test_peak_hold_decays() {
+        let mut acc = ScopeAccumulator::new(ScopeAccumulation::PeakHold { decay: 0.5 });
+        assert_eq!(acc.push(vs(vec![100, 0])).density, [100, 0]);
+        assert_eq!(acc.push(vs(vec![0, 10])).density, [50, 10]);
+        assert_eq!(acc.push(vs(vec![0, 0])).density, [25, 5]);
+    }
+
+    #[test]
+    fn test_shape_change_resets_history() {
+        let mut acc = ScopeAccumulator::new(ScopeAccumulation::Average { frames: 4 });
+        acc.push(vs(vec![100, 100]));
+        assert_eq!(acc.push(vs(vec![2, 2, 2])).density, [2, 2, 2]);
+    }
+
+    #[test]
+    fn test_histogram_peak_is_refreshed() {
+        let hist = |v: u32| HistogramData {
+            bins: [vec![v], vec![0], vec![0], vec![0]],
+            peak: v,
+        };
+        let mut acc = ScopeAccumulator::new(ScopeAccumulation::Average { frames: 2 });
+        acc.push(hist(8));
+        assert_eq!(acc.push(hist(2)).peak, 5);
+    }
+}
@@ -4,6 +4,7 @@
 //! each pixel's chrominance onto a 2D grid using Cb/Cr (blue-difference
 //! and red-difference chroma) axes.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::image::GradingImage;
@@ -12,7 +13,8 @@ use crate::image::GradingImage;
 const DEFAULT_RESOLUTION: u32 = 256;
 
 /// Vectorscope data — plots color saturation and hue on a circular display.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VectorscopeData {
     /// Resolution of the square vectorscope grid.
     pub resolution: u32,
@@ -1,5 +1,6 @@
 //! Waveform scope computation — plots pixel intensity vs. horizontal position.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::image::GradingImage;
@@ -11,7 +12,8 @@ const DEFAULT_HEIGHT: u32 = 256;
 ///
 /// For each column (x-position in the source image), the waveform shows
 /// the distribution of R, G, B intensity values.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WaveformData {
     /// Width of the waveform display in columns.
     pub width: u32,
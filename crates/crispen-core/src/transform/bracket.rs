@@ -0,0 +1,106 @@
+//! Bracketed stills — the current frame under several grade variations.
+//!
+//! For client option reviews a frame is rendered once per variant, e.g.
+//! exposure −1 / 0 / +1 stop or each saved version of the grade, and each
+//! render is written next to the requested path with the variant's label in
+//! its file name:
+//!
+//! ```text
+//! shot.png ─▶ shot_01_EV_-1.png, shot_02_EV_+0.png, shot_03_EV_+1.png
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::transform::params::GradingParams;
+
+/// One grade a bracketed still is rendered with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BracketVariant {
+    /// Human-readable name, also used in the file name.
+    pub label: String,
+    /// The grade to render.
+    pub params: GradingParams,
+}
+
+/// Variants of `base` with `stops` added to its exposure, in the given
+/// order. Labels read `EV -1`, `EV +0`, `EV +0.5`.
+pub fn exposure_bracket(base: &GradingParams, stops: &[f32]) -> Vec<BracketVariant> {
+    stops
+        .iter()
+        .map(|&delta| {
+            let mut params = base.clone();
+            params.exposure.stops += delta;
+            // `+ 0.0` turns -0 into +0 so the zero variant reads `EV +0`.
+            BracketVariant {
+                label: format!("EV {:+}", delta + 0.0),
+                params,
+            }
+        })
+        .collect()
+}
+
+/// File for the `index`th (0-based) variant of a bracket written to `path`:
+/// the stem gains a 1-based sequence number, so files sort in bracket
+/// order, and the label with anything but letters, digits, `+`, `-`, and
+/// `.` replaced by `_`. The extension is kept.
+pub fn bracket_path(path: &Path, index: usize, label: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let slug: String = label
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '+' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut name = format!("{stem}_{:02}", index + 1);
+    if !slug.is_empty() {
+        name = format!("{name}_{slug}");
+    }
+    if let Some(ext) = path.extension() {
+        name = format!("{name}.{}", ext.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_bracket_offsets_the_base_grade() {
+        let mut base = GradingParams::default();
+        base.exposure.stops = 0.5;
+        base.saturation = 1.2;
+        let variants = exposure_bracket(&base, &[-1.0, 0.0, 1.0]);
+
+        let labels: Vec<_> = variants.iter().map(|v| v.label.as_str()).collect();
+        assert_eq!(labels, ["EV -1", "EV +0", "EV +1"]);
+        let stops: Vec<_> = variants.iter().map(|v| v.params.exposure.stops).collect();
+        assert_eq!(stops, [-0.5, 0.5, 1.5]);
+        assert!(variants.iter().all(|v| v.params.saturation == 1.2));
+    }
+
+    #[test]
+    fn test_bracket_path_numbers_and_sanitizes() {
+        let path = Path::new("/out/shot.png");
+        assert_eq!(
+            bracket_path(path, 0, "EV -1"),
+            Path::new("/out/shot_01_EV_-1.png")
+        );
+        assert_eq!(
+            bracket_path(path, 11, "warm/v2"),
+            Path::new("/out/shot_12_warm_v2.png")
+        );
+        assert_eq!(
+            bracket_path(Path::new("still"), 2, ""),
+            Path::new("still_03")
+        );
+    }
+}
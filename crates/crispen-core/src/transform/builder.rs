@@ -0,0 +1,587 @@
+//! Checked construction of [`GradingParams`] for library users.
+//!
+//! Every setter clamps its value into the editor range from
+//! [`validation`](super::validation) and rejects NaN and infinity. A
+//! rejected value leaves the field unchanged and is reported by
+//! [`GradingParamsBuilder::build`], so a bad input is never silently
+//! turned into a plausible-looking grade.
+//!
+//! ```
+//! use crispen_core::transform::builder::{GradingParamsBuilder, Wheel};
+//!
+//! let params = GradingParamsBuilder::new()
+//!     .set_lift_rgb([0.02, 0.0, -0.02])
+//!     .set_wheel(Wheel::Gain, [1.1, 1.0, 0.95, 1.0])
+//!     .set_saturation(1.2)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(params.saturation, 1.2);
+//! ```
+
+use crate::grading::channel_mixer::ChannelMixer;
+use crate::grading::curves::CurveInterpolation;
+use crate::grading::glow::Glow;
+use crate::grading::monochrome::Monochrome;
+use crate::grading::sliders::{HueRotation, InputLevels, SaturationModel};
+use crate::grading::soft_clip::SoftClip;
+use crate::transform::params::{ColorManagementConfig, GradingParams, InputCorrection, ToolBypass};
+use crate::transform::validation::{
+    EXPOSURE_RANGE, GAIN_RANGE, GAMMA_RANGE, GLOW_RANGE, HUE_RANGE, LATERAL_CA_RANGE, LIFT_RANGE,
+    LOG_OFFSET_RANGE, MULTIPLIER_RANGE, OFFSET_RANGE, ParamIssue, RECOVERY_RANGE, RESPONSE_RANGE,
+    TOE_RANGE, UNIT_RANGE, WHITE_BALANCE_RANGE, inverted_levels,
+};
+
+/// One of the four primary controls, each with a bar and a wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wheel {
+    Lift,
+    Gamma,
+    Gain,
+    Offset,
+}
+
+impl Wheel {
+    /// Allowed range of each channel.
+    pub const fn range(self) -> (f32, f32) {
+        match self {
+            Self::Lift => LIFT_RANGE,
+            Self::Gamma => GAMMA_RANGE,
+            Self::Gain => GAIN_RANGE,
+            Self::Offset => OFFSET_RANGE,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Lift => "lift",
+            Self::Gamma => "gamma",
+            Self::Gain => "gain",
+            Self::Offset => "offset",
+        }
+    }
+}
+
+/// One of the four secondary curves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveKind {
+    HueVsHue,
+    HueVsSat,
+    LumVsSat,
+    SatVsSat,
+}
+
+impl CurveKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::HueVsHue => "hue_vs_hue",
+            Self::HueVsSat => "hue_vs_sat",
+            Self::LumVsSat => "lum_vs_sat",
+            Self::SatVsSat => "sat_vs_sat",
+        }
+    }
+}
+
+/// Builds a [`GradingParams`] one checked field at a time.
+#[derive(Debug, Clone, Default)]
+pub struct GradingParamsBuilder {
+    params: GradingParams,
+    rejected: Vec<ParamIssue>,
+}
+
+impl GradingParamsBuilder {
+    /// Start from the identity grade.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing grade. Its values are not re-checked until
+    /// [`build`](Self::build).
+    pub fn from_params(params: GradingParams) -> Self {
+        Self {
+            params,
+            rejected: Vec::new(),
+        }
+    }
+
+    /// Values rejected so far.
+    pub fn rejected(&self) -> &[ParamIssue] {
+        &self.rejected
+    }
+
+    /// Clamp `value` into `range`, or record it as rejected and return
+    /// `None` when it is not finite.
+    fn accept(&mut self, field: &str, value: f32, (min, max): (f32, f32)) -> Option<f32> {
+        if value.is_finite() {
+            Some(value.clamp(min, max))
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: field.to_owned(),
+            });
+            None
+        }
+    }
+
+    /// Accept all channels or none, so a rejected value never leaves a
+    /// half-updated control behind.
+    fn accept_channels<const N: usize>(
+        &mut self,
+        field: &str,
+        values: [f32; N],
+        range: (f32, f32),
+    ) -> Option<[f32; N]> {
+        if values.iter().all(|v| v.is_finite()) {
+            Some(values.map(|v| v.clamp(range.0, range.1)))
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: field.to_owned(),
+            });
+            None
+        }
+    }
+
+    fn bar_mut(&mut self, wheel: Wheel) -> &mut [f32; 4] {
+        match wheel {
+            Wheel::Lift => &mut self.params.lift,
+            Wheel::Gamma => &mut self.params.gamma,
+            Wheel::Gain => &mut self.params.gain,
+            Wheel::Offset => &mut self.params.offset,
+        }
+    }
+
+    fn set_bar_rgb(mut self, wheel: Wheel, rgb: [f32; 3]) -> Self {
+        if let Some(rgb) = self.accept_channels(wheel.name(), rgb, wheel.range()) {
+            self.bar_mut(wheel)[..3].copy_from_slice(&rgb);
+        }
+        self
+    }
+
+    /// Color management configuration.
+    pub fn set_color_management(mut self, config: ColorManagementConfig) -> Self {
+        self.params.color_management = config;
+        self
+    }
+
+    /// Chart-derived input correction. Rejected unless every value is finite.
+    pub fn set_input_correction(mut self, correction: InputCorrection) -> Self {
+        let finite = [correction.gain, correction.power]
+            .into_iter()
+            .chain(correction.matrix)
+            .flatten()
+            .all(f32::is_finite);
+        if finite {
+            self.params.input_correction = correction;
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: "input_correction".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Channel mixer. Rejected unless every value is finite.
+    pub fn set_channel_mixer(mut self, mixer: ChannelMixer) -> Self {
+        let finite = mixer
+            .matrix
+            .into_iter()
+            .chain([mixer.offset])
+            .flatten()
+            .all(f32::is_finite);
+        if finite {
+            self.params.channel_mixer = mixer;
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: "channel_mixer".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Input exposure: `stops` in scene linear after the input transform,
+    /// `log_offset` on code values before it.
+    pub fn set_exposure(mut self, stops: f32, log_offset: f32) -> Self {
+        if let Some(value) = self.accept("exposure.stops", stops, EXPOSURE_RANGE) {
+            self.params.exposure.stops = value;
+        }
+        if let Some(value) = self.accept("exposure.log_offset", log_offset, LOG_OFFSET_RANGE) {
+            self.params.exposure.log_offset = value;
+        }
+        self
+    }
+
+    /// Source range and per-channel black/white points. The points are
+    /// clamped to 0–1; the levels are rejected if any point is not finite
+    /// or a black point is not below its white point.
+    pub fn set_input_levels(mut self, levels: InputLevels) -> Self {
+        let black = self.accept_channels("input_levels.black", levels.black, UNIT_RANGE);
+        let white = self.accept_channels("input_levels.white", levels.white, UNIT_RANGE);
+        let (Some(black), Some(white)) = (black, white) else {
+            return self;
+        };
+        let inverted = inverted_levels(&black, &white);
+        if inverted.is_empty() {
+            self.params.input_levels = InputLevels {
+                black,
+                white,
+                ..levels
+            };
+        } else {
+            self.rejected.extend(inverted);
+        }
+        self
+    }
+
+    /// Black and white conversion. Contrast and tint strength are clamped
+    /// to their ranges; the settings are rejected if any value is not
+    /// finite.
+    pub fn set_monochrome(mut self, mono: Monochrome) -> Self {
+        let finite = mono
+            .weights
+            .into_iter()
+            .chain(mono.tint)
+            .chain([mono.contrast, mono.tint_strength])
+            .all(f32::is_finite);
+        if finite {
+            self.params.monochrome = Monochrome {
+                contrast: mono.contrast.clamp(RESPONSE_RANGE.0, RESPONSE_RANGE.1),
+                tint_strength: mono.tint_strength.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                ..mono
+            };
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: "monochrome".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Highlight soft clip and shadow toe. Each value is clamped to its
+    /// range; the settings are rejected if any value is not finite.
+    pub fn set_soft_clip(mut self, soft_clip: SoftClip) -> Self {
+        let values = [
+            soft_clip.knee,
+            soft_clip.strength,
+            soft_clip.toe,
+            soft_clip.toe_strength,
+        ];
+        if values.iter().all(|v| v.is_finite()) {
+            self.params.soft_clip = SoftClip {
+                knee: soft_clip.knee.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                strength: soft_clip.strength.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                toe: soft_clip.toe.clamp(TOE_RANGE.0, TOE_RANGE.1),
+                toe_strength: soft_clip.toe_strength.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                mode: soft_clip.mode,
+            };
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: "soft_clip".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Post-grade glow. Threshold, spread, and intensity are clamped to
+    /// their ranges; the settings are rejected if any value is not finite.
+    pub fn set_glow(mut self, glow: Glow) -> Self {
+        let finite = [glow.threshold, glow.spread, glow.intensity]
+            .into_iter()
+            .chain(glow.tint)
+            .all(f32::is_finite);
+        if finite {
+            self.params.glow = Glow {
+                threshold: glow.threshold.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                spread: glow.spread.clamp(UNIT_RANGE.0, UNIT_RANGE.1),
+                intensity: glow.intensity.clamp(GLOW_RANGE.0, GLOW_RANGE.1),
+                tint: glow.tint,
+            };
+        } else {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: "glow".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Lift bar red, green, and blue; master is kept.
+    pub fn set_lift_rgb(self, rgb: [f32; 3]) -> Self {
+        self.set_bar_rgb(Wheel::Lift, rgb)
+    }
+
+    /// Gamma bar red, green, and blue; master is kept.
+    pub fn set_gamma_rgb(self, rgb: [f32; 3]) -> Self {
+        self.set_bar_rgb(Wheel::Gamma, rgb)
+    }
+
+    /// Gain bar red, green, and blue; master is kept.
+    pub fn set_gain_rgb(self, rgb: [f32; 3]) -> Self {
+        self.set_bar_rgb(Wheel::Gain, rgb)
+    }
+
+    /// Offset bar red, green, and blue; master is kept.
+    pub fn set_offset_rgb(self, rgb: [f32; 3]) -> Self {
+        self.set_bar_rgb(Wheel::Offset, rgb)
+    }
+
+    /// Master channel of a bar.
+    pub fn set_master(mut self, wheel: Wheel, value: f32) -> Self {
+        let field = format!("{}.master", wheel.name());
+        if let Some(value) = self.accept(&field, value, wheel.range()) {
+            self.bar_mut(wheel)[3] = value;
+        }
+        self
+    }
+
+    /// All four channels of a color wheel, `[R, G, B, Master]`.
+    pub fn set_wheel(mut self, wheel: Wheel, values: [f32; 4]) -> Self {
+        let field = format!("{}_wheel", wheel.name());
+        if let Some(values) = self.accept_channels(&field, values, wheel.range()) {
+            let params = &mut self.params;
+            *match wheel {
+                Wheel::Lift => &mut params.lift_wheel,
+                Wheel::Gamma => &mut params.gamma_wheel,
+                Wheel::Gain => &mut params.gain_wheel,
+                Wheel::Offset => &mut params.offset_wheel,
+            } = values;
+        }
+        self
+    }
+
+    /// Temperature and tint, both in -100..=100.
+    pub fn set_white_balance(mut self, temperature: f32, tint: f32) -> Self {
+        if let Some(value) = self.accept("temperature", temperature, WHITE_BALANCE_RANGE) {
+            self.params.temperature = value;
+        }
+        if let Some(value) = self.accept("tint", tint, WHITE_BALANCE_RANGE) {
+            self.params.tint = value;
+        }
+        self
+    }
+
+    /// Contrast multiplier and the pivot it scales around.
+    pub fn set_contrast(mut self, contrast: f32, pivot: f32) -> Self {
+        if let Some(value) = self.accept("contrast", contrast, MULTIPLIER_RANGE) {
+            self.params.contrast = value;
+        }
+        if let Some(value) = self.accept("pivot", pivot, UNIT_RANGE) {
+            self.params.pivot = value;
+        }
+        self
+    }
+
+    /// Midtone detail (spatial, applied in its own pass).
+    pub fn set_midtone_detail(mut self, value: f32) -> Self {
+        if let Some(value) = self.accept("midtone_detail", value, RECOVERY_RANGE) {
+            self.params.midtone_detail = value;
+        }
+        self
+    }
+
+    /// Chromatic aberration red and blue magnification and fringing
+    /// (spatial, applied in its own pass).
+    pub fn set_chromatic_aberration(
+        mut self,
+        red_scale: f32,
+        blue_scale: f32,
+        fringe: f32,
+    ) -> Self {
+        let red = self.accept(
+            "chromatic_aberration.red_scale",
+            red_scale,
+            LATERAL_CA_RANGE,
+        );
+        if let Some(value) = red {
+            self.params.chromatic_aberration.red_scale = value;
+        }
+        let blue = self.accept(
+            "chromatic_aberration.blue_scale",
+            blue_scale,
+            LATERAL_CA_RANGE,
+        );
+        if let Some(value) = blue {
+            self.params.chromatic_aberration.blue_scale = value;
+        }
+        if let Some(value) = self.accept("chromatic_aberration.fringe", fringe, UNIT_RANGE) {
+            self.params.chromatic_aberration.fringe = value;
+        }
+        self
+    }
+
+    /// Haze removal strength and depth bias (spatial, applied in its own
+    /// pass).
+    pub fn set_dehaze(mut self, strength: f32, depth_bias: f32) -> Self {
+        if let Some(value) = self.accept("dehaze.strength", strength, RECOVERY_RANGE) {
+            self.params.dehaze.strength = value;
+        }
+        if let Some(value) = self.accept("dehaze.depth_bias", depth_bias, RECOVERY_RANGE) {
+            self.params.dehaze.depth_bias = value;
+        }
+        self
+    }
+
+    /// Shadow and highlight recovery.
+    pub fn set_shadows_highlights(mut self, shadows: f32, highlights: f32) -> Self {
+        if let Some(value) = self.accept("shadows", shadows, RECOVERY_RANGE) {
+            self.params.shadows = value;
+        }
+        if let Some(value) = self.accept("highlights", highlights, RECOVERY_RANGE) {
+            self.params.highlights = value;
+        }
+        self
+    }
+
+    /// Saturation multiplier.
+    pub fn set_saturation(mut self, value: f32) -> Self {
+        if let Some(value) = self.accept("saturation", value, MULTIPLIER_RANGE) {
+            self.params.saturation = value;
+        }
+        self
+    }
+
+    /// Vibrance.
+    pub fn set_vibrance(mut self, value: f32) -> Self {
+        if let Some(value) = self.accept("vibrance", value, RECOVERY_RANGE) {
+            self.params.vibrance = value;
+        }
+        self
+    }
+
+    /// Hue rotation in degrees.
+    pub fn set_hue(mut self, degrees: f32) -> Self {
+        if let Some(value) = self.accept("hue", degrees, HUE_RANGE) {
+            self.params.hue = value;
+        }
+        self
+    }
+
+    /// Hue rotation plane and shadow/midtone/highlight offsets in degrees.
+    pub fn set_hue_rotation(mut self, rotation: HueRotation) -> Self {
+        let field = "hue_rotation.ranges";
+        if let Some(ranges) = self.accept_channels(field, rotation.ranges, HUE_RANGE) {
+            self.params.hue_rotation = HueRotation { ranges, ..rotation };
+        }
+        self
+    }
+
+    /// Luma mix weight.
+    pub fn set_luma_mix(mut self, value: f32) -> Self {
+        if let Some(value) = self.accept("luma_mix", value, UNIT_RANGE) {
+            self.params.luma_mix = value;
+        }
+        self
+    }
+
+    /// Control points of a curve. Inputs are clamped to 0..=1 and the
+    /// points sorted by input; the curve is rejected if any point is not
+    /// finite.
+    pub fn set_curve(mut self, kind: CurveKind, points: Vec<[f32; 2]>) -> Self {
+        if !points.as_flattened().iter().all(|v| v.is_finite()) {
+            self.rejected.push(ParamIssue::NotFinite {
+                field: kind.name().to_owned(),
+            });
+            return self;
+        }
+        let mut points: Vec<[f32; 2]> = points
+            .into_iter()
+            .map(|[x, y]| [x.clamp(0.0, 1.0), y])
+            .collect();
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        let params = &mut self.params;
+        *match kind {
+            CurveKind::HueVsHue => &mut params.hue_vs_hue,
+            CurveKind::HueVsSat => &mut params.hue_vs_sat,
+            CurveKind::LumVsSat => &mut params.lum_vs_sat,
+            CurveKind::SatVsSat => &mut params.sat_vs_sat,
+        } = points;
+        self
+    }
+
+    /// How curve control points are joined.
+    pub fn set_curve_interpolation(mut self, interpolation: CurveInterpolation) -> Self {
+        self.params.curve_interpolation = interpolation;
+        self
+    }
+
+    /// Space the saturation slider and saturation curves work in.
+    pub fn set_saturation_model(mut self, model: SaturationModel) -> Self {
+        self.params.saturation_model = model;
+        self
+    }
+
+    /// Sections skipped during evaluation.
+    pub fn set_bypass(mut self, bypass: ToolBypass) -> Self {
+        self.params.bypass = bypass;
+        self
+    }
+
+    /// Finish the grade.
+    ///
+    /// Fails with every rejected value, followed by anything
+    /// [`GradingParams::validate`] finds in the result (which can only
+    /// come from a grade passed to [`from_params`](Self::from_params)).
+    pub fn build(self) -> Result<GradingParams, Vec<ParamIssue>> {
+        let mut issues = self.rejected;
+        issues.extend(self.params.validate());
+        if issues.is_empty() {
+            Ok(self.params)
+        } else {
+            Err(issues)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setters_clamp_into_range() {
+        let params = GradingParamsBuilder::new()
+            .set_gain_rgb([5.0, 1.0, -1.0])
+            .set_hue(400.0)
+            .set_curve(CurveKind::HueVsSat, vec![[1.2, 0.5], [-0.1, 1.5]])
+            .build()
+            .unwrap();
+        assert_eq!(params.gain, [4.0, 1.0, 0.0, 1.0]);
+        assert_eq!(params.hue, 180.0);
+        assert_eq!(params.hue_vs_sat, vec![[0.0, 1.5], [1.0, 0.5]]);
+    }
+
+    #[test]
+    fn test_non_finite_values_are_rejected() {
+        let builder = GradingParamsBuilder::new()
+            .set_saturation(f32::NAN)
+            .set_wheel(Wheel::Lift, [0.1, f32::INFINITY, 0.0, 0.0])
+            .set_contrast(1.5, 0.4);
+        assert_eq!(builder.rejected().len(), 2);
+        let issues = builder.clone().build().unwrap_err();
+        assert_eq!(
+            issues,
+            vec![
+                ParamIssue::NotFinite {
+                    field: "saturation".into()
+                },
+                ParamIssue::NotFinite {
+                    field: "lift_wheel".into()
+                },
+            ]
+        );
+        // Rejected values leave the fields untouched; valid ones still apply.
+        let params = GradingParamsBuilder::from_params(builder.params)
+            .build()
+            .unwrap();
+        assert_eq!(params.saturation, 1.0);
+        assert_eq!(params.lift_wheel, [0.0; 4]);
+        assert_eq!(params.contrast, 1.5);
+    }
+
+    #[test]
+    fn test_build_validates_existing_params() {
+        let params = GradingParams {
+            pivot: 2.0,
+            ..GradingParams::default()
+        };
+        let issues = GradingParamsBuilder::from_params(params)
+            .build()
+            .unwrap_err();
+        assert!(matches!(&issues[..], [ParamIssue::OutOfRange { field, .. }] if field == "pivot"));
+    }
+}
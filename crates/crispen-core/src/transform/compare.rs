@@ -0,0 +1,214 @@
+//! Per-pixel ΔE2000 comparison between two graded images.
+//!
+//! Verifies trim passes: the graded output is compared against a reference
+//! render, or against the same source graded with another version of the
+//! grade, and the difference is shown as a heatmap over the viewer. Both
+//! images hold code values in the grade's output space and are decoded to
+//! linear before CIELAB, so log and PQ outputs are compared perceptually.
+//!
+//! `crispen-gpu` runs the same comparison as a compute pass for the viewer;
+//! [`delta_e_map`] is its CPU reference.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::params::ColorSpaceId;
+use crate::color_management::delta_e::{DeltaEStats, delta_e_2000, linear_rgb_to_lab};
+use crate::color_management::transfer::get_transfer;
+use crate::image::GradingImage;
+
+/// ΔE at which the heatmap reaches its last color.
+pub const DEFAULT_HEATMAP_SCALE: f32 = 10.0;
+
+/// ΔE counted as a visible difference in [`DeltaEReport::above_tolerance`].
+pub const DEFAULT_TOLERANCE: f32 = 2.0;
+
+/// ΔE at which a heatmap pixel becomes fully opaque. Smaller differences
+/// fade out so untouched areas show the image underneath.
+pub const HEATMAP_OPAQUE_AT: f32 = 1.0;
+
+/// Largest heatmap alpha, leaving the image faintly visible underneath.
+pub const HEATMAP_MAX_ALPHA: u8 = 220;
+
+/// Heatmap ramp from no difference to [`DEFAULT_HEATMAP_SCALE`] (or the
+/// chosen scale): blue, green, yellow, red, as sRGB-encoded 0–1 values.
+pub const HEATMAP_STOPS: [[f32; 3]; 4] = [
+    [0.10, 0.20, 0.90],
+    [0.10, 0.85, 0.30],
+    [0.95, 0.90, 0.10],
+    [0.95, 0.15, 0.10],
+];
+
+/// ΔE2000 for every pixel of two equally sized images.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeltaEMap {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major, one value per pixel.
+    pub values: Vec<f32>,
+}
+
+/// Summary of a [`DeltaEMap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeltaEReport {
+    pub width: u32,
+    pub height: u32,
+    pub delta_e_mean: f32,
+    pub delta_e_p95: f32,
+    pub delta_e_max: f32,
+    /// Pixel with the largest ΔE.
+    pub worst_x: u32,
+    pub worst_y: u32,
+    /// ΔE used for `above_tolerance`.
+    pub tolerance: f32,
+    /// Fraction of pixels whose ΔE exceeds `tolerance`.
+    pub above_tolerance: f32,
+}
+
+impl DeltaEMap {
+    /// Summarize the map, counting pixels above `tolerance`.
+    pub fn report(&self, tolerance: f32) -> DeltaEReport {
+        let stats = DeltaEStats::from_values(&self.values);
+        let worst = self
+            .values
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(i, _)| i as u32);
+        let above = self.values.iter().filter(|&&v| v > tolerance).count();
+        DeltaEReport {
+            width: self.width,
+            height: self.height,
+            delta_e_mean: stats.mean,
+            delta_e_p95: stats.p95,
+            delta_e_max: stats.max,
+            worst_x: worst % self.width.max(1),
+            worst_y: worst / self.width.max(1),
+            tolerance,
+            above_tolerance: above as f32 / self.values.len().max(1) as f32,
+        }
+    }
+
+    /// Render the map as RGBA8 (sRGB-encoded) with [`heatmap_color`].
+    pub fn heatmap(&self, scale: f32) -> Vec<u8> {
+        self.values
+            .iter()
+            .flat_map(|&v| heatmap_color(v, scale))
+            .collect()
+    }
+}
+
+/// Heatmap color for one ΔE, reaching the last stop at `scale`.
+pub fn heatmap_color(delta_e: f32, scale: f32) -> [u8; 4] {
+    let t = (delta_e / scale.max(f32::EPSILON)).clamp(0.0, 1.0) * 3.0;
+    let i = (t as usize).min(2);
+    let f = t - i as f32;
+    let (a, b) = (HEATMAP_STOPS[i], HEATMAP_STOPS[i + 1]);
+    let rgb = [0, 1, 2].map(|c| ((a[c] + (b[c] - a[c]) * f) * 255.0).round() as u8);
+    let alpha = (delta_e / HEATMAP_OPAQUE_AT).clamp(0.0, 1.0) * f32::from(HEATMAP_MAX_ALPHA);
+    [rgb[0], rgb[1], rgb[2], alpha.round() as u8]
+}
+
+/// ΔE2000 between `graded` and `reference`, both holding code values in
+/// `output_space`.
+///
+/// Returns `None` when the images differ in size or are empty.
+pub fn delta_e_map(
+    graded: &GradingImage,
+    reference: &GradingImage,
+    output_space: ColorSpaceId,
+) -> Option<DeltaEMap> {
+    if graded.width != reference.width
+        || graded.height != reference.height
+        || graded.pixels.is_empty()
+        || graded.pixels.len() != reference.pixels.len()
+    {
+        return None;
+    }
+
+    let transfer = get_transfer(output_space);
+    let to_lab = |p: [f32; 4]| {
+        let rgb = [p[0], p[1], p[2]];
+        let linear = match &transfer {
+            Some(tf) => rgb.map(|c| tf.to_linear(c)),
+            None => rgb,
+        };
+        linear_rgb_to_lab(linear, output_space)
+    };
+
+    Some(DeltaEMap {
+        width: graded.width,
+        height: graded.height,
+        values: graded
+            .pixels
+            .iter()
+            .zip(&reference.pixels)
+            .map(|(&a, &b)| delta_e_2000(to_lab(a), to_lab(b)))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    fn image(width: u32, height: u32, f: impl Fn(u32) -> [f32; 4]) -> GradingImage {
+        GradingImage {
+            width,
+            height,
+            pixels: (0..width * height).map(f).collect(),
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    fn ramp(i: u32) -> [f32; 4] {
+        let t = (i % 16) as f32 / 15.0;
+        [t, 0.5 * t, 1.0 - t, 1.0]
+    }
+
+    #[test]
+    fn test_identical_images_have_zero_delta_e() {
+        let a = image(16, 4, ramp);
+        let map = delta_e_map(&a, &a, ColorSpaceId::Srgb).expect("same size");
+        assert!(map.values.iter().all(|&v| v.abs() < 1e-3));
+        let report = map.report(DEFAULT_TOLERANCE);
+        assert_eq!(report.above_tolerance, 0.0);
+        assert!(
+            map.heatmap(DEFAULT_HEATMAP_SCALE)
+                .chunks(4)
+                .all(|p| p[3] == 0)
+        );
+    }
+
+    #[test]
+    fn test_report_locates_worst_pixel() {
+        let a = image(8, 8, ramp);
+        let mut b = a.clone();
+        b.pixels[8 * 6 + 2][0] += 0.3;
+        b.pixels[8 + 5][1] += 0.02;
+
+        let report = delta_e_map(&a, &b, ColorSpaceId::Srgb)
+            .expect("same size")
+            .report(DEFAULT_TOLERANCE);
+        assert_eq!((report.worst_x, report.worst_y), (2, 6));
+        assert!(report.delta_e_max > 5.0, "ΔE {}", report.delta_e_max);
+        assert!((report.above_tolerance - 1.0 / 64.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_heatmap_ramp_endpoints() {
+        assert_eq!(heatmap_color(0.0, 10.0), [26, 51, 230, 0]);
+        assert_eq!(heatmap_color(10.0, 10.0), [242, 38, 26, HEATMAP_MAX_ALPHA]);
+        assert_eq!(heatmap_color(50.0, 10.0), heatmap_color(10.0, 10.0));
+        assert_eq!(heatmap_color(0.5, 10.0)[3], 110);
+    }
+
+    #[test]
+    fn test_size_mismatch_is_rejected() {
+        let a = image(4, 4, ramp);
+        let b = image(4, 2, ramp);
+        assert!(delta_e_map(&a, &b, ColorSpaceId::Srgb).is_none());
+    }
+}
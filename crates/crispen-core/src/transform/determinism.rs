@@ -0,0 +1,244 @@
+//! Strict determinism for archival renders.
+//!
+//! The CPU chain is plain IEEE arithmetic with no fast-math, so a grade
+//! evaluated twice on one machine already gives the same result. What
+//! varies is around it: the number of threads a batch is split over
+//! follows the machine, and NaN payloads and the sign of zero depend on
+//! the instruction path that produced them. [`Determinism::Strict`] pins
+//! both, and [`self_check`] proves a grade renders bit-identically before
+//! a job is archived.
+//!
+//! Renders that honor the mode also evaluate every pixel exactly instead
+//! of through a baked LUT; see `crispen-script`'s `render`.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::evaluate::{
+    OutputEncoding, batch_chunk_pixels, evaluate_transform, evaluate_transform_batch_with,
+};
+use super::lut::Lut3D;
+use super::params::GradingParams;
+
+/// Threads a [`Determinism::Strict`] batch is split over on every machine.
+pub const STRICT_THREADS: usize = 4;
+
+/// Grid size of the probe lattice and LUT used by [`self_check`].
+const CHECK_GRID: u32 = 17;
+
+/// Values outside 0..=1 added to the probe so clamping and extrapolation
+/// are covered too.
+const CHECK_EXTRAS: [[f32; 3]; 4] = [
+    [-0.05, 0.0, 0.02],
+    [1.2, 1.1, 1.05],
+    [4.0, 0.5, 0.01],
+    [0.0, 0.0, 0.0],
+];
+
+/// How strictly evaluation pins down its output bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Determinism {
+    /// Use every core; leave NaN payloads and negative zero as computed.
+    #[default]
+    Fast,
+    /// Split batches over [`STRICT_THREADS`] threads, turn every NaN into
+    /// the canonical quiet NaN and `-0.0` into `0.0`, and skip LUT
+    /// approximations in renders.
+    Strict,
+}
+
+impl Determinism {
+    /// Whether this is [`Determinism::Strict`].
+    pub fn is_strict(self) -> bool {
+        self == Self::Strict
+    }
+
+    /// Threads a batch is split over.
+    pub fn batch_threads(self) -> usize {
+        match self {
+            Self::Fast => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            Self::Strict => STRICT_THREADS,
+        }
+    }
+
+    /// An output pixel with its bits made canonical in strict mode.
+    pub fn canonicalize(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::Fast => rgb,
+            Self::Strict => rgb.map(|v| if v.is_nan() { f32::NAN } else { v + 0.0 }),
+        }
+    }
+}
+
+/// FNV-1a digest of the bit patterns of `values`.
+///
+/// Stable across Rust versions and platforms, unlike `std`'s hasher, so
+/// digests can be stored next to archived renders and compared later.
+pub fn digest(values: &[f32]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    values
+        .iter()
+        .flat_map(|v| v.to_bits().to_le_bytes())
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+/// Outcome of [`self_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeterminismReport {
+    /// Strict batch renders compared.
+    pub runs: usize,
+    /// Pixels in each render.
+    pub samples: usize,
+    /// Threads each strict render was split over.
+    pub chunks: usize,
+    /// Digest of the first strict render.
+    pub digest: u64,
+    /// Zero-based runs whose output differed from the first.
+    pub mismatched_runs: Vec<usize>,
+    /// Whether one-thread, strict, and all-core evaluation agreed.
+    pub thread_independent: bool,
+    /// Digest of the first baked LUT.
+    pub lut_digest: u64,
+    /// Whether baking the LUT again gave the same bits.
+    pub lut_repeatable: bool,
+}
+
+impl DeterminismReport {
+    /// Whether every comparison matched.
+    pub fn passed(&self) -> bool {
+        self.mismatched_runs.is_empty() && self.thread_independent && self.lut_repeatable
+    }
+}
+
+impl fmt::Display for DeterminismReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = |ok: bool| if ok { "ok" } else { "MISMATCH" };
+        writeln!(
+            f,
+            "renders: {} x {} pixels over {} threads, digest {:016x}, {}",
+            self.runs,
+            self.samples,
+            self.chunks,
+            self.digest,
+            verdict(self.mismatched_runs.is_empty())
+        )?;
+        if !self.mismatched_runs.is_empty() {
+            writeln!(f, "  differing runs: {:?}", self.mismatched_runs)?;
+        }
+        writeln!(f, "thread count: {}", verdict(self.thread_independent))?;
+        write!(
+            f,
+            "LUT {CHECK_GRID}³: digest {:016x}, {}",
+            self.lut_digest,
+            verdict(self.lut_repeatable)
+        )
+    }
+}
+
+/// Probe pixels: a `CHECK_GRID`³ lattice over 0..=1 plus [`CHECK_EXTRAS`].
+fn probe() -> Vec<f32> {
+    let last = (CHECK_GRID - 1) as f32;
+    let lattice = (0..CHECK_GRID.pow(3)).flat_map(|i| {
+        let r = i % CHECK_GRID;
+        let g = i / CHECK_GRID % CHECK_GRID;
+        let b = i / (CHECK_GRID * CHECK_GRID);
+        [r, g, b].map(|v| v as f32 / last)
+    });
+    lattice.chain(CHECK_EXTRAS.into_iter().flatten()).collect()
+}
+
+/// Render a fixed probe of `params` `runs` times in strict mode and check
+/// every render, every thread split, and a repeated LUT bake agree to the
+/// bit.
+pub fn self_check(params: &GradingParams, runs: usize) -> DeterminismReport {
+    let probe = probe();
+    let render = || {
+        evaluate_transform_batch_with(&probe, params, OutputEncoding::Display, Determinism::Strict)
+    };
+
+    let first = render();
+    let digest_first = digest(&first);
+    let mismatched_runs = (1..runs.max(1))
+        .filter(|_| digest(&render()) != digest_first)
+        .collect();
+
+    let single: Vec<f32> = probe
+        .chunks_exact(3)
+        .flat_map(|p| {
+            Determinism::Strict.canonicalize(evaluate_transform([p[0], p[1], p[2]], params))
+        })
+        .collect();
+    let all_cores: Vec<f32> =
+        evaluate_transform_batch_with(&probe, params, OutputEncoding::Display, Determinism::Fast)
+            .chunks_exact(3)
+            .flat_map(|p| Determinism::Strict.canonicalize([p[0], p[1], p[2]]))
+            .collect();
+    let thread_independent = digest(&single) == digest_first && digest(&all_cores) == digest_first;
+
+    let bake = || {
+        let mut lut = Lut3D::new(CHECK_GRID);
+        lut.bake(params);
+        digest(lut.data.as_flattened())
+    };
+    let lut_digest = bake();
+
+    let samples = probe.len() / 3;
+    DeterminismReport {
+        runs: runs.max(1),
+        samples,
+        chunks: samples.div_ceil(batch_chunk_pixels(samples, Determinism::Strict)),
+        digest: digest_first,
+        mismatched_runs,
+        thread_independent,
+        lut_digest,
+        lut_repeatable: bake() == lut_digest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strict_canonicalizes_nan_and_negative_zero() {
+        let odd_nan = f32::from_bits(0x7fc0_1234);
+        let out = Determinism::Strict.canonicalize([odd_nan, -0.0, 0.5]);
+        assert_eq!(out[0].to_bits(), f32::NAN.to_bits());
+        assert_eq!(out[1].to_bits(), 0.0f32.to_bits());
+        assert_eq!(out[2], 0.5);
+        let fast = Determinism::Fast.canonicalize([odd_nan, -0.0, 0.5]);
+        assert_eq!(fast[0].to_bits(), odd_nan.to_bits());
+    }
+
+    #[test]
+    fn test_digest_is_stable() {
+        // Pinned so archived digests stay comparable across releases.
+        assert_eq!(digest(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(digest(&[1.0]), 0x4b72_477f_9c5c_2f98);
+        assert_ne!(digest(&[0.0]), digest(&[-0.0]));
+    }
+
+    #[test]
+    fn test_self_check_passes_for_a_busy_grade() {
+        let params = GradingParams {
+            temperature: 20.0,
+            gain: [1.2, 1.0, 0.8, 1.0],
+            contrast: 1.3,
+            saturation: 1.2,
+            hue_vs_sat: vec![[0.0, 1.0], [0.5, 1.5], [1.0, 1.0]],
+            ..GradingParams::default()
+        };
+        let report = self_check(&params, 3);
+        assert!(report.passed(), "{report}");
+        assert_eq!(report.samples, 17 * 17 * 17 + CHECK_EXTRAS.len());
+        assert_eq!(report.chunks, STRICT_THREADS);
+        assert_eq!(report.digest, self_check(&params, 1).digest);
+    }
+}
@@ -5,10 +5,34 @@
 
 use crate::color_management::aces::{apply_input_transform, apply_output_transform};
 use crate::color_management::white_balance::apply_white_balance;
+use crate::grading::channel_mixer::apply_channel_mixer;
+use crate::grading::chart_match::apply_input_correction;
 use crate::grading::curves::apply_curves;
-use crate::grading::sliders::{apply_contrast, apply_saturation_hue, apply_shadows_highlights};
+use crate::grading::exposure::{apply_exposure, apply_log_offset};
+use crate::grading::monochrome::apply_monochrome;
+use crate::grading::sliders::{
+    apply_contrast, apply_input_levels, apply_saturation, apply_shadows_highlights, apply_vibrance,
+};
+use crate::grading::soft_clip::apply_soft_clip;
 use crate::grading::wheels::apply_cdl;
-use crate::transform::params::GradingParams;
+use crate::transform::determinism::Determinism;
+use crate::transform::lut::Lut3D;
+use crate::transform::params::{ColorManagementConfig, ColorSpaceId, GradingParams};
+
+/// Pixels below which [`evaluate_transform_batch`] stays on the calling
+/// thread; spawning costs more than grading this many.
+const BATCH_PIXELS_PER_THREAD: usize = 4096;
+
+/// Where [`evaluate_transform_batch`] stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputEncoding {
+    /// Working-space linear, before the output transform (steps 0–11).
+    Working,
+    /// The configured output space, same as [`evaluate_transform`].
+    Display,
+    /// Another output space in place of the configured one.
+    Space(ColorSpaceId),
+}
 
 /// Apply the complete grading transform chain to a single RGB pixel.
 ///
@@ -18,23 +42,211 @@ use crate::transform::params::GradingParams;
 /// ```text
 /// Input RGB
 ///   │
+///   ├─ 0. Input levels (source range + per-channel black/white points) and log offset
 ///   ├─ 1. Input color space transform (linearize + gamut convert)
-///   ├─ 2. White balance (Bradford chromatic adaptation)
-///   ├─ 3. CDL (lift/gamma/gain/offset color wheels)
-///   ├─ 4. Contrast with pivot
-///   ├─ 5. Shadows/highlights recovery
-///   ├─ 6. Saturation and hue rotation
-///   ├─ 7. Curve adjustments (hue-vs-hue, hue-vs-sat, etc.)
-///   ├─ 8. Output color space transform (gamut convert + encode)
+///   ├─ 2. Exposure in stops, then input correction (chart-matched 1D + matrix)
+///   ├─ 3. White balance (Bradford chromatic adaptation)
+///   ├─ 4. Channel mixer (3×3 + offset)
+///   ├─ 5. CDL (lift/gamma/gain/offset color wheels)
+///   ├─ 6. Contrast with pivot
+///   ├─ 7. Shadows/highlights recovery
+///   ├─ 8. Vibrance, saturation (luma, OKLab, or JzAzBz) and hue rotation (RGB or OKLCh, per range)
+///   ├─ 9. Curve adjustments (hue-vs-hue, hue-vs-sat, etc.)
+///   ├─ 10. Black and white (channel weights, film response, tint)
+///   ├─ 11. Highlight soft clip and shadow toe, then steps 2–11 blended over their input (layer blend and mix)
+///   ├─ 12. Output color space transform (gamut convert + encode)
 ///   │
 ///   └─→ Output RGB
 /// ```
 ///
 /// The GPU shader must match this order exactly.
 pub fn evaluate_transform(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
+    let rgb = apply_source_levels(rgb, params);
+    evaluate_working(apply_input_stage(rgb, params), params)
+}
+
+/// [`evaluate_transform`] with an imported input LUT in place of step 1.
+/// Input levels and the log offset still apply first, to the code values
+/// the LUT samples.
+///
+/// `input_lut` comes from [`bake_input_lut`](crate::transform::lut_domain::bake_input_lut)
+/// and maps source code values straight to working-space linear. While
+/// the input LUT or the input transform is bypassed this is the same as
+/// [`evaluate_transform`].
+pub fn evaluate_with_input_lut(
+    rgb: [f32; 3],
+    params: &GradingParams,
+    input_lut: &Lut3D,
+) -> [f32; 3] {
+    if params.bypass.input_lut || params.bypass.input_transform {
+        return evaluate_transform(rgb, params);
+    }
+    let rgb = apply_source_levels(rgb, params);
+    evaluate_working(input_lut.apply(rgb.map(|v| v.clamp(0.0, 1.0))), params)
+}
+
+/// [`evaluate_transform`] over many pixels, split across threads.
+///
+/// `rgb` holds interleaved R, G, B triples; the result has the same
+/// layout and length. `encoding` picks whether to stop in the working
+/// space or to finish with an output transform.
+///
+/// # Panics
+///
+/// If `rgb.len()` is not a multiple of 3.
+pub fn evaluate_transform_batch(
+    rgb: &[f32],
+    params: &GradingParams,
+    encoding: OutputEncoding,
+) -> Vec<f32> {
+    evaluate_transform_batch_with(rgb, params, encoding, Determinism::Fast)
+}
+
+/// [`evaluate_transform_batch`] under an explicit [`Determinism`] mode.
+///
+/// [`Determinism::Strict`] splits the work over a fixed number of threads
+/// and canonicalizes the output bits; see [`Determinism`].
+///
+/// # Panics
+///
+/// If `rgb.len()` is not a multiple of 3.
+pub fn evaluate_transform_batch_with(
+    rgb: &[f32],
+    params: &GradingParams,
+    encoding: OutputEncoding,
+    determinism: Determinism,
+) -> Vec<f32> {
+    assert!(
+        rgb.len().is_multiple_of(3),
+        "batch length {} is not a whole number of RGB pixels",
+        rgb.len()
+    );
+    let output_config = match encoding {
+        OutputEncoding::Working => None,
+        OutputEncoding::Display => Some(params.color_management.clone()),
+        OutputEncoding::Space(space) => Some(ColorManagementConfig {
+            output_space: space,
+            ..params.color_management.clone()
+        }),
+    };
+    let grade = |src: &[f32], dst: &mut [f32]| {
+        for (src, dst) in src.chunks_exact(3).zip(dst.chunks_exact_mut(3)) {
+            let code = apply_source_levels([src[0], src[1], src[2]], params);
+            let linear = apply_input_stage(code, params);
+            let graded = apply_working_grade(linear, params);
+            let out = match &output_config {
+                Some(config) => apply_output_stage(graded, params, config),
+                None => graded,
+            };
+            dst.copy_from_slice(&determinism.canonicalize(out));
+        }
+    };
+
+    let mut out = vec![0.0; rgb.len()];
+    let pixels = rgb.len() / 3;
+    let chunk_pixels = batch_chunk_pixels(pixels, determinism);
+    if chunk_pixels >= pixels {
+        grade(rgb, &mut out);
+        return out;
+    }
+    std::thread::scope(|scope| {
+        for (src, dst) in rgb
+            .chunks(chunk_pixels * 3)
+            .zip(out.chunks_mut(chunk_pixels * 3))
+        {
+            let grade = &grade;
+            scope.spawn(move || grade(src, dst));
+        }
+    });
+    out
+}
+
+/// Pixels each thread of a batch of `pixels` grades.
+///
+/// Fast batches keep at least [`BATCH_PIXELS_PER_THREAD`] per thread.
+/// Strict batches always split over
+/// [`STRICT_THREADS`](crate::transform::determinism::STRICT_THREADS), so
+/// small ones such as the self-check probe exercise the split too.
+pub(crate) fn batch_chunk_pixels(pixels: usize, determinism: Determinism) -> usize {
+    let per_thread = pixels.div_ceil(determinism.batch_threads());
+    match determinism {
+        Determinism::Fast => per_thread.max(BATCH_PIXELS_PER_THREAD),
+        Determinism::Strict => per_thread.max(1),
+    }
+}
+
+/// Step 0 of the chain: input levels, then the exposure log offset, on
+/// source code values.
+pub fn apply_source_levels(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
+    let bypass = &params.bypass;
+    let mut c = rgb;
+    if !bypass.input_levels {
+        c = apply_input_levels(c, &params.input_levels);
+    }
+    if !bypass.exposure {
+        c = apply_log_offset(c, &params.exposure);
+    }
+    c
+}
+
+/// Step 1 of the chain: the grade's input transform, unless bypassed.
+pub fn apply_input_stage(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
+    if params.bypass.input_transform {
+        return rgb;
+    }
+    apply_input_transform(rgb, &params.color_management)
+}
+
+/// Step 12 of the chain: the output transform `config` describes, unless
+/// the grade bypasses it.
+pub fn apply_output_stage(
+    rgb: [f32; 3],
+    params: &GradingParams,
+    config: &ColorManagementConfig,
+) -> [f32; 3] {
+    if params.bypass.output_transform {
+        return rgb;
+    }
+    apply_output_transform(rgb, config)
+}
+
+/// Steps 2–12 of the chain, starting from working-space linear.
+fn evaluate_working(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
+    apply_output_stage(
+        apply_working_grade(rgb, params),
+        params,
+        &params.color_management,
+    )
+}
+
+/// Steps 2–11 of the chain: the grade alone, working-space linear in and out.
+/// The result is blended over `rgb` by the grade's
+/// [`layer_blend`](GradingParams::layer_blend).
+///
+/// Used to stack several grades (e.g. group pre-clip → clip → group
+/// post-clip) between a single pair of input and output transforms.
+pub fn apply_working_grade(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
+    let bypass = &params.bypass;
+    let working_space = params.color_management.working_space;
     let mut c = rgb;
-    c = apply_input_transform(c, &params.color_management);
-    c = apply_white_balance(c, params.temperature, params.tint);
+    if !bypass.exposure {
+        c = apply_exposure(c, &params.exposure);
+    }
+    if !bypass.input_correction {
+        c = apply_input_correction(c, &params.input_correction);
+    }
+    if !bypass.white_balance {
+        c = apply_white_balance(
+            c,
+            params.temperature,
+            params.tint,
+            params.white_balance_model,
+            working_space,
+        );
+    }
+    if !bypass.channel_mixer {
+        c = apply_channel_mixer(c, &params.channel_mixer);
+    }
     c = apply_cdl(
         c,
         &params.combined_lift(),
@@ -42,12 +254,32 @@ pub fn evaluate_transform(rgb: [f32; 3], params: &GradingParams) -> [f32; 3] {
         &params.combined_gain(),
         &params.combined_offset(),
     );
-    c = apply_contrast(c, params.contrast, params.pivot);
-    c = apply_shadows_highlights(c, params.shadows, params.highlights);
-    c = apply_saturation_hue(c, params.saturation, params.hue, params.luma_mix);
+    if !bypass.contrast {
+        c = apply_contrast(c, params.contrast, params.pivot);
+    }
+    if !bypass.shadows_highlights {
+        c = apply_shadows_highlights(c, params.shadows, params.highlights);
+    }
+    if !bypass.color {
+        c = apply_vibrance(c, params.vibrance, params.saturation_model, working_space);
+        c = apply_saturation(
+            c,
+            params.saturation,
+            params.hue,
+            &params.hue_rotation,
+            params.luma_mix,
+            params.saturation_model,
+            working_space,
+        );
+    }
     c = apply_curves(c, params);
-    c = apply_output_transform(c, &params.color_management);
-    c
+    if !bypass.monochrome {
+        c = apply_monochrome(c, &params.monochrome);
+    }
+    if !bypass.soft_clip {
+        c = apply_soft_clip(c, &params.soft_clip);
+    }
+    params.layer_blend.apply(rgb, c)
 }
 
 #[cfg(test)]
@@ -75,6 +307,7 @@ mod tests {
                 working_space: ColorSpaceId::AcesCg,
                 output_space: ColorSpaceId::AcesCg,
                 display_oetf: DisplayOetf::Srgb,
+                ..Default::default()
             },
             ..GradingParams::default()
         };
@@ -99,6 +332,7 @@ mod tests {
                 working_space: ColorSpaceId::AcesCg,
                 output_space: ColorSpaceId::AcesCg,
                 display_oetf: DisplayOetf::Srgb,
+                ..Default::default()
             },
             ..GradingParams::default()
         };
@@ -107,4 +341,92 @@ mod tests {
             assert!(channel.abs() < EPSILON);
         }
     }
+
+    #[test]
+    fn test_exposure_offsets_code_then_scales_linear() {
+        use crate::grading::exposure::Exposure;
+        use crate::transform::params::{ColorManagementConfig, ColorSpaceId, DisplayOetf};
+        let params = GradingParams {
+            color_management: ColorManagementConfig {
+                input_space: ColorSpaceId::AcesCg,
+                working_space: ColorSpaceId::AcesCg,
+                output_space: ColorSpaceId::AcesCg,
+                display_oetf: DisplayOetf::Srgb,
+                ..Default::default()
+            },
+            exposure: Exposure {
+                stops: 1.0,
+                log_offset: 0.1,
+            },
+            ..GradingParams::default()
+        };
+        let result = evaluate_transform([0.2, 0.3, 0.4], &params);
+        for (channel, want) in result.iter().zip([0.6, 0.8, 1.0]) {
+            assert!((channel - want).abs() < EPSILON, "{result:?}");
+        }
+    }
+
+    #[test]
+    fn test_bypassed_wheels_match_neutral_grade() {
+        let mut params = GradingParams {
+            lift_wheel: [0.1, -0.05, 0.0, 0.02],
+            gain: [1.2, 1.0, 0.9, 1.1],
+            contrast: 1.2,
+            ..GradingParams::default()
+        };
+        params.bypass.wheels = true;
+        let neutral = GradingParams {
+            contrast: 1.2,
+            ..GradingParams::default()
+        };
+        let rgb = [0.5, 0.3, 0.7];
+        assert_eq!(
+            evaluate_transform(rgb, &params),
+            evaluate_transform(rgb, &neutral)
+        );
+    }
+
+    #[test]
+    fn test_bypassed_input_lut_uses_input_transform() {
+        let mut params = GradingParams::default();
+        params.bypass.input_lut = true;
+        // Maps everything to black, so any use of it would show.
+        let lut = Lut3D::new(2);
+        let rgb = [0.5, 0.3, 0.7];
+        assert_eq!(
+            evaluate_with_input_lut(rgb, &params, &lut),
+            evaluate_transform(rgb, &params)
+        );
+    }
+
+    #[test]
+    fn test_batch_matches_per_pixel_evaluation() {
+        let params = GradingParams {
+            saturation: 1.3,
+            gain: [1.1, 1.0, 0.9, 1.0],
+            ..GradingParams::default()
+        };
+        // Enough pixels to take the threaded path.
+        let rgb: Vec<f32> = (0..BATCH_PIXELS_PER_THREAD * 3 * 3)
+            .map(|i| (i % 97) as f32 / 96.0)
+            .collect();
+
+        let display = evaluate_transform_batch(&rgb, &params, OutputEncoding::Display);
+        let working = evaluate_transform_batch(&rgb, &params, OutputEncoding::Working);
+        let same_space = evaluate_transform_batch(
+            &rgb,
+            &params,
+            OutputEncoding::Space(params.color_management.output_space),
+        );
+        assert_eq!(display, same_space);
+        for (i, px) in rgb.chunks_exact(3).enumerate() {
+            let px = [px[0], px[1], px[2]];
+            assert_eq!(display[i * 3..i * 3 + 3], evaluate_transform(px, &params));
+            let linear = apply_input_transform(px, &params.color_management);
+            assert_eq!(
+                working[i * 3..i * 3 + 3],
+                apply_working_grade(linear, &params)
+            );
+        }
+    }
 }
@@ -0,0 +1,477 @@
+//! Pipeline inspection — the ordered stages a grade runs through.
+//!
+//! [`pipeline_stages()`] lists every stage of the viewer chain in the
+//! order the GPU runs it: the spatial passes on the source, the stages
+//! baked into the 3D LUT (mirroring [`crate::transform::evaluate`]), the
+//! spatial passes on the graded image, and the display encode. Each row
+//! says whether it changes the image, whether it is bypassed, and the
+//! matrix or LUT it applies, so a colorist can see exactly what stands
+//! between the source and the monitor.
+//!
+//! The chain has no secondary qualifier or display calibration stage, so
+//! neither appears.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::params::{ColorSpaceId, DisplayOetf, GradingParams, NegativePolicy, ToolBypass};
+use crate::color_management::color_space::get_conversion_matrix;
+use crate::color_management::white_balance::white_balance_matrix;
+
+/// Where a stage runs relative to the baked 3D LUT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StagePlacement {
+    /// A spatial pass on source pixels, ahead of the LUT.
+    BeforeLut,
+    /// Baked into the 3D LUT.
+    Lut,
+    /// A spatial pass on graded pixels, after the LUT.
+    AfterLut,
+    /// The framebuffer's encode for the monitor.
+    Display,
+}
+
+/// One stage of the chain.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PipelineStage {
+    /// The stage's [`ToolBypass`] field, or an id for stages without one.
+    pub key: String,
+    /// Display name.
+    pub name: String,
+    /// Where the stage runs.
+    pub placement: StagePlacement,
+    /// Whether the stage changes the image: not identity and not bypassed.
+    pub active: bool,
+    /// Bypass state, `None` for stages that cannot be bypassed.
+    pub bypassed: Option<bool>,
+    /// Short summary of the stage's settings.
+    pub detail: String,
+    /// The 3×3 matrix the stage applies, when it applies one.
+    pub matrix: Option<[[f32; 3]; 3]>,
+    /// Edge length of the 3D LUT the stage applies, when it applies one.
+    pub lut_size: Option<u32>,
+}
+
+/// The stages `params` runs through in the viewer, in order. `lut_size`
+/// is the baked LUT's edge length and `input_lut` the imported input
+/// LUT's, if one is loaded.
+pub fn pipeline_stages(
+    params: &GradingParams,
+    lut_size: u32,
+    input_lut: Option<u32>,
+) -> Vec<PipelineStage> {
+    let bypass = &params.bypass;
+    let cm = &params.color_management;
+    let mut stages = Vec::new();
+
+    stages.push(fixed(
+        "chromatic_aberration",
+        "Chromatic aberration",
+        StagePlacement::BeforeLut,
+        !params.chromatic_aberration.is_identity(),
+        String::new(),
+    ));
+    stages.push(fixed(
+        "dehaze",
+        "Dehaze",
+        StagePlacement::BeforeLut,
+        !params.dehaze.is_identity(),
+        format!("strength {:+.2}", params.dehaze.strength),
+    ));
+
+    stages.push(baked(
+        bypass,
+        "input_levels",
+        "Input levels",
+        !params.input_levels.is_identity(),
+        format!(
+            "{:?} range, black {:.3?}, white {:.3?}",
+            params.input_levels.range, params.input_levels.black, params.input_levels.white
+        ),
+    ));
+    let uses_input_lut = input_lut.is_some() && !bypass.input_lut;
+    let mut input = baked(
+        bypass,
+        "input_transform",
+        "Input transform",
+        uses_input_lut || cm.input_space != cm.working_space,
+        if uses_input_lut {
+            "imported input LUT".to_string()
+        } else {
+            space_change(cm.input_space, cm.working_space, cm.negative_policy)
+        },
+    );
+    if uses_input_lut {
+        input.lut_size = input_lut;
+    } else {
+        input.matrix = conversion(cm.input_space, cm.working_space);
+    }
+    stages.push(input);
+    if let Some(size) = input_lut {
+        let mut stage = baked(
+            bypass,
+            "input_lut",
+            "Input LUT",
+            !bypass.input_transform,
+            "in place of the input transform".to_string(),
+        );
+        stage.lut_size = Some(size);
+        stages.push(stage);
+    }
+    stages.push(baked(
+        bypass,
+        "exposure",
+        "Exposure",
+        !params.exposure.is_identity(),
+        format!(
+            "{:+.2} stops, log offset {:+.3}",
+            params.exposure.stops, params.exposure.log_offset
+        ),
+    ));
+    let mut correction = baked(
+        bypass,
+        "input_correction",
+        "Input correction",
+        !params.input_correction.is_identity(),
+        format!(
+            "gain {:.3?}, power {:.3?}",
+            params.input_correction.gain, params.input_correction.power
+        ),
+    );
+    correction.matrix = Some(params.input_correction.matrix);
+    stages.push(correction);
+    let mut white_balance = baked(
+        bypass,
+        "white_balance",
+        "Temperature / tint",
+        params.temperature != 0.0 || params.tint != 0.0,
+        format!(
+            "{:?}, temperature {:+.2}, tint {:+.2}",
+            params.white_balance_model, params.temperature, params.tint
+        ),
+    );
+    let wb = white_balance_matrix(
+        params.temperature,
+        params.tint,
+        params.white_balance_model,
+        params.color_management.working_space,
+    );
+    white_balance.matrix = Some(wb.0.map(|row| row.map(|v| v as f32)));
+    stages.push(white_balance);
+    let mut mixer = baked(
+        bypass,
+        "channel_mixer",
+        "Channel mixer",
+        !params.channel_mixer.is_identity(),
+        format!("offset {:.3?}", params.channel_mixer.offset),
+    );
+    mixer.matrix = Some(params.channel_mixer.matrix);
+    stages.push(mixer);
+    let mut wheels = params.clone();
+    wheels.bypass.wheels = false;
+    stages.push(baked(
+        bypass,
+        "wheels",
+        "Lift / gamma / gain / offset",
+        [
+            (wheels.combined_lift(), 0.0),
+            (wheels.combined_gamma(), 1.0),
+            (wheels.combined_gain(), 1.0),
+            (wheels.combined_offset(), 0.0),
+        ]
+        .iter()
+        .any(|(v, identity)| v.iter().any(|c| c != identity)),
+        "wheels and bars".to_string(),
+    ));
+    stages.push(baked(
+        bypass,
+        "contrast",
+        "Contrast",
+        params.contrast != 1.0,
+        format!("{:.2} around pivot {:.3}", params.contrast, params.pivot),
+    ));
+    stages.push(baked(
+        bypass,
+        "shadows_highlights",
+        "Shadows / highlights",
+        params.shadows != 0.0 || params.highlights != 0.0,
+        format!(
+            "shadows {:+.2}, highlights {:+.2}",
+            params.shadows, params.highlights
+        ),
+    ));
+    stages.push(baked(
+        bypass,
+        "color",
+        "Vibrance / saturation / hue",
+        params.vibrance != 0.0
+            || params.saturation != 1.0
+            || params.hue != 0.0
+            || params.hue_rotation.ranges != [0.0; 3],
+        format!(
+            "{:?}, saturation {:.2}, vibrance {:+.2}, hue {:+.1}°",
+            params.saturation_model, params.saturation, params.vibrance, params.hue
+        ),
+    ));
+    let mut curves = params.clone();
+    curves.bypass.curves = false;
+    let points = [
+        &params.hue_vs_hue,
+        &params.hue_vs_sat,
+        &params.lum_vs_sat,
+        &params.sat_vs_sat,
+    ]
+    .iter()
+    .map(|c| c.len())
+    .sum::<usize>();
+    stages.push(baked(
+        bypass,
+        "curves",
+        "Curves",
+        curves.has_active_curves(),
+        format!("{points} points"),
+    ));
+    stages.push(baked(
+        bypass,
+        "monochrome",
+        "Black and white",
+        params.monochrome.enabled,
+        format!("weights {:.3?}", params.monochrome.weights),
+    ));
+    stages.push(baked(
+        bypass,
+        "soft_clip",
+        "Soft clip",
+        !params.soft_clip.is_identity(),
+        format!(
+            "knee {:.2}, toe {:.2}",
+            params.soft_clip.knee, params.soft_clip.toe
+        ),
+    ));
+    stages.push(fixed(
+        "layer_blend",
+        "Layer blend",
+        StagePlacement::Lut,
+        !params.layer_blend.is_identity(),
+        format!(
+            "{:?} at {:.0}%",
+            params.layer_blend.mode,
+            params.layer_blend.mix * 100.0
+        ),
+    ));
+    let mut output = baked(
+        bypass,
+        "output_transform",
+        "Output transform",
+        cm.working_space != cm.output_space,
+        space_change(cm.working_space, cm.output_space, cm.negative_policy),
+    );
+    output.matrix = conversion(cm.working_space, cm.output_space);
+    stages.push(output);
+    for stage in &mut stages {
+        if stage.placement == StagePlacement::Lut {
+            stage.lut_size = stage.lut_size.or(Some(lut_size));
+        }
+    }
+
+    stages.push(fixed(
+        "glow",
+        "Glow",
+        StagePlacement::AfterLut,
+        !params.glow.is_identity(),
+        String::new(),
+    ));
+    stages.push(fixed(
+        "display",
+        "Display encode",
+        StagePlacement::Display,
+        cm.display_oetf != DisplayOetf::Linear,
+        format!("{:?} framebuffer OETF", cm.display_oetf),
+    ));
+    stages
+}
+
+/// A stage baked into the LUT, bypassed through `key`.
+fn baked(
+    bypass: &ToolBypass,
+    key: &str,
+    name: &str,
+    changes: bool,
+    detail: String,
+) -> PipelineStage {
+    let bypassed = is_bypassed(bypass, key);
+    PipelineStage {
+        key: key.to_string(),
+        name: name.to_string(),
+        placement: StagePlacement::Lut,
+        active: changes && !bypassed,
+        bypassed: Some(bypassed),
+        detail,
+        matrix: None,
+        lut_size: None,
+    }
+}
+
+/// A stage with no bypass flag.
+fn fixed(
+    key: &str,
+    name: &str,
+    placement: StagePlacement,
+    active: bool,
+    detail: String,
+) -> PipelineStage {
+    PipelineStage {
+        key: key.to_string(),
+        name: name.to_string(),
+        placement,
+        active,
+        bypassed: None,
+        detail,
+        matrix: None,
+        lut_size: None,
+    }
+}
+
+fn is_bypassed(bypass: &ToolBypass, key: &str) -> bool {
+    match key {
+        "input_levels" => bypass.input_levels,
+        "input_transform" => bypass.input_transform,
+        "input_lut" => bypass.input_lut,
+        "exposure" => bypass.exposure,
+        "input_correction" => bypass.input_correction,
+        "white_balance" => bypass.white_balance,
+        "channel_mixer" => bypass.channel_mixer,
+        "wheels" => bypass.wheels,
+        "contrast" => bypass.contrast,
+        "shadows_highlights" => bypass.shadows_highlights,
+        "color" => bypass.color,
+        "curves" => bypass.curves,
+        "monochrome" => bypass.monochrome,
+        "soft_clip" => bypass.soft_clip,
+        "output_transform" => bypass.output_transform,
+        _ => false,
+    }
+}
+
+fn space_change(from: ColorSpaceId, to: ColorSpaceId, policy: NegativePolicy) -> String {
+    match policy {
+        NegativePolicy::Allow => format!("{} → {}", from.label(), to.label()),
+        _ => format!("{} → {}, negatives {policy:?}", from.label(), to.label()),
+    }
+}
+
+/// The gamut matrix between two spaces, if they differ in gamut.
+fn conversion(from: ColorSpaceId, to: ColorSpaceId) -> Option<[[f32; 3]; 3]> {
+    let m = get_conversion_matrix(from, to).0;
+    let m = m.map(|row| row.map(|v| v as f32));
+    (m != [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]).then_some(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::evaluate::evaluate_transform;
+
+    fn stage<'a>(stages: &'a [PipelineStage], key: &str) -> &'a PipelineStage {
+        stages.iter().find(|s| s.key == key).unwrap()
+    }
+
+    #[test]
+    fn test_stages_follow_chain_order() {
+        let stages = pipeline_stages(&GradingParams::default(), 65, None);
+        let keys: Vec<_> = stages.iter().map(|s| s.key.as_str()).collect();
+        let pos = |k: &str| keys.iter().position(|&x| x == k).unwrap();
+        assert!(pos("dehaze") < pos("input_levels"));
+        assert!(pos("input_transform") < pos("wheels"));
+        assert!(pos("wheels") < pos("curves"));
+        assert!(pos("curves") < pos("layer_blend"));
+        assert!(pos("layer_blend") < pos("output_transform"));
+        assert!(pos("output_transform") < pos("glow"));
+        assert_eq!(keys.last(), Some(&"display"));
+        assert!(!keys.contains(&"input_lut"));
+        assert_eq!(stage(&stages, "curves").lut_size, Some(65));
+    }
+
+    #[test]
+    fn test_default_grade_only_transforms_color() {
+        let stages = pipeline_stages(&GradingParams::default(), 33, None);
+        let active: Vec<_> = stages
+            .iter()
+            .filter(|s| s.active)
+            .map(|s| s.key.as_str())
+            .collect();
+        assert_eq!(active, ["input_transform", "output_transform", "display"]);
+        let input = stage(&stages, "input_transform");
+        assert_eq!(input.detail, "sRGB → ACEScg");
+        assert!(input.matrix.is_some());
+    }
+
+    #[test]
+    fn test_bypass_marks_stage_inactive() {
+        let mut params = GradingParams {
+            saturation: 0.5,
+            ..GradingParams::default()
+        };
+        assert!(stage(&pipeline_stages(&params, 33, None), "color").active);
+        params.bypass.color = true;
+        let color = stage(&pipeline_stages(&params, 33, None), "color").clone();
+        assert!(!color.active);
+        assert_eq!(color.bypassed, Some(true));
+    }
+
+    #[test]
+    fn test_input_lut_replaces_transform_matrix() {
+        let stages = pipeline_stages(&GradingParams::default(), 33, Some(17));
+        let input = stage(&stages, "input_transform");
+        assert_eq!(input.lut_size, Some(17));
+        assert!(input.matrix.is_none());
+        assert!(stage(&stages, "input_lut").active);
+    }
+
+    #[test]
+    fn test_bypassed_sections_match_neutral_grade() {
+        let mut params = GradingParams {
+            exposure: crate::grading::exposure::Exposure {
+                stops: 1.0,
+                log_offset: 0.05,
+            },
+            temperature: 0.3,
+            contrast: 1.4,
+            shadows: 0.2,
+            saturation: 0.6,
+            ..GradingParams::default()
+        };
+        params.monochrome.enabled = true;
+        params.bypass = ToolBypass {
+            exposure: true,
+            white_balance: true,
+            contrast: true,
+            shadows_highlights: true,
+            color: true,
+            monochrome: true,
+            ..ToolBypass::default()
+        };
+        let mut neutral = params.with_bypass_applied();
+        neutral.bypass = ToolBypass::default();
+        for rgb in [[0.1, 0.2, 0.3], [0.5, 0.5, 0.5], [0.9, 0.4, 0.05]] {
+            assert_eq!(
+                evaluate_transform(rgb, &params),
+                evaluate_transform(rgb, &neutral)
+            );
+        }
+
+        params.bypass.input_transform = true;
+        params.bypass.output_transform = true;
+        let mut raw = params.clone();
+        raw.color_management.input_space = ColorSpaceId::AcesCg;
+        raw.color_management.output_space = ColorSpaceId::AcesCg;
+        raw.bypass.input_transform = false;
+        raw.bypass.output_transform = false;
+        assert_eq!(
+            evaluate_transform([0.2, 0.4, 0.6], &params),
+            evaluate_transform([0.2, 0.4, 0.6], &raw)
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Look browser — installed LUTs and grade presets rendered as thumbnails.
+//!
+//! Auditioning looks one at a time means loading each, waiting for the
+//! full-size bake, and judging it from memory against the last one. The
+//! browser instead renders a small proxy of the current frame through
+//! every look at once:
+//!
+//! ```text
+//! frame ─ downscale ─▶ proxy ──┬─ look 1 preview LUT ─▶ thumbnail
+//!                              ├─ look 2 preview LUT ─▶ thumbnail
+//!                              └─ …
+//! ```
+//!
+//! Each look is reduced to a [`PREVIEW_LUT_SIZE`]³ LUT of the whole chain
+//! as it would run once the look is applied, so the GPU grades all the
+//! thumbnails in one batched dispatch. [`render_thumbnail`] is the CPU
+//! reference for that pass.
+
+use std::path::{Path, PathBuf};
+
+use crate::image::GradingImage;
+use crate::transform::evaluate::evaluate_with_input_lut;
+use crate::transform::lut::Lut3D;
+use crate::transform::lut_domain::{LutDomain, bake_input_lut};
+use crate::transform::params::GradingParams;
+
+/// Grid size of a look's preview LUT. Thumbnails are too small to show the
+/// difference from a full 65³ bake.
+pub const PREVIEW_LUT_SIZE: u32 = 17;
+
+/// Default thumbnail width in pixels; the height follows the frame.
+pub const THUMBNAIL_WIDTH: u32 = 160;
+
+/// Grid size of the input LUT baked for a LUT look's preview.
+const PREVIEW_INPUT_LUT_SIZE: u32 = 33;
+
+/// Errors from reading a look.
+#[derive(Debug, thiserror::Error)]
+pub enum LookError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid grade preset: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// What a look file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LookKind {
+    /// A `.cube` LUT, applied as the input LUT.
+    Lut,
+    /// A `.json` [`GradingParams`] preset, applied as the grade.
+    Grade,
+}
+
+/// One installed look.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookEntry {
+    /// Display name: the file stem.
+    pub name: String,
+    /// Path of the look file.
+    pub path: PathBuf,
+    /// LUT or grade preset.
+    pub kind: LookKind,
+}
+
+/// List the looks in `dirs`: every `.cube` and `.json` file directly inside
+/// each, sorted by name. Directories that do not exist are skipped.
+pub fn scan_looks(dirs: &[PathBuf]) -> Result<Vec<LookEntry>, LookError> {
+    let mut looks = Vec::new();
+    for dir in dirs {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if let Some(look) = LookEntry::from_path(path) {
+                looks.push(look);
+            }
+        }
+    }
+    looks.sort_by_key(|look| look.name.to_lowercase());
+    Ok(looks)
+}
+
+impl LookEntry {
+    /// The look at `path`, or `None` if it is neither a `.cube` nor a `.json`
+    /// file.
+    pub fn from_path(path: PathBuf) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let kind = match extension.as_str() {
+            "cube" => LookKind::Lut,
+            "json" => LookKind::Grade,
+            _ => return None,
+        };
+        let name = path.file_stem()?.to_string_lossy().into_owned();
+        Some(Self { name, path, kind })
+    }
+
+    /// Read a grade preset and adopt `current`'s color management, since
+    /// the footage's encoding belongs to the shot, not the look.
+    pub fn load_grade(&self, current: &GradingParams) -> Result<GradingParams, LookError> {
+        let mut params: GradingParams =
+            serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        params.color_management = current.color_management.clone();
+        Ok(params)
+    }
+
+    /// Bake the chain this look would produce on top of `current` into a
+    /// LUT over source code values 0..1.
+    ///
+    /// A LUT look becomes the input LUT, with its domain guessed from the
+    /// file name as on import, and `current` grades after it. A grade
+    /// preset replaces `current` as in [`Self::load_grade`].
+    pub fn preview_lut(&self, current: &GradingParams, size: u32) -> Result<Lut3D, LookError> {
+        let mut preview = Lut3D::new(size.max(2));
+        match self.kind {
+            LookKind::Grade => preview.bake(&self.load_grade(current)?),
+            LookKind::Lut => {
+                let cm = &current.color_management;
+                let lut = Lut3D::load_cube(&self.path)?;
+                let domain = LutDomain::guess(&self.name, cm.input_space);
+                let input = bake_input_lut(&lut, domain, cm, PREVIEW_INPUT_LUT_SIZE);
+                // The look is auditioned even if the current input LUT is
+                // bypassed.
+                let mut params = current.clone();
+                params.bypass.input_lut = false;
+                let n = (preview.size - 1) as f32;
+                for (i, entry) in preview.data.iter_mut().enumerate() {
+                    let i = i as u32;
+                    let s = preview.size;
+                    let source = [i % s, i / s % s, i / (s * s)].map(|v| v as f32 / n);
+                    let out = evaluate_with_input_lut(source, &params, &input);
+                    *entry = [out[0], out[1], out[2], 1.0];
+                }
+            }
+        }
+        Ok(preview)
+    }
+}
+
+/// Save `params` as a grade preset, e.g. a version of the current grade,
+/// that [`LookEntry::load_grade`] and bracketed stills read back.
+pub fn save_grade(params: &GradingParams, path: &Path) -> Result<(), LookError> {
+    std::fs::write(path, serde_json::to_string_pretty(params)?)?;
+    Ok(())
+}
+
+/// Downscale `image` to `width` pixels wide, keeping its aspect ratio, by
+/// averaging the source pixels under each proxy pixel. Images no wider
+/// than `width` are returned unchanged.
+pub fn thumbnail_source(image: &GradingImage, width: u32) -> GradingImage {
+    if image.width <= width || width == 0 {
+        return image.clone();
+    }
+    let height =
+        (u64::from(image.height) * u64::from(width) / u64::from(image.width)).max(1) as u32;
+    let span = |i: u32, out: u32, full: u32| {
+        let start = (u64::from(i) * u64::from(full) / u64::from(out)) as u32;
+        let end = (u64::from(i + 1) * u64::from(full) / u64::from(out)) as u32;
+        start..end.max(start + 1)
+    };
+
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        let rows = span(y, height, image.height);
+        for x in 0..width {
+            let cols = span(x, width, image.width);
+            let mut sum = [0.0f32; 4];
+            for sy in rows.clone() {
+                let row = (sy * image.width) as usize;
+                for sx in cols.clone() {
+                    let p = image.pixels[row + sx as usize];
+                    sum.iter_mut().zip(p).for_each(|(s, v)| *s += v);
+                }
+            }
+            let count = (rows.len() * cols.len()) as f32;
+            pixels.push(sum.map(|s| s / count));
+        }
+    }
+    GradingImage {
+        width,
+        height,
+        pixels,
+        source_bit_depth: image.source_bit_depth,
+    }
+}
+
+/// Grade `proxy` through a look's preview LUT into RGBA8 pixels, keeping
+/// the source alpha. CPU reference for the GPU look preview pass.
+pub fn render_thumbnail(proxy: &GradingImage, lut: &Lut3D) -> Vec<u8> {
+    let quantize = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+    proxy
+        .pixels
+        .iter()
+        .flat_map(|p| {
+            let rgb = lut.apply([p[0], p[1], p[2]]);
+            [rgb[0], rgb[1], rgb[2], p[3]].map(quantize)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+    use crate::transform::evaluate::evaluate_transform;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crispen_looks_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scan_finds_luts_and_presets_sorted() {
+        let dir = temp_dir("scan");
+        for file in ["warm.json", "Bleach.cube", "notes.txt", "cool.CUBE"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        let looks = scan_looks(&[dir.clone(), dir.join("missing")]).unwrap();
+        let names: Vec<_> = looks.iter().map(|l| (l.name.as_str(), l.kind)).collect();
+        assert_eq!(
+            names,
+            [
+                ("Bleach", LookKind::Lut),
+                ("cool", LookKind::Lut),
+                ("warm", LookKind::Grade),
+            ]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_grade_preview_keeps_the_shots_color_management() {
+        let dir = temp_dir("grade");
+        let look = GradingParams {
+            saturation: 0.0,
+            ..GradingParams::default()
+        };
+        let path = dir.join("mono.json");
+        std::fs::write(&path, serde_json::to_string(&look).unwrap()).unwrap();
+        let entry = LookEntry::from_path(path).unwrap();
+
+        let mut current = GradingParams::default();
+        current.color_management.input_space = crate::transform::params::ColorSpaceId::Srgb;
+        let lut = entry.preview_lut(&current, 9).unwrap();
+        let expected = evaluate_transform(
+            [1.0, 0.5, 0.0],
+            &GradingParams {
+                color_management: current.color_management.clone(),
+                ..look
+            },
+        );
+        let got = lut.apply([1.0, 0.5, 0.0]);
+        for c in 0..3 {
+            assert!(
+                (got[c] - expected[c]).abs() < 1e-5,
+                "{got:?} vs {expected:?}"
+            );
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_thumbnail_averages_and_quantizes() {
+        let image = GradingImage {
+            width: 4,
+            height: 2,
+            pixels: [[0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0, 1.0]].repeat(4),
+            source_bit_depth: BitDepth::F32,
+        };
+        let proxy = thumbnail_source(&image, 2);
+        assert_eq!((proxy.width, proxy.height), (2, 1));
+        assert_eq!(proxy.pixels, vec![[0.5, 0.5, 0.5, 1.0]; 2]);
+
+        let mut linear = Lut3D::new(2);
+        for (i, entry) in linear.data.iter_mut().enumerate() {
+            *entry = [(i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2) as f32, 1.0];
+        }
+        assert_eq!(
+            render_thumbnail(&proxy, &linear),
+            [128, 128, 128, 255].repeat(2)
+        );
+    }
+}
@@ -14,7 +14,9 @@
 //! ...
 //! ```
 
+#[cfg(feature = "lut-io")]
 use std::io::{BufRead, Write as IoWrite};
+#[cfg(feature = "lut-io")]
 use std::path::Path;
 
 use crate::transform::evaluate::evaluate_transform;
@@ -152,6 +154,7 @@ impl Lut3D {
     }
 
     /// Load a 3D LUT from an Iridas `.cube` file.
+    #[cfg(feature = "lut-io")]
     ///
     /// Parses: `TITLE`, `DOMAIN_MIN`, `DOMAIN_MAX`, `LUT_3D_SIZE`, and data lines.
     pub fn load_cube(path: &Path) -> std::io::Result<Self> {
@@ -244,6 +247,7 @@ impl Lut3D {
     }
 
     /// Save this 3D LUT to an Iridas `.cube` file.
+    #[cfg(feature = "lut-io")]
     pub fn save_cube(&self, path: &Path) -> std::io::Result<()> {
         let file = std::fs::File::create(path)?;
         let mut writer = std::io::BufWriter::new(file);
@@ -283,6 +287,7 @@ mod tests {
                 working_space: ColorSpaceId::AcesCg,
                 output_space: ColorSpaceId::AcesCg,
                 display_oetf: DisplayOetf::Srgb,
+                ..Default::default()
             },
             ..GradingParams::default()
         }
@@ -348,6 +353,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "lut-io")]
     fn test_cube_file_roundtrip_data_matches() {
         let params = identity_params();
         let mut lut = Lut3D::new(5);
@@ -0,0 +1,345 @@
+//! 3D LUT health checks: monotonicity, smoothness, and invertibility.
+//!
+//! Run on imported LUTs to flag broken or badly resampled tables before
+//! they are used: reversals along an axis (a brighter input producing a
+//! darker output), isolated spikes from corrupt or clipped entries, and
+//! regions where no inverse exists.
+
+use glam::{Mat3, Vec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::lut::Lut3D;
+
+/// A second difference this many times the mean grid step is a spike.
+pub const SPIKE_RATIO: f32 = 8.0;
+
+/// Round-trip error above which a grid point counts as non-invertible.
+pub const INVERSE_TOLERANCE: f32 = 1e-3;
+
+/// Reversals smaller than this are treated as float noise.
+const REVERSAL_EPSILON: f32 = 1e-6;
+
+/// Largest grid used for the inverse check; keeps analysis interactive.
+const MAX_INVERSE_SIZE: u32 = 33;
+
+/// Newton iterations per inverse grid point.
+const INVERSE_ITERATIONS: usize = 24;
+
+/// Summary of [`Lut3D::analyze`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LutAnalysis {
+    pub size: u32,
+    /// Grid steps along an input axis where that axis's own output channel
+    /// decreases (e.g. red output falling as red input rises).
+    pub monotonicity_violations: usize,
+    /// Largest such decrease, in output units.
+    pub max_reversal: f32,
+    /// Grid points whose second difference exceeds [`SPIKE_RATIO`] × the
+    /// mean step.
+    pub contrast_spikes: usize,
+    /// Largest second difference relative to the mean step.
+    pub max_curvature: f32,
+    /// Inverse grid points whose round trip misses by more than
+    /// [`INVERSE_TOLERANCE`].
+    pub non_invertible_points: usize,
+    /// Largest round-trip error `|f(f⁻¹(y)) − y|` over the inverse grid.
+    pub max_inverse_error: f32,
+}
+
+impl LutAnalysis {
+    /// True when no check found a problem.
+    pub fn is_clean(&self) -> bool {
+        self.monotonicity_violations == 0
+            && self.contrast_spikes == 0
+            && self.non_invertible_points == 0
+    }
+}
+
+impl Lut3D {
+    /// Run every check. The inverse is built at up to 33³.
+    pub fn analyze(&self) -> LutAnalysis {
+        let (monotonicity_violations, max_reversal) = self.monotonicity_violations();
+        let (contrast_spikes, max_curvature) = self.contrast_spikes(SPIKE_RATIO);
+        let inverse = self.invert(self.size.min(MAX_INVERSE_SIZE));
+        let (non_invertible_points, max_inverse_error) = self.inverse_error(&inverse);
+
+        LutAnalysis {
+            size: self.size,
+            monotonicity_violations,
+            max_reversal,
+            contrast_spikes,
+            max_curvature,
+            non_invertible_points,
+            max_inverse_error,
+        }
+    }
+
+    /// Count steps where an axis's own output channel decreases along it.
+    ///
+    /// Returns `(violations, largest reversal)`.
+    pub fn monotonicity_violations(&self) -> (usize, f32) {
+        let mut count = 0;
+        let mut max_reversal = 0.0_f32;
+        for axis in 0..3 {
+            self.for_each_step(axis, |a, b| {
+                let drop = a[axis] - b[axis];
+                if drop > REVERSAL_EPSILON {
+                    count += 1;
+                    max_reversal = max_reversal.max(drop);
+                }
+            });
+        }
+        (count, max_reversal)
+    }
+
+    /// Count grid points where any channel bends sharper than `ratio` × the
+    /// mean step along any axis.
+    ///
+    /// Smooth curves have second differences far below their first
+    /// differences; a single bad entry shows up as a second difference
+    /// several steps tall. Returns `(spikes, largest curvature ratio)`.
+    pub fn contrast_spikes(&self, ratio: f32) -> (usize, f32) {
+        let n = self.size as usize;
+        if n < 3 {
+            return (0, 0.0);
+        }
+
+        // Scale: mean step of each axis's own channel, so the threshold
+        // follows the LUT's contrast rather than an absolute value.
+        let mut step_sum = 0.0_f64;
+        let mut steps = 0usize;
+        for axis in 0..3 {
+            self.for_each_step(axis, |a, b| {
+                step_sum += f64::from((b[axis] - a[axis]).abs());
+                steps += 1;
+            });
+        }
+        let mean_step = ((step_sum / steps.max(1) as f64) as f32).max(f32::EPSILON);
+
+        let size = self.size;
+        let mut spikes = 0;
+        let mut max_curvature = 0.0_f32;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let centre = self.entry(r, g, b);
+                    let mut worst = 0.0_f32;
+                    for axis in 0..3 {
+                        let mut idx = [r, g, b];
+                        if idx[axis] == 0 || idx[axis] == size - 1 {
+                            continue;
+                        }
+                        idx[axis] -= 1;
+                        let prev = self.entry(idx[0], idx[1], idx[2]);
+                        idx[axis] += 2;
+                        let next = self.entry(idx[0], idx[1], idx[2]);
+                        for c in 0..3 {
+                            let d2 = (prev[c] - 2.0 * centre[c] + next[c]).abs();
+                            worst = worst.max(d2 / mean_step);
+                        }
+                    }
+                    if worst > ratio {
+                        spikes += 1;
+                    }
+                    max_curvature = max_curvature.max(worst);
+                }
+            }
+        }
+        (spikes, max_curvature)
+    }
+
+    /// Build an approximate inverse LUT of the given size.
+    ///
+    /// The inverse's domain is the bounding box of this LUT's output. Each
+    /// grid point is solved with Newton's method on the trilinear
+    /// interpolant, starting from the identity; points with no preimage
+    /// converge to the nearest reachable output instead.
+    pub fn invert(&self, size: u32) -> Lut3D {
+        let (min, max) = self.output_bounds();
+        let mut inverse = Lut3D::new(size);
+        inverse.domain_min = min;
+        inverse.domain_max = max;
+
+        let span = Vec3::from(self.domain_max) - Vec3::from(self.domain_min);
+        let lo = Vec3::from(self.domain_min);
+        let hi = Vec3::from(self.domain_max);
+        let h = span * 1e-3;
+        let eval = |x: Vec3| Vec3::from(self.apply(x.to_array()));
+
+        let size_f = (size - 1).max(1) as f32;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let t = Vec3::new(r as f32, g as f32, b as f32) / size_f;
+                    let target = Vec3::from(min) + t * (Vec3::from(max) - Vec3::from(min));
+
+                    let mut x = target.clamp(lo, hi);
+                    for _ in 0..INVERSE_ITERATIONS {
+                        let residual = eval(x) - target;
+                        if residual.abs().max_element() < 1e-6 {
+                            break;
+                        }
+                        let jacobian = Mat3::from_cols(
+                            (eval(x + Vec3::X * h.x) - eval(x - Vec3::X * h.x)) / (2.0 * h.x),
+                            (eval(x + Vec3::Y * h.y) - eval(x - Vec3::Y * h.y)) / (2.0 * h.y),
+                            (eval(x + Vec3::Z * h.z) - eval(x - Vec3::Z * h.z)) / (2.0 * h.z),
+                        );
+                        let step = if jacobian.determinant().abs() > 1e-8 {
+                            jacobian.inverse() * residual
+                        } else {
+                            // Flat region: fall back to a damped fixed-point step.
+                            residual * 0.5
+                        };
+                        x = (x - step).clamp(lo, hi);
+                    }
+
+                    let idx = ((b * size + g) * size + r) as usize;
+                    inverse.data[idx] = [x.x, x.y, x.z, 1.0];
+                }
+            }
+        }
+        inverse
+    }
+
+    /// Round-trip `self(inverse(y))` at every grid point of `inverse`.
+    ///
+    /// Returns `(points above INVERSE_TOLERANCE, largest error)`.
+    pub fn inverse_error(&self, inverse: &Lut3D) -> (usize, f32) {
+        let mut failures = 0;
+        let mut max_error = 0.0_f32;
+        let size = inverse.size;
+        let size_f = (size - 1).max(1) as f32;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let target: [f32; 3] = std::array::from_fn(|c| {
+                        let t = [r, g, b][c] as f32 / size_f;
+                        inverse.domain_min[c] + t * (inverse.domain_max[c] - inverse.domain_min[c])
+                    });
+                    let x = inverse.entry(r, g, b);
+                    let y = self.apply([x[0], x[1], x[2]]);
+                    let err = (0..3)
+                        .map(|c| (y[c] - target[c]).abs())
+                        .fold(0.0_f32, f32::max);
+                    if err > INVERSE_TOLERANCE {
+                        failures += 1;
+                    }
+                    max_error = max_error.max(err);
+                }
+            }
+        }
+        (failures, max_error)
+    }
+
+    /// Per-channel min and max of the LUT's output, widened where flat so
+    /// the inverse domain never collapses.
+    fn output_bounds(&self) -> ([f32; 3], [f32; 3]) {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for entry in &self.data {
+            for c in 0..3 {
+                min[c] = min[c].min(entry[c]);
+                max[c] = max[c].max(entry[c]);
+            }
+        }
+        for c in 0..3 {
+            if max[c] - min[c] <= f32::EPSILON {
+                max[c] = min[c] + 1.0;
+            }
+        }
+        (min, max)
+    }
+
+    /// Visit every pair of neighbouring entries along `axis`.
+    fn for_each_step(&self, axis: usize, mut f: impl FnMut([f32; 4], [f32; 4])) {
+        let size = self.size;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let mut idx = [r, g, b];
+                    if idx[axis] + 1 >= size {
+                        continue;
+                    }
+                    let a = self.entry(idx[0], idx[1], idx[2]);
+                    idx[axis] += 1;
+                    f(a, self.entry(idx[0], idx[1], idx[2]));
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn entry(&self, r: u32, g: u32, b: u32) -> [f32; 4] {
+        self.data[((b * self.size + g) * self.size + r) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lut_from_fn(size: u32, f: impl Fn([f32; 3]) -> [f32; 3]) -> Lut3D {
+        let mut lut = Lut3D::new(size);
+        let n = (size - 1) as f32;
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let out = f([r as f32 / n, g as f32 / n, b as f32 / n]);
+                    lut.data[((b * size + g) * size + r) as usize] = [out[0], out[1], out[2], 1.0];
+                }
+            }
+        }
+        lut
+    }
+
+    #[test]
+    fn test_identity_lut_is_clean() {
+        let analysis = lut_from_fn(9, |rgb| rgb).analyze();
+        assert!(analysis.is_clean(), "{analysis:?}");
+        assert!(analysis.max_inverse_error < 1e-4);
+    }
+
+    #[test]
+    fn test_reversal_is_counted() {
+        // Red output dips in the middle of the red axis.
+        let lut = lut_from_fn(9, |[r, g, b]| [(r - 0.5).abs(), g, b]);
+        let (violations, max_reversal) = lut.monotonicity_violations();
+        assert_eq!(violations, 4 * 9 * 9);
+        assert!((max_reversal - 0.125).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_a_spike() {
+        let mut lut = lut_from_fn(9, |rgb| rgb.map(|v| v * v));
+        let (spikes, _) = lut.contrast_spikes(SPIKE_RATIO);
+        assert_eq!(spikes, 0, "smooth curve should not spike");
+
+        lut.data[(4 * 9 + 4) * 9 + 4][1] = 1.0;
+        let (spikes, curvature) = lut.contrast_spikes(SPIKE_RATIO);
+        assert!(
+            spikes >= 1 && curvature > SPIKE_RATIO,
+            "{spikes} {curvature}"
+        );
+    }
+
+    #[test]
+    fn test_inverse_of_gamma_curve() {
+        let lut = lut_from_fn(17, |rgb| rgb.map(|v| v.powf(2.2)));
+        let inverse = lut.invert(17);
+        let (failures, max_error) = lut.inverse_error(&inverse);
+        assert_eq!(failures, 0, "max error {max_error}");
+
+        // Mid grey maps back near its gamma-encoded value.
+        let x = inverse.apply([0.18; 3]);
+        assert!((x[0] - 0.18_f32.powf(1.0 / 2.2)).abs() < 0.01, "{x:?}");
+    }
+
+    #[test]
+    fn test_collapsed_channel_is_not_invertible() {
+        // Green and blue collapse onto one output; most targets have no preimage.
+        let analysis = lut_from_fn(5, |[r, g, b]| [r, (g + b) * 0.5, (g + b) * 0.5]).analyze();
+        assert!(analysis.non_invertible_points > 0, "{analysis:?}");
+    }
+}
@@ -0,0 +1,235 @@
+//! Input LUT domain handling.
+//!
+//! Camera and technical LUTs expect a specific encoding on input (LogC3,
+//! S-Log3, ...) and produce a specific encoding on output (usually Rec.709
+//! display code values). Feeding one the source pixels as if they were
+//! 0–1 linear gives wrong results whenever the source encoding differs.
+//!
+//! A [`LutDomain`] declares both ends. [`bake_input_lut`] wraps the imported
+//! LUT with the conversions the pipeline needs and bakes the result into a
+//! LUT that replaces the input transform:
+//!
+//! ```text
+//! source code value ─ input space → domain.input ─▶ LUT ─ domain.output → working linear ─▶ grade
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::color_management::aces::{apply_input_transform, apply_output_transform};
+use crate::color_management::transfer::get_transfer;
+use crate::transform::lut::Lut3D;
+use crate::transform::params::{ColorManagementConfig, ColorSpaceId};
+
+/// Expected input encoding and produced output encoding of an imported LUT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LutDomain {
+    /// Encoding the LUT expects on its input axis.
+    pub input: ColorSpaceId,
+    /// Encoding of the LUT's output values.
+    pub output: ColorSpaceId,
+}
+
+/// File-name keywords for camera log encodings, most specific first.
+const LOG_KEYWORDS: &[(&str, ColorSpaceId)] = &[
+    ("logc4", ColorSpaceId::ArriLogC4),
+    ("logc3", ColorSpaceId::ArriLogC3),
+    ("logc", ColorSpaceId::ArriLogC3),
+    ("slog3", ColorSpaceId::SLog3),
+    ("log3g10", ColorSpaceId::RedLog3G10),
+    ("vlog", ColorSpaceId::VLog),
+    ("acescct", ColorSpaceId::AcesCct),
+    ("acescc", ColorSpaceId::AcesCc),
+];
+
+/// File-name keywords for display / output encodings.
+///
+/// Rec.709 shares sRGB primaries and is treated as sRGB-encoded.
+const OUTPUT_KEYWORDS: &[(&str, ColorSpaceId)] = &[
+    ("rec709", ColorSpaceId::Srgb),
+    ("709", ColorSpaceId::Srgb),
+    ("srgb", ColorSpaceId::Srgb),
+    ("2020", ColorSpaceId::Rec2020),
+    ("p3", ColorSpaceId::DciP3),
+    ("acescg", ColorSpaceId::AcesCg),
+];
+
+impl LutDomain {
+    /// Guess the domain from a LUT file name such as `LogC3_to_Rec709.cube`.
+    ///
+    /// Keywords are matched case-insensitively with separators removed. With
+    /// no log keyword the LUT is assumed to take `fallback_input`; with no
+    /// output keyword a log-input LUT is assumed to be a display transform
+    /// (sRGB out) and anything else a look that preserves its encoding.
+    pub fn guess(file_name: &str, fallback_input: ColorSpaceId) -> Self {
+        let stem = file_name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(file_name)
+            .to_ascii_lowercase()
+            .replace(['-', '_', ' ', '.'], "");
+        let find = |table: &[(&str, ColorSpaceId)]| {
+            table
+                .iter()
+                .find(|(key, _)| stem.contains(key))
+                .map(|&(_, space)| space)
+        };
+
+        let input = find(LOG_KEYWORDS).unwrap_or(fallback_input);
+        let output = find(OUTPUT_KEYWORDS).unwrap_or(if is_log(input) {
+            ColorSpaceId::Srgb
+        } else {
+            input
+        });
+        Self { input, output }
+    }
+}
+
+/// True for log-encoded spaces, where values above 1.0 linear live in [0, 1].
+fn is_log(space: ColorSpaceId) -> bool {
+    get_transfer(space).is_some() && space != ColorSpaceId::Srgb
+}
+
+/// Bake `lut` with its domain conversions into a source → working-linear LUT.
+///
+/// The result is indexed by source code values in [0, 1] (values outside
+/// are clamped, as on the GPU) and replaces the input color transform; the
+/// rest of the grade runs unchanged on its output.
+pub fn bake_input_lut(
+    lut: &Lut3D,
+    domain: LutDomain,
+    config: &ColorManagementConfig,
+    size: u32,
+) -> Lut3D {
+    let to_working = ColorManagementConfig {
+        output_space: domain.input,
+        ..config.clone()
+    };
+    let from_lut = ColorManagementConfig {
+        input_space: domain.output,
+        ..config.clone()
+    };
+    // Skip the pre-transform when the source already is in the LUT's
+    // encoding; the round trip through working linear only adds error.
+    let pre = config.input_space != domain.input;
+
+    let size = size.max(2);
+    let n = (size - 1) as f32;
+    let mut baked = Lut3D::new(size);
+    for b in 0..size {
+        for g in 0..size {
+            for r in 0..size {
+                let source = [r, g, b].map(|i| i as f32 / n);
+                let lut_in = if pre {
+                    let working = apply_input_transform(source, &to_working);
+                    apply_output_transform(working, &to_working)
+                } else {
+                    source
+                };
+                let out = apply_input_transform(lut.apply(lut_in), &from_lut);
+                let idx = ((b * size + g) * size + r) as usize;
+                baked.data[idx] = [out[0], out[1], out[2], 1.0];
+            }
+        }
+    }
+    baked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Identity LUT sampling its own grid.
+    fn identity(size: u32) -> Lut3D {
+        let mut lut = Lut3D::new(size);
+        let n = (size - 1) as f32;
+        for (i, entry) in lut.data.iter_mut().enumerate() {
+            let i = i as u32;
+            let [r, g, b] = [i % size, (i / size) % size, i / (size * size)];
+            *entry = [r as f32 / n, g as f32 / n, b as f32 / n, 1.0];
+        }
+        lut
+    }
+
+    #[test]
+    fn test_guess_reads_camera_and_display_keywords() {
+        let domain = LutDomain::guess("/luts/ARRI_LogC3_to_Rec709.cube", ColorSpaceId::Srgb);
+        assert_eq!(domain.input, ColorSpaceId::ArriLogC3);
+        assert_eq!(domain.output, ColorSpaceId::Srgb);
+
+        let domain = LutDomain::guess("S-Log3 SGamut3Cine.cube", ColorSpaceId::Srgb);
+        assert_eq!(domain.input, ColorSpaceId::SLog3);
+        assert_eq!(domain.output, ColorSpaceId::Srgb);
+
+        let domain = LutDomain::guess("LogC4-Look.cube", ColorSpaceId::Srgb);
+        assert_eq!(domain.input, ColorSpaceId::ArriLogC4);
+    }
+
+    #[test]
+    fn test_guess_falls_back_to_encoding_preserving_look() {
+        let domain = LutDomain::guess("teal_orange.cube", ColorSpaceId::AcesCct);
+        assert_eq!(domain.input, ColorSpaceId::AcesCct);
+        assert_eq!(domain.output, ColorSpaceId::Srgb);
+
+        let domain = LutDomain::guess("bleach.cube", ColorSpaceId::Srgb);
+        assert_eq!(domain.input, ColorSpaceId::Srgb);
+        assert_eq!(domain.output, ColorSpaceId::Srgb);
+    }
+
+    #[test]
+    fn test_matching_domain_reduces_to_input_transform() {
+        let config = ColorManagementConfig {
+            input_space: ColorSpaceId::SLog3,
+            ..ColorManagementConfig::default()
+        };
+        let domain = LutDomain {
+            input: ColorSpaceId::SLog3,
+            output: ColorSpaceId::SLog3,
+        };
+        // The exponential decode is baked on a linear grid, so allow for
+        // interpolation error between 65³ grid points.
+        let baked = bake_input_lut(&identity(17), domain, &config, 65);
+        for v in [0.1, 0.41, 0.6] {
+            let rgb = [v, v * 0.9, v * 1.1];
+            let got = baked.apply(rgb);
+            let want = apply_input_transform(rgb, &config);
+            for c in 0..3 {
+                assert!(
+                    (got[c] - want[c]).abs() < 5e-3 * want[c].abs().max(0.1),
+                    "{got:?} vs {want:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_lut_on_linear_source_is_fed_log_values() {
+        // Linear ACEScg source, LogC3 LUT that halves its code values. With
+        // the domain declared, mid grey reaches the LUT as LogC3 ~0.39, not
+        // as 0.18 linear.
+        let config = ColorManagementConfig {
+            input_space: ColorSpaceId::AcesCg,
+            working_space: ColorSpaceId::AcesCg,
+            ..ColorManagementConfig::default()
+        };
+        let domain = LutDomain {
+            input: ColorSpaceId::ArriLogC3,
+            output: ColorSpaceId::ArriLogC3,
+        };
+        let mut halve = identity(33);
+        for entry in &mut halve.data {
+            for c in entry.iter_mut().take(3) {
+                *c *= 0.5;
+            }
+        }
+        let baked = bake_input_lut(&halve, domain, &config, 65);
+
+        let logc3 = get_transfer(ColorSpaceId::ArriLogC3).expect("log transfer");
+        let expected = logc3.to_linear(logc3.to_encoded(0.18) * 0.5);
+        let got = baked.apply([0.18; 3]);
+        for c in got {
+            assert!((c - expected).abs() < 2e-3, "{c} vs {expected}");
+        }
+    }
+}
@@ -1,5 +1,21 @@
 //! Transform pipeline — parameter definitions, evaluation, and LUT operations.
 
+pub mod bracket;
+pub mod builder;
+pub mod compare;
+pub mod determinism;
 pub mod evaluate;
+pub mod inspect;
+#[cfg(feature = "project")]
+pub mod look_preview;
 pub mod lut;
+pub mod lut_analysis;
+pub mod lut_domain;
+pub mod nuke_export;
 pub mod params;
+pub mod parity;
+#[cfg(feature = "project")]
+pub mod presets;
+pub mod shader_export;
+pub mod shaper;
+pub mod validation;
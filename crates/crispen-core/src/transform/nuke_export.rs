@@ -0,0 +1,290 @@
+//! Export the grade as a Nuke script snippet (`.nk`) or gizmo (`.gizmo`).
+//!
+//! Primary-only grades are rebuilt procedurally so vendors can inspect and
+//! tweak them:
+//!
+//! ```text
+//! OCIOColorSpace (input → working)
+//!   → Grade        (lift/gain/offset/gamma)
+//!   → Expression   (contrast around pivot, only when contrast ≠ 1)
+//!   → ColorCorrect (saturation, only when saturation ≠ 1)
+//!   → OCIOColorSpace (working → output)
+//! ```
+//!
+//! Anything Nuke has no direct equivalent for (white balance,
+//! shadows/highlights, hue rotation, curves, chart correction) falls back to
+//! a single `Vectorfield` node reading a baked `.cube` of the whole chain.
+//!
+//! Either way the snippet expects plates in the grade's input encoding
+//! (e.g. a `Read` node set to `raw`). Color space names follow the ACES
+//! OCIO configs Nuke ships with. `ColorCorrect` saturation uses Rec. 709
+//! luma, so grades with a non-zero luma mix are approximated.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+#[cfg(feature = "lut-io")]
+use crate::transform::lut::Lut3D;
+use crate::transform::params::{ColorSpaceId, GradingParams};
+use crate::transform::shader_export::primaries_only;
+
+/// Nuke output flavor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NukeFormat {
+    /// Bare node chain for pasting into a script.
+    Script,
+    /// Self-contained gizmo with `Input` / `Output` nodes.
+    Gizmo,
+}
+
+impl NukeFormat {
+    /// Pick the format from a file extension (`.gizmo` → gizmo, else script).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("gizmo") => Self::Gizmo,
+            _ => Self::Script,
+        }
+    }
+}
+
+/// Whether the grade needs a baked `.cube` (no procedural equivalent).
+pub fn needs_lut(params: &GradingParams) -> bool {
+    !primaries_only(params)
+}
+
+/// Build the Nuke snippet for `params`.
+///
+/// `lut_file` is referenced by the `Vectorfield` node when
+/// [`needs_lut`] is true and ignored otherwise.
+pub fn export_nuke(params: &GradingParams, lut_file: &str, format: NukeFormat) -> String {
+    let params = &params.with_bypass_applied();
+    let mut out = String::new();
+    let _ = writeln!(out, "# Generated by Crispen");
+
+    if format == NukeFormat::Gizmo {
+        out.push_str("Gizmo {\n name Crispen_Grade\n}\n");
+        out.push_str(" Input {\n  name Input1\n }\n");
+    }
+
+    if needs_lut(params) {
+        write_node(
+            &mut out,
+            "Vectorfield",
+            &[
+                ("vfield_file", quote(lut_file)),
+                ("colorspaceIn", "linear".into()),
+                ("colorspaceOut", "linear".into()),
+                ("name", "Crispen_LUT".into()),
+            ],
+        );
+    } else {
+        write_procedural(&mut out, params);
+    }
+
+    if format == NukeFormat::Gizmo {
+        out.push_str(" Output {\n  name Output1\n }\n");
+        out.push_str("end_group\n");
+    }
+    out
+}
+
+/// Write the snippet to `path`, plus a sibling `.cube` when one is needed.
+#[cfg(feature = "lut-io")]
+pub fn save_nuke(params: &GradingParams, path: &Path, lut_size: u32) -> std::io::Result<()> {
+    let cube_path = path.with_extension("cube");
+    if needs_lut(params) {
+        let mut lut = Lut3D::new(lut_size.max(2));
+        lut.bake(params);
+        lut.save_cube(&cube_path)?;
+    }
+    let lut_file = cube_path.to_string_lossy().replace('\\', "/");
+    std::fs::write(
+        path,
+        export_nuke(params, &lut_file, NukeFormat::from_path(path)),
+    )
+}
+
+fn write_procedural(out: &mut String, params: &GradingParams) {
+    let cm = &params.color_management;
+
+    if cm.input_space != cm.working_space {
+        write_node(
+            out,
+            "OCIOColorSpace",
+            &[
+                ("in_colorspace", quote(ocio_name(cm.input_space))),
+                ("out_colorspace", quote(ocio_name(cm.working_space))),
+                ("name", "Crispen_Input".into()),
+            ],
+        );
+    }
+
+    // Grade: out = pow(white * in + add, 1 / gamma) with black clamping,
+    // which is exactly the CDL in `apply_cdl` with add = lift·(1−gain)+offset.
+    let lift = params.combined_lift();
+    let gamma = params.combined_gamma();
+    let gain = params.combined_gain();
+    let offset = params.combined_offset();
+    let white = [0, 1, 2].map(|c| gain[c] * gain[3]);
+    let add = [0, 1, 2].map(|c| (lift[c] + lift[3]) * (1.0 - white[c]) + offset[c] + offset[3]);
+    let gamma = [0, 1, 2].map(|c| {
+        let g = gamma[c] * gamma[3];
+        if g > 0.0 { g } else { 1.0 }
+    });
+    write_node(
+        out,
+        "Grade",
+        &[
+            ("white", color4(white, 1.0)),
+            ("add", color4(add, 0.0)),
+            ("gamma", color4(gamma, 1.0)),
+            ("black_clamp", "true".into()),
+            ("name", "Crispen_CDL".into()),
+        ],
+    );
+
+    if (params.contrast - 1.0).abs() >= 1e-7 {
+        let expr = |ch: &str| {
+            quote(&format!(
+                "{ch} > 0 ? pow({ch} / {p}, {k}) * {p} : 0",
+                p = params.pivot,
+                k = params.contrast
+            ))
+        };
+        write_node(
+            out,
+            "Expression",
+            &[
+                ("expr0", expr("r")),
+                ("expr1", expr("g")),
+                ("expr2", expr("b")),
+                ("name", "Crispen_Contrast".into()),
+            ],
+        );
+    }
+
+    if (params.saturation - 1.0).abs() >= 1e-7 {
+        write_node(
+            out,
+            "ColorCorrect",
+            &[
+                ("saturation", params.saturation.to_string()),
+                ("name", "Crispen_Saturation".into()),
+            ],
+        );
+    }
+
+    if cm.working_space != cm.output_space {
+        write_node(
+            out,
+            "OCIOColorSpace",
+            &[
+                ("in_colorspace", quote(ocio_name(cm.working_space))),
+                ("out_colorspace", quote(ocio_name(cm.output_space))),
+                ("name", "Crispen_Output".into()),
+            ],
+        );
+    }
+}
+
+fn write_node(out: &mut String, class: &str, knobs: &[(&str, String)]) {
+    let _ = writeln!(out, "{class} {{");
+    for (knob, value) in knobs {
+        let _ = writeln!(out, " {knob} {value}");
+    }
+    out.push_str("}\n");
+}
+
+fn color4(rgb: [f32; 3], alpha: f32) -> String {
+    format!("{{{} {} {} {}}}", rgb[0], rgb[1], rgb[2], alpha)
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Color space name in the ACES OCIO configs bundled with Nuke.
+fn ocio_name(space: ColorSpaceId) -> &'static str {
+    match space {
+        ColorSpaceId::Aces2065_1 => "ACES2065-1",
+        ColorSpaceId::AcesCg => "ACEScg",
+        ColorSpaceId::AcesCc => "ACEScc",
+        ColorSpaceId::AcesCct => "ACEScct",
+        ColorSpaceId::Srgb => "sRGB - Texture",
+        ColorSpaceId::LinearSrgb => "Linear Rec.709 (sRGB)",
+        ColorSpaceId::Rec2020 => "Linear Rec.2020",
+        ColorSpaceId::DciP3 => "Linear P3-D65",
+        ColorSpaceId::ArriLogC3 => "ARRI LogC3 (EI800)",
+        ColorSpaceId::ArriLogC4 => "ARRI LogC4",
+        ColorSpaceId::SLog3 => "Sony S-Log3 S-Gamut3",
+        ColorSpaceId::RedLog3G10 => "RED Log3G10 REDWideGamutRGB",
+        ColorSpaceId::VLog => "Panasonic V-Log V-Gamut",
+        ColorSpaceId::Custom(_) => "scene_linear",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primary_grade_is_procedural() {
+        let params = GradingParams {
+            gain: [1.2, 1.0, 0.9, 1.0],
+            contrast: 1.1,
+            saturation: 0.8,
+            ..GradingParams::default()
+        };
+        let nk = export_nuke(&params, "unused.cube", NukeFormat::Script);
+        assert!(!nk.contains("Vectorfield"));
+        assert!(nk.contains("Grade {"));
+        assert!(nk.contains(" white {1.2 1 0.9 1}"));
+        assert!(nk.contains("Expression {"));
+        assert!(nk.contains(" saturation 0.8"));
+        assert!(nk.contains("in_colorspace \"sRGB - Texture\""));
+    }
+
+    #[test]
+    fn test_identity_contrast_and_saturation_are_omitted() {
+        let nk = export_nuke(&GradingParams::default(), "", NukeFormat::Script);
+        assert!(!nk.contains("Expression"));
+        assert!(!nk.contains("ColorCorrect"));
+    }
+
+    #[test]
+    fn test_secondary_grade_falls_back_to_vectorfield() {
+        let params = GradingParams {
+            shadows: 0.2,
+            ..GradingParams::default()
+        };
+        assert!(needs_lut(&params));
+        let nk = export_nuke(&params, "/tmp/grade.cube", NukeFormat::Script);
+        assert!(nk.contains("vfield_file \"/tmp/grade.cube\""));
+        assert!(!nk.contains("Grade {"));
+    }
+
+    #[test]
+    fn test_gizmo_wraps_chain_in_group() {
+        let nk = export_nuke(&GradingParams::default(), "", NukeFormat::Gizmo);
+        assert!(nk.contains("Gizmo {"));
+        assert!(nk.contains("Input {"));
+        assert!(nk.trim_end().ends_with("end_group"));
+        assert_eq!(
+            NukeFormat::from_path(Path::new("a/b.GIZMO")),
+            NukeFormat::Gizmo
+        );
+        assert_eq!(NukeFormat::from_path(Path::new("b.nk")), NukeFormat::Script);
+    }
+
+    #[test]
+    fn test_grade_add_folds_lift_into_offset() {
+        let params = GradingParams {
+            lift: [0.1, 0.0, 0.0, 0.0],
+            gain: [2.0, 1.0, 1.0, 1.0],
+            ..GradingParams::default()
+        };
+        let nk = export_nuke(&params, "", NukeFormat::Script);
+        // lift·(1 − gain) = 0.1 · −1
+        assert!(nk.contains(" add {-0.1 0 0 0}"), "{nk}");
+    }
+}
@@ -3,10 +3,24 @@
 //! `GradingParams` is the single source of truth for all grading adjustments.
 //! Every tool writes here; the LUT bake shader reads the full struct.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::color_management::white_balance::WhiteBalanceModel;
+use crate::grading::blend::LayerBlend;
+use crate::grading::channel_mixer::ChannelMixer;
+use crate::grading::chromatic_aberration::ChromaticAberration;
+use crate::grading::curves::CurveInterpolation;
+use crate::grading::dehaze::Dehaze;
+use crate::grading::exposure::Exposure;
+use crate::grading::glow::Glow;
+use crate::grading::monochrome::Monochrome;
+use crate::grading::sliders::{HueRotation, InputLevels, SaturationModel};
+use crate::grading::soft_clip::SoftClip;
+
 /// Identifies a color space for input/working/output transforms.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ColorSpaceId {
     /// ACES 2065-1 (AP0 primaries, linear).
     Aces2065_1,
@@ -85,7 +99,8 @@ impl ColorSpaceId {
 /// When the GPU pipeline outputs display-referred values (e.g. after an OCIO
 /// ODT), the OETF encoding must be inverted so that Bevy's sRGB framebuffer
 /// can re-apply the correct encoding for the monitor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DisplayOetf {
     /// Output is already linear — no inverse needed.
     Linear,
@@ -109,13 +124,44 @@ impl DisplayOetf {
     }
 
     /// Default for serde deserialization when the field is absent.
+    #[cfg(feature = "serde")]
     fn default_srgb() -> Self {
         Self::Srgb
     }
 }
 
+/// What space conversions do with negative values, which camera-native
+/// colors produce once matrixed into a smaller gamut.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NegativePolicy {
+    /// Keep negatives through gamut matrices and let each transfer
+    /// function extend below black its own way.
+    #[default]
+    Allow,
+    /// Clamp linear values to zero after decoding, after each gamut
+    /// matrix, and before encoding.
+    Clamp,
+    /// Keep negatives, and point-mirror each transfer function around
+    /// linear black, so a negative value encodes as far below black as
+    /// its magnitude encodes above it.
+    Mirror,
+}
+
+impl NegativePolicy {
+    /// GPU-compatible integer for the shader uniform.
+    pub const fn to_u32(self) -> u32 {
+        match self {
+            Self::Allow => 0,
+            Self::Clamp => 1,
+            Self::Mirror => 2,
+        }
+    }
+}
+
 /// Configuration for color space transforms in the grading pipeline.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColorManagementConfig {
     /// Input color space of the source image.
     pub input_space: ColorSpaceId,
@@ -125,8 +171,11 @@ pub struct ColorManagementConfig {
     /// values in this gamut; Bevy's sRGB framebuffer applies the final OETF.
     pub output_space: ColorSpaceId,
     /// Display OETF to invert when using OCIO ODT output.
-    #[serde(default = "DisplayOetf::default_srgb")]
+    #[cfg_attr(feature = "serde", serde(default = "DisplayOetf::default_srgb"))]
     pub display_oetf: DisplayOetf,
+    /// Negative values through the input and output conversions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub negative_policy: NegativePolicy,
 }
 
 impl Default for ColorManagementConfig {
@@ -136,16 +185,112 @@ impl Default for ColorManagementConfig {
             working_space: ColorSpaceId::AcesCg,
             output_space: ColorSpaceId::LinearSrgb,
             display_oetf: DisplayOetf::Srgb,
+            negative_policy: NegativePolicy::Allow,
+        }
+    }
+}
+
+/// Per-camera input correction applied right after the input transform.
+///
+/// Produced by the chart-matching solver to neutralize a camera against a
+/// reference chart: a per-channel 1D gain/power adjustment followed by a
+/// 3×3 matrix, both in the working space.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InputCorrection {
+    /// Per-channel gain of the 1D adjustment. Default: `[1, 1, 1]`.
+    pub gain: [f32; 3],
+    /// Per-channel power of the 1D adjustment. Default: `[1, 1, 1]`.
+    pub power: [f32; 3],
+    /// Row-major 3×3 matrix applied after the 1D adjustment. Default: identity.
+    pub matrix: [[f32; 3]; 3],
+}
+
+impl Default for InputCorrection {
+    fn default() -> Self {
+        Self {
+            gain: [1.0; 3],
+            power: [1.0; 3],
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
         }
     }
 }
 
+impl InputCorrection {
+    /// Whether this correction is a no-op.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Per-section bypass flags.
+///
+/// A bypassed section is skipped by every evaluation of the grade — CPU
+/// reference, GPU LUT bake, and exports — while its settings are kept, so
+/// turning it back on restores the grade exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ToolBypass {
+    /// Skip lift/gamma/gain/offset, both the primary bars and the wheels.
+    pub wheels: bool,
+    /// Skip the hue, luminance, and saturation curves.
+    pub curves: bool,
+    /// Use the color-space input transform instead of an imported input LUT.
+    pub input_lut: bool,
+    /// Skip the input levels and source range.
+    pub input_levels: bool,
+    /// Skip the input transform or input LUT, grading source code values
+    /// as working-space values.
+    pub input_transform: bool,
+    /// Skip the exposure stops and log offset.
+    pub exposure: bool,
+    /// Skip the chart-matched input correction.
+    pub input_correction: bool,
+    /// Skip temperature and tint.
+    pub white_balance: bool,
+    /// Skip the channel mixer.
+    pub channel_mixer: bool,
+    /// Skip contrast.
+    pub contrast: bool,
+    /// Skip shadows/highlights recovery.
+    pub shadows_highlights: bool,
+    /// Skip vibrance, saturation, and hue rotation.
+    pub color: bool,
+    /// Skip the black and white conversion.
+    pub monochrome: bool,
+    /// Skip the highlight soft clip and shadow toe.
+    pub soft_clip: bool,
+    /// Skip the output transform, sending working-space values to the
+    /// display.
+    pub output_transform: bool,
+}
+
+impl ToolBypass {
+    /// Whether any section is bypassed.
+    pub fn any(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
 /// Every tool writes here. The LUT bake shader reads the full struct.
 /// This is the immutable contract between UI, Bevy, and GPU.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GradingParams {
     /// Color management configuration.
     pub color_management: ColorManagementConfig,
+    /// Chart-derived input correction. Default: identity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub input_correction: InputCorrection,
+    /// Source range and per-channel black/white points, applied to code
+    /// values before the input transform. Default: full range, 0–1.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub input_levels: InputLevels,
+    /// Exposure in stops after the input transform, and a code-value
+    /// offset before it. Default: none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exposure: Exposure,
 
     // Primary Bars [R, G, B, Master]
     /// Lift adjustment from primary bars (shadows). Default: `[0, 0, 0, 0]`.
@@ -167,27 +312,54 @@ pub struct GradingParams {
     /// Offset wheel offset. Default: `[0, 0, 0, 0]`. Combined additively with `offset`.
     pub offset_wheel: [f32; 4],
 
+    /// Channel mixer, applied after white balance. Default: identity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub channel_mixer: ChannelMixer,
+
     // Sliders
     /// Color temperature shift. 0.0 = neutral.
     pub temperature: f32,
     /// Tint shift (green-magenta). 0.0 = neutral.
     pub tint: f32,
+    /// How temperature and tint choose the white point. Default:
+    /// Planckian; grades saved without it load as Legacy.
+    #[cfg_attr(feature = "serde", serde(default = "WhiteBalanceModel::legacy"))]
+    pub white_balance_model: WhiteBalanceModel,
     /// Contrast multiplier. 1.0 = neutral.
     pub contrast: f32,
     /// Contrast pivot point. Default: 0.435.
     pub pivot: f32,
     /// Midtone detail enhancement. 0.0 = off (spatial, separate pass).
     pub midtone_detail: f32,
+    /// Chromatic aberration correction and fringing (spatial, separate
+    /// pass on the source). Default: off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chromatic_aberration: ChromaticAberration,
+    /// Haze removal (spatial, separate pass on the source). Default: off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dehaze: Dehaze,
     /// Shadow recovery. 0.0 = neutral.
     pub shadows: f32,
     /// Highlight recovery. 0.0 = neutral.
     pub highlights: f32,
     /// Saturation multiplier. 1.0 = neutral.
     pub saturation: f32,
+    /// Vibrance: saturation weighted toward muted, non-skin colors.
+    /// 0.0 = neutral.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vibrance: f32,
     /// Hue rotation in degrees. 0.0 = no rotation.
     pub hue: f32,
+    /// Hue rotation plane and per-range offsets added to `hue`.
+    /// Default: RGB, no offsets.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hue_rotation: HueRotation,
     /// Luma mix weight. 0.0 = full chroma weight.
     pub luma_mix: f32,
+    /// Space the saturation slider and saturation curves work in.
+    /// Default: luma.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub saturation_model: SaturationModel,
 
     // Curves (control points, baked to 1D LUTs before LUT bake)
     /// Hue-vs-hue curve control points.
@@ -198,6 +370,33 @@ pub struct GradingParams {
     pub lum_vs_sat: Vec<[f32; 2]>,
     /// Saturation-vs-saturation curve control points.
     pub sat_vs_sat: Vec<[f32; 2]>,
+    /// How curve control points are joined. Default: linear.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub curve_interpolation: CurveInterpolation,
+
+    /// Black and white conversion with film response and paper tint,
+    /// after the curves. Default: off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub monochrome: Monochrome,
+
+    /// Highlight rolloff and shadow toe, the last step before the output
+    /// transform. Default: off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soft_clip: SoftClip,
+
+    /// Blend mode and mix of the grade's result over its input, as a
+    /// layer over the image beneath it. Default: normal at full mix.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub layer_blend: LayerBlend,
+
+    /// Post-grade glow (spatial, separate pass on the graded output).
+    /// Default: off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub glow: Glow,
+
+    /// Sections skipped during evaluation. Default: none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bypass: ToolBypass,
 }
 
 impl Default for GradingParams {
@@ -205,6 +404,9 @@ impl Default for GradingParams {
     fn default() -> Self {
         Self {
             color_management: ColorManagementConfig::default(),
+            input_correction: InputCorrection::default(),
+            input_levels: InputLevels::default(),
+            exposure: Exposure::default(),
             lift: [0.0, 0.0, 0.0, 0.0],
             gamma: [1.0, 1.0, 1.0, 1.0],
             gain: [1.0, 1.0, 1.0, 1.0],
@@ -213,27 +415,104 @@ impl Default for GradingParams {
             gamma_wheel: [1.0, 1.0, 1.0, 1.0],
             gain_wheel: [1.0, 1.0, 1.0, 1.0],
             offset_wheel: [0.0, 0.0, 0.0, 0.0],
+            channel_mixer: ChannelMixer::default(),
             temperature: 0.0,
             tint: 0.0,
+            white_balance_model: WhiteBalanceModel::Planckian,
             contrast: 1.0,
             pivot: 0.435,
             midtone_detail: 0.0,
+            chromatic_aberration: ChromaticAberration::default(),
+            dehaze: Dehaze::default(),
             shadows: 0.0,
             highlights: 0.0,
             saturation: 1.0,
+            vibrance: 0.0,
             hue: 0.0,
+            hue_rotation: HueRotation::default(),
             luma_mix: 0.0,
+            saturation_model: SaturationModel::Luma,
             hue_vs_hue: Vec::new(),
             hue_vs_sat: Vec::new(),
             lum_vs_sat: Vec::new(),
             sat_vs_sat: Vec::new(),
+            curve_interpolation: CurveInterpolation::Linear,
+            monochrome: Monochrome::default(),
+            soft_clip: SoftClip::default(),
+            layer_blend: LayerBlend::default(),
+            glow: Glow::default(),
+            bypass: ToolBypass::default(),
         }
     }
 }
 
 impl GradingParams {
-    /// Combined lift: bar + wheel (additive).
+    /// This grade with every bypassed working-space section reset to its
+    /// identity, for evaluators that read values rather than bypass flags,
+    /// like the GPU bake. The flags are kept; wheels and curves are
+    /// already neutral through [`Self::combined_lift`] and friends and
+    /// [`Self::has_active_curves`], and the transforms are up to the
+    /// evaluator.
+    pub fn with_bypass_applied(&self) -> Self {
+        let bypass = self.bypass;
+        let mut params = self.clone();
+        if bypass.input_levels {
+            params.input_levels = InputLevels::default();
+        }
+        if bypass.exposure {
+            params.exposure = Exposure::default();
+        }
+        if bypass.input_correction {
+            params.input_correction = InputCorrection::default();
+        }
+        if bypass.white_balance {
+            params.temperature = 0.0;
+            params.tint = 0.0;
+        }
+        if bypass.channel_mixer {
+            params.channel_mixer = ChannelMixer::default();
+        }
+        if bypass.contrast {
+            params.contrast = 1.0;
+        }
+        if bypass.shadows_highlights {
+            params.shadows = 0.0;
+            params.highlights = 0.0;
+        }
+        if bypass.color {
+            params.vibrance = 0.0;
+            params.saturation = 1.0;
+            params.hue = 0.0;
+            params.hue_rotation.ranges = [0.0; 3];
+        }
+        if bypass.monochrome {
+            params.monochrome.enabled = false;
+        }
+        if bypass.soft_clip {
+            params.soft_clip = SoftClip::default();
+        }
+        params
+    }
+
+    /// Whether any curve has control points and curves are not bypassed.
+    pub fn has_active_curves(&self) -> bool {
+        !self.bypass.curves
+            && [
+                &self.hue_vs_hue,
+                &self.hue_vs_sat,
+                &self.lum_vs_sat,
+                &self.sat_vs_sat,
+            ]
+            .iter()
+            .any(|curve| !curve.is_empty())
+    }
+
+    /// Combined lift: bar + wheel (additive). Neutral while the wheels are
+    /// bypassed, as are the other `combined_*` values.
     pub fn combined_lift(&self) -> [f32; 4] {
+        if self.bypass.wheels {
+            return [0.0; 4];
+        }
         [
             self.lift[0] + self.lift_wheel[0],
             self.lift[1] + self.lift_wheel[1],
@@ -244,6 +523,9 @@ impl GradingParams {
 
     /// Combined gamma: bar * wheel (multiplicative).
     pub fn combined_gamma(&self) -> [f32; 4] {
+        if self.bypass.wheels {
+            return [1.0; 4];
+        }
         [
             self.gamma[0] * self.gamma_wheel[0],
             self.gamma[1] * self.gamma_wheel[1],
@@ -254,6 +536,9 @@ impl GradingParams {
 
     /// Combined gain: bar * wheel (multiplicative).
     pub fn combined_gain(&self) -> [f32; 4] {
+        if self.bypass.wheels {
+            return [1.0; 4];
+        }
         [
             self.gain[0] * self.gain_wheel[0],
             self.gain[1] * self.gain_wheel[1],
@@ -264,6 +549,9 @@ impl GradingParams {
 
     /// Combined offset: bar + wheel (additive).
     pub fn combined_offset(&self) -> [f32; 4] {
+        if self.bypass.wheels {
+            return [0.0; 4];
+        }
         [
             self.offset[0] + self.offset_wheel[0],
             self.offset[1] + self.offset_wheel[1],
@@ -0,0 +1,213 @@
+//! GPU vs CPU parity check.
+//!
+//! Compares an image graded on the GPU (baked LUT path) against
+//! `evaluate_transform()` run directly on the same source pixels. The CPU
+//! evaluation is the reference math; any error beyond LUT interpolation and
+//! half-float storage points at drift between the bake shader and
+//! `evaluate.rs`.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::evaluate::{evaluate_transform, evaluate_with_input_lut};
+use super::lut::Lut3D;
+use super::params::GradingParams;
+use crate::color_management::delta_e::{DeltaEStats, delta_e_2000, linear_rgb_to_lab};
+use crate::color_management::transfer::get_transfer;
+use crate::image::GradingImage;
+
+/// Default cap on compared pixels; keeps a 4K check well under a second.
+pub const DEFAULT_MAX_SAMPLES: usize = 65_536;
+
+/// One compared pixel: its position and both graded values.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParitySample {
+    pub x: u32,
+    pub y: u32,
+    /// CPU `evaluate_transform()` output.
+    pub cpu: [f32; 3],
+    /// GPU output read back from the graded image.
+    pub gpu: [f32; 3],
+}
+
+/// Error statistics between GPU and CPU grading of the same image.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParityReport {
+    pub width: u32,
+    pub height: u32,
+    /// Number of pixels compared (every `stride`-th pixel).
+    pub sampled: usize,
+    pub stride: usize,
+    /// Largest absolute per-channel difference, in output code values.
+    pub max_abs_error: f32,
+    /// Mean absolute per-channel difference over all sampled channels.
+    pub mean_abs_error: f32,
+    /// CIEDE2000 between the two outputs, decoded to linear output gamut.
+    pub delta_e_mean: f32,
+    pub delta_e_p95: f32,
+    pub delta_e_max: f32,
+    /// Pixel with the largest channel error.
+    pub worst: ParitySample,
+}
+
+/// Pixel stride that keeps the compared count at or below `max_samples`.
+pub fn sample_stride(pixel_count: usize, max_samples: usize) -> usize {
+    pixel_count.div_ceil(max_samples.max(1)).max(1)
+}
+
+/// Grade `source` on the CPU and compare against the GPU result `gpu`.
+///
+/// `input_lut` is the baked input LUT when one replaces the input transform.
+/// Returns `None` when the images differ in size or are empty, e.g. when
+/// the GPU output predates the current source image.
+pub fn compare_with_cpu(
+    params: &GradingParams,
+    source: &GradingImage,
+    gpu: &GradingImage,
+    input_lut: Option<&Lut3D>,
+    max_samples: usize,
+) -> Option<ParityReport> {
+    if source.width != gpu.width
+        || source.height != gpu.height
+        || source.pixels.is_empty()
+        || source.pixels.len() != gpu.pixels.len()
+    {
+        return None;
+    }
+
+    let output_space = params.color_management.output_space;
+    let transfer = get_transfer(output_space);
+    let to_lab = |rgb: [f32; 3]| {
+        let linear = match &transfer {
+            Some(tf) => rgb.map(|c| tf.to_linear(c)),
+            None => rgb,
+        };
+        linear_rgb_to_lab(linear, output_space)
+    };
+
+    let stride = sample_stride(source.pixels.len(), max_samples);
+    let mut report = ParityReport {
+        width: source.width,
+        height: source.height,
+        stride,
+        ..ParityReport::default()
+    };
+    let mut abs_sum = 0.0_f64;
+    let mut delta_es = Vec::with_capacity(source.pixels.len() / stride + 1);
+
+    for i in (0..source.pixels.len()).step_by(stride) {
+        let src = source.pixels[i];
+        let out = gpu.pixels[i];
+        let rgb = [src[0], src[1], src[2]];
+        let cpu = match input_lut {
+            Some(lut) => evaluate_with_input_lut(rgb, params, lut),
+            None => evaluate_transform(rgb, params),
+        };
+        let gpu = [out[0], out[1], out[2]];
+
+        for c in 0..3 {
+            let err = (cpu[c] - gpu[c]).abs();
+            abs_sum += f64::from(err);
+            if err > report.max_abs_error {
+                report.max_abs_error = err;
+                report.worst = ParitySample {
+                    x: i as u32 % source.width,
+                    y: i as u32 / source.width,
+                    cpu,
+                    gpu,
+                };
+            }
+        }
+        delta_es.push(delta_e_2000(to_lab(cpu), to_lab(gpu)));
+    }
+
+    let stats = DeltaEStats::from_values(&delta_es);
+    report.sampled = stats.count;
+    report.mean_abs_error = (abs_sum / (stats.count * 3) as f64) as f32;
+    report.delta_e_mean = stats.mean;
+    report.delta_e_p95 = stats.p95;
+    report.delta_e_max = stats.max;
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::BitDepth;
+
+    fn image(width: u32, height: u32, f: impl Fn(u32) -> [f32; 4]) -> GradingImage {
+        GradingImage {
+            width,
+            height,
+            pixels: (0..width * height).map(f).collect(),
+            source_bit_depth: BitDepth::F32,
+        }
+    }
+
+    fn ramp(i: u32) -> [f32; 4] {
+        let t = (i % 64) as f32 / 63.0;
+        [t, t * 0.5, 1.0 - t, 1.0]
+    }
+
+    fn graded(params: &GradingParams, source: &GradingImage) -> GradingImage {
+        image(source.width, source.height, |i| {
+            let [r, g, b] = evaluate_transform(
+                [
+                    source.pixels[i as usize][0],
+                    source.pixels[i as usize][1],
+                    source.pixels[i as usize][2],
+                ],
+                params,
+            );
+            [r, g, b, 1.0]
+        })
+    }
+
+    #[test]
+    fn test_identical_outputs_report_zero_error() {
+        let params = GradingParams {
+            saturation: 1.3,
+            ..Default::default()
+        };
+        let source = image(32, 16, ramp);
+        let report = compare_with_cpu(&params, &source, &graded(&params, &source), None, 1 << 20)
+            .expect("same size");
+        assert_eq!(report.sampled, 32 * 16);
+        assert_eq!(report.max_abs_error, 0.0);
+        assert_eq!(report.delta_e_max, 0.0);
+    }
+
+    #[test]
+    fn test_worst_pixel_is_located() {
+        let params = GradingParams::default();
+        let source = image(8, 8, ramp);
+        let mut gpu = graded(&params, &source);
+        gpu.pixels[8 * 5 + 3][1] += 0.25;
+
+        let report = compare_with_cpu(&params, &source, &gpu, None, 1 << 20).expect("same size");
+        assert!((report.max_abs_error - 0.25).abs() < 1e-5);
+        assert_eq!((report.worst.x, report.worst.y), (3, 5));
+        assert!(report.delta_e_max > 1.0, "ΔE {}", report.delta_e_max);
+        assert_eq!(report.delta_e_p95, 0.0);
+    }
+
+    #[test]
+    fn test_sampling_is_capped() {
+        let source = image(100, 100, ramp);
+        let params = GradingParams::default();
+        let report = compare_with_cpu(&params, &source, &graded(&params, &source), None, 1_000)
+            .expect("same size");
+        assert_eq!(report.stride, 10);
+        assert_eq!(report.sampled, 1_000);
+    }
+
+    #[test]
+    fn test_size_mismatch_is_rejected() {
+        let params = GradingParams::default();
+        let source = image(4, 4, ramp);
+        let gpu = image(4, 2, ramp);
+        assert!(compare_with_cpu(&params, &source, &gpu, None, DEFAULT_MAX_SAMPLES).is_none());
+    }
+}
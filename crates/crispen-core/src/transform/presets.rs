@@ -0,0 +1,424 @@
+//! Tool presets — named settings for one tool, recalled over any grade.
+//!
+//! A look replaces the whole grade; a tool preset only touches the fields
+//! of one tool, so a "filmic curve" or a "skin saturation" setting can be
+//! dropped onto a shot without disturbing its balance. Presets are the
+//! tool's fields as they serialize in [`GradingParams`], kept in a
+//! [`PresetLibrary`] file grouped by tool and category:
+//!
+//! ```json
+//! {"presets":[{"name":"Filmic","tool":"contrast","category":"Curves",
+//!   "values":{"contrast":1.15,"pivot":0.4,"midtone_detail":0.0,"shadows":0.05,"highlights":-0.1}}]}
+//! ```
+//!
+//! Libraries are shared between suites by exporting presets to a file and
+//! importing it into another library.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::transform::params::GradingParams;
+
+/// Errors from reading, writing, or applying presets.
+#[derive(Debug, thiserror::Error)]
+pub enum PresetError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid preset: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("preset \"{name}\" has no {tool} settings")]
+    Empty { name: String, tool: Tool },
+    #[error("no {tool} preset named \"{name}\"")]
+    NotFound { tool: Tool, name: String },
+}
+
+/// A grading tool whose fields a preset captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    /// Source range and black/white points.
+    InputLevels,
+    /// Exposure and code-value offset.
+    Exposure,
+    /// Temperature and tint.
+    WhiteBalance,
+    /// Primary bars and color wheels.
+    Wheels,
+    /// Channel mixer.
+    ChannelMixer,
+    /// Contrast, pivot, detail, and shadow/highlight recovery.
+    Contrast,
+    /// Saturation, vibrance, hue, and luma mix.
+    Saturation,
+    /// Hue rotation plane and per-range offsets.
+    HueRotation,
+    /// Hue and saturation curves.
+    Curves,
+    /// Black and white conversion.
+    Monochrome,
+    /// Highlight rolloff and shadow toe.
+    SoftClip,
+    /// Post-grade glow.
+    Glow,
+    /// Haze removal.
+    Dehaze,
+    /// Chromatic aberration correction.
+    ChromaticAberration,
+}
+
+impl Tool {
+    /// Every tool, in pipeline order.
+    pub const ALL: [Tool; 14] = [
+        Tool::InputLevels,
+        Tool::Exposure,
+        Tool::WhiteBalance,
+        Tool::Wheels,
+        Tool::ChannelMixer,
+        Tool::Contrast,
+        Tool::Saturation,
+        Tool::HueRotation,
+        Tool::Curves,
+        Tool::Monochrome,
+        Tool::SoftClip,
+        Tool::Glow,
+        Tool::Dehaze,
+        Tool::ChromaticAberration,
+    ];
+
+    /// Identifier used in preset files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::InputLevels => "input_levels",
+            Tool::Exposure => "exposure",
+            Tool::WhiteBalance => "white_balance",
+            Tool::Wheels => "wheels",
+            Tool::ChannelMixer => "channel_mixer",
+            Tool::Contrast => "contrast",
+            Tool::Saturation => "saturation",
+            Tool::HueRotation => "hue_rotation",
+            Tool::Curves => "curves",
+            Tool::Monochrome => "monochrome",
+            Tool::SoftClip => "soft_clip",
+            Tool::Glow => "glow",
+            Tool::Dehaze => "dehaze",
+            Tool::ChromaticAberration => "chromatic_aberration",
+        }
+    }
+
+    /// [`GradingParams`] fields the tool owns.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            Tool::InputLevels => &["input_levels"],
+            Tool::Exposure => &["exposure"],
+            Tool::WhiteBalance => &["temperature", "tint"],
+            Tool::Wheels => &[
+                "lift",
+                "gamma",
+                "gain",
+                "offset",
+                "lift_wheel",
+                "gamma_wheel",
+                "gain_wheel",
+                "offset_wheel",
+            ],
+            Tool::ChannelMixer => &["channel_mixer"],
+            Tool::Contrast => &[
+                "contrast",
+                "pivot",
+                "midtone_detail",
+                "shadows",
+                "highlights",
+            ],
+            Tool::Saturation => &[
+                "saturation",
+                "vibrance",
+                "hue",
+                "luma_mix",
+                "saturation_model",
+            ],
+            Tool::HueRotation => &["hue_rotation"],
+            Tool::Curves => &[
+                "hue_vs_hue",
+                "hue_vs_sat",
+                "lum_vs_sat",
+                "sat_vs_sat",
+                "curve_interpolation",
+            ],
+            Tool::Monochrome => &["monochrome"],
+            Tool::SoftClip => &["soft_clip"],
+            Tool::Glow => &["glow"],
+            Tool::Dehaze => &["dehaze"],
+            Tool::ChromaticAberration => &["chromatic_aberration"],
+        }
+    }
+}
+
+impl std::fmt::Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Named settings for one tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolPreset {
+    /// Display name, unique per tool within a library.
+    pub name: String,
+    /// Tool the settings belong to.
+    pub tool: Tool,
+    /// Free-form grouping, e.g. "Skin" or "Film". Empty for none.
+    #[serde(default)]
+    pub category: String,
+    /// The tool's fields, as they serialize in [`GradingParams`].
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ToolPreset {
+    /// Capture `tool`'s current settings from `params`.
+    pub fn capture(
+        name: impl Into<String>,
+        tool: Tool,
+        category: impl Into<String>,
+        params: &GradingParams,
+    ) -> Result<Self, PresetError> {
+        let serde_json::Value::Object(all) = serde_json::to_value(params)? else {
+            unreachable!("GradingParams serializes as an object");
+        };
+        let values = all
+            .into_iter()
+            .filter(|(field, _)| tool.fields().contains(&field.as_str()))
+            .collect();
+        Ok(Self {
+            name: name.into(),
+            tool,
+            category: category.into(),
+            values,
+        })
+    }
+
+    /// `params` with this preset's fields written over it. Fields of other
+    /// tools, and fields of this tool the preset lacks, are kept.
+    pub fn apply(&self, params: &GradingParams) -> Result<GradingParams, PresetError> {
+        let serde_json::Value::Object(mut all) = serde_json::to_value(params)? else {
+            unreachable!("GradingParams serializes as an object");
+        };
+        let mut applied = 0;
+        for (field, value) in &self.values {
+            // Fields outside the tool are ignored, so a hand-edited preset
+            // cannot reach into the rest of the grade.
+            if self.tool.fields().contains(&field.as_str()) {
+                all.insert(field.clone(), value.clone());
+                applied += 1;
+            }
+        }
+        if applied == 0 {
+            return Err(PresetError::Empty {
+                name: self.name.clone(),
+                tool: self.tool,
+            });
+        }
+        Ok(serde_json::from_value(serde_json::Value::Object(all))?)
+    }
+}
+
+/// A set of tool presets, stored as one JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PresetLibrary {
+    /// Presets in insertion order.
+    #[serde(default)]
+    pub presets: Vec<ToolPreset>,
+}
+
+impl PresetLibrary {
+    /// Read the library at `path`. A library that does not exist yet is
+    /// empty.
+    pub fn load(path: &Path) -> Result<Self, PresetError> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the library to `path`, creating its folder.
+    pub fn save(&self, path: &Path) -> Result<(), PresetError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Add `preset`, replacing any preset of the same tool and name.
+    pub fn add(&mut self, preset: ToolPreset) {
+        match self
+            .presets
+            .iter_mut()
+            .find(|p| p.tool == preset.tool && p.name == preset.name)
+        {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// Remove `tool`'s preset called `name`. Returns whether there was one.
+    pub fn remove(&mut self, tool: Tool, name: &str) -> bool {
+        let before = self.presets.len();
+        self.presets.retain(|p| !(p.tool == tool && p.name == name));
+        self.presets.len() != before
+    }
+
+    /// `tool`'s preset called `name`.
+    pub fn get(&self, tool: Tool, name: &str) -> Option<&ToolPreset> {
+        self.presets
+            .iter()
+            .find(|p| p.tool == tool && p.name == name)
+    }
+
+    /// `params` with `tool`'s preset called `name` applied over it.
+    pub fn apply(
+        &self,
+        tool: Tool,
+        name: &str,
+        params: &GradingParams,
+    ) -> Result<GradingParams, PresetError> {
+        self.get(tool, name)
+            .ok_or_else(|| PresetError::NotFound {
+                tool,
+                name: name.to_string(),
+            })?
+            .apply(params)
+    }
+
+    /// `tool`'s presets, in insertion order.
+    pub fn for_tool(&self, tool: Tool) -> impl Iterator<Item = &ToolPreset> {
+        self.presets.iter().filter(move |p| p.tool == tool)
+    }
+
+    /// Categories in use, sorted, without the empty one.
+    pub fn categories(&self) -> Vec<&str> {
+        self.presets
+            .iter()
+            .map(|p| p.category.as_str())
+            .filter(|c| !c.is_empty())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Add every preset in the library file at `path`, replacing presets of
+    /// the same tool and name. Returns the number imported.
+    pub fn import(&mut self, path: &Path) -> Result<usize, PresetError> {
+        let other: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let count = other.presets.len();
+        for preset in other.presets {
+            self.add(preset);
+        }
+        Ok(count)
+    }
+
+    /// Write the presets of `tool`, or all of them, to `path` as a library
+    /// file another library can import. Returns the number exported.
+    pub fn export(&self, path: &Path, tool: Option<Tool>) -> Result<usize, PresetError> {
+        let subset = Self {
+            presets: self
+                .presets
+                .iter()
+                .filter(|p| tool.is_none_or(|t| p.tool == t))
+                .cloned()
+                .collect(),
+        };
+        subset.save(path)?;
+        Ok(subset.presets.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_tool_field_exists_once() {
+        let serde_json::Value::Object(all) =
+            serde_json::to_value(GradingParams::default()).unwrap()
+        else {
+            panic!("expected an object");
+        };
+        let mut seen = BTreeSet::new();
+        for tool in Tool::ALL {
+            for field in tool.fields() {
+                assert!(all.contains_key(*field), "{tool}: no field {field}");
+                assert!(seen.insert(*field), "{field} belongs to two tools");
+            }
+            let json = serde_json::to_string(&tool).unwrap();
+            assert_eq!(json, format!("\"{}\"", tool.name()));
+        }
+    }
+
+    #[test]
+    fn test_preset_applies_only_its_tool() {
+        let source = GradingParams {
+            contrast: 1.3,
+            pivot: 0.4,
+            saturation: 0.5,
+            ..GradingParams::default()
+        };
+        let preset = ToolPreset::capture("Punchy", Tool::Contrast, "Film", &source).unwrap();
+        assert_eq!(preset.values.len(), Tool::Contrast.fields().len());
+
+        let shot = GradingParams {
+            temperature: 0.2,
+            saturation: 1.1,
+            ..GradingParams::default()
+        };
+        let applied = preset.apply(&shot).unwrap();
+        assert_eq!(applied.contrast, 1.3);
+        assert_eq!(applied.pivot, 0.4);
+        assert_eq!(applied.saturation, 1.1);
+        assert_eq!(applied.temperature, 0.2);
+
+        let foreign = ToolPreset {
+            values: [("saturation".to_string(), serde_json::json!(0.0))]
+                .into_iter()
+                .collect(),
+            ..preset
+        };
+        assert!(matches!(
+            foreign.apply(&shot),
+            Err(PresetError::Empty { .. })
+        ));
+    }
+
+    #[test]
+    fn test_library_round_trips_and_exports_by_tool() {
+        let dir = std::env::temp_dir().join(format!("crispen_presets_{}", std::process::id()));
+        let params = GradingParams::default();
+        let mut library = PresetLibrary::load(&dir.join("missing.json")).unwrap();
+        library.add(ToolPreset::capture("Filmic", Tool::Contrast, "Film", &params).unwrap());
+        library.add(ToolPreset::capture("Warm", Tool::WhiteBalance, "", &params).unwrap());
+        library.add(ToolPreset::capture("Filmic", Tool::Contrast, "Curves", &params).unwrap());
+        assert_eq!(library.presets.len(), 2);
+        assert_eq!(library.categories(), vec!["Curves"]);
+
+        let path = dir.join("presets.json");
+        library.save(&path).unwrap();
+        assert_eq!(PresetLibrary::load(&path).unwrap(), library);
+
+        let exported = dir.join("contrast.json");
+        assert_eq!(library.export(&exported, Some(Tool::Contrast)).unwrap(), 1);
+        let mut other = PresetLibrary::default();
+        assert_eq!(other.import(&exported).unwrap(), 1);
+        assert!(other.apply(Tool::Contrast, "Filmic", &params).is_ok());
+        assert!(matches!(
+            other.apply(Tool::Contrast, "Punchy", &params),
+            Err(PresetError::NotFound { .. })
+        ));
+        assert_eq!(other.for_tool(Tool::WhiteBalance).count(), 0);
+
+        assert!(library.remove(Tool::WhiteBalance, "Warm"));
+        assert!(!library.remove(Tool::WhiteBalance, "Warm"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
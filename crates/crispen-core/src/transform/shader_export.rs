@@ -0,0 +1,481 @@
+//! Export the grading transform as self-contained shader source.
+//!
+//! Emits a single `crispen_grade` function in GLSL, HLSL, or WGSL for
+//! game-engine and realtime pipelines that cannot load `.cube` files.
+//!
+//! When the grade only uses CDL, contrast, and saturation with no color
+//! space conversion, the function is written as analytic math. Otherwise
+//! the transform is baked into a 3D LUT that is embedded in the source,
+//! packed as 10:10:10 unsigned values (one `u32` per entry) rescaled to the
+//! LUT's output range, and sampled with manual trilinear interpolation.
+
+#[cfg(feature = "lut-io")]
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grading::sliders::{HueRotation, LUMA_EQUAL, LUMA_REC709, SaturationModel};
+use crate::transform::lut::Lut3D;
+use crate::transform::params::GradingParams;
+
+/// Target shading language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShaderLanguage {
+    Glsl,
+    Hlsl,
+    Wgsl,
+}
+
+impl ShaderLanguage {
+    /// Conventional file extension (without the dot).
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Glsl => "glsl",
+            Self::Hlsl => "hlsl",
+            Self::Wgsl => "wgsl",
+        }
+    }
+}
+
+/// Name of the generated entry function.
+pub const FUNCTION_NAME: &str = "crispen_grade";
+
+/// Largest value of a 10-bit packed channel.
+const PACKED_MAX: f32 = 1023.0;
+
+/// Whether `params` can be expressed as analytic shader math.
+///
+/// True when input, working, and output spaces match (so both transforms
+/// are pass-through) and only CDL, contrast, and saturation are in use.
+pub fn is_analytic(params: &GradingParams) -> bool {
+    let cm = &params.color_management;
+    cm.input_space == cm.working_space
+        && cm.working_space == cm.output_space
+        && primaries_only(params)
+}
+
+/// Whether the grade uses nothing beyond CDL, contrast, and saturation
+/// (color space conversion aside).
+pub(crate) fn primaries_only(params: &GradingParams) -> bool {
+    params.input_correction.is_identity()
+        && params.input_levels.is_identity()
+        && params.exposure.is_identity()
+        && params.channel_mixer.is_identity()
+        && !params.monochrome.enabled
+        && params.soft_clip.is_identity()
+        && params.layer_blend.is_identity()
+        && params.saturation_model == SaturationModel::Luma
+        && params.temperature.abs() < 1e-7
+        && params.tint.abs() < 1e-7
+        && params.shadows == 0.0
+        && params.highlights == 0.0
+        && params.hue.abs() < 1e-7
+        && params.vibrance == 0.0
+        && params.hue_rotation == HueRotation::default()
+        && !params.has_active_curves()
+}
+
+/// Generate shader source for `params`.
+///
+/// `lut_size` is only used when the grade cannot be written analytically.
+pub fn export_shader(params: &GradingParams, lut_size: u32, language: ShaderLanguage) -> String {
+    let params = &params.with_bypass_applied();
+    if is_analytic(params) {
+        return analytic_source(params, language);
+    }
+    let mut lut = Lut3D::new(lut_size.max(2));
+    lut.bake(params);
+    lut_to_shader(&lut, language)
+}
+
+/// Generate shader source and write it to `path`.
+#[cfg(feature = "lut-io")]
+pub fn save_shader(
+    params: &GradingParams,
+    lut_size: u32,
+    language: ShaderLanguage,
+    path: &Path,
+) -> std::io::Result<()> {
+    std::fs::write(path, export_shader(params, lut_size, language))
+}
+
+/// Generate shader source that embeds and samples `lut`.
+pub fn lut_to_shader(lut: &Lut3D, language: ShaderLanguage) -> String {
+    let packed = pack_lut(lut);
+    let entries = packed
+        .data
+        .chunks(8)
+        .map(|row| {
+            let words: Vec<String> = row.iter().map(|p| format!("0x{p:08x}u")).collect();
+            format!("    {}", words.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    let template = match language {
+        ShaderLanguage::Glsl => GLSL_LUT,
+        ShaderLanguage::Hlsl => HLSL_LUT,
+        ShaderLanguage::Wgsl => WGSL_LUT,
+    };
+    substitute(
+        template,
+        &[
+            ("$SIZE", lut.size.to_string()),
+            ("$COUNT", packed.data.len().to_string()),
+            ("$DOMAIN_MIN", vec3(language, lut.domain_min)),
+            ("$DOMAIN_MAX", vec3(language, lut.domain_max)),
+            ("$RANGE_MIN", vec3(language, packed.range_min)),
+            ("$RANGE_SCALE", vec3(language, packed.range_scale)),
+            ("$ENTRIES", entries),
+            ("$NAME", FUNCTION_NAME.to_string()),
+        ],
+    )
+}
+
+/// A 3D LUT packed as 10:10:10 unsigned words.
+struct PackedLut {
+    data: Vec<u32>,
+    range_min: [f32; 3],
+    range_scale: [f32; 3],
+}
+
+/// Pack each entry's RGB into bits 0–9, 10–19, and 20–29 of a `u32`,
+/// normalized per channel to the LUT's output range.
+fn pack_lut(lut: &Lut3D) -> PackedLut {
+    let mut range_min = [f32::MAX; 3];
+    let mut range_max = [f32::MIN; 3];
+    for entry in &lut.data {
+        for c in 0..3 {
+            range_min[c] = range_min[c].min(entry[c]);
+            range_max[c] = range_max[c].max(entry[c]);
+        }
+    }
+    if lut.data.is_empty() {
+        range_min = [0.0; 3];
+        range_max = [1.0; 3];
+    }
+    let range_scale = [0, 1, 2].map(|c| (range_max[c] - range_min[c]).max(f32::EPSILON));
+
+    let data = lut
+        .data
+        .iter()
+        .map(|entry| {
+            let q = [0, 1, 2].map(|c| {
+                let t = (entry[c] - range_min[c]) / range_scale[c];
+                (t.clamp(0.0, 1.0) * PACKED_MAX).round() as u32
+            });
+            q[0] | (q[1] << 10) | (q[2] << 20)
+        })
+        .collect();
+
+    PackedLut {
+        data,
+        range_min,
+        range_scale,
+    }
+}
+
+fn analytic_source(params: &GradingParams, language: ShaderLanguage) -> String {
+    let lift = params.combined_lift();
+    let gamma = params.combined_gamma();
+    let gain = params.combined_gain();
+    let offset = params.combined_offset();
+
+    let combined_gain = [0, 1, 2].map(|c| gain[c] * gain[3]);
+    let combined_lift = [0, 1, 2].map(|c| lift[c] + lift[3]);
+    let combined_offset = [0, 1, 2].map(|c| offset[c] + offset[3]);
+    // Non-positive gamma is a pass-through in `apply_cdl`.
+    let inv_gamma = [0, 1, 2].map(|c| {
+        let g = gamma[c] * gamma[3];
+        if g > 0.0 { 1.0 / g } else { 1.0 }
+    });
+    let luma = [0, 1, 2]
+        .map(|c| LUMA_REC709[c] * (1.0 - params.luma_mix) + LUMA_EQUAL[c] * params.luma_mix);
+
+    let template = match language {
+        ShaderLanguage::Glsl => GLSL_ANALYTIC,
+        ShaderLanguage::Hlsl => HLSL_ANALYTIC,
+        ShaderLanguage::Wgsl => WGSL_ANALYTIC,
+    };
+    substitute(
+        template,
+        &[
+            ("$GAIN", vec3(language, combined_gain)),
+            ("$LIFT", vec3(language, combined_lift)),
+            ("$OFFSET", vec3(language, combined_offset)),
+            ("$INV_GAMMA", vec3(language, inv_gamma)),
+            ("$CONTRAST", float(params.contrast)),
+            ("$PIVOT", float(params.pivot)),
+            ("$SATURATION", float(params.saturation)),
+            ("$LUMA", vec3(language, luma)),
+            ("$NAME", FUNCTION_NAME.to_string()),
+        ],
+    )
+}
+
+/// Float literal valid in all three languages.
+fn float(v: f32) -> String {
+    let s = format!("{v:?}");
+    if s.contains(['.', 'e']) {
+        s
+    } else {
+        format!("{s}.0")
+    }
+}
+
+fn vec3(language: ShaderLanguage, v: [f32; 3]) -> String {
+    let ctor = match language {
+        ShaderLanguage::Glsl => "vec3",
+        ShaderLanguage::Hlsl => "float3",
+        ShaderLanguage::Wgsl => "vec3<f32>",
+    };
+    format!("{ctor}({}, {}, {})", float(v[0]), float(v[1]), float(v[2]))
+}
+
+fn substitute(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |acc, (key, value)| {
+            acc.replace(key, value)
+        })
+}
+
+// ── Templates ───────────────────────────────────────────────────────
+
+const GLSL_ANALYTIC: &str = "\
+// Generated by Crispen — analytic grade (CDL, contrast, saturation).
+const vec3 CRISPEN_GAIN = $GAIN;
+const vec3 CRISPEN_LIFT = $LIFT;
+const vec3 CRISPEN_OFFSET = $OFFSET;
+const vec3 CRISPEN_INV_GAMMA = $INV_GAMMA;
+const float CRISPEN_CONTRAST = $CONTRAST;
+const float CRISPEN_PIVOT = $PIVOT;
+const float CRISPEN_SATURATION = $SATURATION;
+const vec3 CRISPEN_LUMA = $LUMA;
+
+vec3 $NAME(vec3 rgb) {
+    vec3 c = max(rgb * CRISPEN_GAIN + CRISPEN_LIFT * (1.0 - CRISPEN_GAIN) + CRISPEN_OFFSET, vec3(0.0));
+    c = pow(max(c, vec3(1e-10)), CRISPEN_INV_GAMMA) * step(vec3(1e-10), c);
+    c = pow(max(c, vec3(1e-10)) / CRISPEN_PIVOT, vec3(CRISPEN_CONTRAST)) * CRISPEN_PIVOT * step(vec3(1e-10), c);
+    float luma = dot(c, CRISPEN_LUMA);
+    return vec3(luma) + (c - vec3(luma)) * CRISPEN_SATURATION;
+}
+";
+
+const HLSL_ANALYTIC: &str = "\
+// Generated by Crispen — analytic grade (CDL, contrast, saturation).
+static const float3 CRISPEN_GAIN = $GAIN;
+static const float3 CRISPEN_LIFT = $LIFT;
+static const float3 CRISPEN_OFFSET = $OFFSET;
+static const float3 CRISPEN_INV_GAMMA = $INV_GAMMA;
+static const float CRISPEN_CONTRAST = $CONTRAST;
+static const float CRISPEN_PIVOT = $PIVOT;
+static const float CRISPEN_SATURATION = $SATURATION;
+static const float3 CRISPEN_LUMA = $LUMA;
+
+float3 $NAME(float3 rgb) {
+    float3 c = max(rgb * CRISPEN_GAIN + CRISPEN_LIFT * (1.0 - CRISPEN_GAIN) + CRISPEN_OFFSET, 0.0);
+    c = pow(max(c, 1e-10), CRISPEN_INV_GAMMA) * step(1e-10, c);
+    c = pow(max(c, 1e-10) / CRISPEN_PIVOT, CRISPEN_CONTRAST) * CRISPEN_PIVOT * step(1e-10, c);
+    float luma = dot(c, CRISPEN_LUMA);
+    return luma + (c - luma) * CRISPEN_SATURATION;
+}
+";
+
+const WGSL_ANALYTIC: &str = "\
+// Generated by Crispen — analytic grade (CDL, contrast, saturation).
+const CRISPEN_GAIN = $GAIN;
+const CRISPEN_LIFT = $LIFT;
+const CRISPEN_OFFSET = $OFFSET;
+const CRISPEN_INV_GAMMA = $INV_GAMMA;
+const CRISPEN_CONTRAST: f32 = $CONTRAST;
+const CRISPEN_PIVOT: f32 = $PIVOT;
+const CRISPEN_SATURATION: f32 = $SATURATION;
+const CRISPEN_LUMA = $LUMA;
+
+fn $NAME(rgb: vec3<f32>) -> vec3<f32> {
+    var c = max(rgb * CRISPEN_GAIN + CRISPEN_LIFT * (1.0 - CRISPEN_GAIN) + CRISPEN_OFFSET, vec3(0.0));
+    c = pow(max(c, vec3(1e-10)), CRISPEN_INV_GAMMA) * step(vec3(1e-10), c);
+    c = pow(max(c, vec3(1e-10)) / CRISPEN_PIVOT, vec3(CRISPEN_CONTRAST)) * CRISPEN_PIVOT * step(vec3(1e-10), c);
+    let luma = dot(c, CRISPEN_LUMA);
+    return vec3(luma) + (c - vec3(luma)) * CRISPEN_SATURATION;
+}
+";
+
+const GLSL_LUT: &str = "\
+// Generated by Crispen — $SIZE³ 3D LUT, 10:10:10 packed.
+const int CRISPEN_LUT_SIZE = $SIZE;
+const vec3 CRISPEN_DOMAIN_MIN = $DOMAIN_MIN;
+const vec3 CRISPEN_DOMAIN_MAX = $DOMAIN_MAX;
+const vec3 CRISPEN_RANGE_MIN = $RANGE_MIN;
+const vec3 CRISPEN_RANGE_SPAN = $RANGE_SCALE;
+const uint CRISPEN_LUT[$COUNT] = uint[$COUNT](
+$ENTRIES
+);
+
+vec3 crispen_lut_fetch(ivec3 i) {
+    uint p = CRISPEN_LUT[(i.z * CRISPEN_LUT_SIZE + i.y) * CRISPEN_LUT_SIZE + i.x];
+    vec3 q = vec3(float(p & 1023u), float((p >> 10u) & 1023u), float((p >> 20u) & 1023u)) / 1023.0;
+    return CRISPEN_RANGE_MIN + q * CRISPEN_RANGE_SPAN;
+}
+
+vec3 $NAME(vec3 rgb) {
+    vec3 t = clamp((rgb - CRISPEN_DOMAIN_MIN) / (CRISPEN_DOMAIN_MAX - CRISPEN_DOMAIN_MIN), 0.0, 1.0)
+        * float(CRISPEN_LUT_SIZE - 1);
+    ivec3 i0 = min(ivec3(floor(t)), ivec3(CRISPEN_LUT_SIZE - 2));
+    vec3 f = t - vec3(i0);
+    vec3 c00 = mix(crispen_lut_fetch(i0), crispen_lut_fetch(i0 + ivec3(1, 0, 0)), f.x);
+    vec3 c10 = mix(crispen_lut_fetch(i0 + ivec3(0, 1, 0)), crispen_lut_fetch(i0 + ivec3(1, 1, 0)), f.x);
+    vec3 c01 = mix(crispen_lut_fetch(i0 + ivec3(0, 0, 1)), crispen_lut_fetch(i0 + ivec3(1, 0, 1)), f.x);
+    vec3 c11 = mix(crispen_lut_fetch(i0 + ivec3(0, 1, 1)), crispen_lut_fetch(i0 + ivec3(1, 1, 1)), f.x);
+    return mix(mix(c00, c10, f.y), mix(c01, c11, f.y), f.z);
+}
+";
+
+const HLSL_LUT: &str = "\
+// Generated by Crispen — $SIZE³ 3D LUT, 10:10:10 packed.
+static const int CRISPEN_LUT_SIZE = $SIZE;
+static const float3 CRISPEN_DOMAIN_MIN = $DOMAIN_MIN;
+static const float3 CRISPEN_DOMAIN_MAX = $DOMAIN_MAX;
+static const float3 CRISPEN_RANGE_MIN = $RANGE_MIN;
+static const float3 CRISPEN_RANGE_SPAN = $RANGE_SCALE;
+static const uint CRISPEN_LUT[$COUNT] = {
+$ENTRIES
+};
+
+float3 crispen_lut_fetch(int3 i) {
+    uint p = CRISPEN_LUT[(i.z * CRISPEN_LUT_SIZE + i.y) * CRISPEN_LUT_SIZE + i.x];
+    float3 q = float3(p & 1023u, (p >> 10) & 1023u, (p >> 20) & 1023u) / 1023.0;
+    return CRISPEN_RANGE_MIN + q * CRISPEN_RANGE_SPAN;
+}
+
+float3 $NAME(float3 rgb) {
+    float3 t = saturate((rgb - CRISPEN_DOMAIN_MIN) / (CRISPEN_DOMAIN_MAX - CRISPEN_DOMAIN_MIN))
+        * (CRISPEN_LUT_SIZE - 1);
+    int3 i0 = min(int3(floor(t)), CRISPEN_LUT_SIZE - 2);
+    float3 f = t - i0;
+    float3 c00 = lerp(crispen_lut_fetch(i0), crispen_lut_fetch(i0 + int3(1, 0, 0)), f.x);
+    float3 c10 = lerp(crispen_lut_fetch(i0 + int3(0, 1, 0)), crispen_lut_fetch(i0 + int3(1, 1, 0)), f.x);
+    float3 c01 = lerp(crispen_lut_fetch(i0 + int3(0, 0, 1)), crispen_lut_fetch(i0 + int3(1, 0, 1)), f.x);
+    float3 c11 = lerp(crispen_lut_fetch(i0 + int3(0, 1, 1)), crispen_lut_fetch(i0 + int3(1, 1, 1)), f.x);
+    return lerp(lerp(c00, c10, f.y), lerp(c01, c11, f.y), f.z);
+}
+";
+
+const WGSL_LUT: &str = "\
+// Generated by Crispen — $SIZE³ 3D LUT, 10:10:10 packed.
+const CRISPEN_LUT_SIZE: i32 = $SIZE;
+const CRISPEN_DOMAIN_MIN = $DOMAIN_MIN;
+const CRISPEN_DOMAIN_MAX = $DOMAIN_MAX;
+const CRISPEN_RANGE_MIN = $RANGE_MIN;
+const CRISPEN_RANGE_SPAN = $RANGE_SCALE;
+var<private> CRISPEN_LUT: array<u32, $COUNT> = array<u32, $COUNT>(
+$ENTRIES
+);
+
+fn crispen_lut_fetch(i: vec3<i32>) -> vec3<f32> {
+    let p = CRISPEN_LUT[(i.z * CRISPEN_LUT_SIZE + i.y) * CRISPEN_LUT_SIZE + i.x];
+    let q = vec3<f32>(f32(p & 1023u), f32((p >> 10u) & 1023u), f32((p >> 20u) & 1023u)) / 1023.0;
+    return CRISPEN_RANGE_MIN + q * CRISPEN_RANGE_SPAN;
+}
+
+fn $NAME(rgb: vec3<f32>) -> vec3<f32> {
+    let t = clamp((rgb - CRISPEN_DOMAIN_MIN) / (CRISPEN_DOMAIN_MAX - CRISPEN_DOMAIN_MIN), vec3(0.0), vec3(1.0))
+        * f32(CRISPEN_LUT_SIZE - 1);
+    let i0 = min(vec3<i32>(floor(t)), vec3<i32>(CRISPEN_LUT_SIZE - 2));
+    let f = t - vec3<f32>(i0);
+    let c00 = mix(crispen_lut_fetch(i0), crispen_lut_fetch(i0 + vec3<i32>(1, 0, 0)), f.x);
+    let c10 = mix(crispen_lut_fetch(i0 + vec3<i32>(0, 1, 0)), crispen_lut_fetch(i0 + vec3<i32>(1, 1, 0)), f.x);
+    let c01 = mix(crispen_lut_fetch(i0 + vec3<i32>(0, 0, 1)), crispen_lut_fetch(i0 + vec3<i32>(1, 0, 1)), f.x);
+    let c11 = mix(crispen_lut_fetch(i0 + vec3<i32>(0, 1, 1)), crispen_lut_fetch(i0 + vec3<i32>(1, 1, 1)), f.x);
+    return mix(mix(c00, c10, f.y), mix(c01, c11, f.y), f.z);
+}
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::params::{ColorManagementConfig, ColorSpaceId, DisplayOetf};
+
+    fn same_space_params() -> GradingParams {
+        GradingParams {
+            color_management: ColorManagementConfig {
+                input_space: ColorSpaceId::AcesCg,
+                working_space: ColorSpaceId::AcesCg,
+                output_space: ColorSpaceId::AcesCg,
+                display_oetf: DisplayOetf::Srgb,
+                ..Default::default()
+            },
+            ..GradingParams::default()
+        }
+    }
+
+    #[test]
+    fn test_cdl_only_grade_is_analytic() {
+        let mut params = same_space_params();
+        params.gain = [1.2, 1.0, 0.9, 1.0];
+        params.contrast = 1.3;
+        params.saturation = 0.8;
+        assert!(is_analytic(&params));
+
+        params.hue_vs_sat = vec![[0.0, 0.5], [1.0, 0.5]];
+        assert!(!is_analytic(&params));
+    }
+
+    #[test]
+    fn test_color_space_conversion_forces_lut() {
+        let params = GradingParams::default();
+        assert!(!is_analytic(&params));
+        let src = export_shader(&params, 5, ShaderLanguage::Glsl);
+        assert!(src.contains("CRISPEN_LUT[125]"));
+    }
+
+    #[test]
+    fn test_every_language_defines_entry_function() {
+        let analytic = same_space_params();
+        let mut baked = same_space_params();
+        baked.shadows = 0.1;
+        for language in [
+            ShaderLanguage::Glsl,
+            ShaderLanguage::Hlsl,
+            ShaderLanguage::Wgsl,
+        ] {
+            for params in [&analytic, &baked] {
+                let src = export_shader(params, 5, language);
+                assert!(src.contains(FUNCTION_NAME), "{language:?}");
+                assert!(!src.contains('$'), "unreplaced placeholder in {language:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_packed_lut_decodes_within_quantization_error() {
+        let mut params = same_space_params();
+        params.shadows = 0.2;
+        params.saturation = 1.4;
+        let mut lut = Lut3D::new(9);
+        lut.bake(&params);
+
+        let packed = pack_lut(&lut);
+        for (word, entry) in packed.data.iter().zip(&lut.data) {
+            for (c, &expected) in entry.iter().enumerate().take(3) {
+                let q = ((word >> (10 * c)) & 0x3FF) as f32 / PACKED_MAX;
+                let decoded = packed.range_min[c] + q * packed.range_scale[c];
+                let tolerance = packed.range_scale[c] / PACKED_MAX;
+                assert!(
+                    (decoded - expected).abs() <= tolerance,
+                    "ch{c}: {decoded} vs {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_literals_always_have_decimal_point() {
+        assert_eq!(float(1.0), "1.0");
+        assert_eq!(float(0.25), "0.25");
+        assert_eq!(float(-2.0), "-2.0");
+    }
+}
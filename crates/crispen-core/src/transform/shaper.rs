@@ -0,0 +1,308 @@
+//! Shaper 1D + 3D LUT export for scene-linear input.
+//!
+//! A plain 3D LUT spaces its grid evenly in input code values. Fed
+//! scene-linear data, almost every grid point lands in the highlights and
+//! the shadows fall between the first two, which bands. A shaper 1D LUT
+//! first maps linear input into a log space covering the useful range, and
+//! the 3D LUT is baked on that log grid.
+//!
+//! Written as a single Resolve-style `.cube` holding both tables:
+//! ```text
+//! TITLE "Crispen LUT"
+//! LUT_1D_SIZE 16384
+//! LUT_1D_INPUT_RANGE 0.001989 16.291730
+//! LUT_3D_SIZE 33
+//! LUT_3D_INPUT_RANGE 0.000000 1.000000
+//! <1D entries, then 3D entries>
+//! ```
+
+#[cfg(feature = "lut-io")]
+use std::io::Write as IoWrite;
+#[cfg(feature = "lut-io")]
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::color_management::transfer::{AcesCctTransfer, TransferFunction, get_transfer};
+use crate::transform::evaluate::evaluate_transform;
+use crate::transform::lut::Lut3D;
+use crate::transform::params::GradingParams;
+
+/// Default shaper length; fine enough that linear interpolation between
+/// entries is invisible next to the 3D LUT's own error.
+pub const DEFAULT_SHAPER_SIZE: u32 = 16_384;
+
+/// Exposure range of the [`ShaperTransfer::Log2`] shaper, in stops around
+/// 18% grey (the ACES lg2 shaper range).
+pub const LOG2_MIN_STOPS: f32 = -6.5;
+pub const LOG2_MAX_STOPS: f32 = 6.5;
+
+/// Middle grey, the centre of the Log2 shaper.
+const MID_GREY: f32 = 0.18;
+
+/// Curve used to map scene-linear input onto the 3D LUT grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShaperTransfer {
+    /// Pure log2 over ±6.5 stops around 18% grey.
+    Log2,
+    /// ACEScct, covering linear 0 to ~222 with a toe for the shadows.
+    AcesCct,
+    /// The input color space's own log curve; ACEScct for linear inputs.
+    SourceLog,
+}
+
+/// A shaper 1D LUT and the 3D LUT baked on its output.
+#[derive(Debug, Clone)]
+pub struct ShapedLut {
+    /// Linear input range covered by the shaper.
+    pub input_range: [f32; 2],
+    /// Shaper output in [0, 1] at evenly spaced inputs across `input_range`.
+    pub shaper: Vec<f32>,
+    /// 3D LUT indexed by shaper output.
+    pub lut: Lut3D,
+}
+
+/// Resolved shaper curve for a given input space.
+enum Curve {
+    Log2,
+    Transfer(Box<dyn TransferFunction>),
+}
+
+impl Curve {
+    fn new(shaper: ShaperTransfer, params: &GradingParams) -> Self {
+        match shaper {
+            ShaperTransfer::Log2 => Self::Log2,
+            ShaperTransfer::AcesCct => Self::Transfer(Box::new(AcesCctTransfer)),
+            ShaperTransfer::SourceLog => Self::Transfer(
+                get_transfer(params.color_management.input_space)
+                    .unwrap_or_else(|| Box::new(AcesCctTransfer)),
+            ),
+        }
+    }
+
+    /// Linear → shaper space, clamped to [0, 1].
+    fn encode(&self, linear: f32) -> f32 {
+        let s = match self {
+            Self::Log2 => {
+                let stops = (linear.max(f32::MIN_POSITIVE) / MID_GREY).log2();
+                (stops - LOG2_MIN_STOPS) / (LOG2_MAX_STOPS - LOG2_MIN_STOPS)
+            }
+            Self::Transfer(tf) => tf.to_encoded(linear),
+        };
+        s.clamp(0.0, 1.0)
+    }
+
+    /// Shaper space → linear.
+    fn decode(&self, s: f32) -> f32 {
+        match self {
+            Self::Log2 => {
+                MID_GREY * (s * (LOG2_MAX_STOPS - LOG2_MIN_STOPS) + LOG2_MIN_STOPS).exp2()
+            }
+            Self::Transfer(tf) => tf.to_linear(s),
+        }
+    }
+}
+
+impl ShapedLut {
+    /// Bake `params` behind the given shaper.
+    ///
+    /// The combined LUT takes scene-linear values in the input gamut. Where
+    /// the input space is log-encoded, each grid point is re-encoded before
+    /// evaluation, so the result applies to linearized camera data.
+    pub fn bake(
+        params: &GradingParams,
+        shaper: ShaperTransfer,
+        shaper_size: u32,
+        lut_size: u32,
+    ) -> Self {
+        let curve = Curve::new(shaper, params);
+        let input_encoding = get_transfer(params.color_management.input_space);
+        let input_range = [curve.decode(0.0), curve.decode(1.0)];
+
+        let shaper_size = shaper_size.max(2);
+        let step = (input_range[1] - input_range[0]) / (shaper_size - 1) as f32;
+        let shaper_table = (0..shaper_size)
+            .map(|i| curve.encode(input_range[0] + i as f32 * step))
+            .collect();
+
+        let lut_size = lut_size.max(2);
+        let mut lut = Lut3D::new(lut_size);
+        let n = (lut_size - 1) as f32;
+        for b in 0..lut_size {
+            for g in 0..lut_size {
+                for r in 0..lut_size {
+                    let linear = [r, g, b].map(|i| curve.decode(i as f32 / n));
+                    let input = match &input_encoding {
+                        Some(tf) => linear.map(|v| tf.to_encoded(v)),
+                        None => linear,
+                    };
+                    let out = evaluate_transform(input, params);
+                    let idx = ((b * lut_size + g) * lut_size + r) as usize;
+                    lut.data[idx] = [out[0], out[1], out[2], 1.0];
+                }
+            }
+        }
+
+        Self {
+            input_range,
+            shaper: shaper_table,
+            lut,
+        }
+    }
+
+    /// Apply shaper then 3D LUT to a scene-linear pixel.
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        self.lut.apply(rgb.map(|v| self.shape(v)))
+    }
+
+    /// Linearly interpolate the shaper table.
+    fn shape(&self, linear: f32) -> f32 {
+        let [lo, hi] = self.input_range;
+        let last = self.shaper.len() - 1;
+        let x = ((linear - lo) / (hi - lo)).clamp(0.0, 1.0) * last as f32;
+        let i = (x.floor() as usize).min(last - 1);
+        let f = x - i as f32;
+        self.shaper[i] * (1.0 - f) + self.shaper[i + 1] * f
+    }
+
+    /// Save as a Resolve-style `.cube` with a 1D shaper section.
+    #[cfg(feature = "lut-io")]
+    pub fn save_cube(&self, path: &Path) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        writeln!(writer, "TITLE \"Crispen LUT\"")?;
+        writeln!(writer, "LUT_1D_SIZE {}", self.shaper.len())?;
+        writeln!(
+            writer,
+            "LUT_1D_INPUT_RANGE {:.6} {:.6}",
+            self.input_range[0], self.input_range[1]
+        )?;
+        writeln!(writer, "LUT_3D_SIZE {}", self.lut.size)?;
+        writeln!(writer, "LUT_3D_INPUT_RANGE 0.000000 1.000000")?;
+
+        for s in &self.shaper {
+            writeln!(writer, "{s:.6} {s:.6} {s:.6}")?;
+        }
+        for entry in &self.lut.data {
+            writeln!(writer, "{:.6} {:.6} {:.6}", entry[0], entry[1], entry[2])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Bake and write a shaper + 3D `.cube` for `params`.
+#[cfg(feature = "lut-io")]
+pub fn save_shaped_cube(
+    params: &GradingParams,
+    path: &Path,
+    shaper: ShaperTransfer,
+    lut_size: u32,
+) -> std::io::Result<()> {
+    ShapedLut::bake(params, shaper, DEFAULT_SHAPER_SIZE, lut_size).save_cube(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::params::{ColorManagementConfig, ColorSpaceId};
+
+    /// Scene-linear ACEScg in, sRGB-encoded out: the case that bands.
+    fn linear_to_display() -> GradingParams {
+        GradingParams {
+            color_management: ColorManagementConfig {
+                input_space: ColorSpaceId::AcesCg,
+                working_space: ColorSpaceId::AcesCg,
+                output_space: ColorSpaceId::Srgb,
+                ..ColorManagementConfig::default()
+            },
+            saturation: 1.2,
+            ..GradingParams::default()
+        }
+    }
+
+    /// Largest channel error against direct evaluation over 0.02–8.0 linear.
+    fn max_error(params: &GradingParams, apply: impl Fn([f32; 3]) -> [f32; 3]) -> f32 {
+        let mut worst = 0.0_f32;
+        for i in 0..200 {
+            let v = 0.02 * (8.0_f32 / 0.02).powf(i as f32 / 199.0);
+            let rgb = [v, v * 0.8, v * 0.6];
+            let (got, want) = (apply(rgb), evaluate_transform(rgb, params));
+            for c in 0..3 {
+                worst = worst.max((got[c] - want[c]).abs());
+            }
+        }
+        worst
+    }
+
+    #[test]
+    fn test_shaper_beats_plain_lut_on_linear_input() {
+        let params = linear_to_display();
+        let shaped = ShapedLut::bake(&params, ShaperTransfer::Log2, 4096, 33);
+
+        let mut plain = Lut3D::new(33);
+        plain.domain_max = [16.0; 3];
+        plain.bake(&params);
+
+        let shaped_err = max_error(&params, |rgb| shaped.apply(rgb));
+        let plain_err = max_error(&params, |rgb| plain.apply(rgb));
+        assert!(shaped_err < 0.01, "shaped error {shaped_err}");
+        assert!(
+            shaped_err * 4.0 < plain_err,
+            "shaped {shaped_err} vs plain {plain_err}"
+        );
+    }
+
+    #[test]
+    fn test_acescct_shaper_is_accurate() {
+        // ACEScct spans ~18 stops, so each 33³ cell is coarser than Log2's.
+        let params = linear_to_display();
+        let shaped = ShapedLut::bake(&params, ShaperTransfer::AcesCct, 16_384, 33);
+        let err = max_error(&params, |rgb| shaped.apply(rgb));
+        assert!(err < 0.02, "error {err}");
+    }
+
+    #[test]
+    fn test_source_log_shaper_uses_input_curve() {
+        // S-Log3 input: the shaper re-encodes linear to S-Log3, so the 3D
+        // grid matches a plain LUT baked on code values.
+        let params = GradingParams {
+            color_management: ColorManagementConfig {
+                input_space: ColorSpaceId::SLog3,
+                ..ColorManagementConfig::default()
+            },
+            ..GradingParams::default()
+        };
+        let shaped = ShapedLut::bake(&params, ShaperTransfer::SourceLog, 64, 9);
+        let mut plain = Lut3D::new(9);
+        plain.bake(&params);
+        for (a, b) in shaped.lut.data.iter().zip(&plain.data) {
+            for c in 0..3 {
+                assert!((a[c] - b[c]).abs() < 1e-4, "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "lut-io")]
+    fn test_cube_has_shaper_then_3d_section() {
+        let params = linear_to_display();
+        let shaped = ShapedLut::bake(&params, ShaperTransfer::Log2, 16, 3);
+        let path = std::env::temp_dir().join("crispen_shaper_test.cube");
+        shaped.save_cube(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "LUT_1D_SIZE 16");
+        assert!(lines[2].starts_with("LUT_1D_INPUT_RANGE 0.001989 16.29"));
+        assert_eq!(lines[3], "LUT_3D_SIZE 3");
+        assert_eq!(lines[4], "LUT_3D_INPUT_RANGE 0.000000 1.000000");
+        assert_eq!(lines.len(), 5 + 16 + 27);
+        assert_eq!(lines[5], "0.000000 0.000000 0.000000");
+        assert_eq!(lines[5 + 15], "1.000000 1.000000 1.000000");
+    }
+}
@@ -0,0 +1,319 @@
+//! Range and sanity checks for [`GradingParams`].
+//!
+//! The ranges match what the Crispen editors offer, so a grade that passes
+//! [`GradingParams::validate`] can be shown and edited without clipping
+//! any control. Values outside them still evaluate; the checks exist for
+//! programmatic users who want to catch a bad grade before it is baked.
+
+use crate::transform::params::GradingParams;
+
+/// Lift, bars and wheels, per channel.
+pub const LIFT_RANGE: (f32, f32) = (-1.0, 1.0);
+/// Gamma, bars and wheels, per channel.
+pub const GAMMA_RANGE: (f32, f32) = (0.0, 4.0);
+/// Gain, bars and wheels, per channel.
+pub const GAIN_RANGE: (f32, f32) = (0.0, 4.0);
+/// Offset, bars and wheels, per channel.
+pub const OFFSET_RANGE: (f32, f32) = (-1.0, 1.0);
+/// Temperature and tint.
+pub const WHITE_BALANCE_RANGE: (f32, f32) = (-100.0, 100.0);
+/// Contrast and saturation multipliers.
+pub const MULTIPLIER_RANGE: (f32, f32) = (0.0, 4.0);
+/// Contrast pivot and luma mix.
+pub const UNIT_RANGE: (f32, f32) = (0.0, 1.0);
+/// Midtone detail, dehaze, shadows, highlights, and vibrance.
+pub const RECOVERY_RANGE: (f32, f32) = (-1.0, 1.0);
+/// Input exposure in stops.
+pub const EXPOSURE_RANGE: (f32, f32) = (-8.0, 8.0);
+/// Input exposure offset on source code values.
+pub const LOG_OFFSET_RANGE: (f32, f32) = (-0.5, 0.5);
+/// Chromatic aberration red and blue magnification.
+pub const LATERAL_CA_RANGE: (f32, f32) = (-0.02, 0.02);
+/// Hue rotation in degrees.
+pub const HUE_RANGE: (f32, f32) = (-180.0, 180.0);
+/// Soft clip toe point.
+pub const TOE_RANGE: (f32, f32) = (0.0, 0.5);
+/// Glow intensity.
+pub const GLOW_RANGE: (f32, f32) = (0.0, 2.0);
+/// Black and white response contrast.
+pub const RESPONSE_RANGE: (f32, f32) = (0.5, 2.0);
+
+/// A problem found in a grade.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ParamIssue {
+    /// A value is NaN or infinite.
+    #[error("{field} is not a finite number")]
+    NotFinite { field: String },
+    /// A value lies outside the range the editors offer.
+    #[error("{field} = {value} is outside {min}..={max}")]
+    OutOfRange {
+        field: String,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+    /// A curve control point lies outside 0..=1 on the input axis.
+    #[error("{field} point {index} has input {x} outside 0..=1")]
+    CurvePointOutOfRange { field: String, index: usize, x: f32 },
+    /// Curve control points are not sorted by input.
+    #[error("{field} point {index} is not after the previous point")]
+    CurveNotSorted { field: String, index: usize },
+    /// An input black point is not below its white point.
+    #[error("{field} black point is not below its white point")]
+    LevelsInverted { field: String },
+}
+
+/// Per-channel black points above or at their white points.
+pub(crate) fn inverted_levels(black: &[f32; 3], white: &[f32; 3]) -> Vec<ParamIssue> {
+    ["r", "g", "b"]
+        .into_iter()
+        .zip(black.iter().zip(white))
+        .filter(|(_, (b, w))| b >= w)
+        .map(|(channel, _)| ParamIssue::LevelsInverted {
+            field: format!("input_levels.{channel}"),
+        })
+        .collect()
+}
+
+/// Check a single value, returning its issue if any.
+pub(crate) fn check_value(field: &str, value: f32, (min, max): (f32, f32)) -> Option<ParamIssue> {
+    if !value.is_finite() {
+        Some(ParamIssue::NotFinite {
+            field: field.to_owned(),
+        })
+    } else if value < min || value > max {
+        Some(ParamIssue::OutOfRange {
+            field: field.to_owned(),
+            value,
+            min,
+            max,
+        })
+    } else {
+        None
+    }
+}
+
+fn check_channels(issues: &mut Vec<ParamIssue>, field: &str, values: &[f32], range: (f32, f32)) {
+    const CHANNELS: [&str; 4] = ["r", "g", "b", "master"];
+    for (value, channel) in values.iter().zip(CHANNELS) {
+        issues.extend(check_value(&format!("{field}.{channel}"), *value, range));
+    }
+}
+
+fn check_finite(issues: &mut Vec<ParamIssue>, field: &str, values: &[f32]) {
+    if values.iter().any(|v| !v.is_finite()) {
+        issues.push(ParamIssue::NotFinite {
+            field: field.to_owned(),
+        });
+    }
+}
+
+fn check_curve(issues: &mut Vec<ParamIssue>, field: &str, points: &[[f32; 2]]) {
+    for (index, point) in points.iter().enumerate() {
+        if !point[0].is_finite() || !point[1].is_finite() {
+            issues.push(ParamIssue::NotFinite {
+                field: format!("{field}[{index}]"),
+            });
+            continue;
+        }
+        if !(0.0..=1.0).contains(&point[0]) {
+            issues.push(ParamIssue::CurvePointOutOfRange {
+                field: field.to_owned(),
+                index,
+                x: point[0],
+            });
+        }
+        if index > 0 && point[0] < points[index - 1][0] {
+            issues.push(ParamIssue::CurveNotSorted {
+                field: field.to_owned(),
+                index,
+            });
+        }
+    }
+}
+
+impl GradingParams {
+    /// Every problem in this grade, in field order. Empty when the grade is
+    /// finite and within the editor ranges.
+    pub fn validate(&self) -> Vec<ParamIssue> {
+        let mut issues = Vec::new();
+        let ic = &self.input_correction;
+        check_finite(&mut issues, "input_correction.gain", &ic.gain);
+        check_finite(&mut issues, "input_correction.power", &ic.power);
+        check_finite(
+            &mut issues,
+            "input_correction.matrix",
+            ic.matrix.as_flattened(),
+        );
+        let mixer = &self.channel_mixer;
+        check_finite(
+            &mut issues,
+            "channel_mixer.matrix",
+            mixer.matrix.as_flattened(),
+        );
+        check_finite(&mut issues, "channel_mixer.offset", &mixer.offset);
+        check_finite(&mut issues, "monochrome.weights", &self.monochrome.weights);
+        check_finite(&mut issues, "monochrome.tint", &self.monochrome.tint);
+        check_finite(&mut issues, "glow.tint", &self.glow.tint);
+        let levels = &self.input_levels;
+        check_channels(&mut issues, "input_levels.black", &levels.black, UNIT_RANGE);
+        check_channels(&mut issues, "input_levels.white", &levels.white, UNIT_RANGE);
+        issues.extend(inverted_levels(&levels.black, &levels.white));
+
+        for (field, values, range) in [
+            ("lift", &self.lift, LIFT_RANGE),
+            ("gamma", &self.gamma, GAMMA_RANGE),
+            ("gain", &self.gain, GAIN_RANGE),
+            ("offset", &self.offset, OFFSET_RANGE),
+            ("lift_wheel", &self.lift_wheel, LIFT_RANGE),
+            ("gamma_wheel", &self.gamma_wheel, GAMMA_RANGE),
+            ("gain_wheel", &self.gain_wheel, GAIN_RANGE),
+            ("offset_wheel", &self.offset_wheel, OFFSET_RANGE),
+        ] {
+            check_channels(&mut issues, field, values, range);
+        }
+
+        for (field, value, range) in [
+            ("temperature", self.temperature, WHITE_BALANCE_RANGE),
+            ("tint", self.tint, WHITE_BALANCE_RANGE),
+            ("contrast", self.contrast, MULTIPLIER_RANGE),
+            ("pivot", self.pivot, UNIT_RANGE),
+            ("midtone_detail", self.midtone_detail, RECOVERY_RANGE),
+            ("exposure.stops", self.exposure.stops, EXPOSURE_RANGE),
+            (
+                "exposure.log_offset",
+                self.exposure.log_offset,
+                LOG_OFFSET_RANGE,
+            ),
+            (
+                "chromatic_aberration.red_scale",
+                self.chromatic_aberration.red_scale,
+                LATERAL_CA_RANGE,
+            ),
+            (
+                "chromatic_aberration.blue_scale",
+                self.chromatic_aberration.blue_scale,
+                LATERAL_CA_RANGE,
+            ),
+            (
+                "chromatic_aberration.fringe",
+                self.chromatic_aberration.fringe,
+                UNIT_RANGE,
+            ),
+            ("dehaze.strength", self.dehaze.strength, RECOVERY_RANGE),
+            ("dehaze.depth_bias", self.dehaze.depth_bias, RECOVERY_RANGE),
+            ("shadows", self.shadows, RECOVERY_RANGE),
+            ("highlights", self.highlights, RECOVERY_RANGE),
+            ("saturation", self.saturation, MULTIPLIER_RANGE),
+            ("vibrance", self.vibrance, RECOVERY_RANGE),
+            ("hue", self.hue, HUE_RANGE),
+            ("luma_mix", self.luma_mix, UNIT_RANGE),
+            (
+                "monochrome.contrast",
+                self.monochrome.contrast,
+                RESPONSE_RANGE,
+            ),
+            (
+                "monochrome.tint_strength",
+                self.monochrome.tint_strength,
+                UNIT_RANGE,
+            ),
+            ("soft_clip.knee", self.soft_clip.knee, UNIT_RANGE),
+            ("soft_clip.strength", self.soft_clip.strength, UNIT_RANGE),
+            ("soft_clip.toe", self.soft_clip.toe, TOE_RANGE),
+            (
+                "soft_clip.toe_strength",
+                self.soft_clip.toe_strength,
+                UNIT_RANGE,
+            ),
+            ("layer_blend.mix", self.layer_blend.mix, UNIT_RANGE),
+            ("glow.threshold", self.glow.threshold, UNIT_RANGE),
+            ("glow.spread", self.glow.spread, UNIT_RANGE),
+            ("glow.intensity", self.glow.intensity, GLOW_RANGE),
+        ] {
+            issues.extend(check_value(field, value, range));
+        }
+        for (range, value) in ["shadows", "midtones", "highlights"]
+            .into_iter()
+            .zip(self.hue_rotation.ranges)
+        {
+            let field = format!("hue_rotation.{range}");
+            issues.extend(check_value(&field, value, HUE_RANGE));
+        }
+
+        for (field, points) in [
+            ("hue_vs_hue", &self.hue_vs_hue),
+            ("hue_vs_sat", &self.hue_vs_sat),
+            ("lum_vs_sat", &self.lum_vs_sat),
+            ("sat_vs_sat", &self.sat_vs_sat),
+        ] {
+            check_curve(&mut issues, field, points);
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_params_are_valid() {
+        assert!(GradingParams::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_inverted_levels() {
+        let mut params = GradingParams::default();
+        params.input_levels.black = [0.1, 0.5, 0.0];
+        params.input_levels.white = [0.9, 0.5, 1.0];
+        assert_eq!(
+            params.validate(),
+            vec![ParamIssue::LevelsInverted {
+                field: "input_levels.g".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_each_problem() {
+        let params = GradingParams {
+            saturation: f32::NAN,
+            hue: 270.0,
+            gain_wheel: [1.0, 5.0, 1.0, 1.0],
+            hue_vs_sat: vec![[0.5, 1.0], [0.2, 1.0], [1.5, 1.0]],
+            ..GradingParams::default()
+        };
+        let issues = params.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ParamIssue::OutOfRange {
+                    field: "gain_wheel.g".into(),
+                    value: 5.0,
+                    min: 0.0,
+                    max: 4.0,
+                },
+                ParamIssue::NotFinite {
+                    field: "saturation".into(),
+                },
+                ParamIssue::OutOfRange {
+                    field: "hue".into(),
+                    value: 270.0,
+                    min: -180.0,
+                    max: 180.0,
+                },
+                ParamIssue::CurveNotSorted {
+                    field: "hue_vs_sat".into(),
+                    index: 1,
+                },
+                ParamIssue::CurvePointOutOfRange {
+                    field: "hue_vs_sat".into(),
+                    index: 2,
+                    x: 1.5,
+                },
+            ]
+        );
+        assert_eq!(issues[1].to_string(), "saturation is not a finite number");
+    }
+}
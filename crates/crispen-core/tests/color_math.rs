@@ -0,0 +1,261 @@
+//! Property tests for the color math: transfer function round trips and
+//! monotonicity, and gamut matrix invertibility, transitivity, and white
+//! preservation.
+//!
+//! Samples come from a fixed-seed generator, so failures reproduce exactly.
+//! Pure CPU; run with: `cargo test -p crispen-core --test color_math`
+
+use crispen_core::color_management::aces::{apply_input_transform, apply_output_transform};
+use crispen_core::color_management::color_space::{
+    get_conversion_matrix, rgb_to_xyz_matrix, xyz_to_rgb_matrix,
+};
+use crispen_core::color_management::transfer::{TransferFunction, get_transfer};
+use crispen_core::transform::params::{ColorManagementConfig, ColorSpaceId};
+
+/// Samples drawn per property.
+const SAMPLES: usize = 2_000;
+
+/// Deterministic xorshift64* generator.
+struct Sampler(u64);
+
+impl Sampler {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[lo, hi)`.
+    fn range(&mut self, lo: f32, hi: f32) -> f32 {
+        lo + (hi - lo) * self.unit() as f32
+    }
+
+    /// Log-uniform in `[lo, hi)`; covers shadows as densely as highlights.
+    fn log_range(&mut self, lo: f32, hi: f32) -> f32 {
+        (lo.ln() + (hi.ln() - lo.ln()) * self.unit() as f32).exp()
+    }
+
+    fn rgb(&mut self, lo: f32, hi: f32) -> [f32; 3] {
+        [self.range(lo, hi), self.range(lo, hi), self.range(lo, hi)]
+    }
+}
+
+/// Error relative to `expected`, absolute near zero.
+fn rel_error(actual: f32, expected: f32) -> f32 {
+    (actual - expected).abs() / expected.abs().max(1.0)
+}
+
+fn encoded_spaces() -> Vec<(ColorSpaceId, Box<dyn TransferFunction>)> {
+    ColorSpaceId::all()
+        .iter()
+        .filter_map(|&space| get_transfer(space).map(|tf| (space, tf)))
+        .collect()
+}
+
+/// Linear range each curve is specified for: from just above its black
+/// floor to well past the brightest highlight it can encode.
+fn linear_domain(space: ColorSpaceId) -> (f32, f32) {
+    match space {
+        ColorSpaceId::Srgb => (1e-5, 1.0),
+        ColorSpaceId::AcesCc | ColorSpaceId::AcesCct => (1e-4, 65_504.0),
+        ColorSpaceId::ArriLogC3 => (1e-4, 55.0),
+        ColorSpaceId::ArriLogC4 => (1e-4, 469.0),
+        ColorSpaceId::SLog3 => (1e-4, 38.0),
+        ColorSpaceId::RedLog3G10 => (1e-4, 184.0),
+        ColorSpaceId::VLog => (1e-4, 46.0),
+        _ => (1e-4, 1.0),
+    }
+}
+
+#[test]
+fn transfer_round_trip_linear_encoded_linear() {
+    let mut rng = Sampler::new(0x0c01_0e5a);
+    for (space, tf) in encoded_spaces() {
+        let (lo, hi) = linear_domain(space);
+        for _ in 0..SAMPLES {
+            let linear = rng.log_range(lo, hi);
+            let back = tf.to_linear(tf.to_encoded(linear));
+            let err = (back - linear).abs() / linear;
+            assert!(
+                err < 2e-4,
+                "{space:?}: {linear} -> {back} (relative error {err})"
+            );
+        }
+    }
+}
+
+#[test]
+fn transfer_round_trip_encoded_linear_encoded() {
+    let mut rng = Sampler::new(0xc0de);
+    for (space, tf) in encoded_spaces() {
+        let (lo, hi) = linear_domain(space);
+        let (lo, hi) = (tf.to_encoded(lo), tf.to_encoded(hi));
+        for _ in 0..SAMPLES {
+            let code = rng.range(lo, hi);
+            let back = tf.to_encoded(tf.to_linear(code));
+            assert!(
+                (back - code).abs() < 1e-5,
+                "{space:?}: code {code} -> {back}"
+            );
+        }
+    }
+}
+
+#[test]
+fn transfer_is_monotonic_and_continuous() {
+    for (space, tf) in encoded_spaces() {
+        let (lo, hi) = linear_domain(space);
+        let steps = 20_000;
+        let mut prev = tf.to_encoded(lo);
+        for i in 1..=steps {
+            // Log spacing crosses every piecewise join in small code steps.
+            let t = i as f32 / steps as f32;
+            let linear = lo * (hi / lo).powf(t);
+            let code = tf.to_encoded(linear);
+            assert!(code >= prev, "{space:?}: not monotonic at {linear}");
+            assert!(
+                code - prev < 0.01,
+                "{space:?}: jump of {} at {linear}",
+                code - prev
+            );
+            prev = code;
+        }
+    }
+}
+
+#[test]
+fn transfer_negative_values_round_trip_through_linear_toe() {
+    // Curves with a linear segment below black must stay invertible for
+    // slightly negative input (noise floor, out-of-gamut matrix output).
+    let mut rng = Sampler::new(7);
+    for space in [
+        ColorSpaceId::Srgb,
+        ColorSpaceId::AcesCct,
+        ColorSpaceId::ArriLogC3,
+        ColorSpaceId::ArriLogC4,
+        ColorSpaceId::SLog3,
+        ColorSpaceId::RedLog3G10,
+        ColorSpaceId::VLog,
+    ] {
+        let tf = get_transfer(space).expect("encoded space");
+        for _ in 0..SAMPLES {
+            let linear = rng.range(-0.005, 0.0);
+            let back = tf.to_linear(tf.to_encoded(linear));
+            assert!(
+                (back - linear).abs() < 1e-6,
+                "{space:?}: {linear} -> {back}"
+            );
+        }
+    }
+}
+
+#[test]
+fn matrix_inverse_pairs_compose_to_identity() {
+    let mut rng = Sampler::new(0xa9e5);
+    for &from in ColorSpaceId::all() {
+        for &to in ColorSpaceId::all() {
+            let forward = get_conversion_matrix(from, to);
+            let back = get_conversion_matrix(to, from);
+            for _ in 0..64 {
+                let rgb = rng.rgb(-0.5, 4.0);
+                let out = back.apply(forward.apply(rgb));
+                for c in 0..3 {
+                    assert!(
+                        rel_error(out[c], rgb[c]) < 1e-5,
+                        "{from:?} -> {to:?} -> {from:?}: {rgb:?} -> {out:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn matrix_conversions_are_transitive() {
+    let mut rng = Sampler::new(0x7a5);
+    let spaces = ColorSpaceId::all();
+    for &a in spaces {
+        for &b in spaces {
+            for &c in spaces {
+                let via = (get_conversion_matrix(a, b), get_conversion_matrix(b, c));
+                let direct = get_conversion_matrix(a, c);
+                let rgb = rng.rgb(0.0, 2.0);
+                let chained = via.1.apply(via.0.apply(rgb));
+                let straight = direct.apply(rgb);
+                for ch in 0..3 {
+                    assert!(
+                        rel_error(chained[ch], straight[ch]) < 1e-5,
+                        "{a:?} -> {b:?} -> {c:?} differs from direct: {chained:?} vs {straight:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn matrix_conversions_preserve_white() {
+    // Bradford adaptation maps each space's white to the other's.
+    for &from in ColorSpaceId::all() {
+        for &to in ColorSpaceId::all() {
+            let white = get_conversion_matrix(from, to).apply([1.0; 3]);
+            for c in white {
+                assert!((c - 1.0).abs() < 1e-5, "{from:?} -> {to:?}: {white:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn xyz_matrices_are_inverse_and_normalized() {
+    let mut rng = Sampler::new(0x5eed);
+    for &space in ColorSpaceId::all() {
+        let to_xyz = rgb_to_xyz_matrix(space);
+        let from_xyz = xyz_to_rgb_matrix(space);
+        assert!(
+            (to_xyz.apply([1.0; 3])[1] - 1.0).abs() < 1e-6,
+            "{space:?}: white does not map to Y = 1"
+        );
+        for _ in 0..64 {
+            let rgb = rng.rgb(0.0, 2.0);
+            let out = from_xyz.apply(to_xyz.apply(rgb));
+            for c in 0..3 {
+                assert!(
+                    rel_error(out[c], rgb[c]) < 1e-5,
+                    "{space:?}: {rgb:?} -> {out:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn input_then_output_transform_round_trips() {
+    let mut rng = Sampler::new(0x1d7);
+    for (space, tf) in encoded_spaces() {
+        let (lo, hi) = linear_domain(space);
+        let (lo, hi) = (tf.to_encoded(lo), tf.to_encoded(hi.min(16.0)));
+        let config = ColorManagementConfig {
+            input_space: space,
+            working_space: ColorSpaceId::AcesCg,
+            output_space: space,
+            ..ColorManagementConfig::default()
+        };
+        for _ in 0..256 {
+            let code = rng.rgb(lo, hi);
+            let out = apply_output_transform(apply_input_transform(code, &config), &config);
+            for c in 0..3 {
+                assert!(
+                    (out[c] - code[c]).abs() < 1e-4,
+                    "{space:?} via ACEScg: {code:?} -> {out:?}"
+                );
+            }
+        }
+    }
+}
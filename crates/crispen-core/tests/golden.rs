@@ -0,0 +1,186 @@
+//! Golden-image tests for the color management transforms.
+//!
+//! `tests/golden/manifest.json` lists one case per transform: an input EXR,
+//! the direction, and the color space. The expected output `<name>.exr` was
+//! produced by OCIO or, where no OCIO build was available, by the
+//! independent f64 reference in `golden/spec.rs`; each case records which.
+//! So far every case is `spec-f64`; see `golden/README.md`.
+//! Pure CPU; run with: `cargo test -p crispen-core --test golden`
+//!
+//! Regenerate the spec references with
+//! `cargo test -p crispen-core --test golden -- --ignored`.
+
+#[path = "golden/spec.rs"]
+mod spec;
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crispen_core::color_management::aces::{apply_input_transform, apply_output_transform};
+use crispen_core::transform::params::{ColorManagementConfig, ColorSpaceId};
+
+/// Golden image side length.
+const SIZE: u32 = 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// Largest allowed error, relative above 1.0 and absolute below.
+    tolerance: f32,
+    cases: Vec<GoldenCase>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenCase {
+    name: String,
+    direction: Direction,
+    /// Source space for `input`, destination space for `output`.
+    space: ColorSpaceId,
+    /// Input image file name.
+    input: String,
+    /// OCIO color space names (studio config) for the same conversion.
+    ocio_src: String,
+    ocio_dst: String,
+    /// What produced the expected image (`spec-f64` or `ocio <config>`).
+    source: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Direction {
+    /// `space` → ACEScg via the input transform.
+    Input,
+    /// ACEScg → `space` via the output transform.
+    Output,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn load_manifest() -> Manifest {
+    let path = golden_dir().join("manifest.json");
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path:?}: {e}"));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{path:?}: {e}"))
+}
+
+fn read_exr(name: &str) -> Vec<[f32; 4]> {
+    let path = golden_dir().join(name);
+    let image = image::open(&path)
+        .unwrap_or_else(|e| panic!("{path:?}: {e}"))
+        .into_rgba32f();
+    assert_eq!(image.dimensions(), (SIZE, SIZE), "{path:?}");
+    image.pixels().map(|p| p.0).collect()
+}
+
+fn write_exr(name: &str, pixels: &[[f32; 4]]) {
+    let flat = pixels.iter().flatten().copied().collect();
+    let image = image::Rgba32FImage::from_raw(SIZE, SIZE, flat).expect("pixel count");
+    let path = golden_dir().join(name);
+    image
+        .save(&path)
+        .unwrap_or_else(|e| panic!("{path:?}: {e}"));
+}
+
+fn config(case: &GoldenCase) -> ColorManagementConfig {
+    let (input_space, output_space) = match case.direction {
+        Direction::Input => (case.space, ColorSpaceId::AcesCg),
+        Direction::Output => (ColorSpaceId::AcesCg, case.space),
+    };
+    ColorManagementConfig {
+        input_space,
+        working_space: ColorSpaceId::AcesCg,
+        output_space,
+        ..ColorManagementConfig::default()
+    }
+}
+
+#[test]
+fn golden_images_match_reference() {
+    let manifest = load_manifest();
+    assert!(!manifest.cases.is_empty());
+
+    let mut failures = Vec::new();
+    for case in &manifest.cases {
+        let cfg = config(case);
+        let input = read_exr(&case.input);
+        let expected = read_exr(&format!("{}.exr", case.name));
+
+        let mut worst = (0.0_f32, 0, [0.0; 3], [0.0; 3]);
+        for (i, (px, exp)) in input.iter().zip(&expected).enumerate() {
+            let rgb = [px[0], px[1], px[2]];
+            let out = match case.direction {
+                Direction::Input => apply_input_transform(rgb, &cfg),
+                Direction::Output => apply_output_transform(rgb, &cfg),
+            };
+            for c in 0..3 {
+                let err = (out[c] - exp[c]).abs() / exp[c].abs().max(1.0);
+                if err > worst.0 {
+                    worst = (err, i, out, [exp[0], exp[1], exp[2]]);
+                }
+            }
+        }
+        if worst.0 > manifest.tolerance {
+            let (err, i, got, want) = worst;
+            failures.push(format!(
+                "{} ({}): error {err:.2e} at pixel {i}: got {got:?}, expected {want:?}",
+                case.name, case.source
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// Input pattern covering the encoded code-value range [0, 1].
+fn code_values() -> Vec<[f32; 4]> {
+    pattern(|x, y, z| [x, y, z].map(|t| t as f32))
+}
+
+/// Input pattern covering scene-linear [0, 4], denser in the shadows.
+fn scene_linear() -> Vec<[f32; 4]> {
+    pattern(|x, y, z| [x * x, y * y, z].map(|t| (t * 4.0) as f32))
+}
+
+fn pattern(f: impl Fn(f64, f64, f64) -> [f32; 3]) -> Vec<[f32; 4]> {
+    let n = f64::from(SIZE - 1);
+    (0..SIZE * SIZE)
+        .map(|i| {
+            let (x, y) = (i % SIZE, i / SIZE);
+            let z = (x * 7 + y * 3) % SIZE;
+            let [r, g, b] = f(f64::from(x) / n, f64::from(y) / n, f64::from(z) / n);
+            [r, g, b, 1.0]
+        })
+        .collect()
+}
+
+#[test]
+#[ignore = "rewrites the golden images from the f64 spec reference"]
+fn write_spec_references() {
+    let mut manifest = load_manifest();
+    write_exr("code_values.exr", &code_values());
+    write_exr("scene_linear.exr", &scene_linear());
+
+    for case in &mut manifest.cases {
+        let input = read_exr(&case.input);
+        let expected: Vec<[f32; 4]> = input
+            .iter()
+            .map(|px| {
+                let rgb = [px[0], px[1], px[2]].map(f64::from);
+                let out = match case.direction {
+                    Direction::Input => {
+                        spec::to_acescg(case.space, rgb.map(|v| spec::to_linear(case.space, v)))
+                    }
+                    Direction::Output => {
+                        spec::from_acescg(case.space, rgb).map(|v| spec::to_encoded(case.space, v))
+                    }
+                };
+                [out[0] as f32, out[1] as f32, out[2] as f32, px[3]]
+            })
+            .collect();
+        write_exr(&format!("{}.exr", case.name), &expected);
+        case.source = "spec-f64".to_string();
+    }
+
+    let text = serde_json::to_string_pretty(&manifest).expect("serialize manifest");
+    std::fs::write(golden_dir().join("manifest.json"), text + "\n").expect("write manifest");
+}
@@ -0,0 +1,218 @@
+//! Independent f64 reference for the golden images.
+//!
+//! Written from the published specifications rather than shared with the
+//! crate, so a golden produced here checks the crate's f32 implementation
+//! against the documents instead of against itself. Used when no OCIO
+//! build is available to produce the references (see `README.md`).
+
+use crispen_core::transform::params::ColorSpaceId;
+
+type Mat3 = [[f64; 3]; 3];
+
+/// Encoded code value → scene linear, per vendor / standards documents.
+pub fn to_linear(space: ColorSpaceId, v: f64) -> f64 {
+    match space {
+        // IEC 61966-2-1.
+        ColorSpaceId::Srgb => {
+            if v <= 0.04045 {
+                v / 12.92
+            } else {
+                ((v + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        // S-2014-003.
+        ColorSpaceId::AcesCc => {
+            if v < (9.72 - 15.0) / 17.52 {
+                (2f64.powf(v * 17.52 - 9.72) - 2f64.powi(-16)) * 2.0
+            } else {
+                2f64.powf(v * 17.52 - 9.72)
+            }
+        }
+        // S-2016-001.
+        ColorSpaceId::AcesCct => {
+            let (a, b) = (10.540_237_741_654_5, 0.072_905_534_195_835_5);
+            if v <= 0.155_251_141_552_511 {
+                (v - b) / a
+            } else {
+                2f64.powf(v * 17.52 - 9.72)
+            }
+        }
+        // ARRI "ALEXA Log C Curve — Usage in VFX", EI 800.
+        ColorSpaceId::ArriLogC3 => {
+            let (cut, a, b, c, d, e, f) = (
+                0.010_591, 5.555_556, 0.052_272, 0.247_190, 0.385_537, 5.367_655, 0.092_809,
+            );
+            if v > e * cut + f {
+                (10f64.powf((v - d) / c) - b) / a
+            } else {
+                (v - f) / e
+            }
+        }
+        // ARRI "LogC4 Logarithmic Color Space — Specification" (2022).
+        ColorSpaceId::ArriLogC4 => {
+            let a = (2f64.powi(18) - 16.0) / 117.45;
+            let b = (1023.0 - 95.0) / 1023.0;
+            let c = 95.0 / 1023.0;
+            let s = (7.0 * 2f64.ln() * 2f64.powf(7.0 - 14.0 * c / b)) / (a * b);
+            let t = (2f64.powf(14.0 * (-c / b) + 6.0) - 64.0) / a;
+            if v >= 0.0 {
+                (2f64.powf(14.0 * (v - c) / b + 6.0) - 64.0) / a
+            } else {
+                v * s + t
+            }
+        }
+        // Sony "Technical Summary for S-Gamut3.Cine/S-Log3".
+        ColorSpaceId::SLog3 => {
+            if v >= 171.210_294_692_9 / 1023.0 {
+                10f64.powf((v * 1023.0 - 420.0) / 261.5) * (0.18 + 0.01) - 0.01
+            } else {
+                (v * 1023.0 - 95.0) * 0.01125 / (171.210_294_692_9 - 95.0)
+            }
+        }
+        // RED "REDWideGamutRGB and Log3G10" white paper.
+        ColorSpaceId::RedLog3G10 => {
+            let (a, b, c, g) = (0.224_282, 155.975_327, 0.01, 15.1927);
+            if v < 0.0 {
+                v / g - c
+            } else {
+                (10f64.powf(v / a) - 1.0) / b - c
+            }
+        }
+        // Panasonic "V-Log/V-Gamut Reference Manual".
+        ColorSpaceId::VLog => {
+            let (b, c, d) = (0.00873, 0.241_514, 0.598_206);
+            if v < 0.181 {
+                (v - 0.125) / 5.6
+            } else {
+                10f64.powf((v - d) / c) - b
+            }
+        }
+        _ => v,
+    }
+}
+
+/// Scene linear → encoded code value (output direction).
+pub fn to_encoded(space: ColorSpaceId, v: f64) -> f64 {
+    match space {
+        ColorSpaceId::Srgb => {
+            if v <= 0.003_130_8 {
+                v * 12.92
+            } else {
+                1.055 * v.powf(1.0 / 2.4) - 0.055
+            }
+        }
+        ColorSpaceId::AcesCct => {
+            if v <= 0.007_812_5 {
+                10.540_237_741_654_5 * v + 0.072_905_534_195_835_5
+            } else {
+                (v.log2() + 9.72) / 17.52
+            }
+        }
+        _ => v,
+    }
+}
+
+/// Published CIE 1931 xy primaries and white point: `[r, g, b, w]`.
+fn chromaticities(space: ColorSpaceId) -> [[f64; 2]; 4] {
+    const D65: [f64; 2] = [0.3127, 0.3290];
+    const ACES: [f64; 2] = [0.32168, 0.33767];
+    match space {
+        ColorSpaceId::Aces2065_1 => [[0.7347, 0.2653], [0.0, 1.0], [0.0001, -0.077], ACES],
+        ColorSpaceId::AcesCg | ColorSpaceId::AcesCc | ColorSpaceId::AcesCct => {
+            [[0.713, 0.293], [0.165, 0.830], [0.128, 0.044], ACES]
+        }
+        ColorSpaceId::Rec2020 => [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046], D65],
+        ColorSpaceId::DciP3 => [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060], D65],
+        ColorSpaceId::ArriLogC3 => [[0.684, 0.313], [0.221, 0.848], [0.0861, -0.102], D65],
+        ColorSpaceId::ArriLogC4 => [[0.7347, 0.2653], [0.1424, 0.8576], [0.0991, -0.0308], D65],
+        ColorSpaceId::SLog3 => [[0.766, 0.275], [0.225, 0.800], [0.089, -0.087], D65],
+        ColorSpaceId::RedLog3G10 => [
+            [0.780_308, 0.304_253],
+            [0.121_595, 1.493_994],
+            [0.095_612, -0.084_589],
+            D65,
+        ],
+        ColorSpaceId::VLog => [[0.730, 0.280], [0.165, 0.840], [0.100, -0.030], D65],
+        _ => [[0.640, 0.330], [0.300, 0.600], [0.150, 0.060], D65],
+    }
+}
+
+/// Linear RGB in `from` → linear ACEScg, with Bradford adaptation.
+pub fn to_acescg(from: ColorSpaceId, rgb: [f64; 3]) -> [f64; 3] {
+    apply(&conversion(from, ColorSpaceId::AcesCg), rgb)
+}
+
+/// Linear ACEScg → linear RGB in `to`, with Bradford adaptation.
+pub fn from_acescg(to: ColorSpaceId, rgb: [f64; 3]) -> [f64; 3] {
+    apply(&conversion(ColorSpaceId::AcesCg, to), rgb)
+}
+
+fn conversion(from: ColorSpaceId, to: ColorSpaceId) -> Mat3 {
+    let src = chromaticities(from);
+    let dst = chromaticities(to);
+    let adapt = bradford(src[3], dst[3]);
+    mul(&invert(&npm(&dst)), &mul(&adapt, &npm(&src)))
+}
+
+fn xyz(xy: [f64; 2]) -> [f64; 3] {
+    [xy[0] / xy[1], 1.0, (1.0 - xy[0] - xy[1]) / xy[1]]
+}
+
+/// Normalized primary matrix (SMPTE RP 177).
+fn npm(c: &[[f64; 2]; 4]) -> Mat3 {
+    let [r, g, b] = [xyz(c[0]), xyz(c[1]), xyz(c[2])];
+    let p = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+    let s = apply(&invert(&p), xyz(c[3]));
+    std::array::from_fn(|i| std::array::from_fn(|j| p[i][j] * s[j]))
+}
+
+fn bradford(src: [f64; 2], dst: [f64; 2]) -> Mat3 {
+    const M: Mat3 = [
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ];
+    let (s, d) = (apply(&M, xyz(src)), apply(&M, xyz(dst)));
+    let scale = [
+        [d[0] / s[0], 0.0, 0.0],
+        [0.0, d[1] / s[1], 0.0],
+        [0.0, 0.0, d[2] / s[2]],
+    ];
+    mul(&invert(&M), &mul(&scale, &M))
+}
+
+fn apply(m: &Mat3, v: [f64; 3]) -> [f64; 3] {
+    std::array::from_fn(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+fn mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+/// Gauss-Jordan inverse with partial pivoting.
+fn invert(m: &Mat3) -> Mat3 {
+    let mut a = *m;
+    let mut inv = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for col in 0..3 {
+        let pivot = (col..3)
+            .max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))
+            .unwrap_or(col);
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        let p = a[col][col];
+        for j in 0..3 {
+            a[col][j] /= p;
+            inv[col][j] /= p;
+        }
+        for row in 0..3 {
+            if row != col {
+                let f = a[row][col];
+                for j in 0..3 {
+                    a[row][j] -= f * a[col][j];
+                    inv[row][j] -= f * inv[col][j];
+                }
+            }
+        }
+    }
+    inv
+}
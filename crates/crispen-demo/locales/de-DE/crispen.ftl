@@ -0,0 +1,119 @@
+# German catalog. Messages missing here fall back to en-US.
+
+## Viewer
+
+viewer-title = Viewer
+viewer-source = Quelle
+viewer-load-hint = { $shortcut } zum Laden eines Bildes
+dialog-load-image = Bild laden
+dialog-image-filter = Bilder
+
+## Toolbar
+
+toolbar-input = Eingang
+toolbar-working = Arbeitsraum
+toolbar-output = Ausgang
+toolbar-display = Display
+toolbar-view = Ansicht
+toolbar-split = Vergleich
+toolbar-ofx = OFX
+toolbar-bypass = Bypass
+toolbar-bypass-wheels = Räder
+toolbar-bypass-curves = Kurven
+toolbar-bypass-input-lut = LUT
+
+## Primaries
+
+primaries-title = Primärkorrektur
+wheel-lift = LIFT
+wheel-gamma = GAMMA
+wheel-gain = GAIN
+wheel-offset = OFFSET
+param-temperature = TEMP
+param-tint = TÖNUNG
+param-contrast = KONTRAST
+param-pivot = PIVOT
+param-midtone-detail = MITTEN-DETAIL
+param-shadows = TIEFEN
+param-highlights = LICHTER
+param-saturation = SÄTTIGUNG
+param-hue = FARBTON
+param-luma-mix = LUMA-MIX
+
+## Hue curves
+
+curves-title = Farbtonkurven
+curves-hue-vs-hue = Farbton/Farbton
+curves-hue-vs-sat = Farbton/Sättigung
+curves-lum-vs-sat = Luminanz/Sättigung
+curves-add-hint = Strg+Klick oder Doppeltippen fügt Punkte hinzu
+curves-smooth = Weich
+curves-point-input = Ein
+curves-point-output = Aus
+
+## Scopes
+
+scopes-title = Messinstrumente
+scopes-waiting = Warte auf Bild
+scope-vectorscope = Vektorskop
+scope-waveform = Waveform
+scope-parade = RGB-Parade
+scope-histogram = Histogramm
+scope-cie = CIE-Normfarbtafel
+scope-missing-vectorscope = Keine Vektorskop-Daten
+scope-missing-waveform = Keine Waveform-Daten
+scope-missing-parade = Keine Waveform-Daten für die Parade
+scope-missing-histogram = Keine Histogrammdaten
+scope-missing-cie = Keine CIE-Daten
+scope-mask = Maske
+scope-mask-clear = Löschen
+
+## OFX
+
+ofx-title = OFX-Plugins ({ $count })
+ofx-load-failures = Ladefehler
+
+## Shortcut actions
+
+action-load-image = Bild laden
+action-toggle-split-view = Vergleichsansicht
+action-toggle-ofx-panel = OFX-Bereich
+action-pick-chart = Farbtafel wählen
+action-reset-view = Ansicht zurücksetzen
+action-dev-tools = Entwicklerwerkzeuge
+action-copy-look = Look kopieren
+action-paste-look = Look einfügen
+action-copy-still = Standbild kopieren
+action-reload-ui = Oberfläche neu laden
+action-exposure-up = Belichtung erhöhen
+action-exposure-down = Belichtung verringern
+action-loupe = Lupe
+action-toggle-focus-assist = Fokushilfe
+action-mark-defects = Pixelfehler markieren
+
+## Messages sent to the web UI
+
+error-load-image = Bild konnte nicht geladen werden: { $error }
+error-delta-e-reference = ΔE-Referenz konnte nicht geladen werden: { $error }
+error-probe = Prüfung fehlgeschlagen: { $message }
+error-gallery = Galerie: { $error }
+error-tool-presets = Werkzeug-Presets: { $error }
+recovery-autosave = Die letzte Sitzung wurde unerwartet beendet; eine automatisch gespeicherte Sitzung ist verfügbar.
+settings-error-ui-scale = UI-Skalierung { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-lut-size = LUT-Größe { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-scope-resolution = Scope-Auflösung { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-frame-budget = Das Frame-Budget der Scopes muss eine positive Anzahl Millisekunden sein
+settings-error-sample-stride = Die Sample-Schrittweite der Scopes muss mindestens 1 sein
+settings-error-scope-cadence = Die Scope-Kadenz muss alle 1–{ $max_interval } Frames aktualisieren oder sich innerhalb von { $max_settle } ms beruhigen
+settings-error-autosave-interval = Autosave-Intervall { $value } s liegt außerhalb von { $min }–{ $max } s
+settings-error-log-records = Log-Puffergröße { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-readback-slots = Readback-Slots { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-lut-cache = LUT-Cache-Größe { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-idle-fps = Leerlauf-Bildrate { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-wheel-samples = Rad-Supersampling { $value } liegt außerhalb von { $min }–{ $max }
+settings-error-wheel-aa = Rad-Kantenbreite { $value } px liegt außerhalb von { $min }–{ $max } px
+settings-error-guides = Größen und Deckkraft der Bildhilfslinien müssen zwischen 0 und 1 liegen, das Maskenseitenverhältnis muss positiv sein
+settings-error-focus-assist = Schwelle und Farbe der Fokushilfe müssen zwischen 0 und 1 liegen
+settings-error-key-binding = „{ $binding }“ ist kein gültiges Tastenkürzel für { $action }
+settings-not-saved = Einstellungen übernommen, aber nicht gespeichert: { $error }
+settings-error-duplicate-binding = { $binding } ist sowohl { $first } als auch { $second } zugewiesen
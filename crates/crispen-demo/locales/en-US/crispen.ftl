@@ -0,0 +1,120 @@
+# Source catalog. Every message used by the app is defined here; other
+# locales fall back to it for anything they don't translate.
+
+## Viewer
+
+viewer-title = Viewer
+viewer-source = Source
+viewer-load-hint = { $shortcut } to load image
+dialog-load-image = Load Image
+dialog-image-filter = Images
+
+## Toolbar
+
+toolbar-input = Input
+toolbar-working = Working
+toolbar-output = Output
+toolbar-display = Display
+toolbar-view = View
+toolbar-split = Split
+toolbar-ofx = OFX
+toolbar-bypass = Bypass
+toolbar-bypass-wheels = Wheels
+toolbar-bypass-curves = Curves
+toolbar-bypass-input-lut = LUT
+
+## Primaries
+
+primaries-title = Primaries
+wheel-lift = LIFT
+wheel-gamma = GAMMA
+wheel-gain = GAIN
+wheel-offset = OFFSET
+param-temperature = TEMP
+param-tint = TINT
+param-contrast = CONTRAST
+param-pivot = PIVOT
+param-midtone-detail = MID DETAIL
+param-shadows = SHADOWS
+param-highlights = HIGHLIGHTS
+param-saturation = SATURATION
+param-hue = HUE
+param-luma-mix = LUMA MIX
+
+## Hue curves
+
+curves-title = Hue vs Curves
+curves-hue-vs-hue = Hue vs Hue
+curves-hue-vs-sat = Hue vs Sat
+curves-lum-vs-sat = Lum vs Sat
+curves-add-hint = Ctrl+Click or double-tap to add points
+curves-smooth = Smooth
+curves-point-input = In
+curves-point-output = Out
+
+## Scopes
+
+scopes-title = Scopes
+scopes-waiting = Waiting for image
+scope-vectorscope = Vectorscope
+scope-waveform = Waveform
+scope-parade = RGB Parade
+scope-histogram = Histogram
+scope-cie = CIE Chromaticity
+scope-missing-vectorscope = No vectorscope data
+scope-missing-waveform = No waveform data
+scope-missing-parade = No waveform data for parade
+scope-missing-histogram = No histogram data
+scope-missing-cie = No CIE data
+scope-mask = Mask
+scope-mask-clear = Clear
+
+## OFX
+
+ofx-title = OFX Plugins ({ $count })
+ofx-load-failures = Load Failures
+
+## Shortcut actions
+
+action-load-image = Load image
+action-toggle-split-view = Split view
+action-toggle-ofx-panel = OFX panel
+action-pick-chart = Pick chart
+action-reset-view = Reset view
+action-dev-tools = Dev tools
+action-copy-look = Copy look
+action-paste-look = Paste look
+action-copy-still = Copy still
+action-reload-ui = Reload UI
+action-exposure-up = Exposure up
+action-exposure-down = Exposure down
+action-loupe = Loupe
+action-toggle-focus-assist = Focus assist
+action-mark-defects = Mark defects
+
+## Messages sent to the web UI
+
+error-load-image = Failed to load image: { $error }
+error-delta-e-reference = Failed to load ΔE reference: { $error }
+error-probe = Probe check failed: { $message }
+error-gallery = Gallery: { $error }
+error-tool-presets = Tool presets: { $error }
+recovery-autosave = The previous session ended unexpectedly; an autosaved session is available.
+settings-error-ui-scale = UI scale { $value } is outside { $min }–{ $max }
+settings-error-lut-size = LUT size { $value } is outside { $min }–{ $max }
+settings-error-scope-resolution = Scope resolution { $value } is outside { $min }–{ $max }
+settings-error-frame-budget = Scope frame budget must be a positive number of milliseconds
+settings-error-sample-stride = Scope sample stride must be at least 1
+settings-error-scope-cadence = Scope cadence must update every 1–{ $max_interval } frames or settle within { $max_settle } ms
+settings-error-autosave-interval = Autosave interval { $value }s is outside { $min }–{ $max }s
+settings-error-log-records = Log buffer size { $value } is outside { $min }–{ $max }
+settings-error-readback-slots = Readback slots { $value } is outside { $min }–{ $max }
+settings-error-lut-cache = LUT cache size { $value } is outside { $min }–{ $max }
+settings-error-idle-fps = Idle frame rate { $value } is outside { $min }–{ $max }
+settings-error-wheel-samples = Wheel supersampling { $value } is outside { $min }–{ $max }
+settings-error-wheel-aa = Wheel edge width { $value } px is outside { $min }–{ $max } px
+settings-error-guides = Framing guide sizes and opacities must be 0–1 and the mask aspect positive
+settings-error-focus-assist = Focus assist threshold and color must be 0–1
+settings-error-key-binding = '{ $binding }' is not a valid shortcut for { $action }
+settings-not-saved = Settings applied but not saved: { $error }
+settings-error-duplicate-binding = { $binding } is bound to both { $first } and { $second }
@@ -1,27 +1,55 @@
 //! CEF compositing bridge — replaces the WebSocket bridge.
 //!
-//! Sets up CEF offscreen rendering, creates a full-screen overlay texture,
+//! Sets up CEF offscreen rendering, composites its textures over the window,
 //! and forwards IPC messages between Bevy ECS and the Svelte UI.
 
 use std::path::Path;
 use std::sync::Arc;
 
 use bevy::asset::RenderAssetUsages;
-use bevy::picking::prelude::Pickable;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{
+    Extent3d, Origin3d, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+    TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::renderer::RenderQueue;
+use bevy::render::texture::GpuImage;
+use bevy::render::{ExtractSchedule, MainWorld, Render, RenderApp, RenderSystems};
 
 use crispen_frontend_cef::CefBackend;
-use crispen_frontend_core::{CaptureResult, CompositeBackend};
+use crispen_frontend_core::{
+    CaptureResult, CompositeBackend, DirtyRect, FloatingWebviews, PanelRect,
+};
 
-use crate::config::AppConfig;
+use crate::clipboard::ClipboardCommand;
+use crate::config::{AppConfig, UserConfig, config_dir};
+use crate::crash::RecoveryCommand;
+use crate::diagnostics::LogCommand;
+use crate::gallery::GalleryCommand;
+use crate::i18n::{self, tr_args};
 use crate::image_loader;
 use crate::ipc::{BevyToUi, UiToBevy};
-use crate::layout_sync::PanelLayout;
+use crate::layout_sync::{LAYOUT_PANEL_Z, PanelLayout};
+use crate::presets::PresetCommand;
+use crate::probe::StartProbeCheck;
+use crate::settings::SettingsCommand;
+use crate::setup::SetupCommand;
+use crate::ui::delta_e_overlay;
+use crate::ui_reload::ReloadUi;
 use crispen_bevy::events::{ColorGradingCommand, ImageLoadedEvent};
+use crispen_bevy::render_node::{
+    OverlayAlpha, OverlayComposite, OverlayCompositeCamera, OverlayCompositePlugin, OverlayLayer,
+};
 #[cfg(feature = "ocio")]
 use crispen_bevy::resources::OcioColorManagement;
 use crispen_bevy::resources::{GpuPipelineState, GradingState, ImageState};
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::BitDepth;
+use crispen_core::patterns::TestPattern;
+use crispen_core::transform::compare::DEFAULT_HEATMAP_SCALE;
+use crispen_core::transform::params::ColorSpaceId;
 
 // ── Plugin ───────────────────────────────────────────────────────
 
@@ -30,23 +58,48 @@ pub struct CefBridgePlugin;
 
 impl Plugin for CefBridgePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<OutboundUiMessages>()
+        // CEF paints premultiplied BGRA, which Bevy's UI pipeline would
+        // blend as straight alpha.
+        app.add_plugins(OverlayCompositePlugin)
+            .insert_resource(OverlayComposite {
+                alpha: OverlayAlpha::Premultiplied,
+                ..default()
+            })
+            .init_resource::<OutboundUiMessages>()
             .init_resource::<CefWebviewStatus>()
             .init_resource::<CefLastWindowSize>()
+            .add_message::<FloatingPanelCommand>()
             .add_systems(Startup, setup_cef_frontend.pipe(handle_cef_error))
             .add_systems(
                 Update,
                 (
                     update_cef_texture,
                     handle_cef_resize,
+                    sync_cef_scale_factor,
                     // Run before handle_grading_commands so that any remaining
                     // ColorGradingCommand messages (AutoBalance, ToggleScope, etc.)
                     // are written before they are consumed.
                     handle_cef_ipc
                         .before(crispen_bevy::systems::handle_grading_commands),
+                    forward_context_menu_requests.before(flush_outbound_messages),
                     flush_outbound_messages,
+                    handle_floating_panel_commands.after(handle_cef_ipc),
+                    update_floating_panels.after(handle_floating_panel_commands),
+                    sync_overlay_layers.after(update_floating_panels),
+                    mark_overlay_cameras,
                 ),
             );
+
+        app.init_resource::<CefTextureRegions>();
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<CefTextureRegions>()
+                .add_systems(ExtractSchedule, extract_cef_texture_regions)
+                .add_systems(
+                    Render,
+                    write_cef_texture_regions.in_set(RenderSystems::PrepareResources),
+                );
+        }
     }
 }
 
@@ -54,7 +107,57 @@ impl Plugin for CefBridgePlugin {
 
 /// Non-Send resource holding the CEF backend (CEF is single-threaded).
 pub struct CefFrontendResource {
+    /// The full-window UI.
     pub backend: CefBackend,
+    /// Tool windows floating over it, keyed by panel name.
+    pub floating: FloatingWebviews<CefBackend>,
+}
+
+impl CefFrontendResource {
+    /// Name of the floating webview under a window position (physical
+    /// pixels); `None` means the main webview.
+    pub fn webview_at(&self, x: f32, y: f32) -> Option<String> {
+        self.floating.hit_test(x, y).map(str::to_string)
+    }
+
+    /// The webview called `name`, falling back to the main one.
+    pub fn webview(&mut self, name: Option<&str>) -> &mut CefBackend {
+        match name.and_then(|name| self.floating.get_mut(name)) {
+            Some(view) => &mut view.backend,
+            None => &mut self.backend,
+        }
+    }
+
+    /// The webview called `name` and a window position converted to its
+    /// surface.
+    pub fn webview_with_position(
+        &mut self,
+        name: Option<&str>,
+        x: f32,
+        y: f32,
+    ) -> (&mut CefBackend, f32, f32) {
+        match name.and_then(|name| self.floating.get_mut(name)) {
+            Some(view) => {
+                let (x, y) = view.rect.to_local(x, y);
+                (&mut view.backend, x, y)
+            }
+            None => (&mut self.backend, x, y),
+        }
+    }
+
+    /// The webview that receives keyboard input.
+    pub fn focused(&mut self) -> &mut CefBackend {
+        match self.floating.focused_mut() {
+            Some(view) => &mut view.backend,
+            None => &mut self.backend,
+        }
+    }
+
+    /// The main webview followed by the floating ones.
+    pub fn all_mut(&mut self) -> impl Iterator<Item = &mut CefBackend> {
+        std::iter::once(&mut self.backend)
+            .chain(self.floating.iter_mut().map(|view| &mut view.backend))
+    }
 }
 
 /// Bevy `Handle<Image>` for the overlay texture.
@@ -63,9 +166,28 @@ pub struct CefUiTextureHandle {
     pub handle: Handle<Image>,
 }
 
-/// Marker component for the full-screen overlay `ImageNode`.
+/// Node covering the floating webview called `panel`. It only blocks
+/// picking; `image` is drawn by the overlay composite.
 #[derive(Component)]
-pub struct CefUiOverlay;
+pub struct CefFloatingOverlay {
+    pub panel: String,
+    pub image: Handle<Image>,
+}
+
+/// Open, move or close a floating webview.
+#[derive(Message, Debug, Clone)]
+pub enum FloatingPanelCommand {
+    /// Show `panel` at the given rect in CSS pixels, opening it if needed.
+    Open {
+        panel: String,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    /// Close `panel`.
+    Close { panel: String },
+}
 
 /// CEF lifecycle tracking.
 #[derive(Resource, Default)]
@@ -81,6 +203,18 @@ pub struct CefLastWindowSize {
     pub height: u32,
 }
 
+/// Changed overlay regions waiting to be written into the GPU texture.
+///
+/// Filled in the main world by `update_cef_texture`, moved to the render
+/// world each frame and written there with `RenderQueue::write_texture`.
+#[derive(Resource, Default)]
+pub struct CefTextureRegions {
+    image: Option<AssetId<Image>>,
+    /// Frame size the regions were cut from.
+    size: (u32, u32),
+    regions: Vec<(DirtyRect, Vec<u8>)>,
+}
+
 /// Outbound message queue — systems call `send()`, flushed each frame.
 #[derive(Resource, Default)]
 pub struct OutboundUiMessages {
@@ -109,6 +243,10 @@ fn setup_cef_frontend(world: &mut World) -> Result<(), String> {
         )
     };
 
+    if let Some(cache) = world.resource::<UserConfig>().cache_dir() {
+        crispen_frontend_cef::browser::set_cache_dir(cache.join("cef"));
+    }
+
     let config = world.resource::<AppConfig>();
 
     tracing::info!("setting up CEF UI composite ({width}x{height} physical)");
@@ -117,7 +255,7 @@ fn setup_cef_frontend(world: &mut World) -> Result<(), String> {
         let url = format!("http://localhost:{}", crate::embedded_ui::VITE_DEV_PORT);
         tracing::info!("CEF dev mode: navigating to {url}");
         CefBackend::from_url(&url, (width, height))
-    } else if let Some(index_path) = find_built_ui() {
+    } else if let Some(index_path) = crate::embedded_ui::find_built_ui() {
         let url = format!("file://{}", index_path.display());
         tracing::info!("CEF production: loading {url}");
         CefBackend::from_url(&url, (width, height))
@@ -128,11 +266,30 @@ fn setup_cef_frontend(world: &mut World) -> Result<(), String> {
     };
 
     let backend = backend.map_err(|e| format!("CEF creation failed: {e}"))?;
-    world.insert_non_send_resource(CefFrontendResource { backend });
+    world.insert_non_send_resource(CefFrontendResource {
+        backend,
+        floating: FloatingWebviews::default(),
+    });
+
+    let handle = world
+        .resource_mut::<Assets<Image>>()
+        .add(overlay_image(width, height));
+
+    world.insert_resource(CefUiTextureHandle { handle });
+    world.insert_resource(CefLastWindowSize { width, height });
+
+    tracing::info!("CEF UI overlay created");
+    Ok(())
+}
 
-    // Create BGRA overlay texture.
+/// Transparent BGRA texture that CEF frames are uploaded into.
+fn overlay_image(width: u32, height: u32) -> Image {
     let mut image = Image::new_fill(
-        Extent3d { width, height, depth_or_array_layers: 1 },
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
         TextureDimension::D2,
         &[0, 0, 0, 0],
         TextureFormat::Bgra8UnormSrgb,
@@ -140,30 +297,7 @@ fn setup_cef_frontend(world: &mut World) -> Result<(), String> {
     );
     image.texture_descriptor.usage =
         TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
-
-    let handle = world.resource_mut::<Assets<Image>>().add(image);
-
-    world.insert_resource(CefUiTextureHandle { handle: handle.clone() });
-    world.insert_resource(CefLastWindowSize { width, height });
-
-    // Full-screen overlay with pointer passthrough.
-    world.spawn((
-        ImageNode { image: handle, ..default() },
-        Node {
-            width: Val::Vw(100.0),
-            height: Val::Vh(100.0),
-            position_type: PositionType::Absolute,
-            left: Val::Px(0.0),
-            top: Val::Px(0.0),
-            ..default()
-        },
-        ZIndex(i32::MAX),
-        CefUiOverlay,
-        Pickable::IGNORE,
-    ));
-
-    tracing::info!("CEF UI overlay created");
-    Ok(())
+    image
 }
 
 fn handle_cef_error(In(result): In<Result<(), String>>) {
@@ -175,11 +309,16 @@ fn handle_cef_error(In(result): In<Result<(), String>>) {
 // ── Update systems ───────────────────────────────────────────────
 
 /// Poll CEF and upload dirty framebuffers to the overlay texture.
+///
+/// Small repaints only update the image's CPU copy and queue the changed
+/// regions for `write_cef_texture_regions`; anything else, including every
+/// paint during a drag-resize, re-uploads the whole texture.
 fn update_cef_texture(
     webview: Option<NonSendMut<CefFrontendResource>>,
     ui_tex: Option<Res<CefUiTextureHandle>>,
     mut images: ResMut<Assets<Image>>,
     mut status: ResMut<CefWebviewStatus>,
+    mut pending: ResMut<CefTextureRegions>,
 ) {
     let Some(mut wv) = webview else { return };
     let Some(tex) = ui_tex else { return };
@@ -197,7 +336,11 @@ fn update_cef_texture(
     }
 
     if let Some(capture) = wv.backend.capture_if_dirty() {
-        let CaptureResult::Bgra(bgra_arc, cap_w, cap_h) = capture else { return };
+        let (bgra_arc, cap_w, cap_h, rects) = match capture {
+            CaptureResult::Bgra(bgra, w, h) => (bgra, w, h, None),
+            CaptureResult::BgraRegions(bgra, w, h, rects) => (bgra, w, h, Some(rects)),
+            CaptureResult::Rgba(..) => return,
+        };
         let t_capture = t0.elapsed();
 
         if !status.first_capture_done {
@@ -205,17 +348,42 @@ fn update_cef_texture(
             status.first_capture_done = true;
         }
 
-        if let Some(image) = images.get_mut(&tex.handle) {
-            if image.width() != cap_w || image.height() != cap_h {
-                image.resize(Extent3d {
-                    width: cap_w,
-                    height: cap_h,
-                    depth_or_array_layers: 1,
-                });
+        // Untracked, so the asset is not re-extracted and fully re-uploaded.
+        if let Some(rects) = &rects
+            && let Some(image) = images.get_mut_untracked(&tex.handle)
+            && image.width() == cap_w
+            && image.height() == cap_h
+            && let Some(data) = image.data.as_mut()
+        {
+            let mut data_len = 0;
+            for rect in rects {
+                rect.copy_between(&bgra_arc, data, cap_w);
+                let bytes = rect.extract(&bgra_arc, cap_w);
+                data_len += bytes.len();
+                pending.regions.push((*rect, bytes));
             }
-            let data = Arc::try_unwrap(bgra_arc).unwrap_or_else(|a| (*a).clone());
-            let data_len = data.len();
-            image.data = Some(data);
+            pending.image = Some(tex.handle.id());
+            pending.size = (cap_w, cap_h);
+
+            let t_total = t0.elapsed();
+            tracing::info!(
+                "[PERF] update_cef_texture: poll={:.2}ms capture={:.2}ms upload={:.2}ms total={:.2}ms ({}x{}, {} regions, {}bytes)",
+                t_poll.as_secs_f64() * 1000.0,
+                (t_capture - t_poll).as_secs_f64() * 1000.0,
+                (t_total - t_capture).as_secs_f64() * 1000.0,
+                t_total.as_secs_f64() * 1000.0,
+                cap_w,
+                cap_h,
+                rects.len(),
+                data_len,
+            );
+            return;
+        }
+
+        if let Some(image) = images.get_mut(&tex.handle) {
+            // The full upload supersedes any regions not yet written.
+            pending.regions.clear();
+            let data_len = replace_image_data(image, bgra_arc, cap_w, cap_h);
 
             let t_total = t0.elapsed();
             tracing::info!(
@@ -230,6 +398,74 @@ fn update_cef_texture(
     }
 }
 
+/// Replace an overlay texture's pixels with a whole BGRA frame, resizing it
+/// if needed. Returns the number of bytes uploaded.
+fn replace_image_data(image: &mut Image, bgra: Arc<Vec<u8>>, width: u32, height: u32) -> usize {
+    if image.width() != width || image.height() != height {
+        image.resize(Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        });
+    }
+    let data = Arc::try_unwrap(bgra).unwrap_or_else(|a| (*a).clone());
+    let data_len = data.len();
+    image.data = Some(data);
+    data_len
+}
+
+/// Move the queued overlay regions into the render world.
+fn extract_cef_texture_regions(
+    mut main_world: ResMut<MainWorld>,
+    mut regions: ResMut<CefTextureRegions>,
+) {
+    let mut pending = main_world.resource_mut::<CefTextureRegions>();
+    regions.image = pending.image;
+    regions.size = pending.size;
+    regions.regions.append(&mut pending.regions);
+}
+
+/// Write the queued overlay regions straight into the GPU texture.
+fn write_cef_texture_regions(
+    mut pending: ResMut<CefTextureRegions>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    queue: Res<RenderQueue>,
+) {
+    let regions = std::mem::take(&mut pending.regions);
+    let Some(gpu_image) = pending.image.and_then(|id| gpu_images.get(id)) else {
+        return;
+    };
+    // A texture of another size is being replaced by a full upload.
+    if (gpu_image.size.width, gpu_image.size.height) != pending.size {
+        return;
+    }
+    for (rect, bytes) in regions {
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &gpu_image.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            &bytes,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(rect.width * 4),
+                rows_per_image: Some(rect.height),
+            },
+            Extent3d {
+                width: rect.width,
+                height: rect.height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
+
 /// Resize CEF backend when the Bevy window size changes.
 ///
 /// Does NOT resize the Bevy texture here — `update_cef_texture` handles that
@@ -261,7 +497,22 @@ fn handle_cef_resize(
     wv.backend.resize(w, h);
 }
 
-/// Receive IPC messages from CEF and dispatch as ECS commands.
+/// Keep CEF's device scale factor at the window's scale factor times the
+/// UI scale setting, so web text renders at display density and matches the
+/// native panels.
+fn sync_cef_scale_factor(
+    webview: Option<NonSendMut<CefFrontendResource>>,
+    windows: Query<&Window>,
+    ui_scale: Res<UiScale>,
+) {
+    let Some(mut wv) = webview else { return };
+    let Ok(window) = windows.single() else { return };
+    for backend in wv.all_mut() {
+        backend.set_scale_factor(window.scale_factor() * ui_scale.0);
+    }
+}
+
+/// Receive IPC messages from every CEF webview and dispatch as ECS commands.
 #[allow(clippy::too_many_arguments)]
 fn handle_cef_ipc(
     webview: Option<NonSendMut<CefFrontendResource>>,
@@ -274,10 +525,18 @@ fn handle_cef_ipc(
     mut outbound: ResMut<OutboundUiMessages>,
     mut image_loaded: MessageWriter<ImageLoadedEvent>,
     mut panel_layout: ResMut<PanelLayout>,
+    mut probe_requests: MessageWriter<StartProbeCheck>,
+    mut log_commands: MessageWriter<LogCommand>,
+    mut recovery_commands: MessageWriter<RecoveryCommand>,
+    mut subsystems: SubsystemCommands,
 ) {
     let Some(mut wv) = webview else { return };
 
-    while let Some(json) = wv.backend.try_recv_from_ui() {
+    let received: Vec<String> = wv
+        .all_mut()
+        .flat_map(|backend| std::iter::from_fn(move || backend.try_recv_from_ui()))
+        .collect();
+    for json in received {
         let preview_size = preview_target_from_config(&config);
         match serde_json::from_str::<UiToBevy>(&json) {
             Ok(msg) => {
@@ -294,6 +553,10 @@ fn handle_cef_ipc(
                     &mut outbound,
                     &mut image_loaded,
                     &mut panel_layout,
+                    &mut probe_requests,
+                    &mut log_commands,
+                    &mut recovery_commands,
+                    &mut subsystems,
                 );
             }
             Err(e) => tracing::warn!("failed to parse UI message: {e}\n  json: {json}"),
@@ -301,7 +564,38 @@ fn handle_cef_ipc(
     }
 }
 
-/// Serialize and send queued outbound messages to CEF.
+/// Writers and state for the app's own subsystems, grouped to keep
+/// `handle_cef_ipc` within Bevy's system parameter limit.
+#[derive(SystemParam)]
+struct SubsystemCommands<'w> {
+    setup: MessageWriter<'w, SetupCommand>,
+    settings: MessageWriter<'w, SettingsCommand>,
+    clipboard: MessageWriter<'w, ClipboardCommand>,
+    gallery: MessageWriter<'w, GalleryCommand>,
+    presets: MessageWriter<'w, PresetCommand>,
+    floating_panels: MessageWriter<'w, FloatingPanelCommand>,
+    ui_reload: MessageWriter<'w, ReloadUi>,
+    overlay: ResMut<'w, OverlayComposite>,
+}
+
+/// Pass right-clicks CEF suppressed its native menu for on to the UI.
+fn forward_context_menu_requests(
+    webview: Option<NonSendMut<CefFrontendResource>>,
+    mut outbound: ResMut<OutboundUiMessages>,
+) {
+    let Some(mut wv) = webview else { return };
+    if let Some(request) = wv.backend.take_context_menu_request() {
+        outbound.send(BevyToUi::ContextMenuRequested {
+            x: request.x,
+            y: request.y,
+            editable: request.editable,
+            selection_text: request.selection_text,
+            link_url: request.link_url,
+        });
+    }
+}
+
+/// Serialize and send queued outbound messages to every CEF webview.
 fn flush_outbound_messages(
     mut outbound: ResMut<OutboundUiMessages>,
     webview: Option<NonSendMut<CefFrontendResource>>,
@@ -316,13 +610,191 @@ fn flush_outbound_messages(
     for msg in msgs {
         match serde_json::to_string(&msg) {
             Ok(json) => {
-                let _ = wv.backend.send_to_ui(json);
+                for backend in wv.all_mut() {
+                    let _ = backend.send_to_ui(json.clone());
+                }
             }
             Err(e) => tracing::error!("failed to serialize BevyToUi: {e}"),
         }
     }
 }
 
+// ── Floating panels ──────────────────────────────────────────────
+
+/// Open, move and close floating webviews and their overlay nodes.
+fn handle_floating_panel_commands(
+    mut requests: MessageReader<FloatingPanelCommand>,
+    webview: Option<NonSendMut<CefFrontendResource>>,
+    windows: Query<&Window>,
+    ui_scale: Res<UiScale>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlays: Query<(Entity, &CefFloatingOverlay, &mut Node)>,
+    mut commands: Commands,
+) {
+    let Some(mut wv) = webview else {
+        requests.clear();
+        return;
+    };
+    // CSS pixels to physical ones, as CEF lays the page out.
+    let scale = windows.single().map_or(1.0, Window::scale_factor) * ui_scale.0;
+
+    for request in requests.read() {
+        match request {
+            FloatingPanelCommand::Open {
+                panel,
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let rect = PanelRect {
+                    x: (x * scale).round() as i32,
+                    y: (y * scale).round() as i32,
+                    width: (width * scale).round().max(1.0) as u32,
+                    height: (height * scale).round().max(1.0) as u32,
+                };
+                let node = Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(*x),
+                    top: Val::Px(*y),
+                    width: Val::Px(*width),
+                    height: Val::Px(*height),
+                    ..default()
+                };
+
+                if wv.floating.place(panel, rect) {
+                    wv.floating.focus(Some(panel));
+                    if let Some((_, _, mut existing)) = overlays
+                        .iter_mut()
+                        .find(|(_, overlay, _)| overlay.panel == *panel)
+                    {
+                        *existing = node;
+                    }
+                    continue;
+                }
+
+                let mut backend = match wv.backend.open_panel(panel, (rect.width, rect.height)) {
+                    Ok(backend) => backend,
+                    Err(e) => {
+                        tracing::warn!("failed to open floating panel '{panel}': {e}");
+                        continue;
+                    }
+                };
+                backend.set_scale_factor(scale);
+                wv.floating.open(panel, rect, backend);
+
+                let handle = images.add(overlay_image(rect.width, rect.height));
+                commands.spawn((
+                    node,
+                    // Pickable, so native panels underneath do not react to
+                    // clicks on it.
+                    GlobalZIndex(LAYOUT_PANEL_Z + 1),
+                    CefFloatingOverlay {
+                        panel: panel.clone(),
+                        image: handle,
+                    },
+                ));
+                tracing::info!(
+                    "opened floating panel '{panel}' ({}x{})",
+                    rect.width,
+                    rect.height
+                );
+            }
+            FloatingPanelCommand::Close { panel } => {
+                if wv.floating.close(panel).is_some() {
+                    tracing::info!("closed floating panel '{panel}'");
+                }
+                for (entity, overlay, _) in &overlays {
+                    if overlay.panel == *panel {
+                        commands.entity(entity).despawn();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Poll the floating webviews, upload their frames and keep their overlays
+/// stacked in the same order as the webviews.
+fn update_floating_panels(
+    webview: Option<NonSendMut<CefFrontendResource>>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlays: Query<(&CefFloatingOverlay, &mut GlobalZIndex)>,
+) {
+    let Some(mut wv) = webview else { return };
+
+    for (index, view) in wv.floating.iter_mut().enumerate() {
+        view.backend.poll();
+        let Some((overlay, mut z)) = overlays
+            .iter_mut()
+            .find(|(overlay, _)| overlay.panel == view.name)
+        else {
+            continue;
+        };
+        let z_index = LAYOUT_PANEL_Z + 1 + index as i32;
+        if z.0 != z_index {
+            z.0 = z_index;
+        }
+
+        let (bgra, width, height) = match view.backend.capture_if_dirty() {
+            Some(CaptureResult::Bgra(bgra, w, h) | CaptureResult::BgraRegions(bgra, w, h, _)) => {
+                (bgra, w, h)
+            }
+            _ => continue,
+        };
+        if let Some(image) = images.get_mut(&overlay.image) {
+            replace_image_data(image, bgra, width, height);
+        }
+    }
+}
+
+/// Hand the main texture and the floating ones, bottom to top, to the
+/// overlay composite.
+fn sync_overlay_layers(
+    webview: Option<NonSend<CefFrontendResource>>,
+    ui_tex: Option<Res<CefUiTextureHandle>>,
+    overlays: Query<&CefFloatingOverlay>,
+    mut composite: ResMut<OverlayComposite>,
+) {
+    let (Some(wv), Some(tex)) = (webview, ui_tex) else {
+        return;
+    };
+
+    let mut layers = vec![OverlayLayer {
+        image: tex.handle.id(),
+        rect: None,
+    }];
+    for view in wv.floating.iter() {
+        let Some(overlay) = overlays.iter().find(|overlay| overlay.panel == view.name) else {
+            continue;
+        };
+        let PanelRect {
+            x,
+            y,
+            width,
+            height,
+        } = view.rect;
+        layers.push(OverlayLayer {
+            image: overlay.image.id(),
+            rect: Some(IRect::new(x, y, x + width as i32, y + height as i32)),
+        });
+    }
+    // Only touch the resource on change, so it is not re-extracted every frame.
+    if composite.layers != layers {
+        composite.layers = layers;
+    }
+}
+
+/// Composite the overlay into the UI camera.
+fn mark_overlay_cameras(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<Camera2d>, Without<OverlayCompositeCamera>)>,
+) {
+    for camera in &cameras {
+        commands.entity(camera).insert(OverlayCompositeCamera);
+    }
+}
+
 // ── Message dispatch ─────────────────────────────────────────────
 
 #[allow(clippy::too_many_arguments)]
@@ -337,18 +809,26 @@ fn dispatch_ui_message(
     outbound: &mut ResMut<OutboundUiMessages>,
     image_loaded: &mut MessageWriter<ImageLoadedEvent>,
     panel_layout: &mut ResMut<PanelLayout>,
+    probe_requests: &mut MessageWriter<StartProbeCheck>,
+    log_commands: &mut MessageWriter<LogCommand>,
+    recovery_commands: &mut MessageWriter<RecoveryCommand>,
+    subsystems: &mut SubsystemCommands,
 ) {
     match msg {
         UiToBevy::RequestState => {
             outbound.send(BevyToUi::Initialize {
                 params: state.params.clone(),
+                locale: i18n::locale().to_string(),
             });
+            recovery_commands.write(RecoveryCommand::Query);
+            subsystems.setup.write(SetupCommand::Query);
+            subsystems.settings.write(SettingsCommand::Query);
             if let Some(source) = images.source.as_ref() {
                 outbound.send(BevyToUi::ImageLoaded {
                     path: images.source_path.clone().unwrap_or_default(),
-                    width: source.width,
-                    height: source.height,
-                    bit_depth: format!("{:?}", source.source_bit_depth),
+                    width: source.width(),
+                    height: source.height(),
+                    bit_depth: format!("{:?}", source.source_bit_depth()),
                 });
             }
         }
@@ -356,20 +836,88 @@ fn dispatch_ui_message(
             // Handle directly rather than routing through ColorGradingCommand
             // messages, which can be lost if handle_grading_commands runs before
             // handle_cef_ipc in the same frame.
-            if state.params != params {
+            if state.params != *params {
                 tracing::info!(
                     "SetParams: updating grading state (bars: lift={:?}, gamma={:?}, gain={:?}, offset={:?}; wheels: lift={:?}, gamma={:?}, gain={:?}, offset={:?})",
                     params.lift, params.gamma, params.gain, params.offset,
                     params.lift_wheel, params.gamma_wheel, params.gain_wheel, params.offset_wheel
                 );
-                state.params = params.clone();
+                state.params = (*params).clone();
                 state.dirty = true;
-                outbound.send(BevyToUi::ParamsUpdated { params });
+                outbound.send(BevyToUi::ParamsUpdated { params: *params });
             }
         }
         UiToBevy::AutoBalance => {
             commands.write(ColorGradingCommand::AutoBalance);
         }
+        UiToBevy::AutoContrast {
+            low_percentile,
+            high_percentile,
+            strength,
+        } => {
+            commands.write(ColorGradingCommand::AutoContrast {
+                low_percentile,
+                high_percentile,
+                strength,
+            });
+        }
+        UiToBevy::MatchChart { corners } => {
+            commands.write(ColorGradingCommand::MatchChart { corners });
+        }
+        UiToBevy::DetectDefects { threshold } => {
+            commands.write(ColorGradingCommand::DetectDefects { threshold });
+        }
+        UiToBevy::ClearDefects => {
+            commands.write(ColorGradingCommand::SetDefectMap {
+                map: DefectMap::default(),
+            });
+        }
+        UiToBevy::RunParityCheck => {
+            commands.write(ColorGradingCommand::RunParityCheck);
+        }
+        UiToBevy::CompareDeltaE { path, scale } => {
+            match delta_e_overlay::load_reference(&path, preview_size) {
+                Ok(reference) => {
+                    commands.write(ColorGradingCommand::CompareDeltaE {
+                        reference,
+                        scale: scale.unwrap_or(DEFAULT_HEATMAP_SCALE),
+                    });
+                }
+                Err(error) => {
+                    tracing::error!("Failed to load ΔE reference {path}: {error}");
+                    outbound.send(BevyToUi::Error {
+                        message: tr_args("error-delta-e-reference", &[("error", error.into())]),
+                    });
+                }
+            }
+        }
+        UiToBevy::ClearDeltaE => {
+            commands.write(ColorGradingCommand::ClearDeltaE);
+        }
+        UiToBevy::PreviewLooks { dirs } => {
+            commands.write(ColorGradingCommand::PreviewLooks { dirs });
+        }
+        UiToBevy::ApplyLook { path } => {
+            commands.write(ColorGradingCommand::ApplyLook { path });
+        }
+        UiToBevy::ExportBracket { path, bracket } => {
+            commands.write(ColorGradingCommand::ExportBracket {
+                path,
+                bracket: bracket.into(),
+            });
+        }
+        UiToBevy::SaveVersion { path } => {
+            commands.write(ColorGradingCommand::SaveVersion { path });
+        }
+        UiToBevy::ExportAuditLog { path } => {
+            commands.write(ColorGradingCommand::ExportAuditLog { path });
+        }
+        UiToBevy::SetBlanking { blanking } => {
+            commands.write(ColorGradingCommand::SetBlanking { blanking });
+        }
+        UiToBevy::RunScript { path } => {
+            commands.write(ColorGradingCommand::RunScript { path });
+        }
         UiToBevy::ResetGrade => {
             // Handle directly to avoid command ordering issues.
             let defaults = crispen_core::transform::params::GradingParams::default();
@@ -393,15 +941,209 @@ fn dispatch_ui_message(
                 image_loaded,
             );
         }
-        UiToBevy::LoadLut { path, slot } => {
-            commands.write(ColorGradingCommand::LoadLut { path, slot });
+        UiToBevy::LoadTestPattern {
+            pattern,
+            bit_depth,
+            color_space,
+        } => {
+            handle_load_test_pattern(
+                pattern,
+                bit_depth,
+                color_space,
+                preview_size,
+                images,
+                gpu,
+                #[cfg(feature = "ocio")]
+                ocio_state,
+                state,
+                outbound,
+                image_loaded,
+            );
+        }
+        UiToBevy::LoadLut { path, slot, domain } => {
+            commands.write(ColorGradingCommand::LoadLut { path, slot, domain });
+        }
+        UiToBevy::SetLutDomain { slot, domain } => {
+            commands.write(ColorGradingCommand::SetLutDomain { slot, domain });
         }
-        UiToBevy::ExportLut { path, size } => {
-            commands.write(ColorGradingCommand::ExportLut { path, size });
+        UiToBevy::ClearLut { slot } => {
+            commands.write(ColorGradingCommand::ClearLut { slot });
+        }
+        UiToBevy::SetLutWatch { enabled } => {
+            commands.write(ColorGradingCommand::SetLutWatch { enabled });
+        }
+        UiToBevy::ExportLut { path, size, shaper } => {
+            commands.write(ColorGradingCommand::ExportLut { path, size, shaper });
+        }
+        UiToBevy::ExportIcc { path } => {
+            commands.write(ColorGradingCommand::ExportIcc { path });
+        }
+        UiToBevy::ExportNuke { path, size } => {
+            commands.write(ColorGradingCommand::ExportNuke { path, size });
+        }
+        UiToBevy::ExportShader {
+            path,
+            size,
+            language,
+        } => {
+            commands.write(ColorGradingCommand::ExportShader {
+                path,
+                size,
+                language,
+            });
         }
         UiToBevy::ToggleScope { scope_type, visible } => {
             commands.write(ColorGradingCommand::ToggleScope { scope_type, visible });
         }
+        UiToBevy::SetScopeAccumulation { mode } => {
+            commands.write(ColorGradingCommand::SetScopeAccumulation { mode });
+        }
+        UiToBevy::SetScopeDisplayOptions { options } => {
+            commands.write(ColorGradingCommand::SetScopeDisplayOptions { options });
+        }
+        UiToBevy::SetScopeQuality { quality } => {
+            commands.write(ColorGradingCommand::SetScopeQuality { quality });
+        }
+        UiToBevy::SetScopeMaskPolygon { points } => {
+            commands.write(ColorGradingCommand::SetScopeMaskPolygon { points });
+        }
+        UiToBevy::ClearScopeMask => {
+            commands.write(ColorGradingCommand::ClearScopeMask);
+        }
+        UiToBevy::StartProbeCheck => {
+            probe_requests.write(StartProbeCheck);
+        }
+        UiToBevy::SetLogFilter { directives } => {
+            log_commands.write(LogCommand::SetFilter { directives });
+        }
+        UiToBevy::SetLogStreaming { enabled } => {
+            log_commands.write(LogCommand::SetStreaming { enabled });
+        }
+        UiToBevy::DismissCrashRecovery => {
+            recovery_commands.write(RecoveryCommand::Dismiss);
+        }
+        UiToBevy::RunSetup => {
+            subsystems.setup.write(SetupCommand::Run);
+        }
+        UiToBevy::CompleteSetup { config } => {
+            subsystems.setup.write(SetupCommand::Complete(config));
+        }
+        UiToBevy::RequestSettings => {
+            subsystems.settings.write(SettingsCommand::Query);
+        }
+        UiToBevy::UpdateSettings { settings } => {
+            subsystems.settings.write(SettingsCommand::Update(settings));
+        }
+        UiToBevy::ResetSettings => {
+            subsystems.settings.write(SettingsCommand::Reset);
+        }
+        UiToBevy::SetGuides { guides } => {
+            subsystems
+                .settings
+                .write(SettingsCommand::SetGuides(guides));
+        }
+        UiToBevy::SetFocusAssist { focus_assist } => {
+            subsystems
+                .settings
+                .write(SettingsCommand::SetFocusAssist(focus_assist));
+        }
+        UiToBevy::CopyLook => {
+            subsystems.clipboard.write(ClipboardCommand::CopyLook);
+        }
+        UiToBevy::PasteLook { text } => {
+            subsystems
+                .clipboard
+                .write(ClipboardCommand::PasteLook { text });
+        }
+        UiToBevy::CopyStill => {
+            subsystems.clipboard.write(ClipboardCommand::CopyStill);
+        }
+        UiToBevy::RequestGallery => {
+            subsystems.gallery.write(GalleryCommand::Query);
+        }
+        UiToBevy::GrabStill { label } => {
+            commands.write(ColorGradingCommand::GrabStill { label });
+        }
+        UiToBevy::ApplyStill { label } => {
+            subsystems.gallery.write(GalleryCommand::Apply { label });
+        }
+        UiToBevy::DeleteStill { label } => {
+            subsystems.gallery.write(GalleryCommand::Delete { label });
+        }
+        UiToBevy::ExportStill { label, path } => {
+            subsystems.gallery.write(GalleryCommand::Export {
+                label,
+                path: path.into(),
+            });
+        }
+        UiToBevy::ImportStill { path } => {
+            subsystems
+                .gallery
+                .write(GalleryCommand::Import { path: path.into() });
+        }
+        UiToBevy::RequestToolPresets => {
+            subsystems.presets.write(PresetCommand::Query);
+        }
+        UiToBevy::SaveToolPreset {
+            tool,
+            name,
+            category,
+        } => {
+            subsystems.presets.write(PresetCommand::Save {
+                tool,
+                name,
+                category,
+            });
+        }
+        UiToBevy::ApplyToolPreset { tool, name } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Apply { tool, name });
+        }
+        UiToBevy::DeleteToolPreset { tool, name } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Delete { tool, name });
+        }
+        UiToBevy::ImportToolPresets { path } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Import { path: path.into() });
+        }
+        UiToBevy::ExportToolPresets { path, tool } => {
+            subsystems.presets.write(PresetCommand::Export {
+                path: path.into(),
+                tool,
+            });
+        }
+        UiToBevy::OpenFloatingPanel {
+            panel,
+            x,
+            y,
+            width,
+            height,
+        } => {
+            subsystems
+                .floating_panels
+                .write(FloatingPanelCommand::Open {
+                    panel,
+                    x,
+                    y,
+                    width,
+                    height,
+                });
+        }
+        UiToBevy::CloseFloatingPanel { panel } => {
+            subsystems
+                .floating_panels
+                .write(FloatingPanelCommand::Close { panel });
+        }
+        UiToBevy::ReloadUi => {
+            subsystems.ui_reload.write(ReloadUi);
+        }
+        UiToBevy::SetModalOpen { open } => {
+            subsystems.overlay.dimmed = open;
+        }
         UiToBevy::UiDirty => {
             // Handled internally by CEF dirty flag — nothing to do here.
         }
@@ -451,13 +1193,10 @@ fn handle_load_image(
             let height = img.height;
             let bit_depth = format!("{:?}", img.source_bit_depth);
 
+            let source = images.set_source(img, path.to_string());
             if let Some(gpu) = gpu {
-                let handle = gpu.pipeline.upload_image(&img);
-                gpu.source_handle = Some(handle);
+                gpu.source_handle = Some(gpu.pipeline.upload_source(source));
             }
-
-            images.source = Some(img);
-            images.source_path = Some(path.to_string());
             state.dirty = true;
 
             #[cfg(feature = "ocio")]
@@ -491,53 +1230,69 @@ fn handle_load_image(
         Err(e) => {
             tracing::error!("failed to load image {path}: {e}");
             outbound.send(BevyToUi::Error {
-                message: format!("Failed to load image: {e}"),
+                message: tr_args("error-load-image", &[("error", e.to_string().into())]),
             });
         }
     }
 }
 
-fn preview_target_from_config(config: &AppConfig) -> Option<(u32, u32)> {
-    let w = (config.width - 24.0).max(128.0).round() as u32;
-    let h = (config.height - crate::ui::theme::PRIMARIES_PANEL_HEIGHT - 32.0)
-        .max(128.0)
-        .round() as u32;
-    Some((w, h))
-}
+/// Generate a built-in test pattern and install it as the source image.
+#[allow(clippy::too_many_arguments)]
+fn handle_load_test_pattern(
+    pattern: TestPattern,
+    bit_depth: BitDepth,
+    color_space: ColorSpaceId,
+    preview_size: Option<(u32, u32)>,
+    images: &mut ResMut<ImageState>,
+    gpu: Option<&mut GpuPipelineState>,
+    #[cfg(feature = "ocio")] ocio_state: Option<&mut OcioColorManagement>,
+    state: &mut ResMut<GradingState>,
+    outbound: &mut ResMut<OutboundUiMessages>,
+    image_loaded: &mut MessageWriter<ImageLoadedEvent>,
+) {
+    let (width, height) = preview_size.unwrap_or((1920, 1080));
+    let img = crispen_core::patterns::generate(pattern, width, height, bit_depth, color_space);
+    let path = format!("pattern:{}", pattern.label());
+    let bit_depth = format!("{:?}", img.source_bit_depth);
 
-/// Locate the built Svelte UI (dist/ui/index.html) relative to the executable
-/// or the project root.
-fn find_built_ui() -> Option<std::path::PathBuf> {
-    // 1. Next to the executable (for packaged builds).
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(dir) = exe.parent() {
-            let candidate = dir.join("ui/index.html");
-            if candidate.exists() {
-                return Some(candidate);
-            }
-        }
+    let source = images.set_source(img, path.clone());
+    if let Some(gpu) = gpu {
+        gpu.source_handle = Some(gpu.pipeline.upload_source(source));
     }
+    state.dirty = true;
 
-    // 2. Relative to the working directory (development layout).
-    let candidates = [
-        Path::new("crates/crispen-demo/dist/ui/index.html"),
-        Path::new("dist/ui/index.html"),
-    ];
-    for candidate in &candidates {
-        if candidate.exists() {
-            return std::fs::canonicalize(candidate).ok();
-        }
+    if state.params.color_management.input_space != color_space {
+        state.params.color_management.input_space = color_space;
+        outbound.send(BevyToUi::ParamsUpdated {
+            params: state.params.clone(),
+        });
     }
 
-    None
+    #[cfg(feature = "ocio")]
+    if let Some(ocio) = ocio_state {
+        ocio.input_space =
+            crate::ocio_support::map_detected_to_ocio_name(color_space, &ocio.config);
+        ocio.dirty = true;
+    }
+
+    image_loaded.write(ImageLoadedEvent {
+        path: path.clone(),
+        width,
+        height,
+        bit_depth: bit_depth.clone(),
+    });
+    outbound.send(BevyToUi::ImageLoaded {
+        path,
+        width,
+        height,
+        bit_depth,
+    });
 }
 
-fn config_dir() -> Option<std::path::PathBuf> {
-    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
-        Some(std::path::PathBuf::from(xdg).join("crispen"))
-    } else if let Ok(home) = std::env::var("HOME") {
-        Some(std::path::PathBuf::from(home).join(".config/crispen"))
-    } else {
-        None
-    }
+fn preview_target_from_config(config: &AppConfig) -> Option<(u32, u32)> {
+    let w = (config.width - 24.0).max(128.0).round() as u32;
+    let h = (config.height - crate::ui::theme::PRIMARIES_PANEL_HEIGHT - 32.0)
+        .max(128.0)
+        .round() as u32;
+    Some((w, h))
 }
@@ -0,0 +1,162 @@
+//! Copy and paste of values, looks and stills.
+//!
+//! Looks travel as `GradingParams` JSON and stills as the graded viewer
+//! image. With the CEF frontend they go through the backend's clipboard,
+//! which reaches the system clipboard via Chromium. Otherwise, and for the
+//! native dials and sliders, [`NativeClipboard`] keeps them in-process.
+
+use bevy::prelude::*;
+use crispen_bevy::events::ColorGradingCommand;
+use crispen_bevy::resources::{GradingState, ViewerData};
+use crispen_core::transform::params::GradingParams;
+use crispen_frontend_core::{Clipboard, ClipboardImage, FrontendError, MemoryClipboard};
+
+#[cfg(feature = "cef")]
+use crispen_frontend_core::CompositeBackend;
+
+#[cfg(feature = "cef")]
+use crate::cef_bridge::CefFrontendResource;
+use crate::keymap::{Action, KeyBindings};
+use crate::ui::viewer::write_srgb8;
+
+/// In-process clipboard used where no system clipboard is reachable.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct NativeClipboard(pub MemoryClipboard);
+
+impl NativeClipboard {
+    /// Copy a single control value as text.
+    pub fn copy_value(&mut self, value: f32) {
+        // Writing text to the in-process clipboard cannot fail.
+        let _ = self.write_text(&value.to_string());
+    }
+
+    /// The number on the clipboard, if it holds one.
+    pub fn paste_value(&mut self) -> Option<f32> {
+        self.read_text().ok().flatten()?.trim().parse().ok()
+    }
+}
+
+/// Clipboard requests from shortcuts or the web UI.
+#[derive(Message, Debug, Clone)]
+pub enum ClipboardCommand {
+    /// Copy the current grade as JSON.
+    CopyLook,
+    /// Apply a look. `text` is what the web UI read from the system
+    /// clipboard; without it the active clipboard is read.
+    PasteLook { text: Option<String> },
+    /// Copy the graded viewer image.
+    CopyStill,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ClipboardError {
+    #[error(transparent)]
+    Backend(#[from] FrontendError),
+
+    #[error("the clipboard holds no text")]
+    NoText,
+
+    #[error("the clipboard does not hold a look: {0}")]
+    NotALook(serde_json::Error),
+
+    #[error("there is no graded image to copy")]
+    NoImage,
+}
+
+/// Handles [`ClipboardCommand`]s and their shortcuts.
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NativeClipboard>()
+            .add_message::<ClipboardCommand>()
+            .add_systems(
+                Update,
+                (
+                    handle_clipboard_shortcuts,
+                    handle_clipboard_commands.after(handle_clipboard_shortcuts),
+                ),
+            );
+    }
+}
+
+fn handle_clipboard_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut commands: MessageWriter<ClipboardCommand>,
+) {
+    if bindings.just_pressed(Action::CopyLook, &keys) {
+        commands.write(ClipboardCommand::CopyLook);
+    }
+    if bindings.just_pressed(Action::PasteLook, &keys) {
+        commands.write(ClipboardCommand::PasteLook { text: None });
+    }
+    if bindings.just_pressed(Action::CopyStill, &keys) {
+        commands.write(ClipboardCommand::CopyStill);
+    }
+}
+
+fn handle_clipboard_commands(
+    mut commands: MessageReader<ClipboardCommand>,
+    mut native: ResMut<NativeClipboard>,
+    #[cfg(feature = "cef")] mut webview: Option<NonSendMut<CefFrontendResource>>,
+    state: Res<GradingState>,
+    viewer: Res<ViewerData>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+) {
+    for command in commands.read() {
+        #[cfg(feature = "cef")]
+        let clipboard = match webview.as_mut().and_then(|wv| wv.backend.clipboard()) {
+            Some(clipboard) => clipboard,
+            None => &mut native.0,
+        };
+        #[cfg(not(feature = "cef"))]
+        let clipboard = &mut native.0;
+
+        let result = match command {
+            ClipboardCommand::CopyLook => copy_look(clipboard, &state.params),
+            ClipboardCommand::PasteLook { text } => {
+                paste_look(clipboard, text.clone()).map(|params| {
+                    grading.write(ColorGradingCommand::SetParams { params });
+                })
+            }
+            ClipboardCommand::CopyStill => copy_still(clipboard, &viewer),
+        };
+        match result {
+            Ok(()) => tracing::info!("clipboard: {command:?} done"),
+            Err(e) => tracing::warn!("clipboard: {command:?} failed: {e}"),
+        }
+    }
+}
+
+fn copy_look(clipboard: &mut dyn Clipboard, params: &GradingParams) -> Result<(), ClipboardError> {
+    let json = serde_json::to_string_pretty(params)
+        .map_err(|e| FrontendError::Clipboard(e.to_string()))?;
+    clipboard.write_text(&json)?;
+    Ok(())
+}
+
+fn paste_look(
+    clipboard: &mut dyn Clipboard,
+    text: Option<String>,
+) -> Result<GradingParams, ClipboardError> {
+    let text = match text {
+        Some(text) => text,
+        None => clipboard.read_text()?.ok_or(ClipboardError::NoText)?,
+    };
+    serde_json::from_str(&text).map_err(ClipboardError::NotALook)
+}
+
+fn copy_still(clipboard: &mut dyn Clipboard, viewer: &ViewerData) -> Result<(), ClipboardError> {
+    if viewer.width == 0 || viewer.height == 0 || viewer.pixel_bytes.is_empty() {
+        return Err(ClipboardError::NoImage);
+    }
+    let mut rgba = vec![0; viewer.width as usize * viewer.height as usize * 4];
+    write_srgb8(viewer.format, &viewer.pixel_bytes, &mut rgba);
+    clipboard.write_image(&ClipboardImage {
+        width: viewer.width,
+        height: viewer.height,
+        rgba,
+    })?;
+    Ok(())
+}
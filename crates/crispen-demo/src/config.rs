@@ -1,6 +1,10 @@
 //! Application configuration for the demo.
 
+use std::path::PathBuf;
+
 use bevy::prelude::*;
+use crispen_core::transform::params::ColorSpaceId;
+use serde::{Deserialize, Serialize};
 
 /// Default WebSocket port for the IPC bridge (kept for backward compat).
 const DEFAULT_WS_PORT: u16 = 9400;
@@ -39,6 +43,9 @@ pub struct AppConfig {
     pub height: f32,
     /// Whether to use the Vite dev server for the UI.
     pub dev_mode: bool,
+    /// Whether to reload the built UI when its files change
+    /// (`CRISPEN_UI_WATCH`). Dev mode watches the Vite dev server instead.
+    pub ui_watch: bool,
     /// Which frontend stack to run.
     pub frontend_mode: FrontendMode,
 }
@@ -53,7 +60,126 @@ impl Default for AppConfig {
             width: DEFAULT_WIDTH,
             height: DEFAULT_HEIGHT,
             dev_mode: std::env::var("CRISPEN_DEV").is_ok(),
+            ui_watch: std::env::var("CRISPEN_UI_WATCH").is_ok(),
             frontend_mode: FrontendMode::from_env(),
         }
     }
 }
+
+/// Per-user config directory (`$XDG_CONFIG_HOME/crispen`, falling back to
+/// `~/.config/crispen`). Holds the saved layout and crash reports.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        Some(PathBuf::from(xdg).join("crispen"))
+    } else if let Ok(home) = std::env::var("HOME") {
+        Some(PathBuf::from(home).join(".config/crispen"))
+    } else {
+        None
+    }
+}
+
+/// How color is managed between source, working space, and display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ColorManagementMode {
+    /// Crispen's built-in color space transforms.
+    Native,
+    /// An OpenColorIO config (`ocio` feature).
+    Ocio {
+        /// `.ocio` file to load; `None` uses `$OCIO` or a built-in ACES
+        /// studio config.
+        config_path: Option<String>,
+    },
+}
+
+impl Default for ColorManagementMode {
+    fn default() -> Self {
+        if cfg!(feature = "ocio") {
+            Self::Ocio { config_path: None }
+        } else {
+            Self::Native
+        }
+    }
+}
+
+/// User choices persisted in `config.json` under [`config_dir`].
+///
+/// Written by the first-run setup wizard. Color management and the GPU
+/// adapter are read at startup; a change takes effect on the next launch.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserConfig {
+    /// Whether the setup wizard has been completed.
+    pub setup_complete: bool,
+    /// Native or OCIO color management.
+    pub color_management: ColorManagementMode,
+    /// Working color space for grading.
+    pub working_space: ColorSpaceId,
+    /// Primaries of the display the window is shown on. The sRGB UI
+    /// overlay is converted to them when composited.
+    pub display_space: ColorSpaceId,
+    /// GPU adapter name; `None` picks the default high-performance adapter.
+    pub gpu_adapter: Option<String>,
+    /// Directory for persistent caches, such as the web UI's browser
+    /// cache; `None` uses `<config dir>/cache`.
+    pub cache_dir: Option<String>,
+    /// Directory exports are staged in before being moved into place;
+    /// `None` uses the system temp dir.
+    pub scratch_dir: Option<String>,
+}
+
+impl Default for UserConfig {
+    fn default() -> Self {
+        Self {
+            setup_complete: false,
+            color_management: ColorManagementMode::default(),
+            working_space: ColorSpaceId::AcesCg,
+            display_space: ColorSpaceId::Srgb,
+            gpu_adapter: None,
+            cache_dir: None,
+            scratch_dir: None,
+        }
+    }
+}
+
+impl UserConfig {
+    const FILE_NAME: &str = "config.json";
+
+    /// Load the config file, or defaults if it is missing or unreadable.
+    pub fn load() -> Self {
+        let Some(path) = config_dir().map(|dir| dir.join(Self::FILE_NAME)) else {
+            return Self::default();
+        };
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            tracing::warn!("ignoring invalid {}: {e}", path.display());
+            Self::default()
+        })
+    }
+
+    /// Write the config file, creating the config directory if needed.
+    pub fn save(&self) -> std::io::Result<()> {
+        let dir = config_dir().ok_or_else(|| std::io::Error::other("no config directory"))?;
+        std::fs::create_dir_all(&dir)?;
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(dir.join(Self::FILE_NAME), json)
+    }
+
+    /// Resolved cache directory.
+    #[cfg(feature = "cef")]
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        match &self.cache_dir {
+            Some(dir) => Some(PathBuf::from(dir)),
+            None => config_dir().map(|dir| dir.join("cache")),
+        }
+    }
+
+    /// Resolved scratch directory.
+    pub fn scratch_dir(&self) -> PathBuf {
+        self.scratch_dir
+            .as_ref()
+            .map_or_else(std::env::temp_dir, PathBuf::from)
+    }
+}
@@ -0,0 +1,423 @@
+//! Crash reports and session recovery.
+//!
+//! A panic hook writes a bundle to `<config>/crashes/<unix-time>/`:
+//!
+//! | File | Contents |
+//! |------|----------|
+//! | `report.txt` | Panic message and location, thread, backtrace, app version, OS, GPU adapter |
+//! | `logs.txt` | Active log filter and the records retained by [`crate::diagnostics`] |
+//! | `session.json` | Grade and source image path at the time of the crash |
+//!
+//! It also leaves a `recovery.json` marker in the config dir. On the next
+//! launch the UI is offered the session back; restoring or dismissing the
+//! offer removes the marker.
+//!
+//! Only Rust panics are caught. Native crashes (in a driver or CEF) leave no
+//! bundle, so the session is also autosaved to `autosave.json` at the
+//! interval in [`Settings`]. The file is removed on a clean exit; finding it
+//! at launch without a marker means the last run died, and its session is
+//! offered instead.
+
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use crispen_bevy::resources::{GpuPipelineState, GradingState, ImageState};
+use crispen_core::transform::params::GradingParams;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::diagnostics::{LogCapture, LogTail};
+use crate::i18n::tr;
+use crate::settings::Settings;
+
+/// Marker file in the config dir pointing at the last unrecovered crash.
+const RECOVERY_FILE: &str = "recovery.json";
+
+/// Session autosave in the config dir, present while the app is running.
+const AUTOSAVE_FILE: &str = "autosave.json";
+
+/// Set once a bundle has been written, so panics raised while unwinding
+/// from the first one do not overwrite it.
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Grade and image needed to resume a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Grading parameters in effect.
+    pub params: GradingParams,
+    /// Source image path, if one was loaded.
+    pub image_path: Option<String>,
+}
+
+/// Contents of the recovery marker: where the report is and what to restore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashRecovery {
+    /// Directory holding the crash bundle; `None` when the session comes
+    /// from an autosave because the app died without one.
+    pub report_dir: Option<String>,
+    /// Unix time of the crash in seconds.
+    pub crashed_at: u64,
+    /// Panic message and location, or why the autosave is offered.
+    pub message: String,
+    /// Session at the time of the crash, if it could be captured.
+    pub session: Option<SessionSnapshot>,
+}
+
+/// State the panic hook reads. Updated by ECS systems while the app runs.
+#[derive(Default)]
+struct CrashContext {
+    session: Option<SessionSnapshot>,
+    gpu_adapter: Option<String>,
+    logs: Option<LogTail>,
+    /// Log filter directives in effect, so missing records can be told
+    /// apart from filtered ones.
+    log_filter: Option<String>,
+}
+
+/// Crash handling state.
+#[derive(Resource)]
+pub struct CrashReporter {
+    context: Arc<Mutex<CrashContext>>,
+    /// Crash left over from the previous run, until restored or dismissed.
+    pending: Option<CrashRecovery>,
+    config_dir: Option<PathBuf>,
+}
+
+/// Requests from the UI about a previous crash.
+#[derive(Message, Debug, Clone, Copy)]
+pub enum RecoveryCommand {
+    /// The UI connected; offer recovery if a crash is pending.
+    Query,
+    /// The user restored or declined the session; forget the crash.
+    Dismiss,
+}
+
+/// A previous run crashed and its session can be restored.
+#[derive(Message, Debug, Clone)]
+pub struct RecoveryOffered(pub CrashRecovery);
+
+/// Installs the panic hook and offers recovery from the previous crash.
+///
+/// Add after `LogPlugin` so crash bundles include recent log records.
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        let config_dir = config_dir();
+        let context = Arc::new(Mutex::new(CrashContext {
+            logs: app
+                .world()
+                .get_resource::<LogCapture>()
+                .map(LogCapture::tail),
+            ..default()
+        }));
+
+        let pending = config_dir
+            .as_deref()
+            .and_then(|dir| read_recovery(dir).or_else(|| read_autosave(dir)));
+        if let Some(recovery) = &pending {
+            match &recovery.report_dir {
+                Some(report_dir) => tracing::warn!(
+                    "previous session crashed ({}); report in {report_dir}",
+                    recovery.message
+                ),
+                None => tracing::warn!("{}", recovery.message),
+            }
+        }
+
+        match &config_dir {
+            Some(dir) => install_panic_hook(dir.clone(), context.clone()),
+            None => tracing::warn!("no config directory; crash reports are disabled"),
+        }
+
+        app.insert_resource(CrashReporter {
+            context,
+            pending,
+            config_dir,
+        })
+        .add_message::<RecoveryCommand>()
+        .add_message::<RecoveryOffered>()
+        .add_systems(
+            Update,
+            (
+                update_crash_context,
+                handle_recovery_commands,
+                autosave_session,
+            ),
+        )
+        .add_systems(Last, remove_autosave_on_exit);
+    }
+}
+
+/// Keep the hook's session snapshot, GPU description and log filter
+/// current.
+fn update_crash_context(
+    reporter: Res<CrashReporter>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    gpu: Option<Res<GpuPipelineState>>,
+    capture: Option<Res<LogCapture>>,
+) {
+    if let Some(capture) = capture.filter(|capture| capture.is_changed()) {
+        reporter.context.lock().log_filter = Some(capture.directives().to_string());
+    }
+
+    let gpu_added = gpu.as_ref().is_some_and(|gpu| gpu.is_added());
+    if !state.is_changed() && !images.is_changed() && !gpu_added {
+        return;
+    }
+
+    let mut context = reporter.context.lock();
+    context.session = Some(SessionSnapshot {
+        params: state.params.clone(),
+        image_path: images.source_path.clone(),
+    });
+    if gpu_added {
+        context.gpu_adapter = gpu.and_then(|gpu| {
+            gpu.pipeline.adapter_info().map(|info| {
+                format!(
+                    "{} ({:?}, vendor {:#06x}, device {:#06x}, driver {} {})",
+                    info.name,
+                    info.backend,
+                    info.vendor,
+                    info.device,
+                    info.driver,
+                    info.driver_info
+                )
+            })
+        });
+    }
+}
+
+fn handle_recovery_commands(
+    mut commands: MessageReader<RecoveryCommand>,
+    mut reporter: ResMut<CrashReporter>,
+    mut offers: MessageWriter<RecoveryOffered>,
+) {
+    for command in commands.read() {
+        match command {
+            RecoveryCommand::Query => {
+                if let Some(recovery) = &reporter.pending {
+                    offers.write(RecoveryOffered(recovery.clone()));
+                }
+            }
+            RecoveryCommand::Dismiss => {
+                let Some(recovery) = reporter.pending.take() else {
+                    continue;
+                };
+                let Some(dir) = &reporter.config_dir else {
+                    continue;
+                };
+                if recovery.report_dir.is_some()
+                    && let Err(e) = std::fs::remove_file(dir.join(RECOVERY_FILE))
+                {
+                    tracing::warn!("failed to remove crash recovery marker: {e}");
+                }
+                // The current session replaces it at the next autosave.
+                std::fs::remove_file(dir.join(AUTOSAVE_FILE)).ok();
+            }
+        }
+    }
+}
+
+fn read_recovery(config_dir: &Path) -> Option<CrashRecovery> {
+    let path = config_dir.join(RECOVERY_FILE);
+    let json = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(recovery) => Some(recovery),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable {}: {e}", path.display());
+            std::fs::remove_file(&path).ok();
+            None
+        }
+    }
+}
+
+/// Autosave left behind by a run that neither exited cleanly nor panicked.
+fn read_autosave(config_dir: &Path) -> Option<CrashRecovery> {
+    let path = config_dir.join(AUTOSAVE_FILE);
+    let json = std::fs::read_to_string(&path).ok()?;
+    let session = match serde_json::from_str(&json) {
+        Ok(session) => session,
+        Err(e) => {
+            tracing::warn!("ignoring unreadable {}: {e}", path.display());
+            std::fs::remove_file(&path).ok();
+            return None;
+        }
+    };
+    let crashed_at = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    Some(CrashRecovery {
+        report_dir: None,
+        crashed_at,
+        message: tr("recovery-autosave"),
+        session: Some(session),
+    })
+}
+
+/// Write the session to the autosave file once the configured interval has
+/// passed since the first unsaved change.
+fn autosave_session(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    state: Res<GradingState>,
+    images: Res<ImageState>,
+    reporter: Res<CrashReporter>,
+    mut unsaved_for: Local<Option<Duration>>,
+) {
+    if state.is_changed() || images.is_changed() {
+        unsaved_for.get_or_insert(Duration::ZERO);
+    }
+    let autosave = &settings.autosave;
+    let Some(dir) = reporter.config_dir.as_deref() else {
+        return;
+    };
+    let Some(elapsed) = unsaved_for.as_mut().filter(|_| autosave.enabled) else {
+        return;
+    };
+    *elapsed += time.delta();
+    if *elapsed < Duration::from_secs(autosave.interval_secs.into()) {
+        return;
+    }
+    *unsaved_for = None;
+
+    let session = SessionSnapshot {
+        params: state.params.clone(),
+        image_path: images.source_path.clone(),
+    };
+    // Write then rename so a crash mid-write leaves the previous autosave.
+    let path = dir.join(AUTOSAVE_FILE);
+    let tmp = path.with_extension("json.tmp");
+    let result = serde_json::to_string_pretty(&session)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&tmp, json)))
+        .and_then(|()| std::fs::rename(&tmp, &path));
+    if let Err(e) = result {
+        tracing::warn!("autosave failed: {e}");
+    }
+}
+
+/// A clean exit leaves nothing to recover.
+fn remove_autosave_on_exit(mut exit: MessageReader<AppExit>, reporter: Res<CrashReporter>) {
+    if exit.read().next().is_some()
+        && let Some(dir) = &reporter.config_dir
+    {
+        std::fs::remove_file(dir.join(AUTOSAVE_FILE)).ok();
+    }
+}
+
+fn install_panic_hook(config_dir: PathBuf, context: Arc<Mutex<CrashContext>>) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !REPORTED.swap(true, Ordering::SeqCst) {
+            // The logger may be what panicked, so report on stderr directly.
+            match write_bundle(&config_dir, &context, info) {
+                Ok(dir) => eprintln!("crash report written to {}", dir.display()),
+                Err(e) => eprintln!("failed to write crash report: {e}"),
+            }
+        }
+        previous(info);
+    }));
+}
+
+fn write_bundle(
+    config_dir: &Path,
+    context: &Mutex<CrashContext>,
+    info: &PanicHookInfo<'_>,
+) -> std::io::Result<PathBuf> {
+    let crashed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let dir = config_dir.join("crashes").join(crashed_at.to_string());
+    std::fs::create_dir_all(&dir)?;
+
+    let payload = info
+        .payload()
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    let message = match info.location() {
+        Some(location) => format!("{payload} at {location}"),
+        None => payload.to_string(),
+    };
+
+    // Never block here: the panicking thread may hold the lock.
+    let (session, gpu_adapter, records, log_filter) = match context.try_lock() {
+        Some(context) => (
+            context.session.clone(),
+            context.gpu_adapter.clone(),
+            context.logs.as_ref().and_then(LogTail::try_snapshot),
+            context.log_filter.clone(),
+        ),
+        None => (None, None, None, None),
+    };
+
+    let mut report = String::new();
+    let thread = std::thread::current();
+    let _ = writeln!(
+        report,
+        "Crispen {} crashed at {crashed_at}",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(
+        report,
+        "thread '{}' panicked: {message}",
+        thread.name().unwrap_or("<unnamed>")
+    );
+    let _ = writeln!(
+        report,
+        "OS: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(
+        report,
+        "GPU: {}",
+        gpu_adapter.as_deref().unwrap_or("unknown")
+    );
+    let _ = writeln!(
+        report,
+        "\nBacktrace:\n{}",
+        std::backtrace::Backtrace::force_capture()
+    );
+    std::fs::write(dir.join("report.txt"), report)?;
+
+    let mut logs = match log_filter {
+        Some(filter) => format!("log filter: {filter}\n\n"),
+        None => String::new(),
+    };
+    match records {
+        Some(records) => logs.extend(records.iter().map(|r| format!("{r}\n"))),
+        None => logs.push_str("log records unavailable\n"),
+    }
+    std::fs::write(dir.join("logs.txt"), logs)?;
+
+    if let Some(session) = &session {
+        std::fs::write(
+            dir.join("session.json"),
+            serde_json::to_string_pretty(session).map_err(std::io::Error::other)?,
+        )?;
+    }
+
+    let recovery = CrashRecovery {
+        report_dir: Some(dir.display().to_string()),
+        crashed_at,
+        message,
+        session,
+    };
+    std::fs::write(
+        config_dir.join(RECOVERY_FILE),
+        serde_json::to_string_pretty(&recovery).map_err(std::io::Error::other)?,
+    )?;
+    Ok(dir)
+}
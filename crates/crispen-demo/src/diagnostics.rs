@@ -0,0 +1,347 @@
+//! In-app log capture and runtime log filtering.
+//!
+//! Replaces the `LogPlugin` formatter with two layers that share one
+//! reloadable [`EnvFilter`] directive string: the usual stderr output and a
+//! capture layer that keeps the most recent records in memory. The UI's
+//! diagnostics panel streams the captured records and changes the
+//! directives (`info,crispen_gpu=trace`) without a restart.
+//!
+//! `LogPlugin` still applies its own global filter in front of these
+//! layers, so the plugin is configured at `TRACE` and the directives here
+//! decide what is shown. A `RUST_LOG` set at launch caps the global filter
+//! and seeds the initial directives.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::log::tracing_subscriber::filter::EnvFilter;
+use bevy::log::tracing_subscriber::layer::{Context, Identity, Layer};
+use bevy::log::tracing_subscriber::registry::Registry;
+use bevy::log::tracing_subscriber::{fmt, reload};
+use bevy::log::{BoxedFmtLayer, BoxedLayer, DEFAULT_FILTER};
+use bevy::prelude::*;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+
+/// Records kept in memory for the diagnostics panel unless the settings
+/// say otherwise.
+pub const DEFAULT_CAPTURE_CAPACITY: usize = 2000;
+
+/// Most records sent to the UI in one message.
+const MAX_RECORDS_PER_MESSAGE: usize = 200;
+
+/// One captured log event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// Monotonic sequence number, unique for the session.
+    pub seq: u64,
+    /// Unix time in milliseconds.
+    pub timestamp_ms: u64,
+    /// `ERROR`, `WARN`, `INFO`, `DEBUG` or `TRACE`.
+    pub level: String,
+    /// Module path or explicit `target:` of the event.
+    pub target: String,
+    /// Formatted `message` field.
+    pub message: String,
+    /// Remaining structured fields as `(name, value)` pairs.
+    pub fields: Vec<(String, String)>,
+}
+
+impl std::fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{:03} {:>5} {}: {}",
+            self.timestamp_ms / 1000,
+            self.timestamp_ms % 1000,
+            self.level,
+            self.target,
+            self.message
+        )?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Ring buffer of recent records shared with the capture layer.
+struct LogRing {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    next_seq: u64,
+}
+
+impl Default for LogRing {
+    fn default() -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity: DEFAULT_CAPTURE_CAPACITY,
+            next_seq: 0,
+        }
+    }
+}
+
+impl LogRing {
+    fn push(&mut self, mut record: LogRecord) {
+        record.seq = self.next_seq;
+        self.next_seq += 1;
+        if self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        let excess = self.records.len().saturating_sub(self.capacity);
+        self.records.drain(..excess);
+    }
+}
+
+type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Read-only handle to the captured records, usable outside the ECS.
+#[derive(Clone)]
+pub struct LogTail {
+    ring: Arc<Mutex<LogRing>>,
+}
+
+impl LogTail {
+    /// The retained records, oldest first. Returns `None` instead of
+    /// blocking if the buffer is locked, so it is safe to call from a panic
+    /// hook that may have interrupted a logging call.
+    pub fn try_snapshot(&self) -> Option<Vec<LogRecord>> {
+        self.ring
+            .try_lock()
+            .map(|ring| ring.records.iter().cloned().collect())
+    }
+}
+
+/// Captured records and the handles used to change log levels at runtime.
+#[derive(Resource)]
+pub struct LogCapture {
+    ring: Arc<Mutex<LogRing>>,
+    filters: Vec<FilterHandle>,
+    directives: String,
+    /// Whether new records are forwarded to the UI.
+    pub streaming: bool,
+    /// Next sequence number the UI has not received.
+    pub sent_seq: u64,
+}
+
+impl LogCapture {
+    /// Active filter directives.
+    pub fn directives(&self) -> &str {
+        &self.directives
+    }
+
+    /// Replace the filter directives on every layer.
+    ///
+    /// The directive string is validated first, so an invalid one leaves
+    /// the current filters in place.
+    pub fn set_directives(&mut self, directives: &str) -> Result<(), String> {
+        EnvFilter::builder()
+            .parse(directives)
+            .map_err(|e| format!("invalid log filter '{directives}': {e}"))?;
+        for handle in &self.filters {
+            handle
+                .reload(EnvFilter::new(directives))
+                .map_err(|e| e.to_string())?;
+        }
+        self.directives = directives.to_string();
+        Ok(())
+    }
+
+    /// Change how many records are retained, dropping the oldest if the
+    /// buffer shrinks.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.ring.lock().set_capacity(capacity);
+    }
+
+    /// Handle to the captured records for readers outside the ECS.
+    pub fn tail(&self) -> LogTail {
+        LogTail {
+            ring: self.ring.clone(),
+        }
+    }
+
+    /// Records from `sent_seq` onwards, capped at one message's worth.
+    pub fn take_unsent(&mut self) -> Vec<LogRecord> {
+        let ring = self.ring.lock();
+        let records: Vec<LogRecord> = ring
+            .records
+            .iter()
+            .filter(|r| r.seq >= self.sent_seq)
+            .take(MAX_RECORDS_PER_MESSAGE)
+            .cloned()
+            .collect();
+        if let Some(last) = records.last() {
+            self.sent_seq = last.seq + 1;
+        }
+        records
+    }
+}
+
+/// Requests from the UI to change logging behaviour.
+#[derive(Message, Debug, Clone)]
+pub enum LogCommand {
+    /// Replace the filter directives, e.g. `info,crispen_gpu=debug`.
+    SetFilter { directives: String },
+    /// Start or stop streaming records to the UI. Starting resends the
+    /// retained backlog.
+    SetStreaming { enabled: bool },
+}
+
+/// Emitted after [`LogCommand`]s are applied, with the active directives
+/// and the error if a new filter was rejected.
+#[derive(Message, Debug, Clone)]
+pub struct LogFilterChanged {
+    pub directives: String,
+    pub error: Option<String>,
+}
+
+/// Registers the log command messages and their handler.
+///
+/// Requires [`LogPlugin`](bevy::log::LogPlugin) to be configured with
+/// [`custom_layer`] and [`fmt_layer`]; without them the commands are
+/// ignored.
+pub struct DiagnosticsPlugin;
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<LogCommand>()
+            .add_message::<LogFilterChanged>()
+            .add_systems(Update, handle_log_commands);
+    }
+}
+
+fn handle_log_commands(
+    mut commands: MessageReader<LogCommand>,
+    capture: Option<ResMut<LogCapture>>,
+    mut changed: MessageWriter<LogFilterChanged>,
+) {
+    let Some(mut capture) = capture else {
+        commands.clear();
+        return;
+    };
+    for command in commands.read() {
+        match command {
+            LogCommand::SetFilter { directives } => {
+                let error = capture.set_directives(directives).err();
+                match &error {
+                    Some(e) => tracing::warn!("{e}"),
+                    None => tracing::info!("log filter set to '{directives}'"),
+                }
+                changed.write(LogFilterChanged {
+                    directives: capture.directives().to_string(),
+                    error,
+                });
+            }
+            LogCommand::SetStreaming { enabled } => {
+                capture.streaming = *enabled;
+                if *enabled {
+                    capture.sent_seq = 0;
+                    changed.write(LogFilterChanged {
+                        directives: capture.directives().to_string(),
+                        error: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Global level for `LogPlugin`; the per-layer filters do the real work.
+pub const PLUGIN_LEVEL: bevy::log::Level = bevy::log::Level::TRACE;
+
+/// `LogPlugin::custom_layer` hook: builds the stderr and capture layers
+/// and inserts [`LogCapture`].
+pub fn custom_layer(app: &mut App) -> Option<BoxedLayer> {
+    let directives = std::env::var("RUST_LOG").unwrap_or_else(|_| format!("info,{DEFAULT_FILTER}"));
+    let parse = || EnvFilter::builder().parse_lossy(&directives);
+
+    let (console_filter, console_handle) = reload::Layer::new(parse());
+    let (capture_filter, capture_handle) = reload::Layer::new(parse());
+
+    let ring = Arc::new(Mutex::new(LogRing::default()));
+    let console = fmt::Layer::default()
+        .with_writer(std::io::stderr)
+        .with_filter(console_filter);
+    let capture = CaptureLayer { ring: ring.clone() }.with_filter(capture_filter);
+
+    app.insert_resource(LogCapture {
+        ring,
+        filters: vec![console_handle, capture_handle],
+        directives,
+        streaming: false,
+        sent_seq: 0,
+    });
+    Some(Box::new(vec![console.boxed(), capture.boxed()]))
+}
+
+/// `LogPlugin::fmt_layer` hook: disables the default formatter, which
+/// [`custom_layer`] replaces with a filterable one.
+pub fn fmt_layer(_app: &mut App) -> Option<BoxedFmtLayer> {
+    Some(Box::new(Identity::new()))
+}
+
+/// Tracing layer that appends every event it sees to the ring buffer.
+struct CaptureLayer {
+    ring: Arc<Mutex<LogRing>>,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        self.ring.lock().push(LogRecord {
+            seq: 0,
+            timestamp_ms,
+            level: metadata.level().to_string(),
+            // Events bridged from the `log` crate carry their real target as
+            // a field; the metadata target is just "log".
+            target: visitor
+                .log_target
+                .unwrap_or_else(|| metadata.target().to_string()),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    log_target: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message.push_str(value),
+            "log.target" => self.log_target = Some(value.to_string()),
+            name if name.starts_with("log.") => {}
+            name => self.fields.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{value:?}");
+            }
+            name if name.starts_with("log.") => {}
+            name => self.fields.push((name.to_string(), format!("{value:?}"))),
+        }
+    }
+}
@@ -4,9 +4,38 @@
 //! dev server.  In release mode, HTML is loaded via a `data:` URL (the built
 //! Svelte assets will be embedded later).
 
+use std::path::{Path, PathBuf};
+
 /// Vite dev server port (must match `ui/vite.config.ts`).
 pub const VITE_DEV_PORT: u16 = 5174;
 
+/// Locate the built Svelte UI (dist/ui/index.html) relative to the executable
+/// or the project root.
+pub fn find_built_ui() -> Option<PathBuf> {
+    // 1. Next to the executable (for packaged builds).
+    if let Ok(exe) = std::env::current_exe()
+        && let Some(dir) = exe.parent()
+    {
+        let candidate = dir.join("ui/index.html");
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+
+    // 2. Relative to the working directory (development layout).
+    let candidates = [
+        Path::new("crates/crispen-demo/dist/ui/index.html"),
+        Path::new("dist/ui/index.html"),
+    ];
+    for candidate in &candidates {
+        if candidate.exists() {
+            return std::fs::canonicalize(candidate).ok();
+        }
+    }
+
+    None
+}
+
 /// Get HTML content for the webview.
 pub fn get_html(dev_mode: bool, ws_port: u16) -> String {
     if dev_mode {
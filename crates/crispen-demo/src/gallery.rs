@@ -0,0 +1,303 @@
+//! Gallery of reference stills, each kept with the grade that made it.
+//!
+//! Stills are grabbed through [`ColorGradingCommand::GrabStill`], which
+//! `crispen-bevy` renders with the full chain; they land in the
+//! [`Gallery`] folder under the config dir as float EXRs with a
+//! `.grade.json` sidecar. Exporting writes a still as EXR or 10-bit DPX,
+//! picked by extension, with its sidecar beside it; importing such a pair
+//! brings both the image and its grade back. Every change is reported with
+//! [`GalleryChanged`], which also lists the grade fields each still would
+//! change, refreshed as the grade is edited. The gallery doubles as a
+//! version browser: after each change, and when a new image loads, the
+//! current frame is rendered under the current grade and under every
+//! still's grade through `PreviewGrades`.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use crispen_bevy::events::{
+    ColorGradingCommand, ExportCompletedEvent, GradeSavedEvent, ImageLoadedEvent,
+    ParamsUpdatedEvent, StillGrabbedEvent,
+};
+use crispen_bevy::resources::GradingState;
+use crispen_core::project::gallery::{
+    Gallery, GalleryEntry, GalleryError, StillFormat, StillSidecar,
+};
+use crispen_core::transform::params::GradingParams;
+
+use crate::config::{UserConfig, config_dir};
+use crate::i18n::tr_args;
+use crate::stills::{load_still, save_still};
+
+/// The gallery the app grabs stills into, with its stills as last
+/// reported.
+#[derive(Resource, Debug)]
+pub struct GalleryStore {
+    pub gallery: Gallery,
+    pub stills: Vec<StillStatus>,
+    /// Labels of the stills in the last `PreviewGrades` request, in order.
+    pub previewed: Vec<String>,
+}
+
+impl Default for GalleryStore {
+    fn default() -> Self {
+        let dir = config_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("gallery");
+        Self {
+            gallery: Gallery::new(dir),
+            stills: Vec::new(),
+            previewed: Vec::new(),
+        }
+    }
+}
+
+/// A still and how its grade differs from the current one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StillStatus {
+    pub entry: GalleryEntry,
+    /// Grade fields applying the still would change; empty when it matches
+    /// the current grade.
+    pub changes: Vec<String>,
+}
+
+impl StillStatus {
+    fn new(entry: GalleryEntry, current: &GradingParams) -> Self {
+        let changes = entry
+            .sidecar
+            .changes_from(current)
+            .into_iter()
+            .map(|change| change.field)
+            .collect();
+        Self { entry, changes }
+    }
+}
+
+/// Requests from the UI's gallery panel.
+#[derive(Message, Debug, Clone)]
+pub enum GalleryCommand {
+    /// Report the stills and render their previews.
+    Query,
+    /// Apply a still's grade, keeping the current color management.
+    Apply { label: String },
+    /// Delete a still and its sidecar.
+    Delete { label: String },
+    /// Write a still as EXR or DPX, by extension, with its sidecar.
+    Export { label: String, path: PathBuf },
+    /// Copy an exported still and its sidecar into the gallery.
+    Import { path: PathBuf },
+}
+
+/// Emitted after gallery changes and queries, with every still and the
+/// reason if the last command failed.
+#[derive(Message, Debug, Clone)]
+pub struct GalleryChanged {
+    pub stills: Vec<StillStatus>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum StillError {
+    #[error(transparent)]
+    Gallery(#[from] GalleryError),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+}
+
+/// Stores grabbed stills and handles [`GalleryCommand`]s.
+pub struct GalleryPlugin;
+
+impl Plugin for GalleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GalleryStore>()
+            .add_message::<GalleryCommand>()
+            .add_message::<GalleryChanged>()
+            .add_systems(
+                Update,
+                (
+                    store_grabbed_stills,
+                    handle_gallery_commands.after(store_grabbed_stills),
+                    recheck_changes.after(handle_gallery_commands),
+                    preview_on_image_load,
+                ),
+            );
+    }
+}
+
+fn store_grabbed_stills(
+    mut grabbed: MessageReader<StillGrabbedEvent>,
+    mut store: ResMut<GalleryStore>,
+    state: Res<GradingState>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+    mut saved: MessageWriter<GradeSavedEvent>,
+    mut changed: MessageWriter<GalleryChanged>,
+) {
+    for still in grabbed.read() {
+        let result = save_grabbed(&store.gallery, still);
+        if let Ok(path) = &result {
+            tracing::info!("Gallery still {}: {}", still.label, path.display());
+            saved.write(GradeSavedEvent {
+                path: path.display().to_string(),
+                kind: "still",
+            });
+        }
+        changed.write(report(&mut store, &state.params, result.err()));
+        request_previews(&mut store, &mut grading);
+    }
+}
+
+fn save_grabbed(gallery: &Gallery, still: &StillGrabbedEvent) -> Result<PathBuf, StillError> {
+    let (label, path) = gallery.reserve(&still.label, StillFormat::Exr)?;
+    save_still(&still.image, &path, &label)?;
+    StillSidecar::new(label, still.params.clone(), still.source.clone()).write_beside(&path)?;
+    Ok(path)
+}
+
+fn handle_gallery_commands(
+    mut commands: MessageReader<GalleryCommand>,
+    mut store: ResMut<GalleryStore>,
+    state: Res<GradingState>,
+    config: Res<UserConfig>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+    mut exported: MessageWriter<ExportCompletedEvent>,
+    mut changed: MessageWriter<GalleryChanged>,
+) {
+    for command in commands.read() {
+        let gallery = &store.gallery;
+        let result = match command {
+            GalleryCommand::Query => Ok(()),
+            GalleryCommand::Apply { label } => gallery
+                .get(label)
+                .map(|entry| {
+                    let mut params = entry.sidecar.grade;
+                    // The footage's encoding belongs to the shot, not the
+                    // reference, as for grade looks.
+                    params.color_management = state.params.color_management.clone();
+                    grading.write(ColorGradingCommand::SetParams { params });
+                })
+                .map_err(StillError::from),
+            GalleryCommand::Delete { label } => gallery.remove(label).map_err(StillError::from),
+            GalleryCommand::Export { label, path } => {
+                export(gallery, label, path, &config.scratch_dir()).map(|()| {
+                    exported.write(ExportCompletedEvent {
+                        path: path.display().to_string(),
+                        kind: "still",
+                    });
+                })
+            }
+            GalleryCommand::Import { path } => gallery
+                .import(path)
+                .map(|entry| tracing::info!("Imported still {}", entry.sidecar.label))
+                .map_err(StillError::from),
+        };
+        changed.write(report(&mut store, &state.params, result.err()));
+        request_previews(&mut store, &mut grading);
+    }
+}
+
+/// Re-check the stills against each new grade, reporting them again when
+/// a still starts or stops matching or changes different fields.
+fn recheck_changes(
+    mut updates: MessageReader<ParamsUpdatedEvent>,
+    mut store: ResMut<GalleryStore>,
+    mut changed: MessageWriter<GalleryChanged>,
+) {
+    let Some(update) = updates.read().last() else {
+        return;
+    };
+    let stills: Vec<StillStatus> = store
+        .stills
+        .iter()
+        .map(|still| StillStatus::new(still.entry.clone(), &update.params))
+        .collect();
+    if stills != store.stills {
+        store.stills = stills.clone();
+        changed.write(GalleryChanged {
+            stills,
+            error: None,
+        });
+    }
+}
+
+/// Render the previews again on the new frame.
+fn preview_on_image_load(
+    mut loaded: MessageReader<ImageLoadedEvent>,
+    mut store: ResMut<GalleryStore>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+) {
+    if loaded.read().last().is_some() {
+        request_previews(&mut store, &mut grading);
+    }
+}
+
+/// Ask `crispen-bevy` for the current frame under every still's grade.
+fn request_previews(store: &mut GalleryStore, grading: &mut MessageWriter<ColorGradingCommand>) {
+    if store.stills.is_empty() {
+        return;
+    }
+    store.previewed = store
+        .stills
+        .iter()
+        .map(|still| still.entry.sidecar.label.clone())
+        .collect();
+    let grades = store
+        .stills
+        .iter()
+        .map(|still| still.entry.sidecar.grade.clone())
+        .collect();
+    grading.write(ColorGradingCommand::PreviewGrades { grades });
+}
+
+/// Write the still labelled `label` to `path`, converting it when the
+/// format differs from the stored one, and its sidecar beside it.
+///
+/// A converted still is written in `scratch` and then copied into place,
+/// so a failed conversion never leaves a partial file at `path`.
+fn export(gallery: &Gallery, label: &str, path: &Path, scratch: &Path) -> Result<(), StillError> {
+    let GalleryEntry {
+        mut sidecar,
+        image_path,
+    } = gallery.get(label)?;
+    let format = StillFormat::from_path(path).ok_or_else(|| GalleryError::Format(path.into()))?;
+    if StillFormat::from_path(&image_path) == Some(format) {
+        std::fs::copy(&image_path, path).map_err(GalleryError::from)?;
+    } else {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| GalleryError::Format(path.into()))?;
+        let staged = scratch.join(file_name);
+        std::fs::create_dir_all(scratch).map_err(GalleryError::from)?;
+        save_still(&load_still(&image_path)?, &staged, label)?;
+        let copied = std::fs::copy(&staged, path);
+        std::fs::remove_file(&staged).ok();
+        copied.map_err(GalleryError::from)?;
+    }
+    sidecar.write_beside(path)?;
+    tracing::info!("Exported still {label} to {}", path.display());
+    Ok(())
+}
+
+/// The gallery's stills compared with `current`, with `error` localized
+/// for the UI. Updates the store's stills.
+fn report(
+    store: &mut GalleryStore,
+    current: &GradingParams,
+    error: Option<StillError>,
+) -> GalleryChanged {
+    let (entries, list_error) = match store.gallery.entries() {
+        Ok(entries) => (entries, None),
+        Err(e) => (Vec::new(), Some(StillError::from(e))),
+    };
+    store.stills = entries
+        .into_iter()
+        .map(|entry| StillStatus::new(entry, current))
+        .collect();
+    let error = error.or(list_error).map(|e| {
+        tracing::warn!("gallery: {e}");
+        tr_args("error-gallery", &[("error", e.to_string().into())])
+    });
+    GalleryChanged {
+        stills: store.stills.clone(),
+        error,
+    }
+}
@@ -0,0 +1,72 @@
+//! Translated strings for the native UI and backend messages.
+//!
+//! Catalogs live in `locales/<tag>/crispen.ftl` and are embedded at build
+//! time. The locale comes from `CRISPEN_LOCALE` if set, else the system
+//! locale, and is fixed for the life of the process: native labels are
+//! spawned once, so switching language needs a restart.
+//!
+//! `en-US` is the source catalog and always the last fallback, so a string
+//! missing from a translation shows in English rather than as its id.
+
+use std::sync::OnceLock;
+
+use crispen_core::i18n::{Arg, Catalog, Localizer, negotiate};
+use tracing::{error, info};
+
+/// Environment variable overriding the detected locale, e.g. `de-DE`.
+pub const LOCALE_ENV: &str = "CRISPEN_LOCALE";
+
+const SOURCE_LOCALE: &str = "en-US";
+
+/// Embedded catalogs by BCP 47 tag.
+const CATALOGS: &[(&str, &str)] = &[
+    ("en-US", include_str!("../locales/en-US/crispen.ftl")),
+    ("de-DE", include_str!("../locales/de-DE/crispen.ftl")),
+];
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Tag of the locale in use.
+pub fn locale() -> &'static str {
+    localizer().locale()
+}
+
+/// Translate a message without arguments.
+pub fn tr(id: &str) -> String {
+    localizer().format(id, &[])
+}
+
+/// Translate a message with `{ $name }` arguments.
+pub fn tr_args(id: &str, args: &[(&str, Arg<'_>)]) -> String {
+    localizer().format(id, args)
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| {
+        let requested = std::env::var(LOCALE_ENV)
+            .ok()
+            .filter(|tag| !tag.trim().is_empty())
+            .or_else(sys_locale::get_locale)
+            .unwrap_or_else(|| SOURCE_LOCALE.to_string());
+        let available: Vec<&str> = CATALOGS.iter().map(|(tag, _)| *tag).collect();
+        let locale = negotiate(&requested, &available).unwrap_or_else(|| {
+            info!("no translation for locale '{requested}', using {SOURCE_LOCALE}");
+            SOURCE_LOCALE
+        });
+
+        let chain = if locale == SOURCE_LOCALE {
+            vec![SOURCE_LOCALE]
+        } else {
+            vec![locale, SOURCE_LOCALE]
+        };
+        info!("UI locale: {locale}");
+        Localizer::new(chain.into_iter().filter_map(load_catalog).collect())
+    })
+}
+
+fn load_catalog(locale: &str) -> Option<Catalog> {
+    let (_, source) = CATALOGS.iter().find(|(tag, _)| *tag == locale)?;
+    Catalog::parse(locale, source)
+        .inspect_err(|e| error!("invalid {locale} catalog: {e}"))
+        .ok()
+}
@@ -0,0 +1,79 @@
+//! Window cursor driven by the page under the pointer.
+
+use bevy::prelude::*;
+use bevy::window::{CursorIcon, CursorOptions, PrimaryWindow, SystemCursorIcon};
+use crispen_frontend_core::{CompositeBackend, CursorIcon as FcCursorIcon};
+
+use super::MouseState;
+use crate::cef_bridge::CefFrontendResource;
+
+/// Show the cursor the CEF webview under the pointer asks for (text beam
+/// over inputs, resize arrows on dockview splitters, …). Only touches the
+/// window when it changes.
+pub fn apply_cef_cursor(
+    webview: Option<NonSendMut<CefFrontendResource>>,
+    mouse: Res<MouseState>,
+    mut window: Query<(Entity, &mut CursorOptions), With<PrimaryWindow>>,
+    mut commands: Commands,
+    mut applied: Local<Option<FcCursorIcon>>,
+) {
+    let Some(mut wv) = webview else { return };
+    let Ok((entity, mut options)) = window.single_mut() else {
+        return;
+    };
+
+    let cursor = wv.webview(mouse.target.as_deref()).cursor();
+    if *applied == Some(cursor) {
+        return;
+    }
+    *applied = Some(cursor);
+
+    let hidden = cursor == FcCursorIcon::Hidden;
+    if options.visible == hidden {
+        options.visible = !hidden;
+    }
+    if !hidden {
+        commands
+            .entity(entity)
+            .insert(CursorIcon::System(system_cursor(cursor)));
+    }
+}
+
+fn system_cursor(cursor: FcCursorIcon) -> SystemCursorIcon {
+    match cursor {
+        FcCursorIcon::Default | FcCursorIcon::Hidden => SystemCursorIcon::Default,
+        FcCursorIcon::Pointer => SystemCursorIcon::Pointer,
+        FcCursorIcon::Text => SystemCursorIcon::Text,
+        FcCursorIcon::VerticalText => SystemCursorIcon::VerticalText,
+        FcCursorIcon::Crosshair => SystemCursorIcon::Crosshair,
+        FcCursorIcon::Move => SystemCursorIcon::Move,
+        FcCursorIcon::Grab => SystemCursorIcon::Grab,
+        FcCursorIcon::Grabbing => SystemCursorIcon::Grabbing,
+        FcCursorIcon::AllScroll => SystemCursorIcon::AllScroll,
+        FcCursorIcon::NotAllowed => SystemCursorIcon::NotAllowed,
+        FcCursorIcon::NoDrop => SystemCursorIcon::NoDrop,
+        FcCursorIcon::Copy => SystemCursorIcon::Copy,
+        FcCursorIcon::Alias => SystemCursorIcon::Alias,
+        FcCursorIcon::Wait => SystemCursorIcon::Wait,
+        FcCursorIcon::Progress => SystemCursorIcon::Progress,
+        FcCursorIcon::Help => SystemCursorIcon::Help,
+        FcCursorIcon::ContextMenu => SystemCursorIcon::ContextMenu,
+        FcCursorIcon::Cell => SystemCursorIcon::Cell,
+        FcCursorIcon::EResize => SystemCursorIcon::EResize,
+        FcCursorIcon::NResize => SystemCursorIcon::NResize,
+        FcCursorIcon::NeResize => SystemCursorIcon::NeResize,
+        FcCursorIcon::NwResize => SystemCursorIcon::NwResize,
+        FcCursorIcon::SResize => SystemCursorIcon::SResize,
+        FcCursorIcon::SeResize => SystemCursorIcon::SeResize,
+        FcCursorIcon::SwResize => SystemCursorIcon::SwResize,
+        FcCursorIcon::WResize => SystemCursorIcon::WResize,
+        FcCursorIcon::EwResize => SystemCursorIcon::EwResize,
+        FcCursorIcon::NsResize => SystemCursorIcon::NsResize,
+        FcCursorIcon::NeswResize => SystemCursorIcon::NeswResize,
+        FcCursorIcon::NwseResize => SystemCursorIcon::NwseResize,
+        FcCursorIcon::ColResize => SystemCursorIcon::ColResize,
+        FcCursorIcon::RowResize => SystemCursorIcon::RowResize,
+        FcCursorIcon::ZoomIn => SystemCursorIcon::ZoomIn,
+        FcCursorIcon::ZoomOut => SystemCursorIcon::ZoomOut,
+    }
+}
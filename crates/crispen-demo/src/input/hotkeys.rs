@@ -3,18 +3,17 @@
 use bevy::prelude::*;
 
 use crate::cef_bridge::CefFrontendResource;
+use crate::keymap::{Action, KeyBindings};
 
-/// Toggle Chrome DevTools with Ctrl+Shift+I.
+/// Open Chrome DevTools on the dev tools shortcut (Ctrl+Shift+I by default).
 pub fn handle_devtools_hotkey(
     keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     webview: Option<NonSendMut<CefFrontendResource>>,
 ) {
     let Some(wv) = webview else { return };
 
-    if keys.just_pressed(KeyCode::KeyI)
-        && (keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight))
-        && (keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight))
-    {
+    if bindings.just_pressed(Action::DevTools, &keys) {
         wv.backend.show_dev_tools();
     }
 }
@@ -1,16 +1,34 @@
-//! Keyboard event forwarding to CEF.
+//! Keyboard and IME forwarding to CEF.
 
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
-use crispen_frontend_core::{CompositeBackend, KeyboardEvent, Modifiers};
+use bevy::window::{Ime, PrimaryWindow};
+use crispen_frontend_core::{CompositeBackend, ImeEvent, KeyboardEvent, Modifiers};
 
 use crate::cef_bridge::CefFrontendResource;
 
-/// Forward key press / release events to CEF.
+/// Forward key presses, releases and repeats, and IME compositions, to
+/// the focused CEF webview. IME is switched on only while a text field in
+/// that page has focus.
 pub fn forward_keyboard(
+    mut key_events: MessageReader<KeyboardInput>,
+    mut ime_events: MessageReader<Ime>,
     keys: Res<ButtonInput<KeyCode>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
     webview: Option<NonSendMut<CefFrontendResource>>,
+    mut composing: Local<bool>,
 ) {
-    let Some(mut wv) = webview else { return };
+    let Some(mut wv) = webview else {
+        key_events.clear();
+        ime_events.clear();
+        return;
+    };
+
+    // Floating webviews report their caret relative to their own corner.
+    let origin = wv.floating.focused_mut().map_or(Vec2::ZERO, |view| {
+        Vec2::new(view.rect.x as f32, view.rect.y as f32)
+    });
+    let backend = wv.focused();
 
     let modifiers = Modifiers {
         shift: keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight),
@@ -19,79 +37,80 @@ pub fn forward_keyboard(
         meta: keys.pressed(KeyCode::SuperLeft) || keys.pressed(KeyCode::SuperRight),
     };
 
-    for code in keys.get_just_pressed() {
-        if let Some(key) = keycode_to_string(*code) {
-            wv.backend.send_keyboard_event(KeyboardEvent {
-                key,
-                pressed: true,
-                modifiers: modifiers.clone(),
-            });
+    let mut typed = false;
+    for ev in key_events.read() {
+        let pressed = ev.state.is_pressed();
+        typed |= pressed && ev.text.is_some();
+        backend.send_keyboard_event(KeyboardEvent {
+            key: dom_key(&ev.logical_key),
+            code: dom_code(ev.key_code),
+            text: ev.text.as_ref().map(ToString::to_string),
+            pressed,
+            repeat: ev.repeat,
+            modifiers: modifiers.clone(),
+        });
+    }
+
+    for ev in ime_events.read() {
+        match ev {
+            Ime::Preedit { value, cursor, .. } => {
+                *composing |= !value.is_empty();
+                backend.send_ime_event(ImeEvent::Preedit {
+                    text: value.clone(),
+                    cursor: *cursor,
+                });
+            }
+            // Some platforms pass plain typing through the IME too; only
+            // commit what the key events above did not already type.
+            Ime::Commit { value, .. } => {
+                if *composing || !typed {
+                    backend.send_ime_event(ImeEvent::Commit {
+                        text: value.clone(),
+                    });
+                }
+                *composing = false;
+            }
+            Ime::Disabled { .. } => *composing = false,
+            Ime::Enabled { .. } => {}
         }
     }
 
-    for code in keys.get_just_released() {
-        if let Some(key) = keycode_to_string(*code) {
-            wv.backend.send_keyboard_event(KeyboardEvent {
-                key,
-                pressed: false,
-                modifiers: modifiers.clone(),
-            });
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let active = backend.text_input_active();
+    if window.ime_enabled != active {
+        window.ime_enabled = active;
+    }
+    if let Some((x, y)) = backend.ime_caret() {
+        // The caret is in physical pixels; the window wants logical ones.
+        let position = (origin + Vec2::new(x, y)) / window.scale_factor();
+        if window.ime_position != position {
+            window.ime_position = position;
         }
     }
 }
 
-/// Map Bevy `KeyCode` to a web-compatible key string.
-fn keycode_to_string(code: KeyCode) -> Option<String> {
-    let c = match code {
-        KeyCode::KeyA => 'a',
-        KeyCode::KeyB => 'b',
-        KeyCode::KeyC => 'c',
-        KeyCode::KeyD => 'd',
-        KeyCode::KeyE => 'e',
-        KeyCode::KeyF => 'f',
-        KeyCode::KeyG => 'g',
-        KeyCode::KeyH => 'h',
-        KeyCode::KeyI => 'i',
-        KeyCode::KeyJ => 'j',
-        KeyCode::KeyK => 'k',
-        KeyCode::KeyL => 'l',
-        KeyCode::KeyM => 'm',
-        KeyCode::KeyN => 'n',
-        KeyCode::KeyO => 'o',
-        KeyCode::KeyP => 'p',
-        KeyCode::KeyQ => 'q',
-        KeyCode::KeyR => 'r',
-        KeyCode::KeyS => 's',
-        KeyCode::KeyT => 't',
-        KeyCode::KeyU => 'u',
-        KeyCode::KeyV => 'v',
-        KeyCode::KeyW => 'w',
-        KeyCode::KeyX => 'x',
-        KeyCode::KeyY => 'y',
-        KeyCode::KeyZ => 'z',
-        KeyCode::Digit0 => '0',
-        KeyCode::Digit1 => '1',
-        KeyCode::Digit2 => '2',
-        KeyCode::Digit3 => '3',
-        KeyCode::Digit4 => '4',
-        KeyCode::Digit5 => '5',
-        KeyCode::Digit6 => '6',
-        KeyCode::Digit7 => '7',
-        KeyCode::Digit8 => '8',
-        KeyCode::Digit9 => '9',
-        KeyCode::Space => ' ',
-        KeyCode::Enter => return Some("Enter".into()),
-        KeyCode::Escape => return Some("Escape".into()),
-        KeyCode::Backspace => return Some("Backspace".into()),
-        KeyCode::Tab => return Some("Tab".into()),
-        KeyCode::ArrowUp => return Some("ArrowUp".into()),
-        KeyCode::ArrowDown => return Some("ArrowDown".into()),
-        KeyCode::ArrowLeft => return Some("ArrowLeft".into()),
-        KeyCode::ArrowRight => return Some("ArrowRight".into()),
-        KeyCode::Delete => return Some("Delete".into()),
-        KeyCode::Home => return Some("Home".into()),
-        KeyCode::End => return Some("End".into()),
-        _ => return None,
-    };
-    Some(c.to_string())
+/// DOM `KeyboardEvent.code` for a physical key. Bevy's `KeyCode` variants
+/// are named after the same W3C spec, apart from the Super keys.
+fn dom_code(code: KeyCode) -> String {
+    match code {
+        KeyCode::SuperLeft => "MetaLeft".into(),
+        KeyCode::SuperRight => "MetaRight".into(),
+        KeyCode::Unidentified(_) => String::new(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// DOM `KeyboardEvent.key` for a logical key. Named keys share the W3C
+/// names, apart from Space and Super.
+fn dom_key(key: &Key) -> String {
+    match key {
+        Key::Character(text) => text.to_string(),
+        Key::Space => " ".into(),
+        Key::Super | Key::Meta => "Meta".into(),
+        Key::Dead(_) => "Dead".into(),
+        Key::Unidentified(_) => "Unidentified".into(),
+        other => format!("{other:?}"),
+    }
 }
@@ -1,20 +1,26 @@
-//! Input forwarding — routes Bevy mouse/keyboard events to the CEF backend.
+//! Input forwarding — routes Bevy mouse/touch/keyboard events to the CEF backend
+//! and shows the cursor it asks for.
 
+mod cursor;
 mod hotkeys;
 mod keyboard;
 mod mouse;
+mod touch;
 
 use bevy::prelude::*;
 
 /// Tracks the current mouse position in both window and webview coordinates.
 #[derive(Resource, Default)]
 pub struct MouseState {
-    /// Window-space X (logical pixels).
+    /// Window-space X (physical pixels).
     pub window_x: f32,
-    /// Window-space Y (logical pixels).
+    /// Window-space Y (physical pixels).
     pub window_y: f32,
     /// Last time a mouse move was forwarded to CEF.
     pub last_move_sent: Option<std::time::Instant>,
+    /// Floating webview under the pointer, or `None` for the main one.
+    /// Kept while a button is held so drags stay with their webview.
+    pub target: Option<String>,
 }
 
 /// Plugin that registers all input-forwarding systems.
@@ -28,8 +34,10 @@ impl Plugin for InputForwardingPlugin {
                 mouse::track_mouse_position,
                 mouse::forward_mouse_buttons,
                 mouse::forward_mouse_scroll,
+                touch::forward_touches,
                 keyboard::forward_keyboard,
                 hotkeys::handle_devtools_hotkey,
+                cursor::apply_cef_cursor,
             ),
         );
     }
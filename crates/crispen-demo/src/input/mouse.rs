@@ -11,10 +11,13 @@ use crate::cef_bridge::CefFrontendResource;
 
 const THROTTLE: Duration = Duration::from_millis(16); // ~60 fps
 
-/// Track the cursor and forward move events (throttled).
+/// Track the cursor and forward move events (throttled) to the webview
+/// under it.
 pub fn track_mouse_position(
     mut state: ResMut<MouseState>,
     mut cursor: MessageReader<CursorMoved>,
+    windows: Query<&Window>,
+    buttons: Res<ButtonInput<bevy::input::mouse::MouseButton>>,
     webview: Option<NonSendMut<CefFrontendResource>>,
 ) {
     let Some(mut wv) = webview else {
@@ -24,13 +27,18 @@ pub fn track_mouse_position(
 
     let mut moved = false;
     for ev in cursor.read() {
-        state.window_x = ev.position.x;
-        state.window_y = ev.position.y;
+        // Cursor events are logical; the backend takes physical pixels.
+        let scale = windows.get(ev.window).map_or(1.0, Window::scale_factor);
+        state.window_x = ev.position.x * scale;
+        state.window_y = ev.position.y * scale;
         moved = true;
     }
     if !moved {
         return;
     }
+    if buttons.get_pressed().next().is_none() {
+        state.target = wv.webview_at(state.window_x, state.window_y);
+    }
 
     let now = Instant::now();
     if let Some(last) = state.last_move_sent {
@@ -39,14 +47,14 @@ pub fn track_mouse_position(
         }
     }
 
-    wv.backend.send_mouse_event(MouseEvent::Move {
-        x: state.window_x,
-        y: state.window_y,
-    });
+    let (backend, x, y) =
+        wv.webview_with_position(state.target.as_deref(), state.window_x, state.window_y);
+    backend.send_mouse_event(MouseEvent::Move { x, y });
     state.last_move_sent = Some(now);
 }
 
-/// Forward mouse button presses/releases.
+/// Forward mouse button presses/releases. A press also gives its webview
+/// keyboard focus.
 pub fn forward_mouse_buttons(
     mut events: MessageReader<MouseButtonInput>,
     mouse: Res<MouseState>,
@@ -59,12 +67,17 @@ pub fn forward_mouse_buttons(
 
     for ev in events.read() {
         let Some(btn) = convert_button(ev.button) else { continue };
+        if ev.state.is_pressed() {
+            wv.floating.focus(mouse.target.as_deref());
+        }
+        let (backend, x, y) =
+            wv.webview_with_position(mouse.target.as_deref(), mouse.window_x, mouse.window_y);
         let me = if ev.state.is_pressed() {
-            MouseEvent::ButtonDown { button: btn, x: mouse.window_x, y: mouse.window_y }
+            MouseEvent::ButtonDown { button: btn, x, y }
         } else {
-            MouseEvent::ButtonUp { button: btn, x: mouse.window_x, y: mouse.window_y }
+            MouseEvent::ButtonUp { button: btn, x, y }
         };
-        wv.backend.send_mouse_event(me);
+        backend.send_mouse_event(me);
     }
 }
 
@@ -84,11 +97,13 @@ pub fn forward_mouse_scroll(
             bevy::input::mouse::MouseScrollUnit::Line => (ev.x * 40.0, ev.y * 40.0),
             bevy::input::mouse::MouseScrollUnit::Pixel => (ev.x, ev.y),
         };
-        wv.backend.send_mouse_event(MouseEvent::Scroll {
+        let (backend, x, y) =
+            wv.webview_with_position(mouse.target.as_deref(), mouse.window_x, mouse.window_y);
+        backend.send_mouse_event(MouseEvent::Scroll {
             delta_x: dx,
             delta_y: -dy, // invert Y for web conventions
-            x: mouse.window_x,
-            y: mouse.window_y,
+            x,
+            y,
         });
     }
 }
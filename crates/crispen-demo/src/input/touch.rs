@@ -0,0 +1,54 @@
+//! Touch and pen forwarding to CEF.
+
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+use bevy::prelude::*;
+use crispen_frontend_core::{
+    CompositeBackend, PointerKind, TouchEvent, TouchPhase as FcTouchPhase,
+};
+
+use crate::cef_bridge::CefFrontendResource;
+
+/// Forward touch and pen contacts. Unlike mouse moves these are not
+/// throttled: Chromium's gesture recognition needs every sample.
+pub fn forward_touches(
+    mut events: MessageReader<TouchInput>,
+    windows: Query<&Window>,
+    webview: Option<NonSendMut<CefFrontendResource>>,
+) {
+    let Some(mut wv) = webview else {
+        events.clear();
+        return;
+    };
+
+    for ev in events.read() {
+        // Touch positions are logical; the backend takes physical pixels.
+        let scale = windows.get(ev.window).map_or(1.0, Window::scale_factor);
+        wv.backend.send_touch_event(TouchEvent {
+            id: ev.id,
+            phase: convert_phase(ev.phase),
+            pointer: pointer_kind(ev.force),
+            x: ev.position.x * scale,
+            y: ev.position.y * scale,
+        });
+    }
+}
+
+fn convert_phase(phase: TouchPhase) -> FcTouchPhase {
+    match phase {
+        TouchPhase::Started => FcTouchPhase::Started,
+        TouchPhase::Moved => FcTouchPhase::Moved,
+        TouchPhase::Ended => FcTouchPhase::Ended,
+        TouchPhase::Canceled => FcTouchPhase::Cancelled,
+    }
+}
+
+/// winit reports pens as touches; a stylus altitude is the only tell.
+fn pointer_kind(force: Option<ForceTouch>) -> PointerKind {
+    match force {
+        Some(ForceTouch::Calibrated {
+            altitude_angle: Some(_),
+            ..
+        }) => PointerKind::Pen,
+        _ => PointerKind::Touch,
+    }
+}
@@ -5,10 +5,34 @@
 //! pattern from Pentimento for consistent serialization.
 
 use base64::Engine;
+use image::ImageEncoder;
 use serde::{Deserialize, Serialize};
 
-use crispen_core::scopes::{CieData, HistogramData, VectorscopeData, WaveformData};
-use crispen_core::transform::params::GradingParams;
+use crispen_bevy::events::{Bracket, GradePreviewsReadyEvent, LookPreviewsReadyEvent};
+use crispen_core::framing::{Blanking, FramingGuides};
+use crispen_core::image::BitDepth;
+use crispen_core::patterns::TestPattern;
+use crispen_core::scopes::{
+    CieData, HistogramData, ScopeAccumulation, ScopeDisplayOptions, ScopeQuality, VectorscopeData,
+    WaveformData,
+};
+use crispen_core::transform::compare::DeltaEReport;
+use crispen_core::transform::inspect::PipelineStage;
+use crispen_core::transform::look_preview::LookKind;
+use crispen_core::transform::lut_analysis::LutAnalysis;
+use crispen_core::transform::lut_domain::LutDomain;
+use crispen_core::transform::params::{ColorSpaceId, GradingParams};
+use crispen_core::transform::parity::ParityReport;
+use crispen_core::transform::presets::{Tool, ToolPreset};
+use crispen_core::transform::shader_export::ShaderLanguage;
+use crispen_core::transform::shaper::ShaperTransfer;
+
+use crate::config::UserConfig;
+use crate::crash::CrashRecovery;
+use crate::diagnostics::LogRecord;
+use crate::gallery::StillStatus;
+use crate::probe::ProbeReport;
+use crate::settings::{FocusAssist, Settings};
 
 /// Messages from the Bevy backend to the Svelte UI.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +42,8 @@ pub enum BevyToUi {
     Initialize {
         /// Current grading parameters.
         params: GradingParams,
+        /// BCP 47 tag of the backend's UI language, e.g. `de-DE`.
+        locale: String,
     },
 
     /// Grading parameters were updated (backend → UI sync).
@@ -26,6 +52,13 @@ pub enum BevyToUi {
         params: GradingParams,
     },
 
+    /// The ordered stages the current grade runs through, sent after every
+    /// `ParamsUpdated` and when the input LUT or LUT size changes.
+    Pipeline {
+        /// Stages in chain order, from source to display.
+        stages: Vec<PipelineStage>,
+    },
+
     /// Scope analysis data with binary-encoded arrays for fast serialization.
     ///
     /// Large `u32` density arrays are encoded as base64 little-endian binary
@@ -50,6 +83,153 @@ pub enum BevyToUi {
         bit_depth: String,
     },
 
+    /// A display probe check finished.
+    ProbeReport {
+        /// Per-patch readings and ΔE summary.
+        report: ProbeReport,
+    },
+
+    /// A GPU vs CPU parity check finished.
+    ParityReport {
+        /// Channel error and ΔE between the GPU and CPU grades.
+        report: ParityReport,
+    },
+
+    /// A ΔE comparison finished; its heatmap is shown over the viewer.
+    DeltaEReport {
+        /// ΔE2000 statistics between the graded output and the reference.
+        report: DeltaEReport,
+    },
+
+    /// The look browser's thumbnails were rendered.
+    LookPreviews {
+        /// Thumbnail size in pixels, shared by every look.
+        width: u32,
+        height: u32,
+        /// Looks in browser order.
+        looks: Vec<LookThumbnail>,
+    },
+
+    /// An imported LUT was loaded and checked.
+    LutAnalysis {
+        /// File path of the LUT.
+        path: String,
+        /// Slot the LUT was loaded into.
+        slot: String,
+        /// Declared or guessed input/output encodings.
+        domain: LutDomain,
+        /// Monotonicity, smoothness, and invertibility results.
+        analysis: LutAnalysis,
+    },
+
+    /// A script started with `RunScript` finished.
+    ScriptFinished {
+        /// Script file path.
+        path: String,
+        /// Lines the script printed.
+        output: Vec<String>,
+        /// Error message if the script failed.
+        error: Option<String>,
+    },
+
+    /// A watched LUT file changed on disk and was reloaded.
+    LutReloaded {
+        /// File path of the LUT.
+        path: String,
+        /// Slot the LUT is loaded into.
+        slot: String,
+        /// Read error if the new file could not be loaded.
+        error: Option<String>,
+    },
+
+    /// Newly captured log records, oldest first (while streaming).
+    LogRecords {
+        /// Records in capture order.
+        records: Vec<LogRecord>,
+    },
+
+    /// Active log filter directives, sent when they change or streaming
+    /// starts.
+    LogFilter {
+        /// `EnvFilter` directive string.
+        directives: String,
+        /// Why the requested directives were rejected, if they were.
+        error: Option<String>,
+    },
+
+    /// The previous run crashed; offers its session back.
+    CrashRecovery {
+        /// Report location, crash message, and the session to restore.
+        recovery: CrashRecovery,
+    },
+
+    /// Show the setup wizard, on first run or when re-run by the user.
+    Setup {
+        /// Current choices, used as the wizard's starting values.
+        config: UserConfig,
+        /// GPU adapter names available on this machine.
+        adapters: Vec<String>,
+        /// Whether OCIO color management is available.
+        ocio_available: bool,
+        /// `true` on first run.
+        first_run: bool,
+    },
+
+    /// Settings in effect, sent on connect and after every change.
+    Settings {
+        /// Current settings.
+        settings: Settings,
+        /// Why the requested change was rejected or not saved, if it was.
+        error: Option<String>,
+    },
+
+    /// The gallery's stills, sent on request and after every change.
+    Gallery {
+        /// Stills, oldest first.
+        stills: Vec<GalleryStill>,
+        /// Why the last gallery command failed, if it did.
+        error: Option<String>,
+    },
+
+    /// The current frame under the current grade and under each gallery
+    /// still's grade.
+    GalleryPreviews {
+        /// Thumbnail size in pixels, shared by every preview.
+        width: u32,
+        height: u32,
+        /// PNG data URL of the frame under the current grade.
+        current: String,
+        /// One preview per still that could be rendered.
+        stills: Vec<StillPreview>,
+    },
+
+    /// The tool preset library, sent on request and after every change.
+    ToolPresets {
+        /// Every preset, in the order they were added.
+        presets: Vec<ToolPreset>,
+        /// Why the last preset command failed, if it did.
+        error: Option<String>,
+    },
+
+    /// The user right-clicked somewhere the page did not handle; the
+    /// native menu was suppressed so the UI can show its own.
+    ContextMenuRequested {
+        /// Position in CSS pixels from the top-left of the page.
+        x: i32,
+        /// Position in CSS pixels from the top-left of the page.
+        y: i32,
+        /// Whether the click landed in an editable field.
+        editable: bool,
+        /// Selected text; empty if nothing is selected.
+        selection_text: String,
+        /// Target of the link under the pointer, if any.
+        link_url: Option<String>,
+    },
+
+    /// Reload the page. Sent to browsers on the WebSocket bridge, which the
+    /// backend cannot reload itself.
+    ReloadUi,
+
     /// An error occurred in the backend.
     Error {
         /// Error description.
@@ -66,13 +246,23 @@ pub enum UiToBevy {
 
     /// Set new grading parameters (UI → backend).
     SetParams {
-        /// The new grading parameters.
-        params: GradingParams,
+        /// The new grading parameters, boxed to keep the enum small.
+        params: Box<GradingParams>,
     },
 
     /// Request automatic white balance.
     AutoBalance,
 
+    /// Request histogram-based auto contrast.
+    AutoContrast {
+        /// Percent of samples allowed to clip to black (e.g. 0.1).
+        low_percentile: f32,
+        /// Percentile that maps to white (e.g. 99.9).
+        high_percentile: f32,
+        /// Blend amount, 0–1.
+        strength: f32,
+    },
+
     /// Reset all grading to identity (no-op) defaults.
     ResetGrade,
 
@@ -82,12 +272,45 @@ pub enum UiToBevy {
         path: String,
     },
 
+    /// Load a built-in test pattern as the source image.
+    LoadTestPattern {
+        /// Which pattern to generate.
+        pattern: TestPattern,
+        /// Quantization depth of the generated image.
+        bit_depth: BitDepth,
+        /// Color space the pattern is encoded in (becomes the input space).
+        color_space: ColorSpaceId,
+    },
+
     /// Load a 3D LUT from a file.
     LoadLut {
         /// File path to the .cube LUT file.
         path: String,
         /// Which LUT slot to load into.
         slot: String,
+        /// Input/output encodings of the LUT (None = guess from file name).
+        #[serde(default)]
+        domain: Option<LutDomain>,
+    },
+
+    /// Change the declared input/output encodings of a loaded LUT.
+    SetLutDomain {
+        /// LUT slot to update.
+        slot: String,
+        /// New input/output encodings.
+        domain: LutDomain,
+    },
+
+    /// Remove the LUT from a slot.
+    ClearLut {
+        /// LUT slot to clear.
+        slot: String,
+    },
+
+    /// Enable or disable reloading LUT files when they change on disk.
+    SetLutWatch {
+        /// Whether changed files are reloaded automatically.
+        enabled: bool,
     },
 
     /// Export the current grading as a 3D LUT.
@@ -96,6 +319,33 @@ pub enum UiToBevy {
         path: String,
         /// LUT grid size (e.g., 33 or 65).
         size: u32,
+        /// Prepend a 1D shaper for scene-linear input (None = plain 3D LUT).
+        #[serde(default)]
+        shaper: Option<ShaperTransfer>,
+    },
+
+    /// Export an ICC profile of the output transform.
+    ExportIcc {
+        /// File path for the `.icc` profile.
+        path: String,
+    },
+
+    /// Export the current grading as a Nuke `.nk` script or `.gizmo`.
+    ExportNuke {
+        /// File path; a `.gizmo` extension selects gizmo output.
+        path: String,
+        /// LUT grid size for the `.cube` written when a LUT is needed.
+        size: u32,
+    },
+
+    /// Export the current grading as shader source.
+    ExportShader {
+        /// File path for the generated shader.
+        path: String,
+        /// LUT grid size used when the grade cannot be written analytically.
+        size: u32,
+        /// Target shading language.
+        language: ShaderLanguage,
     },
 
     /// Toggle scope visibility.
@@ -106,6 +356,289 @@ pub enum UiToBevy {
         visible: bool,
     },
 
+    /// Set temporal scope accumulation (instant, N-frame average, peak hold).
+    SetScopeAccumulation {
+        /// Accumulation mode applied to every scope.
+        mode: ScopeAccumulation,
+    },
+
+    /// Set scope drawing options (CIE gamut overlays and projection).
+    SetScopeDisplayOptions {
+        /// Overlays, in legend order, and the CIE projection.
+        options: ScopeDisplayOptions,
+    },
+
+    /// Set per-scope resolutions and automatic quality reduction.
+    SetScopeQuality {
+        /// Requested resolutions and frame budget.
+        quality: ScopeQuality,
+    },
+
+    /// Restrict scope analysis to a region drawn in the UI.
+    SetScopeMaskPolygon {
+        /// Polygon vertices in normalized image coordinates (0..1, top-left
+        /// origin). The polygon closes automatically.
+        points: Vec<[f32; 2]>,
+    },
+
+    /// Include the whole image in scope analysis again.
+    ClearScopeMask,
+
+    /// Run the colorimeter display check.
+    StartProbeCheck,
+
+    /// Fit an input correction to a ColorChecker chart in the source image.
+    MatchChart {
+        /// Normalized outer chart corners: top-left, top-right,
+        /// bottom-right, bottom-left.
+        corners: [[f32; 2]; 4],
+    },
+
+    /// Treat the loaded image as a black frame and add its hot pixels to
+    /// the defect map repaired before grading.
+    DetectDefects {
+        /// How far a pixel's brightest channel must rise above its
+        /// neighbors' median.
+        threshold: f32,
+    },
+
+    /// Empty the defect map.
+    ClearDefects,
+
+    /// Compare the GPU grade of the current image against the CPU reference.
+    RunParityCheck,
+
+    /// Compare the graded output against a reference per pixel (ΔE2000)
+    /// and show the difference as a heatmap over the viewer.
+    CompareDeltaE {
+        /// A render in the output space, or a `.json` grade to apply to the
+        /// current source.
+        path: String,
+        /// ΔE at which the heatmap reaches red; defaults to 10.
+        scale: Option<f32>,
+    },
+
+    /// Hide the ΔE heatmap.
+    ClearDeltaE,
+
+    /// Render the current frame through every `.cube` LUT and `.json`
+    /// grade preset in `dirs` for the look browser.
+    PreviewLooks {
+        /// Folders to list looks from; subfolders are not searched.
+        dirs: Vec<String>,
+    },
+
+    /// Apply a look from the browser: a LUT goes into the input slot, a
+    /// grade preset replaces the grade.
+    ApplyLook {
+        /// Path of the look file.
+        path: String,
+    },
+
+    /// Render the current frame under several grade variations and write
+    /// one labeled still per variant next to `path`.
+    ExportBracket {
+        /// Base file name; the extension picks the format (`.exr` float,
+        /// `.png`/`.tif` 16-bit, otherwise 8-bit).
+        path: String,
+        bracket: BracketRequest,
+    },
+
+    /// Save the current grade as a `.json` version.
+    SaveVersion {
+        /// File path for the grade.
+        path: String,
+    },
+
+    /// Write the audit log of grade changes as a JSON array.
+    ExportAuditLog {
+        /// File path for the `.json` export.
+        path: String,
+    },
+
+    /// Set the output matte previewed in the viewer and baked into script
+    /// renders.
+    SetBlanking {
+        /// `"none"` or `{ "aspect": 2.39 }`.
+        blanking: Blanking,
+    },
+
+    /// Run a Rhai script against the current grade (`script` feature).
+    RunScript {
+        /// Script file path.
+        path: String,
+    },
+
+    /// Replace the log filter directives, e.g. `info,crispen_gpu=debug`.
+    SetLogFilter {
+        /// `EnvFilter` directive string.
+        directives: String,
+    },
+
+    /// Start or stop streaming captured log records to the UI.
+    SetLogStreaming {
+        /// Whether records are streamed; enabling resends the backlog.
+        enabled: bool,
+    },
+
+    /// The crash recovery offer was accepted or declined; forget it.
+    DismissCrashRecovery,
+
+    /// Show the setup wizard again.
+    RunSetup,
+
+    /// Save the setup wizard's choices.
+    CompleteSetup {
+        /// Choices to persist in `config.json`.
+        config: UserConfig,
+    },
+
+    /// Report the current settings.
+    RequestSettings,
+
+    /// Validate, apply and save new settings.
+    UpdateSettings {
+        /// The complete settings to use.
+        settings: Settings,
+    },
+
+    /// Restore the default settings.
+    ResetSettings,
+
+    /// Change the viewer's framing guides, keeping the other settings.
+    SetGuides {
+        /// Guides to draw; saved with the settings.
+        guides: FramingGuides,
+    },
+
+    /// Change the viewer's focus assist overlay, keeping the other settings.
+    SetFocusAssist {
+        /// Overlay to draw; saved with the settings.
+        focus_assist: FocusAssist,
+    },
+
+    /// Copy the current grade to the clipboard.
+    CopyLook,
+
+    /// Apply a grade from the clipboard.
+    PasteLook {
+        /// Look JSON the UI read from the system clipboard; `None` reads
+        /// the backend's clipboard instead.
+        text: Option<String>,
+    },
+
+    /// Copy the graded image to the clipboard.
+    CopyStill,
+
+    /// Report the gallery's stills.
+    RequestGallery,
+
+    /// Render the current frame with its grade into the gallery.
+    GrabStill {
+        /// Label for the still; numbered if the gallery already has it.
+        label: String,
+    },
+
+    /// Apply a gallery still's grade to the current shot.
+    ApplyStill {
+        /// Label of the still.
+        label: String,
+    },
+
+    /// Delete a gallery still and its grade sidecar.
+    DeleteStill {
+        /// Label of the still.
+        label: String,
+    },
+
+    /// Write a gallery still with its `.grade.json` sidecar.
+    ExportStill {
+        /// Label of the still.
+        label: String,
+        /// File path; `.exr` writes float, `.dpx` 10-bit.
+        path: String,
+    },
+
+    /// Copy an exported still and its grade sidecar into the gallery.
+    ImportStill {
+        /// File path of the `.exr` or `.dpx` still.
+        path: String,
+    },
+
+    /// Report the tool preset library.
+    RequestToolPresets,
+
+    /// Save one tool's settings from the current grade as a preset.
+    SaveToolPreset {
+        /// Tool whose fields are captured.
+        tool: Tool,
+        /// Preset name; replaces the tool's preset of the same name.
+        name: String,
+        /// Grouping in the library; empty for none.
+        category: String,
+    },
+
+    /// Apply a tool preset over the current grade.
+    ApplyToolPreset {
+        /// Tool the preset belongs to.
+        tool: Tool,
+        /// Preset name.
+        name: String,
+    },
+
+    /// Remove a tool preset from the library.
+    DeleteToolPreset {
+        /// Tool the preset belongs to.
+        tool: Tool,
+        /// Preset name.
+        name: String,
+    },
+
+    /// Add the presets in a `.json` library file to the library.
+    ImportToolPresets {
+        /// File path of the library to import.
+        path: String,
+    },
+
+    /// Write tool presets to a `.json` library file for another suite.
+    ExportToolPresets {
+        /// File path for the export.
+        path: String,
+        /// Only this tool's presets; `None` exports all of them.
+        tool: Option<Tool>,
+    },
+
+    /// Show a panel in its own floating webview (CEF only), opening it if
+    /// it is not open yet.
+    OpenFloatingPanel {
+        /// Panel name, e.g. `"shortcuts"`.
+        panel: String,
+        /// X position in CSS pixels.
+        x: f32,
+        /// Y position in CSS pixels.
+        y: f32,
+        /// Width in CSS pixels.
+        width: f32,
+        /// Height in CSS pixels.
+        height: f32,
+    },
+
+    /// Close a floating panel's webview.
+    CloseFloatingPanel {
+        /// Panel name.
+        panel: String,
+    },
+
+    /// Reload the web UI without restarting the app.
+    ReloadUi,
+
+    /// A modal dialog opened or closed; the native content under the UI is
+    /// dimmed while one is open (CEF only).
+    SetModalOpen {
+        /// Whether a modal dialog is open.
+        open: bool,
+    },
+
     /// CEF dirty signal — triggers framebuffer recapture.
     UiDirty,
 
@@ -183,6 +716,148 @@ pub fn scope_data_to_binary(
     }
 }
 
+/// One gallery still, without its pixels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryStill {
+    /// Label, unique within the gallery.
+    pub label: String,
+    /// File path of the stored image.
+    pub path: String,
+    /// Source image the still was taken from, if known.
+    pub source: Option<String>,
+    /// Unix time in seconds when the still was taken.
+    pub captured_at: u64,
+    /// Who took the still.
+    pub operator: String,
+    /// Grade fields applying the still would change; empty when it matches
+    /// the current grade.
+    pub changes: Vec<String>,
+}
+
+impl From<&StillStatus> for GalleryStill {
+    fn from(still: &StillStatus) -> Self {
+        let entry = &still.entry;
+        Self {
+            label: entry.sidecar.label.clone(),
+            path: entry.image_path.display().to_string(),
+            source: entry.sidecar.source.clone(),
+            captured_at: entry.sidecar.captured_at,
+            operator: entry.sidecar.operator.clone(),
+            changes: still.changes.clone(),
+        }
+    }
+}
+
+/// One gallery still's grade on the current frame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StillPreview {
+    /// Label of the still.
+    pub label: String,
+    /// PNG data URL of the frame under the still's grade.
+    pub image: String,
+}
+
+/// Build a `BevyToUi::GalleryPreviews` message for previews of the stills
+/// labelled `labels`, in request order. Previews that fail to encode are
+/// dropped.
+pub fn gallery_previews_to_ui(event: &GradePreviewsReadyEvent, labels: &[String]) -> BevyToUi {
+    let encode = |rgba: &[u8]| png_data_url(rgba, event.width, event.height);
+    let current = encode(&event.current).unwrap_or_else(|e| {
+        tracing::warn!("Gallery preview of the current grade: {e}");
+        String::new()
+    });
+    let stills = labels
+        .iter()
+        .zip(&event.thumbnails)
+        .filter_map(|(label, thumbnail)| match encode(thumbnail) {
+            Ok(image) => Some(StillPreview {
+                label: label.clone(),
+                image,
+            }),
+            Err(e) => {
+                tracing::warn!("Gallery preview {label}: {e}");
+                None
+            }
+        })
+        .collect();
+    BevyToUi::GalleryPreviews {
+        width: event.width,
+        height: event.height,
+        current,
+        stills,
+    }
+}
+
+/// One look browser thumbnail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookThumbnail {
+    /// Display name (the file stem).
+    pub name: String,
+    /// Path to send back with `ApplyLook`.
+    pub path: String,
+    pub kind: LookKind,
+    /// PNG data URL of the graded thumbnail.
+    pub image: String,
+}
+
+/// Build a `BevyToUi::LookPreviews` message, encoding each thumbnail as a
+/// PNG data URL. Thumbnails that fail to encode are dropped.
+pub fn look_previews_to_ui(event: &LookPreviewsReadyEvent) -> BevyToUi {
+    let encode = |rgba: &[u8]| png_data_url(rgba, event.width, event.height);
+    let looks = event
+        .looks
+        .iter()
+        .filter_map(|preview| match encode(&preview.thumbnail) {
+            Ok(image) => Some(LookThumbnail {
+                name: preview.look.name.clone(),
+                path: preview.look.path.display().to_string(),
+                kind: preview.look.kind,
+                image,
+            }),
+            Err(e) => {
+                tracing::warn!("Look thumbnail {}: {e}", preview.look.name);
+                None
+            }
+        })
+        .collect();
+    BevyToUi::LookPreviews {
+        width: event.width,
+        height: event.height,
+        looks,
+    }
+}
+
+/// Encode RGBA8 pixels as a PNG data URL.
+fn png_data_url(rgba: &[u8], width: u32, height: u32) -> image::ImageResult<String> {
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png).write_image(
+        rgba,
+        width,
+        height,
+        image::ExtendedColorType::Rgba8,
+    )?;
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    Ok(format!("data:image/png;base64,{data}"))
+}
+
+/// Grade variations of an `ExportBracket` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BracketRequest {
+    /// Exposure offsets in stops, e.g. `[-1, 0, 1]`.
+    Exposure { stops: Vec<f32> },
+    /// Paths of saved `.json` grade versions.
+    Versions { paths: Vec<String> },
+}
+
+impl From<BracketRequest> for Bracket {
+    fn from(request: BracketRequest) -> Self {
+        match request {
+            BracketRequest::Exposure { stops } => Self::Exposure { stops },
+            BracketRequest::Versions { paths } => Self::Versions { paths },
+        }
+    }
+}
+
 /// A rectangular region where Bevy should render a widget.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LayoutRegion {
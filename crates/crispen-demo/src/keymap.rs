@@ -0,0 +1,299 @@
+//! Rebindable keyboard shortcuts for the native app.
+//!
+//! Bindings are written as `Ctrl+Shift+I` style strings in the settings
+//! file and parsed into [`Shortcut`]s. Systems ask [`KeyBindings`] whether
+//! an [`Action`] was triggered instead of testing key codes themselves.
+//! Escape as "cancel" is not rebindable.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+/// Commands that can be bound to a shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Open a file dialog and load an image.
+    LoadImage,
+    /// Toggle the before/after split view.
+    ToggleSplitView,
+    /// Show or hide the OFX panel.
+    ToggleOfxPanel,
+    /// Arm the chart picker to mark ColorChecker corners.
+    PickChart,
+    /// Reset viewer zoom and pan.
+    ResetView,
+    /// Open the webview's developer tools.
+    DevTools,
+    /// Copy the current grade to the clipboard.
+    CopyLook,
+    /// Apply a grade from the clipboard.
+    PasteLook,
+    /// Copy the graded image to the clipboard.
+    CopyStill,
+    /// Reload the web UI.
+    ReloadUi,
+    /// Raise input exposure by a third of a stop.
+    ExposureUp,
+    /// Lower input exposure by a third of a stop.
+    ExposureDown,
+    /// Show the magnifier loupe while held.
+    Loupe,
+    /// Show or hide the focus assist overlay.
+    ToggleFocusAssist,
+    /// Arm defect marking to click dead pixels and dust in the viewer.
+    MarkDefects,
+}
+
+impl Action {
+    /// Every action with its default binding.
+    pub const DEFAULTS: [(Action, &str); 15] = [
+        (Action::LoadImage, "Ctrl+O"),
+        (Action::ToggleSplitView, "Ctrl+\\"),
+        (Action::ToggleOfxPanel, "Ctrl+P"),
+        (Action::PickChart, "M"),
+        (Action::ResetView, "Home"),
+        (Action::DevTools, "Ctrl+Shift+I"),
+        (Action::CopyLook, "Ctrl+Shift+C"),
+        (Action::PasteLook, "Ctrl+Shift+V"),
+        (Action::CopyStill, "Ctrl+Alt+C"),
+        (Action::ReloadUi, "Ctrl+Shift+R"),
+        (Action::ExposureUp, "="),
+        (Action::ExposureDown, "-"),
+        (Action::Loupe, "Z"),
+        (Action::ToggleFocusAssist, "F"),
+        (Action::MarkDefects, "D"),
+    ];
+
+    /// The default key binding for every action.
+    pub fn default_bindings() -> BTreeMap<Action, String> {
+        Self::DEFAULTS
+            .iter()
+            .map(|(action, binding)| (*action, (*binding).to_string()))
+            .collect()
+    }
+
+    /// Translated name for messages.
+    pub fn label(self) -> String {
+        tr(match self {
+            Action::LoadImage => "action-load-image",
+            Action::ToggleSplitView => "action-toggle-split-view",
+            Action::ToggleOfxPanel => "action-toggle-ofx-panel",
+            Action::PickChart => "action-pick-chart",
+            Action::ResetView => "action-reset-view",
+            Action::DevTools => "action-dev-tools",
+            Action::CopyLook => "action-copy-look",
+            Action::PasteLook => "action-paste-look",
+            Action::CopyStill => "action-copy-still",
+            Action::ReloadUi => "action-reload-ui",
+            Action::ExposureUp => "action-exposure-up",
+            Action::ExposureDown => "action-exposure-down",
+            Action::Loupe => "action-loupe",
+            Action::ToggleFocusAssist => "action-toggle-focus-assist",
+            Action::MarkDefects => "action-mark-defects",
+        })
+    }
+}
+
+/// Key names accepted in bindings, as written by [`Shortcut`]'s `Display`.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::KeyA),
+    ("B", KeyCode::KeyB),
+    ("C", KeyCode::KeyC),
+    ("D", KeyCode::KeyD),
+    ("E", KeyCode::KeyE),
+    ("F", KeyCode::KeyF),
+    ("G", KeyCode::KeyG),
+    ("H", KeyCode::KeyH),
+    ("I", KeyCode::KeyI),
+    ("J", KeyCode::KeyJ),
+    ("K", KeyCode::KeyK),
+    ("L", KeyCode::KeyL),
+    ("M", KeyCode::KeyM),
+    ("N", KeyCode::KeyN),
+    ("O", KeyCode::KeyO),
+    ("P", KeyCode::KeyP),
+    ("Q", KeyCode::KeyQ),
+    ("R", KeyCode::KeyR),
+    ("S", KeyCode::KeyS),
+    ("T", KeyCode::KeyT),
+    ("U", KeyCode::KeyU),
+    ("V", KeyCode::KeyV),
+    ("W", KeyCode::KeyW),
+    ("X", KeyCode::KeyX),
+    ("Y", KeyCode::KeyY),
+    ("Z", KeyCode::KeyZ),
+    ("0", KeyCode::Digit0),
+    ("1", KeyCode::Digit1),
+    ("2", KeyCode::Digit2),
+    ("3", KeyCode::Digit3),
+    ("4", KeyCode::Digit4),
+    ("5", KeyCode::Digit5),
+    ("6", KeyCode::Digit6),
+    ("7", KeyCode::Digit7),
+    ("8", KeyCode::Digit8),
+    ("9", KeyCode::Digit9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("Escape", KeyCode::Escape),
+    ("Enter", KeyCode::Enter),
+    ("Space", KeyCode::Space),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Up", KeyCode::ArrowUp),
+    ("Down", KeyCode::ArrowDown),
+    ("Left", KeyCode::ArrowLeft),
+    ("Right", KeyCode::ArrowRight),
+    ("\\", KeyCode::Backslash),
+    ("/", KeyCode::Slash),
+    (",", KeyCode::Comma),
+    (".", KeyCode::Period),
+    (";", KeyCode::Semicolon),
+    ("'", KeyCode::Quote),
+    ("[", KeyCode::BracketLeft),
+    ("]", KeyCode::BracketRight),
+    ("-", KeyCode::Minus),
+    ("=", KeyCode::Equal),
+    ("`", KeyCode::Backquote),
+];
+
+/// A key with the modifiers that must be held with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Shortcut {
+    /// Parse `Ctrl+Shift+I`, `Home`, `M`, ... Modifier and key names are
+    /// case-insensitive.
+    pub fn parse(binding: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = binding.split('+').map(str::trim).collect();
+        // A trailing `+` ("Ctrl++") leaves an empty key name; use `=` instead.
+        let key_name = parts.pop().filter(|name| !name.is_empty())?;
+        let (mut ctrl, mut shift, mut alt) = (false, false, false);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ctrl = true,
+                "shift" => shift = true,
+                "alt" => alt = true,
+                _ => return None,
+            }
+        }
+        let key = KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key_name))
+            .map(|(_, code)| *code)?;
+        Some(Self {
+            key,
+            ctrl,
+            shift,
+            alt,
+        })
+    }
+
+    /// Whether the key was pressed this frame with exactly these modifiers.
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        let key = keys.just_pressed(self.key)
+            // ISO layouts report the key left of Z instead.
+            || (self.key == KeyCode::Backslash && keys.just_pressed(KeyCode::IntlBackslash));
+        key && self.modifiers_held(keys)
+    }
+
+    /// Whether the key is held with exactly these modifiers.
+    pub fn pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        let key = keys.pressed(self.key)
+            || (self.key == KeyCode::Backslash && keys.pressed(KeyCode::IntlBackslash));
+        key && self.modifiers_held(keys)
+    }
+
+    fn modifiers_held(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) == self.ctrl
+            && keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) == self.shift
+            && keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) == self.alt
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        let name = KEY_NAMES
+            .iter()
+            .find(|(_, code)| *code == self.key)
+            .map_or("?", |(name, _)| name);
+        f.write_str(name)
+    }
+}
+
+/// Active shortcut for each action, rebuilt when the settings change.
+#[derive(Resource, Debug, Clone)]
+pub struct KeyBindings(BTreeMap<Action, Shortcut>);
+
+impl KeyBindings {
+    /// Parse a binding table. Unparseable entries are left unbound; the
+    /// settings are validated before they get here.
+    pub fn from_bindings(bindings: &BTreeMap<Action, String>) -> Self {
+        Self(
+            bindings
+                .iter()
+                .filter_map(|(action, binding)| Some((*action, Shortcut::parse(binding)?)))
+                .collect(),
+        )
+    }
+
+    /// The shortcut bound to `action`, if any.
+    pub fn get(&self, action: Action) -> Option<&Shortcut> {
+        self.0.get(&action)
+    }
+
+    /// Whether `action`'s shortcut was pressed this frame.
+    pub fn just_pressed(&self, action: Action, keys: &ButtonInput<KeyCode>) -> bool {
+        self.0
+            .get(&action)
+            .is_some_and(|shortcut| shortcut.just_pressed(keys))
+    }
+
+    /// Whether `action`'s shortcut is held, for actions active only while
+    /// their key is down.
+    pub fn pressed(&self, action: Action, keys: &ButtonInput<KeyCode>) -> bool {
+        self.0
+            .get(&action)
+            .is_some_and(|shortcut| shortcut.pressed(keys))
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::from_bindings(&Action::default_bindings())
+    }
+}
@@ -1,11 +1,13 @@
 //! Panel layout synchronization between dockview (Svelte) and Bevy.
 //!
 //! When the Svelte dockview sends a `LayoutUpdate` IPC message, this module
-//! repositions and resizes Bevy UI containers to match the panel regions.
+//! repositions and resizes Bevy UI containers to match the panel regions,
+//! and pauses the work behind panels that are not on screen.
 
 use bevy::prelude::*;
 
 use crate::ipc::LayoutRegion;
+use crispen_bevy::resources::{GradingState, ScopeConfig};
 
 /// Resource holding the latest panel layout from dockview.
 #[derive(Resource, Default)]
@@ -13,6 +15,22 @@ pub struct PanelLayout {
     pub regions: Vec<LayoutRegion>,
 }
 
+impl PanelLayout {
+    /// Whether the panel `id` is on screen: present in the layout, the
+    /// active tab of its group, and not collapsed to nothing.
+    pub fn is_shown(&self, id: &str) -> bool {
+        self.regions
+            .iter()
+            .find(|r| r.id == id)
+            .is_some_and(|r| r.visible && r.width >= 1.0 && r.height >= 1.0)
+    }
+}
+
+/// Run condition: the panel `id` is on screen.
+pub fn panel_shown(id: &'static str) -> impl Fn(Res<PanelLayout>) -> bool {
+    move |layout: Res<PanelLayout>| layout.is_shown(id)
+}
+
 /// Marker component identifying a Bevy UI entity that should be positioned
 /// by the layout sync system. The `panel_id` must match a dockview panel ID.
 #[derive(Component)]
@@ -20,52 +38,45 @@ pub struct LayoutPanel {
     pub panel_id: String,
 }
 
+/// Global z-index of `LayoutPanel`s. The nodes of floating webviews stack
+/// above this so they take clicks first; the CEF textures themselves are
+/// composited over all native UI.
+pub const LAYOUT_PANEL_Z: i32 = i32::MAX - 256;
+
 /// Plugin that registers layout sync resources and systems.
 pub struct LayoutSyncPlugin;
 
 impl Plugin for LayoutSyncPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PanelLayout>()
-            .add_systems(Update, sync_panel_layout);
+            .add_systems(Update, (sync_panel_layout, pause_hidden_scopes));
     }
 }
 
 /// Reposition `LayoutPanel`-tagged entities to match dockview panel regions.
 ///
-/// CEF reports coordinates in its CSS pixel space, which equals physical pixels
-/// (CEF is created at physical window dimensions with device_scale_factor=1).
-/// Bevy's `Val::Px` uses logical pixels. On HiDPI displays we must divide by
-/// the window scale factor to align the Bevy entity with the transparent
-/// cutout in the CEF overlay.
+/// Dockview reports CSS pixels. CEF's device scale factor is the window scale
+/// factor times [`UiScale`], which is exactly what Bevy applies to `Val::Px`,
+/// so the regions are used as-is to line up with the cutouts in the overlay.
 fn sync_panel_layout(
     layout: Res<PanelLayout>,
     mut query: Query<(&LayoutPanel, &mut Node, &mut Visibility)>,
-    windows: Query<&Window>,
 ) {
     if !layout.is_changed() {
         return;
     }
 
-    let scale = windows
-        .single()
-        .map(|w| w.scale_factor() as f32)
-        .unwrap_or(1.0);
-
     for (panel, mut node, mut vis) in &mut query {
         if let Some(region) = layout.regions.iter().find(|r| r.id == panel.panel_id) {
-            let lx = region.x / scale;
-            let ly = region.y / scale;
-            let lw = region.width / scale;
-            let lh = region.height / scale;
             tracing::info!(
-                "sync_panel_layout: positioning '{}' at ({:.0}, {:.0}) {:.0}x{:.0} (scale={:.3}, css={}, {})",
-                panel.panel_id, lx, ly, lw, lh, scale, region.x, region.y
+                "sync_panel_layout: positioning '{}' at ({:.0}, {:.0}) {:.0}x{:.0}",
+                panel.panel_id, region.x, region.y, region.width, region.height
             );
             node.position_type = PositionType::Absolute;
-            node.left = Val::Px(lx);
-            node.top = Val::Px(ly);
-            node.width = Val::Px(lw);
-            node.height = Val::Px(lh);
+            node.left = Val::Px(region.x);
+            node.top = Val::Px(region.y);
+            node.width = Val::Px(region.width);
+            node.height = Val::Px(region.height);
 
             *vis = if region.visible {
                 Visibility::Inherited
@@ -78,3 +89,25 @@ fn sync_panel_layout(
         }
     }
 }
+
+/// Stop scope compute while the scopes panel is hidden or collapsed.
+///
+/// Re-baking on the way back refreshes scopes that went stale in the
+/// meantime; per-scope toggles are left alone.
+fn pause_hidden_scopes(
+    layout: Res<PanelLayout>,
+    mut scope_config: ResMut<ScopeConfig>,
+    mut grading: ResMut<GradingState>,
+) {
+    if !layout.is_changed() {
+        return;
+    }
+    let shown = layout.is_shown("scopes");
+    if scope_config.on_screen != shown {
+        tracing::debug!("scopes panel {}", if shown { "shown" } else { "hidden" });
+        scope_config.on_screen = shown;
+        if shown {
+            grading.dirty = true;
+        }
+    }
+}
@@ -5,16 +5,29 @@
 
 #[cfg(feature = "cef")]
 mod cef_bridge;
+mod clipboard;
 mod config;
+mod crash;
+mod diagnostics;
 mod embedded_ui;
+mod gallery;
+mod i18n;
 mod image_loader;
 #[cfg(feature = "cef")]
 mod input;
 mod ipc;
+mod keymap;
 #[cfg(feature = "cef")]
 mod layout_sync;
 mod ocio_support;
+mod power;
+mod presets;
+mod probe;
+mod settings;
+mod setup;
+mod stills;
 mod ui;
+mod ui_reload;
 mod ws_bridge;
 
 use bevy::input_focus::InputDispatchPlugin;
@@ -22,11 +35,14 @@ use bevy::prelude::*;
 use bevy::window::WindowResolution;
 
 use config::{AppConfig, FrontendMode};
+#[cfg(feature = "ocio")]
+use config::{ColorManagementMode, UserConfig};
 use crispen_bevy::CrispenPlugin;
 use crispen_bevy::events::{ImageLoadedEvent, ParamsUpdatedEvent};
 #[cfg(feature = "ocio")]
 use crispen_bevy::resources::OcioColorManagement;
-use crispen_bevy::resources::GradingState;
+use crispen_bevy::resources::{GradingState, InputLutState, PipelineSettings};
+use crispen_core::transform::inspect::pipeline_stages;
 #[cfg(feature = "ocio")]
 use crispen_ocio::OcioConfig;
 
@@ -42,8 +58,7 @@ fn main() {
 
     let window = Window {
         title: "Crispen".into(),
-        resolution: WindowResolution::new(config.width as u32, config.height as u32)
-            .with_scale_factor_override(1.0),
+        resolution: WindowResolution::new(config.width as u32, config.height as u32),
         present_mode: bevy::window::PresentMode::AutoVsync,
         ..default()
     };
@@ -59,11 +74,24 @@ fn main() {
                     ..default()
                 })
                 .set(bevy::log::LogPlugin {
-                    level: bevy::log::Level::INFO,
+                    level: diagnostics::PLUGIN_LEVEL,
+                    custom_layer: diagnostics::custom_layer,
+                    fmt_layer: diagnostics::fmt_layer,
                     ..default()
                 }),
         )
-        .add_plugins(CrispenPlugin);
+        .add_plugins(setup::SetupPlugin)
+        .add_plugins(settings::SettingsPlugin)
+        .add_plugins(power::PowerPlugin)
+        .add_plugins(clipboard::ClipboardPlugin)
+        .add_plugins(presets::PresetsPlugin)
+        .add_plugins(stills::StillsPlugin)
+        .add_plugins(gallery::GalleryPlugin)
+        .add_plugins(ui_reload::UiReloadPlugin)
+        .add_plugins(CrispenPlugin)
+        .add_plugins(probe::ProbeCheckPlugin)
+        .add_plugins(diagnostics::DiagnosticsPlugin)
+        .add_plugins(crash::CrashReportPlugin);
 
     // ── Frontend mode ────────────────────────────────────────────
     match frontend_mode {
@@ -71,6 +99,8 @@ fn main() {
         FrontendMode::Cef => {
             app.add_plugins(cef_bridge::CefBridgePlugin)
                 .add_plugins(input::InputForwardingPlugin)
+                .add_plugins(ui::delta_e_overlay::DeltaEOverlayPlugin)
+                .add_plugins(ui::guide_overlay::GuideOverlayPlugin)
                 .add_plugins(layout_sync::LayoutSyncPlugin)
                 .add_plugins(InputDispatchPlugin)
                 .init_resource::<ui::viewer_nav::ViewerTransform>()
@@ -91,11 +121,29 @@ fn main() {
                     (
                         forward_params_to_ui,
                         forward_image_loaded_to_ui,
+                        forward_probe_report_to_ui,
+                        forward_parity_report_to_ui,
+                        forward_delta_e_report_to_ui,
+                        forward_look_previews_to_ui,
+                        forward_lut_analysis_to_ui,
+                        forward_lut_reloads_to_ui,
+                        forward_script_results_to_ui,
+                        forward_logs_to_ui,
+                        forward_crash_recovery_to_ui,
+                        forward_setup_to_ui,
+                        forward_settings_to_ui,
+                        forward_presets_to_ui,
+                        forward_gallery_to_ui,
+                        forward_gallery_previews_to_ui,
                         ui::systems::handle_load_image_shortcut,
+                        // Textures of hidden panels catch up when shown again,
+                        // as change detection spans the skipped frames.
                         ui::viewer::update_viewer_texture
-                            .after(crispen_bevy::systems::consume_gpu_results),
+                            .after(crispen_bevy::systems::consume_gpu_results)
+                            .run_if(layout_sync::panel_shown("viewer")),
                         ui::vectorscope::update_cef_scopes
-                            .after(crispen_bevy::systems::consume_gpu_results),
+                            .after(crispen_bevy::systems::consume_gpu_results)
+                            .run_if(layout_sync::panel_shown("scopes")),
                     ),
                 );
         }
@@ -115,6 +163,20 @@ fn main() {
                         forward_params_to_ui,
                         forward_scopes_to_ui,
                         forward_image_loaded_to_ui,
+                        forward_probe_report_to_ui,
+                        forward_parity_report_to_ui,
+                        forward_delta_e_report_to_ui,
+                        forward_look_previews_to_ui,
+                        forward_lut_analysis_to_ui,
+                        forward_lut_reloads_to_ui,
+                        forward_script_results_to_ui,
+                        forward_logs_to_ui,
+                        forward_crash_recovery_to_ui,
+                        forward_setup_to_ui,
+                        forward_settings_to_ui,
+                        forward_presets_to_ui,
+                        forward_gallery_to_ui,
+                        forward_gallery_previews_to_ui,
                     ),
                 );
 
@@ -133,7 +195,21 @@ fn main() {
 
 #[cfg(feature = "ocio")]
 fn try_insert_ocio_resource(app: &mut App) {
-    let ocio_config = OcioConfig::from_env()
+    let config_path = match &app.world().resource::<UserConfig>().color_management {
+        ColorManagementMode::Native => {
+            tracing::info!("native color management selected; OCIO disabled");
+            return;
+        }
+        ColorManagementMode::Ocio { config_path } => config_path.clone(),
+    };
+
+    let from_file = config_path.and_then(|path| {
+        OcioConfig::from_file(std::path::Path::new(&path))
+            .inspect_err(|e| tracing::warn!("failed to load OCIO config {path}: {e}"))
+            .ok()
+    });
+    let ocio_config = from_file
+        .map_or_else(OcioConfig::from_env, Ok)
         .or_else(|_| OcioConfig::builtin("studio-config-v4.0.0_aces-v2.0_ocio-v2.5"))
         .or_else(|_| OcioConfig::builtin("studio-config-v2.2.0_aces-v1.3_ocio-v2.4"));
 
@@ -216,9 +292,9 @@ fn spawn_cef_viewer_panel(
             position_type: PositionType::Absolute,
             ..default()
         },
-        // Render above the CEF overlay so the Bevy texture is visible through
-        // the transparent cutout in the dockview panel.
-        GlobalZIndex(i32::MAX),
+        // Above the other native UI; the CEF overlay is composited over it
+        // and shows the Bevy texture through the transparent cutout.
+        GlobalZIndex(layout_sync::LAYOUT_PANEL_Z),
         Visibility::Hidden,
     ));
 }
@@ -249,7 +325,7 @@ fn spawn_cef_scope_panel(
                 padding: UiRect::all(Val::Px(4.0)),
                 ..default()
             },
-            GlobalZIndex(i32::MAX),
+            GlobalZIndex(layout_sync::LAYOUT_PANEL_Z),
             Visibility::Hidden,
             Pickable::IGNORE,
         ))
@@ -308,6 +384,7 @@ fn send_initial_state(
 ) {
     let msg = ipc::BevyToUi::Initialize {
         params: state.params.clone(),
+        locale: i18n::locale().to_string(),
     };
 
     #[cfg(feature = "cef")]
@@ -320,17 +397,34 @@ fn send_initial_state(
     ws_outbound.send(msg);
 }
 
-/// Forward `ParamsUpdatedEvent` to the UI.
+/// Forward `ParamsUpdatedEvent` to the UI, followed by the pipeline
+/// stages the grade now runs through. The stages are also re-sent when
+/// the input LUT or the LUT size changes.
 fn forward_params_to_ui(
     mut events: MessageReader<ParamsUpdatedEvent>,
+    state: Res<GradingState>,
+    input_lut: Res<InputLutState>,
+    settings: Res<PipelineSettings>,
     #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
     #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
 ) {
+    let mut msgs = Vec::new();
     for event in events.read() {
-        let msg = ipc::BevyToUi::ParamsUpdated {
+        msgs.push(ipc::BevyToUi::ParamsUpdated {
             params: event.params.clone(),
-        };
+        });
+    }
+    if !msgs.is_empty() || input_lut.is_changed() || settings.is_changed() {
+        msgs.push(ipc::BevyToUi::Pipeline {
+            stages: pipeline_stages(
+                &state.params,
+                settings.lut_size,
+                input_lut.baked.as_ref().map(|lut| lut.size),
+            ),
+        });
+    }
 
+    for msg in msgs {
         #[cfg(feature = "cef")]
         if let Some(ref mut out) = cef_outbound {
             out.send(msg);
@@ -367,6 +461,342 @@ fn forward_image_loaded_to_ui(
     }
 }
 
+/// Forward probe check results to the UI.
+fn forward_probe_report_to_ui(
+    mut events: MessageReader<probe::ProbeCheckFinished>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = match event {
+            probe::ProbeCheckFinished::Report(report) => ipc::BevyToUi::ProbeReport {
+                report: report.clone(),
+            },
+            probe::ProbeCheckFinished::Failed(message) => ipc::BevyToUi::Error {
+                message: i18n::tr_args("error-probe", &[("message", message.as_str().into())]),
+            },
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Forward GPU vs CPU parity reports to the UI.
+fn forward_parity_report_to_ui(
+    mut events: MessageReader<crispen_bevy::events::ParityReportEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::BevyToUi::ParityReport {
+            report: event.report.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Forward ΔE comparison summaries to the UI; the heatmap stays native.
+fn forward_delta_e_report_to_ui(
+    mut events: MessageReader<crispen_bevy::events::DeltaEReportEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::BevyToUi::DeltaEReport {
+            report: event.report,
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Forward look browser thumbnails to the UI.
+fn forward_look_previews_to_ui(
+    mut events: MessageReader<crispen_bevy::events::LookPreviewsReadyEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::look_previews_to_ui(event);
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Forward imported-LUT analysis to the UI's LUT inspector.
+fn forward_lut_analysis_to_ui(
+    mut events: MessageReader<crispen_bevy::events::LutAnalyzedEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::BevyToUi::LutAnalysis {
+            path: event.path.clone(),
+            slot: event.slot.clone(),
+            domain: event.domain,
+            analysis: event.analysis.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Notify the UI when a watched LUT file was reloaded from disk.
+fn forward_lut_reloads_to_ui(
+    mut events: MessageReader<crispen_bevy::events::LutReloadedEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::BevyToUi::LutReloaded {
+            path: event.path.clone(),
+            slot: event.slot.clone(),
+            error: event.error.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Report finished scripts to the UI.
+fn forward_script_results_to_ui(
+    mut events: MessageReader<crispen_bevy::events::ScriptFinishedEvent>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::BevyToUi::ScriptFinished {
+            path: event.path.clone(),
+            output: event.output.clone(),
+            error: event.error.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Stream captured log records and filter changes to the diagnostics panel.
+fn forward_logs_to_ui(
+    capture: Option<ResMut<diagnostics::LogCapture>>,
+    mut changes: MessageReader<diagnostics::LogFilterChanged>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    let mut messages: Vec<ipc::BevyToUi> = changes
+        .read()
+        .map(|change| ipc::BevyToUi::LogFilter {
+            directives: change.directives.clone(),
+            error: change.error.clone(),
+        })
+        .collect();
+    if let Some(mut capture) = capture
+        && capture.streaming
+    {
+        let records = capture.take_unsent();
+        if !records.is_empty() {
+            messages.push(ipc::BevyToUi::LogRecords { records });
+        }
+    }
+
+    for msg in messages {
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Offer the previous crashed session to the UI.
+fn forward_crash_recovery_to_ui(
+    mut offers: MessageReader<crash::RecoveryOffered>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for offer in offers.read() {
+        let msg = ipc::BevyToUi::CrashRecovery {
+            recovery: offer.0.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Ask the UI to show the setup wizard.
+fn forward_setup_to_ui(
+    mut offers: MessageReader<setup::SetupOffered>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for offer in offers.read() {
+        let msg = ipc::BevyToUi::Setup {
+            config: offer.config.clone(),
+            adapters: offer.adapters.clone(),
+            ocio_available: offer.ocio_available,
+            first_run: offer.first_run,
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Report the settings in effect after each settings command.
+fn forward_settings_to_ui(
+    mut changes: MessageReader<settings::SettingsChanged>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for change in changes.read() {
+        let msg = ipc::BevyToUi::Settings {
+            settings: change.settings.clone(),
+            error: change.error.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Report the gallery's stills after each change.
+fn forward_gallery_to_ui(
+    mut changes: MessageReader<gallery::GalleryChanged>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for change in changes.read() {
+        let msg = ipc::BevyToUi::Gallery {
+            stills: change.stills.iter().map(ipc::GalleryStill::from).collect(),
+            error: change.error.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Forward previews of the gallery stills' grades to the UI.
+fn forward_gallery_previews_to_ui(
+    mut events: MessageReader<crispen_bevy::events::GradePreviewsReadyEvent>,
+    store: Res<gallery::GalleryStore>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for event in events.read() {
+        let msg = ipc::gallery_previews_to_ui(event, &store.previewed);
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
+/// Report the tool preset library after each preset command.
+fn forward_presets_to_ui(
+    mut changes: MessageReader<presets::PresetsChanged>,
+    #[cfg(feature = "cef")] mut cef_outbound: Option<ResMut<cef_bridge::OutboundUiMessages>>,
+    #[cfg(not(feature = "cef"))] mut ws_outbound: ResMut<ws_bridge::OutboundUiMessages>,
+) {
+    for change in changes.read() {
+        let msg = ipc::BevyToUi::ToolPresets {
+            presets: change.presets.clone(),
+            error: change.error.clone(),
+        };
+
+        #[cfg(feature = "cef")]
+        if let Some(ref mut out) = cef_outbound {
+            out.send(msg);
+            continue;
+        }
+
+        #[cfg(not(feature = "cef"))]
+        ws_outbound.send(msg);
+    }
+}
+
 /// Forward scope data to the WebSocket UI (legacy fallback mode only).
 ///
 /// In CEF mode, scopes are rendered directly in Bevy via `update_scope_texture`
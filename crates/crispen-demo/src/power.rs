@@ -0,0 +1,63 @@
+//! Idle frame pacing.
+//!
+//! Bevy renders continuously by default, which keeps the GPU busy even when
+//! the grade and the UI sit untouched. Once the pipeline and the user have
+//! both been quiet for [`PipelineActivity::idle_after`], frames drop to the
+//! idle rate from [`PowerSettings`]; any window event wakes the app at once
+//! and the next frame with work restores continuous updates.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::window::WindowEvent;
+use bevy::winit::{UpdateMode, WinitSettings};
+use crispen_bevy::resources::PipelineActivity;
+
+use crate::settings::{PowerSettings, Settings};
+
+/// Switches between continuous and idle frame pacing.
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WinitSettings::game())
+            .add_systems(Last, pace_frames);
+    }
+}
+
+/// Frame pacing while idle.
+fn idle_winit_settings(power: &PowerSettings) -> WinitSettings {
+    let wait = Duration::from_secs_f64(1.0 / f64::from(power.idle_fps.max(1)));
+    WinitSettings {
+        focused_mode: UpdateMode::reactive_low_power(wait),
+        unfocused_mode: UpdateMode::reactive_low_power(wait),
+    }
+}
+
+/// Mark user input as activity and pick the update mode.
+///
+/// Runs in `Last` so the first frame after a wake-up is already over when
+/// input marks the pipeline active, keeping its long frame time out of the
+/// scope quality governor.
+fn pace_frames(
+    mut window_events: MessageReader<WindowEvent>,
+    settings: Res<Settings>,
+    mut activity: ResMut<PipelineActivity>,
+    mut winit: ResMut<WinitSettings>,
+) {
+    if !window_events.is_empty() {
+        window_events.clear();
+        activity.mark_active();
+    }
+
+    let idle = settings.power.idle_saving && activity.is_idle();
+    let wanted = if idle {
+        idle_winit_settings(&settings.power)
+    } else {
+        WinitSettings::game()
+    };
+    if winit.focused_mode != wanted.focused_mode || winit.unfocused_mode != wanted.unfocused_mode {
+        tracing::debug!("frame pacing: {}", if idle { "idle" } else { "active" });
+        *winit = wanted;
+    }
+}
@@ -0,0 +1,159 @@
+//! Named presets for individual tools.
+//!
+//! The [`PresetLibrary`] lives in `tool_presets.json` under the config dir
+//! and is edited from the UI's tool presets panel. Saving captures one
+//! tool's fields from the current grade; applying writes them back over
+//! the current grade through [`ColorGradingCommand::SetParams`], leaving
+//! every other tool alone. Every change is saved at once and reported
+//! with [`PresetsChanged`]; saving a preset also fires the `grade_saved`
+//! hook with the library file as its path.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use crispen_bevy::events::{ColorGradingCommand, GradeSavedEvent};
+use crispen_bevy::resources::GradingState;
+use crispen_core::transform::presets::{PresetError, PresetLibrary, Tool, ToolPreset};
+
+use crate::config::config_dir;
+use crate::i18n::tr_args;
+
+/// The preset library and where it is saved.
+#[derive(Resource, Debug)]
+pub struct ToolPresets {
+    pub library: PresetLibrary,
+    path: Option<PathBuf>,
+}
+
+impl ToolPresets {
+    const FILE_NAME: &str = "tool_presets.json";
+
+    /// Load the library file, or an empty library if it is missing or
+    /// invalid.
+    fn load() -> Self {
+        let path = config_dir().map(|dir| dir.join(Self::FILE_NAME));
+        let library = match path.as_deref().map(PresetLibrary::load) {
+            Some(Ok(library)) => library,
+            Some(Err(e)) => {
+                tracing::warn!("ignoring invalid tool presets: {e}");
+                PresetLibrary::default()
+            }
+            None => PresetLibrary::default(),
+        };
+        Self { library, path }
+    }
+
+    fn save(&self) -> Result<(), PresetError> {
+        let path = self
+            .path
+            .as_deref()
+            .ok_or_else(|| std::io::Error::other("no config directory"))?;
+        self.library.save(path)
+    }
+}
+
+/// Requests from the UI's tool presets panel.
+#[derive(Message, Debug, Clone)]
+pub enum PresetCommand {
+    /// Report the library.
+    Query,
+    /// Capture `tool` from the current grade as `name`, replacing any
+    /// preset of that tool with the same name.
+    Save {
+        tool: Tool,
+        name: String,
+        category: String,
+    },
+    /// Apply `tool`'s preset called `name` over the current grade.
+    Apply { tool: Tool, name: String },
+    /// Remove `tool`'s preset called `name`.
+    Delete { tool: Tool, name: String },
+    /// Add the presets in a library file.
+    Import { path: PathBuf },
+    /// Write the presets of `tool`, or all of them, to a library file.
+    Export { path: PathBuf, tool: Option<Tool> },
+}
+
+/// Emitted after [`PresetCommand`]s, with the library now in effect and
+/// the reason if the command failed.
+#[derive(Message, Debug, Clone)]
+pub struct PresetsChanged {
+    pub presets: Vec<ToolPreset>,
+    pub error: Option<String>,
+}
+
+/// Loads [`ToolPresets`] and handles [`PresetCommand`]s.
+pub struct PresetsPlugin;
+
+impl Plugin for PresetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ToolPresets::load())
+            .add_message::<PresetCommand>()
+            .add_message::<PresetsChanged>()
+            .add_systems(Update, handle_preset_commands);
+    }
+}
+
+fn handle_preset_commands(
+    mut commands: MessageReader<PresetCommand>,
+    mut presets: ResMut<ToolPresets>,
+    state: Res<GradingState>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+    mut saved: MessageWriter<GradeSavedEvent>,
+    mut changed: MessageWriter<PresetsChanged>,
+) {
+    for command in commands.read() {
+        let result = match command {
+            PresetCommand::Query => Ok(()),
+            PresetCommand::Save {
+                tool,
+                name,
+                category,
+            } => ToolPreset::capture(name.trim(), *tool, category.trim(), &state.params)
+                .and_then(|preset| {
+                    presets.library.add(preset);
+                    presets.save()
+                })
+                .map(|()| {
+                    if let Some(path) = &presets.path {
+                        saved.write(GradeSavedEvent {
+                            path: path.display().to_string(),
+                            kind: "preset",
+                        });
+                    }
+                }),
+            PresetCommand::Apply { tool, name } => presets
+                .library
+                .apply(*tool, name, &state.params)
+                .map(|params| {
+                    grading.write(ColorGradingCommand::SetParams { params });
+                }),
+            PresetCommand::Delete { tool, name } => {
+                if presets.library.remove(*tool, name) {
+                    presets.save()
+                } else {
+                    Ok(())
+                }
+            }
+            PresetCommand::Import { path } => presets.library.import(path).and_then(|count| {
+                tracing::info!("imported {count} tool presets from {}", path.display());
+                presets.save()
+            }),
+            PresetCommand::Export { path, tool } => export(&presets.library, path, *tool),
+        };
+        let error = result.err().map(|e| {
+            tracing::warn!("tool presets: {command:?} failed: {e}");
+            tr_args("error-tool-presets", &[("error", e.to_string().into())])
+        });
+        changed.write(PresetsChanged {
+            presets: presets.library.presets.clone(),
+            error,
+        });
+    }
+}
+
+fn export(library: &PresetLibrary, path: &Path, tool: Option<Tool>) -> Result<(), PresetError> {
+    let count = library.export(path, tool)?;
+    tracing::info!("exported {count} tool presets to {}", path.display());
+    Ok(())
+}
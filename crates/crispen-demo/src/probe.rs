@@ -0,0 +1,360 @@
+//! Colorimeter probe integration for a quick display calibration check.
+//!
+//! Drives an ArgyllCMS `spotread` subprocess to measure a fixed set of
+//! patches shown full-frame in the viewer (the clean feed — no grade or
+//! UI chrome applied), then reports CIEDE2000 error against the expected
+//! sRGB / Rec.709 display response.
+//!
+//! The check is a sanity test, not a calibration: it tells the operator
+//! whether the display is roughly where it should be without leaving the
+//! app. The white patch is measured first and used to normalize the
+//! remaining readings, so absolute luminance does not affect the result.
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crispen_bevy::resources::ViewerData;
+use crispen_core::color_management::color_space::{ColorSpaceId, rgb_to_xyz_matrix};
+use crispen_core::color_management::delta_e::{DeltaEStats, delta_e_2000, xyz_to_lab};
+use crispen_core::color_management::transfer::{SrgbTransfer, TransferFunction};
+use serde::{Deserialize, Serialize};
+
+use crate::ui::viewer::ViewerImageHandle;
+
+/// How long a patch stays on screen before the probe is triggered, giving
+/// the display time to settle.
+const PATCH_SETTLE_TIME: Duration = Duration::from_millis(750);
+
+/// Environment variable overriding the `spotread` executable path.
+const SPOTREAD_ENV: &str = "CRISPEN_SPOTREAD";
+
+/// Verification patches as sRGB-encoded display values.
+///
+/// White must stay first — it is the normalization reference for every
+/// other reading.
+pub const VERIFICATION_PATCHES: &[[f32; 3]] = &[
+    [1.0, 1.0, 1.0],
+    [0.75, 0.75, 0.75],
+    [0.5, 0.5, 0.5],
+    [0.25, 0.25, 0.25],
+    [0.1, 0.1, 0.1],
+    [1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [0.0, 1.0, 1.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 1.0, 0.0],
+    [0.76, 0.59, 0.51],
+    [0.45, 0.32, 0.27],
+];
+
+/// Errors from running the probe subprocess.
+#[derive(Debug, thiserror::Error)]
+pub enum ProbeError {
+    #[error("failed to launch {path}: {source}")]
+    Spawn {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("spotread exited with {status}: {stderr}")]
+    Exit { status: String, stderr: String },
+    #[error("no XYZ reading in spotread output")]
+    NoReading,
+    #[error("probe thread panicked")]
+    Panicked,
+    #[error("no viewer texture to show patches on")]
+    NoViewer,
+}
+
+/// Wrapper around the ArgyllCMS `spotread` command-line tool.
+#[derive(Debug, Clone)]
+pub struct SpotreadProbe {
+    /// Path to the `spotread` executable.
+    pub executable: PathBuf,
+    /// Extra arguments forwarded verbatim (e.g. `-y l` for display type).
+    pub extra_args: Vec<String>,
+}
+
+impl Default for SpotreadProbe {
+    fn default() -> Self {
+        Self {
+            executable: std::env::var_os(SPOTREAD_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("spotread")),
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+impl SpotreadProbe {
+    /// Take a single emissive reading and return absolute XYZ (cd/m²).
+    ///
+    /// Blocks until the instrument returns, so call it off the main thread.
+    pub fn measure(&self) -> Result<[f32; 3], ProbeError> {
+        let output = Command::new(&self.executable)
+            .arg("-e")
+            .arg("-O")
+            .args(&self.extra_args)
+            .output()
+            .map_err(|source| ProbeError::Spawn {
+                path: self.executable.display().to_string(),
+                source,
+            })?;
+
+        if !output.status.success() {
+            return Err(ProbeError::Exit {
+                status: output.status.to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        parse_spotread_xyz(&String::from_utf8_lossy(&output.stdout)).ok_or(ProbeError::NoReading)
+    }
+}
+
+/// Extract the XYZ triple from `spotread` output.
+///
+/// `spotread` prints readings as `Result is XYZ: 95.1 100.0 108.9, ...`.
+pub fn parse_spotread_xyz(output: &str) -> Option<[f32; 3]> {
+    let line = output
+        .lines()
+        .rev()
+        .find(|l| l.contains("Result is XYZ:"))?;
+    let rest = line.split("XYZ:").nth(1)?;
+    let mut values = rest
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<f32>());
+    let x = values.next()?.ok()?;
+    let y = values.next()?.ok()?;
+    let z = values.next()?.ok()?;
+    Some([x, y, z])
+}
+
+/// Per-patch measurement result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbePatchResult {
+    /// Displayed sRGB-encoded patch value.
+    pub target: [f32; 3],
+    /// Measured absolute XYZ.
+    pub measured_xyz: [f32; 3],
+    /// CIEDE2000 error relative to the expected display response.
+    pub delta_e: f32,
+}
+
+/// Summary of a completed probe check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeReport {
+    pub patches: Vec<ProbePatchResult>,
+    pub mean_delta_e: f32,
+    pub max_delta_e: f32,
+    pub p95_delta_e: f32,
+}
+
+/// Compare measured XYZ readings against the expected sRGB display response.
+///
+/// `measured[0]` must be the white patch; its XYZ is used as the Lab
+/// reference white for all patches.
+pub fn evaluate_measurements(patches: &[[f32; 3]], measured: &[[f32; 3]]) -> ProbeReport {
+    let to_xyz = rgb_to_xyz_matrix(ColorSpaceId::LinearSrgb);
+    let white = measured.first().copied().unwrap_or([0.0; 3]);
+    let white_y = white[1].max(f32::EPSILON);
+    let expected_white = to_xyz.apply([1.0, 1.0, 1.0]).map(|v| v * white_y);
+
+    let results: Vec<ProbePatchResult> = patches
+        .iter()
+        .zip(measured)
+        .map(|(&target, &measured_xyz)| {
+            let linear = target.map(|v| SrgbTransfer.to_linear(v));
+            let expected = to_xyz.apply(linear).map(|v| v * white_y);
+            let delta_e = delta_e_2000(
+                xyz_to_lab(expected, expected_white),
+                xyz_to_lab(measured_xyz, white),
+            );
+            ProbePatchResult {
+                target,
+                measured_xyz,
+                delta_e,
+            }
+        })
+        .collect();
+
+    let values: Vec<f32> = results.iter().map(|r| r.delta_e).collect();
+    let stats = DeltaEStats::from_values(&values);
+    ProbeReport {
+        patches: results,
+        mean_delta_e: stats.mean,
+        max_delta_e: stats.max,
+        p95_delta_e: stats.p95,
+    }
+}
+
+// ── Bevy integration ─────────────────────────────────────────────
+
+/// Request to start a probe check (sent from the UI bridge).
+#[derive(Message, Debug, Clone)]
+pub struct StartProbeCheck;
+
+/// Emitted when a probe check completes or fails.
+#[derive(Message, Debug, Clone)]
+pub enum ProbeCheckFinished {
+    Report(ProbeReport),
+    Failed(String),
+}
+
+/// In-progress probe session state.
+#[derive(Resource, Default)]
+pub struct ProbeSession {
+    probe: SpotreadProbe,
+    active: bool,
+    patch_index: usize,
+    shown_at: Option<Instant>,
+    reading: Option<JoinHandle<Result<[f32; 3], ProbeError>>>,
+    measured: Vec<[f32; 3]>,
+}
+
+/// Registers the probe session resource, messages, and driver systems.
+pub struct ProbeCheckPlugin;
+
+impl Plugin for ProbeCheckPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProbeSession>()
+            .add_message::<StartProbeCheck>()
+            .add_message::<ProbeCheckFinished>()
+            .add_systems(
+                Update,
+                (
+                    start_probe_check,
+                    drive_probe_session,
+                    show_probe_patch.after(crate::ui::viewer::update_viewer_texture),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn start_probe_check(
+    mut requests: MessageReader<StartProbeCheck>,
+    mut session: ResMut<ProbeSession>,
+) {
+    if requests.read().count() == 0 || session.active {
+        return;
+    }
+    tracing::info!(
+        "probe check started ({} patches, {})",
+        VERIFICATION_PATCHES.len(),
+        session.probe.executable.display()
+    );
+    session.active = true;
+    session.patch_index = 0;
+    session.shown_at = None;
+    session.reading = None;
+    session.measured.clear();
+}
+
+/// Advance the session: trigger a reading once the current patch has
+/// settled, and collect the result when the subprocess finishes.
+fn drive_probe_session(
+    mut session: ResMut<ProbeSession>,
+    mut finished: MessageWriter<ProbeCheckFinished>,
+    mut viewer_data: ResMut<ViewerData>,
+) {
+    if !session.active {
+        return;
+    }
+
+    let Some(shown_at) = session.shown_at else {
+        return;
+    };
+
+    if session.reading.is_none() {
+        if shown_at.elapsed() >= PATCH_SETTLE_TIME {
+            let probe = session.probe.clone();
+            session.reading = Some(std::thread::spawn(move || probe.measure()));
+        }
+        return;
+    }
+
+    if !session.reading.as_ref().is_some_and(|h| h.is_finished()) {
+        return;
+    }
+
+    let result = session
+        .reading
+        .take()
+        .map(|h| h.join().unwrap_or(Err(ProbeError::Panicked)))
+        .unwrap_or(Err(ProbeError::Panicked));
+
+    match result {
+        Ok(xyz) => {
+            session.measured.push(xyz);
+            session.patch_index += 1;
+            session.shown_at = None;
+            if session.patch_index >= VERIFICATION_PATCHES.len() {
+                let report = evaluate_measurements(VERIFICATION_PATCHES, &session.measured);
+                tracing::info!(
+                    "probe check done: mean ΔE00 {:.2}, max {:.2}",
+                    report.mean_delta_e,
+                    report.max_delta_e
+                );
+                session.active = false;
+                finished.write(ProbeCheckFinished::Report(report));
+                // Force the viewer to redraw the graded image.
+                viewer_data.set_changed();
+            }
+        }
+        Err(e) => {
+            tracing::error!("probe check failed: {e}");
+            session.active = false;
+            finished.write(ProbeCheckFinished::Failed(e.to_string()));
+            viewer_data.set_changed();
+        }
+    }
+}
+
+/// Fill the viewer texture with the current patch colour.
+///
+/// Runs after `update_viewer_texture` so a GPU readback arriving mid-check
+/// cannot overwrite the patch. Without a viewer texture the check cannot
+/// show anything, so it ends as failed rather than waiting forever.
+fn show_probe_patch(
+    mut session: ResMut<ProbeSession>,
+    mut finished: MessageWriter<ProbeCheckFinished>,
+    viewer_data: Res<ViewerData>,
+    viewer: Option<Res<ViewerImageHandle>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !session.active || (session.shown_at.is_some() && !viewer_data.is_changed()) {
+        return;
+    }
+    let Some(image) = viewer.and_then(|viewer| images.get_mut(&viewer.handle)) else {
+        tracing::error!("probe check failed: {}", ProbeError::NoViewer);
+        session.active = false;
+        session.reading = None;
+        finished.write(ProbeCheckFinished::Failed(ProbeError::NoViewer.to_string()));
+        return;
+    };
+
+    let patch = VERIFICATION_PATCHES[session.patch_index];
+    let [r, g, b] = patch.map(|v| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8);
+    *image = Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[r, g, b, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+
+    if session.shown_at.is_none() {
+        session.shown_at = Some(Instant::now());
+    }
+}
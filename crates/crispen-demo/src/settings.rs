@@ -0,0 +1,715 @@
+//! User preferences that apply while the app runs.
+//!
+//! [`Settings`] is persisted in `settings.json` under the config dir and
+//! edited from the UI's settings panel. Every change is validated as a
+//! whole, saved, and pushed to the resources it controls on the same
+//! frame: [`PipelineSettings`] for the viewer format, LUT size and readback
+//! ring, [`ScopeConfig`] for scope options, [`LogCapture`] for the log
+//! buffer, and [`KeyBindings`] for shortcuts. Autosave, idle frame pacing,
+//! the color wheels, and the viewer's framing guides read their values
+//! straight from the resource.
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use crispen_bevy::ViewerFormat;
+use crispen_bevy::events::ColorGradingCommand;
+use crispen_bevy::resources::{ImageState, PipelineSettings, ScopeConfig};
+use crispen_core::framing::FramingGuides;
+use crispen_core::i18n::Arg;
+use crispen_core::image::SourcePrecision;
+use crispen_core::scopes::{ScopeCadence, ScopeDisplayOptions, ScopeQuality, ScopeResolution};
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::diagnostics::LogCapture;
+use crate::i18n::{tr, tr_args};
+use crate::keymap::{Action, KeyBindings, Shortcut};
+
+/// Pixel format the viewer reads back from the GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewerPrecision {
+    /// 8-bit sRGB, encoded on the GPU. Least bandwidth.
+    Srgb8,
+    /// Linear half float.
+    F16,
+    /// Linear full float. Most bandwidth.
+    F32,
+}
+
+impl From<ViewerPrecision> for ViewerFormat {
+    fn from(precision: ViewerPrecision) -> Self {
+        match precision {
+            ViewerPrecision::Srgb8 => ViewerFormat::Srgb8,
+            ViewerPrecision::F16 => ViewerFormat::F16,
+            ViewerPrecision::F32 => ViewerFormat::F32,
+        }
+    }
+}
+
+/// Scope options applied at startup and whenever the settings change. The
+/// scope panels can still adjust them for the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeSettings {
+    pub display: ScopeDisplayOptions,
+    pub quality: ScopeQuality,
+}
+
+/// Periodic saving of the grade and source image path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutosaveSettings {
+    pub enabled: bool,
+    /// Seconds between saves while the session has unsaved changes.
+    pub interval_secs: u32,
+}
+
+impl AutosaveSettings {
+    /// Allowed autosave intervals.
+    pub const INTERVAL_RANGE: RangeInclusive<u32> = 10..=3600;
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 120,
+        }
+    }
+}
+
+/// Sizes of in-memory buffers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// Log records kept for the diagnostics panel and crash reports.
+    pub log_records: usize,
+    /// Frames that may be in flight between the GPU and the viewer. More
+    /// slots smooth out slow readbacks at the cost of latency and memory.
+    pub readback_slots: usize,
+    /// Baked grading LUTs kept for switching back to a recent grade
+    /// without a re-bake. `0` disables the cache.
+    pub lut_cache: usize,
+    /// Precision loaded sources are held and uploaded in. Half floats
+    /// halve the memory and upload bandwidth of large frames.
+    pub source_precision: SourcePrecision,
+}
+
+impl CacheSettings {
+    /// Allowed log buffer sizes.
+    pub const LOG_RECORDS_RANGE: RangeInclusive<usize> = 100..=100_000;
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            log_records: crate::diagnostics::DEFAULT_CAPTURE_CAPACITY,
+            readback_slots: PipelineSettings::default().readback_slots,
+            lut_cache: PipelineSettings::default().lut_cache,
+            source_precision: SourcePrecision::default(),
+        }
+    }
+}
+
+/// Frame pacing while nothing changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    /// Lower the frame rate once the grade and the UI have been idle.
+    pub idle_saving: bool,
+    /// Frames per second while idle.
+    pub idle_fps: u32,
+}
+
+impl PowerSettings {
+    /// Allowed idle frame rates.
+    pub const IDLE_FPS_RANGE: RangeInclusive<u32> = 1..=30;
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            idle_saving: true,
+            idle_fps: 10,
+        }
+    }
+}
+
+/// Focus-peaking overlay drawn over the viewer image.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusAssist {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// Edge strength, as a Sobel magnitude of gamma-encoded luma in 0–1,
+    /// from which edges are tinted. Lower marks softer detail.
+    pub threshold: f32,
+    /// Tint of marked edges, display-referred RGB in 0–1.
+    pub color: [f32; 3],
+}
+
+impl Default for FocusAssist {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.2,
+            color: [1.0, 0.1, 0.1],
+        }
+    }
+}
+
+/// How the numeric readouts under the color wheels show a channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WheelReadout {
+    /// Signed distance from neutral, e.g. `+0.050`.
+    #[default]
+    Offset,
+    /// Signed distance from neutral in percent, e.g. `+5.0%`.
+    Percent,
+    /// Film lab printer points, twelve to a stop.
+    PrinterPoints,
+}
+
+/// Rendering quality and readouts of the native color wheels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WheelSettings {
+    /// Subsamples per pixel along each axis; 2 renders four per pixel.
+    pub samples: u32,
+    /// Width of anti-aliased edges in physical pixels.
+    pub aa_px: f32,
+    /// Format of the values shown under each wheel.
+    pub readout: WheelReadout,
+}
+
+impl WheelSettings {
+    /// Allowed subsample counts.
+    pub const SAMPLES_RANGE: RangeInclusive<u32> = 1..=4;
+    /// Allowed edge widths.
+    pub const AA_PX_RANGE: RangeInclusive<f32> = 0.5..=4.0;
+}
+
+impl Default for WheelSettings {
+    fn default() -> Self {
+        Self {
+            samples: 2,
+            aa_px: 1.0,
+            readout: WheelReadout::default(),
+        }
+    }
+}
+
+/// Preferences persisted in `settings.json` under [`config_dir`].
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Size of the interface relative to the display's scale factor; 1.5
+    /// makes text and controls half again as large.
+    pub ui_scale: f32,
+    /// Viewer readback format.
+    pub viewer_format: ViewerPrecision,
+    /// Edge length of the baked grading LUT.
+    pub lut_size: u32,
+    pub scopes: ScopeSettings,
+    pub autosave: AutosaveSettings,
+    pub caches: CacheSettings,
+    pub power: PowerSettings,
+    pub wheels: WheelSettings,
+    /// Safe areas, thirds, and aspect mask drawn over the viewer.
+    pub guides: FramingGuides,
+    /// Edge overlay for judging focus and sharpening.
+    pub focus_assist: FocusAssist,
+    /// Shortcut for each [`Action`], e.g. `Ctrl+O`.
+    pub key_bindings: BTreeMap<Action, String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            viewer_format: ViewerPrecision::Srgb8,
+            lut_size: PipelineSettings::default().lut_size,
+            scopes: ScopeSettings::default(),
+            autosave: AutosaveSettings::default(),
+            caches: CacheSettings::default(),
+            power: PowerSettings::default(),
+            wheels: WheelSettings::default(),
+            guides: FramingGuides::default(),
+            focus_assist: FocusAssist::default(),
+            key_bindings: Action::default_bindings(),
+        }
+    }
+}
+
+/// Why a settings change was rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error(
+        "UI scale {0} is outside {min}–{max}",
+        min = Settings::UI_SCALE_RANGE.start(),
+        max = Settings::UI_SCALE_RANGE.end(),
+    )]
+    UiScale(f32),
+    #[error(
+        "LUT size {0} is outside {min}–{max}",
+        min = PipelineSettings::LUT_SIZE_RANGE.start(),
+        max = PipelineSettings::LUT_SIZE_RANGE.end(),
+    )]
+    LutSize(u32),
+    #[error(
+        "scope resolution {0} is outside {min}–{max}",
+        min = ScopeResolution::MIN,
+        max = ScopeResolution::MAX,
+    )]
+    ScopeResolution(u32),
+    #[error("scope frame budget must be a positive number of milliseconds")]
+    FrameBudget,
+    #[error("scope sample stride must be at least 1")]
+    SampleStride,
+    #[error(
+        "scope cadence must update every 1–{max_interval} frames or settle within {max_settle} ms",
+        max_interval = ScopeCadence::MAX_INTERVAL,
+        max_settle = ScopeCadence::MAX_SETTLE_MS,
+    )]
+    ScopeCadence,
+    #[error(
+        "autosave interval {0}s is outside {min}–{max}s",
+        min = AutosaveSettings::INTERVAL_RANGE.start(),
+        max = AutosaveSettings::INTERVAL_RANGE.end(),
+    )]
+    AutosaveInterval(u32),
+    #[error(
+        "log buffer size {0} is outside {min}–{max}",
+        min = CacheSettings::LOG_RECORDS_RANGE.start(),
+        max = CacheSettings::LOG_RECORDS_RANGE.end(),
+    )]
+    LogRecords(usize),
+    #[error(
+        "readback slots {0} is outside {min}–{max}",
+        min = PipelineSettings::READBACK_SLOTS_RANGE.start(),
+        max = PipelineSettings::READBACK_SLOTS_RANGE.end(),
+    )]
+    ReadbackSlots(usize),
+    #[error(
+        "LUT cache size {0} is outside {min}–{max}",
+        min = PipelineSettings::LUT_CACHE_RANGE.start(),
+        max = PipelineSettings::LUT_CACHE_RANGE.end(),
+    )]
+    LutCache(usize),
+    #[error(
+        "idle frame rate {0} is outside {min}–{max}",
+        min = PowerSettings::IDLE_FPS_RANGE.start(),
+        max = PowerSettings::IDLE_FPS_RANGE.end(),
+    )]
+    IdleFps(u32),
+    #[error(
+        "wheel subsamples {0} is outside {min}–{max}",
+        min = WheelSettings::SAMPLES_RANGE.start(),
+        max = WheelSettings::SAMPLES_RANGE.end(),
+    )]
+    WheelSamples(u32),
+    #[error(
+        "wheel edge width {0} px is outside {min}–{max} px",
+        min = WheelSettings::AA_PX_RANGE.start(),
+        max = WheelSettings::AA_PX_RANGE.end(),
+    )]
+    WheelAa(f32),
+    #[error("framing guide sizes and opacities must be 0–1 and the mask aspect positive")]
+    Guides,
+    #[error("focus assist threshold and color must be 0–1")]
+    FocusAssist,
+    #[error("'{binding}' is not a valid shortcut for {action:?}")]
+    KeyBinding { action: Action, binding: String },
+    #[error("{binding} is bound to both {first:?} and {second:?}")]
+    DuplicateBinding {
+        binding: String,
+        first: Action,
+        second: Action,
+    },
+}
+
+impl SettingsError {
+    /// The message in the UI language; `Display` stays English for logs.
+    pub fn localized(&self) -> String {
+        match self {
+            Self::UiScale(scale) => {
+                out_of_range("settings-error-ui-scale", *scale, Settings::UI_SCALE_RANGE)
+            }
+            Self::LutSize(n) => out_of_range(
+                "settings-error-lut-size",
+                *n,
+                PipelineSettings::LUT_SIZE_RANGE,
+            ),
+            Self::ScopeResolution(n) => out_of_range(
+                "settings-error-scope-resolution",
+                *n,
+                ScopeResolution::MIN..=ScopeResolution::MAX,
+            ),
+            Self::FrameBudget => tr("settings-error-frame-budget"),
+            Self::SampleStride => tr("settings-error-sample-stride"),
+            Self::ScopeCadence => tr_args(
+                "settings-error-scope-cadence",
+                &[
+                    ("max_interval", ScopeCadence::MAX_INTERVAL.into()),
+                    ("max_settle", ScopeCadence::MAX_SETTLE_MS.into()),
+                ],
+            ),
+            Self::AutosaveInterval(n) => out_of_range(
+                "settings-error-autosave-interval",
+                *n,
+                AutosaveSettings::INTERVAL_RANGE,
+            ),
+            Self::LogRecords(n) => out_of_range(
+                "settings-error-log-records",
+                *n,
+                CacheSettings::LOG_RECORDS_RANGE,
+            ),
+            Self::ReadbackSlots(n) => out_of_range(
+                "settings-error-readback-slots",
+                *n,
+                PipelineSettings::READBACK_SLOTS_RANGE,
+            ),
+            Self::LutCache(n) => out_of_range(
+                "settings-error-lut-cache",
+                *n,
+                PipelineSettings::LUT_CACHE_RANGE,
+            ),
+            Self::IdleFps(n) => {
+                out_of_range("settings-error-idle-fps", *n, PowerSettings::IDLE_FPS_RANGE)
+            }
+            Self::WheelSamples(n) => out_of_range(
+                "settings-error-wheel-samples",
+                *n,
+                WheelSettings::SAMPLES_RANGE,
+            ),
+            Self::WheelAa(px) => {
+                out_of_range("settings-error-wheel-aa", *px, WheelSettings::AA_PX_RANGE)
+            }
+            Self::Guides => tr("settings-error-guides"),
+            Self::FocusAssist => tr("settings-error-focus-assist"),
+            Self::KeyBinding { action, binding } => tr_args(
+                "settings-error-key-binding",
+                &[
+                    ("binding", binding.as_str().into()),
+                    ("action", action.label().into()),
+                ],
+            ),
+            Self::DuplicateBinding {
+                binding,
+                first,
+                second,
+            } => tr_args(
+                "settings-error-duplicate-binding",
+                &[
+                    ("binding", binding.as_str().into()),
+                    ("first", first.label().into()),
+                    ("second", second.label().into()),
+                ],
+            ),
+        }
+    }
+}
+
+fn out_of_range<T>(id: &str, value: T, range: RangeInclusive<T>) -> String
+where
+    T: Copy + Into<Arg<'static>>,
+{
+    tr_args(
+        id,
+        &[
+            ("value", value.into()),
+            ("min", (*range.start()).into()),
+            ("max", (*range.end()).into()),
+        ],
+    )
+}
+
+impl Settings {
+    const FILE_NAME: &str = "settings.json";
+
+    /// Accepted [`ui_scale`](Self::ui_scale) values.
+    pub const UI_SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+
+    /// Load the settings file, or defaults if it is missing or invalid.
+    /// Actions missing from the file get their default binding.
+    pub fn load() -> Self {
+        let Some(path) = config_dir().map(|dir| dir.join(Self::FILE_NAME)) else {
+            return Self::default();
+        };
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        let mut settings: Self = match serde_json::from_str(&json) {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::warn!("ignoring invalid {}: {e}", path.display());
+                return Self::default();
+            }
+        };
+        for (action, binding) in Action::default_bindings() {
+            settings.key_bindings.entry(action).or_insert(binding);
+        }
+        if let Err(e) = settings.validate() {
+            tracing::warn!("ignoring {}: {e}", path.display());
+            return Self::default();
+        }
+        settings
+    }
+
+    /// Write the settings file, creating the config directory if needed.
+    pub fn save(&self) -> std::io::Result<()> {
+        let dir = config_dir().ok_or_else(|| std::io::Error::other("no config directory"))?;
+        std::fs::create_dir_all(&dir)?;
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(dir.join(Self::FILE_NAME), json)
+    }
+
+    /// Check every value against its allowed range and every key binding
+    /// for syntax and conflicts.
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if !Self::UI_SCALE_RANGE.contains(&self.ui_scale) {
+            return Err(SettingsError::UiScale(self.ui_scale));
+        }
+        if !PipelineSettings::LUT_SIZE_RANGE.contains(&self.lut_size) {
+            return Err(SettingsError::LutSize(self.lut_size));
+        }
+
+        let quality = &self.scopes.quality;
+        let resolution = quality.resolution;
+        for dim in [
+            resolution.waveform_height,
+            resolution.vectorscope,
+            resolution.cie,
+        ] {
+            if !(ScopeResolution::MIN..=ScopeResolution::MAX).contains(&dim) {
+                return Err(SettingsError::ScopeResolution(dim));
+            }
+        }
+        if !(quality.frame_budget_ms.is_finite() && quality.frame_budget_ms > 0.0) {
+            return Err(SettingsError::FrameBudget);
+        }
+        if quality.sample_stride == Some(0) {
+            return Err(SettingsError::SampleStride);
+        }
+        if !quality.cadence.is_valid() {
+            return Err(SettingsError::ScopeCadence);
+        }
+
+        if !AutosaveSettings::INTERVAL_RANGE.contains(&self.autosave.interval_secs) {
+            return Err(SettingsError::AutosaveInterval(self.autosave.interval_secs));
+        }
+        if !CacheSettings::LOG_RECORDS_RANGE.contains(&self.caches.log_records) {
+            return Err(SettingsError::LogRecords(self.caches.log_records));
+        }
+        if !PipelineSettings::READBACK_SLOTS_RANGE.contains(&self.caches.readback_slots) {
+            return Err(SettingsError::ReadbackSlots(self.caches.readback_slots));
+        }
+        if !PipelineSettings::LUT_CACHE_RANGE.contains(&self.caches.lut_cache) {
+            return Err(SettingsError::LutCache(self.caches.lut_cache));
+        }
+        if !PowerSettings::IDLE_FPS_RANGE.contains(&self.power.idle_fps) {
+            return Err(SettingsError::IdleFps(self.power.idle_fps));
+        }
+        if !WheelSettings::SAMPLES_RANGE.contains(&self.wheels.samples) {
+            return Err(SettingsError::WheelSamples(self.wheels.samples));
+        }
+        if !WheelSettings::AA_PX_RANGE.contains(&self.wheels.aa_px) {
+            return Err(SettingsError::WheelAa(self.wheels.aa_px));
+        }
+        let guides = &self.guides;
+        let unit = 0.0..=1.0;
+        if ![
+            guides.action_safe_size,
+            guides.title_safe_size,
+            guides.mask_opacity,
+            guides.line_opacity,
+        ]
+        .iter()
+        .chain(&guides.line_color)
+        .all(|v| unit.contains(v))
+            || guides.aspect.is_some_and(|a| !(a.is_finite() && a > 0.0))
+        {
+            return Err(SettingsError::Guides);
+        }
+        let focus = &self.focus_assist;
+        if !std::iter::once(&focus.threshold)
+            .chain(&focus.color)
+            .all(|v| unit.contains(v))
+        {
+            return Err(SettingsError::FocusAssist);
+        }
+
+        let mut seen: BTreeMap<String, Action> = BTreeMap::new();
+        for (action, binding) in &self.key_bindings {
+            let shortcut = Shortcut::parse(binding).ok_or_else(|| SettingsError::KeyBinding {
+                action: *action,
+                binding: binding.clone(),
+            })?;
+            let canonical = shortcut.to_string();
+            if let Some(first) = seen.insert(canonical.clone(), *action) {
+                return Err(SettingsError::DuplicateBinding {
+                    binding: canonical,
+                    first,
+                    second: *action,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn pipeline(&self) -> PipelineSettings {
+        PipelineSettings {
+            lut_size: self.lut_size,
+            viewer_format: self.viewer_format.into(),
+            readback_slots: self.caches.readback_slots,
+            lut_cache: self.caches.lut_cache,
+            ..PipelineSettings::default()
+        }
+    }
+}
+
+/// Requests from the UI's settings panel.
+#[derive(Message, Debug, Clone)]
+pub enum SettingsCommand {
+    /// The UI connected or opened the settings panel; report the current
+    /// settings.
+    Query,
+    /// Validate, save and apply these settings.
+    Update(Settings),
+    /// Change only the framing guides, e.g. from the viewer toolbar.
+    SetGuides(FramingGuides),
+    /// Change only the focus assist overlay, e.g. from its shortcut.
+    SetFocusAssist(FocusAssist),
+    /// Restore and save the defaults.
+    Reset,
+}
+
+/// Emitted after [`SettingsCommand`]s, with the settings now in effect and
+/// the reason if an update was rejected.
+#[derive(Message, Debug, Clone)]
+pub struct SettingsChanged {
+    pub settings: Settings,
+    pub error: Option<String>,
+}
+
+/// Loads [`Settings`], keeps the resources they control in sync, and
+/// handles [`SettingsCommand`]s.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let settings = Settings::load();
+        app.insert_resource(settings.pipeline())
+            .insert_resource(KeyBindings::from_bindings(&settings.key_bindings))
+            .insert_resource(settings)
+            .add_message::<SettingsCommand>()
+            .add_message::<SettingsChanged>()
+            .add_systems(
+                Update,
+                (
+                    handle_settings_commands,
+                    apply_settings.after(handle_settings_commands),
+                ),
+            );
+    }
+}
+
+fn handle_settings_commands(
+    mut commands: MessageReader<SettingsCommand>,
+    mut settings: ResMut<Settings>,
+    mut changed: MessageWriter<SettingsChanged>,
+) {
+    for command in commands.read() {
+        let error = match command {
+            SettingsCommand::Query => None,
+            SettingsCommand::Update(new) => update(&mut settings, new.clone()),
+            SettingsCommand::SetGuides(guides) => {
+                let new = Settings {
+                    guides: *guides,
+                    ..settings.clone()
+                };
+                update(&mut settings, new)
+            }
+            SettingsCommand::SetFocusAssist(focus_assist) => {
+                let new = Settings {
+                    focus_assist: *focus_assist,
+                    ..settings.clone()
+                };
+                update(&mut settings, new)
+            }
+            SettingsCommand::Reset => {
+                settings.set_if_neq(Settings::default());
+                save_or_report(&settings)
+            }
+        };
+        changed.write(SettingsChanged {
+            settings: settings.clone(),
+            error,
+        });
+    }
+}
+
+/// Validate, apply, and save `new`; the localized reason if it was
+/// rejected or not saved.
+fn update(settings: &mut ResMut<Settings>, new: Settings) -> Option<String> {
+    match new.validate() {
+        Ok(()) => {
+            settings.set_if_neq(new);
+            save_or_report(settings)
+        }
+        Err(e) => {
+            tracing::warn!("rejected settings: {e}");
+            Some(e.localized())
+        }
+    }
+}
+
+fn save_or_report(settings: &Settings) -> Option<String> {
+    settings.save().err().map(|e| {
+        tracing::warn!("failed to save settings: {e}");
+        tr_args("settings-not-saved", &[("error", e.to_string().into())])
+    })
+}
+
+/// Push changed settings to the resources they control.
+#[allow(clippy::too_many_arguments)]
+fn apply_settings(
+    settings: Res<Settings>,
+    mut pipeline: ResMut<PipelineSettings>,
+    mut images: ResMut<ImageState>,
+    mut bindings: ResMut<KeyBindings>,
+    mut ui_scale: ResMut<UiScale>,
+    scopes: Res<ScopeConfig>,
+    capture: Option<ResMut<LogCapture>>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    pipeline.set_if_neq(settings.pipeline());
+    // Applies to the next source loaded; the current one keeps its storage.
+    if images.precision != settings.caches.source_precision {
+        images.precision = settings.caches.source_precision;
+    }
+    if ui_scale.0 != settings.ui_scale {
+        ui_scale.0 = settings.ui_scale;
+    }
+    *bindings = KeyBindings::from_bindings(&settings.key_bindings);
+    if let Some(mut capture) = capture {
+        capture.set_capacity(settings.caches.log_records);
+    }
+    if scopes.display != settings.scopes.display {
+        grading.write(ColorGradingCommand::SetScopeDisplayOptions {
+            options: settings.scopes.display.clone(),
+        });
+    }
+    if scopes.quality != settings.scopes.quality {
+        grading.write(ColorGradingCommand::SetScopeQuality {
+            quality: settings.scopes.quality,
+        });
+    }
+}
@@ -0,0 +1,133 @@
+//! First-run setup wizard backend.
+//!
+//! Applies the persisted [`UserConfig`] at startup and drives the UI's setup
+//! wizard: the wizard is offered when the UI connects and setup has not been
+//! completed, and again whenever the user re-runs it. Completing it saves
+//! `config.json` and applies the working space and display space
+//! immediately; color management and the GPU adapter are startup choices and
+//! apply on the next launch.
+
+use bevy::prelude::*;
+use crispen_bevy::events::ColorGradingCommand;
+use crispen_bevy::render_node::OverlayComposite;
+use crispen_bevy::resources::{GpuAdapterPreference, GradingState};
+
+use crate::config::UserConfig;
+
+/// Requests from the UI's setup wizard.
+#[derive(Message, Debug, Clone)]
+pub enum SetupCommand {
+    /// The UI connected; offer the wizard if setup is incomplete.
+    Query,
+    /// Show the wizard again, e.g. from a settings command.
+    Run,
+    /// The wizard finished with these choices.
+    Complete(UserConfig),
+}
+
+/// The UI should show the setup wizard.
+#[derive(Message, Debug, Clone)]
+pub struct SetupOffered {
+    /// Current choices, used as the wizard's starting values.
+    pub config: UserConfig,
+    /// GPU adapter names available on this machine.
+    pub adapters: Vec<String>,
+    /// Whether OCIO color management is compiled in.
+    pub ocio_available: bool,
+    /// `true` on first run, `false` when re-run by the user.
+    pub first_run: bool,
+}
+
+/// Adapter names, enumerated once when the wizard is first shown.
+#[derive(Resource, Default)]
+struct AdapterList(Option<Vec<String>>);
+
+/// Loads [`UserConfig`], inserts the GPU adapter preference it names, and
+/// handles [`SetupCommand`]s.
+pub struct SetupPlugin;
+
+impl Plugin for SetupPlugin {
+    fn build(&self, app: &mut App) {
+        let config = UserConfig::load();
+        app.insert_resource(GpuAdapterPreference {
+            name: config.gpu_adapter.clone(),
+        })
+        .insert_resource(config)
+        .init_resource::<AdapterList>()
+        .add_message::<SetupCommand>()
+        .add_message::<SetupOffered>()
+        .add_systems(PreStartup, (apply_working_space, apply_display_space))
+        .add_systems(Update, handle_setup_commands);
+    }
+}
+
+fn apply_working_space(config: Res<UserConfig>, mut state: ResMut<GradingState>) {
+    state.params.color_management.working_space = config.working_space;
+}
+
+/// The UI overlay is only composited natively under CEF, so there may be
+/// nothing to apply the display space to.
+fn apply_display_space(config: Res<UserConfig>, overlay: Option<ResMut<OverlayComposite>>) {
+    if let Some(mut overlay) = overlay {
+        overlay.display_space = config.display_space;
+    }
+}
+
+fn handle_setup_commands(
+    mut commands: MessageReader<SetupCommand>,
+    mut config: ResMut<UserConfig>,
+    mut adapters: ResMut<AdapterList>,
+    state: Res<GradingState>,
+    mut overlay: Option<ResMut<OverlayComposite>>,
+    mut offers: MessageWriter<SetupOffered>,
+    mut grading: MessageWriter<ColorGradingCommand>,
+) {
+    for command in commands.read() {
+        match command {
+            SetupCommand::Query | SetupCommand::Run => {
+                let first_run = !config.setup_complete;
+                if matches!(command, SetupCommand::Query) && !first_run {
+                    continue;
+                }
+                let adapters = adapters
+                    .0
+                    .get_or_insert_with(|| {
+                        crispen_gpu::available_adapters()
+                            .into_iter()
+                            .map(|info| info.name)
+                            .collect()
+                    })
+                    .clone();
+                offers.write(SetupOffered {
+                    config: config.clone(),
+                    adapters,
+                    ocio_available: cfg!(feature = "ocio"),
+                    first_run,
+                });
+            }
+            SetupCommand::Complete(choices) => {
+                let mut choices = choices.clone();
+                choices.setup_complete = true;
+                if choices.color_management != config.color_management
+                    || choices.gpu_adapter != config.gpu_adapter
+                {
+                    tracing::info!("color management and GPU adapter changes apply on restart");
+                }
+                if choices.working_space != state.params.color_management.working_space {
+                    let mut params = state.params.clone();
+                    params.color_management.working_space = choices.working_space;
+                    grading.write(ColorGradingCommand::SetParams { params });
+                }
+                if let Some(overlay) = overlay.as_mut()
+                    && overlay.display_space != choices.display_space
+                {
+                    overlay.display_space = choices.display_space;
+                }
+                if let Err(e) = choices.save() {
+                    tracing::warn!("failed to save config: {e}");
+                }
+                *config = choices;
+            }
+        }
+    }
+}
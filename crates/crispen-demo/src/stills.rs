@@ -0,0 +1,91 @@
+//! Writing rendered stills to disk.
+//!
+//! `crispen-bevy` renders bracketed stills but has no image encoder, so the
+//! demo writes each [`BracketRenderedEvent`] still here and reports it as a
+//! completed `bracket` export, which also fires the export hook. The
+//! gallery reads and writes its stills through the same helpers.
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use crispen_bevy::events::{BracketRenderedEvent, ExportCompletedEvent};
+use crispen_core::image::{BitDepth, GradingImage};
+use crispen_core::project::dpx;
+
+/// Writes bracketed stills.
+pub struct StillsPlugin;
+
+impl Plugin for StillsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, save_bracket_stills);
+    }
+}
+
+fn save_bracket_stills(
+    mut rendered: MessageReader<BracketRenderedEvent>,
+    mut exported: MessageWriter<ExportCompletedEvent>,
+) {
+    for still in rendered.read().flat_map(|event| &event.stills) {
+        match save_still(&still.image, &still.path, &still.label) {
+            Ok(()) => {
+                tracing::info!("Bracket still {}: {}", still.label, still.path.display());
+                exported.write(ExportCompletedEvent {
+                    path: still.path.display().to_string(),
+                    kind: "bracket",
+                });
+            }
+            Err(e) => tracing::error!(
+                "Bracket still {}: failed to write {}: {e}",
+                still.label,
+                still.path.display()
+            ),
+        }
+    }
+}
+
+/// Write output code values, choosing the sample type from the extension:
+/// float for EXR, 10-bit DPX labelled `label`, 16-bit for PNG/TIFF, 8-bit
+/// otherwise.
+pub fn save_still(image: &GradingImage, path: &Path, label: &str) -> image::ImageResult<()> {
+    let data = image.pixels.iter().flatten().copied().collect();
+    let buffer = image::Rgba32FImage::from_raw(image.width, image.height, data)
+        .expect("pixel count matches dimensions");
+    let still = image::DynamicImage::ImageRgba32F(buffer);
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "exr" => still.save(path),
+        "dpx" => dpx::write_dpx(path, image, label).map_err(dpx_error),
+        "png" | "tif" | "tiff" => image::DynamicImage::ImageRgba16(still.to_rgba16()).save(path),
+        "jpg" | "jpeg" => image::DynamicImage::ImageRgb8(still.to_rgb8()).save(path),
+        _ => image::DynamicImage::ImageRgba8(still.to_rgba8()).save(path),
+    }
+}
+
+/// Read a still written by [`save_still`] or another tool as code values.
+pub fn load_still(path: &Path) -> image::ImageResult<GradingImage> {
+    let is_dpx = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("dpx"));
+    if is_dpx {
+        return dpx::read_dpx(path).map_err(dpx_error);
+    }
+    let still = image::open(path)?.to_rgba32f();
+    Ok(GradingImage {
+        width: still.width(),
+        height: still.height(),
+        pixels: still.pixels().map(|p| p.0).collect(),
+        source_bit_depth: BitDepth::F32,
+    })
+}
+
+fn dpx_error(e: dpx::DpxError) -> image::ImageError {
+    match e {
+        dpx::DpxError::Io(e) => image::ImageError::IoError(e),
+        e => image::ImageError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+    }
+}
@@ -0,0 +1,93 @@
+//! Chart picker — click the four corners of a ColorChecker in the viewer to
+//! fit a grade-to-chart input correction.
+//!
+//! Press `M` to arm the picker, then click the chart's outer corners in
+//! order: top-left (dark skin), top-right, bottom-right, bottom-left. After
+//! the fourth click a `MatchChart` command is sent and the picker disarms.
+//! `Escape` cancels.
+
+use bevy::picking::events::{Click, Pointer};
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, ComputedUiRenderTargetInfo, UiGlobalTransform, UiScale};
+
+use crispen_bevy::events::ColorGradingCommand;
+
+use super::scope_mask::pointer_to_image_uv;
+use super::split_viewer::GradedImageNode;
+use crate::keymap::{Action, KeyBindings};
+
+/// UI-side state for the chart corner picker.
+#[derive(Resource, Default)]
+pub struct ChartPickerState {
+    /// Whether clicks on the viewer are captured as chart corners.
+    pub active: bool,
+    /// Corners picked so far (normalized image UV).
+    pub corners: Vec<Vec2>,
+}
+
+pub struct ChartPickerPlugin;
+
+impl Plugin for ChartPickerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChartPickerState>()
+            .add_systems(Update, handle_chart_picker_shortcuts)
+            .add_observer(on_viewer_click);
+    }
+}
+
+fn handle_chart_picker_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut state: ResMut<ChartPickerState>,
+) {
+    if bindings.just_pressed(Action::PickChart, &keys) && !state.active {
+        state.active = true;
+        state.corners.clear();
+        tracing::info!("chart picker armed: click TL, TR, BR, BL corners");
+    } else if keys.just_pressed(KeyCode::Escape) && state.active {
+        state.active = false;
+        state.corners.clear();
+    }
+}
+
+fn on_viewer_click(
+    mut click: On<Pointer<Click>>,
+    mut state: ResMut<ChartPickerState>,
+    mut commands: MessageWriter<ColorGradingCommand>,
+    q_graded: Query<
+        (
+            &ComputedNode,
+            &ComputedUiRenderTargetInfo,
+            &UiGlobalTransform,
+        ),
+        With<GradedImageNode>,
+    >,
+    ui_scale: Res<UiScale>,
+) {
+    if !state.active {
+        return;
+    }
+
+    let target = click.event_target();
+    let Ok((node, node_target, transform)) = q_graded.get(target) else {
+        return;
+    };
+
+    click.propagate(false);
+
+    let uv = pointer_to_image_uv(
+        click.pointer_location.position,
+        node,
+        node_target,
+        transform,
+        ui_scale.0,
+    );
+    state.corners.push(uv);
+
+    if state.corners.len() == 4 {
+        let corners = [0, 1, 2, 3].map(|i| state.corners[i].to_array());
+        commands.write(ColorGradingCommand::MatchChart { corners });
+        state.active = false;
+        state.corners.clear();
+    }
+}
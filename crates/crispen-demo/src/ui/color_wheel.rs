@@ -4,28 +4,40 @@
 //! shader with pointer-based drag interaction. Follows the bevy_feathers
 //! `ColorPlane` pattern for observers, coordinate conversion, and
 //! `ValueChange<Vec2>` emission.
+//!
+//! The value indicator, saturation shading, and reset tick are drawn by the
+//! shader rather than by child nodes, so the indicator can glide to values
+//! set elsewhere (reset, auto balance, undo) without relayout. Supersampling
+//! and anti-aliasing width come from [`WheelSettings`].
+//!
+//! Mouse, touch, and pen all arrive as picking pointers. A drag belongs to
+//! the pointer that started it, so a second finger or a resting palm can't
+//! fight it for the indicator.
 
 use bevy::asset::embedded_asset;
 use bevy::picking::{
-    Pickable,
     events::{Cancel, Drag, DragEnd, DragStart, Pointer, Press},
+    pointer::PointerId,
 };
 use bevy::prelude::*;
 use bevy::render::render_resource::AsBindGroup;
 use bevy::shader::ShaderRef;
 use bevy::ui::{
     ComputedNode, ComputedUiRenderTargetInfo, InteractionDisabled, UiGlobalTransform, UiScale,
-    UiTransform, Val2,
 };
 use bevy::ui_render::prelude::{MaterialNode, UiMaterial, UiMaterialPlugin};
 use bevy::ui_widgets::ValueChange;
 
 use super::master_slider;
 use super::theme;
+use super::wheel_readout;
+use crate::i18n::tr;
+use crate::settings::{Settings, WheelSettings};
 
 // ── Constants ───────────────────────────────────────────────────────────────
 
-const THUMB_SIZE: f32 = 10.0;
+/// Rate at which the indicator closes the gap to its target, per second.
+const INDICATOR_RATE: f32 = 18.0;
 
 // ── Components ──────────────────────────────────────────────────────────────
 
@@ -40,50 +52,75 @@ pub enum WheelType {
 
 impl WheelType {
     /// Human-readable label for display below the wheel.
-    pub fn label(self) -> &'static str {
-        match self {
-            Self::Lift => "LIFT",
-            Self::Gamma => "GAMMA",
-            Self::Gain => "GAIN",
-            Self::Offset => "OFFSET",
-        }
+    pub fn label(self) -> String {
+        tr(match self {
+            Self::Lift => "wheel-lift",
+            Self::Gamma => "wheel-gamma",
+            Self::Gain => "wheel-gain",
+            Self::Offset => "wheel-offset",
+        })
     }
 }
 
-/// Tracks whether the user is actively dragging within this wheel.
+/// The pointer currently dragging within this wheel, if any.
 #[derive(Component, Default)]
-struct ColorWheelDragState(bool);
+struct ColorWheelDragState(Option<PointerId>);
 
 /// Marker for the inner node that receives the `MaterialNode`.
 #[derive(Component, Default)]
 struct ColorWheelInner;
 
-/// Marker for the positioned thumb dot.
+/// Indicator position in -1..1 from center: the value it shows and the
+/// value it is animating toward.
 #[derive(Component, Default)]
-struct ColorWheelThumb;
+pub struct WheelIndicator {
+    pub target: Vec2,
+    shown: Vec2,
+}
+
+impl WheelIndicator {
+    /// Move the indicator to `value` at once.
+    fn snap(&mut self, value: Vec2) {
+        self.target = value;
+        self.shown = value;
+    }
+}
 
 // ── Material ────────────────────────────────────────────────────────────────
 
 /// UiMaterial driving the color wheel fragment shader.
 #[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
 pub struct ColorWheelMaterial {
-    /// Cursor X position, normalized to -1..1 from center.
+    /// Indicator X position, normalized to -1..1 from center.
     #[uniform(0)]
     pub cursor_x: f32,
-    /// Cursor Y position, normalized to -1..1 from center.
+    /// Indicator Y position, normalized to -1..1 from center.
     #[uniform(0)]
     pub cursor_y: f32,
     /// Master channel brightness overlay (0..1 typical).
     #[uniform(0)]
     pub master: f32,
+    /// Rendered wheel size in physical pixels.
+    #[uniform(0)]
+    pub size_px: f32,
+    /// Anti-aliasing edge width in physical pixels.
+    #[uniform(0)]
+    pub aa_px: f32,
+    /// Subsamples per pixel along each axis.
+    #[uniform(0)]
+    pub samples: u32,
 }
 
 impl Default for ColorWheelMaterial {
     fn default() -> Self {
+        let settings = WheelSettings::default();
         Self {
             cursor_x: 0.0,
             cursor_y: 0.0,
             master: 0.5,
+            size_px: theme::WHEEL_SIZE,
+            aa_px: settings.aa_px,
+            samples: settings.samples,
         }
     }
 }
@@ -115,7 +152,8 @@ pub fn color_wheel(wheel_type: WheelType) -> impl Bundle {
         wheel_type,
         ColorWheelDragState::default(),
         children![
-            // Inner node: receives MaterialNode and handles picking.
+            // Inner node: receives MaterialNode, draws the indicator, and
+            // handles picking.
             (
                 Node {
                     width: Val::Px(theme::WHEEL_SIZE),
@@ -127,33 +165,7 @@ pub fn color_wheel(wheel_type: WheelType) -> impl Bundle {
                 },
                 ColorWheelInner,
                 BackgroundColor(Color::srgb(0.12, 0.12, 0.12)),
-                children![
-                    // Thumb dot: positioned absolutely, ignores picking.
-                    (
-                        Node {
-                            position_type: PositionType::Absolute,
-                            left: Val::Percent(50.0),
-                            top: Val::Percent(50.0),
-                            width: Val::Px(THUMB_SIZE),
-                            height: Val::Px(THUMB_SIZE),
-                            border: UiRect::all(Val::Px(1.0)),
-                            border_radius: BorderRadius::MAX,
-                            ..Default::default()
-                        },
-                        ColorWheelThumb,
-                        BorderColor::all(Color::WHITE),
-                        Outline {
-                            width: Val::Px(1.0),
-                            offset: Val::Px(0.0),
-                            color: Color::BLACK,
-                        },
-                        Pickable::IGNORE,
-                        UiTransform::from_translation(Val2::new(
-                            Val::Percent(-50.0),
-                            Val::Percent(-50.0),
-                        )),
-                    )
-                ],
+                WheelIndicator::default(),
             ),
             // Label text below the wheel.
             (
@@ -166,6 +178,8 @@ pub fn color_wheel(wheel_type: WheelType) -> impl Bundle {
             ),
             // Horizontal master-level slider.
             master_slider::master_slider(wheel_type),
+            // R, G, B, and master values.
+            wheel_readout::wheel_readout(wheel_type),
         ],
     )
 }
@@ -190,7 +204,7 @@ fn pointer_to_normalized(
 
 fn on_pointer_press(
     mut press: On<Pointer<Press>>,
-    q_wheels: Query<Has<InteractionDisabled>, With<WheelType>>,
+    q_wheels: Query<(&ColorWheelDragState, Has<InteractionDisabled>), With<WheelType>>,
     q_inner: Query<
         (
             &ComputedNode,
@@ -204,10 +218,10 @@ fn on_pointer_press(
     mut commands: Commands,
 ) {
     if let Ok((node, node_target, transform, parent)) = q_inner.get(press.entity)
-        && let Ok(disabled) = q_wheels.get(parent.0)
+        && let Ok((state, disabled)) = q_wheels.get(parent.0)
     {
         press.propagate(false);
-        if !disabled {
+        if !disabled && state.0.is_none() {
             let new_value = pointer_to_normalized(
                 press.pointer_location.position,
                 node,
@@ -232,8 +246,8 @@ fn on_drag_start(
         && let Ok((mut state, disabled)) = q_wheels.get_mut(parent.0)
     {
         drag_start.propagate(false);
-        if !disabled {
-            state.0 = true;
+        if !disabled && state.0.is_none() {
+            state.0 = Some(drag_start.pointer_id);
         }
     }
 }
@@ -257,7 +271,7 @@ fn on_drag(
         && let Ok((state, disabled)) = q_wheels.get(parent.0)
     {
         drag.propagate(false);
-        if state.0 && !disabled {
+        if state.0 == Some(drag.pointer_id) && !disabled {
             let new_value = pointer_to_normalized(
                 drag.pointer_location.position,
                 node,
@@ -282,7 +296,9 @@ fn on_drag_end(
         && let Ok(mut state) = q_wheels.get_mut(parent.0)
     {
         drag_end.propagate(false);
-        state.0 = false;
+        if state.0 == Some(drag_end.pointer_id) {
+            state.0 = None;
+        }
     }
 }
 
@@ -293,81 +309,107 @@ fn on_drag_cancel(
 ) {
     if let Ok(parent) = q_inner.get(drag_cancel.entity)
         && let Ok(mut state) = q_wheels.get_mut(parent.0)
+        && state.0 == Some(drag_cancel.pointer_id)
     {
-        state.0 = false;
+        state.0 = None;
     }
 }
 
 // ── Systems ─────────────────────────────────────────────────────────────────
 
-/// Ensures each wheel's inner node has a `MaterialNode<ColorWheelMaterial>`,
-/// and keeps cursor/master uniforms in sync with external param changes.
-/// Also updates the thumb position.
+/// Ensures each wheel's inner node has a `MaterialNode<ColorWheelMaterial>`
+/// and keeps its size and quality uniforms in sync with the layout and
+/// [`WheelSettings`].
+#[allow(clippy::type_complexity)]
 fn update_wheel_material(
-    q_wheels: Query<Entity, With<WheelType>>,
-    q_children: Query<&Children>,
-    q_material_node: Query<&MaterialNode<ColorWheelMaterial>>,
-    mut q_node: Query<&mut Node>,
+    q_inner: Query<
+        (
+            Entity,
+            &ComputedNode,
+            Option<&MaterialNode<ColorWheelMaterial>>,
+        ),
+        With<ColorWheelInner>,
+    >,
+    settings: Res<Settings>,
     mut materials: ResMut<Assets<ColorWheelMaterial>>,
     mut commands: Commands,
 ) {
-    for wheel_ent in q_wheels.iter() {
-        let Ok(children) = q_children.get(wheel_ent) else {
+    let quality = &settings.wheels;
+    for (inner_ent, node, material) in q_inner.iter() {
+        let size_px = node.size().x.max(1.0);
+        let Some(material) = material else {
+            let handle = materials.add(ColorWheelMaterial {
+                size_px,
+                aa_px: quality.aa_px,
+                samples: quality.samples,
+                ..Default::default()
+            });
+            commands.entity(inner_ent).insert(MaterialNode(handle));
             continue;
         };
-        // First child is the inner node.
-        let Some(&inner_ent) = children.first() else {
+        // Only touch the asset when something changed, so it is not
+        // re-uploaded every frame.
+        let Some(mat) = materials.get(material.id()) else {
             continue;
         };
-
-        if q_material_node.get(inner_ent).is_err() {
-            // First time: insert the material and center the thumb.
-            let handle = materials.add(ColorWheelMaterial::default());
-            commands.entity(inner_ent).insert(MaterialNode(handle));
-
-            let Ok(inner_children) = q_children.get(inner_ent) else {
-                continue;
-            };
-            let Some(&thumb_ent) = inner_children.first() else {
-                continue;
-            };
-            let Ok(mut thumb_node) = q_node.get_mut(thumb_ent) else {
-                continue;
-            };
-            thumb_node.left = Val::Percent(50.0);
-            thumb_node.top = Val::Percent(50.0);
+        if (mat.size_px != size_px || mat.aa_px != quality.aa_px || mat.samples != quality.samples)
+            && let Some(mat) = materials.get_mut(material.id())
+        {
+            mat.size_px = size_px;
+            mat.aa_px = quality.aa_px;
+            mat.samples = quality.samples;
         }
     }
 }
 
-/// Updates the thumb position when a `ValueChange<Vec2>` is received
-/// for a wheel entity. Value is in 0..1 space.
-fn update_wheel_thumb(
+/// Moves the indicator straight to a `ValueChange<Vec2>` from the wheel
+/// itself, so it tracks the pointer without lag. Value is in 0..1 space.
+fn snap_wheel_indicator(
     event: On<ValueChange<Vec2>>,
-    q_wheels: Query<Entity, With<WheelType>>,
-    q_children: Query<&Children>,
-    mut q_node: Query<&mut Node>,
+    q_wheels: Query<&Children, With<WheelType>>,
+    mut q_indicator: Query<&mut WheelIndicator>,
 ) {
-    let Ok(wheel_ent) = q_wheels.get(event.source) else {
-        return;
-    };
-    let Ok(children) = q_children.get(wheel_ent) else {
+    let Ok(children) = q_wheels.get(event.source) else {
         return;
     };
+    // First child is the inner node.
     let Some(&inner_ent) = children.first() else {
         return;
     };
-    let Ok(inner_children) = q_children.get(inner_ent) else {
-        return;
-    };
-    let Some(&thumb_ent) = inner_children.first() else {
-        return;
-    };
-    let Ok(mut thumb_node) = q_node.get_mut(thumb_ent) else {
-        return;
-    };
-    thumb_node.left = Val::Percent(event.value.x * 100.0);
-    thumb_node.top = Val::Percent(event.value.y * 100.0);
+    if let Ok(mut indicator) = q_indicator.get_mut(inner_ent) {
+        indicator.snap((event.value - Vec2::splat(0.5)) * 2.0);
+    }
+}
+
+/// Glides each indicator toward its target and writes the shown position
+/// to the material.
+fn animate_wheel_indicators(
+    time: Res<Time>,
+    mut q_inner: Query<(&mut WheelIndicator, &MaterialNode<ColorWheelMaterial>)>,
+    mut materials: ResMut<Assets<ColorWheelMaterial>>,
+) {
+    let t = 1.0 - (-INDICATOR_RATE * time.delta_secs()).exp();
+    for (mut indicator, material) in &mut q_inner {
+        let Some(mat) = materials.get(material.id()) else {
+            continue;
+        };
+        if mat.cursor_x == indicator.shown.x
+            && mat.cursor_y == indicator.shown.y
+            && indicator.shown == indicator.target
+        {
+            continue;
+        }
+        let shown = if indicator.shown.distance(indicator.target) < 1e-3 {
+            indicator.target
+        } else {
+            indicator.shown.lerp(indicator.target, t)
+        };
+        indicator.shown = shown;
+        if let Some(mat) = materials.get_mut(material.id()) {
+            mat.cursor_x = shown.x;
+            mat.cursor_y = shown.y;
+        }
+    }
 }
 
 // ── Plugin ──────────────────────────────────────────────────────────────────
@@ -379,12 +421,15 @@ impl Plugin for ColorWheelPlugin {
     fn build(&self, app: &mut App) {
         embedded_asset!(app, "shaders/color_wheel.wgsl");
         app.add_plugins(UiMaterialPlugin::<ColorWheelMaterial>::default());
-        app.add_systems(PostUpdate, update_wheel_material);
+        app.add_systems(
+            PostUpdate,
+            (update_wheel_material, animate_wheel_indicators).chain(),
+        );
         app.add_observer(on_pointer_press)
             .add_observer(on_drag_start)
             .add_observer(on_drag)
             .add_observer(on_drag_end)
             .add_observer(on_drag_cancel)
-            .add_observer(update_wheel_thumb);
+            .add_observer(snap_wheel_indicator);
     }
 }
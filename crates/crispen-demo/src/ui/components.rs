@@ -2,6 +2,8 @@
 
 use bevy::prelude::*;
 
+use crate::i18n::tr;
+
 /// Identifies which `GradingParams` field a control (dial or wheel) manages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
 pub enum ParamId {
@@ -56,17 +58,17 @@ pub fn param_step(id: ParamId) -> f32 {
 }
 
 /// Human-readable short label for a given param.
-pub fn param_label(id: ParamId) -> &'static str {
-    match id {
-        ParamId::Temperature => "TEMP",
-        ParamId::Tint => "TINT",
-        ParamId::Contrast => "CONTRAST",
-        ParamId::Pivot => "PIVOT",
-        ParamId::MidtoneDetail => "MID DETAIL",
-        ParamId::Shadows => "SHADOWS",
-        ParamId::Highlights => "HIGHLIGHTS",
-        ParamId::Saturation => "SATURATION",
-        ParamId::Hue => "HUE",
-        ParamId::LumaMix => "LUMA MIX",
-    }
+pub fn param_label(id: ParamId) -> String {
+    tr(match id {
+        ParamId::Temperature => "param-temperature",
+        ParamId::Tint => "param-tint",
+        ParamId::Contrast => "param-contrast",
+        ParamId::Pivot => "param-pivot",
+        ParamId::MidtoneDetail => "param-midtone-detail",
+        ParamId::Shadows => "param-shadows",
+        ParamId::Highlights => "param-highlights",
+        ParamId::Saturation => "param-saturation",
+        ParamId::Hue => "param-hue",
+        ParamId::LumaMix => "param-luma-mix",
+    })
 }
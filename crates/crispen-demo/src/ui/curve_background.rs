@@ -0,0 +1,158 @@
+//! Background of the hue curve plot.
+//!
+//! Each plot column is tinted with the color it stands for: a hue
+//! gradient for the hue curves and a lightness ramp for Lum vs Sat. A
+//! faint histogram of the graded image rises from the bottom along the
+//! same axis, so it is clear which parts of the picture a curve segment
+//! touches.
+//!
+//! The hue histogram is rebuilt from the vectorscope density instead of a
+//! scope pass of its own: HSL hue depends only on a pixel's chroma, so
+//! every vectorscope cell maps to exactly one hue. The lightness
+//! histogram reuses the luma bins of the histogram scope, which is close
+//! to, but not the same as, the HSL lightness the curve is keyed on.
+
+use bevy::color::Srgba;
+use bevy::prelude::*;
+use crispen_core::scopes::histogram::HistogramData;
+use crispen_core::scopes::vectorscope::VectorscopeData;
+
+use super::theme;
+
+/// Background image width; one column per histogram bin.
+pub(super) const WIDTH: u32 = 180;
+/// Background image height.
+pub(super) const HEIGHT: u32 = 64;
+
+/// How strongly the axis color tints the plot background.
+const AXIS_TINT: f32 = 0.35;
+/// Share of the plot height the tallest histogram column reaches.
+const HISTOGRAM_HEIGHT: f32 = 0.6;
+/// How much the histogram brightens the background under it.
+const HISTOGRAM_LIFT: f32 = 0.14;
+
+/// Chroma scales applied by `vectorscope.wgsl` to B−Y and R−Y.
+const VECTORSCOPE_CB_SCALE: f32 = 0.5389;
+const VECTORSCOPE_CR_SCALE: f32 = 0.6350;
+/// Cells this close to the vectorscope center are treated as neutral.
+const NEUTRAL_CELLS: f32 = 1.5;
+
+/// What the plot's X axis stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BackgroundAxis {
+    Hue,
+    Lightness,
+}
+
+/// Render the background as `WIDTH × HEIGHT` sRGB RGBA8 pixels.
+///
+/// `histogram` holds one value per column in 0..1; `None` draws the axis
+/// colors alone.
+pub(super) fn render(axis: BackgroundAxis, histogram: Option<&[f32]>) -> Vec<u8> {
+    let base = theme::CURVE_PLOT_BG.to_srgba();
+    let base = [base.red, base.green, base.blue];
+    let mut data = Vec::with_capacity((WIDTH * HEIGHT * 4) as usize);
+    let columns: Vec<([f32; 3], f32)> = (0..WIDTH as usize)
+        .map(|col| {
+            let t = (col as f32 + 0.5) / WIDTH as f32;
+            let tint = lerp3(base, axis_color(axis, t), AXIS_TINT);
+            let level = histogram.and_then(|h| h.get(col)).copied().unwrap_or(0.0);
+            (tint, level * HISTOGRAM_HEIGHT)
+        })
+        .collect();
+
+    for row in 0..HEIGHT {
+        // Height above the bottom edge, at the pixel center.
+        let height = 1.0 - (row as f32 + 0.5) / HEIGHT as f32;
+        for &(tint, level) in &columns {
+            let rgb = if height <= level {
+                lerp3(tint, [1.0; 3], HISTOGRAM_LIFT)
+            } else {
+                tint
+            };
+            data.extend_from_slice(&Srgba::rgb(rgb[0], rgb[1], rgb[2]).to_u8_array());
+        }
+    }
+    data
+}
+
+/// Hue distribution of the vectorscope, one bin per column.
+pub(super) fn hue_histogram(data: &VectorscopeData) -> Vec<f32> {
+    let mut bins = vec![0.0f32; WIDTH as usize];
+    let res = data.resolution.max(1) as usize;
+    let res_f = res as f32;
+    for (idx, &count) in data.density.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        // Cell centers, undoing the shader's [-0.5, 0.5] → grid mapping.
+        let cb = ((idx % res) as f32 + 0.5) / res_f - 0.5;
+        let cr = ((idx / res) as f32 + 0.5) / res_f - 0.5;
+        if cb.hypot(cr) * res_f < NEUTRAL_CELLS {
+            continue;
+        }
+        let hue = chroma_hue(cb / VECTORSCOPE_CB_SCALE, cr / VECTORSCOPE_CR_SCALE);
+        let col = ((hue * WIDTH as f32) as usize).min(WIDTH as usize - 1);
+        bins[col] += count as f32;
+    }
+    normalize(bins)
+}
+
+/// Luma distribution of the histogram scope, one bin per column.
+pub(super) fn lightness_histogram(data: &HistogramData) -> Vec<f32> {
+    let mut bins = vec![0.0f32; WIDTH as usize];
+    let luma = &data.bins[3];
+    for (idx, &count) in luma.iter().enumerate() {
+        let col = idx * WIDTH as usize / luma.len().max(1);
+        bins[col.min(WIDTH as usize - 1)] += count as f32;
+    }
+    normalize(bins)
+}
+
+/// HSL hue in 0..1 of a pixel with the given B−Y and R−Y differences.
+/// Adding gray does not change hue, so Y itself is not needed.
+fn chroma_hue(db: f32, dr: f32) -> f32 {
+    let dg = -(0.2126729 * dr + 0.0721750 * db) / 0.7151522;
+    let (r, g, b) = (dr, dg, db);
+    let mx = r.max(g).max(b);
+    let d = mx - r.min(g).min(b);
+    if d <= 0.0 {
+        return 0.0;
+    }
+    let h = if r == mx {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if g == mx {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h / 6.0).fract()
+}
+
+fn axis_color(axis: BackgroundAxis, t: f32) -> [f32; 3] {
+    match axis {
+        BackgroundAxis::Hue => {
+            let c = Color::hsv(t * 360.0, 0.85, 0.9).to_srgba();
+            [c.red, c.green, c.blue]
+        }
+        BackgroundAxis::Lightness => [t; 3],
+    }
+}
+
+/// Scale to a peak of 1. The square root keeps sparse hues visible next
+/// to a dominant one.
+fn normalize(bins: Vec<f32>) -> Vec<f32> {
+    let peak = bins.iter().copied().fold(0.0f32, f32::max);
+    if peak <= 0.0 {
+        return bins;
+    }
+    bins.into_iter().map(|v| (v / peak).sqrt()).collect()
+}
+
+fn lerp3(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
@@ -0,0 +1,97 @@
+//! Defect marker — click dead pixels and dust in the viewer to add them to
+//! the source's defect map, which is inpainted before grading.
+//!
+//! Press `D` to arm marking, then click each defect: a plain click marks a
+//! single pixel, a shift-click a dust spot [`DUST_RADIUS`] pixels wide.
+//! Marking stays armed until `D` is pressed again or `Escape` cancels.
+//! Hot pixels on a black frame can be found automatically with the toolbar's
+//! detect button instead.
+
+use bevy::picking::events::{Click, Pointer};
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, ComputedUiRenderTargetInfo, UiGlobalTransform, UiScale};
+
+use crispen_bevy::events::ColorGradingCommand;
+
+use super::scope_mask::pointer_to_image_uv;
+use super::split_viewer::GradedImageNode;
+use crate::keymap::{Action, KeyBindings};
+
+/// Radius in source pixels of a shift-clicked dust spot.
+pub const DUST_RADIUS: u32 = 3;
+
+/// UI-side state for defect marking.
+#[derive(Resource, Default)]
+pub struct DefectMarkerState {
+    /// Whether clicks on the viewer are captured as defects.
+    pub active: bool,
+}
+
+pub struct DefectMarkerPlugin;
+
+impl Plugin for DefectMarkerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefectMarkerState>()
+            .add_systems(Update, handle_defect_marker_shortcuts)
+            .add_observer(on_viewer_click);
+    }
+}
+
+fn handle_defect_marker_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut state: ResMut<DefectMarkerState>,
+) {
+    if bindings.just_pressed(Action::MarkDefects, &keys) {
+        state.active = !state.active;
+        if state.active {
+            tracing::info!("defect marking armed: click dead pixels, shift-click dust");
+        }
+    } else if keys.just_pressed(KeyCode::Escape) && state.active {
+        state.active = false;
+    }
+}
+
+fn on_viewer_click(
+    mut click: On<Pointer<Click>>,
+    state: Res<DefectMarkerState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut commands: MessageWriter<ColorGradingCommand>,
+    q_graded: Query<
+        (
+            &ComputedNode,
+            &ComputedUiRenderTargetInfo,
+            &UiGlobalTransform,
+        ),
+        With<GradedImageNode>,
+    >,
+    ui_scale: Res<UiScale>,
+) {
+    if !state.active {
+        return;
+    }
+
+    let target = click.event_target();
+    let Ok((node, node_target, transform)) = q_graded.get(target) else {
+        return;
+    };
+
+    click.propagate(false);
+
+    let uv = pointer_to_image_uv(
+        click.pointer_location.position,
+        node,
+        node_target,
+        transform,
+        ui_scale.0,
+    );
+    let radius = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        DUST_RADIUS
+    } else {
+        0
+    };
+    commands.write(ColorGradingCommand::MarkDefect {
+        at: uv.to_array(),
+        radius,
+    });
+}
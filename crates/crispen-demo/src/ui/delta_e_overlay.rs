@@ -0,0 +1,182 @@
+//! ΔE heatmap overlay — shows where the graded output differs from a
+//! reference after a `CompareDeltaE` command.
+//!
+//! The heatmap comes back from the GPU comparison as RGBA8 and is drawn over
+//! whichever node shows the viewer image, so it works in both the native
+//! and the CEF layout. It hides again on `ClearDeltaE` and when a new image
+//! is loaded, since the map no longer matches what is on screen.
+
+use std::path::Path;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crispen_bevy::events::{
+    ColorGradingCommand, DeltaEReference, DeltaEReportEvent, ImageLoadedEvent,
+};
+use crispen_core::transform::params::GradingParams;
+
+use super::viewer::ViewerImageHandle;
+use crate::image_loader;
+
+/// Handle to the heatmap texture drawn over the viewer image.
+#[derive(Resource)]
+pub struct DeltaEOverlayHandle {
+    pub handle: Handle<Image>,
+}
+
+/// Heatmap node drawn over the viewer image.
+#[derive(Component)]
+pub struct DeltaEOverlayNode;
+
+pub struct DeltaEOverlayPlugin;
+
+impl Plugin for DeltaEOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_delta_e_overlay).add_systems(
+            Update,
+            (
+                ensure_overlay_spawned,
+                hide_delta_e_heatmap,
+                apply_delta_e_heatmap,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Resolve a `CompareDeltaE` reference path: a `.json` file is a saved grade
+/// (`GradingParams`) applied to the current source, anything else is a
+/// render in the output space, loaded at the same preview size as sources.
+pub fn load_reference(
+    path: &str,
+    preview_size: Option<(u32, u32)>,
+) -> Result<DeltaEReference, String> {
+    let path = Path::new(path);
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let params: GradingParams = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        return Ok(DeltaEReference::Grade(Box::new(params)));
+    }
+
+    #[cfg(feature = "ocio")]
+    let result = image_loader::load_image_oiio(path, preview_size);
+    #[cfg(not(feature = "ocio"))]
+    let result = image_loader::load_image_for_display(path, preview_size);
+    result
+        .map(|loaded| DeltaEReference::Image(loaded.image))
+        .map_err(|e| e.to_string())
+}
+
+fn setup_delta_e_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let placeholder = Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    commands.insert_resource(DeltaEOverlayHandle {
+        handle: images.add(placeholder),
+    });
+}
+
+/// One-shot system: spawn the heatmap as a child of the node showing the
+/// viewer image once both exist.
+fn ensure_overlay_spawned(
+    mut commands: Commands,
+    overlay_handle: Option<Res<DeltaEOverlayHandle>>,
+    viewer_handle: Option<Res<ViewerImageHandle>>,
+    existing: Query<(), With<DeltaEOverlayNode>>,
+    image_nodes: Query<(Entity, &ImageNode), Without<DeltaEOverlayNode>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let (Some(overlay_handle), Some(viewer_handle)) = (overlay_handle, viewer_handle) else {
+        return;
+    };
+    for (entity, image) in &image_nodes {
+        if image.image != viewer_handle.handle {
+            continue;
+        }
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                DeltaEOverlayNode,
+                ImageNode::new(overlay_handle.handle.clone()).with_mode(NodeImageMode::Stretch),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                Visibility::Hidden,
+            ));
+        });
+    }
+}
+
+/// Copy a finished comparison's heatmap into the overlay and show it.
+fn apply_delta_e_heatmap(
+    mut reports: MessageReader<DeltaEReportEvent>,
+    overlay_handle: Option<Res<DeltaEOverlayHandle>>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlay_vis: Query<&mut Visibility, With<DeltaEOverlayNode>>,
+) {
+    let Some(event) = reports.read().last() else {
+        return;
+    };
+    let Some(overlay_handle) = overlay_handle else {
+        return;
+    };
+
+    let size = Extent3d {
+        width: event.report.width,
+        height: event.report.height,
+        depth_or_array_layers: 1,
+    };
+    if let Some(existing) = images.get_mut(&overlay_handle.handle) {
+        if existing.texture_descriptor.size != size {
+            *existing = Image::new(
+                size,
+                TextureDimension::D2,
+                event.heatmap.clone(),
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+            );
+        } else {
+            existing.data = Some(event.heatmap.clone());
+        }
+    }
+    for mut vis in &mut overlay_vis {
+        *vis = Visibility::Inherited;
+    }
+}
+
+/// Hide the heatmap on `ClearDeltaE` or when a new image replaces the one
+/// it was computed for.
+fn hide_delta_e_heatmap(
+    mut commands: MessageReader<ColorGradingCommand>,
+    mut loaded: MessageReader<ImageLoadedEvent>,
+    mut overlay_vis: Query<&mut Visibility, With<DeltaEOverlayNode>>,
+) {
+    let cleared = commands
+        .read()
+        .any(|cmd| matches!(cmd, ColorGradingCommand::ClearDeltaE));
+    if !cleared && loaded.read().count() == 0 {
+        return;
+    }
+    for mut vis in &mut overlay_vis {
+        *vis = Visibility::Hidden;
+    }
+}
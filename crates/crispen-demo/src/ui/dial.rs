@@ -2,8 +2,12 @@
 //!
 //! Renders a DaVinci Resolve-style dial using a custom UiMaterial shader.
 //! Vertical drag interaction: drag up to increase, drag down to decrease.
+//! When focused, the arrow keys step the value and Delete resets it.
 
 use bevy::asset::embedded_asset;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::FocusedInput;
+use bevy::input_focus::tab_navigation::TabIndex;
 use bevy::picking::{
     Pickable,
     events::{Cancel, Click, Drag, DragEnd, DragStart, Pointer, Press},
@@ -17,7 +21,9 @@ use bevy::ui_render::prelude::{MaterialNode, UiMaterial, UiMaterialPlugin};
 use std::time::{Duration, Instant};
 
 use super::components::ParamId;
+use super::focus::{KeyAdjust, ValueClipboardKey, key_adjustment, value_clipboard_key};
 use super::theme;
+use crate::clipboard::NativeClipboard;
 
 // ── Constants ───────────────────────────────────────────────────────────────
 
@@ -173,6 +179,7 @@ pub fn spawn_param_dial(
                         start_value: default_val,
                     },
                     DialClickState::default(),
+                    TabIndex(0),
                 ))
                 .id();
 
@@ -389,6 +396,64 @@ fn on_dial_click(
     }
 }
 
+// ── Observers (keyboard) ────────────────────────────────────────────────────
+
+/// Step the focused dial with the arrow keys; Delete resets it and
+/// Ctrl+C / Ctrl+V copy and paste its value. The new value reaches the
+/// material and `GradingState` through the usual `Changed<DialValue>`
+/// systems.
+#[allow(clippy::type_complexity)]
+fn on_dial_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_dials: Query<
+        (
+            &mut DialValue,
+            &DialRange,
+            &DialStep,
+            &DialDefaultValue,
+            Has<InteractionDisabled>,
+        ),
+        With<DialInner>,
+    >,
+    mut clipboard: ResMut<NativeClipboard>,
+) {
+    let Ok((mut value, range, step, default_value, disabled)) = q_dials.get_mut(key.focused_entity)
+    else {
+        return;
+    };
+    let adjust = match value_clipboard_key(&key.input, &keys) {
+        Some(ValueClipboardKey::Copy) => {
+            key.propagate(false);
+            clipboard.copy_value(value.0);
+            return;
+        }
+        Some(ValueClipboardKey::Paste) => clipboard.paste_value().map(KeyAdjust::Set),
+        None => key_adjustment(&key.input, &keys),
+    };
+    let Some(adjust) = adjust else {
+        return;
+    };
+    key.propagate(false);
+    if disabled {
+        return;
+    }
+
+    let target = match adjust {
+        KeyAdjust::Steps(steps) => {
+            let raw = value.0 + steps * step.0;
+            snap_to_step(raw, step.0, range.min).clamp(range.min, range.max)
+        }
+        KeyAdjust::Reset => default_value.0,
+        KeyAdjust::Set(pasted) => {
+            snap_to_step(pasted, step.0, range.min).clamp(range.min, range.max)
+        }
+    };
+    if (value.0 - target).abs() > f32::EPSILON {
+        value.0 = target;
+    }
+}
+
 // ── Systems ─────────────────────────────────────────────────────────────────
 
 /// Lazily insert `MaterialNode<DialMaterial>` on dial inner nodes.
@@ -449,6 +514,7 @@ impl Plugin for DialPlugin {
             .add_observer(on_dial_drag_start)
             .add_observer(on_dial_drag)
             .add_observer(on_dial_drag_end)
-            .add_observer(on_dial_drag_cancel);
+            .add_observer(on_dial_drag_cancel)
+            .add_observer(on_dial_key);
     }
 }
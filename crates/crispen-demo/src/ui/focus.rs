@@ -0,0 +1,218 @@
+//! Keyboard focus for the native UI.
+//!
+//! Tab / Shift+Tab cycle through every control carrying a `TabIndex`,
+//! group by group in `TabGroup` order (toolbar, primaries, scopes, curves). Each
+//! widget handles its own keys through a `FocusedInput<KeyboardInput>`
+//! observer; this module only owns the shared pieces: tab navigation, the
+//! focus outline, and the arrow-key and clipboard mapping used by dials and
+//! sliders.
+//!
+//! Requires `InputDispatchPlugin`, which routes keyboard input to the
+//! focused entity.
+
+use bevy::input::ButtonState;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::tab_navigation::TabNavigationPlugin;
+use bevy::input_focus::{InputFocus, InputFocusVisible};
+use bevy::prelude::*;
+
+use super::theme;
+
+// ── Tab groups ──────────────────────────────────────────────────────────────
+
+/// Tab group order of the top toolbar.
+pub const TOOLBAR_TAB_GROUP: i32 = 0;
+
+/// Tab group order of the primaries dials and sliders.
+pub const PRIMARIES_TAB_GROUP: i32 = 1;
+
+/// Tab group order of the scopes header.
+pub const SCOPES_TAB_GROUP: i32 = 2;
+
+/// Tab group order of the hue curves plot and point fields.
+pub const CURVES_TAB_GROUP: i32 = 3;
+
+/// Step multiplier while Shift is held, and for Page Up / Page Down.
+const COARSE_STEP_MULTIPLIER: f32 = 10.0;
+
+const OUTLINE_WIDTH: f32 = 2.0;
+const OUTLINE_OFFSET: f32 = 1.0;
+
+// ── Value adjustment ────────────────────────────────────────────────────────
+
+/// What a key press asks a focused value control to do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyAdjust {
+    /// Move by this many steps (negative = down).
+    Steps(f32),
+    /// Return to the default value.
+    Reset,
+    /// Jump to a pasted value.
+    Set(f32),
+}
+
+/// Map a key press to a value adjustment.
+///
+/// Up / Right step up and Down / Left step down, ten at a time with Shift
+/// held or via Page Up / Page Down. Delete and Backspace reset. Repeats
+/// count, so holding an arrow keeps moving. Home is left alone because it
+/// is the default reset-view shortcut.
+pub fn key_adjustment(input: &KeyboardInput, keys: &ButtonInput<KeyCode>) -> Option<KeyAdjust> {
+    if input.state != ButtonState::Pressed {
+        return None;
+    }
+    let coarse = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        COARSE_STEP_MULTIPLIER
+    } else {
+        1.0
+    };
+    match input.key_code {
+        KeyCode::ArrowUp | KeyCode::ArrowRight => Some(KeyAdjust::Steps(coarse)),
+        KeyCode::ArrowDown | KeyCode::ArrowLeft => Some(KeyAdjust::Steps(-coarse)),
+        KeyCode::PageUp => Some(KeyAdjust::Steps(COARSE_STEP_MULTIPLIER)),
+        KeyCode::PageDown => Some(KeyAdjust::Steps(-COARSE_STEP_MULTIPLIER)),
+        KeyCode::Delete | KeyCode::Backspace => Some(KeyAdjust::Reset),
+        _ => None,
+    }
+}
+
+/// Clipboard shortcut on a focused value control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueClipboardKey {
+    /// Ctrl+C: copy the value.
+    Copy,
+    /// Ctrl+V: set the value from the clipboard.
+    Paste,
+}
+
+/// Map Ctrl+C / Ctrl+V to a value copy or paste. Shift and Alt must be
+/// up so the look and still shortcuts pass through.
+pub fn value_clipboard_key(
+    input: &KeyboardInput,
+    keys: &ButtonInput<KeyCode>,
+) -> Option<ValueClipboardKey> {
+    if input.state != ButtonState::Pressed
+        || input.repeat
+        || !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        || keys.any_pressed([
+            KeyCode::ShiftLeft,
+            KeyCode::ShiftRight,
+            KeyCode::AltLeft,
+            KeyCode::AltRight,
+        ])
+    {
+        return None;
+    }
+    match input.key_code {
+        KeyCode::KeyC => Some(ValueClipboardKey::Copy),
+        KeyCode::KeyV => Some(ValueClipboardKey::Paste),
+        _ => None,
+    }
+}
+
+/// Whether a key press activates a focused button: Enter or Space,
+/// ignoring auto-repeat.
+pub fn is_activate_key(input: &KeyboardInput) -> bool {
+    input.state == ButtonState::Pressed
+        && !input.repeat
+        && matches!(
+            input.key_code,
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space
+        )
+}
+
+/// Keyboard operation of a dropdown button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropdownKey {
+    /// Enter / Space: open, or close when open.
+    Toggle,
+    /// Escape: close.
+    Close,
+    /// Up / Down: select the previous (-1) or next (+1) option.
+    Move(i32),
+}
+
+/// Map a key press to a dropdown action.
+///
+/// Arrows change the selection directly, open or not, like a native
+/// select box; the open list just shows where it landed.
+pub fn dropdown_key(input: &KeyboardInput) -> Option<DropdownKey> {
+    if is_activate_key(input) {
+        return Some(DropdownKey::Toggle);
+    }
+    if input.state != ButtonState::Pressed {
+        return None;
+    }
+    match input.key_code {
+        KeyCode::Escape => Some(DropdownKey::Close),
+        KeyCode::ArrowUp => Some(DropdownKey::Move(-1)),
+        KeyCode::ArrowDown => Some(DropdownKey::Move(1)),
+        _ => None,
+    }
+}
+
+/// Index of the option `delta` places away from `current`, clamped to the
+/// list. An unknown current value starts from the top.
+pub fn step_option(current: Option<usize>, delta: i32, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let Some(current) = current else {
+        return Some(0);
+    };
+    Some((current as i64 + i64::from(delta)).clamp(0, len as i64 - 1) as usize)
+}
+
+// ── Focus outline ───────────────────────────────────────────────────────────
+
+/// Marks the entity currently drawing the focus outline.
+#[derive(Component)]
+struct FocusOutline;
+
+/// Draw an accent outline around the focused control while keyboard
+/// navigation is in use. Clicking hides it again, matching browsers'
+/// `:focus-visible`.
+fn update_focus_outline(
+    focus: Res<InputFocus>,
+    visible: Res<InputFocusVisible>,
+    outlined: Query<Entity, With<FocusOutline>>,
+    nodes: Query<(), With<Node>>,
+    mut commands: Commands,
+) {
+    if !(focus.is_changed() || visible.is_changed()) {
+        return;
+    }
+
+    let target = focus
+        .0
+        .filter(|entity| visible.0 && nodes.contains(*entity));
+
+    for entity in &outlined {
+        if Some(entity) != target {
+            commands.entity(entity).remove::<(FocusOutline, Outline)>();
+        }
+    }
+
+    if let Some(entity) = target {
+        commands.entity(entity).insert((
+            FocusOutline,
+            Outline::new(
+                Val::Px(OUTLINE_WIDTH),
+                Val::Px(OUTLINE_OFFSET),
+                theme::ACCENT,
+            ),
+        ));
+    }
+}
+
+// ── Plugin ──────────────────────────────────────────────────────────────────
+
+/// Adds tab navigation and the focus outline.
+pub struct KeyboardFocusPlugin;
+
+impl Plugin for KeyboardFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(TabNavigationPlugin)
+            .add_systems(PostUpdate, update_focus_outline);
+    }
+}
@@ -0,0 +1,167 @@
+//! Focus assist overlay — focus-peaking style edge tint from
+//! [`Settings::focus_assist`] drawn over the viewer image, for picking sharp
+//! reference stills and judging sharpening.
+//!
+//! The edges are found on the GPU by `shaders/focus_assist.wgsl`, a Sobel
+//! filter over the viewer texture itself, so the overlay follows every
+//! graded frame without a readback or CPU pass. The filter runs at image
+//! resolution; when the viewer shows the image smaller than 1:1, fine
+//! detail between screen pixels is marked only where a screen pixel lands
+//! on it, so zoom in to judge critical focus.
+
+use bevy::asset::embedded_asset;
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use bevy::render::render_resource::AsBindGroup;
+use bevy::shader::ShaderRef;
+use bevy::ui_render::prelude::{MaterialNode, UiMaterial, UiMaterialPlugin};
+use crispen_bevy::resources::ViewerData;
+
+use super::viewer::ViewerImageHandle;
+use crate::keymap::{Action, KeyBindings};
+use crate::settings::{FocusAssist, Settings, SettingsCommand};
+
+/// Focus assist node drawn over the viewer image.
+#[derive(Component)]
+pub struct FocusAssistNode;
+
+/// UiMaterial running the edge filter over the viewer texture.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct FocusAssistMaterial {
+    /// Size of one viewer image pixel in UV.
+    #[uniform(0)]
+    pub texel: Vec2,
+    /// Edge strength from which pixels are tinted.
+    #[uniform(0)]
+    pub threshold: f32,
+    /// Linear-light tint; alpha unused.
+    #[uniform(0)]
+    pub color: Vec4,
+    /// The viewer texture.
+    #[texture(1)]
+    #[sampler(2)]
+    pub image: Handle<Image>,
+}
+
+impl UiMaterial for FocusAssistMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://crispen_demo/ui/shaders/focus_assist.wgsl".into()
+    }
+}
+
+/// Registers the focus assist material, its node, and its shortcut.
+pub struct FocusAssistPlugin;
+
+impl Plugin for FocusAssistPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "shaders/focus_assist.wgsl");
+        app.add_plugins(UiMaterialPlugin::<FocusAssistMaterial>::default())
+            .add_systems(
+                Update,
+                (
+                    toggle_focus_assist,
+                    ensure_overlay_spawned,
+                    update_focus_assist,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Flip [`FocusAssist::enabled`] from the keyboard, saved like any other
+/// settings change.
+fn toggle_focus_assist(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    settings: Res<Settings>,
+    mut commands: MessageWriter<SettingsCommand>,
+) {
+    if bindings.just_pressed(Action::ToggleFocusAssist, &keys) {
+        commands.write(SettingsCommand::SetFocusAssist(FocusAssist {
+            enabled: !settings.focus_assist.enabled,
+            ..settings.focus_assist
+        }));
+    }
+}
+
+/// One-shot system: spawn the overlay as a child of the node showing the
+/// viewer image once both exist.
+fn ensure_overlay_spawned(
+    mut commands: Commands,
+    viewer_handle: Option<Res<ViewerImageHandle>>,
+    existing: Query<(), With<FocusAssistNode>>,
+    image_nodes: Query<(Entity, &ImageNode)>,
+    mut materials: ResMut<Assets<FocusAssistMaterial>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let Some(viewer_handle) = viewer_handle else {
+        return;
+    };
+    let mut material = None;
+    for (entity, image) in &image_nodes {
+        if image.image != viewer_handle.handle {
+            continue;
+        }
+        let material = material
+            .get_or_insert_with(|| {
+                materials.add(FocusAssistMaterial {
+                    texel: Vec2::ONE,
+                    threshold: 1.0,
+                    color: Vec4::ONE,
+                    image: viewer_handle.handle.clone(),
+                })
+            })
+            .clone();
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                FocusAssistNode,
+                MaterialNode(material),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                Visibility::Hidden,
+            ));
+        });
+    }
+}
+
+/// Show the overlay while enabled and an image is loaded, and push the
+/// settings and image size to its material when they change.
+fn update_focus_assist(
+    settings: Res<Settings>,
+    viewer_data: Res<ViewerData>,
+    mut nodes: Query<(&MaterialNode<FocusAssistMaterial>, &mut Visibility), With<FocusAssistNode>>,
+    mut materials: ResMut<Assets<FocusAssistMaterial>>,
+    mut applied: Local<Option<(FocusAssist, u32, u32)>>,
+) {
+    let focus = settings.focus_assist;
+    let (width, height) = (viewer_data.width, viewer_data.height);
+    let visible = focus.enabled && width > 0 && height > 0;
+    let key = Some((focus, width, height));
+    let stale = *applied != key;
+    for (material, mut vis) in &mut nodes {
+        vis.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        if stale
+            && visible
+            && let Some(material) = materials.get_mut(&material.0)
+        {
+            let [r, g, b] = focus.color;
+            material.texel = Vec2::new(width as f32, height as f32).recip();
+            material.threshold = focus.threshold;
+            material.color = Color::srgb(r, g, b).to_linear().to_vec4();
+        }
+    }
+    if visible && !nodes.is_empty() {
+        *applied = key;
+    }
+}
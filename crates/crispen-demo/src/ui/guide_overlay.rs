@@ -0,0 +1,152 @@
+//! Framing guide overlay — safe areas, center cross, thirds, and the aspect
+//! mask from [`Settings::guides`] drawn over the viewer image, plus a
+//! preview of the session's [`OutputBlanking`].
+//!
+//! The layer is rendered on the CPU by [`GuideLayer::render`] at the viewer
+//! image's resolution, the same call review renders use to burn the guides
+//! in, and is only redrawn when the guides, the blanking, or the image size
+//! change. Outside the blanking's active area the layer is opaque black with
+//! the guides composited over it, which is what a render writes there.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crispen_bevy::resources::{OutputBlanking, ViewerData};
+use crispen_core::framing::{Blanking, FramingGuides, GuideLayer};
+
+use super::viewer::ViewerImageHandle;
+use crate::settings::Settings;
+
+/// Handle to the guide texture drawn over the viewer image.
+#[derive(Resource)]
+pub struct GuideOverlayHandle {
+    pub handle: Handle<Image>,
+}
+
+/// Guide node drawn over the viewer image.
+#[derive(Component)]
+pub struct GuideOverlayNode;
+
+pub struct GuideOverlayPlugin;
+
+impl Plugin for GuideOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_guide_overlay)
+            .add_systems(Update, (ensure_overlay_spawned, update_guides).chain());
+    }
+}
+
+fn setup_guide_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let placeholder = Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    commands.insert_resource(GuideOverlayHandle {
+        handle: images.add(placeholder),
+    });
+}
+
+/// One-shot system: spawn the guides as a child of the node showing the
+/// viewer image once both exist.
+fn ensure_overlay_spawned(
+    mut commands: Commands,
+    overlay_handle: Option<Res<GuideOverlayHandle>>,
+    viewer_handle: Option<Res<ViewerImageHandle>>,
+    existing: Query<(), With<GuideOverlayNode>>,
+    image_nodes: Query<(Entity, &ImageNode), Without<GuideOverlayNode>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let (Some(overlay_handle), Some(viewer_handle)) = (overlay_handle, viewer_handle) else {
+        return;
+    };
+    for (entity, image) in &image_nodes {
+        if image.image != viewer_handle.handle {
+            continue;
+        }
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                GuideOverlayNode,
+                ImageNode::new(overlay_handle.handle.clone()).with_mode(NodeImageMode::Stretch),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                Visibility::Hidden,
+            ));
+        });
+    }
+}
+
+/// Redraw the guides when they, the blanking, or the viewer image size
+/// change, and hide the node while nothing would be drawn.
+fn update_guides(
+    settings: Res<Settings>,
+    blanking: Res<OutputBlanking>,
+    viewer_data: Res<ViewerData>,
+    overlay_handle: Option<Res<GuideOverlayHandle>>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlay_vis: Query<&mut Visibility, With<GuideOverlayNode>>,
+    mut drawn: Local<Option<(FramingGuides, Blanking, u32, u32)>>,
+) {
+    let Some(overlay_handle) = overlay_handle else {
+        return;
+    };
+    let (guides, blanking) = (settings.guides, blanking.0);
+    let (width, height) = (viewer_data.width, viewer_data.height);
+    let visible = (guides.is_visible() || blanking != Blanking::None) && width > 0 && height > 0;
+    for mut vis in &mut overlay_vis {
+        vis.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+    let key = (guides, blanking, width, height);
+    if !visible || *drawn == Some(key) {
+        return;
+    }
+
+    let mut layer = GuideLayer::render(&guides, width, height);
+    if blanking != Blanking::None {
+        let active = blanking.active_area(width, height);
+        for (i, p) in layer.pixels.iter_mut().enumerate() {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            if !active.contains_pixel(x, y) {
+                *p = [p[0] * p[3], p[1] * p[3], p[2] * p[3], 1.0];
+            }
+        }
+    }
+    let size = Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    if let Some(existing) = images.get_mut(&overlay_handle.handle) {
+        if existing.texture_descriptor.size != size {
+            *existing = Image::new(
+                size,
+                TextureDimension::D2,
+                layer.to_rgba8(),
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+            );
+        } else {
+            existing.data = Some(layer.to_rgba8());
+        }
+    }
+    *drawn = Some(key);
+}
@@ -2,14 +2,40 @@
 //!
 //! This mirrors the Resolve-style "Hue vs Curves" area with mode tabs
 //! and a curve plot preview.
-
+//!
+//! Points are added with Ctrl+click, or a double-tap on touch screens and
+//! pen displays where there is no modifier key to hold. A point drag
+//! belongs to the pointer that started it.
+//!
+//! Clicking a point selects it. The selected point is removed with Delete
+//! or Backspace while the plot has focus, any point with a right-click,
+//! and its input/output values can be typed into the fields under the
+//! plot. The trace is evaluated with the same interpolation the GPU bake
+//! uses, so toggling "Smooth" previews the Catmull-Rom curve exactly.
+//!
+//! Behind the grid the plot shows what its X axis stands for and a faint
+//! histogram of the graded image (see [`super::curve_background`]).
+
+use bevy::asset::RenderAssetUsages;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input_focus::tab_navigation::{TabGroup, TabIndex};
+use bevy::input_focus::{FocusedInput, InputFocus};
 use bevy::picking::Pickable;
 use bevy::picking::events::{Cancel, Drag, DragEnd, DragStart, Pointer, Press};
+use bevy::picking::pointer::{PointerButton, PointerId};
 use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use bevy::ui::{ComputedNode, ComputedUiRenderTargetInfo, UiGlobalTransform, UiScale};
-use crispen_bevy::resources::GradingState;
+use crispen_bevy::resources::{GradingState, ScopeState};
+use crispen_core::grading::curves::{CurveInterpolation, evaluate_curve};
+use std::time::{Duration, Instant};
 
+use super::curve_background::{self, BackgroundAxis};
+use super::focus::{self, KeyAdjust, ValueClipboardKey, key_adjustment, value_clipboard_key};
 use super::theme;
+use crate::clipboard::NativeClipboard;
+use crate::i18n::tr;
 
 /// Hint text shown when the curve plot has no control points.
 #[derive(Component)]
@@ -17,6 +43,9 @@ struct CurveHintText;
 
 const CURVE_TRACE_SAMPLES: usize = 84;
 const CURVE_THUMB_SIZE: f32 = 8.0;
+const POINT_FIELD_HEIGHT: f32 = 22.0;
+/// Max time between two touch presses on the plot to add a point.
+const DOUBLE_TAP_MAX_GAP: Duration = Duration::from_millis(350);
 
 #[derive(Debug, Clone, Copy)]
 struct CurvePoint {
@@ -34,13 +63,20 @@ enum HueCurveMode {
 }
 
 impl HueCurveMode {
-    fn label(self) -> &'static str {
+    fn background_axis(self) -> BackgroundAxis {
         match self {
-            Self::HueVsHue => "Hue vs Hue",
-            Self::HueVsSat => "Hue vs Sat",
-            Self::LumVsSat => "Lum vs Sat",
+            Self::HueVsHue | Self::HueVsSat => BackgroundAxis::Hue,
+            Self::LumVsSat => BackgroundAxis::Lightness,
         }
     }
+
+    fn label(self) -> String {
+        tr(match self {
+            Self::HueVsHue => "curves-hue-vs-hue",
+            Self::HueVsSat => "curves-hue-vs-sat",
+            Self::LumVsSat => "curves-lum-vs-sat",
+        })
+    }
 }
 
 /// Runtime state for hue-vs-curves control points.
@@ -51,6 +87,8 @@ pub struct HueCurvesState {
     hue_vs_sat: Vec<CurvePoint>,
     lum_vs_sat: Vec<CurvePoint>,
     next_point_id: u32,
+    /// Selected point of the active curve, if any.
+    selected: Option<u32>,
 }
 
 impl Default for HueCurvesState {
@@ -62,6 +100,7 @@ impl Default for HueCurvesState {
             hue_vs_sat: Vec::new(),
             lum_vs_sat: Vec::new(),
             next_point_id: 1,
+            selected: None,
         }
     }
 }
@@ -91,6 +130,19 @@ impl HueCurvesState {
         self.points_for_mode_mut(self.mode)
     }
 
+    fn selected_point(&self) -> Option<CurvePoint> {
+        let id = self.selected?;
+        self.active_points()
+            .iter()
+            .find(|point| point.id == id)
+            .copied()
+    }
+
+    fn set_mode(&mut self, mode: HueCurveMode) {
+        self.mode = mode;
+        self.selected = None;
+    }
+
     fn add_active_point(&mut self, x: f32, y: f32) {
         let point = CurvePoint {
             id: self.next_point_id,
@@ -98,11 +150,20 @@ impl HueCurvesState {
             y: y.clamp(0.0, 1.0),
         };
         self.next_point_id = self.next_point_id.wrapping_add(1);
+        self.selected = Some(point.id);
         let points = self.active_points_mut();
         points.push(point);
         points.sort_by(|a, b| a.x.total_cmp(&b.x));
     }
 
+    fn remove_active_point(&mut self, point_id: u32) {
+        self.active_points_mut()
+            .retain(|point| point.id != point_id);
+        if self.selected == Some(point_id) {
+            self.selected = None;
+        }
+    }
+
     fn update_active_point(&mut self, point_id: u32, x: f32, y: f32) {
         let points = self.active_points_mut();
         if let Some(point) = points.iter_mut().find(|point| point.id == point_id) {
@@ -117,10 +178,39 @@ impl HueCurvesState {
 #[derive(Component)]
 struct HueCurvePlot;
 
+/// Image drawn behind the curve plot grid.
+#[derive(Resource)]
+struct CurveBackgroundImage {
+    handle: Handle<Image>,
+}
+
+/// Marker on the plot node showing [`CurveBackgroundImage`].
+#[derive(Component)]
+struct CurveBackgroundNode;
+
 /// Marker on curve mode tab buttons.
 #[derive(Component, Clone, Copy)]
 struct HueCurveModeButton(HueCurveMode);
 
+/// Marker on the button toggling smooth (Catmull-Rom) interpolation.
+#[derive(Component)]
+struct CurveSmoothToggle;
+
+/// Which coordinate of the selected point a field edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PointAxis {
+    Input,
+    Output,
+}
+
+/// Numeric entry field for the selected point.
+#[derive(Component)]
+struct CurvePointField {
+    axis: PointAxis,
+    /// Text typed so far; `None` while the field shows the live value.
+    edit: Option<String>,
+}
+
 /// Marker on draggable control points.
 #[derive(Component)]
 struct HueCurveThumb {
@@ -136,7 +226,8 @@ struct HueCurveTraceSample {
 /// Drag state for a control point thumb.
 #[derive(Component, Default)]
 struct HueCurveDragState {
-    active: bool,
+    /// Pointer dragging the thumb, if any.
+    pointer: Option<PointerId>,
 }
 
 /// Spawn the hue-vs-curves section in the bottom panel.
@@ -155,10 +246,11 @@ pub fn spawn_hue_curves_section(parent: &mut ChildSpawnerCommands) {
                 ..default()
             },
             BorderColor::all(theme::BORDER_SUBTLE),
+            TabGroup::new(focus::CURVES_TAB_GROUP),
         ))
         .with_children(|section| {
             section.spawn((
-                Text::new("Hue vs Curves"),
+                Text::new(tr("curves-title")),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -167,6 +259,7 @@ pub fn spawn_hue_curves_section(parent: &mut ChildSpawnerCommands) {
             ));
             spawn_curve_mode_tabs(section);
             spawn_curve_plot(section);
+            spawn_point_fields(section);
         });
 }
 
@@ -210,6 +303,30 @@ fn spawn_curve_mode_tabs(section: &mut ChildSpawnerCommands) {
                     )],
                 ));
             }
+
+            tabs.spawn((
+                Button,
+                CurveSmoothToggle,
+                Node {
+                    height: Val::Px(24.0),
+                    padding: UiRect::axes(Val::Px(8.0), Val::Px(0.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(theme::BG_CONTROL),
+                BorderColor::all(theme::BORDER_SUBTLE),
+                children![(
+                    Text::new(tr("curves-smooth")),
+                    TextFont {
+                        font_size: theme::FONT_SIZE_LABEL,
+                        ..default()
+                    },
+                    TextColor(theme::TEXT_DIM),
+                    Pickable::IGNORE,
+                )],
+            ));
         });
 }
 
@@ -229,12 +346,23 @@ fn spawn_curve_plot(section: &mut ChildSpawnerCommands) {
             },
             BackgroundColor(theme::CURVE_PLOT_BG),
             BorderColor::all(theme::BORDER_SUBTLE),
+            TabIndex(0),
         ))
         .with_children(|plot| {
+            plot.spawn((
+                CurveBackgroundNode,
+                ImageNode::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ));
             spawn_grid_lines(plot);
             spawn_neutral_line(plot);
             spawn_curve_trace(plot);
-            spawn_hue_markers(plot);
 
             // Hint text shown until the user adds a control point.
             plot.spawn((
@@ -247,7 +375,7 @@ fn spawn_curve_plot(section: &mut ChildSpawnerCommands) {
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                Text::new("Ctrl+Click to add points"),
+                Text::new(tr("curves-add-hint")),
                 TextFont {
                     font_size: theme::FONT_SIZE_LABEL,
                     ..default()
@@ -258,6 +386,56 @@ fn spawn_curve_plot(section: &mut ChildSpawnerCommands) {
         });
 }
 
+fn spawn_point_fields(section: &mut ChildSpawnerCommands) {
+    section
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            width: Val::Percent(100.0),
+            ..default()
+        })
+        .with_children(|row| {
+            for (axis, label, tab_index) in [
+                (PointAxis::Input, "curves-point-input", 1),
+                (PointAxis::Output, "curves-point-output", 2),
+            ] {
+                row.spawn((
+                    Text::new(tr(label)),
+                    TextFont {
+                        font_size: theme::FONT_SIZE_LABEL,
+                        ..default()
+                    },
+                    TextColor(theme::TEXT_DIM),
+                ));
+                row.spawn((
+                    CurvePointField { axis, edit: None },
+                    Node {
+                        flex_grow: 1.0,
+                        height: Val::Px(POINT_FIELD_HEIGHT),
+                        padding: UiRect::axes(Val::Px(6.0), Val::Px(0.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(theme::BG_CONTROL),
+                    BorderColor::all(theme::BORDER_SUBTLE),
+                    TabIndex(tab_index),
+                    children![(
+                        Text::new("-"),
+                        TextFont {
+                            font_size: theme::FONT_SIZE_LABEL,
+                            ..default()
+                        },
+                        TextColor(theme::TEXT_DIM),
+                        Pickable::IGNORE,
+                    )],
+                ));
+            }
+        });
+}
+
 fn spawn_grid_lines(plot: &mut ChildSpawnerCommands) {
     for step in 0..=4 {
         let pct = step as f32 * 25.0;
@@ -323,44 +501,6 @@ fn spawn_curve_trace(plot: &mut ChildSpawnerCommands) {
     }
 }
 
-fn spawn_hue_markers(plot: &mut ChildSpawnerCommands) {
-    plot.spawn((
-        Node {
-            position_type: PositionType::Absolute,
-            left: Val::Px(10.0),
-            right: Val::Px(10.0),
-            bottom: Val::Px(10.0),
-            display: Display::Flex,
-            flex_direction: FlexDirection::Row,
-            justify_content: JustifyContent::SpaceBetween,
-            align_items: AlignItems::Center,
-            ..default()
-        },
-        Pickable::IGNORE,
-    ))
-    .with_children(|swatches| {
-        for color in [
-            Color::srgb(0.98, 0.20, 0.20),
-            Color::srgb(0.95, 0.76, 0.18),
-            Color::srgb(0.22, 0.86, 0.32),
-            Color::srgb(0.20, 0.85, 0.90),
-            Color::srgb(0.24, 0.45, 0.98),
-            Color::srgb(0.86, 0.24, 0.88),
-        ] {
-            swatches.spawn((
-                Node {
-                    width: Val::Px(8.0),
-                    height: Val::Px(8.0),
-                    border_radius: BorderRadius::all(Val::Px(4.0)),
-                    ..default()
-                },
-                BackgroundColor(color),
-                Pickable::IGNORE,
-            ));
-        }
-    });
-}
-
 fn pointer_to_plot_normalized(
     pointer_pos: Vec2,
     node: &ComputedNode,
@@ -424,13 +564,28 @@ fn on_curve_plot_press(
     >,
     ui_scale: Res<UiScale>,
     keys: Res<ButtonInput<KeyCode>>,
+    mut last_tap: Local<Option<Instant>>,
     mut state: ResMut<HueCurvesState>,
 ) {
     let Ok((node, node_target, transform)) = q_plot.get(press.entity) else {
         return;
     };
+    if press.button != PointerButton::Primary {
+        return;
+    }
     let ctrl_pressed = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
-    if !ctrl_pressed {
+    let double_tap = press.pointer_id.is_touch() && {
+        let now = Instant::now();
+        let is_double = last_tap.is_some_and(|last| now.duration_since(last) <= DOUBLE_TAP_MAX_GAP);
+        // Consume the pair so a third tap starts over.
+        *last_tap = if is_double { None } else { Some(now) };
+        is_double
+    };
+    if !(ctrl_pressed || double_tap) {
+        // A plain click on the background drops the selection.
+        if state.selected.is_some() {
+            state.selected = None;
+        }
         return;
     }
 
@@ -450,6 +605,7 @@ fn on_curve_plot_press(
 
 fn on_curve_thumb_press(
     mut press: On<Pointer<Press>>,
+    q_drag: Query<&HueCurveDragState, With<HueCurveThumb>>,
     q_thumb_parent: Query<&ChildOf, With<HueCurveThumb>>,
     q_thumb: Query<&HueCurveThumb>,
     q_plot: Query<
@@ -463,10 +619,24 @@ fn on_curve_thumb_press(
     ui_scale: Res<UiScale>,
     mut state: ResMut<HueCurvesState>,
 ) {
-    if q_thumb.get(press.entity).is_err() {
+    let Ok(thumb) = q_thumb.get(press.entity) else {
         return;
-    }
+    };
     press.propagate(false);
+    match press.button {
+        PointerButton::Primary => state.selected = Some(thumb.point_id),
+        PointerButton::Secondary => {
+            state.remove_active_point(thumb.point_id);
+            return;
+        }
+        PointerButton::Middle => return,
+    }
+    if q_drag
+        .get(press.entity)
+        .is_ok_and(|drag_state| drag_state.pointer.is_some())
+    {
+        return;
+    }
     update_control_from_pointer(
         press.entity,
         press.pointer_location.position,
@@ -499,7 +669,10 @@ fn on_curve_thumb_drag_start(
     };
 
     drag_start.propagate(false);
-    drag_state.active = true;
+    if drag_state.pointer.is_some() || drag_start.button != PointerButton::Primary {
+        return;
+    }
+    drag_state.pointer = Some(drag_start.pointer_id);
     update_control_from_pointer(
         drag_start.entity,
         drag_start.pointer_location.position,
@@ -531,7 +704,7 @@ fn on_curve_thumb_drag(
         return;
     };
     drag.propagate(false);
-    if !drag_state.active {
+    if drag_state.pointer != Some(drag.pointer_id) {
         return;
     }
 
@@ -554,7 +727,9 @@ fn on_curve_thumb_drag_end(
         return;
     };
     drag_end.propagate(false);
-    drag_state.active = false;
+    if drag_state.pointer == Some(drag_end.pointer_id) {
+        drag_state.pointer = None;
+    }
 }
 
 fn on_curve_thumb_drag_cancel(
@@ -565,7 +740,9 @@ fn on_curve_thumb_drag_cancel(
         return;
     };
     drag_cancel.propagate(false);
-    drag_state.active = false;
+    if drag_state.pointer == Some(drag_cancel.pointer_id) {
+        drag_state.pointer = None;
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -575,11 +752,158 @@ fn handle_curve_mode_buttons(
 ) {
     for (interaction, button) in interactions.iter() {
         if *interaction == Interaction::Pressed && state.mode != button.0 {
-            state.mode = button.0;
+            state.set_mode(button.0);
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn handle_curve_smooth_toggle(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<CurveSmoothToggle>)>,
+    mut grading: ResMut<GradingState>,
+) {
+    for interaction in interactions.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        grading.params.curve_interpolation = match grading.params.curve_interpolation {
+            CurveInterpolation::Linear => CurveInterpolation::Smooth,
+            CurveInterpolation::Smooth => CurveInterpolation::Linear,
+        };
+        grading.dirty = true;
+    }
+}
+
+/// Delete / Backspace removes the selected point; Escape deselects it.
+fn on_curve_plot_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    q_plot: Query<(), With<HueCurvePlot>>,
+    mut state: ResMut<HueCurvesState>,
+) {
+    if !q_plot.contains(key.focused_entity) || key.input.state != ButtonState::Pressed {
+        return;
+    }
+    let Some(selected) = state.selected else {
+        return;
+    };
+    match key.input.key_code {
+        KeyCode::Delete | KeyCode::Backspace => state.remove_active_point(selected),
+        KeyCode::Escape => state.selected = None,
+        _ => return,
+    }
+    key.propagate(false);
+}
+
+/// Display value of one coordinate: hue in degrees, luminance in percent,
+/// hue shift in signed degrees and saturation as a factor.
+fn point_field_value(mode: HueCurveMode, axis: PointAxis, point: CurvePoint) -> f32 {
+    match (axis, mode) {
+        (PointAxis::Input, HueCurveMode::LumVsSat) => point.x * 100.0,
+        (PointAxis::Input, _) => point.x * 360.0,
+        (PointAxis::Output, HueCurveMode::HueVsHue) => map_ui_to_hue_offset(point.y) * 360.0,
+        (PointAxis::Output, _) => map_ui_to_sat_factor(point.y),
+    }
+}
+
+/// Inverse of [`point_field_value`], back to the 0..1 plot coordinate.
+fn point_field_to_ui(mode: HueCurveMode, axis: PointAxis, value: f32) -> f32 {
+    let ui = match (axis, mode) {
+        (PointAxis::Input, HueCurveMode::LumVsSat) => value / 100.0,
+        (PointAxis::Input, _) => value / 360.0,
+        (PointAxis::Output, HueCurveMode::HueVsHue) => value / 360.0 + 0.5,
+        (PointAxis::Output, _) => value / 2.0,
+    };
+    ui.clamp(0.0, 1.0)
+}
+
+/// Arrow-key step of one coordinate, in display units.
+fn point_field_step(mode: HueCurveMode, axis: PointAxis) -> f32 {
+    match (axis, mode) {
+        (PointAxis::Output, HueCurveMode::HueVsSat | HueCurveMode::LumVsSat) => 0.01,
+        _ => 1.0,
+    }
+}
+
+fn format_point_field(mode: HueCurveMode, axis: PointAxis, value: f32) -> String {
+    match (axis, mode) {
+        (PointAxis::Input, HueCurveMode::LumVsSat) => format!("{value:.0}%"),
+        (PointAxis::Input, _) => format!("{value:.0}°"),
+        (PointAxis::Output, HueCurveMode::HueVsHue) => format!("{value:+.0}°"),
+        (PointAxis::Output, _) => format!("{value:.2}"),
+    }
+}
+
+fn set_point_field(state: &mut HueCurvesState, point: CurvePoint, axis: PointAxis, value: f32) {
+    let ui = point_field_to_ui(state.mode, axis, value);
+    match axis {
+        PointAxis::Input => state.update_active_point(point.id, ui, point.y),
+        PointAxis::Output => state.update_active_point(point.id, point.x, ui),
+    }
+}
+
+/// Typing edits the field; Enter commits, Escape cancels. Arrows step the
+/// value and Ctrl+C / Ctrl+V copy and paste it.
+fn on_point_field_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_fields: Query<&mut CurvePointField>,
+    mut state: ResMut<HueCurvesState>,
+    mut clipboard: ResMut<NativeClipboard>,
+) {
+    let Ok(mut field) = q_fields.get_mut(key.focused_entity) else {
+        return;
+    };
+    let Some(point) = state.selected_point() else {
+        return;
+    };
+    if key.input.state != ButtonState::Pressed {
+        return;
+    }
+    let mode = state.mode;
+    let axis = field.axis;
+    let value = point_field_value(mode, axis, point);
+
+    match value_clipboard_key(&key.input, &keys) {
+        Some(ValueClipboardKey::Copy) => clipboard.copy_value(value),
+        Some(ValueClipboardKey::Paste) => {
+            if let Some(pasted) = clipboard.paste_value() {
+                field.edit = None;
+                set_point_field(&mut state, point, axis, pasted);
+            }
+        }
+        None => match key.input.key_code {
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                if let Some(parsed) = field.edit.take().and_then(|text| text.parse().ok()) {
+                    set_point_field(&mut state, point, axis, parsed);
+                }
+            }
+            KeyCode::Escape => field.edit = None,
+            KeyCode::Backspace | KeyCode::Delete => {
+                if let Some(text) = field.edit.as_mut() {
+                    text.pop();
+                }
+            }
+            _ => {
+                if let Some(KeyAdjust::Steps(steps)) = key_adjustment(&key.input, &keys) {
+                    field.edit = None;
+                    let step = point_field_step(mode, axis);
+                    set_point_field(&mut state, point, axis, value + steps * step);
+                } else if let Key::Character(text) = &key.input.logical_key
+                    && text
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c == '.' || c == '-')
+                    && !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+                {
+                    field.edit.get_or_insert_with(String::new).push_str(text);
+                } else {
+                    return;
+                }
+            }
+        },
+    }
+    key.propagate(false);
+}
+
 fn sample_curve(points: &[CurvePoint], t: f32) -> f32 {
     match points.len() {
         0 => return 0.5,
@@ -617,6 +941,25 @@ fn sample_curve(points: &[CurvePoint], t: f32) -> f32 {
     points[0].y
 }
 
+/// Curve height at `t` in plot coordinates, evaluated from the same control
+/// points and interpolation the GPU bake receives.
+fn trace_curve(
+    mode: HueCurveMode,
+    points: &[CurvePoint],
+    interpolation: CurveInterpolation,
+    t: f32,
+) -> f32 {
+    if points.is_empty() {
+        return 0.5;
+    }
+    let curve = points_to_grading_curve(mode, points);
+    let value = evaluate_curve(&curve, t, interpolation);
+    match mode {
+        HueCurveMode::HueVsHue => value + 0.5,
+        HueCurveMode::HueVsSat | HueCurveMode::LumVsSat => value * 0.5,
+    }
+}
+
 fn sync_curve_tab_visuals(
     state: Res<HueCurvesState>,
     mut tabs: Query<
@@ -657,6 +1000,81 @@ fn sync_curve_tab_visuals(
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn sync_smooth_toggle_visuals(
+    grading: Res<GradingState>,
+    mut toggles: Query<
+        (&Children, &mut BackgroundColor, &mut BorderColor),
+        With<CurveSmoothToggle>,
+    >,
+    mut text_colors: Query<&mut TextColor>,
+) {
+    if !grading.is_changed() {
+        return;
+    }
+    let smooth = grading.params.curve_interpolation == CurveInterpolation::Smooth;
+    for (children, mut bg, mut border) in toggles.iter_mut() {
+        bg.0 = if smooth {
+            Color::srgb(0.18, 0.18, 0.18)
+        } else {
+            theme::BG_CONTROL
+        };
+        *border = BorderColor::all(if smooth {
+            theme::ACCENT
+        } else {
+            theme::BORDER_SUBTLE
+        });
+        if let Some(&label) = children.first()
+            && let Ok(mut color) = text_colors.get_mut(label)
+        {
+            color.0 = if smooth {
+                theme::TEXT_PRIMARY
+            } else {
+                theme::TEXT_DIM
+            };
+        }
+    }
+}
+
+/// Show the selected point's values, or the text being typed.
+fn sync_point_fields(
+    state: Res<HueCurvesState>,
+    focus: Res<InputFocus>,
+    mut fields: Query<(Entity, &mut CurvePointField, &Children)>,
+    mut texts: Query<(&mut Text, &mut TextColor)>,
+) {
+    let selected = state.selected_point();
+    for (entity, mut field, children) in fields.iter_mut() {
+        // Leaving the field or the selection abandons a half-typed value.
+        if field.edit.is_some() && (focus.0 != Some(entity) || selected.is_none()) {
+            field.edit = None;
+        }
+        let Some(&label) = children.first() else {
+            continue;
+        };
+        let Ok((mut text, mut color)) = texts.get_mut(label) else {
+            continue;
+        };
+        let (content, tint) = match (selected, &field.edit) {
+            (Some(_), Some(edit)) => (format!("{edit}|"), theme::TEXT_PRIMARY),
+            (Some(point), None) => {
+                let value = point_field_value(state.mode, field.axis, point);
+                (
+                    format_point_field(state.mode, field.axis, value),
+                    theme::TEXT_PRIMARY,
+                )
+            }
+            (None, _) => ("-".to_string(), theme::TEXT_DIM),
+        };
+        if text.0 != content {
+            text.0 = content;
+        }
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+}
+
 fn map_ui_to_hue_offset(y: f32) -> f32 {
     (y - 0.5).clamp(-0.5, 0.5)
 }
@@ -729,6 +1147,7 @@ fn sync_hue_curves_to_grading_params(
 #[allow(clippy::type_complexity)]
 fn sync_curve_visuals(
     state: Res<HueCurvesState>,
+    grading: Res<GradingState>,
     q_plot: Query<Entity, With<HueCurvePlot>>,
     mut commands: Commands,
     mut thumbs: Query<
@@ -738,6 +1157,7 @@ fn sync_curve_visuals(
             &HueCurveDragState,
             &mut Node,
             &mut BackgroundColor,
+            &mut BorderColor,
         ),
         (With<HueCurveThumb>, Without<HueCurveTraceSample>),
     >,
@@ -762,7 +1182,7 @@ fn sync_curve_visuals(
     }
     let mut present_ids = Vec::with_capacity(active_points.len());
 
-    for (entity, thumb, drag_state, mut node, mut bg) in thumbs.iter_mut() {
+    for (entity, thumb, drag_state, mut node, mut bg, mut border) in thumbs.iter_mut() {
         let Some(point) = active_points
             .iter()
             .find(|point| point.id == thumb.point_id)
@@ -775,11 +1195,16 @@ fn sync_curve_visuals(
         present_ids.push(point.id);
         node.left = Val::Percent(point.x * 100.0);
         node.top = Val::Percent((1.0 - point.y) * 100.0);
-        bg.0 = if drag_state.active {
+        bg.0 = if drag_state.pointer.is_some() {
             Color::WHITE
         } else {
             theme::ACCENT
         };
+        *border = BorderColor::all(if state.selected == Some(point.id) {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        });
     }
 
     if !active_points.is_empty() {
@@ -810,28 +1235,95 @@ fn sync_curve_visuals(
     }
 
     for (sample, mut node) in samples.iter_mut() {
-        let y = sample_curve(active_points, sample.t);
+        let y = trace_curve(
+            state.mode,
+            active_points,
+            grading.params.curve_interpolation,
+            sample.t,
+        );
         node.left = Val::Percent(sample.t * 100.0);
         node.top = Val::Percent((1.0 - y) * 100.0);
     }
 }
 
+fn setup_curve_background(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new(
+        Extent3d {
+            width: curve_background::WIDTH,
+            height: curve_background::HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        curve_background::render(BackgroundAxis::Hue, None),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    commands.insert_resource(CurveBackgroundImage {
+        handle: images.add(image),
+    });
+}
+
+/// Redraw the plot background when the curve mode or the scope data
+/// changes, and point new background nodes at it.
+fn update_curve_background(
+    state: Res<HueCurvesState>,
+    scopes: Res<ScopeState>,
+    background: Option<Res<CurveBackgroundImage>>,
+    mut images: ResMut<Assets<Image>>,
+    mut nodes: Query<&mut ImageNode, With<CurveBackgroundNode>>,
+    mut drawn_mode: Local<Option<HueCurveMode>>,
+) {
+    let Some(background) = background else {
+        return;
+    };
+    for mut node in nodes.iter_mut() {
+        if node.image != background.handle {
+            node.image = background.handle.clone();
+        }
+    }
+    if *drawn_mode == Some(state.mode) && !scopes.is_changed() {
+        return;
+    }
+    *drawn_mode = Some(state.mode);
+
+    let axis = state.mode.background_axis();
+    let histogram = match axis {
+        BackgroundAxis::Hue => scopes
+            .vectorscope
+            .as_ref()
+            .map(curve_background::hue_histogram),
+        BackgroundAxis::Lightness => scopes
+            .histogram
+            .as_ref()
+            .map(curve_background::lightness_histogram),
+    };
+    if let Some(image) = images.get_mut(&background.handle) {
+        image.data = Some(curve_background::render(axis, histogram.as_deref()));
+    }
+}
+
 /// Registers Hue-vs-Curves state, observers, and sync systems.
 pub struct HueCurvesPlugin;
 
 impl Plugin for HueCurvesPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<HueCurvesState>();
+        app.init_resource::<HueCurvesState>()
+            .add_systems(Startup, setup_curve_background);
         app.add_systems(
             Update,
             (
                 handle_curve_mode_buttons,
+                handle_curve_smooth_toggle,
                 sync_curve_tab_visuals,
+                sync_smooth_toggle_visuals,
                 sync_hue_curves_to_grading_params,
+                update_curve_background,
             ),
         );
-        app.add_systems(PostUpdate, sync_curve_visuals);
+        app.add_systems(PostUpdate, (sync_curve_visuals, sync_point_fields));
         app.add_observer(on_curve_plot_press)
+            .add_observer(on_curve_plot_key)
+            .add_observer(on_point_field_key)
             .add_observer(on_curve_thumb_press)
             .add_observer(on_curve_thumb_drag_start)
             .add_observer(on_curve_thumb_drag)
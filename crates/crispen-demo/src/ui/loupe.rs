@@ -0,0 +1,334 @@
+//! Magnifier loupe — hold the loupe shortcut (Z by default) over the viewer
+//! to show a magnified inset that follows the cursor.
+//!
+//! The inset samples the viewer texture on the GPU through
+//! `shaders/loupe.wgsl`, snapped to whole image pixels, so it costs no
+//! readback or CPU resampling. Magnification is relative to the image as
+//! currently shown, 4x to 16x; scrolling while the key is held steps it
+//! and does not zoom the viewer. Once an image pixel covers enough screen
+//! pixels a grid is drawn between them, and the pixel under the cursor is
+//! outlined. A readout below the inset shows its position and the
+//! viewer's values there: sRGB code values for 8-bit viewer frames, linear
+//! floats for half and float ones.
+
+use bevy::asset::embedded_asset;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use bevy::render::render_resource::AsBindGroup;
+use bevy::shader::ShaderRef;
+use bevy::ui::{ComputedNode, ComputedUiRenderTargetInfo, UiGlobalTransform, UiScale};
+use bevy::ui_render::prelude::{MaterialNode, UiMaterial, UiMaterialPlugin};
+use bevy::window::PrimaryWindow;
+use crispen_bevy::ViewerFormat;
+use crispen_bevy::resources::ViewerData;
+
+use super::split_viewer::GradedImageNode;
+use super::theme;
+use super::viewer::ViewerImageHandle;
+use crate::keymap::{Action, KeyBindings};
+
+// ── Constants ───────────────────────────────────────────────────────────────
+
+/// Side of the square inset in logical pixels.
+const LOUPE_SIZE: f32 = 176.0;
+/// Gap between the cursor and the inset's corner, in logical pixels.
+const CURSOR_OFFSET: f32 = 24.0;
+/// Magnification steps, relative to the image as shown in the viewer.
+const ZOOM_STEPS: [f32; 3] = [4.0, 8.0, 16.0];
+
+// ── State ───────────────────────────────────────────────────────────────────
+
+/// Whether the loupe is showing and how far it magnifies.
+#[derive(Resource)]
+pub struct LoupeState {
+    /// Shown this frame: the shortcut is held over the viewer image.
+    pub visible: bool,
+    /// Index into [`ZOOM_STEPS`].
+    zoom_step: usize,
+}
+
+impl Default for LoupeState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            zoom_step: 1,
+        }
+    }
+}
+
+impl LoupeState {
+    /// Current magnification.
+    pub fn zoom(&self) -> f32 {
+        ZOOM_STEPS[self.zoom_step]
+    }
+}
+
+/// Root of the loupe: the inset and its readout.
+#[derive(Component)]
+struct LoupeRoot;
+
+/// Node drawing the magnified inset.
+#[derive(Component)]
+struct LoupeInset;
+
+/// Text under the inset with the pixel position and values.
+#[derive(Component)]
+struct LoupeReadout;
+
+// ── Material ────────────────────────────────────────────────────────────────
+
+/// UiMaterial sampling the viewer texture around the cursor.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct LoupeMaterial {
+    /// Viewer image UV under the cursor, at the inset's center.
+    #[uniform(0)]
+    pub center_uv: Vec2,
+    /// Viewer image UV extent the inset covers.
+    #[uniform(0)]
+    pub span_uv: Vec2,
+    /// Viewer image size in pixels.
+    #[uniform(0)]
+    pub image_size: Vec2,
+    /// Size of one image pixel inside the inset, in physical pixels.
+    #[uniform(0)]
+    pub pixel_px: f32,
+    /// The viewer texture.
+    #[texture(1)]
+    #[sampler(2)]
+    pub image: Handle<Image>,
+}
+
+impl UiMaterial for LoupeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://crispen_demo/ui/shaders/loupe.wgsl".into()
+    }
+}
+
+// ── Plugin ──────────────────────────────────────────────────────────────────
+
+/// Registers the loupe material, its nodes, and the systems driving them.
+pub struct LoupePlugin;
+
+impl Plugin for LoupePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "shaders/loupe.wgsl");
+        app.add_plugins(UiMaterialPlugin::<LoupeMaterial>::default())
+            .init_resource::<LoupeState>()
+            .add_systems(
+                Update,
+                (ensure_loupe_spawned, step_loupe_zoom, update_loupe).chain(),
+            );
+    }
+}
+
+/// One-shot system: spawn the loupe once the viewer texture exists.
+fn ensure_loupe_spawned(
+    mut commands: Commands,
+    viewer: Option<Res<ViewerImageHandle>>,
+    existing: Query<(), With<LoupeRoot>>,
+    mut materials: ResMut<Assets<LoupeMaterial>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let Some(viewer) = viewer else {
+        return;
+    };
+    let material = materials.add(LoupeMaterial {
+        center_uv: Vec2::splat(0.5),
+        span_uv: Vec2::ONE,
+        image_size: Vec2::ONE,
+        pixel_px: 1.0,
+        image: viewer.handle.clone(),
+    });
+    commands.spawn((
+        LoupeRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX - 1),
+        Pickable::IGNORE,
+        Visibility::Hidden,
+        children![
+            (
+                LoupeInset,
+                MaterialNode(material),
+                Node {
+                    width: Val::Px(LOUPE_SIZE),
+                    height: Val::Px(LOUPE_SIZE),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(theme::BORDER_SUBTLE),
+                Pickable::IGNORE,
+            ),
+            (
+                LoupeReadout,
+                Text::default(),
+                TextFont {
+                    font_size: theme::FONT_SIZE_VALUE,
+                    ..default()
+                },
+                TextColor(theme::TEXT_PRIMARY),
+                Node {
+                    padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(theme::BG_DARK),
+                Pickable::IGNORE,
+            ),
+        ],
+    ));
+}
+
+/// Step the magnification with the scroll wheel while the loupe shows.
+/// The viewer skips its own scroll zoom meanwhile.
+fn step_loupe_zoom(mut scroll: MessageReader<MouseWheel>, mut state: ResMut<LoupeState>) {
+    let total: f32 = scroll
+        .read()
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / 50.0,
+        })
+        .sum();
+    if !state.visible || total.abs() < f32::EPSILON {
+        return;
+    }
+    let step = if total > 0.0 {
+        (state.zoom_step + 1).min(ZOOM_STEPS.len() - 1)
+    } else {
+        state.zoom_step.saturating_sub(1)
+    };
+    if step != state.zoom_step {
+        state.zoom_step = step;
+    }
+}
+
+/// Show the loupe while the shortcut is held over the viewer image, and
+/// move it, its sampling window, and its readout with the cursor.
+#[allow(clippy::too_many_arguments)]
+fn update_loupe(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut state: ResMut<LoupeState>,
+    viewer_data: Res<ViewerData>,
+    ui_scale: Res<UiScale>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    graded: Query<
+        (
+            &ComputedNode,
+            &ComputedUiRenderTargetInfo,
+            &UiGlobalTransform,
+        ),
+        With<GradedImageNode>,
+    >,
+    mut root: Query<(&mut Node, &mut Visibility), With<LoupeRoot>>,
+    inset: Query<(&MaterialNode<LoupeMaterial>, &ComputedNode), With<LoupeInset>>,
+    mut readout: Query<&mut Text, With<LoupeReadout>>,
+    mut materials: ResMut<Assets<LoupeMaterial>>,
+) {
+    let Ok((mut root_node, mut visibility)) = root.single_mut() else {
+        return;
+    };
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let hovered = cursor.and_then(|cursor| {
+        graded.iter().find_map(|(node, target, transform)| {
+            let local = transform
+                .try_inverse()?
+                .transform_point2(cursor * target.scale_factor() / ui_scale.0);
+            let uv = local / node.size() + Vec2::splat(0.5);
+            let inside = uv.cmpge(Vec2::ZERO).all() && uv.cmple(Vec2::ONE).all();
+            inside.then_some((cursor, uv, node.size()))
+        })
+    });
+    let visible = viewer_data.width > 0 && bindings.pressed(Action::Loupe, &keys);
+    let Some((cursor, uv, shown_px)) = hovered.filter(|_| visible) else {
+        if state.visible {
+            state.visible = false;
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+    if !state.visible {
+        state.visible = true;
+        *visibility = Visibility::Inherited;
+    }
+
+    // Place the inset below-right of the cursor, flipping to the other side
+    // near the window's right and bottom edges.
+    let window_size = windows
+        .single()
+        .map(|w| Vec2::new(w.width(), w.height()) / ui_scale.0)
+        .unwrap_or(Vec2::splat(f32::MAX));
+    let at = cursor / ui_scale.0;
+    let extent = Vec2::new(LOUPE_SIZE, LOUPE_SIZE + 2.0 * CURSOR_OFFSET);
+    let x = if at.x + CURSOR_OFFSET + extent.x > window_size.x {
+        at.x - CURSOR_OFFSET - extent.x
+    } else {
+        at.x + CURSOR_OFFSET
+    };
+    let y = if at.y + CURSOR_OFFSET + extent.y > window_size.y {
+        at.y - CURSOR_OFFSET - extent.y
+    } else {
+        at.y + CURSOR_OFFSET
+    };
+    root_node.left = Val::Px(x.max(0.0));
+    root_node.top = Val::Px(y.max(0.0));
+
+    let image_size = Vec2::new(viewer_data.width as f32, viewer_data.height as f32);
+    if let Ok((material, inset_node)) = inset.single()
+        && let Some(material) = materials.get_mut(&material.0)
+    {
+        let zoom = state.zoom();
+        material.center_uv = uv;
+        material.span_uv = inset_node.size() / (shown_px * zoom);
+        material.image_size = image_size;
+        material.pixel_px = shown_px.x / image_size.x * zoom;
+    }
+
+    let pixel = (uv * image_size)
+        .floor()
+        .min(image_size - Vec2::ONE)
+        .as_uvec2();
+    if let Ok(mut text) = readout.single_mut() {
+        let values = pixel_value(&viewer_data, pixel.x, pixel.y)
+            .map(|[r, g, b]| format!("R {r:.3}  G {g:.3}  B {b:.3}"))
+            .unwrap_or_default();
+        *text = Text::new(format!(
+            "{}x  {}, {}\n{values}",
+            state.zoom(),
+            pixel.x,
+            pixel.y
+        ));
+    }
+}
+
+/// RGB of the viewer frame at pixel `(x, y)`: code values for 8-bit
+/// frames, linear values for half and float ones.
+fn pixel_value(data: &ViewerData, x: u32, y: u32) -> Option<[f32; 3]> {
+    if x >= data.width || y >= data.height {
+        return None;
+    }
+    let index = (y * data.width + x) as usize * 4;
+    let bytes = &data.pixel_bytes;
+    Some(match data.format {
+        ViewerFormat::Srgb8 => {
+            let px = bytes.get(index..index + 3)?;
+            [0, 1, 2].map(|c| px[c] as f32 / 255.0)
+        }
+        ViewerFormat::F16 => {
+            let px = bytes.get(index * 2..index * 2 + 6)?;
+            [0, 1, 2].map(|c| half::f16::from_le_bytes([px[c * 2], px[c * 2 + 1]]).to_f32())
+        }
+        ViewerFormat::F32 => {
+            let px = bytes.get(index * 4..index * 4 + 12)?;
+            [0, 1, 2].map(|c| {
+                f32::from_le_bytes([px[c * 4], px[c * 4 + 1], px[c * 4 + 2], px[c * 4 + 3]])
+            })
+        }
+    })
+}
@@ -3,9 +3,13 @@
 //! Controls the master (luminance) channel — index `[3]` of the
 //! lift / gamma / gain / offset arrays in `GradingParams`.
 //! Horizontal drag interaction: drag right to increase, left to decrease.
-//! Double-click resets to the identity value.
+//! Double-click resets to the identity value. When focused, the arrow
+//! keys nudge the value and Delete resets it.
 
 use bevy::asset::embedded_asset;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::FocusedInput;
+use bevy::input_focus::tab_navigation::TabIndex;
 use bevy::picking::events::{Cancel, Click, Drag, DragEnd, DragStart, Pointer, Press};
 use bevy::picking::pointer::PointerButton;
 use bevy::prelude::*;
@@ -16,13 +20,18 @@ use bevy::ui_render::prelude::{MaterialNode, UiMaterial, UiMaterialPlugin};
 use std::time::{Duration, Instant};
 
 use super::color_wheel::WheelType;
+use super::focus::{KeyAdjust, ValueClipboardKey, key_adjustment, value_clipboard_key};
 use super::theme;
+use crate::clipboard::NativeClipboard;
 
 // ── Constants ───────────────────────────────────────────────────────────────
 
 /// Pixels of horizontal drag for a full min→max sweep.
 const DRAG_PIXELS_FULL_RANGE: f32 = 300.0;
 
+/// Arrow-key presses for a full min→max sweep.
+const KEY_STEPS_FULL_RANGE: f32 = 200.0;
+
 /// Max time between two primary clicks to treat as a double-click reset.
 const DOUBLE_CLICK_MAX_GAP: Duration = Duration::from_millis(350);
 
@@ -145,6 +154,7 @@ pub fn master_slider(wheel_type: WheelType) -> impl Bundle {
             start_value: default,
         },
         MasterSliderClickState::default(),
+        TabIndex(0),
     )
 }
 
@@ -326,6 +336,68 @@ fn on_slider_click(
     }
 }
 
+// ── Observers (keyboard) ────────────────────────────────────────────────────
+
+/// Nudge the focused slider with the arrow keys; Delete resets it and
+/// Ctrl+C / Ctrl+V copy and paste its value.
+#[allow(clippy::type_complexity)]
+fn on_slider_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_sliders: Query<
+        (
+            &mut MasterSliderValue,
+            &MasterSliderDefault,
+            &MasterSliderRange,
+            Has<InteractionDisabled>,
+        ),
+        With<MasterSliderInner>,
+    >,
+    q_material: Query<&MaterialNode<MasterSliderMaterial>>,
+    mut materials: ResMut<Assets<MasterSliderMaterial>>,
+    mut clipboard: ResMut<NativeClipboard>,
+) {
+    let Ok((mut value, default_value, range, disabled)) = q_sliders.get_mut(key.focused_entity)
+    else {
+        return;
+    };
+    let adjust = match value_clipboard_key(&key.input, &keys) {
+        Some(ValueClipboardKey::Copy) => {
+            key.propagate(false);
+            clipboard.copy_value(value.0);
+            return;
+        }
+        Some(ValueClipboardKey::Paste) => clipboard.paste_value().map(KeyAdjust::Set),
+        None => key_adjustment(&key.input, &keys),
+    };
+    let Some(adjust) = adjust else {
+        return;
+    };
+    key.propagate(false);
+    if disabled {
+        return;
+    }
+
+    let target = match adjust {
+        KeyAdjust::Steps(steps) => {
+            let step = (range.max - range.min) / KEY_STEPS_FULL_RANGE;
+            snap_to_step(value.0 + steps * step, step, range.min).clamp(range.min, range.max)
+        }
+        KeyAdjust::Reset => default_value.0,
+        KeyAdjust::Set(pasted) => pasted.clamp(range.min, range.max),
+    };
+    if (value.0 - target).abs() <= f32::EPSILON {
+        return;
+    }
+    value.0 = target;
+
+    if let Ok(mat_node) = q_material.get(key.focused_entity)
+        && let Some(mat) = materials.get_mut(mat_node.id())
+    {
+        mat.value_norm = normalize_value(target, range.min, range.max);
+    }
+}
+
 // ── Systems ─────────────────────────────────────────────────────────────────
 
 /// Lazily insert `MaterialNode<MasterSliderMaterial>` on slider nodes.
@@ -373,6 +445,7 @@ impl Plugin for MasterSliderPlugin {
             .add_observer(on_slider_drag_start)
             .add_observer(on_slider_drag)
             .add_observer(on_slider_drag_end)
-            .add_observer(on_slider_drag_cancel);
+            .add_observer(on_slider_drag_cancel)
+            .add_observer(on_slider_key);
     }
 }
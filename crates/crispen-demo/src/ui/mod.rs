@@ -3,12 +3,21 @@
 //! Replaces the wry/Svelte webview with Bevy's built-in UI widgets,
 //! providing a DaVinci Resolve-style dark interface.
 
+pub mod chart_picker;
 pub mod color_wheel;
 pub mod components;
+pub mod curve_background;
+pub mod defect_marker;
+pub mod delta_e_overlay;
 pub mod dial;
+pub mod focus;
+pub mod focus_assist;
+pub mod guide_overlay;
 pub mod hue_curves;
 pub mod layout;
+pub mod loupe;
 pub mod master_slider;
+pub mod numeric_entry;
 pub mod ofx_panel;
 pub mod primaries;
 pub mod scope_mask;
@@ -19,6 +28,7 @@ pub mod toolbar;
 pub mod vectorscope;
 pub mod viewer;
 pub mod viewer_nav;
+pub mod wheel_readout;
 
 use bevy::prelude::*;
 use bevy::ui::IsDefaultUiCamera;
@@ -34,6 +44,15 @@ impl Plugin for CrispenUiPlugin {
             master_slider::MasterSliderPlugin,
             hue_curves::HueCurvesPlugin,
             scope_mask::ScopeMaskPlugin,
+            delta_e_overlay::DeltaEOverlayPlugin,
+            guide_overlay::GuideOverlayPlugin,
+            chart_picker::ChartPickerPlugin,
+            focus::KeyboardFocusPlugin,
+            numeric_entry::NumericEntryPlugin,
+            wheel_readout::WheelReadoutPlugin,
+            loupe::LoupePlugin,
+            focus_assist::FocusAssistPlugin,
+            defect_marker::DefectMarkerPlugin,
         ))
         .init_resource::<toolbar::ToolbarState>()
         .init_resource::<vectorscope::ScopeViewState>()
@@ -66,6 +85,7 @@ impl Plugin for CrispenUiPlugin {
                 dial::update_dial_visuals,
                 viewer::update_viewer_texture
                     .after(crispen_bevy::systems::consume_gpu_results),
+                viewer::sync_load_hint,
                 split_viewer::update_source_texture,
                 split_viewer::toggle_split_view,
                 toolbar::handle_toolbar_interactions,
@@ -79,15 +99,20 @@ impl Plugin for CrispenUiPlugin {
                 vectorscope::update_scope_texture
                     .after(crispen_bevy::systems::consume_gpu_results),
                 systems::handle_load_image_shortcut,
-                viewer_nav::handle_viewer_scroll,
-                viewer_nav::reset_viewer_transform,
-                viewer_nav::apply_viewer_transform,
+                (
+                    viewer_nav::handle_viewer_scroll,
+                    viewer_nav::handle_viewer_touch,
+                    viewer_nav::reset_viewer_transform,
+                    viewer_nav::apply_viewer_transform,
+                ),
             ),
         )
         .add_observer(systems::on_wheel_value_change)
         .add_observer(toolbar::on_toolbar_option_click)
         .add_observer(toolbar::on_toolbar_click_close_dropdown)
+        .add_observer(toolbar::on_toolbar_key)
         .add_observer(vectorscope::on_scope_option_click)
+        .add_observer(vectorscope::on_scope_dropdown_key)
         .add_observer(viewer_nav::on_viewer_drag_start)
         .add_observer(viewer_nav::on_viewer_drag)
         .add_observer(viewer_nav::on_viewer_drag_end)
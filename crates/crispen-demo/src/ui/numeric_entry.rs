@@ -0,0 +1,215 @@
+//! Numeric entry popover.
+//!
+//! A small text field that opens over a value readout when it is clicked
+//! or activated from the keyboard. Enter commits the typed number as a
+//! [`ValueChange<f32>`] on the readout that opened it, which interprets
+//! the number in whatever unit it displays. Escape, or focus moving
+//! elsewhere, closes the popover without a change.
+//!
+//! The popover takes keyboard focus while open, so typing never reaches
+//! the viewer shortcuts. Only one popover exists at a time; opening
+//! another replaces it.
+
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input_focus::{FocusedInput, InputFocus, InputFocusVisible};
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use bevy::ui_widgets::ValueChange;
+
+use super::theme;
+
+const POPOVER_WIDTH: f32 = 72.0;
+const POPOVER_HEIGHT: f32 = 22.0;
+
+/// Ask for a numeric entry popover under a readout.
+#[derive(Event, Debug, Clone)]
+pub struct OpenNumericEntry {
+    /// Readout that receives the committed value.
+    pub source: Entity,
+    /// Number shown when the popover opens, without units.
+    pub text: String,
+    /// Top-left corner of the popover in logical UI pixels.
+    pub position: Vec2,
+}
+
+impl OpenNumericEntry {
+    /// Place the popover just below `node`, centered on it.
+    pub fn below(
+        source: Entity,
+        text: String,
+        node: &ComputedNode,
+        transform: &UiGlobalTransform,
+    ) -> Self {
+        let scale = node.inverse_scale_factor();
+        let center = transform.translation * scale;
+        let size = node.size() * scale;
+        Self {
+            source,
+            text,
+            position: Vec2::new(center.x - POPOVER_WIDTH * 0.5, center.y + size.y * 0.5),
+        }
+    }
+}
+
+/// The open popover.
+#[derive(Component)]
+pub(crate) struct NumericEntryPopover {
+    source: Entity,
+    text: String,
+    /// The opening value is still untouched; the first typed character
+    /// replaces it instead of appending.
+    fresh: bool,
+}
+
+/// Marker on the popover's text.
+#[derive(Component)]
+struct NumericEntryText;
+
+fn on_open_numeric_entry(
+    open: On<OpenNumericEntry>,
+    existing: Query<Entity, With<NumericEntryPopover>>,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+    mut commands: Commands,
+) {
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+    let popover = commands
+        .spawn((
+            NumericEntryPopover {
+                source: open.source,
+                text: open.text.clone(),
+                fresh: true,
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(open.position.x),
+                top: Val::Px(open.position.y),
+                width: Val::Px(POPOVER_WIDTH),
+                height: Val::Px(POPOVER_HEIGHT),
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(0.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(3.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(theme::BG_CONTROL),
+            BorderColor::all(theme::ACCENT),
+            GlobalZIndex(700),
+            children![(
+                NumericEntryText,
+                Text::new(format!("{}|", open.text)),
+                TextFont {
+                    font_size: theme::FONT_SIZE_LABEL,
+                    ..default()
+                },
+                TextColor(theme::TEXT_PRIMARY),
+                Pickable::IGNORE,
+            )],
+        ))
+        .id();
+    focus.0 = Some(popover);
+    // The popover draws its own accent border.
+    focus_visible.0 = false;
+}
+
+/// Typing edits the number; Enter commits and Escape cancels.
+fn on_numeric_entry_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut popovers: Query<(&mut NumericEntryPopover, &Children)>,
+    mut texts: Query<&mut Text, With<NumericEntryText>>,
+    mut commands: Commands,
+) {
+    let entity = key.focused_entity;
+    let Ok((mut popover, children)) = popovers.get_mut(entity) else {
+        return;
+    };
+    if key.input.state != ButtonState::Pressed {
+        return;
+    }
+    key.propagate(false);
+
+    match key.input.key_code {
+        KeyCode::Enter | KeyCode::NumpadEnter => {
+            if let Ok(value) = parse_number(&popover.text) {
+                commands.trigger(ValueChange {
+                    source: popover.source,
+                    value,
+                });
+            }
+            commands.entity(entity).despawn();
+            return;
+        }
+        KeyCode::Escape => {
+            commands.entity(entity).despawn();
+            return;
+        }
+        KeyCode::Backspace => {
+            if popover.fresh {
+                popover.text.clear();
+            } else {
+                popover.text.pop();
+            }
+        }
+        _ => {
+            let Key::Character(typed) = &key.input.logical_key else {
+                return;
+            };
+            if keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+                || !typed
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-' | '+'))
+            {
+                return;
+            }
+            if popover.fresh {
+                popover.text.clear();
+            }
+            popover.text.push_str(typed);
+        }
+    }
+    popover.fresh = false;
+
+    if let Some(&child) = children.first()
+        && let Ok(mut text) = texts.get_mut(child)
+    {
+        text.0 = format!("{}|", popover.text);
+    }
+}
+
+/// Close the popover once something else takes focus.
+fn close_numeric_entry_on_blur(
+    focus: Res<InputFocus>,
+    popovers: Query<Entity, With<NumericEntryPopover>>,
+    mut commands: Commands,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+    for entity in &popovers {
+        if focus.0 != Some(entity) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Parse a typed number, accepting a comma decimal separator and a
+/// leading plus sign.
+fn parse_number(text: &str) -> Result<f32, std::num::ParseFloatError> {
+    let text = text.trim().replace(',', ".");
+    text.strip_prefix('+').unwrap_or(&text).parse()
+}
+
+/// Registers the numeric entry popover.
+pub struct NumericEntryPlugin;
+
+impl Plugin for NumericEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_open_numeric_entry)
+            .add_observer(on_numeric_entry_key)
+            .add_systems(PostUpdate, close_numeric_entry_on_blur);
+    }
+}
@@ -5,6 +5,7 @@ use crispen_ofx::host::{OfxHost, OfxLoadFailure, OfxPluginDescriptor};
 
 use super::theme;
 use super::toolbar::ToolbarState;
+use crate::i18n::{tr, tr_args};
 
 /// Startup snapshot of discovered OFX plugins and non-fatal load failures.
 #[derive(Resource, Default)]
@@ -47,7 +48,10 @@ pub fn spawn_ofx_panel(parent: &mut ChildSpawnerCommands, registry: &OfxPluginRe
         ))
         .with_children(|panel| {
             panel.spawn((
-                Text::new(format!("OFX Plugins ({})", registry.plugins.len())),
+                Text::new(tr_args(
+                    "ofx-title",
+                    &[("count", registry.plugins.len().into())],
+                )),
                 TextFont {
                     font_size: 13.0,
                     ..default()
@@ -101,7 +105,7 @@ pub fn spawn_ofx_panel(parent: &mut ChildSpawnerCommands, registry: &OfxPluginRe
 
             if !registry.failures.is_empty() {
                 panel.spawn((
-                    Text::new("Load Failures"),
+                    Text::new(tr("ofx-load-failures")),
                     Node {
                         margin: UiRect::top(Val::Px(4.0)),
                         ..default()
@@ -12,14 +12,17 @@
 //! └──────────────────────────────────────────────────────────────────┘
 //! ```
 
+use bevy::input_focus::tab_navigation::TabGroup;
 use bevy::prelude::*;
 
 use super::color_wheel::{WheelType, color_wheel};
 use super::components::{ParamId, param_default, param_label, param_range, param_step};
 use super::dial::{DialLabelPosition, spawn_param_dial};
+use super::focus;
 use super::hue_curves;
 use super::theme;
 use super::vectorscope;
+use crate::i18n::tr;
 
 /// Spawn the primaries panel as a child of the given parent.
 pub fn spawn_primaries_panel(parent: &mut ChildSpawnerCommands, vectorscope_handle: Handle<Image>) {
@@ -42,17 +45,20 @@ pub fn spawn_primaries_panel(parent: &mut ChildSpawnerCommands, vectorscope_hand
         ))
         .with_children(|panel| {
             panel
-                .spawn(Node {
-                    display: Display::Flex,
-                    flex_direction: FlexDirection::Column,
-                    row_gap: Val::Px(6.0),
-                    width: Val::Px(theme::PRIMARIES_SECTION_WIDTH),
-                    flex_shrink: 0.0,
-                    ..default()
-                })
+                .spawn((
+                    Node {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(6.0),
+                        width: Val::Px(theme::PRIMARIES_SECTION_WIDTH),
+                        flex_shrink: 0.0,
+                        ..default()
+                    },
+                    TabGroup::new(focus::PRIMARIES_TAB_GROUP),
+                ))
                 .with_children(|primaries| {
                     primaries.spawn((
-                        Text::new("Primaries"),
+                        Text::new(tr("primaries-title")),
                         TextFont {
                             font_size: 14.0,
                             ..default()
@@ -92,7 +98,7 @@ pub fn spawn_primaries_panel(parent: &mut ChildSpawnerCommands, vectorscope_hand
 fn dial(parent: &mut ChildSpawnerCommands, id: ParamId, label_position: DialLabelPosition) {
     spawn_param_dial(
         parent,
-        param_label(id),
+        &param_label(id),
         id,
         param_range(id),
         param_default(id),
@@ -122,6 +128,15 @@ fn spawn_top_dials(panel: &mut ChildSpawnerCommands) {
         });
 }
 
+/// Height of one wheel column: wheel, label, master slider, and readouts,
+/// with the 3 px gaps between them.
+const WHEEL_COLUMN_HEIGHT: f32 = theme::WHEEL_SIZE
+    + 20.0
+    + 3.0
+    + theme::MASTER_SLIDER_HEIGHT
+    + 3.0
+    + theme::WHEEL_READOUT_HEIGHT;
+
 fn spawn_wheels_row(panel: &mut ChildSpawnerCommands) {
     panel
         .spawn(Node {
@@ -131,9 +146,9 @@ fn spawn_wheels_row(panel: &mut ChildSpawnerCommands) {
             align_items: AlignItems::Center,
             align_self: AlignSelf::Center,
             width: Val::Px(theme::WHEEL_GROUP_WIDTH),
-            height: Val::Px(theme::WHEEL_SIZE + 20.0 + 3.0 + theme::MASTER_SLIDER_HEIGHT),
-            min_height: Val::Px(theme::WHEEL_SIZE + 20.0 + 3.0 + theme::MASTER_SLIDER_HEIGHT),
-            max_height: Val::Px(theme::WHEEL_SIZE + 20.0 + 3.0 + theme::MASTER_SLIDER_HEIGHT),
+            height: Val::Px(WHEEL_COLUMN_HEIGHT),
+            min_height: Val::Px(WHEEL_COLUMN_HEIGHT),
+            max_height: Val::Px(WHEEL_COLUMN_HEIGHT),
             ..default()
         })
         .with_children(|row| {
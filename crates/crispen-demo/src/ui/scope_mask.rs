@@ -11,10 +11,11 @@ use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use bevy::ui::{ComputedNode, ComputedUiRenderTargetInfo, UiGlobalTransform, UiScale};
 
-use crispen_bevy::resources::{GradingState, ImageState, ScopeMaskData};
+use crispen_bevy::resources::{GpuPipelineState, GradingState, ImageState, ScopeMaskData};
 
 use super::split_viewer::GradedImageNode;
 use super::theme;
+use crate::i18n::tr;
 
 // ── Resources ───────────────────────────────────────────────────────
 
@@ -70,7 +71,8 @@ impl Plugin for ScopeMaskPlugin {
                     handle_mask_button_interactions,
                     handle_mask_shortcuts,
                     update_scope_mask,
-                    update_mask_overlay_texture,
+                    request_mask_overlay,
+                    apply_mask_overlay,
                     sync_mask_button_visuals,
                 ),
             )
@@ -146,7 +148,7 @@ pub fn spawn_mask_buttons(parent: &mut ChildSpawnerCommands) {
         .with_children(|button| {
             button.spawn((
                 ScopeMaskButtonLabel,
-                Text::new("Mask"),
+                Text::new(tr("scope-mask")),
                 TextFont {
                     font_size: theme::FONT_SIZE_LABEL,
                     ..default()
@@ -175,7 +177,7 @@ pub fn spawn_mask_buttons(parent: &mut ChildSpawnerCommands) {
         ))
         .with_children(|button| {
             button.spawn((
-                Text::new("Clear"),
+                Text::new(tr("scope-mask-clear")),
                 TextFont {
                     font_size: theme::FONT_SIZE_LABEL,
                     ..default()
@@ -206,7 +208,7 @@ pub fn spawn_mask_overlay(parent: &mut ChildSpawnerCommands, handle: Handle<Imag
 // ── Pointer interaction ─────────────────────────────────────────────
 
 /// Convert pointer screen position to normalized 0..1 UV within the image node.
-fn pointer_to_image_uv(
+pub(crate) fn pointer_to_image_uv(
     pointer_pos: Vec2,
     node: &ComputedNode,
     node_target: &ComputedUiRenderTargetInfo,
@@ -347,56 +349,9 @@ fn on_viewer_drag_end(
     state.mask_dirty = true;
 }
 
-// ── Mask rasterization ──────────────────────────────────────────────
-
-/// Rasterize a normalized polygon into a per-pixel mask using scanline fill (even-odd rule).
-fn rasterize_polygon(polygon: &[Vec2], width: u32, height: u32) -> Vec<u32> {
-    let pixel_count = (width as usize) * (height as usize);
-    let mut mask = vec![0u32; pixel_count];
-
-    if polygon.len() < 3 {
-        return mask;
-    }
-
-    for y in 0..height {
-        let py = (y as f32 + 0.5) / height as f32;
-
-        // Find x-intersections of the scanline with polygon edges.
-        let mut intersections = Vec::new();
-        let n = polygon.len();
-        for i in 0..n {
-            let j = (i + 1) % n;
-            let (y0, y1) = (polygon[i].y, polygon[j].y);
-
-            let (min_y, max_y) = if y0 < y1 { (y0, y1) } else { (y1, y0) };
-            if py < min_y || py >= max_y {
-                continue;
-            }
-
-            let t = (py - y0) / (y1 - y0);
-            let x = polygon[i].x + t * (polygon[j].x - polygon[i].x);
-            intersections.push(x);
-        }
-
-        intersections.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Fill between pairs of intersections (even-odd rule).
-        for pair in intersections.chunks_exact(2) {
-            let x_start = ((pair[0] * width as f32).max(0.0) as u32).min(width);
-            let x_end = ((pair[1] * width as f32).ceil().max(0.0) as u32).min(width);
-            let row_offset = (y * width) as usize;
-            for x in x_start..x_end {
-                mask[row_offset + x as usize] = 1;
-            }
-        }
-    }
-
-    mask
-}
-
 // ── Update systems ──────────────────────────────────────────────────
 
-/// When the mask polygon changes, rasterize it and push to the GPU mask resource.
+/// When the mask polygon changes, hand it to the GPU mask resource.
 fn update_scope_mask(
     mut state: ResMut<ScopeMaskState>,
     image_state: Res<ImageState>,
@@ -412,38 +367,23 @@ fn update_scope_mask(
         return;
     };
 
-    let has_polygon = state.polygon.len() >= 3;
-
-    if has_polygon {
-        let mask = rasterize_polygon(&state.polygon, source.width, source.height);
-        mask_data.mask = mask;
-        mask_data.active = true;
-    } else {
-        mask_data.mask.clear();
-        mask_data.active = false;
-    }
-
-    mask_data.dirty = true;
+    mask_data.set_polygon(&state.polygon, source.width(), source.height());
     grading_state.dirty = true;
 }
 
-/// Render the mask overlay texture and update the Bevy image asset.
+/// Show or hide the mask overlay and request a GPU render of it when the
+/// polygon changes.
 #[allow(clippy::type_complexity)]
-fn update_mask_overlay_texture(
+fn request_mask_overlay(
     state: Res<ScopeMaskState>,
     image_state: Res<ImageState>,
-    overlay_handle: Option<Res<ScopeMaskOverlayHandle>>,
-    mut images: ResMut<Assets<Image>>,
+    gpu: Option<ResMut<GpuPipelineState>>,
     mut overlay_vis: Query<&mut Visibility, With<ScopeMaskOverlayNode>>,
 ) {
     if !state.is_changed() {
         return;
     }
 
-    let Some(overlay_handle) = overlay_handle else {
-        return;
-    };
-
     let has_polygon = state.polygon.len() >= 3;
     let has_tool = state.tool_active;
 
@@ -460,25 +400,41 @@ fn update_mask_overlay_texture(
         return;
     }
 
-    let Some(ref source) = image_state.source else {
+    let (Some(source), Some(mut gpu)) = (image_state.source.as_ref(), gpu) else {
         return;
     };
 
-    // Cap overlay resolution for performance.
+    // Cap overlay resolution: it is read back and uploaded as a texture.
     let max_dim = 1024u32;
-    let scale = if source.width > max_dim || source.height > max_dim {
-        max_dim as f32 / source.width.max(source.height) as f32
+    let (width, height) = (source.width(), source.height());
+    let scale = if width > max_dim || height > max_dim {
+        max_dim as f32 / width.max(height) as f32
     } else {
         1.0
     };
-    let ov_w = ((source.width as f32 * scale) as u32).max(1);
-    let ov_h = ((source.height as f32 * scale) as u32).max(1);
+    let ov_w = ((width as f32 * scale) as u32).max(1);
+    let ov_h = ((height as f32 * scale) as u32).max(1);
 
-    let rgba = render_mask_overlay(&state.polygon, ov_w, ov_h);
+    let polygon: Vec<[f32; 2]> = state.polygon.iter().map(|p| p.to_array()).collect();
+    gpu.pipeline.request_mask_overlay(&polygon, ov_w, ov_h);
+}
+
+/// Copy a finished GPU overlay render into the overlay image asset.
+fn apply_mask_overlay(
+    gpu: Option<ResMut<GpuPipelineState>>,
+    overlay_handle: Option<Res<ScopeMaskOverlayHandle>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (Some(mut gpu), Some(overlay_handle)) = (gpu, overlay_handle) else {
+        return;
+    };
+    let Some(overlay) = gpu.pipeline.try_take_mask_overlay() else {
+        return;
+    };
 
     let new_size = Extent3d {
-        width: ov_w,
-        height: ov_h,
+        width: overlay.width,
+        height: overlay.height,
         depth_or_array_layers: 1,
     };
 
@@ -489,95 +445,12 @@ fn update_mask_overlay_texture(
             *existing = Image::new(
                 new_size,
                 TextureDimension::D2,
-                rgba,
+                overlay.rgba,
                 TextureFormat::Rgba8UnormSrgb,
                 RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
             );
         } else {
-            existing.data = Some(rgba);
-        }
-    }
-}
-
-/// Render a mask overlay RGBA image: transparent inside, semi-dark outside, cyan outline.
-fn render_mask_overlay(polygon: &[Vec2], width: u32, height: u32) -> Vec<u8> {
-    let pixel_count = (width as usize) * (height as usize);
-    let mut rgba = vec![0u8; pixel_count * 4];
-
-    // Build the inside mask via scanline fill.
-    let inside = if polygon.len() >= 3 {
-        rasterize_polygon(polygon, width, height)
-    } else {
-        vec![0u32; pixel_count]
-    };
-
-    // Fill: outside gets a semi-transparent dark overlay, inside is transparent.
-    for i in 0..pixel_count {
-        let base = i * 4;
-        if inside[i] == 0 {
-            rgba[base] = 0;
-            rgba[base + 1] = 0;
-            rgba[base + 2] = 0;
-            rgba[base + 3] = 100; // ~39% opacity
-        }
-        // else: fully transparent (rgba remains 0,0,0,0)
-    }
-
-    // Draw the polygon outline in cyan.
-    let n = polygon.len();
-    if n >= 2 {
-        let segments = if n >= 3 { n } else { n - 1 };
-        for i in 0..segments {
-            let j = (i + 1) % n;
-            draw_overlay_line(
-                &mut rgba,
-                width,
-                height,
-                polygon[i],
-                polygon[j],
-                [0, 200, 255, 220],
-            );
-        }
-    }
-
-    rgba
-}
-
-/// Draw an anti-aliased line segment onto an RGBA overlay.
-fn draw_overlay_line(rgba: &mut [u8], width: u32, height: u32, a: Vec2, b: Vec2, color: [u8; 4]) {
-    let ax = a.x * width as f32;
-    let ay = a.y * height as f32;
-    let bx = b.x * width as f32;
-    let by = b.y * height as f32;
-
-    let dx = (bx - ax).abs();
-    let dy = (by - ay).abs();
-    let step_count = (dx.max(dy) as u32).max(1);
-
-    for i in 0..=step_count {
-        let t = i as f32 / step_count as f32;
-        let px = ax + (bx - ax) * t;
-        let py = ay + (by - ay) * t;
-
-        let ix = px.round() as i32;
-        let iy = py.round() as i32;
-
-        // 3px wide line for visibility.
-        for oy in -1..=1i32 {
-            for ox in -1..=1i32 {
-                let x = ix + ox;
-                let y = iy + oy;
-                if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
-                    let idx = (y as u32 * width + x as u32) as usize * 4;
-                    // Alpha-blend the line color over the current pixel.
-                    let ca = color[3] as f32 / 255.0;
-                    let inv_a = 1.0 - ca;
-                    rgba[idx] = (color[0] as f32 * ca + rgba[idx] as f32 * inv_a) as u8;
-                    rgba[idx + 1] = (color[1] as f32 * ca + rgba[idx + 1] as f32 * inv_a) as u8;
-                    rgba[idx + 2] = (color[2] as f32 * ca + rgba[idx + 2] as f32 * inv_a) as u8;
-                    rgba[idx + 3] = rgba[idx + 3].max(color[3]);
-                }
-            }
+            existing.data = Some(overlay.rgba);
         }
     }
 }
@@ -616,9 +489,7 @@ fn clear_mask(
     state.polygon.clear();
     state.drawing = false;
     state.mask_dirty = true;
-    mask_data.mask.clear();
-    mask_data.active = false;
-    mask_data.dirty = true;
+    mask_data.clear();
     grading_state.dirty = true;
 }
 
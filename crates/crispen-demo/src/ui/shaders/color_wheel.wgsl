@@ -1,6 +1,9 @@
 // Color wheel fragment shader for DaVinci Resolve-style Lift/Gamma/Gain/Offset wheels.
 //
-// Draws an outer HSV hue ring and a neutral inner circle.
+// Draws an outer HSV hue ring, a neutral inner circle shaded toward the
+// current balance, the reset tick at neutral, and the value indicator.
+// Each pixel averages `samples x samples` subsamples; edges are smoothed
+// over `aa_px` physical pixels.
 
 #import bevy_ui::ui_vertex_output::UiVertexOutput
 
@@ -8,6 +11,9 @@ struct ColorWheelUniforms {
     cursor_x: f32,
     cursor_y: f32,
     master: f32,
+    size_px: f32,
+    aa_px: f32,
+    samples: u32,
 }
 
 @group(1) @binding(0) var<uniform> material: ColorWheelUniforms;
@@ -20,8 +26,9 @@ const RING_OUTER: f32 = 0.48;
 const RING_INNER: f32 = 0.43;
 // Inner circle radius (just inside the ring, with a small gap).
 const CIRCLE_R: f32 = 0.405;
-// Anti-alias width.
-const AA: f32 = 0.005;
+// Indicator and reset tick radii, in pixels.
+const INDICATOR_R: f32 = 4.5;
+const RESET_TICK_R: f32 = 2.5;
 
 fn hsv_to_rgb(h: f32, s: f32, v: f32) -> vec3<f32> {
     let c = v * s;
@@ -45,40 +52,65 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> vec3<f32> {
     return rgb + vec3(m);
 }
 
-@fragment
-fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-    let p = in.uv - vec2(0.5);
+// Coverage of a ring of radius `radius_px` and 1px stroke at distance `d_px`.
+fn stroke(d_px: f32, radius_px: f32) -> f32 {
+    let half_aa = material.aa_px * 0.5;
+    return 1.0 - smoothstep(0.5 - half_aa, 0.5 + half_aa, abs(d_px - radius_px));
+}
+
+// Straight-alpha `top` over `base`.
+fn over(top: vec4<f32>, base: vec4<f32>) -> vec4<f32> {
+    let a = top.a + base.a * (1.0 - top.a);
+    if a <= 0.0 {
+        return vec4(0.0);
+    }
+    let rgb = (top.rgb * top.a + base.rgb * base.a * (1.0 - top.a)) / a;
+    return vec4(rgb, a);
+}
+
+// Wheel body: hue ring and shaded inner circle.
+fn wheel_body(p: vec2<f32>, aa: f32) -> vec4<f32> {
     let r = length(p);
 
     // Outside everything: fully transparent.
-    if r > RING_OUTER + AA {
-        return vec4(0.0, 0.0, 0.0, 0.0);
+    if r > RING_OUTER + aa {
+        return vec4(0.0);
     }
 
+    let angle = atan2(p.y, p.x);
+    let hue = fract(angle / TWO_PI);
+
     // Outer ring: HSV hue wheel.
-    if r > RING_INNER - AA {
-        let angle = atan2(p.y, p.x);
-        let hue = fract(angle / TWO_PI);
+    if r > RING_INNER - aa {
         let rgb = hsv_to_rgb(hue, 1.0, 0.98);
         // Anti-aliased edges on both sides of the ring.
-        let outer_alpha = 1.0 - smoothstep(RING_OUTER - AA, RING_OUTER + AA, r);
-        let inner_alpha = smoothstep(RING_INNER - AA, RING_INNER + AA, r);
+        let outer_alpha = 1.0 - smoothstep(RING_OUTER - aa, RING_OUTER + aa, r);
+        let inner_alpha = smoothstep(RING_INNER - aa, RING_INNER + aa, r);
         return vec4(rgb, outer_alpha * inner_alpha);
     }
 
-    // Inner circle: static color field (independent of thumb position).
-    let inner_alpha = 1.0 - smoothstep(CIRCLE_R - AA, CIRCLE_R + AA, r);
+    // Inner circle: static color field.
+    let inner_alpha = 1.0 - smoothstep(CIRCLE_R - aa, CIRCLE_R + aa, r);
     let radial = clamp(r / CIRCLE_R, 0.0, 1.0);
-    let angle = atan2(p.y, p.x);
-    let hue = fract(angle / TWO_PI);
     let sat = smoothstep(0.08, 1.0, radial) * 0.9;
     let val = 0.22 + (1.0 - radial) * 0.18;
     var color = hsv_to_rgb(hue, sat, val);
 
+    // Saturation shading: tint the side the balance is pushed toward,
+    // stronger the further the indicator is from neutral.
+    let cursor = vec2(material.cursor_x, material.cursor_y) * 0.5;
+    let amount = clamp(length(cursor) / CIRCLE_R, 0.0, 1.0);
+    if amount > 0.0 {
+        let cursor_angle = atan2(cursor.y, cursor.x);
+        let toward = max(cos(angle - cursor_angle), 0.0);
+        let tint = hsv_to_rgb(fract(cursor_angle / TWO_PI), 0.85, 0.55);
+        color = mix(color, tint, toward * toward * amount * radial * 0.45);
+    }
+
     // Resolve-like faint cross guides.
     let line_w = 0.0035;
-    let cross_x = 1.0 - smoothstep(line_w, line_w + AA, abs(p.x));
-    let cross_y = 1.0 - smoothstep(line_w, line_w + AA, abs(p.y));
+    let cross_x = 1.0 - smoothstep(line_w, line_w + aa, abs(p.x));
+    let cross_y = 1.0 - smoothstep(line_w, line_w + aa, abs(p.y));
     let cross = max(cross_x, cross_y) * 0.22;
     color = mix(color, vec3(0.78), cross);
 
@@ -86,7 +118,45 @@ fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let center_lift = 1.0 - smoothstep(0.0, 0.11, radial);
     color = mix(color, vec3(0.6), center_lift * 0.2);
 
-    // Keep `master` bound for compatibility without thumb-driven hue shifts.
     color *= 0.92 + (material.master - 0.5) * 0.02;
     return vec4(color, inner_alpha);
 }
+
+// One subsample: the body with the reset tick and value indicator on top.
+fn shade(p: vec2<f32>, aa: f32) -> vec4<f32> {
+    let size_px = max(material.size_px, 1.0);
+    var color = wheel_body(p, aa);
+
+    // Reset tick at neutral.
+    let tick = stroke(length(p) * size_px, RESET_TICK_R);
+    color = over(vec4(vec3(0.85), tick * 0.7), color);
+
+    // Value indicator: white ring with a black outline.
+    let cursor = vec2(material.cursor_x, material.cursor_y) * 0.5;
+    let d_px = length(p - cursor) * size_px;
+    color = over(vec4(vec3(0.0), stroke(d_px, INDICATOR_R + 1.0)), color);
+    color = over(vec4(vec3(1.0), stroke(d_px, INDICATOR_R)), color);
+    return color;
+}
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let p = in.uv - vec2(0.5);
+    let px = 1.0 / max(material.size_px, 1.0);
+    let aa = max(material.aa_px, 0.01) * px;
+
+    let n = max(material.samples, 1u);
+    var acc = vec4(0.0);
+    for (var sy = 0u; sy < n; sy++) {
+        for (var sx = 0u; sx < n; sx++) {
+            let offset = ((vec2(f32(sx), f32(sy)) + 0.5) / f32(n) - 0.5) * px;
+            let c = shade(p + offset, aa);
+            acc += vec4(c.rgb * c.a, c.a);
+        }
+    }
+    acc /= f32(n * n);
+    if acc.a <= 0.0 {
+        return vec4(0.0);
+    }
+    return vec4(acc.rgb / acc.a, acc.a);
+}
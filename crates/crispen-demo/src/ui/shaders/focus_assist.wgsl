@@ -0,0 +1,55 @@
+// Focus assist fragment shader.
+//
+// Runs a 3x3 Sobel filter over the luma of the viewer texture at image
+// resolution and tints pixels whose edge strength passes `threshold`. Luma
+// is gamma-encoded first so the threshold tracks perceived contrast rather
+// than linear light, which would mark every edge in the highlights. The
+// magnitude is scaled so a full black-to-white step reads as 1.
+
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+struct FocusAssistUniforms {
+    // Size of one image pixel in UV.
+    texel: vec2<f32>,
+    threshold: f32,
+    // Linear-light tint; alpha unused.
+    color: vec4<f32>,
+}
+
+@group(1) @binding(0) var<uniform> focus: FocusAssistUniforms;
+@group(1) @binding(1) var image: texture_2d<f32>;
+@group(1) @binding(2) var image_sampler: sampler;
+
+const LUMA: vec3<f32> = vec3(0.2126, 0.7152, 0.0722);
+// Edges fade in over this fraction above the threshold instead of popping.
+const SOFTNESS: f32 = 0.5;
+
+fn luma(uv: vec2<f32>, dx: f32, dy: f32) -> f32 {
+    // Explicit LOD: the node may be minified, and the filter wants image
+    // pixels, not a mip.
+    let rgb = textureSampleLevel(image, image_sampler, uv + vec2(dx, dy) * focus.texel, 0.0).rgb;
+    return pow(max(dot(rgb, LUMA), 0.0), 1.0 / 2.2);
+}
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    // Sample at image pixel centers so the kernel does not blur across two.
+    let uv = (floor(in.uv / focus.texel) + 0.5) * focus.texel;
+
+    let tl = luma(uv, -1.0, -1.0);
+    let t = luma(uv, 0.0, -1.0);
+    let tr = luma(uv, 1.0, -1.0);
+    let l = luma(uv, -1.0, 0.0);
+    let r = luma(uv, 1.0, 0.0);
+    let bl = luma(uv, -1.0, 1.0);
+    let b = luma(uv, 0.0, 1.0);
+    let br = luma(uv, 1.0, 1.0);
+
+    let gx = (tr + 2.0 * r + br) - (tl + 2.0 * l + bl);
+    let gy = (bl + 2.0 * b + br) - (tl + 2.0 * t + tr);
+    let magnitude = length(vec2(gx, gy)) * 0.25;
+
+    let threshold = max(focus.threshold, 1e-3);
+    let alpha = smoothstep(threshold, threshold * (1.0 + SOFTNESS), magnitude);
+    return vec4(focus.color.rgb, alpha);
+}
@@ -0,0 +1,59 @@
+// Magnifier loupe fragment shader.
+//
+// Shows the viewer texture around `center_uv`, `span_uv` wide, with every
+// sample snapped to the center of its image pixel so pixels stay crisp
+// squares at any magnification. Once an image pixel is at least
+// GRID_MIN_PX wide a grid is drawn between pixels, and the pixel under the
+// cursor is outlined. Outside the image the inset shows the viewer
+// background.
+
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+struct LoupeUniforms {
+    center_uv: vec2<f32>,
+    span_uv: vec2<f32>,
+    image_size: vec2<f32>,
+    // Size of one image pixel in the inset, in physical pixels.
+    pixel_px: f32,
+}
+
+@group(1) @binding(0) var<uniform> loupe: LoupeUniforms;
+@group(1) @binding(1) var image: texture_2d<f32>;
+@group(1) @binding(2) var image_sampler: sampler;
+
+const GRID_MIN_PX: f32 = 6.0;
+const GRID_COLOR: vec3<f32> = vec3(0.0, 0.0, 0.0);
+const GRID_ALPHA: f32 = 0.35;
+// Linear-light theme accent, for the cursor pixel outline.
+const CURSOR_COLOR: vec3<f32> = vec3(0.89, 0.27, 0.009);
+const BG_COLOR: vec3<f32> = vec3(0.0049, 0.0049, 0.0049);
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let uv = loupe.center_uv + (in.uv - 0.5) * loupe.span_uv;
+    let texel = uv * loupe.image_size;
+    let cell = floor(texel);
+    let inside = all(uv >= vec2(0.0)) && all(uv <= vec2(1.0));
+
+    // Explicit LOD: the snapped UVs jump between pixels, which would make
+    // implicit derivatives meaningless.
+    var color = textureSampleLevel(image, image_sampler, (cell + 0.5) / loupe.image_size, 0.0).rgb;
+    color = select(BG_COLOR, color, inside);
+
+    // Distance to the nearest pixel edge, in inset pixels.
+    let f = texel - cell;
+    let edge = min(f, 1.0 - f) * loupe.pixel_px;
+    let on_edge = min(edge.x, edge.y) < 0.5;
+
+    if loupe.pixel_px >= GRID_MIN_PX && inside && on_edge {
+        color = mix(color, GRID_COLOR, GRID_ALPHA);
+    }
+
+    let cursor_cell = floor(loupe.center_uv * loupe.image_size);
+    let outline = max(1.0, loupe.pixel_px * 0.08);
+    if all(cell == cursor_cell) && min(edge.x, edge.y) < outline {
+        color = CURSOR_COLOR;
+    }
+
+    return vec4(color, 1.0);
+}
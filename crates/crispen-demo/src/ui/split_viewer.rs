@@ -4,11 +4,13 @@ use bevy::asset::RenderAssetUsages;
 use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use crispen_bevy::resources::ImageState;
+use crispen_core::image::SourceImage;
 
 use super::theme;
 use super::toolbar::ToolbarState;
 use super::viewer;
 use super::viewer_nav::{PICKABLE_IGNORE, ViewerFrame, ViewerImageWrapper};
+use crate::i18n::tr;
 
 /// Handle to the source-image texture used by the split viewer.
 #[derive(Resource)]
@@ -140,7 +142,7 @@ pub fn spawn_viewer_area(
                                     });
 
                                 frame.spawn((
-                                    Text::new("Source"),
+                                    Text::new(tr("viewer-source")),
                                     PICKABLE_IGNORE,
                                     Node {
                                         position_type: PositionType::Absolute,
@@ -245,21 +247,31 @@ pub fn update_source_texture(
     };
 
     let new_size = Extent3d {
-        width: source.width,
-        height: source.height,
+        width: source.width(),
+        height: source.height(),
         depth_or_array_layers: 1,
     };
-    let bytes = bytemuck::cast_slice(source.pixels.as_slice()).to_vec();
+    // A half-precision source stays half on the GPU.
+    let (bytes, format) = match source {
+        SourceImage::Full(image) => (
+            bytemuck::cast_slice(image.pixels.as_slice()).to_vec(),
+            TextureFormat::Rgba32Float,
+        ),
+        SourceImage::Half(image) => (
+            bytemuck::cast_slice(image.pixels.as_slice()).to_vec(),
+            TextureFormat::Rgba16Float,
+        ),
+    };
 
     if let Some(existing) = images.get_mut(&source_image.handle) {
         if existing.texture_descriptor.size != new_size
-            || existing.texture_descriptor.format != TextureFormat::Rgba32Float
+            || existing.texture_descriptor.format != format
         {
             *existing = Image::new(
                 new_size,
                 TextureDimension::D2,
                 bytes,
-                TextureFormat::Rgba32Float,
+                format,
                 RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
             );
         } else {
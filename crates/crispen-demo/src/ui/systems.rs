@@ -6,6 +6,7 @@
 
 use std::path::Path;
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::ui_render::prelude::MaterialNode;
 use bevy::ui_widgets::ValueChange;
@@ -16,7 +17,7 @@ use crispen_bevy::events::ImageLoadedEvent;
 use crispen_bevy::resources::OcioColorManagement;
 use crispen_bevy::resources::{GpuPipelineState, GradingState, ImageState};
 
-use super::color_wheel::{ColorWheelMaterial, WheelType};
+use super::color_wheel::{ColorWheelMaterial, WheelIndicator, WheelType};
 use super::components::ParamId;
 use super::dial::{DialMaterial, DialRange, DialValue, ParamDial};
 use super::master_slider::{
@@ -24,7 +25,9 @@ use super::master_slider::{
     MasterSliderWheel,
 };
 use super::theme;
+use crate::i18n::tr;
 use crate::image_loader;
+use crate::keymap::{Action, KeyBindings};
 
 const PARAM_SYNC_EPSILON: f32 = 1e-4;
 
@@ -183,38 +186,52 @@ pub fn sync_params_to_dials(
 
 // ── Image Loading ───────────────────────────────────────────────────────────
 
-/// Open a native file dialog on Ctrl+O and load the selected image into the
-/// grading pipeline.
+/// Input and window state read by `handle_load_image_shortcut`, grouped
+/// to keep it within clippy's argument limit.
+#[derive(SystemParam)]
+pub struct LoadImageShortcut<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    bindings: Res<'w, KeyBindings>,
+    window_q: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+/// Open a native file dialog on the load image shortcut (Ctrl+O by
+/// default) and load the selected image into the grading pipeline.
 pub fn handle_load_image_shortcut(
-    keys: Res<ButtonInput<KeyCode>>,
-    window_q: Query<&Window, With<PrimaryWindow>>,
+    shortcut: LoadImageShortcut,
     mut image_state: ResMut<ImageState>,
     mut grading_state: ResMut<GradingState>,
     gpu: Option<ResMut<GpuPipelineState>>,
     #[cfg(feature = "ocio")] mut ocio: Option<ResMut<OcioColorManagement>>,
     mut image_loaded: MessageWriter<ImageLoadedEvent>,
 ) {
-    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
-    if !(ctrl && keys.just_pressed(KeyCode::KeyO)) {
+    if !shortcut
+        .bindings
+        .just_pressed(Action::LoadImage, &shortcut.keys)
+    {
         return;
     }
 
-    let dialog = rfd::FileDialog::new().set_title("Load Image");
+    let dialog = rfd::FileDialog::new().set_title(tr("dialog-load-image"));
     #[cfg(feature = "ocio")]
     let dialog = dialog.add_filter(
-        "Images",
+        tr("dialog-image-filter"),
         &[
             "jpg", "jpeg", "png", "tif", "tiff", "exr", "dpx", "cin", "hdr", "bmp", "tga", "webp",
             "psd", "gif",
         ],
     );
     #[cfg(not(feature = "ocio"))]
-    let dialog = dialog.add_filter("Images", &["jpg", "jpeg", "png", "tif", "tiff", "exr"]);
+    let dialog = dialog.add_filter(
+        tr("dialog-image-filter"),
+        &["jpg", "jpeg", "png", "tif", "tiff", "exr"],
+    );
     let Some(path) = dialog.pick_file() else {
         return;
     };
 
-    let preview_size = viewer_target_size(&window_q);
+    let preview_size = viewer_target_size(&shortcut.window_q, shortcut.ui_scale.0);
     load_image_from_path(
         &path,
         preview_size,
@@ -281,14 +298,11 @@ fn load_image_from_path(
     // Update input space to match the actual source encoding.
     grading_state.params.color_management.input_space = detected_input_space;
 
+    let source = image_state.set_source(image, path.display().to_string());
     // Upload to GPU if the pipeline is available.
     if let Some(mut gpu) = gpu {
-        let handle = gpu.pipeline.upload_image(&image);
-        gpu.source_handle = Some(handle);
+        gpu.source_handle = Some(gpu.pipeline.upload_source(source));
     }
-
-    image_state.source = Some(image);
-    image_state.source_path = Some(path.display().to_string());
     grading_state.dirty = true;
 
     #[cfg(feature = "ocio")]
@@ -312,11 +326,14 @@ fn load_image_from_path(
     });
 }
 
-fn viewer_target_size(window_q: &Query<&Window, With<PrimaryWindow>>) -> Option<(u32, u32)> {
+fn viewer_target_size(
+    window_q: &Query<&Window, With<PrimaryWindow>>,
+    ui_scale: f32,
+) -> Option<(u32, u32)> {
     let window = window_q.iter().next()?;
-    let scale = window.resolution.scale_factor();
-    let width = window.width() * scale;
-    let height = window.height() * scale;
+    let width = window.physical_width() as f32;
+    let height = window.physical_height() as f32;
+    let scale = window.scale_factor() * ui_scale;
 
     // Match the UI layout: full-width viewer above a fixed-height primaries panel,
     // with a small margin for panel padding/frame borders.
@@ -409,14 +426,15 @@ pub fn sync_params_to_master_sliders(
 
 // ── GradingState → Wheels ───────────────────────────────────────────────────
 
-/// Sync GradingState back to wheel cursor positions and thumb nodes when
-/// params change externally.
+/// Sync GradingState back to wheel indicators and master uniforms when
+/// params change externally. Indicators glide to the new position.
 pub fn sync_params_to_wheels(
     state: Res<GradingState>,
     wheels: Query<(&WheelType, &Children)>,
-    q_children: Query<&Children>,
-    q_material: Query<&MaterialNode<ColorWheelMaterial>>,
-    mut q_node: Query<&mut Node>,
+    mut q_inner: Query<(
+        &mut WheelIndicator,
+        Option<&MaterialNode<ColorWheelMaterial>>,
+    )>,
     mut materials: ResMut<Assets<ColorWheelMaterial>>,
 ) {
     if !state.is_changed() {
@@ -443,37 +461,27 @@ pub fn sync_params_to_wheels(
             }
         };
 
-        // Convert offset (-1..1) to normalized UI position (0..1).
-        let norm_x = (dx / 2.0 + 0.5).clamp(0.0, 1.0);
-        let norm_y = (dy / 2.0 + 0.5).clamp(0.0, 1.0);
-
         // First child of the wheel entity is the inner node (with MaterialNode).
         let Some(&inner_ent) = children.first() else {
             continue;
         };
+        let Ok((mut indicator, mat_node)) = q_inner.get_mut(inner_ent) else {
+            continue;
+        };
 
-        // Update material cursor uniforms (shader expects -1..1).
-        if let Ok(mat_node) = q_material.get(inner_ent)
+        // The indicator shows -1..1, clamped to the wheel's square.
+        let target = Vec2::new(dx, dy).clamp(Vec2::NEG_ONE, Vec2::ONE);
+        if indicator.target != target {
+            indicator.target = target;
+        }
+
+        if let Some(mat_node) = mat_node
             && let Some(mat) = materials.get_mut(mat_node.id())
         {
-            mat.cursor_x = dx;
-            mat.cursor_y = dy;
             mat.master = match wheel_type {
                 WheelType::Lift | WheelType::Offset => (channels[3] * 0.5 + 0.5).clamp(0.0, 1.0),
                 WheelType::Gamma | WheelType::Gain => (channels[3] * 0.5).clamp(0.0, 1.0),
             };
         }
-
-        // Update thumb position (first child of inner node).
-        let Ok(inner_children) = q_children.get(inner_ent) else {
-            continue;
-        };
-        let Some(&thumb_ent) = inner_children.first() else {
-            continue;
-        };
-        if let Ok(mut thumb_node) = q_node.get_mut(thumb_ent) {
-            thumb_node.left = Val::Percent(norm_x * 100.0);
-            thumb_node.top = Val::Percent(norm_y * 100.0);
-        }
     }
 }
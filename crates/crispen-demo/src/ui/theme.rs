@@ -51,6 +51,9 @@ pub const WHEEL_GAP: f32 = 10.0;
 /// Height (px) of the horizontal master-level slider below each wheel.
 pub const MASTER_SLIDER_HEIGHT: f32 = 14.0;
 
+/// Height (px) of the numeric readout row below each master slider.
+pub const WHEEL_READOUT_HEIGHT: f32 = 14.0;
+
 /// Width (px) reserved for each dial slot in the top/bottom rows.
 pub const DIAL_SLOT_WIDTH: f32 = 78.0;
 
@@ -85,7 +88,7 @@ pub const BOTTOM_DIAL_ROW_MARGIN_TOP: f32 = -6.0;
 pub const PANEL_PADDING: f32 = 8.0;
 
 /// Bottom panel height (px), similar to Resolve primaries module.
-pub const PRIMARIES_PANEL_HEIGHT: f32 = 340.0;
+pub const PRIMARIES_PANEL_HEIGHT: f32 = 357.0;
 
 /// Top toolbar height (px).
 pub const TOOLBAR_HEIGHT: f32 = 36.0;
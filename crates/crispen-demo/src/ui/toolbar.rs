@@ -1,5 +1,16 @@
-//! Top toolbar containing color-management dropdowns and viewer toggles.
-
+//! Top toolbar containing color-management dropdowns, section bypass
+//! toggles, and viewer toggles.
+//!
+//! A lit bypass toggle means its section is skipped in the grade; see
+//! [`ToolBypass`].
+//!
+//! Every control is in the tab order. Dropdowns open with Enter / Space,
+//! close with Escape, and step through their options with Up / Down;
+//! toggles flip with Enter / Space.
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::FocusedInput;
+use bevy::input_focus::tab_navigation::{TabGroup, TabIndex};
 use bevy::picking::Pickable;
 use bevy::picking::events::Click;
 use bevy::picking::pointer::PointerButton;
@@ -7,9 +18,14 @@ use bevy::prelude::*;
 use crispen_bevy::resources::GradingState;
 #[cfg(feature = "ocio")]
 use crispen_bevy::resources::OcioColorManagement;
-use crispen_core::transform::params::ColorSpaceId;
+use crispen_core::transform::params::{ColorSpaceId, ToolBypass};
+use crispen_core::transform::validation::EXPOSURE_RANGE;
 
+use super::focus::{self, DropdownKey, dropdown_key, is_activate_key, step_option};
+use super::numeric_entry::NumericEntryPopover;
 use super::theme;
+use crate::i18n::tr;
+use crate::keymap::{Action, KeyBindings};
 
 /// Runtime UI state for the top toolbar.
 #[derive(Resource, Default)]
@@ -34,17 +50,17 @@ pub enum ToolbarDropdownKind {
 }
 
 impl ToolbarDropdownKind {
-    fn title(self) -> &'static str {
-        match self {
-            Self::InputColorspace => "Input",
-            Self::WorkingColorspace => "Working",
+    fn title(self) -> String {
+        tr(match self {
+            Self::InputColorspace => "toolbar-input",
+            Self::WorkingColorspace => "toolbar-working",
             #[cfg(not(feature = "ocio"))]
-            Self::OutputColorspace => "Output",
+            Self::OutputColorspace => "toolbar-output",
             #[cfg(feature = "ocio")]
-            Self::OcioDisplay => "Display",
+            Self::OcioDisplay => "toolbar-display",
             #[cfg(feature = "ocio")]
-            Self::OcioView => "View",
-        }
+            Self::OcioView => "toolbar-view",
+        })
     }
 }
 
@@ -79,6 +95,44 @@ pub struct SplitViewToggleButton;
 #[derive(Component)]
 pub struct OfxPanelToggleButton;
 
+/// Grading section that can be bypassed from the toolbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BypassSection {
+    Wheels,
+    Curves,
+    InputLut,
+}
+
+impl BypassSection {
+    fn label(self) -> String {
+        match self {
+            Self::Wheels => tr("toolbar-bypass-wheels"),
+            Self::Curves => tr("toolbar-bypass-curves"),
+            Self::InputLut => tr("toolbar-bypass-input-lut"),
+        }
+    }
+
+    fn flag(self, bypass: &mut ToolBypass) -> &mut bool {
+        match self {
+            Self::Wheels => &mut bypass.wheels,
+            Self::Curves => &mut bypass.curves,
+            Self::InputLut => &mut bypass.input_lut,
+        }
+    }
+
+    fn is_bypassed(self, bypass: &ToolBypass) -> bool {
+        match self {
+            Self::Wheels => bypass.wheels,
+            Self::Curves => bypass.curves,
+            Self::InputLut => bypass.input_lut,
+        }
+    }
+}
+
+/// Toggle button bypassing one grading section.
+#[derive(Component)]
+pub struct BypassToggleButton(pub BypassSection);
+
 /// Spawn the top toolbar row.
 pub fn spawn_toolbar(parent: &mut ChildSpawnerCommands) {
     parent
@@ -100,6 +154,7 @@ pub fn spawn_toolbar(parent: &mut ChildSpawnerCommands) {
             BorderColor::all(theme::BORDER_SUBTLE),
             GlobalZIndex(500),
             ZIndex(50),
+            TabGroup::new(focus::TOOLBAR_TAB_GROUP),
         ))
         .with_children(|toolbar| {
             toolbar
@@ -130,8 +185,28 @@ pub fn spawn_toolbar(parent: &mut ChildSpawnerCommands) {
                     ..default()
                 })
                 .with_children(|right| {
-                    spawn_toggle_button(right, SplitViewToggleButton, "Split", 56.0);
-                    spawn_toggle_button(right, OfxPanelToggleButton, "OFX", 40.0);
+                    right.spawn((
+                        Text::new(tr("toolbar-bypass")),
+                        TextFont {
+                            font_size: theme::FONT_SIZE_LABEL,
+                            ..default()
+                        },
+                        TextColor(theme::TEXT_DIM),
+                    ));
+                    for (section, width) in [
+                        (BypassSection::Wheels, 52.0),
+                        (BypassSection::Curves, 52.0),
+                        (BypassSection::InputLut, 40.0),
+                    ] {
+                        spawn_toggle_button(
+                            right,
+                            BypassToggleButton(section),
+                            &section.label(),
+                            width,
+                        );
+                    }
+                    spawn_toggle_button(right, SplitViewToggleButton, &tr("toolbar-split"), 56.0);
+                    spawn_toggle_button(right, OfxPanelToggleButton, &tr("toolbar-ofx"), 40.0);
                 });
         });
 }
@@ -148,6 +223,7 @@ fn spawn_dropdown(parent: &mut ChildSpawnerCommands, kind: ToolbarDropdownKind)
                 .spawn((
                     ToolbarDropdownButton(kind),
                     Button,
+                    TabIndex(0),
                     Node {
                         display: Display::Flex,
                         flex_direction: FlexDirection::Row,
@@ -216,6 +292,7 @@ fn spawn_toggle_button<T: Component>(
         .spawn((
             marker,
             Button,
+            TabIndex(0),
             Node {
                 display: Display::Flex,
                 justify_content: JustifyContent::Center,
@@ -258,11 +335,7 @@ pub fn handle_toolbar_interactions(
 ) {
     for (interaction, button) in &button_interactions {
         if *interaction == Interaction::Pressed {
-            if toolbar_state.active_dropdown == Some(button.0) {
-                toolbar_state.active_dropdown = None;
-            } else {
-                toolbar_state.active_dropdown = Some(button.0);
-            };
+            toggle_dropdown(&mut toolbar_state, button.0);
         }
     }
 
@@ -282,6 +355,14 @@ pub fn handle_toolbar_interactions(
     }
 }
 
+fn toggle_dropdown(toolbar_state: &mut ToolbarState, kind: ToolbarDropdownKind) {
+    if toolbar_state.active_dropdown == Some(kind) {
+        toolbar_state.active_dropdown = None;
+    } else {
+        toolbar_state.active_dropdown = Some(kind);
+    }
+}
+
 /// (Re)build dropdown menu options.
 pub fn rebuild_toolbar_menus(
     mut commands: Commands,
@@ -364,6 +445,7 @@ pub fn sync_toolbar_ui(
         Query<(&ToolbarDropdownOption, &mut BackgroundColor)>,
         Query<&mut BackgroundColor, With<SplitViewToggleButton>>,
         Query<&mut BackgroundColor, With<OfxPanelToggleButton>>,
+        Query<(&BypassToggleButton, &mut BackgroundColor)>,
     )>,
 ) {
     #[cfg(not(feature = "ocio"))]
@@ -424,13 +506,23 @@ pub fn sync_toolbar_ui(
             BackgroundColor(theme::BG_CONTROL)
         };
     }
+
+    for (button, mut bg) in &mut ui_parts.p5() {
+        *bg = if button.0.is_bypassed(&grading_state.params.bypass) {
+            BackgroundColor(theme::BG_TOGGLE_ACTIVE)
+        } else {
+            BackgroundColor(theme::BG_CONTROL)
+        };
+    }
 }
 
 /// Handle toolbar toggle-button clicks.
 pub fn handle_toolbar_toggles(
     split_toggles: Query<&Interaction, (Changed<Interaction>, With<SplitViewToggleButton>)>,
     ofx_toggles: Query<&Interaction, (Changed<Interaction>, With<OfxPanelToggleButton>)>,
+    bypass_toggles: Query<(&Interaction, &BypassToggleButton), Changed<Interaction>>,
     mut toolbar_state: ResMut<ToolbarState>,
+    mut grading_state: ResMut<GradingState>,
 ) {
     for interaction in &split_toggles {
         if *interaction == Interaction::Pressed {
@@ -443,6 +535,18 @@ pub fn handle_toolbar_toggles(
             toolbar_state.ofx_panel_visible = !toolbar_state.ofx_panel_visible;
         }
     }
+
+    for (interaction, button) in &bypass_toggles {
+        if *interaction == Interaction::Pressed {
+            toggle_bypass(&mut grading_state, button.0);
+        }
+    }
+}
+
+fn toggle_bypass(grading_state: &mut GradingState, section: BypassSection) {
+    let flag = section.flag(&mut grading_state.params.bypass);
+    *flag = !*flag;
+    grading_state.dirty = true;
 }
 
 /// Observer: handle clicks on toolbar dropdown option entities via the picking
@@ -535,23 +639,114 @@ fn find_dropdown_kind_ancestor(
     }
 }
 
-/// Keyboard shortcuts for split view and OFX panel toggles.
-pub fn handle_toolbar_shortcuts(
-    keys: Res<ButtonInput<KeyCode>>,
+/// Observer: keyboard operation of the focused toolbar control.
+pub fn on_toolbar_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    dropdowns: Query<&ToolbarDropdownButton>,
+    split_toggles: Query<(), With<SplitViewToggleButton>>,
+    ofx_toggles: Query<(), With<OfxPanelToggleButton>>,
+    bypass_toggles: Query<&BypassToggleButton>,
     mut toolbar_state: ResMut<ToolbarState>,
+    mut grading_state: ResMut<GradingState>,
+    #[cfg(feature = "ocio")] mut ocio: Option<ResMut<OcioColorManagement>>,
 ) {
-    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
-    if !ctrl {
+    let entity = key.focused_entity;
+
+    if let Ok(button) = dropdowns.get(entity) {
+        let Some(action) = dropdown_key(&key.input) else {
+            return;
+        };
+        key.propagate(false);
+        let kind = button.0;
+        match action {
+            DropdownKey::Toggle => toggle_dropdown(&mut toolbar_state, kind),
+            DropdownKey::Close => {
+                if toolbar_state.active_dropdown == Some(kind) {
+                    toolbar_state.active_dropdown = None;
+                }
+            }
+            DropdownKey::Move(delta) => {
+                #[cfg(not(feature = "ocio"))]
+                let (values, selected) = (
+                    dropdown_values(kind),
+                    selected_value_for_kind(kind, &grading_state),
+                );
+                #[cfg(feature = "ocio")]
+                let (values, selected) = (
+                    dropdown_values(kind, ocio.as_deref()),
+                    selected_value_for_kind(kind, &grading_state, ocio.as_deref()),
+                );
+
+                let current = values.iter().position(|value| *value == selected);
+                if let Some(next) = step_option(current, delta, values.len())
+                    && Some(next) != current
+                {
+                    apply_dropdown_selection(
+                        kind,
+                        &values[next],
+                        &mut grading_state,
+                        #[cfg(feature = "ocio")]
+                        ocio.as_deref_mut(),
+                    );
+                }
+            }
+        }
+        return;
+    }
+
+    if !is_activate_key(&key.input) {
         return;
     }
+    if split_toggles.contains(entity) {
+        key.propagate(false);
+        toolbar_state.split_view_active = !toolbar_state.split_view_active;
+    } else if ofx_toggles.contains(entity) {
+        key.propagate(false);
+        toolbar_state.ofx_panel_visible = !toolbar_state.ofx_panel_visible;
+    } else if let Ok(button) = bypass_toggles.get(entity) {
+        key.propagate(false);
+        toggle_bypass(&mut grading_state, button.0);
+    }
+}
 
-    if keys.just_pressed(KeyCode::Backslash) || keys.just_pressed(KeyCode::IntlBackslash) {
+/// Keyboard shortcuts for split view and OFX panel toggles, and the
+/// exposure nudges. Nudges wait while a numeric entry is open, where `-`
+/// is typed as a sign.
+pub fn handle_toolbar_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut toolbar_state: ResMut<ToolbarState>,
+    mut grading_state: ResMut<GradingState>,
+    numeric_entries: Query<(), With<NumericEntryPopover>>,
+) {
+    if bindings.just_pressed(Action::ToggleSplitView, &keys) {
         toolbar_state.split_view_active = !toolbar_state.split_view_active;
     }
 
-    if keys.just_pressed(KeyCode::KeyP) {
+    if bindings.just_pressed(Action::ToggleOfxPanel, &keys) {
         toolbar_state.ofx_panel_visible = !toolbar_state.ofx_panel_visible;
     }
+
+    if !numeric_entries.is_empty() {
+        return;
+    }
+    for (action, thirds) in [(Action::ExposureUp, 1.0), (Action::ExposureDown, -1.0)] {
+        if bindings.just_pressed(action, &keys) {
+            nudge_exposure(&mut grading_state, thirds);
+        }
+    }
+}
+
+/// Move input exposure by `thirds` of a stop, landing on the third-stop
+/// grid so repeated nudges read as whole camera stops.
+fn nudge_exposure(grading_state: &mut GradingState, thirds: f32) {
+    let stops = &mut grading_state.params.exposure.stops;
+    let nudged =
+        (((*stops * 3.0).round() + thirds) / 3.0).clamp(EXPOSURE_RANGE.0, EXPOSURE_RANGE.1);
+    if nudged != *stops {
+        *stops = nudged;
+        grading_state.dirty = true;
+    }
 }
 
 fn parse_color_space_option(value: &str) -> Option<ColorSpaceId> {
@@ -4,17 +4,25 @@
 //! modes in the bottom panel's Scopes section.
 
 use bevy::asset::RenderAssetUsages;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::FocusedInput;
+use bevy::input_focus::tab_navigation::{TabGroup, TabIndex};
 use bevy::picking::Pickable;
 use bevy::picking::events::Click;
 use bevy::picking::pointer::PointerButton;
 use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
-use crispen_bevy::resources::{GradingState, ScopeState};
+use crispen_bevy::resources::{GradingState, ScopeConfig, ScopeState};
 use crispen_core::color_management::{CieChromaticity, chromaticity};
-use crispen_core::scopes::{CieData, HistogramData, VectorscopeData, WaveformData};
+use crispen_core::scopes::{
+    CieData, CieProjection, HistogramData, ScopeDisplayOptions, VectorscopeData, WaveformData,
+};
+use crispen_core::transform::params::ColorManagementConfig;
 
+use super::focus::{self, DropdownKey, dropdown_key, step_option};
 use super::scope_mask;
 use super::theme;
+use crate::i18n::tr;
 
 /// Handle to the dynamic Bevy image used for scope rendering.
 #[derive(Resource)]
@@ -49,24 +57,33 @@ pub enum ScopeViewMode {
 }
 
 impl ScopeViewMode {
-    fn label(self) -> &'static str {
-        match self {
-            Self::Vectorscope => "Vectorscope",
-            Self::Waveform => "Waveform",
-            Self::RgbParade => "RGB Parade",
-            Self::Histogram => "Histogram",
-            Self::CieDiagram => "CIE Chromaticity",
-        }
+    /// All modes in dropdown order.
+    const ALL: [Self; 5] = [
+        Self::Vectorscope,
+        Self::Waveform,
+        Self::RgbParade,
+        Self::Histogram,
+        Self::CieDiagram,
+    ];
+
+    fn label(self) -> String {
+        tr(match self {
+            Self::Vectorscope => "scope-vectorscope",
+            Self::Waveform => "scope-waveform",
+            Self::RgbParade => "scope-parade",
+            Self::Histogram => "scope-histogram",
+            Self::CieDiagram => "scope-cie",
+        })
     }
 
-    fn missing_text(self) -> &'static str {
-        match self {
-            Self::Vectorscope => "No vectorscope data",
-            Self::Waveform => "No waveform data",
-            Self::RgbParade => "No waveform data for parade",
-            Self::Histogram => "No histogram data",
-            Self::CieDiagram => "No CIE data",
-        }
+    fn missing_text(self) -> String {
+        tr(match self {
+            Self::Vectorscope => "scope-missing-vectorscope",
+            Self::Waveform => "scope-missing-waveform",
+            Self::RgbParade => "scope-missing-parade",
+            Self::Histogram => "scope-missing-histogram",
+            Self::CieDiagram => "scope-missing-cie",
+        })
     }
 
     fn is_square(self) -> bool {
@@ -86,10 +103,25 @@ pub(crate) struct ScopePlotArea;
 #[derive(Component)]
 pub(crate) struct ScopeHint;
 
-/// Marker for CIE gamut label overlay (shows output colorspace name).
+/// Marker for CIE gamut legend overlay (one entry per gamut triangle).
 #[derive(Component)]
 pub(crate) struct CieGamutLabel;
 
+/// Legend row for the `n`th CIE gamut overlay.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct CieLegendEntry(pub usize);
+
+/// Line colors for CIE gamut overlays, in legend order. Overlays beyond
+/// this count are not drawn.
+const CIE_OVERLAY_COLORS: [[u8; 3]; 6] = [
+    [150, 150, 156],
+    [205, 120, 90],
+    [90, 150, 215],
+    [195, 175, 80],
+    [160, 115, 210],
+    [95, 185, 160],
+];
+
 /// Dropdown toggle button.
 #[derive(Component)]
 pub(crate) struct ScopeDropdownButton;
@@ -128,17 +160,20 @@ pub fn setup_vectorscope(mut commands: Commands, mut images: ResMut<Assets<Image
 /// Spawn the scopes header with a dropdown selector.
 pub fn spawn_scope_header(parent: &mut ChildSpawnerCommands) {
     parent
-        .spawn(Node {
-            display: Display::Flex,
-            flex_direction: FlexDirection::Row,
-            justify_content: JustifyContent::SpaceBetween,
-            align_items: AlignItems::Center,
-            width: Val::Percent(100.0),
-            ..default()
-        })
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            TabGroup::new(focus::SCOPES_TAB_GROUP),
+        ))
         .with_children(|row| {
             row.spawn((
-                Text::new("Scopes"),
+                Text::new(tr("scopes-title")),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -168,6 +203,7 @@ pub fn spawn_scope_header(parent: &mut ChildSpawnerCommands) {
                     .spawn((
                         ScopeDropdownButton,
                         Button,
+                        TabIndex(0),
                         Node {
                             display: Display::Flex,
                             flex_direction: FlexDirection::Row,
@@ -225,13 +261,7 @@ pub fn spawn_scope_header(parent: &mut ChildSpawnerCommands) {
                         ZIndex(10),
                     ))
                     .with_children(|menu| {
-                        for mode in [
-                            ScopeViewMode::Vectorscope,
-                            ScopeViewMode::Waveform,
-                            ScopeViewMode::RgbParade,
-                            ScopeViewMode::Histogram,
-                            ScopeViewMode::CieDiagram,
-                        ] {
+                        for mode in ScopeViewMode::ALL {
                             menu.spawn((
                                 ScopeDropdownOption(mode),
                                 Button,
@@ -327,7 +357,7 @@ pub fn spawn_vectorscope_panel(parent: &mut ChildSpawnerCommands, handle: Handle
                                 position_type: PositionType::Absolute,
                                 ..default()
                             },
-                            Text::new("Waiting for image"),
+                            Text::new(tr("scopes-waiting")),
                             TextFont {
                                 font_size: theme::FONT_SIZE_LABEL,
                                 ..default()
@@ -336,23 +366,34 @@ pub fn spawn_vectorscope_panel(parent: &mut ChildSpawnerCommands, handle: Handle
                             Pickable::IGNORE,
                         ));
 
-                        frame.spawn((
-                            CieGamutLabel,
-                            Node {
-                                position_type: PositionType::Absolute,
-                                bottom: Val::Px(4.0),
-                                left: Val::Px(6.0),
-                                display: Display::None,
-                                ..default()
-                            },
-                            Text::new(""),
-                            TextFont {
-                                font_size: 11.0,
-                                ..default()
-                            },
-                            TextColor(theme::TEXT_DIM),
-                            Pickable::IGNORE,
-                        ));
+                        frame
+                            .spawn((
+                                CieGamutLabel,
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    bottom: Val::Px(4.0),
+                                    left: Val::Px(6.0),
+                                    flex_direction: FlexDirection::Column,
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                Pickable::IGNORE,
+                            ))
+                            .with_children(|legend| {
+                                for i in 0..CIE_OVERLAY_COLORS.len() {
+                                    legend.spawn((
+                                        CieLegendEntry(i),
+                                        Node::default(),
+                                        Text::new(""),
+                                        TextFont {
+                                            font_size: 11.0,
+                                            ..default()
+                                        },
+                                        TextColor(theme::TEXT_DIM),
+                                        Pickable::IGNORE,
+                                    ));
+                                }
+                            });
                     });
                 });
         });
@@ -398,6 +439,40 @@ pub fn on_scope_option_click(
     state.dropdown_open = false;
 }
 
+/// Observer: Enter / Space toggle the focused scope dropdown, Escape
+/// closes it, and Up / Down switch the scope mode.
+pub fn on_scope_dropdown_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    buttons: Query<(), With<ScopeDropdownButton>>,
+    mut state: ResMut<ScopeViewState>,
+) {
+    if !buttons.contains(key.focused_entity) {
+        return;
+    }
+    let Some(action) = dropdown_key(&key.input) else {
+        return;
+    };
+    key.propagate(false);
+    match action {
+        DropdownKey::Toggle => state.dropdown_open = !state.dropdown_open,
+        DropdownKey::Close => {
+            if state.dropdown_open {
+                state.dropdown_open = false;
+            }
+        }
+        DropdownKey::Move(delta) => {
+            let current = ScopeViewMode::ALL
+                .iter()
+                .position(|mode| *mode == state.mode);
+            if let Some(next) = step_option(current, delta, ScopeViewMode::ALL.len())
+                && Some(next) != current
+            {
+                state.mode = ScopeViewMode::ALL[next];
+            }
+        }
+    }
+}
+
 /// Keep dropdown label/menu visuals in sync with [`ScopeViewState`].
 #[allow(clippy::type_complexity)]
 pub fn sync_scope_dropdown_ui(
@@ -437,6 +512,7 @@ pub fn sync_scope_dropdown_ui(
 #[allow(clippy::type_complexity)]
 pub fn update_scope_texture(
     scope_state: Res<ScopeState>,
+    scope_config: Res<ScopeConfig>,
     view_state: Res<ScopeViewState>,
     grading_state: Res<GradingState>,
     scope_image: Option<Res<VectorscopeImageHandle>>,
@@ -445,10 +521,15 @@ pub fn update_scope_texture(
         Query<&mut Node, With<ScopePlotArea>>,
         Query<&mut Node, With<ScopeImageFrame>>,
         Query<(&mut Node, &mut Text), With<ScopeHint>>,
-        Query<(&mut Node, &mut Text), With<CieGamutLabel>>,
+        Query<&mut Node, With<CieGamutLabel>>,
+        Query<(&CieLegendEntry, &mut Node, &mut Text, &mut TextColor)>,
     )>,
 ) {
-    if !(scope_state.is_changed() || view_state.is_changed() || grading_state.is_changed()) {
+    if !(scope_state.is_changed()
+        || scope_config.is_changed()
+        || view_state.is_changed()
+        || grading_state.is_changed())
+    {
         return;
     }
 
@@ -478,7 +559,8 @@ pub fn update_scope_texture(
         return;
     };
 
-    let output_gamut = chromaticity(grading_state.params.color_management.output_space);
+    let color_management = &grading_state.params.color_management;
+    let display = &scope_config.display;
 
     let rendered = match view_state.mode {
         ScopeViewMode::Vectorscope => scope_state
@@ -488,10 +570,13 @@ pub fn update_scope_texture(
         ScopeViewMode::Waveform => scope_state.waveform.as_ref().and_then(render_waveform),
         ScopeViewMode::RgbParade => scope_state.waveform.as_ref().and_then(render_parade),
         ScopeViewMode::Histogram => scope_state.histogram.as_ref().and_then(render_histogram),
-        ScopeViewMode::CieDiagram => scope_state
-            .cie
-            .as_ref()
-            .and_then(|d| render_cie(d, output_gamut)),
+        ScopeViewMode::CieDiagram => scope_state.cie.as_ref().and_then(|d| {
+            render_cie(
+                d,
+                &cie_overlays(color_management, display),
+                display.cie_projection,
+            )
+        }),
     };
 
     if let Some((w, h, rgba)) = rendered {
@@ -506,19 +591,36 @@ pub fn update_scope_texture(
         }
     }
 
-    // Show output colorspace label on CIE diagram.
+    // Show the gamut legend on the CIE diagram.
     let is_cie = view_state.mode == ScopeViewMode::CieDiagram;
-    for (mut node, mut text) in ui_parts.p3().iter_mut() {
-        if is_cie {
+    for mut node in ui_parts.p3().iter_mut() {
+        node.display = if is_cie { Display::Flex } else { Display::None };
+    }
+    for (entry, mut node, mut text, mut color) in ui_parts.p4().iter_mut() {
+        if let Some(overlay) = display.cie_gamuts.get(entry.0) {
+            let [r, g, b] = CIE_OVERLAY_COLORS[entry.0];
             node.display = Display::Flex;
-            let label = grading_state.params.color_management.output_space.label();
-            *text = Text::new(label);
+            *text = Text::new(overlay.label(color_management));
+            *color = TextColor(Color::srgb_u8(r, g, b));
         } else {
             node.display = Display::None;
         }
     }
 }
 
+/// Resolve the configured gamut overlays to chromaticities and line colors.
+fn cie_overlays(
+    config: &ColorManagementConfig,
+    display: &ScopeDisplayOptions,
+) -> Vec<(&'static CieChromaticity, [u8; 3])> {
+    display
+        .cie_gamuts
+        .iter()
+        .zip(CIE_OVERLAY_COLORS)
+        .map(|(overlay, color)| (chromaticity(overlay.space(config)), color))
+        .collect()
+}
+
 fn upload_scope_texture(
     handle: Handle<Image>,
     images: &mut Assets<Image>,
@@ -953,16 +1055,54 @@ fn draw_line(rgba: &mut [u8], resolution: u32, x0: f32, y0: f32, x1: f32, y1: f3
 
 /// Map CIE xy coordinates to pixel coordinates.
 ///
-/// Matches the mapping in `cie::compute()`:
+/// In xy this matches the mapping in `cie::compute()`:
 /// - x range [0, 0.8] maps to [0, resolution-1]
 /// - y range [0, 0.9] maps to [resolution-1, 0] (inverted)
-fn cie_to_pixel(cx: f32, cy: f32, res: f32) -> (f32, f32) {
-    let px = cx / 0.8 * res;
-    let py = (1.0 - cy / 0.9) * res;
+///
+/// In u'v' the same layout is used over the projection's extent.
+fn cie_to_pixel(cx: f32, cy: f32, res: f32, projection: CieProjection) -> (f32, f32) {
+    let [u, v] = projection.project(cx, cy);
+    let [width, height] = projection.extent();
+    let px = u / width * res;
+    let py = (1.0 - v / height) * res;
     (px, py)
 }
 
-fn render_cie(data: &CieData, gamut: &CieChromaticity) -> Option<(u32, u32, Vec<u8>)> {
+/// Move xy-binned density into `projection`'s grid.
+///
+/// Each cell is reprojected from its center, so u'v' density is slightly
+/// blockier than xy at the same resolution.
+fn project_density(data: &CieData, projection: CieProjection) -> Vec<u32> {
+    if projection == CieProjection::Xy {
+        return data.density.clone();
+    }
+    let resolution = data.resolution.max(1);
+    let res_f = (resolution - 1) as f32;
+    let cell = res_f.max(1.0);
+    let mut projected = vec![0u32; data.density.len()];
+    for (idx, &d) in data.density.iter().enumerate() {
+        if d == 0 {
+            continue;
+        }
+        let gx = (idx as u32 % resolution) as f32;
+        let gy = (idx as u32 / resolution) as f32;
+        let cx = gx / cell * 0.8;
+        let cy = (1.0 - gy / cell) * 0.9;
+        let (px, py) = cie_to_pixel(cx, cy, res_f, projection);
+        let px = px.round().clamp(0.0, res_f) as u32;
+        let py = py.round().clamp(0.0, res_f) as u32;
+        let target = (py * resolution + px) as usize;
+        projected[target] = projected[target].saturating_add(d);
+    }
+    projected
+}
+
+/// Render the CIE diagram with one triangle and white point per overlay.
+fn render_cie(
+    data: &CieData,
+    overlays: &[(&CieChromaticity, [u8; 3])],
+    projection: CieProjection,
+) -> Option<(u32, u32, Vec<u8>)> {
     let resolution = data.resolution.max(1);
     let pixel_count = (resolution as usize).saturating_mul(resolution as usize);
     if data.density.len() < pixel_count {
@@ -971,6 +1111,7 @@ fn render_cie(data: &CieData, gamut: &CieChromaticity) -> Option<(u32, u32, Vec<
 
     let res_f = (resolution - 1) as f32;
     let mut rgba = vec![0u8; pixel_count * 4];
+    let to_pixel = |cx: f32, cy: f32| cie_to_pixel(cx, cy, res_f, projection);
 
     // Dark background fill
     for px in rgba.chunks_exact_mut(4) {
@@ -983,63 +1124,63 @@ fn render_cie(data: &CieData, gamut: &CieChromaticity) -> Option<(u32, u32, Vec<
     // --- Spectral locus outline ---
     let locus_color: [u8; 3] = [38, 38, 42];
     for i in 0..SPECTRAL_LOCUS.len() - 1 {
-        let (x0, y0) = cie_to_pixel(SPECTRAL_LOCUS[i][0], SPECTRAL_LOCUS[i][1], res_f);
-        let (x1, y1) = cie_to_pixel(SPECTRAL_LOCUS[i + 1][0], SPECTRAL_LOCUS[i + 1][1], res_f);
+        let (x0, y0) = to_pixel(SPECTRAL_LOCUS[i][0], SPECTRAL_LOCUS[i][1]);
+        let (x1, y1) = to_pixel(SPECTRAL_LOCUS[i + 1][0], SPECTRAL_LOCUS[i + 1][1]);
         draw_line(&mut rgba, resolution, x0, y0, x1, y1, locus_color);
     }
     // Purple line: connect 780 nm back to 380 nm
     let last = SPECTRAL_LOCUS.len() - 1;
-    let (x0, y0) = cie_to_pixel(SPECTRAL_LOCUS[last][0], SPECTRAL_LOCUS[last][1], res_f);
-    let (x1, y1) = cie_to_pixel(SPECTRAL_LOCUS[0][0], SPECTRAL_LOCUS[0][1], res_f);
+    let (x0, y0) = to_pixel(SPECTRAL_LOCUS[last][0], SPECTRAL_LOCUS[last][1]);
+    let (x1, y1) = to_pixel(SPECTRAL_LOCUS[0][0], SPECTRAL_LOCUS[0][1]);
     draw_line(&mut rgba, resolution, x0, y0, x1, y1, locus_color);
 
-    // --- Output gamut triangle ---
-    let triangle_color: [u8; 3] = [70, 75, 80];
-    let primaries = [
-        [gamut.r[0] as f32, gamut.r[1] as f32],
-        [gamut.g[0] as f32, gamut.g[1] as f32],
-        [gamut.b[0] as f32, gamut.b[1] as f32],
-    ];
-    for i in 0..3 {
-        let j = (i + 1) % 3;
-        let (x0, y0) = cie_to_pixel(primaries[i][0], primaries[i][1], res_f);
-        let (x1, y1) = cie_to_pixel(primaries[j][0], primaries[j][1], res_f);
-        draw_line(&mut rgba, resolution, x0, y0, x1, y1, triangle_color);
-    }
+    for &(gamut, color) in overlays {
+        // --- Gamut triangle ---
+        let primaries = [
+            [gamut.r[0] as f32, gamut.r[1] as f32],
+            [gamut.g[0] as f32, gamut.g[1] as f32],
+            [gamut.b[0] as f32, gamut.b[1] as f32],
+        ];
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let (x0, y0) = to_pixel(primaries[i][0], primaries[i][1]);
+            let (x1, y1) = to_pixel(primaries[j][0], primaries[j][1]);
+            draw_line(&mut rgba, resolution, x0, y0, x1, y1, color);
+        }
 
-    // --- White point cross ---
-    let wp = [gamut.w[0] as f32, gamut.w[1] as f32];
-    let (wpx, wpy) = cie_to_pixel(wp[0], wp[1], res_f);
-    let cross_size = res_f * 0.015;
-    let wp_color: [u8; 3] = [90, 90, 95];
-    draw_line(
-        &mut rgba,
-        resolution,
-        wpx - cross_size,
-        wpy,
-        wpx + cross_size,
-        wpy,
-        wp_color,
-    );
-    draw_line(
-        &mut rgba,
-        resolution,
-        wpx,
-        wpy - cross_size,
-        wpx,
-        wpy + cross_size,
-        wp_color,
-    );
+        // --- White point cross ---
+        let (wpx, wpy) = to_pixel(gamut.w[0] as f32, gamut.w[1] as f32);
+        let cross_size = res_f * 0.015;
+        draw_line(
+            &mut rgba,
+            resolution,
+            wpx - cross_size,
+            wpy,
+            wpx + cross_size,
+            wpy,
+            color,
+        );
+        draw_line(
+            &mut rgba,
+            resolution,
+            wpx,
+            wpy - cross_size,
+            wpx,
+            wpy + cross_size,
+            color,
+        );
+    }
 
     // --- Pixel density overlay ---
-    let peak = data.density.iter().copied().max().unwrap_or(0) as f32;
+    let density = project_density(data, projection);
+    let peak = density.iter().copied().max().unwrap_or(0) as f32;
     if peak > 0.0 {
         let log_peak = (peak + 1.0).ln().max(1.0);
 
         for y in 0..resolution {
             for x in 0..resolution {
                 let idx = (y * resolution + x) as usize;
-                let d = data.density[idx] as f32;
+                let d = density[idx] as f32;
                 if d <= 0.0 {
                     continue;
                 }
@@ -1094,11 +1235,12 @@ pub fn setup_cef_scopes(mut commands: Commands, mut images: ResMut<Assets<Image>
 /// Render all scope types and upload to their respective images (CEF mode).
 pub fn update_cef_scopes(
     scope_state: Res<ScopeState>,
+    scope_config: Res<ScopeConfig>,
     grading_state: Res<GradingState>,
     handles: Option<Res<CefScopeHandles>>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    if !scope_state.is_changed() && !grading_state.is_changed() {
+    if !scope_state.is_changed() && !scope_config.is_changed() && !grading_state.is_changed() {
         return;
     }
     let Some(handles) = handles else { return };
@@ -1121,9 +1263,10 @@ pub fn update_cef_scopes(
         }
     }
 
-    let output_gamut = chromaticity(grading_state.params.color_management.output_space);
+    let display = &scope_config.display;
+    let overlays = cie_overlays(&grading_state.params.color_management, display);
     if let Some(data) = scope_state.cie.as_ref() {
-        if let Some((w, h, rgba)) = render_cie(data, output_gamut) {
+        if let Some((w, h, rgba)) = render_cie(data, &overlays, display.cie_projection) {
             upload_scope_texture(handles.cie.clone(), &mut images, w, h, rgba);
         }
     }
@@ -18,8 +18,11 @@ use crispen_bevy::resources::ViewerData;
 use super::split_viewer::GradedImageNode;
 use super::theme;
 use super::viewer_nav::{PICKABLE_IGNORE, ViewerFrame, ViewerImageWrapper, ViewerTransform};
+use crate::i18n::{tr, tr_args};
+use crate::keymap::{Action, KeyBindings};
 
-/// Marker for the "Ctrl+O to load" hint text, hidden once an image is loaded.
+/// Marker for the "Ctrl+O to load image" hint text, hidden once an image is
+/// loaded.
 #[derive(Component)]
 pub struct LoadHint;
 
@@ -100,9 +103,8 @@ fn linear_to_srgb_u8(v: f32) -> u8 {
 }
 
 /// Convert an `Rgba16Float` byte buffer to `Rgba8UnormSrgb`.
-fn f16_linear_to_srgb8(src: &[u8], pixel_count: usize) -> Vec<u8> {
-    let mut dst = vec![0u8; pixel_count * 4];
-    for i in 0..pixel_count {
+fn f16_linear_to_srgb8(src: &[u8], dst: &mut [u8]) {
+    for i in 0..dst.len() / 4 {
         let si = i * 8; // 4 channels × 2 bytes
         let di = i * 4;
         let r = f16_to_f32(src[si], src[si + 1]);
@@ -114,13 +116,11 @@ fn f16_linear_to_srgb8(src: &[u8], pixel_count: usize) -> Vec<u8> {
         dst[di + 2] = linear_to_srgb_u8(b);
         dst[di + 3] = (a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
     }
-    dst
 }
 
 /// Convert an `Rgba32Float` byte buffer to `Rgba8UnormSrgb`.
-fn f32_linear_to_srgb8(src: &[u8], pixel_count: usize) -> Vec<u8> {
-    let mut dst = vec![0u8; pixel_count * 4];
-    for i in 0..pixel_count {
+fn f32_linear_to_srgb8(src: &[u8], dst: &mut [u8]) {
+    for i in 0..dst.len() / 4 {
         let si = i * 16; // 4 channels × 4 bytes
         let di = i * 4;
         let r = f32::from_le_bytes([src[si], src[si + 1], src[si + 2], src[si + 3]]);
@@ -132,7 +132,17 @@ fn f32_linear_to_srgb8(src: &[u8], pixel_count: usize) -> Vec<u8> {
         dst[di + 2] = linear_to_srgb_u8(b);
         dst[di + 3] = (a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
     }
-    dst
+}
+
+/// Convert viewer pixel data of `format` into `Rgba8UnormSrgb` bytes in `dst`.
+///
+/// `dst` must hold exactly four bytes per pixel.
+pub(crate) fn write_srgb8(format: ViewerFormat, src: &[u8], dst: &mut [u8]) {
+    match format {
+        ViewerFormat::Srgb8 => dst.copy_from_slice(&src[..dst.len()]),
+        ViewerFormat::F16 => f16_linear_to_srgb8(src, dst),
+        ViewerFormat::F32 => f32_linear_to_srgb8(src, dst),
+    }
 }
 
 /// Spawn the top viewer section inside the given parent.
@@ -198,7 +208,7 @@ pub fn spawn_viewer_panel(parent: &mut ChildSpawnerCommands, handle: Handle<Imag
                         });
 
                     frame.spawn((
-                        Text::new("Viewer"),
+                        Text::new(tr("viewer-title")),
                         PICKABLE_IGNORE,
                         Node {
                             position_type: PositionType::Absolute,
@@ -216,7 +226,7 @@ pub fn spawn_viewer_panel(parent: &mut ChildSpawnerCommands, handle: Handle<Imag
                     frame.spawn((
                         LoadHint,
                         PICKABLE_IGNORE,
-                        Text::new("Ctrl+O to load image"),
+                        Text::default(),
                         TextFont {
                             font_size: 18.0,
                             ..default()
@@ -227,6 +237,22 @@ pub fn spawn_viewer_panel(parent: &mut ChildSpawnerCommands, handle: Handle<Imag
         });
 }
 
+/// Show the current load-image shortcut in the hint.
+pub fn sync_load_hint(bindings: Res<KeyBindings>, mut hints: Query<(Ref<LoadHint>, &mut Text)>) {
+    for (hint, mut text) in &mut hints {
+        if !hint.is_added() && !bindings.is_changed() {
+            continue;
+        }
+        let shortcut = bindings
+            .get(Action::LoadImage)
+            .map_or_else(|| "-".to_string(), ToString::to_string);
+        *text = Text::new(tr_args(
+            "viewer-load-hint",
+            &[("shortcut", shortcut.into())],
+        ));
+    }
+}
+
 /// Convert the GPU pipeline's linear-light pixel data to `Rgba8UnormSrgb`
 /// and upload it to the Bevy `Image` asset referenced by the viewer
 /// `ImageNode`.
@@ -263,25 +289,35 @@ pub fn update_viewer_texture(
 
     let t_setup = t0.elapsed();
 
-    // Convert to sRGB u8. Srgb8 is already GPU-converted — just copy bytes.
-    let srgb_bytes = match viewer_data.format {
-        ViewerFormat::Srgb8 => viewer_data.pixel_bytes.clone(),
-        ViewerFormat::F16 => f16_linear_to_srgb8(&viewer_data.pixel_bytes, pixel_count),
-        ViewerFormat::F32 => f32_linear_to_srgb8(&viewer_data.pixel_bytes, pixel_count),
+    let Some(existing) = images.get_mut(&viewer.handle) else {
+        tracing::warn!("viewer Image asset not found for handle");
+        return;
     };
+    let new_size = Extent3d {
+        width: viewer_data.width,
+        height: viewer_data.height,
+        depth_or_array_layers: 1,
+    };
+    let srgb_len = pixel_count * 4;
 
-    let t_convert = t0.elapsed();
-
-    if let Some(existing) = images.get_mut(&viewer.handle) {
-        let new_size = Extent3d {
-            width: viewer_data.width,
-            height: viewer_data.height,
-            depth_or_array_layers: 1,
-        };
-
-        if existing.texture_descriptor.size != new_size
-            || existing.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb
-        {
+    // Convert to sRGB u8 straight into the image's existing pixel storage
+    // when its size and format still match; only a resize allocates.
+    // Srgb8 is already GPU-converted, so that path is a single copy.
+    let reusable = existing.texture_descriptor.size == new_size
+        && existing.texture_descriptor.format == TextureFormat::Rgba8UnormSrgb
+        && existing.data.as_ref().is_some_and(|d| d.len() == srgb_len);
+    let resized = match existing.data.as_mut() {
+        Some(data) if reusable => {
+            write_srgb8(viewer_data.format, &viewer_data.pixel_bytes, data);
+            false
+        }
+        _ => {
+            let mut srgb_bytes = vec![0u8; srgb_len];
+            write_srgb8(
+                viewer_data.format,
+                &viewer_data.pixel_bytes,
+                &mut srgb_bytes,
+            );
             *existing = Image::new(
                 new_size,
                 TextureDimension::D2,
@@ -289,20 +325,17 @@ pub fn update_viewer_texture(
                 TextureFormat::Rgba8UnormSrgb,
                 RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
             );
-        } else {
-            existing.data = Some(srgb_bytes);
+            true
         }
-    } else {
-        tracing::warn!("viewer Image asset not found for handle");
-    }
+    };
 
     let t_total = t0.elapsed();
     tracing::info!(
-        "[PERF] update_viewer_texture: setup={:.2}ms convert={:.2}ms upload={:.2}ms total={:.2}ms ({}x{} {:?})",
+        "[PERF] update_viewer_texture: setup={:.2}ms convert={:.2}ms total={:.2}ms resized={} ({}x{} {:?})",
         t_setup.as_secs_f64() * 1000.0,
-        (t_convert - t_setup).as_secs_f64() * 1000.0,
-        (t_total - t_convert).as_secs_f64() * 1000.0,
+        (t_total - t_setup).as_secs_f64() * 1000.0,
         t_total.as_secs_f64() * 1000.0,
+        resized,
         viewer_data.width,
         viewer_data.height,
         viewer_data.format,
@@ -1,9 +1,12 @@
 //! Zoom and pan navigation for the image viewer.
 //!
-//! Scroll-wheel zooms centered on the cursor, middle-click drag pans,
-//! double-click or Home key resets to fit.
+//! Scroll-wheel zooms centered on the cursor (while the loupe is not
+//! showing; it takes the wheel for its own zoom), middle-click drag pans,
+//! double-click or the reset view shortcut (Home by default) resets to fit.
+//! On touch screens two fingers pan and pinch-zoom, and a double-tap resets.
 
 use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::Touches;
 use bevy::picking::{
     Pickable,
     events::{Cancel, Click, Drag, DragEnd, DragStart, Pointer},
@@ -14,6 +17,9 @@ use bevy::ui::ComputedUiRenderTargetInfo;
 use bevy::window::PrimaryWindow;
 use std::time::{Duration, Instant};
 
+use super::loupe::LoupeState;
+use crate::keymap::{Action, KeyBindings};
+
 // ── Constants ───────────────────────────────────────────────────────────────
 
 const ZOOM_MIN: f32 = 0.1;
@@ -22,6 +28,9 @@ const ZOOM_MAX: f32 = 20.0;
 const ZOOM_FACTOR: f32 = 1.1;
 /// Maximum gap between two primary clicks to treat as double-click reset.
 const DOUBLE_CLICK_MAX_GAP: Duration = Duration::from_millis(350);
+/// Finger spread (logical px) below which a pinch doesn't zoom, so two
+/// fingers landing close together can't produce a huge ratio.
+const PINCH_MIN_SPREAD: f32 = 16.0;
 
 // ── Resource ────────────────────────────────────────────────────────────────
 
@@ -37,6 +46,15 @@ pub struct ViewerTransform {
     drag_prev_pos: Option<Vec2>,
     /// Timestamp of the last primary click on the viewer (for double-click).
     last_click_at: Option<Instant>,
+    /// Two-finger gesture in progress.
+    pinch: Option<Pinch>,
+}
+
+/// Last sample of a two-finger gesture.
+struct Pinch {
+    ids: [u64; 2],
+    midpoint: Vec2,
+    spread: f32,
 }
 
 impl Default for ViewerTransform {
@@ -47,6 +65,7 @@ impl Default for ViewerTransform {
             image_aspect_ratio: None,
             drag_prev_pos: None,
             last_click_at: None,
+            pinch: None,
         }
     }
 }
@@ -184,6 +203,7 @@ pub fn on_viewer_click(
 pub fn handle_viewer_scroll(
     mut scroll: MessageReader<MouseWheel>,
     mut state: ResMut<ViewerTransform>,
+    loupe: Res<LoupeState>,
     frames: Query<
         (
             &ComputedNode,
@@ -203,7 +223,7 @@ pub fn handle_viewer_scroll(
             MouseScrollUnit::Pixel => ev.y / 50.0,
         };
     }
-    if total.abs() < f32::EPSILON {
+    if total.abs() < f32::EPSILON || loupe.visible {
         return;
     }
 
@@ -234,9 +254,73 @@ pub fn handle_viewer_scroll(
     state.zoom = new_zoom;
 }
 
-/// Reset zoom/pan on Home key.
-pub fn reset_viewer_transform(keys: Res<ButtonInput<KeyCode>>, mut state: ResMut<ViewerTransform>) {
-    if keys.just_pressed(KeyCode::Home) {
+/// Two-finger pan and pinch zoom over a viewer frame.
+///
+/// The midpoint between the fingers pans, and the change in their spread
+/// zooms around it. Single touches are left to picking, so taps behave
+/// like clicks. Samples are kept here rather than read from
+/// `Touch::previous_position`, which goes stale on frames without input.
+pub fn handle_viewer_touch(
+    touches: Res<Touches>,
+    mut state: ResMut<ViewerTransform>,
+    frames: Query<
+        (
+            &ComputedNode,
+            &UiGlobalTransform,
+            &ComputedUiRenderTargetInfo,
+        ),
+        With<ViewerFrame>,
+    >,
+    ui_scale: Res<UiScale>,
+) {
+    let mut pressed = touches.iter();
+    let (Some(a), Some(b), None) = (pressed.next(), pressed.next(), pressed.next()) else {
+        if state.pinch.is_some() {
+            state.pinch = None;
+        }
+        return;
+    };
+
+    let mut ids = [a.id(), b.id()];
+    ids.sort_unstable();
+    let midpoint = (a.position() + b.position()) / 2.0;
+    let spread = a.position().distance(b.position());
+    let previous = state.pinch.replace(Pinch {
+        ids,
+        midpoint,
+        spread,
+    });
+    // The first sample of a gesture only records where it starts.
+    let Some(previous) = previous.filter(|pinch| pinch.ids == ids) else {
+        return;
+    };
+
+    let over_viewer = frames.iter().find(|(node, transform, target)| {
+        cursor_in_frame(previous.midpoint, transform, node, target, ui_scale.0)
+    });
+    let Some((_, frame_transform, frame_target)) = over_viewer else {
+        return;
+    };
+
+    state.pan += midpoint - previous.midpoint;
+
+    if previous.spread >= PINCH_MIN_SPREAD && spread >= PINCH_MIN_SPREAD {
+        let old_zoom = state.zoom;
+        let new_zoom = (old_zoom * spread / previous.spread).clamp(ZOOM_MIN, ZOOM_MAX);
+        let local = cursor_local(midpoint, frame_transform, frame_target, ui_scale.0);
+        let zoom_ratio = new_zoom / old_zoom;
+        state.pan = local * (1.0 - zoom_ratio) + state.pan * zoom_ratio;
+        state.zoom = new_zoom;
+    }
+}
+
+/// Reset zoom/pan on the reset view shortcut (Home by default).
+pub fn reset_viewer_transform(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut state: ResMut<ViewerTransform>,
+) {
+    if bindings.just_pressed(Action::ResetView, &keys) {
         state.zoom = 1.0;
         state.pan = Vec2::ZERO;
     }
@@ -0,0 +1,286 @@
+//! Numeric readouts under the color wheels.
+//!
+//! Each wheel shows its red, green, blue, and master values in the format
+//! chosen by [`WheelSettings::readout`]: the signed distance from neutral,
+//! the same in percent, or printer points. Lift and Offset are additive, so
+//! neutral is 0; Gamma and Gain multiply, so neutral is 1 and their printer
+//! points follow the exposure ratio.
+//!
+//! Clicking a value, or Enter / Space while it has focus, opens the
+//! [`numeric_entry`](super::numeric_entry) popover. The typed number is
+//! read in the displayed unit.
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input_focus::FocusedInput;
+use bevy::input_focus::tab_navigation::TabIndex;
+use bevy::picking::Pickable;
+use bevy::picking::events::{Click, Pointer};
+use bevy::picking::pointer::PointerButton;
+use bevy::prelude::*;
+use bevy::ui::UiGlobalTransform;
+use bevy::ui_widgets::ValueChange;
+use crispen_bevy::resources::GradingState;
+use crispen_core::grading::wheels::{
+    factor_to_printer_points, offset_to_printer_points, printer_points_to_factor,
+    printer_points_to_offset,
+};
+
+use super::color_wheel::WheelType;
+use super::focus::is_activate_key;
+use super::master_slider::master_params;
+use super::numeric_entry::OpenNumericEntry;
+use super::theme;
+use crate::settings::{Settings, WheelReadout, WheelSettings};
+
+/// Text color of each channel: red, green, blue, master.
+const CHANNEL_COLORS: [Color; 4] = [
+    Color::srgb(0.92, 0.45, 0.45),
+    Color::srgb(0.45, 0.85, 0.5),
+    Color::srgb(0.5, 0.65, 0.98),
+    theme::TEXT_PRIMARY,
+];
+
+/// One channel value under a wheel.
+#[derive(Component, Debug, Clone, Copy)]
+struct WheelReadoutCell {
+    wheel: WheelType,
+    /// Index into the wheel's `[R, G, B, Master]`.
+    channel: usize,
+}
+
+/// Spawn the readout row for a wheel.
+pub fn wheel_readout(wheel_type: WheelType) -> impl Bundle {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            width: Val::Px(theme::WHEEL_SIZE),
+            height: Val::Px(theme::WHEEL_READOUT_HEIGHT),
+            ..Default::default()
+        },
+        Children::spawn(SpawnIter((0..4).map(move |channel| {
+            (
+                WheelReadoutCell {
+                    wheel: wheel_type,
+                    channel,
+                },
+                Node {
+                    flex_grow: 1.0,
+                    flex_basis: Val::Px(0.0),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                TabIndex(0),
+                children![(
+                    Text::new(""),
+                    TextFont {
+                        font_size: theme::FONT_SIZE_VALUE,
+                        ..Default::default()
+                    },
+                    TextColor(CHANNEL_COLORS[channel]),
+                    Pickable::IGNORE,
+                )],
+            )
+        }))),
+    )
+}
+
+fn is_additive(wheel: WheelType) -> bool {
+    matches!(wheel, WheelType::Lift | WheelType::Offset)
+}
+
+fn channels(grading: &GradingState, wheel: WheelType) -> &[f32; 4] {
+    match wheel {
+        WheelType::Lift => &grading.params.lift_wheel,
+        WheelType::Gamma => &grading.params.gamma_wheel,
+        WheelType::Gain => &grading.params.gain_wheel,
+        WheelType::Offset => &grading.params.offset_wheel,
+    }
+}
+
+/// A channel value in the readout's unit.
+fn to_display(readout: WheelReadout, wheel: WheelType, value: f32) -> f32 {
+    let neutral = if is_additive(wheel) { 0.0 } else { 1.0 };
+    match readout {
+        WheelReadout::Offset => value - neutral,
+        WheelReadout::Percent => (value - neutral) * 100.0,
+        WheelReadout::PrinterPoints if is_additive(wheel) => offset_to_printer_points(value),
+        WheelReadout::PrinterPoints => factor_to_printer_points(value),
+    }
+}
+
+/// Inverse of [`to_display`].
+fn from_display(readout: WheelReadout, wheel: WheelType, shown: f32) -> f32 {
+    let neutral = if is_additive(wheel) { 0.0 } else { 1.0 };
+    match readout {
+        WheelReadout::Offset => shown + neutral,
+        WheelReadout::Percent => shown / 100.0 + neutral,
+        WheelReadout::PrinterPoints if is_additive(wheel) => printer_points_to_offset(shown),
+        WheelReadout::PrinterPoints => printer_points_to_factor(shown),
+    }
+}
+
+/// Number of decimals shown for a readout.
+fn precision(readout: WheelReadout) -> usize {
+    match readout {
+        WheelReadout::Offset => 3,
+        WheelReadout::Percent | WheelReadout::PrinterPoints => 1,
+    }
+}
+
+/// Displayed number without a plus sign or unit, as the popover starts
+/// with it.
+fn entry_text(readout: WheelReadout, shown: f32) -> String {
+    format!("{:.*}", precision(readout), round_shown(readout, shown))
+}
+
+fn format_readout(readout: WheelReadout, shown: f32) -> String {
+    let shown = round_shown(readout, shown);
+    let digits = precision(readout);
+    match readout {
+        WheelReadout::Percent => format!("{shown:+.digits$}%"),
+        WheelReadout::Offset | WheelReadout::PrinterPoints => format!("{shown:+.digits$}"),
+    }
+}
+
+/// Round to the shown precision so a value just below zero reads `+0.0`
+/// rather than `-0.0`.
+fn round_shown(readout: WheelReadout, shown: f32) -> f32 {
+    let scale = 10f32.powi(precision(readout) as i32);
+    (shown * scale).round() / scale + 0.0
+}
+
+fn open_entry(
+    commands: &mut Commands,
+    entity: Entity,
+    cell: WheelReadoutCell,
+    node: &ComputedNode,
+    transform: &UiGlobalTransform,
+    grading: &GradingState,
+    wheels: &WheelSettings,
+) {
+    let value = channels(grading, cell.wheel)[cell.channel];
+    let shown = to_display(wheels.readout, cell.wheel, value);
+    commands.trigger(OpenNumericEntry::below(
+        entity,
+        entry_text(wheels.readout, shown),
+        node,
+        transform,
+    ));
+}
+
+fn on_readout_click(
+    click: On<Pointer<Click>>,
+    cells: Query<(&WheelReadoutCell, &ComputedNode, &UiGlobalTransform)>,
+    grading: Res<GradingState>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    if click.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((cell, node, transform)) = cells.get(click.entity) else {
+        return;
+    };
+    open_entry(
+        &mut commands,
+        click.entity,
+        *cell,
+        node,
+        transform,
+        &grading,
+        &settings.wheels,
+    );
+}
+
+fn on_readout_key(
+    mut key: On<FocusedInput<KeyboardInput>>,
+    cells: Query<(&WheelReadoutCell, &ComputedNode, &UiGlobalTransform)>,
+    grading: Res<GradingState>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    let entity = key.focused_entity;
+    let Ok((cell, node, transform)) = cells.get(entity) else {
+        return;
+    };
+    if !is_activate_key(&key.input) {
+        return;
+    }
+    key.propagate(false);
+    open_entry(
+        &mut commands,
+        entity,
+        *cell,
+        node,
+        transform,
+        &grading,
+        &settings.wheels,
+    );
+}
+
+/// Apply a value typed into the popover, read in the displayed unit.
+fn on_readout_value_change(
+    event: On<ValueChange<f32>>,
+    cells: Query<&WheelReadoutCell>,
+    settings: Res<Settings>,
+    mut grading: ResMut<GradingState>,
+) {
+    let Ok(cell) = cells.get(event.source) else {
+        return;
+    };
+    let (min, max, _) = master_params(cell.wheel);
+    let value = from_display(settings.wheels.readout, cell.wheel, event.value).clamp(min, max);
+    if channels(&grading, cell.wheel)[cell.channel] == value {
+        return;
+    }
+    let params = &mut grading.params;
+    let channels = match cell.wheel {
+        WheelType::Lift => &mut params.lift_wheel,
+        WheelType::Gamma => &mut params.gamma_wheel,
+        WheelType::Gain => &mut params.gain_wheel,
+        WheelType::Offset => &mut params.offset_wheel,
+    };
+    channels[cell.channel] = value;
+    grading.dirty = true;
+}
+
+/// Refresh the readouts when the grade or the readout format changes.
+fn sync_wheel_readouts(
+    grading: Res<GradingState>,
+    settings: Res<Settings>,
+    cells: Query<(Ref<WheelReadoutCell>, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let refresh = grading.is_changed() || settings.is_changed();
+    let readout = settings.wheels.readout;
+    for (cell, children) in &cells {
+        if !(refresh || cell.is_added()) {
+            continue;
+        }
+        let Some(&label) = children.first() else {
+            continue;
+        };
+        let Ok(mut text) = texts.get_mut(label) else {
+            continue;
+        };
+        let value = channels(&grading, cell.wheel)[cell.channel];
+        let formatted = format_readout(readout, to_display(readout, cell.wheel, value));
+        if text.0 != formatted {
+            text.0 = formatted;
+        }
+    }
+}
+
+/// Registers readout observers and the sync system.
+pub struct WheelReadoutPlugin;
+
+impl Plugin for WheelReadoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_readout_click)
+            .add_observer(on_readout_key)
+            .add_observer(on_readout_value_change)
+            .add_systems(PostUpdate, sync_wheel_readouts);
+    }
+}
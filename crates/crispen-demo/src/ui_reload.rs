@@ -0,0 +1,188 @@
+//! Web UI hot-reload for frontend development.
+//!
+//! With `CRISPEN_UI_WATCH` set, the built UI folder is polled for changed
+//! files. In dev mode the Vite dev server is polled instead: Vite swaps
+//! edited modules in itself, but a restarted server leaves the page dead.
+//! On a change, the `ReloadUi` command or its shortcut, CEF webviews reload
+//! themselves and a browser on the WebSocket bridge is told to, so UI work
+//! never needs a Bevy restart.
+
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use bevy::prelude::*;
+
+#[cfg(feature = "cef")]
+use crispen_frontend_core::CompositeBackend;
+
+#[cfg(feature = "cef")]
+use crate::cef_bridge::CefFrontendResource;
+use crate::config::AppConfig;
+use crate::embedded_ui::{VITE_DEV_PORT, find_built_ui};
+use crate::ipc::BevyToUi;
+use crate::keymap::{Action, KeyBindings};
+use crate::ws_bridge;
+
+/// How long a dev server probe may block the frame.
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Reload the web UI.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ReloadUi;
+
+/// What is polled for UI changes.
+enum WatchTarget {
+    /// Vite dev server; reload when it answers again after going away.
+    DevServer {
+        addrs: Vec<SocketAddr>,
+        reachable: bool,
+    },
+    /// Built UI folder; reload once its newest file stops changing, so a
+    /// build in progress is not picked up half-written.
+    Dist {
+        dir: PathBuf,
+        modified: Option<SystemTime>,
+        changed: bool,
+    },
+}
+
+/// Polling of the UI source for changes.
+#[derive(Resource)]
+struct UiWatch {
+    target: Option<WatchTarget>,
+    poll: Timer,
+}
+
+/// Watches the UI and applies [`ReloadUi`] to whichever frontend is active.
+pub struct UiReloadPlugin;
+
+impl Plugin for UiReloadPlugin {
+    fn build(&self, app: &mut App) {
+        let target = watch_target(app.world().resource::<AppConfig>());
+        app.insert_resource(UiWatch {
+            target,
+            poll: Timer::from_seconds(0.5, TimerMode::Repeating),
+        })
+        .add_message::<ReloadUi>()
+        .add_systems(
+            Update,
+            (
+                watch_ui,
+                handle_reload_shortcut,
+                apply_ui_reload
+                    .after(watch_ui)
+                    .after(handle_reload_shortcut),
+            ),
+        );
+    }
+}
+
+fn watch_target(config: &AppConfig) -> Option<WatchTarget> {
+    if config.dev_mode {
+        let addrs = ("localhost", VITE_DEV_PORT)
+            .to_socket_addrs()
+            .ok()?
+            .collect();
+        return Some(WatchTarget::DevServer {
+            addrs,
+            reachable: true,
+        });
+    }
+    if !config.ui_watch {
+        return None;
+    }
+    let Some(dir) = find_built_ui().and_then(|index| Some(index.parent()?.to_path_buf())) else {
+        tracing::warn!("CRISPEN_UI_WATCH is set but no built UI was found");
+        return None;
+    };
+    tracing::info!("watching {} for UI changes", dir.display());
+    Some(WatchTarget::Dist {
+        modified: newest_modification(&dir),
+        dir,
+        changed: false,
+    })
+}
+
+fn watch_ui(time: Res<Time>, mut watch: ResMut<UiWatch>, mut reload: MessageWriter<ReloadUi>) {
+    if !watch.poll.tick(time.delta()).just_finished() {
+        return;
+    }
+    let changed = match &mut watch.target {
+        None => false,
+        Some(WatchTarget::DevServer { addrs, reachable }) => {
+            let now = addrs
+                .iter()
+                .any(|addr| TcpStream::connect_timeout(addr, CONNECT_TIMEOUT).is_ok());
+            let back = now && !*reachable;
+            *reachable = now;
+            back
+        }
+        Some(WatchTarget::Dist {
+            dir,
+            modified,
+            changed,
+        }) => {
+            let newest = newest_modification(dir);
+            if newest != *modified {
+                *modified = newest;
+                *changed = true;
+                false
+            } else {
+                std::mem::take(changed)
+            }
+        }
+    };
+    if changed {
+        tracing::info!("UI changed, reloading");
+        reload.write(ReloadUi);
+    }
+}
+
+/// Latest modification time of any file under `dir`.
+fn newest_modification(dir: &Path) -> Option<SystemTime> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if metadata.is_dir() {
+                newest_modification(&entry.path())
+            } else {
+                metadata.modified().ok()
+            }
+        })
+        .max()
+}
+
+fn handle_reload_shortcut(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut reload: MessageWriter<ReloadUi>,
+) {
+    if bindings.just_pressed(Action::ReloadUi, &keys) {
+        reload.write(ReloadUi);
+    }
+}
+
+fn apply_ui_reload(
+    mut requests: MessageReader<ReloadUi>,
+    #[cfg(feature = "cef")] webview: Option<NonSendMut<CefFrontendResource>>,
+    outbound: Option<ResMut<ws_bridge::OutboundUiMessages>>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+
+    #[cfg(feature = "cef")]
+    if let Some(mut wv) = webview {
+        for backend in wv.all_mut() {
+            backend.reload();
+        }
+        return;
+    }
+
+    if let Some(mut outbound) = outbound {
+        outbound.send(BevyToUi::ReloadUi);
+    }
+}
@@ -6,17 +6,34 @@
 
 use std::path::Path;
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 
+use crate::clipboard::ClipboardCommand;
 use crate::config::AppConfig;
+use crate::crash::RecoveryCommand;
+use crate::diagnostics::LogCommand;
+use crate::gallery::GalleryCommand;
+use crate::i18n::{self, tr_args};
 use crate::image_loader;
 use crate::ipc::{BevyToUi, UiToBevy};
+use crate::presets::PresetCommand;
+use crate::probe::StartProbeCheck;
+use crate::settings::SettingsCommand;
+use crate::setup::SetupCommand;
+use crate::ui::delta_e_overlay;
+use crate::ui_reload::ReloadUi;
 use crispen_bevy::events::{ColorGradingCommand, ImageLoadedEvent};
 #[cfg(feature = "ocio")]
 use crispen_bevy::resources::OcioColorManagement;
 use crispen_bevy::resources::{GpuPipelineState, GradingState, ImageState};
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::BitDepth;
+use crispen_core::patterns::TestPattern;
+use crispen_core::transform::compare::DEFAULT_HEATMAP_SCALE;
+use crispen_core::transform::params::ColorSpaceId;
 
 /// Resource holding outbound messages to send to the UI.
 ///
@@ -168,6 +185,10 @@ pub fn poll_inbound_messages(
     mut state: ResMut<GradingState>,
     mut outbound: ResMut<OutboundUiMessages>,
     mut image_loaded: MessageWriter<ImageLoadedEvent>,
+    mut probe_requests: MessageWriter<StartProbeCheck>,
+    mut log_commands: MessageWriter<LogCommand>,
+    mut recovery_commands: MessageWriter<RecoveryCommand>,
+    mut subsystems: SubsystemCommands,
 ) {
     while let Ok(json) = bridge.inbound_rx.try_recv() {
         let preview_size = preview_target_from_config(&config);
@@ -183,12 +204,28 @@ pub fn poll_inbound_messages(
                 &mut state,
                 &mut outbound,
                 &mut image_loaded,
+                &mut probe_requests,
+                &mut log_commands,
+                &mut recovery_commands,
+                &mut subsystems,
             ),
             Err(e) => tracing::warn!("Failed to parse UI message: {e}"),
         }
     }
 }
 
+/// Writers for the app's own subsystems, grouped to keep
+/// `poll_inbound_messages` within Bevy's system parameter limit.
+#[derive(SystemParam)]
+pub struct SubsystemCommands<'w> {
+    setup: MessageWriter<'w, SetupCommand>,
+    settings: MessageWriter<'w, SettingsCommand>,
+    clipboard: MessageWriter<'w, ClipboardCommand>,
+    gallery: MessageWriter<'w, GalleryCommand>,
+    presets: MessageWriter<'w, PresetCommand>,
+    ui_reload: MessageWriter<'w, ReloadUi>,
+}
+
 /// Convert a `UiToBevy` message into the appropriate ECS action.
 ///
 /// Most messages become `ColorGradingCommand` events. `LoadImage` is
@@ -204,27 +241,103 @@ fn dispatch_ui_message(
     state: &mut ResMut<GradingState>,
     outbound: &mut ResMut<OutboundUiMessages>,
     image_loaded: &mut MessageWriter<ImageLoadedEvent>,
+    probe_requests: &mut MessageWriter<StartProbeCheck>,
+    log_commands: &mut MessageWriter<LogCommand>,
+    recovery_commands: &mut MessageWriter<RecoveryCommand>,
+    subsystems: &mut SubsystemCommands,
 ) {
     match msg {
         UiToBevy::RequestState => {
             outbound.send(BevyToUi::Initialize {
                 params: state.params.clone(),
+                locale: i18n::locale().to_string(),
             });
+            recovery_commands.write(RecoveryCommand::Query);
+            subsystems.setup.write(SetupCommand::Query);
+            subsystems.settings.write(SettingsCommand::Query);
             if let Some(source) = images.source.as_ref() {
                 outbound.send(BevyToUi::ImageLoaded {
                     path: images.source_path.clone().unwrap_or_default(),
-                    width: source.width,
-                    height: source.height,
-                    bit_depth: format!("{:?}", source.source_bit_depth),
+                    width: source.width(),
+                    height: source.height(),
+                    bit_depth: format!("{:?}", source.source_bit_depth()),
                 });
             }
         }
         UiToBevy::SetParams { params } => {
-            commands.write(ColorGradingCommand::SetParams { params });
+            commands.write(ColorGradingCommand::SetParams { params: *params });
         }
         UiToBevy::AutoBalance => {
             commands.write(ColorGradingCommand::AutoBalance);
         }
+        UiToBevy::AutoContrast {
+            low_percentile,
+            high_percentile,
+            strength,
+        } => {
+            commands.write(ColorGradingCommand::AutoContrast {
+                low_percentile,
+                high_percentile,
+                strength,
+            });
+        }
+        UiToBevy::MatchChart { corners } => {
+            commands.write(ColorGradingCommand::MatchChart { corners });
+        }
+        UiToBevy::DetectDefects { threshold } => {
+            commands.write(ColorGradingCommand::DetectDefects { threshold });
+        }
+        UiToBevy::ClearDefects => {
+            commands.write(ColorGradingCommand::SetDefectMap {
+                map: DefectMap::default(),
+            });
+        }
+        UiToBevy::RunParityCheck => {
+            commands.write(ColorGradingCommand::RunParityCheck);
+        }
+        UiToBevy::CompareDeltaE { path, scale } => {
+            match delta_e_overlay::load_reference(&path, preview_size) {
+                Ok(reference) => {
+                    commands.write(ColorGradingCommand::CompareDeltaE {
+                        reference,
+                        scale: scale.unwrap_or(DEFAULT_HEATMAP_SCALE),
+                    });
+                }
+                Err(error) => {
+                    tracing::error!("Failed to load ΔE reference {path}: {error}");
+                    outbound.send(BevyToUi::Error {
+                        message: tr_args("error-delta-e-reference", &[("error", error.into())]),
+                    });
+                }
+            }
+        }
+        UiToBevy::ClearDeltaE => {
+            commands.write(ColorGradingCommand::ClearDeltaE);
+        }
+        UiToBevy::PreviewLooks { dirs } => {
+            commands.write(ColorGradingCommand::PreviewLooks { dirs });
+        }
+        UiToBevy::ApplyLook { path } => {
+            commands.write(ColorGradingCommand::ApplyLook { path });
+        }
+        UiToBevy::ExportBracket { path, bracket } => {
+            commands.write(ColorGradingCommand::ExportBracket {
+                path,
+                bracket: bracket.into(),
+            });
+        }
+        UiToBevy::SaveVersion { path } => {
+            commands.write(ColorGradingCommand::SaveVersion { path });
+        }
+        UiToBevy::ExportAuditLog { path } => {
+            commands.write(ColorGradingCommand::ExportAuditLog { path });
+        }
+        UiToBevy::SetBlanking { blanking } => {
+            commands.write(ColorGradingCommand::SetBlanking { blanking });
+        }
+        UiToBevy::RunScript { path } => {
+            commands.write(ColorGradingCommand::RunScript { path });
+        }
         UiToBevy::ResetGrade => {
             commands.write(ColorGradingCommand::ResetGrade);
         }
@@ -241,11 +354,56 @@ fn dispatch_ui_message(
                 image_loaded,
             );
         }
-        UiToBevy::LoadLut { path, slot } => {
-            commands.write(ColorGradingCommand::LoadLut { path, slot });
+        UiToBevy::LoadTestPattern {
+            pattern,
+            bit_depth,
+            color_space,
+        } => {
+            handle_load_test_pattern(
+                pattern,
+                bit_depth,
+                color_space,
+                preview_size,
+                images,
+                gpu,
+                #[cfg(feature = "ocio")]
+                ocio_state,
+                state,
+                outbound,
+                image_loaded,
+            );
+        }
+        UiToBevy::LoadLut { path, slot, domain } => {
+            commands.write(ColorGradingCommand::LoadLut { path, slot, domain });
+        }
+        UiToBevy::SetLutDomain { slot, domain } => {
+            commands.write(ColorGradingCommand::SetLutDomain { slot, domain });
+        }
+        UiToBevy::ClearLut { slot } => {
+            commands.write(ColorGradingCommand::ClearLut { slot });
+        }
+        UiToBevy::SetLutWatch { enabled } => {
+            commands.write(ColorGradingCommand::SetLutWatch { enabled });
+        }
+        UiToBevy::ExportLut { path, size, shaper } => {
+            commands.write(ColorGradingCommand::ExportLut { path, size, shaper });
         }
-        UiToBevy::ExportLut { path, size } => {
-            commands.write(ColorGradingCommand::ExportLut { path, size });
+        UiToBevy::ExportIcc { path } => {
+            commands.write(ColorGradingCommand::ExportIcc { path });
+        }
+        UiToBevy::ExportNuke { path, size } => {
+            commands.write(ColorGradingCommand::ExportNuke { path, size });
+        }
+        UiToBevy::ExportShader {
+            path,
+            size,
+            language,
+        } => {
+            commands.write(ColorGradingCommand::ExportShader {
+                path,
+                size,
+                language,
+            });
         }
         UiToBevy::ToggleScope {
             scope_type,
@@ -256,8 +414,137 @@ fn dispatch_ui_message(
                 visible,
             });
         }
+        UiToBevy::SetScopeAccumulation { mode } => {
+            commands.write(ColorGradingCommand::SetScopeAccumulation { mode });
+        }
+        UiToBevy::SetScopeDisplayOptions { options } => {
+            commands.write(ColorGradingCommand::SetScopeDisplayOptions { options });
+        }
+        UiToBevy::SetScopeQuality { quality } => {
+            commands.write(ColorGradingCommand::SetScopeQuality { quality });
+        }
+        UiToBevy::SetScopeMaskPolygon { points } => {
+            commands.write(ColorGradingCommand::SetScopeMaskPolygon { points });
+        }
+        UiToBevy::ClearScopeMask => {
+            commands.write(ColorGradingCommand::ClearScopeMask);
+        }
+        UiToBevy::StartProbeCheck => {
+            probe_requests.write(StartProbeCheck);
+        }
+        UiToBevy::SetLogFilter { directives } => {
+            log_commands.write(LogCommand::SetFilter { directives });
+        }
+        UiToBevy::SetLogStreaming { enabled } => {
+            log_commands.write(LogCommand::SetStreaming { enabled });
+        }
+        UiToBevy::DismissCrashRecovery => {
+            recovery_commands.write(RecoveryCommand::Dismiss);
+        }
+        UiToBevy::RunSetup => {
+            subsystems.setup.write(SetupCommand::Run);
+        }
+        UiToBevy::CompleteSetup { config } => {
+            subsystems.setup.write(SetupCommand::Complete(config));
+        }
+        UiToBevy::RequestSettings => {
+            subsystems.settings.write(SettingsCommand::Query);
+        }
+        UiToBevy::UpdateSettings { settings } => {
+            subsystems.settings.write(SettingsCommand::Update(settings));
+        }
+        UiToBevy::ResetSettings => {
+            subsystems.settings.write(SettingsCommand::Reset);
+        }
+        UiToBevy::SetGuides { guides } => {
+            subsystems
+                .settings
+                .write(SettingsCommand::SetGuides(guides));
+        }
+        UiToBevy::SetFocusAssist { focus_assist } => {
+            subsystems
+                .settings
+                .write(SettingsCommand::SetFocusAssist(focus_assist));
+        }
+        UiToBevy::CopyLook => {
+            subsystems.clipboard.write(ClipboardCommand::CopyLook);
+        }
+        UiToBevy::PasteLook { text } => {
+            subsystems
+                .clipboard
+                .write(ClipboardCommand::PasteLook { text });
+        }
+        UiToBevy::CopyStill => {
+            subsystems.clipboard.write(ClipboardCommand::CopyStill);
+        }
+        UiToBevy::RequestGallery => {
+            subsystems.gallery.write(GalleryCommand::Query);
+        }
+        UiToBevy::GrabStill { label } => {
+            commands.write(ColorGradingCommand::GrabStill { label });
+        }
+        UiToBevy::ApplyStill { label } => {
+            subsystems.gallery.write(GalleryCommand::Apply { label });
+        }
+        UiToBevy::DeleteStill { label } => {
+            subsystems.gallery.write(GalleryCommand::Delete { label });
+        }
+        UiToBevy::ExportStill { label, path } => {
+            subsystems.gallery.write(GalleryCommand::Export {
+                label,
+                path: path.into(),
+            });
+        }
+        UiToBevy::ImportStill { path } => {
+            subsystems
+                .gallery
+                .write(GalleryCommand::Import { path: path.into() });
+        }
+        UiToBevy::RequestToolPresets => {
+            subsystems.presets.write(PresetCommand::Query);
+        }
+        UiToBevy::SaveToolPreset {
+            tool,
+            name,
+            category,
+        } => {
+            subsystems.presets.write(PresetCommand::Save {
+                tool,
+                name,
+                category,
+            });
+        }
+        UiToBevy::ApplyToolPreset { tool, name } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Apply { tool, name });
+        }
+        UiToBevy::DeleteToolPreset { tool, name } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Delete { tool, name });
+        }
+        UiToBevy::ImportToolPresets { path } => {
+            subsystems
+                .presets
+                .write(PresetCommand::Import { path: path.into() });
+        }
+        UiToBevy::ExportToolPresets { path, tool } => {
+            subsystems.presets.write(PresetCommand::Export {
+                path: path.into(),
+                tool,
+            });
+        }
+        UiToBevy::ReloadUi => {
+            subsystems.ui_reload.write(ReloadUi);
+        }
         // CEF-only messages — ignored on the WebSocket bridge.
-        UiToBevy::UiDirty | UiToBevy::LayoutUpdate { .. } | UiToBevy::SaveLayout { .. } => {}
+        UiToBevy::UiDirty
+        | UiToBevy::LayoutUpdate { .. }
+        | UiToBevy::SaveLayout { .. }
+        | UiToBevy::OpenFloatingPanel { .. }
+        | UiToBevy::CloseFloatingPanel { .. }
+        | UiToBevy::SetModalOpen { .. } => {}
     }
 }
 
@@ -286,14 +573,11 @@ fn handle_load_image(
             let height = img.height;
             let bit_depth = format!("{:?}", img.source_bit_depth);
 
+            let source = images.set_source(img, path.to_string());
             // Upload to GPU if pipeline is available.
             if let Some(gpu) = gpu {
-                let handle = gpu.pipeline.upload_image(&img);
-                gpu.source_handle = Some(handle);
+                gpu.source_handle = Some(gpu.pipeline.upload_source(source));
             }
-
-            images.source = Some(img);
-            images.source_path = Some(path.to_string());
             state.dirty = true;
 
             #[cfg(feature = "ocio")]
@@ -328,12 +612,65 @@ fn handle_load_image(
         Err(e) => {
             tracing::error!("Failed to load image {path}: {e}");
             outbound.send(BevyToUi::Error {
-                message: format!("Failed to load image: {e}"),
+                message: tr_args("error-load-image", &[("error", e.to_string().into())]),
             });
         }
     }
 }
 
+/// Generate a built-in test pattern and install it as the source image.
+#[allow(clippy::too_many_arguments)]
+fn handle_load_test_pattern(
+    pattern: TestPattern,
+    bit_depth: BitDepth,
+    color_space: ColorSpaceId,
+    preview_size: Option<(u32, u32)>,
+    images: &mut ResMut<ImageState>,
+    gpu: Option<&mut GpuPipelineState>,
+    #[cfg(feature = "ocio")] ocio_state: Option<&mut OcioColorManagement>,
+    state: &mut ResMut<GradingState>,
+    outbound: &mut ResMut<OutboundUiMessages>,
+    image_loaded: &mut MessageWriter<ImageLoadedEvent>,
+) {
+    let (width, height) = preview_size.unwrap_or((1920, 1080));
+    let img = crispen_core::patterns::generate(pattern, width, height, bit_depth, color_space);
+    let path = format!("pattern:{}", pattern.label());
+    let bit_depth = format!("{:?}", img.source_bit_depth);
+
+    let source = images.set_source(img, path.clone());
+    if let Some(gpu) = gpu {
+        gpu.source_handle = Some(gpu.pipeline.upload_source(source));
+    }
+    state.dirty = true;
+
+    if state.params.color_management.input_space != color_space {
+        state.params.color_management.input_space = color_space;
+        outbound.send(BevyToUi::ParamsUpdated {
+            params: state.params.clone(),
+        });
+    }
+
+    #[cfg(feature = "ocio")]
+    if let Some(ocio) = ocio_state {
+        ocio.input_space =
+            crate::ocio_support::map_detected_to_ocio_name(color_space, &ocio.config);
+        ocio.dirty = true;
+    }
+
+    image_loaded.write(ImageLoadedEvent {
+        path: path.clone(),
+        width,
+        height,
+        bit_depth: bit_depth.clone(),
+    });
+    outbound.send(BevyToUi::ImageLoaded {
+        path,
+        width,
+        height,
+        bit_depth,
+    });
+}
+
 fn preview_target_from_config(config: &AppConfig) -> Option<(u32, u32)> {
     let width = config.width;
     let height = config.height;
@@ -6,7 +6,27 @@
  * - WebSocket (legacy fallback): Connects to ws://localhost:{port}
  */
 
-import type { BevyToUi, GradingParams, LayoutRegion, UiToBevy } from './types';
+import type {
+  BevyToUi,
+  BitDepth,
+  Blanking,
+  BracketRequest,
+  FocusAssist,
+  FramingGuides,
+  GradingParams,
+  LayoutRegion,
+  LutDomain,
+  ScopeAccumulation,
+  ScopeDisplayOptions,
+  ScopeQuality,
+  Settings,
+  ShaderLanguage,
+  ShaperTransfer,
+  TestPattern,
+  Tool,
+  UiToBevy,
+  UserConfig,
+} from './types';
 
 declare global {
   interface Window {
@@ -187,25 +207,316 @@ class CrispenBridge {
     this.send({ type: 'AutoBalance' });
   }
 
+  autoContrast(lowPercentile = 0.1, highPercentile = 99.9, strength = 1): void {
+    this.send({
+      type: 'AutoContrast',
+      data: { low_percentile: lowPercentile, high_percentile: highPercentile, strength },
+    });
+  }
+
   resetGrade(): void {
     this.send({ type: 'ResetGrade' });
   }
 
+  copyLook(): void {
+    this.send({ type: 'CopyLook' });
+  }
+
+  /**
+   * Apply a look from the system clipboard. Where the page cannot read it
+   * (no permission, insecure origin) the backend's clipboard is used.
+   */
+  async pasteLook(): Promise<void> {
+    const text = await navigator.clipboard?.readText().catch(() => null);
+    this.send({ type: 'PasteLook', data: { text: text ?? null } });
+  }
+
+  copyStill(): void {
+    this.send({ type: 'CopyStill' });
+  }
+
+  /**
+   * Ask for the gallery's stills; answered with `Gallery`, then
+   * `GalleryPreviews` of each still's grade on the current frame.
+   */
+  requestGallery(): void {
+    this.send({ type: 'RequestGallery' });
+  }
+
+  /** Render the current frame with its grade into the gallery. */
+  grabStill(label: string): void {
+    this.send({ type: 'GrabStill', data: { label } });
+  }
+
+  /** Apply a gallery still's grade, keeping the shot's color management. */
+  applyStill(label: string): void {
+    this.send({ type: 'ApplyStill', data: { label } });
+  }
+
+  deleteStill(label: string): void {
+    this.send({ type: 'DeleteStill', data: { label } });
+  }
+
+  /** Write a still as `.exr` or 10-bit `.dpx` with its `.grade.json` sidecar. */
+  exportStill(label: string, path: string): void {
+    this.send({ type: 'ExportStill', data: { label, path } });
+  }
+
+  /** Copy an exported still and its grade sidecar into the gallery. */
+  importStill(path: string): void {
+    this.send({ type: 'ImportStill', data: { path } });
+  }
+
+  /** Ask for the tool preset library; answered with `ToolPresets`. */
+  requestToolPresets(): void {
+    this.send({ type: 'RequestToolPresets' });
+  }
+
+  /** Save `tool`'s current settings as a preset, replacing one of the same name. */
+  saveToolPreset(tool: Tool, name: string, category: string): void {
+    this.send({ type: 'SaveToolPreset', data: { tool, name, category } });
+  }
+
+  /** Apply a tool preset over the current grade, leaving other tools alone. */
+  applyToolPreset(tool: Tool, name: string): void {
+    this.send({ type: 'ApplyToolPreset', data: { tool, name } });
+  }
+
+  deleteToolPreset(tool: Tool, name: string): void {
+    this.send({ type: 'DeleteToolPreset', data: { tool, name } });
+  }
+
+  /** Add the presets in a library file exported by another suite. */
+  importToolPresets(path: string): void {
+    this.send({ type: 'ImportToolPresets', data: { path } });
+  }
+
+  /** Write `tool`'s presets, or all of them when `null`, to a library file. */
+  exportToolPresets(path: string, tool: Tool | null): void {
+    this.send({ type: 'ExportToolPresets', data: { path, tool } });
+  }
+
+  /** Reload this page (and any floating panels) without restarting the app. */
+  reloadUi(): void {
+    this.send({ type: 'ReloadUi' });
+  }
+
+  /** True when the host dims its native content under modal dialogs (CEF only). */
+  get hostDimsModals(): boolean {
+    return this.useCef;
+  }
+
+  setModalOpen(open: boolean): void {
+    this.send({ type: 'SetModalOpen', data: { open } });
+  }
+
+  /** Show `panel` in its own floating webview at a rect in CSS pixels (CEF only). */
+  openFloatingPanel(
+    panel: string,
+    rect: { x: number; y: number; width: number; height: number },
+  ): void {
+    this.send({ type: 'OpenFloatingPanel', data: { panel, ...rect } });
+  }
+
+  closeFloatingPanel(panel: string): void {
+    this.send({ type: 'CloseFloatingPanel', data: { panel } });
+  }
+
+  /** Corners are normalized image coords: top-left, top-right, bottom-right, bottom-left. */
+  matchChart(corners: [number, number][]): void {
+    this.send({ type: 'MatchChart', data: { corners } });
+  }
+
+  /** Add hot pixels found on the loaded image, taken as a black frame, to the repair map. */
+  detectDefects(threshold: number): void {
+    this.send({ type: 'DetectDefects', data: { threshold } });
+  }
+
+  /** Empty the dead-pixel and dust repair map. */
+  clearDefects(): void {
+    this.send({ type: 'ClearDefects' });
+  }
+
   loadImage(path: string): void {
     this.send({ type: 'LoadImage', data: { path } });
   }
 
-  loadLut(path: string, slot: string): void {
-    this.send({ type: 'LoadLut', data: { path, slot } });
+  loadTestPattern(pattern: TestPattern, bitDepth: BitDepth, colorSpace: string): void {
+    this.send({
+      type: 'LoadTestPattern',
+      data: { pattern, bit_depth: bitDepth, color_space: colorSpace },
+    });
+  }
+
+  /** `domain` declares the LUT's encodings; `null` guesses from the file name. */
+  loadLut(path: string, slot: string, domain: LutDomain | null = null): void {
+    this.send({ type: 'LoadLut', data: { path, slot, domain } });
+  }
+
+  setLutDomain(slot: string, domain: LutDomain): void {
+    this.send({ type: 'SetLutDomain', data: { slot, domain } });
+  }
+
+  clearLut(slot: string): void {
+    this.send({ type: 'ClearLut', data: { slot } });
+  }
+
+  /** Reload LUT files automatically when another tool rewrites them. */
+  setLutWatch(enabled: boolean): void {
+    this.send({ type: 'SetLutWatch', data: { enabled } });
+  }
+
+  /** `shaper` prepends a 1D shaper LUT for scene-linear input. */
+  exportLut(path: string, size: number, shaper: ShaperTransfer | null = null): void {
+    this.send({ type: 'ExportLut', data: { path, size, shaper } });
+  }
+
+  exportIcc(path: string): void {
+    this.send({ type: 'ExportIcc', data: { path } });
+  }
+
+  exportNuke(path: string, size: number): void {
+    this.send({ type: 'ExportNuke', data: { path, size } });
   }
 
-  exportLut(path: string, size: number): void {
-    this.send({ type: 'ExportLut', data: { path, size } });
+  exportShader(path: string, size: number, language: ShaderLanguage): void {
+    this.send({ type: 'ExportShader', data: { path, size, language } });
   }
 
   toggleScope(scopeType: string, visible: boolean): void {
     this.send({ type: 'ToggleScope', data: { scope_type: scopeType, visible } });
   }
+
+  setScopeAccumulation(mode: ScopeAccumulation): void {
+    this.send({ type: 'SetScopeAccumulation', data: { mode } });
+  }
+
+  setScopeDisplayOptions(options: ScopeDisplayOptions): void {
+    this.send({ type: 'SetScopeDisplayOptions', data: { options } });
+  }
+
+  setScopeQuality(quality: ScopeQuality): void {
+    this.send({ type: 'SetScopeQuality', data: { quality } });
+  }
+
+  /** Limit scope analysis to a polygon in normalized image coords; closes automatically. */
+  setScopeMaskPolygon(points: [number, number][]): void {
+    this.send({ type: 'SetScopeMaskPolygon', data: { points } });
+  }
+
+  clearScopeMask(): void {
+    this.send({ type: 'ClearScopeMask' });
+  }
+
+  startProbeCheck(): void {
+    this.send({ type: 'StartProbeCheck' });
+  }
+
+  /** Grade the current image on GPU and CPU and report the difference. */
+  runParityCheck(): void {
+    this.send({ type: 'RunParityCheck' });
+  }
+
+  /**
+   * Show a ΔE2000 heatmap of the graded output against a reference: a render
+   * in the output space, or a `.json` grade applied to the current image.
+   * `scale` is the ΔE shown as red (default 10).
+   */
+  compareDeltaE(path: string, scale: number | null = null): void {
+    this.send({ type: 'CompareDeltaE', data: { path, scale } });
+  }
+
+  clearDeltaE(): void {
+    this.send({ type: 'ClearDeltaE' });
+  }
+
+  /** Render the current frame through every LUT and grade preset in `dirs`. */
+  previewLooks(dirs: string[]): void {
+    this.send({ type: 'PreviewLooks', data: { dirs } });
+  }
+
+  /** Apply a look from the browser: a LUT as the input LUT, a preset as the grade. */
+  applyLook(path: string): void {
+    this.send({ type: 'ApplyLook', data: { path } });
+  }
+
+  /**
+   * Write one still of the current frame per variation, named after `path`
+   * and the variation's label (e.g. `shot_01_EV_-1.png`).
+   */
+  exportBracket(path: string, bracket: BracketRequest): void {
+    this.send({ type: 'ExportBracket', data: { path, bracket } });
+  }
+
+  /** Save the current grade as a `.json` version, recorded in the audit log. */
+  saveVersion(path: string): void {
+    this.send({ type: 'SaveVersion', data: { path } });
+  }
+
+  /** Write the audit log of grade changes as a JSON array. */
+  exportAuditLog(path: string): void {
+    this.send({ type: 'ExportAuditLog', data: { path } });
+  }
+
+  /** Set the output matte previewed in the viewer and baked into renders. */
+  setBlanking(blanking: Blanking): void {
+    this.send({ type: 'SetBlanking', data: { blanking } });
+  }
+
+  /** Run a Rhai script; the grade it leaves behind is applied. */
+  runScript(path: string): void {
+    this.send({ type: 'RunScript', data: { path } });
+  }
+
+  /** Set log levels with `EnvFilter` directives, e.g. `info,crispen_gpu=debug`. */
+  setLogFilter(directives: string): void {
+    this.send({ type: 'SetLogFilter', data: { directives } });
+  }
+
+  /** Start or stop `LogRecords` messages; starting resends the backlog. */
+  setLogStreaming(enabled: boolean): void {
+    this.send({ type: 'SetLogStreaming', data: { enabled } });
+  }
+
+  /** Forget the crashed session offered by `CrashRecovery`. */
+  dismissCrashRecovery(): void {
+    this.send({ type: 'DismissCrashRecovery' });
+  }
+
+  /** Show the setup wizard again; the backend replies with `Setup`. */
+  runSetup(): void {
+    this.send({ type: 'RunSetup' });
+  }
+
+  /** Save the setup wizard's choices to the config file. */
+  completeSetup(config: UserConfig): void {
+    this.send({ type: 'CompleteSetup', data: { config } });
+  }
+
+  /** Ask for the current settings; answered with `Settings`. */
+  requestSettings(): void {
+    this.send({ type: 'RequestSettings' });
+  }
+
+  /** Validate, apply and save settings; answered with `Settings`. */
+  updateSettings(settings: Settings): void {
+    this.send({ type: 'UpdateSettings', data: { settings } });
+  }
+
+  /** Restore the default settings. */
+  resetSettings(): void {
+    this.send({ type: 'ResetSettings' });
+  }
+
+  /** Change the viewer's framing guides; saved with the settings. */
+  setGuides(guides: FramingGuides): void {
+    this.send({ type: 'SetGuides', data: { guides } });
+  }
+
+  /** Change the viewer's focus assist overlay; saved with the settings. */
+  setFocusAssist(focus_assist: FocusAssist): void {
+    this.send({ type: 'SetFocusAssist', data: { focus_assist } });
+  }
 }
 
 export const bridge = new CrispenBridge();
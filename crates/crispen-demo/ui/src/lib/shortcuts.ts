@@ -0,0 +1,20 @@
+import type { ShortcutAction } from './types';
+
+/** Rebindable commands with their display names, in menu order. */
+export const shortcutLabels: [ShortcutAction, string][] = [
+  ['load_image', 'Load image'],
+  ['toggle_split_view', 'Split view'],
+  ['toggle_ofx_panel', 'OFX panel'],
+  ['pick_chart', 'Pick chart'],
+  ['reset_view', 'Reset view'],
+  ['dev_tools', 'Dev tools'],
+  ['copy_look', 'Copy look'],
+  ['paste_look', 'Paste look'],
+  ['copy_still', 'Copy still'],
+  ['reload_ui', 'Reload UI'],
+  ['exposure_up', 'Exposure up'],
+  ['exposure_down', 'Exposure down'],
+  ['loupe', 'Loupe (hold)'],
+  ['toggle_focus_assist', 'Focus assist'],
+  ['mark_defects', 'Mark defects'],
+];
@@ -8,15 +8,108 @@
 
 // -- Domain types --
 
+/** What space conversions do with negative values. */
+export type NegativePolicy = 'Allow' | 'Clamp' | 'Mirror';
+
 export interface ColorManagementConfig {
   input_space: string;
   working_space: string;
   output_space: string;
   display_oetf: string;
+  negative_policy: NegativePolicy;
+}
+
+export interface InputCorrection {
+  gain: [number, number, number];
+  power: [number, number, number];
+  matrix: [[number, number, number], [number, number, number], [number, number, number]];
+}
+
+export type InputRange = 'Full' | 'Video' | 'PrinterDensity';
+
+export interface InputLevels {
+  range: InputRange;
+  black: [number, number, number];
+  white: [number, number, number];
+}
+
+/** Input exposure: stops in scene linear, and an offset on code values. */
+export interface Exposure {
+  stops: number;
+  log_offset: number;
+}
+
+/** How temperature and tint choose the white point to adapt to. */
+export type WhiteBalanceModel = 'Legacy' | 'Planckian';
+
+export type SaturationModel = 'Luma' | 'OkLab' | 'JzAzBz';
+
+export type HueModel = 'Rgb' | 'OkLch';
+
+export interface HueRotation {
+  model: HueModel;
+  ranges: [number, number, number];
+}
+
+/** `matrix[out][in]` weights plus a per-output offset. */
+export interface ChannelMixer {
+  matrix: [[number, number, number], [number, number, number], [number, number, number]];
+  offset: [number, number, number];
+}
+
+/** Radial red/blue realignment and fringing, run as a spatial GPU pass. */
+export interface ChromaticAberration {
+  red_scale: number;
+  blue_scale: number;
+  fringe: number;
+}
+
+/** Dark-channel haze removal, run as a spatial GPU pass. */
+export interface Dehaze {
+  strength: number;
+  depth_bias: number;
+}
+
+/** Post-grade glow, run as a spatial GPU pass on the graded output. */
+export interface Glow {
+  threshold: number;
+  spread: number;
+  intensity: number;
+  tint: [number, number, number];
+}
+
+/** Black and white conversion; weights are normalized when applied. */
+export interface Monochrome {
+  enabled: boolean;
+  weights: [number, number, number];
+  contrast: number;
+  tint: [number, number, number];
+  tint_strength: number;
+}
+
+export type BlendMode = 'Normal' | 'Multiply' | 'Overlay' | 'Color';
+
+/** How a grade's result combines with its input; mix is the key output gain. */
+export interface LayerBlend {
+  mode: BlendMode;
+  mix: number;
+}
+
+export type SoftClipMode = 'PerChannel' | 'Luma';
+
+export interface SoftClip {
+  knee: number;
+  strength: number;
+  toe: number;
+  toe_strength: number;
+  mode: SoftClipMode;
 }
 
 export interface GradingParams {
   color_management: ColorManagementConfig;
+  input_correction: InputCorrection;
+  input_levels: InputLevels;
+  exposure: Exposure;
   lift: [number, number, number, number];
   gamma: [number, number, number, number];
   gain: [number, number, number, number];
@@ -25,20 +118,372 @@ export interface GradingParams {
   gamma_wheel: [number, number, number, number];
   gain_wheel: [number, number, number, number];
   offset_wheel: [number, number, number, number];
+  channel_mixer: ChannelMixer;
   temperature: number;
   tint: number;
+  white_balance_model: WhiteBalanceModel;
   contrast: number;
   pivot: number;
   midtone_detail: number;
+  chromatic_aberration: ChromaticAberration;
+  dehaze: Dehaze;
   shadows: number;
   highlights: number;
   saturation: number;
+  vibrance: number;
   hue: number;
+  hue_rotation: HueRotation;
   luma_mix: number;
+  saturation_model: SaturationModel;
   hue_vs_hue: [number, number][];
   hue_vs_sat: [number, number][];
   lum_vs_sat: [number, number][];
   sat_vs_sat: [number, number][];
+  curve_interpolation: CurveInterpolation;
+  monochrome: Monochrome;
+  soft_clip: SoftClip;
+  layer_blend: LayerBlend;
+  glow: Glow;
+  bypass: ToolBypass;
+}
+
+/** Grading sections skipped during evaluation; settings are kept. */
+export interface ToolBypass {
+  wheels: boolean;
+  curves: boolean;
+  input_lut: boolean;
+  input_levels: boolean;
+  input_transform: boolean;
+  exposure: boolean;
+  input_correction: boolean;
+  white_balance: boolean;
+  channel_mixer: boolean;
+  contrast: boolean;
+  shadows_highlights: boolean;
+  color: boolean;
+  monochrome: boolean;
+  soft_clip: boolean;
+  output_transform: boolean;
+}
+
+/** Where a pipeline stage runs relative to the baked 3D LUT. */
+export type StagePlacement = 'BeforeLut' | 'Lut' | 'AfterLut' | 'Display';
+
+/** One stage of the color pipeline, in chain order. */
+export interface PipelineStage {
+  /** `ToolBypass` field, or an id for stages that cannot be bypassed. */
+  key: string;
+  name: string;
+  placement: StagePlacement;
+  /** Whether the stage changes the image. */
+  active: boolean;
+  /** Bypass state; null when the stage has no bypass. */
+  bypassed: boolean | null;
+  detail: string;
+  matrix: number[][] | null;
+  lut_size: number | null;
+}
+
+export type CurveInterpolation = 'Linear' | 'Smooth';
+
+export type ShaderLanguage = 'Glsl' | 'Hlsl' | 'Wgsl';
+
+export type ShaperTransfer = 'Log2' | 'AcesCct' | 'SourceLog';
+
+/** Grade variations of a bracketed still export. */
+export type BracketRequest =
+  | { Exposure: { stops: number[] } }
+  | { Versions: { paths: string[] } };
+
+export type ScopeAccumulation =
+  | { type: 'Instant' }
+  | { type: 'Average'; frames: number }
+  | { type: 'PeakHold'; decay: number };
+
+/** A gamut triangle on the CIE diagram; `Space` takes a color space id. */
+export type GamutOverlay = 'Output' | 'Input' | { Space: string };
+
+export type CieProjection = 'Xy' | 'UvPrime';
+
+export interface ScopeDisplayOptions {
+  cie_gamuts: GamutOverlay[];
+  cie_projection: CieProjection;
+}
+
+export interface ScopeResolution {
+  waveform_height: number;
+  vectorscope: number;
+  cie: number;
+}
+
+/**
+ * How often scopes follow viewer updates while the grade changes:
+ * every update, every Nth update, or only after the grade has been
+ * unchanged for the given milliseconds.
+ */
+export type ScopeCadence = 'EveryUpdate' | { EveryNth: number } | { OnSettle: number };
+
+export interface ScopeQuality {
+  resolution: ScopeResolution;
+  auto_reduce: boolean;
+  frame_budget_ms: number;
+  /** Accumulate every Nth pixel; null picks a stride from the image size. */
+  sample_stride: number | null;
+  cadence: ScopeCadence;
+}
+
+// -- Test patterns --
+
+export type TestPattern = 'ColorBars' | 'GradientRamp' | 'GraySteps' | 'Pluge' | 'Macbeth';
+
+export type BitDepth = 'U8' | 'U10' | 'U12' | 'U16' | 'F16' | 'F32';
+
+// -- Probe --
+
+export interface ProbePatchResult {
+  target: [number, number, number];
+  measured_xyz: [number, number, number];
+  delta_e: number;
+}
+
+export interface ProbeReport {
+  patches: ProbePatchResult[];
+  mean_delta_e: number;
+  max_delta_e: number;
+  p95_delta_e: number;
+}
+
+export interface ParitySample {
+  x: number;
+  y: number;
+  cpu: [number, number, number];
+  gpu: [number, number, number];
+}
+
+export interface ParityReport {
+  width: number;
+  height: number;
+  sampled: number;
+  stride: number;
+  max_abs_error: number;
+  mean_abs_error: number;
+  delta_e_mean: number;
+  delta_e_p95: number;
+  delta_e_max: number;
+  worst: ParitySample;
+}
+
+/** Per-pixel ΔE2000 between the graded output and a reference. */
+export interface DeltaEReport {
+  width: number;
+  height: number;
+  delta_e_mean: number;
+  delta_e_p95: number;
+  delta_e_max: number;
+  worst_x: number;
+  worst_y: number;
+  tolerance: number;
+  /** Fraction (0–1) of pixels whose ΔE exceeds `tolerance`. */
+  above_tolerance: number;
+}
+
+/** One look browser thumbnail: a `.cube` LUT or a `.json` grade preset. */
+export interface LookThumbnail {
+  name: string;
+  path: string;
+  kind: 'Lut' | 'Grade';
+  /** PNG data URL of the current frame through the look. */
+  image: string;
+}
+
+/** Input/output encodings of an imported LUT (color space ids). */
+export interface LutDomain {
+  input: string;
+  output: string;
+}
+
+export interface LutAnalysis {
+  size: number;
+  monotonicity_violations: number;
+  max_reversal: number;
+  contrast_spikes: number;
+  max_curvature: number;
+  non_invertible_points: number;
+  max_inverse_error: number;
+}
+
+// -- Diagnostics --
+
+export interface LogRecord {
+  seq: number;
+  /** Unix time in milliseconds. */
+  timestamp_ms: number;
+  level: 'ERROR' | 'WARN' | 'INFO' | 'DEBUG' | 'TRACE';
+  target: string;
+  message: string;
+  fields: [string, string][];
+}
+
+// -- User config / setup --
+
+export type ColorManagementMode =
+  | { mode: 'native' }
+  | { mode: 'ocio'; config_path: string | null };
+
+export interface UserConfig {
+  setup_complete: boolean;
+  color_management: ColorManagementMode;
+  working_space: string;
+  display_space: string;
+  gpu_adapter: string | null;
+  cache_dir: string | null;
+  scratch_dir: string | null;
+}
+
+// -- Output blanking --
+
+/** Hard matte baked into renders: the full frame or a delivery aspect. */
+export type Blanking = 'none' | { aspect: number };
+
+// -- Gallery --
+
+/** One gallery still; its grade stays in the backend's sidecar. */
+export interface GalleryStill {
+  label: string;
+  path: string;
+  source: string | null;
+  /** Unix time in seconds. */
+  captured_at: number;
+  operator: string;
+  /** Grade fields applying the still would change; empty when it matches. */
+  changes: string[];
+}
+
+/** The current frame under one still's grade. */
+export interface StillPreview {
+  label: string;
+  /** PNG data URL. */
+  image: string;
+}
+
+// -- Tool presets --
+
+/** Grading tool whose fields a preset captures. */
+export type Tool =
+  | 'input_levels'
+  | 'exposure'
+  | 'white_balance'
+  | 'wheels'
+  | 'channel_mixer'
+  | 'contrast'
+  | 'saturation'
+  | 'hue_rotation'
+  | 'curves'
+  | 'monochrome'
+  | 'soft_clip'
+  | 'glow'
+  | 'dehaze'
+  | 'chromatic_aberration';
+
+/** Named settings for one tool: its `GradingParams` fields by name. */
+export interface ToolPreset {
+  name: string;
+  tool: Tool;
+  category: string;
+  values: Record<string, unknown>;
+}
+
+// -- Settings --
+
+export type ViewerPrecision = 'srgb8' | 'f16' | 'f32';
+
+export type WheelReadout = 'offset' | 'percent' | 'printer_points';
+
+/** Commands with a rebindable keyboard shortcut. */
+export type ShortcutAction =
+  | 'load_image'
+  | 'toggle_split_view'
+  | 'toggle_ofx_panel'
+  | 'pick_chart'
+  | 'reset_view'
+  | 'dev_tools'
+  | 'copy_look'
+  | 'paste_look'
+  | 'copy_still'
+  | 'reload_ui'
+  | 'exposure_up'
+  | 'exposure_down'
+  | 'loupe'
+  | 'toggle_focus_assist'
+  | 'mark_defects';
+
+/** Safe areas, thirds, and aspect mask drawn over the viewer. */
+export interface FramingGuides {
+  /** Master switch; the individual toggles keep their state while off. */
+  enabled: boolean;
+  action_safe: boolean;
+  /** Fraction of the active area, 0.93 by default. */
+  action_safe_size: number;
+  title_safe: boolean;
+  /** Fraction of the active area, 0.90 by default. */
+  title_safe_size: number;
+  center_cross: boolean;
+  thirds: boolean;
+  /** Width / height of the framing mask, e.g. 2.39; null for no mask. */
+  aspect: number | null;
+  mask_opacity: number;
+  line_color: [number, number, number];
+  line_opacity: number;
+}
+
+/** Edge overlay for judging focus and sharpening. */
+export interface FocusAssist {
+  enabled: boolean;
+  /** Edge strength, 0–1, from which edges are tinted. */
+  threshold: number;
+  /** Display-referred RGB, 0–1. */
+  color: [number, number, number];
+}
+
+/** Precision loaded sources are held and uploaded in. */
+export type SourcePrecision = 'Full' | 'Half' | 'Auto';
+
+export interface Settings {
+  /** Interface size relative to the display scale factor. */
+  ui_scale: number;
+  viewer_format: ViewerPrecision;
+  lut_size: number;
+  scopes: { display: ScopeDisplayOptions; quality: ScopeQuality };
+  autosave: { enabled: boolean; interval_secs: number };
+  caches: {
+    log_records: number;
+    readback_slots: number;
+    lut_cache: number;
+    source_precision: SourcePrecision;
+  };
+  power: { idle_saving: boolean; idle_fps: number };
+  /** Native color wheel subsamples per axis, edge width in pixels, and readout format. */
+  wheels: { samples: number; aa_px: number; readout: WheelReadout };
+  guides: FramingGuides;
+  focus_assist: FocusAssist;
+  /** Shortcut strings such as `Ctrl+Shift+I`. */
+  key_bindings: Record<ShortcutAction, string>;
+}
+
+// -- Crash recovery --
+
+export interface SessionSnapshot {
+  params: GradingParams;
+  image_path: string | null;
+}
+
+export interface CrashRecovery {
+  /** Null when the session comes from an autosave, with no crash report. */
+  report_dir: string | null;
+  /** Unix time in seconds. */
+  crashed_at: number;
+  message: string;
+  session: SessionSnapshot | null;
 }
 
 // -- Layout --
@@ -55,20 +500,121 @@ export interface LayoutRegion {
 // -- IPC messages (tag + content pattern) --
 
 export type BevyToUi =
-  | { type: 'Initialize'; data: { params: GradingParams } }
+  | { type: 'Initialize'; data: { params: GradingParams; locale: string } }
   | { type: 'ParamsUpdated'; data: { params: GradingParams } }
+  | { type: 'Pipeline'; data: { stages: PipelineStage[] } }
   | { type: 'ImageLoaded'; data: { path: string; width: number; height: number; bit_depth: string } }
+  | { type: 'ProbeReport'; data: { report: ProbeReport } }
+  | { type: 'ParityReport'; data: { report: ParityReport } }
+  | { type: 'DeltaEReport'; data: { report: DeltaEReport } }
+  | { type: 'LookPreviews'; data: { width: number; height: number; looks: LookThumbnail[] } }
+  | {
+      type: 'LutAnalysis';
+      data: { path: string; slot: string; domain: LutDomain; analysis: LutAnalysis };
+    }
+  | { type: 'LutReloaded'; data: { path: string; slot: string; error: string | null } }
+  | { type: 'ScriptFinished'; data: { path: string; output: string[]; error: string | null } }
+  | { type: 'LogRecords'; data: { records: LogRecord[] } }
+  | { type: 'LogFilter'; data: { directives: string; error: string | null } }
+  | { type: 'CrashRecovery'; data: { recovery: CrashRecovery } }
+  | {
+      type: 'Setup';
+      data: { config: UserConfig; adapters: string[]; ocio_available: boolean; first_run: boolean };
+    }
+  | { type: 'Settings'; data: { settings: Settings; error: string | null } }
+  | { type: 'Gallery'; data: { stills: GalleryStill[]; error: string | null } }
+  | {
+      type: 'GalleryPreviews';
+      data: { width: number; height: number; current: string; stills: StillPreview[] };
+    }
+  | { type: 'ToolPresets'; data: { presets: ToolPreset[]; error: string | null } }
+  | {
+      type: 'ContextMenuRequested';
+      data: {
+        x: number;
+        y: number;
+        editable: boolean;
+        selection_text: string;
+        link_url: string | null;
+      };
+    }
+  | { type: 'ReloadUi' }
   | { type: 'Error'; data: { message: string } };
 
 export type UiToBevy =
   | { type: 'RequestState' }
   | { type: 'SetParams'; data: { params: GradingParams } }
   | { type: 'AutoBalance' }
+  | {
+      type: 'AutoContrast';
+      data: { low_percentile: number; high_percentile: number; strength: number };
+    }
   | { type: 'ResetGrade' }
   | { type: 'LoadImage'; data: { path: string } }
-  | { type: 'LoadLut'; data: { path: string; slot: string } }
-  | { type: 'ExportLut'; data: { path: string; size: number } }
+  | {
+      type: 'LoadTestPattern';
+      data: { pattern: TestPattern; bit_depth: BitDepth; color_space: string };
+    }
+  | { type: 'LoadLut'; data: { path: string; slot: string; domain: LutDomain | null } }
+  | { type: 'SetLutDomain'; data: { slot: string; domain: LutDomain } }
+  | { type: 'ClearLut'; data: { slot: string } }
+  | { type: 'SetLutWatch'; data: { enabled: boolean } }
+  | { type: 'ExportLut'; data: { path: string; size: number; shaper: ShaperTransfer | null } }
+  | { type: 'ExportIcc'; data: { path: string } }
+  | { type: 'ExportNuke'; data: { path: string; size: number } }
+  | { type: 'ExportShader'; data: { path: string; size: number; language: ShaderLanguage } }
   | { type: 'ToggleScope'; data: { scope_type: string; visible: boolean } }
+  | { type: 'SetScopeAccumulation'; data: { mode: ScopeAccumulation } }
+  | { type: 'SetScopeDisplayOptions'; data: { options: ScopeDisplayOptions } }
+  | { type: 'SetScopeQuality'; data: { quality: ScopeQuality } }
+  | { type: 'SetScopeMaskPolygon'; data: { points: [number, number][] } }
+  | { type: 'ClearScopeMask' }
+  | { type: 'StartProbeCheck' }
+  | { type: 'MatchChart'; data: { corners: [number, number][] } }
+  | { type: 'DetectDefects'; data: { threshold: number } }
+  | { type: 'ClearDefects' }
+  | { type: 'RunParityCheck' }
+  | { type: 'CompareDeltaE'; data: { path: string; scale: number | null } }
+  | { type: 'ClearDeltaE' }
+  | { type: 'PreviewLooks'; data: { dirs: string[] } }
+  | { type: 'ApplyLook'; data: { path: string } }
+  | { type: 'ExportBracket'; data: { path: string; bracket: BracketRequest } }
+  | { type: 'SaveVersion'; data: { path: string } }
+  | { type: 'ExportAuditLog'; data: { path: string } }
+  | { type: 'SetBlanking'; data: { blanking: Blanking } }
+  | { type: 'RunScript'; data: { path: string } }
+  | { type: 'SetLogFilter'; data: { directives: string } }
+  | { type: 'SetLogStreaming'; data: { enabled: boolean } }
+  | { type: 'DismissCrashRecovery' }
+  | { type: 'RunSetup' }
+  | { type: 'CompleteSetup'; data: { config: UserConfig } }
+  | { type: 'RequestSettings' }
+  | { type: 'UpdateSettings'; data: { settings: Settings } }
+  | { type: 'ResetSettings' }
+  | { type: 'SetGuides'; data: { guides: FramingGuides } }
+  | { type: 'SetFocusAssist'; data: { focus_assist: FocusAssist } }
+  | { type: 'CopyLook' }
+  | { type: 'PasteLook'; data: { text: string | null } }
+  | { type: 'CopyStill' }
+  | { type: 'RequestGallery' }
+  | { type: 'GrabStill'; data: { label: string } }
+  | { type: 'ApplyStill'; data: { label: string } }
+  | { type: 'DeleteStill'; data: { label: string } }
+  | { type: 'ExportStill'; data: { label: string; path: string } }
+  | { type: 'ImportStill'; data: { path: string } }
+  | { type: 'RequestToolPresets' }
+  | { type: 'SaveToolPreset'; data: { tool: Tool; name: string; category: string } }
+  | { type: 'ApplyToolPreset'; data: { tool: Tool; name: string } }
+  | { type: 'DeleteToolPreset'; data: { tool: Tool; name: string } }
+  | { type: 'ImportToolPresets'; data: { path: string } }
+  | { type: 'ExportToolPresets'; data: { path: string; tool: Tool | null } }
+  | {
+      type: 'OpenFloatingPanel';
+      data: { panel: string; x: number; y: number; width: number; height: number };
+    }
+  | { type: 'CloseFloatingPanel'; data: { panel: string } }
+  | { type: 'ReloadUi' }
+  | { type: 'SetModalOpen'; data: { open: boolean } }
   | { type: 'UiDirty' }
   | { type: 'LayoutUpdate'; data: { regions: LayoutRegion[] } }
   | { type: 'SaveLayout'; data: { layout_json: string } };
@@ -1,11 +1,13 @@
 import './theme.css';
 import App from './App.svelte';
+import FloatingPanel from './FloatingPanel.svelte';
 import { mount } from 'svelte';
 import { setupAutoMarkDirty } from '$lib/bridge';
 
-const app = mount(App, {
-  target: document.getElementById('app')!,
-});
+// Floating webviews load the same page with `?panel=<name>`.
+const panel = new URLSearchParams(window.location.search).get('panel');
+const target = document.getElementById('app')!;
+const app = panel ? mount(FloatingPanel, { target, props: { panel } }) : mount(App, { target });
 
 setupAutoMarkDirty();
 
@@ -1,15 +1,20 @@
 //! CEF browser creation, handler wiring, and subprocess management.
 
+use crate::capture;
 use cef::args::Args;
 use cef::rc::Rc as _;
 use cef::{
-    api_hash, sys, wrap_app, wrap_client, wrap_display_handler, wrap_render_handler, App, Browser,
-    BrowserSettings, CefString, CefStringUtf16, Client, CommandLine, DisplayHandler, ImplApp,
-    ImplClient, ImplCommandLine, ImplDisplayHandler, ImplRenderHandler, LogSeverity,
-    PaintElementType, Rect, RenderHandler, Settings, WindowInfo, WrapApp, WrapClient,
-    WrapDisplayHandler, WrapRenderHandler,
+    App, Browser, BrowserSettings, CefString, CefStringUserfree, CefStringUtf16, Client,
+    CommandLine, ContextMenuHandler, ContextMenuParams, CursorHandle, CursorInfo, CursorType,
+    DisplayHandler, Frame, ImplApp, ImplClient, ImplCommandLine, ImplContextMenuHandler,
+    ImplContextMenuParams, ImplDisplayHandler, ImplFrame, ImplLoadHandler, ImplMenuModel,
+    ImplRenderHandler, LoadHandler, LogSeverity, MenuModel, PaintElementType, Range, Rect,
+    RenderHandler, ScreenInfo, Settings, TextInputMode, WindowInfo, WrapApp, WrapClient,
+    WrapContextMenuHandler, WrapDisplayHandler, WrapLoadHandler, WrapRenderHandler, api_hash, sys,
+    wrap_app, wrap_client, wrap_context_menu_handler, wrap_display_handler, wrap_load_handler,
+    wrap_render_handler,
 };
-use crispen_frontend_core::FrontendError;
+use crispen_frontend_core::{ContextMenuRequest, CursorIcon, DirtyRect, FrontendError};
 use std::ffi::c_int;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
@@ -18,6 +23,16 @@ use tokio::sync::mpsc;
 /// Global flag guarding one-time CEF initialisation.
 static CEF_INITIALIZED: OnceLock<bool> = OnceLock::new();
 
+/// Browser cache directory chosen by the host, see [`set_cache_dir`].
+static CACHE_DIR: OnceLock<std::path::PathBuf> = OnceLock::new();
+
+/// Keep CEF's browser cache in `dir` instead of `~/.cache/crispen/cef`.
+///
+/// Only takes effect before CEF is initialised; later calls are ignored.
+pub fn set_cache_dir(dir: std::path::PathBuf) {
+    let _ = CACHE_DIR.set(dir);
+}
+
 /// Prefix for IPC messages sent via `console.log` from JavaScript.
 pub(crate) const IPC_PREFIX: &str = "__CRISPEN_IPC__:";
 
@@ -32,10 +47,40 @@ pub(crate) struct SharedState {
     pub framebuffer_size: Mutex<(u32, u32)>,
     /// Set when the framebuffer has been updated.
     pub dirty: Arc<AtomicBool>,
-    /// Current viewport size.
+    /// Regions painted since the last capture.
+    pub dirty_rects: Mutex<Vec<DirtyRect>>,
+    /// Current viewport size in physical pixels.
     pub size: Mutex<(u32, u32)>,
+    /// Device scale factor reported to CEF.
+    pub scale_factor: Mutex<f32>,
     /// Channel for forwarding IPC messages from JavaScript to Bevy.
     pub from_ui_tx: mpsc::UnboundedSender<String>,
+    /// Set while an editable element has focus.
+    pub text_input_active: AtomicBool,
+    /// Bounds of the last composed character (device-independent pixels).
+    pub ime_caret: Mutex<Option<Rect>>,
+    /// Cursor requested by the page under the mouse.
+    pub cursor: Mutex<CursorIcon>,
+    /// Right-click waiting to be reported to the UI.
+    pub context_menu: Mutex<Option<ContextMenuRequest>>,
+    /// Set when the main frame finishes loading, including after a reload.
+    pub page_loaded: AtomicBool,
+}
+
+impl SharedState {
+    /// The viewport in CEF's device-independent pixels. CEF paints at this
+    /// size times the scale factor, i.e. the physical size.
+    pub fn view_rect(&self) -> Rect {
+        let (width, height) = *self.size.lock().unwrap();
+        let scale = *self.scale_factor.lock().unwrap();
+        let dip = |px: u32| ((px as f32 / scale).round() as c_int).max(1);
+        Rect {
+            x: 0,
+            y: 0,
+            width: dip(width),
+            height: dip(height),
+        }
+    }
 }
 
 // ── Render handler ───────────────────────────────────────────────
@@ -53,19 +98,27 @@ wrap_render_handler! {
     impl RenderHandler {
         fn view_rect(&self, _browser: Option<&mut Browser>, rect: Option<&mut Rect>) {
             if let Some(rect) = rect {
-                let size = self.handler.shared.size.lock().unwrap();
-                rect.x = 0;
-                rect.y = 0;
-                rect.width = size.0 as c_int;
-                rect.height = size.1 as c_int;
+                *rect = self.handler.shared.view_rect();
             }
         }
 
+        fn screen_info(
+            &self,
+            _browser: Option<&mut Browser>,
+            screen_info: Option<&mut ScreenInfo>,
+        ) -> c_int {
+            let Some(info) = screen_info else { return 0 };
+            info.device_scale_factor = *self.handler.shared.scale_factor.lock().unwrap();
+            info.rect = self.handler.shared.view_rect();
+            info.available_rect = info.rect.clone();
+            1
+        }
+
         fn on_paint(
             &self,
             _browser: Option<&mut Browser>,
             type_: PaintElementType,
-            _dirty_rects: Option<&[Rect]>,
+            dirty_rects: Option<&[Rect]>,
             buffer: *const u8,
             width: c_int,
             height: c_int,
@@ -83,11 +136,33 @@ wrap_render_handler! {
 
             // Safety: CEF guarantees the buffer is valid for the duration of `on_paint`.
             let bgra = unsafe { std::slice::from_raw_parts(buffer, len) };
-            let buffer_copy = Arc::new(bgra.to_vec());
+            let shared = &self.handler.shared;
+            capture::store_paint(shared, bgra, width, height, dirty_rects.unwrap_or_default());
+            shared.dirty.store(true, Ordering::SeqCst);
+        }
+
+        fn on_virtual_keyboard_requested(
+            &self,
+            _browser: Option<&mut Browser>,
+            input_mode: TextInputMode,
+        ) {
+            // Offscreen, this fires when an editable element gains or
+            // loses focus, which is exactly when IME should be on.
+            let active = input_mode != TextInputMode::NONE;
+            self.handler.shared.text_input_active.store(active, Ordering::SeqCst);
+            if !active {
+                *self.handler.shared.ime_caret.lock().unwrap() = None;
+            }
+        }
 
-            *self.handler.shared.framebuffer.lock().unwrap() = Some(buffer_copy);
-            *self.handler.shared.framebuffer_size.lock().unwrap() = (width, height);
-            self.handler.shared.dirty.store(true, Ordering::SeqCst);
+        fn on_ime_composition_range_changed(
+            &self,
+            _browser: Option<&mut Browser>,
+            _selected_range: Option<&Range>,
+            character_bounds: Option<&[Rect]>,
+        ) {
+            let caret = character_bounds.and_then(|bounds| bounds.last()).cloned();
+            *self.handler.shared.ime_caret.lock().unwrap() = caret;
         }
     }
 }
@@ -132,6 +207,17 @@ wrap_display_handler! {
             }
             0
         }
+
+        fn on_cursor_change(
+            &self,
+            _browser: Option<&mut Browser>,
+            _cursor: CursorHandle,
+            type_: CursorType,
+            _custom_cursor_info: Option<&CursorInfo>,
+        ) -> c_int {
+            *self.handler.shared.cursor.lock().unwrap() = to_cursor_icon(type_);
+            1 // handled: offscreen there is no native view to set it on
+        }
     }
 }
 
@@ -141,12 +227,143 @@ impl DisplayHandlerBuilder {
     }
 }
 
-// ── Client (wires render + display handlers) ─────────────────────
+/// Map a Chromium cursor to its CSS name. Custom image cursors fall back
+/// to the default arrow.
+fn to_cursor_icon(type_: CursorType) -> CursorIcon {
+    match type_ {
+        CursorType::HAND => CursorIcon::Pointer,
+        CursorType::IBEAM => CursorIcon::Text,
+        CursorType::VERTICALTEXT => CursorIcon::VerticalText,
+        CursorType::CROSS => CursorIcon::Crosshair,
+        CursorType::MOVE | CursorType::DND_MOVE => CursorIcon::Move,
+        CursorType::GRAB => CursorIcon::Grab,
+        CursorType::GRABBING => CursorIcon::Grabbing,
+        CursorType::MIDDLEPANNING
+        | CursorType::MIDDLE_PANNING_VERTICAL
+        | CursorType::MIDDLE_PANNING_HORIZONTAL
+        | CursorType::EASTPANNING
+        | CursorType::NORTHPANNING
+        | CursorType::NORTHEASTPANNING
+        | CursorType::NORTHWESTPANNING
+        | CursorType::SOUTHPANNING
+        | CursorType::SOUTHEASTPANNING
+        | CursorType::SOUTHWESTPANNING
+        | CursorType::WESTPANNING => CursorIcon::AllScroll,
+        CursorType::NOTALLOWED => CursorIcon::NotAllowed,
+        CursorType::NODROP | CursorType::DND_NONE => CursorIcon::NoDrop,
+        CursorType::COPY | CursorType::DND_COPY => CursorIcon::Copy,
+        CursorType::ALIAS | CursorType::DND_LINK => CursorIcon::Alias,
+        CursorType::WAIT => CursorIcon::Wait,
+        CursorType::PROGRESS => CursorIcon::Progress,
+        CursorType::HELP => CursorIcon::Help,
+        CursorType::CONTEXTMENU => CursorIcon::ContextMenu,
+        CursorType::CELL => CursorIcon::Cell,
+        CursorType::EASTRESIZE => CursorIcon::EResize,
+        CursorType::NORTHRESIZE => CursorIcon::NResize,
+        CursorType::NORTHEASTRESIZE => CursorIcon::NeResize,
+        CursorType::NORTHWESTRESIZE => CursorIcon::NwResize,
+        CursorType::SOUTHRESIZE => CursorIcon::SResize,
+        CursorType::SOUTHEASTRESIZE => CursorIcon::SeResize,
+        CursorType::SOUTHWESTRESIZE => CursorIcon::SwResize,
+        CursorType::WESTRESIZE => CursorIcon::WResize,
+        CursorType::EASTWESTRESIZE => CursorIcon::EwResize,
+        CursorType::NORTHSOUTHRESIZE => CursorIcon::NsResize,
+        CursorType::NORTHEASTSOUTHWESTRESIZE => CursorIcon::NeswResize,
+        CursorType::NORTHWESTSOUTHEASTRESIZE => CursorIcon::NwseResize,
+        CursorType::COLUMNRESIZE => CursorIcon::ColResize,
+        CursorType::ROWRESIZE => CursorIcon::RowResize,
+        CursorType::ZOOMIN => CursorIcon::ZoomIn,
+        CursorType::ZOOMOUT => CursorIcon::ZoomOut,
+        CursorType::NONE => CursorIcon::Hidden,
+        _ => CursorIcon::Default,
+    }
+}
+
+// ── Context menu handler ─────────────────────────────────────────
+
+#[derive(Clone)]
+pub(crate) struct OsrContextMenuHandler {
+    pub shared: Arc<SharedState>,
+}
+
+wrap_context_menu_handler! {
+    pub(crate) struct ContextMenuHandlerBuilder {
+        handler: OsrContextMenuHandler,
+    }
+
+    impl ContextMenuHandler {
+        fn on_before_context_menu(
+            &self,
+            _browser: Option<&mut Browser>,
+            _frame: Option<&mut Frame>,
+            params: Option<&mut ContextMenuParams>,
+            model: Option<&mut MenuModel>,
+        ) {
+            // An empty model shows no native menu; the Svelte UI draws its own.
+            if let Some(model) = model {
+                model.clear();
+            }
+            let Some(params) = params else { return };
+            let text = |s: CefStringUserfree| CefString::from(&s).to_string();
+            let link_url = text(params.link_url());
+            *self.handler.shared.context_menu.lock().unwrap() = Some(ContextMenuRequest {
+                x: params.xcoord(),
+                y: params.ycoord(),
+                editable: params.is_editable() != 0,
+                selection_text: text(params.selection_text()),
+                link_url: (!link_url.is_empty()).then_some(link_url),
+            });
+        }
+    }
+}
+
+impl ContextMenuHandlerBuilder {
+    pub fn build(handler: OsrContextMenuHandler) -> ContextMenuHandler {
+        Self::new(handler)
+    }
+}
+
+// ── Load handler (page reloads) ──────────────────────────────────
+
+#[derive(Clone)]
+pub(crate) struct OsrLoadHandler {
+    pub shared: Arc<SharedState>,
+}
+
+wrap_load_handler! {
+    pub(crate) struct LoadHandlerBuilder {
+        handler: OsrLoadHandler,
+    }
+
+    impl LoadHandler {
+        fn on_load_end(
+            &self,
+            _browser: Option<&mut Browser>,
+            frame: Option<&mut Frame>,
+            _http_status_code: c_int,
+        ) {
+            // A reload replaces `window`, so the IPC bridge must be injected again.
+            if frame.is_some_and(|frame| frame.is_main() != 0) {
+                self.handler.shared.page_loaded.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+impl LoadHandlerBuilder {
+    pub fn build(handler: OsrLoadHandler) -> LoadHandler {
+        Self::new(handler)
+    }
+}
+
+// ── Client (wires the handlers) ──────────────────────────────────
 
 wrap_client! {
     pub(crate) struct ClientBuilder {
         render_handler: RenderHandler,
         display_handler: DisplayHandler,
+        context_menu_handler: ContextMenuHandler,
+        load_handler: LoadHandler,
     }
 
     impl Client {
@@ -157,14 +374,30 @@ wrap_client! {
         fn display_handler(&self) -> Option<cef::DisplayHandler> {
             Some(self.display_handler.clone())
         }
+
+        fn context_menu_handler(&self) -> Option<cef::ContextMenuHandler> {
+            Some(self.context_menu_handler.clone())
+        }
+
+        fn load_handler(&self) -> Option<cef::LoadHandler> {
+            Some(self.load_handler.clone())
+        }
     }
 }
 
 impl ClientBuilder {
     pub fn build(shared: Arc<SharedState>) -> Client {
-        let rh = RenderHandlerBuilder::build(OsrRenderHandler { shared: Arc::clone(&shared) });
-        let dh = DisplayHandlerBuilder::build(OsrDisplayHandler { shared });
-        Self::new(rh, dh)
+        let rh = RenderHandlerBuilder::build(OsrRenderHandler {
+            shared: Arc::clone(&shared),
+        });
+        let dh = DisplayHandlerBuilder::build(OsrDisplayHandler {
+            shared: Arc::clone(&shared),
+        });
+        let cmh = ContextMenuHandlerBuilder::build(OsrContextMenuHandler {
+            shared: Arc::clone(&shared),
+        });
+        let lh = LoadHandlerBuilder::build(OsrLoadHandler { shared });
+        Self::new(rh, dh, cmh, lh)
     }
 }
 
@@ -302,8 +535,12 @@ pub fn ensure_cef_initialized() -> Result<(), FrontendError> {
         }
 
         // Set root_cache_path to suppress singleton behavior warning.
-        if let Ok(home) = std::env::var("HOME") {
-            let cache = std::path::PathBuf::from(home).join(".cache/crispen/cef");
+        let cache = CACHE_DIR.get().cloned().or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| std::path::PathBuf::from(home).join(".cache/crispen/cef"))
+        });
+        if let Some(cache) = cache {
             let _ = std::fs::create_dir_all(&cache);
             settings.root_cache_path = cache.to_str().unwrap_or_default().into();
         }
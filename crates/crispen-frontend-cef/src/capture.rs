@@ -3,11 +3,23 @@
 //! Utilities for extracting the CEF framebuffer.  The `Arc<Vec<u8>>`
 //! wrapper enables zero-copy sharing (~20 ns clone vs ~6–12 ms memcpy
 //! for an 18 MB HiDPI buffer).
+//!
+//! CEF reports which parts of the view each paint changed.  Those regions
+//! are accumulated until the next capture so the consumer can upload just
+//! them; a resize or a mostly-changed frame falls back to a full upload.
 
 use crate::browser::SharedState;
-use crispen_frontend_core::CaptureResult;
-use std::sync::atomic::Ordering;
+use cef::Rect;
+use crispen_frontend_core::{CaptureResult, DirtyRect};
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// More regions than this are merged into their bounding box.
+const MAX_REGIONS: usize = 16;
+
+/// Above this fraction of the frame a full upload is cheaper than many
+/// small copies.
+const FULL_UPLOAD_FRACTION: f64 = 0.5;
 
 /// Return the current framebuffer if it has changed since the last call.
 pub(crate) fn capture_if_dirty(shared: &Arc<SharedState>) -> Option<CaptureResult> {
@@ -17,8 +29,12 @@ pub(crate) fn capture_if_dirty(shared: &Arc<SharedState>) -> Option<CaptureResul
 
     let buffer = shared.framebuffer.lock().unwrap().clone()?;
     let (width, height) = *shared.framebuffer_size.lock().unwrap();
+    let rects = std::mem::take(&mut *shared.dirty_rects.lock().unwrap());
 
-    Some(CaptureResult::Bgra(buffer, width, height))
+    Some(match coalesce(rects, width, height) {
+        Some(rects) => CaptureResult::BgraRegions(buffer, width, height, rects),
+        None => CaptureResult::Bgra(buffer, width, height),
+    })
 }
 
 /// Whether a framebuffer has been received at all.
@@ -26,11 +42,91 @@ pub(crate) fn has_framebuffer(shared: &Arc<SharedState>) -> bool {
     shared.framebuffer.lock().unwrap().is_some()
 }
 
+/// Store a paint of `bgra` (`width` x `height`) in the shared framebuffer,
+/// copying only the dirty regions when the size is unchanged.
+pub(crate) fn store_paint(
+    shared: &SharedState,
+    bgra: &[u8],
+    width: u32,
+    height: u32,
+    dirty_rects: &[Rect],
+) {
+    let mut framebuffer = shared.framebuffer.lock().unwrap();
+    let mut size = shared.framebuffer_size.lock().unwrap();
+    let mut pending = shared.dirty_rects.lock().unwrap();
+
+    match framebuffer.as_mut() {
+        Some(buffer) if *size == (width, height) && !dirty_rects.is_empty() => {
+            // Clones only if the last capture is still held downstream.
+            let buffer = Arc::make_mut(buffer);
+            for rect in dirty_rects.iter().filter_map(|r| clamp(r, width, height)) {
+                rect.copy_between(bgra, buffer, width);
+                pending.push(rect);
+            }
+        }
+        _ => {
+            *framebuffer = Some(Arc::new(bgra.to_vec()));
+            *size = (width, height);
+            pending.clear();
+            pending.push(DirtyRect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            });
+        }
+    }
+}
+
+/// Clip a CEF rect to the frame; `None` if nothing is left.
+fn clamp(rect: &Rect, width: u32, height: u32) -> Option<DirtyRect> {
+    let x0 = rect.x.clamp(0, width as i32) as u32;
+    let y0 = rect.y.clamp(0, height as i32) as u32;
+    let x1 = rect.x.saturating_add(rect.width).clamp(0, width as i32) as u32;
+    let y1 = rect.y.saturating_add(rect.height).clamp(0, height as i32) as u32;
+    (x1 > x0 && y1 > y0).then(|| DirtyRect {
+        x: x0,
+        y: y0,
+        width: x1 - x0,
+        height: y1 - y0,
+    })
+}
+
+/// The regions worth uploading separately, or `None` for a full upload.
+fn coalesce(mut rects: Vec<DirtyRect>, width: u32, height: u32) -> Option<Vec<DirtyRect>> {
+    if rects.is_empty() {
+        return None;
+    }
+    if rects.len() > MAX_REGIONS {
+        rects = vec![bounding_box(&rects)];
+    }
+    let frame = u64::from(width) * u64::from(height);
+    let changed: u64 = rects.iter().map(DirtyRect::area).sum();
+    if changed as f64 > frame as f64 * FULL_UPLOAD_FRACTION {
+        return None;
+    }
+    Some(rects)
+}
+
+fn bounding_box(rects: &[DirtyRect]) -> DirtyRect {
+    let x0 = rects.iter().map(|r| r.x).min().unwrap_or(0);
+    let y0 = rects.iter().map(|r| r.y).min().unwrap_or(0);
+    let x1 = rects.iter().map(|r| r.x + r.width).max().unwrap_or(0);
+    let y1 = rects.iter().map(|r| r.y + r.height).max().unwrap_or(0);
+    DirtyRect {
+        x: x0,
+        y: y0,
+        width: x1 - x0,
+        height: y1 - y0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::AtomicBool;
+    use crispen_frontend_core::CursorIcon;
     use std::sync::Mutex;
+    use std::sync::atomic::AtomicBool;
     use tokio::sync::mpsc;
 
     fn test_shared() -> Arc<SharedState> {
@@ -39,11 +135,27 @@ mod tests {
             framebuffer: Mutex::new(None),
             framebuffer_size: Mutex::new((0, 0)),
             dirty: Arc::new(AtomicBool::new(false)),
+            dirty_rects: Mutex::new(Vec::new()),
             size: Mutex::new((800, 600)),
+            scale_factor: Mutex::new(1.0),
             from_ui_tx: tx,
+            text_input_active: AtomicBool::new(false),
+            ime_caret: Mutex::new(None),
+            cursor: Mutex::new(CursorIcon::Default),
+            context_menu: Mutex::new(None),
+            page_loaded: AtomicBool::new(false),
         })
     }
 
+    fn cef_rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
     #[test]
     fn not_dirty_returns_none() {
         assert!(capture_if_dirty(&test_shared()).is_none());
@@ -73,4 +185,97 @@ mod tests {
 
         assert!(!s.dirty.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn small_repaint_returns_regions() {
+        let s = test_shared();
+        store_paint(
+            &s,
+            &vec![0u8; 800 * 600 * 4],
+            800,
+            600,
+            &[cef_rect(0, 0, 800, 600)],
+        );
+        s.dirty.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            capture_if_dirty(&s),
+            Some(CaptureResult::Bgra(..))
+        ));
+
+        let mut frame = vec![0u8; 800 * 600 * 4];
+        let at = (20 * 800 + 10) * 4;
+        frame[at] = 255;
+        store_paint(&s, &frame, 800, 600, &[cef_rect(10, 20, 30, 40)]);
+        s.dirty.store(true, Ordering::SeqCst);
+
+        match capture_if_dirty(&s) {
+            Some(CaptureResult::BgraRegions(buf, 800, 600, rects)) => {
+                assert_eq!(
+                    rects,
+                    vec![DirtyRect {
+                        x: 10,
+                        y: 20,
+                        width: 30,
+                        height: 40
+                    }]
+                );
+                assert_eq!(buf[at], 255);
+            }
+            other => panic!("expected BgraRegions, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resize_forces_full_upload() {
+        let s = test_shared();
+        store_paint(&s, &vec![0u8; 8 * 8 * 4], 8, 8, &[cef_rect(0, 0, 1, 1)]);
+        store_paint(&s, &vec![0u8; 16 * 8 * 4], 16, 8, &[cef_rect(0, 0, 1, 1)]);
+        s.dirty.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            capture_if_dirty(&s),
+            Some(CaptureResult::Bgra(_, 16, 8))
+        ));
+    }
+
+    #[test]
+    fn rects_are_clipped_to_the_frame() {
+        assert_eq!(
+            clamp(&cef_rect(-5, 90, 20, 20), 100, 100),
+            Some(DirtyRect {
+                x: 0,
+                y: 90,
+                width: 15,
+                height: 10
+            })
+        );
+        assert_eq!(clamp(&cef_rect(120, 0, 10, 10), 100, 100), None);
+    }
+
+    #[test]
+    fn many_or_large_regions_coalesce() {
+        let tiny = |i: u32| DirtyRect {
+            x: i * 2,
+            y: 0,
+            width: 1,
+            height: 1,
+        };
+        let merged = coalesce((0..20).map(tiny).collect(), 100, 100).unwrap();
+        assert_eq!(
+            merged,
+            vec![DirtyRect {
+                x: 0,
+                y: 0,
+                width: 39,
+                height: 1
+            }]
+        );
+
+        let big = DirtyRect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 60,
+        };
+        assert_eq!(coalesce(vec![big], 100, 100), None);
+    }
 }
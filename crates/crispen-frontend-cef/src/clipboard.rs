@@ -0,0 +1,62 @@
+//! System clipboard access through the page.
+//!
+//! CEF has no clipboard API of its own, so writes are handed to the page's
+//! async Clipboard API, which Chromium backs with the system clipboard.
+//! Chromium only answers reads asynchronously, so reads return what was
+//! last written from this process; the page reads the system clipboard
+//! itself when the user pastes into it.
+
+use base64::Engine;
+use crispen_frontend_core::{Clipboard, ClipboardImage, FrontendError, MemoryClipboard};
+
+/// Clipboard of a [`CefBackend`](crate::CefBackend).
+#[derive(Debug, Default)]
+pub struct CefClipboard {
+    written: MemoryClipboard,
+    pending: Vec<String>,
+}
+
+impl CefClipboard {
+    /// Scripts that put queued writes on the system clipboard, oldest
+    /// first. Only the newest matters, but each is cheap to run.
+    pub(crate) fn take_scripts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+impl Clipboard for CefClipboard {
+    fn read_text(&mut self) -> Result<Option<String>, FrontendError> {
+        self.written.read_text()
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), FrontendError> {
+        self.written.write_text(text)?;
+        let literal =
+            serde_json::to_string(text).map_err(|e| FrontendError::Clipboard(e.to_string()))?;
+        self.pending.push(format!(
+            "navigator.clipboard.writeText({literal}).catch((e) => console.warn('clipboard write failed:', e));"
+        ));
+        Ok(())
+    }
+
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>, FrontendError> {
+        self.written.read_image()
+    }
+
+    fn write_image(&mut self, image: &ClipboardImage) -> Result<(), FrontendError> {
+        self.written.write_image(image)?;
+        // The Clipboard API only takes PNG, so let the page encode it.
+        let data = base64::engine::general_purpose::STANDARD.encode(&image.rgba);
+        let (width, height) = (image.width, image.height);
+        self.pending.push(format!(
+            r#"(async () => {{
+                const bytes = Uint8ClampedArray.from(atob('{data}'), (c) => c.charCodeAt(0));
+                const canvas = new OffscreenCanvas({width}, {height});
+                canvas.getContext('2d').putImageData(new ImageData(bytes, {width}, {height}), 0, 0);
+                const blob = await canvas.convertToBlob({{ type: 'image/png' }});
+                await navigator.clipboard.write([new ClipboardItem({{ 'image/png': blob }})]);
+            }})().catch((e) => console.warn('clipboard write failed:', e));"#
+        ));
+        Ok(())
+    }
+}
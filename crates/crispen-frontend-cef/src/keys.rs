@@ -0,0 +1,193 @@
+//! DOM key code → platform key code tables for CEF key events.
+//!
+//! CEF derives `KeyboardEvent.keyCode` from the Windows virtual-key code on
+//! every platform. On Linux it derives `KeyboardEvent.code` from the native
+//! key code, which is the XKB keycode (evdev scancode + 8).
+
+use crispen_frontend_core::Modifiers;
+use std::ffi::c_int;
+
+const EVENTFLAG_SHIFT_DOWN: u32 = 1 << 1;
+const EVENTFLAG_CONTROL_DOWN: u32 = 1 << 2;
+const EVENTFLAG_ALT_DOWN: u32 = 1 << 3;
+const EVENTFLAG_COMMAND_DOWN: u32 = 1 << 7;
+const EVENTFLAG_IS_KEY_PAD: u32 = 1 << 9;
+const EVENTFLAG_IS_LEFT: u32 = 1 << 10;
+const EVENTFLAG_IS_RIGHT: u32 = 1 << 11;
+const EVENTFLAG_IS_REPEAT: u32 = 1 << 13;
+
+/// Platform codes for one physical key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KeyCodes {
+    /// Windows virtual-key code.
+    pub windows: c_int,
+    /// Linux evdev scancode.
+    pub evdev: c_int,
+}
+
+impl KeyCodes {
+    /// The `native_key_code` CEF expects on this platform, or 0 where it
+    /// is not needed.
+    pub fn native(self) -> c_int {
+        if cfg!(target_os = "linux") {
+            self.evdev + 8
+        } else {
+            0
+        }
+    }
+}
+
+/// `(DOM code, Windows virtual-key code, evdev scancode)`.
+const KEYS: &[(&str, c_int, c_int)] = &[
+    ("KeyA", 0x41, 30),
+    ("KeyB", 0x42, 48),
+    ("KeyC", 0x43, 46),
+    ("KeyD", 0x44, 32),
+    ("KeyE", 0x45, 18),
+    ("KeyF", 0x46, 33),
+    ("KeyG", 0x47, 34),
+    ("KeyH", 0x48, 35),
+    ("KeyI", 0x49, 23),
+    ("KeyJ", 0x4A, 36),
+    ("KeyK", 0x4B, 37),
+    ("KeyL", 0x4C, 38),
+    ("KeyM", 0x4D, 50),
+    ("KeyN", 0x4E, 49),
+    ("KeyO", 0x4F, 24),
+    ("KeyP", 0x50, 25),
+    ("KeyQ", 0x51, 16),
+    ("KeyR", 0x52, 19),
+    ("KeyS", 0x53, 31),
+    ("KeyT", 0x54, 20),
+    ("KeyU", 0x55, 22),
+    ("KeyV", 0x56, 47),
+    ("KeyW", 0x57, 17),
+    ("KeyX", 0x58, 45),
+    ("KeyY", 0x59, 21),
+    ("KeyZ", 0x5A, 44),
+    ("Digit1", 0x31, 2),
+    ("Digit2", 0x32, 3),
+    ("Digit3", 0x33, 4),
+    ("Digit4", 0x34, 5),
+    ("Digit5", 0x35, 6),
+    ("Digit6", 0x36, 7),
+    ("Digit7", 0x37, 8),
+    ("Digit8", 0x38, 9),
+    ("Digit9", 0x39, 10),
+    ("Digit0", 0x30, 11),
+    ("Enter", 0x0D, 28),
+    ("Escape", 0x1B, 1),
+    ("Backspace", 0x08, 14),
+    ("Tab", 0x09, 15),
+    ("Space", 0x20, 57),
+    ("Minus", 0xBD, 12),
+    ("Equal", 0xBB, 13),
+    ("BracketLeft", 0xDB, 26),
+    ("BracketRight", 0xDD, 27),
+    ("Backslash", 0xDC, 43),
+    ("Semicolon", 0xBA, 39),
+    ("Quote", 0xDE, 40),
+    ("Backquote", 0xC0, 41),
+    ("Comma", 0xBC, 51),
+    ("Period", 0xBE, 52),
+    ("Slash", 0xBF, 53),
+    ("IntlBackslash", 0xE2, 86),
+    ("CapsLock", 0x14, 58),
+    ("F1", 0x70, 59),
+    ("F2", 0x71, 60),
+    ("F3", 0x72, 61),
+    ("F4", 0x73, 62),
+    ("F5", 0x74, 63),
+    ("F6", 0x75, 64),
+    ("F7", 0x76, 65),
+    ("F8", 0x77, 66),
+    ("F9", 0x78, 67),
+    ("F10", 0x79, 68),
+    ("F11", 0x7A, 87),
+    ("F12", 0x7B, 88),
+    ("PrintScreen", 0x2C, 99),
+    ("ScrollLock", 0x91, 70),
+    ("Pause", 0x13, 119),
+    ("Insert", 0x2D, 110),
+    ("Home", 0x24, 102),
+    ("PageUp", 0x21, 104),
+    ("Delete", 0x2E, 111),
+    ("End", 0x23, 107),
+    ("PageDown", 0x22, 109),
+    ("ArrowRight", 0x27, 106),
+    ("ArrowLeft", 0x25, 105),
+    ("ArrowDown", 0x28, 108),
+    ("ArrowUp", 0x26, 103),
+    ("NumLock", 0x90, 69),
+    ("NumpadDivide", 0x6F, 98),
+    ("NumpadMultiply", 0x6A, 55),
+    ("NumpadSubtract", 0x6D, 74),
+    ("NumpadAdd", 0x6B, 78),
+    ("NumpadEnter", 0x0D, 96),
+    ("Numpad1", 0x61, 79),
+    ("Numpad2", 0x62, 80),
+    ("Numpad3", 0x63, 81),
+    ("Numpad4", 0x64, 75),
+    ("Numpad5", 0x65, 76),
+    ("Numpad6", 0x66, 77),
+    ("Numpad7", 0x67, 71),
+    ("Numpad8", 0x68, 72),
+    ("Numpad9", 0x69, 73),
+    ("Numpad0", 0x60, 82),
+    ("NumpadDecimal", 0x6E, 83),
+    ("ContextMenu", 0x5D, 127),
+    ("ShiftLeft", 0x10, 42),
+    ("ShiftRight", 0x10, 54),
+    ("ControlLeft", 0x11, 29),
+    ("ControlRight", 0x11, 97),
+    ("AltLeft", 0x12, 56),
+    ("AltRight", 0x12, 100),
+    ("MetaLeft", 0x5B, 125),
+    ("MetaRight", 0x5C, 126),
+];
+
+/// Platform codes for a DOM `code`, if it is a key we know.
+pub(crate) fn lookup(code: &str) -> Option<KeyCodes> {
+    KEYS.iter()
+        .find(|(dom, _, _)| *dom == code)
+        .map(|&(_, windows, evdev)| KeyCodes { windows, evdev })
+}
+
+/// Windows virtual-key code for a key with no known physical position,
+/// guessed from the character it types.
+pub(crate) fn windows_code_for_char(c: char) -> c_int {
+    match c {
+        'a'..='z' => c.to_ascii_uppercase() as c_int,
+        'A'..='Z' | '0'..='9' | ' ' => c as c_int,
+        _ => 0,
+    }
+}
+
+/// `cef_event_flags_t` bits for a key event.
+pub(crate) fn event_flags(code: &str, modifiers: &Modifiers, repeat: bool) -> u32 {
+    let mut flags = 0;
+    if modifiers.shift {
+        flags |= EVENTFLAG_SHIFT_DOWN;
+    }
+    if modifiers.ctrl {
+        flags |= EVENTFLAG_CONTROL_DOWN;
+    }
+    if modifiers.alt {
+        flags |= EVENTFLAG_ALT_DOWN;
+    }
+    if modifiers.meta {
+        flags |= EVENTFLAG_COMMAND_DOWN;
+    }
+    if code.starts_with("Numpad") {
+        flags |= EVENTFLAG_IS_KEY_PAD;
+    }
+    match code {
+        "ShiftLeft" | "ControlLeft" | "AltLeft" | "MetaLeft" => flags |= EVENTFLAG_IS_LEFT,
+        "ShiftRight" | "ControlRight" | "AltRight" | "MetaRight" => flags |= EVENTFLAG_IS_RIGHT,
+        _ => {}
+    }
+    if repeat {
+        flags |= EVENTFLAG_IS_REPEAT;
+    }
+    flags
+}
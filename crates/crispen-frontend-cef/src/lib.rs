@@ -18,22 +18,30 @@
 //! 1. Creating a browser with `windowless_rendering_enabled`.
 //! 2. Implementing a `RenderHandler` that receives BGRA paint callbacks.
 //! 3. Storing the pixel buffer in an `Arc<Vec<u8>>` for zero-copy sharing.
+//!
+//! Every [`CefBackend`] is a separate browser with its own framebuffer and
+//! IPC channel, so several can run side by side;
+//! [`CefBackend::open_panel`] starts one for a floating tool window.
 
 pub mod browser;
 pub mod capture;
+pub mod clipboard;
 pub mod devtools;
+mod keys;
 
-use browser::{SharedState, IPC_PREFIX};
+use browser::{IPC_PREFIX, SharedState};
 use cef::{
-    Browser, CefStringUtf16, ImplBrowser, ImplBrowserHost, ImplFrame, KeyEvent, KeyEventType,
-    MouseButtonType,
+    Browser, CefString, CefStringUtf16, CompositionUnderline, CompositionUnderlineStyle,
+    ImplBrowser, ImplBrowserHost, ImplFrame, KeyEvent, KeyEventType, MouseButtonType, PointerType,
+    Range, TouchEventType,
 };
+use clipboard::CefClipboard;
 use crispen_frontend_core::{
-    CaptureResult, CompositeBackend, FrontendError, KeyboardEvent, MouseButton, MouseEvent,
+    CaptureResult, Clipboard, CompositeBackend, ContextMenuRequest, CursorIcon, FrontendError,
+    ImeEvent, KeyboardEvent, MouseButton, MouseEvent, PointerKind, TouchEvent, TouchPhase,
 };
 use std::ffi::c_int;
-use std::mem::size_of;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
@@ -48,16 +56,26 @@ pub enum CefState {
     Ready,
 }
 
+/// What a browser was loaded from.
+#[derive(Debug, Clone)]
+enum Source {
+    Html,
+    Url(String),
+}
+
 // ── Backend ──────────────────────────────────────────────────────
 
 /// CEF-based offscreen webview backend.
 pub struct CefBackend {
     size: (u32, u32),
+    scale_factor: f32,
     state: CefState,
     shared: Arc<SharedState>,
     browser: Option<Browser>,
     from_ui_rx: mpsc::UnboundedReceiver<String>,
     to_ui_messages: Vec<String>,
+    clipboard: CefClipboard,
+    source: Source,
 }
 
 impl CefBackend {
@@ -73,19 +91,29 @@ impl CefBackend {
             framebuffer: Mutex::new(None),
             framebuffer_size: Mutex::new((0, 0)),
             dirty: Arc::new(AtomicBool::new(false)),
+            dirty_rects: Mutex::new(Vec::new()),
             size: Mutex::new(size),
+            scale_factor: Mutex::new(1.0),
             from_ui_tx,
+            text_input_active: AtomicBool::new(false),
+            ime_caret: Mutex::new(None),
+            cursor: Mutex::new(CursorIcon::Default),
+            context_menu: Mutex::new(None),
+            page_loaded: AtomicBool::new(false),
         });
 
         let browser_instance = browser::create_browser(html_content, size, &shared)?;
 
         Ok(Self {
             size,
+            scale_factor: 1.0,
             state: CefState::Loading,
             shared,
             browser: Some(browser_instance),
             from_ui_rx,
             to_ui_messages: Vec::new(),
+            clipboard: CefClipboard::default(),
+            source: Source::Html,
         })
     }
 
@@ -99,22 +127,44 @@ impl CefBackend {
             framebuffer: Mutex::new(None),
             framebuffer_size: Mutex::new((0, 0)),
             dirty: Arc::new(AtomicBool::new(false)),
+            dirty_rects: Mutex::new(Vec::new()),
             size: Mutex::new(size),
+            scale_factor: Mutex::new(1.0),
             from_ui_tx,
+            text_input_active: AtomicBool::new(false),
+            ime_caret: Mutex::new(None),
+            cursor: Mutex::new(CursorIcon::Default),
+            context_menu: Mutex::new(None),
+            page_loaded: AtomicBool::new(false),
         });
 
         let browser_instance = browser::create_browser_from_url(url, size, &shared)?;
 
         Ok(Self {
             size,
+            scale_factor: 1.0,
             state: CefState::Loading,
             shared,
             browser: Some(browser_instance),
             from_ui_rx,
             to_ui_messages: Vec::new(),
+            clipboard: CefClipboard::default(),
+            source: Source::Url(url.to_string()),
         })
     }
 
+    /// Open a separate browser showing only `panel` of the same UI, for a
+    /// floating window. It loads this backend's URL with `?panel=<panel>`
+    /// and has its own framebuffer and IPC channel.
+    pub fn open_panel(&self, panel: &str, size: (u32, u32)) -> Result<Self, FrontendError> {
+        match &self.source {
+            Source::Url(url) => Self::from_url(&panel_url(url, panel), size),
+            Source::Html => Err(FrontendError::Backend(
+                "floating panels need the UI to be loaded from a URL".into(),
+            )),
+        }
+    }
+
     /// Current lifecycle state.
     pub fn state(&self) -> CefState {
         self.state
@@ -200,6 +250,18 @@ impl CefBackend {
             }
         }
     }
+
+    /// Run queued clipboard writes in the page.
+    fn flush_clipboard_writes(&mut self) {
+        if self.state != CefState::Ready {
+            return;
+        }
+        for js in self.clipboard.take_scripts() {
+            if let Err(e) = self.eval(&js) {
+                tracing::warn!("failed to write clipboard: {e}");
+            }
+        }
+    }
 }
 
 // ── CompositeBackend impl ────────────────────────────────────────
@@ -208,13 +270,18 @@ impl CompositeBackend for CefBackend {
     fn poll(&mut self) {
         cef::do_message_loop_work();
 
+        let page_loaded = self.shared.page_loaded.swap(false, Ordering::SeqCst);
         if self.state == CefState::Loading && capture::has_framebuffer(&self.shared) {
             self.state = CefState::Ready;
             tracing::info!("CEF webview ready");
             self.inject_ipc_bridge();
+        } else if page_loaded && self.state == CefState::Ready {
+            // The bridge script is a no-op on a page that still has it.
+            self.inject_ipc_bridge();
         }
 
         self.flush_to_ui_messages();
+        self.flush_clipboard_writes();
     }
 
     fn is_ready(&self) -> bool {
@@ -244,80 +311,200 @@ impl CompositeBackend for CefBackend {
         }
     }
 
+    fn set_scale_factor(&mut self, scale: f32) {
+        if !scale.is_finite() || scale <= 0.0 || scale == self.scale_factor {
+            return;
+        }
+        self.scale_factor = scale;
+        *self.shared.scale_factor.lock().unwrap() = scale;
+        tracing::info!("CEF device scale factor set to {scale}");
+
+        if let Some(browser) = &self.browser {
+            if let Some(host) = browser.host() {
+                host.notify_screen_info_changed();
+                host.was_resized();
+            }
+        }
+    }
+
     fn send_mouse_event(&mut self, event: MouseEvent) {
         let Some(browser) = &self.browser else { return };
         let Some(host) = browser.host() else { return };
+        // CEF takes positions in device-independent pixels.
+        let scale = self.scale_factor;
 
         match event {
             MouseEvent::Move { x, y } => {
-                let me = cef::MouseEvent { x: x as c_int, y: y as c_int, modifiers: 0 };
+                let me = cef::MouseEvent {
+                    x: (x / scale) as c_int,
+                    y: (y / scale) as c_int,
+                    modifiers: 0,
+                };
                 host.send_mouse_move_event(Some(&me), 0);
             }
             MouseEvent::ButtonDown { button, x, y } => {
-                let me = cef::MouseEvent { x: x as c_int, y: y as c_int, modifiers: 0 };
+                let me = cef::MouseEvent {
+                    x: (x / scale) as c_int,
+                    y: (y / scale) as c_int,
+                    modifiers: 0,
+                };
                 host.send_mouse_click_event(Some(&me), to_cef_button(button), 0, 1);
             }
             MouseEvent::ButtonUp { button, x, y } => {
-                let me = cef::MouseEvent { x: x as c_int, y: y as c_int, modifiers: 0 };
+                let me = cef::MouseEvent {
+                    x: (x / scale) as c_int,
+                    y: (y / scale) as c_int,
+                    modifiers: 0,
+                };
                 host.send_mouse_click_event(Some(&me), to_cef_button(button), 1, 1);
             }
-            MouseEvent::Scroll { x, y, delta_x, delta_y } => {
-                let me = cef::MouseEvent { x: x as c_int, y: y as c_int, modifiers: 0 };
+            MouseEvent::Scroll {
+                x,
+                y,
+                delta_x,
+                delta_y,
+            } => {
+                let me = cef::MouseEvent {
+                    x: (x / scale) as c_int,
+                    y: (y / scale) as c_int,
+                    modifiers: 0,
+                };
                 host.send_mouse_wheel_event(Some(&me), delta_x as c_int, delta_y as c_int);
             }
         }
     }
 
+    fn send_touch_event(&mut self, event: TouchEvent) {
+        let Some(browser) = &self.browser else { return };
+        let Some(host) = browser.host() else { return };
+        let scale = self.scale_factor;
+
+        // Chromium turns these into pointer events with the right
+        // `pointerType` and synthesizes clicks from taps itself.
+        let te = cef::TouchEvent {
+            id: event.id as c_int,
+            x: event.x / scale,
+            y: event.y / scale,
+            radius_x: 0.0,
+            radius_y: 0.0,
+            rotation_angle: 0.0,
+            // Pressure-insensitive: 0 means "not reported".
+            pressure: 0.0,
+            type_: to_cef_touch_type(event.phase),
+            modifiers: 0,
+            pointer_type: match event.pointer {
+                PointerKind::Touch => PointerType::TOUCH,
+                PointerKind::Pen => PointerType::PEN,
+            },
+        };
+        host.send_touch_event(Some(&te));
+    }
+
     fn send_keyboard_event(&mut self, event: KeyboardEvent) {
         let Some(browser) = &self.browser else { return };
         let Some(host) = browser.host() else { return };
 
-        let char_code = event.key.chars().next().unwrap_or('\0');
-        let vk_code = if char_code.is_ascii_lowercase() {
-            char_code.to_ascii_uppercase() as c_int
-        } else {
-            char_code as c_int
+        let codes = keys::lookup(&event.code);
+        let windows_key_code = match codes {
+            Some(codes) => codes.windows,
+            None => single_char(&event.key).map_or(0, keys::windows_code_for_char),
         };
-        let typed_char = if event.modifiers.shift && char_code.is_ascii_lowercase() {
-            char_code.to_ascii_uppercase()
-        } else {
-            char_code
+        let native_key_code = codes.map_or(0, |codes| codes.native());
+        let modifiers = keys::event_flags(&event.code, &event.modifiers, event.repeat);
+        let text: Vec<u16> = event.text.as_deref().unwrap_or("").encode_utf16().collect();
+
+        let key_event = |type_, character| KeyEvent {
+            type_,
+            modifiers,
+            windows_key_code,
+            native_key_code,
+            character,
+            unmodified_character: character,
+            ..Default::default()
         };
 
-        let mut modifiers: u32 = 0;
-        if event.modifiers.shift { modifiers |= 1 << 1; }
-        if event.modifiers.ctrl { modifiers |= 1 << 2; }
-        if event.modifiers.alt { modifiers |= 1 << 3; }
+        let type_ = if event.pressed {
+            KeyEventType::RAWKEYDOWN
+        } else {
+            KeyEventType::KEYUP
+        };
+        host.send_key_event(Some(&key_event(type_, text.first().copied().unwrap_or(0))));
+
+        // Ctrl / Cmd shortcuts (copy, cut, paste, select all, undo) run
+        // from Chromium's key-down bindings; a CHAR would also type the
+        // letter. AltGr arrives as Ctrl+Alt on Windows and still types.
+        let shortcut = (event.modifiers.ctrl && !event.modifiers.alt) || event.modifiers.meta;
+        if event.pressed && !shortcut {
+            for unit in text {
+                host.send_key_event(Some(&key_event(KeyEventType::CHAR, unit)));
+            }
+        }
+    }
 
-        let key_event = KeyEvent {
-            size: size_of::<KeyEvent>(),
-            type_: if event.pressed { KeyEventType::RAWKEYDOWN } else { KeyEventType::KEYUP },
-            modifiers,
-            windows_key_code: vk_code,
-            native_key_code: 0,
-            is_system_key: 0,
-            character: typed_char as u16,
-            unmodified_character: char_code as u16,
-            focus_on_editable_field: 0,
+    fn send_ime_event(&mut self, event: ImeEvent) {
+        let Some(browser) = &self.browser else { return };
+        let Some(host) = browser.host() else { return };
+        // An invalid range means "replace the current composition".
+        let current = Range {
+            from: u32::MAX,
+            to: u32::MAX,
         };
-        host.send_key_event(Some(&key_event));
-
-        if event.pressed && char_code != '\0' {
-            let char_event = KeyEvent {
-                size: size_of::<KeyEvent>(),
-                type_: KeyEventType::CHAR,
-                modifiers,
-                windows_key_code: vk_code,
-                native_key_code: 0,
-                is_system_key: 0,
-                character: typed_char as u16,
-                unmodified_character: char_code as u16,
-                focus_on_editable_field: 0,
-            };
-            host.send_key_event(Some(&char_event));
+
+        match event {
+            ImeEvent::Preedit { text, .. } if text.is_empty() => host.ime_cancel_composition(),
+            ImeEvent::Preedit { text, cursor } => {
+                let len = utf16_len(&text);
+                let underline = CompositionUnderline {
+                    range: Range { from: 0, to: len },
+                    color: 0xFF00_0000,
+                    style: CompositionUnderlineStyle::SOLID,
+                    ..Default::default()
+                };
+                // IME cursors are byte offsets; CEF counts UTF-16 units.
+                let offset = |byte: usize| text.get(..byte).map_or(len, utf16_len);
+                let selection = match cursor {
+                    Some((start, end)) => Range {
+                        from: offset(start),
+                        to: offset(end),
+                    },
+                    None => Range { from: len, to: len },
+                };
+                let cef_text: CefString = text.as_str().into();
+                host.ime_set_composition(
+                    Some(&cef_text),
+                    Some(std::slice::from_ref(&underline)),
+                    Some(&current),
+                    Some(&selection),
+                );
+            }
+            ImeEvent::Commit { text } => {
+                let cef_text: CefString = text.as_str().into();
+                host.ime_commit_text(Some(&cef_text), Some(&current), 0);
+            }
         }
     }
 
+    fn text_input_active(&self) -> bool {
+        self.shared.text_input_active.load(Ordering::SeqCst)
+    }
+
+    fn ime_caret(&self) -> Option<(f32, f32)> {
+        let caret = self.shared.ime_caret.lock().unwrap().clone()?;
+        let scale = self.scale_factor;
+        Some((
+            caret.x as f32 * scale,
+            (caret.y + caret.height) as f32 * scale,
+        ))
+    }
+
+    fn take_context_menu_request(&mut self) -> Option<ContextMenuRequest> {
+        self.shared.context_menu.lock().unwrap().take()
+    }
+
+    fn cursor(&self) -> CursorIcon {
+        *self.shared.cursor.lock().unwrap()
+    }
+
     fn send_to_ui(&mut self, json: String) -> Result<(), FrontendError> {
         self.to_ui_messages.push(json);
         Ok(())
@@ -327,6 +514,17 @@ impl CompositeBackend for CefBackend {
         self.from_ui_rx.try_recv().ok()
     }
 
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        Some(&mut self.clipboard)
+    }
+
+    fn reload(&mut self) {
+        if let Some(browser) = &self.browser {
+            tracing::info!("reloading CEF webview");
+            browser.reload_ignore_cache();
+        }
+    }
+
     fn show_dev_tools(&self) {
         self.show_dev_tools();
     }
@@ -352,3 +550,36 @@ fn to_cef_button(button: MouseButton) -> MouseButtonType {
         MouseButton::Right => MouseButtonType::RIGHT,
     }
 }
+
+/// The only character of `s`, if it has exactly one.
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+/// `url` with a `panel` query parameter, placed before any fragment.
+fn panel_url(url: &str, panel: &str) -> String {
+    let (base, fragment) = url
+        .split_once('#')
+        .map_or((url, None), |(b, f)| (b, Some(f)));
+    let separator = if base.contains('?') { '&' } else { '?' };
+    let mut out = format!("{base}{separator}panel={panel}");
+    if let Some(fragment) = fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
+
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+fn to_cef_touch_type(phase: TouchPhase) -> TouchEventType {
+    match phase {
+        TouchPhase::Started => TouchEventType::PRESSED,
+        TouchPhase::Moved => TouchEventType::MOVED,
+        TouchPhase::Ended => TouchEventType::RELEASED,
+        TouchPhase::Cancelled => TouchEventType::CANCELLED,
+    }
+}
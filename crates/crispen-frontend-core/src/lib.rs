@@ -1,9 +1,10 @@
 //! Frontend core abstractions for Crispen.
 //!
 //! Defines the [`CompositeBackend`] trait that abstracts over different UI
-//! rendering backends (CEF, WebKitGTK, etc.).  Input event types and capture
-//! results are also defined here to avoid coupling the backend crate to
-//! domain-specific IPC types.
+//! rendering backends (CEF, WebKitGTK, etc.).  Input event types, capture
+//! results and the [`Clipboard`] abstraction are also defined here to avoid
+//! coupling the backend crate to domain-specific IPC types.
+//! [`FloatingWebviews`] stacks extra backend instances over the main one.
 
 use std::sync::Arc;
 
@@ -18,6 +19,49 @@ pub enum CaptureResult {
     Rgba(Vec<u8>, u32, u32),
     /// BGRA pixel data (shared via `Arc`) with dimensions.
     Bgra(Arc<Vec<u8>>, u32, u32),
+    /// Like [`Bgra`](Self::Bgra), but only the listed regions changed since
+    /// the previous capture, so only they need uploading.
+    BgraRegions(Arc<Vec<u8>>, u32, u32, Vec<DirtyRect>),
+}
+
+/// A changed region of a captured frame, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirtyRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DirtyRect {
+    /// Number of pixels covered.
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    /// Copy this region out of a tightly packed 4-byte-per-pixel frame
+    /// `frame_width` pixels wide, row by row.
+    pub fn extract(&self, frame: &[u8], frame_width: u32) -> Vec<u8> {
+        let row_bytes = self.width as usize * 4;
+        let stride = frame_width as usize * 4;
+        let mut out = Vec::with_capacity(row_bytes * self.height as usize);
+        for row in self.y as usize..(self.y + self.height) as usize {
+            let start = row * stride + self.x as usize * 4;
+            out.extend_from_slice(&frame[start..start + row_bytes]);
+        }
+        out
+    }
+
+    /// Copy this region from one tightly packed 4-byte-per-pixel frame
+    /// `frame_width` pixels wide into another of the same size.
+    pub fn copy_between(&self, src: &[u8], dst: &mut [u8], frame_width: u32) {
+        let row_bytes = self.width as usize * 4;
+        let stride = frame_width as usize * 4;
+        for row in self.y as usize..(self.y + self.height) as usize {
+            let start = row * stride + self.x as usize * 4;
+            dst[start..start + row_bytes].copy_from_slice(&src[start..start + row_bytes]);
+        }
+    }
 }
 
 // ── Errors ───────────────────────────────────────────────────────
@@ -33,11 +77,17 @@ pub enum FrontendError {
 
     #[error("backend error: {0}")]
     Backend(String),
+
+    #[error("clipboard error: {0}")]
+    Clipboard(String),
 }
 
 // ── Input events ─────────────────────────────────────────────────
 
 /// Mouse event forwarded from Bevy to the webview.
+///
+/// Positions are in physical pixels of the surface; backends convert them
+/// to their own coordinate space.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MouseEvent {
     Move { x: f32, y: f32 },
@@ -54,11 +104,74 @@ pub enum MouseButton {
     Middle,
 }
 
+/// Touch or pen contact forwarded from Bevy to the webview.
+///
+/// Positions are in physical pixels, like [`MouseEvent`]. Pressure and tilt
+/// are not forwarded; a pen behaves like a single precise finger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TouchEvent {
+    /// Contact id, stable from `Started` to `Ended` / `Cancelled`.
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub pointer: PointerKind,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl TouchEvent {
+    /// The mouse event a backend without touch support should see instead:
+    /// contact down / move / up drive the left button.
+    pub fn as_mouse_event(&self) -> MouseEvent {
+        let (x, y) = (self.x, self.y);
+        match self.phase {
+            TouchPhase::Started => MouseEvent::ButtonDown {
+                button: MouseButton::Left,
+                x,
+                y,
+            },
+            TouchPhase::Moved => MouseEvent::Move { x, y },
+            TouchPhase::Ended | TouchPhase::Cancelled => MouseEvent::ButtonUp {
+                button: MouseButton::Left,
+                x,
+                y,
+            },
+        }
+    }
+}
+
+/// Stage of a touch or pen contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// What is touching the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PointerKind {
+    Touch,
+    Pen,
+}
+
 /// Keyboard input event forwarded from Bevy to the webview.
+///
+/// `key` and `code` follow the DOM `KeyboardEvent.key` / `.code` values, so
+/// a backend can tell the layout-dependent meaning of a key from the
+/// physical key that was pressed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyboardEvent {
+    /// Logical key: the character for printable keys (`"a"`, `"A"`, `"é"`),
+    /// otherwise a named key (`"Enter"`, `"ArrowUp"`, `"F5"`).
     pub key: String,
+    /// Physical key (`"KeyA"`, `"Numpad1"`, `"ArrowUp"`); empty if unknown.
+    pub code: String,
+    /// Text the key press types, if any.
+    pub text: Option<String>,
     pub pressed: bool,
+    /// Auto-repeat of a held key.
+    pub repeat: bool,
     pub modifiers: Modifiers,
 }
 
@@ -71,6 +184,165 @@ pub struct Modifiers {
     pub meta: bool,
 }
 
+/// Input method (IME) composition forwarded from Bevy to the webview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImeEvent {
+    /// The uncommitted composition text changed. `cursor` is a byte range
+    /// into `text`; empty text cancels the composition.
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composition finished and `text` should be inserted.
+    Commit { text: String },
+}
+
+// ── Cursor ───────────────────────────────────────────────────────
+
+/// Mouse cursor the UI asks for, named after the CSS `cursor` values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    VerticalText,
+    Crosshair,
+    Move,
+    Grab,
+    Grabbing,
+    AllScroll,
+    NotAllowed,
+    NoDrop,
+    Copy,
+    Alias,
+    Wait,
+    Progress,
+    Help,
+    ContextMenu,
+    Cell,
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    ZoomIn,
+    ZoomOut,
+    /// `cursor: none`.
+    Hidden,
+}
+
+// ── Context menu ─────────────────────────────────────────────────
+
+/// A right-click the page did not handle itself. Backends suppress their
+/// built-in menu and report it so the UI can show its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextMenuRequest {
+    /// Position in CSS pixels from the top-left of the page.
+    pub x: i32,
+    pub y: i32,
+    /// Whether the click landed in an editable field.
+    pub editable: bool,
+    /// Selected text; empty if nothing is selected.
+    pub selection_text: String,
+    /// Target of the link under the pointer, if any.
+    pub link_url: Option<String>,
+}
+
+// ── Clipboard ────────────────────────────────────────────────────
+
+/// An image on the clipboard: 8-bit sRGB RGBA, straight alpha, rows top
+/// to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Fail unless `rgba` holds exactly `width * height` pixels.
+    pub fn validate(&self) -> Result<(), FrontendError> {
+        let expected = self.width as usize * self.height as usize * 4;
+        if self.width == 0 || self.height == 0 || self.rgba.len() != expected {
+            return Err(FrontendError::Clipboard(format!(
+                "{}x{} image needs {expected} bytes, got {}",
+                self.width,
+                self.height,
+                self.rgba.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Text and image clipboard shared by every frontend mode, so copying a
+/// value, look or still behaves the same whichever UI is in front.
+///
+/// Reads return `Ok(None)` when the clipboard holds nothing of that kind.
+pub trait Clipboard {
+    /// The text on the clipboard.
+    fn read_text(&mut self) -> Result<Option<String>, FrontendError>;
+
+    /// Replace the clipboard contents with `text`.
+    fn write_text(&mut self, text: &str) -> Result<(), FrontendError>;
+
+    /// The image on the clipboard.
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>, FrontendError>;
+
+    /// Replace the clipboard contents with `image`.
+    fn write_image(&mut self, image: &ClipboardImage) -> Result<(), FrontendError>;
+}
+
+#[derive(Debug, Clone)]
+enum ClipboardContent {
+    Text(String),
+    Image(ClipboardImage),
+}
+
+/// Clipboard that lives in this process only. Used where no system
+/// clipboard is reachable, and by backends to remember what they wrote.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    content: Option<ClipboardContent>,
+}
+
+impl Clipboard for MemoryClipboard {
+    fn read_text(&mut self) -> Result<Option<String>, FrontendError> {
+        Ok(match &self.content {
+            Some(ClipboardContent::Text(text)) => Some(text.clone()),
+            _ => None,
+        })
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), FrontendError> {
+        self.content = Some(ClipboardContent::Text(text.to_string()));
+        Ok(())
+    }
+
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>, FrontendError> {
+        Ok(match &self.content {
+            Some(ClipboardContent::Image(image)) => Some(image.clone()),
+            _ => None,
+        })
+    }
+
+    fn write_image(&mut self, image: &ClipboardImage) -> Result<(), FrontendError> {
+        image.validate()?;
+        self.content = Some(ClipboardContent::Image(image.clone()));
+        Ok(())
+    }
+}
+
 // ── Backend trait ────────────────────────────────────────────────
 
 /// Trait for UI rendering backends that can be composited into the Bevy scene.
@@ -93,18 +365,194 @@ pub trait CompositeBackend {
     /// Resize the backend surface.
     fn resize(&mut self, width: u32, height: u32);
 
+    /// Set the device scale factor (physical pixels per CSS pixel) so text
+    /// and layout render at the display's density. The surface stays
+    /// [`size`](Self::size) physical pixels.
+    fn set_scale_factor(&mut self, _scale: f32) {}
+
     /// Forward a mouse event to the backend.
     fn send_mouse_event(&mut self, event: MouseEvent);
 
+    /// Forward a touch or pen contact to the backend. The default replays
+    /// it as left-button mouse input.
+    fn send_touch_event(&mut self, event: TouchEvent) {
+        self.send_mouse_event(event.as_mouse_event());
+    }
+
     /// Forward a keyboard event to the backend.
     fn send_keyboard_event(&mut self, event: KeyboardEvent);
 
+    /// Forward an IME composition to the backend.
+    fn send_ime_event(&mut self, _event: ImeEvent) {}
+
+    /// Whether a text field in the UI has focus, so IME should be enabled.
+    fn text_input_active(&self) -> bool {
+        false
+    }
+
+    /// Bottom-left of the text caret in physical pixels, for placing the
+    /// IME candidate window. Only known while composing.
+    fn ime_caret(&self) -> Option<(f32, f32)> {
+        None
+    }
+
     /// Send a serialised JSON message to the UI.
     fn send_to_ui(&mut self, json: String) -> Result<(), FrontendError>;
 
     /// Try to receive a serialised JSON message from the UI (non-blocking).
     fn try_recv_from_ui(&mut self) -> Option<String>;
 
+    /// Take the context menu request made since the last call, if any.
+    fn take_context_menu_request(&mut self) -> Option<ContextMenuRequest> {
+        None
+    }
+
+    /// The cursor the UI wants under the pointer.
+    fn cursor(&self) -> CursorIcon {
+        CursorIcon::Default
+    }
+
+    /// The clipboard this backend can reach, if it has one.
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        None
+    }
+
+    /// Reload the UI page, e.g. after its files changed. Backends that
+    /// cannot reload themselves ignore this.
+    fn reload(&mut self) {}
+
     /// Open developer tools for debugging (CEF only).
     fn show_dev_tools(&self) {}
 }
+
+// ── Floating webviews ────────────────────────────────────────────
+
+/// Where a floating webview sits in the window, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PanelRect {
+    /// Whether the window position (`x`, `y`) lies inside.
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (left, top) = (self.x as f32, self.y as f32);
+        x >= left && y >= top && x < left + self.width as f32 && y < top + self.height as f32
+    }
+
+    /// Convert a window position to one on this panel's surface.
+    pub fn to_local(&self, x: f32, y: f32) -> (f32, f32) {
+        (x - self.x as f32, y - self.y as f32)
+    }
+}
+
+/// A named webview drawn over the main one.
+#[derive(Debug)]
+pub struct FloatingWebview<B> {
+    pub name: String,
+    pub rect: PanelRect,
+    pub backend: B,
+}
+
+/// Independent webviews floating over a main backend (tool windows, help),
+/// each with its own surface and IPC channel. Kept bottom to top; the one
+/// last clicked is raised and receives keyboard input.
+#[derive(Debug)]
+pub struct FloatingWebviews<B> {
+    views: Vec<FloatingWebview<B>>,
+    focused: Option<String>,
+}
+
+impl<B> Default for FloatingWebviews<B> {
+    fn default() -> Self {
+        Self {
+            views: Vec::new(),
+            focused: None,
+        }
+    }
+}
+
+impl<B: CompositeBackend> FloatingWebviews<B> {
+    /// Add `backend` on top as `name`, sized to `rect`. A webview already
+    /// open under that name is closed and returned.
+    pub fn open(&mut self, name: &str, rect: PanelRect, mut backend: B) -> Option<B> {
+        let previous = self.close(name);
+        backend.resize(rect.width, rect.height);
+        self.views.push(FloatingWebview {
+            name: name.to_string(),
+            rect,
+            backend,
+        });
+        self.focused = Some(name.to_string());
+        previous
+    }
+
+    /// Remove the webview called `name`.
+    pub fn close(&mut self, name: &str) -> Option<B> {
+        let index = self.views.iter().position(|v| v.name == name)?;
+        if self.focused.as_deref() == Some(name) {
+            self.focused = None;
+        }
+        Some(self.views.remove(index).backend)
+    }
+
+    /// Move or resize the webview called `name`. Returns `false` if it is
+    /// not open.
+    pub fn place(&mut self, name: &str, rect: PanelRect) -> bool {
+        let Some(view) = self.get_mut(name) else {
+            return false;
+        };
+        view.rect = rect;
+        view.backend.resize(rect.width, rect.height);
+        true
+    }
+
+    /// The webview called `name`.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut FloatingWebview<B>> {
+        self.views.iter_mut().find(|v| v.name == name)
+    }
+
+    /// Name of the topmost webview containing the window position.
+    pub fn hit_test(&self, x: f32, y: f32) -> Option<&str> {
+        self.views
+            .iter()
+            .rev()
+            .find(|v| v.rect.contains(x, y))
+            .map(|v| v.name.as_str())
+    }
+
+    /// Give `name` keyboard focus and raise it, or hand focus back to the
+    /// main webview with `None`.
+    pub fn focus(&mut self, name: Option<&str>) {
+        self.focused = None;
+        let Some(name) = name else { return };
+        if let Some(index) = self.views.iter().position(|v| v.name == name) {
+            let view = self.views.remove(index);
+            self.views.push(view);
+            self.focused = Some(name.to_string());
+        }
+    }
+
+    /// The webview with keyboard focus, if it is not the main one.
+    pub fn focused_mut(&mut self) -> Option<&mut FloatingWebview<B>> {
+        let name = self.focused.clone()?;
+        self.get_mut(&name)
+    }
+
+    /// Open webviews, bottom to top.
+    pub fn iter(&self) -> impl Iterator<Item = &FloatingWebview<B>> {
+        self.views.iter()
+    }
+
+    /// Open webviews, bottom to top.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut FloatingWebview<B>> {
+        self.views.iter_mut()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+}
@@ -0,0 +1,63 @@
+//! GPU submit and readback latency.
+//!
+//! Needs a GPU adapter, so it only builds with the `gpu-bench` feature:
+//! `cargo bench -p crispen-gpu --features gpu-bench`. Without an adapter
+//! the benchmarks are skipped with a message rather than failing.
+
+use crispen_core::image::BitDepth;
+use crispen_core::patterns::{TestPattern, generate};
+use crispen_core::{ColorSpaceId, GradingParams};
+use crispen_gpu::GpuGradingPipeline;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+fn pipeline(c: &mut Criterion) {
+    let mut pipeline = match GpuGradingPipeline::create_blocking() {
+        Ok(pipeline) => pipeline,
+        Err(e) => {
+            eprintln!("skipping GPU benchmarks: {e}");
+            return;
+        }
+    };
+    let params = GradingParams {
+        saturation: 1.2,
+        contrast: 1.1,
+        ..GradingParams::default()
+    };
+
+    let mut group = c.benchmark_group("gpu_bake_lut");
+    for size in [33, 65] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter(|| {
+                pipeline.bake_lut(&params, size);
+                pipeline
+                    .device()
+                    .poll(wgpu::PollType::wait_indefinitely())
+                    .unwrap();
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("gpu_submit_frame");
+    group.sample_size(20);
+    for (label, width, height) in [("720p", 1280, 720), ("1080p", 1920, 1080)] {
+        let image = generate(
+            TestPattern::ColorBars,
+            width,
+            height,
+            BitDepth::F32,
+            ColorSpaceId::Srgb,
+        );
+        let source = pipeline.upload_image(&image);
+        group.bench_function(label, |b| {
+            b.iter(|| {
+                let frame = pipeline.submit_frame(&source, &params, 65);
+                pipeline.recycle_viewer_bytes(frame.viewer_bytes);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
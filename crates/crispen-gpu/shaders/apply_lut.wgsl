@@ -6,11 +6,16 @@
 @group(0) @binding(3) var lut_sampler: sampler;
 @group(0) @binding(4) var<uniform> dimensions: vec2<u32>;
 
+// Baked into each pipeline variant (see `specialization.rs`); 0 reads the
+// width from `dimensions` instead.
+override IMAGE_WIDTH: u32 = 0u;
+
 @compute @workgroup_size(16, 16, 1)
 fn apply_lut(@builtin(global_invocation_id) gid: vec3<u32>) {
-    if (gid.x >= dimensions.x || gid.y >= dimensions.y) { return; }
+    let width = select(dimensions.x, IMAGE_WIDTH, IMAGE_WIDTH != 0u);
+    if (gid.x >= width || gid.y >= dimensions.y) { return; }
 
-    let idx = gid.y * dimensions.x + gid.x;
+    let idx = gid.y * width + gid.x;
     let pixel = source[idx];
 
     // Clamp RGB to [0,1] for LUT lookup.
@@ -20,8 +20,33 @@ struct GradingParamsGpu {
     working_space: u32,
     output_space: u32,
     display_oetf: u32,
-    _pad0: u32,
-    _pad1: u32,
+    use_input_lut: u32,
+    soft_clip_luma: u32,
+    input_gain: vec4<f32>,
+    input_power: vec4<f32>,
+    input_matrix_r: vec4<f32>,
+    input_matrix_g: vec4<f32>,
+    input_matrix_b: vec4<f32>,
+    input_black: vec4<f32>,
+    input_white: vec4<f32>,
+    soft_clip: vec4<f32>,
+    saturation_model: u32,
+    hue_model: u32,
+    vibrance: f32,
+    monochrome: u32,
+    hue_ranges: vec4<f32>,
+    mixer_r: vec4<f32>,
+    mixer_g: vec4<f32>,
+    mixer_b: vec4<f32>,
+    mono_weights: vec4<f32>,
+    mono_tint: vec4<f32>,
+    exposure: vec4<f32>,
+    transform_bypass: vec4<u32>,
+    white_balance_r: vec4<f32>,
+    white_balance_g: vec4<f32>,
+    white_balance_b: vec4<f32>,
+    negative_policy: vec4<u32>,
+    layer_blend: vec4<f32>,
 };
 
 @group(0) @binding(0) var lut_data: texture_storage_3d<rgba32float, write>;
@@ -35,6 +60,7 @@ struct GradingParamsGpu {
 @group(0) @binding(8) var ocio_idt_lut: texture_3d<f32>;
 @group(0) @binding(9) var ocio_odt_lut: texture_3d<f32>;
 @group(0) @binding(10) var ocio_sampler: sampler;
+@group(0) @binding(11) var input_lut: texture_3d<f32>;
 
 // ── Color space matrices (to/from CIE XYZ D65) ─────────────────────
 
@@ -122,32 +148,26 @@ fn linear_to_logc3(v: f32) -> f32 {
 }
 
 fn logc4_to_linear(v: f32) -> f32 {
-    // ARRI LogC4 (ALEXA 35)
-    let a = 2231.826309;
-    let b = 64.0;
-    let c = 0.0740718;
-    let d = 1.0;
-    let t = 0.01011722;
-    let cut_encoded = c * log2(a * t + b) + d;
-    if (v >= cut_encoded) {
-        return (pow(2.0, (v - d) / c) - b) / a;
+    // ARRI LogC4 (ALEXA 35), LogC4 Specification (2022).
+    let a = 2231.8263;
+    let b = 0.9071359;
+    let c = 0.092864126;
+    if (v < 0.0) {
+        // Linear toe: slope s and offset t derived from a, b, c.
+        return v * 0.11359721 - 0.018056996;
     }
-    let lin_slope = a * c * log(2.0) / (a * t + b);
-    return (v - cut_encoded) / lin_slope + t;
+    return (pow(2.0, 14.0 * (v - c) / b + 6.0) - 64.0) / a;
 }
 
 fn linear_to_logc4(v: f32) -> f32 {
-    let a = 2231.826309;
-    let b = 64.0;
-    let c = 0.0740718;
-    let d = 1.0;
-    let t = 0.01011722;
-    if (v >= t) {
-        return c * log2(a * v + b) + d;
+    let a = 2231.8263;
+    let b = 0.9071359;
+    let c = 0.092864126;
+    let t = -0.018056996;
+    if (v < t) {
+        return (v - t) / 0.11359721;
     }
-    let cut_encoded = c * log2(a * t + b) + d;
-    let lin_slope = a * c * log(2.0) / (a * t + b);
-    return cut_encoded + lin_slope * (v - t);
+    return (log2(a * v + 64.0) - 6.0) / 14.0 * b + c;
 }
 
 fn slog3_to_linear(v: f32) -> f32 {
@@ -170,7 +190,8 @@ fn redlog3g10_to_linear(v: f32) -> f32 {
     let a = 0.224282;
     let b = 155.975327;
     let c = 0.01;
-    if (v < 0.0) { return v / a; }
+    let g = 15.1927;
+    if (v < 0.0) { return v / g - c; }
     return (pow(10.0, v / a) - 1.0) / b - c;
 }
 
@@ -178,7 +199,8 @@ fn linear_to_redlog3g10(v: f32) -> f32 {
     let a = 0.224282;
     let b = 155.975327;
     let c = 0.01;
-    if (v < -c) { return v * a; }
+    let g = 15.1927;
+    if (v < -c) { return (v + c) * g; }
     return a * log(b * (v + c) + 1.0) / log(10.0);
 }
 
@@ -186,7 +208,9 @@ fn vlog_to_linear(v: f32) -> f32 {
     let d = 0.598206;
     let c = 0.241514;
     let b = 0.00873;
-    if (v < d) {
+    // Encoded value of the linear cut: 5.6 * 0.01 + 0.125.
+    let cut_encoded = 0.181;
+    if (v < cut_encoded) {
         return (v - 0.125) / 5.6;
     }
     return pow(10.0, (v - d) / c) - b;
@@ -206,7 +230,7 @@ fn linear_to_vlog(v: f32) -> f32 {
 // ACEScc/ACEScct use AP1 primaries with log encoding.
 fn acescc_to_linear(v: f32) -> f32 {
     if (v < -0.3013699) {
-        return (pow(2.0, v * 17.52 - 9.72) - 0.000030518) * 2.0;
+        return (pow(2.0, v * 17.52 - 9.72) - 0.0000152587890625) * 2.0;
     }
     return pow(2.0, v * 17.52 - 9.72);
 }
@@ -214,7 +238,7 @@ fn acescc_to_linear(v: f32) -> f32 {
 fn linear_to_acescc(v: f32) -> f32 {
     if (v <= 0.0) { return -0.3584475; }
     if (v < 0.000030518) {
-        return (log2(0.000030518 + v * 0.5) + 9.72) / 17.52;
+        return (log2(0.0000152587890625 + v * 0.5) + 9.72) / 17.52;
     }
     return (log2(v) + 9.72) / 17.52;
 }
@@ -346,14 +370,63 @@ fn xyz_to_gamut(v: vec3<f32>, space: u32) -> vec3<f32> {
 }
 
 fn input_transform(v: vec3<f32>, from_space: u32, to_space: u32) -> vec3<f32> {
+    // An imported input LUT, pre-baked with its domain conversions, maps
+    // source code values straight to working linear.
+    if (params.use_input_lut == 1u) {
+        let clamped = clamp(v, vec3(0.0), vec3(1.0));
+        return textureSampleLevel(input_lut, ocio_sampler, clamped, 0.0).rgb;
+    }
     if (params.use_ocio == 1u) {
         let clamped = clamp(v, vec3(0.0), vec3(1.0));
         return textureSampleLevel(ocio_idt_lut, ocio_sampler, clamped, 0.0).rgb;
     }
-    var lin = linearize(v, from_space);
-    if (from_space == to_space) { return lin; }
+    if (from_space == to_space) { return linearize(v, from_space); }
+    let lin = linearize_with_policy(v, from_space);
     let xyz = gamut_to_xyz(lin, from_space);
-    return xyz_to_gamut(xyz, to_space);
+    return clamp_negatives(xyz_to_gamut(xyz, to_space));
+}
+
+// Negative policy of the conversions — mirrors NegativePolicy in
+// crispen-core: 0 allow, 1 clamp, 2 mirror around linear black.
+fn clamp_negatives(v: vec3<f32>) -> vec3<f32> {
+    if (params.negative_policy.x == 1u) { return max(v, vec3(0.0)); }
+    return v;
+}
+
+fn linearize_with_policy(v: vec3<f32>, space: u32) -> vec3<f32> {
+    let lin = linearize(v, space);
+    if (params.negative_policy.x == 2u) {
+        let black = vec3(encode_channel(0.0, space));
+        let mirrored = -linearize(2.0 * black - v, space);
+        return select(lin, mirrored, v < black);
+    }
+    return clamp_negatives(lin);
+}
+
+// Input levels: per-channel black/white code values to 0/1, unclipped.
+fn apply_input_levels(v: vec3<f32>) -> vec3<f32> {
+    let black = params.input_black.xyz;
+    let span = max(params.input_white.xyz - black, vec3(1e-6));
+    return (v - black) / span;
+}
+
+// Channel mixer: each output a weighted mix of R, G, B plus the w offset.
+fn apply_channel_mixer(v: vec3<f32>) -> vec3<f32> {
+    let one = vec4<f32>(v, 1.0);
+    return vec3<f32>(dot(params.mixer_r, one), dot(params.mixer_g, one), dot(params.mixer_b, one));
+}
+
+// Chart-matched input correction: sign-preserving 1D gain/power, then 3x3.
+fn apply_input_correction(v: vec3<f32>) -> vec3<f32> {
+    // Clamp away from zero — pow(0, y) is undefined in WGSL; sign(v) zeroes it.
+    let mag = pow(max(abs(v), vec3(1e-10)), params.input_power.xyz);
+    let shaped = sign(v) * mag * params.input_gain.xyz;
+    return mat3_mul(
+        params.input_matrix_r.xyz,
+        params.input_matrix_g.xyz,
+        params.input_matrix_b.xyz,
+        shaped,
+    );
 }
 
 fn output_transform(v: vec3<f32>, from_space: u32, to_space: u32) -> vec3<f32> {
@@ -369,61 +442,20 @@ fn output_transform(v: vec3<f32>, from_space: u32, to_space: u32) -> vec3<f32> {
     var out = v;
     if (from_space != to_space) {
         let xyz = gamut_to_xyz(out, from_space);
-        out = xyz_to_gamut(xyz, to_space);
+        out = clamp_negatives(xyz_to_gamut(xyz, to_space));
     }
     return out;
 }
 
-// ── White balance (simplified Planckian shift via Bradford) ──────────
-
-// Bradford cone response matrix.
-const BRAD_0: vec3<f32> = vec3<f32>( 0.8951,  0.2664, -0.1614);
-const BRAD_1: vec3<f32> = vec3<f32>(-0.7502,  1.7135,  0.0367);
-const BRAD_2: vec3<f32> = vec3<f32>( 0.0389, -0.0685,  1.0296);
-const BRAD_INV_0: vec3<f32> = vec3<f32>( 0.9870, -0.1471,  0.1600);
-const BRAD_INV_1: vec3<f32> = vec3<f32>( 0.4323,  0.5184,  0.0493);
-const BRAD_INV_2: vec3<f32> = vec3<f32>(-0.0085,  0.0400,  0.9685);
-
-fn planckian_xy(kelvin: f32) -> vec2<f32> {
-    // CIE daylight locus approximation.
-    let t = kelvin;
-    let t2 = t * t;
-    let t3 = t2 * t;
-    var x: f32;
-    if (t <= 7000.0) {
-        x = -4.607e9 / t3 + 2.9678e6 / t2 + 0.09911e3 / t + 0.244063;
-    } else {
-        x = -2.0064e9 / t3 + 1.9018e6 / t2 + 0.24748e3 / t + 0.237040;
-    }
-    let y = -3.0 * x * x + 2.87 * x - 0.275;
-    return vec2<f32>(x, y);
-}
+// ── White balance ────────────────────────────────────────────────
 
-fn xy_to_xyz(xy: vec2<f32>) -> vec3<f32> {
-    return vec3<f32>(xy.x / xy.y, 1.0, (1.0 - xy.x - xy.y) / xy.y);
-}
-
-fn white_balance(v: vec3<f32>, temp: f32, tint_val: f32) -> vec3<f32> {
-    if (temp == 0.0 && tint_val == 0.0) { return v; }
-
-    // D65 reference (6500K)
-    let src_xy = planckian_xy(6500.0);
-    let dst_xy = planckian_xy(6500.0 + temp * 100.0);
-    // Apply tint as green-magenta shift on y axis.
-    let dst_xy_tinted = vec2<f32>(dst_xy.x, dst_xy.y + tint_val * 0.02);
-
-    let src_wp = xy_to_xyz(src_xy);
-    let dst_wp = xy_to_xyz(dst_xy_tinted);
-
-    let src_cone = mat3_mul(BRAD_0, BRAD_1, BRAD_2, src_wp);
-    let dst_cone = mat3_mul(BRAD_0, BRAD_1, BRAD_2, dst_wp);
-
-    let scale = dst_cone / src_cone;
-
-    // Apply: Bradford → scale → inverse Bradford
-    let cone = mat3_mul(BRAD_0, BRAD_1, BRAD_2, v);
-    let adapted = cone * scale;
-    return mat3_mul(BRAD_INV_0, BRAD_INV_1, BRAD_INV_2, adapted);
+// The 3x3 matrix comes precomputed from the CPU for either model.
+fn white_balance(v: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(
+        dot(params.white_balance_r.xyz, v),
+        dot(params.white_balance_g.xyz, v),
+        dot(params.white_balance_b.xyz, v),
+    );
 }
 
 // ── CDL (lift/gamma/gain/offset) ────────────────────────────────────
@@ -474,19 +506,177 @@ fn apply_shadows_highlights(v: vec3<f32>) -> vec3<f32> {
     return v + v * (sh * shadow_w + hi * highlight_w);
 }
 
+// ── Perceptual chroma (OKLab / JzAzBz) ──────────────────────────────
+// Same constants as crispen_core::color_management::perceptual.
+
+fn signed_pow(v: vec3<f32>, e: f32) -> vec3<f32> {
+    return sign(v) * pow(abs(v), vec3<f32>(e));
+}
+
+fn xyz_to_oklab(xyz: vec3<f32>) -> vec3<f32> {
+    let lms = mat3_mul(
+        vec3<f32>(0.8189330101, 0.3618667424, -0.1288597137),
+        vec3<f32>(0.0329845436, 0.9293118715, 0.0361456387),
+        vec3<f32>(0.0482003018, 0.2643662691, 0.6338517070),
+        xyz,
+    );
+    return mat3_mul(
+        vec3<f32>(0.2104542553, 0.7936177850, -0.0040720468),
+        vec3<f32>(1.9779984951, -2.4285922050, 0.4505937099),
+        vec3<f32>(0.0259040371, 0.7827717662, -0.8086757660),
+        signed_pow(lms, 1.0 / 3.0),
+    );
+}
+
+fn oklab_to_xyz(lab: vec3<f32>) -> vec3<f32> {
+    let lms = mat3_mul(
+        vec3<f32>(0.9999999985, 0.3963377922, 0.2158037581),
+        vec3<f32>(1.0000000089, -0.1055613423, -0.0638541748),
+        vec3<f32>(1.0000000547, -0.0894841821, -1.2914855379),
+        lab,
+    );
+    return mat3_mul(
+        vec3<f32>(1.2270138511, -0.5577999807, 0.2812561490),
+        vec3<f32>(-0.0405801784, 1.1122568696, -0.0716766787),
+        vec3<f32>(-0.0763812845, -0.4214819784, 1.5861632204),
+        lms * lms * lms,
+    );
+}
+
+const JZ_WHITE_NITS: f32 = 100.0;
+const JZ_B: f32 = 1.15;
+const JZ_G: f32 = 0.66;
+const JZ_PQ_C1: f32 = 0.8359375;
+const JZ_PQ_C2: f32 = 18.8515625;
+const JZ_PQ_C3: f32 = 18.6875;
+const JZ_PQ_N: f32 = 0.1593017578125;
+const JZ_PQ_P: f32 = 134.034375;
+
+fn jz_pq_encode(v: vec3<f32>) -> vec3<f32> {
+    let yn = pow(abs(v) / 10000.0, vec3<f32>(JZ_PQ_N));
+    return sign(v) * pow((JZ_PQ_C1 + JZ_PQ_C2 * yn) / (1.0 + JZ_PQ_C3 * yn), vec3<f32>(JZ_PQ_P));
+}
+
+fn jz_pq_decode(v: vec3<f32>) -> vec3<f32> {
+    let vp = pow(abs(v), vec3<f32>(1.0 / JZ_PQ_P));
+    let y = max((JZ_PQ_C1 - vp) / (JZ_PQ_C3 * vp - JZ_PQ_C2), vec3<f32>(0.0));
+    return sign(v) * pow(y, vec3<f32>(1.0 / JZ_PQ_N)) * 10000.0;
+}
+
+fn xyz_to_izazbz(xyz_rel: vec3<f32>) -> vec3<f32> {
+    let xyz = xyz_rel * JZ_WHITE_NITS;
+    let pre = vec3<f32>(JZ_B * xyz.x - (JZ_B - 1.0) * xyz.z, JZ_G * xyz.y - (JZ_G - 1.0) * xyz.x, xyz.z);
+    let lms = mat3_mul(
+        vec3<f32>(0.41478972, 0.579999, 0.0146480),
+        vec3<f32>(-0.2015100, 1.120649, 0.0531008),
+        vec3<f32>(-0.0166008, 0.264800, 0.6684799),
+        pre,
+    );
+    return mat3_mul(
+        vec3<f32>(0.5, 0.5, 0.0),
+        vec3<f32>(3.524000, -4.066708, 0.542708),
+        vec3<f32>(0.199076, 1.096799, -1.295875),
+        jz_pq_encode(lms),
+    );
+}
+
+fn izazbz_to_xyz(iab: vec3<f32>) -> vec3<f32> {
+    let lms = jz_pq_decode(mat3_mul(
+        vec3<f32>(1.0, 0.1386050433, 0.0580473162),
+        vec3<f32>(1.0, -0.1386050433, -0.0580473162),
+        vec3<f32>(1.0, -0.0960192420, -0.8118918961),
+        iab,
+    ));
+    let pre = mat3_mul(
+        vec3<f32>(1.9242264358, -1.0047923126, 0.0376514040),
+        vec3<f32>(0.3503167621, 0.7264811939, -0.0653844229),
+        vec3<f32>(-0.0909828110, -0.3127282905, 1.5227665613),
+        lms,
+    );
+    let x = (pre.x + (JZ_B - 1.0) * pre.z) / JZ_B;
+    let y = (pre.y + (JZ_G - 1.0) * x) / JZ_G;
+    return vec3<f32>(x, y, pre.z) / JZ_WHITE_NITS;
+}
+
+fn to_opponent(xyz: vec3<f32>) -> vec3<f32> {
+    if (params.saturation_model == 1u) { return xyz_to_oklab(xyz); }
+    return xyz_to_izazbz(xyz);
+}
+
+fn from_opponent(v: vec3<f32>) -> vec3<f32> {
+    if (params.saturation_model == 1u) { return oklab_to_xyz(v); }
+    return izazbz_to_xyz(v);
+}
+
+// Scale chroma by `factor` in the selected model, away from the gray of
+// the same luminance. Mirrors crispen_core::grading::sliders::scale_chroma.
+fn scale_chroma(v: vec3<f32>, factor: f32) -> vec3<f32> {
+    if (params.saturation_model == 0u) {
+        let l = dot(v, LUMA_709);
+        return mix(vec3<f32>(l, l, l), v, factor);
+    }
+    let xyz = gamut_to_xyz(v, params.working_space);
+    let gray = to_opponent(gamut_to_xyz(vec3<f32>(xyz.y), params.working_space));
+    let lab = to_opponent(xyz);
+    let ab = gray.yz + (lab.yz - gray.yz) * factor;
+    return xyz_to_gamut(from_opponent(vec3<f32>(lab.x, ab)), params.working_space);
+}
+
+// Rotate the OKLCh hue around the gray of the same luminance. Mirrors
+// crispen_core::grading::sliders::rotate_hue_oklch.
+fn rotate_hue_oklch(v: vec3<f32>, degrees: f32) -> vec3<f32> {
+    let xyz = gamut_to_xyz(v, params.working_space);
+    let gray = xyz_to_oklab(gamut_to_xyz(vec3<f32>(xyz.y), params.working_space));
+    let lab = xyz_to_oklab(xyz);
+    let rad = degrees * 3.14159265358979 / 180.0;
+    let ab = lab.yz - gray.yz;
+    let rotated = vec2<f32>(ab.x * cos(rad) - ab.y * sin(rad), ab.x * sin(rad) + ab.y * cos(rad));
+    return xyz_to_gamut(oklab_to_xyz(vec3<f32>(lab.x, gray.yz + rotated)), params.working_space);
+}
+
+// Shadow, midtone, and highlight weights summing to 1.
+fn tonal_weights(luma: f32) -> vec3<f32> {
+    let shadows = 1.0 - smoothstep(0.0, 0.5, luma);
+    let highlights = smoothstep(0.5, 1.0, luma);
+    return vec3<f32>(shadows, 1.0 - shadows - highlights, highlights);
+}
+
+// Vibrance: chroma gain that favors muted colors and spares a skin-tone
+// hue wedge. Mirrors crispen_core::grading::sliders::apply_vibrance.
+const SKIN_HUE: f32 = 25.0;
+const SKIN_HALF_WIDTH: f32 = 25.0;
+
+fn apply_vibrance(v: vec3<f32>) -> vec3<f32> {
+    if (params.vibrance == 0.0) { return v; }
+    let mx = max(v.x, max(v.y, v.z));
+    let mn = min(v.x, min(v.y, v.z));
+    if (mx <= 1e-6) { return v; }
+    let sat = clamp((mx - mn) / mx, 0.0, 1.0);
+    let hue = rgb_to_hsl(v).x * 360.0;
+    let d = hue - SKIN_HUE + 180.0;
+    let distance = abs(d - 360.0 * floor(d / 360.0) - 180.0);
+    let skin = 1.0 - smoothstep(0.0, SKIN_HALF_WIDTH, distance);
+    return scale_chroma(v, 1.0 + params.vibrance * (1.0 - sat) * (1.0 - skin));
+}
+
 // ── Saturation + Hue rotation + Luma mix ────────────────────────────
 
 fn apply_saturation_hue(v: vec3<f32>) -> vec3<f32> {
     let sat = params.saturation;
-    let hue_d = params.hue_deg;
     let lm = params.luma_mix;
 
     let luma = dot(v, LUMA_709);
-    // Saturation: lerp toward monochrome.
-    var r = mix(vec3<f32>(luma, luma, luma), v, sat);
-
-    // Hue rotation around the (1,1,1) axis via Rodrigues' formula.
-    if (hue_d != 0.0) {
+    let hue_d = params.hue_deg + dot(tonal_weights(luma), params.hue_ranges.xyz);
+    let oklch = params.hue_model == 1u;
+    // Saturation: lerp toward monochrome; perceptual models and OKLCh hue
+    // scale chroma after the hue rotation instead.
+    let perceptual = params.saturation_model != 0u;
+    var r = mix(vec3<f32>(luma, luma, luma), v, select(sat, 1.0, perceptual || oklch));
+
+    if (oklch && hue_d != 0.0) {
+        r = rotate_hue_oklch(r, hue_d);
+    } else if (hue_d != 0.0) {
+        // Hue rotation around the (1,1,1) axis via Rodrigues' formula.
         let rad = hue_d * 3.14159265358979 / 180.0;
         let cos_a = cos(rad);
         let sin_a = sin(rad);
@@ -496,6 +686,14 @@ fn apply_saturation_hue(v: vec3<f32>) -> vec3<f32> {
         r = r * cos_a + cross_kr * sin_a + k * d * (1.0 - cos_a);
     }
 
+    if (perceptual) {
+        return scale_chroma(r, sat);
+    }
+    if (oklch) {
+        let l = dot(r, LUMA_709);
+        r = mix(vec3<f32>(l, l, l), r, sat);
+    }
+
     // Luma mix: blend between chroma-preserving and luma-preserving versions.
     if (lm != 0.0) {
         let new_luma = dot(r, LUMA_709);
@@ -527,6 +725,68 @@ fn rgb_to_hsl(rgb: vec3<f32>) -> vec3<f32> {
     return vec3<f32>(h, s, l);
 }
 
+// Black and white: weighted gray, response curve around 18% gray, tint gain;
+// same as crispen_core::grading::monochrome.
+fn apply_monochrome(v: vec3<f32>) -> vec3<f32> {
+    if (params.monochrome == 0u) { return v; }
+    var y = dot(v, params.mono_weights.xyz);
+    let contrast = params.mono_weights.w;
+    if (y > 0.0 && contrast != 1.0) {
+        y = 0.18 * pow(y / 0.18, contrast);
+    }
+    return y * params.mono_tint.xyz;
+}
+
+// The grade's result blended over its input by mode (normal, multiply,
+// overlay, color), then mixed by the key output gain; same as
+// crispen_core::grading::blend.
+fn apply_layer_blend(base: vec3<f32>, layer: vec3<f32>) -> vec3<f32> {
+    let mode = u32(params.layer_blend.y);
+    if (mode == 0u && params.layer_blend.x >= 1.0) { return layer; }
+    var blended = layer;
+    if (mode == 1u) {
+        blended = base * layer;
+    } else if (mode == 2u) {
+        blended = select(
+            1.0 - 2.0 * (1.0 - base) * (1.0 - layer),
+            2.0 * base * layer,
+            base < vec3<f32>(0.5)
+        );
+    } else if (mode == 3u) {
+        blended = layer + (dot(base, LUMA_709) - dot(layer, LUMA_709));
+    }
+    let amount = clamp(params.layer_blend.x, 0.0, 1.0);
+    return base + (blended - base) * amount;
+}
+
+// Rational rolloff above the knee and mirrored toe below the toe point;
+// same formula as crispen_core::grading::soft_clip.
+fn soft_clip_curve(v: f32) -> f32 {
+    let knee = clamp(params.soft_clip.x, 0.0, 1.0 - 1e-4);
+    let strength = params.soft_clip.y;
+    let toe = clamp(params.soft_clip.z, 1e-4, knee);
+    let toe_strength = params.soft_clip.w;
+    if (v > knee && strength > 0.0) {
+        let d = v - knee;
+        return knee + d / (1.0 + strength * d / (1.0 - knee));
+    }
+    if (v < toe && toe_strength > 0.0) {
+        let d = toe - v;
+        return toe - d / (1.0 + toe_strength * d / toe);
+    }
+    return v;
+}
+
+fn apply_soft_clip(v: vec3<f32>) -> vec3<f32> {
+    if (params.soft_clip.y <= 0.0 && params.soft_clip.w <= 0.0) { return v; }
+    if (params.soft_clip_luma == 1u) {
+        let luma = dot(v, LUMA_709);
+        if (luma <= 1e-6) { return v; }
+        return v * (soft_clip_curve(luma) / luma);
+    }
+    return vec3<f32>(soft_clip_curve(v.x), soft_clip_curve(v.y), soft_clip_curve(v.z));
+}
+
 fn apply_curves(v: vec3<f32>) -> vec3<f32> {
     let hsl = rgb_to_hsl(clamp(v, vec3<f32>(0.0), vec3<f32>(1.0)));
     let h = hsl.x;
@@ -557,8 +817,7 @@ fn apply_curves(v: vec3<f32>) -> vec3<f32> {
     }
     // Apply saturation adjustment from curves.
     if (sat_mult != 1.0) {
-        let l = dot(r, LUMA_709);
-        r = mix(vec3<f32>(l, l, l), r, sat_mult);
+        r = scale_chroma(r, sat_mult);
     }
     return r;
 }
@@ -576,14 +835,28 @@ fn bake_lut(@builtin(global_invocation_id) gid: vec3<u32>) {
     var c = vec3<f32>(r, g, b);
 
     // Full grading chain — mirrors evaluate_transform() exactly.
-    c = input_transform(c, params.input_space, params.working_space);
-    c = white_balance(c, params.temperature, params.tint);
+    c = apply_input_levels(c);
+    c = c + params.exposure.y;
+    if (params.transform_bypass.x == 0u) {
+        c = input_transform(c, params.input_space, params.working_space);
+    }
+    let layer_base = c;
+    c = c * params.exposure.x;
+    c = apply_input_correction(c);
+    c = white_balance(c);
+    c = apply_channel_mixer(c);
     c = apply_cdl(c);
     c = apply_contrast(c);
     c = apply_shadows_highlights(c);
+    c = apply_vibrance(c);
     c = apply_saturation_hue(c);
     c = apply_curves(c);
-    c = output_transform(c, params.working_space, params.output_space);
+    c = apply_monochrome(c);
+    c = apply_soft_clip(c);
+    c = apply_layer_blend(layer_base, c);
+    if (params.transform_bypass.y == 0u) {
+        c = output_transform(c, params.working_space, params.output_space);
+    }
 
     textureStore(
         lut_data,
@@ -0,0 +1,53 @@
+// chromatic_aberration.wgsl — Radial red/blue realignment and fringing.
+//
+// Mirrors `crispen_core::grading::chromatic_aberration`. Red and blue are
+// sampled bilinearly at `c + (p − c) / magnification`; green and alpha are
+// copied from the source.
+
+struct CaParams {
+    width: u32,
+    height: u32,
+    red_scale: f32,
+    blue_scale: f32,
+    // fringe · FRINGE_SHIFT, scaled by the normalized squared radius.
+    fringe: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: CaParams;
+@group(0) @binding(1) var<storage, read> source: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> output: array<vec4<f32>>;
+
+fn at(x: u32, y: u32) -> vec4<f32> {
+    return source[y * params.width + x];
+}
+
+// Bilinear sample at continuous position `pos`, edges clamped.
+fn sample(pos: vec2<f32>) -> vec4<f32> {
+    let last = vec2<f32>(f32(params.width - 1u), f32(params.height - 1u));
+    let f = clamp(pos - 0.5, vec2<f32>(0.0), last);
+    let p0 = vec2<u32>(floor(f));
+    let p1 = min(p0 + 1u, vec2<u32>(params.width - 1u, params.height - 1u));
+    let t = f - floor(f);
+    let top = mix(at(p0.x, p0.y), at(p1.x, p0.y), t.x);
+    let bottom = mix(at(p0.x, p1.y), at(p1.x, p1.y), t.x);
+    return mix(top, bottom, t.y);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height) { return; }
+    let center = vec2<f32>(f32(params.width), f32(params.height)) * 0.5;
+    let d = vec2<f32>(gid.xy) + 0.5 - center;
+    let r2 = dot(d, d) / dot(center, center);
+    let mag_red = 1.0 + params.red_scale + params.fringe * r2;
+    let mag_blue = 1.0 + params.blue_scale - params.fringe * r2;
+
+    let i = gid.y * params.width + gid.x;
+    let own = source[i];
+    let red = sample(center + d / mag_red).x;
+    let blue = sample(center + d / mag_blue).z;
+    output[i] = vec4<f32>(red, own.y, blue, own.w);
+}
@@ -0,0 +1,104 @@
+// dehaze.wgsl — Dark-channel-prior haze removal on the source image.
+//
+// Mirrors `crispen_core::grading::dehaze::apply_dehaze`. Dispatched once per
+// entry point, in order:
+//   dark_rows  — min(R, G, B), minimum over each row window   → dark_a
+//   dark_cols  — minimum over each column window, haze level  → dark_b, airlight
+//   blur_rows  — box blur over each row window                → dark_a
+//   blur_cols  — box blur over each column window             → dark_b
+//   recover    — (in − A) / t + A                             → output
+// The airlight is a running atomicMax on the f32 bits; non-negative floats
+// order the same as their bit patterns, and the caller zeroes it first.
+
+struct DehazeParams {
+    width: u32,
+    height: u32,
+    strength: f32,
+    // 2^depth_bias.
+    haze_exponent: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: DehazeParams;
+@group(0) @binding(1) var<storage, read> source: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> output: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read_write> dark_a: array<f32>;
+@group(0) @binding(4) var<storage, read_write> dark_b: array<f32>;
+@group(0) @binding(5) var<storage, read_write> airlight: atomic<u32>;
+
+// `PATCH_RADIUS`, `MIN_TRANSMISSION`, and `MIN_AIRLIGHT` in
+// `crispen_core::grading::dehaze`.
+const RADIUS: i32 = 7;
+const MIN_TRANSMISSION: f32 = 0.1;
+const MIN_AIRLIGHT: f32 = 0.05;
+
+fn min_rgb(i: u32) -> f32 {
+    let p = source[i];
+    return max(min(min(p.x, p.y), p.z), 0.0);
+}
+
+// Index of the pixel `d` steps from `gid` along a row or column, clamped
+// to the image.
+fn offset_index(gid: vec3<u32>, d: i32, horizontal: bool) -> u32 {
+    if (horizontal) {
+        let x = u32(clamp(i32(gid.x) + d, 0, i32(params.width) - 1));
+        return gid.y * params.width + x;
+    }
+    let y = u32(clamp(i32(gid.y) + d, 0, i32(params.height) - 1));
+    return y * params.width + gid.x;
+}
+
+fn outside(gid: vec3<u32>) -> bool {
+    return gid.x >= params.width || gid.y >= params.height;
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn dark_rows(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    var m = 3.4e38;
+    for (var d = -RADIUS; d <= RADIUS; d = d + 1) {
+        m = min(m, min_rgb(offset_index(gid, d, true)));
+    }
+    dark_a[gid.y * params.width + gid.x] = m;
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn dark_cols(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    var m = 3.4e38;
+    for (var d = -RADIUS; d <= RADIUS; d = d + 1) {
+        m = min(m, dark_a[offset_index(gid, d, false)]);
+    }
+    dark_b[gid.y * params.width + gid.x] = m;
+    atomicMax(&airlight, bitcast<u32>(m));
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn blur_rows(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    var sum = 0.0;
+    for (var d = -RADIUS; d <= RADIUS; d = d + 1) {
+        sum = sum + dark_b[offset_index(gid, d, true)];
+    }
+    dark_a[gid.y * params.width + gid.x] = sum / f32(2 * RADIUS + 1);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn blur_cols(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    var sum = 0.0;
+    for (var d = -RADIUS; d <= RADIUS; d = d + 1) {
+        sum = sum + dark_a[offset_index(gid, d, false)];
+    }
+    dark_b[gid.y * params.width + gid.x] = sum / f32(2 * RADIUS + 1);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn recover(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    let i = gid.y * params.width + gid.x;
+    let a = max(bitcast<f32>(atomicLoad(&airlight)), MIN_AIRLIGHT);
+    let haze = pow(clamp(dark_b[i] / a, 0.0, 1.0), params.haze_exponent);
+    let t = max(1.0 - params.strength * haze, MIN_TRANSMISSION);
+    let p = source[i];
+    output[i] = vec4<f32>((p.xyz - vec3<f32>(a)) / t + vec3<f32>(a), p.w);
+}
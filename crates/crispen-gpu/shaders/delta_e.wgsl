@@ -0,0 +1,158 @@
+// delta_e.wgsl — Per-pixel CIEDE2000 between two graded images and its heatmap.
+//
+// Mirrors `crispen_core::transform::compare`: both images hold code values
+// in the output space, decoded through a table of the output transfer,
+// taken to XYZ with the output gamut's matrix, then to CIELAB (D65).
+// Computed in f32, so values differ slightly from the f64 CPU reference.
+
+struct CompareParams {
+    width: u32,
+    height: u32,
+    // 1 when the output space has a transfer to decode through `decode`.
+    encoded: u32,
+    // Heatmap ΔE at which the ramp reaches its last color.
+    scale: f32,
+    // Rows of the output gamut's RGB -> XYZ matrix (w unused).
+    to_xyz_0: vec4<f32>,
+    to_xyz_1: vec4<f32>,
+    to_xyz_2: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> params: CompareParams;
+@group(0) @binding(1) var<storage, read> graded: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read> reference: array<vec4<f32>>;
+// Output transfer `to_linear` sampled uniformly over code values 0..1.
+@group(0) @binding(3) var<storage, read> decode: array<f32>;
+@group(0) @binding(4) var<storage, read_write> delta_e: array<f32>;
+// One packed RGBA8 (sRGB-encoded) heatmap pixel per u32.
+@group(0) @binding(5) var<storage, read_write> heatmap: array<u32>;
+
+const PI: f32 = 3.14159265358979;
+const TAU: f32 = 6.28318530717959;
+const D65_WHITE: vec3<f32> = vec3<f32>(0.95047, 1.0, 1.08883);
+const LAB_EPS: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+const POW25_7: f32 = 6103515625.0;
+
+// Heatmap ramp, matching `HEATMAP_STOPS`, `HEATMAP_OPAQUE_AT`, and
+// `HEATMAP_MAX_ALPHA` in `crispen_core::transform::compare`.
+const STOP_0: vec3<f32> = vec3<f32>(0.10, 0.20, 0.90);
+const STOP_1: vec3<f32> = vec3<f32>(0.10, 0.85, 0.30);
+const STOP_2: vec3<f32> = vec3<f32>(0.95, 0.90, 0.10);
+const STOP_3: vec3<f32> = vec3<f32>(0.95, 0.15, 0.10);
+const OPAQUE_AT: f32 = 1.0;
+const MAX_ALPHA: f32 = 220.0 / 255.0;
+
+fn decode_channel(v: f32) -> f32 {
+    if (params.encoded == 0u) { return v; }
+    let last = arrayLength(&decode) - 1u;
+    let x = clamp(v, 0.0, 1.0) * f32(last);
+    let i = min(u32(x), last - 1u);
+    let f = x - f32(i);
+    return mix(decode[i], decode[i + 1u], f);
+}
+
+fn lab_f(t: f32) -> f32 {
+    if (t > LAB_EPS) { return pow(t, 1.0 / 3.0); }
+    return (LAB_KAPPA * t + 16.0) / 116.0;
+}
+
+fn to_lab(code: vec4<f32>) -> vec3<f32> {
+    let rgb = vec3<f32>(
+        decode_channel(code.r),
+        decode_channel(code.g),
+        decode_channel(code.b),
+    );
+    let xyz = vec3<f32>(
+        dot(params.to_xyz_0.xyz, rgb),
+        dot(params.to_xyz_1.xyz, rgb),
+        dot(params.to_xyz_2.xyz, rgb),
+    ) / D65_WHITE;
+    // The linear segment below LAB_EPS also covers negative values.
+    let f = vec3<f32>(lab_f(xyz.x), lab_f(xyz.y), lab_f(xyz.z));
+    return vec3<f32>(116.0 * f.y - 16.0, 500.0 * (f.x - f.y), 200.0 * (f.y - f.z));
+}
+
+fn hue_angle(b: f32, a: f32) -> f32 {
+    if (a == 0.0 && b == 0.0) { return 0.0; }
+    let h = atan2(b, a);
+    return select(h, h + TAU, h < 0.0);
+}
+
+fn ciede2000(lab1: vec3<f32>, lab2: vec3<f32>) -> f32 {
+    let c1 = length(lab1.yz);
+    let c2 = length(lab2.yz);
+    let c_bar7 = pow((c1 + c2) * 0.5, 7.0);
+    let g = 0.5 * (1.0 - sqrt(c_bar7 / (c_bar7 + POW25_7)));
+
+    let a1p = (1.0 + g) * lab1.y;
+    let a2p = (1.0 + g) * lab2.y;
+    let c1p = length(vec2<f32>(a1p, lab1.z));
+    let c2p = length(vec2<f32>(a2p, lab2.z));
+    let h1p = hue_angle(lab1.z, a1p);
+    let h2p = hue_angle(lab2.z, a2p);
+
+    let dlp = lab2.x - lab1.x;
+    let dcp = c2p - c1p;
+    var dhp = 0.0;
+    if (c1p * c2p != 0.0) {
+        dhp = h2p - h1p;
+        if (dhp > PI) { dhp = dhp - TAU; } else if (dhp < -PI) { dhp = dhp + TAU; }
+    }
+    let dhp_big = 2.0 * sqrt(c1p * c2p) * sin(dhp * 0.5);
+
+    let l_bar_p = (lab1.x + lab2.x) * 0.5;
+    let c_bar_p = (c1p + c2p) * 0.5;
+    var h_bar_p = h1p + h2p;
+    if (c1p * c2p != 0.0) {
+        if (abs(h1p - h2p) <= PI) {
+            h_bar_p = (h1p + h2p) * 0.5;
+        } else if (h1p + h2p < TAU) {
+            h_bar_p = (h1p + h2p + TAU) * 0.5;
+        } else {
+            h_bar_p = (h1p + h2p - TAU) * 0.5;
+        }
+    }
+
+    let t = 1.0 - 0.17 * cos(h_bar_p - radians(30.0))
+        + 0.24 * cos(2.0 * h_bar_p)
+        + 0.32 * cos(3.0 * h_bar_p + radians(6.0))
+        - 0.20 * cos(4.0 * h_bar_p - radians(63.0));
+    let hd = (degrees(h_bar_p) - 275.0) / 25.0;
+    let d_theta = radians(30.0) * exp(-hd * hd);
+    let c_bar_p7 = pow(c_bar_p, 7.0);
+    let r_c = 2.0 * sqrt(c_bar_p7 / (c_bar_p7 + POW25_7));
+    let l50 = (l_bar_p - 50.0) * (l_bar_p - 50.0);
+    let s_l = 1.0 + 0.015 * l50 / sqrt(20.0 + l50);
+    let s_c = 1.0 + 0.045 * c_bar_p;
+    let s_h = 1.0 + 0.015 * c_bar_p * t;
+    let r_t = -sin(2.0 * d_theta) * r_c;
+
+    let tl = dlp / s_l;
+    let tc = dcp / s_c;
+    let th = dhp_big / s_h;
+    return sqrt(max(tl * tl + tc * tc + th * th + r_t * tc * th, 0.0));
+}
+
+fn heatmap_color(de: f32) -> vec4<f32> {
+    let t = clamp(de / max(params.scale, 1.1920929e-7), 0.0, 1.0) * 3.0;
+    let i = min(u32(t), 2u);
+    let f = t - f32(i);
+    var rgb: vec3<f32>;
+    switch i {
+        case 0u: { rgb = mix(STOP_0, STOP_1, f); }
+        case 1u: { rgb = mix(STOP_1, STOP_2, f); }
+        default: { rgb = mix(STOP_2, STOP_3, f); }
+    }
+    return vec4<f32>(rgb, clamp(de / OPAQUE_AT, 0.0, 1.0) * MAX_ALPHA);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn compare_delta_e(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height) { return; }
+    let idx = gid.y * params.width + gid.x;
+
+    let de = ciede2000(to_lab(graded[idx]), to_lab(reference[idx]));
+    delta_e[idx] = de;
+    heatmap[idx] = pack4x8unorm(heatmap_color(de));
+}
@@ -0,0 +1,100 @@
+// glow.wgsl — Post-grade glow over a Gaussian-like pyramid.
+//
+// Mirrors `crispen_core::grading::glow::apply_glow`. Each dispatch reads
+// `src` and writes `dst`, sized by its own `GlowStep`:
+//   bright_down — bright pass of the graded image, downsampled  → level 0
+//   down        — level i downsampled                           → level i + 1
+//   up          — level i += spread · upsampled level i + 1     (coarsest first)
+//   composite   — graded image += scale · tint · upsampled level 0
+
+struct GlowStep {
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    threshold: f32,
+    spread: f32,
+    // intensity / Σ spreadᵏ, used by `composite`.
+    scale: f32,
+    _pad: f32,
+    // RGB tint (w unused).
+    tint: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> step: GlowStep;
+@group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;
+
+const LUMA: vec3<f32> = vec3<f32>(0.2126, 0.7152, 0.0722);
+// `DOWN_WEIGHTS` in `crispen_core::grading::glow`.
+const DOWN_WEIGHTS: array<f32, 4> = array<f32, 4>(0.125, 0.375, 0.375, 0.125);
+
+fn src_at(x: i32, y: i32) -> vec3<f32> {
+    let cx = clamp(x, 0, i32(step.src_width) - 1);
+    let cy = clamp(y, 0, i32(step.src_height) - 1);
+    return src[u32(cy) * step.src_width + u32(cx)].xyz;
+}
+
+fn bright(rgb: vec3<f32>) -> vec3<f32> {
+    let luma = dot(rgb, LUMA);
+    return rgb * (max(luma - step.threshold, 0.0) / max(luma, 1e-4));
+}
+
+fn downsample(gid: vec3<u32>, bright_pass: bool) -> vec3<f32> {
+    var sum = vec3<f32>(0.0);
+    for (var j = 0; j < 4; j = j + 1) {
+        for (var i = 0; i < 4; i = i + 1) {
+            var s = src_at(2 * i32(gid.x) - 1 + i, 2 * i32(gid.y) - 1 + j);
+            if (bright_pass) { s = bright(s); }
+            sum = sum + DOWN_WEIGHTS[i] * DOWN_WEIGHTS[j] * s;
+        }
+    }
+    return sum;
+}
+
+// Bilinear sample of `src` for destination pixel `gid`.
+fn upsample(gid: vec3<u32>) -> vec3<f32> {
+    let scale = vec2<f32>(
+        f32(step.src_width) / f32(step.dst_width),
+        f32(step.src_height) / f32(step.dst_height),
+    );
+    let f = max((vec2<f32>(gid.xy) + 0.5) * scale - 0.5, vec2<f32>(0.0));
+    let p = floor(f);
+    let t = f - p;
+    let x0 = i32(p.x);
+    let y0 = i32(p.y);
+    let top = mix(src_at(x0, y0), src_at(x0 + 1, y0), t.x);
+    let bottom = mix(src_at(x0, y0 + 1), src_at(x0 + 1, y0 + 1), t.x);
+    return mix(top, bottom, t.y);
+}
+
+fn outside(gid: vec3<u32>) -> bool {
+    return gid.x >= step.dst_width || gid.y >= step.dst_height;
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn bright_down(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    dst[gid.y * step.dst_width + gid.x] = vec4<f32>(downsample(gid, true), 0.0);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn down(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    dst[gid.y * step.dst_width + gid.x] = vec4<f32>(downsample(gid, false), 0.0);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn up(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    let i = gid.y * step.dst_width + gid.x;
+    dst[i] = vec4<f32>(dst[i].xyz + step.spread * upsample(gid), 0.0);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn composite(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (outside(gid)) { return; }
+    let i = gid.y * step.dst_width + gid.x;
+    let glow = step.scale * step.tint.xyz * upsample(gid);
+    dst[i] = vec4<f32>(dst[i].xyz + glow, dst[i].w);
+}
@@ -4,7 +4,11 @@
 @group(0) @binding(1) var<storage, read_write> bins: array<atomic<u32>>;
 @group(0) @binding(2) var<uniform> pixel_count: u32;
 @group(0) @binding(3) var<storage, read> mask: array<u32>;
-@group(0) @binding(4) var<uniform> mask_active: u32;
+// Only every `sample_stride`-th pixel is accumulated.
+@group(0) @binding(4) var<uniform> sample_stride: u32;
+
+// Baked into each pipeline variant (see `specialization.rs`).
+override MASK_ACTIVE: bool = false;
 
 // Layout: bins[0..255] = R, bins[256..511] = G, bins[512..767] = B, bins[768..1023] = Luma.
 var<workgroup> local_bins: array<atomic<u32>, 1024>;
@@ -22,9 +26,9 @@ fn histogram(
     }
     workgroupBarrier();
 
-    let thread_id = gid.x;
+    let thread_id = gid.x * sample_stride;
     if (thread_id < pixel_count) {
-        if (mask_active != 0u && mask[thread_id] == 0u) {
+        if (MASK_ACTIVE && mask[thread_id] == 0u) {
             // Skip pixels outside the scope mask.
         } else {
         let pixel = pixels[thread_id];
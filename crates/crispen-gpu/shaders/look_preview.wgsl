@@ -0,0 +1,52 @@
+// look_preview.wgsl — Grade one proxy image through many small LUTs at once.
+//
+// Every look's preview LUT is packed back to back in `luts`; workgroup z
+// picks the look, so one dispatch renders the whole thumbnail grid.
+// Trilinear lookup mirrors `Lut3D::apply` over a 0..1 domain, and the
+// result is packed to RGBA8 like `crispen_core::transform::look_preview::render_thumbnail`.
+
+struct PreviewParams {
+    width: u32,
+    height: u32,
+    // Grid size per axis of every preview LUT.
+    lut_size: u32,
+    // Number of looks in `luts`.
+    count: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: PreviewParams;
+@group(0) @binding(1) var<storage, read> proxy: array<vec4<f32>>;
+// `count` LUTs of `lut_size`³ entries, red fastest.
+@group(0) @binding(2) var<storage, read> luts: array<vec4<f32>>;
+// `count` thumbnails of `width * height` packed RGBA8 pixels.
+@group(0) @binding(3) var<storage, read_write> thumbnails: array<u32>;
+
+fn lut_entry(base: u32, r: u32, g: u32, b: u32) -> vec3<f32> {
+    let n = params.lut_size;
+    return luts[base + (b * n + g) * n + r].xyz;
+}
+
+fn sample_lut(base: u32, rgb: vec3<f32>) -> vec3<f32> {
+    let last = params.lut_size - 1u;
+    let p = clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)) * f32(last);
+    let i = min(vec3<u32>(p), vec3<u32>(last - 1u));
+    let f = p - vec3<f32>(i);
+
+    let c00 = mix(lut_entry(base, i.x, i.y, i.z), lut_entry(base, i.x + 1u, i.y, i.z), f.x);
+    let c10 = mix(lut_entry(base, i.x, i.y + 1u, i.z), lut_entry(base, i.x + 1u, i.y + 1u, i.z), f.x);
+    let c01 = mix(lut_entry(base, i.x, i.y, i.z + 1u), lut_entry(base, i.x + 1u, i.y, i.z + 1u), f.x);
+    let c11 = mix(lut_entry(base, i.x, i.y + 1u, i.z + 1u), lut_entry(base, i.x + 1u, i.y + 1u, i.z + 1u), f.x);
+    return mix(mix(c00, c10, f.y), mix(c01, c11, f.y), f.z);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn render_previews(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height || gid.z >= params.count) { return; }
+    let pixels = params.width * params.height;
+    let idx = gid.y * params.width + gid.x;
+
+    let source = proxy[idx];
+    let base = gid.z * params.lut_size * params.lut_size * params.lut_size;
+    let graded = sample_lut(base, source.xyz);
+    thumbnails[gid.z * pixels + idx] = pack4x8unorm(vec4<f32>(graded, source.w));
+}
@@ -0,0 +1,85 @@
+// pixel_repair.wgsl — Dead-pixel and dust inpainting on the source image.
+//
+// Mirrors `crispen_core::grading::pixel_repair::repair_pixels`. Unmasked
+// pixels are copied; a masked pixel is interpolated between the nearest
+// clean pixels each way along four axes, weighted by 1 / span².
+// The mask is one bit per pixel, row-major, 32 pixels per word.
+
+struct RepairParams {
+    width: u32,
+    height: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: RepairParams;
+@group(0) @binding(1) var<storage, read> source: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> output: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> mask: array<u32>;
+
+// `MAX_SEARCH_RADIUS` in `crispen_core::grading::pixel_repair`.
+const MAX_SEARCH: i32 = 32;
+
+fn masked(i: u32) -> bool {
+    return (mask[i >> 5u] & (1u << (i & 31u))) != 0u;
+}
+
+// Nearest clean pixel from `p` along `dir`: rgb and distance in steps in
+// w, or w = 0 when there is none in reach.
+fn nearest(p: vec2<i32>, dir: vec2<i32>) -> vec4<f32> {
+    for (var step = 1; step <= MAX_SEARCH; step = step + 1) {
+        let s = p + dir * step;
+        if (s.x < 0 || s.y < 0 || s.x >= i32(params.width) || s.y >= i32(params.height)) {
+            return vec4<f32>(0.0);
+        }
+        let j = u32(s.y) * params.width + u32(s.x);
+        if (!masked(j)) {
+            return vec4<f32>(source[j].xyz, f32(step));
+        }
+    }
+    return vec4<f32>(0.0);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height) { return; }
+    let i = gid.y * params.width + gid.x;
+    let own = source[i];
+    if (!masked(i)) {
+        output[i] = own;
+        return;
+    }
+
+    let axes = array<vec2<i32>, 4>(
+        vec2<i32>(1, 0), vec2<i32>(0, 1), vec2<i32>(1, 1), vec2<i32>(1, -1),
+    );
+    let p = vec2<i32>(gid.xy);
+    var sum = vec3<f32>(0.0);
+    var weight = 0.0;
+    for (var k = 0; k < 4; k = k + 1) {
+        let a = nearest(p, axes[k]);
+        let b = nearest(p, -axes[k]);
+        var value: vec3<f32>;
+        var span: f32;
+        if (a.w > 0.0 && b.w > 0.0) {
+            value = (a.xyz * b.w + b.xyz * a.w) / (a.w + b.w);
+            span = a.w + b.w;
+        } else if (a.w > 0.0) {
+            value = a.xyz;
+            span = 2.0 * a.w;
+        } else if (b.w > 0.0) {
+            value = b.xyz;
+            span = 2.0 * b.w;
+        } else {
+            continue;
+        }
+        let w = 1.0 / (span * span);
+        sum = sum + w * value;
+        weight = weight + w;
+    }
+    if (weight > 0.0) {
+        output[i] = vec4<f32>(sum / weight, own.w);
+    } else {
+        output[i] = own;
+    }
+}
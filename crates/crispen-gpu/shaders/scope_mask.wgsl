@@ -0,0 +1,84 @@
+// scope_mask.wgsl — Rasterize the scope mask polygon and its viewer overlay.
+//
+// Vertices are normalized image coordinates (0..1, top-left origin); the
+// polygon closes back to its first vertex. Coverage uses the even-odd rule
+// at pixel centers, counting an edge when the scanline lies in
+// [min_y, max_y).
+
+struct MaskParams {
+    width: u32,
+    height: u32,
+    vertex_count: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: MaskParams;
+@group(0) @binding(1) var<storage, read> vertices: array<vec2<f32>>;
+// `rasterize_mask`: one u32 per pixel (1 = inside).
+// `render_overlay`: one packed RGBA8 pixel per u32.
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+// Overlay colors, matching the former CPU overlay.
+const SHADE: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 100.0 / 255.0);
+const OUTLINE: vec4<f32> = vec4<f32>(0.0, 200.0 / 255.0, 1.0, 220.0 / 255.0);
+// Half the outline width in overlay pixels.
+const OUTLINE_HALF_WIDTH: f32 = 1.5;
+
+fn inside(p: vec2<f32>) -> bool {
+    let n = params.vertex_count;
+    if (n < 3u) { return false; }
+    var crossings = 0u;
+    for (var i = 0u; i < n; i = i + 1u) {
+        let a = vertices[i];
+        let b = vertices[(i + 1u) % n];
+        if ((a.y <= p.y) != (b.y <= p.y)) {
+            let t = (p.y - a.y) / (b.y - a.y);
+            if (p.x < a.x + t * (b.x - a.x)) {
+                crossings = crossings ^ 1u;
+            }
+        }
+    }
+    return crossings == 1u;
+}
+
+// Distance in pixels to the outline; an open stroke while fewer than three
+// vertices exist.
+fn outline_distance(p: vec2<f32>, size: vec2<f32>) -> f32 {
+    let n = params.vertex_count;
+    if (n < 2u) { return 1e9; }
+    let segments = select(n - 1u, n, n >= 3u);
+    var best = 1e9;
+    for (var i = 0u; i < segments; i = i + 1u) {
+        let a = vertices[i] * size;
+        let b = vertices[(i + 1u) % n] * size;
+        let ab = b - a;
+        let t = clamp(dot(p - a, ab) / max(dot(ab, ab), 1e-6), 0.0, 1.0);
+        best = min(best, length(p - (a + ab * t)));
+    }
+    return best;
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn rasterize_mask(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height) { return; }
+    let size = vec2<f32>(f32(params.width), f32(params.height));
+    let p = (vec2<f32>(gid.xy) + 0.5) / size;
+    output[gid.y * params.width + gid.x] = select(0u, 1u, inside(p));
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn render_overlay(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.width || gid.y >= params.height) { return; }
+    let size = vec2<f32>(f32(params.width), f32(params.height));
+    let p_px = vec2<f32>(gid.xy) + 0.5;
+
+    var color = vec4<f32>(0.0);
+    if (params.vertex_count >= 3u && !inside(p_px / size)) {
+        color = SHADE;
+    }
+    if (outline_distance(p_px, size) <= OUTLINE_HALF_WIDTH) {
+        let rgb = OUTLINE.rgb * OUTLINE.a + color.rgb * (1.0 - OUTLINE.a);
+        color = vec4<f32>(rgb, max(color.a, OUTLINE.a));
+    }
+    output[gid.y * params.width + gid.x] = pack4x8unorm(color);
+}
@@ -0,0 +1,21 @@
+// Expand packed f16 RGBA source pixels to the f32 working layout.
+//
+// Input:  array<vec2<u32>> — packed f16 pairs (rg, ba) per pixel
+// Output: array<vec4<f32>> — the source buffer every pass reads
+//
+// unpack2x16float is core WGSL, so this needs no SHADER_F16 support.
+
+@group(0) @binding(0) var<storage, read> input: array<vec2<u32>>;
+@group(0) @binding(1) var<storage, read_write> output: array<vec4<f32>>;
+@group(0) @binding(2) var<uniform> pixel_count: u32;
+
+@compute @workgroup_size(256, 1, 1)
+fn convert_f16_to_f32(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let idx = gid.x;
+    if idx >= pixel_count {
+        return;
+    }
+
+    let packed = input[idx];
+    output[idx] = vec4<f32>(unpack2x16float(packed.x), unpack2x16float(packed.y));
+}
@@ -5,14 +5,19 @@
 @group(0) @binding(2) var<uniform> pixel_count: u32;
 @group(0) @binding(3) var<uniform> resolution: u32;
 @group(0) @binding(4) var<storage, read> mask: array<u32>;
-@group(0) @binding(5) var<uniform> mask_active: u32;
+// Only every `sample_stride`-th pixel is accumulated.
+@group(0) @binding(5) var<uniform> sample_stride: u32;
+
+// Baked into each pipeline variant (see `specialization.rs`).
+override MASK_ACTIVE: bool = false;
 
 @compute @workgroup_size(256, 1, 1)
 fn vectorscope(@builtin(global_invocation_id) gid: vec3<u32>) {
-    if (gid.x >= pixel_count) { return; }
-    if (mask_active != 0u && mask[gid.x] == 0u) { return; }
+    let idx = gid.x * sample_stride;
+    if (idx >= pixel_count) { return; }
+    if (MASK_ACTIVE && mask[idx] == 0u) { return; }
 
-    let pixel = pixels[gid.x];
+    let pixel = pixels[idx];
     // BT.709 luma
     let y = 0.2126729 * pixel.x + 0.7151522 * pixel.y + 0.0721750 * pixel.z;
     // Cb, Cr scaled to roughly [-0.5, 0.5]
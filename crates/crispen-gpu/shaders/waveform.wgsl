@@ -7,16 +7,25 @@
 @group(0) @binding(3) var<uniform> image_height: u32;
 @group(0) @binding(4) var<uniform> waveform_height: u32;
 @group(0) @binding(5) var<storage, read> mask: array<u32>;
-@group(0) @binding(6) var<uniform> mask_active: u32;
+// Only every `sample_stride`-th row is accumulated, so every column keeps samples.
+@group(0) @binding(6) var<uniform> sample_stride: u32;
+
+// Baked into each pipeline variant (see `specialization.rs`); an
+// IMAGE_WIDTH of 0 reads the width from the uniform instead.
+override IMAGE_WIDTH: u32 = 0u;
+override MASK_ACTIVE: bool = false;
 
 @compute @workgroup_size(256, 1, 1)
 fn waveform_compute(@builtin(global_invocation_id) gid: vec3<u32>) {
-    let total_pixels = image_width * image_height;
-    if (gid.x >= total_pixels) { return; }
-    if (mask_active != 0u && mask[gid.x] == 0u) { return; }
+    let width = select(image_width, IMAGE_WIDTH, IMAGE_WIDTH != 0u);
+    let sampled_rows = (image_height + sample_stride - 1u) / sample_stride;
+    if (gid.x >= width * sampled_rows) { return; }
+
+    let x = gid.x % width;
+    let idx = (gid.x / width) * sample_stride * width + x;
+    if (MASK_ACTIVE && mask[idx] == 0u) { return; }
 
-    let x = gid.x % image_width;
-    let pixel = pixels[gid.x];
+    let pixel = pixels[idx];
     let h = waveform_height;
     let hf = f32(h - 1u);
 
@@ -25,7 +34,7 @@ fn waveform_compute(@builtin(global_invocation_id) gid: vec3<u32>) {
     let b_bin = min(u32(clamp(pixel.z, 0.0, 1.0) * hf), h - 1u);
 
     // Buffer layout: channel * (width * height) + x * height + bin
-    let stride = image_width * h;
+    let stride = width * h;
     atomicAdd(&waveform[0u * stride + x * h + r_bin], 1u);
     atomicAdd(&waveform[1u * stride + x * h + g_bin], 1u);
     atomicAdd(&waveform[2u * stride + x * h + b_bin], 1u);
@@ -1,40 +1,104 @@
-//! Double-buffered asynchronous GPU readback for viewer image and scope data.
+//! Ring-buffered asynchronous GPU readback for viewer image and scope data.
 //!
-//! Two staging buffer "slots" alternate: while the GPU writes to one slot,
-//! the CPU reads from the other. The main thread never blocks on GPU work.
+//! Several staging buffer "slots" rotate: the GPU writes into a free slot
+//! while earlier slots are still being mapped or read, so the main thread
+//! never blocks on GPU work and an occasionally skipped consumer frame does
+//! not stall submission.
+//!
+//! Each slot remembers the sizes it was written with and is resized on its
+//! next write, so scope resolution or image size can change while other
+//! slots are still in flight.
+//!
+//! A submission may leave out the scopes; its slot then copies and maps
+//! only the viewer image, and the consumed result carries no scope data.
+//!
+//! Viewer bytes are copied out of the mapped staging into pooled `Vec`s.
+//! Consumers hand the previous frame's buffer back with
+//! [`AsyncReadback::recycle_viewer_bytes`], so steady-state playback does
+//! not allocate a full frame per readback.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
 use crispen_core::scopes::{CieData, HistogramData, VectorscopeData, WaveformData};
 
 use crate::buffers::{ScopeBuffers, ScopeConfig};
 use crate::readback::ScopeResults;
 
-/// Double-buffered async readback for image + scope data.
+/// Slots used when none is requested.
+pub const DEFAULT_READBACK_SLOTS: usize = 3;
+/// Allowed slot counts.
+pub const READBACK_SLOT_RANGE: std::ops::RangeInclusive<usize> = 2..=4;
+
+/// Ring-buffered async readback for image + scope data.
 pub struct AsyncReadback {
-    slots: [ReadbackSlot; 2],
-    /// Which slot has a pending `map_async` (None = no pending readback).
-    pending_idx: Option<usize>,
-    scope_config: ScopeConfig,
-    image_width: u32,
+    slots: Vec<ReadbackSlot>,
+    /// Slots with a pending `map_async`, oldest first.
+    in_flight: VecDeque<usize>,
+    /// Slot whose copies were recorded but not yet mapped.
+    recorded: Option<usize>,
+    /// Returned viewer byte buffers, reused for the next consume.
+    viewer_pool: Vec<Vec<u8>>,
+    stats: ReadbackStats,
+}
+
+/// Frame pacing counters for the readback ring.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadbackStats {
+    /// Number of slots in the ring.
+    pub slots: usize,
+    /// Readbacks currently in flight.
+    pub in_flight: usize,
+    /// Readbacks submitted.
+    pub submitted: u64,
+    /// Readbacks handed to the consumer.
+    pub consumed: u64,
+    /// Completed readbacks skipped because a newer one was also ready.
+    pub dropped: u64,
+    /// Submissions refused because every slot was in flight.
+    pub stalls: u64,
+    /// Consumes that had to allocate a viewer buffer (pool empty or too small).
+    pub viewer_allocations: u64,
+    /// Submit-to-consume time of the last consumed readback.
+    pub last_latency: Duration,
+    /// Moving average of the submit-to-consume time.
+    pub mean_latency: Duration,
 }
 
 /// One set of staging buffers for image + scopes.
 struct ReadbackSlot {
+    label: String,
     image_staging: wgpu::Buffer,
     histogram_staging: wgpu::Buffer,
     waveform_staging: wgpu::Buffer,
     vectorscope_staging: wgpu::Buffer,
     cie_staging: wgpu::Buffer,
-    /// Counter incremented by each map_async callback. Ready when == 5.
+    /// Counter incremented by each map_async callback. Ready once every
+    /// mapped buffer reported back.
     maps_done: Arc<AtomicU32>,
+    /// Whether the last write included the scope buffers.
+    with_scopes: bool,
+    /// Scope dimensions the staging buffers are sized for.
+    scope_config: ScopeConfig,
+    /// Image width the waveform staging is sized for.
+    image_width: u32,
+    /// Image dimensions and viewer byte count of the last write.
+    image_size: (u32, u32),
+    viewer_byte_size: u64,
+    submitted_at: Option<Instant>,
 }
 
 /// Results consumed from an async readback slot.
 pub struct AsyncFrameResult {
     pub viewer_bytes: Vec<u8>,
-    pub scopes: ScopeResults,
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// `None` when the submission left out the scopes.
+    pub scopes: Option<ScopeResults>,
 }
 
 impl ReadbackSlot {
@@ -43,59 +107,91 @@ impl ReadbackSlot {
         scope_config: &ScopeConfig,
         image_width: u32,
         image_staging_size: u64,
-        slot_label: &str,
+        label: String,
     ) -> Self {
-        let image_staging = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("crispen_image_staging_{slot_label}")),
-            size: image_staging_size,
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        });
-
-        let histogram_staging = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("crispen_histogram_staging_{slot_label}")),
-            size: 1024 * 4,
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        });
-
-        let wf_size = (image_width as u64) * (scope_config.waveform_height as u64) * 3 * 4;
-        let waveform_staging = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("crispen_waveform_staging_{slot_label}")),
-            size: wf_size,
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        });
-
-        let vs_size = (scope_config.vectorscope_resolution as u64).pow(2) * 4;
-        let vectorscope_staging = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("crispen_vectorscope_staging_{slot_label}")),
-            size: vs_size,
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        });
-
-        let cie_size = (scope_config.cie_resolution as u64).pow(2) * 4;
-        let cie_staging = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("crispen_cie_staging_{slot_label}")),
-            size: cie_size,
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        });
-
+        let staging = |kind: &str, size| {
+            staging_buffer(device, &format!("crispen_{kind}_staging_{label}"), size)
+        };
         Self {
-            image_staging,
-            histogram_staging,
-            waveform_staging,
-            vectorscope_staging,
-            cie_staging,
+            image_staging: staging("image", image_staging_size),
+            histogram_staging: staging("histogram", 1024 * 4),
+            waveform_staging: staging("waveform", scope_config.waveform_bytes(image_width)),
+            vectorscope_staging: staging("vectorscope", scope_config.vectorscope_bytes()),
+            cie_staging: staging("cie", scope_config.cie_bytes()),
             maps_done: Arc::new(AtomicU32::new(0)),
+            with_scopes: true,
+            scope_config: *scope_config,
+            image_width,
+            image_size: (0, 0),
+            viewer_byte_size: 0,
+            submitted_at: None,
+            label,
+        }
+    }
+
+    /// Grow the image staging and resize the scope staging to match the
+    /// buffers about to be copied. Only called on a slot that is not mapped.
+    fn fit(
+        &mut self,
+        device: &wgpu::Device,
+        viewer_byte_size: u64,
+        scope_buffers: Option<&ScopeBuffers>,
+    ) {
+        let label = &self.label;
+        if self.image_staging.size() < viewer_byte_size {
+            self.image_staging = staging_buffer(
+                device,
+                &format!("crispen_image_staging_{label}"),
+                viewer_byte_size,
+            );
+        }
+        self.with_scopes = scope_buffers.is_some();
+        let Some(scope_buffers) = scope_buffers else {
+            return;
+        };
+        if self.waveform_staging.size() != scope_buffers.waveform.size() {
+            self.waveform_staging = staging_buffer(
+                device,
+                &format!("crispen_waveform_staging_{label}"),
+                scope_buffers.waveform.size(),
+            );
         }
+        if self.vectorscope_staging.size() != scope_buffers.vectorscope.size() {
+            self.vectorscope_staging = staging_buffer(
+                device,
+                &format!("crispen_vectorscope_staging_{label}"),
+                scope_buffers.vectorscope.size(),
+            );
+        }
+        if self.cie_staging.size() != scope_buffers.cie.size() {
+            self.cie_staging = staging_buffer(
+                device,
+                &format!("crispen_cie_staging_{label}"),
+                scope_buffers.cie.size(),
+            );
+        }
+        self.scope_config = scope_buffers.config;
+        self.image_width = scope_buffers.image_width;
     }
 
-    /// Returns true if all 5 map_async callbacks have fired.
+    /// Returns true if every mapped buffer's map_async callback has fired.
     fn is_ready(&self) -> bool {
-        self.maps_done.load(Ordering::Acquire) >= 5
+        self.maps_done.load(Ordering::Acquire) as usize >= self.mapped_buffers().count()
+    }
+
+    /// Staging buffers written by the last submission: the image, plus the
+    /// four scopes when they were included.
+    fn mapped_buffers(&self) -> impl Iterator<Item = &wgpu::Buffer> {
+        let count = if self.with_scopes { 5 } else { 1 };
+        [
+            &self.image_staging,
+            &self.histogram_staging,
+            &self.waveform_staging,
+            &self.vectorscope_staging,
+            &self.cie_staging,
+        ]
+        .into_iter()
+        .take(count)
     }
 
     /// Record copy commands from GPU buffers to this slot's staging buffers.
@@ -104,52 +200,29 @@ impl ReadbackSlot {
         encoder: &mut wgpu::CommandEncoder,
         viewer_src: &wgpu::Buffer,
         viewer_byte_size: u64,
-        scope_buffers: &ScopeBuffers,
+        scope_buffers: Option<&ScopeBuffers>,
     ) {
         encoder.copy_buffer_to_buffer(viewer_src, 0, &self.image_staging, 0, viewer_byte_size);
-        encoder.copy_buffer_to_buffer(
-            &scope_buffers.histogram,
-            0,
-            &self.histogram_staging,
-            0,
-            self.histogram_staging.size(),
-        );
-        encoder.copy_buffer_to_buffer(
-            &scope_buffers.waveform,
-            0,
-            &self.waveform_staging,
-            0,
-            self.waveform_staging.size(),
-        );
-        encoder.copy_buffer_to_buffer(
-            &scope_buffers.vectorscope,
-            0,
-            &self.vectorscope_staging,
-            0,
-            self.vectorscope_staging.size(),
-        );
-        encoder.copy_buffer_to_buffer(
-            &scope_buffers.cie,
-            0,
-            &self.cie_staging,
-            0,
-            self.cie_staging.size(),
-        );
+        let Some(scope_buffers) = scope_buffers else {
+            return;
+        };
+        let scopes = [
+            (&scope_buffers.histogram, &self.histogram_staging),
+            (&scope_buffers.waveform, &self.waveform_staging),
+            (&scope_buffers.vectorscope, &self.vectorscope_staging),
+            (&scope_buffers.cie, &self.cie_staging),
+        ];
+        for (src, dst) in scopes {
+            encoder.copy_buffer_to_buffer(src, 0, dst, 0, dst.size());
+        }
     }
 
-    /// Initiate map_async on all 5 staging buffers. Resets the counter first.
+    /// Initiate map_async on the written staging buffers. Resets the
+    /// counter first.
     fn begin_map(&self) {
         self.maps_done.store(0, Ordering::Release);
 
-        let bufs = [
-            &self.image_staging,
-            &self.histogram_staging,
-            &self.waveform_staging,
-            &self.vectorscope_staging,
-            &self.cie_staging,
-        ];
-
-        for buf in bufs {
+        for buf in self.mapped_buffers() {
             let counter = Arc::clone(&self.maps_done);
             buf.slice(..).map_async(wgpu::MapMode::Read, move |_| {
                 counter.fetch_add(1, Ordering::Release);
@@ -157,21 +230,38 @@ impl ReadbackSlot {
         }
     }
 
+    /// Unmap without reading (a newer readback superseded this one).
+    fn discard(&self) {
+        for buf in self.mapped_buffers() {
+            buf.unmap();
+        }
+    }
+
     /// Read mapped data from all staging buffers and unmap them.
-    fn consume(
-        &self,
-        viewer_byte_size: u64,
-        scope_config: &ScopeConfig,
-        image_width: u32,
-    ) -> AsyncFrameResult {
+    ///
+    /// Viewer bytes are written into `viewer_bytes`, reusing its allocation.
+    fn consume(&self, mut viewer_bytes: Vec<u8>) -> AsyncFrameResult {
+        let viewer_byte_size = self.viewer_byte_size;
+        let scope_config = &self.scope_config;
+        let image_width = self.image_width;
+
         // Read viewer image bytes.
-        let viewer_bytes = {
+        {
             let data = self.image_staging.slice(..).get_mapped_range();
-            let bytes = data[..viewer_byte_size as usize].to_vec();
+            viewer_bytes.clear();
+            viewer_bytes.extend_from_slice(&data[..viewer_byte_size as usize]);
             drop(data);
             self.image_staging.unmap();
-            bytes
-        };
+        }
+
+        if !self.with_scopes {
+            return AsyncFrameResult {
+                viewer_bytes,
+                width: self.image_size.0,
+                height: self.image_size.1,
+                scopes: None,
+            };
+        }
 
         // Read histogram.
         let histogram = {
@@ -239,94 +329,172 @@ impl ReadbackSlot {
 
         AsyncFrameResult {
             viewer_bytes,
-            scopes: ScopeResults {
+            width: self.image_size.0,
+            height: self.image_size.1,
+            scopes: Some(ScopeResults {
                 histogram,
                 waveform,
                 vectorscope,
                 cie,
-            },
+            }),
         }
     }
 }
 
 impl AsyncReadback {
-    /// Create double-buffered staging resources.
+    /// Create `slot_count` staging slots (clamped to [`READBACK_SLOT_RANGE`]).
     pub fn new(
         device: &wgpu::Device,
+        slot_count: usize,
         scope_config: &ScopeConfig,
         image_width: u32,
         image_staging_size: u64,
     ) -> Self {
-        let slots = [
-            ReadbackSlot::new(device, scope_config, image_width, image_staging_size, "a"),
-            ReadbackSlot::new(device, scope_config, image_width, image_staging_size, "b"),
-        ];
+        let slot_count = slot_count.clamp(*READBACK_SLOT_RANGE.start(), *READBACK_SLOT_RANGE.end());
+        let slots = (0..slot_count)
+            .map(|i| {
+                ReadbackSlot::new(
+                    device,
+                    scope_config,
+                    image_width,
+                    image_staging_size,
+                    i.to_string(),
+                )
+            })
+            .collect();
         Self {
             slots,
-            pending_idx: None,
-            scope_config: *scope_config,
-            image_width,
+            in_flight: VecDeque::with_capacity(slot_count),
+            recorded: None,
+            viewer_pool: Vec::with_capacity(slot_count),
+            stats: ReadbackStats {
+                slots: slot_count,
+                ..ReadbackStats::default()
+            },
         }
     }
 
-    /// Non-blocking poll: check if the pending readback is ready.
-    /// Calls `device.poll(PollType::poll())` to process callbacks.
-    /// Returns `Some(result)` if data is available, `None` otherwise.
-    pub fn try_consume(
-        &mut self,
-        device: &wgpu::Device,
-        viewer_byte_size: u64,
-    ) -> Option<AsyncFrameResult> {
+    /// Non-blocking poll for completed readbacks.
+    ///
+    /// Calls `device.poll(PollType::Poll)` to process callbacks. If several
+    /// readbacks completed since the last call, only the newest is returned
+    /// and the older ones are released and counted as dropped.
+    pub fn try_consume(&mut self, device: &wgpu::Device) -> Option<AsyncFrameResult> {
         // Drive the GPU event loop without blocking.
         let _ = device.poll(wgpu::PollType::Poll);
 
-        let pending = self.pending_idx?;
-        if !self.slots[pending].is_ready() {
+        let ready = self
+            .in_flight
+            .iter()
+            .take_while(|&&idx| self.slots[idx].is_ready())
+            .count();
+        if ready == 0 {
             return None;
         }
+        for _ in 1..ready {
+            let idx = self.in_flight.pop_front()?;
+            self.slots[idx].discard();
+            self.stats.dropped += 1;
+        }
 
-        let result =
-            self.slots[pending].consume(viewer_byte_size, &self.scope_config, self.image_width);
-        self.pending_idx = None;
+        let idx = self.in_flight.pop_front()?;
+        let viewer_bytes = self.take_viewer_buffer(self.slots[idx].viewer_byte_size as usize);
+        let slot = &mut self.slots[idx];
+        let result = slot.consume(viewer_bytes);
+        if let Some(submitted_at) = slot.submitted_at.take() {
+            let latency = submitted_at.elapsed();
+            self.stats.last_latency = latency;
+            self.stats.mean_latency = if self.stats.consumed == 0 {
+                latency
+            } else {
+                (self.stats.mean_latency * 7 + latency) / 8
+            };
+        }
+        self.stats.consumed += 1;
+        self.stats.in_flight = self.in_flight.len();
         Some(result)
     }
 
-    /// Record staging copies and begin map_async on the write slot.
+    /// Return a consumed frame's viewer bytes for reuse.
+    ///
+    /// The pool holds at most one buffer per slot; extras are dropped.
+    pub fn recycle_viewer_bytes(&mut self, bytes: Vec<u8>) {
+        if bytes.capacity() > 0 && self.viewer_pool.len() < self.slots.len() {
+            self.viewer_pool.push(bytes);
+        }
+    }
+
+    /// A pooled buffer with room for `len` bytes, or a new allocation.
+    fn take_viewer_buffer(&mut self, len: usize) -> Vec<u8> {
+        match self.viewer_pool.iter().position(|b| b.capacity() >= len) {
+            Some(i) => self.viewer_pool.swap_remove(i),
+            None => {
+                // Undersized buffers belong to an older, smaller image.
+                self.viewer_pool.clear();
+                self.stats.viewer_allocations += 1;
+                Vec::with_capacity(len)
+            }
+        }
+    }
+
+    /// Whether a slot is free for another submission.
+    pub fn has_free_slot(&self) -> bool {
+        self.in_flight.len() < self.slots.len()
+    }
+
+    /// Count a submission that was skipped because no slot was free.
+    pub fn record_stall(&mut self) {
+        self.stats.stalls += 1;
+    }
+
+    /// Record staging copies into a free slot, resizing it to fit first.
     ///
-    /// Must be called AFTER `queue.submit()` for the encoder that contains
-    /// the compute dispatches.
+    /// Call [`begin_map_after_submit`](Self::begin_map_after_submit) after
+    /// the encoder has been submitted. Panics if no slot is free; check
+    /// [`has_free_slot`](Self::has_free_slot) first. Without
+    /// `scope_buffers` only the viewer image is read back.
     pub fn submit_readback(
         &mut self,
+        device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
         viewer_src: &wgpu::Buffer,
         viewer_byte_size: u64,
-        scope_buffers: &ScopeBuffers,
+        image_size: (u32, u32),
+        scope_buffers: Option<&ScopeBuffers>,
     ) {
-        // Choose the slot that's NOT pending (or 0 if none pending).
-        let write_idx = match self.pending_idx {
-            Some(idx) => 1 - idx,
-            None => 0,
-        };
-
-        self.slots[write_idx].record_copies(encoder, viewer_src, viewer_byte_size, scope_buffers);
-
-        // Note: begin_map must be called after queue.submit — the caller
-        // handles this via the returned write_idx.
+        let write_idx = (0..self.slots.len())
+            .find(|idx| !self.in_flight.contains(idx))
+            .expect("submit_readback called with every slot in flight");
+
+        let slot = &mut self.slots[write_idx];
+        slot.fit(device, viewer_byte_size, scope_buffers);
+        slot.record_copies(encoder, viewer_src, viewer_byte_size, scope_buffers);
+        slot.image_size = image_size;
+        slot.viewer_byte_size = viewer_byte_size;
+        self.recorded = Some(write_idx);
     }
 
-    /// Begin map_async on the write slot. Call this AFTER `queue.submit()`.
+    /// Begin map_async on the recorded slot. Call this AFTER `queue.submit()`.
     pub fn begin_map_after_submit(&mut self) {
-        let write_idx = match self.pending_idx {
-            Some(idx) => 1 - idx,
-            None => 0,
+        let Some(idx) = self.recorded.take() else {
+            return;
         };
-        self.slots[write_idx].begin_map();
-        self.pending_idx = Some(write_idx);
+        let slot = &mut self.slots[idx];
+        slot.begin_map();
+        slot.submitted_at = Some(Instant::now());
+        self.in_flight.push_back(idx);
+        self.stats.submitted += 1;
+        self.stats.in_flight = self.in_flight.len();
     }
 
-    /// Whether a readback is currently in flight.
+    /// Whether any readback is currently in flight.
     pub fn has_pending(&self) -> bool {
-        self.pending_idx.is_some()
+        !self.in_flight.is_empty()
+    }
+
+    /// Frame pacing counters.
+    pub fn stats(&self) -> ReadbackStats {
+        self.stats
     }
 }
 
@@ -348,3 +516,12 @@ fn convert_waveform_channel(src: &[u32], width: usize, height: usize) -> Vec<u32
     }
     dst
 }
+
+fn staging_buffer(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    })
+}
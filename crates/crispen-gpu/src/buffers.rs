@@ -1,6 +1,6 @@
 //! GPU buffer and texture management for the grading pipeline.
 
-use crispen_core::image::GradingImage;
+use crispen_core::image::{GradingImage, HalfImage};
 use wgpu::util::DeviceExt;
 
 /// Handle to a GPU image stored as a storage buffer of `vec4<f32>`.
@@ -29,6 +29,17 @@ impl GpuImageHandle {
         }
     }
 
+    /// Upload a [`HalfImage`] as packed f16 (8 bytes per pixel), returning
+    /// the buffer for [`crate::format_converter::FormatConverter::unpack_f16`]
+    /// to expand into an f32 image.
+    pub fn upload_packed_f16(device: &wgpu::Device, image: &HalfImage) -> wgpu::Buffer {
+        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("crispen_image_upload_f16"),
+            contents: bytemuck::cast_slice(&image.pixels),
+            usage: wgpu::BufferUsages::STORAGE,
+        })
+    }
+
     /// Create an uninitialized GPU image buffer for output.
     pub fn create_output(device: &wgpu::Device, width: u32, height: u32) -> Self {
         let size = (width as u64) * (height as u64) * 16; // 4 x f32
@@ -119,14 +130,25 @@ pub struct ScopeBuffers {
     pub vectorscope: wgpu::Buffer,
     /// `resolution^2` u32s.
     pub cie: wgpu::Buffer,
+    /// Dimensions the buffers are currently sized for.
+    pub config: ScopeConfig,
+    /// Image width the waveform buffer is currently sized for.
+    pub image_width: u32,
 }
 
+/// Sampled pixels per scope pass that automatic stride selection aims for
+/// (about one 1080p frame).
+pub const AUTO_SCOPE_SAMPLES: u32 = 1 << 21;
+
 /// Configuration for scope buffer dimensions.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ScopeConfig {
     pub waveform_height: u32,
     pub vectorscope_resolution: u32,
     pub cie_resolution: u32,
+    /// Accumulate only every Nth pixel; `None` picks a stride from the
+    /// image size (see [`auto_sample_stride`]).
+    pub sample_stride: Option<u32>,
 }
 
 impl Default for ScopeConfig {
@@ -135,58 +157,100 @@ impl Default for ScopeConfig {
             waveform_height: 256,
             vectorscope_resolution: 512,
             cie_resolution: 512,
+            sample_stride: None,
         }
     }
 }
 
-impl ScopeBuffers {
-    /// Create all scope output buffers, zeroed.
-    pub fn new(device: &wgpu::Device, config: &ScopeConfig, image_width: u32) -> Self {
-        let histogram = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("crispen_histogram_buffer"),
-            size: 1024 * 4, // 256 * 4 channels * sizeof(u32)
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+/// Smallest stride that keeps a scope pass near [`AUTO_SCOPE_SAMPLES`].
+pub fn auto_sample_stride(pixel_count: u32) -> u32 {
+    pixel_count.div_ceil(AUTO_SCOPE_SAMPLES).max(1)
+}
 
-        let waveform_elements = (image_width as u64) * (config.waveform_height as u64) * 3;
-        let waveform = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("crispen_waveform_buffer"),
-            size: waveform_elements * 4,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+impl ScopeConfig {
+    /// Sample stride for an image of `pixel_count` pixels.
+    pub fn sample_stride_for(&self, pixel_count: u32) -> u32 {
+        self.sample_stride
+            .unwrap_or_else(|| auto_sample_stride(pixel_count))
+            .max(1)
+    }
 
-        let vs_elements =
-            (config.vectorscope_resolution as u64) * (config.vectorscope_resolution as u64);
-        let vectorscope = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("crispen_vectorscope_buffer"),
-            size: vs_elements * 4,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+    /// Byte size of the waveform buffer for an image `image_width` wide.
+    pub fn waveform_bytes(&self, image_width: u32) -> u64 {
+        (image_width as u64) * (self.waveform_height as u64) * 3 * 4
+    }
 
-        let cie_elements = (config.cie_resolution as u64) * (config.cie_resolution as u64);
-        let cie = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("crispen_cie_buffer"),
-            size: cie_elements * 4,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+    /// Byte size of the vectorscope buffer.
+    pub fn vectorscope_bytes(&self) -> u64 {
+        (self.vectorscope_resolution as u64).pow(2) * 4
+    }
+
+    /// Byte size of the CIE buffer.
+    pub fn cie_bytes(&self) -> u64 {
+        (self.cie_resolution as u64).pow(2) * 4
+    }
+}
 
+impl ScopeBuffers {
+    /// Create all scope output buffers, zeroed.
+    pub fn new(device: &wgpu::Device, config: &ScopeConfig, image_width: u32) -> Self {
         Self {
-            histogram,
-            waveform,
-            vectorscope,
-            cie,
+            // 256 bins * 4 channels * sizeof(u32)
+            histogram: scope_storage(device, "crispen_histogram_buffer", 1024 * 4),
+            waveform: scope_storage(
+                device,
+                "crispen_waveform_buffer",
+                config.waveform_bytes(image_width),
+            ),
+            vectorscope: scope_storage(
+                device,
+                "crispen_vectorscope_buffer",
+                config.vectorscope_bytes(),
+            ),
+            cie: scope_storage(device, "crispen_cie_buffer", config.cie_bytes()),
+            config: *config,
+            image_width,
         }
     }
+
+    /// Reallocate only the buffers whose size differs for `config` and
+    /// `image_width`. Returns `true` if anything was reallocated.
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        config: &ScopeConfig,
+        image_width: u32,
+    ) -> bool {
+        if self.config == *config && self.image_width == image_width {
+            return false;
+        }
+        let waveform_bytes = config.waveform_bytes(image_width);
+        if self.waveform.size() != waveform_bytes {
+            self.waveform = scope_storage(device, "crispen_waveform_buffer", waveform_bytes);
+        }
+        if self.vectorscope.size() != config.vectorscope_bytes() {
+            self.vectorscope = scope_storage(
+                device,
+                "crispen_vectorscope_buffer",
+                config.vectorscope_bytes(),
+            );
+        }
+        if self.cie.size() != config.cie_bytes() {
+            self.cie = scope_storage(device, "crispen_cie_buffer", config.cie_bytes());
+        }
+        self.config = *config;
+        self.image_width = image_width;
+        true
+    }
+}
+
+fn scope_storage(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage: wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
 }
@@ -0,0 +1,167 @@
+//! GPU chromatic aberration pass on the source image, ahead of the LUT.
+//!
+//! Realignment resamples red and blue at other pixel positions, so it
+//! cannot be baked into the 3D LUT. `chromatic_aberration.wgsl` writes an
+//! owned image that dehaze and the LUT applicator then read in place of
+//! the source.
+//! `crispen_core::grading::chromatic_aberration::apply_chromatic_aberration`
+//! is the CPU reference.
+
+use std::num::NonZeroU64;
+
+use crispen_core::grading::chromatic_aberration::{ChromaticAberration, FRINGE_SHIFT};
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Uniform block of `chromatic_aberration.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct CaParams {
+    width: u32,
+    height: u32,
+    red_scale: f32,
+    blue_scale: f32,
+    fringe: f32,
+    _pad: [f32; 3],
+}
+
+/// Owns the `chromatic_aberration.wgsl` pipeline and its output image.
+pub struct ChromaticAberrationPass {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    output: Option<GpuImageHandle>,
+}
+
+impl ChromaticAberrationPass {
+    /// Create the pipeline. Compiles `chromatic_aberration.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_ca_shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../shaders/chromatic_aberration.wgsl").into(),
+            ),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_ca_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 32),
+                // binding 1: source image (read)
+                buffer_entry(1, wgpu::BufferBindingType::Storage { read_only: true }, 16),
+                // binding 2: realigned image (read_write)
+                buffer_entry(2, wgpu::BufferBindingType::Storage { read_only: false }, 16),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_ca_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("crispen_ca_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_ca_params"),
+                size: std::mem::size_of::<CaParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            output: None,
+        }
+    }
+
+    /// Record the realignment of `source` onto `encoder` and return the
+    /// realigned image, or `None` when `ca` is off and the source should be
+    /// used as is.
+    ///
+    /// The caller is responsible for submitting the encoder.
+    pub fn run(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        source: &GpuImageHandle,
+        ca: &ChromaticAberration,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Option<&GpuImageHandle> {
+        let (width, height) = (source.width, source.height);
+        if ca.is_identity() || width == 0 || height == 0 {
+            return None;
+        }
+        if self
+            .output
+            .as_ref()
+            .is_none_or(|o| o.width != width || o.height != height)
+        {
+            self.output = Some(GpuImageHandle::create_output(device, width, height));
+        }
+        let output = self.output.as_ref()?;
+
+        let params = CaParams {
+            width,
+            height,
+            red_scale: ca.red_scale,
+            blue_scale: ca.blue_scale,
+            fringe: ca.fringe * FRINGE_SHIFT,
+            _pad: [0.0; 3],
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_ca_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: source.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: output.buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("crispen_ca_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+        Some(output)
+    }
+}
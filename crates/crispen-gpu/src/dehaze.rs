@@ -0,0 +1,231 @@
+//! GPU dehaze pass on the source image, ahead of the LUT.
+//!
+//! Dehaze needs a neighborhood of pixels, so it cannot be baked into the
+//! 3D LUT. `dehaze.wgsl` runs five dispatches — separable minimum filter,
+//! separable box blur, recovery — into an owned image the LUT applicator
+//! then reads in place of the source.
+//! `crispen_core::grading::dehaze::apply_dehaze` is the CPU reference.
+
+use std::num::NonZeroU64;
+
+use crispen_core::grading::dehaze::Dehaze;
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Entry points of `dehaze.wgsl`, in dispatch order.
+const ENTRY_POINTS: [&str; 5] = [
+    "dark_rows",
+    "dark_cols",
+    "blur_rows",
+    "blur_cols",
+    "recover",
+];
+
+/// Uniform block of `dehaze.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct DehazeParams {
+    width: u32,
+    height: u32,
+    strength: f32,
+    haze_exponent: f32,
+}
+
+/// Dehazed image and the dark-channel scratch for one image size.
+struct DehazeTarget {
+    output: GpuImageHandle,
+    dark_a: wgpu::Buffer,
+    dark_b: wgpu::Buffer,
+}
+
+impl DehazeTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let scratch = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: u64::from(width) * u64::from(height) * 4,
+                usage: wgpu::BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            })
+        };
+        Self {
+            output: GpuImageHandle::create_output(device, width, height),
+            dark_a: scratch("crispen_dehaze_dark_a"),
+            dark_b: scratch("crispen_dehaze_dark_b"),
+        }
+    }
+}
+
+/// Owns the `dehaze.wgsl` pipelines and their buffers.
+pub struct DehazePass {
+    pipelines: Vec<wgpu::ComputePipeline>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    airlight: wgpu::Buffer,
+    target: Option<DehazeTarget>,
+}
+
+impl DehazePass {
+    /// Create the pipelines. Compiles `dehaze.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_dehaze_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/dehaze.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let read = wgpu::BufferBindingType::Storage { read_only: true };
+        let write = wgpu::BufferBindingType::Storage { read_only: false };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_dehaze_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 16),
+                // binding 1: source image (read)
+                buffer_entry(1, read, 16),
+                // binding 2: dehazed image (read_write)
+                buffer_entry(2, write, 16),
+                // binding 3: dark channel scratch A (read_write)
+                buffer_entry(3, write, 4),
+                // binding 4: dark channel scratch B (read_write)
+                buffer_entry(4, write, 4),
+                // binding 5: airlight as atomic f32 bits (read_write)
+                buffer_entry(5, write, 4),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_dehaze_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipelines = ENTRY_POINTS
+            .iter()
+            .map(|entry_point| {
+                device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some("crispen_dehaze_pipeline"),
+                    layout: Some(&pipeline_layout),
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    compilation_options: Default::default(),
+                    cache: None,
+                })
+            })
+            .collect();
+
+        Self {
+            pipelines,
+            bind_group_layout,
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_dehaze_params"),
+                size: std::mem::size_of::<DehazeParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            airlight: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_dehaze_airlight"),
+                size: 4,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            target: None,
+        }
+    }
+
+    /// Record the dehaze dispatches for `source` onto `encoder` and return
+    /// the dehazed image, or `None` when `dehaze` is off and the source
+    /// should be used as is.
+    ///
+    /// The caller is responsible for submitting the encoder.
+    pub fn run(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        source: &GpuImageHandle,
+        dehaze: &Dehaze,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Option<&GpuImageHandle> {
+        let (width, height) = (source.width, source.height);
+        if dehaze.is_identity() || width == 0 || height == 0 {
+            return None;
+        }
+        if self
+            .target
+            .as_ref()
+            .is_none_or(|t| t.output.width != width || t.output.height != height)
+        {
+            self.target = Some(DehazeTarget::new(device, width, height));
+        }
+        let target = self.target.as_ref()?;
+
+        let params = DehazeParams {
+            width,
+            height,
+            strength: dehaze.strength,
+            haze_exponent: dehaze.haze_exponent(),
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+        queue.write_buffer(&self.airlight, 0, &0u32.to_le_bytes());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_dehaze_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: source.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: target.output.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: target.dark_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: target.dark_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: self.airlight.as_entire_binding(),
+                },
+            ],
+        });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("crispen_dehaze_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_bind_group(0, &bind_group, &[]);
+            // wgpu inserts a barrier between dispatches, so each entry point
+            // sees the previous one's writes.
+            for pipeline in &self.pipelines {
+                pass.set_pipeline(pipeline);
+                pass.dispatch_workgroups(
+                    width.div_ceil(WORKGROUP_SIZE),
+                    height.div_ceil(WORKGROUP_SIZE),
+                    1,
+                );
+            }
+        }
+        Some(&target.output)
+    }
+}
@@ -0,0 +1,287 @@
+//! GPU ΔE2000 comparison of two graded images, with its heatmap.
+//!
+//! Runs `delta_e.wgsl` over two images already on the GPU — typically the
+//! current output and a reference render or the same source under another
+//! grade — and reads back the per-pixel ΔE and an RGBA8 heatmap in one
+//! blocking round trip. `crispen_core::transform::compare::delta_e_map` is
+//! the CPU reference.
+//!
+//! The output transfer's decode is sampled into a table on the CPU, so the
+//! shader stays free of the twelve transfer functions in `bake_lut.wgsl`.
+
+use std::num::NonZeroU64;
+
+use crispen_core::color_management::color_space::rgb_to_xyz_matrix;
+use crispen_core::color_management::transfer::get_transfer;
+use crispen_core::transform::compare::DeltaEMap;
+use crispen_core::transform::params::ColorSpaceId;
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Entries in the output transfer decode table, spanning code values 0..1.
+/// Code values outside that range are clamped to it.
+pub const DECODE_TABLE_SIZE: usize = 4096;
+
+/// Uniform block of `delta_e.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct CompareParams {
+    width: u32,
+    height: u32,
+    encoded: u32,
+    scale: f32,
+    to_xyz: [[f32; 4]; 3],
+}
+
+/// A finished comparison.
+#[derive(Debug, Clone)]
+pub struct DeltaEResult {
+    /// Per-pixel ΔE2000.
+    pub map: DeltaEMap,
+    /// `width * height` RGBA8 heatmap pixels (sRGB-encoded).
+    pub heatmap: Vec<u8>,
+}
+
+/// Output buffers and their staging copies for one image size.
+struct CompareTarget {
+    pixels: u64,
+    delta_e: wgpu::Buffer,
+    heatmap: wgpu::Buffer,
+    delta_e_staging: wgpu::Buffer,
+    heatmap_staging: wgpu::Buffer,
+}
+
+impl CompareTarget {
+    fn new(device: &wgpu::Device, pixels: u64) -> Self {
+        let buffer = |label, usage| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: pixels * 4,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let output = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC;
+        let staging = wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ;
+        Self {
+            pixels,
+            delta_e: buffer("crispen_delta_e", output),
+            heatmap: buffer("crispen_delta_e_heatmap", output),
+            delta_e_staging: buffer("crispen_delta_e_staging", staging),
+            heatmap_staging: buffer("crispen_delta_e_heatmap_staging", staging),
+        }
+    }
+}
+
+/// Owns the `delta_e.wgsl` pipeline and its buffers.
+pub struct DeltaECompare {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    decode: wgpu::Buffer,
+    /// Output space the decode table was last built for.
+    decode_space: Option<ColorSpaceId>,
+    target: Option<CompareTarget>,
+}
+
+impl DeltaECompare {
+    /// Create the pipeline. Compiles `delta_e.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_delta_e_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/delta_e.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let read = wgpu::BufferBindingType::Storage { read_only: true };
+        let write = wgpu::BufferBindingType::Storage { read_only: false };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_delta_e_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 64),
+                // binding 1: graded image (read)
+                buffer_entry(1, read, 16),
+                // binding 2: reference image (read)
+                buffer_entry(2, read, 16),
+                // binding 3: transfer decode table (read)
+                buffer_entry(3, read, 8),
+                // binding 4: per-pixel ΔE (read_write)
+                buffer_entry(4, write, 4),
+                // binding 5: packed RGBA8 heatmap (read_write)
+                buffer_entry(5, write, 4),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_delta_e_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("crispen_delta_e_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("compare_delta_e"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_delta_e_params"),
+                size: std::mem::size_of::<CompareParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            decode: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_delta_e_decode"),
+                size: (DECODE_TABLE_SIZE * 4) as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            decode_space: None,
+            target: None,
+        }
+    }
+
+    /// Compare `graded` against `reference`, both holding code values in
+    /// `output_space`. Blocks until the readback completes.
+    ///
+    /// `scale` is the ΔE at which the heatmap reaches red. Returns `None`
+    /// when the images differ in size.
+    pub fn compare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        graded: &GpuImageHandle,
+        reference: &GpuImageHandle,
+        output_space: ColorSpaceId,
+        scale: f32,
+    ) -> Option<DeltaEResult> {
+        if graded.width != reference.width || graded.height != reference.height {
+            return None;
+        }
+        let (width, height) = (graded.width, graded.height);
+        let pixels = u64::from(width) * u64::from(height);
+        if pixels == 0 {
+            return None;
+        }
+
+        let transfer = get_transfer(output_space);
+        if self.decode_space != Some(output_space) {
+            if let Some(tf) = &transfer {
+                let last = (DECODE_TABLE_SIZE - 1) as f32;
+                let table: Vec<f32> = (0..DECODE_TABLE_SIZE)
+                    .map(|i| tf.to_linear(i as f32 / last))
+                    .collect();
+                queue.write_buffer(&self.decode, 0, bytemuck::cast_slice(&table));
+            }
+            self.decode_space = Some(output_space);
+        }
+        let m = rgb_to_xyz_matrix(output_space).0;
+        let params = CompareParams {
+            width,
+            height,
+            encoded: u32::from(transfer.is_some()),
+            scale,
+            to_xyz: m.map(|row| [row[0] as f32, row[1] as f32, row[2] as f32, 0.0]),
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+
+        if self.target.as_ref().is_none_or(|t| t.pixels != pixels) {
+            self.target = Some(CompareTarget::new(device, pixels));
+        }
+        let target = self.target.as_ref()?;
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_delta_e_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: graded.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: reference.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.decode.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: target.delta_e.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: target.heatmap.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("crispen_delta_e_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("crispen_delta_e_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+        let size = pixels * 4;
+        encoder.copy_buffer_to_buffer(&target.delta_e, 0, &target.delta_e_staging, 0, size);
+        encoder.copy_buffer_to_buffer(&target.heatmap, 0, &target.heatmap_staging, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        target
+            .delta_e_staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |_| {});
+        target
+            .heatmap_staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+
+        let values: Vec<f32> =
+            bytemuck::cast_slice(&target.delta_e_staging.slice(..).get_mapped_range()).to_vec();
+        let heatmap = target.heatmap_staging.slice(..).get_mapped_range().to_vec();
+        target.delta_e_staging.unmap();
+        target.heatmap_staging.unmap();
+
+        Some(DeltaEResult {
+            map: DeltaEMap {
+                width,
+                height,
+                values,
+            },
+            heatmap,
+        })
+    }
+}
@@ -1,7 +1,10 @@
-//! GPU compute pass for converting f32 pixels to f16 (for viewer readback).
+//! GPU compute passes converting between f32 and f16 pixels: f32→f16 for
+//! viewer readback, and f16→f32 for half-precision source uploads.
 
 use std::num::NonZeroU64;
 
+use wgpu::util::DeviceExt;
+
 use crate::buffers::GpuImageHandle;
 
 /// Viewer pixel format — configurable for profiling quality vs bandwidth.
@@ -27,10 +30,13 @@ impl ViewerFormat {
     }
 }
 
-/// Manages the f32→f16 and f32→sRGB8 conversion pipelines and their output buffers.
+/// Manages the f32→f16, f32→sRGB8, and f16→f32 conversion pipelines and
+/// the readback output buffers.
 pub struct FormatConverter {
     f16_pipeline: wgpu::ComputePipeline,
     f16_layout: wgpu::BindGroupLayout,
+    unpack_pipeline: wgpu::ComputePipeline,
+    unpack_layout: wgpu::BindGroupLayout,
     srgb_pipeline: wgpu::ComputePipeline,
     srgb_layout: wgpu::BindGroupLayout,
     pixel_count_buffer: wgpu::Buffer,
@@ -46,8 +52,8 @@ struct ConvertOutput {
 }
 
 impl FormatConverter {
-    /// Create the format conversion pipelines. Compiles `format_convert.wgsl`
-    /// and `linear_to_srgb.wgsl`.
+    /// Create the format conversion pipelines. Compiles `format_convert.wgsl`,
+    /// `linear_to_srgb.wgsl`, and `unpack_f16.wgsl`.
     pub fn new(device: &wgpu::Device) -> Self {
         // Shared pixel_count uniform buffer.
         let pixel_count_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -87,6 +93,37 @@ impl FormatConverter {
             cache: None,
         });
 
+        // ── F16 unpack pipeline ──────────────────────────────────────
+        let unpack_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_unpack_f16_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/unpack_f16.wgsl").into()),
+        });
+
+        let unpack_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_unpack_f16_layout"),
+            entries: &[
+                storage_ro_entry(0, 8),
+                storage_rw_entry(1, 16),
+                uniform_entry(2, 4),
+            ],
+        });
+
+        let unpack_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("crispen_unpack_f16_pipeline_layout"),
+                bind_group_layouts: &[&unpack_layout],
+                push_constant_ranges: &[],
+            });
+
+        let unpack_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("crispen_unpack_f16_pipeline"),
+            layout: Some(&unpack_pipeline_layout),
+            module: &unpack_shader,
+            entry_point: Some("convert_f16_to_f32"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
         // ── sRGB pipeline ────────────────────────────────────────────
         let srgb_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("crispen_linear_to_srgb_shader"),
@@ -122,6 +159,8 @@ impl FormatConverter {
         Self {
             f16_pipeline,
             f16_layout,
+            unpack_pipeline,
+            unpack_layout,
             srgb_pipeline,
             srgb_layout,
             pixel_count_buffer,
@@ -277,6 +316,53 @@ impl FormatConverter {
         &srgb_out.buffer
     }
 
+    /// Dispatch the f16→f32 expansion of `packed` (8 bytes per pixel) into
+    /// `target` on the given encoder.
+    ///
+    /// The pixel count gets its own uniform rather than the shared one, so
+    /// an upload never races a frame's readback conversion.
+    pub fn unpack_f16(
+        &self,
+        device: &wgpu::Device,
+        packed: &wgpu::Buffer,
+        target: &GpuImageHandle,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let pixel_count = target.pixel_count();
+        let count_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("crispen_unpack_f16_pixel_count"),
+            contents: bytemuck::cast_slice(&[pixel_count, 0u32, 0u32, 0u32]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_unpack_f16_bg"),
+            layout: &self.unpack_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: packed.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: target.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: count_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("crispen_unpack_f16_pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.unpack_pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(pixel_count.div_ceil(256), 1, 1);
+    }
+
     /// Get a reference to the f16 output buffer, if allocated.
     pub fn f16_buffer(&self) -> Option<&wgpu::Buffer> {
         self.f16_output.as_ref().map(|o| &o.buffer)
@@ -0,0 +1,217 @@
+//! GPU glow pass on the graded output, after the LUT.
+//!
+//! Runs `glow.wgsl` over a pyramid of half-size levels: a bright pass into
+//! the first level, downsamples to the coarsest, upsample-and-add back to
+//! the finest, then a composite into the graded image in place.
+//! `crispen_core::grading::glow::apply_glow` is the CPU reference.
+
+use std::num::NonZeroU64;
+
+use crispen_core::grading::glow::{Glow, level_sizes};
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Uniform block of one `glow.wgsl` dispatch.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GlowStep {
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    threshold: f32,
+    spread: f32,
+    scale: f32,
+    _pad: f32,
+    tint: [f32; 4],
+}
+
+/// Entry points of `glow.wgsl`.
+#[derive(Clone, Copy)]
+enum Stage {
+    BrightDown,
+    Down,
+    Up,
+    Composite,
+}
+
+/// Pyramid levels and one uniform per dispatch for one image size.
+struct GlowTarget {
+    width: u32,
+    height: u32,
+    levels: Vec<GpuImageHandle>,
+    steps: Vec<wgpu::Buffer>,
+}
+
+impl GlowTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let levels: Vec<_> = level_sizes(width, height)
+            .into_iter()
+            .map(|(w, h)| GpuImageHandle::create_output(device, w, h))
+            .collect();
+        // One step down into each level, one up into all but the
+        // coarsest, and the composite.
+        let steps = (0..levels.len() * 2)
+            .map(|_| {
+                device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("crispen_glow_step"),
+                    size: std::mem::size_of::<GlowStep>() as u64,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            levels,
+            steps,
+        }
+    }
+}
+
+/// Owns the `glow.wgsl` pipelines and the pyramid.
+pub struct GlowPass {
+    pipelines: [wgpu::ComputePipeline; 4],
+    bind_group_layout: wgpu::BindGroupLayout,
+    target: Option<GlowTarget>,
+}
+
+impl GlowPass {
+    /// Create the pipelines. Compiles `glow.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_glow_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/glow.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_glow_layout"),
+            entries: &[
+                // binding 0: step uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 48),
+                // binding 1: source level or image (read)
+                buffer_entry(1, wgpu::BufferBindingType::Storage { read_only: true }, 16),
+                // binding 2: destination level or image (read_write)
+                buffer_entry(2, wgpu::BufferBindingType::Storage { read_only: false }, 16),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_glow_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipelines = ["bright_down", "down", "up", "composite"].map(|entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("crispen_glow_pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        });
+
+        Self {
+            pipelines,
+            bind_group_layout,
+            target: None,
+        }
+    }
+
+    /// Record the glow dispatches onto `encoder`, adding glow to `image`
+    /// in place. Records nothing when `glow` is off.
+    ///
+    /// The caller is responsible for submitting the encoder.
+    pub fn run(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        image: &GpuImageHandle,
+        glow: &Glow,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        if glow.is_identity() {
+            return;
+        }
+        let (width, height) = (image.width, image.height);
+        if self
+            .target
+            .as_ref()
+            .is_none_or(|t| t.width != width || t.height != height)
+        {
+            self.target = Some(GlowTarget::new(device, width, height));
+        }
+        let Some(target) = self.target.as_ref() else {
+            return;
+        };
+        let levels = &target.levels;
+        let Some(finest) = levels.first() else {
+            return;
+        };
+
+        // (stage, source, destination) in dispatch order.
+        let mut dispatches = vec![(Stage::BrightDown, image, finest)];
+        dispatches.extend(levels.windows(2).map(|w| (Stage::Down, &w[0], &w[1])));
+        dispatches.extend(levels.windows(2).rev().map(|w| (Stage::Up, &w[1], &w[0])));
+        dispatches.push((Stage::Composite, finest, image));
+
+        let [r, g, b] = glow.tint;
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("crispen_glow_pass"),
+            timestamp_writes: None,
+        });
+        for ((stage, src, dst), uniform) in dispatches.into_iter().zip(&target.steps) {
+            let step = GlowStep {
+                src_width: src.width,
+                src_height: src.height,
+                dst_width: dst.width,
+                dst_height: dst.height,
+                threshold: glow.threshold,
+                spread: glow.spread,
+                scale: glow.composite_scale(levels.len()),
+                _pad: 0.0,
+                tint: [r, g, b, 0.0],
+            };
+            queue.write_buffer(uniform, 0, bytemuck::bytes_of(&step));
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("crispen_glow_bind_group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uniform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: src.buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: dst.buffer.as_entire_binding(),
+                    },
+                ],
+            });
+            pass.set_pipeline(&self.pipelines[stage as usize]);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                dst.width.div_ceil(WORKGROUP_SIZE),
+                dst.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+    }
+}
@@ -3,29 +3,53 @@
 //! This crate owns all GPU resources. No Bevy dependency — it exposes a
 //! plain wgpu API that `crispen-bevy` wraps into ECS resources and systems.
 
+use crispen_core::color_management::color_space::ColorMatrix;
+use crispen_core::color_management::white_balance::white_balance_matrix;
+use crispen_core::grading::blend::BlendMode;
+use crispen_core::grading::channel_mixer::ChannelMixer;
+use crispen_core::grading::sliders::{HueModel, SaturationModel};
+use crispen_core::grading::soft_clip::SoftClipMode;
 use crispen_core::transform::params::{ColorSpaceId, GradingParams};
 
 pub mod async_readback;
 pub mod buffers;
+pub mod chromatic_aberration;
+pub mod dehaze;
+pub mod delta_e;
 pub mod format_converter;
+pub mod glow;
+pub mod look_preview;
 pub mod lut_applicator;
 pub mod lut_baker;
+pub mod lut_cache;
+pub mod mask_raster;
 pub mod pipeline;
+pub mod pixel_repair;
 pub mod readback;
 pub mod scope_dispatch;
+pub mod specialization;
 pub mod vulkan_interop;
 
-pub use async_readback::AsyncFrameResult;
-pub use buffers::{GpuImageHandle, GpuLutHandle, ScopeBuffers, ScopeConfig};
+pub use async_readback::{AsyncFrameResult, DEFAULT_READBACK_SLOTS, ReadbackStats};
+pub use buffers::{GpuImageHandle, GpuLutHandle, ScopeBuffers, ScopeConfig, auto_sample_stride};
+pub use delta_e::DeltaEResult;
 pub use format_converter::ViewerFormat;
-pub use pipeline::{FrameResult, GpuGradingPipeline, required_features};
+pub use lut_cache::{DEFAULT_LUT_CACHE_CAPACITY, LutCacheStats};
+pub use mask_raster::MaskOverlay;
+pub use pipeline::{FrameResult, GpuGradingPipeline, available_adapters, required_features};
 pub use readback::ScopeResults;
 
 /// GPU-compatible grading parameters packed for a wgpu uniform buffer.
 ///
 /// WGSL uniform buffers require 16-byte alignment for `vec4<f32>`.
 /// Layout: 4 vec4s (64 bytes) then scalars in groups of 4 (16 bytes each)
-/// then color space IDs and OCIO flag. Total: 128 bytes.
+/// then color space IDs and OCIO flag, then the chart-matched input
+/// correction (gain, power, and matrix rows as vec4s), then the input
+/// levels' black and white code values, the soft clip, the saturation and
+/// hue models with vibrance and the black and white switch, the per-range
+/// hue offsets, the channel mixer rows, the black and white weights and
+/// tint, the exposure, transform bypass, white balance matrix, negative
+/// policy, and the layer blend. Total: 480 bytes.
 ///
 /// The `Vec` curve fields from [`GradingParams`] are excluded — they are
 /// baked to 1D textures on the CPU and bound separately.
@@ -60,13 +84,79 @@ pub struct GradingParamsGpu {
     pub output_space: u32,
     /// Display OETF to invert after OCIO ODT (0=Linear, 1=sRGB, 2=PQ, 3=HLG).
     pub display_oetf: u32,
-    pub _pad0: u32,
-    pub _pad1: u32,
+    /// Sample the imported input LUT instead of the input transform (0/1).
+    pub use_input_lut: u32,
+    /// Soft clip on luma instead of per channel (0/1).
+    pub soft_clip_luma: u32,
+
+    // Input correction (w components unused)
+    pub input_gain: [f32; 4],
+    pub input_power: [f32; 4],
+    pub input_matrix_r: [f32; 4],
+    pub input_matrix_g: [f32; 4],
+    pub input_matrix_b: [f32; 4],
+
+    // Input levels as code values (w components unused)
+    pub input_black: [f32; 4],
+    pub input_white: [f32; 4],
+
+    /// Soft clip knee, strength, toe, and toe strength.
+    pub soft_clip: [f32; 4],
+
+    // Scalar group 5 (16 bytes)
+    /// Saturation model (0=Luma, 1=OKLab, 2=JzAzBz).
+    pub saturation_model: u32,
+    /// Hue rotation plane (0=RGB, 1=OKLCh).
+    pub hue_model: u32,
+    pub vibrance: f32,
+    /// Convert to black and white (0/1).
+    pub monochrome: u32,
+
+    /// Shadow, midtone, and highlight hue offsets in degrees (w unused).
+    pub hue_ranges: [f32; 4],
+
+    /// Channel mixer rows over input R, G, B, with the offset in w.
+    pub mixer_r: [f32; 4],
+    pub mixer_g: [f32; 4],
+    pub mixer_b: [f32; 4],
+
+    /// Black and white weights, normalized, with the response contrast in w.
+    pub mono_weights: [f32; 4],
+    /// Black and white tint gain (w unused).
+    pub mono_tint: [f32; 4],
+    /// Exposure scale (`2^stops`) and code-value log offset (zw unused).
+    pub exposure: [f32; 4],
+    /// Skip the input transform (x) and output transform (y) (0/1, zw
+    /// unused).
+    pub transform_bypass: [u32; 4],
+    /// White balance matrix rows (w unused), built on the CPU so both
+    /// white balance models match the reference exactly.
+    pub white_balance_r: [f32; 4],
+    pub white_balance_g: [f32; 4],
+    pub white_balance_b: [f32; 4],
+    /// `NegativePolicy` of the input and output conversions (yzw unused).
+    pub negative_policy: [u32; 4],
+    /// Layer mix (x) and blend mode (y: 0=Normal, 1=Multiply, 2=Overlay,
+    /// 3=Color) of the grade over its input (zw unused).
+    pub layer_blend: [f32; 4],
 }
 
 impl GradingParamsGpu {
     /// Convert from the core [`GradingParams`] to the GPU-compatible layout.
-    pub fn from_params(params: &GradingParams, use_ocio: bool) -> Self {
+    /// Bypassed sections are written as their identity values.
+    pub fn from_params(params: &GradingParams, use_ocio: bool, use_input_lut: bool) -> Self {
+        let params = &params.with_bypass_applied();
+        let ic = &params.input_correction;
+        let (input_black, input_white) = params.input_levels.code_values();
+        let sc = &params.soft_clip;
+        let mono = &params.monochrome;
+        let exposure = &params.exposure;
+        let white_balance = white_balance_matrix(
+            params.temperature,
+            params.tint,
+            params.white_balance_model,
+            params.color_management.working_space,
+        );
         Self {
             lift: params.combined_lift(),
             gamma: params.combined_gamma(),
@@ -86,12 +176,76 @@ impl GradingParamsGpu {
             working_space: color_space_to_u32(&params.color_management.working_space),
             output_space: color_space_to_u32(&params.color_management.output_space),
             display_oetf: params.color_management.display_oetf.to_u32(),
-            _pad0: 0,
-            _pad1: 0,
+            use_input_lut: u32::from(use_input_lut),
+            soft_clip_luma: u32::from(params.soft_clip.mode == SoftClipMode::Luma),
+            input_gain: vec3_to_vec4(ic.gain),
+            input_power: vec3_to_vec4(ic.power),
+            input_matrix_r: vec3_to_vec4(ic.matrix[0]),
+            input_matrix_g: vec3_to_vec4(ic.matrix[1]),
+            input_matrix_b: vec3_to_vec4(ic.matrix[2]),
+            input_black: vec3_to_vec4(input_black),
+            input_white: vec3_to_vec4(input_white),
+            soft_clip: [sc.knee, sc.strength, sc.toe, sc.toe_strength],
+            saturation_model: match params.saturation_model {
+                SaturationModel::Luma => 0,
+                SaturationModel::OkLab => 1,
+                SaturationModel::JzAzBz => 2,
+            },
+            hue_model: match params.hue_rotation.model {
+                HueModel::Rgb => 0,
+                HueModel::OkLch => 1,
+            },
+            vibrance: params.vibrance,
+            monochrome: u32::from(mono.enabled),
+            hue_ranges: vec3_to_vec4(params.hue_rotation.ranges),
+            mixer_r: mixer_row(&params.channel_mixer, 0),
+            mixer_g: mixer_row(&params.channel_mixer, 1),
+            mixer_b: mixer_row(&params.channel_mixer, 2),
+            mono_weights: {
+                let [r, g, b] = mono.normalized_weights();
+                [r, g, b, mono.contrast]
+            },
+            mono_tint: vec3_to_vec4(mono.tint_gain()),
+            exposure: [exposure.scale(), exposure.log_offset, 0.0, 0.0],
+            transform_bypass: [
+                u32::from(params.bypass.input_transform),
+                u32::from(params.bypass.output_transform),
+                0,
+                0,
+            ],
+            white_balance_r: matrix_row(&white_balance, 0),
+            white_balance_g: matrix_row(&white_balance, 1),
+            white_balance_b: matrix_row(&white_balance, 2),
+            negative_policy: [params.color_management.negative_policy.to_u32(), 0, 0, 0],
+            layer_blend: [
+                params.layer_blend.mix,
+                match params.layer_blend.mode {
+                    BlendMode::Normal => 0.0,
+                    BlendMode::Multiply => 1.0,
+                    BlendMode::Overlay => 2.0,
+                    BlendMode::Color => 3.0,
+                },
+                0.0,
+                0.0,
+            ],
         }
     }
 }
 
+fn vec3_to_vec4(v: [f32; 3]) -> [f32; 4] {
+    [v[0], v[1], v[2], 0.0]
+}
+
+fn matrix_row(matrix: &ColorMatrix, row: usize) -> [f32; 4] {
+    let m = matrix.0[row];
+    [m[0] as f32, m[1] as f32, m[2] as f32, 0.0]
+}
+
+fn mixer_row(mixer: &ChannelMixer, row: usize) -> [f32; 4] {
+    let m = mixer.matrix[row];
+    [m[0], m[1], m[2], mixer.offset[row]]
+}
+
 /// Map a [`ColorSpaceId`] to a `u32` for GPU uniform consumption.
 pub fn color_space_to_u32(id: &ColorSpaceId) -> u32 {
     match id {
@@ -0,0 +1,215 @@
+//! GPU look browser thumbnails: one proxy graded through many preview LUTs.
+//!
+//! Runs `look_preview.wgsl` with every look's small preview LUT packed into
+//! one storage buffer and the look index on workgroup z, so a whole
+//! thumbnail grid costs one upload, one dispatch, and one blocking
+//! readback. `crispen_core::transform::look_preview::render_thumbnail` is
+//! the CPU reference.
+
+use std::num::NonZeroU64;
+
+use crispen_core::image::GradingImage;
+use crispen_core::transform::lut::Lut3D;
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Uniform block of `look_preview.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PreviewParams {
+    width: u32,
+    height: u32,
+    lut_size: u32,
+    count: u32,
+}
+
+/// Owns the `look_preview.wgsl` pipeline.
+///
+/// Buffers are sized per call: the grid is rendered when the browser
+/// opens or the frame changes, not every frame.
+pub struct LookPreviewPass {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+}
+
+impl LookPreviewPass {
+    /// Create the pipeline. Compiles `look_preview.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_look_preview_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/look_preview.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let read = wgpu::BufferBindingType::Storage { read_only: true };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_look_preview_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 16),
+                // binding 1: proxy image (read)
+                buffer_entry(1, read, 16),
+                // binding 2: packed preview LUTs (read)
+                buffer_entry(2, read, 16),
+                // binding 3: packed RGBA8 thumbnails (read_write)
+                buffer_entry(3, wgpu::BufferBindingType::Storage { read_only: false }, 4),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_look_preview_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("crispen_look_preview_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("render_previews"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_look_preview_params"),
+                size: std::mem::size_of::<PreviewParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        }
+    }
+
+    /// Grade `proxy` through each of `luts` and return one RGBA8 thumbnail
+    /// (`proxy.width * proxy.height * 4` bytes) per LUT, in order. Blocks
+    /// until the readback completes.
+    ///
+    /// Every LUT must share one grid size and span the 0..1 domain, as
+    /// `LookEntry::preview_lut` bakes them. Returns `None` when `luts` is
+    /// empty, the sizes differ, or the proxy is empty.
+    pub fn render(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        proxy: &GradingImage,
+        luts: &[Lut3D],
+    ) -> Option<Vec<Vec<u8>>> {
+        let lut_size = luts.first()?.size;
+        if lut_size < 2 || luts.iter().any(|lut| lut.size != lut_size) {
+            return None;
+        }
+        let (width, height) = (proxy.width, proxy.height);
+        let pixels = u64::from(width) * u64::from(height);
+        if pixels == 0 {
+            return None;
+        }
+        let count = luts.len() as u32;
+
+        let params = PreviewParams {
+            width,
+            height,
+            lut_size,
+            count,
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+
+        let source = GpuImageHandle::upload(device, queue, proxy);
+        let packed: Vec<[f32; 4]> = luts
+            .iter()
+            .flat_map(|lut| lut.data.iter().copied())
+            .collect();
+        let lut_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("crispen_look_preview_luts"),
+            size: (packed.len() * 16) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&lut_buffer, 0, bytemuck::cast_slice(&packed));
+
+        let size = pixels * u64::from(count) * 4;
+        let buffer = |label, usage| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let thumbnails = buffer(
+            "crispen_look_preview_thumbnails",
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let staging = buffer(
+            "crispen_look_preview_staging",
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_look_preview_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: source.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: lut_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: thumbnails.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("crispen_look_preview_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("crispen_look_preview_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                count,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&thumbnails, 0, &staging, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        staging.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+        let bytes = staging.slice(..).get_mapped_range().to_vec();
+        staging.unmap();
+
+        Some(
+            bytes
+                .chunks_exact((pixels * 4) as usize)
+                .map(<[u8]>::to_vec)
+                .collect(),
+        )
+    }
+}
@@ -3,10 +3,14 @@
 use std::num::NonZeroU64;
 
 use crate::buffers::{GpuImageHandle, GpuLutHandle};
+use crate::specialization::{Overrides, ShaderVariant, VariantCache};
 
 /// Manages the `apply_lut.wgsl` compute pipeline and its resources.
+///
+/// Keeps a pipeline variant per common image width; see
+/// [`crate::specialization`].
 pub struct LutApplicator {
-    pipeline: wgpu::ComputePipeline,
+    pipelines: VariantCache,
     bind_group_layout: wgpu::BindGroupLayout,
     dimensions_buffer: wgpu::Buffer,
 }
@@ -82,14 +86,17 @@ impl LutApplicator {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("crispen_apply_lut_pipeline"),
-            layout: Some(&pipeline_layout),
-            module: &shader,
-            entry_point: Some("apply_lut"),
-            compilation_options: wgpu::PipelineCompilationOptions::default(),
-            cache: None,
-        });
+        let pipelines = VariantCache::new(
+            device,
+            "apply_lut",
+            shader,
+            pipeline_layout,
+            "apply_lut",
+            Overrides {
+                width: true,
+                mask: false,
+            },
+        );
 
         let dimensions_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("crispen_apply_dimensions_uniform"),
@@ -99,7 +106,7 @@ impl LutApplicator {
         });
 
         Self {
-            pipeline,
+            pipelines,
             bind_group_layout,
             dimensions_buffer,
         }
@@ -109,7 +116,7 @@ impl LutApplicator {
     ///
     /// The caller is responsible for submitting the encoder.
     pub fn apply(
-        &self,
+        &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         source: &GpuImageHandle,
@@ -155,7 +162,8 @@ impl LutApplicator {
                 label: Some("crispen_apply_lut_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&self.pipeline);
+            let variant = ShaderVariant::for_image(source.width, false);
+            pass.set_pipeline(self.pipelines.get(device, variant));
             pass.set_bind_group(0, &bind_group, &[]);
             pass.dispatch_workgroups(wg_x, wg_y, 1);
         }
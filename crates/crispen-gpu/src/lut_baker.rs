@@ -4,6 +4,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
 use std::num::NonZeroU64;
 
+use crispen_core::grading::curves::{CurveInterpolation, evaluate_curve};
 use crispen_core::transform::params::GradingParams;
 
 use crate::GradingParamsGpu;
@@ -27,6 +28,9 @@ pub struct LutBaker {
     ocio_odt_view: wgpu::TextureView,
     ocio_sampler: wgpu::Sampler,
     use_ocio: bool,
+    input_lut_texture: wgpu::Texture,
+    input_lut_view: wgpu::TextureView,
+    use_input_lut: bool,
     /// Hash of the last uploaded curve data (skip re-upload when unchanged).
     last_curve_hash: u64,
 }
@@ -100,6 +104,8 @@ impl LutBaker {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                // binding 11: optional imported input LUT (sampled with the OCIO sampler)
+                ocio_lut_texture_layout_entry(11),
             ],
         });
 
@@ -151,6 +157,9 @@ impl LutBaker {
         let ocio_idt_view = ocio_idt_texture.create_view(&wgpu::TextureViewDescriptor::default());
         let ocio_odt_texture = create_identity_ocio_lut_texture(device, queue, "crispen_ocio_odt");
         let ocio_odt_view = ocio_odt_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let input_lut_texture =
+            create_identity_ocio_lut_texture(device, queue, "crispen_input_lut");
+        let input_lut_view = input_lut_texture.create_view(&wgpu::TextureViewDescriptor::default());
         let ocio_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("crispen_ocio_lut_sampler"),
             address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -176,6 +185,9 @@ impl LutBaker {
             ocio_odt_view,
             ocio_sampler,
             use_ocio: false,
+            input_lut_texture,
+            input_lut_view,
+            use_input_lut: false,
             last_curve_hash: 0,
         }
     }
@@ -212,6 +224,30 @@ impl LutBaker {
         self.use_ocio = true;
     }
 
+    /// Upload an imported input LUT that replaces the input transform.
+    ///
+    /// `lut` maps source code values to working-space linear (see
+    /// `crispen_core::transform::lut_domain::bake_input_lut`). Passing `None`
+    /// restores the analytic or OCIO input transform.
+    pub fn set_input_lut(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        lut: Option<&[[f32; 4]]>,
+        size: u32,
+    ) {
+        let expected_len = size as usize * size as usize * size as usize;
+        let Some(data) = lut.filter(|data| size >= 2 && data.len() == expected_len) else {
+            self.use_input_lut = false;
+            return;
+        };
+
+        let texture = write_ocio_lut_texture(device, queue, data, size, "crispen_input_lut");
+        self.input_lut_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.input_lut_texture = texture;
+        self.use_input_lut = true;
+    }
+
     /// Upload curve data from `GradingParams` as 1D textures.
     ///
     /// Skips re-upload if curve data is unchanged since the last call.
@@ -222,20 +258,7 @@ impl LutBaker {
         params: &GradingParams,
     ) {
         // Hash curve control points to skip redundant texture uploads.
-        let mut hasher = DefaultHasher::new();
-        for curve in [
-            &params.hue_vs_hue,
-            &params.hue_vs_sat,
-            &params.lum_vs_sat,
-            &params.sat_vs_sat,
-        ] {
-            hasher.write_usize(curve.len());
-            for pt in curve {
-                hasher.write(&pt[0].to_ne_bytes());
-                hasher.write(&pt[1].to_ne_bytes());
-            }
-        }
-        let hash = hasher.finish();
+        let hash = curve_hash(params);
         if hash == self.last_curve_hash {
             return;
         }
@@ -248,17 +271,43 @@ impl LutBaker {
             (&params.sat_vs_sat, 3),
         ];
 
+        // Empty curves are rebaked too, so deleting every point resets the
+        // texture to identity instead of leaving the previous shape bound.
+        // Bypassed curves bake as empty ones.
         for (points, idx) in curves {
-            if points.is_empty() {
-                continue;
-            }
-            let lut_data = bake_curve_cpu(points, CURVE_LUT_SIZE as usize, idx == 0);
+            let points: &[[f32; 2]] = if params.bypass.curves { &[] } else { points };
+            let lut_data = bake_curve_cpu(
+                points,
+                CURVE_LUT_SIZE as usize,
+                idx == 0,
+                params.curve_interpolation,
+            );
             let texture = write_curve_texture(device, queue, &lut_data, idx);
             self.curve_views[idx] = texture.create_view(&wgpu::TextureViewDescriptor::default());
             self.curve_textures[idx] = texture;
         }
     }
 
+    /// Key identifying the LUT `bake` would produce for `params` at
+    /// `lut_size`.
+    ///
+    /// Covers the packed uniform block and the curves, so viewer-only and
+    /// spatial settings (glow, dehaze, chromatic aberration) never change
+    /// it. The input LUT and OCIO textures are not hashed; the pipeline
+    /// drops its cached LUTs when they are replaced.
+    pub fn bake_key(&self, params: &GradingParams, lut_size: u32) -> u64 {
+        let gpu_params = GradingParamsGpu::from_params(
+            params,
+            self.use_ocio,
+            self.use_input_lut && !params.bypass.input_lut,
+        );
+        let mut hasher = DefaultHasher::new();
+        hasher.write(bytemuck::bytes_of(&gpu_params));
+        hasher.write_u64(curve_hash(params));
+        hasher.write_u32(lut_size);
+        hasher.finish()
+    }
+
     /// Dispatch the LUT bake compute shader onto the given encoder.
     ///
     /// The caller is responsible for submitting the encoder.
@@ -270,7 +319,11 @@ impl LutBaker {
         lut: &GpuLutHandle,
         encoder: &mut wgpu::CommandEncoder,
     ) {
-        let gpu_params = GradingParamsGpu::from_params(params, self.use_ocio);
+        let gpu_params = GradingParamsGpu::from_params(
+            params,
+            self.use_ocio,
+            self.use_input_lut && !params.bypass.input_lut,
+        );
         queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&gpu_params));
 
         let size_bytes = [lut.size, 0u32, 0u32, 0u32];
@@ -324,6 +377,10 @@ impl LutBaker {
                     binding: 10,
                     resource: wgpu::BindingResource::Sampler(&self.ocio_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 11,
+                    resource: wgpu::BindingResource::TextureView(&self.input_lut_view),
+                },
             ],
         });
 
@@ -344,6 +401,26 @@ impl LutBaker {
 
 // ── Helpers ─────────────────────────────────────────────────────────
 
+/// Hash of the curve inputs `upload_curves` bakes into textures.
+fn curve_hash(params: &GradingParams) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u8(params.curve_interpolation as u8);
+    hasher.write_u8(u8::from(params.bypass.curves));
+    for curve in [
+        &params.hue_vs_hue,
+        &params.hue_vs_sat,
+        &params.lum_vs_sat,
+        &params.sat_vs_sat,
+    ] {
+        hasher.write_usize(curve.len());
+        for pt in curve {
+            hasher.write(&pt[0].to_ne_bytes());
+            hasher.write(&pt[1].to_ne_bytes());
+        }
+    }
+    hasher.finish()
+}
+
 fn curve_texture_layout_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
     wgpu::BindGroupLayoutEntry {
         binding,
@@ -484,8 +561,13 @@ fn write_ocio_lut_texture(
     texture
 }
 
-/// Bake curve control points to a 1D LUT via linear interpolation.
-fn bake_curve_cpu(points: &[[f32; 2]], size: usize, is_hue: bool) -> Vec<f32> {
+/// Bake curve control points to a 1D LUT with the requested interpolation.
+fn bake_curve_cpu(
+    points: &[[f32; 2]],
+    size: usize,
+    is_hue: bool,
+    interpolation: CurveInterpolation,
+) -> Vec<f32> {
     let identity = if is_hue { 0.0 } else { 1.0 };
     if points.is_empty() {
         return vec![identity; size];
@@ -494,27 +576,7 @@ fn bake_curve_cpu(points: &[[f32; 2]], size: usize, is_hue: bool) -> Vec<f32> {
     (0..size)
         .map(|i| {
             let t = i as f32 / (size - 1) as f32;
-            eval_curve_linear(points, t)
+            evaluate_curve(points, t, interpolation)
         })
         .collect()
 }
-
-fn eval_curve_linear(points: &[[f32; 2]], t: f32) -> f32 {
-    if points.is_empty() {
-        return t;
-    }
-    if t <= points[0][0] {
-        return points[0][1];
-    }
-    let last = points.len() - 1;
-    if t >= points[last][0] {
-        return points[last][1];
-    }
-    for i in 0..last {
-        if t >= points[i][0] && t <= points[i + 1][0] {
-            let frac = (t - points[i][0]) / (points[i + 1][0] - points[i][0]);
-            return points[i][1] + frac * (points[i + 1][1] - points[i][1]);
-        }
-    }
-    points[last][1]
-}
@@ -0,0 +1,98 @@
+//! Least-recently-used cache of baked grading LUTs.
+//!
+//! Switching between grade versions, or toggling a bypass and back, asks
+//! for a LUT that was baked moments ago. The pipeline keys every bake with
+//! [`LutBaker::bake_key`](crate::lut_baker::LutBaker::bake_key) and parks
+//! the LUT it replaces here, so returning to a recent grade swaps a texture
+//! in instead of re-running `bake_lut.wgsl`.
+
+use crate::buffers::GpuLutHandle;
+
+/// Baked LUTs kept besides the current one by default. At 65³ each takes
+/// about 4.4 MB of GPU memory.
+pub const DEFAULT_LUT_CACHE_CAPACITY: usize = 8;
+
+/// Bake counters since the pipeline was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LutCacheStats {
+    /// Grades served by the current or a cached LUT without a bake.
+    pub hits: u64,
+    /// Grades that needed a bake.
+    pub misses: u64,
+    /// LUTs currently cached, not counting the current one.
+    pub entries: usize,
+}
+
+/// Baked LUTs by bake key, least recently used first.
+pub struct LutCache {
+    entries: Vec<(u64, GpuLutHandle)>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl LutCache {
+    /// An empty cache holding up to `capacity` LUTs.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Remove and return the LUT baked for `key`.
+    pub fn take(&mut self, key: u64) -> Option<GpuLutHandle> {
+        let index = self.entries.iter().position(|(k, _)| *k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Cache `lut` as the most recently used entry. Returns the LUT evicted
+    /// to make room, so its texture can be baked into again.
+    pub fn insert(&mut self, key: u64, lut: GpuLutHandle) -> Option<GpuLutHandle> {
+        if let Some(replaced) = self.take(key) {
+            self.entries.push((key, lut));
+            return Some(replaced);
+        }
+        self.entries.push((key, lut));
+        (self.entries.len() > self.capacity).then(|| self.entries.remove(0).1)
+    }
+
+    /// Drop every cached LUT, e.g. when the input LUT or OCIO transforms
+    /// they were baked with change.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Change how many LUTs are kept, evicting the least recently used.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess);
+    }
+
+    /// Maximum number of cached LUTs.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Count a grade served without a bake.
+    pub fn record_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    /// Count a grade that needed a bake.
+    pub fn record_miss(&mut self) {
+        self.misses += 1;
+    }
+
+    /// Hit and miss counters and the current entry count.
+    pub fn stats(&self) -> LutCacheStats {
+        LutCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! GPU rasterization of the scope mask polygon and its viewer overlay.
+//!
+//! Both passes run `scope_mask.wgsl`: one invocation per pixel tests the
+//! pixel center against the polygon with the even-odd rule. The mask pass
+//! writes straight into the scope mask buffer, so a polygon edit never
+//! round-trips a full-resolution mask through the CPU. The overlay pass
+//! renders the shaded outline for the viewer into a packed RGBA8 buffer
+//! that is read back asynchronously.
+
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Uniform block of `scope_mask.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct MaskParams {
+    width: u32,
+    height: u32,
+    vertex_count: u32,
+    _pad: u32,
+}
+
+/// A rendered overlay: `width * height` RGBA8 pixels (sRGB-encoded).
+#[derive(Debug, Clone)]
+pub struct MaskOverlay {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// An overlay render waiting for the previous readback to finish.
+struct OverlayRequest {
+    polygon: Vec<[f32; 2]>,
+    width: u32,
+    height: u32,
+}
+
+/// Per-pass inputs. The mask and overlay passes keep separate buffers so
+/// both can be queued before either submission executes.
+struct PassInputs {
+    params: wgpu::Buffer,
+    vertices: wgpu::Buffer,
+}
+
+impl PassInputs {
+    fn new(device: &wgpu::Device, label: &str) -> Self {
+        Self {
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(&format!("crispen_{label}_params")),
+                size: std::mem::size_of::<MaskParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            vertices: vertex_buffer(device, label, 16),
+        }
+    }
+
+    /// Upload the polygon and image size, growing the vertex buffer if needed.
+    fn write(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        label: &str,
+        polygon: &[[f32; 2]],
+        width: u32,
+        height: u32,
+    ) {
+        let params = MaskParams {
+            width,
+            height,
+            vertex_count: polygon.len() as u32,
+            _pad: 0,
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+
+        let bytes: &[u8] = bytemuck::cast_slice(polygon);
+        if self.vertices.size() < bytes.len() as u64 {
+            self.vertices = vertex_buffer(device, label, bytes.len() as u64);
+        }
+        if !bytes.is_empty() {
+            queue.write_buffer(&self.vertices, 0, bytes);
+        }
+    }
+}
+
+/// Overlay output and its staging copy for one overlay size.
+struct OverlayTarget {
+    width: u32,
+    height: u32,
+    output: wgpu::Buffer,
+    staging: wgpu::Buffer,
+    /// Set by the `map_async` callback.
+    mapped: Arc<AtomicBool>,
+    in_flight: bool,
+}
+
+impl OverlayTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let size = u64::from(width) * u64::from(height) * 4;
+        Self {
+            width,
+            height,
+            output: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_mask_overlay"),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            staging: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_mask_overlay_staging"),
+                size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            mapped: Arc::new(AtomicBool::new(false)),
+            in_flight: false,
+        }
+    }
+}
+
+/// Owns the `scope_mask.wgsl` pipelines and the overlay readback.
+///
+/// Overlay renders are latest-wins: while one readback is in flight, a
+/// newer request replaces any request still waiting behind it.
+pub struct MaskRasterizer {
+    mask_pipeline: wgpu::ComputePipeline,
+    overlay_pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    mask_inputs: PassInputs,
+    overlay_inputs: PassInputs,
+    overlay: Option<OverlayTarget>,
+    queued: Option<OverlayRequest>,
+}
+
+impl MaskRasterizer {
+    /// Create both pipelines. Compiles `scope_mask.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_scope_mask_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/scope_mask.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_scope_mask_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 16),
+                // binding 1: polygon vertices (read)
+                buffer_entry(1, wgpu::BufferBindingType::Storage { read_only: true }, 8),
+                // binding 2: mask or overlay pixels (read_write)
+                buffer_entry(2, wgpu::BufferBindingType::Storage { read_only: false }, 4),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_scope_mask_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(&format!("crispen_{entry_point}_pipeline")),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        Self {
+            mask_pipeline: pipeline("rasterize_mask"),
+            overlay_pipeline: pipeline("render_overlay"),
+            bind_group_layout,
+            mask_inputs: PassInputs::new(device, "scope_mask"),
+            overlay_inputs: PassInputs::new(device, "mask_overlay"),
+            overlay: None,
+            queued: None,
+        }
+    }
+
+    /// Rasterize `polygon` (normalized coordinates) into `mask`, one u32 per
+    /// pixel of a `width x height` image, and submit the pass.
+    ///
+    /// Fewer than three vertices produce an empty mask.
+    pub fn rasterize(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        polygon: &[[f32; 2]],
+        width: u32,
+        height: u32,
+        mask: &wgpu::Buffer,
+    ) {
+        self.mask_inputs
+            .write(device, queue, "scope_mask", polygon, width, height);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("crispen_scope_mask_encoder"),
+        });
+        self.encode(
+            device,
+            &mut encoder,
+            &self.mask_pipeline,
+            &self.mask_inputs,
+            mask,
+            width,
+            height,
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Request an overlay render of `polygon` at `width x height`.
+    ///
+    /// Starts immediately when no overlay readback is in flight; otherwise
+    /// replaces any request already waiting. Collect the result with
+    /// [`Self::poll_overlay`].
+    pub fn request_overlay(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        polygon: &[[f32; 2]],
+        width: u32,
+        height: u32,
+    ) {
+        self.queued = Some(OverlayRequest {
+            polygon: polygon.to_vec(),
+            width: width.max(1),
+            height: height.max(1),
+        });
+        self.start_queued(device, queue);
+    }
+
+    /// Non-blocking: return the finished overlay, if any, and start the next
+    /// queued request.
+    pub fn poll_overlay(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<MaskOverlay> {
+        let target = self.overlay.as_mut().filter(|t| t.in_flight)?;
+        let _ = device.poll(wgpu::PollType::Poll);
+        if !target.mapped.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let rgba = target.staging.slice(..).get_mapped_range().to_vec();
+        target.staging.unmap();
+        target.in_flight = false;
+        let overlay = MaskOverlay {
+            width: target.width,
+            height: target.height,
+            rgba,
+        };
+        self.start_queued(device, queue);
+        Some(overlay)
+    }
+
+    /// Render and map the queued request unless a readback is in flight.
+    fn start_queued(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.overlay.as_ref().is_some_and(|t| t.in_flight) {
+            return;
+        }
+        let Some(request) = self.queued.take() else {
+            return;
+        };
+        let (width, height) = (request.width, request.height);
+        if self
+            .overlay
+            .as_ref()
+            .is_none_or(|t| t.width != width || t.height != height)
+        {
+            self.overlay = Some(OverlayTarget::new(device, width, height));
+        }
+        let Some(target) = self.overlay.as_ref() else {
+            return;
+        };
+
+        self.overlay_inputs.write(
+            device,
+            queue,
+            "mask_overlay",
+            &request.polygon,
+            width,
+            height,
+        );
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("crispen_mask_overlay_encoder"),
+        });
+        self.encode(
+            device,
+            &mut encoder,
+            &self.overlay_pipeline,
+            &self.overlay_inputs,
+            &target.output,
+            width,
+            height,
+        );
+        encoder.copy_buffer_to_buffer(&target.output, 0, &target.staging, 0, target.staging.size());
+        queue.submit(std::iter::once(encoder.finish()));
+
+        target.mapped.store(false, Ordering::Release);
+        let mapped = Arc::clone(&target.mapped);
+        target
+            .staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                mapped.store(result.is_ok(), Ordering::Release);
+            });
+        if let Some(target) = self.overlay.as_mut() {
+            target.in_flight = true;
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn encode(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        inputs: &PassInputs,
+        output: &wgpu::Buffer,
+        width: u32,
+        height: u32,
+    ) {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_scope_mask_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: inputs.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: inputs.vertices.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("crispen_scope_mask_pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        );
+    }
+}
+
+fn vertex_buffer(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(&format!("crispen_{label}_vertices")),
+        size: size.max(16),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
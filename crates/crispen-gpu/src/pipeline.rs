@@ -2,20 +2,33 @@
 
 use std::sync::Arc;
 
-use crispen_core::image::GradingImage;
-use crispen_core::transform::params::GradingParams;
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::{GradingImage, HalfImage, SourceImage};
+use crispen_core::transform::lut::Lut3D;
+use crispen_core::transform::params::{ColorSpaceId, GradingParams};
 
-use crate::async_readback::AsyncReadback;
+use crate::async_readback::{AsyncReadback, DEFAULT_READBACK_SLOTS, ReadbackStats};
 use crate::buffers::{GpuImageHandle, GpuLutHandle, ScopeBuffers, ScopeConfig};
+use crate::chromatic_aberration::ChromaticAberrationPass;
+use crate::dehaze::DehazePass;
+use crate::delta_e::{DeltaECompare, DeltaEResult};
 use crate::format_converter::{FormatConverter, ViewerFormat};
+use crate::glow::GlowPass;
+use crate::look_preview::LookPreviewPass;
 use crate::lut_applicator::LutApplicator;
 use crate::lut_baker::LutBaker;
+use crate::lut_cache::{DEFAULT_LUT_CACHE_CAPACITY, LutCache, LutCacheStats};
+use crate::mask_raster::{MaskOverlay, MaskRasterizer};
+use crate::pixel_repair::PixelRepairPass;
 use crate::readback::{Readback, ScopeResults};
 use crate::scope_dispatch::ScopeDispatch;
 
 /// Results from a single frame submission.
 pub struct FrameResult {
     /// Raw pixel bytes for the viewer (f16 or f32 depending on format).
+    ///
+    /// Async results come from a buffer pool; hand the buffer back with
+    /// [`GpuGradingPipeline::recycle_viewer_bytes`] once it is no longer needed.
     pub viewer_bytes: Vec<u8>,
     /// Image width in pixels.
     pub width: u32,
@@ -35,24 +48,48 @@ pub fn required_features() -> wgpu::Features {
     wgpu::Features::FLOAT32_FILTERABLE
 }
 
-/// Orchestrates the full GPU grading pipeline: LUT bake → apply → scopes.
+/// Lists the adapters [`GpuGradingPipeline::create_blocking_with_adapter`]
+/// can choose from, for adapter pickers.
+pub fn available_adapters() -> Vec<wgpu::AdapterInfo> {
+    wgpu::Instance::new(&wgpu::InstanceDescriptor::default())
+        .enumerate_adapters(wgpu::Backends::all())
+        .iter()
+        .map(wgpu::Adapter::get_info)
+        .collect()
+}
+
+/// Orchestrates the full GPU grading pipeline: LUT bake → pixel repair →
+/// chromatic aberration → dehaze → apply → glow → scopes.
 pub struct GpuGradingPipeline {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
-    adapter_backend: wgpu::Backend,
+    adapter_info: Option<wgpu::AdapterInfo>,
     enabled_features: wgpu::Features,
     lut_baker: LutBaker,
     lut_applicator: LutApplicator,
+    repair_pass: PixelRepairPass,
+    ca_pass: ChromaticAberrationPass,
+    dehaze_pass: DehazePass,
+    glow_pass: GlowPass,
     format_converter: FormatConverter,
     scope_dispatch: ScopeDispatch,
+    mask_rasterizer: MaskRasterizer,
+    delta_e_compare: DeltaECompare,
+    look_preview: LookPreviewPass,
     current_lut: Option<GpuLutHandle>,
+    /// Bake key of `current_lut`; `None` until it is baked.
+    current_lut_key: Option<u64>,
+    /// Recently baked LUTs, for switching back without a re-bake.
+    lut_cache: LutCache,
     current_output: Option<GpuImageHandle>,
     scope_buffers: Option<ScopeBuffers>,
     readback: Option<Readback>,
     /// Cached staging buffer for blocking image readback (legacy path).
     image_readback_staging: Option<wgpu::Buffer>,
-    /// Double-buffered async readback (primary path).
+    /// Ring-buffered async readback (primary path).
     async_readback: Option<AsyncReadback>,
+    /// Slot count for the async readback ring.
+    readback_slots: usize,
     scope_config: ScopeConfig,
     viewer_format: ViewerFormat,
     /// Per-scope visibility flags (skips GPU compute when hidden).
@@ -60,10 +97,6 @@ pub struct GpuGradingPipeline {
     scope_waveform_visible: bool,
     scope_vectorscope_visible: bool,
     scope_cie_visible: bool,
-    /// Dimensions + format of the last async submission (for FrameResult).
-    last_async_width: u32,
-    last_async_height: u32,
-    last_async_viewer_byte_size: u64,
 }
 
 impl GpuGradingPipeline {
@@ -72,14 +105,34 @@ impl GpuGradingPipeline {
     /// Blocks on async wgpu calls via `pollster`. Call this once at startup
     /// (e.g. from a Bevy startup system) and store the result as a resource.
     pub fn create_blocking() -> Result<Self, String> {
+        Self::create_blocking_with_adapter(None)
+    }
+
+    /// Like [`create_blocking`](Self::create_blocking), but uses the adapter
+    /// whose name matches `preferred` when one is present. Falls back to the
+    /// default high-performance adapter otherwise.
+    pub fn create_blocking_with_adapter(preferred: Option<&str>) -> Result<Self, String> {
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            compatible_surface: None,
-            force_fallback_adapter: false,
-        }))
-        .map_err(|e| format!("no suitable GPU adapter found: {e}"))?;
-        let adapter_backend = adapter.get_info().backend;
+        let named = preferred.and_then(|name| {
+            let found = instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .into_iter()
+                .find(|adapter| adapter.get_info().name == name);
+            if found.is_none() {
+                tracing::warn!("GPU adapter '{name}' not found; using the default adapter");
+            }
+            found
+        });
+        let adapter = match named {
+            Some(adapter) => adapter,
+            None => pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            }))
+            .map_err(|e| format!("no suitable GPU adapter found: {e}"))?,
+        };
+        let adapter_info = adapter.get_info();
         let required_features = required_features();
 
         let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
@@ -94,7 +147,7 @@ impl GpuGradingPipeline {
         Ok(Self::new_with_metadata(
             Arc::new(device),
             Arc::new(queue),
-            adapter_backend,
+            Some(adapter_info),
             enabled_features,
         ))
     }
@@ -102,50 +155,72 @@ impl GpuGradingPipeline {
     /// Create the full GPU pipeline. Compiles all shaders.
     pub fn new(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Self {
         let enabled_features = device.features();
-        Self::new_with_metadata(device, queue, wgpu::Backend::Noop, enabled_features)
+        Self::new_with_metadata(device, queue, None, enabled_features)
     }
 
     fn new_with_metadata(
         device: Arc<wgpu::Device>,
         queue: Arc<wgpu::Queue>,
-        adapter_backend: wgpu::Backend,
+        adapter_info: Option<wgpu::AdapterInfo>,
         enabled_features: wgpu::Features,
     ) -> Self {
         let lut_baker = LutBaker::new(&device, &queue);
         let lut_applicator = LutApplicator::new(&device);
+        let repair_pass = PixelRepairPass::new(&device);
+        let ca_pass = ChromaticAberrationPass::new(&device);
+        let dehaze_pass = DehazePass::new(&device);
+        let glow_pass = GlowPass::new(&device);
         let format_converter = FormatConverter::new(&device);
         let scope_dispatch = ScopeDispatch::new(&device);
+        let mask_rasterizer = MaskRasterizer::new(&device);
+        let delta_e_compare = DeltaECompare::new(&device);
+        let look_preview = LookPreviewPass::new(&device);
 
         Self {
             device,
             queue,
-            adapter_backend,
+            adapter_info,
             enabled_features,
             lut_baker,
             lut_applicator,
+            repair_pass,
+            ca_pass,
+            dehaze_pass,
+            glow_pass,
             format_converter,
             scope_dispatch,
+            mask_rasterizer,
+            delta_e_compare,
+            look_preview,
             current_lut: None,
+            current_lut_key: None,
+            lut_cache: LutCache::new(DEFAULT_LUT_CACHE_CAPACITY),
             current_output: None,
             scope_buffers: None,
             readback: None,
             image_readback_staging: None,
             async_readback: None,
+            readback_slots: DEFAULT_READBACK_SLOTS,
             scope_config: ScopeConfig::default(),
             viewer_format: ViewerFormat::Srgb8,
             scope_histogram_visible: true,
             scope_waveform_visible: true,
             scope_vectorscope_visible: true,
             scope_cie_visible: true,
-            last_async_width: 0,
-            last_async_height: 0,
-            last_async_viewer_byte_size: 0,
         }
     }
 
     /// Access the backend used by the underlying adapter (when known).
     pub fn adapter_backend(&self) -> wgpu::Backend {
-        self.adapter_backend
+        self.adapter_info
+            .as_ref()
+            .map_or(wgpu::Backend::Noop, |info| info.backend)
+    }
+
+    /// Name, vendor, driver, and backend of the adapter, when the pipeline
+    /// created its own device.
+    pub fn adapter_info(&self) -> Option<&wgpu::AdapterInfo> {
+        self.adapter_info.as_ref()
     }
 
     /// Access the enabled features on the underlying device.
@@ -168,6 +243,31 @@ impl GpuGradingPipeline {
         GpuImageHandle::upload(&self.device, &self.queue, image)
     }
 
+    /// Upload a half-float image. It crosses the bus as packed f16 —
+    /// half the bytes of [`Self::upload_image`] — and is expanded on the
+    /// GPU into the f32 buffer the passes read.
+    pub fn upload_half_image(&self, image: &HalfImage) -> GpuImageHandle {
+        let packed = GpuImageHandle::upload_packed_f16(&self.device, image);
+        let handle = GpuImageHandle::create_output(&self.device, image.width, image.height);
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("crispen_unpack_f16_encoder"),
+            });
+        self.format_converter
+            .unpack_f16(&self.device, &packed, &handle, &mut encoder);
+        self.queue.submit(std::iter::once(encoder.finish()));
+        handle
+    }
+
+    /// Upload a source image in whichever precision it is held.
+    pub fn upload_source(&self, source: &SourceImage) -> GpuImageHandle {
+        match source {
+            SourceImage::Full(image) => self.upload_image(image),
+            SourceImage::Half(image) => self.upload_half_image(image),
+        }
+    }
+
     /// Download a graded image from the GPU. Blocks until complete.
     pub fn download_image(&mut self, handle: &GpuImageHandle) -> GradingImage {
         Readback::download_image(
@@ -189,6 +289,55 @@ impl GpuGradingPipeline {
         ))
     }
 
+    /// Copy the most recently graded output into a new GPU image, so it
+    /// survives the next grade (e.g. as a reference for
+    /// [`Self::compare_delta_e`]).
+    pub fn snapshot_current_output(&self) -> Option<GpuImageHandle> {
+        let output = self.current_output.as_ref()?;
+        let snapshot = GpuImageHandle::create_output(&self.device, output.width, output.height);
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("crispen_snapshot_output_encoder"),
+            });
+        encoder.copy_buffer_to_buffer(&output.buffer, 0, &snapshot.buffer, 0, output.byte_size());
+        self.queue.submit(std::iter::once(encoder.finish()));
+        Some(snapshot)
+    }
+
+    /// Blocking: per-pixel ΔE2000 between the most recently graded output
+    /// and `reference`, both in `output_space` code values, with a heatmap
+    /// that reaches red at `scale`. `None` without an output or when the
+    /// sizes differ.
+    pub fn compare_delta_e(
+        &mut self,
+        reference: &GpuImageHandle,
+        output_space: ColorSpaceId,
+        scale: f32,
+    ) -> Option<DeltaEResult> {
+        let output = self.current_output.as_ref()?;
+        self.delta_e_compare.compare(
+            &self.device,
+            &self.queue,
+            output,
+            reference,
+            output_space,
+            scale,
+        )
+    }
+
+    /// Blocking: grade `proxy` through each preview LUT in one dispatch
+    /// and return an RGBA8 thumbnail per LUT, for the look browser. `None`
+    /// when `luts` is empty or their sizes differ.
+    pub fn render_look_previews(
+        &self,
+        proxy: &GradingImage,
+        luts: &[Lut3D],
+    ) -> Option<Vec<Vec<u8>>> {
+        self.look_preview
+            .render(&self.device, &self.queue, proxy, luts)
+    }
+
     /// Submit the full grading pipeline in a single GPU submission:
     /// bake LUT + apply LUT + format convert + scopes + staging copies.
     ///
@@ -200,16 +349,11 @@ impl GpuGradingPipeline {
         params: &GradingParams,
         lut_size: u32,
     ) -> FrameResult {
-        // Upload curve textures (immediate, no encoder needed).
-        self.lut_baker
-            .upload_curves(&self.device, &self.queue, params);
-
-        // Ensure LUT handle exists.
-        let lut = self
-            .current_lut
-            .get_or_insert_with(|| GpuLutHandle::new(&self.device, lut_size));
-        if lut.size != lut_size {
-            *lut = GpuLutHandle::new(&self.device, lut_size);
+        // Reuse a cached LUT, or upload curve textures for a bake.
+        let needs_bake = self.select_lut(params, lut_size);
+        if needs_bake {
+            self.lut_baker
+                .upload_curves(&self.device, &self.queue, params);
         }
 
         // Ensure output handle exists.
@@ -220,10 +364,7 @@ impl GpuGradingPipeline {
             *output = GpuImageHandle::create_output(&self.device, source.width, source.height);
         }
 
-        let cfg = self.scope_config;
-        let _scope_buffers = self
-            .scope_buffers
-            .get_or_insert_with(|| ScopeBuffers::new(&self.device, &cfg, source.width));
+        let cfg = self.sync_scope_resources(source.width);
 
         let _readback = self
             .readback
@@ -236,15 +377,49 @@ impl GpuGradingPipeline {
                 label: Some("crispen_frame_encoder"),
             });
 
-        // 1. Bake LUT.
+        // 1. Bake LUT unless it came from the cache.
         let lut = self.current_lut.as_ref().unwrap();
-        self.lut_baker
-            .bake(&self.device, &self.queue, params, lut, &mut encoder);
+        if needs_bake {
+            self.lut_baker
+                .bake(&self.device, &self.queue, params, lut, &mut encoder);
+        }
 
-        // 2. Apply LUT to source image.
+        // 2. Repair, realign, and dehaze (spatial, when enabled), then apply the LUT.
+        let source = self
+            .repair_pass
+            .run(&self.device, &self.queue, source, &mut encoder)
+            .unwrap_or(source);
+        let source = self
+            .ca_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.chromatic_aberration,
+                &mut encoder,
+            )
+            .unwrap_or(source);
+        let source = self
+            .dehaze_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.dehaze,
+                &mut encoder,
+            )
+            .unwrap_or(source);
         let output = self.current_output.as_ref().unwrap();
         self.lut_applicator
             .apply(&self.device, &self.queue, source, lut, output, &mut encoder);
+        // Post-grade effects on the graded output (spatial, when enabled).
+        self.glow_pass.run(
+            &self.device,
+            &self.queue,
+            output,
+            &params.glow,
+            &mut encoder,
+        );
 
         // 3. Format conversion + staging copy for viewer image.
         let pixel_count = output.pixel_count();
@@ -310,6 +485,7 @@ impl GpuGradingPipeline {
             cfg.waveform_height,
             cfg.vectorscope_resolution,
             cfg.cie_resolution,
+            cfg.sample_stride_for(output.pixel_count()),
             &mut encoder,
             self.scope_histogram_visible,
             self.scope_waveform_visible,
@@ -362,13 +538,10 @@ impl GpuGradingPipeline {
 
     /// Bake grading parameters into a 3D LUT (legacy single-step API).
     pub fn bake_lut(&mut self, params: &GradingParams, lut_size: u32) {
-        let lut = self
-            .current_lut
-            .get_or_insert_with(|| GpuLutHandle::new(&self.device, lut_size));
-
-        if lut.size != lut_size {
-            *lut = GpuLutHandle::new(&self.device, lut_size);
+        if !self.select_lut(params, lut_size) {
+            return;
         }
+        let lut = self.current_lut.as_ref().unwrap();
 
         self.lut_baker
             .upload_curves(&self.device, &self.queue, params);
@@ -383,6 +556,39 @@ impl GpuGradingPipeline {
         self.queue.submit(std::iter::once(encoder.finish()));
     }
 
+    /// Make `current_lut` the LUT for `params` at `lut_size`. Returns
+    /// whether it still has to be baked.
+    ///
+    /// The LUT it replaces is parked in the cache. A cached LUT with the
+    /// same bake key is swapped in; on a miss the LUT the cache evicted is
+    /// baked into again, so a disabled cache allocates no more than before.
+    fn select_lut(&mut self, params: &GradingParams, lut_size: u32) -> bool {
+        let key = self.lut_baker.bake_key(params, lut_size);
+        if self.current_lut.is_some() && self.current_lut_key == Some(key) {
+            self.lut_cache.record_hit();
+            return false;
+        }
+
+        let cached = self.lut_cache.take(key);
+        let recycled = match (self.current_lut.take(), self.current_lut_key) {
+            (Some(lut), Some(previous)) => self.lut_cache.insert(previous, lut),
+            (lut, _) => lut,
+        };
+        self.current_lut_key = Some(key);
+        if let Some(lut) = cached {
+            self.current_lut = Some(lut);
+            self.lut_cache.record_hit();
+            return false;
+        }
+        self.current_lut = Some(
+            recycled
+                .filter(|lut| lut.size == lut_size)
+                .unwrap_or_else(|| GpuLutHandle::new(&self.device, lut_size)),
+        );
+        self.lut_cache.record_miss();
+        true
+    }
+
     /// Apply the current LUT to a source image (legacy single-step API).
     pub fn apply_lut(&mut self, source: &GpuImageHandle) -> &GpuImageHandle {
         let lut = self
@@ -410,6 +616,86 @@ impl GpuGradingPipeline {
         self.current_output.as_ref().unwrap()
     }
 
+    /// Grade `source` with the full chain (pixel repair, chromatic
+    /// aberration, dehaze, LUT, glow) and read the result back in output
+    /// code values. Blocks until the readback completes.
+    ///
+    /// For stills rendered outside the viewer loop, such as bracketed
+    /// exports. The LUT comes from the cache when `params` was baked
+    /// recently.
+    pub fn render_still(
+        &mut self,
+        source: &GpuImageHandle,
+        params: &GradingParams,
+        lut_size: u32,
+    ) -> GradingImage {
+        let needs_bake = self.select_lut(params, lut_size);
+        if needs_bake {
+            self.lut_baker
+                .upload_curves(&self.device, &self.queue, params);
+        }
+
+        let output = self.current_output.get_or_insert_with(|| {
+            GpuImageHandle::create_output(&self.device, source.width, source.height)
+        });
+        if output.width != source.width || output.height != source.height {
+            *output = GpuImageHandle::create_output(&self.device, source.width, source.height);
+        }
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("crispen_render_still_encoder"),
+            });
+        let lut = self.current_lut.as_ref().unwrap();
+        if needs_bake {
+            self.lut_baker
+                .bake(&self.device, &self.queue, params, lut, &mut encoder);
+        }
+        let source = self
+            .repair_pass
+            .run(&self.device, &self.queue, source, &mut encoder)
+            .unwrap_or(source);
+        let source = self
+            .ca_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.chromatic_aberration,
+                &mut encoder,
+            )
+            .unwrap_or(source);
+        let source = self
+            .dehaze_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.dehaze,
+                &mut encoder,
+            )
+            .unwrap_or(source);
+        let output = self.current_output.as_ref().unwrap();
+        self.lut_applicator
+            .apply(&self.device, &self.queue, source, lut, output, &mut encoder);
+        self.glow_pass.run(
+            &self.device,
+            &self.queue,
+            output,
+            &params.glow,
+            &mut encoder,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        Readback::download_image(
+            &self.device,
+            &self.queue,
+            output,
+            &mut self.image_readback_staging,
+        )
+    }
+
     /// Compute scopes on the most recently graded output image, if available.
     pub fn compute_scopes_on_current_output(&mut self) -> Option<ScopeResults> {
         self.current_output.as_ref()?;
@@ -420,15 +706,13 @@ impl GpuGradingPipeline {
     fn compute_scopes_on_output(&mut self) -> ScopeResults {
         let output = self.current_output.as_ref().expect("must apply LUT first");
         let width = output.width;
-        let cfg = self.scope_config;
+        let cfg = self.sync_scope_resources(width);
 
-        let scope_buffers = self
-            .scope_buffers
-            .get_or_insert_with(|| ScopeBuffers::new(&self.device, &cfg, width));
+        let scope_buffers = self.scope_buffers.as_ref().unwrap();
 
         let readback = self
             .readback
-            .get_or_insert_with(|| Readback::new(&self.device, &cfg, width));
+            .get_or_insert_with(|| Readback::new(&self.device, &cfg, scope_buffers.image_width));
 
         let mut encoder = self
             .device
@@ -444,6 +728,7 @@ impl GpuGradingPipeline {
             cfg.waveform_height,
             cfg.vectorscope_resolution,
             cfg.cie_resolution,
+            cfg.sample_stride_for(self.current_output.as_ref().unwrap().pixel_count()),
             &mut encoder,
             self.scope_histogram_visible,
             self.scope_waveform_visible,
@@ -461,24 +746,17 @@ impl GpuGradingPipeline {
 
     /// Submit GPU work (bake + apply + format convert + scopes) without
     /// blocking. Results are consumed later via [`try_consume_readback`].
+    ///
+    /// Returns `false` without submitting (and counts a stall) when every
+    /// readback slot is still in flight. With every scope hidden through
+    /// [`set_scope_visibility`](Self::set_scope_visibility), only the viewer
+    /// image is computed and read back, and the result has no scopes.
     pub fn submit_gpu_work(
         &mut self,
         source: &GpuImageHandle,
         params: &GradingParams,
         lut_size: u32,
-    ) {
-        // Upload curve textures.
-        self.lut_baker
-            .upload_curves(&self.device, &self.queue, params);
-
-        // Ensure LUT handle.
-        let lut = self
-            .current_lut
-            .get_or_insert_with(|| GpuLutHandle::new(&self.device, lut_size));
-        if lut.size != lut_size {
-            *lut = GpuLutHandle::new(&self.device, lut_size);
-        }
-
+    ) -> bool {
         // Ensure output handle.
         let output = self.current_output.get_or_insert_with(|| {
             GpuImageHandle::create_output(&self.device, source.width, source.height)
@@ -487,10 +765,7 @@ impl GpuGradingPipeline {
             *output = GpuImageHandle::create_output(&self.device, source.width, source.height);
         }
 
-        let cfg = self.scope_config;
-        let _scope_buffers = self
-            .scope_buffers
-            .get_or_insert_with(|| ScopeBuffers::new(&self.device, &cfg, source.width));
+        let cfg = self.sync_scope_resources(source.width);
 
         let pixel_count = source.width as u64 * source.height as u64;
         let viewer_format = self.viewer_format;
@@ -500,12 +775,27 @@ impl GpuGradingPipeline {
         if self.async_readback.is_none() {
             self.async_readback = Some(AsyncReadback::new(
                 &self.device,
+                self.readback_slots,
                 &cfg,
                 source.width,
                 viewer_byte_size,
             ));
         }
 
+        let async_rb = self.async_readback.as_mut().unwrap();
+        if !async_rb.has_free_slot() {
+            async_rb.record_stall();
+            return false;
+        }
+
+        // Reuse a cached LUT, or upload curve textures for a bake. Done
+        // after the stall check so a skipped frame never claims a bake.
+        let needs_bake = self.select_lut(params, lut_size);
+        if needs_bake {
+            self.lut_baker
+                .upload_curves(&self.device, &self.queue, params);
+        }
+
         // ── Single encoder ───────────────────────────────────────
         let mut encoder = self
             .device
@@ -513,15 +803,49 @@ impl GpuGradingPipeline {
                 label: Some("crispen_async_frame_encoder"),
             });
 
-        // 1. Bake LUT.
+        // 1. Bake LUT unless it came from the cache.
         let lut = self.current_lut.as_ref().unwrap();
-        self.lut_baker
-            .bake(&self.device, &self.queue, params, lut, &mut encoder);
+        if needs_bake {
+            self.lut_baker
+                .bake(&self.device, &self.queue, params, lut, &mut encoder);
+        }
 
-        // 2. Apply LUT.
+        // 2. Repair, realign, and dehaze (spatial, when enabled), then apply the LUT.
+        let source = self
+            .repair_pass
+            .run(&self.device, &self.queue, source, &mut encoder)
+            .unwrap_or(source);
+        let source = self
+            .ca_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.chromatic_aberration,
+                &mut encoder,
+            )
+            .unwrap_or(source);
+        let source = self
+            .dehaze_pass
+            .run(
+                &self.device,
+                &self.queue,
+                source,
+                &params.dehaze,
+                &mut encoder,
+            )
+            .unwrap_or(source);
         let output = self.current_output.as_ref().unwrap();
         self.lut_applicator
             .apply(&self.device, &self.queue, source, lut, output, &mut encoder);
+        // Post-grade effects on the graded output (spatial, when enabled).
+        self.glow_pass.run(
+            &self.device,
+            &self.queue,
+            output,
+            &params.glow,
+            &mut encoder,
+        );
 
         // 3. Format conversion + 4. Scope dispatches.
         let output = self.current_output.as_ref().unwrap();
@@ -544,25 +868,40 @@ impl GpuGradingPipeline {
             ViewerFormat::F32 => &output.buffer,
         };
 
-        // 4. Scope dispatches (conditional on visibility).
-        self.scope_dispatch.dispatch(
-            &self.device,
-            &self.queue,
-            output,
-            scope_buffers,
-            cfg.waveform_height,
-            cfg.vectorscope_resolution,
-            cfg.cie_resolution,
-            &mut encoder,
-            self.scope_histogram_visible,
-            self.scope_waveform_visible,
-            self.scope_vectorscope_visible,
-            self.scope_cie_visible,
-        );
+        // 4. Scope dispatches (conditional on visibility). With every scope
+        // hidden the scope buffers are neither computed nor read back.
+        let with_scopes = self.scope_histogram_visible
+            || self.scope_waveform_visible
+            || self.scope_vectorscope_visible
+            || self.scope_cie_visible;
+        if with_scopes {
+            self.scope_dispatch.dispatch(
+                &self.device,
+                &self.queue,
+                output,
+                scope_buffers,
+                cfg.waveform_height,
+                cfg.vectorscope_resolution,
+                cfg.cie_resolution,
+                cfg.sample_stride_for(output.pixel_count()),
+                &mut encoder,
+                self.scope_histogram_visible,
+                self.scope_waveform_visible,
+                self.scope_vectorscope_visible,
+                self.scope_cie_visible,
+            );
+        }
 
         // 5. Async readback staging copies.
         let async_rb = self.async_readback.as_mut().unwrap();
-        async_rb.submit_readback(&mut encoder, viewer_src, viewer_byte_size, scope_buffers);
+        async_rb.submit_readback(
+            &self.device,
+            &mut encoder,
+            viewer_src,
+            viewer_byte_size,
+            (output.width, output.height),
+            with_scopes.then_some(scope_buffers),
+        );
 
         // ── Single submit ────────────────────────────────────────
         self.queue.submit(std::iter::once(encoder.finish()));
@@ -570,11 +909,7 @@ impl GpuGradingPipeline {
         // Begin map_async (must happen after submit).
         let async_rb = self.async_readback.as_mut().unwrap();
         async_rb.begin_map_after_submit();
-
-        // Track dimensions for when we consume the result.
-        self.last_async_width = output.width;
-        self.last_async_height = output.height;
-        self.last_async_viewer_byte_size = viewer_byte_size;
+        true
     }
 
     /// Non-blocking: check if async readback data is ready and consume it.
@@ -583,24 +918,45 @@ impl GpuGradingPipeline {
     /// Should be called every frame — it drives `device.poll()` internally.
     pub fn try_consume_readback(&mut self) -> Option<FrameResult> {
         let async_rb = self.async_readback.as_mut()?;
-        let result = async_rb.try_consume(&self.device, self.last_async_viewer_byte_size)?;
+        let result = async_rb.try_consume(&self.device)?;
 
         Some(FrameResult {
             viewer_bytes: result.viewer_bytes,
-            width: self.last_async_width,
-            height: self.last_async_height,
+            width: result.width,
+            height: result.height,
             format: self.viewer_format,
-            scopes: Some(result.scopes),
+            scopes: result.scopes,
         })
     }
 
     /// Set the scope configuration (waveform height, vectorscope/CIE resolution).
+    ///
+    /// Scope buffers and their staging are resized in place on the next
+    /// submission; the viewer image staging is kept.
     pub fn set_scope_config(&mut self, config: ScopeConfig) {
         self.scope_config = config;
-        // Invalidate cached scope resources so they're recreated.
-        self.scope_buffers = None;
-        self.readback = None;
-        self.async_readback = None;
+    }
+
+    /// The requested scope configuration.
+    pub fn scope_config(&self) -> ScopeConfig {
+        self.scope_config
+    }
+
+    /// Bring the scope buffers in line with `scope_config` and the image
+    /// width, reallocating only what changed. Returns the dimensions the
+    /// buffers are sized for.
+    ///
+    /// Readbacks already in flight keep their own staging; each async slot
+    /// is resized when it is next written.
+    fn sync_scope_resources(&mut self, image_width: u32) -> ScopeConfig {
+        let wanted = self.scope_config;
+        let buffers = self
+            .scope_buffers
+            .get_or_insert_with(|| ScopeBuffers::new(&self.device, &wanted, image_width));
+        if buffers.resize(&self.device, &wanted, image_width) {
+            self.readback = None;
+        }
+        buffers.config
     }
 
     /// Upload optional OCIO IDT/ODT LUT textures used by `bake_lut.wgsl`.
@@ -612,6 +968,33 @@ impl GpuGradingPipeline {
     ) {
         self.lut_baker
             .set_ocio_luts(&self.device, &self.queue, idt_lut, odt_lut, size);
+        self.invalidate_luts();
+    }
+
+    /// Upload an imported input LUT that replaces the input transform, or
+    /// `None` to remove it. Takes effect on the next LUT bake.
+    pub fn set_input_lut(&mut self, lut: Option<&[[f32; 4]]>, size: u32) {
+        self.lut_baker
+            .set_input_lut(&self.device, &self.queue, lut, size);
+        self.invalidate_luts();
+    }
+
+    /// Forget every baked LUT: the textures they sampled were replaced, but
+    /// bake keys only cover the parameters.
+    fn invalidate_luts(&mut self) {
+        self.lut_cache.clear();
+        self.current_lut_key = None;
+    }
+
+    /// Set how many baked LUTs are kept besides the current one for
+    /// switching back without a re-bake. `0` disables the cache.
+    pub fn set_lut_cache_capacity(&mut self, capacity: usize) {
+        self.lut_cache.set_capacity(capacity);
+    }
+
+    /// Bake counters of the LUT cache.
+    pub fn lut_cache_stats(&self) -> LutCacheStats {
+        self.lut_cache.stats()
     }
 
     /// Get a reference to the current output image, if any.
@@ -648,6 +1031,31 @@ impl GpuGradingPipeline {
         self.viewer_format
     }
 
+    /// Set the number of async readback slots (clamped to 2–4).
+    ///
+    /// Rebuilds the ring, discarding readbacks still in flight.
+    pub fn set_readback_slots(&mut self, slots: usize) {
+        if self.readback_slots != slots {
+            self.readback_slots = slots;
+            self.async_readback = None;
+        }
+    }
+
+    /// Hand a consumed [`FrameResult::viewer_bytes`] buffer back for reuse.
+    ///
+    /// Consumers that keep only the latest frame should return the previous
+    /// one here so the next readback reuses its allocation.
+    pub fn recycle_viewer_bytes(&mut self, bytes: Vec<u8>) {
+        if let Some(async_rb) = self.async_readback.as_mut() {
+            async_rb.recycle_viewer_bytes(bytes);
+        }
+    }
+
+    /// Frame pacing counters of the async readback ring, once it exists.
+    pub fn readback_stats(&self) -> Option<ReadbackStats> {
+        self.async_readback.as_ref().map(AsyncReadback::stats)
+    }
+
     /// Whether an async readback is currently in flight (not yet consumed).
     pub fn has_pending_readback(&self) -> bool {
         self.async_readback
@@ -661,8 +1069,42 @@ impl GpuGradingPipeline {
             .update_mask(&self.device, &self.queue, mask);
     }
 
+    /// Set the dead-pixel and dust map repaired on every source frame
+    /// before grading, e.g. from [`Timeline::defect_map_for`]. An empty map
+    /// turns repair off.
+    ///
+    /// [`Timeline::defect_map_for`]: crispen_core::project::Timeline::defect_map_for
+    pub fn set_defect_map(&mut self, map: &DefectMap) {
+        self.repair_pass.set_map(map);
+    }
+
     /// Clear the scope mask so all pixels are included in scope analysis.
     pub fn clear_scope_mask(&mut self) {
-        self.scope_dispatch.clear_mask(&self.queue);
+        self.scope_dispatch.clear_mask();
+    }
+
+    /// Rasterize a polygon in normalized image coordinates into the scope
+    /// mask on the GPU. The pass is submitted right away, ahead of the next
+    /// frame's scope dispatch.
+    pub fn set_scope_mask_polygon(&mut self, polygon: &[[f32; 2]], width: u32, height: u32) {
+        let mask = self
+            .scope_dispatch
+            .mask_target(&self.device, width * height);
+        self.mask_rasterizer
+            .rasterize(&self.device, &self.queue, polygon, width, height, mask);
+    }
+
+    /// Request a viewer overlay of the mask polygon at `width x height`:
+    /// the outside shaded, the outline drawn. Newer requests replace ones
+    /// not yet started; collect the result with
+    /// [`Self::try_take_mask_overlay`].
+    pub fn request_mask_overlay(&mut self, polygon: &[[f32; 2]], width: u32, height: u32) {
+        self.mask_rasterizer
+            .request_overlay(&self.device, &self.queue, polygon, width, height);
+    }
+
+    /// Non-blocking: the most recently finished mask overlay, if any.
+    pub fn try_take_mask_overlay(&mut self) -> Option<MaskOverlay> {
+        self.mask_rasterizer.poll_overlay(&self.device, &self.queue)
     }
 }
@@ -0,0 +1,201 @@
+//! GPU dead-pixel and dust repair on the source image, first in the chain.
+//!
+//! The defect map is set once per clip or camera with
+//! [`PixelRepairPass::set_map`] and rasterized to a one-bit-per-pixel mask
+//! the first time it is used at a given image size. `pixel_repair.wgsl`
+//! then writes a repaired copy of the source that the later passes read.
+//! `crispen_core::grading::pixel_repair::repair_pixels` is the CPU
+//! reference.
+
+use std::num::NonZeroU64;
+
+use crispen_core::grading::pixel_repair::DefectMap;
+
+use crate::buffers::GpuImageHandle;
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Uniform block of `pixel_repair.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct RepairParams {
+    width: u32,
+    height: u32,
+    _pad: [u32; 2],
+}
+
+/// Repaired image and the packed mask for one image size.
+struct RepairTarget {
+    output: GpuImageHandle,
+    mask: wgpu::Buffer,
+}
+
+impl RepairTarget {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue, map: &DefectMap, w: u32, h: u32) -> Self {
+        let mut words = vec![0u32; (w as usize * h as usize).div_ceil(32).max(1)];
+        for (i, _) in map.mask(w, h).iter().enumerate().filter(|(_, m)| **m) {
+            words[i / 32] |= 1 << (i % 32);
+        }
+        let mask = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("crispen_repair_mask"),
+            size: (words.len() * 4) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&mask, 0, bytemuck::cast_slice(&words));
+        Self {
+            output: GpuImageHandle::create_output(device, w, h),
+            mask,
+        }
+    }
+}
+
+/// Owns the `pixel_repair.wgsl` pipeline, the defect map, and its mask.
+pub struct PixelRepairPass {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    map: DefectMap,
+    target: Option<RepairTarget>,
+}
+
+impl PixelRepairPass {
+    /// Create the pipeline with an empty map. Compiles `pixel_repair.wgsl`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("crispen_repair_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/pixel_repair.wgsl").into()),
+        });
+
+        let buffer_entry = |binding, ty, min_size| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(min_size),
+            },
+            count: None,
+        };
+        let read = wgpu::BufferBindingType::Storage { read_only: true };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("crispen_repair_layout"),
+            entries: &[
+                // binding 0: params uniform
+                buffer_entry(0, wgpu::BufferBindingType::Uniform, 16),
+                // binding 1: source image (read)
+                buffer_entry(1, read, 16),
+                // binding 2: repaired image (read_write)
+                buffer_entry(2, wgpu::BufferBindingType::Storage { read_only: false }, 16),
+                // binding 3: defect mask, one bit per pixel (read)
+                buffer_entry(3, read, 4),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("crispen_repair_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("crispen_repair_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_repair_params"),
+                size: std::mem::size_of::<RepairParams>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            map: DefectMap::default(),
+            target: None,
+        }
+    }
+
+    /// Replace the defect map. An empty map turns the pass off.
+    pub fn set_map(&mut self, map: &DefectMap) {
+        if self.map != *map {
+            self.map = map.clone();
+            self.target = None;
+        }
+    }
+
+    /// Record the repair of `source` onto `encoder` and return the repaired
+    /// image, or `None` when the map is empty and the source should be used
+    /// as is.
+    ///
+    /// The caller is responsible for submitting the encoder.
+    pub fn run(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        source: &GpuImageHandle,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Option<&GpuImageHandle> {
+        let (width, height) = (source.width, source.height);
+        if self.map.is_empty() || width == 0 || height == 0 {
+            return None;
+        }
+        if self
+            .target
+            .as_ref()
+            .is_none_or(|t| t.output.width != width || t.output.height != height)
+        {
+            self.target = Some(RepairTarget::new(device, queue, &self.map, width, height));
+        }
+        let target = self.target.as_ref()?;
+
+        let params = RepairParams {
+            width,
+            height,
+            _pad: [0; 2],
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("crispen_repair_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: source.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: target.output.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: target.mask.as_entire_binding(),
+                },
+            ],
+        });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("crispen_repair_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                width.div_ceil(WORKGROUP_SIZE),
+                height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+        Some(&target.output)
+    }
+}
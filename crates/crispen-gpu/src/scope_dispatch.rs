@@ -5,16 +5,20 @@ use std::num::NonZeroU64;
 use wgpu::util::DeviceExt;
 
 use crate::buffers::{GpuImageHandle, ScopeBuffers};
+use crate::specialization::{Overrides, ShaderVariant, VariantCache};
 
 /// Dispatches scope compute shaders and manages their pipeline state.
+///
+/// Each scope keeps pipeline variants specialized for the mask state (and,
+/// for the waveform, common image widths); see [`crate::specialization`].
 pub struct ScopeDispatch {
-    histogram_pipeline: wgpu::ComputePipeline,
+    histogram_pipelines: VariantCache,
     histogram_layout: wgpu::BindGroupLayout,
-    waveform_pipeline: wgpu::ComputePipeline,
+    waveform_pipelines: VariantCache,
     waveform_layout: wgpu::BindGroupLayout,
-    vectorscope_pipeline: wgpu::ComputePipeline,
+    vectorscope_pipelines: VariantCache,
     vectorscope_layout: wgpu::BindGroupLayout,
-    cie_pipeline: wgpu::ComputePipeline,
+    cie_pipelines: VariantCache,
     cie_layout: wgpu::BindGroupLayout,
     // Cached uniform buffers (updated via queue.write_buffer each frame).
     pixel_count_buf: wgpu::Buffer,
@@ -23,16 +27,23 @@ pub struct ScopeDispatch {
     wf_waveform_height_buf: wgpu::Buffer,
     vs_resolution_buf: wgpu::Buffer,
     cie_resolution_buf: wgpu::Buffer,
-    // Scope mask: per-pixel u32 buffer + active flag uniform.
+    sample_stride_buf: wgpu::Buffer,
+    // Scope mask: per-pixel u32 buffer; `mask_active` selects the masked
+    // pipeline variants.
     mask_buf: wgpu::Buffer,
-    mask_active_buf: wgpu::Buffer,
+    mask_active: bool,
     mask_pixel_count: u32,
 }
 
 impl ScopeDispatch {
     /// Create all scope compute pipelines.
     pub fn new(device: &wgpu::Device) -> Self {
-        let (histogram_pipeline, histogram_layout) = create_scope_pipeline(
+        let mask_only = Overrides {
+            width: false,
+            mask: true,
+        };
+
+        let (histogram_pipelines, histogram_layout) = create_scope_pipeline(
             device,
             "histogram",
             include_str!("../shaders/histogram.wgsl"),
@@ -41,11 +52,12 @@ impl ScopeDispatch {
                 storage_rw_entry(1),
                 uniform_entry(2, 4),
                 storage_ro_entry(3), // mask
-                uniform_entry(4, 4), // mask_active
+                uniform_entry(4, 4), // sample_stride
             ],
+            mask_only,
         );
 
-        let (waveform_pipeline, waveform_layout) = create_scope_pipeline(
+        let (waveform_pipelines, waveform_layout) = create_scope_pipeline(
             device,
             "waveform",
             include_str!("../shaders/waveform.wgsl"),
@@ -56,11 +68,15 @@ impl ScopeDispatch {
                 uniform_entry(3, 4),
                 uniform_entry(4, 4),
                 storage_ro_entry(5), // mask
-                uniform_entry(6, 4), // mask_active
+                uniform_entry(6, 4), // sample_stride
             ],
+            Overrides {
+                width: true,
+                mask: true,
+            },
         );
 
-        let (vectorscope_pipeline, vectorscope_layout) = create_scope_pipeline(
+        let (vectorscope_pipelines, vectorscope_layout) = create_scope_pipeline(
             device,
             "vectorscope",
             include_str!("../shaders/vectorscope.wgsl"),
@@ -70,11 +86,12 @@ impl ScopeDispatch {
                 uniform_entry(2, 4),
                 uniform_entry(3, 4),
                 storage_ro_entry(4), // mask
-                uniform_entry(5, 4), // mask_active
+                uniform_entry(5, 4), // sample_stride
             ],
+            mask_only,
         );
 
-        let (cie_pipeline, cie_layout) = create_scope_pipeline(
+        let (cie_pipelines, cie_layout) = create_scope_pipeline(
             device,
             "cie",
             include_str!("../shaders/cie.wgsl"),
@@ -84,8 +101,9 @@ impl ScopeDispatch {
                 uniform_entry(2, 4),
                 uniform_entry(3, 4),
                 storage_ro_entry(4), // mask
-                uniform_entry(5, 4), // mask_active
+                uniform_entry(5, 4), // sample_stride
             ],
+            mask_only,
         );
 
         // Pre-allocate cached uniform buffers (updated via queue.write_buffer).
@@ -105,16 +123,14 @@ impl ScopeDispatch {
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
 
-        let mask_active_buf = make_uniform("crispen_scope_mask_active");
-
         Self {
-            histogram_pipeline,
+            histogram_pipelines,
             histogram_layout,
-            waveform_pipeline,
+            waveform_pipelines,
             waveform_layout,
-            vectorscope_pipeline,
+            vectorscope_pipelines,
             vectorscope_layout,
-            cie_pipeline,
+            cie_pipelines,
             cie_layout,
             pixel_count_buf: make_uniform("crispen_scope_pixel_count"),
             wf_width_buf: make_uniform("crispen_scope_wf_width"),
@@ -122,8 +138,9 @@ impl ScopeDispatch {
             wf_waveform_height_buf: make_uniform("crispen_scope_wf_wh"),
             vs_resolution_buf: make_uniform("crispen_scope_vs_res"),
             cie_resolution_buf: make_uniform("crispen_scope_cie_res"),
+            sample_stride_buf: make_uniform("crispen_scope_sample_stride"),
             mask_buf,
-            mask_active_buf,
+            mask_active: false,
             mask_pixel_count: 0,
         }
     }
@@ -142,31 +159,39 @@ impl ScopeDispatch {
         } else {
             queue.write_buffer(&self.mask_buf, 0, bytemuck::cast_slice(mask));
         }
-        let pad = |v: u32| -> [u32; 4] { [v, 0, 0, 0] };
-        queue.write_buffer(
-            &self.mask_active_buf,
-            0,
-            bytemuck::cast_slice(&pad(1)),
-        );
+        self.mask_active = true;
+    }
+
+    /// Activate the scope mask and return its buffer, sized for
+    /// `pixel_count` pixels, for a GPU pass to write into.
+    pub fn mask_target(&mut self, device: &wgpu::Device, pixel_count: u32) -> &wgpu::Buffer {
+        let size = (u64::from(pixel_count) * 4).max(16);
+        if pixel_count != self.mask_pixel_count || self.mask_buf.size() < size {
+            self.mask_buf = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("crispen_scope_mask"),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.mask_pixel_count = pixel_count;
+        }
+        self.mask_active = true;
+        &self.mask_buf
     }
 
     /// Clear the scope mask so all pixels are included.
-    pub fn clear_mask(&self, queue: &wgpu::Queue) {
-        let pad = |v: u32| -> [u32; 4] { [v, 0, 0, 0] };
-        queue.write_buffer(
-            &self.mask_active_buf,
-            0,
-            bytemuck::cast_slice(&pad(0)),
-        );
+    pub fn clear_mask(&mut self) {
+        self.mask_active = false;
     }
 
     /// Dispatch scope shaders onto the given encoder.
     ///
     /// Only dispatches compute passes for visible scopes. Buffers are always
-    /// cleared so readback returns zeroed data for hidden scopes.
+    /// cleared so readback returns zeroed data for hidden scopes. Each pass
+    /// accumulates every `sample_stride`-th pixel (row, for the waveform).
     #[allow(clippy::too_many_arguments)]
     pub fn dispatch(
-        &self,
+        &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         image: &GpuImageHandle,
@@ -174,6 +199,7 @@ impl ScopeDispatch {
         waveform_height: u32,
         vectorscope_resolution: u32,
         cie_resolution: u32,
+        sample_stride: u32,
         encoder: &mut wgpu::CommandEncoder,
         histogram_visible: bool,
         waveform_visible: bool,
@@ -189,7 +215,10 @@ impl ScopeDispatch {
             return;
         }
         let pixel_count = image.pixel_count();
-        let workgroups = pixel_count.div_ceil(256);
+        let sample_stride = sample_stride.max(1);
+        let workgroups = pixel_count.div_ceil(sample_stride).div_ceil(256);
+        let wf_workgroups = (image.width * image.height.div_ceil(sample_stride)).div_ceil(256);
+        let variant = ShaderVariant::for_image(image.width, self.mask_active);
 
         // Update cached uniform buffers via queue.write_buffer (no allocations).
         let pad = |v: u32| -> [u32; 4] { [v, 0, 0, 0] };
@@ -223,6 +252,11 @@ impl ScopeDispatch {
             0,
             bytemuck::cast_slice(&pad(cie_resolution)),
         );
+        queue.write_buffer(
+            &self.sample_stride_buf,
+            0,
+            bytemuck::cast_slice(&pad(sample_stride)),
+        );
 
         // Clear all scope buffers.
         encoder.clear_buffer(&scope_buffers.histogram, 0, None);
@@ -254,7 +288,7 @@ impl ScopeDispatch {
                     },
                     wgpu::BindGroupEntry {
                         binding: 4,
-                        resource: self.mask_active_buf.as_entire_binding(),
+                        resource: self.sample_stride_buf.as_entire_binding(),
                     },
                 ],
             });
@@ -262,7 +296,7 @@ impl ScopeDispatch {
                 label: Some("crispen_histogram_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&self.histogram_pipeline);
+            pass.set_pipeline(self.histogram_pipelines.get(device, variant));
             pass.set_bind_group(0, &hist_bg, &[]);
             pass.dispatch_workgroups(workgroups, 1, 1);
         }
@@ -299,7 +333,7 @@ impl ScopeDispatch {
                     },
                     wgpu::BindGroupEntry {
                         binding: 6,
-                        resource: self.mask_active_buf.as_entire_binding(),
+                        resource: self.sample_stride_buf.as_entire_binding(),
                     },
                 ],
             });
@@ -307,9 +341,9 @@ impl ScopeDispatch {
                 label: Some("crispen_waveform_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&self.waveform_pipeline);
+            pass.set_pipeline(self.waveform_pipelines.get(device, variant));
             pass.set_bind_group(0, &wf_bg, &[]);
-            pass.dispatch_workgroups(workgroups, 1, 1);
+            pass.dispatch_workgroups(wf_workgroups, 1, 1);
         }
 
         // Vectorscope pass.
@@ -340,7 +374,7 @@ impl ScopeDispatch {
                     },
                     wgpu::BindGroupEntry {
                         binding: 5,
-                        resource: self.mask_active_buf.as_entire_binding(),
+                        resource: self.sample_stride_buf.as_entire_binding(),
                     },
                 ],
             });
@@ -348,7 +382,7 @@ impl ScopeDispatch {
                 label: Some("crispen_vectorscope_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&self.vectorscope_pipeline);
+            pass.set_pipeline(self.vectorscope_pipelines.get(device, variant));
             pass.set_bind_group(0, &vs_bg, &[]);
             pass.dispatch_workgroups(workgroups, 1, 1);
         }
@@ -381,7 +415,7 @@ impl ScopeDispatch {
                     },
                     wgpu::BindGroupEntry {
                         binding: 5,
-                        resource: self.mask_active_buf.as_entire_binding(),
+                        resource: self.sample_stride_buf.as_entire_binding(),
                     },
                 ],
             });
@@ -389,7 +423,7 @@ impl ScopeDispatch {
                 label: Some("crispen_cie_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&self.cie_pipeline);
+            pass.set_pipeline(self.cie_pipelines.get(device, variant));
             pass.set_bind_group(0, &cie_bg, &[]);
             pass.dispatch_workgroups(workgroups, 1, 1);
         }
@@ -439,10 +473,11 @@ fn uniform_entry(binding: u32, min_size: u64) -> wgpu::BindGroupLayoutEntry {
 
 fn create_scope_pipeline(
     device: &wgpu::Device,
-    name: &str,
+    name: &'static str,
     wgsl_source: &str,
     layout_entries: &[wgpu::BindGroupLayoutEntry],
-) -> (wgpu::ComputePipeline, wgpu::BindGroupLayout) {
+    overrides: Overrides,
+) -> (VariantCache, wgpu::BindGroupLayout) {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some(&format!("crispen_{name}_shader")),
         source: wgpu::ShaderSource::Wgsl(wgsl_source.into()),
@@ -466,14 +501,14 @@ fn create_scope_pipeline(
         _ => name,
     };
 
-    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-        label: Some(&format!("crispen_{name}_pipeline")),
-        layout: Some(&pipeline_layout),
-        module: &shader,
-        entry_point: Some(entry_point),
-        compilation_options: wgpu::PipelineCompilationOptions::default(),
-        cache: None,
-    });
+    let pipelines = VariantCache::new(
+        device,
+        name,
+        shader,
+        pipeline_layout,
+        entry_point,
+        overrides,
+    );
 
-    (pipeline, bind_group_layout)
+    (pipelines, bind_group_layout)
 }
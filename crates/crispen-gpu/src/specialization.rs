@@ -0,0 +1,120 @@
+//! Pipeline variants specialized through WGSL override constants.
+//!
+//! The apply and scope shaders declare `override` constants for the image
+//! width and for whether a scope mask is bound. Baking them into the
+//! pipeline lets the shader compiler fold the per-pixel index arithmetic
+//! for common widths and drop the mask branch entirely, instead of reading
+//! uniforms and branching on them for every pixel.
+//!
+//! Variants are compiled on first use and cached, so an image in an
+//! unusual width costs nothing beyond the generic pipeline.
+
+use std::collections::HashMap;
+
+/// Image widths compiled into their own pipeline variants. Other widths
+/// use the generic variant, which reads the width from a uniform.
+pub const SPECIALIZED_WIDTHS: [u32; 5] = [1280, 1920, 2048, 3840, 4096];
+
+/// Override constants that select one pipeline variant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ShaderVariant {
+    /// `IMAGE_WIDTH`: a specialized width, or 0 for the generic variant.
+    pub width: u32,
+    /// `MASK_ACTIVE`: whether the scope mask is tested.
+    pub masked: bool,
+}
+
+impl ShaderVariant {
+    /// The variant for an image of `width` pixels.
+    pub fn for_image(width: u32, masked: bool) -> Self {
+        Self {
+            width: if SPECIALIZED_WIDTHS.contains(&width) {
+                width
+            } else {
+                0
+            },
+            masked,
+        }
+    }
+}
+
+/// Which override constants a shader declares.
+#[derive(Debug, Clone, Copy)]
+pub struct Overrides {
+    /// The shader declares `override IMAGE_WIDTH: u32`.
+    pub width: bool,
+    /// The shader declares `override MASK_ACTIVE: bool`.
+    pub mask: bool,
+}
+
+/// Lazily compiled variants of one compute shader entry point.
+pub struct VariantCache {
+    name: &'static str,
+    module: wgpu::ShaderModule,
+    layout: wgpu::PipelineLayout,
+    entry_point: &'static str,
+    overrides: Overrides,
+    pipelines: HashMap<ShaderVariant, wgpu::ComputePipeline>,
+}
+
+impl VariantCache {
+    /// Wrap a compiled module; the generic variant is built right away so
+    /// shader errors surface at startup.
+    pub fn new(
+        device: &wgpu::Device,
+        name: &'static str,
+        module: wgpu::ShaderModule,
+        layout: wgpu::PipelineLayout,
+        entry_point: &'static str,
+        overrides: Overrides,
+    ) -> Self {
+        let mut cache = Self {
+            name,
+            module,
+            layout,
+            entry_point,
+            overrides,
+            pipelines: HashMap::new(),
+        };
+        cache.get(device, ShaderVariant::default());
+        cache
+    }
+
+    /// The pipeline for `variant`, compiling it on first use. Constants the
+    /// shader does not declare are ignored.
+    pub fn get(&mut self, device: &wgpu::Device, variant: ShaderVariant) -> &wgpu::ComputePipeline {
+        let variant = ShaderVariant {
+            width: if self.overrides.width {
+                variant.width
+            } else {
+                0
+            },
+            masked: self.overrides.mask && variant.masked,
+        };
+        self.pipelines.entry(variant).or_insert_with(|| {
+            let mut constants = Vec::with_capacity(2);
+            if self.overrides.width {
+                constants.push(("IMAGE_WIDTH", f64::from(variant.width)));
+            }
+            if self.overrides.mask {
+                constants.push(("MASK_ACTIVE", if variant.masked { 1.0 } else { 0.0 }));
+            }
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(&format!(
+                    "crispen_{}_pipeline_w{}{}",
+                    self.name,
+                    variant.width,
+                    if variant.masked { "_masked" } else { "" }
+                )),
+                layout: Some(&self.layout),
+                module: &self.module,
+                entry_point: Some(self.entry_point),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &constants,
+                    ..Default::default()
+                },
+                cache: None,
+            })
+        })
+    }
+}
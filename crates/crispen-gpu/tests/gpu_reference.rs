@@ -210,3 +210,492 @@ fn test_bake_lut_workgroup_coverage() {
         "Gain 1.5 should produce visibly different output"
     );
 }
+
+#[test]
+fn test_delta_e_matches_cpu_reference() {
+    use crispen_core::transform::compare::{DEFAULT_HEATMAP_SCALE, delta_e_map};
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let params = GradingParams::default();
+    let image = create_test_gradient(37, 19);
+    let source = pipeline.upload_image(&image);
+    pipeline.bake_lut(&params, 33);
+    pipeline.apply_lut(&source);
+    let graded = pipeline
+        .download_current_output()
+        .expect("output should exist");
+
+    let mut shifted = graded.clone();
+    for (i, p) in shifted.pixels.iter_mut().enumerate() {
+        p[i % 3] = (p[i % 3] + 0.05 * (i % 5) as f32).min(1.0);
+    }
+    let reference = pipeline.upload_image(&shifted);
+
+    let space = ColorSpaceId::Srgb;
+    let gpu = pipeline
+        .compare_delta_e(&reference, space, DEFAULT_HEATMAP_SCALE)
+        .expect("sizes match");
+    let cpu = delta_e_map(&graded, &shifted, space).expect("sizes match");
+
+    assert_eq!(gpu.heatmap.len(), cpu.values.len() * 4);
+    for (i, (g, c)) in gpu.map.values.iter().zip(&cpu.values).enumerate() {
+        assert!((g - c).abs() < 0.01, "pixel {i}: gpu ΔE {g} cpu ΔE {c}");
+    }
+}
+
+#[test]
+fn test_exposure_bake_matches_cpu_reference() {
+    use crispen_core::grading::exposure::Exposure;
+    use crispen_core::transform::evaluate::evaluate_transform;
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let mut params = GradingParams::default();
+    params.color_management.input_space = ColorSpaceId::ArriLogC3;
+    params.color_management.working_space = ColorSpaceId::AcesCg;
+    params.color_management.output_space = ColorSpaceId::Srgb;
+    params.exposure = Exposure {
+        stops: -1.0,
+        log_offset: 0.05,
+    };
+    pipeline.bake_lut(&params, 33);
+
+    let image = create_test_gradient(8, 8);
+    let source = pipeline.upload_image(&image);
+    pipeline.apply_lut(&source);
+    let gpu = pipeline
+        .download_current_output()
+        .expect("output should exist");
+    for (i, (src, g)) in image.pixels.iter().zip(&gpu.pixels).enumerate() {
+        let cpu = evaluate_transform([src[0], src[1], src[2]], &params);
+        for ch in 0..3 {
+            assert!(
+                (g[ch] - cpu[ch]).abs() < 0.02,
+                "pixel {i} channel {ch}: gpu {} cpu {}",
+                g[ch],
+                cpu[ch],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_negative_policy_bake_matches_cpu_reference() {
+    use crispen_core::transform::evaluate::evaluate_transform;
+    use crispen_core::transform::params::{ColorSpaceId, NegativePolicy};
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    // S-Log3 code values below black decode negative, and the saturated
+    // corners leave the output gamut.
+    let image = create_test_gradient(8, 8);
+    let source = pipeline.upload_image(&image);
+    for policy in [NegativePolicy::Clamp, NegativePolicy::Mirror] {
+        let mut params = GradingParams::default();
+        params.color_management.input_space = ColorSpaceId::SLog3;
+        params.color_management.working_space = ColorSpaceId::AcesCg;
+        params.color_management.output_space = ColorSpaceId::LinearSrgb;
+        params.color_management.negative_policy = policy;
+        params.saturation = 2.0;
+        pipeline.bake_lut(&params, 33);
+        pipeline.apply_lut(&source);
+        let gpu = pipeline
+            .download_current_output()
+            .expect("output should exist");
+        for (i, (src, g)) in image.pixels.iter().zip(&gpu.pixels).enumerate() {
+            let cpu = evaluate_transform([src[0], src[1], src[2]], &params);
+            for ch in 0..3 {
+                assert!(
+                    (g[ch] - cpu[ch]).abs() < 0.02,
+                    "{policy:?} pixel {i} channel {ch}: gpu {} cpu {}",
+                    g[ch],
+                    cpu[ch],
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_log_curves_bake_matches_cpu_reference() {
+    use crispen_core::transform::evaluate::evaluate_transform;
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    // The LogC4, Log3G10, V-Log and ACEScc curves, decoded on input and
+    // encoded on output, across their linear cuts.
+    let image = create_test_gradient(16, 16);
+    let source = pipeline.upload_image(&image);
+    for space in [
+        ColorSpaceId::ArriLogC4,
+        ColorSpaceId::RedLog3G10,
+        ColorSpaceId::VLog,
+        ColorSpaceId::AcesCc,
+    ] {
+        for (input, output) in [
+            (space, ColorSpaceId::LinearSrgb),
+            (ColorSpaceId::LinearSrgb, space),
+        ] {
+            let mut params = GradingParams::default();
+            params.color_management.input_space = input;
+            params.color_management.working_space = ColorSpaceId::AcesCg;
+            params.color_management.output_space = output;
+            pipeline.bake_lut(&params, 33);
+            pipeline.apply_lut(&source);
+            let gpu = pipeline
+                .download_current_output()
+                .expect("output should exist");
+            for (i, (src, g)) in image.pixels.iter().zip(&gpu.pixels).enumerate() {
+                let cpu = evaluate_transform([src[0], src[1], src[2]], &params);
+                for ch in 0..3 {
+                    assert!(
+                        (g[ch] - cpu[ch]).abs() < 0.02,
+                        "{input:?} -> {output:?} pixel {i} channel {ch}: gpu {} cpu {}",
+                        g[ch],
+                        cpu[ch],
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layer_blend_bake_matches_cpu_reference() {
+    use crispen_core::grading::blend::{BlendMode, LayerBlend};
+    use crispen_core::transform::evaluate::evaluate_transform;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let image = create_test_gradient(8, 8);
+    let source = pipeline.upload_image(&image);
+    for mode in [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Overlay,
+        BlendMode::Color,
+    ] {
+        let params = GradingParams {
+            saturation: 1.6,
+            gain: [1.3, 1.0, 0.8, 1.0],
+            layer_blend: LayerBlend { mode, mix: 0.6 },
+            ..GradingParams::default()
+        };
+        pipeline.bake_lut(&params, 33);
+        pipeline.apply_lut(&source);
+        let gpu = pipeline
+            .download_current_output()
+            .expect("output should exist");
+        for (i, (src, g)) in image.pixels.iter().zip(&gpu.pixels).enumerate() {
+            let cpu = evaluate_transform([src[0], src[1], src[2]], &params);
+            for ch in 0..3 {
+                assert!(
+                    (g[ch] - cpu[ch]).abs() < 0.02,
+                    "{mode:?} pixel {i} channel {ch}: gpu {} cpu {}",
+                    g[ch],
+                    cpu[ch],
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_half_upload_expands_to_promoted_pixels() {
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let half = create_test_gradient(7, 5).to_half();
+    let handle = pipeline.upload_half_image(&half);
+    let gpu = pipeline.download_image(&handle);
+    assert_eq!((gpu.width, gpu.height), (7, 5));
+    assert_eq!(gpu.pixels, half.to_full().pixels);
+}
+
+#[test]
+fn test_dehaze_matches_cpu_reference() {
+    use crispen_core::grading::dehaze::{Dehaze, apply_dehaze};
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let mut params = GradingParams::default();
+    params.color_management.input_space = ColorSpaceId::LinearSrgb;
+    params.color_management.working_space = ColorSpaceId::LinearSrgb;
+    params.color_management.output_space = ColorSpaceId::LinearSrgb;
+    params.dehaze = Dehaze {
+        strength: 0.6,
+        depth_bias: 0.3,
+    };
+
+    let mut image = create_test_gradient(41, 23);
+    for p in &mut image.pixels {
+        for c in &mut p[..3] {
+            *c = *c * 0.6 + 0.3;
+        }
+    }
+    let source = pipeline.upload_image(&image);
+    pipeline.submit_frame(&source, &params, 33);
+    let gpu = pipeline
+        .download_current_output()
+        .expect("output should exist");
+
+    // The identity LUT clamps to its 0–1 domain.
+    let mut cpu = image.clone();
+    apply_dehaze(&mut cpu, &params.dehaze);
+    for (i, (g, c)) in gpu.pixels.iter().zip(&cpu.pixels).enumerate() {
+        for ch in 0..3 {
+            let expected = c[ch].clamp(0.0, 1.0);
+            assert!(
+                (g[ch] - expected).abs() < 0.02,
+                "pixel {i} channel {ch}: gpu {} cpu {expected}",
+                g[ch],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_glow_matches_cpu_reference() {
+    use crispen_core::grading::glow::{Glow, apply_glow};
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let mut params = GradingParams::default();
+    params.color_management.input_space = ColorSpaceId::LinearSrgb;
+    params.color_management.working_space = ColorSpaceId::LinearSrgb;
+    params.color_management.output_space = ColorSpaceId::LinearSrgb;
+    params.glow = Glow {
+        threshold: 0.6,
+        spread: 0.7,
+        intensity: 1.2,
+        tint: [1.0, 0.8, 0.6],
+    };
+
+    let image = create_test_gradient(53, 29);
+    let source = pipeline.upload_image(&image);
+    pipeline.submit_frame(&source, &params, 33);
+    let gpu = pipeline
+        .download_current_output()
+        .expect("output should exist");
+
+    let mut cpu = image.clone();
+    apply_glow(&mut cpu, &params.glow);
+    for (i, (g, c)) in gpu.pixels.iter().zip(&cpu.pixels).enumerate() {
+        for ch in 0..4 {
+            assert!(
+                (g[ch] - c[ch]).abs() < 0.02,
+                "pixel {i} channel {ch}: gpu {} cpu {}",
+                g[ch],
+                c[ch],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_chromatic_aberration_matches_cpu_reference() {
+    use crispen_core::grading::chromatic_aberration::{
+        ChromaticAberration, apply_chromatic_aberration,
+    };
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let mut params = GradingParams::default();
+    params.color_management.input_space = ColorSpaceId::LinearSrgb;
+    params.color_management.working_space = ColorSpaceId::LinearSrgb;
+    params.color_management.output_space = ColorSpaceId::LinearSrgb;
+    params.chromatic_aberration = ChromaticAberration {
+        red_scale: 0.015,
+        blue_scale: -0.01,
+        fringe: 0.8,
+    };
+
+    let image = create_test_gradient(61, 37);
+    let source = pipeline.upload_image(&image);
+    pipeline.submit_frame(&source, &params, 33);
+    let gpu = pipeline
+        .download_current_output()
+        .expect("output should exist");
+
+    let mut cpu = image.clone();
+    apply_chromatic_aberration(&mut cpu, &params.chromatic_aberration);
+    for (i, (g, c)) in gpu.pixels.iter().zip(&cpu.pixels).enumerate() {
+        for ch in 0..3 {
+            assert!(
+                (g[ch] - c[ch]).abs() < 0.01,
+                "pixel {i} channel {ch}: gpu {} cpu {}",
+                g[ch],
+                c[ch],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pixel_repair_matches_cpu_reference() {
+    use crispen_core::grading::pixel_repair::{DefectMap, repair_pixels};
+    use crispen_core::transform::params::ColorSpaceId;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let mut params = GradingParams::default();
+    params.color_management.input_space = ColorSpaceId::LinearSrgb;
+    params.color_management.working_space = ColorSpaceId::LinearSrgb;
+    params.color_management.output_space = ColorSpaceId::LinearSrgb;
+
+    let mut map = DefectMap::default();
+    map.mark(12, 9, 3);
+    map.mark(30, 2, 0);
+    map.mark(31, 2, 0);
+    map.mark(0, 0, 1);
+    pipeline.set_defect_map(&map);
+
+    let image = create_test_gradient(43, 27);
+    let source = pipeline.upload_image(&image);
+    pipeline.submit_frame(&source, &params, 33);
+    let gpu = pipeline
+        .download_current_output()
+        .expect("output should exist");
+
+    let mut cpu = image.clone();
+    repair_pixels(&mut cpu, &map);
+    for (i, (g, c)) in gpu.pixels.iter().zip(&cpu.pixels).enumerate() {
+        for ch in 0..3 {
+            assert!(
+                (g[ch] - c[ch]).abs() < 0.01,
+                "pixel {i} channel {ch}: gpu {} cpu {}",
+                g[ch],
+                c[ch],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_look_previews_match_cpu_reference() {
+    use crispen_core::transform::look_preview::render_thumbnail;
+    use crispen_core::transform::lut::Lut3D;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let looks: Vec<Lut3D> = [1.0, 0.0, 1.8]
+        .into_iter()
+        .map(|saturation| {
+            let mut lut = Lut3D::new(9);
+            lut.bake(&GradingParams {
+                saturation,
+                ..GradingParams::default()
+            });
+            lut
+        })
+        .collect();
+
+    let proxy = create_test_gradient(21, 13);
+    let thumbnails = pipeline
+        .render_look_previews(&proxy, &looks)
+        .expect("thumbnails should render");
+    assert_eq!(thumbnails.len(), looks.len());
+    for (look, (gpu, lut)) in thumbnails.iter().zip(&looks).enumerate() {
+        let cpu = render_thumbnail(&proxy, lut);
+        assert_eq!(gpu.len(), cpu.len());
+        for (i, (g, c)) in gpu.iter().zip(&cpu).enumerate() {
+            assert!(g.abs_diff(*c) <= 1, "look {look} byte {i}: gpu {g} cpu {c}");
+        }
+    }
+}
+
+#[test]
+fn test_switching_back_reuses_cached_lut() {
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let a = GradingParams::default();
+    let b = GradingParams {
+        saturation: 0.0,
+        ..GradingParams::default()
+    };
+    let image = create_test_gradient(8, 8);
+    let source = pipeline.upload_image(&image);
+    let render = |pipeline: &mut GpuGradingPipeline, params: &GradingParams| {
+        pipeline.bake_lut(params, 17);
+        pipeline.apply_lut(&source);
+        pipeline
+            .download_current_output()
+            .expect("output should exist")
+            .pixels
+    };
+
+    let first = render(&mut pipeline, &a);
+    let graded = render(&mut pipeline, &b);
+    assert_ne!(first, graded);
+    let stats = pipeline.lut_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 2, 1));
+
+    let again = render(&mut pipeline, &a);
+    assert_eq!(again, first);
+    let stats = pipeline.lut_cache_stats();
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+
+    // A changed input LUT invalidates every cached bake.
+    pipeline.set_input_lut(None, 2);
+    render(&mut pipeline, &b);
+    assert_eq!(pipeline.lut_cache_stats().misses, 3);
+}
+
+#[test]
+fn test_bracketed_stills_match_cpu_reference() {
+    use crispen_core::transform::bracket::exposure_bracket;
+    use crispen_core::transform::evaluate::evaluate_transform;
+
+    let _lock = gpu_test_lock().lock().expect("gpu test lock poisoned");
+    let (device, queue) = create_test_device();
+    let mut pipeline = GpuGradingPipeline::new(device.clone(), queue.clone());
+
+    let image = create_test_gradient(8, 8);
+    let source = pipeline.upload_image(&image);
+    for variant in exposure_bracket(&GradingParams::default(), &[-1.0, 0.0, 1.0]) {
+        let still = pipeline.render_still(&source, &variant.params, 33);
+        assert_eq!((still.width, still.height), (image.width, image.height));
+        for (i, (src, g)) in image.pixels.iter().zip(&still.pixels).enumerate() {
+            let cpu = evaluate_transform([src[0], src[1], src[2]], &variant.params);
+            for ch in 0..3 {
+                assert!(
+                    (g[ch] - cpu[ch]).abs() < 0.02,
+                    "{} pixel {i} channel {ch}: gpu {} cpu {}",
+                    variant.label,
+                    g[ch],
+                    cpu[ch],
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Regenerates crispen-core's golden images from OCIO.
+//!
+//! Reads `crispen-core/tests/golden/manifest.json`, runs each case's input
+//! image through the OCIO processor named by `ocio_src` / `ocio_dst`, and
+//! overwrites the expected EXR and the case's `source`. Ignored by default
+//! because it rewrites checked-in files:
+//!
+//! `cargo test -p crispen-ocio --test golden_export -- --ignored`
+
+use std::path::{Path, PathBuf};
+
+use crispen_ocio::OcioConfig;
+use serde_json::Value;
+
+/// Built-in config the references are generated from. Pinned so that
+/// regenerating on another machine reproduces the same images; needs
+/// OCIO 2.4 or later.
+const CONFIG: &str = "studio-config-v2.2.0_aces-v1.3_ocio-v2.4";
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../crispen-core/tests/golden")
+}
+
+/// Resolve a manifest name against the config, ignoring case and punctuation.
+fn resolve(config: &OcioConfig, name: &str) -> String {
+    let key = |s: &str| -> String {
+        s.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+    config
+        .color_space_names()
+        .into_iter()
+        .find(|n| key(n) == key(name))
+        .unwrap_or_else(|| panic!("color space {name:?} not in config"))
+}
+
+#[test]
+#[ignore = "rewrites crispen-core golden images from OCIO"]
+fn write_ocio_references() {
+    let config = OcioConfig::builtin(CONFIG)
+        .unwrap_or_else(|e| panic!("needs OCIO 2.4+ with the built-in {CONFIG}: {e}"));
+
+    let dir = golden_dir();
+    let manifest_path = dir.join("manifest.json");
+    let mut manifest: Value =
+        serde_json::from_str(&std::fs::read_to_string(&manifest_path).expect("read manifest"))
+            .expect("parse manifest");
+
+    for case in manifest["cases"].as_array_mut().expect("cases array") {
+        let field = |key: &str| case[key].as_str().expect(key).to_string();
+        let (name, input) = (field("name"), field("input"));
+        let src = resolve(&config, &field("ocio_src"));
+        let dst = resolve(&config, &field("ocio_dst"));
+
+        let image = image::open(dir.join(&input))
+            .expect("read input EXR")
+            .into_rgba32f();
+        let (width, height) = image.dimensions();
+        let mut pixels: Vec<[f32; 4]> = image.pixels().map(|p| p.0).collect();
+
+        config
+            .processor(&src, &dst)
+            .and_then(|p| p.cpu_f32())
+            .unwrap_or_else(|e| panic!("{name}: {src} -> {dst}: {e}"))
+            .apply_rgba(&mut pixels, width, height);
+
+        let flat = pixels.into_iter().flatten().collect();
+        image::Rgba32FImage::from_raw(width, height, flat)
+            .expect("pixel count")
+            .save(dir.join(format!("{name}.exr")))
+            .expect("write expected EXR");
+        case["source"] = Value::String(format!("ocio {CONFIG}"));
+    }
+
+    let text = serde_json::to_string_pretty(&manifest).expect("serialize manifest");
+    std::fs::write(&manifest_path, text + "\n").expect("write manifest");
+}
@@ -0,0 +1,74 @@
+//! Headless consistency report over a timeline's source media.
+//!
+//! Loads a clip list, reads one still per clip through the same loader as
+//! `load_image`, and runs [`analyze_timeline`]. Image sequence patterns
+//! (`shot.%04d.exr`) are read at the middle frame of the clip's source
+//! range. Movie containers are not decoded; such clips are listed as
+//! skipped.
+
+use std::path::{Path, PathBuf};
+
+use crispen_core::project::ale::load_ale;
+use crispen_core::project::edl::load_edl;
+use crispen_core::project::otio::load_otio;
+use crispen_core::project::{
+    Clip, ConsistencyReport, ConsistencySettings, Timeline, analyze_timeline, expand_pattern,
+};
+
+use crate::error::ScriptError;
+use crate::host::load_image;
+
+/// Frame rate assumed for EDLs; only source ranges depend on it.
+const EDL_RATE: f64 = 24.0;
+
+/// Load `.otio`, `.edl`, or `.ale` by extension.
+pub fn load_timeline(path: &Path) -> Result<Timeline, ScriptError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "otio" => load_otio(path).map_err(|e| ScriptError::Timeline(e.to_string())),
+        "edl" => Ok(load_edl(path, EDL_RATE)?),
+        "ale" => Ok(load_ale(path)?),
+        _ => Err(ScriptError::Timeline(format!(
+            "{}: expected .otio, .edl, or .ale",
+            path.display()
+        ))),
+    }
+}
+
+/// Measure every clip of the timeline at `path` and flag the outliers.
+///
+/// Relative media paths resolve against the timeline's folder.
+pub fn consistency_report(
+    path: &Path,
+    settings: &ConsistencySettings,
+) -> Result<ConsistencyReport, ScriptError> {
+    let timeline = load_timeline(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(analyze_timeline(&timeline, settings, |clip| {
+        let media = representative_frame(clip).ok_or_else(|| "no media path".to_string())?;
+        load_image(&media_file(base, &media)).map_err(|e| format!("{media}: {e}"))
+    }))
+}
+
+/// The clip's media path, or for a sequence pattern the middle frame of
+/// its source range.
+pub(crate) fn representative_frame(clip: &Clip) -> Option<String> {
+    let media = clip.media_path.as_deref()?;
+    let middle = clip.source_range.map_or(0, |r| {
+        (r.start + (r.duration / 2.0).floor()).max(0.0) as u64
+    });
+    Some(expand_pattern(media, middle).unwrap_or_else(|| media.to_string()))
+}
+
+pub(crate) fn media_file(base: &Path, media: &str) -> PathBuf {
+    let media = Path::new(media.strip_prefix("file://").unwrap_or(media));
+    if media.is_absolute() {
+        media.to_path_buf()
+    } else {
+        base.join(media)
+    }
+}
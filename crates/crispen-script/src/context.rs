@@ -0,0 +1,47 @@
+//! State a script reads and modifies.
+
+use crispen_core::framing::{Blanking, FramingGuides};
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::GradingImage;
+use crispen_core::project::Sidecars;
+use crispen_core::transform::determinism::Determinism;
+use crispen_core::transform::params::GradingParams;
+
+/// Grade, image, and captured output shared between the host and a script.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptContext {
+    /// Current grade; scripts replace it with `set_grade` / `load_grade`.
+    pub params: GradingParams,
+    /// Source image for `render`, if one is loaded.
+    pub image: Option<GradingImage>,
+    /// Path `image` was loaded from.
+    pub image_path: Option<String>,
+    /// Lines written with `print`, in order.
+    pub output: Vec<String>,
+    /// `Strict` makes `render` evaluate every pixel exactly and pin its
+    /// output bits; set with `set_strict` or `crispen-script --strict`.
+    pub determinism: Determinism,
+    /// Output matte `render` bakes in; set with `set_blanking` or seeded
+    /// from the app session.
+    pub blanking: Blanking,
+    /// Framing guides `render` burns into review output when visible; set
+    /// with `set_guides`.
+    pub guides: FramingGuides,
+    /// Color recipe files `render` writes beside its output; set with
+    /// `set_sidecars`.
+    pub sidecars: Sidecars,
+    /// Dead-pixel and dust map `render` repairs on the source before
+    /// grading; seeded from the app session.
+    pub defects: DefectMap,
+}
+
+impl ScriptContext {
+    /// Context seeded with an existing grade and image, e.g. the app session.
+    pub fn new(params: GradingParams, image: Option<GradingImage>) -> Self {
+        Self {
+            params,
+            image,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Headless scene cut detection for a numbered image sequence.
+//!
+//! Every frame is loaded once and reduced to a
+//! [`FrameProxy`](crispen_core::project::FrameProxy) right away, so memory
+//! stays flat however long the sequence is.
+
+use std::path::Path;
+
+use crispen_core::project::{
+    CutSettings, FrameProxy, ImageSequence, Timeline, detect_cuts, frame_differences,
+    split_sequence,
+};
+
+use crate::error::ScriptError;
+use crate::host::load_image;
+
+/// Split the sequence `frame` belongs to into one clip per detected shot.
+pub fn split_image_sequence(
+    frame: &Path,
+    rate: f64,
+    settings: &CutSettings,
+) -> Result<Timeline, ScriptError> {
+    let sequence = ImageSequence::from_frame(frame).ok_or_else(|| {
+        ScriptError::Timeline(format!("{}: not a numbered frame", frame.display()))
+    })?;
+    let proxies = sequence
+        .frames
+        .iter()
+        .map(|&number| {
+            let path = sequence.frame_path(number);
+            load_image(&path)
+                .map(|image| FrameProxy::new(&image))
+                .map_err(|e| ScriptError::Timeline(format!("{}: {e}", path.display())))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cuts = detect_cuts(&frame_differences(&proxies), settings);
+    tracing::info!(
+        "{}: {} frame(s), {} shot(s)",
+        sequence.pattern(),
+        proxies.len(),
+        cuts.len() + 1
+    );
+    Ok(split_sequence(&sequence, &cuts, rate))
+}
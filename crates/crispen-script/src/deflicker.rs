@@ -0,0 +1,65 @@
+//! Headless deflicker over a timeline's image sequences.
+//!
+//! Every frame in each clip's source range is loaded, measured, and
+//! dropped right away, so memory stays flat however long the sequence is.
+//! The gains land on each clip's master gain keys, one key per frame.
+//! Clips that are not sequence patterns (`shot.%04d.exr`) or have no
+//! source range are left untouched.
+
+use std::path::Path;
+
+use crispen_core::project::{
+    DeflickerSettings, Timeline, deflicker_track, expand_pattern, frame_exposure,
+};
+
+use crate::consistency::{load_timeline, media_file};
+use crate::error::ScriptError;
+use crate::host::load_image;
+
+/// Load the timeline at `path` and key a deflicker gain on every frame of
+/// its sequence clips. Returns the timeline and the number of clips keyed.
+///
+/// Relative media paths resolve against the timeline's folder.
+pub fn deflicker_timeline(
+    path: &Path,
+    settings: &DeflickerSettings,
+) -> Result<(Timeline, usize), ScriptError> {
+    let mut timeline = load_timeline(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut keyed = 0;
+    for clip in &mut timeline.clips {
+        let (Some(media), Some(range)) = (clip.media_path.as_deref(), clip.source_range) else {
+            continue;
+        };
+        let start = range.start.max(0.0) as u64;
+        let frames: Vec<u64> = (start..start + range.duration.max(0.0) as u64).collect();
+        if frames.is_empty() || expand_pattern(media, start).is_none() {
+            continue;
+        }
+        let grade = clip.grade.clone().unwrap_or_default();
+        let exposures = frames
+            .iter()
+            .map(|&number| {
+                let frame = expand_pattern(media, number).unwrap_or_default();
+                load_image(&media_file(base, &frame))
+                    .map(|image| frame_exposure(&image, &grade))
+                    .map_err(|e| ScriptError::Timeline(format!("{frame}: {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let frames: Vec<f64> = frames.iter().map(|&n| n as f64).collect();
+        let track = deflicker_track(&frames, &exposures, settings);
+        let worst = track
+            .keys
+            .iter()
+            .map(|k| k.value.log2().abs())
+            .fold(0.0_f32, f32::max);
+        tracing::info!(
+            "{}: {} frame(s), largest correction {worst:.2} stops",
+            clip.name,
+            frames.len()
+        );
+        clip.gain_keys = Some(track);
+        keyed += 1;
+    }
+    Ok((timeline, keyed))
+}
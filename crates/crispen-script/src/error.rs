@@ -0,0 +1,22 @@
+use crispen_core::project::sequence_export::SequenceExportError;
+use crispen_core::project::sidecars::SidecarError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("script error: {0}")]
+    Eval(String),
+    #[error("timeline error: {0}")]
+    Timeline(String),
+    #[error("export error: {0}")]
+    Export(#[from] SequenceExportError),
+    #[error("sidecar error: {0}")]
+    Sidecar(#[from] SidecarError),
+}
+
+impl From<Box<rhai::EvalAltResult>> for ScriptError {
+    fn from(err: Box<rhai::EvalAltResult>) -> Self {
+        Self::Eval(err.to_string())
+    }
+}
@@ -0,0 +1,1006 @@
+//! Rhai engine setup and the functions scripts can call.
+//!
+//! | Function | Effect |
+//! |----------|--------|
+//! | `grade()` / `set_grade(map)` | Read / replace the whole grade as a map |
+//! | `param(name)` / `set_param(name, value)` | Read / write one `GradingParams` field |
+//! | `reset_grade()` | Restore the identity grade |
+//! | `load_grade(path)` / `save_grade(path)` | Grade as JSON on disk |
+//! | `load_image(path)` | Load the source image |
+//! | `image_width()` / `image_height()` | Source image size, 0 without an image |
+//! | `render(path)` | Grade the source image through the viewer's full chain, defect repair and spatial effects included, and write it (format from extension, DPX included), with guides burned in |
+//! | `blanking()` / `set_blanking(aspect)` | Read / set the output matte `render` bakes in; 0 for none |
+//! | `clip_blanking(timeline, clip)` | Matte in effect for a timeline clip, its override or the timeline's; 0 for none |
+//! | `guides()` / `set_guides(map)` | Read / replace the framing guides; unset fields take their defaults |
+//! | `export_lut(path, size)` | Bake the grade to a `.cube` LUT |
+//! | `set_strict(on)` | Toggle strict determinism for `render` |
+//! | `self_check(runs)` | Check the grade renders bit-identically; map of `passed`, `digest`, `lut_digest` |
+//! | `consistency_report(path)` | Exposure/color consistency of a timeline's clips, as a map |
+//! | `session_report(path, out)` | Write a printable HTML report of a timeline's clips |
+//! | `sidecars()` / `set_sidecars(on or map)` | Read / choose the recipe files `render` writes beside its output: `lut`, `cdl`, `grade`, `manifest`, and the manifest's `checksum` (`"md5"` or `"xxh64"`) |
+//! | `checksum_manifest(dir, algorithm)` | Write `<dir>/<dir name>.md5` or `.xxh64` covering every file under `dir`; returns its path |
+//! | `verify_manifest(path)` | Check a manifest against the files beside it; map of `passed`, `matched`, `mismatched`, `missing` |
+//! | `export_sequence(timeline, clip, pattern[, options])` | Render a sequence clip graded to `pattern` (`%04d`, EXR/DPX/TIFF); options `range`, `first_frame`, `overwrite`, `sidecars`; map of `written`, `skipped` counts |
+//! | `print(text)` | Captured in [`ScriptContext::output`] and logged |
+//!
+//! Command-line arguments are available as the `ARGS` string array.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+
+use crispen_core::framing::{Blanking, FramingGuides, GuideLayer};
+use crispen_core::grading::chromatic_aberration::apply_chromatic_aberration;
+use crispen_core::grading::dehaze::apply_dehaze;
+use crispen_core::grading::glow::apply_glow;
+use crispen_core::grading::pixel_repair::{DefectMap, repair_pixels};
+use crispen_core::image::{BitDepth, GradingImage};
+use crispen_core::project::dpx::write_dpx;
+use crispen_core::project::{
+    ChecksumAlgorithm, ConsistencySettings, Manifest, OverwritePolicy, SequenceExport, Sidecars,
+    folder_manifest_path, sidecar_base, verify_manifest, write_sidecars,
+};
+use crispen_core::transform::determinism::{self, Determinism};
+use crispen_core::transform::evaluate::{OutputEncoding, evaluate_transform_batch_with};
+use crispen_core::transform::lut::Lut3D;
+use crispen_core::transform::params::GradingParams;
+
+use crate::consistency::{consistency_report, load_timeline};
+use crate::context::ScriptContext;
+use crate::error::ScriptError;
+use crate::report::session_report;
+use crate::sequence_export::{clip_frames, export_sequence};
+
+/// LUT size used by `render`, matching the GPU pipeline's bake.
+pub(crate) const RENDER_LUT_SIZE: u32 = 65;
+
+type Shared = Arc<Mutex<ScriptContext>>;
+type FnResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Runs scripts against a shared [`ScriptContext`].
+pub struct ScriptHost {
+    engine: Engine,
+    context: Shared,
+}
+
+impl ScriptHost {
+    /// Create a host with all Crispen functions registered.
+    pub fn new(context: ScriptContext) -> Self {
+        let context = Arc::new(Mutex::new(context));
+        let mut engine = Engine::new();
+        register_grade_fns(&mut engine, &context);
+        register_io_fns(&mut engine, &context);
+
+        let ctx = context.clone();
+        engine.on_print(move |text| {
+            tracing::info!(target: "crispen_script", "{text}");
+            ctx.lock().output.push(text.to_string());
+        });
+
+        Self { engine, context }
+    }
+
+    /// Run script source with `args` bound to `ARGS`.
+    pub fn run(&self, source: &str, args: &[String]) -> Result<(), ScriptError> {
+        let mut scope = Scope::new();
+        let args: Array = args.iter().cloned().map(Dynamic::from).collect();
+        scope.push_constant("ARGS", args);
+        self.engine.run_with_scope(&mut scope, source)?;
+        Ok(())
+    }
+
+    /// Run a script file with `args` bound to `ARGS`.
+    pub fn run_file(&self, path: &Path, args: &[String]) -> Result<(), ScriptError> {
+        self.run(&std::fs::read_to_string(path)?, args)
+    }
+
+    /// Current state, including changes made by scripts run so far.
+    pub fn context(&self) -> MutexGuard<'_, ScriptContext> {
+        self.context.lock()
+    }
+
+    /// Consume the host and return the final state.
+    pub fn into_context(self) -> ScriptContext {
+        std::mem::take(&mut *self.context.lock())
+    }
+}
+
+fn register_grade_fns(engine: &mut Engine, context: &Shared) {
+    let ctx = context.clone();
+    engine.register_fn("grade", move || -> FnResult<Dynamic> {
+        rhai::serde::to_dynamic(&ctx.lock().params)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_grade", move |map: Map| -> FnResult<()> {
+        // Via JSON: Rhai numbers are f64 and won't deserialize into f32 directly.
+        let value: serde_json::Value = rhai::serde::from_dynamic(&Dynamic::from_map(map))?;
+        ctx.lock().params = serde_json::from_value(value).map_err(runtime)?;
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("param", move |name: &str| -> FnResult<Dynamic> {
+        let params = serde_json::to_value(&ctx.lock().params).map_err(runtime)?;
+        let value = params
+            .get(name)
+            .ok_or_else(|| runtime(format!("unknown grade parameter '{name}'")))?;
+        rhai::serde::to_dynamic(value)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn(
+        "set_param",
+        move |name: &str, value: Dynamic| -> FnResult<()> {
+            let mut ctx = ctx.lock();
+            let mut params = serde_json::to_value(&ctx.params).map_err(runtime)?;
+            let field = params
+                .get_mut(name)
+                .ok_or_else(|| runtime(format!("unknown grade parameter '{name}'")))?;
+            *field = rhai::serde::from_dynamic(&value)?;
+            ctx.params = serde_json::from_value(params)
+                .map_err(|e| runtime(format!("invalid value for '{name}': {e}")))?;
+            Ok(())
+        },
+    );
+
+    let ctx = context.clone();
+    engine.register_fn("reset_grade", move || {
+        ctx.lock().params = GradingParams::default();
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("load_grade", move |path: &str| -> FnResult<()> {
+        let json = std::fs::read_to_string(path).map_err(runtime)?;
+        ctx.lock().params = serde_json::from_str(&json).map_err(runtime)?;
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("save_grade", move |path: &str| -> FnResult<()> {
+        let json = serde_json::to_string_pretty(&ctx.lock().params).map_err(runtime)?;
+        std::fs::write(path, json).map_err(runtime)
+    });
+}
+
+fn register_io_fns(engine: &mut Engine, context: &Shared) {
+    let ctx = context.clone();
+    engine.register_fn("load_image", move |path: &str| -> FnResult<()> {
+        let image = load_image(Path::new(path)).map_err(runtime)?;
+        let mut ctx = ctx.lock();
+        ctx.image = Some(image);
+        ctx.image_path = Some(path.to_string());
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("image_width", move || {
+        ctx.lock().image.as_ref().map_or(0, |i| i64::from(i.width))
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("image_height", move || {
+        ctx.lock().image.as_ref().map_or(0, |i| i64::from(i.height))
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("render", move |path: &str| -> FnResult<()> {
+        let ctx = ctx.lock();
+        let image = ctx
+            .image
+            .as_ref()
+            .ok_or_else(|| runtime("render: no image loaded"))?;
+        let mut graded = grade_image(image, &ctx.params, &ctx.defects, ctx.determinism);
+        if ctx.blanking != Blanking::None || ctx.guides.is_visible() {
+            graded = overlay(image, graded, ctx.blanking, &ctx.guides);
+        }
+        save_graded(image, graded, Path::new(path)).map_err(runtime)?;
+        if ctx.sidecars.any() {
+            let mut lut = Lut3D::new(RENDER_LUT_SIZE);
+            lut.bake(&ctx.params);
+            let path = Path::new(path);
+            write_sidecars(
+                &sidecar_base(path),
+                &ctx.sidecars,
+                &ctx.params,
+                &lut,
+                &[path.to_path_buf()],
+            )
+            .map_err(runtime)?;
+        }
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("sidecars", move || -> FnResult<Dynamic> {
+        rhai::serde::to_dynamic(&ctx.lock().sidecars)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_sidecars", move |on: bool| {
+        ctx.lock().sidecars = if on {
+            Sidecars::ALL
+        } else {
+            Sidecars::default()
+        };
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_sidecars", move |map: Map| -> FnResult<()> {
+        ctx.lock().sidecars = rhai::serde::from_dynamic(&Dynamic::from_map(map))?;
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("blanking", move || -> f64 {
+        ctx.lock().blanking.aspect().map_or(0.0, f64::from)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_blanking", move |aspect: f64| {
+        ctx.lock().blanking = Blanking::from_aspect(aspect as f32);
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_blanking", move |aspect: i64| {
+        ctx.lock().blanking = Blanking::from_aspect(aspect as f32);
+    });
+
+    engine.register_fn("clip_blanking", |path: &str, clip: &str| -> FnResult<f64> {
+        let timeline = load_timeline(Path::new(path)).map_err(runtime)?;
+        let clip = timeline
+            .clips
+            .iter()
+            .find(|c| c.name == clip)
+            .ok_or_else(|| runtime(format!("clip_blanking: no clip '{clip}' in {path}")))?;
+        Ok(timeline.blanking_for(clip).aspect().map_or(0.0, f64::from))
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("guides", move || -> FnResult<Dynamic> {
+        rhai::serde::to_dynamic(&ctx.lock().guides)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_guides", move |map: Map| -> FnResult<()> {
+        let value: serde_json::Value = rhai::serde::from_dynamic(&Dynamic::from_map(map))?;
+        ctx.lock().guides = serde_json::from_value(value).map_err(runtime)?;
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("export_lut", move |path: &str, size: i64| -> FnResult<()> {
+        let size = u32::try_from(size)
+            .ok()
+            .filter(|s| (2..=129).contains(s))
+            .ok_or_else(|| runtime(format!("export_lut: size {size} out of range 2–129")))?;
+        let mut lut = Lut3D::new(size);
+        lut.bake(&ctx.lock().params);
+        lut.save_cube(Path::new(path)).map_err(runtime)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("set_strict", move |on: bool| {
+        ctx.lock().determinism = if on {
+            Determinism::Strict
+        } else {
+            Determinism::Fast
+        };
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("self_check", move |runs: i64| -> FnResult<Map> {
+        let runs = usize::try_from(runs)
+            .ok()
+            .filter(|r| (1..=100).contains(r))
+            .ok_or_else(|| runtime(format!("self_check: runs {runs} out of range 1–100")))?;
+        let report = determinism::self_check(&ctx.lock().params, runs);
+        let mut map = Map::new();
+        map.insert("passed".into(), report.passed().into());
+        map.insert("digest".into(), format!("{:016x}", report.digest).into());
+        map.insert(
+            "lut_digest".into(),
+            format!("{:016x}", report.lut_digest).into(),
+        );
+        Ok(map)
+    });
+
+    engine.register_fn("consistency_report", |path: &str| -> FnResult<Dynamic> {
+        let report = consistency_report(Path::new(path), &ConsistencySettings::default())
+            .map_err(runtime)?;
+        rhai::serde::to_dynamic(&report)
+    });
+
+    engine.register_fn("session_report", |path: &str, out: &str| -> FnResult<()> {
+        let report = session_report(Path::new(path), None).map_err(runtime)?;
+        std::fs::write(out, report.to_html()).map_err(runtime)
+    });
+
+    engine.register_fn(
+        "checksum_manifest",
+        |dir: &str, algorithm: &str| -> FnResult<String> {
+            let algorithm: ChecksumAlgorithm = algorithm.parse().map_err(runtime)?;
+            let dir = Path::new(dir);
+            let path = folder_manifest_path(dir, algorithm);
+            Manifest::from_folder(dir, algorithm)
+                .and_then(|manifest| manifest.save(&path))
+                .map_err(runtime)?;
+            Ok(path.display().to_string())
+        },
+    );
+
+    engine.register_fn("verify_manifest", |path: &str| -> FnResult<Map> {
+        let report = verify_manifest(Path::new(path)).map_err(runtime)?;
+        let paths = |paths: &[PathBuf]| -> Dynamic {
+            paths
+                .iter()
+                .map(|p| Dynamic::from(p.display().to_string()))
+                .collect::<rhai::Array>()
+                .into()
+        };
+        let mut map = Map::new();
+        map.insert("passed".into(), report.passed().into());
+        map.insert("matched".into(), (report.matched as i64).into());
+        map.insert("mismatched".into(), paths(&report.mismatched));
+        map.insert("missing".into(), paths(&report.missing));
+        Ok(map)
+    });
+
+    engine.register_fn(
+        "export_sequence",
+        |path: &str, clip: &str, pattern: &str| -> FnResult<Map> {
+            script_export_sequence(path, clip, pattern, Map::new())
+        },
+    );
+
+    engine.register_fn(
+        "export_sequence",
+        |path: &str, clip: &str, pattern: &str, options: Map| -> FnResult<Map> {
+            script_export_sequence(path, clip, pattern, options)
+        },
+    );
+}
+
+/// `export_sequence` for scripts. `options` may set `range` (`"1001-1100"`),
+/// `first_frame` to renumber the output, and `overwrite` (`"skip"`,
+/// `"overwrite"`, or `"fail"`), and `sidecars` (`true` for all, or a map
+/// like `set_sidecars` takes); the range defaults to the frames the clip
+/// uses.
+fn script_export_sequence(path: &str, clip: &str, pattern: &str, options: Map) -> FnResult<Map> {
+    let option = |key: &str| options.get(key).map(|v| v.to_string());
+    let range = match option("range") {
+        Some(range) => range.parse().map_err(runtime)?,
+        None => clip_frames(Path::new(path), clip).map_err(runtime)?,
+    };
+    let mut export = SequenceExport::new(pattern, range);
+    if let Some(first) = options.get("first_frame") {
+        let first = first
+            .as_int()
+            .ok()
+            .and_then(|f| u64::try_from(f).ok())
+            .ok_or_else(|| runtime("export_sequence: first_frame must be a frame number"))?;
+        export.first_output = Some(first);
+    }
+    if let Some(policy) = option("overwrite") {
+        export.overwrite = policy.parse::<OverwritePolicy>().map_err(runtime)?;
+    }
+    if let Some(which) = options.get("sidecars") {
+        export.sidecars = match which.as_bool() {
+            Ok(true) => Sidecars::ALL,
+            Ok(false) => Sidecars::default(),
+            Err(_) => rhai::serde::from_dynamic(which)?,
+        };
+    }
+    let report = export_sequence(Path::new(path), clip, &export).map_err(runtime)?;
+    let mut map = Map::new();
+    map.insert("written".into(), (report.written.len() as i64).into());
+    map.insert("skipped".into(), (report.skipped.len() as i64).into());
+    Ok(map)
+}
+
+pub(crate) fn load_image(path: &Path) -> Result<GradingImage, image::ImageError> {
+    let img = image::open(path)?;
+    let source_bit_depth = match img.color() {
+        image::ColorType::Rgb16 | image::ColorType::Rgba16 => BitDepth::U16,
+        image::ColorType::Rgb32F | image::ColorType::Rgba32F => BitDepth::F32,
+        _ => BitDepth::U8,
+    };
+    let rgba = img.to_rgba32f();
+    let (width, height) = rgba.dimensions();
+    Ok(GradingImage {
+        width,
+        height,
+        pixels: rgba.pixels().map(|p| p.0).collect(),
+        source_bit_depth,
+    })
+}
+
+/// The viewer's passes on the source ahead of the grade, in its order:
+/// defect repair, chromatic aberration, then dehaze.
+pub(crate) fn prepare_source(
+    image: &mut GradingImage,
+    params: &GradingParams,
+    defects: &DefectMap,
+) {
+    repair_pixels(image, defects);
+    apply_chromatic_aberration(image, &params.chromatic_aberration);
+    apply_dehaze(image, &params.dehaze);
+}
+
+/// Grade `image` to interleaved RGBA with the viewer's full chain: the
+/// source passes of [`prepare_source`], the grade, then glow. The grade
+/// goes through a baked LUT normally; in strict mode every pixel is
+/// evaluated exactly, with pinned output bits.
+fn grade_image(
+    image: &GradingImage,
+    params: &GradingParams,
+    defects: &DefectMap,
+    determinism: Determinism,
+) -> Vec<f32> {
+    let mut source = image.clone();
+    prepare_source(&mut source, params, defects);
+    let pixels = if determinism.is_strict() {
+        let rgb: Vec<f32> = source
+            .pixels
+            .iter()
+            .flat_map(|p| [p[0], p[1], p[2]])
+            .collect();
+        let graded =
+            evaluate_transform_batch_with(&rgb, params, OutputEncoding::Display, determinism);
+        graded
+            .chunks_exact(3)
+            .zip(&source.pixels)
+            .map(|(c, p)| [c[0], c[1], c[2], p[3]])
+            .collect()
+    } else {
+        let mut lut = Lut3D::new(RENDER_LUT_SIZE);
+        lut.bake(params);
+        source
+            .pixels
+            .iter()
+            .map(|p| {
+                let [r, g, b] = lut.apply([p[0], p[1], p[2]]);
+                [r, g, b, p[3]]
+            })
+            .collect()
+    };
+    let mut graded = GradingImage { pixels, ..source };
+    apply_glow(&mut graded, &params.glow);
+    graded.pixels.into_iter().flatten().collect()
+}
+
+/// Matte graded RGBA `data` for `image` with `blanking`, then composite the
+/// framing guides over it if they are visible.
+fn overlay(
+    image: &GradingImage,
+    data: Vec<f32>,
+    blanking: Blanking,
+    guides: &FramingGuides,
+) -> Vec<f32> {
+    let mut pixels: Vec<[f32; 4]> = data
+        .chunks_exact(4)
+        .map(|c| [c[0], c[1], c[2], c[3]])
+        .collect();
+    blanking.apply(&mut pixels, image.width, image.height);
+    if guides.is_visible() {
+        GuideLayer::render(guides, image.width, image.height).burn_into(&mut pixels);
+    }
+    pixels.into_iter().flatten().collect()
+}
+
+/// Write graded RGBA `data` for `image`, choosing the sample type from the
+/// extension: float for EXR, 10-bit for DPX, 16-bit for PNG/TIFF, 8-bit
+/// otherwise.
+pub(crate) fn save_graded(
+    image: &GradingImage,
+    data: Vec<f32>,
+    path: &Path,
+) -> Result<(), image::ImageError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if ext == "dpx" {
+        let graded = GradingImage {
+            width: image.width,
+            height: image.height,
+            pixels: data
+                .chunks_exact(4)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect(),
+            source_bit_depth: image.source_bit_depth,
+        };
+        let label = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        return write_dpx(path, &graded, label)
+            .map_err(|e| image::ImageError::IoError(std::io::Error::other(e.to_string())));
+    }
+
+    let buffer = image::Rgba32FImage::from_raw(image.width, image.height, data)
+        .expect("pixel count matches dimensions");
+    let graded = image::DynamicImage::ImageRgba32F(buffer);
+    match ext.as_str() {
+        "exr" => graded.save(path),
+        "png" | "tif" | "tiff" => image::DynamicImage::ImageRgba16(graded.to_rgba16()).save(path),
+        "jpg" | "jpeg" => image::DynamicImage::ImageRgb8(graded.to_rgb8()).save(path),
+        _ => image::DynamicImage::ImageRgba8(graded.to_rgba8()).save(path),
+    }
+}
+
+fn runtime(err: impl std::fmt::Display) -> Box<EvalAltResult> {
+    err.to_string().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_param_updates_grade() {
+        let host = ScriptHost::new(ScriptContext::default());
+        host.run(
+            r#"
+                set_param("saturation", 1.5);
+                let g = grade();
+                g.contrast = 1.25;
+                set_grade(g);
+                print(param("saturation"));
+            "#,
+            &[],
+        )
+        .unwrap();
+        let ctx = host.into_context();
+        assert_eq!(ctx.params.saturation, 1.5);
+        assert_eq!(ctx.params.contrast, 1.25);
+        assert_eq!(ctx.output, ["1.5"]);
+    }
+
+    #[test]
+    fn test_unknown_param_is_script_error() {
+        let host = ScriptHost::new(ScriptContext::default());
+        let err = host.run(r#"set_param("nope", 1.0);"#, &[]).unwrap_err();
+        assert!(err.to_string().contains("unknown grade parameter 'nope'"));
+    }
+
+    #[test]
+    fn test_args_and_render_without_image() {
+        let host = ScriptHost::new(ScriptContext::default());
+        host.run(r#"print(ARGS[0] + " " + image_width());"#, &["shot".into()])
+            .unwrap();
+        assert_eq!(host.context().output, ["shot 0"]);
+        assert!(host.run(r#"render("/tmp/x.png");"#, &[]).is_err());
+    }
+
+    #[test]
+    fn test_render_applies_grade() {
+        let dir = std::env::temp_dir().join(format!("crispen_script_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("graded.exr");
+
+        let image = GradingImage {
+            width: 2,
+            height: 1,
+            pixels: vec![[0.2, 0.4, 0.6, 1.0], [0.5, 0.5, 0.5, 1.0]],
+            source_bit_depth: BitDepth::F32,
+        };
+        let host = ScriptHost::new(ScriptContext::new(GradingParams::default(), Some(image)));
+        host.run(
+            &format!(
+                r#"set_param("saturation", 0.0); render("{}");"#,
+                out.display()
+            ),
+            &[],
+        )
+        .unwrap();
+
+        let graded = image::open(&out).unwrap().to_rgba32f();
+        let [r, g, b, _] = graded.get_pixel(0, 0).0;
+        assert!((r - g).abs() < 1e-3 && (g - b).abs() < 1e-3, "{r} {g} {b}");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_render_burns_in_aspect_mask() {
+        let dir = std::env::temp_dir().join(format!("crispen_guides_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("review.exr");
+
+        let image = GradingImage {
+            width: 40,
+            height: 20,
+            pixels: vec![[0.5, 0.5, 0.5, 1.0]; 40 * 20],
+            source_bit_depth: BitDepth::F32,
+        };
+        let host = ScriptHost::new(ScriptContext::new(GradingParams::default(), Some(image)));
+        host.run(
+            &format!(
+                r#"
+                    set_guides(#{{ enabled: true, title_safe: false, action_safe: false,
+                                   aspect: 4.0, mask_opacity: 1.0 }});
+                    print(guides().thirds);
+                    render("{}");
+                "#,
+                out.display()
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(host.context().output, ["false"]);
+
+        // 4:1 inside 40×20 keeps rows 5..15.
+        let graded = image::open(&out).unwrap().to_rgba32f();
+        assert_eq!(graded.get_pixel(20, 2).0, [0.0, 0.0, 0.0, 1.0]);
+        assert!(graded.get_pixel(20, 10).0[0] > 0.1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_render_bakes_clip_blanking() {
+        use crispen_core::project::otio::save_otio;
+        use crispen_core::project::{Clip, Timeline};
+
+        let dir = std::env::temp_dir().join(format!("crispen_blanking_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let otio = dir.join("reel.otio");
+        let timeline = Timeline {
+            clips: vec![
+                Clip {
+                    name: "scope".into(),
+                    ..Clip::default()
+                },
+                Clip {
+                    name: "square".into(),
+                    blanking: Some(Blanking::Aspect(1.0)),
+                    ..Clip::default()
+                },
+            ],
+            blanking: Blanking::SCOPE,
+            ..Timeline::default()
+        };
+        save_otio(&timeline, &otio).unwrap();
+        let out = dir.join("square.exr");
+
+        let image = GradingImage {
+            width: 40,
+            height: 20,
+            pixels: vec![[0.5, 0.5, 0.5, 1.0]; 40 * 20],
+            source_bit_depth: BitDepth::F32,
+        };
+        let host = ScriptHost::new(ScriptContext::new(GradingParams::default(), Some(image)));
+        host.run(
+            &format!(
+                r#"
+                    print(clip_blanking("{otio}", "scope"));
+                    set_blanking(clip_blanking("{otio}", "square"));
+                    render("{out}");
+                "#,
+                otio = otio.display(),
+                out = out.display()
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(host.context().output[0].parse::<f32>().unwrap(), 2.39);
+
+        // 1:1 inside 40×20 keeps columns 10..30 at full alpha.
+        let graded = image::open(&out).unwrap().to_rgba32f();
+        assert_eq!(graded.get_pixel(9, 10).0, [0.0, 0.0, 0.0, 1.0]);
+        assert!(graded.get_pixel(10, 10).0[0] > 0.1);
+        assert_eq!(graded.get_pixel(30, 10).0, [0.0, 0.0, 0.0, 1.0]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_strict_render_is_exact_and_self_check_passes() {
+        let image = GradingImage {
+            width: 2,
+            height: 1,
+            pixels: vec![[0.2, 0.4, 0.6, 1.0], [0.5, 0.5, 0.5, 0.25]],
+            source_bit_depth: BitDepth::F32,
+        };
+        let params = GradingParams {
+            saturation: 1.4,
+            ..GradingParams::default()
+        };
+        let data = grade_image(&image, &params, &DefectMap::default(), Determinism::Strict);
+        let exact = crispen_core::evaluate_transform([0.5, 0.5, 0.5], &params);
+        assert_eq!(data[4..], [exact[0], exact[1], exact[2], 0.25]);
+
+        let host = ScriptHost::new(ScriptContext::new(params, Some(image)));
+        host.run(
+            r#"set_strict(true); let r = self_check(2); print(r.passed);"#,
+            &[],
+        )
+        .unwrap();
+        let ctx = host.into_context();
+        assert!(ctx.determinism.is_strict());
+        assert_eq!(ctx.output, ["true"]);
+    }
+
+    #[test]
+    fn test_consistency_report_flags_dark_clip() {
+        use crispen_core::project::otio::save_otio;
+        use crispen_core::project::{Clip, Timeline};
+
+        let dir = std::env::temp_dir().join(format!("crispen_consistency_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut timeline = Timeline::default();
+        for (i, level) in [128, 128, 12, 128, 128].into_iter().enumerate() {
+            let name = format!("shot{i}.png");
+            image::RgbImage::from_pixel(4, 4, image::Rgb([level; 3]))
+                .save(dir.join(&name))
+                .unwrap();
+            timeline.clips.push(Clip {
+                name: format!("shot{i}"),
+                media_path: Some(name),
+                ..Clip::default()
+            });
+        }
+        timeline.clips.push(Clip {
+            name: "offline".into(),
+            media_path: Some("missing.png".into()),
+            ..Clip::default()
+        });
+        let otio = dir.join("reel.otio");
+        save_otio(&timeline, &otio).unwrap();
+
+        let host = ScriptHost::new(ScriptContext::default());
+        host.run(
+            &format!(
+                r#"
+                    let r = consistency_report("{}");
+                    print(r.skipped.len());
+                    for c in r.clips {{
+                        if c.outliers.len() > 0 {{ print(c.clip_name); }}
+                    }}
+                "#,
+                otio.display()
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(host.context().output, ["1", "shot2"]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_session_report_writes_thumbnails_and_notes() {
+        use crispen_core::project::otio::save_otio;
+        use crispen_core::project::{Clip, Timeline};
+
+        let dir = std::env::temp_dir().join(format!("crispen_report_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        image::RgbImage::from_pixel(8, 4, image::Rgb([64; 3]))
+            .save(dir.join("shot.png"))
+            .unwrap();
+        let grade = GradingParams {
+            gain_wheel: [1.0, 1.0, 1.0, 2.0],
+            ..GradingParams::default()
+        };
+        let timeline = Timeline {
+            clips: vec![
+                Clip {
+                    name: "shot".into(),
+                    media_path: Some("shot.png".into()),
+                    grade: Some(grade),
+                    notes: Some("hold for VFX".into()),
+                    ..Clip::default()
+                },
+                Clip {
+                    name: "offline".into(),
+                    media_path: Some("missing.png".into()),
+                    ..Clip::default()
+                },
+            ],
+            ..Timeline::default()
+        };
+        let otio = dir.join("reel.otio");
+        save_otio(&timeline, &otio).unwrap();
+
+        let report = crate::session_report(&otio, None).unwrap();
+        let before = report.clips[0].before.as_deref().unwrap();
+        let after = report.clips[0].after.as_deref().unwrap();
+        assert!(before.starts_with("data:image/png;base64,"));
+        assert_ne!(before, after, "the grade brightens the after thumbnail");
+        assert!(report.clips[1].skipped.is_some());
+
+        let out = dir.join("report.html");
+        let host = ScriptHost::new(ScriptContext::default());
+        host.run(
+            &format!(
+                r#"session_report("{}", "{}");"#,
+                otio.display(),
+                out.display()
+            ),
+            &[],
+        )
+        .unwrap();
+        let html = std::fs::read_to_string(&out).unwrap();
+        assert!(html.contains("hold for VFX"));
+        assert_eq!(html.matches("<img ").count(), 2);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_sequence_follows_keys_and_resumes() {
+        use crispen_core::project::otio::save_otio;
+        use crispen_core::project::{Clip, Keyframe, KeyframeTrack, SourceRange, Timeline};
+
+        let dir = std::env::temp_dir().join(format!("crispen_export_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for frame in 1001..=1003 {
+            image::Rgba32FImage::from_pixel(8, 4, image::Rgba([0.25, 0.25, 0.25, 1.0]))
+                .save(dir.join(format!("src.{frame}.exr")))
+                .unwrap();
+        }
+        let timeline = Timeline {
+            clips: vec![Clip {
+                name: "shot".into(),
+                media_path: Some("src.%04d.exr".into()),
+                source_range: Some(SourceRange {
+                    start: 1001.0,
+                    duration: 3.0,
+                    rate: 24.0,
+                }),
+                gain_keys: Some(KeyframeTrack::new(vec![
+                    Keyframe {
+                        frame: 1001.0,
+                        value: 1.0,
+                    },
+                    Keyframe {
+                        frame: 1003.0,
+                        value: 2.0,
+                    },
+                ])),
+                ..Clip::default()
+            }],
+            ..Timeline::default()
+        };
+        let otio = dir.join("reel.otio");
+        save_otio(&timeline, &otio).unwrap();
+
+        let host = ScriptHost::new(ScriptContext::default());
+        let script = format!(
+            r#"
+                let r = export_sequence("{otio}", "shot", "{out}", #{{ first_frame: 1 }});
+                print(`${{r.written}} ${{r.skipped}}`);
+            "#,
+            otio = otio.display(),
+            out = dir.join("out.%04d.exr").display()
+        );
+        host.run(&script, &[]).unwrap();
+        host.run(&script, &[]).unwrap();
+        assert_eq!(host.context().output, ["3 0", "0 3"]);
+
+        let red = |n: u32| {
+            image::open(dir.join(format!("out.{n:04}.exr")))
+                .unwrap()
+                .to_rgba32f()
+                .get_pixel(4, 2)
+                .0[0]
+        };
+        assert!(red(3) > red(1), "the gain key ramps up over the clip");
+
+        host.run(
+            &format!(
+                r#"export_sequence("{}", "shot", "{}", #{{ first_frame: 1, sidecars: #{{ grade: true, manifest: true }} }});"#,
+                otio.display(),
+                dir.join("out.%04d.exr").display()
+            ),
+            &[],
+        )
+        .unwrap();
+        assert!(!dir.join("out.cube").exists());
+        let manifest = std::fs::read_to_string(dir.join("out.md5")).unwrap();
+        assert_eq!(manifest.lines().count(), 4, "three frames and the grade");
+        assert!(manifest.contains("  out.grade.json\n"));
+
+        host.run(
+            &format!(
+                r#"
+                    let m = checksum_manifest("{dir}", "xxh64");
+                    print(verify_manifest(m).passed);
+                "#,
+                dir = dir.display()
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(host.context().output.last().unwrap(), "true");
+
+        let failed = host.run(
+            &format!(
+                r#"export_sequence("{}", "shot", "{}", #{{ first_frame: 1, overwrite: "fail" }});"#,
+                otio.display(),
+                dir.join("out.%04d.exr").display()
+            ),
+            &[],
+        );
+        assert!(failed.is_err(), "every frame exists");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_sequence_repairs_and_applies_spatial_effects() {
+        use crispen_core::grading::dehaze::Dehaze;
+        use crispen_core::grading::glow::Glow;
+        use crispen_core::project::otio::save_otio;
+        use crispen_core::project::{Clip, SourceRange, Timeline};
+
+        let dir = std::env::temp_dir().join(format!("crispen_export_fx_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // Gray with a bright patch on the right to glow, and a hot pixel.
+        let source = image::Rgba32FImage::from_fn(16, 8, |x, y| match (x, y) {
+            (3, 3) => image::Rgba([8.0, 8.0, 8.0, 1.0]),
+            (x, _) if x >= 12 => image::Rgba([0.9, 0.9, 0.9, 1.0]),
+            _ => image::Rgba([0.3, 0.3, 0.3, 1.0]),
+        });
+        source.save(dir.join("src.1001.exr")).unwrap();
+        let mut map = DefectMap::default();
+        map.mark(3, 3, 0);
+        let timeline = Timeline {
+            clips: vec![Clip {
+                name: "shot".into(),
+                media_path: Some("src.%04d.exr".into()),
+                camera: Some("A".into()),
+                source_range: Some(SourceRange {
+                    start: 1001.0,
+                    duration: 1.0,
+                    rate: 24.0,
+                }),
+                grade: Some(GradingParams {
+                    glow: Glow {
+                        threshold: 0.5,
+                        intensity: 1.0,
+                        ..Glow::default()
+                    },
+                    dehaze: Dehaze {
+                        strength: 0.6,
+                        ..Dehaze::default()
+                    },
+                    ..GradingParams::default()
+                }),
+                ..Clip::default()
+            }],
+            defect_maps: [("A".to_string(), map)].into(),
+            ..Timeline::default()
+        };
+        let otio = dir.join("reel.otio");
+        save_otio(&timeline, &otio).unwrap();
+
+        let export = SequenceExport::new(
+            dir.join("out.%04d.exr").display().to_string(),
+            clip_frames(&otio, "shot").unwrap(),
+        );
+        export_sequence(&otio, "shot", &export).unwrap();
+        let exported = image::open(dir.join("out.1001.exr")).unwrap().to_rgba32f();
+        let lut = load_timeline(&otio)
+            .unwrap()
+            .grade_stack(0)
+            .unwrap()
+            .bake(RENDER_LUT_SIZE);
+        let plain = |x: u32, y: u32| {
+            let p = source.get_pixel(x, y).0;
+            lut.apply([p[0], p[1], p[2]])[0]
+        };
+        let red = |x: u32, y: u32| exported.get_pixel(x, y).0[0];
+
+        assert!(plain(3, 3) - plain(2, 3) > 0.2, "the hot pixel stands out");
+        assert!(
+            (red(3, 3) - red(2, 3)).abs() < 0.05,
+            "the camera's defect map repairs it: {} vs {}",
+            red(3, 3),
+            red(2, 3)
+        );
+        assert!(
+            (red(10, 4) - plain(10, 4)).abs() > 1e-3,
+            "glow and dehaze change pixels away from the defect"
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
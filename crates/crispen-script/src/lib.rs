@@ -0,0 +1,36 @@
+//! Crispen Script — embedded Rhai scripting for automation.
+//!
+//! A [`ScriptHost`] runs [Rhai](https://rhai.rs) scripts against a
+//! [`ScriptContext`] holding a grade and an optional image, so TDs can batch
+//! grade, render, and export without touching the UI. The same host runs
+//! headless (`crispen-script` binary) and inside the app, where the grade a
+//! script leaves behind is applied to the session.
+//!
+//! Rhai is pure Rust and sandboxed by default: scripts reach the file system
+//! only through the functions registered here.
+//!
+//! [`consistency_report`] measures every clip of a timeline for the
+//! `crispen-script --consistency` batch job, [`split_image_sequence`]
+//! splits a scanned sequence into shots for `--detect-cuts`, and
+//! [`deflicker_timeline`] keys per-frame gains for `--deflicker`.
+//! [`session_report`] renders a timeline's before/after thumbnails into a
+//! printable report for `--report`. [`export_sequence`] renders a graded
+//! EXR, DPX, or TIFF sequence of a clip for `--export-sequence`.
+
+mod consistency;
+mod context;
+mod cuts;
+mod deflicker;
+mod error;
+mod host;
+mod report;
+mod sequence_export;
+
+pub use consistency::{consistency_report, load_timeline};
+pub use context::ScriptContext;
+pub use cuts::split_image_sequence;
+pub use deflicker::deflicker_timeline;
+pub use error::ScriptError;
+pub use host::ScriptHost;
+pub use report::session_report;
+pub use sequence_export::{SequenceExportReport, clip_frames, export_sequence};
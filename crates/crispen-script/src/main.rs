@@ -0,0 +1,462 @@
+//! Headless script runner.
+//!
+//! ```text
+//! crispen-script [--strict] <script.rhai> [args...]
+//! crispen-script --self-check [grade.json] [runs]
+//! crispen-script --consistency [--graded] <timeline> [report.json]
+//! crispen-script --detect-cuts <frame> <out.otio> [fps]
+//! crispen-script --deflicker <timeline> <out.otio> [window]
+//! crispen-script --report <timeline> <out.html> [notes]
+//! crispen-script --merge <base> <ours> <project.otio>
+//! crispen-script --export-sequence [--sidecars] [--checksum md5|xxh64] <timeline> <clip> <out.%04d.exr> [first-last] [skip|overwrite|fail]
+//! crispen-script --manifest <folder> [md5|xxh64] [manifest]
+//! crispen-script --verify <manifest>
+//! ```
+//!
+//! Runs the script with an identity grade and no image; `args` are bound to
+//! `ARGS`. `--strict` starts it in strict determinism mode. Exits non-zero
+//! if the script fails.
+//!
+//! `--self-check` renders a probe of the grade (identity without a file)
+//! `runs` times, default 5, prints the digests, and exits non-zero unless
+//! every render was bit-identical.
+//!
+//! `--consistency` measures one frame per clip of an `.otio`, `.edl`, or
+//! `.ale` timeline, prints the clips that stand out, most inconsistent
+//! first, and optionally writes the full report as JSON. `--graded`
+//! measures through each clip's grade instead of the source.
+//!
+//! `--detect-cuts` finds the numbered sequence `frame` belongs to, splits it
+//! into one clip per shot, and writes the clips as an OTIO timeline at
+//! `fps` (default 24).
+//!
+//! `--deflicker` measures every frame of each image-sequence clip of a
+//! timeline and writes it back as OTIO with a per-frame master gain key
+//! that evens out flicker against the `window` frames on each side
+//! (default 5).
+//!
+//! `--report` writes a printable HTML session report of a timeline: each
+//! clip's before/after thumbnails, CDL, input LUT, and notes, with
+//! `notes` under the heading. Print it to PDF from a browser.
+//!
+//! `--merge` locks the shared OTIO project, merges the grades changed in
+//! `ours` since `base` into it clip by clip, writes it back, and prints
+//! the conflicts. Exits 1 if any clip was changed on both sides; ours is
+//! kept for those.
+//!
+//! `--export-sequence` renders an image-sequence clip through its grade,
+//! keyframes, defect repair, and spatial effects included, and output
+//! matte into a numbered EXR, DPX, or TIFF sequence. The range defaults to the frames the clip uses; existing
+//! frames are skipped unless the policy says otherwise, so re-running an
+//! interrupted export picks up where it stopped. `--sidecars` writes the
+//! applied `.cube`, an ASC `.cc`, the grade as JSON, and an `.md5`
+//! manifest beside the frames, named after the pattern. `--checksum`
+//! picks the manifest's algorithm, and on its own writes just the
+//! manifest.
+//!
+//! `--manifest` writes a checksum manifest of every file under a delivery
+//! folder, by default `<folder>/<folder name>.md5`. `--verify` checks a
+//! `.md5` or `.xxh64` manifest against the files beside it, prints the
+//! failures, and exits 1 if any file is missing or differs.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use crispen_core::project::audit::current_operator;
+use crispen_core::project::otio::save_otio;
+use crispen_core::project::save_merged;
+use crispen_core::project::{ChecksumAlgorithm, Manifest, folder_manifest_path, verify_manifest};
+use crispen_core::project::{
+    ConsistencySettings, CutSettings, DeflickerSettings, OverwritePolicy, SequenceExport, Sidecars,
+};
+use crispen_core::transform::determinism::{self, Determinism};
+use crispen_core::transform::params::GradingParams;
+use crispen_script::{ScriptContext, ScriptHost};
+
+const USAGE: &str = "usage: crispen-script [--strict] <script.rhai> [args...]\n       \
+                     crispen-script --self-check [grade.json] [runs]\n       \
+                     crispen-script --consistency [--graded] <timeline> [report.json]\n       \
+                     crispen-script --detect-cuts <frame> <out.otio> [fps]\n       \
+                     crispen-script --deflicker <timeline> <out.otio> [window]\n       \
+                     crispen-script --report <timeline> <out.html> [notes]\n       \
+                     crispen-script --merge <base> <ours> <project.otio>\n       \
+                     crispen-script --export-sequence [--sidecars] [--checksum md5|xxh64] \
+                     <timeline> <clip> <out.%04d.exr> [first-last] [skip|overwrite|fail]\n       \
+                     crispen-script --manifest <folder> [md5|xxh64] [manifest]\n       \
+                     crispen-script --verify <manifest>";
+
+/// Frame rate for `--detect-cuts` when none is given.
+const DEFAULT_FPS: f64 = 24.0;
+
+/// Default render count for `--self-check`.
+const SELF_CHECK_RUNS: usize = 5;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if_eq("--self-check").is_some() {
+        return self_check(args.next(), args.next());
+    }
+    if args.next_if_eq("--consistency").is_some() {
+        let graded = args.next_if_eq("--graded").is_some();
+        return consistency(graded, args.next(), args.next());
+    }
+    if args.next_if_eq("--detect-cuts").is_some() {
+        return detect_cuts(args.next(), args.next(), args.next());
+    }
+    if args.next_if_eq("--deflicker").is_some() {
+        return deflicker(args.next(), args.next(), args.next());
+    }
+    if args.next_if_eq("--report").is_some() {
+        return report(args.next(), args.next(), args.next());
+    }
+    if args.next_if_eq("--merge").is_some() {
+        return merge(args.next(), args.next(), args.next());
+    }
+    if args.next_if_eq("--export-sequence").is_some() {
+        let mut sidecars = if args.next_if_eq("--sidecars").is_some() {
+            Sidecars::ALL
+        } else {
+            Sidecars::default()
+        };
+        if args.next_if_eq("--checksum").is_some() {
+            match args.next().map(|a| a.parse::<ChecksumAlgorithm>()) {
+                Some(Ok(algorithm)) => {
+                    sidecars.manifest = true;
+                    sidecars.checksum = algorithm;
+                }
+                _ => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                }
+            }
+        }
+        let args: Vec<String> = args.collect();
+        return export_sequence(sidecars, &args);
+    }
+    if args.next_if_eq("--manifest").is_some() {
+        return manifest(args.next(), args.next(), args.next());
+    }
+    if args.next_if_eq("--verify").is_some() {
+        return verify(args.next());
+    }
+    let strict = args.next_if_eq("--strict").is_some();
+    let Some(script) = args.next() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let args: Vec<String> = args.collect();
+
+    let context = ScriptContext {
+        determinism: if strict {
+            Determinism::Strict
+        } else {
+            Determinism::Fast
+        },
+        ..ScriptContext::default()
+    };
+    let host = ScriptHost::new(context);
+    let result = host.run_file(Path::new(&script), &args);
+    for line in &host.context().output {
+        println!("{line}");
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{script}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn self_check(grade: Option<String>, runs: Option<String>) -> ExitCode {
+    let params = match &grade {
+        Some(path) => match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("{path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => GradingParams::default(),
+    };
+    let runs = match runs.map(|r| r.parse::<usize>()) {
+        None => SELF_CHECK_RUNS,
+        Some(Ok(runs)) if runs > 0 => runs,
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let report = determinism::self_check(&params, runs);
+    println!("{report}");
+    if report.passed() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn consistency(graded: bool, timeline: Option<String>, out: Option<String>) -> ExitCode {
+    let Some(timeline) = timeline else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let settings = ConsistencySettings {
+        graded,
+        ..ConsistencySettings::default()
+    };
+    let report = match crispen_script::consistency_report(Path::new(&timeline), &settings) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{timeline}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    print!("{report}");
+
+    if let Some(out) = out {
+        let written = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&out, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            eprintln!("{out}: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn detect_cuts(frame: Option<String>, out: Option<String>, fps: Option<String>) -> ExitCode {
+    let (Some(frame), Some(out)) = (frame, out) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let rate = match fps.map(|f| f.parse::<f64>()) {
+        None => DEFAULT_FPS,
+        Some(Ok(rate)) if rate > 0.0 => rate,
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let timeline = match crispen_script::split_image_sequence(
+        Path::new(&frame),
+        rate,
+        &CutSettings::default(),
+    ) {
+        Ok(timeline) => timeline,
+        Err(e) => {
+            eprintln!("{frame}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for clip in &timeline.clips {
+        if let Some(range) = clip.source_range {
+            println!(
+                "{}  {}  {} frame(s)",
+                clip.name, range.start, range.duration
+            );
+        }
+    }
+    if let Err(e) = save_otio(&timeline, Path::new(&out)) {
+        eprintln!("{out}: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn deflicker(timeline: Option<String>, out: Option<String>, window: Option<String>) -> ExitCode {
+    let (Some(timeline), Some(out)) = (timeline, out) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let mut settings = DeflickerSettings::default();
+    match window.map(|w| w.parse::<usize>()) {
+        None => {}
+        Some(Ok(window)) if window > 0 => settings.window = window,
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    let (deflickered, keyed) =
+        match crispen_script::deflicker_timeline(Path::new(&timeline), &settings) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{timeline}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+    println!("{keyed} of {} clip(s) deflickered", deflickered.clips.len());
+    if let Err(e) = save_otio(&deflickered, Path::new(&out)) {
+        eprintln!("{out}: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn report(timeline: Option<String>, out: Option<String>, notes: Option<String>) -> ExitCode {
+    let (Some(timeline), Some(out)) = (timeline, out) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let report = match crispen_script::session_report(Path::new(&timeline), notes.as_deref()) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{timeline}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for clip in &report.clips {
+        if let Some(reason) = &clip.skipped {
+            println!("{}: no thumbnails ({reason})", clip.name);
+        }
+    }
+    if let Err(e) = std::fs::write(&out, report.to_html()) {
+        eprintln!("{out}: {e}");
+        return ExitCode::FAILURE;
+    }
+    println!("{} clip(s) reported to {out}", report.clips.len());
+    ExitCode::SUCCESS
+}
+
+fn merge(base: Option<String>, ours: Option<String>, project: Option<String>) -> ExitCode {
+    let (Some(base), Some(ours), Some(project)) = (base, ours, project) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let mut timelines = Vec::new();
+    for path in [&base, &ours] {
+        match crispen_script::load_timeline(Path::new(path)) {
+            Ok(timeline) => timelines.push(timeline),
+            Err(e) => {
+                eprintln!("{path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    match save_merged(
+        Path::new(&project),
+        &timelines[0],
+        &timelines[1],
+        &current_operator(),
+    ) {
+        Ok(report) => {
+            print!("{report}");
+            if report.conflicts.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("{project}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn export_sequence(sidecars: Sidecars, args: &[String]) -> ExitCode {
+    let [timeline, clip, pattern, rest @ ..] = args else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let range = match rest.first() {
+        Some(range) => range.parse().map_err(|e| format!("{e}")),
+        None => crispen_script::clip_frames(Path::new(timeline), clip).map_err(|e| e.to_string()),
+    };
+    let range = match range {
+        Ok(range) => range,
+        Err(e) => {
+            eprintln!("{timeline}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut export = SequenceExport::new(pattern.as_str(), range);
+    export.sidecars = sidecars;
+    match rest.get(1).map(|p| p.parse::<OverwritePolicy>()) {
+        None => {}
+        Some(Ok(policy)) => export.overwrite = policy,
+        Some(Err(_)) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    match crispen_script::export_sequence(Path::new(timeline), clip, &export) {
+        Ok(report) => {
+            println!(
+                "{clip} {range}: {} frame(s) written, {} skipped",
+                report.written.len(),
+                report.skipped.len()
+            );
+            for sidecar in &report.sidecars {
+                println!("  {}", sidecar.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{timeline}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn manifest(dir: Option<String>, algorithm: Option<String>, out: Option<String>) -> ExitCode {
+    let Some(dir) = dir else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let algorithm = match algorithm.map(|a| a.parse::<ChecksumAlgorithm>()) {
+        None => ChecksumAlgorithm::Md5,
+        Some(Ok(algorithm)) => algorithm,
+        Some(Err(_)) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let dir = Path::new(&dir);
+    let out = out.map_or_else(|| folder_manifest_path(dir, algorithm), Into::into);
+
+    match Manifest::from_folder(dir, algorithm).and_then(|m| m.save(&out).map(|()| m)) {
+        Ok(manifest) => {
+            println!(
+                "{} file(s) listed in {}",
+                manifest.entries.len(),
+                out.display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", dir.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify(manifest: Option<String>) -> ExitCode {
+    let Some(manifest) = manifest else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    match verify_manifest(Path::new(&manifest)) {
+        Ok(report) => {
+            print!("{report}");
+            if report.passed() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("{manifest}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
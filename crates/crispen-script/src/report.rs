@@ -0,0 +1,90 @@
+//! Headless session report over a timeline's source media.
+//!
+//! Fills a [`SessionReport`] with before/after thumbnails: one still per
+//! clip, read like the consistency report's, downscaled once and encoded as
+//! PNG `data:` URLs — as loaded for "before", through the clip's grade
+//! stack for "after" — so the HTML stays a single file.
+
+use std::path::Path;
+
+use base64::Engine;
+use image::ImageEncoder;
+
+use crispen_core::image::GradingImage;
+use crispen_core::project::SessionReport;
+use crispen_core::transform::look_preview::{render_thumbnail, thumbnail_source};
+
+use crate::consistency::{load_timeline, media_file, representative_frame};
+use crate::error::ScriptError;
+use crate::host::load_image;
+
+/// Width of report thumbnails in pixels.
+const THUMBNAIL_WIDTH: u32 = 320;
+
+/// LUT size the "after" thumbnails are graded through.
+const THUMBNAIL_LUT_SIZE: u32 = 33;
+
+/// Build the report for the timeline at `path`, with `notes` under the
+/// heading. Clips whose media cannot be read keep their row with the
+/// reason in place of the thumbnails.
+///
+/// Relative media paths resolve against the timeline's folder.
+pub fn session_report(path: &Path, notes: Option<&str>) -> Result<SessionReport, ScriptError> {
+    let timeline = load_timeline(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut report = SessionReport::from_timeline(&timeline);
+    report.notes = notes.map(str::to_string);
+
+    for (index, row) in report.clips.iter_mut().enumerate() {
+        let clip = &timeline.clips[index];
+        let proxy = representative_frame(clip)
+            .ok_or_else(|| "no media path".to_string())
+            .and_then(|media| {
+                load_image(&media_file(base, &media)).map_err(|e| format!("{media}: {e}"))
+            })
+            .map(|image| thumbnail_source(&image, THUMBNAIL_WIDTH));
+        let proxy = match proxy {
+            Ok(proxy) if !proxy.pixels.is_empty() => proxy,
+            Ok(_) => {
+                row.skipped = Some("empty frame".into());
+                continue;
+            }
+            Err(reason) => {
+                row.skipped = Some(reason);
+                continue;
+            }
+        };
+
+        let before = source_rgba8(&proxy);
+        let after = match timeline.grade_stack(index) {
+            Some(stack) => render_thumbnail(&proxy, &stack.bake(THUMBNAIL_LUT_SIZE)),
+            None => before.clone(),
+        };
+        match (png_data_url(&before, &proxy), png_data_url(&after, &proxy)) {
+            (Ok(before), Ok(after)) => {
+                row.before = Some(before);
+                row.after = Some(after);
+            }
+            (Err(e), _) | (_, Err(e)) => row.skipped = Some(e.to_string()),
+        }
+    }
+    Ok(report)
+}
+
+/// Ungraded RGBA8 pixels of `proxy`.
+fn source_rgba8(proxy: &GradingImage) -> Vec<u8> {
+    let quantize = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+    proxy.pixels.iter().flat_map(|p| p.map(quantize)).collect()
+}
+
+fn png_data_url(rgba: &[u8], proxy: &GradingImage) -> image::ImageResult<String> {
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png).write_image(
+        rgba,
+        proxy.width,
+        proxy.height,
+        image::ExtendedColorType::Rgba8,
+    )?;
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    Ok(format!("data:image/png;base64,{data}"))
+}
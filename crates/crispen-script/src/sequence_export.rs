@@ -0,0 +1,159 @@
+//! Headless graded sequence export for finishing.
+//!
+//! Renders the frames of a timeline clip's image sequence through the
+//! viewer's full chain and the output matte into an EXR, DPX, or TIFF
+//! sequence, following a [`SequenceExport`] plan. Each frame has the clip's
+//! defect map repaired, then the clip grade's chromatic aberration and
+//! dehaze applied, then the grade stack, then glow. A clip with keyframes is re-baked for every
+//! frame from [`Clip::grade_at`](crispen_core::project::Clip::grade_at);
+//! otherwise the stack is baked once. Frames are loaded, graded, written,
+//! and dropped one at a time, so memory stays flat however long the clip is.
+//! The export's sidecars are written last, so their manifest covers every
+//! frame, including frames a resumed export skipped.
+
+use std::path::{Path, PathBuf};
+
+use crispen_core::grading::glow::apply_glow;
+use crispen_core::grading::pixel_repair::DefectMap;
+use crispen_core::image::GradingImage;
+use crispen_core::project::{
+    FrameRange, GradeStack, SequenceExport, expand_pattern, write_sidecars,
+};
+
+use crate::consistency::{load_timeline, media_file};
+use crate::error::ScriptError;
+use crate::host::{RENDER_LUT_SIZE, load_image, prepare_source, save_graded};
+
+/// Files an export wrote and left alone.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceExportReport {
+    pub written: Vec<PathBuf>,
+    /// Existing frames kept under the skip policy.
+    pub skipped: Vec<PathBuf>,
+    /// Sidecar files written beside the frames.
+    pub sidecars: Vec<PathBuf>,
+}
+
+/// Source frames the clip named `clip` of the timeline at `path` uses, the
+/// default range of an export.
+pub fn clip_frames(path: &Path, clip: &str) -> Result<FrameRange, ScriptError> {
+    let timeline = load_timeline(path)?;
+    let clip = timeline
+        .clips
+        .iter()
+        .find(|c| c.name == clip)
+        .ok_or_else(|| ScriptError::Timeline(format!("no clip '{clip}'")))?;
+    clip.source_range
+        .as_ref()
+        .and_then(FrameRange::from_source)
+        .ok_or_else(|| ScriptError::Timeline(format!("{}: no source range", clip.name)))
+}
+
+/// Render the clip named `clip` of the timeline at `path` as `export`
+/// describes. Stops at the first frame that fails to read or write; frames
+/// written so far stay, and re-running with the skip policy resumes.
+///
+/// Relative media paths resolve against the timeline's folder.
+pub fn export_sequence(
+    path: &Path,
+    clip: &str,
+    export: &SequenceExport,
+) -> Result<SequenceExportReport, ScriptError> {
+    let timeline = load_timeline(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let index = timeline
+        .clips
+        .iter()
+        .position(|c| c.name == clip)
+        .ok_or_else(|| ScriptError::Timeline(format!("no clip '{clip}'")))?;
+    let clip = &timeline.clips[index];
+    let media = clip
+        .media_path
+        .as_deref()
+        .filter(|media| expand_pattern(media, 0).is_some())
+        .ok_or_else(|| {
+            ScriptError::Timeline(format!("{}: not an image sequence clip", clip.name))
+        })?;
+
+    let plan = export.plan()?;
+    let stack = timeline.grade_stack(index).expect("clip index is in range");
+    let fixed_lut = clip
+        .gain_keys
+        .is_none()
+        .then(|| stack.bake(RENDER_LUT_SIZE));
+    let blanking = timeline.blanking_for(clip);
+    let no_defects = DefectMap::default();
+    let defects = timeline.defect_map_for(clip).unwrap_or(&no_defects);
+
+    for frame in &plan.frames {
+        let source = expand_pattern(media, frame.source).unwrap_or_default();
+        let mut image = load_image(&media_file(base, &source))
+            .map_err(|e| ScriptError::Timeline(format!("{source}: {e}")))?;
+        let keyed;
+        let (grade, lut) = match &fixed_lut {
+            Some(lut) => (stack.clip, lut),
+            None => {
+                let grade = clip.grade_at(frame.source as f64).unwrap_or_default();
+                let lut = GradeStack {
+                    clip: &grade,
+                    ..stack
+                }
+                .bake(RENDER_LUT_SIZE);
+                keyed = (grade, lut);
+                (&keyed.0, &keyed.1)
+            }
+        };
+        prepare_source(&mut image, grade, defects);
+        let pixels = image
+            .pixels
+            .iter()
+            .map(|p| {
+                let [r, g, b] = lut.apply([p[0], p[1], p[2]]);
+                [r, g, b, p[3]]
+            })
+            .collect();
+        let mut graded = GradingImage { pixels, ..image };
+        apply_glow(&mut graded, &grade.glow);
+        blanking.apply(&mut graded.pixels, graded.width, graded.height);
+        let data = graded.pixels.iter().flatten().copied().collect();
+        save_graded(&graded, data, &frame.path)
+            .map_err(|e| ScriptError::Timeline(format!("{}: {e}", frame.path.display())))?;
+        tracing::debug!("{}: frame {} written", clip.name, frame.source);
+    }
+
+    tracing::info!(
+        "{}: {} frame(s) written, {} skipped",
+        clip.name,
+        plan.frames.len(),
+        plan.skipped.len()
+    );
+    let written: Vec<PathBuf> = plan.frames.into_iter().map(|frame| frame.path).collect();
+
+    let mut sidecars = Vec::new();
+    if export.sidecars.any() {
+        // A keyed clip's recipe is its grade at the first exported frame.
+        let grade = clip.grade_at(export.range.first as f64).unwrap_or_default();
+        let lut = match fixed_lut {
+            Some(lut) => lut,
+            None => GradeStack {
+                clip: &grade,
+                ..stack
+            }
+            .bake(RENDER_LUT_SIZE),
+        };
+        let mut media: Vec<PathBuf> = written.iter().chain(&plan.skipped).cloned().collect();
+        media.sort();
+        sidecars = write_sidecars(
+            &export.sidecar_base(),
+            &export.sidecars,
+            &grade,
+            &lut,
+            &media,
+        )?;
+    }
+    Ok(SequenceExportReport {
+        written,
+        skipped: plan.skipped,
+        sidecars,
+    })
+}
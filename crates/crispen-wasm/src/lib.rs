@@ -0,0 +1,99 @@
+//! Crispen WASM — the core transform for the browser.
+//!
+//! Wraps `crispen-core`, built without scopes or file IO, in a small
+//! [`wasm_bindgen`] API: parse a grade saved by the app, grade pixels, and
+//! bake a 3D LUT for a WebGPU shader to apply. The numbers are the ones the
+//! desktop app bakes, since both call the same
+//! [`evaluate_transform`](crispen_core::evaluate_transform).
+//!
+//! Build with `wasm-pack build crates/crispen-wasm --target web --out-dir
+//! www/pkg`; `www/` holds a previewer that uses it.
+
+use crispen_core::transform::evaluate::{OutputEncoding, evaluate_transform_batch};
+use crispen_core::{GradingParams, Lut3D};
+use wasm_bindgen::prelude::*;
+
+/// Largest LUT [`Grade::bake_lut`] bakes, matching the app's maximum.
+const MAX_LUT_SIZE: u32 = 129;
+
+/// A grade ready to evaluate or bake.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct Grade {
+    params: GradingParams,
+}
+
+#[wasm_bindgen]
+impl Grade {
+    /// The identity grade.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Grade {
+        Grade::default()
+    }
+
+    /// Parse a grade from `GradingParams` JSON, as saved by the app.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Grade, JsError> {
+        Ok(Grade {
+            params: serde_json::from_str(json)?,
+        })
+    }
+
+    /// The grade as `GradingParams` JSON.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.params).unwrap_or_default()
+    }
+
+    /// Problems [`GradingParams::validate`] finds, one message each.
+    pub fn issues(&self) -> Vec<String> {
+        self.params
+            .validate()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Grade interleaved RGB through to the configured output space.
+    pub fn evaluate(&self, rgb: &[f32]) -> Result<Vec<f32>, JsError> {
+        self.evaluate_to(rgb, OutputEncoding::Display)
+    }
+
+    /// Grade interleaved RGB and stop in working-space linear.
+    #[wasm_bindgen(js_name = evaluateWorking)]
+    pub fn evaluate_working(&self, rgb: &[f32]) -> Result<Vec<f32>, JsError> {
+        self.evaluate_to(rgb, OutputEncoding::Working)
+    }
+
+    /// Bake a `size³` LUT as RGBA `f32`, red varying fastest, ready for
+    /// `GPUQueue.writeTexture` into an `rgba32float` 3D texture.
+    #[wasm_bindgen(js_name = bakeLut)]
+    pub fn bake_lut(&self, size: u32) -> Result<Vec<f32>, JsError> {
+        if !(2..=MAX_LUT_SIZE).contains(&size) {
+            return Err(JsError::new(&format!(
+                "LUT size {size} is outside 2..={MAX_LUT_SIZE}"
+            )));
+        }
+        let mut lut = Lut3D::new(size);
+        lut.bake(&self.params);
+        Ok(lut.data.into_iter().flatten().collect())
+    }
+}
+
+impl Grade {
+    fn evaluate_to(&self, rgb: &[f32], encoding: OutputEncoding) -> Result<Vec<f32>, JsError> {
+        if !rgb.len().is_multiple_of(3) {
+            return Err(JsError::new(&format!(
+                "{} values is not a whole number of RGB pixels",
+                rgb.len()
+            )));
+        }
+        Ok(evaluate_transform_batch(rgb, &self.params, encoding))
+    }
+}
+
+impl From<GradingParams> for Grade {
+    fn from(params: GradingParams) -> Self {
+        Self { params }
+    }
+}
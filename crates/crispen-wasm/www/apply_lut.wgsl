@@ -0,0 +1,46 @@
+// apply_lut.wgsl — Draw an image through a baked 3D LUT.
+//
+// The LUT is `rgba32float`, which WebGPU cannot filter without the
+// `float32-filterable` feature, so the trilinear blend is done by hand.
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var lut: texture_3d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// One triangle that covers the viewport.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+fn lut_at(cell: vec3<i32>) -> vec3<f32> {
+    return textureLoad(lut, cell, 0).rgb;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let dims = vec2<f32>(textureDimensions(source));
+    let texel = clamp(vec2<i32>(in.uv * dims), vec2<i32>(0), vec2<i32>(dims) - 1);
+    let pixel = textureLoad(source, texel, 0);
+
+    let last = i32(textureDimensions(lut).x) - 1;
+    let pos = clamp(pixel.rgb, vec3<f32>(0.0), vec3<f32>(1.0)) * f32(last);
+    let lo = min(vec3<i32>(floor(pos)), vec3<i32>(last - 1));
+    let t = pos - vec3<f32>(lo);
+
+    let c00 = mix(lut_at(lo), lut_at(lo + vec3<i32>(1, 0, 0)), t.x);
+    let c10 = mix(lut_at(lo + vec3<i32>(0, 1, 0)), lut_at(lo + vec3<i32>(1, 1, 0)), t.x);
+    let c01 = mix(lut_at(lo + vec3<i32>(0, 0, 1)), lut_at(lo + vec3<i32>(1, 0, 1)), t.x);
+    let c11 = mix(lut_at(lo + vec3<i32>(0, 1, 1)), lut_at(lo + vec3<i32>(1, 1, 1)), t.x);
+    let graded = mix(mix(c00, c10, t.y), mix(c01, c11, t.y), t.z);
+
+    return vec4<f32>(graded, pixel.a);
+}
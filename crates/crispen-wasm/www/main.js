@@ -0,0 +1,173 @@
+// WebGPU look preview: crispen-core bakes the grade into a 3D LUT in WASM,
+// and a fragment shader applies it to the chosen image.
+import init, { Grade } from "./pkg/crispen_wasm.js";
+
+const LUT_SIZE = 33;
+
+// GradingParams fields exposed as sliders: [field, label, min, max, step].
+const SLIDERS = [
+  ["temperature", "Temperature", -100, 100, 1],
+  ["tint", "Tint", -100, 100, 1],
+  ["contrast", "Contrast", 0, 2, 0.01],
+  ["saturation", "Saturation", 0, 2, 0.01],
+  ["hue", "Hue", -180, 180, 1],
+  ["shadows", "Shadows", -1, 1, 0.01],
+  ["highlights", "Highlights", -1, 1, 0.01],
+];
+
+const status = document.getElementById("status");
+const jsonField = document.getElementById("json");
+const canvas = document.getElementById("view");
+
+await init();
+if (!navigator.gpu) {
+  throw fail("WebGPU is not available in this browser.");
+}
+const adapter = await navigator.gpu.requestAdapter();
+const device = await adapter.requestDevice();
+const context = canvas.getContext("webgpu");
+const format = navigator.gpu.getPreferredCanvasFormat();
+context.configure({ device, format, alphaMode: "opaque" });
+
+const module = device.createShaderModule({
+  code: await (await fetch("apply_lut.wgsl")).text(),
+});
+const pipeline = device.createRenderPipeline({
+  layout: "auto",
+  vertex: { module, entryPoint: "vs_main" },
+  fragment: { module, entryPoint: "fs_main", targets: [{ format }] },
+});
+
+const lutTexture = device.createTexture({
+  size: [LUT_SIZE, LUT_SIZE, LUT_SIZE],
+  dimension: "3d",
+  format: "rgba32float",
+  usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST,
+});
+
+// The browser hands us sRGB-encoded pixels, which is the default input
+// space. The canvas has no OETF of its own, so the grade encodes for it.
+const grade = JSON.parse(new Grade().toJson());
+grade.color_management.output_space = "Srgb";
+
+let sourceTexture = null;
+let bindGroup = null;
+
+function fail(message) {
+  status.textContent = message;
+  return new Error(message);
+}
+
+function bake() {
+  let baked;
+  try {
+    baked = Grade.fromJson(JSON.stringify(grade));
+  } catch (error) {
+    fail(`Invalid grade: ${error}`);
+    return;
+  }
+  status.textContent = baked.issues().join("\n");
+  const data = baked.bakeLut(LUT_SIZE);
+  baked.free();
+  device.queue.writeTexture(
+    { texture: lutTexture },
+    data,
+    { bytesPerRow: LUT_SIZE * 16, rowsPerImage: LUT_SIZE },
+    [LUT_SIZE, LUT_SIZE, LUT_SIZE],
+  );
+  draw();
+}
+
+function draw() {
+  if (!bindGroup) return;
+  const encoder = device.createCommandEncoder();
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [
+      {
+        view: context.getCurrentTexture().createView(),
+        loadOp: "clear",
+        storeOp: "store",
+        clearValue: [0, 0, 0, 1],
+      },
+    ],
+  });
+  pass.setPipeline(pipeline);
+  pass.setBindGroup(0, bindGroup);
+  pass.draw(3);
+  pass.end();
+  device.queue.submit([encoder.finish()]);
+}
+
+async function loadImage(file) {
+  const bitmap = await createImageBitmap(file, { colorSpaceConversion: "none" });
+  canvas.width = bitmap.width;
+  canvas.height = bitmap.height;
+  sourceTexture?.destroy();
+  sourceTexture = device.createTexture({
+    size: [bitmap.width, bitmap.height],
+    format: "rgba8unorm",
+    usage:
+      GPUTextureUsage.TEXTURE_BINDING |
+      GPUTextureUsage.COPY_DST |
+      GPUTextureUsage.RENDER_ATTACHMENT,
+  });
+  device.queue.copyExternalImageToTexture(
+    { source: bitmap },
+    { texture: sourceTexture },
+    [bitmap.width, bitmap.height],
+  );
+  bitmap.close();
+  bindGroup = device.createBindGroup({
+    layout: pipeline.getBindGroupLayout(0),
+    entries: [
+      { binding: 0, resource: sourceTexture.createView() },
+      { binding: 1, resource: lutTexture.createView() },
+    ],
+  });
+  draw();
+}
+
+function showJson() {
+  jsonField.value = JSON.stringify(grade, null, 2);
+}
+
+const controls = document.getElementById("controls");
+for (const [field, label, min, max, step] of SLIDERS) {
+  const row = document.createElement("label");
+  const slider = Object.assign(document.createElement("input"), {
+    type: "range",
+    min,
+    max,
+    step,
+    value: grade[field],
+  });
+  slider.dataset.field = field;
+  slider.addEventListener("input", () => {
+    grade[field] = Number(slider.value);
+    showJson();
+    bake();
+  });
+  row.append(label, slider);
+  controls.append(row);
+}
+
+document.getElementById("apply-json").addEventListener("click", () => {
+  try {
+    Object.assign(grade, JSON.parse(jsonField.value));
+  } catch (error) {
+    fail(`Invalid JSON: ${error.message}`);
+    return;
+  }
+  for (const slider of controls.querySelectorAll("input")) {
+    slider.value = grade[slider.dataset.field];
+  }
+  bake();
+});
+
+document.getElementById("image").addEventListener("change", (event) => {
+  const [file] = event.target.files;
+  if (file) loadImage(file);
+});
+
+showJson();
+bake();